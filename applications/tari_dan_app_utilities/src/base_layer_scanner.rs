@@ -70,7 +70,12 @@ use tari_template_lib::models::{EncryptedData, UnclaimedConfidentialOutputAddres
 use tokio::{task, task::JoinHandle, time};
 use url::ParseError;
 
-use crate::template_manager::interface::{TemplateExecutable, TemplateManagerError, TemplateManagerHandle};
+use crate::template_manager::interface::{
+    TemplateExecutable,
+    TemplateManagerError,
+    TemplateManagerHandle,
+    TemplateVersion,
+};
 
 const LOG_TARGET: &str = "tari::dan::base_layer_scanner";
 
@@ -484,6 +489,7 @@ impl<TAddr: NodeAddressable + 'static> BaseLayerScanner<TAddr> {
                     registration.binary_sha,
                 ),
                 Some(template_name),
+                TemplateVersion::default(),
             )
             .await?;
 
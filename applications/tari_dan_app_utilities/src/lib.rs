@@ -25,6 +25,7 @@ pub mod common;
 pub mod configuration;
 pub mod json_encoding;
 pub mod keypair;
+pub mod logging;
 pub mod p2p_config;
 pub mod seed_peer;
 pub mod substate_file_cache;
@@ -0,0 +1,38 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::EnvFilter;
+
+/// The format of the application log output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Output is configured by the log4rs configuration file
+    #[default]
+    Log4rs,
+    /// One JSON object per line is written to stdout. Lines that are logged within a span (e.g. while processing a
+    /// transaction) include the fields of that span, such as the `transaction_id`.
+    Json,
+}
+
+impl LogFormat {
+    pub fn is_json(&self) -> bool {
+        matches!(self, Self::Json)
+    }
+}
+
+/// Initializes structured JSON logging. Existing `log` records are forwarded to the subscriber so that they are logged
+/// with the fields of the current span. The level filter is read from `RUST_LOG`, falling back to `default_filter`.
+pub fn initialize_json_logging(default_filter: &str) -> Result<(), anyhow::Error> {
+    let filter = EnvFilter::try_from_default_env().or_else(|_| EnvFilter::try_new(default_filter))?;
+    tracing_subscriber::fmt()
+        .json()
+        .flatten_event(true)
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_env_filter(filter)
+        .try_init()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize JSON logging: {e}"))?;
+    Ok(())
+}
@@ -13,6 +13,21 @@ pub struct P2pConfig {
     pub enable_mdns: bool,
     pub listener_port: u16,
     pub reachability_mode: ReachabilityMode,
+    /// The maximum number of bytes per second accepted from a single peer. Messages over the limit are dropped. If not
+    /// set, inbound bandwidth is not limited.
+    pub max_inbound_bytes_per_peer_per_sec: Option<u64>,
+    /// The maximum number of bytes per second sent to a single peer. Messages over the limit are dropped. If not set,
+    /// outbound bandwidth is not limited.
+    pub max_outbound_bytes_per_peer_per_sec: Option<u64>,
+}
+
+impl P2pConfig {
+    pub fn bandwidth_limits(&self) -> tari_networking::BandwidthLimits {
+        tari_networking::BandwidthLimits {
+            max_inbound_bytes_per_peer_per_sec: self.max_inbound_bytes_per_peer_per_sec,
+            max_outbound_bytes_per_peer_per_sec: self.max_outbound_bytes_per_peer_per_sec,
+        }
+    }
 }
 
 impl Default for P2pConfig {
@@ -21,6 +36,8 @@ impl Default for P2pConfig {
             enable_mdns: true,
             listener_port: 0,
             reachability_mode: ReachabilityMode::default(),
+            max_inbound_bytes_per_peer_per_sec: None,
+            max_outbound_bytes_per_peer_per_sec: None,
         }
     }
 }
@@ -47,7 +47,7 @@ use tari_template_lib::{models::TemplateAddress, Hash};
 use super::TemplateConfig;
 use crate::template_manager::{
     implementation::cmap_semaphore,
-    interface::{Template, TemplateExecutable, TemplateManagerError, TemplateMetadata},
+    interface::{Template, TemplateExecutable, TemplateManagerError, TemplateMetadata, TemplateVersion},
 };
 
 const LOG_TARGET: &str = "tari::validator_node::template_manager";
@@ -120,6 +120,10 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
                 name: name.to_string(),
                 address,
                 binary_sha,
+                author_public_key: None,
+                template_type: DbTemplateType::Wasm,
+                status: TemplateStatus::Active,
+                version: TemplateVersion::default(),
             },
             executable: TemplateExecutable::CompiledWasm(compiled_code),
         }
@@ -185,6 +189,26 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         Ok(templates)
     }
 
+    /// Returns the metadata of all known versions of the lineage that the given template belongs to, ordered by
+    /// version.
+    pub fn fetch_template_versions(
+        &self,
+        address: &TemplateAddress,
+    ) -> Result<Vec<TemplateMetadata>, TemplateManagerError> {
+        if let Some(template) = self.builtin_templates.get(address) {
+            return Ok(vec![template.metadata.clone()]);
+        }
+
+        let mut tx = self.global_db.create_transaction()?;
+        let mut templates_db = self.global_db.templates(&mut tx);
+        let template = templates_db
+            .get_template(address)?
+            .ok_or(TemplateManagerError::TemplateNotFound { address: *address })?;
+        let lineage_root = template.lineage_root.unwrap_or(template.template_address);
+        let versions = templates_db.get_template_versions(&lineage_root)?;
+        Ok(versions.into_iter().map(Into::into).collect())
+    }
+
     pub(super) fn add_template(
         &self,
         author_public_key: PublicKey,
@@ -192,6 +216,7 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
         template: TemplateExecutable,
         template_name: Option<String>,
         template_status: Option<TemplateStatus>,
+        version: TemplateVersion,
     ) -> Result<(), TemplateManagerError> {
         enum TemplateHash {
             Hash(Hash),
@@ -244,6 +269,9 @@ impl<TAddr: NodeAddressable> TemplateManager<TAddr> {
             flow_json,
             manifest,
             url: template_url,
+            template_version: version.template_version,
+            previous_version: version.previous_version,
+            lineage_root: version.lineage_root,
         };
 
         let mut tx = self.global_db.create_transaction()?;
@@ -339,6 +367,23 @@ impl<TAddr: NodeAddressable + Send + Sync + 'static> TemplateProvider for Templa
             TemplateExecutable::CompiledWasm(template.to_vec()),
             None,
             Some(TemplateStatus::Active),
+            TemplateVersion::default(),
+        )
+    }
+
+    fn add_flow_template(
+        &self,
+        author_public_key: PublicKey,
+        template_address: tari_engine_types::TemplateAddress,
+        flow_json: &str,
+    ) -> Result<(), Self::Error> {
+        self.add_template(
+            author_public_key,
+            template_address,
+            TemplateExecutable::Flow(flow_json.to_string()),
+            None,
+            Some(TemplateStatus::Active),
+            TemplateVersion::default(),
         )
     }
 }
@@ -41,7 +41,12 @@ use super::{
     downloader::{DownloadRequest, DownloadResult},
     TemplateManager,
 };
-use crate::template_manager::interface::{TemplateExecutable, TemplateManagerError, TemplateManagerRequest};
+use crate::template_manager::interface::{
+    TemplateExecutable,
+    TemplateManagerError,
+    TemplateManagerRequest,
+    TemplateVersion,
+};
 
 const LOG_TARGET: &str = "tari::template_manager";
 
@@ -124,11 +129,12 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
                 template_address,
                 template,
                 template_name,
+                version,
                 reply,
             } => {
                 handle(
                     reply,
-                    self.handle_add_template(author_public_key, template_address, template, template_name)
+                    self.handle_add_template(author_public_key, template_address, template, template_name, version)
                         .await,
                 );
             },
@@ -243,6 +249,7 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
         template_address: tari_engine_types::TemplateAddress,
         template: TemplateExecutable,
         template_name: Option<String>,
+        version: TemplateVersion,
     ) -> Result<(), TemplateManagerError> {
         let template_status = if matches!(template, TemplateExecutable::DownloadableWasm(_, _)) {
             TemplateStatus::New
@@ -255,6 +262,7 @@ impl<TAddr: NodeAddressable + 'static> TemplateManagerService<TAddr> {
             template.clone(),
             template_name,
             Some(template_status),
+            version,
         )?;
 
         // TODO: remove when we remove support for base layer template registration
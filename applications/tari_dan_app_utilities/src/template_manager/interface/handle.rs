@@ -25,7 +25,14 @@ use tari_template_lib::models::TemplateAddress;
 use tari_validator_node_client::types::TemplateAbi;
 use tokio::sync::{mpsc, oneshot};
 
-use super::{types::TemplateManagerRequest, Template, TemplateExecutable, TemplateManagerError, TemplateMetadata};
+use super::{
+    types::TemplateManagerRequest,
+    Template,
+    TemplateExecutable,
+    TemplateManagerError,
+    TemplateMetadata,
+    TemplateVersion,
+};
 
 #[derive(Debug, Clone)]
 pub struct TemplateManagerHandle {
@@ -70,6 +77,7 @@ impl TemplateManagerHandle {
         template_address: TemplateAddress,
         template: TemplateExecutable,
         template_name: Option<String>,
+        version: TemplateVersion,
     ) -> Result<(), TemplateManagerError> {
         let (tx, rx) = oneshot::channel();
         self.request_tx
@@ -78,6 +86,7 @@ impl TemplateManagerHandle {
                 template_address,
                 template,
                 template_name,
+                version,
                 reply: tx,
             })
             .await
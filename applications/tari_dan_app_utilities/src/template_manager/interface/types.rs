@@ -22,7 +22,8 @@
 
 use reqwest::Url;
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_storage::global::{DbTemplate, DbTemplateType};
+use tari_crypto::tari_utilities::ByteArray;
+use tari_dan_storage::global::{DbTemplate, DbTemplateType, TemplateStatus};
 use tari_template_lib::models::TemplateAddress;
 use tari_validator_node_client::types::TemplateAbi;
 use tokio::sync::oneshot;
@@ -35,6 +36,29 @@ pub struct TemplateMetadata {
     pub address: TemplateAddress,
     /// SHA hash of binary
     pub binary_sha: FixedHash,
+    /// The public key of the template author. This is None for builtin templates.
+    pub author_public_key: Option<PublicKey>,
+    pub template_type: DbTemplateType,
+    pub status: TemplateStatus,
+    pub version: TemplateVersion,
+}
+
+/// The position of a template within its lineage of published versions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TemplateVersion {
+    /// The version of the template within its lineage. The first published version is 0.
+    pub template_version: u32,
+    /// The template that this template is a new version of, if any
+    pub previous_version: Option<TemplateAddress>,
+    /// The first version of the lineage. None if this template is the first version.
+    pub lineage_root: Option<TemplateAddress>,
+}
+
+impl TemplateVersion {
+    /// Returns the root of the lineage, given the address of the template
+    pub fn lineage_root_or(&self, address: TemplateAddress) -> TemplateAddress {
+        self.lineage_root.unwrap_or(address)
+    }
 }
 
 // TODO: Allow fetching of just the template metadata without the compiled code
@@ -42,8 +66,17 @@ impl From<DbTemplate> for TemplateMetadata {
     fn from(record: DbTemplate) -> Self {
         TemplateMetadata {
             name: record.template_name,
+            // TODO: this will change when common engine types are moved around
             address: record.template_address,
-            binary_sha: FixedHash::zero(),
+            binary_sha: record.expected_hash,
+            author_public_key: PublicKey::from_canonical_bytes(record.author_public_key.as_slice()).ok(),
+            template_type: record.template_type,
+            status: record.status,
+            version: TemplateVersion {
+                template_version: record.template_version,
+                previous_version: record.previous_version,
+                lineage_root: record.lineage_root,
+            },
         }
     }
 }
@@ -66,20 +99,15 @@ pub struct Template {
 
 // we encapsulate the db row format to not expose it to the caller
 impl From<DbTemplate> for Template {
-    fn from(record: DbTemplate) -> Self {
+    fn from(mut record: DbTemplate) -> Self {
+        let executable = match record.template_type {
+            DbTemplateType::Wasm => TemplateExecutable::CompiledWasm(record.compiled_code.take().unwrap()),
+            DbTemplateType::Flow => TemplateExecutable::Flow(record.flow_json.take().unwrap()),
+            DbTemplateType::Manifest => TemplateExecutable::Manifest(record.manifest.take().unwrap()),
+        };
         Template {
-            metadata: TemplateMetadata {
-                name: record.template_name,
-                // TODO: this will change when common engine types are moved around
-                address: record.template_address,
-                // TODO: add field to db
-                binary_sha: FixedHash::zero(),
-            },
-            executable: match record.template_type {
-                DbTemplateType::Wasm => TemplateExecutable::CompiledWasm(record.compiled_code.unwrap()),
-                DbTemplateType::Flow => TemplateExecutable::Flow(record.flow_json.unwrap()),
-                DbTemplateType::Manifest => TemplateExecutable::Manifest(record.manifest.unwrap()),
-            },
+            metadata: record.into(),
+            executable,
         }
     }
 }
@@ -91,6 +119,7 @@ pub enum TemplateManagerRequest {
         template_address: tari_engine_types::TemplateAddress,
         template: TemplateExecutable,
         template_name: Option<String>,
+        version: TemplateVersion,
         reply: oneshot::Sender<Result<(), TemplateManagerError>>,
     },
     GetTemplate {
@@ -30,8 +30,11 @@ use std::{
 
 use anyhow::anyhow;
 use clap::{Args, Subcommand};
+use serde::Serialize;
 use serde_json as json;
-use tari_template_lib::models::Amount;
+use tari_common_types::types::PublicKey;
+use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{constants::XTR, models::Amount};
 use tari_utilities::ByteArray;
 use tari_wallet_daemon_client::{
     types::{
@@ -40,12 +43,16 @@ use tari_wallet_daemon_client::{
         AccountsCreateRequest,
         AccountsGetBalancesRequest,
         AccountsInvokeRequest,
+        AccountsTransferRequest,
         ClaimBurnRequest,
+        KeyBranch,
         RevealFundsRequest,
     },
     ComponentAddressOrName,
+    PublicKeyOrContact,
     WalletDaemonClient,
 };
+use tokio::task::JoinSet;
 
 use crate::{
     command::transaction::{print_execution_results, summarize_finalize_result, CliArg},
@@ -76,6 +83,9 @@ pub enum AccountsSubcommand {
     CreateFreeTestCoins(CreateFreeTestCoinsArgs),
     #[clap(alias = "default")]
     SetDefault(SetDefaultArgs),
+    /// Creates many accounts, each with a new key, and writes a manifest of the created accounts. Used to generate
+    /// load-test fixtures.
+    Generate(GenerateArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -146,6 +156,30 @@ pub struct CreateFreeTestCoinsArgs {
     pub key_id: Option<u64>,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct GenerateArgs {
+    /// The number of accounts to create
+    #[clap(long, short = 'n')]
+    pub count: usize,
+    /// Accounts are named <prefix>-<index>
+    #[clap(long, default_value = "generated")]
+    pub name_prefix: String,
+    /// The maximum number of accounts that are created at the same time
+    #[clap(long, short = 'c', default_value_t = 4)]
+    pub concurrency: usize,
+    /// The account from which each created account is funded. If not provided, the accounts are not funded.
+    #[clap(long, alias = "faucet")]
+    pub fund_from: Option<ComponentAddressOrName>,
+    /// The amount of Tari that each account is funded with
+    #[clap(long, default_value_t = 1000)]
+    pub fund_amount: u64,
+    #[clap(long, short = 'f')]
+    pub fee: Option<u32>,
+    /// The file to which the manifest of created accounts is written
+    #[clap(long, short = 'o', default_value = "accounts.json")]
+    pub output: PathBuf,
+}
+
 impl AccountsSubcommand {
     pub async fn handle(self, mut client: WalletDaemonClient) -> Result<(), anyhow::Error> {
         match self {
@@ -169,6 +203,7 @@ impl AccountsSubcommand {
             AccountsSubcommand::RevealFunds(args) => handle_reveal_funds(args, &mut client).await?,
             AccountsSubcommand::CreateFreeTestCoins(args) => handle_create_free_test_coins(args, &mut client).await?,
             AccountsSubcommand::SetDefault(args) => handle_set_default(args, &mut client).await?,
+            AccountsSubcommand::Generate(args) => handle_generate(args, &mut client).await?,
         }
         Ok(())
     }
@@ -194,6 +229,127 @@ async fn handle_create(args: CreateArgs, client: &mut WalletDaemonClient) -> Res
     Ok(())
 }
 
+#[derive(Debug, Serialize)]
+struct GeneratedAccount {
+    name: String,
+    address: SubstateId,
+    public_key: PublicKey,
+    key_id: u64,
+    funded_amount: Option<Amount>,
+}
+
+async fn handle_generate(args: GenerateArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    if args.count == 0 {
+        return Err(anyhow!("--count must be greater than zero"));
+    }
+    let concurrency = args.concurrency.max(1);
+    let max_fee = args.fee.map(|u| Amount::new(u.into()));
+
+    println!("Creating {} account(s) with up to {} at a time...", args.count, concurrency);
+    let mut tasks = JoinSet::new();
+    let mut accounts = Vec::with_capacity(args.count);
+    let mut num_failed = 0;
+    for index in 0..args.count {
+        if tasks.len() >= concurrency {
+            if let Some(result) = tasks.join_next().await {
+                collect_generated(result?, &mut accounts, &mut num_failed);
+            }
+        }
+        let name = format!("{}-{}", args.name_prefix, index);
+        tasks.spawn(create_generated_account(client.clone(), index, name, max_fee));
+    }
+    while let Some(result) = tasks.join_next().await {
+        collect_generated(result?, &mut accounts, &mut num_failed);
+    }
+    accounts.sort_by_key(|(index, _)| *index);
+    let mut accounts = accounts.into_iter().map(|(_, account)| account).collect::<Vec<_>>();
+
+    // Funding transactions are submitted one at a time because they all spend from the same account
+    if let Some(fund_from) = args.fund_from {
+        let amount = Amount::try_from(args.fund_amount)?;
+        println!("Funding {} account(s) with {} from {}...", accounts.len(), amount, fund_from);
+        for account in &mut accounts {
+            let result = client
+                .accounts_transfer(AccountsTransferRequest {
+                    account: Some(fund_from.clone()),
+                    amount: amount.into(),
+                    resource_address: XTR,
+                    destination_public_key: PublicKeyOrContact::PublicKey(account.public_key.clone()),
+                    max_fee: max_fee.map(Into::into),
+                    proof_from_badge_resource: None,
+                    dry_run: false,
+                    fee_account: None,
+                })
+                .await;
+            match result {
+                Ok(resp) if resp.result.is_full_accept() => account.funded_amount = Some(amount),
+                Ok(resp) => {
+                    num_failed += 1;
+                    println!("❌ Funding {} was rejected: {}", account.name, resp.result.result);
+                },
+                Err(err) => {
+                    num_failed += 1;
+                    println!("❌ Funding {} failed: {}", account.name, err);
+                },
+            }
+        }
+    }
+
+    let file = fs::File::create(&args.output)
+        .map_err(|e| anyhow!("Failed to create manifest file {}: {}", args.output.display(), e))?;
+    json::to_writer_pretty(file, &accounts)?;
+
+    println!();
+    println!("✅ {} account(s) created", accounts.len());
+    println!("   manifest: {}", args.output.display());
+    if num_failed > 0 {
+        return Err(anyhow!("{} account operation(s) failed", num_failed));
+    }
+    Ok(())
+}
+
+async fn create_generated_account(
+    mut client: WalletDaemonClient,
+    index: usize,
+    name: String,
+    max_fee: Option<Amount>,
+) -> Result<(usize, GeneratedAccount), anyhow::Error> {
+    let key = client.create_key(KeyBranch::Transaction).await?;
+    let resp = client
+        .create_account(AccountsCreateRequest {
+            account_name: Some(name.clone()),
+            custom_access_rules: None,
+            is_default: false,
+            max_fee,
+            key_id: Some(key.id),
+        })
+        .await
+        .map_err(|e| anyhow!("Failed to create account {}: {}", name, e))?;
+
+    println!("   {} {}", name, resp.address);
+    Ok((index, GeneratedAccount {
+        name,
+        address: resp.address,
+        public_key: resp.public_key,
+        key_id: key.id,
+        funded_amount: None,
+    }))
+}
+
+fn collect_generated(
+    result: Result<(usize, GeneratedAccount), anyhow::Error>,
+    accounts: &mut Vec<(usize, GeneratedAccount)>,
+    num_failed: &mut usize,
+) {
+    match result {
+        Ok(account) => accounts.push(account),
+        Err(err) => {
+            *num_failed += 1;
+            println!("❌ {}", err);
+        },
+    }
+}
+
 async fn handle_set_default(args: SetDefaultArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
     let _resp = client.accounts_set_default(args.account_name).await?;
     println!("✅ Default account set");
@@ -288,7 +444,7 @@ pub async fn handle_claim_burn(args: ClaimBurnArgs, client: &mut WalletDaemonCli
     let req = ClaimBurnRequest {
         account,
         claim_proof,
-        max_fee: fee.map(Into::into),
+        max_fee: fee.map(|f| Amount::from(f).into()),
         key_id,
     };
 
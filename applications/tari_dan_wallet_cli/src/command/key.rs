@@ -24,7 +24,7 @@ use clap::Subcommand;
 use tari_common_types::types::PublicKey;
 use tari_wallet_daemon_client::{types::KeyBranch, WalletDaemonClient};
 
-use crate::{table::Table, table_row};
+use crate::{prompt::Prompt, table::Table, table_row};
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum KeysSubcommand {
@@ -34,6 +34,17 @@ pub enum KeysSubcommand {
     Use {
         index: u64,
     },
+    /// Displays the seed words that can be used to restore the wallet
+    SeedWords {
+        /// An optional passphrase that will be required, along with the seed words, to restore the wallet
+        #[clap(long)]
+        passphrase: Option<String>,
+    },
+    /// Checks that the given seed words (and passphrase) restore this wallet
+    VerifySeedWords {
+        #[clap(long)]
+        passphrase: Option<String>,
+    },
 }
 
 impl KeysSubcommand {
@@ -60,6 +71,33 @@ impl KeysSubcommand {
                 let resp = client.list_keys(KeyBranch::Transaction).await?;
                 print_keys(resp.keys);
             },
+            SeedWords { passphrase } => {
+                let resp = client.get_seed_words(passphrase).await?;
+                println!("Seed words:");
+                println!();
+                for (i, word) in resp.seed_words.iter().enumerate() {
+                    println!("{:>2}. {}", i + 1, word);
+                }
+                println!();
+                println!(
+                    "⚠️ Write these words down and keep them somewhere safe. Anyone with these words (and \
+                     passphrase, if set) can spend your funds."
+                );
+                println!("Use 'keys verify-seed-words' to check your backup.");
+            },
+            VerifySeedWords { passphrase } => {
+                let seed_words = Prompt::new("Enter your seed words separated by spaces").ask()?;
+                let seed_words = seed_words.split_whitespace().map(ToString::to_string).collect();
+                let resp = client.verify_seed_words(seed_words, passphrase).await?;
+                if resp.is_valid {
+                    println!("✅ The seed words restore this wallet");
+                } else {
+                    println!(
+                        "❌ The seed words do not restore this wallet: {}",
+                        resp.error.as_deref().unwrap_or("unknown error")
+                    );
+                }
+            },
         }
         Ok(())
     }
@@ -22,11 +22,12 @@
 
 use clap::Subcommand;
 
-use self::{auth::AuthSubcommand, nfts::AccountNftSubcommand, webrtc::WebRtcSubcommand};
+use self::{auth::AuthSubcommand, nfts::AccountNftSubcommand, offline::SignOfflineArgs, webrtc::WebRtcSubcommand};
 use crate::command::{
     account::AccountsSubcommand,
     key::KeysSubcommand,
     proof::ProofsSubcommand,
+    template::TemplatesSubcommand,
     transaction::TransactionSubcommand,
     validator::ValidatorSubcommand,
 };
@@ -35,9 +36,12 @@ mod account;
 mod auth;
 mod key;
 mod nfts;
+mod offline;
 mod proof;
+mod template;
 pub mod transaction;
 mod validator;
+pub mod wait_for;
 mod webrtc;
 
 #[allow(clippy::large_enum_variant)]
@@ -59,4 +63,8 @@ pub enum Command {
     AccountNft(AccountNftSubcommand),
     #[clap(subcommand)]
     Validator(ValidatorSubcommand),
+    #[clap(subcommand, alias = "template")]
+    Templates(TemplatesSubcommand),
+    /// Signs a transaction that was exported with `transactions submit-manifest --export-unsigned`
+    SignOffline(SignOfflineArgs),
 }
@@ -0,0 +1,76 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::anyhow;
+use clap::Args;
+use tari_common_types::types::PrivateKey;
+use tari_transaction::OfflineUnsignedTransaction;
+use tari_utilities::hex::Hex;
+
+use crate::command::transaction::summarize_transaction;
+
+/// Signs a transaction exported by the wallet daemon. This does not connect to the wallet daemon, so it can be run on
+/// an offline machine.
+#[derive(Debug, Args, Clone)]
+pub struct SignOfflineArgs {
+    /// The file containing the exported unsigned transaction
+    unsigned_transaction: PathBuf,
+    /// A file containing the hex-encoded secret keys to sign with, one per line
+    #[clap(long, short = 'k')]
+    key_file: PathBuf,
+    /// The file to write the signed transaction to. If not given, the signed transaction is printed.
+    #[clap(long, short = 'o')]
+    output: Option<PathBuf>,
+}
+
+impl SignOfflineArgs {
+    pub fn handle(self) -> Result<(), anyhow::Error> {
+        let blob = fs::read_to_string(&self.unsigned_transaction)
+            .map_err(|e| anyhow!("Failed to read unsigned transaction: {}", e))?;
+        let unsigned = OfflineUnsignedTransaction::from_blob(&blob)?;
+        let secret_keys = read_secret_keys(&self.key_file)?;
+
+        summarize_transaction(&unsigned.transaction);
+        println!("Required signers:");
+        for signer in &unsigned.required_signers {
+            println!("- {}", signer);
+        }
+        println!();
+
+        let signed = unsigned.sign(&secret_keys)?.to_blob();
+        match self.output {
+            Some(path) => {
+                fs::write(&path, signed).map_err(|e| anyhow!("Failed to write signed transaction: {}", e))?;
+                println!(
+                    "✅ Signed transaction with {} key(s) and wrote it to {}",
+                    secret_keys.len(),
+                    path.display()
+                );
+            },
+            None => {
+                println!("✅ Signed transaction:");
+                println!("{}", signed);
+            },
+        }
+        Ok(())
+    }
+}
+
+fn read_secret_keys(path: &Path) -> Result<Vec<PrivateKey>, anyhow::Error> {
+    let contents = fs::read_to_string(path).map_err(|e| anyhow!("Failed to read key file: {}", e))?;
+    let secret_keys = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| PrivateKey::from_hex(line).map_err(|e| anyhow!("Invalid secret key in key file: {}", e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if secret_keys.is_empty() {
+        return Err(anyhow!("Key file {} does not contain any keys", path.display()));
+    }
+    Ok(secret_keys)
+}
@@ -0,0 +1,158 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fs, path::PathBuf};
+
+use anyhow::anyhow;
+use clap::{Args, Subcommand};
+use tari_engine_types::published_template::TemplateType;
+use tari_template_lib::models::{Amount, ComponentAddress, TemplateAddress};
+use tari_wallet_daemon_client::{
+    types::{
+        TemplateBinarySource,
+        TemplatesListVersionsRequest,
+        TemplatesMigrateComponentRequest,
+        TemplatesPublishRequest,
+    },
+    ComponentAddressOrName,
+    WalletDaemonClient,
+};
+
+use crate::{command::transaction::summarize_finalize_result, from_hex::FromHex, table::Table, table_row};
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum TemplatesSubcommand {
+    /// Publishes a WASM template or JSON flow template to the network
+    Publish(PublishArgs),
+    /// Lists the versions of a published template, from the first version up to the given template
+    ListVersions(ListVersionsArgs),
+    /// Migrates a component to a newer version of its template
+    Migrate(MigrateArgs),
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct PublishArgs {
+    /// Path to the compiled WASM template, or to the JSON flow definition if --flow is set
+    pub path: PathBuf,
+    /// Publish a JSON flow definition instead of a WASM binary
+    #[clap(long)]
+    pub flow: bool,
+    /// Publish the template as the next version of this template
+    #[clap(long)]
+    pub previous_version: Option<FromHex<TemplateAddress>>,
+    #[clap(long, short = 'f')]
+    pub fee_account: Option<ComponentAddressOrName>,
+    #[clap(long)]
+    pub max_fee: Option<u32>,
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ListVersionsArgs {
+    pub template_address: FromHex<TemplateAddress>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MigrateArgs {
+    pub component_address: ComponentAddress,
+    /// The newer version of the component template
+    pub template_address: FromHex<TemplateAddress>,
+    /// The account that pays the fees. The account key must be the component owner.
+    #[clap(long, short = 'f')]
+    pub fee_account: Option<ComponentAddressOrName>,
+    #[clap(long)]
+    pub max_fee: Option<u32>,
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+impl TemplatesSubcommand {
+    pub async fn handle(self, mut client: WalletDaemonClient) -> Result<(), anyhow::Error> {
+        match self {
+            TemplatesSubcommand::Publish(args) => handle_publish(args, &mut client).await?,
+            TemplatesSubcommand::ListVersions(args) => handle_list_versions(args, &mut client).await?,
+            TemplatesSubcommand::Migrate(args) => handle_migrate(args, &mut client).await?,
+        }
+        Ok(())
+    }
+}
+
+async fn handle_publish(args: PublishArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    let binary = fs::read(&args.path).map_err(|e| anyhow!("Failed to read '{}': {}", args.path.display(), e))?;
+    let template_type = if args.flow {
+        TemplateType::Flow
+    } else {
+        TemplateType::Wasm
+    };
+
+    println!("Submitting publish {} template transaction...", template_type);
+
+    let resp = client
+        .publish_template(TemplatesPublishRequest {
+            binary: TemplateBinarySource::Binary(binary),
+            template_type,
+            previous_version: args.previous_version.map(|a| a.into_inner()),
+            fee_account: args.fee_account,
+            max_fee: args.max_fee.map(Amount::from),
+            dry_run: args.dry_run,
+        })
+        .await?;
+
+    println!("Transaction: {}", resp.transaction_id);
+    println!("Template: {}", resp.template_address);
+    println!("Fee: {}", resp.fee);
+    println!();
+    summarize_finalize_result(&resp.result);
+
+    Ok(())
+}
+
+async fn handle_list_versions(args: ListVersionsArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    let resp = client
+        .list_template_versions(TemplatesListVersionsRequest {
+            template_address: args.template_address.into_inner(),
+        })
+        .await?;
+
+    let mut table = Table::new();
+    table.set_titles(vec!["Version", "Template", "Previous Version", "Author"]);
+    for version in resp.versions {
+        table.add_row(table_row!(
+            version.template_version,
+            version.template_address,
+            version
+                .previous_version
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| "--".to_string()),
+            version
+                .author_public_key
+                .map(|k| k.to_string())
+                .unwrap_or_else(|| "--".to_string())
+        ));
+    }
+    table.print_stdout();
+
+    Ok(())
+}
+
+async fn handle_migrate(args: MigrateArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    println!("Submitting migrate component transaction...");
+
+    let resp = client
+        .migrate_component(TemplatesMigrateComponentRequest {
+            component_address: args.component_address,
+            template_address: args.template_address.into_inner(),
+            fee_account: args.fee_account,
+            max_fee: args.max_fee.map(Amount::from),
+            dry_run: args.dry_run,
+        })
+        .await?;
+
+    println!("Transaction: {}", resp.transaction_id);
+    println!("Fee: {}", resp.fee);
+    println!();
+    summarize_finalize_result(&resp.result);
+
+    Ok(())
+}
@@ -33,12 +33,11 @@ use std::{
 use anyhow::anyhow;
 use clap::{Args, Subcommand};
 use tari_bor::decode_exact;
-use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{Epoch, SubstateAddress, SubstateRequirement};
 use tari_dan_engine::abi::Type;
 use tari_dan_wallet_sdk::apis::confidential_transfer::ConfidentialTransferInputSelection;
 use tari_engine_types::{
-    commit_result::{FinalizeResult, RejectReason, TransactionResult},
+    commit_result::{FinalizeResult, RejectReason, TemplateAbort, TransactionResult},
     instruction::Instruction,
     instruction_result::InstructionResult,
     parse_template_address,
@@ -61,17 +60,23 @@ use tari_wallet_daemon_client::{
         AccountGetResponse,
         AccountsTransferRequest,
         ConfidentialTransferRequest,
+        TransactionExportUnsignedRequest,
         TransactionGetResultRequest,
+        TransactionImportSignedRequest,
         TransactionSubmitDryRunRequest,
         TransactionSubmitRequest,
         TransactionWaitResultRequest,
         TransactionWaitResultResponse,
     },
     ComponentAddressOrName,
+    PublicKeyOrContact,
     WalletDaemonClient,
 };
 
-use crate::from_hex::FromHex;
+use crate::{
+    command::wait_for::{wait_for_conditions, WaitCondition},
+    from_hex::FromHex,
+};
 
 #[derive(Debug, Subcommand, Clone)]
 pub enum TransactionSubcommand {
@@ -80,6 +85,8 @@ pub enum TransactionSubcommand {
     SubmitManifest(SubmitManifestArgs),
     Send(SendArgs),
     ConfidentialTransfer(ConfidentialTransferArgs),
+    /// Submits a transaction that was signed with `sign-offline`
+    ImportSigned(ImportSignedArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -120,6 +127,26 @@ pub struct CommonSubmitArgs {
     pub min_epoch: Option<u64>,
     #[clap(long)]
     pub max_epoch: Option<u64>,
+    /// Wait for a condition once the transaction is submitted. May be given more than once. One of `finalized`,
+    /// `substate:<substate_id>:<min_version>` or `balance:<resource_address>:<min_amount>[:<account>]`. The
+    /// --wait-timeout applies to the transaction result and all conditions. Exits with code 2 if the timeout is reached
+    /// and with code 3 if the transaction is rejected.
+    #[clap(long)]
+    pub wait_for: Vec<WaitCondition>,
+}
+
+impl CommonSubmitArgs {
+    fn wait_deadline(&self) -> Option<Instant> {
+        self.wait_for_result_timeout_secs
+            .map(|secs| Instant::now() + Duration::from_secs(secs))
+    }
+
+    fn check_wait_for_is_allowed(&self) -> Result<(), anyhow::Error> {
+        if self.dry_run && !self.wait_for.is_empty() {
+            return Err(anyhow!("--wait-for cannot be used with --dry-run"));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Args, Clone)]
@@ -127,15 +154,29 @@ pub struct SubmitManifestArgs {
     manifest: PathBuf,
     #[clap(long, short = 'g')]
     input_variables: Vec<String>,
+    /// Write the transaction, with its inputs resolved, to this file for signing with `sign-offline` instead of
+    /// submitting it
+    #[clap(long)]
+    export_unsigned: Option<PathBuf>,
     #[clap(flatten)]
     common: CommonSubmitArgs,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct ImportSignedArgs {
+    /// The file containing the signed transaction
+    signed_transaction: PathBuf,
+    /// Timeout in seconds
+    #[clap(long, short = 't', alias = "wait-timeout")]
+    wait_for_result_timeout_secs: Option<u64>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct SendArgs {
     amount: u64,
     resource_address: ResourceAddress,
-    destination_public_key: FromHex<Vec<u8>>,
+    /// The destination public key or the name of a contact
+    destination_public_key: PublicKeyOrContact,
     #[clap(flatten)]
     common: CommonSubmitArgs,
     source_account_name: Option<ComponentAddressOrName>,
@@ -144,7 +185,8 @@ pub struct SendArgs {
 #[derive(Debug, Args, Clone)]
 pub struct ConfidentialTransferArgs {
     amount: u64,
-    destination_public_key: FromHex<Vec<u8>>,
+    /// The destination public key or the name of a contact
+    destination_public_key: PublicKeyOrContact,
     #[clap(flatten)]
     common: CommonSubmitArgs,
     #[clap(long, short = 'a', alias = "account")]
@@ -186,6 +228,7 @@ impl TransactionSubcommand {
             TransactionSubcommand::ConfidentialTransfer(args) => {
                 handle_confidential_transfer(args, &mut client).await?;
             },
+            TransactionSubcommand::ImportSigned(args) => handle_import_signed(args, &mut client).await?,
         }
         Ok(())
     }
@@ -234,6 +277,9 @@ pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) ->
         },
     };
 
+    common.check_wait_for_is_allowed()?;
+    let deadline = common.wait_deadline();
+
     let fee_account;
     if let Some(fee_account_name) = common.fee_account.clone() {
         fee_account = client.accounts_get(fee_account_name).await?.account;
@@ -274,9 +320,10 @@ pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) ->
                 autofill_inputs: vec![],
                 detect_inputs: common.detect_inputs.unwrap_or(true),
                 proof_ids: vec![],
+                substate_overrides: Default::default(),
             })
             .await?;
-        wait_transaction_result(resp.transaction_id, client).await?;
+        wait_transaction_result(resp.transaction_id, None, client).await?;
     } else {
         let request = TransactionSubmitRequest {
             transaction,
@@ -287,7 +334,15 @@ pub async fn handle_submit(args: SubmitArgs, client: &mut WalletDaemonClient) ->
             proof_ids: vec![],
         };
         let resp = client.submit_transaction(&request).await?;
-        wait_transaction_result(resp.transaction_id, client).await?;
+        let wait_resp = wait_transaction_result(resp.transaction_id, deadline, client).await?;
+        wait_for_conditions(
+            client,
+            resp.transaction_id,
+            wait_resp.result.as_ref(),
+            &common.wait_for,
+            deadline,
+        )
+        .await?;
     }
     Ok(())
 }
@@ -300,6 +355,8 @@ async fn handle_submit_manifest(
     let contents = fs::read_to_string(&args.manifest).map_err(|e| anyhow!("Failed to read manifest: {}", e))?;
     let instructions = parse_manifest(&contents, parse_globals(args.input_variables)?, Default::default())?;
     let common = args.common;
+    common.check_wait_for_is_allowed()?;
+    let deadline = common.wait_deadline();
 
     let fee_account;
     if let Some(fee_account_name) = common.fee_account.clone() {
@@ -328,6 +385,23 @@ async fn handle_submit_manifest(
     let transaction = builder.build_unsigned_transaction();
     summarize_transaction(&transaction);
 
+    if let Some(path) = args.export_unsigned {
+        let resp = client
+            .export_unsigned_transaction(TransactionExportUnsignedRequest {
+                transaction,
+                signing_key_index: None,
+                detect_inputs: common.detect_inputs.unwrap_or(true),
+            })
+            .await?;
+        fs::write(&path, resp.blob).map_err(|e| anyhow!("Failed to write unsigned transaction: {}", e))?;
+        println!(
+            "✅ Exported unsigned transaction with {} input(s) to {}",
+            resp.transaction.inputs().len(),
+            path.display()
+        );
+        return Ok(());
+    }
+
     if common.dry_run {
         println!("NOTE: Dry run is enabled. This transaction will not be processed by the network.");
         println!();
@@ -339,6 +413,7 @@ async fn handle_submit_manifest(
                 autofill_inputs: vec![],
                 detect_inputs: common.detect_inputs.unwrap_or(true),
                 proof_ids: vec![],
+                substate_overrides: Default::default(),
             })
             .await?;
         summarize(&resp.result.finalize, timer.elapsed());
@@ -353,12 +428,33 @@ async fn handle_submit_manifest(
         };
 
         let resp = client.submit_transaction(&request).await?;
-        wait_transaction_result(resp.transaction_id, client).await?;
+        let wait_resp = wait_transaction_result(resp.transaction_id, deadline, client).await?;
+        wait_for_conditions(
+            client,
+            resp.transaction_id,
+            wait_resp.result.as_ref(),
+            &common.wait_for,
+            deadline,
+        )
+        .await?;
     }
 
     Ok(())
 }
 
+async fn handle_import_signed(args: ImportSignedArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
+    let blob = fs::read_to_string(&args.signed_transaction)
+        .map_err(|e| anyhow!("Failed to read signed transaction: {}", e))?;
+    let deadline = args
+        .wait_for_result_timeout_secs
+        .map(|secs| Instant::now() + Duration::from_secs(secs));
+    let resp = client
+        .import_signed_transaction(TransactionImportSignedRequest { blob })
+        .await?;
+    wait_transaction_result(resp.transaction_id, deadline, client).await?;
+    Ok(())
+}
+
 pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Result<(), anyhow::Error> {
     let SendArgs {
         source_account_name,
@@ -367,20 +463,19 @@ pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Res
         destination_public_key,
         common,
     } = args;
+    let deadline = common.wait_deadline();
 
-    let destination_public_key =
-        PublicKey::from_canonical_bytes(&destination_public_key.into_inner()).map_err(anyhow::Error::msg)?;
-
-    let fee = common.max_fee.map(|f| f.try_into()).transpose()?;
+    let fee = common.max_fee.map(Amount::try_from).transpose()?;
     let resp = client
         .accounts_transfer(AccountsTransferRequest {
             account: source_account_name,
-            amount: Amount::try_from(amount)?,
+            amount: Amount::try_from(amount)?.into(),
             resource_address,
             destination_public_key,
-            max_fee: fee,
+            max_fee: fee.map(Into::into),
             proof_from_badge_resource: None,
             dry_run: false,
+            fee_account: common.fee_account,
         })
         .await?;
 
@@ -389,6 +484,15 @@ pub async fn handle_send(args: SendArgs, client: &mut WalletDaemonClient) -> Res
     println!();
     summarize_finalize_result(&resp.result);
 
+    wait_for_conditions(
+        client,
+        resp.transaction_id,
+        Some(&resp.result),
+        &common.wait_for,
+        deadline,
+    )
+    .await?;
+
     Ok(())
 }
 
@@ -403,18 +507,17 @@ pub async fn handle_confidential_transfer(
         destination_public_key,
         common,
     } = args;
+    let deadline = common.wait_deadline();
 
     // let AccountByNameResponse { account, .. } = client.accounts_get_by_name(&source_account_name).await?;
-    let destination_public_key =
-        PublicKey::from_canonical_bytes(&destination_public_key.into_inner()).map_err(anyhow::Error::msg)?;
     let resp = client
         .accounts_confidential_transfer(ConfidentialTransferRequest {
             account: source_account,
             input_selection: ConfidentialTransferInputSelection::PreferConfidential,
-            amount: Amount::try_from(amount)?,
+            amount: Amount::try_from(amount)?.into(),
             resource_address: resource_address.unwrap_or(CONFIDENTIAL_TARI_RESOURCE_ADDRESS),
             destination_public_key,
-            max_fee: common.max_fee.map(|f| f.try_into()).transpose()?,
+            max_fee: common.max_fee.map(Amount::try_from).transpose()?.map(Into::into),
             output_to_revealed: false,
             proof_from_badge_resource: None,
             dry_run: false,
@@ -426,11 +529,22 @@ pub async fn handle_confidential_transfer(
     println!();
     summarize_finalize_result(&resp.result);
 
+    wait_for_conditions(
+        client,
+        resp.transaction_id,
+        Some(&resp.result),
+        &common.wait_for,
+        deadline,
+    )
+    .await?;
+
     Ok(())
 }
 
+/// Waits for the transaction result until the deadline, or indefinitely if no deadline is given
 pub async fn wait_transaction_result(
     transaction_id: TransactionId,
+    deadline: Option<Instant>,
     client: &mut WalletDaemonClient,
 ) -> Result<TransactionWaitResultResponse, anyhow::Error> {
     let timer = Instant::now();
@@ -445,8 +559,8 @@ pub async fn wait_transaction_result(
     let wait_resp = client
         .wait_transaction_result(TransactionWaitResultRequest {
             transaction_id,
-            // Never timeout, you can ctrl+c to exit
-            timeout_secs: None,
+            // Without a deadline, never timeout, you can ctrl+c to exit
+            timeout_secs: deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs()),
         })
         .await?;
     if wait_resp.timed_out {
@@ -461,7 +575,7 @@ pub async fn wait_transaction_result(
     Ok(wait_resp)
 }
 
-fn summarize_transaction(transaction: &UnsignedTransaction) {
+pub(crate) fn summarize_transaction(transaction: &UnsignedTransaction) {
     println!("Inputs:");
     if transaction.inputs().is_empty() {
         println!("  None");
@@ -499,6 +613,16 @@ fn summarize(result: &FinalizeResult, time_taken: Duration) {
 
     println!();
     println!("Fee: {}", result.fee_receipt.total_fees_charged());
+    println!(
+        "Peak WASM memory: {} bytes, host allocated: {} bytes",
+        result.fee_receipt.memory_usage.peak_wasm_memory_bytes, result.fee_receipt.memory_usage.host_allocated_bytes
+    );
+    println!(
+        "Gas: {} instructions, {} memory growth (limit {})",
+        result.fee_receipt.gas_usage.instructions,
+        result.fee_receipt.gas_usage.memory_growth,
+        result.fee_receipt.gas_usage.limit
+    );
     println!("Time taken: {:?}", time_taken);
     println!();
     println!("OVERALL DECISION: {}", result.result);
@@ -562,6 +686,17 @@ fn print_reject_reason(reason: &RejectReason) {
     println!("❌️ Transaction rejected: {}", reason);
 }
 
+fn print_template_abort(abort: &TemplateAbort) {
+    println!(
+        "💥 Template {} aborted in function '{}' ({} instruction {}): {}",
+        abort.template_address,
+        abort.function,
+        if abort.is_fee_instruction { "fee" } else { "main" },
+        abort.instruction_index,
+        abort.message
+    );
+}
+
 pub fn summarize_finalize_result(finalize: &FinalizeResult) {
     println!("========= Substates =========");
     match finalize.result {
@@ -572,6 +707,9 @@ pub fn summarize_finalize_result(finalize: &FinalizeResult) {
         },
         TransactionResult::Reject(ref reason) => print_reject_reason(reason),
     }
+    if let Some(ref abort) = finalize.abort {
+        print_template_abort(abort);
+    }
 
     println!("========= Return Values =========");
     print_execution_results(&finalize.execution_results);
@@ -89,7 +89,7 @@ pub async fn handle_claim_validator_fees(
             account: dest_account_name
                 .map(|name| ComponentAddressOrName::from_str(&name))
                 .transpose()?,
-            max_fee: max_fee.map(Amount::from),
+            max_fee: max_fee.map(|f| Amount::from(f).into()),
             validator_public_key: PublicKey::from_canonical_bytes(validator_public_key.into_inner().as_bytes())
                 .map_err(anyhow::Error::msg)?,
             epoch: Epoch(epoch),
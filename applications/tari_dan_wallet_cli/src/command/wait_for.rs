@@ -0,0 +1,211 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt,
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use tari_engine_types::{commit_result::FinalizeResult, substate::SubstateId};
+use tari_template_lib::{models::Amount, prelude::ResourceAddress};
+use tari_transaction::TransactionId;
+use tari_wallet_daemon_client::{
+    types::{AccountsGetBalancesRequest, SubstatesGetRequest},
+    ComponentAddressOrName,
+    WalletDaemonClient,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A condition to wait for after a transaction has been submitted. Every condition also waits for the transaction to
+/// be finalized.
+///
+/// Formats:
+/// - `finalized`
+/// - `substate:<substate_id>:<min_version>`
+/// - `balance:<resource_address>:<min_amount>[:<account>]` (the default account is used if no account is given)
+#[derive(Debug, Clone)]
+pub enum WaitCondition {
+    TransactionFinalized,
+    SubstateVersion {
+        substate_id: SubstateId,
+        min_version: u32,
+    },
+    BalanceAtLeast {
+        resource_address: ResourceAddress,
+        min_balance: Amount,
+        account: Option<ComponentAddressOrName>,
+    },
+}
+
+impl FromStr for WaitCondition {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "finalized" {
+            return Ok(Self::TransactionFinalized);
+        }
+
+        if let Some(rest) = s.strip_prefix("substate:") {
+            let (substate_id, min_version) = rest
+                .rsplit_once(':')
+                .ok_or_else(|| anyhow!("Expected substate:<substate_id>:<min_version>"))?;
+            return Ok(Self::SubstateVersion {
+                substate_id: substate_id.parse()?,
+                min_version: min_version.parse()?,
+            });
+        }
+
+        if let Some(rest) = s.strip_prefix("balance:") {
+            let mut parts = rest.splitn(3, ':');
+            let (Some(resource_address), Some(min_balance)) = (parts.next(), parts.next()) else {
+                return Err(anyhow!("Expected balance:<resource_address>:<min_amount>[:<account>]"));
+            };
+            let resource_address = SubstateId::from_str(resource_address)?;
+            return Ok(Self::BalanceAtLeast {
+                resource_address: resource_address
+                    .as_resource_address()
+                    .ok_or_else(|| anyhow!("Expected resource address but got {}", resource_address))?,
+                min_balance: Amount::try_from(min_balance.parse::<u64>()?)?,
+                account: parts.next().map(|account| account.parse()).transpose()?,
+            });
+        }
+
+        Err(anyhow!(
+            "Invalid wait condition '{}'. Expected finalized, substate:<substate_id>:<min_version> or \
+             balance:<resource_address>:<min_amount>[:<account>]",
+            s
+        ))
+    }
+}
+
+impl Display for WaitCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransactionFinalized => write!(f, "transaction finalized"),
+            Self::SubstateVersion {
+                substate_id,
+                min_version,
+            } => write!(f, "substate {} at version {} or later", substate_id, min_version),
+            Self::BalanceAtLeast {
+                resource_address,
+                min_balance,
+                account,
+            } => {
+                write!(f, "balance of {} at least {}", resource_address, min_balance)?;
+                if let Some(account) = account {
+                    write!(f, " in account {}", account)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Errors that are reported with a specific process exit code so that scripts can tell them apart from other failures
+#[derive(Debug, thiserror::Error)]
+pub enum WaitForError {
+    #[error("Timed out waiting for {condition}")]
+    TimedOut { condition: WaitCondition },
+    #[error("Transaction {transaction_id} was rejected: {reason}")]
+    TransactionRejected {
+        transaction_id: TransactionId,
+        reason: String,
+    },
+}
+
+impl WaitForError {
+    /// The process exit code for this error. Other errors exit with code 1.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::TimedOut { .. } => 2,
+            Self::TransactionRejected { .. } => 3,
+        }
+    }
+}
+
+/// Waits until all conditions are met or the deadline is reached. `result` is the finalized result of the submitted
+/// transaction, or None if the transaction was not finalized in time.
+pub async fn wait_for_conditions(
+    client: &mut WalletDaemonClient,
+    transaction_id: TransactionId,
+    result: Option<&FinalizeResult>,
+    conditions: &[WaitCondition],
+    deadline: Option<Instant>,
+) -> Result<(), anyhow::Error> {
+    if conditions.is_empty() {
+        return Ok(());
+    }
+
+    let result = result.ok_or(WaitForError::TimedOut {
+        condition: WaitCondition::TransactionFinalized,
+    })?;
+    if let Some(reason) = result.full_reject() {
+        return Err(WaitForError::TransactionRejected {
+            transaction_id,
+            reason: reason.to_string(),
+        }
+        .into());
+    }
+
+    for condition in conditions {
+        loop {
+            if is_condition_met(client, condition).await? {
+                println!("✅ Condition met: {}", condition);
+                break;
+            }
+            if deadline.is_some_and(|deadline| Instant::now() + POLL_INTERVAL > deadline) {
+                return Err(WaitForError::TimedOut {
+                    condition: condition.clone(),
+                }
+                .into());
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    Ok(())
+}
+
+async fn is_condition_met(client: &mut WalletDaemonClient, condition: &WaitCondition) -> Result<bool, anyhow::Error> {
+    match condition {
+        WaitCondition::TransactionFinalized => Ok(true),
+        WaitCondition::SubstateVersion {
+            substate_id,
+            min_version,
+        } => {
+            // The substate is not known to the wallet until the wallet has scanned it
+            let Ok(resp) = client
+                .substates_get(SubstatesGetRequest {
+                    substate_id: substate_id.clone(),
+                })
+                .await
+            else {
+                return Ok(false);
+            };
+            Ok(resp.record.version >= *min_version)
+        },
+        WaitCondition::BalanceAtLeast {
+            resource_address,
+            min_balance,
+            account,
+        } => {
+            let resp = client
+                .get_account_balances(AccountsGetBalancesRequest {
+                    account: account.clone(),
+                    refresh: true,
+                })
+                .await?;
+            let balance = resp
+                .balances
+                .iter()
+                .filter(|entry| entry.resource_address == *resource_address)
+                .map(|entry| entry.balance.saturating_add(entry.confidential_balance))
+                .sum::<Amount>();
+            Ok(balance >= *min_balance)
+        },
+    }
+}
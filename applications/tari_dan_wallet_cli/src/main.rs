@@ -23,7 +23,10 @@
 use anyhow::anyhow;
 use multiaddr::{Multiaddr, Protocol};
 use reqwest::Url;
-use tari_dan_wallet_cli::{cli::Cli, command::Command};
+use tari_dan_wallet_cli::{
+    cli::Cli,
+    command::{wait_for::WaitForError, Command},
+};
 use tari_wallet_daemon_client::WalletDaemonClient;
 
 #[tokio::main(flavor = "current_thread")]
@@ -40,6 +43,9 @@ async fn main() -> Result<(), anyhow::Error> {
 
     if let Err(err) = handle_command(cli.command, client).await {
         eprintln!("👮 Command failed with error \"{}\"", err);
+        if let Some(err) = err.downcast_ref::<WaitForError>() {
+            std::process::exit(err.exit_code());
+        }
         return Err(err);
     }
 
@@ -48,7 +54,6 @@ async fn main() -> Result<(), anyhow::Error> {
 
 async fn handle_command(command: Command, client: WalletDaemonClient) -> anyhow::Result<()> {
     match command {
-        // Command::Templates(cmd) => cmd.handle(client).await?,
         Command::Keys(cmd) => cmd.handle(client).await?,
         Command::Transactions(cmd) => cmd.handle(client).await?,
         Command::Accounts(cmd) => cmd.handle(client).await?,
@@ -57,6 +62,9 @@ async fn handle_command(command: Command, client: WalletDaemonClient) -> anyhow:
         Command::Auth(cmd) => cmd.handle(client).await?,
         Command::AccountNft(cmd) => cmd.handle(client).await?,
         Command::Validator(cmd) => cmd.handle(client).await?,
+        Command::Templates(cmd) => cmd.handle(client).await?,
+        // Does not use the client, which does not connect until a request is made
+        Command::SignOffline(cmd) => cmd.handle()?,
     }
 
     Ok(())
@@ -28,6 +28,28 @@ fn exit_on_ci() {
     }
 }
 
+/// Exposes the git commit hash and enabled cargo features to the daemon for use in build attestations
+fn emit_build_info() {
+    println!("cargo:rerun-if-changed=../../.git/HEAD");
+    let build_hash = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=WALLET_DAEMON_BUILD_HASH={}", build_hash);
+
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+    println!("cargo:rustc-env=WALLET_DAEMON_FEATURES={}", features.join(","));
+}
+
 const BUILD: &[(&str, &str)] = &[
     ("../../bindings", "tsc"),
     ("../../clients/javascript/wallet_daemon_client", "build"),
@@ -37,6 +59,7 @@ const BUILD: &[(&str, &str)] = &[
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=../tari_dan_wallet_web_ui/src");
     println!("cargo:rerun-if-changed=../tari_dan_wallet_web_ui/public");
+    emit_build_info();
 
     if env::var("CARGO_FEATURE_TS").is_ok() {
         println!("cargo:warning=The web ui is not being compiled when we are generating typescript types/interfaces.");
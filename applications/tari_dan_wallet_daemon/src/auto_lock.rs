@@ -0,0 +1,38 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use log::*;
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::handlers::HandlerContext;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::auto_lock";
+
+/// Locks the wallet once no JSON-RPC requests have been received for `timeout`
+pub async fn run_auto_lock(context: HandlerContext, timeout: Duration, mut shutdown_signal: ShutdownSignal) {
+    let mut check_interval = time::interval((timeout / 10).clamp(Duration::from_secs(1), Duration::from_secs(30)));
+    check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = shutdown_signal.wait() => {
+                break;
+            }
+
+            _ = check_interval.tick() => {
+                let sdk = context.wallet_sdk();
+                if !sdk.is_locked() && context.idle_time() >= timeout {
+                    sdk.lock();
+                    info!(
+                        target: LOG_TARGET,
+                        "🔒 Wallet locked after {:.2?} without any requests",
+                        timeout
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Preloads a new wallet from a bootstrap file. The bootstrap file is imported once, the first time the wallet daemon
+//! starts with it configured, and is ignored on subsequent runs.
+//!
+//! Example bootstrap file:
+//! ```json
+//! {
+//!   "keys": [{ "index": 0, "is_active": true }, { "index": 1 }],
+//!   "accounts": [
+//!     { "name": "alice", "address": "component_...", "key_index": 0, "is_default": true },
+//!     { "name": "bob", "address": "component_...", "key_index": 1 }
+//!   ],
+//!   "substates": [{ "substate_id": "resource_...", "version": 0 }]
+//! }
+//! ```
+
+use std::{fs, io, path::Path};
+
+use log::*;
+use serde::Deserialize;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_dan_wallet_sdk::{
+    apis::{
+        accounts::AccountsApiError,
+        config::{ConfigApi, ConfigApiError, ConfigKey},
+        key_manager,
+        key_manager::KeyManagerApiError,
+        substate::SubstateApiError,
+    },
+    models::VersionedSubstateId,
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_engine_types::{serde_with, substate::SubstateId};
+use tari_key_manager::cipher_seed::CipherSeed;
+use tari_template_lib::prelude::ComponentAddress;
+use tari_transaction::TransactionId;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::bootstrap";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BootstrapFile {
+    /// The cipher seed from which all wallet keys are derived. If provided, the wallet must not have been initialized
+    /// with a different seed. This allows a known set of keys to be reproduced across wallets.
+    #[serde(default)]
+    pub cipher_seed: Option<CipherSeed>,
+    #[serde(default)]
+    pub keys: Vec<BootstrapKey>,
+    #[serde(default)]
+    pub accounts: Vec<BootstrapAccount>,
+    /// Additional substates that the wallet should track
+    #[serde(default)]
+    pub substates: Vec<BootstrapSubstate>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BootstrapKey {
+    #[serde(default = "default_key_branch")]
+    pub branch: String,
+    pub index: u64,
+    #[serde(default)]
+    pub is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BootstrapAccount {
+    pub name: Option<String>,
+    pub address: ComponentAddress,
+    /// The transaction key index of the account owner key
+    pub key_index: u64,
+    /// The known version of the account component. The account monitor updates this to the latest version.
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BootstrapSubstate {
+    #[serde(with = "serde_with::string")]
+    pub substate_id: SubstateId,
+    #[serde(default)]
+    pub version: u32,
+    /// If provided, the substate is tracked as a child of this substate (e.g. a vault owned by an account)
+    #[serde(default, with = "serde_with::string::option")]
+    pub parent: Option<SubstateId>,
+}
+
+fn default_key_branch() -> String {
+    key_manager::TRANSACTION_BRANCH.to_string()
+}
+
+impl BootstrapFile {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, BootstrapError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| BootstrapError::ReadFile {
+            path: path.display().to_string(),
+            source,
+        })?;
+        serde_json::from_str(&contents).map_err(|source| BootstrapError::InvalidFile {
+            path: path.display().to_string(),
+            source,
+        })
+    }
+
+    /// Returns true if a bootstrap file has previously been imported into this wallet
+    pub fn is_imported<TStore: WalletStore>(config_api: &ConfigApi<'_, TStore>) -> Result<bool, BootstrapError> {
+        let is_imported = config_api.get(ConfigKey::BootstrapImported).optional()?;
+        Ok(is_imported.unwrap_or(false))
+    }
+
+    /// Sets the wallet cipher seed from the bootstrap file. This must be called before the wallet SDK is initialized,
+    /// otherwise a random seed is generated.
+    pub fn apply_cipher_seed<TStore: WalletStore>(
+        &self,
+        config_api: &ConfigApi<'_, TStore>,
+    ) -> Result<(), BootstrapError> {
+        let Some(ref cipher_seed) = self.cipher_seed else {
+            return Ok(());
+        };
+
+        let existing = config_api.get::<serde_json::Value>(ConfigKey::CipherSeed).optional()?;
+        match existing {
+            Some(existing) => {
+                if existing != serde_json::to_value(cipher_seed)? {
+                    return Err(BootstrapError::CipherSeedMismatch);
+                }
+            },
+            None => {
+                info!(target: LOG_TARGET, "🥾 Setting wallet cipher seed from bootstrap file");
+                config_api.set(ConfigKey::CipherSeed, cipher_seed, true)?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Imports the keys, accounts and substates in the bootstrap file into the wallet.
+    pub fn import<TStore, TNetworkInterface>(
+        &self,
+        sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+    ) -> Result<(), BootstrapError>
+    where
+        TStore: WalletStore,
+        TNetworkInterface: WalletNetworkInterface,
+        TNetworkInterface::Error: IsNotFoundError,
+    {
+        let key_manager_api = sdk.key_manager_api();
+        for key in &self.keys {
+            key_manager_api.add_key_index(&key.branch, key.index)?;
+            if key.is_active {
+                key_manager_api.set_active_key(&key.branch, key.index)?;
+            }
+        }
+
+        let accounts_api = sdk.accounts_api();
+        let substate_api = sdk.substate_api();
+        for account in &self.accounts {
+            let address = SubstateId::Component(account.address);
+            if accounts_api.exists_by_address(&address)? {
+                warn!(target: LOG_TARGET, "🥾 Account {} already exists. Skipping.", address);
+                continue;
+            }
+            key_manager_api.add_key_index(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+            // The account monitor requires the account substate in order to refresh the account vaults
+            substate_api.save_root(TransactionId::default(), VersionedSubstateId {
+                substate_id: address.clone(),
+                version: account.version,
+            })?;
+            accounts_api.add_account(account.name.as_deref(), &address, account.key_index, account.is_default)?;
+            info!(target: LOG_TARGET, "🥾 Imported account {}", address);
+        }
+
+        for substate in &self.substates {
+            // The creating transaction is not known so the default (zero) id is recorded
+            let created_by_tx = TransactionId::default();
+            let address = VersionedSubstateId {
+                substate_id: substate.substate_id.clone(),
+                version: substate.version,
+            };
+            match substate.parent {
+                Some(ref parent) => substate_api.save_child(created_by_tx, parent.clone(), address)?,
+                None => substate_api.save_root(created_by_tx, address)?,
+            }
+        }
+
+        sdk.config_api().set(ConfigKey::BootstrapImported, &true, false)?;
+        info!(
+            target: LOG_TARGET,
+            "🥾 Bootstrap complete: imported {} key(s), {} account(s) and {} substate(s)",
+            self.keys.len(),
+            self.accounts.len(),
+            self.substates.len()
+        );
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BootstrapError {
+    #[error("Unable to read bootstrap file '{path}': {source}")]
+    ReadFile { path: String, source: io::Error },
+    #[error("Invalid bootstrap file '{path}': {source}")]
+    InvalidFile { path: String, source: serde_json::Error },
+    #[error("The wallet has already been initialized with a different cipher seed to the one in the bootstrap file")]
+    CipherSeedMismatch,
+    #[error("Failed to encode cipher seed: {0}")]
+    CipherSeedEncoding(#[from] serde_json::Error),
+    #[error("Config API error: {0}")]
+    ConfigApi(#[from] ConfigApiError),
+    #[error("Key manager API error: {0}")]
+    KeyManagerApi(#[from] KeyManagerApiError),
+    #[error("Accounts API error: {0}")]
+    AccountsApi(#[from] AccountsApiError),
+    #[error("Substate API error: {0}")]
+    SubstateApi(#[from] SubstateApiError),
+}
@@ -20,7 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, path::PathBuf};
 
 use clap::Parser;
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
@@ -42,6 +42,17 @@ pub struct Cli {
     pub indexer_node_json_rpc_url: Option<String>,
     #[clap(long)]
     pub derive_secret: Option<u64>,
+    #[clap(long, env = "TARI_DAN_WALLET_BOOTSTRAP_FILE")]
+    pub bootstrap_file: Option<PathBuf>,
+    /// The wallet database password
+    #[clap(long, env = "TARI_DAN_WALLET_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+    /// Seed words (separated by spaces) from which the wallet root key is restored when the wallet is first initialized
+    #[clap(long, env = "TARI_DAN_WALLET_SEED_WORDS", hide_env_values = true)]
+    pub seed_words: Option<String>,
+    /// The passphrase that the seed words were created with
+    #[clap(long, env = "TARI_DAN_WALLET_SEED_WORDS_PASSPHRASE", hide_env_values = true)]
+    pub seed_words_passphrase: Option<String>,
 }
 
 impl Cli {
@@ -77,6 +88,21 @@ impl ConfigOverrideProvider for Cli {
                 indexer_node_json_rpc_url.clone(),
             ));
         }
+        if let Some(ref bootstrap_file) = self.bootstrap_file {
+            overrides.push((
+                "dan_wallet_daemon.bootstrap_file".to_string(),
+                bootstrap_file.display().to_string(),
+            ));
+        }
+        if let Some(ref password) = self.password {
+            overrides.push(("dan_wallet_daemon.password".to_string(), password.clone()));
+        }
+        if let Some(ref seed_words) = self.seed_words {
+            overrides.push(("dan_wallet_daemon.seed_words".to_string(), seed_words.clone()));
+        }
+        if let Some(ref passphrase) = self.seed_words_passphrase {
+            overrides.push(("dan_wallet_daemon.seed_words_passphrase".to_string(), passphrase.clone()));
+        }
         overrides
     }
 }
@@ -25,7 +25,11 @@ use std::{net::SocketAddr, path::PathBuf, time::Duration};
 use config::Config;
 use serde::{Deserialize, Serialize};
 use tari_common::{configuration::CommonConfig, ConfigurationError, DefaultConfigLoader, SubConfigPath};
+use tari_dan_app_utilities::logging::LogFormat;
 use tari_dan_common_types::crypto::create_secret;
+use tari_dan_wallet_sdk::models::FeePayerStrategy;
+use tari_utilities::SafePassword;
+use url::Url;
 
 #[derive(Debug, Clone)]
 pub struct ApplicationConfig {
@@ -57,6 +61,13 @@ pub struct WalletDaemonConfig {
     pub signaling_server_address: Option<SocketAddr>,
     /// The validator nodes jrpc endpoint url
     pub indexer_node_json_rpc_url: String,
+    /// Additional indexer JSON-RPC URLs. Requests are sent to the healthy endpoint with the lowest latency and fail
+    /// over to the other endpoints if the indexer cannot be reached.
+    #[serde(default)]
+    pub fallback_indexer_node_json_rpc_urls: Vec<String>,
+    /// How often the latency and health of the indexer endpoints are checked. Only used if fallback URLs are set.
+    #[serde(default = "default_indexer_health_check_interval", with = "humantime_serde")]
+    pub indexer_health_check_interval: Duration,
     /// Expiration duration of the JWT token
     #[serde(with = "humantime_serde::option")]
     pub jwt_expiry: Option<Duration>,
@@ -70,6 +81,66 @@ pub struct WalletDaemonConfig {
     /// utility. If this is not set, the value lookup table will be generated on the fly which will have a large
     /// performance cost when brute forcing high-value outputs.
     pub value_lookup_table_file: Option<PathBuf>,
+    /// How often confidential resources that have previously been audited using a resource view key are rescanned.
+    /// If not set, balances are only audited on request.
+    #[serde(default, with = "humantime_serde::option")]
+    pub balance_audit_interval: Option<Duration>,
+    /// A JSON file listing keys, accounts and substates that are imported into the wallet the first time the wallet
+    /// daemon starts. The file is ignored once it has been imported.
+    pub bootstrap_file: Option<PathBuf>,
+    /// Selects the account that pays fees when a request does not specify one. One of "same_as_source",
+    /// "round_robin" or { designated = "<account name or address>" }.
+    #[serde(default)]
+    pub fee_payer_strategy: FeePayerStrategy,
+    /// Controls automatic resubmission of transactions that are rejected because an input version was already
+    /// consumed
+    #[serde(default)]
+    pub transaction_retry_policy: TransactionRetryPolicy,
+    /// The password used to encrypt the wallet database. If the database is not yet encrypted, it is encrypted with
+    /// this password on startup. Prefer setting this with the TARI_DAN_WALLET_PASSWORD environment variable.
+    #[serde(default, skip_serializing)]
+    pub password: Option<SafePassword>,
+    /// Seed words from which the wallet root key is restored when the wallet is first initialized. Prefer setting this
+    /// with the TARI_DAN_WALLET_SEED_WORDS environment variable.
+    #[serde(default, skip_serializing)]
+    pub seed_words: Option<SafePassword>,
+    /// The passphrase that the seed words were created with, if any
+    #[serde(default, skip_serializing)]
+    pub seed_words_passphrase: Option<SafePassword>,
+    /// If set, the wallet database is locked once no JSON-RPC requests have been received for this duration. The
+    /// wallet must then be unlocked with the wallet.unlock method.
+    #[serde(default, with = "humantime_serde::option")]
+    pub auto_lock_timeout: Option<Duration>,
+    /// How often scheduled transactions are checked and submitted if they are due
+    #[serde(default = "default_scheduler_poll_interval", with = "humantime_serde")]
+    pub scheduler_poll_interval: Duration,
+    /// Controls how transactions that were not finalized before their max_epoch are expired and rebuilt
+    #[serde(default)]
+    pub transaction_expiry: TransactionExpiryConfig,
+    /// Controls removal of unreachable substate records and orphaned confidential proofs from the wallet store
+    #[serde(default)]
+    pub garbage_collection: GarbageCollectionConfig,
+    /// Controls detection of the accounts that are created by the first deposit to a key that the wallet has issued
+    #[serde(default)]
+    pub deposit_accounts: DepositAccountsConfig,
+    /// Controls syncing of encrypted wallet metadata (account names, contacts and transaction annotations) to a remote
+    /// endpoint. Key material is never synced.
+    #[serde(default)]
+    pub metadata_sync: MetadataSyncConfig,
+    /// Controls where the binaries of templates published with templates.publish may be loaded from
+    #[serde(default)]
+    pub template_publish: TemplatePublishConfig,
+    /// The log output format. If set to json, the log4rs config file is not used.
+    #[serde(default)]
+    pub log_format: LogFormat,
+}
+
+fn default_scheduler_poll_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
+fn default_indexer_health_check_interval() -> Duration {
+    Duration::from_secs(30)
 }
 
 impl Default for WalletDaemonConfig {
@@ -80,17 +151,230 @@ impl Default for WalletDaemonConfig {
             ui_connect_address: None,
             signaling_server_address: Some(SocketAddr::from(([127u8, 0, 0, 1], 9100))),
             indexer_node_json_rpc_url: "http://127.0.0.1:18300/json_rpc".to_string(),
+            fallback_indexer_node_json_rpc_urls: vec![],
+            indexer_health_check_interval: default_indexer_health_check_interval(),
             // TODO: Come up with a reasonable default value
             jwt_expiry: Some(Duration::from_secs(500 * 60)),
             jwt_secret_key: Some(create_secret()),
             http_ui_address: Some("127.0.0.1:5100".parse().unwrap()),
             value_lookup_table_file: None,
+            balance_audit_interval: None,
+            bootstrap_file: None,
+            fee_payer_strategy: FeePayerStrategy::default(),
+            transaction_retry_policy: TransactionRetryPolicy::default(),
+            password: None,
+            seed_words: None,
+            seed_words_passphrase: None,
+            auto_lock_timeout: None,
+            scheduler_poll_interval: default_scheduler_poll_interval(),
+            transaction_expiry: TransactionExpiryConfig::default(),
+            garbage_collection: GarbageCollectionConfig::default(),
+            deposit_accounts: DepositAccountsConfig::default(),
+            metadata_sync: MetadataSyncConfig::default(),
+            template_publish: TemplatePublishConfig::default(),
+            log_format: LogFormat::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionRetryPolicy {
+    /// The maximum number of times a transaction is resubmitted after an input conflict. Set to 0 to disable
+    /// automatic retries.
+    pub max_attempts: u32,
+    /// The delay before the first retry. The delay doubles for each subsequent attempt.
+    #[serde(with = "humantime_serde")]
+    pub initial_backoff: Duration,
+    /// The maximum delay between retries
+    #[serde(with = "humantime_serde")]
+    pub max_backoff: Duration,
+}
+
+impl TransactionRetryPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 0
+    }
+
+    /// Returns the delay before the given retry attempt, starting at 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let multiplier = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff.saturating_mul(multiplier).min(self.max_backoff)
+    }
+}
+
+impl Default for TransactionRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransactionExpiryConfig {
+    /// How often pending transactions are checked against the current epoch
+    #[serde(with = "humantime_serde")]
+    pub poll_interval: Duration,
+    /// The maximum number of times an expired transaction is rebuilt with new epoch bounds and resubmitted. Set to 0
+    /// to only mark transactions as expired.
+    pub max_rebuild_attempts: u32,
+    /// The number of epochs a rebuilt transaction is valid for if the expired transaction did not specify a
+    /// min_epoch. Otherwise, the rebuilt transaction is valid for the same number of epochs as the original.
+    pub rebuild_epoch_window: u64,
+}
+
+impl TransactionExpiryConfig {
+    pub fn is_rebuild_enabled(&self) -> bool {
+        self.max_rebuild_attempts > 0
+    }
+}
+
+impl Default for TransactionExpiryConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+            max_rebuild_attempts: 0,
+            rebuild_epoch_window: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GarbageCollectionConfig {
+    /// How often garbage collection runs. If not set, garbage is only collected on request (wallet.gc).
+    #[serde(default, with = "humantime_serde::option")]
+    pub interval: Option<Duration>,
+    /// Confidential proofs that were never bound to a transaction are only removed once they are at least this old
+    #[serde(with = "humantime_serde")]
+    pub unbound_proof_min_age: Duration,
+}
+
+impl Default for GarbageCollectionConfig {
+    fn default() -> Self {
+        Self {
+            interval: None,
+            unbound_proof_min_age: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DepositAccountsConfig {
+    /// How often issued transaction keys without an account in the wallet are checked for an account created by a
+    /// deposit. If not set, deposit accounts are not detected.
+    #[serde(default, with = "humantime_serde::option")]
+    pub scan_interval: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MetadataSyncConfig {
+    /// The remote object that wallet metadata is synced with. If not set, metadata is not synced.
+    #[serde(default)]
+    pub endpoint: Option<MetadataSyncEndpoint>,
+    /// How often metadata is synced
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+impl Default for MetadataSyncConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: None,
+            interval: Duration::from_secs(5 * 60),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplatePublishConfig {
+    /// The directory from which template binaries may be loaded by path. If not set, templates cannot be published
+    /// from a path on the wallet daemon host.
+    #[serde(default)]
+    pub binary_dir: Option<PathBuf>,
+    /// The maximum time allowed to download a template binary from a URL
+    #[serde(with = "humantime_serde")]
+    pub download_timeout: Duration,
+    /// The maximum size of a template binary. Downloads and files larger than this are rejected.
+    pub template_binary_max_size_bytes: usize,
+}
+
+impl Default for TemplatePublishConfig {
+    fn default() -> Self {
+        Self {
+            binary_dir: None,
+            download_timeout: Duration::from_secs(30),
+            template_binary_max_size_bytes: 1000 * 1000 * 5, // 5MB
+        }
+    }
+}
+
+/// A single remote object that holds the encrypted metadata. Every device that shares the wallet seed must be
+/// configured with the same object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetadataSyncEndpoint {
+    /// A file URL on a WebDAV server e.g. https://dav.example.com/tari/wallet-metadata
+    WebDav {
+        url: Url,
+        username: Option<String>,
+        #[serde(default, skip_serializing)]
+        password: Option<String>,
+    },
+    /// An object URL on S3 or an S3 compatible store e.g. https://bucket.s3.eu-west-1.amazonaws.com/wallet-metadata.
+    /// Requests are signed with AWS signature version 4.
+    S3 {
+        url: Url,
+        region: String,
+        access_key_id: String,
+        #[serde(default, skip_serializing)]
+        secret_access_key: String,
+    },
+}
+
 impl SubConfigPath for WalletDaemonConfig {
     fn main_key_prefix() -> &'static str {
         "dan_wallet_daemon"
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_doubles_the_retry_backoff_up_to_the_maximum() {
+        let policy = TransactionRetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(30),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(2), Duration::from_secs(4));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(4), Duration::from_secs(16));
+        assert_eq!(policy.backoff(5), Duration::from_secs(30));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+        // Large attempts saturate instead of overflowing
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+        // Attempt 0 is treated as the first attempt
+        assert_eq!(policy.backoff(0), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn it_disables_retries_if_max_attempts_is_zero() {
+        let policy = TransactionRetryPolicy {
+            max_attempts: 0,
+            ..Default::default()
+        };
+        assert!(!policy.is_enabled());
+        assert!(TransactionRetryPolicy::default().is_enabled());
+    }
+}
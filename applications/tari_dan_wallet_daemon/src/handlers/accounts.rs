@@ -1,12 +1,16 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
-use std::convert::TryFrom;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::anyhow;
 use base64;
 use log::*;
 use rand::rngs::OsRng;
-use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
 use tari_crypto::{
     commitment::HomomorphicCommitment as Commitment,
     keys::PublicKey as _,
@@ -16,7 +20,13 @@ use tari_crypto::{
 use tari_dan_common_types::{optional::Optional, SubstateRequirement};
 use tari_dan_wallet_crypto::ConfidentialProofStatement;
 use tari_dan_wallet_sdk::{
-    apis::{confidential_transfer::TransferParams, jwt::JrpcPermission, key_manager, substate::ValidatorScanResult},
+    apis::{
+        account_recovery::DEFAULT_RECOVERY_GAP_LIMIT,
+        confidential_transfer::TransferParams,
+        jwt::JrpcPermission,
+        key_manager,
+        substate::ValidatorScanResult,
+    },
     models::NewAccountInfo,
     storage::WalletStore,
     DanWalletSdk,
@@ -34,30 +44,42 @@ use tari_template_lib::{
     args,
     constants::{XTR_FAUCET_COMPONENT_ADDRESS, XTR_FAUCET_VAULT_ADDRESS},
     models::{Amount, UnclaimedConfidentialOutputAddress},
-    prelude::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+    prelude::{ResourceType, CONFIDENTIAL_TARI_RESOURCE_ADDRESS},
 };
 use tari_transaction::Transaction;
 use tari_wallet_daemon_client::{
     types::{
         AccountGetDefaultRequest,
         AccountGetRequest,
+        AccountBalanceStatement,
         AccountGetResponse,
         AccountInfo,
         AccountSetDefaultRequest,
         AccountSetDefaultResponse,
+        AccountsCreateBalanceProofRequest,
+        AccountsCreateBalanceProofResponse,
         AccountsCreateFreeTestCoinsRequest,
         AccountsCreateFreeTestCoinsResponse,
         AccountsCreateRequest,
         AccountsCreateResponse,
+        AccountsGetBalanceAtEpochRequest,
+        AccountsGetBalanceAtEpochResponse,
         AccountsGetBalancesRequest,
         AccountsGetBalancesResponse,
+        AccountsImportRequest,
+        AccountsImportResponse,
+        AccountsImportWatchOnlyRequest,
+        AccountsImportWatchOnlyResponse,
         AccountsInvokeRequest,
         AccountsInvokeResponse,
         AccountsListRequest,
         AccountsListResponse,
+        AccountsRecoverRequest,
+        AccountsRecoverResponse,
         AccountsTransferRequest,
         AccountsTransferResponse,
         BalanceEntry,
+        BalanceStatementEntry,
         ClaimBurnRequest,
         ClaimBurnResponse,
         ConfidentialTransferRequest,
@@ -72,15 +94,22 @@ use tokio::task;
 use super::context::HandlerContext;
 use crate::{
     handlers::helpers::{
+        check_can_sign,
         get_account,
+        get_account_inputs,
         get_account_or_default,
+        get_account_public_key,
         get_account_with_inputs,
+        get_fee_account,
         invalid_params,
+        resolve_amount,
+        resolve_max_fee,
+        resolve_public_key,
         wait_for_result,
         wait_for_result_and_account,
     },
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
-    services::TransactionSubmittedEvent,
+    services::{TransactionSubmittedEvent, DEFAULT_AUDIT_VALUE_RANGE},
     DEFAULT_FEE,
 };
 
@@ -102,13 +131,16 @@ pub async fn handle_create(
     }
 
     let default_account = sdk.accounts_api().get_default()?;
+    check_can_sign(&default_account)?;
     let inputs = sdk
         .substate_api()
         .locate_dependent_substates(&[default_account.address.clone()])
         .await?;
 
-    let signing_key_index = req.key_id.unwrap_or(default_account.key_index);
-    let signing_key = key_manager_api.derive_key(key_manager::TRANSACTION_BRANCH, signing_key_index)?;
+    let signing_key = match req.key_id {
+        Some(key_id) => key_manager_api.derive_key(key_manager::TRANSACTION_BRANCH, key_id)?,
+        None => key_manager_api.derive_key(default_account.key_branch(), default_account.key_index)?,
+    };
 
     let owner_key = key_manager_api.next_key(key_manager::TRANSACTION_BRANCH)?;
     let owner_pk = PublicKey::from_secret_key(&owner_key.key);
@@ -177,6 +209,99 @@ pub async fn handle_set_default(
     Ok(AccountSetDefaultResponse {})
 }
 
+pub async fn handle_recover(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsRecoverRequest,
+) -> Result<AccountsRecoverResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let gap_limit = req.gap_limit.unwrap_or(DEFAULT_RECOVERY_GAP_LIMIT);
+    let recovered = sdk.account_recovery_api().recover_accounts(gap_limit).await?;
+
+    let mut accounts = Vec::with_capacity(recovered.len());
+    for recovered_account in recovered {
+        // Populate the vaults and balances of the recovered account
+        if let Err(err) = context
+            .account_monitor()
+            .refresh_account(recovered_account.address.clone())
+            .await
+        {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to refresh recovered account {}: {}", recovered_account.address, err
+            );
+        }
+        let account = sdk.accounts_api().get_account_by_address(&recovered_account.address)?;
+        accounts.push(AccountInfo {
+            account,
+            public_key: recovered_account.public_key,
+        });
+    }
+
+    Ok(AccountsRecoverResponse { accounts })
+}
+
+pub async fn handle_import(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsImportRequest,
+) -> Result<AccountsImportResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let imported = sdk
+        .account_recovery_api()
+        .import_account(req.account_name.as_deref(), &req.secret_key, req.is_default)
+        .await?;
+
+    // Populate the vaults and balances of the imported account
+    if let Err(err) = context
+        .account_monitor()
+        .refresh_account(imported.address.clone())
+        .await
+    {
+        warn!(
+            target: LOG_TARGET,
+            "Failed to refresh imported account {}: {}", imported.address, err
+        );
+    }
+
+    let account = sdk.accounts_api().get_account_by_address(&imported.address)?;
+    Ok(AccountsImportResponse {
+        account: AccountInfo {
+            account,
+            public_key: imported.public_key,
+        },
+    })
+}
+
+pub async fn handle_import_watch_only(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsImportWatchOnlyRequest,
+) -> Result<AccountsImportWatchOnlyResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let address = SubstateId::Component(req.address);
+    sdk.accounts_api().add_watch_only_account(
+        req.account_name.as_deref(),
+        &address,
+        &req.owner_public_key,
+        req.view_key.as_ref(),
+    )?;
+
+    // Populate the vaults and balances of the account
+    if let Err(err) = context.account_monitor().refresh_account(address.clone()).await {
+        warn!(
+            target: LOG_TARGET,
+            "Failed to refresh watch-only account {}: {}", address, err
+        );
+    }
+
+    let account = sdk.accounts_api().get_account_by_address(&address)?;
+    Ok(AccountsImportWatchOnlyResponse { account })
+}
+
 pub async fn handle_list(
     context: &HandlerContext,
     token: Option<String>,
@@ -186,16 +311,11 @@ pub async fn handle_list(
     sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let accounts = sdk.accounts_api().get_many(req.offset, req.limit)?;
     let total = sdk.accounts_api().count()?;
-    let km = sdk.key_manager_api();
     let accounts = accounts
         .into_iter()
         .map(|a| {
-            let key = km.derive_key(key_manager::TRANSACTION_BRANCH, a.key_index)?;
-            let pk = PublicKey::from_secret_key(&key.key);
-            Ok(AccountInfo {
-                account: a,
-                public_key: pk,
-            })
+            let public_key = get_account_public_key(&a, &sdk)?;
+            Ok(AccountInfo { account: a, public_key })
         })
         .collect::<Result<_, anyhow::Error>>()?;
 
@@ -211,10 +331,11 @@ pub async fn handle_invoke(
     sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    check_can_sign(&account)?;
 
     let signing_key = sdk
         .key_manager_api()
-        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        .derive_key(account.key_branch(), account.key_index)?;
 
     let inputs = sdk.substate_api().load_dependent_substates(&[&account.address])?;
 
@@ -275,6 +396,8 @@ pub async fn handle_get_balances(
             resource_type: vault.resource_type,
             confidential_balance: vault.confidential_balance,
             token_symbol: vault.token_symbol,
+            decimals: vault.decimals,
+            is_frozen: vault.is_frozen,
         })
     }
 
@@ -284,6 +407,96 @@ pub async fn handle_get_balances(
     })
 }
 
+pub async fn handle_get_balance_at_epoch(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsGetBalanceAtEpochRequest,
+) -> Result<AccountsGetBalanceAtEpochResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::AccountBalance(account.clone().address)])?;
+
+    let balances = sdk
+        .balance_history_api()
+        .get_balances_at_epoch(&account.address, req.epoch)
+        .await?;
+
+    Ok(AccountsGetBalanceAtEpochResponse {
+        address: account.address,
+        epoch: req.epoch,
+        balances,
+    })
+}
+
+pub async fn handle_create_balance_proof(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AccountsCreateBalanceProofRequest,
+) -> Result<AccountsCreateBalanceProofResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    check_can_sign(&account)?;
+
+    if req.refresh {
+        context
+            .account_monitor()
+            .refresh_account(account.address.clone())
+            .await?;
+    }
+    let vaults = sdk.accounts_api().get_vaults_by_account(&account.address)?;
+
+    // Confidential balances opened with the view key, keyed by vault
+    let mut view_key_balances = HashMap::new();
+    if let Some(view_key_id) = req.view_key_id {
+        let confidential_resources = vaults
+            .iter()
+            .filter(|v| v.resource_type == ResourceType::Confidential)
+            .map(|v| v.resource_address)
+            .collect::<HashSet<_>>();
+        for resource_address in confidential_resources {
+            let audited = if req.refresh {
+                context
+                    .balance_auditor()
+                    .audit_resource(resource_address, view_key_id, DEFAULT_AUDIT_VALUE_RANGE)
+                    .await?
+            } else {
+                sdk.confidential_audit_api().get_balances(&resource_address, view_key_id)?
+            };
+            view_key_balances.extend(audited.into_iter().map(|b| (SubstateId::Vault(b.vault_id), b.balance)));
+        }
+    }
+
+    let balances = vaults
+        .into_iter()
+        .map(|vault| BalanceStatementEntry {
+            view_key_balance: view_key_balances.get(&vault.address).copied(),
+            vault_address: vault.address,
+            resource_address: vault.resource_address,
+            resource_type: vault.resource_type,
+            revealed_balance: vault.revealed_balance,
+            confidential_balance: vault.confidential_balance,
+            token_symbol: vault.token_symbol,
+        })
+        .collect();
+
+    let owner_key = sdk
+        .key_manager_api()
+        .derive_key(account.key_branch(), account.key_index)?;
+    let statement = AccountBalanceStatement {
+        account: account.address,
+        balances,
+        public_key: PublicKey::from_secret_key(&owner_key.key),
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        nonce: req.nonce,
+    };
+    let signature = Signature::sign(&owner_key.key, statement.to_signing_message(), &mut OsRng)
+        .map_err(|e| anyhow!("Failed to sign balance statement: {e}"))?;
+
+    Ok(AccountsCreateBalanceProofResponse { statement, signature })
+}
+
 pub async fn handle_get(
     context: &HandlerContext,
     token: Option<String>,
@@ -292,9 +505,7 @@ pub async fn handle_get(
     let sdk = context.wallet_sdk();
     sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let account = get_account(&req.name_or_address, &sdk.accounts_api())?;
-    let km = sdk.key_manager_api();
-    let key = km.derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
-    let public_key = PublicKey::from_secret_key(&key.key);
+    let public_key = get_account_public_key(&account, &sdk)?;
     Ok(AccountGetResponse { account, public_key })
 }
 
@@ -306,9 +517,7 @@ pub async fn handle_get_default(
     let sdk = context.wallet_sdk();
     sdk.jwt_api().check_auth(token, &[JrpcPermission::AccountInfo])?;
     let account = get_account_or_default(None, &sdk.accounts_api())?;
-    let km = sdk.key_manager_api();
-    let key = km.derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
-    let public_key = PublicKey::from_secret_key(&key.key);
+    let public_key = get_account_public_key(&account, &sdk)?;
     Ok(AccountGetResponse { account, public_key })
 }
 
@@ -327,6 +536,7 @@ pub async fn handle_reveal_funds(
     // spawn a task that will continue running.
     task::spawn(async move {
         let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+        check_can_sign(&account)?;
 
         let vault = sdk
             .accounts_api()
@@ -344,7 +554,7 @@ pub async fn handle_reveal_funds(
 
         let account_key = sdk
             .key_manager_api()
-            .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+            .derive_key(account.key_branch(), account.key_index)?;
 
         let output_mask = sdk.key_manager_api().next_key(key_manager::TRANSACTION_BRANCH)?;
         let (_, public_nonce) = PublicKey::random_keypair(&mut OsRng);
@@ -368,7 +578,7 @@ pub async fn handle_reveal_funds(
 
         let inputs = sdk
             .confidential_outputs_api()
-            .resolve_output_masks(inputs, key_manager::TRANSACTION_BRANCH)?;
+            .resolve_output_masks(inputs, account.key_branch())?;
 
         let reveal_proof = sdk.confidential_crypto_api().generate_withdraw_proof(
             &inputs,
@@ -471,7 +681,7 @@ pub async fn handle_claim_burn(
         key_id,
     } = req;
 
-    let max_fee = max_fee.unwrap_or(DEFAULT_FEE);
+    let max_fee = resolve_max_fee(max_fee.as_ref(), None, sdk).await?;
     if max_fee.is_negative() {
         return Err(invalid_params("fee", Some("cannot be negative")));
     }
@@ -815,10 +1025,15 @@ fn get_or_create_account<T: WalletStore>(
     };
     let (account_address, account_secret_key, new_account_name) = match maybe_account {
         Some(account) => {
-            let key_index = key_id.unwrap_or(account.key_index);
-            let account_secret_key = sdk
-                .key_manager_api()
-                .derive_key(key_manager::TRANSACTION_BRANCH, key_index)?;
+            check_can_sign(&account)?;
+            let account_secret_key = match key_id {
+                Some(key_id) => sdk
+                    .key_manager_api()
+                    .derive_key(key_manager::TRANSACTION_BRANCH, key_id)?,
+                None => sdk
+                    .key_manager_api()
+                    .derive_key(account.key_branch(), account.key_index)?,
+            };
             let account_substate = sdk.substate_api().get_substate(&account.address)?;
             inputs.push(account_substate.address.into());
 
@@ -854,6 +1069,7 @@ pub async fn handle_transfer(
     sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
 
     let (account, mut inputs) = get_account_with_inputs(req.account, &sdk)?;
+    let destination_public_key = resolve_public_key(req.destination_public_key, &sdk)?;
 
     // get the source account component address
     let source_account_address = account
@@ -868,6 +1084,9 @@ pub async fn handle_transfer(
     let src_vault_substate = sdk.substate_api().get_substate(&src_vault.address)?;
     inputs.push(src_vault_substate.address);
 
+    let amount = resolve_amount("amount", &req.amount, Some(&account), &req.resource_address, &sdk).await?;
+    let max_fee = resolve_max_fee(req.max_fee.as_ref(), Some(&account), &sdk).await?;
+
     // add the input for the resource address to be transfered
     let resource_substate = sdk
         .substate_api()
@@ -883,7 +1102,7 @@ pub async fn handle_transfer(
     let mut fee_instructions = vec![];
 
     let destination_account_address =
-        new_component_address_from_public_key(&ACCOUNT_TEMPLATE_ADDRESS, &req.destination_public_key);
+        new_component_address_from_public_key(&ACCOUNT_TEMPLATE_ADDRESS, &destination_public_key);
     let existing_account = sdk
         .substate_api()
         .scan_for_substate(&SubstateId::Component(destination_account_address), None)
@@ -894,7 +1113,7 @@ pub async fn handle_transfer(
         inputs.push(address);
     } else {
         instructions.push(Instruction::CreateAccount {
-            public_key_address: req.destination_public_key,
+            public_key_address: destination_public_key,
             owner_rule: None,
             access_rules: None,
             workspace_bucket: None,
@@ -913,12 +1132,11 @@ pub async fn handle_transfer(
     }
 
     // build the transaction
-    let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
     instructions.extend([
         Instruction::CallMethod {
             component_address: source_account_address,
             method: "withdraw".to_string(),
-            args: args![req.resource_address, req.amount],
+            args: args![req.resource_address, amount],
         },
         Instruction::PutLastInstructionOutputOnWorkspace {
             key: b"bucket".to_vec(),
@@ -934,22 +1152,41 @@ pub async fn handle_transfer(
         instructions.push(Instruction::DropAllProofsInWorkspace);
     }
 
+    let fee_account = get_fee_account(context, req.fee_account, Some(&account)).await?;
+    let fee_account_address = fee_account
+        .address
+        .as_component_address()
+        .ok_or_else(|| anyhow!("Invalid fee account address"))?;
+    // If another account pays the fees, it must also sign the transaction
+    let fee_account_secret_key = if fee_account.address == account.address {
+        None
+    } else {
+        inputs.extend(get_account_inputs(&fee_account, &sdk)?);
+        Some(
+            sdk.key_manager_api()
+                .derive_key(fee_account.key_branch(), fee_account.key_index)?,
+        )
+    };
+
     fee_instructions.extend([Instruction::CallMethod {
-        component_address: source_account_address,
+        component_address: fee_account_address,
         method: "pay_fee".to_string(),
         args: args![max_fee],
     }]);
 
     let account_secret_key = sdk
         .key_manager_api()
-        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        .derive_key(account.key_branch(), account.key_index)?;
 
-    let transaction = Transaction::builder()
+    let mut builder = Transaction::builder()
         .with_fee_instructions(fee_instructions)
         .with_instructions(instructions)
         .with_inputs(vec![resource_substate_address])
-        .sign(&account_secret_key.key)
-        .build();
+        .sign(&account_secret_key.key);
+    if let Some(ref fee_account_secret_key) = fee_account_secret_key {
+        builder = builder.sign(&fee_account_secret_key.key);
+    }
+    let transaction = builder.build();
 
     let required_inputs = inputs.into_iter().map(Into::into).collect();
     // If dry run we can return the result immediately
@@ -1009,24 +1246,27 @@ pub async fn handle_confidential_transfer(
     let sdk = context.wallet_sdk().clone();
     sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
     let notifier = context.notifier().clone();
-
-    if req.amount.is_negative() {
-        return Err(invalid_params("amount", Some("must be positive")));
-    }
     let transaction_service = context.transaction_service().clone();
 
     task::spawn(async move {
         let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+        check_can_sign(&account)?;
+        let amount = resolve_amount("amount", &req.amount, Some(&account), &req.resource_address, &sdk).await?;
+        if amount.is_negative() {
+            return Err(invalid_params("amount", Some("must be positive")));
+        }
+        let max_fee = resolve_max_fee(req.max_fee.as_ref(), Some(&account), &sdk).await?;
+        let destination_public_key = resolve_public_key(req.destination_public_key, &sdk)?;
 
         let transfer = sdk
             .confidential_transfer_api()
             .transfer(TransferParams {
                 from_account: account.address.as_component_address().unwrap(),
                 input_selection: req.input_selection,
-                amount: req.amount,
-                destination_public_key: req.destination_public_key,
+                amount,
+                destination_public_key,
                 resource_address: req.resource_address,
-                max_fee: req.max_fee.unwrap_or(DEFAULT_FEE),
+                max_fee,
                 output_to_revealed: req.output_to_revealed,
                 proof_from_resource: req.proof_from_badge_resource,
                 is_dry_run: req.dry_run,
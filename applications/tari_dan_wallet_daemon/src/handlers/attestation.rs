@@ -0,0 +1,64 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use blake2::Blake2b;
+use digest::consts::U32;
+use rand::rngs::OsRng;
+use tari_common_types::types::{PublicKey, Signature};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as _, tari_utilities::hex::Hex};
+use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_wallet_daemon_client::types::{AttestationGetRequest, AttestationGetResponse, DaemonAttestation};
+
+use crate::{config::WalletDaemonConfig, handlers::HandlerContext};
+
+hash_domain!(
+    WalletDaemonAttestationHashDomain,
+    "com.tari.dan.wallet_daemon.attestation",
+    0
+);
+
+const BUILD_HASH: &str = env!("WALLET_DAEMON_BUILD_HASH");
+const ENABLED_FEATURES: &str = env!("WALLET_DAEMON_FEATURES");
+
+pub async fn handle_get(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: AttestationGetRequest,
+) -> Result<AttestationGetResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    let identity_key = sdk.key_manager_api().derive_key(key_manager::IDENTITY_BRANCH, 0)?;
+    let attestation = DaemonAttestation {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        build_hash: BUILD_HASH.to_string(),
+        enabled_features: ENABLED_FEATURES
+            .split(',')
+            .filter(|f| !f.is_empty())
+            .map(ToString::to_string)
+            .collect(),
+        config_fingerprint: config_fingerprint(context.config())?,
+        public_key: PublicKey::from_secret_key(&identity_key.key),
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        nonce: req.nonce,
+    };
+
+    let signature = Signature::sign(&identity_key.key, attestation.to_signing_message(), &mut OsRng)
+        .map_err(|e| anyhow!("Failed to sign attestation: {e}"))?;
+
+    Ok(AttestationGetResponse { attestation, signature })
+}
+
+/// Hashes the daemon configuration so that operators can check that all daemons in a fleet are running with the same
+/// configuration. Secrets are removed before hashing, so that the fingerprint cannot be used to guess them.
+fn config_fingerprint(config: &WalletDaemonConfig) -> Result<String, anyhow::Error> {
+    let mut config = config.clone();
+    config.jwt_secret_key = None;
+    let hash = DomainSeparatedHasher::<Blake2b<U32>, WalletDaemonAttestationHashDomain>::new_with_label("config")
+        .chain(serde_json::to_vec(&config)?)
+        .finalize();
+    Ok(hash.as_ref().to_hex())
+}
@@ -19,6 +19,8 @@ use tari_dan_wallet_sdk::{
 use tari_engine_types::confidential::get_commitment_factory;
 use tari_template_lib::models::Amount;
 use tari_wallet_daemon_client::types::{
+    ConfidentialAuditBalancesRequest,
+    ConfidentialAuditBalancesResponse,
     ConfidentialCreateOutputProofRequest,
     ConfidentialCreateOutputProofResponse,
     ConfidentialViewVaultBalanceRequest,
@@ -30,9 +32,12 @@ use tari_wallet_daemon_client::types::{
 };
 use tokio::{task::block_in_place, time::Instant};
 
-use crate::handlers::{
-    helpers::{get_account_or_default, invalid_params},
-    HandlerContext,
+use crate::{
+    handlers::{
+        helpers::{check_can_sign, get_account_or_default, invalid_params},
+        HandlerContext,
+    },
+    services::DEFAULT_AUDIT_VALUE_RANGE,
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::json_rpc::confidential";
@@ -59,6 +64,7 @@ pub async fn handle_create_transfer_proof(
     }
 
     let account = get_account_or_default(req.account, &sdk.accounts_api())?;
+    check_can_sign(&account)?;
     let vault = sdk
         .accounts_api()
         .get_vault_by_resource(&account.address, &req.resource_address)?;
@@ -83,7 +89,7 @@ pub async fn handle_create_transfer_proof(
     // TODO: Wrap up key/encrypted data handling in the wallet SDK
     let account_secret = sdk
         .key_manager_api()
-        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        .derive_key(account.key_branch(), account.key_index)?;
     let output_mask = sdk.key_manager_api().next_key(key_manager::TRANSACTION_BRANCH)?;
     let (_, public_nonce) = PublicKey::random_keypair(&mut OsRng);
 
@@ -162,7 +168,7 @@ pub async fn handle_create_transfer_proof(
 
     let inputs = sdk
         .confidential_outputs_api()
-        .resolve_output_masks(inputs, key_manager::TRANSACTION_BRANCH)?;
+        .resolve_output_masks(inputs, account.key_branch())?;
 
     let proof = sdk.confidential_crypto_api().generate_withdraw_proof(
         &inputs,
@@ -301,3 +307,41 @@ pub async fn handle_view_vault_balance(
             .collect(),
     })
 }
+
+pub async fn handle_audit_balances(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: ConfidentialAuditBalancesRequest,
+) -> Result<ConfidentialAuditBalancesResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    let mut balances = sdk
+        .confidential_audit_api()
+        .get_balances(&req.resource_address, req.view_key_id)?;
+
+    if req.refresh || balances.is_empty() {
+        let value_range = req.minimum_expected_value.unwrap_or(*DEFAULT_AUDIT_VALUE_RANGE.start())..=
+            req.maximum_expected_value.unwrap_or(*DEFAULT_AUDIT_VALUE_RANGE.end());
+
+        let timer = Instant::now();
+        balances = context
+            .balance_auditor()
+            .audit_resource(req.resource_address, req.view_key_id, value_range)
+            .await?;
+        info!(
+            target: LOG_TARGET,
+            "Audited {} vault(s) of resource {} in {:.2?}",
+            balances.len(),
+            req.resource_address,
+            timer.elapsed()
+        );
+    }
+
+    let total_balance = balances.iter().map(|b| b.balance).sum();
+
+    Ok(ConfidentialAuditBalancesResponse {
+        balances,
+        total_balance,
+    })
+}
@@ -0,0 +1,52 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::{apis::jwt::JrpcPermission, models::Contact};
+use tari_wallet_daemon_client::types::{
+    ContactsAddRequest,
+    ContactsAddResponse,
+    ContactsListRequest,
+    ContactsListResponse,
+    ContactsRemoveRequest,
+    ContactsRemoveResponse,
+};
+
+use super::context::HandlerContext;
+
+pub async fn handle_add(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: ContactsAddRequest,
+) -> Result<ContactsAddResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let contact = Contact {
+        name: req.name,
+        component_address: req.component_address,
+        public_key: req.public_key,
+    };
+    sdk.contacts_api().add_contact(&contact)?;
+    Ok(ContactsAddResponse { contact })
+}
+
+pub async fn handle_list(
+    context: &HandlerContext,
+    token: Option<String>,
+    _req: ContactsListRequest,
+) -> Result<ContactsListResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let contacts = sdk.contacts_api().get_contacts()?;
+    Ok(ContactsListResponse { contacts })
+}
+
+pub async fn handle_remove(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: ContactsRemoveRequest,
+) -> Result<ContactsRemoveResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    sdk.contacts_api().remove_contact(&req.name)?;
+    Ok(ContactsRemoveResponse {})
+}
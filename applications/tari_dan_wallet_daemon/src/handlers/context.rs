@@ -1,14 +1,21 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
 use tari_dan_wallet_sdk::DanWalletSdk;
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 
+#[cfg(feature = "metrics")]
+use crate::metrics::WalletDaemonMetrics;
 use crate::{
     config::WalletDaemonConfig,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     notify::Notify,
-    services::{AccountMonitorHandle, TransactionServiceHandle, WalletEvent},
+    services::{AccountMonitorHandle, BalanceAuditorHandle, TransactionServiceHandle, WalletEvent},
 };
 
 #[derive(Debug, Clone)]
@@ -17,7 +24,11 @@ pub struct HandlerContext {
     notifier: Notify<WalletEvent>,
     transaction_service: TransactionServiceHandle,
     account_monitor: AccountMonitorHandle,
+    balance_auditor: BalanceAuditorHandle,
     config: WalletDaemonConfig,
+    last_activity: Arc<Mutex<Instant>>,
+    #[cfg(feature = "metrics")]
+    metrics: WalletDaemonMetrics,
 }
 
 impl HandlerContext {
@@ -26,6 +37,7 @@ impl HandlerContext {
         notifier: Notify<WalletEvent>,
         transaction_service: TransactionServiceHandle,
         account_monitor: AccountMonitorHandle,
+        balance_auditor: BalanceAuditorHandle,
         config: WalletDaemonConfig,
     ) -> Self {
         Self {
@@ -33,7 +45,11 @@ impl HandlerContext {
             notifier,
             transaction_service,
             account_monitor,
+            balance_auditor,
             config,
+            last_activity: Arc::new(Mutex::new(Instant::now())),
+            #[cfg(feature = "metrics")]
+            metrics: WalletDaemonMetrics::new(),
         }
     }
 
@@ -49,6 +65,10 @@ impl HandlerContext {
         &self.account_monitor
     }
 
+    pub fn balance_auditor(&self) -> &BalanceAuditorHandle {
+        &self.balance_auditor
+    }
+
     pub fn transaction_service(&self) -> &TransactionServiceHandle {
         &self.transaction_service
     }
@@ -56,4 +76,24 @@ impl HandlerContext {
     pub fn config(&self) -> &WalletDaemonConfig {
         &self.config
     }
+
+    pub fn record_activity(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    /// Returns the time since the last JSON-RPC request was received
+    pub fn idle_time(&self) -> Duration {
+        self.last_activity.lock().unwrap().elapsed()
+    }
+
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> &WalletDaemonMetrics {
+        &self.metrics
+    }
+
+    /// Called when a request is rejected because of a missing, invalid or revoked JWT
+    pub fn record_auth_failure(&self) {
+        #[cfg(feature = "metrics")]
+        self.metrics.on_jwt_auth_failure();
+    }
 }
@@ -0,0 +1,23 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::apis::jwt::JrpcPermission;
+use tari_wallet_daemon_client::types::{EventsListRequest, EventsListResponse};
+
+use crate::handlers::HandlerContext;
+
+pub async fn handle_list(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: EventsListRequest,
+) -> Result<EventsListResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::SubstatesRead])?;
+
+    let events = sdk
+        .get_network_interface()
+        .get_events(req.topic, req.substate_id, req.offset, req.limit)
+        .await?;
+
+    Ok(EventsListResponse { events })
+}
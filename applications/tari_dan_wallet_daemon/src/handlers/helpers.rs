@@ -3,21 +3,30 @@
 
 use std::fmt::Display;
 
+use tari_common_types::types::PublicKey;
+use tari_crypto::keys::PublicKey as _;
 use tari_dan_common_types::optional::Optional;
 use tari_dan_wallet_sdk::{
     apis::accounts::{AccountsApi, AccountsApiError},
-    models::{Account, VersionedSubstateId},
+    models::{Account, AccountKind, VersionedSubstateId},
+    network::WalletNetworkInterface,
     DanWalletSdk,
 };
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{
+    models::{Amount, ComponentAddress, ResourceAddress},
+    prelude::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+};
 use tari_transaction::TransactionId;
-use tari_wallet_daemon_client::ComponentAddressOrName;
+use tari_wallet_daemon_client::{types::AmountInput, ComponentAddressOrName, PublicKeyOrContact};
 use tokio::sync::broadcast;
 
 use crate::{
+    handlers::HandlerContext,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     services::{TransactionFinalizedEvent, WalletEvent},
+    DEFAULT_FEE,
 };
 
 pub async fn wait_for_result(
@@ -28,6 +37,13 @@ pub async fn wait_for_result(
         let wallet_event = events.recv().await?;
         match wallet_event {
             WalletEvent::TransactionFinalized(event) if event.transaction_id == transaction_id => return Ok(event),
+            WalletEvent::TransactionExpired(event) if event.transaction_id == transaction_id => {
+                return Err(anyhow::anyhow!(
+                    "Transaction expired: max_epoch {} has passed (current epoch {})",
+                    event.max_epoch,
+                    event.current_epoch
+                ));
+            },
             WalletEvent::TransactionInvalid(event) if event.transaction_id == transaction_id => {
                 return Err(anyhow::anyhow!(
                     "Transaction invalid: {} [status: {}]",
@@ -57,6 +73,13 @@ pub async fn wait_for_result_and_account(
             WalletEvent::TransactionFinalized(event) if event.transaction_id == *transaction_id => {
                 maybe_result = Some(event);
             },
+            WalletEvent::TransactionExpired(event) if event.transaction_id == *transaction_id => {
+                return Err(anyhow::anyhow!(
+                    "Transaction expired: max_epoch {} has passed (current epoch {})",
+                    event.max_epoch,
+                    event.current_epoch
+                ));
+            },
             WalletEvent::TransactionInvalid(event) if event.transaction_id == *transaction_id => {
                 return Err(anyhow::anyhow!(
                     "Transaction invalid: {} [status: {}]",
@@ -90,7 +113,16 @@ pub fn get_account_with_inputs(
     sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
 ) -> Result<(Account, Vec<VersionedSubstateId>), anyhow::Error> {
     let account = get_account_or_default(account, &sdk.accounts_api())?;
+    check_can_sign(&account)?;
+    let inputs = get_account_inputs(&account, sdk)?;
+    Ok((account, inputs))
+}
 
+/// Returns the account component substate and all versioned child substates of the account
+pub fn get_account_inputs(
+    account: &Account,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<Vec<VersionedSubstateId>, anyhow::Error> {
     let mut inputs = vec![];
 
     // add the input for the source account component substate
@@ -101,7 +133,53 @@ pub fn get_account_with_inputs(
     let child_addresses = sdk.substate_api().load_dependent_substates(&[&account.address])?;
     inputs.extend(child_addresses);
 
-    Ok((account, inputs))
+    Ok(inputs)
+}
+
+/// Returns the account that pays the transaction fees. If no fee account is given, the account is selected by the
+/// configured fee payer strategy.
+pub async fn get_fee_account(
+    context: &HandlerContext,
+    fee_account: Option<ComponentAddressOrName>,
+    source_account: Option<&Account>,
+) -> Result<Account, anyhow::Error> {
+    let account = match fee_account {
+        Some(fee_account) => get_account(&fee_account, &context.wallet_sdk().accounts_api())?,
+        None => {
+            context
+                .transaction_service()
+                .select_fee_account(source_account.cloned())
+                .await?
+        },
+    };
+    check_can_sign(&account)?;
+    Ok(account)
+}
+
+/// Returns an error if the wallet does not hold the owner key of the account i.e. the account is watch-only
+pub fn check_can_sign(account: &Account) -> Result<(), AccountsApiError> {
+    if account.is_watch_only() {
+        return Err(AccountsApiError::WatchOnlyAccount {
+            address: account.address.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the public key of the account owner. For watch-only accounts this is the public key that was imported.
+pub fn get_account_public_key(
+    account: &Account,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<PublicKey, anyhow::Error> {
+    match account.kind {
+        AccountKind::Owned | AccountKind::Imported => {
+            let key = sdk
+                .key_manager_api()
+                .derive_key(account.key_branch(), account.key_index)?;
+            Ok(PublicKey::from_secret_key(&key.key))
+        },
+        AccountKind::WatchOnly { ref owner_public_key } => Ok(owner_public_key.clone()),
+    }
 }
 
 pub fn get_account<TStore>(
@@ -138,6 +216,94 @@ where
     Ok(result)
 }
 
+/// Resolves a public key or the name of a contact in the address book to a public key
+pub fn resolve_public_key(
+    public_key_or_contact: PublicKeyOrContact,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<PublicKey, anyhow::Error> {
+    match public_key_or_contact {
+        PublicKeyOrContact::PublicKey(public_key) => Ok(public_key),
+        PublicKeyOrContact::Contact(name) => {
+            let contact = sdk.contacts_api().get_contact(&name)?;
+            contact
+                .public_key
+                .ok_or_else(|| anyhow::anyhow!("Contact '{}' does not have a public key", name))
+        },
+    }
+}
+
+/// Resolves a component address or name to a component address. Names are resolved to a wallet account first and then
+/// to a contact in the address book.
+pub fn resolve_component_address(
+    address_or_name: &ComponentAddressOrName,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<ComponentAddress, anyhow::Error> {
+    match address_or_name {
+        ComponentAddressOrName::ComponentAddress(address) => Ok(*address),
+        ComponentAddressOrName::Name(name) => {
+            if let Some(account) = sdk.accounts_api().get_account_by_name(name).optional()? {
+                return Ok(account.address.as_component_address().unwrap());
+            }
+            let contact = sdk.contacts_api().get_contact(name)?;
+            contact
+                .account_address()
+                .ok_or_else(|| anyhow::anyhow!("Contact '{}' does not have an address", name))
+        },
+    }
+}
+
+/// Converts an amount input into the smallest unit of the resource. Display amounts are converted using the decimals
+/// of the account's vault for the resource, falling back to the `DECIMALS` metadata of the resource substate.
+pub async fn resolve_amount(
+    field: &str,
+    input: &AmountInput,
+    account: Option<&Account>,
+    resource_address: &ResourceAddress,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<Amount, anyhow::Error> {
+    if let Some(amount) = input.as_raw_amount() {
+        return Ok(amount);
+    }
+
+    let vault_decimals = match account {
+        Some(account) => sdk
+            .accounts_api()
+            .get_vault_by_resource(&account.address, resource_address)
+            .optional()?
+            .and_then(|vault| vault.decimals),
+        None => None,
+    };
+
+    let decimals = match vault_decimals {
+        Some(decimals) => decimals,
+        None => {
+            let resource = sdk
+                .get_network_interface()
+                .query_substate(&SubstateId::Resource(*resource_address), None, false)
+                .await?
+                .substate
+                .into_substate_value()
+                .into_resource()
+                .ok_or_else(|| invalid_params(field, Some("resource substate is not a resource")))?;
+            resource.decimals().unwrap_or(0)
+        },
+    };
+
+    input.to_amount(decimals).map_err(|e| invalid_params(field, Some(e)))
+}
+
+/// Resolves the maximum fee of a request, returning the default fee if none is given
+pub async fn resolve_max_fee(
+    max_fee: Option<&AmountInput>,
+    account: Option<&Account>,
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+) -> Result<Amount, anyhow::Error> {
+    match max_fee {
+        Some(max_fee) => resolve_amount("max_fee", max_fee, account, &CONFIDENTIAL_TARI_RESOURCE_ADDRESS, sdk).await,
+        None => Ok(DEFAULT_FEE),
+    }
+}
+
 pub(super) fn invalid_params<T: Display>(field: &str, details: Option<T>) -> anyhow::Error {
     axum_jrpc::error::JsonRpcError::new(
         axum_jrpc::error::JsonRpcErrorReason::InvalidParams,
@@ -2,15 +2,23 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use tari_common_types::types::PublicKey;
-use tari_crypto::keys::PublicKey as PublicKeyTrait;
-use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_crypto::{keys::PublicKey as PublicKeyTrait, tari_utilities::SafePassword};
+use tari_dan_wallet_sdk::apis::{
+    jwt::JrpcPermission,
+    key_manager,
+    key_manager::{parse_seed_words, KeyManagerApiError},
+};
 use tari_wallet_daemon_client::types::{
     KeysCreateRequest,
     KeysCreateResponse,
+    KeysGetSeedWordsRequest,
+    KeysGetSeedWordsResponse,
     KeysListRequest,
     KeysListResponse,
     KeysSetActiveRequest,
     KeysSetActiveResponse,
+    KeysVerifySeedWordsRequest,
+    KeysVerifySeedWordsResponse,
 };
 
 use super::context::HandlerContext;
@@ -59,3 +67,47 @@ pub async fn handle_set_active(
         public_key: PublicKey::from_secret_key(&key.key),
     })
 }
+
+pub async fn handle_get_seed_words(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: KeysGetSeedWordsRequest,
+) -> Result<KeysGetSeedWordsResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let seed_words = sdk
+        .key_manager_api()
+        .get_seed_words(req.passphrase.map(SafePassword::from))?;
+    let seed_words = seed_words.join(" ");
+
+    Ok(KeysGetSeedWordsResponse {
+        seed_words: seed_words.reveal().split(' ').map(ToString::to_string).collect(),
+    })
+}
+
+pub async fn handle_verify_seed_words(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: KeysVerifySeedWordsRequest,
+) -> Result<KeysVerifySeedWordsResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let seed_words = parse_seed_words(&req.seed_words.join(" "));
+    match sdk
+        .key_manager_api()
+        .verify_seed_words(&seed_words, req.passphrase.map(SafePassword::from))
+    {
+        Ok(()) => Ok(KeysVerifySeedWordsResponse {
+            is_valid: true,
+            error: None,
+        }),
+        // Invalid words, an incorrect passphrase or words for a different wallet
+        Err(err @ (KeyManagerApiError::SeedWordsMismatch | KeyManagerApiError::KeyManagerError(_))) => {
+            Ok(KeysVerifySeedWordsResponse {
+                is_valid: false,
+                error: Some(err.to_string()),
+            })
+        },
+        Err(err) => Err(err.into()),
+    }
+}
@@ -2,18 +2,25 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 pub mod accounts;
+pub mod attestation;
 pub mod confidential;
+pub mod contacts;
 mod context;
 pub mod error;
+pub mod events;
 mod helpers;
 pub mod keys;
+pub mod network;
 pub mod nfts;
 pub mod rpc;
+pub mod schedules;
 pub mod settings;
 pub mod substates;
 pub mod templates;
 pub mod transaction;
+pub mod transaction_export;
 pub mod validator;
+pub mod wallet;
 pub mod webrtc;
 
 use std::future::Future;
@@ -0,0 +1,22 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::apis::jwt::JrpcPermission;
+use tari_wallet_daemon_client::types::{NetworkStatusRequest, NetworkStatusResponse};
+
+use crate::handlers::HandlerContext;
+
+pub async fn handle_status(
+    context: &HandlerContext,
+    token: Option<String>,
+    _req: NetworkStatusRequest,
+) -> Result<NetworkStatusResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let network_interface = sdk.get_network_interface();
+
+    Ok(NetworkStatusResponse {
+        active_endpoint: network_interface.get_endpoint().to_string(),
+        endpoints: network_interface.endpoint_status(),
+    })
+}
@@ -8,10 +8,7 @@ use log::info;
 use tari_common_types::types::PublicKey;
 use tari_crypto::{keys::PublicKey as PK, ristretto::RistrettoSecretKey, tari_utilities::ByteArray};
 use tari_dan_common_types::SubstateRequirement;
-use tari_dan_wallet_sdk::{
-    apis::{jwt::JrpcPermission, key_manager},
-    models::Account,
-};
+use tari_dan_wallet_sdk::{apis::jwt::JrpcPermission, models::Account};
 use tari_engine_types::{instruction::Instruction, substate::SubstateId};
 use tari_template_builtin::ACCOUNT_NFT_TEMPLATE_ADDRESS;
 use tari_template_lib::{
@@ -30,7 +27,10 @@ use tari_wallet_daemon_client::types::{
 };
 use tokio::sync::broadcast;
 
-use super::{context::HandlerContext, helpers::get_account_or_default};
+use super::{
+    context::HandlerContext,
+    helpers::{check_can_sign, get_account_or_default},
+};
 use crate::{
     handlers::helpers::get_account,
     services::{TransactionFinalizedEvent, WalletEvent},
@@ -85,9 +85,9 @@ pub async fn handle_mint_account_nft(
     sdk.jwt_api().check_auth(token.clone(), &[JrpcPermission::Admin])?;
 
     let account = get_account(&req.account, &sdk.accounts_api())?;
+    check_can_sign(&account)?;
 
-    let signing_key_index = account.key_index;
-    let signing_key = key_manager_api.derive_key(key_manager::TRANSACTION_BRANCH, signing_key_index)?;
+    let signing_key = key_manager_api.derive_key(account.key_branch(), account.key_index)?;
 
     let owner_pk = PublicKey::from_secret_key(&signing_key.key);
     let owner_token =
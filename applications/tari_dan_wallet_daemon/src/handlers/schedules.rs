@@ -0,0 +1,72 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use anyhow::anyhow;
+use tari_dan_wallet_sdk::{apis::jwt::JrpcPermission, models::ScheduledTransactionStatus};
+use tari_wallet_daemon_client::types::{
+    SchedulesCancelRequest,
+    SchedulesCancelResponse,
+    SchedulesCreateRequest,
+    SchedulesCreateResponse,
+    SchedulesListRequest,
+    SchedulesListResponse,
+};
+
+use super::context::HandlerContext;
+use crate::{
+    handlers::helpers::{check_can_sign, get_account_or_default},
+    DEFAULT_FEE,
+};
+
+pub async fn handle_create(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: SchedulesCreateRequest,
+) -> Result<SchedulesCreateResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let fee_account = get_account_or_default(req.fee_account, &sdk.accounts_api())?;
+    check_can_sign(&fee_account)?;
+    let fee_account_address = fee_account
+        .address
+        .as_component_address()
+        .ok_or_else(|| anyhow!("Fee account address {} is not a component", fee_account.address))?;
+
+    let scheduled_transaction = sdk.scheduled_transactions_api().create(
+        req.name,
+        req.schedule,
+        req.instructions,
+        fee_account_address,
+        req.max_fee.unwrap_or(DEFAULT_FEE),
+        req.max_runs,
+        chrono::Utc::now().naive_utc(),
+    )?;
+    Ok(SchedulesCreateResponse { scheduled_transaction })
+}
+
+pub async fn handle_list(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: SchedulesListRequest,
+) -> Result<SchedulesListResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let status = if req.include_finished {
+        None
+    } else {
+        Some(ScheduledTransactionStatus::Active)
+    };
+    let scheduled_transactions = sdk.scheduled_transactions_api().get_all(status)?;
+    Ok(SchedulesListResponse { scheduled_transactions })
+}
+
+pub async fn handle_cancel(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: SchedulesCancelRequest,
+) -> Result<SchedulesCancelResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let scheduled_transaction = sdk.scheduled_transactions_api().cancel(req.id)?;
+    Ok(SchedulesCancelResponse { scheduled_transaction })
+}
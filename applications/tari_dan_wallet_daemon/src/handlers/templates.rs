@@ -1,10 +1,47 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use anyhow::anyhow;
+use log::*;
+use tari_dan_common_types::SubstateRequirement;
+use tari_dan_engine::{function_definitions::FlowFunctionDefinition, template::TemplateModuleLoader, wasm::WasmModule};
 use tari_dan_wallet_sdk::{apis::jwt::JrpcPermission, network::WalletNetworkInterface};
-use tari_wallet_daemon_client::types::{TemplatesGetRequest, TemplatesGetResponse};
+use tari_engine_types::{
+    commit_result::FinalizeResult,
+    published_template::{PublishedTemplate, TemplateType},
+    substate::SubstateId,
+    TemplateAddress,
+};
+use tari_transaction::Transaction;
+use tari_wallet_daemon_client::types::{
+    TemplateBinarySource,
+    TemplateVersionInfo,
+    TemplatesGetAbiRequest,
+    TemplatesGetAbiResponse,
+    TemplatesGetRequest,
+    TemplatesGetResponse,
+    TemplatesListVersionsRequest,
+    TemplatesListVersionsResponse,
+    TemplatesMigrateComponentRequest,
+    TemplatesMigrateComponentResponse,
+    TemplatesPublishRequest,
+    TemplatesPublishResponse,
+};
+use tokio::{fs, task};
 
-use crate::handlers::HandlerContext;
+use crate::{
+    config::TemplatePublishConfig,
+    handlers::{
+        helpers::{get_account_inputs, get_fee_account, invalid_params, wait_for_result},
+        HandlerContext,
+    },
+    DEFAULT_FEE,
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::templates";
+
+/// All WASM binaries start with these magic bytes
+const WASM_MAGIC: &[u8] = b"\0asm";
 
 pub async fn handle_get(
     context: &HandlerContext,
@@ -21,3 +58,353 @@ pub async fn handle_get(
 
     Ok(TemplatesGetResponse { template_definition })
 }
+
+pub async fn handle_get_abi(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TemplatesGetAbiRequest,
+) -> Result<TemplatesGetAbiResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::TemplatesRead])?;
+
+    let template = fetch_published_template(context, req.template_address).await?;
+    let binary_size = template.binary.len() as u64;
+
+    let abi = match template.template_type {
+        TemplateType::Wasm => {
+            // Compiling the WASM module is CPU intensive
+            let loaded = task::block_in_place(|| WasmModule::from_code(template.binary).load_template())
+                .map_err(|e| anyhow!("Failed to load template {}: {}", req.template_address, e))?;
+            loaded.template_def().clone()
+        },
+        TemplateType::Flow => parse_flow_definition(&template.binary)
+            .map_err(|e| anyhow!("Failed to load flow template {}: {}", req.template_address, e))?
+            .to_template_def(),
+    };
+
+    Ok(TemplatesGetAbiResponse {
+        template_address: req.template_address,
+        abi,
+        binary_size,
+    })
+}
+
+pub async fn handle_publish(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TemplatesPublishRequest,
+) -> Result<TemplatesPublishResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
+    if max_fee.is_negative() {
+        return Err(invalid_params("max_fee", Some("cannot be negative")));
+    }
+
+    let binary = load_template_binary(&context.config().template_publish, req.binary).await?;
+    match req.template_type {
+        TemplateType::Wasm => {
+            if !binary.starts_with(WASM_MAGIC) {
+                return Err(invalid_params("binary", Some("not a WASM binary")));
+            }
+        },
+        // The flow is fully validated by the engine when the transaction is executed
+        TemplateType::Flow => {
+            parse_flow_definition(&binary)
+                .map_err(|e| invalid_params("binary", Some(format!("not a valid flow definition: {}", e))))?;
+        },
+    }
+    let binary_size = binary.len() as u64;
+
+    let account = get_fee_account(context, req.fee_account, None).await?;
+    let inputs = get_account_inputs(&account, &sdk)?;
+    let account_address = account
+        .address
+        .as_component_address()
+        .ok_or_else(|| anyhow!("Invalid account address"))?;
+    let account_secret_key = sdk
+        .key_manager_api()
+        .derive_key(account.key_branch(), account.key_index)?;
+
+    let builder = Transaction::builder().fee_transaction_pay_from_component(account_address, max_fee);
+    let builder = match (req.template_type, req.previous_version) {
+        (TemplateType::Wasm, Some(previous_version)) => builder.publish_template_version(binary, previous_version),
+        (TemplateType::Wasm, None) => builder.publish_template(binary),
+        (TemplateType::Flow, previous_version) => {
+            builder.publish_flow_template(String::from_utf8(binary)?, previous_version)
+        },
+    };
+    let transaction = builder.sign(&account_secret_key.key).build();
+
+    let mut required_inputs = inputs.into_iter().map(Into::into).collect::<Vec<SubstateRequirement>>();
+    if let Some(previous_version) = req.previous_version {
+        required_inputs.push(SubstateRequirement::unversioned(SubstateId::Template(
+            previous_version.into(),
+        )));
+    }
+
+    if req.dry_run {
+        let transaction_id = *transaction.id();
+        let execute_result = context
+            .transaction_service()
+            .submit_dry_run_transaction(transaction, required_inputs)
+            .await?;
+        let finalize = execute_result.finalize;
+        let template_address = find_published_template_address(&finalize)
+            .ok_or_else(|| anyhow!("Template was not published in dry run: {}", reject_reason(&finalize)))?;
+        return Ok(TemplatesPublishResponse {
+            transaction_id,
+            template_address,
+            fee: finalize.fee_receipt.total_fees_paid,
+            result: finalize,
+        });
+    }
+
+    let mut events = context.notifier().subscribe();
+    let transaction_id = context
+        .transaction_service()
+        .submit_transaction(transaction, required_inputs)
+        .await?;
+
+    let finalized = wait_for_result(&mut events, transaction_id).await?;
+    if let Some(reason) = finalized.finalize.full_reject() {
+        return Err(anyhow!("Publish template transaction failed: {}", reason));
+    }
+    let template_address = find_published_template_address(&finalized.finalize)
+        .ok_or_else(|| anyhow!("Publish template transaction succeeded but no template was created"))?;
+
+    sdk.templates_api()
+        .add_published_template(&template_address, &account.address, transaction_id, binary_size)?;
+
+    info!(
+        target: LOG_TARGET,
+        "✅ Published template {} ({} bytes) in transaction {}. Fee: {}",
+        template_address,
+        binary_size,
+        transaction_id,
+        finalized.final_fee
+    );
+
+    Ok(TemplatesPublishResponse {
+        transaction_id,
+        template_address,
+        fee: finalized.final_fee,
+        result: finalized.finalize,
+    })
+}
+
+pub async fn handle_list_versions(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TemplatesListVersionsRequest,
+) -> Result<TemplatesListVersionsResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TemplatesRead])?;
+
+    // Templates only record the version they were published after, so the lineage is followed back to the first
+    // version
+    let mut versions = Vec::new();
+    let mut next = Some(req.template_address);
+    while let Some(template_address) = next {
+        let template = fetch_published_template(context, template_address).await?;
+        let previous_version = template.previous_version.map(|a| a.as_hash());
+        versions.push(TemplateVersionInfo {
+            template_address,
+            template_version: template.template_version,
+            previous_version,
+            author_public_key: template.author,
+        });
+        next = previous_version;
+    }
+    versions.reverse();
+
+    Ok(TemplatesListVersionsResponse { versions })
+}
+
+pub async fn handle_migrate_component(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TemplatesMigrateComponentRequest,
+) -> Result<TemplatesMigrateComponentResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk().clone();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+
+    let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
+    if max_fee.is_negative() {
+        return Err(invalid_params("max_fee", Some("cannot be negative")));
+    }
+
+    let component_id = SubstateId::Component(req.component_address);
+    let component = sdk
+        .get_network_interface()
+        .query_substate(&component_id, None, false)
+        .await?
+        .substate
+        .into_substate_value()
+        .into_component()
+        .ok_or_else(|| invalid_params("component_address", Some("substate is not a component")))?;
+
+    // The fee account key signs the transaction and must therefore be the component owner
+    let account = get_fee_account(context, req.fee_account, None).await?;
+    let inputs = get_account_inputs(&account, &sdk)?;
+    let account_address = account
+        .address
+        .as_component_address()
+        .ok_or_else(|| anyhow!("Invalid account address"))?;
+    let account_secret_key = sdk
+        .key_manager_api()
+        .derive_key(account.key_branch(), account.key_index)?;
+
+    let transaction = Transaction::builder()
+        .fee_transaction_pay_from_component(account_address, max_fee)
+        .migrate_component(req.component_address, req.template_address)
+        .sign(&account_secret_key.key)
+        .build();
+
+    // Both the current and new template versions are required to check the lineage
+    let required_inputs = inputs
+        .into_iter()
+        .map(Into::into)
+        .chain([
+            SubstateRequirement::unversioned(component_id),
+            SubstateRequirement::unversioned(SubstateId::Template(component.template_address.into())),
+            SubstateRequirement::unversioned(SubstateId::Template(req.template_address.into())),
+        ])
+        .collect();
+
+    if req.dry_run {
+        let transaction_id = *transaction.id();
+        let execute_result = context
+            .transaction_service()
+            .submit_dry_run_transaction(transaction, required_inputs)
+            .await?;
+        let finalize = execute_result.finalize;
+        return Ok(TemplatesMigrateComponentResponse {
+            transaction_id,
+            fee: finalize.fee_receipt.total_fees_paid,
+            result: finalize,
+        });
+    }
+
+    let mut events = context.notifier().subscribe();
+    let transaction_id = context
+        .transaction_service()
+        .submit_transaction(transaction, required_inputs)
+        .await?;
+
+    let finalized = wait_for_result(&mut events, transaction_id).await?;
+    if let Some(reason) = finalized.finalize.full_reject() {
+        return Err(anyhow!("Migrate component transaction failed: {}", reason));
+    }
+
+    info!(
+        target: LOG_TARGET,
+        "✅ Migrated component {} to template {} in transaction {}. Fee: {}",
+        req.component_address,
+        req.template_address,
+        transaction_id,
+        finalized.final_fee
+    );
+
+    Ok(TemplatesMigrateComponentResponse {
+        transaction_id,
+        fee: finalized.final_fee,
+        result: finalized.finalize,
+    })
+}
+
+async fn fetch_published_template(
+    context: &HandlerContext,
+    template_address: TemplateAddress,
+) -> Result<PublishedTemplate, anyhow::Error> {
+    let substate_id = SubstateId::Template(template_address.into());
+    let result = context
+        .wallet_sdk()
+        .get_network_interface()
+        .query_substate(&substate_id, None, false)
+        .await?;
+    result
+        .substate
+        .into_substate_value()
+        .into_published_template()
+        .ok_or_else(|| invalid_params("template_address", Some("substate is not a published template")))
+}
+
+async fn load_template_binary(
+    config: &TemplatePublishConfig,
+    source: TemplateBinarySource,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let max_size = config.template_binary_max_size_bytes;
+    let too_large = || invalid_params("binary", Some(format!("template binary exceeds {} bytes", max_size)));
+
+    match source {
+        TemplateBinarySource::Binary(binary) => {
+            if binary.len() > max_size {
+                return Err(too_large());
+            }
+            Ok(binary)
+        },
+        TemplateBinarySource::Path(path) => {
+            let binary_dir = config.binary_dir.as_ref().ok_or_else(|| {
+                invalid_params(
+                    "binary",
+                    Some("publishing from a path is disabled because template_publish.binary_dir is not configured"),
+                )
+            })?;
+            let unreadable = |e: std::io::Error| {
+                invalid_params("binary", Some(format!("unable to read '{}': {}", path.display(), e)))
+            };
+            let binary_dir = fs::canonicalize(binary_dir).await?;
+            // Canonicalizing resolves symlinks and ".." components so that the path cannot escape the binary dir
+            let file_path = fs::canonicalize(binary_dir.join(&path)).await.map_err(unreadable)?;
+            if !file_path.starts_with(&binary_dir) {
+                return Err(invalid_params(
+                    "binary",
+                    Some(format!("'{}' is outside of the template binary directory", path.display())),
+                ));
+            }
+            let metadata = fs::metadata(&file_path).await.map_err(unreadable)?;
+            if metadata.len() > max_size as u64 {
+                return Err(too_large());
+            }
+            fs::read(&file_path).await.map_err(unreadable)
+        },
+        TemplateBinarySource::Url(url) => {
+            let client = reqwest::Client::builder().timeout(config.download_timeout).build()?;
+            let mut resp = client.get(&url).send().await?.error_for_status()?;
+            if resp.content_length().is_some_and(|len| len > max_size as u64) {
+                return Err(too_large());
+            }
+            // The content length may be absent or wrong, so the limit is also enforced while reading the body
+            let mut binary = Vec::new();
+            while let Some(chunk) = resp.chunk().await? {
+                if binary.len() + chunk.len() > max_size {
+                    return Err(too_large());
+                }
+                binary.extend_from_slice(&chunk);
+            }
+            Ok(binary)
+        },
+    }
+}
+
+fn parse_flow_definition(flow_json: &[u8]) -> Result<FlowFunctionDefinition, serde_json::Error> {
+    serde_json::from_slice(flow_json)
+}
+
+fn find_published_template_address(finalize: &FinalizeResult) -> Option<TemplateAddress> {
+    finalize.result.accept()?.up_iter().find_map(|(id, _)| match id {
+        SubstateId::Template(address) => Some(address.as_hash()),
+        _ => None,
+    })
+}
+
+fn reject_reason(finalize: &FinalizeResult) -> String {
+    finalize
+        .full_reject()
+        .map(|r| r.to_string())
+        .unwrap_or_else(|| "unknown reason".to_string())
+}
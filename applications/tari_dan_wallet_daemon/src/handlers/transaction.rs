@@ -5,22 +5,46 @@ use std::{collections::HashSet, time::Duration};
 use anyhow::anyhow;
 use futures::{future, future::Either};
 use log::*;
+use tari_crypto::tari_utilities::hex::to_hex;
 use tari_dan_app_utilities::json_encoding;
 use tari_dan_common_types::{optional::Optional, Epoch, SubstateRequirement};
-use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_dan_wallet_sdk::{
+    apis::{jwt::JrpcPermission, key_manager},
+    models::{Account, TransactionStatus},
+    DanWalletSdk,
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_engine_types::{indexed_value::IndexedValue, instruction::Instruction, substate::SubstateId};
-use tari_template_lib::{args, args::Arg, models::Amount};
-use tari_transaction::Transaction;
+use tari_template_lib::{
+    args,
+    args::Arg,
+    models::{Amount, ComponentAddress},
+};
+use tari_transaction::{OfflineSignedTransaction, OfflineUnsignedTransaction, Transaction, TransactionCanonicalBytes};
 use tari_wallet_daemon_client::types::{
-    AccountGetRequest,
-    AccountGetResponse,
     CallInstructionRequest,
+    TransactionAcceptSponsoredRequest,
+    TransactionAcceptSponsoredResponse,
+    TransactionExportUnsignedRequest,
+    TransactionExportUnsignedResponse,
     TransactionGetAllRequest,
     TransactionGetAllResponse,
+    TransactionGetCanonicalBytesRequest,
+    TransactionGetCanonicalBytesResponse,
+    TransactionGetFailuresRequest,
+    TransactionGetFailuresResponse,
     TransactionGetRequest,
     TransactionGetResponse,
     TransactionGetResultRequest,
     TransactionGetResultResponse,
+    TransactionImportSignedRequest,
+    TransactionImportSignedResponse,
+    TransactionRetryRequest,
+    TransactionRetryResponse,
+    TransactionReviewSponsoredRequest,
+    TransactionReviewSponsoredResponse,
+    TransactionSetAnnotationRequest,
+    TransactionSetAnnotationResponse,
     TransactionSubmitDryRunRequest,
     TransactionSubmitDryRunResponse,
     TransactionSubmitRequest,
@@ -29,9 +53,17 @@ use tari_wallet_daemon_client::types::{
     TransactionWaitResultResponse,
 };
 use tokio::time;
+use tracing::{info_span, Instrument};
 
-use super::{accounts, context::HandlerContext};
-use crate::{handlers::HandlerError, services::WalletEvent};
+use super::{
+    context::HandlerContext,
+    helpers::{check_can_sign, get_fee_account, resolve_component_address},
+};
+use crate::{
+    handlers::HandlerError,
+    indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
+    services::{sign_with_wallet_keys, WalletEvent},
+};
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::transaction";
 
@@ -42,26 +74,16 @@ pub async fn handle_submit_instruction(
 ) -> Result<TransactionSubmitResponse, anyhow::Error> {
     let mut builder = Transaction::builder().with_instructions(req.instructions);
 
-    if let Some(dump_account) = req.dump_outputs_into {
-        let AccountGetResponse {
-            account: dump_account, ..
-        } = accounts::handle_get(context, token.clone(), AccountGetRequest {
-            name_or_address: dump_account,
-        })
-        .await?;
+    if let Some(ref dump_account) = req.dump_outputs_into {
+        // Outputs may be deposited into a wallet account or the account of a contact
+        let dump_account = resolve_component_address(dump_account, context.wallet_sdk())?;
 
-        builder = builder.put_last_instruction_output_on_workspace("bucket").call_method(
-            dump_account.address.as_component_address().unwrap(),
-            "deposit",
-            args![Variable("bucket")],
-        );
+        builder =
+            builder
+                .put_last_instruction_output_on_workspace("bucket")
+                .call_method(dump_account, "deposit", args![Variable("bucket")]);
     }
-    let AccountGetResponse {
-        account: fee_account, ..
-    } = accounts::handle_get(context, token.clone(), AccountGetRequest {
-        name_or_address: req.fee_account,
-    })
-    .await?;
+    let fee_account = get_fee_account(context, req.fee_account, None).await?;
 
     let transaction = builder
         .fee_transaction_pay_from_component(
@@ -142,15 +164,19 @@ pub async fn handle_submit(
             .proofs_set_transaction_hash(proof_id, *transaction.id())?;
     }
 
-    info!(
-        target: LOG_TARGET,
-        "Submitted transaction with hash {}",
-        transaction.hash()
-    );
+    let span = info_span!("transaction", transaction_id = %transaction.id());
+    span.in_scope(|| {
+        info!(
+            target: LOG_TARGET,
+            "Submitted transaction with hash {}",
+            transaction.hash()
+        )
+    });
 
     let transaction_id = context
         .transaction_service()
         .submit_transaction(transaction, autofill_inputs)
+        .instrument(span)
         .await?;
 
     Ok(TransactionSubmitResponse { transaction_id })
@@ -193,14 +219,18 @@ pub async fn handle_submit_dry_run(
             .proofs_set_transaction_hash(proof_id, *transaction.id())?;
     }
 
-    info!(
-        target: LOG_TARGET,
-        "Submitted transaction with hash {}",
-        transaction.hash()
-    );
+    let span = info_span!("transaction", transaction_id = %transaction.id(), dry_run = true);
+    span.in_scope(|| {
+        info!(
+            target: LOG_TARGET,
+            "Submitted transaction with hash {}",
+            transaction.hash()
+        )
+    });
     let exec_result = context
         .transaction_service()
-        .submit_dry_run_transaction(transaction, autofill_inputs.clone())
+        .submit_dry_run_transaction_with_overrides(transaction, autofill_inputs, req.substate_overrides)
+        .instrument(span)
         .await?;
 
     let json_result = json_encoding::encode_finalize_result_into_json(&exec_result.finalize)?;
@@ -227,15 +257,39 @@ pub async fn handle_get(
         .get(req.transaction_id)
         .optional()?
         .ok_or(HandlerError::NotFound)?;
+    let annotation = context
+        .wallet_sdk()
+        .metadata_sync_api()
+        .get_transaction_annotation(&req.transaction_id)?;
 
     Ok(TransactionGetResponse {
         transaction: transaction.transaction,
         result: transaction.finalize,
         status: transaction.status,
         last_update_time: transaction.last_update_time,
+        annotation,
     })
 }
 
+/// Attaches a note to a transaction. Notes are included in the wallet metadata that is synced between devices.
+pub async fn handle_set_annotation(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionSetAnnotationRequest,
+) -> Result<TransactionSetAnnotationResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::TransactionGet])?;
+    // Ensure that the transaction exists
+    sdk.transaction_api()
+        .get(req.transaction_id)
+        .optional()?
+        .ok_or(HandlerError::NotFound)?;
+    let annotation = req.annotation.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+    sdk.metadata_sync_api()
+        .set_transaction_annotation(&req.transaction_id, annotation)?;
+    Ok(TransactionSetAnnotationResponse {})
+}
+
 pub async fn handle_get_all(
     context: &HandlerContext,
     token: Option<String>,
@@ -257,6 +311,292 @@ pub async fn handle_get_all(
     })
 }
 
+pub async fn handle_get_failures(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionGetFailuresRequest,
+) -> Result<TransactionGetFailuresResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    let failures = context
+        .wallet_sdk()
+        .transaction_api()
+        .get_failures(req.include_retried)?;
+    Ok(TransactionGetFailuresResponse { failures })
+}
+
+/// Resubmits a failed transaction with its inputs re-detected at their latest versions. The new transaction is signed
+/// with the same keys that signed the failed transaction.
+pub async fn handle_retry(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionRetryRequest,
+) -> Result<TransactionRetryResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let transaction_api = sdk.transaction_api();
+    let rebuilt = transaction_api.rebuild_failed_transaction(req.transaction_id).await?;
+    let transaction = sign_with_wallet_keys(sdk, rebuilt.unsigned_transaction, &rebuilt.signers)
+        .map_err(|e| anyhow!("Cannot retry transaction {}: {}", req.transaction_id, e))?;
+
+    let span = info_span!("transaction", transaction_id = %transaction.id(), retry_of = %req.transaction_id);
+    span.in_scope(|| {
+        info!(
+            target: LOG_TARGET,
+            "Retrying failed transaction {} as {}",
+            req.transaction_id,
+            transaction.id()
+        )
+    });
+
+    let transaction_id = context
+        .transaction_service()
+        .submit_transaction_with_opts(transaction, rebuilt.required_substates, rebuilt.new_account_info)
+        .instrument(span)
+        .await?;
+    transaction_api.mark_failure_retried(req.transaction_id, transaction_id)?;
+
+    Ok(TransactionRetryResponse { transaction_id })
+}
+
+/// Returns what the user signs when accepting a sponsored transaction i.e. a transaction built by a sponsor (e.g. a
+/// dapp) whose fee instructions pay from the sponsor component.
+pub async fn handle_review_sponsored(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionReviewSponsoredRequest,
+) -> Result<TransactionReviewSponsoredResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let involved_accounts = verify_sponsored_transaction(sdk, &req.transaction, &req.sponsor)?;
+
+    Ok(TransactionReviewSponsoredResponse {
+        sponsor: req.sponsor,
+        max_fee: req.transaction.declared_max_fee(),
+        existing_signers: req
+            .transaction
+            .signatures()
+            .iter()
+            .map(|sig| sig.public_key().clone())
+            .collect(),
+        involved_accounts: involved_accounts
+            .iter()
+            .filter_map(|account| account.address.as_component_address())
+            .collect(),
+        fee_instructions: req.transaction.fee_instructions().to_vec(),
+        instructions: req.transaction.instructions().to_vec(),
+    })
+}
+
+/// Signs a sponsored transaction with the owner keys of the wallet accounts that it uses and submits it. The
+/// transaction is otherwise submitted as is, so that the signatures of the sponsor remain valid.
+pub async fn handle_accept_sponsored(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionAcceptSponsoredRequest,
+) -> Result<TransactionAcceptSponsoredResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let involved_accounts = verify_sponsored_transaction(sdk, &req.transaction, &req.sponsor)?;
+
+    let key_api = sdk.key_manager_api();
+    let signing_keys = match req.signing_key_index {
+        Some(index) => vec![key_api.derive_key(key_manager::TRANSACTION_BRANCH, index)?],
+        None if involved_accounts.is_empty() => {
+            vec![key_api.get_key_or_active(key_manager::TRANSACTION_BRANCH, None)?.1]
+        },
+        None => {
+            let mut keys = Vec::with_capacity(involved_accounts.len());
+            for account in &involved_accounts {
+                check_can_sign(account)?;
+                keys.push(key_api.derive_key(account.key_branch(), account.key_index)?);
+            }
+            keys
+        },
+    };
+    let transaction = signing_keys
+        .iter()
+        .fold(req.transaction, |transaction, key| transaction.sign(&key.key));
+
+    let span = info_span!("transaction", transaction_id = %transaction.id(), sponsor = %req.sponsor);
+    span.in_scope(|| {
+        info!(
+            target: LOG_TARGET,
+            "Accepted sponsored transaction {} with {} signature(s)",
+            transaction.id(),
+            signing_keys.len()
+        )
+    });
+
+    let transaction_id = context
+        .transaction_service()
+        .submit_transaction(transaction, vec![])
+        .instrument(span)
+        .await?;
+
+    Ok(TransactionAcceptSponsoredResponse { transaction_id })
+}
+
+/// Exports a transaction for signing on an offline machine. The inputs are resolved to their current versions here,
+/// since the offline machine cannot access the network.
+pub async fn handle_export_unsigned(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionExportUnsignedRequest,
+) -> Result<TransactionExportUnsignedResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let signer = sdk
+        .key_manager_api()
+        .get_public_key(key_manager::TRANSACTION_BRANCH, req.signing_key_index)?;
+
+    let mut transaction = req.transaction;
+    if req.detect_inputs {
+        let mut substates = get_referenced_substate_addresses(&transaction.instructions)?;
+        substates.extend(get_referenced_substate_addresses(&transaction.fee_instructions)?);
+        let substates = substates.into_iter().collect::<Vec<_>>();
+        let resolved_inputs = sdk.substate_api().locate_dependent_substates(&substates).await?;
+        transaction = Transaction::builder()
+            .with_unsigned_transaction(transaction)
+            .with_inputs(resolved_inputs)
+            .build_unsigned_transaction();
+    }
+    if transaction.has_inputs_without_version() {
+        return Err(anyhow!(
+            "Exported transactions must only have versioned inputs because the inputs cannot be resolved offline"
+        ));
+    }
+
+    info!(
+        target: LOG_TARGET,
+        "Exporting unsigned transaction with {} input(s) for signing by {}",
+        transaction.inputs().len(),
+        signer
+    );
+
+    let required_signers = vec![signer];
+    let blob = OfflineUnsignedTransaction::new(transaction.clone(), required_signers.clone()).to_blob();
+    Ok(TransactionExportUnsignedResponse {
+        blob,
+        transaction,
+        required_signers,
+    })
+}
+
+/// Verifies and submits a transaction that was exported with `transactions.export_unsigned` and signed on an offline
+/// machine
+pub async fn handle_import_signed(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionImportSignedRequest,
+) -> Result<TransactionImportSignedResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionSend(None)])?;
+    let transaction = OfflineSignedTransaction::from_blob(&req.blob)?.into_transaction();
+    sdk.transaction_api()
+        .verify_offline_signed_transaction(&transaction)
+        .await?;
+
+    let span = info_span!("transaction", transaction_id = %transaction.id(), offline_signed = true);
+    span.in_scope(|| {
+        info!(
+            target: LOG_TARGET,
+            "Importing offline signed transaction {} with {} signature(s)",
+            transaction.id(),
+            transaction.signatures().len()
+        )
+    });
+
+    let transaction_id = context
+        .transaction_service()
+        .submit_transaction(transaction, vec![])
+        .instrument(span)
+        .await?;
+
+    Ok(TransactionImportSignedResponse { transaction_id })
+}
+
+pub async fn handle_get_canonical_bytes(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: TransactionGetCanonicalBytesRequest,
+) -> Result<TransactionGetCanonicalBytesResponse, anyhow::Error> {
+    context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionGet])?;
+    let transaction = Transaction::new(req.transaction, req.signatures);
+    let canonical = transaction.canonical_bytes();
+
+    Ok(TransactionGetCanonicalBytesResponse {
+        signature_fields: to_hex(canonical.signature_fields()),
+        signature_domain_separation_tag: TransactionCanonicalBytes::signature_domain_separation_tag(),
+        signature_message: to_hex(&canonical.signature_message()),
+        transaction_id_preimage: to_hex(canonical.transaction_id_preimage()),
+        transaction_id_domain_separation_tag: TransactionCanonicalBytes::transaction_id_domain_separation_tag(),
+        transaction_id: *transaction.id(),
+    })
+}
+
+/// Checks that the fees of a sponsored transaction are only paid by the sponsor and that the signatures already on the
+/// transaction are valid. Returns the wallet accounts that the instructions use.
+fn verify_sponsored_transaction(
+    sdk: &DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>,
+    transaction: &Transaction,
+    sponsor: &ComponentAddress,
+) -> Result<Vec<Account>, anyhow::Error> {
+    if transaction.fee_instructions().is_empty() {
+        return Err(anyhow!("Sponsored transaction has no fee instructions"));
+    }
+
+    for instruction in transaction.fee_instructions() {
+        match instruction {
+            Instruction::CallMethod { component_address, .. } if component_address == sponsor => {},
+            Instruction::CallMethod { component_address, .. } => {
+                return Err(anyhow!(
+                    "Sponsored transaction fee instructions call component {} that is not the sponsor {}",
+                    component_address,
+                    sponsor
+                ));
+            },
+            Instruction::CallFunction { .. } => {
+                return Err(anyhow!("Sponsored transaction fee instructions may not call template functions"));
+            },
+            _ => {},
+        }
+    }
+
+    let accounts_api = sdk.accounts_api();
+    for substate_id in get_referenced_substate_addresses(transaction.fee_instructions())? {
+        if accounts_api.has_account(&substate_id)? {
+            return Err(anyhow!("Sponsored transaction fee instructions use wallet account {}", substate_id));
+        }
+    }
+
+    if !transaction
+        .signatures()
+        .iter()
+        .all(|sig| sig.verify(transaction.unsigned_transaction()))
+    {
+        return Err(anyhow!("Sponsored transaction has an invalid signature"));
+    }
+
+    let mut involved_accounts = Vec::new();
+    for substate_id in get_referenced_substate_addresses(transaction.instructions())? {
+        if accounts_api.has_account(&substate_id)? {
+            involved_accounts.push(accounts_api.get_account_by_address(&substate_id)?);
+        }
+    }
+    Ok(involved_accounts)
+}
+
 pub async fn handle_get_result(
     context: &HandlerContext,
     token: Option<String>,
@@ -317,6 +657,17 @@ pub async fn handle_wait_result(
         });
     }
 
+    if transaction.status == TransactionStatus::Expired {
+        return Ok(TransactionWaitResultResponse {
+            transaction_id: req.transaction_id,
+            result: None,
+            status: transaction.status,
+            final_fee: Amount::zero(),
+            timed_out: false,
+            json_result: None,
+        });
+    }
+
     let mut timeout = match req.timeout_secs {
         Some(timeout) => Either::Left(Box::pin(time::sleep(Duration::from_secs(timeout)))),
         None => Either::Right(future::pending()),
@@ -346,6 +697,16 @@ pub async fn handle_wait_result(
                     json_result: Some(json_result),
                 });
             },
+            Some(WalletEvent::TransactionExpired(event)) if event.transaction_id == req.transaction_id => {
+                return Ok(TransactionWaitResultResponse {
+                    transaction_id: req.transaction_id,
+                    result: None,
+                    status: TransactionStatus::Expired,
+                    final_fee: Amount::zero(),
+                    timed_out: false,
+                    json_result: None,
+                });
+            },
             Some(WalletEvent::TransactionInvalid(event)) if event.transaction_id == req.transaction_id => {
                 return Ok(TransactionWaitResultResponse {
                     transaction_id: req.transaction_id,
@@ -371,7 +732,7 @@ pub async fn handle_wait_result(
     }
 }
 
-fn get_referenced_substate_addresses(instructions: &[Instruction]) -> anyhow::Result<HashSet<SubstateId>> {
+pub(crate) fn get_referenced_substate_addresses(instructions: &[Instruction]) -> anyhow::Result<HashSet<SubstateId>> {
     let mut substates = HashSet::new();
     for instruction in instructions {
         match instruction {
@@ -396,6 +757,19 @@ fn get_referenced_substate_addresses(instructions: &[Instruction]) -> anyhow::Re
                     }
                 }
             },
+            Instruction::PublishTemplate {
+                previous_version: Some(previous_version),
+                ..
+            } => {
+                substates.insert(SubstateId::Template((*previous_version).into()));
+            },
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => {
+                substates.insert(SubstateId::Component(*component_address));
+                substates.insert(SubstateId::Template((*template_address).into()));
+            },
             _ => {},
         }
     }
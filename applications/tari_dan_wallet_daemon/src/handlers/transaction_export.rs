@@ -0,0 +1,172 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{io, sync::Arc};
+
+use axum::{
+    body::StreamBody,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use axum_jrpc::{
+    error::{JsonRpcError, JsonRpcErrorReason},
+    JsonRpcExtractor,
+    JsonRpcResponse,
+};
+use futures::stream;
+use log::*;
+use tari_dan_wallet_sdk::{apis::jwt::JrpcPermission, models::WalletTransaction};
+use tari_wallet_daemon_client::types::{TransactionExportFormat, TransactionExportRecord, TransactionsExportRequest};
+use tokio::{sync::mpsc, task};
+
+use super::HandlerContext;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::transaction_export";
+
+/// Number of transactions loaded from the database at a time
+const EXPORT_BATCH_SIZE: u64 = 100;
+/// Number of rendered rows that may be buffered before the exporter waits for the client to catch up
+const EXPORT_BUFFER_SIZE: usize = 100;
+
+const CSV_HEADER: &str =
+    "transaction_id,status,outcome,reject_reason,final_fee,execution_time_ms,finalized_time_ms,last_update_time\n";
+
+pub async fn handle_export(context: Arc<HandlerContext>, value: JsonRpcExtractor, token: Option<String>) -> Response {
+    let answer_id = value.get_answer_id();
+    if let Err(e) = context
+        .wallet_sdk()
+        .jwt_api()
+        .check_auth(token, &[JrpcPermission::TransactionGet])
+    {
+        context.record_auth_failure();
+        return JsonRpcResponse::error(
+            answer_id,
+            JsonRpcError::new(
+                JsonRpcErrorReason::ApplicationError(401),
+                format!("Not authorized: {e}"),
+                serde_json::Value::Null,
+            ),
+        )
+        .into_response();
+    }
+    let req = match value.parse_params::<TransactionsExportRequest>() {
+        Ok(req) => req,
+        Err(resp) => return resp.into_response(),
+    };
+
+    let format = req.format;
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFER_SIZE);
+    // Reading from the database is blocking so we render the rows on a blocking thread and stream them to the client
+    task::spawn_blocking(move || write_transactions(&context, format, &sender));
+
+    let body = StreamBody::new(stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    }));
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"transactions.{}\"", format.file_extension()),
+            ),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+fn write_transactions(
+    context: &HandlerContext,
+    format: TransactionExportFormat,
+    sender: &mpsc::Sender<Result<String, io::Error>>,
+) {
+    if format == TransactionExportFormat::Csv && sender.blocking_send(Ok(CSV_HEADER.to_string())).is_err() {
+        return;
+    }
+
+    let mut num_exported = 0usize;
+    for transaction in context
+        .wallet_sdk()
+        .transaction_api()
+        .transactions_iter(EXPORT_BATCH_SIZE)
+    {
+        let chunk = transaction
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+            .and_then(|transaction| render_record(format, &to_export_record(transaction)));
+        let is_err = chunk.is_err();
+        if let Err(e) = &chunk {
+            error!(target: LOG_TARGET, "Transaction export failed after {} transaction(s): {}", num_exported, e);
+        }
+        if sender.blocking_send(chunk).is_err() {
+            warn!(
+                target: LOG_TARGET,
+                "Transaction export cancelled by the client after {} transaction(s)", num_exported
+            );
+            return;
+        }
+        // The error aborts the response body, so there is no point in continuing
+        if is_err {
+            return;
+        }
+        num_exported += 1;
+    }
+
+    info!(target: LOG_TARGET, "📤️ Exported {} transaction(s)", num_exported);
+}
+
+fn to_export_record(transaction: WalletTransaction) -> TransactionExportRecord {
+    TransactionExportRecord {
+        transaction_id: *transaction.transaction.id(),
+        status: transaction.status,
+        final_fee: transaction.final_fee,
+        finalize: transaction.finalize,
+        execution_time_ms: transaction.execution_time.map(|t| t.as_millis() as u64),
+        finalized_time_ms: transaction.finalized_time.map(|t| t.as_millis() as u64),
+        last_update_time: transaction.last_update_time,
+    }
+}
+
+fn render_record(format: TransactionExportFormat, record: &TransactionExportRecord) -> Result<String, io::Error> {
+    match format {
+        TransactionExportFormat::Csv => Ok(to_csv_row(record)),
+        TransactionExportFormat::JsonLines => {
+            let mut line = serde_json::to_string(record)?;
+            line.push('\n');
+            Ok(line)
+        },
+    }
+}
+
+fn to_csv_row(record: &TransactionExportRecord) -> String {
+    let (outcome, reject_reason) = match &record.finalize {
+        Some(finalize) if finalize.is_full_accept() => ("Accept", None),
+        Some(finalize) if finalize.is_fee_only() => ("AcceptFeeRejectRest", finalize.full_reject()),
+        Some(finalize) => ("Reject", finalize.full_reject()),
+        None => ("", None),
+    };
+
+    let fields = [
+        record.transaction_id.to_string(),
+        record.status.to_string(),
+        outcome.to_string(),
+        reject_reason.map(|r| r.to_string()).unwrap_or_default(),
+        record.final_fee.map(|f| f.to_string()).unwrap_or_default(),
+        record.execution_time_ms.map(|t| t.to_string()).unwrap_or_default(),
+        record.finalized_time_ms.map(|t| t.to_string()).unwrap_or_default(),
+        record.last_update_time.to_string(),
+    ];
+
+    let mut row = fields.iter().map(|f| escape_csv_field(f)).collect::<Vec<_>>().join(",");
+    row.push('\n');
+    row
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -3,7 +3,7 @@
 
 use anyhow::anyhow;
 use log::*;
-use tari_dan_wallet_sdk::apis::{jwt::JrpcPermission, key_manager};
+use tari_dan_wallet_sdk::apis::jwt::JrpcPermission;
 use tari_engine_types::instruction::Instruction;
 use tari_template_lib::args;
 use tari_transaction::Transaction;
@@ -14,12 +14,9 @@ use tari_wallet_daemon_client::types::{
     GetValidatorFeesResponse,
 };
 
-use crate::{
-    handlers::{
-        helpers::{get_account_with_inputs, wait_for_result},
-        HandlerContext,
-    },
-    DEFAULT_FEE,
+use crate::handlers::{
+    helpers::{get_account_with_inputs, resolve_max_fee, wait_for_result},
+    HandlerContext,
 };
 
 const LOG_TARGET: &str = "tari::dan::walletd::handlers::validator";
@@ -47,7 +44,7 @@ pub async fn handle_claim_validator_fees(
     let account_address = account.address.as_component_address().unwrap();
 
     // build the transaction
-    let max_fee = req.max_fee.unwrap_or(DEFAULT_FEE);
+    let max_fee = resolve_max_fee(req.max_fee.as_ref(), Some(&account), &sdk).await?;
     fee_instructions.extend([
         Instruction::ClaimValidatorFees {
             validator_public_key: req.validator_public_key.clone(),
@@ -72,7 +69,7 @@ pub async fn handle_claim_validator_fees(
     // In future we should change it to allow a separate public key
     let account_secret_key = sdk
         .key_manager_api()
-        .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        .derive_key(account.key_branch(), account.key_index)?;
 
     let transaction = Transaction::builder()
         .with_fee_instructions(fee_instructions)
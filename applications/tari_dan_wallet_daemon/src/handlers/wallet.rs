@@ -0,0 +1,60 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_wallet_sdk::apis::jwt::JrpcPermission;
+use tari_utilities::SafePassword;
+use tari_wallet_daemon_client::types::{
+    WalletGcRequest,
+    WalletGcResponse,
+    WalletLockRequest,
+    WalletLockResponse,
+    WalletUnlockRequest,
+    WalletUnlockResponse,
+};
+
+use crate::handlers::HandlerContext;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::handlers::wallet";
+
+pub async fn handle_lock(
+    context: &HandlerContext,
+    token: Option<String>,
+    _req: WalletLockRequest,
+) -> Result<WalletLockResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    sdk.lock();
+    info!(target: LOG_TARGET, "🔒 Wallet locked by request");
+    Ok(WalletLockResponse {})
+}
+
+pub async fn handle_unlock(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: WalletUnlockRequest,
+) -> Result<WalletUnlockResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    sdk.unlock(&SafePassword::from(req.password))?;
+    Ok(WalletUnlockResponse {})
+}
+
+/// Removes unreachable substate records and orphaned confidential proofs from the wallet store. If dry_run is set, the
+/// records that would be removed are returned without removing them.
+pub async fn handle_gc(
+    context: &HandlerContext,
+    token: Option<String>,
+    req: WalletGcRequest,
+) -> Result<WalletGcResponse, anyhow::Error> {
+    let sdk = context.wallet_sdk();
+    sdk.jwt_api().check_auth(token, &[JrpcPermission::Admin])?;
+    let report = sdk
+        .garbage_collection_api()
+        .collect_garbage(context.config().garbage_collection.unbound_proof_min_age, req.dry_run)?;
+    Ok(WalletGcResponse {
+        is_dry_run: report.is_dry_run,
+        removed_substates: report.removed_substates,
+        removed_proofs: report.removed_proofs,
+    })
+}
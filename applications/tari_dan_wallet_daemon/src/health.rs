@@ -0,0 +1,110 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    sync::Arc,
+    time::{Duration, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::Extension,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use log::*;
+use serde::Serialize;
+use tari_dan_wallet_sdk::{models::TransactionStatus, network::WalletNetworkInterface};
+use tokio::time;
+
+use crate::handlers::HandlerContext;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::health";
+
+/// The maximum time the health check waits for the indexer to respond
+const INDEXER_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthStatus {
+    /// True if the indexer is reachable
+    pub is_healthy: bool,
+    /// None if no request has been made to the indexer yet
+    pub indexer_connected: Option<bool>,
+    /// Unix timestamp in seconds of the last successful request to the indexer
+    pub last_successful_network_query: Option<u64>,
+    /// The number of submitted transactions that are not yet finalized. None if the wallet is locked.
+    pub pending_transactions: Option<usize>,
+    pub is_locked: bool,
+}
+
+impl HealthStatus {
+    /// Collects the health status from the last known state without making any network requests
+    pub fn collect(context: &HandlerContext) -> Self {
+        let sdk = context.wallet_sdk();
+        let connection = sdk.get_network_interface().connection_status();
+        let is_locked = sdk.is_locked();
+        let pending_transactions = if is_locked {
+            None
+        } else {
+            sdk.transaction_api()
+                .fetch_all(Some(TransactionStatus::Pending), None)
+                .map(|transactions| transactions.len())
+                .inspect_err(|err| warn!(target: LOG_TARGET, "Failed to count pending transactions: {}", err))
+                .ok()
+        };
+
+        Self {
+            is_healthy: connection.is_connected == Some(true),
+            indexer_connected: connection.is_connected,
+            last_successful_network_query: connection
+                .last_successful_query_at
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs()),
+            pending_transactions,
+            is_locked,
+        }
+    }
+}
+
+/// Returns the health status of the wallet daemon. The indexer is queried so that the status is current even if the
+/// wallet has been idle. Responds with 503 if the indexer is not reachable.
+pub async fn handle_health(Extension(context): Extension<Arc<HandlerContext>>) -> Response {
+    let probe = time::timeout(
+        INDEXER_PROBE_TIMEOUT,
+        context.wallet_sdk().get_network_interface().get_current_epoch(),
+    )
+    .await;
+
+    let mut status = HealthStatus::collect(&context);
+    if probe.is_err() {
+        warn!(
+            target: LOG_TARGET,
+            "Indexer did not respond to the health check within {:.2?}", INDEXER_PROBE_TIMEOUT
+        );
+        status.indexer_connected = Some(false);
+        status.is_healthy = false;
+    }
+
+    let code = if status.is_healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(status)).into_response()
+}
+
+#[cfg(feature = "metrics")]
+pub async fn handle_metrics(Extension(context): Extension<Arc<HandlerContext>>) -> Response {
+    let status = HealthStatus::collect(&context);
+    match context.metrics().encode(&status) {
+        Ok(s) => s.into_response(),
+        Err(e) => {
+            error!(target: LOG_TARGET, "Failed to encode metrics: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to encode metrics: {e}"),
+            )
+                .into_response()
+        },
+    }
+}
@@ -0,0 +1,243 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::*;
+use reqwest::Url;
+use tari_shutdown::ShutdownSignal;
+use tari_wallet_daemon_client::types::IndexerEndpointStatus;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::indexer_jrpc_impl::IndexerJsonRpcNetworkInterface;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::indexer_connection_manager";
+
+/// A healthy endpoint only replaces the active endpoint if its latency is lower by at least this much. This prevents
+/// switching back and forth between endpoints with similar latencies.
+const LATENCY_SWITCH_THRESHOLD: Duration = Duration::from_millis(25);
+
+/// Tracks the health of the configured indexer endpoints and selects the endpoint that requests are sent to. The first
+/// endpoint is the primary endpoint, which is used until another endpoint is known to be better.
+#[derive(Debug, Clone)]
+pub struct IndexerConnectionManager {
+    endpoints: Vec<IndexerEndpoint>,
+    active: usize,
+}
+
+#[derive(Debug, Clone)]
+struct IndexerEndpoint {
+    url: Url,
+    /// None if no request has been made to the endpoint yet
+    is_healthy: Option<bool>,
+    /// The round trip time of the last health check
+    latency: Option<Duration>,
+    last_checked_at: Option<SystemTime>,
+    last_error: Option<String>,
+}
+
+impl IndexerEndpoint {
+    fn new(url: Url) -> Self {
+        Self {
+            url,
+            is_healthy: None,
+            latency: None,
+            last_checked_at: None,
+            last_error: None,
+        }
+    }
+}
+
+impl IndexerConnectionManager {
+    pub fn new(primary: Url) -> Self {
+        Self {
+            endpoints: vec![IndexerEndpoint::new(primary)],
+            active: 0,
+        }
+    }
+
+    /// Adds an endpoint that requests fail over to. Endpoints that are already known are ignored.
+    pub fn add_fallback(&mut self, url: Url) {
+        if self.endpoints.iter().any(|e| e.url == url) {
+            return;
+        }
+        self.endpoints.push(IndexerEndpoint::new(url));
+    }
+
+    /// Replaces the primary endpoint and makes it the active endpoint
+    pub fn set_primary(&mut self, url: Url) {
+        self.endpoints.remove(0);
+        self.endpoints.retain(|e| e.url != url);
+        self.endpoints.insert(0, IndexerEndpoint::new(url));
+        self.active = 0;
+    }
+
+    pub fn active_url(&self) -> &Url {
+        &self.endpoints[self.active].url
+    }
+
+    pub fn num_endpoints(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn urls(&self) -> Vec<Url> {
+        self.endpoints.iter().map(|e| e.url.clone()).collect()
+    }
+
+    /// Returns the endpoints in the order that a request should try them. The active endpoint is always first.
+    pub fn failover_order(&self) -> Vec<Url> {
+        let mut order = vec![self.active];
+        order.extend(self.ranked().into_iter().filter(|i| *i != self.active));
+        order.into_iter().map(|i| self.endpoints[i].url.clone()).collect()
+    }
+
+    /// Records that a request to the endpoint succeeded. If the endpoint is not active, it becomes the active
+    /// endpoint, since the request failed over to it.
+    pub fn record_request_success(&mut self, url: &Url) {
+        let Some(index) = self.index_of(url) else {
+            return;
+        };
+        let endpoint = &mut self.endpoints[index];
+        endpoint.is_healthy = Some(true);
+        endpoint.last_checked_at = Some(SystemTime::now());
+        endpoint.last_error = None;
+        if index != self.active {
+            self.set_active(index);
+        }
+    }
+
+    /// Records that a request to the endpoint could not be sent. If the endpoint is active, the best remaining
+    /// endpoint becomes active.
+    pub fn record_request_failure(&mut self, url: &Url, error: String) {
+        let Some(index) = self.index_of(url) else {
+            return;
+        };
+        self.set_unhealthy(index, error);
+        if index == self.active {
+            self.select_best();
+        }
+    }
+
+    /// Records the outcome of a health check. The active endpoint is not changed until [`Self::select_best`] is
+    /// called.
+    pub fn record_health_check(&mut self, url: &Url, result: Result<Duration, String>) {
+        let Some(index) = self.index_of(url) else {
+            return;
+        };
+        match result {
+            Ok(latency) => {
+                let endpoint = &mut self.endpoints[index];
+                endpoint.is_healthy = Some(true);
+                endpoint.latency = Some(latency);
+                endpoint.last_checked_at = Some(SystemTime::now());
+                endpoint.last_error = None;
+            },
+            Err(error) => self.set_unhealthy(index, error),
+        }
+    }
+
+    /// Makes the healthy endpoint with the lowest latency active. A healthy active endpoint is only replaced if
+    /// another endpoint is faster by at least [`LATENCY_SWITCH_THRESHOLD`].
+    pub fn select_best(&mut self) {
+        let Some(best) = self.ranked().into_iter().next() else {
+            return;
+        };
+        if best == self.active {
+            return;
+        }
+        let active = &self.endpoints[self.active];
+        if active.is_healthy == Some(true) {
+            let candidate = &self.endpoints[best];
+            let is_faster = match (active.latency, candidate.latency) {
+                (Some(active), Some(candidate)) => candidate + LATENCY_SWITCH_THRESHOLD < active,
+                _ => false,
+            };
+            if !is_faster {
+                return;
+            }
+        }
+        self.set_active(best);
+    }
+
+    pub fn status(&self) -> Vec<IndexerEndpointStatus> {
+        self.endpoints
+            .iter()
+            .enumerate()
+            .map(|(i, e)| IndexerEndpointStatus {
+                url: e.url.to_string(),
+                is_active: i == self.active,
+                is_healthy: e.is_healthy,
+                latency_ms: e.latency.map(|l| l.as_millis() as u64),
+                last_checked_at: e
+                    .last_checked_at
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs()),
+                last_error: e.last_error.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the endpoint indexes ordered by preference: healthy endpoints by latency, then endpoints that have not
+    /// been checked, then unhealthy endpoints. Endpoints with the same preference keep the configured order.
+    fn ranked(&self) -> Vec<usize> {
+        let mut indexes = (0..self.endpoints.len()).collect::<Vec<_>>();
+        indexes.sort_by_key(|i| {
+            let endpoint = &self.endpoints[*i];
+            match endpoint.is_healthy {
+                Some(true) => (0, endpoint.latency.unwrap_or(Duration::MAX)),
+                None => (1, Duration::ZERO),
+                Some(false) => (2, Duration::ZERO),
+            }
+        });
+        indexes
+    }
+
+    fn set_unhealthy(&mut self, index: usize, error: String) {
+        let endpoint = &mut self.endpoints[index];
+        endpoint.is_healthy = Some(false);
+        endpoint.last_checked_at = Some(SystemTime::now());
+        endpoint.last_error = Some(error);
+    }
+
+    fn set_active(&mut self, index: usize) {
+        info!(
+            target: LOG_TARGET,
+            "🔀 Switching indexer endpoint from {} to {}",
+            self.endpoints[self.active].url,
+            self.endpoints[index].url
+        );
+        self.active = index;
+    }
+
+    fn index_of(&self, url: &Url) -> Option<usize> {
+        self.endpoints.iter().position(|e| e.url == *url)
+    }
+}
+
+/// Periodically checks the indexer endpoints so that requests are sent to the healthy endpoint with the lowest latency
+pub async fn run_health_checks(
+    network_interface: IndexerJsonRpcNetworkInterface,
+    interval: Duration,
+    mut shutdown_signal: ShutdownSignal,
+) {
+    let mut check_interval = time::interval(interval);
+    check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = shutdown_signal.wait() => {
+                break;
+            }
+
+            _ = check_interval.tick() => {
+                network_interface.check_endpoints().await;
+                debug!(
+                    target: LOG_TARGET,
+                    "Checked {} indexer endpoint(s). Active endpoint: {}",
+                    network_interface.num_endpoints(),
+                    network_interface.get_endpoint()
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,17 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
 
 use axum::async_trait;
+use log::*;
 use reqwest::{IntoUrl, Url};
-use tari_dan_common_types::{optional::IsNotFoundError, substate_type::SubstateType, SubstateRequirement};
+use tari_dan_common_types::{optional::IsNotFoundError, substate_type::SubstateType, Epoch, SubstateRequirement};
 use tari_dan_wallet_sdk::network::{
     SubstateListItem,
     SubstateListResult,
@@ -14,12 +20,17 @@ use tari_dan_wallet_sdk::network::{
     TransactionQueryResult,
     WalletNetworkInterface,
 };
-use tari_engine_types::substate::SubstateId;
+use tari_engine_types::{
+    events::Event,
+    substate::{SubstateId, SubstateValue},
+};
 use tari_indexer_client::{
     error::IndexerClientError,
     json_rpc_client::IndexerJsonRpcClient,
     types::{
+        GetEventsRequest,
         GetSubstateRequest,
+        GetTemplateDefinitionRequest,
         GetTransactionResultRequest,
         IndexerTransactionFinalizedResult,
         ListSubstateItem,
@@ -29,36 +40,180 @@ use tari_indexer_client::{
 };
 use tari_template_lib::models::TemplateAddress;
 use tari_transaction::{Transaction, TransactionId};
+use tari_wallet_daemon_client::types::IndexerEndpointStatus;
+use tokio::time;
 use url::ParseError;
 
+use crate::{indexer_connection_manager::IndexerConnectionManager, trace_id};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::indexer_jrpc";
+
+/// The maximum time that an endpoint health check waits for a response
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug, Clone)]
 pub struct IndexerJsonRpcNetworkInterface {
-    indexer_jrpc_address: Arc<Mutex<Url>>,
+    connection_manager: Arc<Mutex<IndexerConnectionManager>>,
+    status: Arc<Mutex<IndexerConnectionStatus>>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IndexerConnectionStatus {
+    /// True if the indexer responded to the last request, None if no request has been made yet
+    pub is_connected: Option<bool>,
+    pub last_successful_query_at: Option<SystemTime>,
 }
 
 impl IndexerJsonRpcNetworkInterface {
     pub fn new<T: IntoUrl>(indexer_jrpc_address: T) -> Self {
         Self {
-            indexer_jrpc_address: Arc::new(Mutex::new(
+            connection_manager: Arc::new(Mutex::new(IndexerConnectionManager::new(
                 indexer_jrpc_address
                     .into_url()
                     .expect("Malformed indexer JSON-RPC address"),
-            )),
+            ))),
+            status: Arc::new(Mutex::new(IndexerConnectionStatus::default())),
         }
     }
 
-    fn get_client(&self) -> Result<IndexerJsonRpcClient, IndexerJrpcError> {
-        let client = IndexerJsonRpcClient::connect((*self.indexer_jrpc_address.lock().unwrap()).clone())?;
+    /// Adds endpoints that requests fail over to if the primary endpoint cannot be reached
+    pub fn with_fallback_endpoints<I: IntoIterator<Item = Url>>(self, endpoints: I) -> Self {
+        {
+            let mut connection_manager = self.connection_manager.lock().unwrap();
+            for endpoint in endpoints {
+                connection_manager.add_fallback(endpoint);
+            }
+        }
+        self
+    }
+
+    fn get_client(&self, endpoint: Url) -> Result<IndexerJsonRpcClient, IndexerJrpcError> {
+        let mut client = IndexerJsonRpcClient::connect(endpoint)?;
+        // Propagate the trace ID of the JSON-RPC request that is being handled, if any
+        if let Some(trace_id) = trace_id::current() {
+            client = client.with_trace_id(trace_id);
+        }
         Ok(client)
     }
 
+    /// Sets the primary endpoint, which is used for all requests until it fails
     pub fn set_endpoint(&mut self, endpoint: &str) -> Result<(), IndexerJrpcError> {
-        *self.indexer_jrpc_address.lock().unwrap() = Url::parse(endpoint)?;
+        self.connection_manager.lock().unwrap().set_primary(Url::parse(endpoint)?);
         Ok(())
     }
 
+    /// Returns the endpoint that requests are currently sent to
     pub fn get_endpoint(&self) -> Url {
-        (*self.indexer_jrpc_address.lock().unwrap()).clone()
+        self.connection_manager.lock().unwrap().active_url().clone()
+    }
+
+    pub fn num_endpoints(&self) -> usize {
+        self.connection_manager.lock().unwrap().num_endpoints()
+    }
+
+    pub fn connection_status(&self) -> IndexerConnectionStatus {
+        *self.status.lock().unwrap()
+    }
+
+    pub fn endpoint_status(&self) -> Vec<IndexerEndpointStatus> {
+        self.connection_manager.lock().unwrap().status()
+    }
+
+    /// Measures the latency of every endpoint and makes the healthy endpoint with the lowest latency active
+    pub async fn check_endpoints(&self) {
+        let endpoints = self.connection_manager.lock().unwrap().urls();
+        for endpoint in endpoints {
+            let result = self.check_endpoint(endpoint.clone()).await;
+            if let Err(ref err) = result {
+                warn!(target: LOG_TARGET, "Indexer endpoint {} failed the health check: {}", endpoint, err);
+            }
+            self.connection_manager
+                .lock()
+                .unwrap()
+                .record_health_check(&endpoint, result);
+        }
+        self.connection_manager.lock().unwrap().select_best();
+    }
+
+    async fn check_endpoint(&self, endpoint: Url) -> Result<Duration, String> {
+        let mut client = self.get_client(endpoint).map_err(|e| e.to_string())?;
+        let timer = Instant::now();
+        match time::timeout(HEALTH_CHECK_TIMEOUT, client.get_epoch_manager_stats()).await {
+            Ok(Ok(_)) => Ok(timer.elapsed()),
+            Ok(Err(err)) => Err(err.to_string()),
+            Err(_) => Err(format!("No response within {:.2?}", HEALTH_CHECK_TIMEOUT)),
+        }
+    }
+
+    /// Sends a request to the active endpoint. If the request cannot be sent, it is retried on the other endpoints in
+    /// order of preference and the first endpoint that responds becomes active.
+    async fn request<T, F, Fut>(&self, send: F) -> Result<T, IndexerJrpcError>
+    where
+        F: Fn(IndexerJsonRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, IndexerClientError>>,
+    {
+        let endpoints = self.connection_manager.lock().unwrap().failover_order();
+        let mut last_error = None;
+        for endpoint in endpoints {
+            let client = self.get_client(endpoint.clone())?;
+            match send(client).await {
+                Err(err) if is_unreachable(&err) => {
+                    warn!(target: LOG_TARGET, "Indexer endpoint {} is unreachable: {}", endpoint, err);
+                    self.connection_manager
+                        .lock()
+                        .unwrap()
+                        .record_request_failure(&endpoint, err.to_string());
+                    last_error = Some(err);
+                },
+                result => {
+                    self.connection_manager
+                        .lock()
+                        .unwrap()
+                        .record_request_success(&endpoint);
+                    return Ok(self.record_response(result)?);
+                },
+            }
+        }
+
+        let err = last_error.expect("there is always at least one indexer endpoint");
+        self.record_response(Err(err)).map_err(Into::into)
+    }
+
+    /// Returns the events indexed by the indexer, filtered by the filters that are set
+    pub async fn get_events(
+        &self,
+        topic: Option<String>,
+        substate_id: Option<SubstateId>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Event>, IndexerJrpcError> {
+        let result = self
+            .request(|mut client| {
+                let request = GetEventsRequest {
+                    topic: topic.clone(),
+                    substate_id: substate_id.clone(),
+                    offset,
+                    limit,
+                };
+                async move { client.get_events(request).await }
+            })
+            .await?;
+        Ok(result.events)
+    }
+
+    /// Records the outcome of a request to the indexer. Error responses from the indexer still mean that it is
+    /// reachable, only a failure to send the request is counted as a lost connection.
+    fn record_response<T>(&self, result: Result<T, IndexerClientError>) -> Result<T, IndexerClientError> {
+        let mut status = self.status.lock().unwrap();
+        match &result {
+            Ok(_) => {
+                status.is_connected = Some(true);
+                status.last_successful_query_at = Some(SystemTime::now());
+            },
+            Err(IndexerClientError::RequestFailed { .. }) => status.is_connected = Some(false),
+            Err(_) => status.is_connected = Some(true),
+        }
+        result
     }
 }
 
@@ -72,12 +227,14 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         version: Option<u32>,
         local_search_only: bool,
     ) -> Result<SubstateQueryResult, Self::Error> {
-        let mut client = self.get_client()?;
-        let result = client
-            .get_substate(GetSubstateRequest {
-                address: address.clone(),
-                version,
-                local_search_only,
+        let result = self
+            .request(|mut client| {
+                let request = GetSubstateRequest {
+                    address: address.clone(),
+                    version,
+                    local_search_only,
+                };
+                async move { client.get_substate(request).await }
             })
             .await?;
         Ok(SubstateQueryResult {
@@ -95,13 +252,15 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         limit: Option<u64>,
         offset: Option<u64>,
     ) -> Result<SubstateListResult, Self::Error> {
-        let mut client = self.get_client()?;
-        let result = client
-            .list_substates(ListSubstatesRequest {
-                filter_by_template,
-                filter_by_type,
-                limit,
-                offset,
+        let result = self
+            .request(|mut client| {
+                let request = ListSubstatesRequest {
+                    filter_by_template,
+                    filter_by_type,
+                    limit,
+                    offset,
+                };
+                async move { client.list_substates(request).await }
             })
             .await?;
         let substates = result
@@ -132,12 +291,16 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
     ) -> Result<TransactionId, Self::Error> {
-        let mut client = self.get_client()?;
-        let result = client
-            .submit_transaction(SubmitTransactionRequest {
-                transaction,
-                required_substates,
-                is_dry_run: false,
+        // Submitting the same transaction to another indexer is safe because the transaction ID does not change
+        let result = self
+            .request(|mut client| {
+                let request = SubmitTransactionRequest {
+                    transaction: transaction.clone(),
+                    required_substates: required_substates.clone(),
+                    is_dry_run: false,
+                    substate_overrides: HashMap::new(),
+                };
+                async move { client.submit_transaction(request).await }
             })
             .await?;
         Ok(result.transaction_id)
@@ -147,13 +310,17 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         &self,
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<TransactionQueryResult, Self::Error> {
-        let mut client = self.get_client()?;
-        let resp = client
-            .submit_transaction(SubmitTransactionRequest {
-                transaction,
-                required_substates,
-                is_dry_run: true,
+        let resp = self
+            .request(|mut client| {
+                let request = SubmitTransactionRequest {
+                    transaction: transaction.clone(),
+                    required_substates: required_substates.clone(),
+                    is_dry_run: true,
+                    substate_overrides: substate_overrides.clone(),
+                };
+                async move { client.submit_transaction(request).await }
             })
             .await?;
 
@@ -167,9 +334,12 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         &self,
         transaction_id: TransactionId,
     ) -> Result<TransactionQueryResult, Self::Error> {
-        let mut client = self.get_client()?;
-        let resp = client
-            .get_transaction_result(GetTransactionResultRequest { transaction_id })
+        let resp = self
+            .request(|mut client| async move {
+                client
+                    .get_transaction_result(GetTransactionResultRequest { transaction_id })
+                    .await
+            })
             .await?;
 
         Ok(TransactionQueryResult {
@@ -182,13 +352,23 @@ impl WalletNetworkInterface for IndexerJsonRpcNetworkInterface {
         &self,
         template_address: TemplateAddress,
     ) -> Result<tari_template_abi::TemplateDef, Self::Error> {
-        let mut client = self.get_client()?;
-        let resp = client
-            .get_template_definition(tari_indexer_client::types::GetTemplateDefinitionRequest { template_address })
+        let resp = self
+            .request(|mut client| async move {
+                client
+                    .get_template_definition(GetTemplateDefinitionRequest { template_address })
+                    .await
+            })
             .await?;
 
         Ok(resp.definition)
     }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        let resp = self
+            .request(|mut client| async move { client.get_epoch_manager_stats().await })
+            .await?;
+        Ok(resp.current_epoch)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -199,6 +379,15 @@ pub enum IndexerJrpcError {
     IndexerParseError(#[from] ParseError),
 }
 
+/// Returns true if the request could not be sent to the indexer. HTTP error responses mean that the indexer is
+/// reachable.
+fn is_unreachable(err: &IndexerClientError) -> bool {
+    match err {
+        IndexerClientError::RequestFailed { source } => source.status().is_none(),
+        _ => false,
+    }
+}
+
 impl IsNotFoundError for IndexerJrpcError {
     fn is_not_found_error(&self) -> bool {
         match self {
@@ -5,10 +5,10 @@ use std::{net::SocketAddr, sync::Arc};
 
 use axum::{
     extract::Extension,
-    http::{Request, StatusCode},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
     middleware::Next,
-    response::Response,
-    routing::post,
+    response::{IntoResponse, Response},
+    routing::{get, post},
     Router,
 };
 use axum_jrpc::{
@@ -22,23 +22,36 @@ use log::*;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
 use tari_dan_wallet_sdk::apis::jwt::JwtApiError;
+use tari_engine_types::rpc_encoding::RpcEncoding;
 use tari_shutdown::ShutdownSignal;
 use tokio::task;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 use super::handlers::{substates, templates, HandlerContext};
-use crate::handlers::{
-    accounts,
-    confidential,
-    error::HandlerError,
-    keys,
-    nfts,
-    rpc,
-    settings,
-    transaction,
-    validator,
-    webrtc,
-    Handler,
+use crate::{
+    handlers::{
+        accounts,
+        attestation,
+        confidential,
+        contacts,
+        error::HandlerError,
+        events,
+        keys,
+        network,
+        nfts,
+        rpc,
+        schedules,
+        settings,
+        transaction,
+        transaction_export,
+        validator,
+        wallet,
+        webrtc,
+        Handler,
+    },
+    health,
+    trace_id,
+    trace_id::TRACE_ID_HEADER,
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_daemon::json_rpc";
@@ -58,6 +71,18 @@ async fn extract_token<B>(mut request: Request<B>, next: Next<B>) -> Result<Resp
     Ok(response)
 }
 
+/// Handles the request within the scope of the trace ID provided by the client, or a generated trace ID if none was
+/// provided. The trace ID is returned in the response headers.
+async fn scope_trace_id<B>(request: Request<B>, next: Next<B>) -> Result<Response, StatusCode> {
+    let trace_id =
+        trace_id::from_client_or_generate(request.headers().get(TRACE_ID_HEADER).and_then(|v| v.to_str().ok()));
+    let mut response = trace_id::scope(trace_id.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&trace_id) {
+        response.headers_mut().insert(TRACE_ID_HEADER, value);
+    }
+    Ok(response)
+}
+
 pub fn spawn_listener(
     preferred_address: SocketAddr,
     signaling_server_address: SocketAddr,
@@ -67,13 +92,18 @@ pub fn spawn_listener(
     let router = Router::new()
         .route("/", post(handler))
         .route("/json_rpc", post(handler))
+        .route("/health", get(health::handle_health));
+    #[cfg(feature = "metrics")]
+    let router = router.route("/metrics", get(health::handle_metrics));
+    let router = router
         // TODO: Get these traces to work
         .layer(TraceLayer::new_for_http())
         .layer(Extension(Arc::new(context)))
         .layer(Extension((preferred_address,signaling_server_address)))
         .layer(Extension(Arc::new(shutdown_signal.clone())))
         .layer(CorsLayer::permissive())
-        .layer(axum::middleware::from_fn(extract_token));
+        .layer(axum::middleware::from_fn(extract_token))
+        .layer(axum::middleware::from_fn(scope_trace_id));
 
     let server = axum::Server::try_bind(&preferred_address)?;
     let server = server.serve(router.into_make_service());
@@ -94,10 +124,49 @@ async fn handler(
     Extension(addresses): Extension<(SocketAddr, SocketAddr)>,
     Extension(shutdown_signal): Extension<Arc<ShutdownSignal>>,
     Extension(token): Extension<Option<String>>,
+    headers: HeaderMap,
     value: JsonRpcExtractor,
-) -> JrpcResult {
-    info!(target: LOG_TARGET, "🌐 JSON-RPC request: {}", value.method);
+) -> Response {
+    info!(
+        target: LOG_TARGET,
+        "🌐 JSON-RPC request: {} (trace_id={})",
+        value.method,
+        trace_id::current().unwrap_or_default()
+    );
     debug!(target: LOG_TARGET, "🌐 JSON-RPC request: {:?}", value);
+    context.record_activity();
+    // The export is streamed back as a chunked response body rather than a JSON-RPC response
+    if value.method == "transactions.export" {
+        return transaction_export::handle_export(context, value, token).await;
+    }
+    let encoding = RpcEncoding::from_accept_header(headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()));
+    let result = handle_jrpc(context, addresses, shutdown_signal, token, value).await;
+    encode_response(encoding, result)
+}
+
+/// Encodes the response in the encoding that the client accepts. JSON responses are unchanged.
+fn encode_response(encoding: RpcEncoding, result: JrpcResult) -> Response {
+    let response = result.unwrap_or_else(|err| err);
+    if encoding == RpcEncoding::Json {
+        return response.into_response();
+    }
+
+    match encoding.encode(&response) {
+        Ok(body) => ([(header::CONTENT_TYPE, encoding.content_type())], body).into_response(),
+        Err(e) => {
+            error!(target: LOG_TARGET, "🚨 Failed to encode JSON-RPC response: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to encode response: {e}")).into_response()
+        },
+    }
+}
+
+async fn handle_jrpc(
+    context: Arc<HandlerContext>,
+    addresses: (SocketAddr, SocketAddr),
+    shutdown_signal: Arc<ShutdownSignal>,
+    token: Option<String>,
+    value: JsonRpcExtractor,
+) -> JrpcResult {
     match value.method.as_str().split_once('.') {
         Some(("auth", method)) => match method {
             "request" => call_handler(context, value, token, rpc::handle_login_request).await,
@@ -112,12 +181,34 @@ async fn handler(
             "set" => call_handler(context, value, token, settings::handle_set).await,
             _ => Ok(value.method_not_found(&value.method)),
         },
+        Some(("wallet", method)) => match method {
+            "lock" => call_handler(context, value, token, wallet::handle_lock).await,
+            "unlock" => call_handler(context, value, token, wallet::handle_unlock).await,
+            "gc" => call_handler(context, value, token, wallet::handle_gc).await,
+            _ => Ok(value.method_not_found(&value.method)),
+        },
+        Some(("network", "status")) => call_handler(context, value, token, network::handle_status).await,
+        Some(("attestation", "get")) => call_handler(context, value, token, attestation::handle_get).await,
         Some(("webrtc", "start")) => webrtc::handle_start(context, value, token, shutdown_signal, addresses),
         Some(("rpc", "discover")) => call_handler(context, value, token, rpc::handle_discover).await,
+        Some(("contacts", method)) => match method {
+            "add" => call_handler(context, value, token, contacts::handle_add).await,
+            "list" => call_handler(context, value, token, contacts::handle_list).await,
+            "remove" => call_handler(context, value, token, contacts::handle_remove).await,
+            _ => Ok(value.method_not_found(&value.method)),
+        },
+        Some(("schedules", method)) => match method {
+            "create" => call_handler(context, value, token, schedules::handle_create).await,
+            "list" => call_handler(context, value, token, schedules::handle_list).await,
+            "cancel" => call_handler(context, value, token, schedules::handle_cancel).await,
+            _ => Ok(value.method_not_found(&value.method)),
+        },
         Some(("keys", method)) => match method {
             "create" => call_handler(context, value, token, keys::handle_create).await,
             "list" => call_handler(context, value, token, keys::handle_list).await,
             "set_active" => call_handler(context, value, token, keys::handle_set_active).await,
+            "get_seed_words" => call_handler(context, value, token, keys::handle_get_seed_words).await,
+            "verify_seed_words" => call_handler(context, value, token, keys::handle_verify_seed_words).await,
             _ => Ok(value.method_not_found(&value.method)),
         },
         Some(("transactions", method)) => match method {
@@ -128,6 +219,16 @@ async fn handler(
             "get_result" => call_handler(context, value, token, transaction::handle_get_result).await,
             "wait_result" => call_handler(context, value, token, transaction::handle_wait_result).await,
             "get_all" => call_handler(context, value, token, transaction::handle_get_all).await,
+            "get_failures" => call_handler(context, value, token, transaction::handle_get_failures).await,
+            "retry" => call_handler(context, value, token, transaction::handle_retry).await,
+            "set_annotation" => call_handler(context, value, token, transaction::handle_set_annotation).await,
+            "review_sponsored" => call_handler(context, value, token, transaction::handle_review_sponsored).await,
+            "accept_sponsored" => call_handler(context, value, token, transaction::handle_accept_sponsored).await,
+            "export_unsigned" => call_handler(context, value, token, transaction::handle_export_unsigned).await,
+            "import_signed" => call_handler(context, value, token, transaction::handle_import_signed).await,
+            "get_canonical_bytes" => {
+                call_handler(context, value, token, transaction::handle_get_canonical_bytes).await
+            },
             _ => Ok(value.method_not_found(&value.method)),
         },
         Some(("accounts", method)) => match method {
@@ -136,6 +237,12 @@ async fn handler(
             "create" => call_handler(context, value, token, accounts::handle_create).await,
             "list" => call_handler(context, value, token, accounts::handle_list).await,
             "get_balances" => call_handler(context, value, token, accounts::handle_get_balances).await,
+            "get_balance_at_epoch" => {
+                call_handler(context, value, token, accounts::handle_get_balance_at_epoch).await
+            },
+            "create_balance_proof" => {
+                call_handler(context, value, token, accounts::handle_create_balance_proof).await
+            },
             "invoke" => call_handler(context, value, token, accounts::handle_invoke).await,
             "get" => call_handler(context, value, token, accounts::handle_get).await,
             "get_default" => call_handler(context, value, token, accounts::handle_get_default).await,
@@ -144,6 +251,9 @@ async fn handler(
                 call_handler(context, value, token, accounts::handle_confidential_transfer).await
             },
             "set_default" => call_handler(context, value, token, accounts::handle_set_default).await,
+            "recover" => call_handler(context, value, token, accounts::handle_recover).await,
+            "import" => call_handler(context, value, token, accounts::handle_import).await,
+            "import_watch_only" => call_handler(context, value, token, accounts::handle_import_watch_only).await,
             "create_free_test_coins" => {
                 call_handler(context, value, token, accounts::handle_create_free_test_coins).await
             },
@@ -159,6 +269,7 @@ async fn handler(
                 call_handler(context, value, token, confidential::handle_create_output_proof).await
             },
             "view_vault_balance" => call_handler(context, value, token, confidential::handle_view_vault_balance).await,
+            "audit_balances" => call_handler(context, value, token, confidential::handle_audit_balances).await,
             _ => Ok(value.method_not_found(&value.method)),
         },
         Some(("substates", method)) => match method {
@@ -166,7 +277,15 @@ async fn handler(
             "list" => call_handler(context, value, token, substates::handle_list).await,
             _ => Ok(value.method_not_found(&value.method)),
         },
-        Some(("templates", "get")) => call_handler(context, value, token, templates::handle_get).await,
+        Some(("events", "list")) => call_handler(context, value, token, events::handle_list).await,
+        Some(("templates", method)) => match method {
+            "get" => call_handler(context, value, token, templates::handle_get).await,
+            "get_abi" => call_handler(context, value, token, templates::handle_get_abi).await,
+            "publish" => call_handler(context, value, token, templates::handle_publish).await,
+            "list_versions" => call_handler(context, value, token, templates::handle_list_versions).await,
+            "migrate_component" => call_handler(context, value, token, templates::handle_migrate_component).await,
+            _ => Ok(value.method_not_found(&value.method)),
+        },
         Some(("nfts", method)) => match method {
             "mint_account_nft" => call_handler(context, value, token, nfts::handle_mint_account_nft).await,
             "get" => call_handler(context, value, token, nfts::handle_get_nft).await,
@@ -194,6 +313,8 @@ where
     H: for<'a> Handler<'a, TReq, Response = TResp>,
 {
     let answer_id = value.get_answer_id();
+    #[cfg(feature = "metrics")]
+    let _timer = context.metrics().start_request_timer(&value.method);
     let resp = handler
         .handle(
             &context,
@@ -208,10 +329,24 @@ where
             })?,
         )
         .await
-        .map_err(|e| resolve_handler_error(answer_id, &e))?;
+        .map_err(|e| {
+            if is_auth_error(&e) {
+                context.record_auth_failure();
+            }
+            resolve_handler_error(answer_id, &e)
+        })?;
     Ok(JsonRpcResponse::success(answer_id, resp))
 }
 
+fn is_auth_error(e: &HandlerError) -> bool {
+    match e {
+        HandlerError::Anyhow(e) => {
+            e.downcast_ref::<JwtApiError>().is_some() || e.downcast_ref::<HandlerError>().is_some_and(is_auth_error)
+        },
+        HandlerError::NotFound => false,
+    }
+}
+
 fn resolve_handler_error(answer_id: i64, e: &HandlerError) -> JsonRpcResponse {
     match e {
         HandlerError::Anyhow(e) => resolve_any_error(answer_id, e),
@@ -223,7 +358,7 @@ fn resolve_handler_error(answer_id: i64, e: &HandlerError) -> JsonRpcResponse {
 }
 
 fn resolve_any_error(answer_id: i64, e: &anyhow::Error) -> JsonRpcResponse {
-    warn!(target: LOG_TARGET, "🌐 JSON-RPC error: {}", e);
+    warn!(target: LOG_TARGET, "🌐 JSON-RPC error: {} (trace_id={})", e, trace_id::current().unwrap_or_default());
     if let Some(handler_err) = e.downcast_ref::<HandlerError>() {
         return resolve_handler_error(answer_id, handler_err);
     }
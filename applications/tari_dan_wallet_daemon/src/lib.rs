@@ -20,18 +20,26 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod auto_lock;
+mod bootstrap;
 pub mod cli;
 pub mod config;
 mod handlers;
+mod health;
 mod http_ui;
+mod indexer_connection_manager;
 pub mod indexer_jrpc_impl;
 mod jrpc_server;
+#[cfg(feature = "metrics")]
+mod metrics;
 mod notify;
 mod services;
+mod trace_id;
 mod webrtc;
 
-use std::{fs, panic, process};
+use std::{fs, panic, process, str};
 
+use anyhow::anyhow;
 use log::*;
 use tari_dan_common_types::optional::Optional;
 use tari_dan_wallet_sdk::{
@@ -39,18 +47,25 @@ use tari_dan_wallet_sdk::{
         config::{ConfigApi, ConfigKey},
         key_manager,
     },
+    storage::WalletStore,
+    CipherSeed,
     DanWalletSdk,
     WalletSdkConfig,
 };
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_shutdown::ShutdownSignal;
 use tari_template_lib::models::Amount;
+use tari_utilities::SafePassword;
 use tokio::task;
+use url::Url;
 
 use crate::{
+    auto_lock::run_auto_lock,
+    bootstrap::BootstrapFile,
     config::ApplicationConfig,
     handlers::HandlerContext,
     http_ui::server::run_http_ui_server,
+    indexer_connection_manager::run_health_checks,
     indexer_jrpc_impl::IndexerJsonRpcNetworkInterface,
     notify::Notify,
     services::spawn_services,
@@ -73,7 +88,12 @@ pub async fn run_tari_dan_wallet_daemon(
         .get_or_create_initial(key_manager::TRANSACTION_BRANCH)?;
     let notify = Notify::new(100);
 
-    let services = spawn_services(shutdown_signal.clone(), notify.clone(), wallet_sdk.clone());
+    let services = spawn_services(
+        shutdown_signal.clone(),
+        notify.clone(),
+        wallet_sdk.clone(),
+        &config.dan_wallet_daemon,
+    );
 
     let jrpc_address = config.dan_wallet_daemon.json_rpc_address.unwrap();
     let signaling_server_address = config.dan_wallet_daemon.signaling_server_address.unwrap();
@@ -82,8 +102,26 @@ pub async fn run_tari_dan_wallet_daemon(
         notify,
         services.transaction_service_handle.clone(),
         services.account_monitor_handle.clone(),
+        services.balance_auditor_handle.clone(),
         config.dan_wallet_daemon.clone(),
     );
+    if let Some(timeout) = config.dan_wallet_daemon.auto_lock_timeout {
+        if config.dan_wallet_daemon.password.is_none() {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ auto_lock_timeout is set but the wallet database is not encrypted. The wallet will not be locked."
+            );
+        }
+        task::spawn(run_auto_lock(handlers.clone(), timeout, shutdown_signal.clone()));
+    }
+    if wallet_sdk.get_network_interface().num_endpoints() > 1 {
+        task::spawn(run_health_checks(
+            wallet_sdk.get_network_interface().clone(),
+            config.dan_wallet_daemon.indexer_health_check_interval,
+            shutdown_signal.clone(),
+        ));
+    }
+
     let (jrpc_address, listen_fut) =
         jrpc_server::spawn_listener(jrpc_address, signaling_server_address, handlers, shutdown_signal)?;
 
@@ -126,20 +164,84 @@ pub fn initialize_wallet_sdk(
 ) -> anyhow::Result<DanWalletSdk<SqliteWalletStore, IndexerJsonRpcNetworkInterface>> {
     let store = SqliteWalletStore::try_open(config.common.base_path.join("data/wallet.sqlite"))?;
     store.run_migrations()?;
+    // The store is unlocked before the bootstrap file is applied because it reads and writes wallet config
+    match config.dan_wallet_daemon.password {
+        Some(ref password) => store.unlock(password)?,
+        None if store.is_encrypted() => {
+            return Err(anyhow!(
+                "The wallet database is encrypted. Provide the password using --password or the \
+                 TARI_DAN_WALLET_PASSWORD environment variable"
+            ));
+        },
+        None => {},
+    }
 
     let sdk_config = WalletSdkConfig {
-        // TODO: Configure
+        // Already unlocked above
         password: None,
         jwt_expiry: config.dan_wallet_daemon.jwt_expiry.unwrap(),
         jwt_secret_key: config.dan_wallet_daemon.jwt_secret_key.clone().unwrap(),
     };
     let config_api = ConfigApi::new(&store);
+    if let Some(ref seed_words) = config.dan_wallet_daemon.seed_words {
+        restore_from_seed_words(
+            &config_api,
+            seed_words,
+            config.dan_wallet_daemon.seed_words_passphrase.clone(),
+        )?;
+    }
+    let bootstrap_file = match config.dan_wallet_daemon.bootstrap_file {
+        Some(ref path) if !BootstrapFile::is_imported(&config_api)? => {
+            info!(target: LOG_TARGET, "🥾 Loading bootstrap file {}", path.display());
+            let bootstrap_file = BootstrapFile::load(path)?;
+            bootstrap_file.apply_cipher_seed(&config_api)?;
+            Some(bootstrap_file)
+        },
+        _ => None,
+    };
     let indexer_jrpc_endpoint = if let Some(indexer_url) = config_api.get(ConfigKey::IndexerUrl).optional()? {
         indexer_url
     } else {
         config.dan_wallet_daemon.indexer_node_json_rpc_url.clone()
     };
-    let indexer = IndexerJsonRpcNetworkInterface::new(indexer_jrpc_endpoint);
+    let fallback_endpoints = config
+        .dan_wallet_daemon
+        .fallback_indexer_node_json_rpc_urls
+        .iter()
+        .map(|url| Url::parse(url).map_err(|e| anyhow!("Invalid fallback indexer URL '{}': {}", url, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let indexer =
+        IndexerJsonRpcNetworkInterface::new(indexer_jrpc_endpoint).with_fallback_endpoints(fallback_endpoints);
     let wallet_sdk = DanWalletSdk::initialize(store, indexer, sdk_config)?;
+    if let Some(bootstrap_file) = bootstrap_file {
+        bootstrap_file.import(&wallet_sdk)?;
+    }
     Ok(wallet_sdk)
 }
+
+/// Sets the wallet root key from seed words. This must be called before the wallet SDK is initialized, otherwise a
+/// random root key is generated. If the wallet has already been initialized, the seed words must restore the existing
+/// root key.
+fn restore_from_seed_words<TStore: WalletStore>(
+    config_api: &ConfigApi<'_, TStore>,
+    seed_words: &SafePassword,
+    passphrase: Option<SafePassword>,
+) -> anyhow::Result<()> {
+    let seed_words = str::from_utf8(seed_words.reveal()).map_err(|_| anyhow!("Seed words are not valid UTF-8"))?;
+    let cipher_seed = key_manager::cipher_seed_from_seed_words(&key_manager::parse_seed_words(seed_words), passphrase)?;
+    match config_api.get::<CipherSeed>(ConfigKey::CipherSeed).optional()? {
+        Some(existing) => {
+            if existing.entropy() != cipher_seed.entropy() {
+                return Err(anyhow!(
+                    "The wallet has already been initialized with a different root key to the one restored from the \
+                     seed words"
+                ));
+            }
+        },
+        None => {
+            info!(target: LOG_TARGET, "🌱 Restoring wallet root key from seed words");
+            config_api.set(ConfigKey::CipherSeed, &cipher_seed, true)?;
+        },
+    }
+    Ok(())
+}
@@ -24,7 +24,7 @@ use std::{fs, panic, process};
 
 use tari_common::initialize_logging;
 use tari_crypto::{keys::PublicKey, ristretto::RistrettoPublicKey};
-use tari_dan_app_utilities::configuration::load_configuration;
+use tari_dan_app_utilities::{configuration::load_configuration, logging::initialize_json_logging};
 use tari_dan_wallet_daemon::{cli::Cli, config::ApplicationConfig, initialize_wallet_sdk, run_tari_dan_wallet_daemon};
 use tari_dan_wallet_sdk::apis::key_manager;
 use tari_shutdown::Shutdown;
@@ -61,7 +61,9 @@ async fn main() -> Result<(), anyhow::Error> {
     let shutdown = Shutdown::new();
     let shutdown_signal = shutdown.to_signal();
 
-    if let Err(e) = initialize_logging(
+    if config.dan_wallet_daemon.log_format.is_json() {
+        initialize_json_logging("info")?;
+    } else if let Err(e) = initialize_logging(
         &cli.common.log_config_path("dan_wallet_daemon"),
         &cli.common.get_base_path(),
         include_str!("../log4rs_sample.yml"),
@@ -0,0 +1,115 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use prometheus::{
+    core::Collector,
+    HistogramOpts,
+    HistogramTimer,
+    HistogramVec,
+    IntCounter,
+    IntGauge,
+    Registry,
+    TextEncoder,
+};
+
+use crate::health::HealthStatus;
+
+#[derive(Debug, Clone)]
+pub struct WalletDaemonMetrics {
+    registry: Registry,
+    request_duration: HistogramVec,
+    jwt_auth_failures: IntCounter,
+    indexer_connected: IntGauge,
+    last_successful_network_query: IntGauge,
+    pending_transactions: IntGauge,
+}
+
+impl WalletDaemonMetrics {
+    pub fn new() -> Self {
+        let mut labels = HashMap::with_capacity(1);
+        labels.insert("app".to_string(), "WalletDaemon".to_string());
+        let registry = Registry::new_custom(Some("tari".to_string()), Some(labels)).unwrap();
+
+        Self {
+            request_duration: register(
+                &registry,
+                HistogramVec::new(
+                    HistogramOpts::new(
+                        "wallet_daemon_request_duration_seconds",
+                        "Time taken to handle a JSON-RPC request",
+                    ),
+                    &["method"],
+                )
+                .unwrap(),
+            ),
+            jwt_auth_failures: register(
+                &registry,
+                IntCounter::new(
+                    "wallet_daemon_jwt_auth_failures",
+                    "Number of requests rejected due to a missing, invalid or revoked JWT",
+                )
+                .unwrap(),
+            ),
+            indexer_connected: register(
+                &registry,
+                IntGauge::new(
+                    "wallet_daemon_indexer_connected",
+                    "1 if the indexer responded to the last request, otherwise 0",
+                )
+                .unwrap(),
+            ),
+            last_successful_network_query: register(
+                &registry,
+                IntGauge::new(
+                    "wallet_daemon_last_successful_network_query_timestamp",
+                    "Unix timestamp in seconds of the last successful request to the indexer",
+                )
+                .unwrap(),
+            ),
+            pending_transactions: register(
+                &registry,
+                IntGauge::new(
+                    "wallet_daemon_pending_transactions",
+                    "Number of submitted transactions that are not yet finalized",
+                )
+                .unwrap(),
+            ),
+            registry,
+        }
+    }
+
+    /// Returns a timer that records the duration of the request when it is dropped
+    pub fn start_request_timer(&self, method: &str) -> HistogramTimer {
+        self.request_duration.with_label_values(&[method]).start_timer()
+    }
+
+    pub fn on_jwt_auth_failure(&self) {
+        self.jwt_auth_failures.inc();
+    }
+
+    /// Encodes all metrics in the Prometheus text format. The gauges are updated from the given health status first.
+    pub fn encode(&self, health: &HealthStatus) -> Result<String, prometheus::Error> {
+        self.indexer_connected
+            .set(i64::from(health.indexer_connected == Some(true)));
+        if let Some(timestamp) = health.last_successful_network_query {
+            self.last_successful_network_query.set(timestamp as i64);
+        }
+        if let Some(pending) = health.pending_transactions {
+            self.pending_transactions.set(pending as i64);
+        }
+        TextEncoder::new().encode_to_string(&self.registry.gather())
+    }
+}
+
+impl Default for WalletDaemonMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn register<C: Collector + Clone + 'static>(registry: &Registry, collector: C) -> C {
+    registry.register(Box::new(collector.clone())).unwrap();
+    collector
+}
@@ -4,16 +4,20 @@
 use std::{collections::HashMap, time::Duration};
 
 use log::*;
-use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    Epoch,
+};
 use tari_dan_wallet_sdk::{
     apis::{
         accounts::AccountsApiError,
+        balance_history::BalanceHistoryApiError,
         confidential_outputs::ConfidentialOutputsApiError,
         non_fungible_tokens::NonFungibleTokensApiError,
         substate::{SubstateApiError, ValidatorScanResult},
         transaction::TransactionApiError,
     },
-    models::{NewAccountInfo, NonFungibleToken},
+    models::{NewAccountInfo, NonFungibleToken, VersionedSubstateId},
     network::WalletNetworkInterface,
     storage::WalletStore,
     DanWalletSdk,
@@ -117,6 +121,10 @@ where
     }
 
     async fn on_poll(&self) {
+        if self.wallet_sdk.is_locked() {
+            debug!(target: LOG_TARGET, "Wallet is locked, skipping poll");
+            return;
+        }
         if let Err(err) = self.refresh_all_accounts().await {
             error!(target: LOG_TARGET, "Error refreshing all accounts: {}", err);
         }
@@ -156,6 +164,7 @@ where
             return Ok(false);
         }
 
+        let current_epoch = self.get_current_epoch().await;
         let mut is_updated = false;
         let account_substate = substate_api.get_substate(account_address)?;
         let ValidatorScanResult {
@@ -195,6 +204,8 @@ where
                 continue;
             };
 
+            self.record_vault_version(&versioned_account_address.substate_id, &versioned_addr, current_epoch)?;
+
             if let Some(vault_version) = maybe_vault_version {
                 // The first time a vault is found, know about the vault substate from the tx result but never added
                 // it to the database.
@@ -278,6 +289,7 @@ where
                 *vault.resource_address(),
                 vault.resource_type(),
                 token_symbol,
+                resource.decimals(),
             )?;
             has_changed = true;
         }
@@ -316,6 +328,17 @@ where
             accounts_api.update_vault_balance(&vault_addr, balance, confidential_balance)?;
             has_changed = true;
         }
+        if vault_balance.is_frozen != vault.is_frozen() {
+            info!(
+                target: LOG_TARGET,
+                "🧊 vault {} in account {} is {}",
+                vault_id,
+                account_address,
+                if vault.is_frozen() { "frozen" } else { "unfrozen" }
+            );
+            accounts_api.set_vault_frozen(&vault_addr, vault.is_frozen())?;
+            has_changed = true;
+        }
 
         for id in vault.get_non_fungible_ids() {
             let Some(nft) = nfts.get(id) else {
@@ -356,6 +379,7 @@ where
     async fn process_result(&mut self, tx_id: TransactionId, diff: &SubstateDiff) -> Result<(), AccountMonitorError> {
         let substate_api = self.wallet_sdk.substate_api();
         let accounts_api = self.wallet_sdk.accounts_api();
+        let current_epoch = self.get_current_epoch().await;
 
         let mut new_account = None;
         if let Some(account) = self.pending_accounts.remove(&tx_id) {
@@ -412,10 +436,18 @@ where
         for (account_addr, value) in accounts {
             for vault_id in value.vault_ids() {
                 // Any vaults we process here do not need to be reprocesed later
-                if let Some(vault) = vaults.remove(vault_id).and_then(|s| s.substate_value().vault()) {
+                let Some(substate) = vaults.remove(vault_id) else {
+                    continue;
+                };
+                if let Some(vault) = substate.substate_value().vault() {
                     self.add_vault_to_account_if_not_exist(account_addr, *vault_id, vault)
                         .await?;
                     self.refresh_vault(account_addr, *vault_id, vault, &nfts).await?;
+                    let vault_address = VersionedSubstateId {
+                        substate_id: SubstateId::Vault(*vault_id),
+                        version: substate.version(),
+                    };
+                    self.record_vault_version(account_addr, &vault_address, current_epoch)?;
                 }
             }
         }
@@ -457,6 +489,11 @@ where
 
             // Update the vault balance / confidential outputs
             self.refresh_vault(&account_addr, vault_id, vault, &nfts).await?;
+            let vault_address = VersionedSubstateId {
+                substate_id: vault_addr,
+                version: substate.version(),
+            };
+            self.record_vault_version(&account_addr, &vault_address, current_epoch)?;
             updated_accounts.push(account_addr);
         }
 
@@ -490,6 +527,40 @@ where
         Ok(resx)
     }
 
+    /// Returns the current epoch, or None if it could not be fetched
+    async fn get_current_epoch(&self) -> Option<Epoch> {
+        match self.wallet_sdk.get_network_interface().get_current_epoch().await {
+            Ok(epoch) => Some(epoch),
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to get the current epoch. Vault versions will not be recorded in the balance history: {}", e
+                );
+                None
+            },
+        }
+    }
+
+    /// Records the vault version in the balance history of the account. Nothing is recorded if the current epoch is
+    /// not known or the account does not belong to this wallet.
+    fn record_vault_version(
+        &self,
+        account_address: &SubstateId,
+        vault_address: &VersionedSubstateId,
+        current_epoch: Option<Epoch>,
+    ) -> Result<(), AccountMonitorError> {
+        let Some(epoch) = current_epoch else {
+            return Ok(());
+        };
+        if !self.wallet_sdk.accounts_api().exists_by_address(account_address)? {
+            return Ok(());
+        }
+        self.wallet_sdk
+            .balance_history_api()
+            .record_vault_version(account_address, vault_address, epoch)?;
+        Ok(())
+    }
+
     async fn add_vault_to_account_if_not_exist(
         &self,
         account_addr: &SubstateId,
@@ -518,7 +589,10 @@ where
             },
         };
 
-        let token_symbol = maybe_resource.and_then(|r| r.metadata().get(TOKEN_SYMBOL).map(|s| s.to_string()));
+        let token_symbol = maybe_resource
+            .as_ref()
+            .and_then(|r| r.metadata().get(TOKEN_SYMBOL).map(|s| s.to_string()));
+        let decimals = maybe_resource.as_ref().and_then(|r| r.decimals());
         info!(
             target: LOG_TARGET,
             "👁️‍🗨️ New {} in account {}",
@@ -531,6 +605,7 @@ where
             *vault.resource_address(),
             vault.resource_type(),
             token_symbol,
+            decimals,
         )?;
 
         Ok(())
@@ -551,6 +626,9 @@ where
             WalletEvent::TransactionInvalid(event) => {
                 self.pending_accounts.remove(&event.transaction_id);
             },
+            WalletEvent::TransactionExpired(event) => {
+                self.pending_accounts.remove(&event.transaction_id);
+            },
             WalletEvent::AccountCreated(_) | WalletEvent::AccountChanged(_) | WalletEvent::AuthLoginRequest(_) => {},
         }
         Ok(())
@@ -596,6 +674,8 @@ pub enum AccountMonitorError {
     ConfidentialOutputs(#[from] ConfidentialOutputsApiError),
     #[error("Non Fungibles API error: {0}")]
     NonFungibleTokens(#[from] NonFungibleTokensApiError),
+    #[error("Balance history API error: {0}")]
+    BalanceHistory(#[from] BalanceHistoryApiError),
     #[error("Failed to decode binary value: {0}")]
     DecodeValueFailed(#[from] IndexedValueError),
     #[error("Unexpected substate: {0}")]
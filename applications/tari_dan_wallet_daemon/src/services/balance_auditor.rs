@@ -0,0 +1,332 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fs, io, ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use log::*;
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    substate_type::SubstateType,
+};
+use tari_dan_wallet_crypto::{AlwaysMissLookupTable, IoReaderValueLookup};
+use tari_dan_wallet_sdk::{
+    apis::{
+        confidential_audit::ConfidentialAuditApiError,
+        substate::{SubstateApiError, ValidatorScanResult},
+    },
+    models::AuditedVaultBalance,
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_engine_types::{
+    indexed_value::{IndexedValueError, IndexedWellKnownTypes},
+    substate::{SubstateId, SubstateValue},
+    vault::Vault,
+};
+use tari_shutdown::ShutdownSignal;
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_template_lib::{
+    models::{ResourceAddress, VaultId},
+    prelude::ComponentAddress,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::block_in_place,
+    time,
+    time::MissedTickBehavior,
+};
+
+use crate::services::Reply;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::balance_auditor";
+
+const ACCOUNT_PAGE_SIZE: u64 = 100;
+pub const DEFAULT_AUDIT_VALUE_RANGE: RangeInclusive<u64> = 0..=10_000_000_000;
+
+/// Scans all accounts for vaults of confidential resources for which this wallet holds the view key and records the
+/// decrypted balances. No spending keys are required.
+pub struct BalanceAuditor<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    request_rx: mpsc::Receiver<BalanceAuditorRequest>,
+    value_lookup_table_file: Option<PathBuf>,
+    rescan_interval: Option<Duration>,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> BalanceAuditor<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        value_lookup_table_file: Option<PathBuf>,
+        rescan_interval: Option<Duration>,
+        shutdown_signal: ShutdownSignal,
+    ) -> (Self, BalanceAuditorHandle) {
+        let (request_tx, request_rx) = mpsc::channel(1);
+
+        (
+            Self {
+                wallet_sdk,
+                request_rx,
+                value_lookup_table_file,
+                rescan_interval,
+                shutdown_signal,
+            },
+            BalanceAuditorHandle { sender: request_tx },
+        )
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        // If no interval is configured, previously audited resources are only rescanned on request
+        let mut rescan_interval = time::interval(self.rescan_interval.unwrap_or(Duration::from_secs(60 * 60)));
+        rescan_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // Skip the immediate first tick
+        rescan_interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = rescan_interval.tick(), if self.rescan_interval.is_some() => {
+                    self.on_rescan().await;
+                }
+
+                Some(req) = self.request_rx.recv() => {
+                    self.handle_request(req).await;
+                }
+            }
+        }
+    }
+
+    async fn handle_request(&self, req: BalanceAuditorRequest) {
+        match req {
+            BalanceAuditorRequest::AuditResource {
+                resource_address,
+                view_key_index,
+                value_range,
+                reply,
+            } => {
+                let _ignore = reply.send(
+                    self.audit_resource(&resource_address, view_key_index, value_range)
+                        .await,
+                );
+            },
+        }
+    }
+
+    async fn on_rescan(&self) {
+        let resources = match self.wallet_sdk.confidential_audit_api().get_audited_resources() {
+            Ok(resources) => resources,
+            Err(err) => {
+                error!(target: LOG_TARGET, "Failed to load audited resources: {}", err);
+                return;
+            },
+        };
+
+        for (resource_address, view_key_index) in resources {
+            info!(
+                target: LOG_TARGET,
+                "🔍️ Rescanning balances for resource {} (view key {})", resource_address, view_key_index
+            );
+            if let Err(err) = self
+                .audit_resource(&resource_address, view_key_index, DEFAULT_AUDIT_VALUE_RANGE)
+                .await
+            {
+                error!(
+                    target: LOG_TARGET,
+                    "Error auditing balances for resource {}: {}", resource_address, err
+                );
+            }
+        }
+    }
+
+    async fn audit_resource(
+        &self,
+        resource_address: &ResourceAddress,
+        view_key_index: u64,
+        value_range: RangeInclusive<u64>,
+    ) -> Result<Vec<AuditedVaultBalance>, BalanceAuditorError> {
+        let substate_api = self.wallet_sdk.substate_api();
+        let mut balances = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let page = self
+                .wallet_sdk
+                .get_network_interface()
+                .list_substates(
+                    Some(ACCOUNT_TEMPLATE_ADDRESS),
+                    Some(SubstateType::Component),
+                    Some(ACCOUNT_PAGE_SIZE),
+                    Some(offset),
+                )
+                .await
+                .map_err(|e| BalanceAuditorError::NetworkError(e.into()))?;
+            let num_accounts = page.substates.len() as u64;
+
+            for item in page.substates {
+                let Some(owner) = item.substate_id.as_component_address() else {
+                    continue;
+                };
+                let ValidatorScanResult { substate, .. } = substate_api
+                    .scan_for_substate(&item.substate_id, Some(item.version))
+                    .await?;
+                let Some(component) = substate.component() else {
+                    warn!(target: LOG_TARGET, "Substate {} is not a component", item.substate_id);
+                    continue;
+                };
+                let value = IndexedWellKnownTypes::from_value(component.state())?;
+
+                for vault_id in value.vault_ids() {
+                    let scan_result = substate_api
+                        .scan_for_substate(&SubstateId::Vault(*vault_id), None)
+                        .await
+                        .optional()?;
+                    let Some(ValidatorScanResult {
+                        address,
+                        substate: SubstateValue::Vault(vault),
+                        ..
+                    }) = scan_result
+                    else {
+                        continue;
+                    };
+
+                    if vault.resource_address() != resource_address || vault.get_confidential_commitments().is_none() {
+                        continue;
+                    }
+
+                    let audited = block_in_place(|| {
+                        self.audit_vault(
+                            view_key_index,
+                            *vault_id,
+                            address.version,
+                            owner,
+                            &vault,
+                            value_range.clone(),
+                        )
+                    })?;
+                    debug!(
+                        target: LOG_TARGET,
+                        "🔍️ Vault {} owned by {} has audited balance {}", vault_id, owner, audited.balance
+                    );
+                    balances.push(audited);
+                }
+            }
+
+            if num_accounts < ACCOUNT_PAGE_SIZE {
+                break;
+            }
+            offset += num_accounts;
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "🔍️ Audited {} vault(s) for resource {}",
+            balances.len(),
+            resource_address
+        );
+
+        Ok(balances)
+    }
+
+    fn audit_vault(
+        &self,
+        view_key_index: u64,
+        vault_id: VaultId,
+        vault_version: u32,
+        owner: ComponentAddress,
+        vault: &Vault,
+        value_range: RangeInclusive<u64>,
+    ) -> Result<AuditedVaultBalance, BalanceAuditorError> {
+        let audit_api = self.wallet_sdk.confidential_audit_api();
+        let audited = match self.value_lookup_table_file.as_ref() {
+            Some(path) => {
+                let to_error = |source| BalanceAuditorError::ValueLookupFile {
+                    path: path.clone(),
+                    source,
+                };
+                let mut file = fs::File::open(path).map_err(to_error)?;
+                let mut lookup = IoReaderValueLookup::load(&mut file).map_err(to_error)?;
+                audit_api.audit_vault(
+                    view_key_index,
+                    vault_id,
+                    vault_version,
+                    Some(owner),
+                    vault,
+                    value_range,
+                    &mut lookup,
+                )?
+            },
+            None => audit_api.audit_vault(
+                view_key_index,
+                vault_id,
+                vault_version,
+                Some(owner),
+                vault,
+                value_range,
+                &mut AlwaysMissLookupTable,
+            )?,
+        };
+        Ok(audited)
+    }
+}
+
+#[derive(Debug)]
+enum BalanceAuditorRequest {
+    AuditResource {
+        resource_address: ResourceAddress,
+        view_key_index: u64,
+        value_range: RangeInclusive<u64>,
+        reply: Reply<Result<Vec<AuditedVaultBalance>, BalanceAuditorError>>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct BalanceAuditorHandle {
+    sender: mpsc::Sender<BalanceAuditorRequest>,
+}
+
+impl BalanceAuditorHandle {
+    /// Scans the network for all account vaults of the given resource and records their balances as decrypted by the
+    /// view key.
+    pub async fn audit_resource(
+        &self,
+        resource_address: ResourceAddress,
+        view_key_index: u64,
+        value_range: RangeInclusive<u64>,
+    ) -> Result<Vec<AuditedVaultBalance>, BalanceAuditorError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(BalanceAuditorRequest::AuditResource {
+                resource_address,
+                view_key_index,
+                value_range,
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| BalanceAuditorError::ServiceShutdown)?;
+        reply_rx.await.map_err(|_| BalanceAuditorError::ServiceShutdown)?
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BalanceAuditorError {
+    #[error("Confidential audit API error: {0}")]
+    ConfidentialAudit(#[from] ConfidentialAuditApiError),
+    #[error("Substate API error: {0}")]
+    Substate(#[from] SubstateApiError),
+    #[error("Network error: {0}")]
+    NetworkError(anyhow::Error),
+    #[error("Failed to decode binary value: {0}")]
+    DecodeValueFailed(#[from] IndexedValueError),
+    #[error("Unable to load value lookup file '{}': {source}", path.display())]
+    ValueLookupFile { path: PathBuf, source: io::Error },
+    #[error("Balance auditor service is not running")]
+    ServiceShutdown,
+}
@@ -0,0 +1,108 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::optional::IsNotFoundError;
+use tari_dan_wallet_sdk::{network::WalletNetworkInterface, storage::WalletStore, DanWalletSdk};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::{
+    config::DepositAccountsConfig,
+    notify::Notify,
+    services::{AccountChangedEvent, AccountCreatedEvent, AccountMonitorHandle, WalletEvent},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::deposit_monitor";
+
+/// Periodically adds the accounts that depositors have created for keys that the wallet has issued. An
+/// AccountCreated event is emitted for each detected account, followed by an AccountChanged event once its vaults
+/// have been loaded.
+pub struct DepositAccountMonitor<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    notify: Notify<WalletEvent>,
+    account_monitor: AccountMonitorHandle,
+    config: DepositAccountsConfig,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> DepositAccountMonitor<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        notify: Notify<WalletEvent>,
+        account_monitor: AccountMonitorHandle,
+        config: DepositAccountsConfig,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            wallet_sdk,
+            notify,
+            account_monitor,
+            config,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let Some(interval) = self.config.scan_interval else {
+            // Deposit accounts are not detected
+            self.shutdown_signal.wait().await;
+            return Ok(());
+        };
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = interval.tick() => {
+                    if let Err(err) = self.on_tick().await {
+                        error!(target: LOG_TARGET, "Failed to detect deposit accounts: {}", err);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn on_tick(&self) -> Result<(), anyhow::Error> {
+        // The wallet store cannot be read until the wallet is unlocked
+        if self.wallet_sdk.is_locked() {
+            return Ok(());
+        }
+
+        let detected = self.wallet_sdk.account_recovery_api().detect_deposit_accounts().await?;
+        if detected.is_empty() {
+            debug!(target: LOG_TARGET, "No new deposit accounts detected");
+            return Ok(());
+        }
+
+        info!(target: LOG_TARGET, "📥 Detected {} new deposit account(s)", detected.len());
+        for deposit in detected {
+            let account = self
+                .wallet_sdk
+                .accounts_api()
+                .get_account_by_address(&deposit.address)?;
+            self.notify.notify(AccountCreatedEvent {
+                account,
+                created_by_tx: deposit.created_by_tx,
+            });
+
+            let is_updated = self.account_monitor.refresh_account(deposit.address.clone()).await?;
+            if is_updated {
+                self.notify.notify(AccountChangedEvent {
+                    account_address: deposit.address,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_dan_common_types::Epoch;
 use tari_dan_wallet_sdk::models::{Account, NewAccountInfo, TransactionStatus};
 use tari_engine_types::{commit_result::FinalizeResult, substate::SubstateId};
 use tari_template_lib::models::Amount;
@@ -11,6 +12,7 @@ pub enum WalletEvent {
     TransactionSubmitted(TransactionSubmittedEvent),
     TransactionFinalized(TransactionFinalizedEvent),
     TransactionInvalid(TransactionInvalidEvent),
+    TransactionExpired(TransactionExpiredEvent),
     AccountCreated(AccountCreatedEvent),
     AccountChanged(AccountChangedEvent),
     AuthLoginRequest(#[allow(dead_code)] AuthLoginRequestEvent),
@@ -40,6 +42,12 @@ impl From<TransactionInvalidEvent> for WalletEvent {
     }
 }
 
+impl From<TransactionExpiredEvent> for WalletEvent {
+    fn from(value: TransactionExpiredEvent) -> Self {
+        Self::TransactionExpired(value)
+    }
+}
+
 impl From<AuthLoginRequestEvent> for WalletEvent {
     fn from(value: AuthLoginRequestEvent) -> Self {
         Self::AuthLoginRequest(value)
@@ -87,5 +95,12 @@ pub struct TransactionInvalidEvent {
     pub final_fee: Option<Amount>,
 }
 
+#[derive(Debug, Clone)]
+pub struct TransactionExpiredEvent {
+    pub transaction_id: TransactionId,
+    pub max_epoch: Epoch,
+    pub current_epoch: Epoch,
+}
+
 #[derive(Debug, Clone)]
 pub struct AuthLoginRequestEvent;
@@ -0,0 +1,281 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use anyhow::anyhow;
+use log::*;
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    Epoch,
+};
+use tari_dan_wallet_sdk::{
+    models::{TransactionStatus, WalletTransaction},
+    network::{TransactionFinalizedResult, WalletNetworkInterface},
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+use tracing::{info_span, Instrument};
+
+use crate::{
+    config::TransactionExpiryConfig,
+    notify::Notify,
+    services::{sign_with_wallet_keys, TransactionExpiredEvent, TransactionServiceHandle, WalletEvent},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::transaction_expiry_monitor";
+
+/// Watches epoch progression and expires transactions that have not been finalized before their max_epoch. If
+/// enabled, expired transactions are rebuilt with new epoch bounds and resubmitted.
+pub struct TransactionExpiryMonitor<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    transaction_service: TransactionServiceHandle,
+    notify: Notify<WalletEvent>,
+    config: TransactionExpiryConfig,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> TransactionExpiryMonitor<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        transaction_service: TransactionServiceHandle,
+        notify: Notify<WalletEvent>,
+        config: TransactionExpiryConfig,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            wallet_sdk,
+            transaction_service,
+            notify,
+            config,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let mut poll_interval = time::interval(self.config.poll_interval);
+        poll_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = poll_interval.tick() => {
+                    if let Err(err) = self.on_poll().await {
+                        error!(target: LOG_TARGET, "Error checking for expired transactions: {}", err);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn on_poll(&self) -> Result<(), anyhow::Error> {
+        // Transactions cannot be updated or re-signed until the wallet is unlocked
+        if self.wallet_sdk.is_locked() {
+            return Ok(());
+        }
+
+        let current_epoch = self
+            .wallet_sdk
+            .get_network_interface()
+            .get_current_epoch()
+            .await
+            .map_err(|e| anyhow!("Failed to get the current epoch: {}", e))?;
+        let transaction_api = self.wallet_sdk.transaction_api();
+        let mut transactions = transaction_api.fetch_all(Some(TransactionStatus::Pending), None)?;
+        transactions.extend(transaction_api.fetch_all(Some(TransactionStatus::New), None)?);
+
+        for transaction in transactions {
+            let Some(max_epoch) = transaction.transaction.max_epoch() else {
+                continue;
+            };
+            if !is_expired(max_epoch, current_epoch) {
+                continue;
+            }
+            let transaction_id = *transaction.transaction.id();
+            if let Err(err) = self
+                .expire_transaction(transaction, max_epoch, current_epoch)
+                .instrument(info_span!("transaction", %transaction_id))
+                .await
+            {
+                error!(target: LOG_TARGET, "Error expiring transaction {}: {}", transaction_id, err);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn expire_transaction(
+        &self,
+        transaction: WalletTransaction,
+        max_epoch: Epoch,
+        current_epoch: Epoch,
+    ) -> Result<(), anyhow::Error> {
+        let transaction_id = *transaction.transaction.id();
+
+        // The transaction may have been finalized in or before its max_epoch without the wallet having seen the
+        // result yet. In that case the transaction service will pick up the result.
+        let result = self
+            .wallet_sdk
+            .get_network_interface()
+            .query_transaction_result(transaction_id)
+            .await
+            .optional()
+            .map_err(|e| anyhow!("Failed to query transaction result: {}", e))?;
+        if result.is_some_and(|r| matches!(r.result, TransactionFinalizedResult::Finalized { .. })) {
+            debug!(
+                target: LOG_TARGET,
+                "Transaction {} is past its max_epoch {} but has been finalized", transaction_id, max_epoch
+            );
+            return Ok(());
+        }
+
+        let transaction_api = self.wallet_sdk.transaction_api();
+        if transaction_api.mark_expired(transaction_id, current_epoch)?.is_none() {
+            return Ok(());
+        }
+        info!(
+            target: LOG_TARGET,
+            "⌛ Transaction {} expired (max_epoch = {}, current epoch = {})", transaction_id, max_epoch, current_epoch
+        );
+        self.notify.notify(TransactionExpiredEvent {
+            transaction_id,
+            max_epoch,
+            current_epoch,
+        });
+
+        if self.config.is_rebuild_enabled() {
+            self.rebuild_transaction(&transaction, current_epoch).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds an expired transaction with up-to-date inputs and epoch bounds starting at the current epoch, then
+    /// signs and resubmits it
+    async fn rebuild_transaction(
+        &self,
+        expired: &WalletTransaction,
+        current_epoch: Epoch,
+    ) -> Result<(), anyhow::Error> {
+        let transaction_id = *expired.transaction.id();
+        let transaction_api = self.wallet_sdk.transaction_api();
+        let failure = transaction_api.get_failure(transaction_id)?;
+        if !should_rebuild(&self.config, failure.attempt) {
+            warn!(
+                target: LOG_TARGET,
+                "Transaction {} expired after {} rebuild attempt(s). Giving up.", transaction_id, failure.attempt
+            );
+            return Ok(());
+        }
+
+        let mut rebuilt = transaction_api.rebuild_failed_transaction(transaction_id).await?;
+        let (min_epoch, max_epoch) = rebuilt_epoch_bounds(
+            self.config.rebuild_epoch_window,
+            expired.transaction.min_epoch(),
+            expired.transaction.max_epoch(),
+            current_epoch,
+        );
+        rebuilt.unsigned_transaction.min_epoch = min_epoch;
+        rebuilt.unsigned_transaction.max_epoch = Some(max_epoch);
+        let transaction = sign_with_wallet_keys(&self.wallet_sdk, rebuilt.unsigned_transaction, &rebuilt.signers)?;
+
+        info!(
+            target: LOG_TARGET,
+            "⌛ Rebuilding expired transaction {} as {} with max_epoch {} (attempt {}/{})",
+            transaction_id,
+            transaction.id(),
+            max_epoch,
+            failure.attempt + 1,
+            self.config.max_rebuild_attempts
+        );
+        let new_transaction_id = self
+            .transaction_service
+            .submit_transaction_with_opts(transaction, rebuilt.required_substates, rebuilt.new_account_info)
+            .await?;
+        transaction_api.mark_failure_retried(transaction_id, new_transaction_id)?;
+        Ok(())
+    }
+}
+
+/// A transaction is valid up to and including its max_epoch
+fn is_expired(max_epoch: Epoch, current_epoch: Epoch) -> bool {
+    max_epoch < current_epoch
+}
+
+/// Returns true if an expired transaction that has been rebuilt `attempt` times should be rebuilt again
+fn should_rebuild(config: &TransactionExpiryConfig, attempt: u32) -> bool {
+    config.is_rebuild_enabled() && attempt < config.max_rebuild_attempts
+}
+
+/// Returns the epoch bounds for a rebuilt transaction. The rebuilt transaction is valid for as many epochs as the
+/// expired transaction, starting at the current epoch.
+fn rebuilt_epoch_bounds(
+    rebuild_epoch_window: u64,
+    min_epoch: Option<Epoch>,
+    max_epoch: Option<Epoch>,
+    current_epoch: Epoch,
+) -> (Option<Epoch>, Epoch) {
+    let window = match (min_epoch, max_epoch) {
+        (Some(min_epoch), Some(max_epoch)) => max_epoch.saturating_sub(min_epoch).as_u64(),
+        _ => rebuild_epoch_window,
+    };
+    (
+        min_epoch.map(|_| current_epoch),
+        Epoch(current_epoch.as_u64().saturating_add(window)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_expires_transactions_after_the_max_epoch() {
+        assert!(!is_expired(Epoch(5), Epoch(4)));
+        // The transaction is still valid in its max_epoch
+        assert!(!is_expired(Epoch(5), Epoch(5)));
+        assert!(is_expired(Epoch(5), Epoch(6)));
+        assert!(is_expired(Epoch(0), Epoch(1)));
+    }
+
+    #[test]
+    fn it_does_not_rebuild_if_rebuilding_is_disabled() {
+        let config = TransactionExpiryConfig {
+            max_rebuild_attempts: 0,
+            ..Default::default()
+        };
+        assert!(!config.is_rebuild_enabled());
+        assert!(!should_rebuild(&config, 0));
+    }
+
+    #[test]
+    fn it_rebuilds_up_to_the_maximum_number_of_attempts() {
+        let config = TransactionExpiryConfig {
+            max_rebuild_attempts: 2,
+            ..Default::default()
+        };
+        assert!(should_rebuild(&config, 0));
+        assert!(should_rebuild(&config, 1));
+        assert!(!should_rebuild(&config, 2));
+    }
+
+    #[test]
+    fn it_keeps_the_epoch_window_of_the_expired_transaction() {
+        assert_eq!(
+            rebuilt_epoch_bounds(10, Some(Epoch(2)), Some(Epoch(5)), Epoch(8)),
+            (Some(Epoch(8)), Epoch(11))
+        );
+        assert_eq!(
+            rebuilt_epoch_bounds(10, None, Some(Epoch(5)), Epoch(8)),
+            (None, Epoch(18))
+        );
+    }
+}
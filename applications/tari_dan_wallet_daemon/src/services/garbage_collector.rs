@@ -0,0 +1,75 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_wallet_sdk::{network::WalletNetworkInterface, storage::WalletStore, DanWalletSdk};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::config::GarbageCollectionConfig;
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::garbage_collector";
+
+/// Periodically removes unreachable substate records and orphaned confidential proofs from the wallet store
+pub struct GarbageCollector<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    config: GarbageCollectionConfig,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> GarbageCollector<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        config: GarbageCollectionConfig,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            wallet_sdk,
+            config,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let Some(interval) = self.config.interval else {
+            // Garbage is only collected on request
+            self.shutdown_signal.wait().await;
+            return Ok(());
+        };
+        let mut interval = time::interval(interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        // Skip the immediate first tick
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = interval.tick() => {
+                    self.on_tick();
+                }
+            }
+        }
+    }
+
+    fn on_tick(&self) {
+        // The wallet store cannot be read until the wallet is unlocked
+        if self.wallet_sdk.is_locked() {
+            return;
+        }
+
+        if let Err(err) = self
+            .wallet_sdk
+            .garbage_collection_api()
+            .collect_garbage(self.config.unbound_proof_min_age, false)
+        {
+            error!(target: LOG_TARGET, "Garbage collection failed: {}", err);
+        }
+    }
+}
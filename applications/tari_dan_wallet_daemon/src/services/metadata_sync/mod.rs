@@ -0,0 +1,194 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Syncs wallet metadata (account names, contacts and transaction annotations) between devices that share a wallet
+//! seed, through a user-supplied WebDAV or S3 object. The metadata is encrypted with a key derived from the wallet
+//! seed, so the remote endpoint learns nothing but the size of the document. Keys are never synced: each device
+//! derives its own keys from the seed.
+
+mod remote;
+
+use blake2::Blake2b;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit},
+    Key,
+    XChaCha20Poly1305,
+    XNonce,
+};
+use digest::consts::U32;
+use log::*;
+use rand::rngs::OsRng;
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, tari_utilities::ByteArray};
+use tari_dan_wallet_sdk::{
+    apis::{
+        key_manager::{KeyManagerApiError, METADATA_SYNC_BRANCH},
+        metadata_sync::{MetadataSyncApiError, WalletMetadata},
+    },
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::{config::MetadataSyncConfig, services::metadata_sync::remote::RemoteMetadataStore};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::metadata_sync";
+
+/// Identifies the format of the encrypted document: the magic bytes, followed by the nonce and the ciphertext
+const DOCUMENT_MAGIC: &[u8] = b"TWM1";
+const NONCE_LEN: usize = 24;
+
+hash_domain!(
+    WalletMetadataSyncHashDomain,
+    "com.tari.dan.wallet_daemon.metadata_sync",
+    0
+);
+
+/// Periodically merges the local wallet metadata with the remote metadata document
+pub struct MetadataSyncService<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    config: MetadataSyncConfig,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> MetadataSyncService<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        config: MetadataSyncConfig,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            wallet_sdk,
+            config,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let Some(endpoint) = self.config.endpoint.clone() else {
+            self.shutdown_signal.wait().await;
+            return Ok(());
+        };
+        let remote = RemoteMetadataStore::new(endpoint);
+        let mut interval = time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = interval.tick() => {
+                    // The wallet store cannot be read until the wallet is unlocked
+                    if self.wallet_sdk.is_locked() {
+                        continue;
+                    }
+                    match self.sync(&remote).await {
+                        Ok(()) => {},
+                        // Another device synced between our read and write. The next sync merges its changes.
+                        Err(MetadataSyncError::RemoteChanged) => {
+                            debug!(target: LOG_TARGET, "Remote metadata changed during sync. Retrying later");
+                        },
+                        Err(err) => error!(target: LOG_TARGET, "Metadata sync failed: {}", err),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn sync(&self, remote: &RemoteMetadataStore) -> Result<(), MetadataSyncError> {
+        let cipher = self.cipher()?;
+        let remote_document = remote.get().await?;
+        let remote_metadata = match remote_document {
+            Some(ref document) => decrypt_metadata(&cipher, &document.data)?,
+            None => WalletMetadata::default(),
+        };
+
+        let metadata_api = self.wallet_sdk.metadata_sync_api();
+        let base = metadata_api.get_sync_base()?;
+        let local = metadata_api.export_metadata()?;
+        let merge = WalletMetadata::merge(&base, &local, &remote_metadata);
+        for conflict in &merge.conflicts {
+            warn!(
+                target: LOG_TARGET,
+                "Conflicting changes to {} '{}'. Keeping the local value", conflict.kind, conflict.key
+            );
+        }
+
+        // The remote is updated first, so that local changes are only applied once they are in the remote document
+        if remote_document.is_none() || merge.merged != remote_metadata {
+            let data = encrypt_metadata(&cipher, &merge.merged)?;
+            let etag = remote_document.as_ref().and_then(|document| document.etag.as_deref());
+            remote.put(data, etag).await?;
+        }
+        if merge.merged != local || merge.merged != base {
+            metadata_api.apply_metadata(&merge.merged)?;
+            info!(target: LOG_TARGET, "🔄 Synced wallet metadata ({} conflict(s))", merge.conflicts.len());
+        }
+        Ok(())
+    }
+
+    /// Returns the cipher for the metadata document. The key is derived from the wallet seed so that every device that
+    /// is restored from the same seed can decrypt the document.
+    fn cipher(&self) -> Result<XChaCha20Poly1305, MetadataSyncError> {
+        let key = self.wallet_sdk.key_manager_api().derive_key(METADATA_SYNC_BRANCH, 0)?;
+        let hash = DomainSeparatedHasher::<Blake2b<U32>, WalletMetadataSyncHashDomain>::new_with_label("cipher_key")
+            .chain(key.key.as_bytes())
+            .finalize();
+        Ok(XChaCha20Poly1305::new(Key::from_slice(hash.as_ref())))
+    }
+}
+
+fn encrypt_metadata(cipher: &XChaCha20Poly1305, metadata: &WalletMetadata) -> Result<Vec<u8>, MetadataSyncError> {
+    let plaintext = serde_json::to_vec(metadata)?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| MetadataSyncError::Encryption)?;
+    let mut data = Vec::with_capacity(DOCUMENT_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    data.extend_from_slice(DOCUMENT_MAGIC);
+    data.extend_from_slice(&nonce);
+    data.extend(ciphertext);
+    Ok(data)
+}
+
+fn decrypt_metadata(cipher: &XChaCha20Poly1305, data: &[u8]) -> Result<WalletMetadata, MetadataSyncError> {
+    let data = data
+        .strip_prefix(DOCUMENT_MAGIC)
+        .filter(|data| data.len() >= NONCE_LEN)
+        .ok_or(MetadataSyncError::InvalidDocument)?;
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    // Fails if the document was encrypted with another wallet seed or has been tampered with
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| MetadataSyncError::InvalidDocument)?;
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataSyncError {
+    #[error("The remote document was changed by another device during sync")]
+    RemoteChanged,
+    #[error("The remote document is not a metadata document for this wallet")]
+    InvalidDocument,
+    #[error("Failed to encrypt metadata")]
+    Encryption,
+    #[error("Invalid metadata sync endpoint '{url}'")]
+    InvalidEndpoint { url: String },
+    #[error("Remote request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Invalid header value: {0}")]
+    InvalidHeader(#[from] reqwest::header::InvalidHeaderValue),
+    #[error("Failed to encode metadata: {0}")]
+    Encoding(#[from] serde_json::Error),
+    #[error("Metadata sync API error: {0}")]
+    MetadataSyncApi(#[from] MetadataSyncApiError),
+    #[error("Key manager API error: {0}")]
+    KeyManagerApi(#[from] KeyManagerApiError),
+}
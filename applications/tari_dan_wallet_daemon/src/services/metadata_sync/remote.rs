@@ -0,0 +1,171 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION, ETAG, HOST, IF_MATCH, IF_NONE_MATCH},
+    Method,
+    RequestBuilder,
+    StatusCode,
+};
+use sha2::{Digest, Sha256};
+use tari_crypto::tari_utilities::hex::to_hex;
+
+use super::MetadataSyncError;
+use crate::config::MetadataSyncEndpoint;
+
+/// The encrypted metadata object and the version that it was read at
+pub struct RemoteDocument {
+    pub data: Vec<u8>,
+    pub etag: Option<String>,
+}
+
+/// Reads and writes the metadata object on a WebDAV or S3 endpoint. Writes are conditional on the object not having
+/// changed since it was read, so that concurrent syncs from different devices do not overwrite each other.
+pub struct RemoteMetadataStore {
+    client: reqwest::Client,
+    endpoint: MetadataSyncEndpoint,
+}
+
+impl RemoteMetadataStore {
+    pub fn new(endpoint: MetadataSyncEndpoint) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint,
+        }
+    }
+
+    /// Returns the remote document, or None if it has not been created yet
+    pub async fn get(&self) -> Result<Option<RemoteDocument>, MetadataSyncError> {
+        let response = self.request(Method::GET, &[], HeaderMap::new())?.send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|etag| etag.to_str().ok())
+            .map(ToString::to_string);
+        let data = response.bytes().await?.to_vec();
+        Ok(Some(RemoteDocument { data, etag }))
+    }
+
+    /// Writes the document if the remote object is still at `etag`, or does not exist if `etag` is None
+    pub async fn put(&self, data: Vec<u8>, etag: Option<&str>) -> Result<(), MetadataSyncError> {
+        let mut headers = HeaderMap::new();
+        match etag {
+            Some(etag) => headers.insert(IF_MATCH, HeaderValue::from_str(etag)?),
+            None => headers.insert(IF_NONE_MATCH, HeaderValue::from_static("*")),
+        };
+        let response = self.request(Method::PUT, &data, headers)?.body(data).send().await?;
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Err(MetadataSyncError::RemoteChanged);
+        }
+        response.error_for_status()?;
+        Ok(())
+    }
+
+    fn request(&self, method: Method, body: &[u8], headers: HeaderMap) -> Result<RequestBuilder, MetadataSyncError> {
+        match &self.endpoint {
+            MetadataSyncEndpoint::WebDav {
+                url,
+                username,
+                password,
+            } => {
+                let request = self.client.request(method, url.clone()).headers(headers);
+                match username {
+                    Some(username) => Ok(request.basic_auth(username, password.as_ref())),
+                    None => Ok(request),
+                }
+            },
+            MetadataSyncEndpoint::S3 {
+                url,
+                region,
+                access_key_id,
+                secret_access_key,
+            } => {
+                let host = match (url.host_str(), url.port()) {
+                    (Some(host), Some(port)) => format!("{}:{}", host, port),
+                    (Some(host), None) => host.to_string(),
+                    (None, _) => return Err(MetadataSyncError::InvalidEndpoint { url: url.to_string() }),
+                };
+                let signed = sign_s3_request(&S3Request {
+                    method: method.as_str(),
+                    host: &host,
+                    path: url.path(),
+                    body,
+                    region,
+                    access_key_id,
+                    secret_access_key,
+                    amz_date: &Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+                });
+                let mut headers = headers;
+                headers.insert(HOST, HeaderValue::from_str(&host)?);
+                headers.insert("x-amz-date", HeaderValue::from_str(&signed.amz_date)?);
+                headers.insert("x-amz-content-sha256", HeaderValue::from_str(&signed.content_sha256)?);
+                headers.insert(AUTHORIZATION, HeaderValue::from_str(&signed.authorization)?);
+                Ok(self.client.request(method, url.clone()).headers(headers))
+            },
+        }
+    }
+}
+
+struct S3Request<'a> {
+    method: &'a str,
+    host: &'a str,
+    path: &'a str,
+    body: &'a [u8],
+    region: &'a str,
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    /// The request time formatted as YYYYMMDD'T'HHMMSS'Z'
+    amz_date: &'a str,
+}
+
+struct SignedS3Request {
+    amz_date: String,
+    content_sha256: String,
+    authorization: String,
+}
+
+/// Signs a request without a query string with AWS signature version 4
+fn sign_s3_request(request: &S3Request<'_>) -> SignedS3Request {
+    const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+    let date = &request.amz_date[..8];
+    let content_sha256 = to_hex(&Sha256::digest(request.body));
+    let canonical_request = format!(
+        "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+        request.method, request.path, request.host, content_sha256, request.amz_date, SIGNED_HEADERS, content_sha256
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, request.region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        request.amz_date,
+        scope,
+        to_hex(&Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let signing_key = [request.region, "s3", "aws4_request"].iter().fold(
+        hmac_sha256(format!("AWS4{}", request.secret_access_key).as_bytes(), date.as_bytes()),
+        |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+    let signature = to_hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    SignedS3Request {
+        amz_date: request.amz_date.to_string(),
+        content_sha256,
+        authorization: format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            request.access_key_id, scope, SIGNED_HEADERS, signature
+        ),
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
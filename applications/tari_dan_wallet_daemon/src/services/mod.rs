@@ -7,6 +7,19 @@ pub use events::*;
 mod account_monitor;
 pub use account_monitor::AccountMonitorHandle;
 
+mod balance_auditor;
+pub use balance_auditor::{BalanceAuditorHandle, DEFAULT_AUDIT_VALUE_RANGE};
+
+mod deposit_monitor;
+
+mod expiry_monitor;
+
+mod garbage_collector;
+
+mod metadata_sync;
+
+mod scheduler;
+
 mod transaction_service;
 // -------------------------------- Spawn -------------------------------- //
 use anyhow::anyhow;
@@ -16,9 +29,21 @@ use tari_dan_wallet_sdk::{network::WalletNetworkInterface, storage::WalletStore,
 use tari_shutdown::ShutdownSignal;
 use tokio::{sync::oneshot, task::JoinHandle};
 use transaction_service::TransactionService;
-pub use transaction_service::TransactionServiceHandle;
+pub use transaction_service::{sign_with_wallet_keys, TransactionServiceHandle};
 
-use crate::{notify::Notify, services::account_monitor::AccountMonitor};
+use crate::{
+    config::WalletDaemonConfig,
+    notify::Notify,
+    services::{
+        account_monitor::AccountMonitor,
+        balance_auditor::BalanceAuditor,
+        deposit_monitor::DepositAccountMonitor,
+        expiry_monitor::TransactionExpiryMonitor,
+        garbage_collector::GarbageCollector,
+        metadata_sync::MetadataSyncService,
+        scheduler::TransactionScheduler,
+    },
+};
 
 type Reply<T> = oneshot::Sender<T>;
 
@@ -26,22 +51,82 @@ pub fn spawn_services<TStore, TNetworkInterface>(
     shutdown_signal: ShutdownSignal,
     notify: Notify<WalletEvent>,
     wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    config: &WalletDaemonConfig,
 ) -> Services
 where
     TStore: WalletStore + Clone + Send + Sync + 'static,
     TNetworkInterface: WalletNetworkInterface + Clone + Send + Sync + 'static,
     TNetworkInterface::Error: IsNotFoundError,
 {
-    let (transaction_service, transaction_service_handle) =
-        TransactionService::new(notify.clone(), wallet_sdk.clone(), shutdown_signal.clone());
+    let (transaction_service, transaction_service_handle) = TransactionService::new(
+        notify.clone(),
+        wallet_sdk.clone(),
+        config.fee_payer_strategy.clone(),
+        config.transaction_retry_policy.clone(),
+        shutdown_signal.clone(),
+    );
     let transaction_service_join_handle = tokio::spawn(transaction_service.run());
-    let (account_monitor, account_monitor_handle) = AccountMonitor::new(notify, wallet_sdk, shutdown_signal);
+    let (account_monitor, account_monitor_handle) =
+        AccountMonitor::new(notify.clone(), wallet_sdk.clone(), shutdown_signal.clone());
     let account_monitor_join_handle = tokio::spawn(account_monitor.run());
+    let deposit_monitor = DepositAccountMonitor::new(
+        wallet_sdk.clone(),
+        notify.clone(),
+        account_monitor_handle.clone(),
+        config.deposit_accounts.clone(),
+        shutdown_signal.clone(),
+    );
+    let deposit_monitor_join_handle = tokio::spawn(deposit_monitor.run());
+    let transaction_scheduler = TransactionScheduler::new(
+        wallet_sdk.clone(),
+        transaction_service_handle.clone(),
+        config.scheduler_poll_interval,
+        shutdown_signal.clone(),
+    );
+    let transaction_scheduler_join_handle = tokio::spawn(transaction_scheduler.run());
+    let expiry_monitor = TransactionExpiryMonitor::new(
+        wallet_sdk.clone(),
+        transaction_service_handle.clone(),
+        notify,
+        config.transaction_expiry.clone(),
+        shutdown_signal.clone(),
+    );
+    let expiry_monitor_join_handle = tokio::spawn(expiry_monitor.run());
+    let garbage_collector = GarbageCollector::new(
+        wallet_sdk.clone(),
+        config.garbage_collection.clone(),
+        shutdown_signal.clone(),
+    );
+    let garbage_collector_join_handle = tokio::spawn(garbage_collector.run());
+    let metadata_sync = MetadataSyncService::new(
+        wallet_sdk.clone(),
+        config.metadata_sync.clone(),
+        shutdown_signal.clone(),
+    );
+    let metadata_sync_join_handle = tokio::spawn(metadata_sync.run());
+    let (balance_auditor, balance_auditor_handle) = BalanceAuditor::new(
+        wallet_sdk,
+        config.value_lookup_table_file.clone(),
+        config.balance_audit_interval,
+        shutdown_signal,
+    );
+    let balance_auditor_join_handle = tokio::spawn(balance_auditor.run());
 
     Services {
         account_monitor_handle,
         transaction_service_handle,
-        services_fut: try_select_any([transaction_service_join_handle, account_monitor_join_handle]).boxed(),
+        balance_auditor_handle,
+        services_fut: try_select_any([
+            transaction_service_join_handle,
+            account_monitor_join_handle,
+            deposit_monitor_join_handle,
+            balance_auditor_join_handle,
+            transaction_scheduler_join_handle,
+            expiry_monitor_join_handle,
+            garbage_collector_join_handle,
+            metadata_sync_join_handle,
+        ])
+        .boxed(),
     }
 }
 
@@ -49,6 +134,7 @@ pub struct Services {
     pub services_fut: BoxFuture<'static, Result<(), anyhow::Error>>,
     pub account_monitor_handle: AccountMonitorHandle,
     pub transaction_service_handle: TransactionServiceHandle,
+    pub balance_auditor_handle: BalanceAuditorHandle,
 }
 
 async fn try_select_any<I>(handles: I) -> Result<(), anyhow::Error>
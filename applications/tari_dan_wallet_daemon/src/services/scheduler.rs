@@ -0,0 +1,192 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use log::*;
+use tari_dan_common_types::{optional::IsNotFoundError, Epoch, SubstateRequirement};
+use tari_dan_wallet_sdk::{
+    models::{ScheduledTransaction, ScheduledTransactionStatus, TransactionSchedule},
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+    DanWalletSdk,
+};
+use tari_engine_types::substate::SubstateId;
+use tari_shutdown::ShutdownSignal;
+use tari_transaction::{Transaction, TransactionId};
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::{handlers::transaction::get_referenced_substate_addresses, services::TransactionServiceHandle};
+
+const LOG_TARGET: &str = "tari::dan::wallet_daemon::transaction_scheduler";
+
+/// Builds, signs and submits scheduled transactions when they are due. Each submission is signed with the key of the
+/// fee account of the scheduled transaction.
+pub struct TransactionScheduler<TStore, TNetworkInterface> {
+    wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+    transaction_service: TransactionServiceHandle,
+    poll_interval: Duration,
+    shutdown_signal: ShutdownSignal,
+}
+
+impl<TStore, TNetworkInterface> TransactionScheduler<TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        transaction_service: TransactionServiceHandle,
+        poll_interval: Duration,
+        shutdown_signal: ShutdownSignal,
+    ) -> Self {
+        Self {
+            wallet_sdk,
+            transaction_service,
+            poll_interval,
+            shutdown_signal,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<(), anyhow::Error> {
+        let mut poll_interval = time::interval(self.poll_interval);
+        poll_interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = self.shutdown_signal.wait() => {
+                    break Ok(());
+                }
+
+                _ = poll_interval.tick() => {
+                    if let Err(err) = self.on_poll().await {
+                        error!(target: LOG_TARGET, "Error processing scheduled transactions: {}", err);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn on_poll(&self) -> Result<(), anyhow::Error> {
+        // Scheduled transactions cannot be read or signed until the wallet is unlocked
+        if self.wallet_sdk.is_locked() {
+            return Ok(());
+        }
+
+        let scheduled_api = self.wallet_sdk.scheduled_transactions_api();
+        let active = scheduled_api.get_all(Some(ScheduledTransactionStatus::Active))?;
+        if active.is_empty() {
+            return Ok(());
+        }
+
+        // Only query the network for the epoch if an epoch based schedule is active
+        let has_epoch_schedule = active
+            .iter()
+            .any(|scheduled| matches!(scheduled.schedule, TransactionSchedule::Epoch { .. }));
+        let current_epoch = if has_epoch_schedule {
+            match self.wallet_sdk.get_network_interface().get_current_epoch().await {
+                Ok(epoch) => Some(epoch),
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to get the current epoch. Epoch schedules are skipped: {}", err
+                    );
+                    None
+                },
+            }
+        } else {
+            None
+        };
+
+        let now = chrono::Utc::now().naive_utc();
+        for scheduled in active {
+            if !scheduled.is_due(now, current_epoch) {
+                continue;
+            }
+            self.run_scheduled(scheduled, current_epoch).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_scheduled(
+        &self,
+        scheduled: ScheduledTransaction,
+        current_epoch: Option<Epoch>,
+    ) -> Result<(), anyhow::Error> {
+        info!(
+            target: LOG_TARGET,
+            "⏰ Submitting scheduled transaction {} '{}' (run {})",
+            scheduled.id,
+            scheduled.name,
+            scheduled.num_runs + 1
+        );
+        let result = self.submit(&scheduled).await.map_err(|err| {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to submit scheduled transaction {} '{}': {}", scheduled.id, scheduled.name, err
+            );
+            err.to_string()
+        });
+
+        let updated = self.wallet_sdk.scheduled_transactions_api().record_run(
+            scheduled.id,
+            chrono::Utc::now().naive_utc(),
+            current_epoch,
+            result,
+        )?;
+        if !updated.is_active() {
+            info!(
+                target: LOG_TARGET,
+                "Scheduled transaction {} '{}' is {} after {} run(s)",
+                updated.id,
+                updated.name,
+                updated.status,
+                updated.num_runs
+            );
+        }
+        Ok(())
+    }
+
+    async fn submit(&self, scheduled: &ScheduledTransaction) -> Result<TransactionId, anyhow::Error> {
+        let account = self
+            .wallet_sdk
+            .accounts_api()
+            .get_account_by_address(&scheduled.fee_account.into())?;
+        if account.is_watch_only() {
+            return Err(anyhow!(
+                "Fee account {} is a watch-only account and cannot sign transactions",
+                account.address
+            ));
+        }
+        let key = self
+            .wallet_sdk
+            .key_manager_api()
+            .derive_key(account.key_branch(), account.key_index)?;
+
+        let mut substates = get_referenced_substate_addresses(&scheduled.instructions)?;
+        substates.insert(SubstateId::from(scheduled.fee_account));
+        let substates = substates.into_iter().collect::<Vec<_>>();
+        let loaded_substates = self
+            .wallet_sdk
+            .substate_api()
+            .locate_dependent_substates(&substates)
+            .await?;
+        let inputs = loaded_substates
+            .into_iter()
+            .chain(substates.into_iter().map(SubstateRequirement::unversioned))
+            .collect::<Vec<_>>();
+
+        let transaction = Transaction::builder()
+            .with_instructions(scheduled.instructions.clone())
+            .fee_transaction_pay_from_component(scheduled.fee_account, scheduled.max_fee)
+            .with_inputs(inputs)
+            .sign(&key.key)
+            .build();
+
+        let transaction_id = self.transaction_service.submit_transaction(transaction, vec![]).await?;
+        Ok(transaction_id)
+    }
+}
@@ -1,7 +1,13 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_wallet_sdk::apis::transaction::TransactionApiError;
+use tari_common_types::types::PublicKey;
+use tari_dan_wallet_sdk::apis::{
+    accounts::AccountsApiError,
+    key_manager::KeyManagerApiError,
+    substate::SubstateApiError,
+    transaction::TransactionApiError,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionServiceError {
@@ -11,4 +17,16 @@ pub enum TransactionServiceError {
     TransactionApiError(#[from] TransactionApiError),
     #[error("Dry run transaction failed: {details}")]
     DryRunTransactionFailed { details: String },
+    #[error("Accounts API error: {0}")]
+    AccountsApiError(#[from] AccountsApiError),
+    #[error("Key manager API error: {0}")]
+    KeyManagerApiError(#[from] KeyManagerApiError),
+    #[error("Substate API error: {0}")]
+    SubstateApiError(#[from] SubstateApiError),
+    #[error("No account has a Tari balance with which to pay fees")]
+    NoFundedFeeAccount,
+    #[error("The wallet does not hold the signing key {public_key}")]
+    SigningKeyNotFound { public_key: PublicKey },
+    #[error("Failed to detect transaction inputs: {details}")]
+    InputDetectionFailed { details: String },
 }
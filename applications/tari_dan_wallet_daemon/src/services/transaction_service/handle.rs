@@ -1,11 +1,17 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::collections::HashMap;
+
 use tari_dan_common_types::SubstateRequirement;
-use tari_dan_wallet_sdk::models::NewAccountInfo;
-use tari_engine_types::commit_result::ExecuteResult;
+use tari_dan_wallet_sdk::models::{Account, NewAccountInfo};
+use tari_engine_types::{
+    commit_result::ExecuteResult,
+    substate::{SubstateId, SubstateValue},
+};
 use tari_transaction::{Transaction, TransactionId};
 use tokio::sync::{mpsc, oneshot};
+use tracing::Span;
 
 use super::TransactionServiceError;
 use crate::services::Reply;
@@ -16,14 +22,23 @@ pub(super) enum TransactionServiceRequest {
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
         new_account_info: Option<NewAccountInfo>,
+        /// The span of the caller, so that the service logs within the same context
+        span: Span,
         reply: Reply<Result<TransactionId, TransactionServiceError>>,
     },
 
     SubmitDryRunTransaction {
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
+        span: Span,
         reply: Reply<Result<ExecuteResult, TransactionServiceError>>,
     },
+
+    SelectFeeAccount {
+        source_account: Option<Account>,
+        reply: Reply<Result<Account, TransactionServiceError>>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -61,12 +76,42 @@ impl TransactionServiceHandle {
         &self,
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
+    ) -> Result<ExecuteResult, TransactionServiceError> {
+        self.submit_dry_run_transaction_with_overrides(transaction, required_substates, HashMap::new())
+            .await
+    }
+
+    /// Executes a dry run of the transaction with the given substates replacing their network state
+    pub async fn submit_dry_run_transaction_with_overrides(
+        &self,
+        transaction: Transaction,
+        required_substates: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<ExecuteResult, TransactionServiceError> {
         let (reply_tx, reply_rx) = oneshot::channel();
         self.sender
             .send(TransactionServiceRequest::SubmitDryRunTransaction {
                 transaction,
                 required_substates,
+                substate_overrides,
+                span: Span::current(),
+                reply: reply_tx,
+            })
+            .await
+            .map_err(|_| TransactionServiceError::ServiceShutdown)?;
+        reply_rx.await.map_err(|_| TransactionServiceError::ServiceShutdown)?
+    }
+
+    /// Selects the account that pays the fees for a transaction using the configured fee payer strategy. The source
+    /// account is the account that the transaction spends from, if any.
+    pub async fn select_fee_account(
+        &self,
+        source_account: Option<Account>,
+    ) -> Result<Account, TransactionServiceError> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(TransactionServiceRequest::SelectFeeAccount {
+                source_account,
                 reply: reply_tx,
             })
             .await
@@ -86,6 +131,7 @@ impl TransactionServiceHandle {
                 transaction,
                 required_substates,
                 new_account_info,
+                span: Span::current(),
                 reply: reply_tx,
             })
             .await
@@ -4,7 +4,9 @@
 mod error;
 mod handle;
 mod service;
+mod signing;
 
 pub use error::*;
 pub use handle::*;
 pub(super) use service::*;
+pub use signing::sign_with_wallet_keys;
@@ -1,30 +1,36 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{sync::Arc, time::Duration};
+use std::{str::FromStr, sync::Arc, time::Duration};
 
 use log::*;
 use tari_dan_common_types::{optional::IsNotFoundError, SubstateRequirement};
 use tari_dan_wallet_sdk::{
-    models::{NewAccountInfo, TransactionStatus},
+    apis::accounts::AccountsApiError,
+    models::{Account, FeePayerStrategy, NewAccountInfo, TransactionStatus},
     network::WalletNetworkInterface,
     storage::WalletStore,
     DanWalletSdk,
 };
-use tari_engine_types::commit_result::ExecuteResult;
+use tari_engine_types::{commit_result::ExecuteResult, substate::SubstateId};
 use tari_shutdown::ShutdownSignal;
+use tari_template_lib::prelude::ComponentAddress;
 use tari_transaction::{Transaction, TransactionId};
 use tokio::{
     sync::{mpsc, watch, Semaphore},
     time,
     time::MissedTickBehavior,
 };
+use tracing::{info_span, Instrument};
 
 use super::{
     error::TransactionServiceError,
     handle::{TransactionServiceHandle, TransactionServiceRequest},
+    signing::sign_with_wallet_keys,
 };
 use crate::{
+    config::TransactionRetryPolicy,
+    handlers::transaction::get_referenced_substate_addresses,
     notify::Notify,
     services::{TransactionFinalizedEvent, TransactionInvalidEvent, TransactionSubmittedEvent, WalletEvent},
 };
@@ -38,6 +44,10 @@ pub struct TransactionService<TStore, TNetworkInterface> {
     trigger_poll: watch::Sender<()>,
     rx_trigger: watch::Receiver<()>,
     poll_semaphore: Arc<Semaphore>,
+    fee_payer_strategy: FeePayerStrategy,
+    /// The number of fee accounts selected using the round-robin strategy
+    round_robin_count: usize,
+    retry_policy: TransactionRetryPolicy,
     shutdown_signal: ShutdownSignal,
 }
 
@@ -50,6 +60,8 @@ where
     pub fn new(
         notify: Notify<WalletEvent>,
         wallet_sdk: DanWalletSdk<TStore, TNetworkInterface>,
+        fee_payer_strategy: FeePayerStrategy,
+        retry_policy: TransactionRetryPolicy,
         shutdown_signal: ShutdownSignal,
     ) -> (Self, TransactionServiceHandle) {
         let (trigger, rx_trigger) = watch::channel(());
@@ -61,6 +73,9 @@ where
             trigger_poll: trigger,
             rx_trigger,
             poll_semaphore: Arc::new(Semaphore::new(1)),
+            fee_payer_strategy,
+            round_robin_count: 0,
+            retry_policy,
             shutdown_signal,
         };
 
@@ -72,6 +87,12 @@ where
         let mut poll_interval = time::interval(Duration::from_secs(10));
         poll_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
+        if self.retry_policy.is_enabled() {
+            if let Err(err) = self.resume_pending_retries() {
+                error!(target: LOG_TARGET, "Error resuming pending transaction retries: {}", err);
+            }
+        }
+
         loop {
             tokio::select! {
                 _ = self.shutdown_signal.wait() => {
@@ -101,17 +122,19 @@ where
         }
     }
 
-    async fn handle_request(&self, request: TransactionServiceRequest) -> Result<(), TransactionServiceError> {
+    async fn handle_request(&mut self, request: TransactionServiceRequest) -> Result<(), TransactionServiceError> {
         match request {
             TransactionServiceRequest::SubmitTransaction {
                 transaction,
                 required_substates,
                 new_account_info,
+                span,
                 reply,
             } => {
                 reply
                     .send(
                         self.handle_submit_transaction(transaction, required_substates, new_account_info)
+                            .instrument(span)
                             .await,
                     )
                     .map_err(|_| TransactionServiceError::ServiceShutdown)?;
@@ -119,12 +142,15 @@ where
             TransactionServiceRequest::SubmitDryRunTransaction {
                 transaction,
                 required_substates,
+                substate_overrides,
+                span,
                 reply,
             } => {
                 let transaction_id = *transaction.id();
                 let transaction_api = self.wallet_sdk.transaction_api();
                 match transaction_api
-                    .submit_dry_run_transaction(transaction, required_substates)
+                    .submit_dry_run_transaction(transaction, required_substates, substate_overrides)
+                    .instrument(span)
                     .await
                 {
                     Ok(finalized_transaction) => {
@@ -140,6 +166,7 @@ where
                             .send(finalize.map(|finalize| ExecuteResult {
                                 finalize,
                                 execution_time: finalized_transaction.execution_time.unwrap_or_default(),
+                                substate_access_stats: None,
                             }))
                             .map_err(|_| TransactionServiceError::ServiceShutdown)?;
                     },
@@ -154,10 +181,49 @@ where
                     },
                 }
             },
+            TransactionServiceRequest::SelectFeeAccount { source_account, reply } => {
+                reply
+                    .send(self.select_fee_account(source_account))
+                    .map_err(|_| TransactionServiceError::ServiceShutdown)?;
+            },
         }
         Ok(())
     }
 
+    fn select_fee_account(&mut self, source_account: Option<Account>) -> Result<Account, TransactionServiceError> {
+        let accounts_api = self.wallet_sdk.accounts_api();
+        let account = match self.fee_payer_strategy {
+            FeePayerStrategy::SameAsSource => match source_account {
+                Some(account) => account,
+                None => accounts_api.get_default()?,
+            },
+            FeePayerStrategy::Designated(ref name_or_address) => match ComponentAddress::from_str(name_or_address) {
+                Ok(address) => accounts_api.get_account_by_address(&SubstateId::Component(address))?,
+                Err(_) => accounts_api.get_account_by_name(name_or_address)?,
+            },
+            FeePayerStrategy::RoundRobin => {
+                let funded = accounts_api.get_fee_funded_accounts()?;
+                if funded.is_empty() {
+                    return Err(TransactionServiceError::NoFundedFeeAccount);
+                }
+                let account = funded[self.round_robin_count % funded.len()].clone();
+                self.round_robin_count = self.round_robin_count.wrapping_add(1);
+                account
+            },
+        };
+        if account.is_watch_only() {
+            return Err(AccountsApiError::WatchOnlyAccount {
+                address: account.address,
+            }
+            .into());
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Selected fee account {} ({:?} strategy)", account.address, self.fee_payer_strategy
+        );
+        Ok(account)
+    }
+
     async fn handle_submit_transaction(
         &self,
         transaction: Transaction,
@@ -177,6 +243,11 @@ where
     }
 
     async fn on_poll(&self) -> Result<(), TransactionServiceError> {
+        if self.wallet_sdk.is_locked() {
+            debug!(target: LOG_TARGET, "Wallet is locked, skipping poll");
+            return Ok(());
+        }
+
         let permit = match self.poll_semaphore.clone().try_acquire_owned() {
             Ok(permit) => permit,
             Err(_) => {
@@ -187,11 +258,12 @@ where
 
         let wallet_sdk = self.wallet_sdk.clone();
         let notify = self.notify.clone();
+        let retry_policy = self.retry_policy.clone();
         tokio::spawn(async move {
             if let Err(err) = Self::resubmit_new_transactions(&wallet_sdk, &notify).await {
                 error!(target: LOG_TARGET, "Error re-submitting new transactions: {}", err);
             }
-            if let Err(err) = Self::check_pending_transactions(&wallet_sdk, &notify).await {
+            if let Err(err) = Self::check_pending_transactions(&wallet_sdk, &notify, &retry_policy).await {
                 error!(target: LOG_TARGET, "Error checking pending transactions: {}", err);
             }
 
@@ -218,13 +290,10 @@ where
             new_transactions.len()
         );
         for transaction in new_transactions {
-            info!(
-                target: LOG_TARGET,
-                "Resubmitting transaction {}",
-                transaction.transaction.id()
-            );
             let transaction_id = *transaction.transaction.id();
-            transaction_api.submit_transaction(transaction_id).await?;
+            let span = info_span!("transaction", %transaction_id);
+            span.in_scope(|| info!(target: LOG_TARGET, "Resubmitting transaction {}", transaction_id));
+            transaction_api.submit_transaction(transaction_id).instrument(span).await?;
             notify.notify(TransactionSubmittedEvent {
                 transaction_id,
                 new_account: transaction.new_account_info,
@@ -236,6 +305,7 @@ where
     async fn check_pending_transactions(
         wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
         notify: &Notify<WalletEvent>,
+        retry_policy: &TransactionRetryPolicy,
     ) -> Result<(), TransactionServiceError> {
         let transaction_api = wallet_sdk.transaction_api();
         let pending_transactions = transaction_api.fetch_all(Some(TransactionStatus::Pending), None)?;
@@ -251,49 +321,210 @@ where
             pending_transactions.len()
         );
         for transaction in pending_transactions {
-            info!(
-                target: LOG_TARGET,
-                "Requesting result for transaction {}",
-                transaction.transaction.id()
-            );
-            let maybe_finalized_transaction = transaction_api
-                .check_and_store_finalized_transaction(*transaction.transaction.id())
+            let transaction_id = *transaction.transaction.id();
+            Self::check_pending_transaction(wallet_sdk, notify, retry_policy, transaction_id)
+                .instrument(info_span!("transaction", %transaction_id))
                 .await?;
+        }
+        Ok(())
+    }
 
-            match maybe_finalized_transaction {
-                Some(transaction) => {
-                    debug!(
-                        target: LOG_TARGET,
-                        "Transaction {} has been finalized: {}",
-                        transaction.transaction.id(),
-                        transaction.status,
-                    );
-                    match transaction.finalize {
-                        Some(finalize) => {
-                            notify.notify(TransactionFinalizedEvent {
-                                transaction_id: *transaction.transaction.id(),
-                                finalize,
-                                final_fee: transaction.final_fee.unwrap_or_default(),
-                                status: transaction.status,
-                            });
-                        },
-                        None => notify.notify(TransactionInvalidEvent {
+    async fn check_pending_transaction(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+        retry_policy: &TransactionRetryPolicy,
+        transaction_id: TransactionId,
+    ) -> Result<(), TransactionServiceError> {
+        info!(target: LOG_TARGET, "Requesting result for transaction {}", transaction_id);
+        let maybe_finalized_transaction = wallet_sdk
+            .transaction_api()
+            .check_and_store_finalized_transaction(transaction_id)
+            .await?;
+
+        match maybe_finalized_transaction {
+            Some(transaction) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Transaction {} has been finalized: {}",
+                    transaction.transaction.id(),
+                    transaction.status,
+                );
+                let is_input_conflict = transaction
+                    .finalize
+                    .as_ref()
+                    .and_then(|f| f.full_reject())
+                    .is_some_and(|reason| reason.is_input_conflict());
+                if is_input_conflict && retry_policy.is_enabled() {
+                    let transaction_id = *transaction.transaction.id();
+                    if let Err(err) = Self::schedule_retry(wallet_sdk, notify, retry_policy, transaction_id) {
+                        error!(target: LOG_TARGET, "Error scheduling retry for transaction {}: {}", transaction_id, err);
+                    }
+                }
+
+                match transaction.finalize {
+                    Some(finalize) => {
+                        notify.notify(TransactionFinalizedEvent {
                             transaction_id: *transaction.transaction.id(),
+                            finalize,
+                            final_fee: transaction.final_fee.unwrap_or_default(),
                             status: transaction.status,
-                            finalize: transaction.finalize,
-                            final_fee: transaction.final_fee,
-                        }),
-                    }
-                },
-                None => {
-                    debug!(
-                        target: LOG_TARGET,
-                        "Transaction {} is still pending",
-                        transaction.transaction.hash()
-                    );
-                },
+                        });
+                    },
+                    None => notify.notify(TransactionInvalidEvent {
+                        transaction_id: *transaction.transaction.id(),
+                        status: transaction.status,
+                        finalize: transaction.finalize,
+                        final_fee: transaction.final_fee,
+                    }),
+                }
+            },
+            None => {
+                debug!(target: LOG_TARGET, "Transaction {} is still pending", transaction_id);
+            },
+        }
+        Ok(())
+    }
+
+    /// Schedules retries for transactions that were rejected due to an input conflict but not yet retried when the wallet
+    /// daemon was stopped
+    fn resume_pending_retries(&self) -> Result<(), TransactionServiceError> {
+        let transaction_api = self.wallet_sdk.transaction_api();
+        for failure in transaction_api.get_failures(false)? {
+            let transaction = transaction_api.get(failure.transaction_id)?;
+            let is_input_conflict = transaction
+                .finalize
+                .as_ref()
+                .and_then(|f| f.full_reject())
+                .is_some_and(|reason| reason.is_input_conflict());
+            if !is_input_conflict {
+                continue;
+            }
+            info!(
+                target: LOG_TARGET,
+                "Resuming retry of transaction {} (attempt {})",
+                failure.transaction_id,
+                failure.attempt + 1
+            );
+            Self::schedule_retry(
+                &self.wallet_sdk,
+                &self.notify,
+                &self.retry_policy,
+                failure.transaction_id,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Schedules a retry of a transaction that was rejected due to an input conflict, unless the retry policy's
+    /// maximum number of attempts has been reached
+    fn schedule_retry(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+        retry_policy: &TransactionRetryPolicy,
+        transaction_id: TransactionId,
+    ) -> Result<(), TransactionServiceError> {
+        let failure = wallet_sdk.transaction_api().get_failure(transaction_id)?;
+        if failure.attempt >= retry_policy.max_attempts {
+            warn!(
+                target: LOG_TARGET,
+                "Transaction {} was rejected due to an input conflict after {} retry attempt(s). Giving up.",
+                transaction_id,
+                failure.attempt
+            );
+            return Ok(());
+        }
+
+        let attempt = failure.attempt + 1;
+        let delay = retry_policy.backoff(attempt);
+        info!(
+            target: LOG_TARGET,
+            "🔁 Transaction {} was rejected due to an input conflict ({}). Retrying in {:.2?} (attempt {}/{})",
+            transaction_id,
+            failure.reason,
+            delay,
+            attempt,
+            retry_policy.max_attempts
+        );
+
+        let wallet_sdk = wallet_sdk.clone();
+        let notify = notify.clone();
+        tokio::spawn(
+            async move {
+                time::sleep(delay).await;
+                if let Err(err) = Self::retry_transaction(&wallet_sdk, &notify, transaction_id).await {
+                    error!(target: LOG_TARGET, "Error retrying transaction {}: {}", transaction_id, err);
+                }
             }
+            .instrument(info_span!("transaction", %transaction_id)),
+        );
+        Ok(())
+    }
+
+    /// Rebuilds a failed transaction with the latest inputs known to the wallet, then signs and resubmits it
+    async fn retry_transaction(
+        wallet_sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+        notify: &Notify<WalletEvent>,
+        transaction_id: TransactionId,
+    ) -> Result<(), TransactionServiceError> {
+        let transaction_api = wallet_sdk.transaction_api();
+        let failure = transaction_api.get_failure(transaction_id)?;
+        if let Some(retried_as) = failure.retried_as {
+            debug!(
+                target: LOG_TARGET,
+                "Transaction {} has already been retried as {}", transaction_id, retried_as
+            );
+            return Ok(());
         }
+        let failed = transaction_api.get(transaction_id)?;
+
+        let mut substates = get_referenced_substate_addresses(failed.transaction.instructions())
+            .map_err(|e| TransactionServiceError::InputDetectionFailed { details: e.to_string() })?;
+        substates.extend(
+            get_referenced_substate_addresses(failed.transaction.fee_instructions())
+                .map_err(|e| TransactionServiceError::InputDetectionFailed { details: e.to_string() })?,
+        );
+        // Include any inputs of the failed transaction that are not referenced by the instructions
+        substates.extend(
+            failed
+                .transaction
+                .all_inputs_iter()
+                .map(|input| input.into_substate_id()),
+        );
+        let substates = substates.into_iter().collect::<Vec<_>>();
+        let located = wallet_sdk.substate_api().locate_dependent_substates(&substates).await?;
+
+        let (mut unsigned_transaction, signatures, _) = failed.transaction.into_parts();
+        // Inputs are deduplicated by substate id, so the unversioned inputs are only added if no version was located
+        unsigned_transaction.inputs = located
+            .into_iter()
+            .chain(substates.into_iter().map(SubstateRequirement::unversioned))
+            .collect();
+        let signers = signatures.iter().map(|s| s.public_key().clone()).collect::<Vec<_>>();
+        let transaction = sign_with_wallet_keys(wallet_sdk, unsigned_transaction, &signers)?;
+        let new_transaction_id = *transaction.id();
+
+        info!(
+            target: LOG_TARGET,
+            "🔁 Retrying transaction {} as {} (attempt {})",
+            transaction_id,
+            new_transaction_id,
+            failure.attempt + 1
+        );
+        // Versions for the required substates are filled in by the network when the transaction is submitted
+        let required_substates = failed
+            .required_substates
+            .into_iter()
+            .map(|r| SubstateRequirement::unversioned(r.into_substate_id()))
+            .collect();
+        transaction_api
+            .insert_new_transaction(transaction, required_substates, failed.new_account_info.clone(), false)
+            .await?;
+        transaction_api.mark_failure_retried(transaction_id, new_transaction_id)?;
+        transaction_api.submit_transaction(new_transaction_id).await?;
+        notify.notify(TransactionSubmittedEvent {
+            transaction_id: new_transaction_id,
+            new_account: failed.new_account_info,
+        });
         Ok(())
     }
 
@@ -303,6 +534,7 @@ where
                 let _ = self.trigger_poll.send(());
             },
             WalletEvent::TransactionInvalid(_) |
+            WalletEvent::TransactionExpired(_) |
             WalletEvent::TransactionFinalized(_) |
             WalletEvent::AccountChanged(_) |
             WalletEvent::AuthLoginRequest(_) |
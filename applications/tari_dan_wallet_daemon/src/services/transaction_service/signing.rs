@@ -0,0 +1,41 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::optional::IsNotFoundError;
+use tari_dan_wallet_sdk::{apis::key_manager, network::WalletNetworkInterface, storage::WalletStore, DanWalletSdk};
+use tari_transaction::{Transaction, UnsignedTransaction};
+
+use super::TransactionServiceError;
+
+/// Signs the transaction with the wallet transaction or imported keys that correspond to each of the given public keys.
+/// This is used to re-sign a rebuilt transaction with the same keys that signed the original.
+pub fn sign_with_wallet_keys<TStore, TNetworkInterface>(
+    sdk: &DanWalletSdk<TStore, TNetworkInterface>,
+    unsigned_transaction: UnsignedTransaction,
+    signers: &[PublicKey],
+) -> Result<Transaction, TransactionServiceError>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    let key_api = sdk.key_manager_api();
+    let keys = key_api.get_all_keys(key_manager::TRANSACTION_BRANCH)?;
+    let mut builder = Transaction::builder().with_unsigned_transaction(unsigned_transaction);
+    for signer in signers {
+        let key = match keys.iter().find(|(_, pk, _)| pk == signer) {
+            Some((index, _, _)) => key_api.derive_key(key_manager::TRANSACTION_BRANCH, *index)?,
+            None => {
+                let index = key_api.get_imported_key_index(signer)?.ok_or_else(|| {
+                    TransactionServiceError::SigningKeyNotFound {
+                        public_key: signer.clone(),
+                    }
+                })?;
+                key_api.derive_key(key_manager::IMPORTED_KEY_BRANCH, index)?
+            },
+        };
+        builder = builder.sign(&key.key);
+    }
+    Ok(builder.build())
+}
@@ -0,0 +1,48 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Per-request trace IDs. Each JSON-RPC request is handled within the scope of a trace ID, which is either provided by
+//! the client in the `x-request-id` header or generated by the wallet daemon. The trace ID is returned in the response
+//! headers, included in the logs and sent along with requests to the indexer so that a specific user action can be
+//! followed across services.
+
+use std::future::Future;
+
+pub use tari_indexer_client::json_rpc_client::TRACE_ID_HEADER;
+
+/// Client provided trace IDs that are longer than this are replaced with a generated ID
+const MAX_TRACE_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Returns the trace ID of the request that is currently being handled, if any
+pub fn current() -> Option<String> {
+    TRACE_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Runs the future within the scope of the given trace ID
+pub async fn scope<F: Future>(trace_id: String, fut: F) -> F::Output {
+    TRACE_ID.scope(trace_id, fut).await
+}
+
+/// Returns the client provided trace ID if it is valid, otherwise a newly generated trace ID
+pub fn from_client_or_generate(client_trace_id: Option<&str>) -> String {
+    match client_trace_id {
+        Some(id) if is_valid(id) => id.to_string(),
+        _ => generate(),
+    }
+}
+
+fn generate() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+fn is_valid(trace_id: &str) -> bool {
+    !trace_id.is_empty() &&
+        trace_id.len() <= MAX_TRACE_ID_LEN &&
+        trace_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
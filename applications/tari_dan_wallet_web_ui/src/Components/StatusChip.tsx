@@ -38,6 +38,7 @@ const colorList: Record<string, string> = {
   Rejected: "#DB7E7E",
   InvalidTransaction: "#DB7E7E",
   OnlyFeeAccepted: "#FFA500",
+  Expired: "#9E9E9E",
 };
 
 export default function StatusChip({ status, showTitle = true }: StatusChipProps) {
@@ -50,6 +51,7 @@ export default function StatusChip({ status, showTitle = true }: StatusChipProps
     New: <IoDiamondOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     Rejected: <IoCloseOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     InvalidTransaction: <IoCloseOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
+    Expired: <IoHourglassOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />,
     OnlyFeeAccepted: (
       <>
         <IoCheckmarkOutline style={{ height: 14, width: 14 }} color={theme.palette.background.paper} />
@@ -109,6 +109,7 @@ export const useAccountsTransfer = (
         input_selection,
         output_to_revealed,
         dry_run,
+        fee_account: null,
       };
       if (isConfidential) {
         return accountsConfidentialTransfer(transferRequest);
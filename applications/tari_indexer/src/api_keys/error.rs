@@ -0,0 +1,28 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use tari_dan_storage::StorageError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyError {
+    #[error("An API key is required")]
+    MissingApiKey,
+    #[error("Invalid or revoked API key")]
+    InvalidApiKey,
+    #[error("Method '{method}' is not allowed for this API key")]
+    MethodNotAllowed { method: String },
+    #[error("Rate limit exceeded. Retry after {retry_after:.2?}")]
+    RateLimited { retry_after: Duration },
+    #[error("API key management is disabled because no admin key is configured")]
+    AdminDisabled,
+    #[error("API key '{key_id}' not found")]
+    NotFound { key_id: String },
+    #[error("Invalid request: {details}")]
+    InvalidRequest { details: String },
+    #[error("Storage error: {0}")]
+    StorageError(#[from] StorageError),
+    #[error("Invalid allowed methods for API key: {0}")]
+    InvalidAllowedMethods(#[from] serde_json::Error),
+}
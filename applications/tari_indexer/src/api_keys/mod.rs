@@ -0,0 +1,360 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! API keys for the JSON-RPC API. When enabled, every request must include an issued key in the `x-api-key` header.
+//! Each key may be restricted to a set of methods and a number of requests per minute. Keys are managed with the
+//! `api_keys.*` methods, which require the admin key from the config.
+
+mod error;
+mod rate_limiter;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use blake2::{
+    digest::{consts::U32, Digest},
+    Blake2b,
+};
+pub use error::ApiKeyError;
+use log::*;
+use rand::{rngs::OsRng, RngCore};
+use rate_limiter::RateLimiter;
+use tari_crypto::tari_utilities::hex::to_hex;
+use tari_indexer_client::types::{ApiKeyInfo, ApiKeyUsage};
+use tari_shutdown::ShutdownSignal;
+use tokio::time;
+
+use crate::{
+    config::ApiKeysConfig,
+    substate_storage_sqlite::{
+        models::api_key::{ApiKey, NewApiKey},
+        sqlite_substate_store_factory::{
+            SqliteSubstateStore,
+            SubstateStore,
+            SubstateStoreReadTransaction,
+            SubstateStoreWriteTransaction,
+        },
+    },
+};
+
+const LOG_TARGET: &str = "tari::indexer::api_keys";
+
+/// Prefix of all issued API keys, so that they are easy to identify
+const API_KEY_PREFIX: &str = "tidx_";
+/// Methods with this prefix manage API keys. They require the admin key and cannot be granted to an API key.
+const ADMIN_METHOD_PREFIX: &str = "api_keys.";
+
+pub struct ApiKeyManager {
+    config: ApiKeysConfig,
+    admin_key_hash: Option<String>,
+    store: SqliteSubstateStore,
+    /// Unrevoked keys indexed by the hash of the secret key
+    keys: Mutex<HashMap<String, ActiveApiKey>>,
+}
+
+struct ActiveApiKey {
+    key_id: String,
+    allowed_methods: Option<HashSet<String>>,
+    rate_limiter: Option<RateLimiter>,
+    rate_limited_requests: u64,
+    denied_requests: u64,
+    /// Requests that have not yet been written to the database
+    unflushed_requests: u64,
+    last_used_at: Option<u64>,
+}
+
+impl ApiKeyManager {
+    /// Loads all unrevoked keys from the store
+    pub fn load(config: ApiKeysConfig, store: SqliteSubstateStore) -> Result<Self, ApiKeyError> {
+        let now = Instant::now();
+        let mut keys = HashMap::new();
+        for key in store.with_read_tx(|tx| tx.get_all_api_keys())? {
+            if key.is_revoked() {
+                continue;
+            }
+            let active = ActiveApiKey {
+                key_id: key.key_id.clone(),
+                allowed_methods: parse_allowed_methods(key.allowed_methods.as_deref())?
+                    .map(|m| m.into_iter().collect()),
+                rate_limiter: rate_limit_for(&config, key.rate_limit_per_minute)
+                    .map(|limit| RateLimiter::per_minute(limit, now)),
+                rate_limited_requests: 0,
+                denied_requests: 0,
+                unflushed_requests: 0,
+                last_used_at: None,
+            };
+            keys.insert(key.secret_hash, active);
+        }
+
+        if config.enabled {
+            info!(target: LOG_TARGET, "🔑 API keys enabled with {} active key(s)", keys.len());
+        }
+
+        Ok(Self {
+            admin_key_hash: config.admin_key.as_deref().map(hash_secret),
+            config,
+            store,
+            keys: Mutex::new(keys),
+        })
+    }
+
+    /// Checks that the API key may call the method and counts the request against the key's usage
+    pub fn authorize(&self, api_key: Option<&str>, method: &str) -> Result<(), ApiKeyError> {
+        if method.starts_with(ADMIN_METHOD_PREFIX) {
+            return self.authorize_admin(api_key);
+        }
+
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let api_key = api_key.ok_or(ApiKeyError::MissingApiKey)?;
+        let secret_hash = hash_secret(api_key);
+        let mut keys = self.keys.lock().unwrap();
+        let key = keys.get_mut(&secret_hash).ok_or(ApiKeyError::InvalidApiKey)?;
+
+        if key
+            .allowed_methods
+            .as_ref()
+            .is_some_and(|allowed| !allowed.contains(method))
+        {
+            key.denied_requests += 1;
+            return Err(ApiKeyError::MethodNotAllowed {
+                method: method.to_string(),
+            });
+        }
+
+        if let Some(ref mut rate_limiter) = key.rate_limiter {
+            if let Err(retry_after) = rate_limiter.try_acquire(Instant::now()) {
+                key.rate_limited_requests += 1;
+                return Err(ApiKeyError::RateLimited { retry_after });
+            }
+        }
+
+        key.unflushed_requests += 1;
+        key.last_used_at = Some(unix_now());
+        Ok(())
+    }
+
+    fn authorize_admin(&self, api_key: Option<&str>) -> Result<(), ApiKeyError> {
+        let admin_key_hash = self.admin_key_hash.as_ref().ok_or(ApiKeyError::AdminDisabled)?;
+        let api_key = api_key.ok_or(ApiKeyError::MissingApiKey)?;
+        if hash_secret(api_key) != *admin_key_hash {
+            return Err(ApiKeyError::InvalidApiKey);
+        }
+        Ok(())
+    }
+
+    /// Issues a new API key. The returned secret key is not stored and cannot be retrieved again.
+    pub fn issue(
+        &self,
+        name: String,
+        allowed_methods: Option<Vec<String>>,
+        rate_limit_per_minute: Option<u32>,
+    ) -> Result<(String, ApiKeyInfo), ApiKeyError> {
+        if let Some(admin_method) = allowed_methods
+            .iter()
+            .flatten()
+            .find(|m| m.starts_with(ADMIN_METHOD_PREFIX))
+        {
+            return Err(ApiKeyError::InvalidRequest {
+                details: format!("Method '{}' cannot be granted to an API key", admin_method),
+            });
+        }
+        let rate_limit =
+            rate_limit_per_minute
+                .map(i32::try_from)
+                .transpose()
+                .map_err(|_| ApiKeyError::InvalidRequest {
+                    details: "rate_limit_per_minute is too large".to_string(),
+                })?;
+
+        let mut key_id = [0u8; 8];
+        OsRng.fill_bytes(&mut key_id);
+        let key_id = to_hex(&key_id);
+        let mut secret = [0u8; 32];
+        OsRng.fill_bytes(&mut secret);
+        let api_key = format!("{}{}", API_KEY_PREFIX, to_hex(&secret));
+        let secret_hash = hash_secret(&api_key);
+        let created_at = unix_now();
+
+        let new_api_key = NewApiKey {
+            key_id: key_id.clone(),
+            name,
+            secret_hash: secret_hash.clone(),
+            allowed_methods: allowed_methods.as_ref().map(serde_json::to_string).transpose()?,
+            rate_limit_per_minute: rate_limit,
+            created_at: created_at as i64,
+        };
+        self.store.with_write_tx(|tx| tx.insert_api_key(new_api_key.clone()))?;
+
+        self.keys.lock().unwrap().insert(secret_hash, ActiveApiKey {
+            key_id: key_id.clone(),
+            allowed_methods: allowed_methods.clone().map(|m| m.into_iter().collect()),
+            rate_limiter: rate_limit_for(&self.config, rate_limit)
+                .map(|limit| RateLimiter::per_minute(limit, Instant::now())),
+            rate_limited_requests: 0,
+            denied_requests: 0,
+            unflushed_requests: 0,
+            last_used_at: None,
+        });
+
+        info!(target: LOG_TARGET, "🔑 Issued API key {} ({})", key_id, new_api_key.name);
+        Ok((api_key, ApiKeyInfo {
+            key_id,
+            name: new_api_key.name,
+            allowed_methods,
+            rate_limit_per_minute,
+            created_at,
+            revoked_at: None,
+            last_used_at: None,
+            usage: ApiKeyUsage::default(),
+        }))
+    }
+
+    pub fn revoke(&self, key_id: &str) -> Result<(), ApiKeyError> {
+        // Persist the usage of the key so that it is not lost once the key is removed
+        self.flush_usage()?;
+        let is_revoked = self
+            .store
+            .with_write_tx(|tx| tx.revoke_api_key(key_id, unix_now() as i64))?;
+        if !is_revoked {
+            return Err(ApiKeyError::NotFound {
+                key_id: key_id.to_string(),
+            });
+        }
+        self.keys.lock().unwrap().retain(|_, key| key.key_id != key_id);
+        info!(target: LOG_TARGET, "🔑 Revoked API key {}", key_id);
+        Ok(())
+    }
+
+    pub fn list(&self, include_revoked: bool) -> Result<Vec<ApiKeyInfo>, ApiKeyError> {
+        let stored_keys = self.store.with_read_tx(|tx| tx.get_all_api_keys())?;
+        let keys = self.keys.lock().unwrap();
+        let active_keys = keys
+            .values()
+            .map(|key| (key.key_id.as_str(), key))
+            .collect::<HashMap<_, _>>();
+
+        stored_keys
+            .into_iter()
+            .filter(|key| include_revoked || !key.is_revoked())
+            .map(|key| {
+                let active = active_keys.get(key.key_id.as_str()).copied();
+                to_api_key_info(key, active)
+            })
+            .collect()
+    }
+
+    /// Writes request counts that have not yet been persisted to the database
+    pub fn flush_usage(&self) -> Result<(), ApiKeyError> {
+        let pending = {
+            let mut keys = self.keys.lock().unwrap();
+            keys.values_mut()
+                .filter(|key| key.unflushed_requests > 0)
+                .map(|key| {
+                    let num_requests = key.unflushed_requests;
+                    key.unflushed_requests = 0;
+                    (
+                        key.key_id.clone(),
+                        num_requests,
+                        key.last_used_at.unwrap_or_else(unix_now),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+
+        let result = self.store.with_write_tx(|tx| {
+            for (key_id, num_requests, last_used_at) in &pending {
+                tx.add_api_key_usage(key_id, *num_requests, *last_used_at as i64)?;
+            }
+            Ok::<_, ApiKeyError>(())
+        });
+
+        if result.is_err() {
+            // Restore the counts so that they are written on the next flush
+            let mut keys = self.keys.lock().unwrap();
+            for (key_id, num_requests, _) in pending {
+                if let Some(key) = keys.values_mut().find(|key| key.key_id == key_id) {
+                    key.unflushed_requests += num_requests;
+                }
+            }
+        }
+        result
+    }
+
+    /// Periodically persists key usage until shutdown
+    pub async fn run_usage_flusher(self: Arc<Self>, mut shutdown: ShutdownSignal) {
+        let mut interval = time::interval(self.config.usage_flush_interval);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.flush_usage() {
+                        error!(target: LOG_TARGET, "Failed to persist API key usage: {}", err);
+                    }
+                },
+                _ = shutdown.wait() => {
+                    if let Err(err) = self.flush_usage() {
+                        error!(target: LOG_TARGET, "Failed to persist API key usage on shutdown: {}", err);
+                    }
+                    break;
+                },
+            }
+        }
+    }
+}
+
+fn to_api_key_info(key: ApiKey, active: Option<&ActiveApiKey>) -> Result<ApiKeyInfo, ApiKeyError> {
+    let total_requests = u64::try_from(key.total_requests).unwrap_or_default();
+    let mut usage = ApiKeyUsage {
+        total_requests,
+        ..Default::default()
+    };
+    let mut last_used_at = key.last_used_at.map(|t| t as u64);
+    if let Some(active) = active {
+        usage.total_requests += active.unflushed_requests;
+        usage.rate_limited_requests = active.rate_limited_requests;
+        usage.denied_requests = active.denied_requests;
+        last_used_at = active.last_used_at.or(last_used_at);
+    }
+
+    Ok(ApiKeyInfo {
+        allowed_methods: parse_allowed_methods(key.allowed_methods.as_deref())?,
+        rate_limit_per_minute: key.rate_limit_per_minute.map(|l| l as u32),
+        created_at: key.created_at as u64,
+        revoked_at: key.revoked_at.map(|t| t as u64),
+        last_used_at,
+        usage,
+        key_id: key.key_id,
+        name: key.name,
+    })
+}
+
+fn parse_allowed_methods(allowed_methods: Option<&str>) -> Result<Option<Vec<String>>, ApiKeyError> {
+    let allowed_methods = allowed_methods.map(serde_json::from_str).transpose()?;
+    Ok(allowed_methods)
+}
+
+fn rate_limit_for(config: &ApiKeysConfig, rate_limit_per_minute: Option<i32>) -> Option<u32> {
+    rate_limit_per_minute
+        .map(|l| l.max(0) as u32)
+        .or(config.default_rate_limit_per_minute)
+}
+
+fn hash_secret(secret: &str) -> String {
+    to_hex(Blake2b::<U32>::digest(secret.as_bytes()).as_slice())
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,88 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::{Duration, Instant};
+
+/// A token bucket that allows bursts of up to `limit` requests and refills at `limit` requests per minute.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub fn per_minute(limit: u32, now: Instant) -> Self {
+        let capacity = f64::from(limit);
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: now,
+        }
+    }
+
+    /// Takes a token from the bucket. If the bucket is empty, the duration until a token is available is returned.
+    pub fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            return Ok(());
+        }
+
+        if self.refill_per_sec <= 0.0 {
+            // A limit of zero never refills
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_allows_bursts_up_to_the_limit() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::per_minute(120, now);
+        for _ in 0..120 {
+            limiter.try_acquire(now).unwrap();
+        }
+        let retry_after = limiter.try_acquire(now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn it_refills_over_time() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::per_minute(60, now);
+        for _ in 0..60 {
+            limiter.try_acquire(now).unwrap();
+        }
+        limiter.try_acquire(now).unwrap_err();
+
+        let later = now + Duration::from_secs(2);
+        limiter.try_acquire(later).unwrap();
+        limiter.try_acquire(later).unwrap();
+        limiter.try_acquire(later).unwrap_err();
+
+        // The bucket never holds more than the limit
+        let much_later = now + Duration::from_secs(3600);
+        for _ in 0..60 {
+            limiter.try_acquire(much_later).unwrap();
+        }
+        limiter.try_acquire(much_later).unwrap_err();
+    }
+
+    #[test]
+    fn it_rejects_all_requests_with_a_zero_limit() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::per_minute(0, now);
+        assert_eq!(limiter.try_acquire(now).unwrap_err(), Duration::MAX);
+    }
+}
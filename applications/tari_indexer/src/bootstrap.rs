@@ -113,6 +113,7 @@ pub async fn spawn_services(
             },
             reachability_mode: config.indexer.p2p.reachability_mode.into(),
             announce: false,
+            bandwidth_limits: config.indexer.p2p.bandwidth_limits(),
             ..Default::default()
         },
         seed_peers,
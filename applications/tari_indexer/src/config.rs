@@ -102,6 +102,14 @@ pub struct IndexerConfig {
     pub burnt_utxo_sidechain_id: Option<RistrettoPublicKey>,
     /// The event filtering configuration
     pub event_filters: Vec<EventFilterConfig>,
+    /// API key configuration for the JSON-RPC API
+    pub api_keys: ApiKeysConfig,
+    /// Aggregation of the transaction pools of the validators in the current epoch
+    pub pending_transactions: PendingTransactionsConfig,
+    /// Retention windows for each class of indexed data
+    pub pruning: PruningConfig,
+    /// The worker pool that executes heavy queries submitted with `submit_query`
+    pub query_workers: QueryWorkersConfig,
 }
 
 impl IndexerConfig {
@@ -142,6 +150,10 @@ impl Default for IndexerConfig {
             templates_sidechain_id: None,
             burnt_utxo_sidechain_id: None,
             event_filters: vec![],
+            api_keys: ApiKeysConfig::default(),
+            pending_transactions: PendingTransactionsConfig::default(),
+            pruning: PruningConfig::default(),
+            query_workers: QueryWorkersConfig::default(),
         }
     }
 }
@@ -159,3 +171,110 @@ pub struct EventFilterConfig {
     pub substate_id: Option<String>,
     pub template_address: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeysConfig {
+    /// If true, every JSON-RPC request must include a valid API key in the `x-api-key` header
+    pub enabled: bool,
+    /// The key that grants access to the API key management methods (`api_keys.*`). If not set, keys cannot be
+    /// managed over JSON-RPC.
+    pub admin_key: Option<String>,
+    /// The rate limit applied to keys that were issued without their own limit. If not set, these keys are unlimited.
+    pub default_rate_limit_per_minute: Option<u32>,
+    /// How often the request counts of each key are written to the database
+    #[serde(with = "serializers::seconds")]
+    pub usage_flush_interval: Duration,
+}
+
+impl Default for ApiKeysConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            admin_key: None,
+            default_rate_limit_per_minute: None,
+            usage_flush_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingTransactionsConfig {
+    /// If true, a member of each committee is polled for the transactions in its pool that are not yet finalized
+    pub enabled: bool,
+    /// How often the committees are polled
+    #[serde(with = "serializers::seconds")]
+    pub poll_interval: Duration,
+    /// The maximum number of pending transactions requested from each committee per poll
+    pub max_transactions_per_committee: usize,
+}
+
+impl Default for PendingTransactionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            poll_interval: Duration::from_secs(10),
+            max_transactions_per_committee: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PruningConfig {
+    /// If true, data that is older than the retention window of its class is deleted on an interval
+    pub enabled: bool,
+    /// How often the retention windows are enforced
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// How long a raw substate value is kept after it was last updated. Pruned substates are fetched from the network
+    /// again when they are requested. If not set, substates are never pruned.
+    #[serde(default, with = "humantime_serde::option")]
+    pub substate_retention: Option<Duration>,
+    /// How long decoded events and their payload fields are kept. If not set, events are never pruned.
+    #[serde(default, with = "humantime_serde::option")]
+    pub event_retention: Option<Duration>,
+    /// The number of most recent epochs for which per-epoch stats are kept. If not set, stats are never pruned.
+    #[serde(default)]
+    pub epoch_stats_retention_epochs: Option<u64>,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60 * 60),
+            substate_retention: None,
+            event_retention: None,
+            epoch_stats_retention_epochs: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QueryWorkersConfig {
+    /// The number of queries that are executed concurrently. Each worker has its own read-only database connection.
+    pub num_workers: usize,
+    /// The maximum number of queries that may be waiting for a worker. Further queries are rejected.
+    pub max_queued_queries: usize,
+    /// Queries that take longer than this are reported as timed out
+    #[serde(with = "humantime_serde")]
+    pub time_limit: Duration,
+    /// How long a result is kept after the query completed. An identical query that is submitted in this time is
+    /// served from the cached result.
+    #[serde(with = "humantime_serde")]
+    pub result_ttl: Duration,
+}
+
+impl Default for QueryWorkersConfig {
+    fn default() -> Self {
+        Self {
+            num_workers: 2,
+            max_queued_queries: 100,
+            time_limit: Duration::from_secs(60),
+            result_ttl: Duration::from_secs(10 * 60),
+        }
+    }
+}
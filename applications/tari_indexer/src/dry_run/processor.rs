@@ -27,12 +27,12 @@ use tari_dan_app_utilities::{
     template_manager::implementation::TemplateManager,
     transaction_executor::{TariDanTransactionProcessor, TransactionExecutor as _},
 };
-use tari_dan_common_types::{Epoch, PeerAddress, SubstateAddress, SubstateRequirement};
+use tari_dan_common_types::{Epoch, PeerAddress, SubstateAddress, SubstateRequirement, VersionedSubstateId};
 use tari_dan_engine::{fees::FeeTable, state_store::new_memory_store, transaction::TransactionProcessorConfig};
 use tari_engine_types::{
     commit_result::ExecuteResult,
     instruction::Instruction,
-    substate::{Substate, SubstateId},
+    substate::{Substate, SubstateId, SubstateValue},
     virtual_substate::{VirtualSubstate, VirtualSubstateId, VirtualSubstates},
 };
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
@@ -89,22 +89,40 @@ where TSubstateCache: SubstateCache + 'static
         }
     }
 
+    /// Executes the transaction against the current network state without submitting it. Any `substate_overrides`
+    /// replace the network state of those substates (or add them if they do not exist yet) and are included as
+    /// transaction inputs.
     pub async fn process_transaction(
         &self,
         transaction: Transaction,
         substate_requirements: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<ExecuteResult, DryRunTransactionProcessorError> {
         info!(target: LOG_TARGET, "process_transaction: {}", transaction.hash());
 
+        let substate_requirements = exclude_overridden_requirements(substate_requirements, &substate_overrides);
+
         // automatically scan the inputs and add all related involved objects
         // note that this operation does not alter the transaction hash
-        let (transaction, mut found_substates) = self
+        let (mut transaction, mut found_substates) = self
             .transaction_autofiller
             .autofill_transaction(transaction, substate_requirements)
             .await?;
 
         let epoch = self.epoch_manager.current_epoch().await?;
-        found_substates.extend(self.fetch_input_substates(&transaction, epoch).await?);
+        found_substates.extend(
+            self.fetch_input_substates(&transaction, epoch, &substate_overrides)
+                .await?,
+        );
+
+        if !substate_overrides.is_empty() {
+            info!(
+                target: LOG_TARGET,
+                "process_transaction: applying {} substate override(s)",
+                substate_overrides.len()
+            );
+        }
+        apply_substate_overrides(&mut transaction, &mut found_substates, substate_overrides);
 
         let payload_processor = self.build_payload_processor(&transaction);
 
@@ -133,6 +151,7 @@ where TSubstateCache: SubstateCache + 'static
                 per_byte_storage_cost: 1,
                 per_event_cost: 1,
                 per_log_cost: 1,
+                gas_per_fee_unit: 1_000_000,
             }
         } else {
             FeeTable::zero_rated()
@@ -149,11 +168,15 @@ where TSubstateCache: SubstateCache + 'static
         &self,
         transaction: &Transaction,
         epoch: Epoch,
+        substate_overrides: &HashMap<SubstateId, SubstateValue>,
     ) -> Result<HashMap<SubstateId, Substate>, DryRunTransactionProcessorError> {
         let mut substates = HashMap::new();
 
         // Fetch explicit inputs that may not have been resolved by the autofiller
         for requirement in transaction.inputs() {
+            if substate_overrides.contains_key(&requirement.substate_id) {
+                continue;
+            }
             let Some(address) = requirement.to_substate_address() else {
                 // No version, we cant fetch it
                 continue;
@@ -267,3 +290,126 @@ where TSubstateCache: SubstateCache + 'static
         Ok(virtual_substates)
     }
 }
+
+/// Overridden substates may not exist on the network (e.g. outputs of a pending transaction), so they are not fetched
+fn exclude_overridden_requirements(
+    substate_requirements: Vec<SubstateRequirement>,
+    substate_overrides: &HashMap<SubstateId, SubstateValue>,
+) -> Vec<SubstateRequirement> {
+    substate_requirements
+        .into_iter()
+        .filter(|req| !substate_overrides.contains_key(req.substate_id()))
+        .collect()
+}
+
+/// Replaces the values of existing substates with the overrides, or adds them if they do not exist yet, and includes
+/// them as transaction inputs
+fn apply_substate_overrides(
+    transaction: &mut Transaction,
+    substates: &mut HashMap<SubstateId, Substate>,
+    substate_overrides: HashMap<SubstateId, SubstateValue>,
+) {
+    for (id, value) in substate_overrides {
+        // Keep the known version of the substate so that the engine produces the same versions as it would for the
+        // real substate
+        let version = substates.get(&id).map(|s| s.version()).unwrap_or(0);
+        transaction
+            .filled_inputs_mut()
+            .insert(VersionedSubstateId::new(id.clone(), version));
+        substates.insert(id, Substate::new(version, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_engine_types::resource::Resource;
+    use tari_template_lib::{
+        auth::{OwnerRule, ResourceAccessRules},
+        models::{Metadata, ObjectKey, ResourceAddress},
+        resource::ResourceType,
+    };
+
+    use super::*;
+
+    fn resource_id(n: u8) -> SubstateId {
+        SubstateId::Resource(ResourceAddress::new([n; ObjectKey::LENGTH].into()))
+    }
+
+    fn resource_value(resource_type: ResourceType) -> SubstateValue {
+        Resource::new(
+            resource_type,
+            None,
+            OwnerRule::None,
+            ResourceAccessRules::new(),
+            Metadata::new(),
+            None,
+            None,
+        )
+        .into()
+    }
+
+    fn resource_type_of(substate: &Substate) -> ResourceType {
+        substate.substate_value().as_resource().unwrap().resource_type()
+    }
+
+    #[test]
+    fn it_replaces_an_existing_substate_with_the_override() {
+        let id = resource_id(1);
+        let mut transaction = Transaction::builder().build();
+        let mut substates = HashMap::new();
+        substates.insert(id.clone(), Substate::new(3, resource_value(ResourceType::Fungible)));
+
+        let overrides = HashMap::from([(id.clone(), resource_value(ResourceType::NonFungible))]);
+        apply_substate_overrides(&mut transaction, &mut substates, overrides);
+
+        assert_eq!(substates.len(), 1);
+        let substate = substates.get(&id).unwrap();
+        // The network version is kept and only the value is replaced
+        assert_eq!(substate.version(), 3);
+        assert_eq!(resource_type_of(substate), ResourceType::NonFungible);
+        assert!(transaction.filled_inputs().contains(&VersionedSubstateId::new(id, 3)));
+    }
+
+    #[test]
+    fn it_creates_a_substate_from_an_override_that_does_not_exist() {
+        let existing = resource_id(1);
+        let new = resource_id(2);
+        let mut transaction = Transaction::builder().build();
+        let mut substates = HashMap::new();
+        substates.insert(
+            existing.clone(),
+            Substate::new(1, resource_value(ResourceType::Fungible)),
+        );
+
+        let overrides = HashMap::from([(new.clone(), resource_value(ResourceType::Confidential))]);
+        apply_substate_overrides(&mut transaction, &mut substates, overrides);
+
+        assert_eq!(substates.len(), 2);
+        assert_eq!(
+            resource_type_of(substates.get(&existing).unwrap()),
+            ResourceType::Fungible
+        );
+        let substate = substates.get(&new).unwrap();
+        assert_eq!(substate.version(), 0);
+        assert_eq!(resource_type_of(substate), ResourceType::Confidential);
+        assert!(transaction.filled_inputs().contains(&VersionedSubstateId::new(new, 0)));
+        assert!(!transaction
+            .filled_inputs()
+            .contains(&VersionedSubstateId::new(existing, 1)));
+    }
+
+    #[test]
+    fn it_does_not_fetch_overridden_substates() {
+        let overridden = resource_id(1);
+        let fetched = resource_id(2);
+        let requirements = vec![
+            SubstateRequirement::unversioned(overridden.clone()),
+            SubstateRequirement::unversioned(fetched.clone()),
+        ];
+        let overrides = HashMap::from([(overridden, resource_value(ResourceType::Fungible))]);
+
+        let requirements = exclude_overridden_requirements(requirements, &overrides);
+        assert_eq!(requirements.len(), 1);
+        assert_eq!(*requirements[0].substate_id(), fetched);
+    }
+}
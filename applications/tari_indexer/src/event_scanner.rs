@@ -20,7 +20,11 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::anyhow;
 use futures::StreamExt;
@@ -45,6 +49,7 @@ use crate::{
     event_data::EventData,
     substate_storage_sqlite::{
         models::{
+            epoch_stats::NewEpochStats,
             events::{NewEvent, NewScannedBlockId},
             substate::NewSubstate,
         },
@@ -175,6 +180,7 @@ impl EventScanner {
                 new_blocks.len(),
                 epoch,
             );
+            let num_blocks = new_blocks.len();
             let transactions = self.extract_transactions_from_blocks(new_blocks);
             info!(
                 target: LOG_TARGET,
@@ -182,11 +188,15 @@ impl EventScanner {
                 transactions.len(),
                 epoch,
             );
+            let num_transactions = transactions.len();
+            let mut num_events = 0;
+            let mut num_stored_events = 0;
 
             for transaction in transactions {
                 // fetch all the events in the transaction
                 let events = self.get_events_for_transaction(transaction.transaction_id).await?;
                 event_count += events.len();
+                num_events += events.len();
 
                 // only keep the events specified by the indexer filter
                 let filtered_events: Vec<EventData> =
@@ -197,13 +207,31 @@ impl EventScanner {
                     epoch,
                     filtered_events.len()
                 );
+                num_stored_events += filtered_events.len();
                 self.store_events_in_db(&filtered_events, transaction).await?;
             }
+
+            if num_blocks > 0 {
+                self.add_epoch_stats(NewEpochStats {
+                    epoch: epoch.as_u64() as i64,
+                    num_blocks: num_blocks as i64,
+                    num_transactions: num_transactions as i64,
+                    num_events: num_events as i64,
+                    num_stored_events: num_stored_events as i64,
+                    updated_at: now_unix_secs() as i64,
+                })?;
+            }
         }
 
         Ok(event_count)
     }
 
+    fn add_epoch_stats(&self, stats: NewEpochStats) -> Result<(), anyhow::Error> {
+        self.substate_store
+            .with_write_tx(|tx| tx.add_epoch_stats(stats))
+            .map_err(|e| e.into())
+    }
+
     async fn delete_scanned_epochs_older_than(&self, epoch: Epoch) -> Result<(), anyhow::Error> {
         self.substate_store
             .with_write_tx(|tx| tx.delete_scanned_epochs_older_than(epoch))
@@ -616,3 +644,10 @@ impl EventScanner {
         Ok(blocks)
     }
 }
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -48,26 +48,50 @@ use tari_indexer_client::types::{
     self,
     AddPeerRequest,
     AddPeerResponse,
+    ApiKeysIssueRequest,
+    ApiKeysIssueResponse,
+    ApiKeysListRequest,
+    ApiKeysListResponse,
+    ApiKeysRevokeRequest,
+    ApiKeysRevokeResponse,
     ConnectionDirection,
     GetAllVnsRequest,
     GetAllVnsResponse,
     GetCommsStatsResponse,
     GetConnectionsResponse,
     GetEpochManagerStatsResponse,
+    GetEpochStatsRequest,
+    GetEpochStatsResponse,
+    GetEventsRequest,
+    GetEventsResponse,
     GetIdentityResponse,
     GetNonFungibleCollectionsResponse,
     GetNonFungibleCountRequest,
     GetNonFungibleCountResponse,
     GetNonFungiblesRequest,
     GetNonFungiblesResponse,
+    GetPendingTransactionRequest,
+    GetPendingTransactionResponse,
+    GetPendingTransactionsRequest,
+    GetPendingTransactionsResponse,
+    GetQueryResultRequest,
+    GetQueryResultResponse,
     GetRelatedTransactionsRequest,
     GetRelatedTransactionsResponse,
+    GetSubstateAccessStatsRequest,
+    GetSubstateAccessStatsResponse,
+    GetSubstateDiffRequest,
+    GetSubstateDiffResponse,
     GetSubstateRequest,
     GetSubstateResponse,
     GetTemplateDefinitionRequest,
     GetTemplateDefinitionResponse,
     GetTransactionResultRequest,
     GetTransactionResultResponse,
+    GetValidatorIdentityChallengeRequest,
+    GetValidatorIdentityChallengeResponse,
+    GetValidatorIdentityRequest,
+    GetValidatorIdentityResponse,
     IndexerTransactionFinalizedResult,
     InspectSubstateRequest,
     InspectSubstateResponse,
@@ -75,7 +99,13 @@ use tari_indexer_client::types::{
     ListSubstatesResponse,
     ListTemplatesRequest,
     ListTemplatesResponse,
+    ListValidatorIdentitiesRequest,
+    ListValidatorIdentitiesResponse,
     NonFungibleSubstate,
+    RegisterValidatorIdentityRequest,
+    RegisterValidatorIdentityResponse,
+    SubmitQueryRequest,
+    SubmitQueryResponse,
     SubmitTransactionRequest,
     SubmitTransactionResponse,
     TemplateMetadata,
@@ -84,14 +114,26 @@ use tari_networking::{is_supported_multiaddr, NetworkingHandle, NetworkingServic
 use tari_validator_node_rpc::client::{SubstateResult, TariValidatorNodeRpcClientFactory, TransactionResultStatus};
 
 use crate::{
+    api_keys::{ApiKeyError, ApiKeyManager},
     bootstrap::Services,
     dry_run::processor::DryRunTransactionProcessor,
+    event_manager::EventManager,
     json_rpc::error::internal_error,
+    pending_transactions::PendingTransactionsMonitor,
+    query_workers::{QueryWorkerError, QueryWorkerPool},
+    substate_diff::diff_substates,
     substate_manager::SubstateManager,
     transaction_manager::{error::TransactionManagerError, TransactionManager},
+    validator_identity::{ValidatorIdentityError, ValidatorIdentityRegistry},
 };
 
 const LOG_TARGET: &str = "tari::indexer::json_rpc::handlers";
+/// The number of epochs returned by get_epoch_stats if the request does not set a limit
+const DEFAULT_EPOCH_STATS_LIMIT: u64 = 100;
+/// The number of events returned by get_events if the request does not set a limit
+const DEFAULT_EVENTS_LIMIT: u32 = 100;
+/// The number of identities returned by validators.list_identities if the request does not set a limit
+const DEFAULT_VALIDATOR_IDENTITIES_LIMIT: u64 = 100;
 
 pub struct JsonRpcHandlers {
     consensus_constants: BaseLayerConsensusConstants,
@@ -104,6 +146,11 @@ pub struct JsonRpcHandlers {
         TransactionManager<EpochManagerHandle<PeerAddress>, TariValidatorNodeRpcClientFactory, SubstateFileCache>,
     template_manager: TemplateManager<PeerAddress>,
     dry_run_transaction_processor: DryRunTransactionProcessor<SubstateFileCache>,
+    api_key_manager: Arc<ApiKeyManager>,
+    pending_transactions: PendingTransactionsMonitor,
+    query_workers: QueryWorkerPool,
+    event_manager: Arc<EventManager>,
+    validator_identities: Arc<ValidatorIdentityRegistry>,
 }
 
 impl JsonRpcHandlers {
@@ -119,6 +166,11 @@ impl JsonRpcHandlers {
         >,
         template_manager: TemplateManager<PeerAddress>,
         dry_run_transaction_processor: DryRunTransactionProcessor<SubstateFileCache>,
+        api_key_manager: Arc<ApiKeyManager>,
+        pending_transactions: PendingTransactionsMonitor,
+        query_workers: QueryWorkerPool,
+        event_manager: Arc<EventManager>,
+        validator_identities: Arc<ValidatorIdentityRegistry>,
     ) -> Self {
         Self {
             consensus_constants,
@@ -130,6 +182,11 @@ impl JsonRpcHandlers {
             transaction_manager,
             template_manager,
             dry_run_transaction_processor,
+            api_key_manager,
+            pending_transactions,
+            query_workers,
+            event_manager,
+            validator_identities,
         }
     }
 
@@ -139,6 +196,45 @@ impl JsonRpcHandlers {
 }
 
 impl JsonRpcHandlers {
+    /// Checks that the API key, if any, may call the requested method
+    pub fn authorize(&self, value: &JsonRpcExtractor, api_key: Option<&str>) -> Result<(), JsonRpcResponse> {
+        self.api_key_manager
+            .authorize(api_key, &value.method)
+            .map_err(|e| Self::api_key_error(value.get_answer_id(), e))
+    }
+
+    pub fn api_keys_issue(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: ApiKeysIssueRequest = value.parse_params()?;
+        let (api_key, info) = self
+            .api_key_manager
+            .issue(request.name, request.allowed_methods, request.rate_limit_per_minute)
+            .map_err(|e| Self::api_key_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, ApiKeysIssueResponse {
+            api_key,
+            info,
+        }))
+    }
+
+    pub fn api_keys_revoke(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: ApiKeysRevokeRequest = value.parse_params()?;
+        self.api_key_manager
+            .revoke(&request.key_id)
+            .map_err(|e| Self::api_key_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, ApiKeysRevokeResponse {}))
+    }
+
+    pub fn api_keys_list(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: ApiKeysListRequest = value.parse_params()?;
+        let keys = self
+            .api_key_manager
+            .list(request.include_revoked)
+            .map_err(|e| Self::api_key_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, ApiKeysListResponse { keys }))
+    }
+
     pub fn rpc_discover(&self, value: JsonRpcExtractor) -> JrpcResult {
         Ok(JsonRpcResponse::success(
             value.id,
@@ -411,6 +507,72 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_substate_diff(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetSubstateDiffRequest = value.parse_params()?;
+
+        if request.to_version.is_some_and(|v| v <= request.from_version) {
+            return Err(Self::error_response(
+                answer_id,
+                JsonRpcErrorReason::InvalidParams,
+                "to_version must be greater than from_version",
+            ));
+        }
+
+        let from = self
+            .substate_manager
+            .get_substate(&request.address, Some(request.from_version))
+            .await
+            .map_err(|e| Self::internal_error(answer_id, format!("Error getting substate: {}", e)))?
+            .ok_or_else(|| {
+                Self::not_found(
+                    answer_id,
+                    format!(
+                        "Substate {} (version:{}) not found",
+                        request.address, request.from_version
+                    ),
+                )
+            })?;
+        let to = self
+            .substate_manager
+            .get_substate(&request.address, request.to_version)
+            .await
+            .map_err(|e| Self::internal_error(answer_id, format!("Error getting substate: {}", e)))?
+            .ok_or_else(|| {
+                Self::not_found(
+                    answer_id,
+                    format!(
+                        "Substate {} (version:{}) not found",
+                        request.address,
+                        request
+                            .to_version
+                            .map(|v| v.to_string())
+                            .unwrap_or_else(|| "latest".to_string())
+                    ),
+                )
+            })?;
+
+        let changes = diff_substates(&from.substate, &to.substate).map_err(|e| Self::internal_error(answer_id, e))?;
+
+        let template_address = to.substate.substate_value().component().map(|c| c.template_address);
+        let template_name = template_address.and_then(|address| match self.template_manager.fetch_template(&address) {
+            Ok(template) => Some(template.metadata.name),
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Unable to fetch template {} for substate diff: {}", address, e);
+                None
+            },
+        });
+
+        Ok(JsonRpcResponse::success(answer_id, GetSubstateDiffResponse {
+            address: to.address,
+            from_version: from.version,
+            to_version: to.version,
+            template_address,
+            template_name,
+            changes,
+        }))
+    }
+
     pub async fn get_non_fungible_collections(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
 
@@ -472,12 +634,17 @@ impl JsonRpcHandlers {
     pub async fn submit_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request: SubmitTransactionRequest = value.parse_params()?;
+        Self::check_substate_overrides_permitted(answer_id, &request)?;
 
         if request.is_dry_run {
             let transaction_id = *request.transaction.id();
             let exec_result = self
                 .dry_run_transaction_processor
-                .process_transaction(request.transaction, request.required_substates)
+                .process_transaction(
+                    request.transaction,
+                    request.required_substates,
+                    request.substate_overrides,
+                )
                 .await
                 .map_err(|e| Self::internal_error(answer_id, e))?;
 
@@ -543,6 +710,29 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_substate_access_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetSubstateAccessStatsRequest = value.parse_params()?;
+        let transaction_id = *request.transaction.id();
+        let exec_result = self
+            .dry_run_transaction_processor
+            .process_transaction(
+                request.transaction,
+                request.required_substates,
+                request.substate_overrides,
+            )
+            .await
+            .map_err(|e| Self::internal_error(answer_id, e))?;
+        let stats = exec_result
+            .substate_access_stats
+            .ok_or_else(|| Self::internal_error(answer_id, "Substate access statistics were not collected"))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetSubstateAccessStatsResponse {
+            transaction_id,
+            stats,
+            fee_receipt: exec_result.finalize.fee_receipt,
+        }))
+    }
     pub async fn get_epoch_manager_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let current_epoch = self.epoch_manager.current_epoch().await.map_err(|e| {
@@ -723,6 +913,167 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, resp))
     }
 
+    pub async fn get_epoch_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetEpochStatsRequest = value.parse_params()?;
+        let stats = self
+            .substate_manager
+            .get_epoch_stats(req.limit.unwrap_or(DEFAULT_EPOCH_STATS_LIMIT))
+            .await
+            .map_err(|e| {
+                warn!(target: LOG_TARGET, "Error getting epoch stats: {}", e);
+                Self::internal_error(answer_id, format!("Error getting epoch stats: {}", e))
+            })?;
+        Ok(JsonRpcResponse::success(answer_id, GetEpochStatsResponse { stats }))
+    }
+
+    pub async fn get_events(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetEventsRequest = value.parse_params()?;
+        let events = self
+            .event_manager
+            .get_events_from_db(
+                req.topic,
+                req.substate_id,
+                req.offset.unwrap_or(0),
+                req.limit.unwrap_or(DEFAULT_EVENTS_LIMIT),
+            )
+            .await
+            .map_err(|e| {
+                warn!(target: LOG_TARGET, "Error getting events: {}", e);
+                Self::internal_error(answer_id, format!("Error getting events: {}", e))
+            })?;
+        Ok(JsonRpcResponse::success(answer_id, GetEventsResponse { events }))
+    }
+
+    pub fn get_pending_transactions(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetPendingTransactionsRequest = value.parse_params()?;
+        self.ensure_pending_transactions_enabled(answer_id)?;
+        let transactions = self.pending_transactions.get_all(req.limit.map(|l| l as usize));
+        Ok(JsonRpcResponse::success(answer_id, GetPendingTransactionsResponse {
+            transactions,
+        }))
+    }
+
+    pub fn get_pending_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetPendingTransactionRequest = value.parse_params()?;
+        self.ensure_pending_transactions_enabled(answer_id)?;
+        let transaction = self.pending_transactions.get(&req.transaction_id).ok_or_else(|| {
+            Self::not_found(
+                answer_id,
+                format!("Transaction {} is not pending in any committee", req.transaction_id),
+            )
+        })?;
+        Ok(JsonRpcResponse::success(answer_id, GetPendingTransactionResponse {
+            transaction,
+        }))
+    }
+
+    pub fn submit_query(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: SubmitQueryRequest = value.parse_params()?;
+        let (query_id, status) = self.query_workers.submit(req.query).map_err(|e| match e {
+            QueryWorkerError::QueueFull => {
+                Self::error_response(answer_id, JsonRpcErrorReason::ApplicationError(429), e)
+            },
+            e => Self::internal_error(answer_id, e),
+        })?;
+        Ok(JsonRpcResponse::success(answer_id, SubmitQueryResponse { query_id, status }))
+    }
+
+    pub async fn validators_identity_challenge(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetValidatorIdentityChallengeRequest = value.parse_params()?;
+        let challenge = self
+            .validator_identities
+            .issue_challenge(req.public_key)
+            .await
+            .map_err(|e| Self::validator_identity_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, GetValidatorIdentityChallengeResponse {
+            challenge,
+            expires_in_secs: self.validator_identities.challenge_ttl().as_secs(),
+        }))
+    }
+
+    pub async fn validators_register_identity(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: RegisterValidatorIdentityRequest = value.parse_params()?;
+        let identity = self
+            .validator_identities
+            .register(req.metadata, &req.challenge, req.signature)
+            .await
+            .map_err(|e| Self::validator_identity_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, RegisterValidatorIdentityResponse {
+            identity,
+        }))
+    }
+
+    pub fn validators_get_identity(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetValidatorIdentityRequest = value.parse_params()?;
+        let identity = self
+            .validator_identities
+            .get(&req.public_key)
+            .map_err(|e| Self::validator_identity_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, GetValidatorIdentityResponse { identity }))
+    }
+
+    pub fn validators_list_identities(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: ListValidatorIdentitiesRequest = value.parse_params()?;
+        let identities = self
+            .validator_identities
+            .list(
+                req.offset.unwrap_or(0),
+                req.limit.unwrap_or(DEFAULT_VALIDATOR_IDENTITIES_LIMIT),
+            )
+            .map_err(|e| Self::validator_identity_error(answer_id, e))?;
+        Ok(JsonRpcResponse::success(answer_id, ListValidatorIdentitiesResponse {
+            identities,
+        }))
+    }
+
+    pub fn get_query_result(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetQueryResultRequest = value.parse_params()?;
+        let (status, result) = self.query_workers.get(req.query_id).ok_or_else(|| {
+            Self::not_found(
+                answer_id,
+                format!("Query {} not found or its result has expired", req.query_id),
+            )
+        })?;
+        Ok(JsonRpcResponse::success(answer_id, GetQueryResultResponse { status, result }))
+    }
+
+    fn ensure_pending_transactions_enabled(&self, answer_id: i64) -> Result<(), JsonRpcResponse> {
+        if self.pending_transactions.is_enabled() {
+            return Ok(());
+        }
+        Err(Self::error_response(
+            answer_id,
+            JsonRpcErrorReason::ApplicationError(501),
+            "Pending transaction tracking is disabled. Set indexer.pending_transactions.enabled to enable it",
+        ))
+    }
+
+    /// Substate overrides change the state that a transaction is executed against, so they are only permitted in dry
+    /// runs
+    fn check_substate_overrides_permitted(
+        answer_id: i64,
+        request: &SubmitTransactionRequest,
+    ) -> Result<(), JsonRpcResponse> {
+        if !request.is_dry_run && !request.substate_overrides.is_empty() {
+            return Err(Self::error_response(
+                answer_id,
+                JsonRpcErrorReason::InvalidParams,
+                "Substate overrides are only permitted for dry run transactions",
+            ));
+        }
+        Ok(())
+    }
+
     fn error_response<T: Display>(answer_id: i64, reason: JsonRpcErrorReason, message: T) -> JsonRpcResponse {
         JsonRpcResponse::error(
             answer_id,
@@ -738,4 +1089,85 @@ impl JsonRpcHandlers {
         let msg = error.to_string();
         Self::error_response(answer_id, JsonRpcErrorReason::InternalError, msg)
     }
+
+    fn api_key_error(answer_id: i64, err: ApiKeyError) -> JsonRpcResponse {
+        let reason = match err {
+            ApiKeyError::MissingApiKey | ApiKeyError::InvalidApiKey => JsonRpcErrorReason::ApplicationError(401),
+            ApiKeyError::MethodNotAllowed { .. } | ApiKeyError::AdminDisabled => {
+                JsonRpcErrorReason::ApplicationError(403)
+            },
+            ApiKeyError::NotFound { .. } => JsonRpcErrorReason::ApplicationError(404),
+            ApiKeyError::RateLimited { .. } => JsonRpcErrorReason::ApplicationError(429),
+            ApiKeyError::InvalidRequest { .. } => JsonRpcErrorReason::InvalidParams,
+            ApiKeyError::StorageError(_) | ApiKeyError::InvalidAllowedMethods(_) => {
+                warn!(target: LOG_TARGET, "API key error: {}", err);
+                return Self::internal_error(answer_id, "Something went wrong");
+            },
+        };
+        Self::error_response(answer_id, reason, err)
+    }
+
+    fn validator_identity_error(answer_id: i64, err: ValidatorIdentityError) -> JsonRpcResponse {
+        let reason = match err {
+            ValidatorIdentityError::NotFound { .. } => JsonRpcErrorReason::ApplicationError(404),
+            ValidatorIdentityError::InvalidChallenge | ValidatorIdentityError::InvalidSignature => {
+                JsonRpcErrorReason::ApplicationError(401)
+            },
+            ValidatorIdentityError::NotAValidator { .. } => JsonRpcErrorReason::ApplicationError(403),
+            ValidatorIdentityError::TooManyChallenges => JsonRpcErrorReason::ApplicationError(429),
+            ValidatorIdentityError::InvalidMetadata { .. } => JsonRpcErrorReason::InvalidParams,
+            ValidatorIdentityError::CorruptRecord { .. } |
+            ValidatorIdentityError::StorageError(_) |
+            ValidatorIdentityError::SignatureEncoding(_) |
+            ValidatorIdentityError::EpochManagerError(_) => {
+                warn!(target: LOG_TARGET, "Validator identity error: {}", err);
+                return Self::internal_error(answer_id, "Something went wrong");
+            },
+        };
+        Self::error_response(answer_id, reason, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum_jrpc::JsonRpcAnswer;
+    use tari_engine_types::{
+        non_fungible_index::NonFungibleIndex,
+        substate::{SubstateId, SubstateValue},
+    };
+    use tari_template_lib::models::{ComponentAddress, NonFungibleAddress, NonFungibleId, ResourceAddress};
+    use tari_transaction::Transaction;
+
+    use super::*;
+
+    fn submit_request(
+        is_dry_run: bool,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
+    ) -> SubmitTransactionRequest {
+        SubmitTransactionRequest {
+            transaction: Transaction::builder().build(),
+            required_substates: vec![],
+            is_dry_run,
+            substate_overrides,
+        }
+    }
+
+    fn an_override() -> HashMap<SubstateId, SubstateValue> {
+        let address = NonFungibleAddress::new(ResourceAddress::new(Default::default()), NonFungibleId::from_u32(1));
+        let id = SubstateId::Component(ComponentAddress::new(Default::default()));
+        HashMap::from([(id, NonFungibleIndex::new(address).into())])
+    }
+
+    #[test]
+    fn it_rejects_substate_overrides_if_not_a_dry_run() {
+        let err =
+            JsonRpcHandlers::check_substate_overrides_permitted(1, &submit_request(false, an_override())).unwrap_err();
+        assert!(matches!(
+            err.result,
+            JsonRpcAnswer::Error(ref e) if matches!(e.error_reason(), JsonRpcErrorReason::InvalidParams)
+        ));
+
+        JsonRpcHandlers::check_substate_overrides_permitted(1, &submit_request(true, an_override())).unwrap();
+        JsonRpcHandlers::check_substate_overrides_permitted(1, &submit_request(false, HashMap::new())).unwrap();
+    }
 }
@@ -22,9 +22,16 @@
 
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::{extract::Extension, middleware, routing::post, Router};
+use axum::{
+    extract::Extension,
+    http::{header, HeaderMap},
+    middleware,
+    routing::post,
+    Router,
+};
 use axum_jrpc::{JrpcResult, JsonRpcExtractor};
 use log::*;
+use tari_indexer_client::json_rpc_client::{API_KEY_HEADER, TRACE_ID_HEADER};
 use tower_http::cors::CorsLayer;
 
 use super::handlers::JsonRpcHandlers;
@@ -54,11 +61,22 @@ pub fn spawn_json_rpc(preferred_address: SocketAddr, handlers: JsonRpcHandlers)
     Ok(listen_addr)
 }
 
-async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: JsonRpcExtractor) -> JrpcResult {
-    info!(target: LOG_TARGET, "🌐 JSON-RPC request: {}", value.method);
+async fn handler(
+    Extension(handlers): Extension<Arc<JsonRpcHandlers>>,
+    headers: HeaderMap,
+    value: JsonRpcExtractor,
+) -> JrpcResult {
+    match headers.get(TRACE_ID_HEADER).and_then(|v| v.to_str().ok()) {
+        Some(trace_id) => info!(target: LOG_TARGET, "🌐 JSON-RPC request: {} (trace_id={})", value.method, trace_id),
+        None => info!(target: LOG_TARGET, "🌐 JSON-RPC request: {}", value.method),
+    }
     debug!(target: LOG_TARGET, "🌐 JSON-RPC body: {:?}", value);
+    handlers.authorize(&value, get_api_key(&headers))?;
     match value.method.as_str() {
         "rpc.discover" => handlers.rpc_discover(value),
+        "api_keys.issue" => handlers.api_keys_issue(value),
+        "api_keys.revoke" => handlers.api_keys_revoke(value),
+        "api_keys.list" => handlers.api_keys_list(value),
         "get_identity" => handlers.get_identity(value).await,
         "get_all_vns" => handlers.get_all_vns(value).await,
         "add_peer" => handlers.add_peer(value).await,
@@ -66,20 +84,43 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         "list_substates" => handlers.list_substates(value).await,
         "get_substate" => handlers.get_substate(value).await,
         "inspect_substate" => handlers.inspect_substate(value).await,
+        "get_substate_diff" => handlers.get_substate_diff(value).await,
         "get_connections" => handlers.get_connections(value).await,
         "get_non_fungible_collections" => handlers.get_non_fungible_collections(value).await,
         "get_non_fungible_count" => handlers.get_non_fungible_count(value).await,
         "get_non_fungibles" => handlers.get_non_fungibles(value).await,
         "submit_transaction" => handlers.submit_transaction(value).await,
+        "get_substate_access_stats" => handlers.get_substate_access_stats(value).await,
         "get_transaction_result" => handlers.get_transaction_result(value).await,
         "get_substate_transactions" => handlers.get_substate_transactions(value).await,
         "get_epoch_manager_stats" => handlers.get_epoch_manager_stats(value).await,
         "get_template_definition" => handlers.get_template_definition(value).await,
         "list_templates" => handlers.list_templates(value).await,
+        "get_pending_transactions" => handlers.get_pending_transactions(value),
+        "get_pending_transaction" => handlers.get_pending_transaction(value),
+        "get_epoch_stats" => handlers.get_epoch_stats(value).await,
+        "get_events" => handlers.get_events(value).await,
+        "submit_query" => handlers.submit_query(value),
+        "get_query_result" => handlers.get_query_result(value),
+        "validators.identity_challenge" => handlers.validators_identity_challenge(value).await,
+        "validators.register_identity" => handlers.validators_register_identity(value).await,
+        "validators.get_identity" => handlers.validators_get_identity(value),
+        "validators.list_identities" => handlers.validators_list_identities(value),
         method => Ok(value.method_not_found(method)),
     }
 }
 
+/// Returns the API key from the `x-api-key` header, or from an `Authorization: Bearer` header
+fn get_api_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(api_key) = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+        return Some(api_key);
+    }
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+}
+
 // TODO: this is a janky and fairly costly logger found on SO, replace with tracing middleware
 mod logger {
     use async_graphql::futures_util;
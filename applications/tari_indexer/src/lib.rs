@@ -25,6 +25,7 @@ extern crate diesel;
 #[macro_use]
 extern crate diesel_migrations;
 
+mod api_keys;
 mod bootstrap;
 pub mod cli;
 pub mod config;
@@ -36,9 +37,14 @@ mod event_data;
 mod event_manager;
 mod event_scanner;
 mod json_rpc;
+mod pending_transactions;
+mod pruner;
+mod query_workers;
+mod substate_diff;
 mod substate_manager;
 mod substate_storage_sqlite;
 mod transaction_manager;
+mod validator_identity;
 
 use std::{fs, sync::Arc};
 
@@ -53,7 +59,10 @@ use tari_common::{
 };
 use tari_consensus::consensus_constants::ConsensusConstants;
 use tari_dan_app_utilities::{keypair::setup_keypair_prompt, substate_file_cache::SubstateFileCache};
-use tari_dan_engine::transaction::TransactionProcessorConfig;
+use tari_dan_engine::{
+    runtime::{GasLimits, MemoryLimits, SandboxLimits},
+    transaction::TransactionProcessorConfig,
+};
 use tari_dan_storage::global::DbFactory;
 use tari_dan_storage_sqlite::SqliteDbFactory;
 use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
@@ -63,13 +72,18 @@ use tari_shutdown::ShutdownSignal;
 use tokio::{task, time};
 
 use crate::{
+    api_keys::ApiKeyManager,
     bootstrap::{spawn_services, Services},
     config::ApplicationConfig,
     dry_run::processor::DryRunTransactionProcessor,
     event_manager::EventManager,
     graphql::server::run_graphql,
     json_rpc::{spawn_json_rpc, JsonRpcHandlers},
+    pending_transactions::PendingTransactionsMonitor,
+    pruner::Pruner,
+    query_workers::QueryWorkerPool,
     transaction_manager::TransactionManager,
+    validator_identity::ValidatorIdentityRegistry,
 };
 
 const LOG_TARGET: &str = "tari::indexer::app";
@@ -125,6 +139,22 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
         TransactionProcessorConfig::builder()
             .with_network(config.network)
             .with_template_binary_max_size_bytes(consensus_constants.template_binary_max_size_bytes)
+            .with_memory_limits(MemoryLimits {
+                max_wasm_memory_bytes: consensus_constants.max_wasm_memory_bytes,
+                max_host_allocated_bytes: consensus_constants.max_host_allocated_bytes,
+            })
+            .with_gas_limits(GasLimits {
+                max_gas_per_transaction: consensus_constants.max_gas_per_transaction,
+                ..Default::default()
+            })
+            .with_sandbox_limits(SandboxLimits {
+                max_memory_pages: consensus_constants.max_wasm_memory_pages,
+                max_table_elements: consensus_constants.max_wasm_table_elements,
+                max_execution_time: consensus_constants.max_template_execution_time,
+                ..Default::default()
+            })
+            // Dry runs report substate access statistics to help template authors reduce fees
+            .with_substate_access_stats(true)
             .build(),
         services.epoch_manager.clone(),
         services.validator_node_client_factory.clone(),
@@ -132,6 +162,36 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
         services.template_manager.clone(),
     );
 
+    // Aggregated view of the transaction pools of the current committees
+    let pending_transactions = PendingTransactionsMonitor::new(
+        config.indexer.pending_transactions.clone(),
+        services.epoch_manager.clone(),
+        services.validator_node_client_factory.clone(),
+    );
+    task::spawn(pending_transactions.clone().run(shutdown_signal.clone()));
+
+    // Enforce the retention windows of the indexed data
+    let pruner = Pruner::new(
+        config.indexer.pruning.clone(),
+        services.substate_store.clone(),
+        services.epoch_manager.clone(),
+    );
+    task::spawn(pruner.run(shutdown_signal.clone()));
+
+    // Run the event manager
+    let event_manager = Arc::new(EventManager::new(
+        services.substate_store.clone(),
+        dan_layer_scanner.clone(),
+    ));
+
+    // Heavy queries are executed on separate read-only connections so that they do not block the API
+    let query_workers = QueryWorkerPool::spawn(
+        config.indexer.query_workers.clone(),
+        config.indexer.state_db_path(),
+        shutdown_signal.clone(),
+    )
+    .map_err(|e| ExitError::new(ExitCode::DatabaseError, e))?;
+
     // Run the JSON-RPC API
     let jrpc_address = config.indexer.json_rpc_address;
     if let Some(jrpc_address) = jrpc_address {
@@ -141,6 +201,15 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
             .get_base_layer_consensus_constants()
             .await
             .map_err(|e| ExitError::new(ExitCode::UnknownError, e))?;
+        let api_key_manager = Arc::new(
+            ApiKeyManager::load(config.indexer.api_keys.clone(), services.substate_store.clone())
+                .map_err(|e| ExitError::new(ExitCode::DatabaseError, e))?,
+        );
+        task::spawn(api_key_manager.clone().run_usage_flusher(shutdown_signal.clone()));
+        let validator_identities = Arc::new(ValidatorIdentityRegistry::new(
+            services.substate_store.clone(),
+            services.epoch_manager.clone(),
+        ));
         let handlers = JsonRpcHandlers::new(
             consensus_constants,
             &services,
@@ -149,6 +218,11 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
             transaction_manager,
             services.template_manager.clone(),
             dry_run_transaction_processor,
+            api_key_manager,
+            pending_transactions,
+            query_workers,
+            event_manager.clone(),
+            validator_identities,
         );
         let jrpc_address = spawn_json_rpc(jrpc_address, handlers)?;
         // Run the http ui
@@ -163,12 +237,6 @@ pub async fn run_indexer(config: ApplicationConfig, mut shutdown_signal: Shutdow
         }
     }
 
-    // Run the event manager
-    let event_manager = Arc::new(EventManager::new(
-        services.substate_store.clone(),
-        dan_layer_scanner.clone(),
-    ));
-
     // Run the event scanner
     let event_filters: Vec<EventFilter> = config
         .indexer
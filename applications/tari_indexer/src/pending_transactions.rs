@@ -0,0 +1,189 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! An aggregated view of the transaction pools of the validators in the current epoch. One member of each committee
+//! is polled on an interval, so that users can see which committees have a submitted transaction before it is
+//! finalized. Transactions are removed from the view once no committee reports them as pending.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::*;
+use tari_dan_common_types::{Epoch, PeerAddress, ShardGroup};
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
+use tari_indexer_client::types::{IndexerPendingTransaction, PendingTransactionCommittee};
+use tari_shutdown::ShutdownSignal;
+use tari_transaction::TransactionId;
+use tari_validator_node_rpc::client::{
+    PendingTransactions,
+    TariValidatorNodeRpcClientFactory,
+    ValidatorNodeClientFactory,
+    ValidatorNodeRpcClient,
+};
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::config::PendingTransactionsConfig;
+
+const LOG_TARGET: &str = "tari::indexer::pending_transactions";
+
+#[derive(Clone)]
+pub struct PendingTransactionsMonitor {
+    config: PendingTransactionsConfig,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    client_factory: TariValidatorNodeRpcClientFactory,
+    transactions: Arc<Mutex<HashMap<TransactionId, IndexerPendingTransaction>>>,
+}
+
+impl PendingTransactionsMonitor {
+    pub fn new(
+        config: PendingTransactionsConfig,
+        epoch_manager: EpochManagerHandle<PeerAddress>,
+        client_factory: TariValidatorNodeRpcClientFactory,
+    ) -> Self {
+        Self {
+            config,
+            epoch_manager,
+            client_factory,
+            transactions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Returns the pending transactions, oldest first
+    pub fn get_all(&self, limit: Option<usize>) -> Vec<IndexerPendingTransaction> {
+        let transactions = self.transactions.lock().unwrap();
+        let mut all = transactions.values().cloned().collect::<Vec<_>>();
+        all.sort_by_key(|tx| tx.first_seen_at);
+        if let Some(limit) = limit {
+            all.truncate(limit);
+        }
+        all
+    }
+
+    pub fn get(&self, transaction_id: &TransactionId) -> Option<IndexerPendingTransaction> {
+        self.transactions.lock().unwrap().get(transaction_id).cloned()
+    }
+
+    pub async fn run(self, mut shutdown: ShutdownSignal) {
+        if !self.config.enabled {
+            return;
+        }
+        info!(
+            target: LOG_TARGET,
+            "Polling validator transaction pools every {:.2?}", self.config.poll_interval
+        );
+        let mut interval = time::interval(self.config.poll_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.poll().await {
+                        warn!(target: LOG_TARGET, "Failed to poll validator transaction pools: {}", err);
+                    }
+                },
+                _ = shutdown.wait() => break,
+            }
+        }
+    }
+
+    async fn poll(&self) -> Result<(), anyhow::Error> {
+        let epoch = self.epoch_manager.current_epoch().await?;
+        let committees = self.epoch_manager.get_committees(epoch).await?;
+
+        let mut results = Vec::with_capacity(committees.len());
+        for (shard_group, committee) in committees {
+            // Committees that could not be reached keep their last known transactions
+            if let Some(pending) = self
+                .fetch_from_committee(epoch, shard_group, committee.addresses())
+                .await
+            {
+                results.push((shard_group, pending));
+            }
+        }
+
+        self.apply_results(results);
+        Ok(())
+    }
+
+    async fn fetch_from_committee<'a, I: Iterator<Item = &'a PeerAddress>>(
+        &self,
+        epoch: Epoch,
+        shard_group: ShardGroup,
+        members: I,
+    ) -> Option<PendingTransactions> {
+        for address in members {
+            let mut client = self.client_factory.create_client(address);
+            match client
+                .get_pending_transactions(self.config.max_transactions_per_committee)
+                .await
+            {
+                Ok(pending) => return Some(pending),
+                Err(err) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Failed to get pending transactions from {} in {} ({}): {}", address, shard_group, epoch, err
+                    );
+                },
+            }
+        }
+        warn!(
+            target: LOG_TARGET,
+            "No member of committee {} responded with pending transactions in {}", shard_group, epoch
+        );
+        None
+    }
+
+    fn apply_results(&self, results: Vec<(ShardGroup, PendingTransactions)>) {
+        let now = now_unix_secs();
+        let mut transactions = self.transactions.lock().unwrap();
+
+        let mut reported = BTreeMap::<TransactionId, Vec<PendingTransactionCommittee>>::new();
+        for (shard_group, pending) in &results {
+            for tx in &pending.transactions {
+                reported
+                    .entry(tx.transaction_id)
+                    .or_default()
+                    .push(PendingTransactionCommittee {
+                        shard_group: *shard_group,
+                        stage: tx.stage,
+                        is_ready: tx.is_ready,
+                    });
+            }
+        }
+
+        // A committee that responded and no longer reports a transaction has finalized (or dropped) it
+        let polled = results.iter().map(|(sg, _)| *sg).collect::<Vec<_>>();
+        for tx in transactions.values_mut() {
+            tx.committees.retain(|c| !polled.contains(&c.shard_group));
+        }
+
+        for (transaction_id, committees) in reported {
+            let entry = transactions
+                .entry(transaction_id)
+                .or_insert_with(|| IndexerPendingTransaction {
+                    transaction_id,
+                    first_seen_at: now,
+                    last_seen_at: now,
+                    committees: vec![],
+                });
+            entry.last_seen_at = now;
+            entry.committees.extend(committees);
+            entry.committees.sort_by_key(|c| c.shard_group);
+        }
+        transactions.retain(|_, tx| !tx.committees.is_empty());
+    }
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,128 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Enforces the retention window of each class of indexed data. Raw substate values, decoded events and per-epoch
+//! stats are pruned independently, so that a deployment can cap its disk usage while keeping the data it cares about.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use log::*;
+use tari_dan_common_types::{Epoch, PeerAddress};
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
+use tari_shutdown::ShutdownSignal;
+use tokio::{time, time::MissedTickBehavior};
+
+use crate::{
+    config::PruningConfig,
+    substate_storage_sqlite::sqlite_substate_store_factory::{
+        SqliteSubstateStore,
+        SubstateStore,
+        SubstateStoreWriteTransaction,
+    },
+};
+
+const LOG_TARGET: &str = "tari::indexer::pruner";
+
+pub struct Pruner {
+    config: PruningConfig,
+    substate_store: SqliteSubstateStore,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+}
+
+impl Pruner {
+    pub fn new(
+        config: PruningConfig,
+        substate_store: SqliteSubstateStore,
+        epoch_manager: EpochManagerHandle<PeerAddress>,
+    ) -> Self {
+        Self {
+            config,
+            substate_store,
+            epoch_manager,
+        }
+    }
+
+    pub async fn run(self, mut shutdown: ShutdownSignal) {
+        if !self.config.enabled {
+            return;
+        }
+        info!(
+            target: LOG_TARGET,
+            "Pruning every {:.2?} (substates: {}, events: {}, epoch stats: {})",
+            self.config.interval,
+            display_retention(self.config.substate_retention),
+            display_retention(self.config.event_retention),
+            self.config
+                .epoch_stats_retention_epochs
+                .map_or_else(|| "forever".to_string(), |n| format!("{} epoch(s)", n)),
+        );
+        let mut interval = time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(err) = self.prune().await {
+                        warn!(target: LOG_TARGET, "Failed to prune indexer data: {}", err);
+                    }
+                },
+                _ = shutdown.wait() => break,
+            }
+        }
+    }
+
+    async fn prune(&self) -> Result<(), anyhow::Error> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let substates_before = self.config.substate_retention.map(|r| cutoff_timestamp(now, r));
+        let events_before = self.config.event_retention.map(|r| cutoff_timestamp(now, r));
+        let epoch_stats_before = match self.config.epoch_stats_retention_epochs {
+            Some(num_epochs) => {
+                let current_epoch = self.epoch_manager.current_epoch().await?;
+                Some(Epoch(current_epoch.as_u64().saturating_sub(num_epochs)))
+            },
+            None => None,
+        };
+
+        let (num_substates, num_events, num_epoch_stats) = self.substate_store.with_write_tx(|tx| {
+            let num_substates = substates_before
+                .map(|ts| tx.delete_substates_older_than(ts))
+                .transpose()?;
+            let num_events = events_before.map(|ts| tx.delete_events_older_than(ts)).transpose()?;
+            let num_epoch_stats = epoch_stats_before
+                .map(|epoch| tx.delete_epoch_stats_older_than(epoch))
+                .transpose()?;
+            Ok::<_, anyhow::Error>((num_substates, num_events, num_epoch_stats))
+        })?;
+
+        info!(
+            target: LOG_TARGET,
+            "🧹 Pruned {} substate(s), {} event(s) and the stats of {} epoch(s)",
+            num_substates.unwrap_or(0),
+            num_events.unwrap_or(0),
+            num_epoch_stats.unwrap_or(0),
+        );
+        Ok(())
+    }
+}
+
+/// Returns the unix timestamp in seconds before which data is outside of the retention window
+fn cutoff_timestamp(now: Duration, retention: Duration) -> i64 {
+    i64::try_from(now.saturating_sub(retention).as_secs()).unwrap_or(i64::MAX)
+}
+
+fn display_retention(retention: Option<Duration>) -> String {
+    retention.map_or_else(|| "forever".to_string(), |r| format!("{:.2?}", r))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_calculates_the_cutoff_timestamp() {
+        let now = Duration::from_secs(10_000);
+        assert_eq!(cutoff_timestamp(now, Duration::from_secs(3_600)), 6_400);
+        // Retention windows longer than the time since the unix epoch keep everything
+        assert_eq!(cutoff_timestamp(now, Duration::from_secs(20_000)), 0);
+    }
+}
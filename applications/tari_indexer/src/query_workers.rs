@@ -0,0 +1,292 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Executes heavy queries on a pool of workers, each with its own read-only database connection, so that long-running
+//! queries do not block the interactive JSON-RPC API. A query is submitted with `submit_query` and its result is polled
+//! with `get_query_result`. Results are cached for a while, and an identical query that is submitted in that time is
+//! served from the cache.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use log::*;
+use tari_dan_storage::StorageError;
+use tari_indexer_client::types::{IndexerQuery, IndexerQueryResult, IndexerQueryStatus};
+use tari_shutdown::ShutdownSignal;
+use tokio::{sync::mpsc, task, time};
+
+use crate::{
+    config::QueryWorkersConfig,
+    substate_storage_sqlite::sqlite_substate_store_factory::{
+        SqliteSubstateStore,
+        SubstateStore,
+        SubstateStoreReadTransaction,
+    },
+};
+
+const LOG_TARGET: &str = "tari::indexer::query_workers";
+
+pub type QueryId = u64;
+
+type QueryReceiver = Arc<tokio::sync::Mutex<mpsc::Receiver<(QueryId, IndexerQuery)>>>;
+
+#[derive(Clone)]
+pub struct QueryWorkerPool {
+    config: QueryWorkersConfig,
+    sender: mpsc::Sender<(QueryId, IndexerQuery)>,
+    jobs: Arc<Mutex<QueryJobs>>,
+}
+
+impl QueryWorkerPool {
+    /// Opens a read-only connection to the database for each worker and starts the workers
+    pub fn spawn(
+        config: QueryWorkersConfig,
+        db_path: PathBuf,
+        shutdown: ShutdownSignal,
+    ) -> Result<Self, QueryWorkerError> {
+        let (sender, receiver) = mpsc::channel(config.max_queued_queries.max(1));
+        let receiver = Arc::new(tokio::sync::Mutex::new(receiver));
+        let pool = Self {
+            config,
+            sender,
+            jobs: Arc::new(Mutex::new(QueryJobs::default())),
+        };
+
+        for _ in 0..pool.config.num_workers.max(1) {
+            let store = SqliteSubstateStore::try_open_read_only(db_path.clone())?;
+            task::spawn(pool.clone().run_worker(store, receiver.clone(), shutdown.clone()));
+        }
+        info!(
+            target: LOG_TARGET,
+            "Started {} query worker(s) (time limit: {:.2?})",
+            pool.config.num_workers.max(1),
+            pool.config.time_limit
+        );
+
+        Ok(pool)
+    }
+
+    /// Queues the query. If an identical query is queued, running or completed within the result TTL, the id of that
+    /// query is returned instead.
+    pub fn submit(&self, query: IndexerQuery) -> Result<(QueryId, IndexerQueryStatus), QueryWorkerError> {
+        let cache_key = serde_json::to_string(&query)
+            .map_err(|e| QueryWorkerError::InvalidQuery(e.to_string()))?;
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.remove_expired(self.config.result_ttl);
+
+        if let Some(job_id) = jobs.by_query.get(&cache_key) {
+            let status = &jobs.jobs[job_id].status;
+            // Failed and timed out queries are executed again
+            if matches!(
+                status,
+                IndexerQueryStatus::Queued | IndexerQueryStatus::Running | IndexerQueryStatus::Completed
+            ) {
+                debug!(target: LOG_TARGET, "Query {} reused for an identical query", job_id);
+                return Ok((*job_id, status.clone()));
+            }
+        }
+
+        let query_id = jobs.next_id;
+        self.sender
+            .try_send((query_id, query))
+            .map_err(|_| QueryWorkerError::QueueFull)?;
+        jobs.next_id += 1;
+        jobs.by_query.insert(cache_key.clone(), query_id);
+        jobs.jobs.insert(query_id, QueryJob {
+            cache_key,
+            status: IndexerQueryStatus::Queued,
+            result: None,
+            finished_at: None,
+        });
+
+        Ok((query_id, IndexerQueryStatus::Queued))
+    }
+
+    /// Returns the status and, if completed, the result of the query
+    pub fn get(&self, query_id: QueryId) -> Option<(IndexerQueryStatus, Option<IndexerQueryResult>)> {
+        let mut jobs = self.jobs.lock().unwrap();
+        jobs.remove_expired(self.config.result_ttl);
+        jobs.jobs
+            .get(&query_id)
+            .map(|job| (job.status.clone(), job.result.clone()))
+    }
+
+    async fn run_worker(self, store: SqliteSubstateStore, receiver: QueryReceiver, mut shutdown: ShutdownSignal) {
+        loop {
+            let next = {
+                let mut receiver = receiver.lock().await;
+                tokio::select! {
+                    next = receiver.recv() => next,
+                    _ = shutdown.wait() => None,
+                }
+            };
+            let Some((query_id, query)) = next else {
+                break;
+            };
+
+            self.set_status(query_id, IndexerQueryStatus::Running, None);
+            let timer = Instant::now();
+            let store = store.clone();
+            // A query that times out is not interrupted. Its result is discarded and the worker's connection remains
+            // busy until the query returns.
+            let result = time::timeout(
+                self.config.time_limit,
+                task::spawn_blocking(move || execute_query(&store, query)),
+            )
+            .await;
+
+            match result {
+                Ok(Ok(Ok(result))) => {
+                    debug!(target: LOG_TARGET, "Query {} completed in {:.2?}", query_id, timer.elapsed());
+                    self.set_status(query_id, IndexerQueryStatus::Completed, Some(result));
+                },
+                Ok(Ok(Err(err))) => {
+                    warn!(target: LOG_TARGET, "Query {} failed: {}", query_id, err);
+                    let error = err.to_string();
+                    self.set_status(query_id, IndexerQueryStatus::Failed { error }, None);
+                },
+                Ok(Err(err)) => {
+                    error!(target: LOG_TARGET, "Query {} panicked: {}", query_id, err);
+                    let error = err.to_string();
+                    self.set_status(query_id, IndexerQueryStatus::Failed { error }, None);
+                },
+                Err(_) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Query {} did not complete within {:.2?}", query_id, self.config.time_limit
+                    );
+                    self.set_status(query_id, IndexerQueryStatus::TimedOut, None);
+                },
+            }
+        }
+    }
+
+    fn set_status(&self, query_id: QueryId, status: IndexerQueryStatus, result: Option<IndexerQueryResult>) {
+        let mut jobs = self.jobs.lock().unwrap();
+        if let Some(job) = jobs.jobs.get_mut(&query_id) {
+            if !matches!(status, IndexerQueryStatus::Queued | IndexerQueryStatus::Running) {
+                job.finished_at = Some(Instant::now());
+            }
+            job.status = status;
+            job.result = result;
+        }
+    }
+}
+
+#[derive(Default)]
+struct QueryJobs {
+    next_id: QueryId,
+    jobs: HashMap<QueryId, QueryJob>,
+    /// The id of the most recent job for each query, keyed by the serialized query
+    by_query: HashMap<String, QueryId>,
+}
+
+impl QueryJobs {
+    fn remove_expired(&mut self, ttl: Duration) {
+        let by_query = &mut self.by_query;
+        self.jobs.retain(|id, job| {
+            let is_expired = job.finished_at.is_some_and(|at| at.elapsed() >= ttl);
+            if is_expired && by_query.get(&job.cache_key) == Some(id) {
+                by_query.remove(&job.cache_key);
+            }
+            !is_expired
+        });
+    }
+}
+
+struct QueryJob {
+    cache_key: String,
+    status: IndexerQueryStatus,
+    result: Option<IndexerQueryResult>,
+    finished_at: Option<Instant>,
+}
+
+fn execute_query(store: &SqliteSubstateStore, query: IndexerQuery) -> Result<IndexerQueryResult, StorageError> {
+    let mut tx = store.create_read_tx()?;
+    let result = match query {
+        IndexerQuery::ListSubstates {
+            filter_by_template,
+            filter_by_type,
+            limit,
+            offset,
+        } => IndexerQueryResult::ListSubstates {
+            substates: tx.list_substates(filter_by_type, filter_by_template, limit, offset)?,
+        },
+        IndexerQuery::EpochStats { limit } => IndexerQueryResult::EpochStats {
+            stats: tx.get_epoch_stats(limit)?.into_iter().map(Into::into).collect(),
+        },
+        IndexerQuery::NonFungibleCollections => IndexerQueryResult::NonFungibleCollections {
+            collections: tx.get_non_fungible_collections()?,
+        },
+    };
+    Ok(result)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QueryWorkerError {
+    #[error("Too many queries are queued. Try again later.")]
+    QueueFull,
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+    #[error("Storage error: {0}")]
+    StorageError(#[from] StorageError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_pool(max_queued_queries: usize) -> (QueryWorkerPool, mpsc::Receiver<(QueryId, IndexerQuery)>) {
+        let (sender, receiver) = mpsc::channel(max_queued_queries);
+        let pool = QueryWorkerPool {
+            config: QueryWorkersConfig {
+                max_queued_queries,
+                ..Default::default()
+            },
+            sender,
+            jobs: Arc::new(Mutex::new(QueryJobs::default())),
+        };
+        (pool, receiver)
+    }
+
+    #[test]
+    fn it_reuses_identical_queries() {
+        let (pool, _receiver) = create_pool(10);
+        let (id1, _) = pool.submit(IndexerQuery::EpochStats { limit: 10 }).unwrap();
+        let (id2, _) = pool.submit(IndexerQuery::EpochStats { limit: 10 }).unwrap();
+        let (id3, _) = pool.submit(IndexerQuery::EpochStats { limit: 20 }).unwrap();
+        assert_eq!(id1, id2);
+        assert_ne!(id1, id3);
+
+        // Failed queries are executed again
+        let error = "boom".to_string();
+        pool.set_status(id1, IndexerQueryStatus::Failed { error }, None);
+        let (id4, status) = pool.submit(IndexerQuery::EpochStats { limit: 10 }).unwrap();
+        assert_ne!(id1, id4);
+        assert!(matches!(status, IndexerQueryStatus::Queued));
+    }
+
+    #[test]
+    fn it_rejects_queries_when_the_queue_is_full() {
+        let (pool, _receiver) = create_pool(1);
+        pool.submit(IndexerQuery::NonFungibleCollections).unwrap();
+        let err = pool.submit(IndexerQuery::EpochStats { limit: 10 }).unwrap_err();
+        assert!(matches!(err, QueryWorkerError::QueueFull));
+    }
+
+    #[test]
+    fn it_removes_expired_results() {
+        let (pool, _receiver) = create_pool(10);
+        let (id, _) = pool.submit(IndexerQuery::NonFungibleCollections).unwrap();
+        pool.set_status(id, IndexerQueryStatus::Completed, None);
+        assert!(pool.get(id).is_some());
+
+        pool.jobs.lock().unwrap().remove_expired(Duration::ZERO);
+        assert!(pool.get(id).is_none());
+        assert!(pool.jobs.lock().unwrap().by_query.is_empty());
+    }
+}
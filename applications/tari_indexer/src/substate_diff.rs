@@ -0,0 +1,148 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde_json as json;
+use tari_dan_app_utilities::json_encoding::{encode_substate_into_json, JsonEncodingError};
+use tari_engine_types::substate::Substate;
+use tari_indexer_client::types::{SubstateFieldChange, SubstateFieldChangeKind};
+
+/// Returns the field-level changes between two versions of a substate. Binary values (e.g. component state) are decoded
+/// into JSON before being compared, so that changes are reported per field rather than for the whole value.
+pub fn diff_substates(from: &Substate, to: &Substate) -> Result<Vec<SubstateFieldChange>, JsonEncodingError> {
+    let from = substate_value_json(from)?;
+    let to = substate_value_json(to)?;
+    let mut changes = Vec::new();
+    diff_json_values(String::new(), &from, &to, &mut changes);
+    Ok(changes)
+}
+
+/// Returns the inner value of the encoded substate, dropping the substate version and the substate type variant (e.g.
+/// `{"Component": {...}}`) so that paths start at the fields of the value.
+fn substate_value_json(substate: &Substate) -> Result<json::Value, JsonEncodingError> {
+    let mut encoded = encode_substate_into_json(substate)?;
+    let value = encoded
+        .get_mut("substate")
+        .map(json::Value::take)
+        .ok_or_else(|| JsonEncodingError::Unexpected("substate field does not exist".to_string()))?;
+    match value {
+        json::Value::Object(mut variant) if variant.len() == 1 => {
+            Ok(variant.values_mut().next().map(json::Value::take).unwrap_or_default())
+        },
+        value => Ok(value),
+    }
+}
+
+fn diff_json_values(path: String, from: &json::Value, to: &json::Value, changes: &mut Vec<SubstateFieldChange>) {
+    match (from, to) {
+        (json::Value::Object(from), json::Value::Object(to)) => {
+            for (key, from_value) in from {
+                let path = join_key(&path, key);
+                match to.get(key) {
+                    Some(to_value) => diff_json_values(path, from_value, to_value, changes),
+                    None => changes.push(removed(path, from_value)),
+                }
+            }
+            for (key, to_value) in to {
+                if !from.contains_key(key) {
+                    changes.push(added(join_key(&path, key), to_value));
+                }
+            }
+        },
+        (json::Value::Array(from), json::Value::Array(to)) => {
+            for (i, from_value) in from.iter().enumerate() {
+                let path = format!("{}[{}]", path, i);
+                match to.get(i) {
+                    Some(to_value) => diff_json_values(path, from_value, to_value, changes),
+                    None => changes.push(removed(path, from_value)),
+                }
+            }
+            for (i, to_value) in to.iter().enumerate().skip(from.len()) {
+                changes.push(added(format!("{}[{}]", path, i), to_value));
+            }
+        },
+        (from, to) => {
+            if from != to {
+                changes.push(SubstateFieldChange {
+                    path,
+                    kind: SubstateFieldChangeKind::Modified,
+                    old_value: Some(from.clone()),
+                    new_value: Some(to.clone()),
+                });
+            }
+        },
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn added(path: String, value: &json::Value) -> SubstateFieldChange {
+    SubstateFieldChange {
+        path,
+        kind: SubstateFieldChangeKind::Added,
+        old_value: None,
+        new_value: Some(value.clone()),
+    }
+}
+
+fn removed(path: String, value: &json::Value) -> SubstateFieldChange {
+    SubstateFieldChange {
+        path,
+        kind: SubstateFieldChangeKind::Removed,
+        old_value: Some(value.clone()),
+        new_value: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn diff(from: json::Value, to: json::Value) -> Vec<SubstateFieldChange> {
+        let mut changes = Vec::new();
+        diff_json_values(String::new(), &from, &to, &mut changes);
+        changes
+    }
+
+    #[test]
+    fn it_reports_modified_nested_fields() {
+        let changes = diff(
+            json!({ "state": { "counter": 1, "owner": "alice" } }),
+            json!({ "state": { "counter": 2, "owner": "alice" } }),
+        );
+        assert_eq!(changes, vec![SubstateFieldChange {
+            path: "state.counter".to_string(),
+            kind: SubstateFieldChangeKind::Modified,
+            old_value: Some(json!(1)),
+            new_value: Some(json!(2)),
+        }]);
+    }
+
+    #[test]
+    fn it_reports_added_and_removed_fields() {
+        let changes = diff(
+            json!({ "a": 1, "items": [1, 2, 3] }),
+            json!({ "b": true, "items": [1, 5] }),
+        );
+        let summary = changes.iter().map(|c| (c.path.as_str(), c.kind)).collect::<Vec<_>>();
+        assert_eq!(summary, vec![
+            ("a", SubstateFieldChangeKind::Removed),
+            ("items[1]", SubstateFieldChangeKind::Modified),
+            ("items[2]", SubstateFieldChangeKind::Removed),
+            ("b", SubstateFieldChangeKind::Added),
+        ]);
+    }
+
+    #[test]
+    fn it_reports_no_changes_for_equal_values() {
+        let value = json!({ "state": { "vaults": [{ "id": "vault_1" }] } });
+        assert!(diff(value.clone(), value).is_empty());
+    }
+}
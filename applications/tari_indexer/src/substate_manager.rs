@@ -28,7 +28,7 @@ use tari_dan_app_utilities::substate_file_cache::SubstateFileCache;
 use tari_dan_common_types::{substate_type::SubstateType, PeerAddress};
 use tari_engine_types::substate::{Substate, SubstateId};
 use tari_epoch_manager::base_layer::EpochManagerHandle;
-use tari_indexer_client::types::ListSubstateItem;
+use tari_indexer_client::types::{IndexerEpochStats, ListSubstateItem};
 use tari_indexer_lib::{substate_scanner::SubstateScanner, NonFungibleSubstate};
 use tari_template_lib::models::TemplateAddress;
 use tari_transaction::TransactionId;
@@ -177,6 +177,13 @@ impl SubstateManager {
         Ok(count as u64)
     }
 
+    /// Returns the stats of the most recent epochs, most recent first
+    pub async fn get_epoch_stats(&self, limit: u64) -> Result<Vec<IndexerEpochStats>, anyhow::Error> {
+        let mut tx = self.substate_store.create_read_tx()?;
+        let stats = tx.get_epoch_stats(limit)?.into_iter().map(Into::into).collect();
+        Ok(stats)
+    }
+
     pub async fn get_non_fungibles(
         &self,
         substate_address: &SubstateId,
@@ -0,0 +1,19 @@
+-- API keys that grant access to the JSON-RPC API when API keys are enabled. Only a hash of the secret key is stored.
+create table api_keys
+(
+    id                    integer not NULL primary key AUTOINCREMENT,
+    key_id                text    not NULL,
+    name                  text    not NULL,
+    secret_hash           text    not NULL,
+    -- JSON array of the JSON-RPC methods that the key may call. NULL allows all methods.
+    allowed_methods       text    NULL,
+    -- NULL uses the configured default rate limit
+    rate_limit_per_minute integer NULL,
+    total_requests        bigint  not NULL DEFAULT 0,
+    last_used_at          bigint  NULL,
+    created_at            bigint  not NULL,
+    revoked_at            bigint  NULL
+);
+
+create unique index api_keys_uniq_key_id on api_keys (key_id);
+create unique index api_keys_uniq_secret_hash on api_keys (secret_hash);
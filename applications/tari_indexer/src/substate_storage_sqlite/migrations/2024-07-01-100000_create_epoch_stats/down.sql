@@ -0,0 +1,3 @@
+drop index substates_idx_timestamp;
+drop index events_idx_timestamp;
+drop table epoch_stats;
@@ -0,0 +1,19 @@
+-- Aggregated counts for each scanned epoch. These are kept separately from the indexed data so that substates and
+-- events can be pruned without losing the aggregates.
+create table epoch_stats
+(
+    id                integer not NULL primary key AUTOINCREMENT,
+    epoch             bigint  not NULL,
+    num_blocks        bigint  not NULL DEFAULT 0,
+    num_transactions  bigint  not NULL DEFAULT 0,
+    num_events        bigint  not NULL DEFAULT 0,
+    -- The number of events that matched the event filters and were stored
+    num_stored_events bigint  not NULL DEFAULT 0,
+    updated_at        bigint  not NULL
+);
+
+create unique index epoch_stats_uniq_epoch on epoch_stats (epoch);
+
+-- Pruning deletes by age
+create index events_idx_timestamp on events (timestamp);
+create index substates_idx_timestamp on substates (timestamp);
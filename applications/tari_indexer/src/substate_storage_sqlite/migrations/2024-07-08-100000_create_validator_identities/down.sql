@@ -0,0 +1 @@
+drop table validator_identities;
@@ -0,0 +1,16 @@
+-- Metadata provided by validator operators. A row is only written once the operator has proven control of the
+-- validator key by signing an identity challenge issued by the indexer.
+create table validator_identities
+(
+    id          integer not NULL primary key AUTOINCREMENT,
+    -- Hex encoded validator public key
+    public_key  text    not NULL,
+    name        text    not NULL,
+    website     text    NULL,
+    contact     text    NULL,
+    -- JSON encoded signature of the challenge and metadata
+    signature   text    not NULL,
+    verified_at bigint  not NULL
+);
+
+create unique index validator_identities_uniq_public_key on validator_identities (public_key);
@@ -0,0 +1,36 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use crate::substate_storage_sqlite::schema::*;
+
+#[derive(Debug, Clone, Identifiable, Queryable)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKey {
+    pub id: i32,
+    pub key_id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub allowed_methods: Option<String>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub total_requests: i64,
+    pub last_used_at: Option<i64>,
+    pub created_at: i64,
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiKey {
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = api_keys)]
+pub struct NewApiKey {
+    pub key_id: String,
+    pub name: String,
+    pub secret_hash: String,
+    pub allowed_methods: Option<String>,
+    pub rate_limit_per_minute: Option<i32>,
+    pub created_at: i64,
+}
@@ -0,0 +1,44 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::Epoch;
+use tari_indexer_client::types::IndexerEpochStats;
+
+use crate::substate_storage_sqlite::schema::*;
+
+#[derive(Debug, Clone, Identifiable, Queryable)]
+#[diesel(table_name = epoch_stats)]
+pub struct EpochStats {
+    pub id: i32,
+    pub epoch: i64,
+    pub num_blocks: i64,
+    pub num_transactions: i64,
+    pub num_events: i64,
+    pub num_stored_events: i64,
+    pub updated_at: i64,
+}
+
+impl From<EpochStats> for IndexerEpochStats {
+    fn from(row: EpochStats) -> Self {
+        Self {
+            epoch: Epoch(row.epoch as u64),
+            num_blocks: row.num_blocks as u64,
+            num_transactions: row.num_transactions as u64,
+            num_events: row.num_events as u64,
+            num_stored_events: row.num_stored_events as u64,
+            updated_at: row.updated_at as u64,
+        }
+    }
+}
+
+/// Counts that are added to the stats of an epoch
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = epoch_stats)]
+pub struct NewEpochStats {
+    pub epoch: i64,
+    pub num_blocks: i64,
+    pub num_transactions: i64,
+    pub num_events: i64,
+    pub num_stored_events: i64,
+    pub updated_at: i64,
+}
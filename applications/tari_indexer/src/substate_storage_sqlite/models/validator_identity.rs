@@ -0,0 +1,28 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use crate::substate_storage_sqlite::schema::*;
+
+#[derive(Debug, Clone, Identifiable, Queryable)]
+#[diesel(table_name = validator_identities)]
+pub struct ValidatorIdentity {
+    pub id: i32,
+    pub public_key: String,
+    pub name: String,
+    pub website: Option<String>,
+    pub contact: Option<String>,
+    pub signature: String,
+    pub verified_at: i64,
+}
+
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = validator_identities)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewValidatorIdentity {
+    pub public_key: String,
+    pub name: String,
+    pub website: Option<String>,
+    pub contact: Option<String>,
+    pub signature: String,
+    pub verified_at: i64,
+}
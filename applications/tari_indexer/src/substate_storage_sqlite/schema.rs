@@ -1,5 +1,32 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_keys (id) {
+        id -> Integer,
+        key_id -> Text,
+        name -> Text,
+        secret_hash -> Text,
+        allowed_methods -> Nullable<Text>,
+        rate_limit_per_minute -> Nullable<Integer>,
+        total_requests -> BigInt,
+        last_used_at -> Nullable<BigInt>,
+        created_at -> BigInt,
+        revoked_at -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    epoch_stats (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        num_blocks -> BigInt,
+        num_transactions -> BigInt,
+        num_events -> BigInt,
+        num_stored_events -> BigInt,
+        updated_at -> BigInt,
+    }
+}
+
 diesel::table! {
     event_payloads (id) {
         id -> Integer,
@@ -53,12 +80,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    validator_identities (id) {
+        id -> Integer,
+        public_key -> Text,
+        name -> Text,
+        website -> Nullable<Text>,
+        contact -> Nullable<Text>,
+        signature -> Text,
+        verified_at -> BigInt,
+    }
+}
+
 diesel::joinable!(event_payloads -> events (event_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_keys,
+    epoch_stats,
     event_payloads,
     events,
     non_fungible_indexes,
     scanned_block_ids,
     substates,
+    validator_identities,
 );
@@ -53,8 +53,11 @@ use super::models::{
     non_fungible_index::{IndexedNftSubstate, NewNonFungibleIndex},
 };
 use crate::substate_storage_sqlite::models::{
+    api_key::{ApiKey, NewApiKey},
+    epoch_stats::{EpochStats, NewEpochStats},
     events::{Event, NewEventPayloadField, ScannedBlockId},
     substate::{NewSubstate, Substate},
+    validator_identity::{NewValidatorIdentity, ValidatorIdentity},
 };
 
 const LOG_TARGET: &str = "tari::indexer::substate_storage_sqlite";
@@ -87,6 +90,30 @@ impl SqliteSubstateStore {
         })
     }
 
+    /// Opens a separate read-only connection to an existing database. Queries on this connection do not wait for the
+    /// connection that is used by the rest of the indexer.
+    pub fn try_open_read_only(path: PathBuf) -> Result<Self, StorageError> {
+        let database_url = path.to_str().expect("database_url utf-8 error").to_string();
+        let mut connection = SqliteConnection::establish(&database_url).map_err(SqliteStorageError::from)?;
+        sql_query("PRAGMA query_only = ON;")
+            .execute(&mut connection)
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "set pragma".to_string(),
+            })?;
+        // Wait for the writer instead of failing immediately if the database is locked
+        sql_query("PRAGMA busy_timeout = 5000;")
+            .execute(&mut connection)
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "set pragma".to_string(),
+            })?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+        })
+    }
+
     pub fn find_by_address(address: String, conn: &mut SqliteConnection) -> Result<Option<Substate>, StorageError> {
         use crate::substate_storage_sqlite::schema::substates;
 
@@ -236,6 +263,11 @@ pub trait SubstateStoreReadTransaction {
         epoch: Epoch,
         shard_group: ShardGroup,
     ) -> Result<Option<BlockId>, StorageError>;
+    fn get_all_api_keys(&mut self) -> Result<Vec<ApiKey>, StorageError>;
+    /// Returns the stats of the most recent epochs, most recent first
+    fn get_epoch_stats(&mut self, limit: u64) -> Result<Vec<EpochStats>, StorageError>;
+    fn get_validator_identity(&mut self, public_key: &str) -> Result<Option<ValidatorIdentity>, StorageError>;
+    fn get_validator_identities(&mut self, offset: u64, limit: u64) -> Result<Vec<ValidatorIdentity>, StorageError>;
 }
 
 impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
@@ -647,6 +679,66 @@ impl SubstateStoreReadTransaction for SqliteSubstateStoreReadTransaction<'_> {
 
         Ok(block_id_option)
     }
+
+    fn get_all_api_keys(&mut self) -> Result<Vec<ApiKey>, StorageError> {
+        use crate::substate_storage_sqlite::schema::api_keys;
+
+        let keys = api_keys::table
+            .order_by(api_keys::id.asc())
+            .get_results(self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("get_all_api_keys: {}", e),
+            })?;
+
+        Ok(keys)
+    }
+
+    fn get_epoch_stats(&mut self, limit: u64) -> Result<Vec<EpochStats>, StorageError> {
+        use crate::substate_storage_sqlite::schema::epoch_stats;
+
+        let limit = i64::try_from(limit).map_err(|_| StorageError::InvalidIntegerCast)?;
+        let stats = epoch_stats::table
+            .order_by(epoch_stats::epoch.desc())
+            .limit(limit)
+            .get_results(self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("get_epoch_stats: {}", e),
+            })?;
+
+        Ok(stats)
+    }
+
+    fn get_validator_identity(&mut self, public_key: &str) -> Result<Option<ValidatorIdentity>, StorageError> {
+        use crate::substate_storage_sqlite::schema::validator_identities;
+
+        let identity = validator_identities::table
+            .filter(validator_identities::public_key.eq(public_key))
+            .first(self.connection())
+            .optional()
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("get_validator_identity: {}", e),
+            })?;
+
+        Ok(identity)
+    }
+
+    fn get_validator_identities(&mut self, offset: u64, limit: u64) -> Result<Vec<ValidatorIdentity>, StorageError> {
+        use crate::substate_storage_sqlite::schema::validator_identities;
+
+        let offset = i64::try_from(offset).map_err(|_| StorageError::InvalidIntegerCast)?;
+        let limit = i64::try_from(limit).map_err(|_| StorageError::InvalidIntegerCast)?;
+        let identities = validator_identities::table
+            .order_by(validator_identities::name.asc())
+            .then_order_by(validator_identities::public_key.asc())
+            .offset(offset)
+            .limit(limit)
+            .get_results(self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("get_validator_identities: {}", e),
+            })?;
+
+        Ok(identities)
+    }
 }
 
 pub struct SqliteSubstateStoreWriteTransaction<'a> {
@@ -680,6 +772,17 @@ pub trait SubstateStoreWriteTransaction {
     fn save_event(&mut self, new_event: NewEvent) -> Result<(), StorageError>;
     fn save_scanned_block_id(&mut self, new_scanned_block_id: NewScannedBlockId) -> Result<(), StorageError>;
     fn delete_scanned_epochs_older_than(&mut self, epoch: Epoch) -> Result<(), StorageError>;
+    fn insert_api_key(&mut self, new_api_key: NewApiKey) -> Result<(), StorageError>;
+    /// Marks the API key as revoked. Returns false if there is no unrevoked key with the given id.
+    fn revoke_api_key(&mut self, key_id: &str, revoked_at: i64) -> Result<bool, StorageError>;
+    fn add_api_key_usage(&mut self, key_id: &str, num_requests: u64, last_used_at: i64) -> Result<(), StorageError>;
+    /// Adds the counts to the stats of the epoch, creating the stats if they do not exist
+    fn add_epoch_stats(&mut self, stats: NewEpochStats) -> Result<(), StorageError>;
+    fn delete_substates_older_than(&mut self, timestamp: i64) -> Result<usize, StorageError>;
+    fn delete_events_older_than(&mut self, timestamp: i64) -> Result<usize, StorageError>;
+    fn delete_epoch_stats_older_than(&mut self, epoch: Epoch) -> Result<usize, StorageError>;
+    /// Inserts the identity or replaces the existing identity of the validator
+    fn set_validator_identity(&mut self, identity: NewValidatorIdentity) -> Result<(), StorageError>;
 }
 
 impl SubstateStoreWriteTransaction for SqliteSubstateStoreWriteTransaction<'_> {
@@ -855,6 +958,142 @@ impl SubstateStoreWriteTransaction for SqliteSubstateStoreWriteTransaction<'_> {
 
         Ok(())
     }
+
+    fn insert_api_key(&mut self, new_api_key: NewApiKey) -> Result<(), StorageError> {
+        use crate::substate_storage_sqlite::schema::api_keys;
+
+        diesel::insert_into(api_keys::table)
+            .values(&new_api_key)
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("insert_api_key: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    fn revoke_api_key(&mut self, key_id: &str, revoked_at: i64) -> Result<bool, StorageError> {
+        use crate::substate_storage_sqlite::schema::api_keys;
+
+        let num_updated = diesel::update(api_keys::table)
+            .filter(api_keys::key_id.eq(key_id))
+            .filter(api_keys::revoked_at.is_null())
+            .set(api_keys::revoked_at.eq(revoked_at))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("revoke_api_key: {}", e),
+            })?;
+
+        Ok(num_updated > 0)
+    }
+
+    fn add_api_key_usage(&mut self, key_id: &str, num_requests: u64, last_used_at: i64) -> Result<(), StorageError> {
+        use crate::substate_storage_sqlite::schema::api_keys;
+
+        let num_requests = i64::try_from(num_requests).map_err(|_| StorageError::InvalidIntegerCast)?;
+        diesel::update(api_keys::table)
+            .filter(api_keys::key_id.eq(key_id))
+            .set((
+                api_keys::total_requests.eq(api_keys::total_requests + num_requests),
+                api_keys::last_used_at.eq(last_used_at),
+            ))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("add_api_key_usage: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    fn add_epoch_stats(&mut self, stats: NewEpochStats) -> Result<(), StorageError> {
+        use diesel::upsert::excluded;
+
+        use crate::substate_storage_sqlite::schema::epoch_stats;
+
+        diesel::insert_into(epoch_stats::table)
+            .values(&stats)
+            .on_conflict(epoch_stats::epoch)
+            .do_update()
+            .set((
+                epoch_stats::num_blocks.eq(epoch_stats::num_blocks + excluded(epoch_stats::num_blocks)),
+                epoch_stats::num_transactions
+                    .eq(epoch_stats::num_transactions + excluded(epoch_stats::num_transactions)),
+                epoch_stats::num_events.eq(epoch_stats::num_events + excluded(epoch_stats::num_events)),
+                epoch_stats::num_stored_events
+                    .eq(epoch_stats::num_stored_events + excluded(epoch_stats::num_stored_events)),
+                epoch_stats::updated_at.eq(excluded(epoch_stats::updated_at)),
+            ))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("add_epoch_stats: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    fn delete_substates_older_than(&mut self, timestamp: i64) -> Result<usize, StorageError> {
+        use crate::substate_storage_sqlite::schema::substates;
+
+        let num_deleted = diesel::delete(substates::table)
+            .filter(substates::timestamp.lt(timestamp))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("delete_substates_older_than: {}", e),
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn delete_events_older_than(&mut self, timestamp: i64) -> Result<usize, StorageError> {
+        use crate::substate_storage_sqlite::schema::{event_payloads, events};
+
+        let old_events = events::table.select(events::id).filter(events::timestamp.lt(timestamp));
+        diesel::delete(event_payloads::table)
+            .filter(event_payloads::event_id.eq_any(old_events))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("delete_events_older_than: {}", e),
+            })?;
+
+        let num_deleted = diesel::delete(events::table)
+            .filter(events::timestamp.lt(timestamp))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("delete_events_older_than: {}", e),
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn delete_epoch_stats_older_than(&mut self, epoch: Epoch) -> Result<usize, StorageError> {
+        use crate::substate_storage_sqlite::schema::epoch_stats;
+
+        let epoch = i64::try_from(epoch.as_u64()).map_err(|_| StorageError::InvalidIntegerCast)?;
+        let num_deleted = diesel::delete(epoch_stats::table)
+            .filter(epoch_stats::epoch.lt(epoch))
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("delete_epoch_stats_older_than: {}", e),
+            })?;
+
+        Ok(num_deleted)
+    }
+
+    fn set_validator_identity(&mut self, identity: NewValidatorIdentity) -> Result<(), StorageError> {
+        use crate::substate_storage_sqlite::schema::validator_identities;
+
+        diesel::insert_into(validator_identities::table)
+            .values(&identity)
+            .on_conflict(validator_identities::public_key)
+            .do_update()
+            .set(&identity)
+            .execute(&mut *self.connection())
+            .map_err(|e| StorageError::QueryError {
+                reason: format!("set_validator_identity: {}", e),
+            })?;
+
+        Ok(())
+    }
 }
 
 impl<'a> Deref for SqliteSubstateStoreWriteTransaction<'a> {
@@ -0,0 +1,302 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Registry of operator-provided metadata for validator nodes. An operator proves control of a validator node key by
+//! requesting a single-use challenge from the indexer and signing it, together with the metadata, with that key. Only
+//! validators that are registered in the current epoch may publish metadata.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use log::*;
+use rand::{rngs::OsRng, RngCore};
+use tari_common_types::types::PublicKey;
+use tari_crypto::tari_utilities::hex::{to_hex, Hex};
+use tari_dan_common_types::{optional::IsNotFoundError, PeerAddress};
+use tari_dan_storage::{consensus_models::ValidatorSignature, StorageError};
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerError, EpochManagerReader};
+use tari_indexer_client::types::{ValidatorIdentity, ValidatorMetadata};
+
+use crate::substate_storage_sqlite::{
+    models::validator_identity::{NewValidatorIdentity, ValidatorIdentity as ValidatorIdentityRow},
+    sqlite_substate_store_factory::{
+        SqliteSubstateStore,
+        SubstateStore,
+        SubstateStoreReadTransaction,
+        SubstateStoreWriteTransaction,
+    },
+};
+
+const LOG_TARGET: &str = "tari::indexer::validator_identity";
+
+/// How long an issued challenge may be used to register metadata
+const CHALLENGE_TTL: Duration = Duration::from_secs(10 * 60);
+/// The maximum number of outstanding challenges, to bound the memory used by unauthenticated requests
+const MAX_PENDING_CHALLENGES: usize = 10_000;
+const MAX_NAME_LEN: usize = 64;
+const MAX_WEBSITE_LEN: usize = 256;
+const MAX_CONTACT_LEN: usize = 256;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ValidatorIdentityError {
+    #[error("No identity registered for validator {public_key}")]
+    NotFound { public_key: PublicKey },
+    #[error("Validator {public_key} is not registered in the current epoch")]
+    NotAValidator { public_key: PublicKey },
+    #[error("Invalid or expired challenge. Request a new challenge and sign it with the validator node key")]
+    InvalidChallenge,
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Too many pending challenges. Try again later")]
+    TooManyChallenges,
+    #[error("Invalid metadata: {details}")]
+    InvalidMetadata { details: String },
+    #[error("Corrupt identity record for {public_key}: {details}")]
+    CorruptRecord { public_key: String, details: String },
+    #[error("Storage error: {0}")]
+    StorageError(#[from] StorageError),
+    #[error("Failed to encode signature: {0}")]
+    SignatureEncoding(#[from] serde_json::Error),
+    #[error("Epoch manager error: {0}")]
+    EpochManagerError(#[from] EpochManagerError),
+}
+
+pub struct ValidatorIdentityRegistry {
+    store: SqliteSubstateStore,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    challenges: Mutex<HashMap<PublicKey, IssuedChallenge>>,
+}
+
+struct IssuedChallenge {
+    challenge: String,
+    expires_at: Instant,
+}
+
+impl ValidatorIdentityRegistry {
+    pub fn new(store: SqliteSubstateStore, epoch_manager: EpochManagerHandle<PeerAddress>) -> Self {
+        Self {
+            store,
+            epoch_manager,
+            challenges: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issues a challenge for the validator, replacing any previous challenge. Returns the hex encoded challenge.
+    pub async fn issue_challenge(&self, public_key: PublicKey) -> Result<String, ValidatorIdentityError> {
+        self.ensure_is_validator(&public_key).await?;
+
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let challenge = to_hex(&bytes);
+
+        let mut challenges = self.challenges.lock().unwrap();
+        let now = Instant::now();
+        challenges.retain(|_, c| c.expires_at > now);
+        if challenges.len() >= MAX_PENDING_CHALLENGES && !challenges.contains_key(&public_key) {
+            return Err(ValidatorIdentityError::TooManyChallenges);
+        }
+        challenges.insert(public_key, IssuedChallenge {
+            challenge: challenge.clone(),
+            expires_at: now + CHALLENGE_TTL,
+        });
+
+        Ok(challenge)
+    }
+
+    pub fn challenge_ttl(&self) -> Duration {
+        CHALLENGE_TTL
+    }
+
+    /// Verifies that the signature of the challenge and metadata was created with the validator node key, and stores
+    /// the metadata. The challenge is consumed, whether or not the registration succeeds.
+    pub async fn register(
+        &self,
+        metadata: ValidatorMetadata,
+        challenge: &str,
+        signature: ValidatorSignature,
+    ) -> Result<ValidatorIdentity, ValidatorIdentityError> {
+        let public_key = signature.public_key().clone();
+        let issued = self
+            .challenges
+            .lock()
+            .unwrap()
+            .remove(&public_key)
+            .ok_or(ValidatorIdentityError::InvalidChallenge)?;
+        if issued.challenge != challenge || issued.expires_at <= Instant::now() {
+            return Err(ValidatorIdentityError::InvalidChallenge);
+        }
+        if !signature.verify(metadata.challenge_message(challenge)) {
+            return Err(ValidatorIdentityError::InvalidSignature);
+        }
+        let metadata = validate_metadata(metadata)?;
+        // The validator may have been deregistered since the challenge was issued
+        self.ensure_is_validator(&public_key).await?;
+
+        let verified_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time is after the unix epoch")
+            .as_secs();
+        let signature_json = serde_json::to_string(&signature)?;
+        self.store.with_write_tx(|tx| {
+            tx.set_validator_identity(NewValidatorIdentity {
+                public_key: public_key.to_hex(),
+                name: metadata.name.clone(),
+                website: metadata.website.clone(),
+                contact: metadata.contact.clone(),
+                signature: signature_json,
+                verified_at: verified_at as i64,
+            })
+        })?;
+
+        info!(
+            target: LOG_TARGET,
+            "🪪 Registered identity '{}' for validator {}", metadata.name, public_key
+        );
+        Ok(ValidatorIdentity {
+            public_key,
+            metadata,
+            verified_at,
+        })
+    }
+
+    pub fn get(&self, public_key: &PublicKey) -> Result<ValidatorIdentity, ValidatorIdentityError> {
+        let row = self
+            .store
+            .with_read_tx(|tx| tx.get_validator_identity(&public_key.to_hex()))?
+            .ok_or_else(|| ValidatorIdentityError::NotFound {
+                public_key: public_key.clone(),
+            })?;
+        row_to_identity(row)
+    }
+
+    pub fn list(&self, offset: u64, limit: u64) -> Result<Vec<ValidatorIdentity>, ValidatorIdentityError> {
+        self.store
+            .with_read_tx(|tx| tx.get_validator_identities(offset, limit))?
+            .into_iter()
+            .map(row_to_identity)
+            .collect()
+    }
+
+    async fn ensure_is_validator(&self, public_key: &PublicKey) -> Result<(), ValidatorIdentityError> {
+        let epoch = self.epoch_manager.current_epoch().await?;
+        match self
+            .epoch_manager
+            .get_validator_node_by_public_key(epoch, public_key.clone())
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(err) if err.is_not_found_error() => Err(ValidatorIdentityError::NotAValidator {
+                public_key: public_key.clone(),
+            }),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+fn validate_metadata(metadata: ValidatorMetadata) -> Result<ValidatorMetadata, ValidatorIdentityError> {
+    fn invalid<T: Into<String>>(details: T) -> ValidatorIdentityError {
+        ValidatorIdentityError::InvalidMetadata {
+            details: details.into(),
+        }
+    }
+
+    let name = metadata.name.trim().to_string();
+    if name.is_empty() {
+        return Err(invalid("name must not be empty"));
+    }
+    if name.len() > MAX_NAME_LEN {
+        return Err(invalid(format!("name must be at most {} bytes", MAX_NAME_LEN)));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(invalid("name must not contain control characters"));
+    }
+
+    let website = metadata.website.map(|w| w.trim().to_string()).filter(|w| !w.is_empty());
+    if let Some(ref website) = website {
+        if website.len() > MAX_WEBSITE_LEN {
+            return Err(invalid(format!("website must be at most {} bytes", MAX_WEBSITE_LEN)));
+        }
+        if !website.starts_with("https://") && !website.starts_with("http://") {
+            return Err(invalid("website must be an http or https URL"));
+        }
+        if website.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(invalid("website must not contain whitespace"));
+        }
+    }
+
+    let contact = metadata.contact.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    if let Some(ref contact) = contact {
+        if contact.len() > MAX_CONTACT_LEN {
+            return Err(invalid(format!("contact must be at most {} bytes", MAX_CONTACT_LEN)));
+        }
+        if contact.chars().any(char::is_control) {
+            return Err(invalid("contact must not contain control characters"));
+        }
+    }
+
+    Ok(ValidatorMetadata { name, website, contact })
+}
+
+fn row_to_identity(row: ValidatorIdentityRow) -> Result<ValidatorIdentity, ValidatorIdentityError> {
+    let public_key =
+        PublicKey::from_hex(&row.public_key).map_err(|e| ValidatorIdentityError::CorruptRecord {
+            public_key: row.public_key.clone(),
+            details: e.to_string(),
+        })?;
+    Ok(ValidatorIdentity {
+        public_key,
+        metadata: ValidatorMetadata {
+            name: row.name,
+            website: row.website,
+            contact: row.contact,
+        },
+        verified_at: row.verified_at as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_common_types::types::PrivateKey;
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    fn metadata(name: &str, website: Option<&str>) -> ValidatorMetadata {
+        ValidatorMetadata {
+            name: name.to_string(),
+            website: website.map(ToString::to_string),
+            contact: None,
+        }
+    }
+
+    #[test]
+    fn it_validates_metadata() {
+        let validated = validate_metadata(metadata("  Tari VN  ", Some(" https://tari.com "))).unwrap();
+        assert_eq!(validated, metadata("Tari VN", Some("https://tari.com")));
+        // Empty optional fields are removed
+        let validated = validate_metadata(metadata("Tari VN", Some(""))).unwrap();
+        assert_eq!(validated.website, None);
+
+        validate_metadata(metadata(" ", None)).unwrap_err();
+        validate_metadata(metadata(&"a".repeat(MAX_NAME_LEN + 1), None)).unwrap_err();
+        validate_metadata(metadata("Tari\nVN", None)).unwrap_err();
+        validate_metadata(metadata("Tari VN", Some("javascript:alert(1)"))).unwrap_err();
+        validate_metadata(metadata("Tari VN", Some("https://tari.com/ x"))).unwrap_err();
+    }
+
+    #[test]
+    fn it_binds_the_signature_to_the_challenge_and_metadata() {
+        let secret = PrivateKey::random(&mut OsRng);
+        let metadata = metadata("Tari VN", Some("https://tari.com"));
+        let signature = ValidatorSignature::sign(&secret, metadata.challenge_message("aa"));
+        assert!(signature.verify(metadata.challenge_message("aa")));
+        assert!(!signature.verify(metadata.challenge_message("bb")));
+
+        let mut changed = metadata.clone();
+        changed.contact = Some("".to_string());
+        assert!(!signature.verify(changed.challenge_message("aa")));
+    }
+}
@@ -0,0 +1,23 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AutoRegistrationConfig {
+    /// If true, a re-registration is submitted before the registration of this validator node expires
+    pub enabled: bool,
+    /// The number of epochs before the registration expires at which the re-registration is submitted. This should
+    /// allow enough time for the transaction to be mined and confirmed on the base layer.
+    pub epochs_before_expiry: u64,
+}
+
+impl Default for AutoRegistrationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            epochs_before_expiry: 2,
+        }
+    }
+}
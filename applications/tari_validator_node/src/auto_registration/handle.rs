@@ -0,0 +1,27 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::{Arc, RwLock};
+
+use tari_validator_node_client::types::AutoRegistrationStatus;
+
+#[derive(Debug, Clone)]
+pub struct AutoRegistrationHandle {
+    status: Arc<RwLock<AutoRegistrationStatus>>,
+}
+
+impl AutoRegistrationHandle {
+    pub(super) fn new(status: AutoRegistrationStatus) -> Self {
+        Self {
+            status: Arc::new(RwLock::new(status)),
+        }
+    }
+
+    pub fn status(&self) -> AutoRegistrationStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    pub(super) fn update_status<F: FnOnce(&mut AutoRegistrationStatus)>(&self, f: F) {
+        f(&mut self.status.write().unwrap());
+    }
+}
@@ -0,0 +1,47 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_crypto::ristretto::RistrettoPublicKey;
+use tari_dan_app_utilities::keypair::RistrettoKeypair;
+use tari_dan_common_types::PeerAddress;
+use tari_epoch_manager::base_layer::EpochManagerHandle;
+use tari_shutdown::ShutdownSignal;
+use tari_validator_node_client::types::AutoRegistrationStatus;
+use tokio::{task, task::JoinHandle};
+
+use super::{service::AutoRegistrationService, AutoRegistrationConfig, AutoRegistrationHandle};
+use crate::file_l1_submitter::FileLayerOneSubmitter;
+
+const LOG_TARGET: &str = "tari::dan::validator_node::auto_registration";
+
+pub fn spawn(
+    config: AutoRegistrationConfig,
+    keypair: RistrettoKeypair,
+    fee_claim_public_key: RistrettoPublicKey,
+    sidechain_id: Option<RistrettoPublicKey>,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    layer_one_submitter: FileLayerOneSubmitter,
+    shutdown: ShutdownSignal,
+) -> (JoinHandle<anyhow::Result<()>>, AutoRegistrationHandle) {
+    let handle = AutoRegistrationHandle::new(AutoRegistrationStatus {
+        enabled: config.enabled,
+        epochs_before_expiry: config.epochs_before_expiry,
+        ..Default::default()
+    });
+    let service = AutoRegistrationService::new(
+        config,
+        keypair,
+        fee_claim_public_key,
+        sidechain_id,
+        epoch_manager,
+        layer_one_submitter,
+        handle.clone(),
+        shutdown,
+    );
+
+    let join_handle = task::spawn(service.run());
+    debug!(target: LOG_TARGET, "Spawning auto registration service (task: {:?})", join_handle);
+
+    (join_handle, handle)
+}
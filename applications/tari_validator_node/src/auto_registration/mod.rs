@@ -0,0 +1,18 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Automatic re-registration of the local validator node. Registrations on the base layer are only valid for a limited
+//! number of epochs, so a configurable number of epochs before the registration expires a re-registration is submitted
+//! as a layer one transaction. The validator node has no base layer wallet, so the transaction is written to the layer
+//! one transaction path for the watcher to submit.
+
+mod config;
+pub use config::AutoRegistrationConfig;
+
+mod handle;
+pub use handle::AutoRegistrationHandle;
+
+mod initializer;
+pub use initializer::spawn;
+
+mod service;
@@ -0,0 +1,171 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_common_types::epoch::VnEpoch;
+use tari_core::transactions::transaction_components::ValidatorNodeSignature;
+use tari_crypto::ristretto::RistrettoPublicKey;
+use tari_dan_app_utilities::keypair::RistrettoKeypair;
+use tari_dan_common_types::{
+    layer_one_transaction::{LayerOnePayloadType, LayerOneTransactionDef},
+    Epoch,
+    PeerAddress,
+};
+use tari_epoch_manager::{
+    base_layer::EpochManagerHandle,
+    traits::LayerOneTransactionSubmitter,
+    EpochManagerEvent,
+    EpochManagerReader,
+};
+use tari_shutdown::ShutdownSignal;
+use tokio::sync::broadcast;
+
+use super::{AutoRegistrationConfig, AutoRegistrationHandle};
+use crate::{file_l1_submitter::FileLayerOneSubmitter, validator_registration_file::ValidatorRegistrationFile};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::auto_registration";
+
+pub struct AutoRegistrationService {
+    config: AutoRegistrationConfig,
+    keypair: RistrettoKeypair,
+    fee_claim_public_key: RistrettoPublicKey,
+    sidechain_id: Option<RistrettoPublicKey>,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    layer_one_submitter: FileLayerOneSubmitter,
+    handle: AutoRegistrationHandle,
+    shutdown: ShutdownSignal,
+}
+
+impl AutoRegistrationService {
+    pub fn new(
+        config: AutoRegistrationConfig,
+        keypair: RistrettoKeypair,
+        fee_claim_public_key: RistrettoPublicKey,
+        sidechain_id: Option<RistrettoPublicKey>,
+        epoch_manager: EpochManagerHandle<PeerAddress>,
+        layer_one_submitter: FileLayerOneSubmitter,
+        handle: AutoRegistrationHandle,
+        shutdown: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            keypair,
+            fee_claim_public_key,
+            sidechain_id,
+            epoch_manager,
+            layer_one_submitter,
+            handle,
+            shutdown,
+        }
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            info!(target: LOG_TARGET, "Automatic validator node re-registration is disabled");
+            return Ok(());
+        }
+
+        let mut epoch_events = self.epoch_manager.subscribe();
+        self.epoch_manager.wait_for_initial_scanning_to_complete().await?;
+        let current_epoch = self.epoch_manager.current_epoch().await?;
+        self.check_registration(current_epoch).await;
+
+        loop {
+            tokio::select! {
+                event = epoch_events.recv() => {
+                    match event {
+                        Ok(EpochManagerEvent::EpochChanged { epoch, .. }) => self.check_registration(epoch).await,
+                        // A missed epoch change is caught up on the next epoch change
+                        Err(broadcast::error::RecvError::Lagged(_)) => {},
+                        Err(broadcast::error::RecvError::Closed) => {
+                            info!(
+                                target: LOG_TARGET,
+                                "Epoch manager event channel closed. Auto registration shutting down"
+                            );
+                            break;
+                        },
+                    }
+                },
+                _ = self.shutdown.wait() => {
+                    info!(target: LOG_TARGET, "Auto registration shutting down");
+                    break;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn check_registration(&self, epoch: Epoch) {
+        let end_epoch = match self.epoch_manager.get_our_validator_node(epoch).await {
+            Ok(vn) => vn.end_epoch,
+            // Initial registration is submitted by the node operator
+            Err(err) if err.is_not_registered_error() => None,
+            Err(err) => {
+                error!(target: LOG_TARGET, "Failed to get the local validator node registration: {}", err);
+                self.handle.update_status(|status| status.last_error = Some(err.to_string()));
+                return;
+            },
+        };
+        self.handle.update_status(|status| status.registration_end_epoch = end_epoch);
+
+        let Some(end_epoch) = end_epoch else {
+            return;
+        };
+        let submitted_for_end_epoch = self.handle.status().last_submitted_for_end_epoch;
+        if !should_reregister(epoch, end_epoch, self.config.epochs_before_expiry, submitted_for_end_epoch) {
+            return;
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "📝 Registration expires after {} (current: {}). Submitting a re-registration", end_epoch, epoch
+        );
+        match self.submit_registration().await {
+            Ok(()) => self.handle.update_status(|status| {
+                status.last_submitted_epoch = Some(epoch);
+                status.last_submitted_for_end_epoch = Some(end_epoch);
+                status.last_error = None;
+            }),
+            Err(err) => {
+                error!(target: LOG_TARGET, "Failed to submit re-registration: {}", err);
+                self.handle.update_status(|status| status.last_error = Some(err.to_string()));
+            },
+        }
+    }
+
+    async fn submit_registration(&self) -> Result<(), std::io::Error> {
+        // Signed in the same way as the registration file so that the watcher and base layer treat both alike
+        let signature = ValidatorNodeSignature::sign(
+            self.keypair.secret_key(),
+            self.sidechain_id.as_ref(),
+            &self.fee_claim_public_key,
+            VnEpoch::zero(),
+        );
+        let registration = ValidatorRegistrationFile {
+            signature,
+            public_key: self.keypair.public_key().clone(),
+            claim_fees_public_key: self.fee_claim_public_key.clone(),
+        };
+        self.layer_one_submitter
+            .submit_transaction(LayerOneTransactionDef {
+                proof_type: LayerOnePayloadType::ValidatorNodeRegistration,
+                payload: registration,
+            })
+            .await
+    }
+}
+
+/// Returns true if the registration that ends at `end_epoch` expires within `epochs_before_expiry` epochs and a
+/// re-registration has not yet been submitted for it. Once the re-registration is scanned, the end epoch moves forward.
+fn should_reregister(
+    current_epoch: Epoch,
+    end_epoch: Epoch,
+    epochs_before_expiry: u64,
+    submitted_for_end_epoch: Option<Epoch>,
+) -> bool {
+    if submitted_for_end_epoch == Some(end_epoch) {
+        return false;
+    }
+    end_epoch.saturating_sub(current_epoch).as_u64() <= epochs_before_expiry
+}
@@ -59,7 +59,11 @@ use tari_dan_common_types::{
     ShardGroup,
     VersionedSubstateId,
 };
-use tari_dan_engine::{fees::FeeTable, transaction::TransactionProcessorConfig};
+use tari_dan_engine::{
+    fees::FeeTable,
+    runtime::{GasLimits, MemoryLimits, SandboxLimits},
+    transaction::TransactionProcessorConfig,
+};
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::{
     consensus_models::{Block, BlockId, SubstateRecord},
@@ -106,11 +110,14 @@ use tokio::{
 };
 
 #[cfg(feature = "metrics")]
-use crate::consensus::metrics::PrometheusConsensusMetrics;
+use crate::consensus::metrics::{PrometheusConsensusMetrics, PrometheusTransactionExecutionMetrics};
 use crate::{
+    auto_registration::{self, AutoRegistrationHandle},
     consensus::{self, ConsensusHandle, TariDanBlockTransactionExecutor},
     dry_run_transaction_processor::DryRunTransactionProcessor,
+    epoch_rehearsal,
     file_l1_submitter::FileLayerOneSubmitter,
+    network_params::NetworkParams,
     p2p::{
         create_tari_validator_node_rpc_service,
         services::{
@@ -120,8 +127,16 @@ use crate::{
         },
         NopLogger,
     },
+    state_store_maintenance,
+    substate_pruning::{self, SubstatePruningHandle},
     substate_resolver::TariSubstateResolver,
-    transaction_validators::{FeeTransactionValidator, HasInputs, TemplateExistsValidator, TransactionValidationError},
+    transaction_validators::{
+        FeeTransactionValidator,
+        HasInputs,
+        MinimumFeeValidator,
+        TemplateExistsValidator,
+        TransactionValidationError,
+    },
     validator::Validator,
     validator_registration_file::ValidatorRegistrationFile,
     virtual_substate::VirtualSubstateManager,
@@ -137,6 +152,7 @@ pub async fn spawn_services(
     keypair: RistrettoKeypair,
     global_db: GlobalDb<SqliteGlobalDbAdapter<PeerAddress>>,
     consensus_constants: ConsensusConstants,
+    network_params: NetworkParams,
     base_node_client: GrpcBaseNodeClient,
     #[cfg(feature = "metrics")] metrics_registry: &prometheus::Registry,
 ) -> Result<Services, anyhow::Error> {
@@ -183,7 +199,7 @@ pub async fn spawn_services(
         tari_networking::Config {
             listener_port: config.validator_node.p2p.listener_port,
             swarm: SwarmConfig {
-                protocol_version: format!("/tari/{}/0.0.1", config.network).parse().unwrap(),
+                protocol_version: network_params.p2p_protocol_version.parse()?,
                 user_agent: "/tari/validator/0.0.1".to_string(),
                 enable_mdns: config.validator_node.p2p.enable_mdns,
                 enable_relay: true,
@@ -194,6 +210,7 @@ pub async fn spawn_services(
             },
             reachability_mode: config.validator_node.p2p.reachability_mode.into(),
             announce: true,
+            bandwidth_limits: config.validator_node.p2p.bandwidth_limits(),
             ..Default::default()
         },
         seed_peers,
@@ -201,6 +218,19 @@ pub async fn spawn_services(
     )?;
     handles.push(join_handle);
 
+    #[cfg(feature = "metrics")]
+    handles.push(crate::p2p::bandwidth_metrics::spawn(
+        networking.clone(),
+        shutdown.clone(),
+        metrics_registry,
+    ));
+    #[cfg(feature = "metrics")]
+    handles.push(crate::p2p::connection_metrics::spawn(
+        networking.clone(),
+        shutdown.clone(),
+        metrics_registry,
+    ));
+
     info!(target: LOG_TARGET, "Message logging initializing");
 
     info!(target: LOG_TARGET, "State store initializing");
@@ -211,7 +241,7 @@ pub async fn spawn_services(
     state_store.with_write_tx(|tx| {
         bootstrap_state(
             tx,
-            config.network,
+            &network_params,
             consensus_constants.num_preshards,
             sidechain_id.clone(),
         )
@@ -258,15 +288,6 @@ pub async fn spawn_services(
         template_manager::implementation::spawn(template_manager.clone(), shutdown.clone());
     handles.push(join_handle);
 
-    info!(target: LOG_TARGET, "Payload processor initializing");
-    // Payload processor
-    let fee_table = FeeTable {
-        per_module_call_cost: 1,
-        per_byte_storage_cost: 1,
-        per_event_cost: 1,
-        per_log_cost: 1,
-    };
-
     let (tx_hotstuff_events, _) = broadcast::channel(100);
     // Consensus gossip
     let (consensus_gossip_service, join_handle, rx_consensus_gossip_messages) = consensus_gossip::spawn(
@@ -293,20 +314,18 @@ pub async fn spawn_services(
         consensus_gossip_service.clone(),
         networking.clone(),
         message_logger.clone(),
+        config.validator_node.message_batching.clone(),
     );
 
     // Consensus
-    let payload_processor = TariDanTransactionProcessor::new(
-        TransactionProcessorConfig::builder()
-            .with_network(config.network)
-            .with_template_binary_max_size_bytes(consensus_constants.template_binary_max_size_bytes)
-            .build(),
-        template_manager.clone(),
-        fee_table,
-    );
+    info!(target: LOG_TARGET, "Payload processor initializing");
+    let payload_processor =
+        create_transaction_processor(config.network, &consensus_constants, template_manager.clone());
     let transaction_executor = TariDanBlockTransactionExecutor::new(
         payload_processor.clone(),
         consensus::create_transaction_validator(template_manager.clone()).boxed(),
+        #[cfg(feature = "metrics")]
+        PrometheusTransactionExecutionMetrics::new(metrics_registry),
     );
 
     #[cfg(feature = "metrics")]
@@ -328,17 +347,21 @@ pub async fn spawn_services(
         validator_node_client_factory.clone(),
         metrics,
         shutdown.clone(),
-        transaction_executor,
+        transaction_executor.clone(),
         tx_hotstuff_events,
         consensus_constants.clone(),
+        config.validator_node.consensus.clone(),
+        #[cfg(feature = "consensus-fault-injection")]
+        std::sync::Arc::new(config.validator_node.fault_injector.clone().unwrap_or_default()),
     )
     .await;
     handles.push(consensus_join_handle);
 
     let (mempool, join_handle) = mempool::spawn(
+        config.validator_node.mempool.clone(),
         consensus_constants.num_preshards,
         epoch_manager.clone(),
-        create_mempool_transaction_validator(template_manager.clone()),
+        create_mempool_transaction_validator(template_manager.clone(), config.validator_node.mempool.min_fee),
         state_store.clone(),
         consensus_handle.clone(),
         networking.clone(),
@@ -348,6 +371,45 @@ pub async fn spawn_services(
     );
     handles.push(join_handle);
 
+    let join_handle = state_store_maintenance::spawn(
+        config.validator_node.state_store_maintenance.clone(),
+        state_store.clone(),
+        consensus_handle.clone(),
+        shutdown.clone(),
+        #[cfg(feature = "metrics")]
+        metrics_registry,
+    );
+    handles.push(join_handle);
+
+    let (join_handle, substate_pruning) = substate_pruning::spawn(
+        config.validator_node.substate_pruning.clone(),
+        state_store.clone(),
+        consensus_handle.clone(),
+        shutdown.clone(),
+    );
+    handles.push(join_handle);
+
+    let join_handle = epoch_rehearsal::spawn(
+        config.validator_node.epoch_rehearsal.clone(),
+        consensus_constants.num_preshards,
+        epoch_manager.clone(),
+        state_store.clone(),
+        transaction_executor,
+        shutdown.clone(),
+    );
+    handles.push(join_handle);
+
+    let (join_handle, auto_registration) = auto_registration::spawn(
+        config.validator_node.auto_registration.clone(),
+        keypair.clone(),
+        config.validator_node.fee_claim_public_key.clone(),
+        config.validator_node.validator_node_sidechain_id.clone(),
+        epoch_manager.clone(),
+        FileLayerOneSubmitter::new(config.get_layer_one_transaction_base_path()),
+        shutdown.clone(),
+    );
+    handles.push(join_handle);
+
     // Base Node scanner
     let join_handle = base_layer_scanner::spawn(
         global_db.clone(),
@@ -411,6 +473,8 @@ pub async fn spawn_services(
         // global_db,
         state_store,
         dry_run_transaction_processor,
+        substate_pruning,
+        auto_registration,
         handles,
         // validator_node_client_factory,
         // consensus_gossip_service,
@@ -478,6 +542,8 @@ pub struct Services {
     // pub validator_node_client_factory: TariValidatorNodeRpcClientFactory,
     // pub consensus_gossip_service: ConsensusGossipHandle,
     pub state_store: SqliteStateStore<PeerAddress>,
+    pub substate_pruning: SubstatePruningHandle,
+    pub auto_registration: AutoRegistrationHandle,
 
     pub handles: Vec<JoinHandle<Result<(), anyhow::Error>>>,
 }
@@ -522,7 +588,7 @@ async fn spawn_p2p_rpc(
 
 fn bootstrap_state<TTx>(
     tx: &mut TTx,
-    network: Network,
+    network_params: &NetworkParams,
     num_preshards: NumPreshards,
     sidechain_id: Option<RistrettoPublicKey>,
 ) -> Result<(), StorageError>
@@ -531,6 +597,7 @@ where
     TTx::Target: StateStoreReadTransaction,
     TTx::Addr: NodeAddressable + Serialize,
 {
+    let network = network_params.network;
     // Assume that if the public identity resource exists, then the rest of the state has been bootstrapped
     if SubstateRecord::exists(&**tx, &VersionedSubstateId::new(PUBLIC_IDENTITY_RESOURCE_ADDRESS, 0))? {
         return Ok(());
@@ -565,7 +632,7 @@ where
     );
 
     // Create faucet component
-    if !matches!(network, Network::MainNet) {
+    if network_params.genesis.create_faucet {
         let value = ComponentHeader {
             template_address: tari_template_builtin::FAUCET_TEMPLATE_ADDRESS,
             module_name: "XtrFaucet".to_string(),
@@ -658,10 +725,50 @@ where
     Ok(())
 }
 
+/// Creates the transaction processor that executes transactions with the limits set by the consensus constants
+pub(crate) fn create_transaction_processor(
+    network: Network,
+    consensus_constants: &ConsensusConstants,
+    template_manager: TemplateManager<PeerAddress>,
+) -> TariDanTransactionProcessor<TemplateManager<PeerAddress>> {
+    let fee_table = FeeTable {
+        per_module_call_cost: 1,
+        per_byte_storage_cost: 1,
+        per_event_cost: 1,
+        per_log_cost: 1,
+        gas_per_fee_unit: 1_000_000,
+    };
+
+    TariDanTransactionProcessor::new(
+        TransactionProcessorConfig::builder()
+            .with_network(network)
+            .with_template_binary_max_size_bytes(consensus_constants.template_binary_max_size_bytes)
+            .with_memory_limits(MemoryLimits {
+                max_wasm_memory_bytes: consensus_constants.max_wasm_memory_bytes,
+                max_host_allocated_bytes: consensus_constants.max_host_allocated_bytes,
+            })
+            .with_gas_limits(GasLimits {
+                max_gas_per_transaction: consensus_constants.max_gas_per_transaction,
+                ..Default::default()
+            })
+            .with_sandbox_limits(SandboxLimits {
+                max_memory_pages: consensus_constants.max_wasm_memory_pages,
+                max_table_elements: consensus_constants.max_wasm_table_elements,
+                max_execution_time: consensus_constants.max_template_execution_time,
+                ..Default::default()
+            })
+            .build(),
+        template_manager,
+        fee_table,
+    )
+}
+
 fn create_mempool_transaction_validator(
     template_manager: TemplateManager<PeerAddress>,
+    min_fee: u64,
 ) -> impl Validator<Transaction, Context = (), Error = TransactionValidationError> {
     HasInputs::new()
         .and_then(TemplateExistsValidator::new(template_manager))
         .and_then(FeeTransactionValidator)
+        .and_then(MinimumFeeValidator::new(min_fee))
 }
@@ -22,7 +22,7 @@
 
 use std::net::SocketAddr;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
 use reqwest::Url;
 use tari_common::configuration::{ConfigOverrideProvider, Network};
@@ -59,6 +59,31 @@ pub struct Cli {
     /// FOR DEBUGGING PURPOSES ONLY
     #[clap(long, short = 'd')]
     pub debug_templates: Vec<String>,
+    /// Automatically submit a re-registration to the base layer before the validator node registration expires
+    #[clap(long)]
+    pub auto_register: bool,
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand, Clone)]
+pub enum Command {
+    /// Re-executes committed blocks from the local state store and reports the first block whose recomputed execution
+    /// results or state root differ from what was committed. The validator node is not started.
+    Replay(ReplayArgs),
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ReplayArgs {
+    /// The epoch of the blocks to replay. Defaults to the epoch of the last committed block.
+    #[clap(long)]
+    pub epoch: Option<u64>,
+    /// The height of the first block to replay
+    #[clap(long, default_value_t = 0)]
+    pub from_height: u64,
+    /// The height of the last block to replay. Defaults to the last committed block in the epoch.
+    #[clap(long)]
+    pub to_height: Option<u64>,
 }
 
 impl ConfigOverrideProvider for Cli {
@@ -107,6 +132,9 @@ impl ConfigOverrideProvider for Cli {
         if let Some(url) = self.minotari_node_grpc_url.as_ref() {
             overrides.push(("validator_node.base_node_grpc_url".to_string(), url.to_string()));
         }
+        if self.auto_register {
+            overrides.push(("validator_node.auto_registration.enabled".to_string(), "true".to_string()));
+        }
         overrides
     }
 }
@@ -35,13 +35,25 @@ use tari_common::{
     DefaultConfigLoader,
     SubConfigPath,
 };
+#[cfg(feature = "consensus-fault-injection")]
+use tari_consensus::hotstuff::fault_injection::ScriptedFaultInjector;
 use tari_crypto::ristretto::RistrettoPublicKey;
 use tari_dan_app_utilities::{
+    logging::LogFormat,
     p2p_config::{P2pConfig, PeerSeedsConfig, RpcConfig},
     template_manager::implementation::TemplateConfig,
 };
 use url::Url;
 
+use crate::{
+    auto_registration::AutoRegistrationConfig,
+    consensus::ConsensusConfig,
+    epoch_rehearsal::EpochRehearsalConfig,
+    p2p::services::{mempool::MempoolConfig, messaging::MessageBatchingConfig},
+    state_store_maintenance::StateStoreMaintenanceConfig,
+    substate_pruning::SubstatePruningConfig,
+};
+
 #[derive(Debug, Clone)]
 pub struct ApplicationConfig {
     pub common: CommonConfig,
@@ -118,6 +130,26 @@ pub struct ValidatorNodeConfig {
     pub burnt_utxo_sidechain_id: Option<RistrettoPublicKey>,
     /// The path to store layer one transactions.
     pub layer_one_transaction_path: PathBuf,
+    /// Consensus config
+    pub consensus: ConsensusConfig,
+    /// Mempool config
+    pub mempool: MempoolConfig,
+    /// Batching of consensus messages that are multicast to a shard group config
+    pub message_batching: MessageBatchingConfig,
+    /// State store maintenance (VACUUM/ANALYZE) config
+    pub state_store_maintenance: StateStoreMaintenanceConfig,
+    /// Pruning of historical substate versions config
+    pub substate_pruning: SubstatePruningConfig,
+    /// Epoch transition rehearsal (shadow mode) config
+    pub epoch_rehearsal: EpochRehearsalConfig,
+    /// Automatic re-registration before the validator node registration expires config
+    pub auto_registration: AutoRegistrationConfig,
+    /// The log output format. If set to json, the log4rs config file is not used.
+    pub log_format: LogFormat,
+    /// Faults to apply to consensus messages. This is set by tests and cannot be configured.
+    #[cfg(feature = "consensus-fault-injection")]
+    #[serde(skip)]
+    pub fault_injector: Option<ScriptedFaultInjector>,
 }
 
 impl ValidatorNodeConfig {
@@ -162,6 +194,16 @@ impl Default for ValidatorNodeConfig {
             template_sidechain_id: None,
             burnt_utxo_sidechain_id: None,
             layer_one_transaction_path: PathBuf::from("data/layer_one_transactions"),
+            consensus: ConsensusConfig::default(),
+            mempool: MempoolConfig::default(),
+            message_batching: MessageBatchingConfig::default(),
+            state_store_maintenance: StateStoreMaintenanceConfig::default(),
+            substate_pruning: SubstatePruningConfig::default(),
+            epoch_rehearsal: EpochRehearsalConfig::default(),
+            auto_registration: AutoRegistrationConfig::default(),
+            log_format: LogFormat::default(),
+            #[cfg(feature = "consensus-fault-injection")]
+            fault_injector: None,
         }
     }
 }
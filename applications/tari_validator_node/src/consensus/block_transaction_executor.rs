@@ -15,6 +15,8 @@ use tari_engine_types::{
 };
 use tari_transaction::Transaction;
 
+#[cfg(feature = "metrics")]
+use super::metrics::PrometheusTransactionExecutionMetrics;
 use crate::{transaction_validators::TransactionValidationError, validator::Validator};
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::block_transaction_executor";
@@ -23,15 +25,23 @@ const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::block_transaction_exec
 pub struct TariDanBlockTransactionExecutor<TExecutor, TValidator> {
     executor: TExecutor,
     validator: Arc<TValidator>,
+    #[cfg(feature = "metrics")]
+    metrics: PrometheusTransactionExecutionMetrics,
 }
 
 impl<TExecutor, TValidator> TariDanBlockTransactionExecutor<TExecutor, TValidator>
 where TExecutor: TransactionExecutor
 {
-    pub fn new(executor: TExecutor, validator: TValidator) -> Self {
+    pub fn new(
+        executor: TExecutor,
+        validator: TValidator,
+        #[cfg(feature = "metrics")] metrics: PrometheusTransactionExecutionMetrics,
+    ) -> Self {
         Self {
             executor,
             validator: Arc::new(validator),
+            #[cfg(feature = "metrics")]
+            metrics,
         }
     }
 
@@ -100,6 +110,8 @@ where
 
         let executed = ExecutedTransaction::new(exec_output.transaction, exec_output.result, resolved_inputs);
         info!(target: LOG_TARGET, "Transaction {} executed. {}", id,executed.result().finalize.result);
+        #[cfg(feature = "metrics")]
+        self.metrics.on_transaction_executed(&executed);
         Ok(executed)
     }
 }
@@ -109,6 +121,8 @@ impl<TExecutor: Clone, TValidator> Clone for TariDanBlockTransactionExecutor<TEx
         Self {
             executor: self.executor.clone(),
             validator: self.validator.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -0,0 +1,22 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsensusConfig {
+    /// The maximum number of transactions in a block that are executed in parallel. If not set, the number of
+    /// available CPU cores is used. Set to 1 to execute transactions sequentially.
+    pub max_parallel_executions: Option<usize>,
+}
+
+impl ConsensusConfig {
+    pub fn max_parallel_executions(&self) -> usize {
+        self.max_parallel_executions
+            .unwrap_or_else(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+            .max(1)
+    }
+}
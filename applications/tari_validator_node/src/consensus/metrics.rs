@@ -1,24 +1,64 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use prometheus::{core::Collector, IntCounter, IntGauge, IntGaugeVec, Opts, Registry};
+use log::*;
+use prometheus::{
+    core::Collector,
+    Histogram,
+    HistogramOpts,
+    HistogramVec,
+    IntCounter,
+    IntGauge,
+    IntGaugeVec,
+    Opts,
+    Registry,
+};
 use tari_consensus::{hotstuff::HotStuffError, messages::HotstuffMessage, traits::hooks::ConsensusHooks};
-use tari_dan_common_types::{NodeHeight, PeerAddress};
+use tari_dan_common_types::{Epoch, NodeHeight, PeerAddress};
 use tari_dan_storage::{
-    consensus_models::{Decision, QuorumDecision, TransactionAtom, ValidBlock},
+    consensus_models::{
+        Block,
+        Decision,
+        ExecutedTransaction,
+        LeafBlock,
+        QuorumDecision,
+        TransactionAtom,
+        TransactionRecord,
+        ValidBlock,
+    },
     StateStore,
+    StateStoreReadTransaction,
 };
+use tari_engine_types::{instruction::Instruction, substate::SubstateValue};
 use tari_state_store_sqlite::SqliteStateStore;
+use tari_template_lib::prelude::ResourceType;
 use tari_transaction::TransactionId;
 
 use crate::metrics::{CollectorRegister, LabelledCollector};
 
+const LOG_TARGET: &str = "tari::validator_node::consensus::metrics";
+
+/// Counting the substates requires a table scan, so the gauge is refreshed at most once per interval
+const SUBSTATES_COUNT_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Ready transactions that are not finalized within this time are no longer tracked for the commit latency
+const COMMIT_LATENCY_TRACKING_TTL: Duration = Duration::from_secs(60 * 60);
+/// The number of tracked ready transactions above which transactions older than the TTL are removed
+const COMMIT_LATENCY_TRACKING_PRUNE_THRESHOLD: usize = 10_000;
+
 #[derive(Debug, Clone)]
 pub struct PrometheusConsensusMetrics<S = SqliteStateStore<PeerAddress>> {
-    _state_store: S,
+    state_store: S,
+    current_epoch: IntGauge,
     local_blocks_received: IntCounter,
+    blocks_proposed: IntCounter,
+    blocks_committed: IntCounter,
+    committed_height: IntGauge,
     blocks_accepted: IntCounter,
     blocks_rejected: IntCounter,
     blocks_validation_failed: IntCounter,
@@ -26,6 +66,9 @@ pub struct PrometheusConsensusMetrics<S = SqliteStateStore<PeerAddress>> {
     commands_count: IntGaugeVec,
 
     messages_received: IntCounter,
+    missing_transactions_requests_received: IntCounter,
+    missing_transactions_responses_received: IntCounter,
+    missing_transactions_received: IntCounter,
 
     errors: IntCounter,
 
@@ -33,22 +76,43 @@ pub struct PrometheusConsensusMetrics<S = SqliteStateStore<PeerAddress>> {
     pacemaker_leader_failures: IntCounter,
     needs_sync: IntCounter,
 
+    foreign_proposals_buffered: IntGauge,
+    foreign_proposals_evicted: IntCounter,
+
     _transactions_pool_size: IntGauge,
     transactions_ready_for_consensus: IntCounter,
     transactions_finalized_committed: IntCounter,
     transactions_finalized_aborted: IntCounter,
+    transactions_commit_latency: HistogramVec,
+    /// The time at which each unfinalized transaction became ready for consensus
+    transactions_ready_at: HashMap<TransactionId, Instant>,
+
+    substates_count: IntGauge,
+    substates_count_updated_at: Option<Instant>,
 }
 
 impl<S: StateStore> PrometheusConsensusMetrics<S> {
     pub fn new(state_store: S, registry: &Registry) -> Self {
         Self {
-            _state_store: state_store,
+            state_store,
+            current_epoch: IntGauge::new("consensus_current_epoch", "Current consensus epoch")
+                .unwrap()
+                .register_at(registry),
             local_blocks_received: IntCounter::new("consensus_blocks_received", "Number of blocks added")
                 .unwrap()
                 .register_at(registry),
             blocks_accepted: IntCounter::new("consensus_blocks_accepted", "Number of blocks accepted")
                 .unwrap()
                 .register_at(registry),
+            blocks_proposed: IntCounter::new("consensus_blocks_proposed", "Number of blocks proposed by this node")
+                .unwrap()
+                .register_at(registry),
+            blocks_committed: IntCounter::new("consensus_blocks_committed", "Number of blocks committed")
+                .unwrap()
+                .register_at(registry),
+            committed_height: IntGauge::new("consensus_committed_height", "Height of the last committed block")
+                .unwrap()
+                .register_at(registry),
             commands_count: IntGaugeVec::new(Opts::new("consensus_num_commands", "Number of commands added"), &[
                 "block_height",
                 "block_id",
@@ -58,6 +122,24 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             messages_received: IntCounter::new("consensus_messages_received", "Number of messages received")
                 .unwrap()
                 .register_at(registry),
+            missing_transactions_requests_received: IntCounter::new(
+                "consensus_missing_transactions_requests_received",
+                "Number of missing transaction requests received from peers",
+            )
+            .unwrap()
+            .register_at(registry),
+            missing_transactions_responses_received: IntCounter::new(
+                "consensus_missing_transactions_responses_received",
+                "Number of missing transaction responses received from peers",
+            )
+            .unwrap()
+            .register_at(registry),
+            missing_transactions_received: IntCounter::new(
+                "consensus_missing_transactions_received",
+                "Number of transactions received in missing transaction responses",
+            )
+            .unwrap()
+            .register_at(registry),
             errors: IntCounter::new("consensus_errors", "Number of errors")
                 .unwrap()
                 .register_at(registry),
@@ -79,6 +161,18 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             needs_sync: IntCounter::new("consensus_needs_sync", "Number of times consensus needs to sync")
                 .unwrap()
                 .register_at(registry),
+            foreign_proposals_buffered: IntGauge::new(
+                "consensus_foreign_proposals_buffered",
+                "Number of foreign proposals for future epochs held in memory",
+            )
+            .unwrap()
+            .register_at(registry),
+            foreign_proposals_evicted: IntCounter::new(
+                "consensus_foreign_proposals_evicted",
+                "Number of foreign proposals evicted to storage because the buffer was full",
+            )
+            .unwrap()
+            .register_at(registry),
             transactions_ready_for_consensus: IntCounter::new(
                 "consensus_transaction_ready_for_consensus",
                 "Number of transactions ready for consensus",
@@ -97,15 +191,72 @@ impl<S: StateStore> PrometheusConsensusMetrics<S> {
             )
             .unwrap()
             .register_at(registry),
+            transactions_commit_latency: HistogramVec::new(
+                HistogramOpts::new(
+                    "consensus_transaction_commit_latency_seconds",
+                    "Time from a transaction being submitted for consensus to being finalized as committed",
+                )
+                .buckets(vec![
+                    0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0,
+                ]),
+                &["class"],
+            )
+            .unwrap()
+            .register_at(registry),
+            transactions_ready_at: HashMap::new(),
             _transactions_pool_size: IntGauge::new(
                 "consensus_transactions_pool_size",
                 "Number of transactions in pool",
             )
             .unwrap()
             .register_at(registry),
+            substates_count: IntGauge::new("state_store_substates_count", "Number of substates that are UP")
+                .unwrap()
+                .register_at(registry),
+            substates_count_updated_at: None,
+        }
+    }
+
+    fn update_substates_count(&mut self) {
+        if self
+            .substates_count_updated_at
+            .is_some_and(|at| at.elapsed() < SUBSTATES_COUNT_REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.substates_count_updated_at = Some(Instant::now());
+
+        match self.state_store.with_read_tx(|tx| tx.substates_count_up()) {
+            Ok(count) => {
+                self.substates_count.set(i64::try_from(count).unwrap_or(i64::MAX));
+            },
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Failed to count substates for metrics: {err}");
+            },
         }
     }
 
+    fn observe_commit_latency(&mut self, transaction: &TransactionAtom) {
+        let ready_at = self.transactions_ready_at.remove(transaction.id());
+        let record = match self.state_store.with_read_tx(|tx| transaction.get_transaction(tx)) {
+            Ok(record) => record,
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Failed to load finalized transaction for metrics: {err}");
+                return;
+            },
+        };
+        // Transactions that became ready before the node started only have a database timestamp, which has a
+        // resolution of one second
+        let Some(latency) = ready_at.map(|at| at.elapsed()).or_else(|| record.finalized_time()) else {
+            return;
+        };
+
+        let class = TransactionClass::classify(transaction, &record);
+        self.transactions_commit_latency
+            .with_label_values(&[class.as_label()])
+            .observe(latency.as_secs_f64());
+    }
+
     fn clean_up_commands_count(&self, prune_height: u64) {
         let metrics = self.commands_count.collect();
         let mut labels_to_remove = HashMap::new();
@@ -151,12 +302,36 @@ impl<S: StateStore> ConsensusHooks for PrometheusConsensusMetrics<S> {
         }
     }
 
+    fn on_block_proposed(&mut self, _block: &Block) {
+        self.blocks_proposed.inc();
+    }
+
+    fn on_block_committed(&mut self, block: &LeafBlock) {
+        self.blocks_committed.inc();
+        self.committed_height.set(block.height.as_u64() as i64);
+        self.update_substates_count();
+    }
+
+    fn on_epoch_changed(&mut self, epoch: Epoch) {
+        self.current_epoch.set(epoch.as_u64() as i64);
+    }
+
     fn on_block_validation_failed<E: ToString>(&mut self, _err: &E) {
         self.blocks_validation_failed.inc();
     }
 
-    fn on_message_received(&mut self, _message: &HotstuffMessage) {
+    fn on_message_received(&mut self, message: &HotstuffMessage) {
         self.messages_received.inc();
+        match message {
+            HotstuffMessage::MissingTransactionsRequest(_) => {
+                self.missing_transactions_requests_received.inc();
+            },
+            HotstuffMessage::MissingTransactionsResponse(msg) => {
+                self.missing_transactions_responses_received.inc();
+                self.missing_transactions_received.inc_by(msg.transactions.len() as u64);
+            },
+            _ => {},
+        }
     }
 
     fn on_error(&mut self, _err: &HotStuffError) {
@@ -175,18 +350,113 @@ impl<S: StateStore> ConsensusHooks for PrometheusConsensusMetrics<S> {
         self.needs_sync.inc();
     }
 
-    fn on_transaction_ready(&mut self, _tx_id: &TransactionId) {
+    fn on_foreign_proposals_buffered(&mut self, num_buffered: usize) {
+        self.foreign_proposals_buffered.set(num_buffered as i64);
+    }
+
+    fn on_foreign_proposal_evicted(&mut self, _block: &Block) {
+        self.foreign_proposals_evicted.inc();
+    }
+
+    fn on_transaction_ready(&mut self, tx_id: &TransactionId) {
         self.transactions_ready_for_consensus.inc();
+        if self.transactions_ready_at.len() >= COMMIT_LATENCY_TRACKING_PRUNE_THRESHOLD {
+            self.transactions_ready_at
+                .retain(|_, ready_at| ready_at.elapsed() < COMMIT_LATENCY_TRACKING_TTL);
+        }
+        self.transactions_ready_at.entry(*tx_id).or_insert_with(Instant::now);
     }
 
     fn on_transaction_finalized(&mut self, transaction: &TransactionAtom) {
         match transaction.decision {
             Decision::Commit => {
                 self.transactions_finalized_committed.inc();
+                self.observe_commit_latency(transaction);
             },
             Decision::Abort(_) => {
+                self.transactions_ready_at.remove(transaction.id());
                 self.transactions_finalized_aborted.inc();
             },
         }
     }
 }
+
+/// The class of a transaction used to label the commit latency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransactionClass {
+    SingleShard,
+    MultiShard,
+    Confidential,
+}
+
+impl TransactionClass {
+    fn classify(transaction: &TransactionAtom, record: &TransactionRecord) -> Self {
+        if is_confidential(record) {
+            return Self::Confidential;
+        }
+        if transaction.evidence.num_shard_groups() > 1 {
+            return Self::MultiShard;
+        }
+        Self::SingleShard
+    }
+
+    fn as_label(&self) -> &'static str {
+        match self {
+            Self::SingleShard => "single_shard",
+            Self::MultiShard => "multi_shard",
+            Self::Confidential => "confidential",
+        }
+    }
+}
+
+/// Returns true if the transaction claims a burn or changes a confidential vault
+fn is_confidential(record: &TransactionRecord) -> bool {
+    let transaction = record.transaction();
+    let is_claim_burn = transaction
+        .fee_instructions()
+        .iter()
+        .chain(transaction.instructions())
+        .any(|instruction| matches!(instruction, Instruction::ClaimBurn { .. }));
+    if is_claim_burn {
+        return true;
+    }
+
+    record
+        .execution_result()
+        .and_then(|result| result.finalize.accept())
+        .is_some_and(|diff| {
+            diff.up_iter().any(|(_, substate)| {
+                matches!(
+                    substate.substate_value(),
+                    SubstateValue::Vault(vault) if vault.resource_type() == ResourceType::Confidential
+                )
+            })
+        })
+}
+
+#[derive(Debug, Clone)]
+pub struct PrometheusTransactionExecutionMetrics {
+    execution_time: Histogram,
+}
+
+impl PrometheusTransactionExecutionMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            execution_time: Histogram::with_opts(
+                HistogramOpts::new(
+                    "consensus_transaction_execution_time_seconds",
+                    "Time taken to execute a transaction",
+                )
+                .buckets(vec![
+                    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+                ]),
+            )
+            .unwrap()
+            .register_at(registry),
+        }
+    }
+
+    pub fn on_transaction_executed(&self, transaction: &ExecutedTransaction) {
+        self.execution_time.observe(transaction.execution_time().as_secs_f64());
+    }
+}
@@ -1,9 +1,25 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+#[cfg(feature = "consensus-fault-injection")]
+use std::sync::Arc;
+
 use tari_common::configuration::Network;
+#[cfg(feature = "consensus-fault-injection")]
+use tari_consensus::hotstuff::fault_injection::{
+    FaultInjectingInboundMessaging,
+    FaultInjectingOutboundMessaging,
+    FaultInjector,
+};
 use tari_consensus::{
-    hotstuff::{ConsensusWorker, ConsensusWorkerContext, HotstuffConfig, HotstuffWorker},
+    hotstuff::{
+        ConsensusWorker,
+        ConsensusWorkerContext,
+        DeduplicatingInboundMessaging,
+        HotstuffConfig,
+        HotstuffWorker,
+        MessageDedupConfig,
+    },
     traits::ConsensusSpec,
 };
 use tari_crypto::ristretto::RistrettoPublicKey;
@@ -13,7 +29,7 @@ use tari_dan_app_utilities::{
 };
 use tari_dan_common_types::PeerAddress;
 use tari_dan_storage::consensus_models::TransactionPool;
-use tari_epoch_manager::base_layer::EpochManagerHandle;
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
 use tari_rpc_state_sync::RpcStateSyncManager;
 use tari_shutdown::ShutdownSignal;
 use tari_state_store_sqlite::SqliteStateStore;
@@ -41,6 +57,7 @@ use crate::{
 };
 
 mod block_transaction_executor;
+mod config;
 mod handle;
 mod leader_selection;
 #[cfg(feature = "metrics")]
@@ -49,6 +66,7 @@ mod signature_service;
 mod spec;
 
 pub use block_transaction_executor::*;
+pub use config::ConsensusConfig;
 pub use handle::*;
 pub use signature_service::*;
 use tari_consensus::{consensus_constants::ConsensusConstants, hotstuff::HotstuffEvent};
@@ -75,6 +93,8 @@ pub async fn spawn(
     >,
     tx_hotstuff_events: broadcast::Sender<HotstuffEvent>,
     consensus_constants: ConsensusConstants,
+    config: ConsensusConfig,
+    #[cfg(feature = "consensus-fault-injection")] fault_injector: Arc<dyn FaultInjector>,
 ) -> (JoinHandle<Result<(), anyhow::Error>>, ConsensusHandle) {
     let (tx_new_transaction, rx_new_transactions) = mpsc::channel(10);
 
@@ -85,8 +105,21 @@ pub async fn spawn(
         network,
         sidechain_id,
         consensus_constants,
+        max_parallel_executions: config.max_parallel_executions(),
+        max_buffered_foreign_proposals: 1000,
     };
 
+    // Faults are injected closest to the network so that the deduplication below sees duplicated messages
+    #[cfg(feature = "consensus-fault-injection")]
+    let (inbound_messaging, outbound_messaging) = (
+        FaultInjectingInboundMessaging::new(inbound_messaging, fault_injector.clone()),
+        FaultInjectingOutboundMessaging::new(outbound_messaging, fault_injector),
+    );
+
+    // Drop replayed and duplicated messages before they reach the hotstuff worker
+    let inbound_messaging =
+        DeduplicatingInboundMessaging::new(inbound_messaging, MessageDedupConfig::default(), epoch_manager.subscribe());
+
     let hotstuff_worker = HotstuffWorker::<TariConsensusSpec>::new(
         hs_config,
         local_addr,
@@ -1,9 +1,11 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+#[cfg(feature = "consensus-fault-injection")]
+use tari_consensus::hotstuff::fault_injection::{FaultInjectingInboundMessaging, FaultInjectingOutboundMessaging};
 #[cfg(not(feature = "metrics"))]
 use tari_consensus::traits::hooks::NoopHooks;
-use tari_consensus::traits::ConsensusSpec;
+use tari_consensus::{hotstuff::DeduplicatingInboundMessaging, traits::ConsensusSpec};
 use tari_dan_app_utilities::{
     template_manager::implementation::TemplateManager,
     transaction_executor::TariDanTransactionProcessor,
@@ -38,9 +40,16 @@ impl ConsensusSpec for TariConsensusSpec {
     type Hooks = NoopHooks;
     #[cfg(feature = "metrics")]
     type Hooks = PrometheusConsensusMetrics;
-    type InboundMessaging = ConsensusInboundMessaging<NopLogger>;
+    #[cfg(not(feature = "consensus-fault-injection"))]
+    type InboundMessaging = DeduplicatingInboundMessaging<ConsensusInboundMessaging<NopLogger>>;
+    #[cfg(feature = "consensus-fault-injection")]
+    type InboundMessaging =
+        DeduplicatingInboundMessaging<FaultInjectingInboundMessaging<ConsensusInboundMessaging<NopLogger>>>;
     type LeaderStrategy = RoundRobinLeaderStrategy;
+    #[cfg(not(feature = "consensus-fault-injection"))]
     type OutboundMessaging = ConsensusOutboundMessaging<NopLogger>;
+    #[cfg(feature = "consensus-fault-injection")]
+    type OutboundMessaging = FaultInjectingOutboundMessaging<ConsensusOutboundMessaging<NopLogger>>;
     type SignatureService = TariSignatureService;
     type StateStore = SqliteStateStore<Self::Addr>;
     type SyncManager = RpcStateSyncManager<Self>;
@@ -22,13 +22,14 @@
 
 use log::*;
 use tari_consensus::hotstuff::HotstuffEvent;
-use tari_dan_app_utilities::template_manager::interface::TemplateExecutable;
+use tari_dan_app_utilities::template_manager::interface::{TemplateExecutable, TemplateVersion};
 use tari_dan_storage::{
     consensus_models::{Block, Decision},
     StateStore,
 };
 use tari_engine_types::{
     commit_result::TransactionResult,
+    published_template::TemplateType,
     substate::{SubstateId, SubstateValue},
 };
 use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
@@ -118,11 +119,28 @@ impl DanNode {
                     if let SubstateId::Template(template_address) = substate_id {
                         let template_address_hash = template_address.as_hash();
                         if let SubstateValue::Template(template) = substate.into_substate_value() {
-                            templates.push((
-                                signer_pub_key.clone(),
-                                template_address_hash,
-                                TemplateExecutable::CompiledWasm(template.binary),
-                            ));
+                            let version = TemplateVersion {
+                                template_version: template.template_version,
+                                previous_version: template.previous_version.map(|a| a.as_hash()),
+                                lineage_root: template.lineage_root.map(|a| a.as_hash()),
+                            };
+                            let executable = match template.template_type {
+                                TemplateType::Wasm => TemplateExecutable::CompiledWasm(template.binary),
+                                // The flow JSON is validated when the template is published
+                                TemplateType::Flow => match String::from_utf8(template.binary) {
+                                    Ok(flow_json) => TemplateExecutable::Flow(flow_json),
+                                    Err(err) => {
+                                        warn!(
+                                            target: LOG_TARGET,
+                                            "Published flow template {} is not valid UTF-8: {}",
+                                            template_address_hash,
+                                            err
+                                        );
+                                        continue;
+                                    },
+                                },
+                            };
+                            templates.push((signer_pub_key.clone(), template_address_hash, executable, version));
                         }
                     }
                 }
@@ -131,10 +149,10 @@ impl DanNode {
 
         // adding templates to template manager
         let mut template_counter = 0;
-        for (author_pub_key, template_address, template) in templates {
+        for (author_pub_key, template_address, template, version) in templates {
             self.services
                 .template_manager
-                .add_template(author_pub_key, template_address, template, None)
+                .add_template(author_pub_key, template_address, template, None, version)
                 .await?;
             template_counter += 1;
         }
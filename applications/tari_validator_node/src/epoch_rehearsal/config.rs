@@ -0,0 +1,32 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EpochRehearsalConfig {
+    /// If true, the transition into the next epoch is rehearsed periodically and the outcome is logged
+    pub enabled: bool,
+    /// How often the transition is rehearsed
+    #[serde(with = "serializers::seconds")]
+    pub interval: Duration,
+    /// If true, pending transactions are executed against a throwaway copy of the state that would be handed over
+    pub shadow_execution: bool,
+    /// The maximum number of pending transactions to shadow execute in each rehearsal
+    pub max_shadow_transactions: usize,
+}
+
+impl Default for EpochRehearsalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: Duration::from_secs(60),
+            shadow_execution: true,
+            max_shadow_transactions: 100,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_consensus::traits::BlockTransactionExecutor;
+use tari_dan_common_types::{NumPreshards, PeerAddress};
+use tari_epoch_manager::base_layer::EpochManagerHandle;
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tokio::{task, task::JoinHandle};
+
+use super::{service::EpochRehearsalService, EpochRehearsalConfig};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::epoch_rehearsal";
+
+pub fn spawn<TExecutor>(
+    config: EpochRehearsalConfig,
+    num_preshards: NumPreshards,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    state_store: SqliteStateStore<PeerAddress>,
+    transaction_executor: TExecutor,
+    shutdown: ShutdownSignal,
+) -> JoinHandle<anyhow::Result<()>>
+where
+    TExecutor: BlockTransactionExecutor<SqliteStateStore<PeerAddress>> + Clone + Send + Sync + 'static,
+{
+    let service = EpochRehearsalService::new(
+        config,
+        num_preshards,
+        epoch_manager,
+        state_store,
+        transaction_executor,
+        shutdown,
+    );
+
+    let join_handle = task::spawn(service.run());
+    debug!(target: LOG_TARGET, "Spawning epoch rehearsal service (task: {:?})", join_handle);
+
+    join_handle
+}
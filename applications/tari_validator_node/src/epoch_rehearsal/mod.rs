@@ -0,0 +1,22 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Epoch transition rehearsal (shadow mode). The committee assignment of the upcoming epoch is projected from the
+//! validator registrations known so far and compared with the current assignment, so that operators can see the
+//! expected workload and any state that must be synced before the real transition. Pending transactions may also be
+//! shadow executed against a throwaway store seeded with the state that would be handed over. Nothing is persisted
+//! and live consensus is not affected.
+
+mod config;
+pub use config::EpochRehearsalConfig;
+
+mod initializer;
+pub use initializer::spawn;
+
+mod rehearsal;
+pub use rehearsal::rehearse_epoch_transition;
+
+mod service;
+
+mod shadow;
+pub use shadow::shadow_execute_pending;
@@ -0,0 +1,79 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeSet;
+
+use tari_dan_common_types::{shard::Shard, Epoch, PeerAddress, ShardGroup};
+use tari_dan_storage::{StateStore, StateStoreReadTransaction};
+use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_validator_node_client::types::EpochTransitionRehearsal;
+
+/// Rehearses the transition of the local validator from the current epoch into the next epoch
+pub async fn rehearse_epoch_transition(
+    epoch_manager: &EpochManagerHandle<PeerAddress>,
+    state_store: &SqliteStateStore<PeerAddress>,
+) -> anyhow::Result<EpochTransitionRehearsal> {
+    let current_epoch = epoch_manager.current_epoch().await?;
+    let next_epoch = Epoch(current_epoch.as_u64() + 1);
+
+    let current_shard_group = match epoch_manager.get_local_committee_info(current_epoch).await {
+        Ok(committee_info) => Some(committee_info.shard_group()),
+        Err(err) if err.is_not_registered_error() => None,
+        Err(err) => return Err(err.into()),
+    };
+    let next = epoch_manager.get_projected_committee_assignment(next_epoch).await?;
+    let pending_transactions = state_store.with_read_tx(|tx| tx.transaction_pool_count(None, None, None))? as u64;
+
+    let current_shards = shards_of(current_shard_group);
+    let next_shards = shards_of(next.local_shard_group);
+    let shards_to_sync = next_shards.difference(&current_shards).copied().collect::<Vec<_>>();
+    let shards_to_hand_off = current_shards.difference(&next_shards).copied().collect::<Vec<_>>();
+
+    let mut warnings = Vec::new();
+    if current_shard_group.is_some() && next.local_shard_group.is_none() {
+        warnings.push(format!(
+            "This validator is not registered for {} and will not be a member of any committee",
+            next_epoch
+        ));
+    }
+    if !shards_to_sync.is_empty() {
+        warnings.push(format!(
+            "The state of {} shard(s) must be synced before this validator can participate in {}",
+            shards_to_sync.len(),
+            next_epoch
+        ));
+    }
+    if next.local_shard_group.is_some() && max_faulty(next.local_committee_size) == 0 {
+        warnings.push(format!(
+            "The committee in {} has {} validator(s) and cannot tolerate a faulty validator",
+            next_epoch, next.local_committee_size
+        ));
+    }
+
+    Ok(EpochTransitionRehearsal {
+        current_epoch,
+        next_epoch,
+        current_shard_group,
+        next_shard_group: next.local_shard_group,
+        next_num_validators: next.num_validators,
+        next_num_committees: next.num_committees,
+        next_committee_size: next.local_committee_size,
+        shards_to_sync,
+        shards_to_hand_off,
+        pending_transactions,
+        warnings,
+        shadow_execution: None,
+    })
+}
+
+pub(super) fn shards_of(shard_group: Option<ShardGroup>) -> BTreeSet<Shard> {
+    shard_group
+        .map(|shard_group| shard_group.shard_iter().collect())
+        .unwrap_or_default()
+}
+
+/// The maximum number of faulty validators that a committee of the given size can tolerate
+fn max_faulty(committee_size: u32) -> u32 {
+    committee_size.saturating_sub(1) / 3
+}
@@ -0,0 +1,160 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_consensus::traits::BlockTransactionExecutor;
+use tari_dan_common_types::{NumPreshards, PeerAddress, ShardGroup};
+use tari_epoch_manager::base_layer::EpochManagerHandle;
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_validator_node_client::types::{EpochTransitionRehearsal, ShadowExecutionSummary};
+use tokio::{task, time, time::MissedTickBehavior};
+
+use super::{rehearsal::shards_of, rehearse_epoch_transition, shadow_execute_pending, EpochRehearsalConfig};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::epoch_rehearsal";
+
+pub struct EpochRehearsalService<TExecutor> {
+    config: EpochRehearsalConfig,
+    num_preshards: NumPreshards,
+    epoch_manager: EpochManagerHandle<PeerAddress>,
+    state_store: SqliteStateStore<PeerAddress>,
+    transaction_executor: TExecutor,
+    shutdown: ShutdownSignal,
+    last_rehearsal: Option<EpochTransitionRehearsal>,
+}
+
+impl<TExecutor> EpochRehearsalService<TExecutor>
+where TExecutor: BlockTransactionExecutor<SqliteStateStore<PeerAddress>> + Clone + Send + Sync + 'static
+{
+    pub fn new(
+        config: EpochRehearsalConfig,
+        num_preshards: NumPreshards,
+        epoch_manager: EpochManagerHandle<PeerAddress>,
+        state_store: SqliteStateStore<PeerAddress>,
+        transaction_executor: TExecutor,
+        shutdown: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            num_preshards,
+            epoch_manager,
+            state_store,
+            transaction_executor,
+            shutdown,
+            last_rehearsal: None,
+        }
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            info!(target: LOG_TARGET, "Epoch transition rehearsal is disabled");
+            return Ok(());
+        }
+
+        let mut interval = time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    self.rehearse().await;
+                },
+                _ = self.shutdown.wait() => {
+                    info!(target: LOG_TARGET, "Epoch rehearsal shutting down");
+                    break;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn rehearse(&mut self) {
+        let mut rehearsal = match rehearse_epoch_transition(&self.epoch_manager, &self.state_store).await {
+            Ok(rehearsal) => rehearsal,
+            Err(err) => {
+                error!(target: LOG_TARGET, "Epoch transition rehearsal failed: {}", err);
+                return;
+            },
+        };
+        if self.config.shadow_execution {
+            match self.shadow_execute(&rehearsal).await {
+                Ok(summary) => rehearsal.shadow_execution = summary,
+                Err(err) => error!(target: LOG_TARGET, "Shadow execution failed: {}", err),
+            }
+        }
+
+        // Only report when the projected transition changes, e.g. when new registrations are scanned
+        if self.last_rehearsal.as_ref() == Some(&rehearsal) {
+            return;
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "🎭 Rehearsed transition {} -> {}: shard group {} -> {}, committee of {} validator(s) ({} committee(s)), {} \
+             shard(s) to sync, {} shard(s) to hand off, {} pending transaction(s)",
+            rehearsal.current_epoch,
+            rehearsal.next_epoch,
+            display_shard_group(rehearsal.current_shard_group),
+            display_shard_group(rehearsal.next_shard_group),
+            rehearsal.next_committee_size,
+            rehearsal.next_num_committees,
+            rehearsal.shards_to_sync.len(),
+            rehearsal.shards_to_hand_off.len(),
+            rehearsal.pending_transactions,
+        );
+        if let Some(ref summary) = rehearsal.shadow_execution {
+            info!(
+                target: LOG_TARGET,
+                "🎭 Shadow executed pending transactions against {} handed off substate(s): {} accepted, {} rejected, \
+                 {} awaiting state sync, {} awaiting foreign inputs",
+                summary.handed_off_substates,
+                summary.accepted,
+                summary.rejected,
+                summary.awaiting_sync,
+                summary.awaiting_foreign_inputs,
+            );
+        }
+        for warning in &rehearsal.warnings {
+            warn!(target: LOG_TARGET, "⚠️ {}", warning);
+        }
+
+        self.last_rehearsal = Some(rehearsal);
+    }
+
+    async fn shadow_execute(
+        &self,
+        rehearsal: &EpochTransitionRehearsal,
+    ) -> anyhow::Result<Option<ShadowExecutionSummary>> {
+        let Some(next_shard_group) = rehearsal.next_shard_group else {
+            // Nothing to rehearse if this validator is not in a committee in the next epoch
+            return Ok(None);
+        };
+        let executor = self.transaction_executor.clone();
+        let state_store = self.state_store.clone();
+        let num_preshards = self.num_preshards;
+        let next_epoch = rehearsal.next_epoch;
+        let local_shards = shards_of(rehearsal.current_shard_group);
+        let max_transactions = self.config.max_shadow_transactions;
+
+        let summary = task::spawn_blocking(move || {
+            shadow_execute_pending(
+                &executor,
+                &state_store,
+                num_preshards,
+                next_epoch,
+                next_shard_group,
+                &local_shards,
+                max_transactions,
+            )
+        })
+        .await??;
+
+        Ok(Some(summary))
+    }
+}
+
+fn display_shard_group(shard_group: Option<ShardGroup>) -> String {
+    shard_group.map_or_else(|| "none".to_string(), |shard_group| shard_group.to_string())
+}
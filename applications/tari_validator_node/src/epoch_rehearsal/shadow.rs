@@ -0,0 +1,350 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    marker::PhantomData,
+};
+
+use tari_consensus::traits::BlockTransactionExecutor;
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, NumPreshards, ShardGroup, SubstateAddress};
+use tari_dan_engine::state_store::{memory::MemoryStateStore, new_memory_store, StateReader, StateStoreError, StateWriter};
+use tari_dan_storage::{
+    consensus_models::{SubstateRecord, TransactionRecord},
+    StateStore,
+    StateStoreReadTransaction,
+};
+use tari_engine_types::substate::{Substate, SubstateId};
+use tari_transaction::Transaction;
+use tari_validator_node_client::types::ShadowExecutionSummary;
+
+/// Executes transactions against a throwaway store that is seeded with the state that the local validator would hand
+/// over into the next epoch. The live state store is only ever read and nothing is proposed or persisted.
+pub struct ShadowExecution<'a, TStateStore, TExecutor> {
+    executor: &'a TExecutor,
+    num_preshards: NumPreshards,
+    next_epoch: Epoch,
+    next_shard_group: ShardGroup,
+    local_shards: &'a BTreeSet<Shard>,
+    store: MemoryStateStore,
+    summary: ShadowExecutionSummary,
+    _state_store: PhantomData<TStateStore>,
+}
+
+impl<'a, TStateStore, TExecutor> ShadowExecution<'a, TStateStore, TExecutor>
+where
+    TStateStore: StateStore,
+    TExecutor: BlockTransactionExecutor<TStateStore>,
+{
+    pub fn new(
+        executor: &'a TExecutor,
+        num_preshards: NumPreshards,
+        next_epoch: Epoch,
+        next_shard_group: ShardGroup,
+        local_shards: &'a BTreeSet<Shard>,
+    ) -> Self {
+        Self {
+            executor,
+            num_preshards,
+            next_epoch,
+            next_shard_group,
+            local_shards,
+            store: new_memory_store(),
+            summary: ShadowExecutionSummary::default(),
+            _state_store: PhantomData,
+        }
+    }
+
+    /// Copies the latest version of every input of the given transactions that the local validator currently holds
+    /// from the live state store into the throwaway store.
+    pub fn hand_off_inputs(
+        &mut self,
+        tx: &TStateStore::ReadTransaction<'_>,
+        transactions: &[Transaction],
+    ) -> anyhow::Result<()> {
+        for requirement in transactions.iter().flat_map(|t| t.all_inputs_iter()) {
+            let substate_id = requirement.substate_id();
+            if !self.is_held_locally(substate_id) || self.store.exists(substate_id)? {
+                continue;
+            }
+            let Some(substate) = SubstateRecord::get_latest(tx, substate_id).optional()? else {
+                continue;
+            };
+            if substate.is_destroyed() {
+                continue;
+            }
+            self.hand_off(substate_id.clone(), substate.into_substate())?;
+        }
+        Ok(())
+    }
+
+    /// Adds a substate to the throwaway store
+    pub fn hand_off(&mut self, substate_id: SubstateId, substate: Substate) -> Result<(), StateStoreError> {
+        self.store.set_state(substate_id, substate)?;
+        self.summary.handed_off_substates += 1;
+        Ok(())
+    }
+
+    /// Executes the transaction against the throwaway store. If the transaction is accepted, its substate diff is
+    /// applied to the throwaway store so that subsequent transactions observe its outputs.
+    pub fn execute(&mut self, transaction: Transaction) -> anyhow::Result<()> {
+        let mut resolved_inputs = HashMap::new();
+        let mut awaiting_sync = false;
+        let mut awaiting_foreign_inputs = false;
+        let mut missing_local_input = false;
+        for requirement in transaction.all_inputs_iter() {
+            let substate_id = requirement.substate_id();
+            if self.store.exists(substate_id)? {
+                let substate = self.store.get_state(substate_id)?;
+                if requirement.version().is_some_and(|v| v != substate.version()) {
+                    // The requested version has already been consumed
+                    missing_local_input = true;
+                    continue;
+                }
+                resolved_inputs.insert(requirement, substate.clone());
+                continue;
+            }
+
+            let shard = self.shard_of(substate_id);
+            if self.local_shards.contains(&shard) {
+                missing_local_input = true;
+            } else if self.next_shard_group.contains(&shard) {
+                awaiting_sync = true;
+            } else {
+                awaiting_foreign_inputs = true;
+            }
+        }
+
+        if awaiting_sync {
+            self.summary.awaiting_sync += 1;
+            return Ok(());
+        }
+        if awaiting_foreign_inputs {
+            self.summary.awaiting_foreign_inputs += 1;
+            return Ok(());
+        }
+        if missing_local_input {
+            self.summary.rejected += 1;
+            return Ok(());
+        }
+
+        let executed = self.executor.execute(transaction, self.next_epoch, &resolved_inputs)?;
+        let finalize = &executed.result().finalize;
+        if let Some(diff) = finalize.accept() {
+            for (substate_id, _) in diff.down_iter() {
+                self.store.delete_state(substate_id);
+            }
+            for (substate_id, substate) in diff.up_iter() {
+                self.store.set_state(substate_id.clone(), substate.clone())?;
+            }
+        }
+        if finalize.is_full_accept() {
+            self.summary.accepted += 1;
+        } else {
+            self.summary.rejected += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn into_summary(self) -> ShadowExecutionSummary {
+        self.summary
+    }
+
+    fn is_held_locally(&self, substate_id: &SubstateId) -> bool {
+        self.local_shards.contains(&self.shard_of(substate_id))
+    }
+
+    fn shard_of(&self, substate_id: &SubstateId) -> Shard {
+        // The version does not affect the shard
+        SubstateAddress::from_substate_id(substate_id, 0).to_shard(self.num_preshards)
+    }
+}
+
+/// Shadow executes up to `max_transactions` transactions from the local transaction pool against the state that the
+/// local validator would hold in the next epoch
+pub fn shadow_execute_pending<TStateStore, TExecutor>(
+    executor: &TExecutor,
+    state_store: &TStateStore,
+    num_preshards: NumPreshards,
+    next_epoch: Epoch,
+    next_shard_group: ShardGroup,
+    local_shards: &BTreeSet<Shard>,
+    max_transactions: usize,
+) -> anyhow::Result<ShadowExecutionSummary>
+where
+    TStateStore: StateStore,
+    TExecutor: BlockTransactionExecutor<TStateStore>,
+{
+    let mut shadow = ShadowExecution::new(executor, num_preshards, next_epoch, next_shard_group, local_shards);
+    let transactions = state_store.with_read_tx(|tx| {
+        let pool = tx.transaction_pool_get_all()?;
+        let (transactions, _) = TransactionRecord::get_any(tx, pool.iter().map(|rec| rec.transaction_id()))?;
+        let mut transactions = transactions
+            .into_iter()
+            .map(|rec| rec.into_transaction())
+            .collect::<Vec<_>>();
+        // Execute in a deterministic order so that consecutive rehearsals are comparable
+        transactions.sort_by(|a, b| a.id().cmp(b.id()));
+        transactions.truncate(max_transactions);
+        shadow.hand_off_inputs(tx, &transactions)?;
+        Ok::<_, anyhow::Error>(transactions)
+    })?;
+
+    for transaction in transactions {
+        shadow.execute(transaction)?;
+    }
+
+    Ok(shadow.into_summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_common_types::types::PublicKey;
+    use tari_consensus::traits::BlockTransactionExecutorError;
+    use tari_dan_common_types::{PeerAddress, SubstateRequirement};
+    use tari_dan_storage::consensus_models::ExecutedTransaction;
+    use tari_engine_types::{
+        commit_result::{ExecuteResult, FinalizeResult, RejectReason, TransactionResult},
+        fee_claim::{FeeClaim, FeeClaimAddress},
+        fees::FeeReceipt,
+        substate::SubstateDiff,
+    };
+    use tari_state_store_sqlite::SqliteStateStore;
+    use tari_template_lib::{models::Amount, Hash};
+
+    use super::*;
+
+    type TestStateStore = SqliteStateStore<PeerAddress>;
+
+    /// Consumes every input and outputs the next version of it, unless the transaction is in the reject set
+    struct BumpVersionExecutor {
+        reject: Vec<Transaction>,
+    }
+
+    impl BlockTransactionExecutor<TestStateStore> for BumpVersionExecutor {
+        fn validate(
+            &self,
+            _tx: &<TestStateStore as StateStore>::ReadTransaction<'_>,
+            _current_epoch: Epoch,
+            _transaction: &Transaction,
+        ) -> Result<(), BlockTransactionExecutorError> {
+            Ok(())
+        }
+
+        fn execute(
+            &self,
+            transaction: Transaction,
+            _current_epoch: Epoch,
+            resolved_inputs: &HashMap<SubstateRequirement, Substate>,
+        ) -> Result<ExecutedTransaction, BlockTransactionExecutorError> {
+            let result = if self.reject.iter().any(|t| t.id() == transaction.id()) {
+                TransactionResult::Reject(RejectReason::ExecutionFailure("rejected".to_string()))
+            } else {
+                let mut diff = SubstateDiff::new();
+                for (requirement, substate) in resolved_inputs {
+                    diff.down(requirement.substate_id().clone(), substate.version());
+                    diff.up(
+                        requirement.substate_id().clone(),
+                        Substate::new(substate.version() + 1, substate.substate_value().clone()),
+                    );
+                }
+                TransactionResult::Accept(diff)
+            };
+            let result = ExecuteResult {
+                finalize: FinalizeResult::new(Hash::default(), vec![], vec![], result, FeeReceipt::default()),
+                execution_time: Default::default(),
+                substate_access_stats: None,
+            };
+            Ok(ExecutedTransaction::new(transaction, result, vec![]))
+        }
+    }
+
+    fn substate_in_shard(shard: u32, num_preshards: NumPreshards) -> (SubstateId, Substate) {
+        let substate_id = (0u8..=u8::MAX)
+            .map(|i| SubstateId::FeeClaim(FeeClaimAddress::from_hash(Hash::from_array([i; 32]))))
+            .find(|id| SubstateAddress::from_substate_id(id, 0).to_shard(num_preshards) == Shard::from(shard))
+            .expect("no substate id found in shard");
+        let substate = Substate::new(0, FeeClaim {
+            epoch: 0,
+            validator_public_key: PublicKey::default(),
+            amount: Amount::new(1),
+        });
+        (substate_id, substate)
+    }
+
+    fn transaction_with_input(substate_id: &SubstateId, version: Option<u32>) -> Transaction {
+        Transaction::builder()
+            .add_input(SubstateRequirement::new(substate_id.clone(), version))
+            .build()
+    }
+
+    #[test]
+    fn it_executes_against_the_handed_off_state() {
+        let num_preshards = NumPreshards::P4;
+        let local_shards = [Shard::from(0)].into_iter().collect();
+        let executor = BumpVersionExecutor { reject: vec![] };
+        let mut shadow = ShadowExecution::<TestStateStore, _>::new(
+            &executor,
+            num_preshards,
+            Epoch(2),
+            ShardGroup::new(0, 1),
+            &local_shards,
+        );
+        let (substate_id, substate) = substate_in_shard(0, num_preshards);
+        shadow.hand_off(substate_id.clone(), substate).unwrap();
+
+        // Executes against the handed off version 0
+        shadow.execute(transaction_with_input(&substate_id, None)).unwrap();
+        // Observes version 1 output by the previous transaction
+        shadow.execute(transaction_with_input(&substate_id, Some(1))).unwrap();
+        // Version 0 has already been consumed
+        shadow.execute(transaction_with_input(&substate_id, Some(0))).unwrap();
+
+        let summary = shadow.into_summary();
+        assert_eq!(summary.handed_off_substates, 1);
+        assert_eq!(summary.accepted, 2);
+        assert_eq!(summary.rejected, 1);
+        assert_eq!(summary.awaiting_sync, 0);
+        assert_eq!(summary.awaiting_foreign_inputs, 0);
+    }
+
+    #[test]
+    fn it_reports_transactions_that_need_state_that_is_not_held_locally() {
+        let num_preshards = NumPreshards::P4;
+        let local_shards = [Shard::from(0)].into_iter().collect();
+        let (local_id, local_substate) = substate_in_shard(0, num_preshards);
+        let (to_sync_id, _) = substate_in_shard(1, num_preshards);
+        let (foreign_id, _) = substate_in_shard(3, num_preshards);
+        let rejected = transaction_with_input(&local_id, None);
+        let executor = BumpVersionExecutor {
+            reject: vec![rejected.clone()],
+        };
+        let mut shadow = ShadowExecution::<TestStateStore, _>::new(
+            &executor,
+            num_preshards,
+            Epoch(2),
+            ShardGroup::new(0, 1),
+            &local_shards,
+        );
+        shadow.hand_off(local_id.clone(), local_substate).unwrap();
+
+        shadow.execute(transaction_with_input(&to_sync_id, None)).unwrap();
+        shadow.execute(transaction_with_input(&foreign_id, None)).unwrap();
+        shadow
+            .execute(
+                Transaction::builder()
+                    .add_input(SubstateRequirement::new(local_id.clone(), None))
+                    .add_input(SubstateRequirement::new(to_sync_id, None))
+                    .build(),
+            )
+            .unwrap();
+        shadow.execute(rejected).unwrap();
+
+        let summary = shadow.into_summary();
+        assert_eq!(summary.awaiting_sync, 2);
+        assert_eq!(summary.awaiting_foreign_inputs, 1);
+        assert_eq!(summary.accepted, 0);
+        assert_eq!(summary.rejected, 1);
+    }
+}
@@ -20,6 +20,8 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::collections::HashMap;
+
 use axum_jrpc::{
     error::{JsonRpcError, JsonRpcErrorReason},
     JrpcResult,
@@ -30,15 +32,40 @@ use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
 use log::*;
 use serde_json::{self as json, json};
 use tari_base_node_client::{grpc::GrpcBaseNodeClient, BaseNodeClient};
-use tari_dan_app_utilities::{keypair::RistrettoKeypair, template_manager::interface::TemplateManagerHandle};
-use tari_dan_common_types::{optional::Optional, public_key_to_peer_id, Epoch, PeerAddress, SubstateAddress};
+use tari_consensus::hotstuff::{substate_store::ShardedStateTree, HotStuffError};
+use tari_dan_app_utilities::{
+    keypair::RistrettoKeypair,
+    template_manager::interface::{self, TemplateManagerHandle},
+};
+use tari_dan_common_types::{
+    optional::Optional,
+    public_key_to_peer_id,
+    Epoch,
+    PeerAddress,
+    SubstateAddress,
+    VersionedSubstateId,
+};
 use tari_dan_p2p::TariMessagingSpec;
 use tari_dan_storage::{
-    consensus_models::{Block, ExecutedTransaction, LeafBlock, QuorumDecision, SubstateRecord, TransactionRecord},
+    consensus_models::{
+        Block,
+        ExecutedTransaction,
+        ForeignReceiveCounters,
+        ForeignReceiveGap,
+        LeafBlock,
+        MisbehaviourEvidence,
+        QuorumDecision,
+        SubstateRecord,
+        TransactionEvent,
+        TransactionRecord,
+        TransactionTrace,
+    },
     Ordering,
     StateStore,
     StateStoreReadTransaction,
+    StorageError,
 };
+use tari_engine_types::substate::{SubstateId, SubstateValue};
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
 use tari_networking::{is_supported_multiaddr, NetworkingHandle, NetworkingService};
 use tari_state_store_sqlite::SqliteStateStore;
@@ -46,10 +73,15 @@ use tari_validator_node_client::types::{
     self,
     AddPeerRequest,
     AddPeerResponse,
+    BandwidthStats,
+    CommitteeShardInfo,
     ConnectionDirection,
     DryRunTransactionFinalizeResult,
+    EvictMempoolTransactionRequest,
+    EvictMempoolTransactionResponse,
     GetAllVnsRequest,
     GetAllVnsResponse,
+    GetBandwidthStatsResponse,
     GetBaseLayerEpochChangesRequest,
     GetBaseLayerEpochChangesResponse,
     GetBlockRequest,
@@ -63,16 +95,29 @@ use tari_validator_node_client::types::{
     GetConnectionsResponse,
     GetConsensusStatusResponse,
     GetEpochManagerStatsResponse,
+    GetEpochTransitionRehearsalResponse,
+    GetEventsRequest,
+    GetEventsResponse,
     GetFilteredBlocksCountRequest,
     GetIdentityResponse,
     GetMempoolStatsResponse,
+    GetMempoolTransactionsResponse,
+    GetForeignReceiveGapsRequest,
+    GetForeignReceiveGapsResponse,
+    GetMisbehaviourEvidenceRequest,
+    GetMisbehaviourEvidenceResponse,
+    GetNetworkCommitteeResponse,
+    GetNetworkCommitteesRequest,
     GetRecentTransactionsResponse,
     GetShardKeyRequest,
     GetShardKeyResponse,
     GetStateRequest,
     GetStateResponse,
+    GetSubstatePruningStatusResponse,
     GetSubstateRequest,
     GetSubstateResponse,
+    GetSubstateWithProofRequest,
+    GetSubstateWithProofResponse,
     GetSubstatesByTransactionRequest,
     GetSubstatesByTransactionResponse,
     GetTemplateRequest,
@@ -83,25 +128,41 @@ use tari_validator_node_client::types::{
     GetTransactionResponse,
     GetTransactionResultRequest,
     GetTransactionResultResponse,
+    GetTxPoolByPriorityRequest,
+    GetTxPoolByPriorityResponse,
     GetValidatorFeesRequest,
     GetValidatorFeesResponse,
+    GetVaultBalanceRequest,
+    GetVaultBalanceResponse,
     ListBlocksRequest,
     ListBlocksResponse,
+    MempoolTransactionInfo,
     SubmitTransactionRequest,
     SubmitTransactionResponse,
     SubstateStatus,
     TemplateMetadata,
+    TraceTransactionRequest,
+    TraceTransactionResponse,
 };
 
 use crate::{
+    auto_registration::AutoRegistrationHandle,
     consensus::ConsensusHandle,
     dry_run_transaction_processor::DryRunTransactionProcessor,
+    epoch_rehearsal::rehearse_epoch_transition,
     json_rpc::jrpc_errors::{internal_error, not_found},
-    p2p::services::mempool::MempoolHandle,
+    p2p::services::mempool::{MempoolError, MempoolHandle},
+    substate_pruning::SubstatePruningHandle,
     Services,
 };
 
 const LOG_TARGET: &str = "tari::validator_node::json_rpc::handlers";
+/// The number of evidence records returned if the request does not set a limit
+const DEFAULT_MISBEHAVIOUR_EVIDENCE_LIMIT: u64 = 100;
+/// The number of foreign receive gaps returned if the request does not set a limit
+const DEFAULT_FOREIGN_RECEIVE_GAPS_LIMIT: u64 = 100;
+/// The number of events returned if the request does not set a limit
+const DEFAULT_EVENTS_LIMIT: u64 = 100;
 
 pub struct JsonRpcHandlers {
     keypair: RistrettoKeypair,
@@ -113,6 +174,8 @@ pub struct JsonRpcHandlers {
     base_node_client: GrpcBaseNodeClient,
     state_store: SqliteStateStore<PeerAddress>,
     dry_run_transaction_processor: DryRunTransactionProcessor,
+    substate_pruning: SubstatePruningHandle,
+    auto_registration: AutoRegistrationHandle,
 }
 
 impl JsonRpcHandlers {
@@ -127,6 +190,8 @@ impl JsonRpcHandlers {
             base_node_client,
             state_store: services.state_store.clone(),
             dry_run_transaction_processor: services.dry_run_transaction_processor.clone(),
+            substate_pruning: services.substate_pruning.clone(),
+            auto_registration: services.auto_registration.clone(),
         }
     }
 
@@ -316,6 +381,27 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, res))
     }
 
+    pub async fn get_tx_pool_by_priority(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetTxPoolByPriorityRequest = value.parse_params()?;
+        let mut tx_pool = self
+            .state_store
+            .with_read_tx(|tx| tx.transaction_pool_get_all())
+            .map_err(internal_error(answer_id))?;
+        // Same order as proposals: transactions in progress first, then new transactions by priority fee
+        tx_pool.sort_by(|a, b| {
+            a.current_stage()
+                .is_new()
+                .cmp(&b.current_stage().is_new())
+                .then_with(|| b.priority_fee().cmp(&a.priority_fee()))
+                .then_with(|| a.transaction_id().cmp(b.transaction_id()))
+        });
+        if let Some(limit) = request.limit {
+            tx_pool.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        }
+        Ok(JsonRpcResponse::success(answer_id, GetTxPoolByPriorityResponse { tx_pool }))
+    }
+
     pub async fn get_transaction_result(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request: GetTransactionResultRequest = value.parse_params()?;
@@ -382,6 +468,85 @@ impl JsonRpcHandlers {
         }
     }
 
+    pub async fn get_vault_balance(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let req: GetVaultBalanceRequest = value.parse_params()?;
+
+        let substate_id = SubstateId::Vault(req.vault_id);
+        let substate = self
+            .state_store
+            .with_read_tx(|tx| SubstateRecord::get_latest(tx, &substate_id).optional())
+            .map_err(internal_error(answer_id))?
+            .filter(|s| !s.is_destroyed())
+            .ok_or_else(|| not_found(answer_id, format!("Vault {} not found", req.vault_id)))?;
+
+        let version = substate.version();
+        let SubstateValue::Vault(vault) = substate.into_substate_value() else {
+            return Err(internal_error(answer_id)(format!("Substate {} is not a vault", substate_id)));
+        };
+
+        Ok(JsonRpcResponse::success(answer_id, GetVaultBalanceResponse {
+            version,
+            resource_address: *vault.resource_address(),
+            resource_type: vault.resource_type(),
+            balance: vault.balance(),
+            locked_balance: vault.locked_balance(),
+            num_confidential_outputs: vault.get_confidential_commitments().map_or(0, |c| c.len() as u64),
+            num_non_fungibles: vault.get_non_fungible_ids().len() as u64,
+        }))
+    }
+
+    pub async fn get_substate_with_proof(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let data: GetSubstateWithProofRequest = value.parse_params()?;
+
+        let epoch = self.consensus.current_epoch();
+        let committee_info = self
+            .epoch_manager
+            .get_local_committee_info(epoch)
+            .await
+            .map_err(internal_error(answer_id))?;
+        let id = VersionedSubstateId::new(data.address, data.version);
+        let address = SubstateAddress::from_substate_id(id.substate_id(), id.version());
+        if !committee_info.includes_substate_address(&address) {
+            return Err(not_found(
+                answer_id,
+                format!("Substate {id} is not in the shard group of this validator node"),
+            ));
+        }
+        let shard = address.to_shard(committee_info.num_preshards());
+
+        // The proof, substate and block are read in the same transaction so that the proof matches the state root of
+        // the last committed block
+        let maybe_proof = self
+            .state_store
+            .with_read_tx(|tx| {
+                let Some(block) = Block::get_last_n_in_epoch(tx, 1, epoch)?.pop() else {
+                    return Ok(None);
+                };
+                let Some(substate) = SubstateRecord::get(tx, &address).optional()? else {
+                    return Ok(None);
+                };
+                if substate.is_destroyed() {
+                    return Ok(None);
+                }
+                let maybe_proof =
+                    ShardedStateTree::new(tx).get_substate_inclusion_proof(committee_info.shard_group(), shard, &id)?;
+                Ok::<_, HotStuffError>(maybe_proof.map(|proof| (block, substate, proof)))
+            })
+            .map_err(internal_error(answer_id))?;
+
+        let (block, substate, proof) =
+            maybe_proof.ok_or_else(|| not_found(answer_id, format!("Substate {id} is not in the committed state")))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetSubstateWithProofResponse {
+            created_by_tx: substate.created_by_transaction,
+            value: substate.into_substate_value(),
+            block_header: block.header().clone(),
+            proof,
+        }))
+    }
+
     pub async fn get_substates_created_by_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let data: GetSubstatesByTransactionRequest = value.parse_params()?;
@@ -474,14 +639,7 @@ impl JsonRpcHandlers {
             .map_err(internal_error(answer_id))?;
 
         Ok(JsonRpcResponse::success(answer_id, GetTemplatesResponse {
-            templates: templates
-                .into_iter()
-                .map(|t| TemplateMetadata {
-                    name: t.name,
-                    address: t.address,
-                    binary_sha: t.binary_sha.to_vec(),
-                })
-                .collect(),
+            templates: templates.into_iter().map(to_template_metadata).collect(),
         }))
     }
 
@@ -502,11 +660,7 @@ impl JsonRpcHandlers {
             .map_err(internal_error(answer_id))?;
 
         Ok(JsonRpcResponse::success(answer_id, GetTemplateResponse {
-            registration_metadata: TemplateMetadata {
-                name: template.metadata.name,
-                address: template.metadata.address,
-                binary_sha: template.metadata.binary_sha.to_vec(),
-            },
+            registration_metadata: to_template_metadata(template.metadata),
             abi,
         }))
     }
@@ -557,6 +711,47 @@ impl JsonRpcHandlers {
         Ok(JsonRpcResponse::success(answer_id, GetMempoolStatsResponse { size }))
     }
 
+    pub async fn get_mempool_transactions(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let mut transactions = self
+            .mempool
+            .get_transactions()
+            .await
+            .map_err(internal_error(answer_id))?;
+        transactions.sort_by_key(|t| t.received_at);
+        let transactions = transactions
+            .into_iter()
+            .map(|t| MempoolTransactionInfo {
+                transaction_id: t.transaction_id,
+                input_shards: t.input_shards.iter().copied().collect(),
+                age: t.age(),
+                declared_max_fee: t.declared_max_fee.and_then(|fee| fee.as_u64_checked()),
+                fee_per_instruction: t.fee_per_instruction,
+                deferred_until: t.deferred_until,
+            })
+            .collect();
+        Ok(JsonRpcResponse::success(answer_id, GetMempoolTransactionsResponse {
+            transactions,
+        }))
+    }
+
+    pub async fn evict_mempool_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: EvictMempoolTransactionRequest = value.parse_params()?;
+        self.mempool
+            .evict_transaction(request.transaction_id)
+            .await
+            .map_err(|err| match err {
+                MempoolError::TransactionNotFound { .. } => not_found(answer_id, err.to_string()),
+                MempoolError::TransactionAlreadyProposed { .. } => JsonRpcResponse::error(
+                    answer_id,
+                    JsonRpcError::new(JsonRpcErrorReason::InvalidParams, err.to_string(), json::Value::Null),
+                ),
+                err => internal_error(answer_id)(err),
+            })?;
+        Ok(JsonRpcResponse::success(answer_id, EvictMempoolTransactionResponse {}))
+    }
+
     pub async fn get_epoch_manager_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         self.epoch_manager
@@ -630,10 +825,33 @@ impl JsonRpcHandlers {
             is_valid: committee_info.is_some(),
             start_epoch: local_vn_start_epoch,
             committee_info,
+            auto_registration: self.auto_registration.status(),
         };
         Ok(JsonRpcResponse::success(answer_id, response))
     }
 
+    pub async fn get_epoch_transition_rehearsal(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        self.epoch_manager
+            .wait_for_initial_scanning_to_complete()
+            .await
+            .map_err(internal_error(answer_id))?;
+        let rehearsal = rehearse_epoch_transition(&self.epoch_manager, &self.state_store)
+            .await
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(
+            answer_id,
+            GetEpochTransitionRehearsalResponse { rehearsal },
+        ))
+    }
+
+    pub async fn get_substate_pruning_status(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        Ok(JsonRpcResponse::success(answer_id, GetSubstatePruningStatusResponse {
+            status: self.substate_pruning.status(),
+        }))
+    }
+
     pub async fn add_peer(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let AddPeerRequest {
@@ -688,6 +906,39 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_bandwidth_stats(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let report = self
+            .networking
+            .get_bandwidth_report()
+            .await
+            .map_err(internal_error(answer_id))?;
+
+        fn convert(stats: tari_networking::BandwidthStats) -> BandwidthStats {
+            BandwidthStats {
+                bytes_sent: stats.bytes_sent,
+                bytes_received: stats.bytes_received,
+                messages_sent: stats.messages_sent,
+                messages_received: stats.messages_received,
+                messages_throttled: stats.messages_throttled,
+            }
+        }
+
+        Ok(JsonRpcResponse::success(answer_id, GetBandwidthStatsResponse {
+            total: convert(report.total),
+            per_peer: report
+                .per_peer
+                .into_iter()
+                .map(|(peer_id, stats)| (peer_id.to_string(), convert(stats)))
+                .collect(),
+            per_message_type: report
+                .per_message_type
+                .into_iter()
+                .map(|(message_type, stats)| (message_type, convert(stats)))
+                .collect(),
+        }))
+    }
+
     pub async fn get_shard_key(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request = value.parse_params::<GetShardKeyRequest>()?;
@@ -706,22 +957,95 @@ impl JsonRpcHandlers {
     pub async fn get_committee(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request = value.parse_params::<GetCommitteeRequest>()?;
-        if let Ok(committee) = self
+        let shard_group = match (request.substate_address, request.shard_group) {
+            (Some(substate_address), None) => self
+                .epoch_manager
+                .get_committee_info_for_substate(request.epoch, substate_address)
+                .await
+                .map_err(internal_error(answer_id))?
+                .shard_group(),
+            (None, Some(shard_group)) => shard_group,
+            _ => {
+                return Err(JsonRpcResponse::error(
+                    answer_id,
+                    JsonRpcError::new(
+                        JsonRpcErrorReason::InvalidParams,
+                        "Exactly one of substate_address or shard_group must be provided".to_string(),
+                        json::Value::Null,
+                    ),
+                ));
+            },
+        };
+        let committee = self
             .epoch_manager
-            .get_committee_for_substate(request.epoch, request.substate_address)
+            .get_committee_by_shard_group(request.epoch, shard_group, None)
             .await
-        {
-            Ok(JsonRpcResponse::success(answer_id, GetCommitteeResponse { committee }))
-        } else {
-            Err(JsonRpcResponse::error(
-                answer_id,
-                JsonRpcError::new(
-                    JsonRpcErrorReason::InvalidParams,
-                    "Something went wrong".to_string(),
-                    json::Value::Null,
-                ),
-            ))
+            .map_err(internal_error(answer_id))?;
+
+        Ok(JsonRpcResponse::success(answer_id, GetCommitteeResponse {
+            shard_group,
+            committee,
+        }))
+    }
+
+    pub async fn get_network_committees(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request = value.parse_params::<GetNetworkCommitteesRequest>()?;
+        let current_epoch = self
+            .epoch_manager
+            .current_epoch()
+            .await
+            .map_err(internal_error(answer_id))?;
+        let epoch = request.epoch.unwrap_or(current_epoch);
+
+        let committees = self
+            .epoch_manager
+            .get_committees(epoch)
+            .await
+            .map_err(internal_error(answer_id))?;
+        if committees.is_empty() {
+            return Ok(JsonRpcResponse::success(answer_id, GetNetworkCommitteeResponse {
+                current_epoch,
+                epoch,
+                committees: vec![],
+            }));
         }
+
+        let num_preshards = self
+            .epoch_manager
+            .get_committee_info_for_substate(epoch, SubstateAddress::zero())
+            .await
+            .map_err(internal_error(answer_id))?
+            .num_preshards();
+        let mut validators = self
+            .epoch_manager
+            .get_all_validator_nodes(epoch)
+            .await
+            .map_err(internal_error(answer_id))?
+            .into_iter()
+            .map(|vn| (vn.address, vn))
+            .collect::<HashMap<_, _>>();
+
+        let mut committees = committees
+            .into_iter()
+            .map(|(shard_group, committee)| CommitteeShardInfo {
+                shard: shard_group.start(),
+                shard_group,
+                substate_address_range: shard_group.to_substate_address_range(num_preshards),
+                validators: committee
+                    .addresses()
+                    .filter_map(|addr| validators.remove(addr))
+                    .map(Into::into)
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        committees.sort_by_key(|c| c.shard_group);
+
+        Ok(JsonRpcResponse::success(answer_id, GetNetworkCommitteeResponse {
+            current_epoch,
+            epoch,
+            committees,
+        }))
     }
 
     pub async fn get_all_vns(&self, value: JsonRpcExtractor) -> JrpcResult {
@@ -794,6 +1118,85 @@ impl JsonRpcHandlers {
         }))
     }
 
+    pub async fn get_misbehaviour_evidence(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetMisbehaviourEvidenceRequest = value.parse_params()?;
+        let evidence = self
+            .state_store
+            .with_read_tx(|tx| {
+                MisbehaviourEvidence::get_all(
+                    tx,
+                    request.epoch,
+                    request.offender.as_ref(),
+                    request.limit.unwrap_or(DEFAULT_MISBEHAVIOUR_EVIDENCE_LIMIT),
+                    request.offset.unwrap_or(0),
+                )
+            })
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(answer_id, GetMisbehaviourEvidenceResponse {
+            evidence,
+        }))
+    }
+
+    pub async fn get_foreign_receive_gaps(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetForeignReceiveGapsRequest = value.parse_params()?;
+        let (gaps, counters) = self
+            .state_store
+            .with_read_tx(|tx| {
+                let gaps = ForeignReceiveGap::get_all(
+                    tx,
+                    request.include_resolved,
+                    request.limit.unwrap_or(DEFAULT_FOREIGN_RECEIVE_GAPS_LIMIT),
+                    request.offset.unwrap_or(0),
+                )?;
+                let counters = ForeignReceiveCounters::get_or_default(tx)?;
+                Ok::<_, StorageError>((gaps, counters))
+            })
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(answer_id, GetForeignReceiveGapsResponse {
+            gaps,
+            last_received: counters.last_received,
+        }))
+    }
+
+    pub async fn trace_transaction(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: TraceTransactionRequest = value.parse_params()?;
+        let epoch = self.consensus.current_epoch();
+        let committee_info = self
+            .epoch_manager
+            .get_local_committee_info(epoch)
+            .await
+            .map_err(internal_error(answer_id))?;
+        let trace = self
+            .state_store
+            .with_read_tx(|tx| TransactionTrace::get(tx, &request.transaction_id, epoch, committee_info.shard_group()))
+            .optional()
+            .map_err(internal_error(answer_id))?
+            .ok_or_else(|| not_found(answer_id, format!("Transaction {} not found", request.transaction_id)))?;
+        Ok(JsonRpcResponse::success(answer_id, TraceTransactionResponse { trace }))
+    }
+
+    pub async fn get_events(&self, value: JsonRpcExtractor) -> JrpcResult {
+        let answer_id = value.get_answer_id();
+        let request: GetEventsRequest = value.parse_params()?;
+        let events = self
+            .state_store
+            .with_read_tx(|tx| {
+                TransactionEvent::get_all(
+                    tx,
+                    request.transaction_id.as_ref(),
+                    request.topic.as_deref(),
+                    request.substate_id.as_ref(),
+                    request.limit.unwrap_or(DEFAULT_EVENTS_LIMIT),
+                    request.offset.unwrap_or(0),
+                )
+            })
+            .map_err(internal_error(answer_id))?;
+        Ok(JsonRpcResponse::success(answer_id, GetEventsResponse { events }))
+    }
+
     pub async fn get_validator_fees(&self, value: JsonRpcExtractor) -> JrpcResult {
         let answer_id = value.get_answer_id();
         let request = value.parse_params::<GetValidatorFeesRequest>()?;
@@ -816,3 +1219,14 @@ impl JsonRpcHandlers {
         }))
     }
 }
+
+fn to_template_metadata(metadata: interface::TemplateMetadata) -> TemplateMetadata {
+    TemplateMetadata {
+        name: metadata.name,
+        address: metadata.address,
+        binary_sha: metadata.binary_sha.to_vec(),
+        author_public_key: metadata.author_public_key,
+        template_type: metadata.template_type.as_str().to_string(),
+        status: metadata.status.as_str().to_string(),
+    }
+}
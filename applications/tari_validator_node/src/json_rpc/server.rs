@@ -22,9 +22,16 @@
 
 use std::{net::SocketAddr, sync::Arc};
 
-use axum::{extract::Extension, routing::post, Router};
+use axum::{
+    extract::Extension,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
 use axum_jrpc::{error::JsonRpcErrorReason, JrpcResult, JsonRpcAnswer, JsonRpcExtractor};
 use log::*;
+use tari_engine_types::rpc_encoding::RpcEncoding;
 use tower_http::cors::CorsLayer;
 
 use super::handlers::JsonRpcHandlers;
@@ -40,7 +47,13 @@ pub fn spawn_json_rpc(
         .route("/", post(handler))
         .route("/json_rpc", post(handler));
     #[cfg(feature = "metrics")]
-    let router = router.route("/_metrics", axum::routing::get(metrics::MetricsHandler(registry)));
+    let router = {
+        let metrics_handler = metrics::MetricsHandler(registry);
+        // "/_metrics" is kept for existing scrape configs
+        router
+            .route("/metrics", axum::routing::get(metrics_handler.clone()))
+            .route("/_metrics", axum::routing::get(metrics_handler))
+    };
     let router = router
         .layer(Extension(Arc::new(handlers)))
         .layer(CorsLayer::permissive());
@@ -61,7 +74,17 @@ pub fn spawn_json_rpc(
     Ok(addr)
 }
 
-async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: JsonRpcExtractor) -> JrpcResult {
+async fn handler(
+    Extension(handlers): Extension<Arc<JsonRpcHandlers>>,
+    headers: HeaderMap,
+    value: JsonRpcExtractor,
+) -> Response {
+    let encoding = RpcEncoding::from_accept_header(headers.get(header::ACCEPT).and_then(|v| v.to_str().ok()));
+    let result = handle_request(&handlers, value).await;
+    encode_response(encoding, result)
+}
+
+async fn handle_request(handlers: &JsonRpcHandlers, value: JsonRpcExtractor) -> JrpcResult {
     debug!(target: LOG_TARGET, "🌐 JSON-RPC request: {}", value.method);
     let result = match value.method.as_str() {
         // Transaction
@@ -72,10 +95,13 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         "get_transaction_result" => handlers.get_transaction_result(value).await,
         "get_state" => handlers.get_state(value).await,
         "get_substate" => handlers.get_substate(value).await,
+        "get_substate_with_proof" => handlers.get_substate_with_proof(value).await,
+        "get_vault_balance" => handlers.get_vault_balance(value).await,
         "get_substates_created_by_transaction" => handlers.get_substates_created_by_transaction(value).await,
         "get_substates_destroyed_by_transaction" => handlers.get_substates_destroyed_by_transaction(value).await,
         "list_blocks" => handlers.list_blocks(value).await,
         "get_tx_pool" => handlers.get_tx_pool(value).await,
+        "get_tx_pool_by_priority" => handlers.get_tx_pool_by_priority(value).await,
         // Blocks
         "get_block" => handlers.get_block(value).await,
         "get_blocks_count" => handlers.get_blocks_count(value).await,
@@ -87,17 +113,26 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
         // Validator Node
         "get_identity" => handlers.get_identity(value).await,
         "get_mempool_stats" => handlers.get_mempool_stats(value).await,
+        "get_mempool_transactions" => handlers.get_mempool_transactions(value).await,
+        "evict_mempool_transaction" => handlers.evict_mempool_transaction(value).await,
         "get_epoch_manager_stats" => handlers.get_epoch_manager_stats(value).await,
+        "get_epoch_transition_rehearsal" => handlers.get_epoch_transition_rehearsal(value).await,
+        "get_substate_pruning_status" => handlers.get_substate_pruning_status(value).await,
         "get_shard_key" => handlers.get_shard_key(value).await,
         "get_committee" => handlers.get_committee(value).await,
         "get_all_vns" => handlers.get_all_vns(value).await,
         "get_base_layer_validator_changes" => handlers.get_base_layer_validator_changes(value).await,
         "get_consensus_status" => handlers.get_consensus_status(value).await,
-        // "get_network_committees" => handlers.get_network_committees(value).await,
+        "get_misbehaviour_evidence" => handlers.get_misbehaviour_evidence(value).await,
+        "get_events" => handlers.get_events(value).await,
+        "get_foreign_receive_gaps" => handlers.get_foreign_receive_gaps(value).await,
+        "trace_transaction" => handlers.trace_transaction(value).await,
+        "get_network_committees" => handlers.get_network_committees(value).await,
         "get_fees" => handlers.get_validator_fees(value).await,
         // Comms
         "add_peer" => handlers.add_peer(value).await,
         "get_comms_stats" => handlers.get_comms_stats(value).await,
+        "get_bandwidth_stats" => handlers.get_bandwidth_stats(value).await,
         "get_connections" => handlers.get_connections(value).await,
         method => Ok(value.method_not_found(method)),
     };
@@ -123,6 +158,22 @@ async fn handler(Extension(handlers): Extension<Arc<JsonRpcHandlers>>, value: Js
     result
 }
 
+/// Encodes the response in the encoding that the client accepts. JSON responses are unchanged.
+fn encode_response(encoding: RpcEncoding, result: JrpcResult) -> Response {
+    let response = result.unwrap_or_else(|err| err);
+    if encoding == RpcEncoding::Json {
+        return response.into_response();
+    }
+
+    match encoding.encode(&response) {
+        Ok(body) => ([(header::CONTENT_TYPE, encoding.content_type())], body).into_response(),
+        Err(e) => {
+            error!(target: LOG_TARGET, "🚨 Failed to encode JSON-RPC response: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to encode response: {e}")).into_response()
+        },
+    }
+}
+
 #[cfg(feature = "metrics")]
 mod metrics {
     use std::future;
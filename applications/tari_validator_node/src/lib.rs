@@ -20,18 +20,24 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod auto_registration;
 mod bootstrap;
 pub mod cli;
 mod config;
 mod consensus;
 mod dan_node;
 mod dry_run_transaction_processor;
+mod epoch_rehearsal;
 mod event_subscription;
 mod http_ui;
 mod json_rpc;
 #[cfg(feature = "metrics")]
 mod metrics;
+mod network_params;
 mod p2p;
+mod replay;
+mod state_store_maintenance;
+mod substate_pruning;
 mod substate_resolver;
 mod virtual_substate;
 
@@ -55,6 +61,7 @@ use tari_dan_common_types::SubstateAddress;
 use tari_dan_storage::global::DbFactory;
 use tari_dan_storage_sqlite::SqliteDbFactory;
 use tari_shutdown::ShutdownSignal;
+pub use replay::{run_replay, ReplayReport};
 use tokio::task;
 pub use validator_registration_file::ValidatorRegistrationFile;
 
@@ -64,6 +71,7 @@ use crate::{
     dan_node::DanNode,
     http_ui::server::run_http_ui_server,
     json_rpc::{spawn_json_rpc, JsonRpcHandlers},
+    network_params::NetworkParams,
 };
 
 const LOG_TARGET: &str = "tari::validator_node::app";
@@ -119,8 +127,10 @@ pub async fn run_validator_node(
     #[cfg(feature = "metrics")]
     let metrics_registry = create_metrics_registry(keypair.public_key());
 
+    let network_params =
+        NetworkParams::load(config.network).map_err(|e| ExitError::new(ExitCode::ConfigError, e))?;
     let consensus_constants = ConsensusConstants::from(config.network);
-    let mut base_node_client = create_base_layer_client(config).await?;
+    let mut base_node_client = create_base_layer_client(config, &network_params).await?;
     verify_correct_network(&mut base_node_client, config.network).await?;
     let services = spawn_services(
         config,
@@ -128,6 +138,7 @@ pub async fn run_validator_node(
         keypair.clone(),
         global_db,
         consensus_constants,
+        network_params,
         base_node_client.clone(),
         #[cfg(feature = "metrics")]
         &metrics_registry,
@@ -171,9 +182,15 @@ pub async fn run_validator_node(
     Ok(())
 }
 
-async fn create_base_layer_client(config: &ApplicationConfig) -> Result<GrpcBaseNodeClient, ExitError> {
+async fn create_base_layer_client(
+    config: &ApplicationConfig,
+    network_params: &NetworkParams,
+) -> Result<GrpcBaseNodeClient, ExitError> {
     let base_node_address = config.validator_node.base_node_grpc_url.clone().unwrap_or_else(|| {
-        let port = grpc_default_port(ApplicationType::BaseNode, config.network);
+        let port = network_params
+            .ports
+            .base_node_grpc
+            .unwrap_or_else(|| grpc_default_port(ApplicationType::BaseNode, config.network));
         format!("http://127.0.0.1:{port}")
             .parse()
             .expect("Default base node GRPC URL is malformed")
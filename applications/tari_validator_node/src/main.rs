@@ -30,9 +30,14 @@ use tari_common::{
     exit_codes::{ExitCode, ExitError},
     initialize_logging,
 };
-use tari_dan_app_utilities::configuration::load_configuration;
+use tari_dan_app_utilities::{configuration::load_configuration, logging::initialize_json_logging};
 use tari_shutdown::Shutdown;
-use tari_validator_node::{cli::Cli, run_validator_node, ApplicationConfig};
+use tari_validator_node::{
+    cli::{Cli, Command},
+    run_replay,
+    run_validator_node,
+    ApplicationConfig,
+};
 
 const LOG_TARGET: &str = "tari::validator_node::app";
 
@@ -73,7 +78,11 @@ async fn main_inner() -> Result<(), ExitError> {
     // Remove the pid file if it exists
     let _file = fs::remove_file(config.common.base_path.join("pid"));
     let mut shutdown = Shutdown::new();
-    if let Err(e) = initialize_logging(
+    if config.validator_node.log_format.is_json() {
+        if let Err(e) = initialize_json_logging("info") {
+            eprintln!("{}", e);
+        }
+    } else if let Err(e) = initialize_logging(
         &cli.common.log_config_path("validator"),
         &cli.common.get_base_path(),
         include_str!("../log4rs_sample.yml"),
@@ -81,6 +90,18 @@ async fn main_inner() -> Result<(), ExitError> {
         eprintln!("{}", e);
     }
 
+    if let Some(Command::Replay(args)) = cli.command.as_ref() {
+        let report = run_replay(&config, args).map_err(|e| ExitError::new(ExitCode::UnknownError, e))?;
+        println!("{}", report);
+        if report.divergence.is_some() {
+            return Err(ExitError::new(
+                ExitCode::UnknownError,
+                "Replay found a divergence from the committed state",
+            ));
+        }
+        return Ok(());
+    }
+
     match run_validator_node(&config, shutdown.to_signal()).await {
         Ok(_) => info!(target: LOG_TARGET, "Validator node shutdown successfully"),
         Err(e) => match e.downcast() {
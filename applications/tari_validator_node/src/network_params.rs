@@ -0,0 +1,80 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Registry of network parameters. A definition file for each supported network is embedded in the binary (see the
+//! `networks` directory) so that a single validator node binary can run on any of these networks. The network is
+//! selected at runtime with the `--network` flag.
+
+use serde::Deserialize;
+use tari_common::configuration::Network;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkParams {
+    pub network: Network,
+    /// The libp2p protocol name. Nodes only connect to peers that use the same protocol name.
+    pub p2p_protocol_version: String,
+    pub genesis: GenesisParams,
+    #[serde(default)]
+    pub ports: NetworkPorts,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisParams {
+    /// Create the XTR faucet component and vault when bootstrapping the genesis state
+    pub create_faucet: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkPorts {
+    /// The base node GRPC port. If not set, the Minotari default port for the network is used.
+    pub base_node_grpc: Option<u16>,
+}
+
+impl NetworkParams {
+    /// Loads the embedded parameters for the given network
+    pub fn load(network: Network) -> Result<Self, NetworkParamsError> {
+        let definition = definition(network).ok_or(NetworkParamsError::UnsupportedNetwork(network))?;
+        let params = config::Config::builder()
+            .add_source(config::File::from_str(definition, config::FileFormat::Toml))
+            .build()
+            .and_then(|cfg| cfg.try_deserialize::<Self>())
+            .map_err(|e| NetworkParamsError::InvalidDefinition {
+                network,
+                details: e.to_string(),
+            })?;
+
+        if params.network != network {
+            return Err(NetworkParamsError::NetworkMismatch {
+                expected: network,
+                actual: params.network,
+            });
+        }
+
+        Ok(params)
+    }
+}
+
+fn definition(network: Network) -> Option<&'static str> {
+    match network {
+        Network::MainNet => None,
+        Network::StageNet => Some(include_str!("../networks/stagenet.toml")),
+        Network::NextNet => Some(include_str!("../networks/nextnet.toml")),
+        Network::LocalNet => Some(include_str!("../networks/localnet.toml")),
+        Network::Igor => Some(include_str!("../networks/igor.toml")),
+        Network::Esmeralda => Some(include_str!("../networks/esmeralda.toml")),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum NetworkParamsError {
+    #[error("The validator node does not support network {0}")]
+    UnsupportedNetwork(Network),
+    #[error("Invalid network definition for {network}: {details}")]
+    InvalidDefinition { network: Network, details: String },
+    #[error("Network definition for {expected} is for network {actual}")]
+    NetworkMismatch { expected: Network, actual: Network },
+}
+
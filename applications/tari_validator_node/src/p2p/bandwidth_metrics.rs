@@ -0,0 +1,107 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use log::*;
+use prometheus::{IntCounterVec, Opts, Registry};
+use tari_dan_p2p::TariMessagingSpec;
+use tari_networking::{BandwidthStats, NetworkingHandle};
+use tari_shutdown::ShutdownSignal;
+use tokio::{task, task::JoinHandle, time};
+
+use crate::metrics::{CollectorRegister, LabelledCollector};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::p2p::bandwidth_metrics";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically exports the networking bandwidth report by message type. Per-peer stats are not exported to keep the
+/// number of series bounded. They are available from the get_bandwidth_stats JSON-RPC method.
+pub fn spawn(
+    networking: NetworkingHandle<TariMessagingSpec>,
+    shutdown: ShutdownSignal,
+    metrics_registry: &prometheus::Registry,
+) -> JoinHandle<anyhow::Result<()>> {
+    let metrics = PrometheusBandwidthMetrics::new(metrics_registry);
+    task::spawn(run(networking, metrics, shutdown))
+}
+
+async fn run(
+    networking: NetworkingHandle<TariMessagingSpec>,
+    metrics: PrometheusBandwidthMetrics,
+    mut shutdown: ShutdownSignal,
+) -> anyhow::Result<()> {
+    let mut interval = time::interval(POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match networking.get_bandwidth_report().await {
+                    Ok(report) => {
+                        for (message_type, stats) in &report.per_message_type {
+                            metrics.update(message_type, stats);
+                        }
+                    },
+                    Err(err) => {
+                        warn!(target: LOG_TARGET, "Failed to get bandwidth report: {}", err);
+                    },
+                }
+            },
+            _ = shutdown.wait() => break,
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct PrometheusBandwidthMetrics {
+    bytes: IntCounterVec,
+    messages: IntCounterVec,
+    messages_throttled: IntCounterVec,
+}
+
+impl PrometheusBandwidthMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            bytes: IntCounterVec::new(
+                Opts::new("networking_bandwidth_bytes", "Bytes sent and received by message type"),
+                &["message_type", "direction"],
+            )
+            .unwrap()
+            .register_at(registry),
+            messages: IntCounterVec::new(
+                Opts::new("networking_messages", "Messages sent and received by message type"),
+                &["message_type", "direction"],
+            )
+            .unwrap()
+            .register_at(registry),
+            messages_throttled: IntCounterVec::new(
+                Opts::new(
+                    "networking_messages_throttled",
+                    "Messages dropped due to per-peer bandwidth limits by message type",
+                ),
+                &["message_type"],
+            )
+            .unwrap()
+            .register_at(registry),
+        }
+    }
+
+    fn update(&self, message_type: &str, stats: &BandwidthStats) {
+        // The report contains totals, so counters are advanced by the difference since the last update
+        fn advance_to(counter: &prometheus::IntCounter, total: u64) {
+            counter.inc_by(total.saturating_sub(counter.get()));
+        }
+        advance_to(&self.bytes.with_two_labels(message_type, "in"), stats.bytes_received);
+        advance_to(&self.bytes.with_two_labels(message_type, "out"), stats.bytes_sent);
+        advance_to(
+            &self.messages.with_two_labels(message_type, "in"),
+            stats.messages_received,
+        );
+        advance_to(&self.messages.with_two_labels(message_type, "out"), stats.messages_sent);
+        advance_to(
+            &self.messages_throttled.with_label(message_type),
+            stats.messages_throttled,
+        );
+    }
+}
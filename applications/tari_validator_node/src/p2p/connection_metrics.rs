@@ -0,0 +1,89 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use log::*;
+use prometheus::{IntCounter, IntCounterVec, Opts, Registry};
+use tari_dan_p2p::TariMessagingSpec;
+use tari_networking::{InboundConnectionStats, NetworkingHandle};
+use tari_shutdown::ShutdownSignal;
+use tokio::{task, task::JoinHandle, time};
+
+use crate::metrics::{CollectorRegister, LabelledCollector};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::p2p::connection_metrics";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Periodically exports the outcomes of inbound connection attempts and the number of banned addresses
+pub fn spawn(
+    networking: NetworkingHandle<TariMessagingSpec>,
+    shutdown: ShutdownSignal,
+    metrics_registry: &prometheus::Registry,
+) -> JoinHandle<anyhow::Result<()>> {
+    let metrics = PrometheusConnectionMetrics::new(metrics_registry);
+    task::spawn(run(networking, metrics, shutdown))
+}
+
+async fn run(
+    networking: NetworkingHandle<TariMessagingSpec>,
+    metrics: PrometheusConnectionMetrics,
+    mut shutdown: ShutdownSignal,
+) -> anyhow::Result<()> {
+    let mut interval = time::interval(POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match networking.get_inbound_connection_stats().await {
+                    Ok(stats) => metrics.update(&stats),
+                    Err(err) => {
+                        warn!(target: LOG_TARGET, "Failed to get inbound connection stats: {}", err);
+                    },
+                }
+            },
+            _ = shutdown.wait() => break,
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+struct PrometheusConnectionMetrics {
+    inbound_connections: IntCounterVec,
+    bans: IntCounter,
+}
+
+impl PrometheusConnectionMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            inbound_connections: IntCounterVec::new(
+                Opts::new("networking_inbound_connections", "Inbound connection attempts by outcome"),
+                &["outcome"],
+            )
+            .unwrap()
+            .register_at(registry),
+            bans: IntCounter::new(
+                "networking_inbound_address_bans",
+                "Addresses banned for repeated connection rate limit or handshake violations",
+            )
+            .unwrap()
+            .register_at(registry),
+        }
+    }
+
+    fn update(&self, stats: &InboundConnectionStats) {
+        // The stats are totals, so counters are advanced by the difference since the last update
+        fn advance_to(counter: &IntCounter, total: u64) {
+            counter.inc_by(total.saturating_sub(counter.get()));
+        }
+        advance_to(&self.inbound_connections.with_label("accepted"), stats.accepted);
+        advance_to(&self.inbound_connections.with_label("rate_limited"), stats.rate_limited);
+        advance_to(&self.inbound_connections.with_label("banned"), stats.denied_banned);
+        advance_to(
+            &self.inbound_connections.with_label("handshake_failed"),
+            stats.handshake_failures,
+        );
+        advance_to(&self.bans, stats.bans);
+    }
+}
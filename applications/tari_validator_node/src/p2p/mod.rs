@@ -20,6 +20,11 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+#[cfg(feature = "metrics")]
+pub mod bandwidth_metrics;
+#[cfg(feature = "metrics")]
+pub mod connection_metrics;
+
 mod rpc;
 pub use rpc::create_tari_validator_node_rpc_service;
 
@@ -32,6 +32,10 @@ use tari_dan_p2p::{
         GetCheckpointResponse,
         GetHighQcRequest,
         GetHighQcResponse,
+        GetPendingTransactionsRequest,
+        GetPendingTransactionsResponse,
+        GetStateSnapshotChunkRequest,
+        GetStateSnapshotChunkResponse,
         GetSubstateRequest,
         GetSubstateResponse,
         GetTransactionResultRequest,
@@ -45,8 +49,18 @@ use tari_dan_p2p::{
     },
 };
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, EpochCheckpoint, HighQc, StateTransitionId, SubstateRecord, TransactionRecord},
+    consensus_models::{
+        Block,
+        BlockId,
+        EpochCheckpoint,
+        HighQc,
+        StateSnapshot,
+        StateTransitionId,
+        SubstateRecord,
+        TransactionRecord,
+    },
     StateStore,
+    StateStoreReadTransaction,
 };
 use tari_engine_types::virtual_substate::VirtualSubstateId;
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
@@ -67,6 +81,11 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::p2p::rpc";
 
+/// The maximum number of pending transactions returned in a single response
+const MAX_PENDING_TRANSACTIONS: usize = 1000;
+/// The number of substates in each state snapshot chunk
+const STATE_SNAPSHOT_CHUNK_SIZE: u64 = 1000;
+
 pub struct ValidatorNodeRpcServiceImpl {
     epoch_manager: EpochManagerHandle<PeerAddress>,
     shard_state_store: SqliteStateStore<PeerAddress>,
@@ -393,4 +412,84 @@ impl ValidatorNodeRpcService for ValidatorNodeRpcServiceImpl {
 
         Ok(Streaming::new(receiver))
     }
+
+    async fn get_pending_transactions(
+        &self,
+        request: Request<GetPendingTransactionsRequest>,
+    ) -> Result<Response<GetPendingTransactionsResponse>, RpcStatus> {
+        let req = request.into_message();
+        let limit = match req.limit as usize {
+            0 => MAX_PENDING_TRANSACTIONS,
+            limit => limit.min(MAX_PENDING_TRANSACTIONS),
+        };
+
+        let epoch = self.consensus.current_epoch();
+        let transactions = self
+            .shard_state_store
+            .with_read_tx(|tx| tx.transaction_pool_get_all())
+            .map_err(RpcStatus::log_internal_error(LOG_TARGET))?;
+
+        Ok(Response::new(GetPendingTransactionsResponse {
+            epoch: epoch.as_u64(),
+            transactions: transactions
+                .iter()
+                .take(limit)
+                .map(|rec| proto::rpc::PendingTransaction {
+                    transaction_id: rec.transaction_id().as_bytes().to_vec(),
+                    stage: rec.current_stage().to_string(),
+                    is_ready: rec.is_ready(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn get_state_snapshot_chunk(
+        &self,
+        request: Request<GetStateSnapshotChunkRequest>,
+    ) -> Result<Response<GetStateSnapshotChunkResponse>, RpcStatus> {
+        let req = request.into_message();
+        let current_epoch = self.consensus.current_epoch();
+        if req.current_epoch != current_epoch {
+            // This may occur if one of the nodes has not fully scanned the base layer
+            return Err(RpcStatus::bad_request(format!(
+                "Peer requested state snapshot with epoch {} but current epoch is {}",
+                req.current_epoch, current_epoch
+            )));
+        }
+
+        let shard = Shard::from(req.shard);
+        let snapshot = self
+            .shard_state_store
+            .with_read_tx(|tx| {
+                if req.epoch == 0 {
+                    StateSnapshot::get_latest(tx, shard, current_epoch.saturating_sub(Epoch(1)))
+                } else {
+                    StateSnapshot::get(tx, Epoch(req.epoch), shard)
+                }
+            })
+            .optional()
+            .map_err(RpcStatus::log_internal_error(LOG_TARGET))?
+            .ok_or_else(|| RpcStatus::not_found(format!("No state snapshot found for {shard}")))?;
+
+        let num_chunks = snapshot.num_substates.div_ceil(STATE_SNAPSHOT_CHUNK_SIZE);
+        if req.chunk_index > 0 && req.chunk_index >= num_chunks {
+            return Err(RpcStatus::bad_request(format!(
+                "Chunk index {} is out of range. The snapshot has {} chunk(s)",
+                req.chunk_index, num_chunks
+            )));
+        }
+
+        let substates = self
+            .shard_state_store
+            .with_read_tx(|tx| {
+                snapshot.get_substates(tx, req.chunk_index * STATE_SNAPSHOT_CHUNK_SIZE, STATE_SNAPSHOT_CHUNK_SIZE)
+            })
+            .map_err(RpcStatus::log_internal_error(LOG_TARGET))?;
+
+        Ok(Response::new(GetStateSnapshotChunkResponse {
+            snapshot: Some(snapshot.into()),
+            num_chunks,
+            substates: substates.into_iter().map(Into::into).collect(),
+        }))
+    }
 }
@@ -46,6 +46,15 @@ impl<TStateStore: StateStore> StateSyncTask<TStateStore> {
         let mut counter = 0usize;
         loop {
             match self.fetch_next_batch(&mut buffer, current_state_transition_id) {
+                Ok(Some(_)) if counter == 0 && !self.is_contiguous(&buffer) => {
+                    // The transitions that the peer requires have been pruned from this node
+                    self.send(Err(RpcStatus::not_found(format!(
+                        "State transitions following {} have been pruned. Sync from a state snapshot instead.",
+                        self.start_state_transition_id
+                    ))))
+                    .await?;
+                    return Err(());
+                },
                 Ok(Some(last_state_transition_id)) => {
                     info!(target: LOG_TARGET, "🌍Fetched {} state transitions up to transition {}", buffer.len(), last_state_transition_id);
                     current_state_transition_id = last_state_transition_id;
@@ -108,6 +117,13 @@ impl<TStateStore: StateStore> StateSyncTask<TStateStore> {
         })
     }
 
+    /// Returns true if the first transition in the buffer directly follows the requested start transition
+    fn is_contiguous(&self, buffer: &UpdateBuffer) -> bool {
+        buffer
+            .first()
+            .map_or(true, |first| first.id.seq() == self.start_state_transition_id.seq() + 1)
+    }
+
     async fn send(&mut self, result: Result<SyncStateResponse, RpcStatus>) -> Result<(), ()> {
         if self.sender.send(result).await.is_err() {
             debug!(
@@ -0,0 +1,28 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MempoolConfig {
+    /// Transactions that declare a maximum fee below this amount are rejected. If 0, no minimum is enforced.
+    pub min_fee: u64,
+    /// The maximum number of epochs into the future that a transaction's min_epoch may be. Transactions with a
+    /// min_epoch further in the future are rejected.
+    #[serde(default = "default_max_deferred_epochs")]
+    pub max_deferred_epochs: u64,
+}
+
+fn default_max_deferred_epochs() -> u64 {
+    10
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        Self {
+            min_fee: 0,
+            max_deferred_epochs: default_max_deferred_epochs(),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::BTreeMap, mem};
+
+use tari_dan_common_types::Epoch;
+use tari_transaction::{Transaction, TransactionId};
+
+use super::MempoolError;
+
+/// Transactions that are not valid until a future epoch, keyed by the epoch in which they become valid
+#[derive(Debug)]
+pub(super) struct DeferredTransactions {
+    max_deferred_epochs: u64,
+    transactions: BTreeMap<Epoch, Vec<Transaction>>,
+}
+
+impl DeferredTransactions {
+    pub fn new(max_deferred_epochs: u64) -> Self {
+        Self {
+            max_deferred_epochs,
+            transactions: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the epoch that the transaction must be deferred until, or None if the transaction is valid in the
+    /// current epoch. Returns an error if the transaction's min_epoch is too far in the future.
+    pub fn deferred_until(
+        &self,
+        transaction: &Transaction,
+        current_epoch: Epoch,
+    ) -> Result<Option<Epoch>, MempoolError> {
+        let Some(min_epoch) = transaction.min_epoch().filter(|min_epoch| *min_epoch > current_epoch) else {
+            return Ok(None);
+        };
+        let max_epoch = Epoch(current_epoch.as_u64().saturating_add(self.max_deferred_epochs));
+        if min_epoch > max_epoch {
+            return Err(MempoolError::TransactionDeferredTooFar {
+                transaction_id: *transaction.id(),
+                min_epoch,
+                max_epoch,
+            });
+        }
+        Ok(Some(min_epoch))
+    }
+
+    pub fn insert(&mut self, min_epoch: Epoch, transaction: Transaction) {
+        self.transactions.entry(min_epoch).or_default().push(transaction);
+    }
+
+    pub fn remove(&mut self, transaction_id: &TransactionId) {
+        for transactions in self.transactions.values_mut() {
+            transactions.retain(|t| t.id() != transaction_id);
+        }
+        self.transactions.retain(|_, transactions| !transactions.is_empty());
+    }
+
+    /// Removes and returns all transactions that are valid in the given epoch
+    pub fn take_ready(&mut self, epoch: Epoch) -> Vec<Transaction> {
+        let still_deferred = self.transactions.split_off(&Epoch(epoch.as_u64() + 1));
+        let ready = mem::replace(&mut self.transactions, still_deferred);
+        ready.into_values().flatten().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction_with_min_epoch(min_epoch: u64) -> Transaction {
+        Transaction::builder().with_min_epoch(Some(Epoch(min_epoch))).build()
+    }
+
+    #[test]
+    fn it_releases_transactions_once_the_min_epoch_is_reached() {
+        let mut deferred = DeferredTransactions::new(10);
+        let current_epoch = Epoch(1);
+        let tx3 = transaction_with_min_epoch(3);
+        let tx5 = transaction_with_min_epoch(5);
+        for tx in [&tx5, &tx3] {
+            let min_epoch = deferred.deferred_until(tx, current_epoch).unwrap().unwrap();
+            deferred.insert(min_epoch, tx.clone());
+        }
+        assert_eq!(deferred.len(), 2);
+
+        assert!(deferred.take_ready(Epoch(2)).is_empty());
+        let ready = deferred.take_ready(Epoch(3));
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id(), tx3.id());
+        assert_eq!(deferred.len(), 1);
+
+        // An epoch change may skip epochs
+        let ready = deferred.take_ready(Epoch(7));
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].id(), tx5.id());
+        assert_eq!(deferred.len(), 0);
+    }
+
+    #[test]
+    fn it_does_not_defer_transactions_that_are_already_valid() {
+        let deferred = DeferredTransactions::new(10);
+        assert_eq!(
+            deferred
+                .deferred_until(&transaction_with_min_epoch(3), Epoch(3))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            deferred
+                .deferred_until(&Transaction::builder().build(), Epoch(3))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn it_rejects_transactions_deferred_past_the_configured_limit() {
+        let deferred = DeferredTransactions::new(2);
+        assert_eq!(
+            deferred
+                .deferred_until(&transaction_with_min_epoch(3), Epoch(1))
+                .unwrap(),
+            Some(Epoch(3))
+        );
+        let err = deferred
+            .deferred_until(&transaction_with_min_epoch(4), Epoch(1))
+            .unwrap_err();
+        assert!(matches!(err, MempoolError::TransactionDeferredTooFar { max_epoch, .. } if max_epoch == Epoch(3)));
+    }
+
+    #[test]
+    fn it_removes_evicted_transactions() {
+        let mut deferred = DeferredTransactions::new(10);
+        let tx = transaction_with_min_epoch(3);
+        deferred.insert(Epoch(3), tx.clone());
+        deferred.remove(tx.id());
+        assert_eq!(deferred.len(), 0);
+        assert!(deferred.take_ready(Epoch(3)).is_empty());
+    }
+}
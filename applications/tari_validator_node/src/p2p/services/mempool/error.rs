@@ -1,9 +1,11 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+use tari_dan_common_types::Epoch;
 use tari_dan_storage::StorageError;
 use tari_epoch_manager::EpochManagerError;
 use tari_networking::NetworkingError;
+use tari_transaction::TransactionId;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{
@@ -30,6 +32,19 @@ pub enum MempoolError {
     TransactionValidationError(#[from] TransactionValidationError),
     #[error("Network error: {0}")]
     NetworkingError(#[from] NetworkingError),
+    #[error(
+        "Transaction {transaction_id} is not valid until {min_epoch}, which is later than the maximum deferral epoch \
+         {max_epoch}"
+    )]
+    TransactionDeferredTooFar {
+        transaction_id: TransactionId,
+        min_epoch: Epoch,
+        max_epoch: Epoch,
+    },
+    #[error("Transaction {transaction_id} is not in the mempool")]
+    TransactionNotFound { transaction_id: TransactionId },
+    #[error("Transaction {transaction_id} cannot be evicted because it has already been proposed")]
+    TransactionAlreadyProposed { transaction_id: TransactionId },
 }
 
 impl From<mpsc::error::SendError<MempoolRequest>> for MempoolError {
@@ -23,7 +23,7 @@
 use tari_transaction::{Transaction, TransactionId};
 use tokio::sync::{mpsc, oneshot};
 
-use crate::p2p::services::mempool::MempoolError;
+use crate::p2p::services::mempool::{MempoolError, MempoolTransaction};
 
 pub enum MempoolRequest {
     SubmitTransaction {
@@ -37,6 +37,13 @@ pub enum MempoolRequest {
     GetMempoolSize {
         reply: oneshot::Sender<usize>,
     },
+    GetTransactions {
+        reply: oneshot::Sender<Vec<MempoolTransaction>>,
+    },
+    EvictTransaction {
+        transaction_id: TransactionId,
+        reply: oneshot::Sender<Result<(), MempoolError>>,
+    },
 }
 
 #[derive(Debug)]
@@ -83,4 +90,22 @@ impl MempoolHandle {
             .await?;
         rx.await.map_err(Into::into)
     }
+
+    pub async fn get_transactions(&self) -> Result<Vec<MempoolTransaction>, MempoolError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx_mempool_request
+            .send(MempoolRequest::GetTransactions { reply: tx })
+            .await?;
+        rx.await.map_err(Into::into)
+    }
+
+    /// Removes a pending transaction from the mempool. The transaction is also removed from the consensus transaction
+    /// pool if it has not yet been proposed.
+    pub async fn evict_transaction(&self, transaction_id: TransactionId) -> Result<(), MempoolError> {
+        let (reply, rx) = oneshot::channel();
+        self.tx_mempool_request
+            .send(MempoolRequest::EvictTransaction { transaction_id, reply })
+            .await?;
+        rx.await?
+    }
 }
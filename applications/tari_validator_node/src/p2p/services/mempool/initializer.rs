@@ -34,7 +34,7 @@ use tokio::{sync::mpsc, task, task::JoinHandle};
 use super::metrics::PrometheusMempoolMetrics;
 use crate::{
     consensus::ConsensusHandle,
-    p2p::services::mempool::{handle::MempoolHandle, service::MempoolService},
+    p2p::services::mempool::{handle::MempoolHandle, service::MempoolService, MempoolConfig},
     transaction_validators::TransactionValidationError,
     validator::Validator,
 };
@@ -42,6 +42,7 @@ use crate::{
 const LOG_TARGET: &str = "tari::dan::validator_node::mempool";
 
 pub fn spawn<TValidator>(
+    config: MempoolConfig,
     num_preshards: NumPreshards,
     epoch_manager: EpochManagerHandle<PeerAddress>,
     transaction_validator: TValidator,
@@ -61,6 +62,7 @@ where
     #[cfg(feature = "metrics")]
     let metrics = PrometheusMempoolMetrics::new(metrics_registry);
     let mempool = MempoolService::new(
+        config,
         num_preshards,
         rx_mempool_request,
         epoch_manager,
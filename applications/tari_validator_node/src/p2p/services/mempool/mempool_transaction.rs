@@ -0,0 +1,45 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::BTreeSet,
+    time::{Duration, Instant},
+};
+
+use tari_dan_common_types::{shard::Shard, Epoch, NumPreshards};
+use tari_template_lib::models::Amount;
+use tari_transaction::{Transaction, TransactionId};
+
+/// A summary of a transaction that is pending in the mempool
+#[derive(Debug, Clone)]
+pub struct MempoolTransaction {
+    pub transaction_id: TransactionId,
+    /// The shards of the transaction inputs
+    pub input_shards: BTreeSet<Shard>,
+    pub received_at: Instant,
+    pub declared_max_fee: Option<Amount>,
+    pub fee_per_instruction: u64,
+    /// Set if the transaction is held back until its min_epoch
+    pub deferred_until: Option<Epoch>,
+}
+
+impl MempoolTransaction {
+    pub fn new(transaction: &Transaction, num_preshards: NumPreshards, deferred_until: Option<Epoch>) -> Self {
+        Self {
+            transaction_id: *transaction.id(),
+            input_shards: transaction
+                .all_inputs_iter()
+                // Versions do not affect the shard
+                .map(|input| input.to_substate_address_zero_version().to_shard(num_preshards))
+                .collect(),
+            received_at: Instant::now(),
+            declared_max_fee: transaction.declared_max_fee(),
+            fee_per_instruction: transaction.fee_per_instruction(),
+            deferred_until,
+        }
+    }
+
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
+}
@@ -1,7 +1,7 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use prometheus::{IntCounter, Registry};
+use prometheus::{IntCounter, IntGauge, Registry};
 use tari_transaction::{Transaction, TransactionId};
 
 use crate::metrics::CollectorRegister;
@@ -10,6 +10,7 @@ use crate::metrics::CollectorRegister;
 pub struct PrometheusMempoolMetrics {
     transactions_received: IntCounter,
     transaction_validation_error: IntCounter,
+    mempool_size: IntGauge,
 }
 
 impl PrometheusMempoolMetrics {
@@ -24,6 +25,9 @@ impl PrometheusMempoolMetrics {
             )
             .unwrap()
             .register_at(registry),
+            mempool_size: IntGauge::new("mempool_size", "Number of transactions in the mempool")
+                .unwrap()
+                .register_at(registry),
         }
     }
 
@@ -34,4 +38,8 @@ impl PrometheusMempoolMetrics {
     pub fn on_transaction_validation_error<E: ToString>(&mut self, _transaction: &TransactionId, _err: &E) {
         self.transaction_validation_error.inc();
     }
+
+    pub fn on_mempool_size_changed(&mut self, size: usize) {
+        self.mempool_size.set(size as i64);
+    }
 }
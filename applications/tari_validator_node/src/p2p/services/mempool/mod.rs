@@ -20,12 +20,19 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod config;
+pub use config::MempoolConfig;
+
 mod handle;
 pub use handle::{MempoolHandle, MempoolRequest};
 
 mod initializer;
 pub use initializer::spawn;
 
+mod mempool_transaction;
+pub use mempool_transaction::MempoolTransaction;
+
+mod deferred;
 mod error;
 mod gossip;
 pub use gossip::TOPIC_PREFIX;
@@ -20,27 +20,39 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{collections::HashSet, fmt::Display, iter};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    iter,
+    time::Instant,
+};
 
 use libp2p::{gossipsub, PeerId};
 use log::*;
 use tari_consensus::hotstuff::HotstuffEvent;
-use tari_dan_common_types::{optional::Optional, NumPreshards, PeerAddress, ShardGroup, ToSubstateAddress};
+use tari_dan_common_types::{optional::Optional, Epoch, NumPreshards, PeerAddress, ShardGroup, ToSubstateAddress};
 use tari_dan_p2p::{DanMessage, NewTransactionMessage, TariMessagingSpec};
-use tari_dan_storage::{consensus_models::TransactionRecord, StateStore};
+use tari_dan_storage::{
+    consensus_models::TransactionRecord,
+    StateStore,
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+};
 use tari_engine_types::commit_result::RejectReason;
 use tari_epoch_manager::{base_layer::EpochManagerHandle, EpochManagerReader};
 use tari_networking::NetworkingHandle;
 use tari_state_store_sqlite::SqliteStateStore;
 use tari_transaction::{Transaction, TransactionId};
 use tokio::sync::{mpsc, oneshot};
+use tracing::{info_span, Instrument};
 
 #[cfg(feature = "metrics")]
 use super::metrics::PrometheusMempoolMetrics;
-use super::MempoolError;
+use super::{MempoolConfig, MempoolError, MempoolTransaction};
 use crate::{
     consensus::ConsensusHandle,
     p2p::services::mempool::{
+        deferred::DeferredTransactions,
         gossip::{IncomingMessage, MempoolGossip},
         handle::MempoolRequest,
     },
@@ -52,7 +64,9 @@ const LOG_TARGET: &str = "tari::validator_node::mempool::service";
 
 #[derive(Debug)]
 pub struct MempoolService<TValidator> {
-    transactions: HashSet<TransactionId>,
+    num_preshards: NumPreshards,
+    transactions: HashMap<TransactionId, MempoolTransaction>,
+    deferred_transactions: DeferredTransactions,
     mempool_requests: mpsc::Receiver<MempoolRequest>,
     epoch_manager: EpochManagerHandle<PeerAddress>,
     before_execute_validator: TValidator,
@@ -67,6 +81,7 @@ impl<TValidator> MempoolService<TValidator>
 where TValidator: Validator<Transaction, Context = (), Error = TransactionValidationError>
 {
     pub(super) fn new(
+        config: MempoolConfig,
         num_preshards: NumPreshards,
         mempool_requests: mpsc::Receiver<MempoolRequest>,
         epoch_manager: EpochManagerHandle<PeerAddress>,
@@ -79,7 +94,9 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
     ) -> Self {
         Self {
             gossip: MempoolGossip::new(num_preshards, epoch_manager.clone(), networking, rx_gossip),
+            num_preshards,
             transactions: Default::default(),
+            deferred_transactions: DeferredTransactions::new(config.max_deferred_epochs),
             mempool_requests,
             epoch_manager,
             before_execute_validator,
@@ -92,6 +109,7 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
 
     pub async fn run(mut self) -> anyhow::Result<()> {
         let mut consensus_events = self.consensus_handle.subscribe_to_hotstuff_events();
+        self.restore_deferred_transactions()?;
 
         loop {
             tokio::select! {
@@ -109,6 +127,10 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
                         info!(target: LOG_TARGET, "Not registered for epoch {epoch}, unsubscribing from gossip");
                         self.gossip.unsubscribe().await?;
                     }
+                    self.release_deferred_transactions(epoch).await;
+                },
+                Ok(HotstuffEvent::TransactionsRequeued { epoch, transaction_ids }) = consensus_events.recv() => {
+                    self.requeue_transactions(epoch, transaction_ids).await;
                 },
 
                 else => {
@@ -130,18 +152,164 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
             },
             MempoolRequest::RemoveTransactions { transaction_ids, reply } => {
                 let num_found = self.remove_transactions(&transaction_ids);
+                #[cfg(feature = "metrics")]
+                self.metrics.on_mempool_size_changed(self.transactions.len());
                 handle::<_, MempoolError>(reply, Ok(num_found));
             },
             MempoolRequest::GetMempoolSize { reply } => {
                 let _ignore = reply.send(self.transactions.len());
             },
+            MempoolRequest::GetTransactions { reply } => {
+                let _ignore = reply.send(self.transactions.values().cloned().collect());
+            },
+            MempoolRequest::EvictTransaction { transaction_id, reply } => {
+                handle(reply, self.evict_transaction(&transaction_id));
+                #[cfg(feature = "metrics")]
+                self.metrics.on_mempool_size_changed(self.transactions.len());
+            },
+        }
+    }
+
+    fn evict_transaction(&mut self, transaction_id: &TransactionId) -> Result<(), MempoolError> {
+        if !self.transactions.contains_key(transaction_id) {
+            return Err(MempoolError::TransactionNotFound {
+                transaction_id: *transaction_id,
+            });
+        }
+
+        self.state_store.with_write_tx(|tx| {
+            let pool_record = tx
+                .transaction_pool_get_all()?
+                .into_iter()
+                .find(|rec| rec.transaction_id() == transaction_id);
+            if let Some(pool_record) = pool_record {
+                // Once proposed, the transaction must be completed by consensus
+                if !pool_record.current_stage().is_new() {
+                    return Err(MempoolError::TransactionAlreadyProposed {
+                        transaction_id: *transaction_id,
+                    });
+                }
+                tx.transaction_pool_remove(transaction_id)?;
+            }
+            // Consensus may not have stored the transaction yet
+            if let Some(mut record) = TransactionRecord::get(&**tx, transaction_id).optional()? {
+                record
+                    .set_abort_reason(RejectReason::InvalidTransaction(
+                        "Evicted from mempool by the validator node operator".to_string(),
+                    ))
+                    .update(tx)?;
+            }
+            Ok::<_, MempoolError>(())
+        })?;
+
+        self.transactions.remove(transaction_id);
+        self.deferred_transactions.remove(transaction_id);
+        info!(target: LOG_TARGET, "🎱 Evicted transaction {} from mempool", transaction_id);
+        Ok(())
+    }
+
+    /// Loads the deferred transactions that were persisted before the validator node was restarted. They are released
+    /// on the next epoch change at or after their min_epoch.
+    fn restore_deferred_transactions(&mut self) -> Result<(), MempoolError> {
+        let transactions = self
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::get_deferred(tx))?;
+        for transaction in transactions {
+            let transaction = transaction.into_transaction();
+            let Some(min_epoch) = transaction.min_epoch() else {
+                continue;
+            };
+            self.transactions.insert(
+                *transaction.id(),
+                MempoolTransaction::new(&transaction, self.num_preshards, Some(min_epoch)),
+            );
+            self.deferred_transactions.insert(min_epoch, transaction);
+        }
+        if !self.deferred_transactions.is_empty() {
+            info!(
+                target: LOG_TARGET,
+                "⏳️ Restored {} deferred transaction(s)",
+                self.deferred_transactions.len()
+            );
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.on_mempool_size_changed(self.transactions.len());
+        Ok(())
+    }
+
+    async fn release_deferred_transactions(&mut self, epoch: Epoch) {
+        for transaction in self.deferred_transactions.take_ready(epoch) {
+            // The transaction may have been removed from the mempool while it was deferred
+            let Some(deferred) = self.transactions.get_mut(transaction.id()) else {
+                continue;
+            };
+            deferred.deferred_until = None;
+            info!(
+                target: LOG_TARGET,
+                "⏰️ Deferred transaction {} is valid from {}",
+                transaction.id(),
+                epoch
+            );
+            // The transaction was validated and propagated when it was received, so it is handed straight to
+            // consensus
+            if self
+                .consensus_handle
+                .notify_new_transaction(transaction, 0)
+                .await
+                .is_err()
+            {
+                error!(target: LOG_TARGET, "Consensus channel closed while releasing deferred transactions");
+                return;
+            }
+        }
+    }
+
+    async fn requeue_transactions(&mut self, epoch: Epoch, transaction_ids: Vec<TransactionId>) {
+        let transactions = match self
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::get_any(tx, &transaction_ids))
+        {
+            Ok((transactions, _)) => transactions,
+            Err(e) => {
+                error!(target: LOG_TARGET, "Failed to load requeued transactions: {}", e);
+                return;
+            },
+        };
+        info!(
+            target: LOG_TARGET,
+            "♻️ Resubmitting {} transaction(s) released at the start of {}",
+            transactions.len(),
+            epoch
+        );
+        for transaction in transactions {
+            let transaction_id = *transaction.id();
+            let requeued = self.transactions.remove(&transaction_id);
+            // The transaction is routed to the committees of the new epoch
+            if let Err(e) = self
+                .handle_new_transaction(transaction.into_transaction(), None, 0)
+                .await
+            {
+                warn!(target: LOG_TARGET, "Mempool rejected requeued transaction: {}", e);
+            }
+            if let Some(requeued) = requeued {
+                self.restore_received_at(&transaction_id, requeued.received_at);
+            }
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.on_mempool_size_changed(self.transactions.len());
+    }
+
+    /// Keeps the age of a transaction that is reprocessed from the time that it was first received
+    fn restore_received_at(&mut self, transaction_id: &TransactionId, received_at: Instant) {
+        if let Some(transaction) = self.transactions.get_mut(transaction_id) {
+            transaction.received_at = received_at;
         }
     }
 
     fn remove_transactions(&mut self, ids: &[TransactionId]) -> usize {
         let mut num_found = 0;
         for id in ids {
-            if self.transactions.remove(id) {
+            if self.transactions.remove(id).is_some() {
                 num_found += 1;
             }
         }
@@ -220,6 +388,18 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
         transaction: Transaction,
         sender_shard_group: Option<ShardGroup>,
         num_pending: usize,
+    ) -> Result<(), MempoolError> {
+        let span = info_span!("transaction", transaction_id = %transaction.id());
+        self.process_new_transaction(transaction, sender_shard_group, num_pending)
+            .instrument(span)
+            .await
+    }
+
+    async fn process_new_transaction(
+        &mut self,
+        transaction: Transaction,
+        sender_shard_group: Option<ShardGroup>,
+        num_pending: usize,
     ) -> Result<(), MempoolError> {
         #[cfg(feature = "metrics")]
         self.metrics.on_transaction_received(&transaction);
@@ -239,6 +419,9 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
             return Err(e.into());
         }
 
+        let current_epoch = self.consensus_handle.current_view().get_epoch();
+        let deferred_until = self.deferred_transactions.deferred_until(&transaction, current_epoch)?;
+
         // Get the shards involved in claim fees.
         let fee_claims = transaction.fee_claims().collect::<Vec<_>>();
 
@@ -254,7 +437,6 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
             warn!(target: LOG_TARGET, "⚠ No involved shards for payload");
         }
 
-        let current_epoch = self.consensus_handle.current_view().get_epoch();
         let tx_substate_address = transaction.id().to_substate_address();
 
         let local_committee_shard = self.epoch_manager.get_local_committee_info(current_epoch).await?;
@@ -267,11 +449,33 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
 
         if is_input_shard || is_output_shard {
             debug!(target: LOG_TARGET, "🎱 New transaction {} in mempool", transaction.id());
-            self.transactions.insert(*transaction.id());
-            self.consensus_handle
-                .notify_new_transaction(transaction.clone(), num_pending)
-                .await
-                .map_err(|_| MempoolError::ConsensusChannelClosed)?;
+            self.transactions.insert(
+                *transaction.id(),
+                MempoolTransaction::new(&transaction, self.num_preshards, deferred_until),
+            );
+            #[cfg(feature = "metrics")]
+            self.metrics.on_mempool_size_changed(self.transactions.len());
+            match deferred_until {
+                Some(min_epoch) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "⏳️ Transaction {} deferred until {} (current epoch: {})",
+                        transaction.id(),
+                        min_epoch,
+                        current_epoch
+                    );
+                    // The transaction is persisted so that it is released after a restart
+                    self.state_store
+                        .with_write_tx(|tx| TransactionRecord::new(transaction.clone()).save(tx))?;
+                    self.deferred_transactions.insert(min_epoch, transaction.clone());
+                },
+                None => {
+                    self.consensus_handle
+                        .notify_new_transaction(transaction.clone(), num_pending)
+                        .await
+                        .map_err(|_| MempoolError::ConsensusChannelClosed)?;
+                },
+            }
 
             // If we received the message from our local shard group, we don't need to gossip it again on the topic
             // (prevents Duplicate errors)
@@ -325,7 +529,7 @@ where TValidator: Validator<Transaction, Context = (), Error = TransactionValida
     }
 
     fn transaction_exists(&self, id: &TransactionId) -> Result<bool, MempoolError> {
-        if self.transactions.contains(id) {
+        if self.transactions.contains_key(id) {
             debug!(
                 target: LOG_TARGET,
                 "🎱 Transaction {} already in mempool",
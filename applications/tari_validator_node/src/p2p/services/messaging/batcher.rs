@@ -0,0 +1,148 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use libp2p::PeerId;
+use log::*;
+use tari_consensus::traits::MessagePriority;
+use tari_dan_common_types::ShardGroup;
+use tari_dan_p2p::proto;
+use tokio::{
+    sync::mpsc,
+    time::{self, Instant},
+};
+use tracing::debug_span;
+
+use super::{
+    outbound::{Destination, OutboundRequest},
+    MessageBatchingConfig,
+};
+
+const LOG_TARGET: &str = "tari::dan::messages::outbound::batcher";
+
+/// A message that is multicast to the members of a shard group
+pub(super) struct BatchRequest {
+    pub shard_group: ShardGroup,
+    pub priority: MessagePriority,
+    pub peers: Vec<PeerId>,
+    pub message: proto::consensus::HotStuffMessage,
+}
+
+struct PendingBatch {
+    peers: Vec<PeerId>,
+    messages: Vec<proto::consensus::HotStuffMessage>,
+    flush_at: Instant,
+}
+
+/// Collects messages that are multicast to the same shard group on the same lane and hands them to the lane as a
+/// single message batch. A batch is sent when it is full or when its oldest message has waited for the flush interval.
+pub(super) struct MessageBatcher {
+    config: MessageBatchingConfig,
+    high_priority_lane: mpsc::Sender<OutboundRequest>,
+    low_priority_lane: mpsc::Sender<OutboundRequest>,
+    pending: HashMap<(ShardGroup, MessagePriority), PendingBatch>,
+}
+
+impl MessageBatcher {
+    pub fn new(
+        config: MessageBatchingConfig,
+        high_priority_lane: mpsc::Sender<OutboundRequest>,
+        low_priority_lane: mpsc::Sender<OutboundRequest>,
+    ) -> Self {
+        Self {
+            config,
+            high_priority_lane,
+            low_priority_lane,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub async fn run(mut self, mut rx_requests: mpsc::Receiver<BatchRequest>) {
+        loop {
+            let next_flush = self.pending.values().map(|batch| batch.flush_at).min();
+            tokio::select! {
+                maybe_request = rx_requests.recv() => {
+                    let Some(request) = maybe_request else {
+                        break;
+                    };
+                    self.add(request).await;
+                },
+                _ = time::sleep_until(next_flush.unwrap_or_else(Instant::now)), if next_flush.is_some() => {
+                    self.flush_due().await;
+                },
+            }
+        }
+
+        let keys = self.pending.keys().copied().collect::<Vec<_>>();
+        for key in keys {
+            self.flush(key).await;
+        }
+    }
+
+    async fn add(&mut self, request: BatchRequest) {
+        let key = (request.shard_group, request.priority);
+        // The committee of the shard group changed (e.g. a new epoch started), so the pending messages are sent to the
+        // previous committee first
+        if self.pending.get(&key).is_some_and(|batch| batch.peers != request.peers) {
+            self.flush(key).await;
+        }
+
+        let flush_interval = self.config.flush_interval;
+        let batch = self.pending.entry(key).or_insert_with(|| PendingBatch {
+            peers: request.peers,
+            messages: Vec::new(),
+            flush_at: Instant::now() + flush_interval,
+        });
+        batch.messages.push(request.message);
+        if batch.messages.len() >= self.config.max_batch_size {
+            self.flush(key).await;
+        }
+    }
+
+    async fn flush_due(&mut self) {
+        let now = Instant::now();
+        let due = self
+            .pending
+            .iter()
+            .filter(|(_, batch)| batch.flush_at <= now)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in due {
+            self.flush(key).await;
+        }
+    }
+
+    async fn flush(&mut self, key: (ShardGroup, MessagePriority)) {
+        let Some(mut batch) = self.pending.remove(&key) else {
+            return;
+        };
+        let (shard_group, priority) = key;
+        let num_messages = batch.messages.len();
+        // A single message is sent as is, so that batching adds no overhead when there is no other message to send
+        let message = if num_messages == 1 {
+            batch.messages.remove(0)
+        } else {
+            proto::consensus::HotStuffMessage::batch(batch.messages)
+        };
+
+        let lane = match priority {
+            MessagePriority::High => &self.high_priority_lane,
+            MessagePriority::Low => &self.low_priority_lane,
+        };
+        let request = OutboundRequest {
+            destination: Destination::Multicast(batch.peers),
+            message,
+            span: debug_span!("outbound_message_batch", %shard_group, num_messages),
+        };
+        if lane.send(request).await.is_err() {
+            warn!(
+                target: LOG_TARGET,
+                "Dropped a batch of {} message(s) to {}: {} priority lane closed",
+                num_messages,
+                shard_group,
+                priority.as_str()
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageBatchingConfig {
+    /// If true, consensus messages that are multicast to the same shard group within the flush interval are sent in a
+    /// single network frame
+    pub enabled: bool,
+    /// The maximum number of messages in a batch. A full batch is sent immediately.
+    pub max_batch_size: usize,
+    /// How long a multicast message may wait for other messages to the same shard group before the batch is sent
+    #[serde(with = "humantime_serde")]
+    pub flush_interval: Duration,
+}
+
+impl Default for MessageBatchingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_batch_size: 16,
+            flush_interval: Duration::from_millis(5),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::collections::VecDeque;
+
 use libp2p::PeerId;
 use tari_consensus::{messages::HotstuffMessage, traits::InboundMessagingError};
 use tari_dan_common_types::PeerAddress;
@@ -14,6 +16,8 @@ pub struct ConsensusInboundMessaging<TMsgLogger> {
     rx_inbound_msg: mpsc::UnboundedReceiver<(PeerId, proto::consensus::HotStuffMessage)>,
     rx_gossip: mpsc::Receiver<(PeerId, proto::consensus::HotStuffMessage)>,
     rx_loopback: mpsc::UnboundedReceiver<HotstuffMessage>,
+    /// Messages from a received batch that have not been returned yet
+    pending_batch: VecDeque<(PeerId, proto::consensus::HotStuffMessage)>,
     msg_logger: TMsgLogger,
 }
 
@@ -30,11 +34,34 @@ impl<TMsgLogger: MessageLogger> ConsensusInboundMessaging<TMsgLogger> {
             rx_inbound_msg,
             rx_gossip,
             rx_loopback,
+            pending_batch: VecDeque::new(),
             msg_logger,
         }
     }
 
     fn handle_message(
+        &mut self,
+        from: PeerId,
+        msg: proto::consensus::HotStuffMessage,
+    ) -> Option<Result<(PeerAddress, HotstuffMessage), InboundMessagingError>> {
+        let mut messages = match msg.unbatch() {
+            Ok(messages) => messages.into_iter(),
+            Err(err) => {
+                return Some(Err(InboundMessagingError::InvalidMessage {
+                    reason: err.to_string(),
+                }))
+            },
+        };
+        let Some(msg) = messages.next() else {
+            return Some(Err(InboundMessagingError::InvalidMessage {
+                reason: "Message batch is empty".to_string(),
+            }));
+        };
+        self.pending_batch.extend(messages.map(|msg| (from, msg)));
+        self.convert_message(from, msg)
+    }
+
+    fn convert_message(
         &self,
         from: PeerId,
         msg: proto::consensus::HotStuffMessage,
@@ -58,6 +85,11 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::InboundMessaging
     type Addr = PeerAddress;
 
     async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+        // The remaining messages of a batch are returned before any other message is received
+        if let Some((from, msg)) = self.pending_batch.pop_front() {
+            return self.convert_message(from, msg);
+        }
+
         tokio::select! {
             // BIASED: messaging priority is loopback, then other
             biased;
@@ -1,6 +1,11 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+mod batcher;
+
+mod config;
+pub use config::*;
+
 mod inbound;
 pub use inbound::*;
 
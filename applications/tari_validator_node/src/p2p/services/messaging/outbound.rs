@@ -23,22 +23,43 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_consensus::{messages::HotstuffMessage, traits::OutboundMessagingError};
+use libp2p::PeerId;
+use log::*;
+use tari_consensus::{
+    messages::HotstuffMessage,
+    traits::{MessagePriority, OutboundMessagingError},
+};
 use tari_dan_common_types::{PeerAddress, ShardGroup};
 use tari_dan_p2p::{proto, TariMessagingSpec};
 use tari_networking::{NetworkingHandle, NetworkingService};
-use tokio::sync::mpsc;
+use tokio::{sync::mpsc, task};
+use tracing::{debug_span, Instrument, Span};
 
+use super::{
+    batcher::{BatchRequest, MessageBatcher},
+    MessageBatchingConfig,
+};
 use crate::p2p::{logging::MessageLogger, services::consensus_gossip::ConsensusGossipHandle};
 
-const _LOG_TARGET: &str = "tari::dan::messages::outbound::validator_node";
+const LOG_TARGET: &str = "tari::dan::messages::outbound::validator_node";
+
+/// The number of messages that may be queued on the high priority lane before senders have to wait
+const HIGH_PRIORITY_LANE_CAPACITY: usize = 1000;
+/// The number of messages that may be queued on the low priority lane before senders have to wait. This is kept small
+/// since these messages are large and the senders (e.g. the missing transactions responder) can afford to wait.
+const LOW_PRIORITY_LANE_CAPACITY: usize = 100;
+/// The number of multicast messages that may be queued for batching before senders have to wait
+const BATCHER_CAPACITY: usize = 1000;
 
 #[derive(Debug, Clone)]
 pub struct ConsensusOutboundMessaging<TMsgLogger> {
     our_node_addr: PeerAddress,
     loopback_sender: mpsc::UnboundedSender<HotstuffMessage>,
     consensus_gossip: ConsensusGossipHandle,
-    networking: NetworkingHandle<TariMessagingSpec>,
+    high_priority_lane: mpsc::Sender<OutboundRequest>,
+    low_priority_lane: mpsc::Sender<OutboundRequest>,
+    /// None if batching is disabled
+    batcher: Option<mpsc::Sender<BatchRequest>>,
     msg_logger: TMsgLogger,
 }
 
@@ -48,15 +69,44 @@ impl<TMsgLogger: MessageLogger> ConsensusOutboundMessaging<TMsgLogger> {
         consensus_gossip: ConsensusGossipHandle,
         networking: NetworkingHandle<TariMessagingSpec>,
         msg_logger: TMsgLogger,
+        batching: MessageBatchingConfig,
     ) -> Self {
+        let (high_priority_lane, rx_high_priority) = mpsc::channel(HIGH_PRIORITY_LANE_CAPACITY);
+        let (low_priority_lane, rx_low_priority) = mpsc::channel(LOW_PRIORITY_LANE_CAPACITY);
+        task::spawn(run_lane(MessagePriority::High, networking.clone(), rx_high_priority));
+        task::spawn(run_lane(MessagePriority::Low, networking.clone(), rx_low_priority));
+
+        let batcher = if batching.enabled && batching.max_batch_size > 1 {
+            let (batcher, rx_batch_requests) = mpsc::channel(BATCHER_CAPACITY);
+            let message_batcher = MessageBatcher::new(batching, high_priority_lane.clone(), low_priority_lane.clone());
+            task::spawn(message_batcher.run(rx_batch_requests));
+            Some(batcher)
+        } else {
+            None
+        };
+
         Self {
             our_node_addr: (*networking.local_peer_id()).into(),
             loopback_sender,
             consensus_gossip,
-            networking,
+            high_priority_lane,
+            low_priority_lane,
+            batcher,
             msg_logger,
         }
     }
+
+    async fn enqueue(&self, priority: MessagePriority, request: OutboundRequest) -> Result<(), OutboundMessagingError> {
+        let lane = match priority {
+            MessagePriority::High => &self.high_priority_lane,
+            MessagePriority::Low => &self.low_priority_lane,
+        };
+        lane.send(request)
+            .await
+            .map_err(|_| OutboundMessagingError::FailedToEnqueueMessage {
+                reason: format!("{} priority lane closed", priority.as_str()),
+            })
+    }
 }
 
 impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
@@ -81,10 +131,11 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
         Ok(())
     }
 
-    async fn send<T: Into<HotstuffMessage> + Send>(
+    async fn send_with_priority<T: Into<HotstuffMessage> + Send>(
         &mut self,
         to: Self::Addr,
         message: T,
+        priority: MessagePriority,
     ) -> Result<(), OutboundMessagingError> {
         if to == self.our_node_addr {
             return self.send_self(message).await;
@@ -94,45 +145,110 @@ impl<TMsgLogger: MessageLogger + Send> tari_consensus::traits::OutboundMessaging
 
         self.msg_logger
             .log_outbound_message("send", &to.to_string(), msg.as_type_str(), "", &msg);
-        self.networking
-            .send_message(to.as_peer_id(), proto::consensus::HotStuffMessage::from(&msg))
-            .await
-            .map_err(OutboundMessagingError::from_error)?;
-
-        Ok(())
+        // Nested in the span of the caller, so that messages sent while processing a transaction are logged with its
+        // transaction_id
+        let span = debug_span!("outbound_message", message_type = msg.as_type_str(), to = %to);
+        self.enqueue(priority, OutboundRequest {
+            destination: Destination::Peer(to.as_peer_id()),
+            message: proto::consensus::HotStuffMessage::from(&msg),
+            span,
+        })
+        .await
     }
 
-    async fn multicast<T, I>(&mut self, addresses: I, message: T) -> Result<(), OutboundMessagingError>
+    async fn multicast<T, I>(
+        &mut self,
+        shard_group: ShardGroup,
+        addresses: I,
+        message: T,
+    ) -> Result<(), OutboundMessagingError>
     where
         I: IntoIterator<Item = Self::Addr> + Send,
         T: Into<HotstuffMessage> + Send,
     {
         let message = message.into();
+        let priority = MessagePriority::for_message(&message);
+        let peers = addresses
+            .into_iter()
+            .filter(|addr| *addr != self.our_node_addr)
+            .map(|addr| addr.as_peer_id())
+            .collect();
+
+        if let Some(batcher) = &self.batcher {
+            return batcher
+                .send(BatchRequest {
+                    shard_group,
+                    priority,
+                    peers,
+                    message: proto::consensus::HotStuffMessage::from(&message),
+                })
+                .await
+                .map_err(|_| OutboundMessagingError::FailedToEnqueueMessage {
+                    reason: "message batcher closed".to_string(),
+                });
+        }
 
-        self.networking
-            .send_multicast(
-                addresses
-                    .into_iter()
-                    .filter(|addr| *addr != self.our_node_addr)
-                    .map(|addr| addr.as_peer_id())
-                    .collect::<Vec<_>>(),
-                proto::consensus::HotStuffMessage::from(&message),
-            )
-            .await
-            .map_err(OutboundMessagingError::from_error)?;
-
-        Ok(())
+        let span = debug_span!("outbound_message", message_type = message.as_type_str(), %shard_group);
+        self.enqueue(priority, OutboundRequest {
+            destination: Destination::Multicast(peers),
+            message: proto::consensus::HotStuffMessage::from(&message),
+            span,
+        })
+        .await
     }
 
     async fn broadcast<T>(&mut self, shard_group: ShardGroup, message: T) -> Result<(), OutboundMessagingError>
     where T: Into<HotstuffMessage> + Send {
         let message = message.into();
+        let span = debug_span!("outbound_message", message_type = message.as_type_str(), %shard_group);
 
         self.consensus_gossip
             .publish(shard_group, message)
+            .instrument(span)
             .await
             .map_err(OutboundMessagingError::from_error)?;
 
         Ok(())
     }
 }
+
+pub(super) struct OutboundRequest {
+    pub destination: Destination,
+    pub message: proto::consensus::HotStuffMessage,
+    pub span: Span,
+}
+
+pub(super) enum Destination {
+    Peer(PeerId),
+    Multicast(Vec<PeerId>),
+}
+
+/// Hands messages to the networking service in the order that they were queued on the lane. Each lane is serviced by
+/// its own task so that a lane waiting on the networking service does not hold up the other lane.
+async fn run_lane(
+    priority: MessagePriority,
+    mut networking: NetworkingHandle<TariMessagingSpec>,
+    mut rx_requests: mpsc::Receiver<OutboundRequest>,
+) {
+    while let Some(OutboundRequest {
+        destination,
+        message,
+        span,
+    }) = rx_requests.recv().await
+    {
+        let result = match destination {
+            Destination::Peer(peer_id) => networking.send_message(peer_id, message).instrument(span).await,
+            Destination::Multicast(peer_ids) => networking
+                .send_multicast(peer_ids, message)
+                .instrument(span)
+                .await
+                .map(|_| ()),
+        };
+        if let Err(err) = result {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to send message on {} priority lane: {}", priority.as_str(), err
+            );
+        }
+    }
+}
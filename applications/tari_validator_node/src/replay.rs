@@ -0,0 +1,534 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Replays committed blocks from the local state store. Every transaction that was executed in a block is re-executed
+//! against the inputs that it was executed with, and the state root of every block is recomputed from the state
+//! transitions that the block committed. The first block where either differs from what was committed is reported.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{Display, Formatter},
+};
+
+use anyhow::anyhow;
+use log::*;
+use tari_common_types::types::FixedHash;
+use tari_consensus::consensus_constants::ConsensusConstants;
+use tari_dan_app_utilities::{
+    template_manager::implementation::TemplateManager,
+    transaction_executor::TransactionExecutor,
+};
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, NodeHeight, PeerAddress, ToSubstateAddress};
+use tari_dan_engine::state_store::{new_memory_store, StateWriter};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        BlockId,
+        BlockTransactionExecution,
+        LastExecuted,
+        StateTransition,
+        StateTransitionId,
+        SubstateRecord,
+        SubstateUpdate,
+        TransactionExecution,
+        TransactionRecord,
+    },
+    global::DbFactory,
+    StateStore,
+    StateStoreReadTransaction,
+};
+use tari_dan_storage_sqlite::SqliteDbFactory;
+use tari_engine_types::{
+    commit_result::FinalizeResult,
+    substate::hash_substate,
+    virtual_substate::{VirtualSubstate, VirtualSubstateId, VirtualSubstates},
+};
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_state_tree::{
+    memory_store::MemoryTreeStore,
+    RootStateTree,
+    SpreadPrefixStateTree,
+    SubstateTreeChange,
+    TreeHash,
+    Version,
+    SPARSE_MERKLE_PLACEHOLDER_HASH,
+};
+use tari_transaction::TransactionId;
+
+use crate::{bootstrap::create_transaction_processor, cli::ReplayArgs, ApplicationConfig};
+
+const LOG_TARGET: &str = "tari::validator_node::replay";
+
+/// The number of block heights that are loaded at a time
+const BLOCK_PAGE_SIZE: u64 = 100;
+/// The number of state transitions of a shard that are loaded at a time
+const TRANSITION_PAGE_SIZE: usize = 1000;
+
+/// Replays the committed blocks selected by `args` from the local state store
+pub fn run_replay(config: &ApplicationConfig, args: &ReplayArgs) -> Result<ReplayReport, anyhow::Error> {
+    let db_factory = SqliteDbFactory::new(config.validator_node.data_dir.clone());
+    let global_db = db_factory.get_or_create_global_db()?;
+    let state_store = SqliteStateStore::<PeerAddress>::connect(&format!(
+        "sqlite://{}",
+        config.validator_node.state_db_path().display()
+    ))?;
+    let template_manager = TemplateManager::initialize(global_db, config.validator_node.templates.clone())?;
+    let consensus_constants = ConsensusConstants::from(config.network);
+    let executor = create_transaction_processor(config.network, &consensus_constants, template_manager);
+
+    state_store.with_read_tx(|tx| replay_blocks(tx, &executor, args))
+}
+
+fn replay_blocks<TTx, TExecutor>(
+    tx: &TTx,
+    executor: &TExecutor,
+    args: &ReplayArgs,
+) -> Result<ReplayReport, anyhow::Error>
+where
+    TTx: StateStoreReadTransaction,
+    TExecutor: TransactionExecutor,
+{
+    let last_committed = LastExecuted::get(tx)?;
+    let epoch = args.epoch.map(Epoch).unwrap_or(last_committed.epoch);
+    let from_height = NodeHeight(args.from_height);
+    let to_height = match args.to_height {
+        Some(height) => NodeHeight(height),
+        None => tx.blocks_max_height()?,
+    };
+    if from_height > to_height {
+        return Err(anyhow!(
+            "The first height ({}) must not be greater than the last height ({})",
+            from_height,
+            to_height
+        ));
+    }
+    info!(target: LOG_TARGET, "🔁 Replaying blocks {} to {} in {}", from_height, to_height, epoch);
+
+    let mut report = ReplayReport::new(epoch);
+    // The state trees are rebuilt from the first epoch, so that the state root of the first replayed block can be
+    // recomputed. This is set to None if the state history of this node is incomplete.
+    let mut state_trees = Some(StateTrees::new());
+    for block_epoch in (1..=epoch.as_u64()).map(Epoch) {
+        let Some(genesis_id) = Block::get_ids_by_epoch_and_height(tx, block_epoch, NodeHeight::zero())?
+            .into_iter()
+            .next()
+        else {
+            continue;
+        };
+        let shard_group = Block::get(tx, &genesis_id)?.shard_group();
+        let is_replayed_epoch = block_epoch == epoch;
+        let end_height = if is_replayed_epoch {
+            to_height
+        } else {
+            tx.blocks_max_height()?
+        };
+
+        let mut start_height = NodeHeight::zero();
+        while start_height <= end_height {
+            let page_end_height = NodeHeight((start_height.as_u64() + BLOCK_PAGE_SIZE - 1).min(end_height.as_u64()));
+            let blocks = Block::get_all_blocks_between(
+                tx,
+                block_epoch,
+                shard_group,
+                start_height,
+                page_end_height,
+                true,
+                // Blocks that were not committed may share a height with a committed block
+                BLOCK_PAGE_SIZE * 10,
+            )?;
+
+            for block in blocks.into_iter().filter(|b| b.is_committed()) {
+                let is_replayed = is_replayed_epoch && block.height() >= from_height;
+                if is_replayed {
+                    report.num_blocks += 1;
+                    if let Some(divergence) = replay_executions(tx, executor, &block, &mut report)? {
+                        report.divergence = Some(divergence);
+                        return Ok(report);
+                    }
+                }
+
+                let Some(trees) = state_trees.as_mut() else {
+                    continue;
+                };
+                let state_root = match trees.apply_block(tx, &block)? {
+                    Ok(state_root) => state_root,
+                    Err(reason) => {
+                        report.notes.push(format!("State roots are not verified: {}", reason));
+                        state_trees = None;
+                        continue;
+                    },
+                };
+                // The state root of a genesis block is not calculated from the state changes in the block
+                if block.is_genesis() || state_root == *block.state_merkle_root() {
+                    continue;
+                }
+                if is_replayed {
+                    report.divergence = Some(ReplayDivergence {
+                        block_id: *block.id(),
+                        height: block.height(),
+                        kind: DivergenceKind::StateRoot {
+                            committed: *block.state_merkle_root(),
+                            recomputed: state_root,
+                        },
+                    });
+                    return Ok(report);
+                }
+                // Either a block before the replayed range diverged or this node did not commit (and record the state
+                // transitions of) every block since the first epoch, e.g. because it joined the shard group later
+                report.notes.push(format!(
+                    "State roots are not verified: the state root of block {} in {} at height {} could not be \
+                     reconstructed",
+                    block.id(),
+                    block.epoch(),
+                    block.height()
+                ));
+                state_trees = None;
+            }
+
+            start_height = page_end_height + NodeHeight(1);
+        }
+    }
+
+    report.state_roots_verified = state_trees.is_some();
+    Ok(report)
+}
+
+/// Re-executes the transactions that were executed in the block. Returns the first execution that differs.
+fn replay_executions<TTx, TExecutor>(
+    tx: &TTx,
+    executor: &TExecutor,
+    block: &Block,
+    report: &mut ReplayReport,
+) -> Result<Option<ReplayDivergence>, anyhow::Error>
+where
+    TTx: StateStoreReadTransaction,
+    TExecutor: TransactionExecutor,
+{
+    let mut replayed = HashSet::new();
+    for transaction_id in block.all_transaction_ids() {
+        // A block may contain several commands for the same transaction
+        if !replayed.insert(transaction_id) {
+            continue;
+        }
+        let Some(execution) = BlockTransactionExecution::get_by_block(tx, transaction_id, block.id()).optional()?
+        else {
+            continue;
+        };
+        match replay_execution(tx, executor, block.epoch(), execution.transaction_execution())? {
+            ExecutionReplay::Matches => {
+                report.num_executions += 1;
+            },
+            ExecutionReplay::Skipped(reason) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Skipped transaction {} in block {}: {}",
+                    transaction_id,
+                    block.id(),
+                    reason
+                );
+                report.num_skipped_executions += 1;
+            },
+            ExecutionReplay::Differs(field) => {
+                return Ok(Some(ReplayDivergence {
+                    block_id: *block.id(),
+                    height: block.height(),
+                    kind: DivergenceKind::Execution {
+                        transaction_id: *transaction_id,
+                        field,
+                    },
+                }));
+            },
+        }
+    }
+
+    Ok(None)
+}
+
+enum ExecutionReplay {
+    Matches,
+    Skipped(String),
+    Differs(&'static str),
+}
+
+fn replay_execution<TTx, TExecutor>(
+    tx: &TTx,
+    executor: &TExecutor,
+    epoch: Epoch,
+    execution: &TransactionExecution,
+) -> Result<ExecutionReplay, anyhow::Error>
+where
+    TTx: StateStoreReadTransaction,
+    TExecutor: TransactionExecutor,
+{
+    let transaction = TransactionRecord::get(tx, &execution.transaction_id)?.into_transaction();
+
+    // Inputs that are not in the local state store were pledged by a foreign shard group or have been pruned
+    let mut state_db = new_memory_store();
+    let inputs = execution
+        .resolved_inputs
+        .iter()
+        .filter(|input| !input.lock_type().is_output());
+    for input in inputs {
+        let id = input.versioned_substate_id();
+        let Some(record) = SubstateRecord::get(tx, &id.to_substate_address()).optional()? else {
+            return Ok(ExecutionReplay::Skipped(format!(
+                "input {} is not in the local state store",
+                id
+            )));
+        };
+        state_db.set_state(id.substate_id().clone(), record.into_substate())?;
+    }
+
+    let mut virtual_substates = VirtualSubstates::new();
+    virtual_substates.insert(
+        VirtualSubstateId::CurrentEpoch,
+        VirtualSubstate::CurrentEpoch(epoch.as_u64()),
+    );
+
+    let output = executor.execute(transaction, state_db.into_read_only(), virtual_substates)?;
+    match first_difference(&execution.result.finalize, &output.result.finalize)? {
+        Some(field) => Ok(ExecutionReplay::Differs(field)),
+        None => Ok(ExecutionReplay::Matches),
+    }
+}
+
+/// Returns the name of the first part of the finalized results that differs, or None if they are the same
+fn first_difference(
+    committed: &FinalizeResult,
+    replayed: &FinalizeResult,
+) -> Result<Option<&'static str>, serde_json::Error> {
+    let parts = [
+        (
+            "result",
+            serde_json::to_value(&committed.result)?,
+            serde_json::to_value(&replayed.result)?,
+        ),
+        (
+            "fee receipt",
+            serde_json::to_value(&committed.fee_receipt)?,
+            serde_json::to_value(&replayed.fee_receipt)?,
+        ),
+        (
+            "events",
+            serde_json::to_value(&committed.events)?,
+            serde_json::to_value(&replayed.events)?,
+        ),
+        (
+            "logs",
+            serde_json::to_value(&committed.logs)?,
+            serde_json::to_value(&replayed.logs)?,
+        ),
+        (
+            "instruction results",
+            serde_json::to_value(&committed.execution_results)?,
+            serde_json::to_value(&replayed.execution_results)?,
+        ),
+    ];
+    Ok(parts
+        .into_iter()
+        .find(|(_, committed, replayed)| committed != replayed)
+        .map(|(name, _, _)| name))
+}
+
+/// In-memory state trees of the shards, rebuilt from the state transitions recorded when blocks were committed
+struct StateTrees {
+    shards: HashMap<Shard, ShardStateTree>,
+}
+
+impl StateTrees {
+    fn new() -> Self {
+        Self { shards: HashMap::new() }
+    }
+
+    /// Applies the state transitions committed by the block and returns the resulting state root of the block's shard
+    /// group, or the reason that the state root cannot be calculated
+    fn apply_block<TTx: StateStoreReadTransaction>(
+        &mut self,
+        tx: &TTx,
+        block: &Block,
+    ) -> Result<Result<FixedHash, String>, anyhow::Error> {
+        let committing = block.all_committing_transactions_ids().collect::<HashSet<_>>();
+        // Minted outputs are recorded without a transaction
+        let mints = block.commands().iter().any(|c| c.is_mint_confidential_output());
+        let no_transaction = TransactionId::default();
+
+        let mut shard_roots = Vec::with_capacity(block.shard_group().len());
+        for shard in block.shard_group().shard_iter() {
+            let tree = self.shards.entry(shard).or_insert_with(ShardStateTree::new);
+            let mut changes = Vec::new();
+            loop {
+                if tree.pending.is_empty() {
+                    // Transitions are recorded in commit order, so the transitions of later epochs are not needed yet
+                    let end_epoch = Epoch(block.epoch().as_u64() + 1);
+                    let after = StateTransitionId::new(block.epoch(), shard, tree.last_seq);
+                    let transitions = StateTransition::get_n_after(tx, TRANSITION_PAGE_SIZE, after, end_epoch)?;
+                    if tree.last_seq == 0 {
+                        if let Some(first) = transitions.first().filter(|t| t.id.seq() > 1) {
+                            return Ok(Err(format!(
+                                "the state transitions of shard {} before {} have been pruned",
+                                shard, first.id
+                            )));
+                        }
+                    }
+                    if transitions.is_empty() {
+                        break;
+                    }
+                    tree.pending.extend(transitions);
+                }
+
+                let Some(next) = tree.pending.front() else {
+                    break;
+                };
+                let transaction_id = match &next.update {
+                    SubstateUpdate::Create(create) => &create.substate.created_by_transaction,
+                    SubstateUpdate::Destroy(destroy) => &destroy.destroyed_by_transaction,
+                };
+                if !committing.contains(transaction_id) && !(mints && *transaction_id == no_transaction) {
+                    break;
+                }
+
+                let transition = tree.pending.pop_front().expect("front was Some");
+                tree.last_seq = transition.id.seq();
+                changes.push(match transition.update {
+                    SubstateUpdate::Create(create) => SubstateTreeChange::Up {
+                        id: create.substate.to_versioned_substate_id(),
+                        value_hash: hash_substate(&create.substate.substate_value, create.substate.version),
+                    },
+                    SubstateUpdate::Destroy(destroy) => SubstateTreeChange::Down {
+                        id: destroy.to_versioned_substate_id(),
+                    },
+                });
+            }
+
+            if !changes.is_empty() {
+                tree.apply(changes)?;
+            }
+            shard_roots.push(tree.root);
+        }
+
+        let mut store = MemoryTreeStore::new();
+        let (root, _) = RootStateTree::new(&mut store).compute_update_batch(None, 1, shard_roots)?;
+        Ok(Ok(FixedHash::new(root.into_array())))
+    }
+}
+
+struct ShardStateTree {
+    store: MemoryTreeStore<Version>,
+    version: Option<Version>,
+    root: TreeHash,
+    /// The sequence number of the last state transition that was applied
+    last_seq: u64,
+    /// State transitions that have been loaded but not yet applied
+    pending: VecDeque<StateTransition>,
+}
+
+impl ShardStateTree {
+    fn new() -> Self {
+        Self {
+            store: MemoryTreeStore::new(),
+            version: None,
+            root: SPARSE_MERKLE_PLACEHOLDER_HASH,
+            last_seq: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn apply(&mut self, changes: Vec<SubstateTreeChange>) -> Result<(), anyhow::Error> {
+        // The root hash does not depend on the version numbers, so these need not match the versions of the local
+        // state tree
+        let next_version = self.version.unwrap_or(0) + 1;
+        let mut tree = SpreadPrefixStateTree::new(&mut self.store);
+        self.root = tree.put_substate_changes(self.version, next_version, changes)?;
+        self.store.clear_stale_nodes();
+        self.version = Some(next_version);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayReport {
+    pub epoch: Epoch,
+    pub num_blocks: usize,
+    pub num_executions: usize,
+    /// Executions that could not be replayed because their inputs are not in the local state store
+    pub num_skipped_executions: usize,
+    pub state_roots_verified: bool,
+    pub notes: Vec<String>,
+    pub divergence: Option<ReplayDivergence>,
+}
+
+impl ReplayReport {
+    fn new(epoch: Epoch) -> Self {
+        Self {
+            epoch,
+            num_blocks: 0,
+            num_executions: 0,
+            num_skipped_executions: 0,
+            state_roots_verified: false,
+            notes: Vec::new(),
+            divergence: None,
+        }
+    }
+}
+
+impl Display for ReplayReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Replayed {} block(s) in {}: {} transaction execution(s) matched, {} skipped",
+            self.num_blocks, self.epoch, self.num_executions, self.num_skipped_executions
+        )?;
+        for note in &self.notes {
+            writeln!(f, "Note: {}", note)?;
+        }
+        match self.divergence {
+            Some(ref divergence) => write!(f, "❌ First divergence: {}", divergence),
+            None if self.state_roots_verified => {
+                write!(f, "✅ No divergence found in execution results or state roots")
+            },
+            None => write!(f, "✅ No divergence found in execution results"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReplayDivergence {
+    pub block_id: BlockId,
+    pub height: NodeHeight,
+    pub kind: DivergenceKind,
+}
+
+#[derive(Debug)]
+pub enum DivergenceKind {
+    Execution {
+        transaction_id: TransactionId,
+        field: &'static str,
+    },
+    StateRoot {
+        committed: FixedHash,
+        recomputed: FixedHash,
+    },
+}
+
+impl Display for ReplayDivergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            DivergenceKind::Execution {
+                ref transaction_id,
+                field,
+            } => write!(
+                f,
+                "block {} at height {}: the {} of transaction {} differs from the committed execution",
+                self.block_id, self.height, field, transaction_id
+            ),
+            DivergenceKind::StateRoot {
+                ref committed,
+                ref recomputed,
+            } => write!(
+                f,
+                "block {} at height {}: committed state root {} but recomputed {}",
+                self.block_id, self.height, committed, recomputed
+            ),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateStoreMaintenanceConfig {
+    /// If false, no automatic maintenance is performed
+    pub enabled: bool,
+    /// If false, only the query planner statistics are refreshed and the database file is never compacted
+    pub vacuum: bool,
+    /// The minimum time to wait after an epoch transition before running maintenance
+    #[serde(with = "serializers::seconds")]
+    pub delay_after_epoch_change: Duration,
+    /// Maintenance only runs once no blocks have been committed for this long
+    #[serde(with = "serializers::seconds")]
+    pub quiet_period: Duration,
+    /// The maximum time to wait for a quiet period after an epoch transition. Once elapsed, maintenance runs
+    /// regardless of consensus activity.
+    #[serde(with = "serializers::seconds")]
+    pub max_wait: Duration,
+}
+
+impl Default for StateStoreMaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            vacuum: true,
+            delay_after_epoch_change: Duration::from_secs(60),
+            quiet_period: Duration::from_secs(10),
+            max_wait: Duration::from_secs(30 * 60),
+        }
+    }
+}
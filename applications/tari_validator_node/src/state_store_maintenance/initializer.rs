@@ -0,0 +1,39 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::PeerAddress;
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tokio::{task, task::JoinHandle};
+
+#[cfg(feature = "metrics")]
+use super::metrics::PrometheusStateStoreMaintenanceMetrics;
+use super::{service::StateStoreMaintenanceService, StateStoreMaintenanceConfig};
+use crate::consensus::ConsensusHandle;
+
+const LOG_TARGET: &str = "tari::dan::validator_node::state_store_maintenance";
+
+pub fn spawn(
+    config: StateStoreMaintenanceConfig,
+    state_store: SqliteStateStore<PeerAddress>,
+    mut consensus_handle: ConsensusHandle,
+    shutdown: ShutdownSignal,
+    #[cfg(feature = "metrics")] metrics_registry: &prometheus::Registry,
+) -> JoinHandle<anyhow::Result<()>> {
+    #[cfg(feature = "metrics")]
+    let metrics = PrometheusStateStoreMaintenanceMetrics::new(metrics_registry);
+    let service = StateStoreMaintenanceService::new(
+        config,
+        state_store,
+        consensus_handle.subscribe_to_hotstuff_events(),
+        shutdown,
+        #[cfg(feature = "metrics")]
+        metrics,
+    );
+
+    let join_handle = task::spawn(service.run());
+    debug!(target: LOG_TARGET, "Spawning state store maintenance service (task: {:?})", join_handle);
+
+    join_handle
+}
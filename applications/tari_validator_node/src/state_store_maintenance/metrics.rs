@@ -0,0 +1,55 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use prometheus::{IntCounter, IntGauge, Registry};
+
+use crate::metrics::CollectorRegister;
+
+#[derive(Debug, Clone)]
+pub struct PrometheusStateStoreMaintenanceMetrics {
+    runs: IntCounter,
+    errors: IntCounter,
+    last_duration_ms: IntGauge,
+    database_size: IntGauge,
+}
+
+impl PrometheusStateStoreMaintenanceMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            runs: IntCounter::new("state_store_maintenance_runs", "Number of state store maintenance runs")
+                .unwrap()
+                .register_at(registry),
+            errors: IntCounter::new(
+                "state_store_maintenance_errors",
+                "Number of failed state store maintenance runs",
+            )
+            .unwrap()
+            .register_at(registry),
+            last_duration_ms: IntGauge::new(
+                "state_store_maintenance_last_duration_ms",
+                "Duration of the last state store maintenance run in milliseconds",
+            )
+            .unwrap()
+            .register_at(registry),
+            database_size: IntGauge::new(
+                "state_store_database_size_bytes",
+                "Size of the state store database file",
+            )
+            .unwrap()
+            .register_at(registry),
+        }
+    }
+
+    pub fn on_maintenance_completed(&mut self, duration: Duration, database_size: u64) {
+        self.runs.inc();
+        self.last_duration_ms
+            .set(i64::try_from(duration.as_millis()).unwrap_or(i64::MAX));
+        self.database_size.set(i64::try_from(database_size).unwrap_or(i64::MAX));
+    }
+
+    pub fn on_maintenance_error<E: ToString>(&mut self, _err: &E) {
+        self.errors.inc();
+    }
+}
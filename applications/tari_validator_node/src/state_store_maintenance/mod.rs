@@ -0,0 +1,16 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Periodic maintenance of the state store. After each epoch transition, once consensus has been quiet for a while,
+//! the database statistics are refreshed (ANALYZE) and the database file is compacted (VACUUM). Without this, long
+//! running nodes suffer from degraded query plans and a bloated database file.
+
+mod config;
+pub use config::StateStoreMaintenanceConfig;
+
+mod initializer;
+pub use initializer::spawn;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+mod service;
@@ -0,0 +1,173 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use anyhow::anyhow;
+use log::*;
+use tari_consensus::hotstuff::HotstuffEvent;
+use tari_dan_common_types::{Epoch, PeerAddress};
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tokio::{
+    sync::broadcast,
+    task,
+    time::{self, Instant},
+};
+
+#[cfg(feature = "metrics")]
+use super::metrics::PrometheusStateStoreMaintenanceMetrics;
+use super::StateStoreMaintenanceConfig;
+
+const LOG_TARGET: &str = "tari::dan::validator_node::state_store_maintenance";
+
+/// Maintenance that has been scheduled for an epoch but has not yet run
+#[derive(Debug, Clone, Copy)]
+struct PendingMaintenance {
+    epoch: Epoch,
+    not_before: Instant,
+    deadline: Instant,
+}
+
+pub struct StateStoreMaintenanceService {
+    config: StateStoreMaintenanceConfig,
+    state_store: SqliteStateStore<PeerAddress>,
+    hotstuff_events: broadcast::Receiver<HotstuffEvent>,
+    shutdown: ShutdownSignal,
+    pending: Option<PendingMaintenance>,
+    last_activity: Instant,
+    #[cfg(feature = "metrics")]
+    metrics: PrometheusStateStoreMaintenanceMetrics,
+}
+
+impl StateStoreMaintenanceService {
+    pub fn new(
+        config: StateStoreMaintenanceConfig,
+        state_store: SqliteStateStore<PeerAddress>,
+        hotstuff_events: broadcast::Receiver<HotstuffEvent>,
+        shutdown: ShutdownSignal,
+        #[cfg(feature = "metrics")] metrics: PrometheusStateStoreMaintenanceMetrics,
+    ) -> Self {
+        Self {
+            config,
+            state_store,
+            hotstuff_events,
+            shutdown,
+            pending: None,
+            last_activity: Instant::now(),
+            #[cfg(feature = "metrics")]
+            metrics,
+        }
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            info!(target: LOG_TARGET, "State store maintenance is disabled");
+            return Ok(());
+        }
+
+        loop {
+            let next_run = self.next_run_at();
+            tokio::select! {
+                event = self.hotstuff_events.recv() => {
+                    match event {
+                        Ok(event) => self.handle_hotstuff_event(event),
+                        // Lagging behind means consensus is busy
+                        Err(broadcast::error::RecvError::Lagged(_)) => self.last_activity = Instant::now(),
+                        Err(broadcast::error::RecvError::Closed) => {
+                            info!(target: LOG_TARGET, "Hotstuff event channel closed. State store maintenance shutting down");
+                            break;
+                        },
+                    }
+                },
+                _ = time::sleep_until(next_run.unwrap_or_else(Instant::now)), if next_run.is_some() => {
+                    self.run_maintenance().await;
+                },
+                _ = self.shutdown.wait() => {
+                    info!(target: LOG_TARGET, "State store maintenance shutting down");
+                    break;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_hotstuff_event(&mut self, event: HotstuffEvent) {
+        match event {
+            HotstuffEvent::EpochChanged { epoch, .. } => {
+                let now = Instant::now();
+                let not_before = now + self.config.delay_after_epoch_change;
+                debug!(
+                    target: LOG_TARGET,
+                    "Scheduling state store maintenance for {} in {:.2?}", epoch, self.config.delay_after_epoch_change
+                );
+                self.pending = Some(PendingMaintenance {
+                    epoch,
+                    not_before,
+                    deadline: not_before + self.config.max_wait,
+                });
+            },
+            HotstuffEvent::BlockCommitted { .. } => {
+                self.last_activity = Instant::now();
+            },
+            _ => {},
+        }
+    }
+
+    /// Returns the instant at which pending maintenance should run, assuming no further consensus activity.
+    fn next_run_at(&self) -> Option<Instant> {
+        let pending = self.pending?;
+        let quiet_at = self.last_activity + self.config.quiet_period;
+        Some(pending.not_before.max(quiet_at).min(pending.deadline))
+    }
+
+    async fn run_maintenance(&mut self) {
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        if Instant::now() >= pending.deadline {
+            warn!(
+                target: LOG_TARGET,
+                "No quiet period occurred within {:.2?} of {}. Running state store maintenance anyway.",
+                self.config.max_wait,
+                pending.epoch
+            );
+        }
+
+        info!(target: LOG_TARGET, "🧹 Running state store maintenance for {}", pending.epoch);
+        let state_store = self.state_store.clone();
+        let vacuum = self.config.vacuum;
+        // These operations hold the database connection for their duration, so we run them on a blocking thread
+        let result = task::spawn_blocking(move || {
+            let timer = std::time::Instant::now();
+            let size_before = state_store.database_size()?;
+            state_store.analyze()?;
+            if vacuum {
+                state_store.vacuum()?;
+            }
+            let size_after = state_store.database_size()?;
+            Ok::<_, anyhow::Error>((timer.elapsed(), size_before, size_after))
+        })
+        .await
+        .map_err(|e| anyhow!("State store maintenance task panicked: {e}"))
+        .and_then(|r| r);
+
+        match result {
+            Ok((elapsed, size_before, size_after)) => {
+                info!(
+                    target: LOG_TARGET,
+                    "🧹 State store maintenance completed in {:.2?}. Database size {} -> {} bytes",
+                    elapsed,
+                    size_before,
+                    size_after
+                );
+                #[cfg(feature = "metrics")]
+                self.metrics.on_maintenance_completed(elapsed, size_after);
+            },
+            Err(err) => {
+                error!(target: LOG_TARGET, "State store maintenance failed: {}", err);
+                #[cfg(feature = "metrics")]
+                self.metrics.on_maintenance_error(&err);
+            },
+        }
+    }
+}
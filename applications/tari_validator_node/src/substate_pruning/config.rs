@@ -0,0 +1,26 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubstatePruningConfig {
+    /// If false, historical substate versions are never pruned
+    pub enabled: bool,
+    /// The number of epochs for which destroyed substate versions and state transitions are retained. Peers that are
+    /// further behind must sync from a state snapshot, so this should be greater than the state snapshot interval.
+    pub retention_epochs: u64,
+    /// The maximum number of substates that are deleted in a single database transaction
+    pub batch_size: usize,
+}
+
+impl Default for SubstatePruningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_epochs: 10,
+            batch_size: 1000,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::{Arc, RwLock};
+
+use tari_validator_node_client::types::SubstatePruningStatus;
+
+#[derive(Debug, Clone)]
+pub struct SubstatePruningHandle {
+    status: Arc<RwLock<SubstatePruningStatus>>,
+}
+
+impl SubstatePruningHandle {
+    pub(super) fn new(status: SubstatePruningStatus) -> Self {
+        Self {
+            status: Arc::new(RwLock::new(status)),
+        }
+    }
+
+    pub fn status(&self) -> SubstatePruningStatus {
+        self.status.read().unwrap().clone()
+    }
+
+    pub(super) fn update_status<F: FnOnce(&mut SubstatePruningStatus)>(&self, f: F) {
+        f(&mut self.status.write().unwrap());
+    }
+}
@@ -0,0 +1,39 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::PeerAddress;
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_validator_node_client::types::SubstatePruningStatus;
+use tokio::{task, task::JoinHandle};
+
+use super::{service::SubstatePruningService, SubstatePruningConfig, SubstatePruningHandle};
+use crate::consensus::ConsensusHandle;
+
+const LOG_TARGET: &str = "tari::dan::validator_node::substate_pruning";
+
+pub fn spawn(
+    config: SubstatePruningConfig,
+    state_store: SqliteStateStore<PeerAddress>,
+    mut consensus_handle: ConsensusHandle,
+    shutdown: ShutdownSignal,
+) -> (JoinHandle<anyhow::Result<()>>, SubstatePruningHandle) {
+    let handle = SubstatePruningHandle::new(SubstatePruningStatus {
+        enabled: config.enabled,
+        retention_epochs: config.retention_epochs,
+        ..Default::default()
+    });
+    let service = SubstatePruningService::new(
+        config,
+        state_store,
+        consensus_handle.subscribe_to_hotstuff_events(),
+        handle.clone(),
+        shutdown,
+    );
+
+    let join_handle = task::spawn(service.run());
+    debug!(target: LOG_TARGET, "Spawning substate pruning service (task: {:?})", join_handle);
+
+    (join_handle, handle)
+}
@@ -0,0 +1,18 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Pruning of historical substate versions. After each epoch transition, substate versions that were destroyed before
+//! the retention window and the state transitions that precede it are deleted from the state store. The current
+//! version of every substate is always kept, and peers that need state from before the retention window sync from a
+//! state snapshot instead.
+
+mod config;
+pub use config::SubstatePruningConfig;
+
+mod handle;
+pub use handle::SubstatePruningHandle;
+
+mod initializer;
+pub use initializer::spawn;
+
+mod service;
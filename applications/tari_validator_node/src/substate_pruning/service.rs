@@ -0,0 +1,159 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Instant;
+
+use anyhow::anyhow;
+use log::*;
+use tari_consensus::hotstuff::HotstuffEvent;
+use tari_dan_common_types::{Epoch, PeerAddress};
+use tari_dan_storage::{
+    consensus_models::{StateTransition, SubstateRecord},
+    StateStore,
+    StorageError,
+};
+use tari_shutdown::ShutdownSignal;
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_validator_node_client::types::SubstatePruningRun;
+use tokio::{sync::broadcast, task};
+
+use super::{SubstatePruningConfig, SubstatePruningHandle};
+
+const LOG_TARGET: &str = "tari::dan::validator_node::substate_pruning";
+
+pub struct SubstatePruningService {
+    config: SubstatePruningConfig,
+    state_store: SqliteStateStore<PeerAddress>,
+    hotstuff_events: broadcast::Receiver<HotstuffEvent>,
+    handle: SubstatePruningHandle,
+    shutdown: ShutdownSignal,
+}
+
+impl SubstatePruningService {
+    pub fn new(
+        config: SubstatePruningConfig,
+        state_store: SqliteStateStore<PeerAddress>,
+        hotstuff_events: broadcast::Receiver<HotstuffEvent>,
+        handle: SubstatePruningHandle,
+        shutdown: ShutdownSignal,
+    ) -> Self {
+        Self {
+            config,
+            state_store,
+            hotstuff_events,
+            handle,
+            shutdown,
+        }
+    }
+
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        if !self.config.enabled {
+            info!(target: LOG_TARGET, "Substate pruning is disabled");
+            return Ok(());
+        }
+
+        loop {
+            tokio::select! {
+                event = self.hotstuff_events.recv() => {
+                    match event {
+                        Ok(HotstuffEvent::EpochChanged { epoch, .. }) => self.prune(epoch).await,
+                        Ok(_) => {},
+                        // A missed epoch change is caught up on the next epoch change
+                        Err(broadcast::error::RecvError::Lagged(_)) => {},
+                        Err(broadcast::error::RecvError::Closed) => {
+                            info!(target: LOG_TARGET, "Hotstuff event channel closed. Substate pruning shutting down");
+                            break;
+                        },
+                    }
+                },
+                _ = self.shutdown.wait() => {
+                    info!(target: LOG_TARGET, "Substate pruning shutting down");
+                    break;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn prune(&mut self, epoch: Epoch) {
+        let prune_before = epoch.saturating_sub(Epoch(self.config.retention_epochs));
+        let pruned_before = self.handle.status().pruned_before_epoch;
+        if prune_before.is_zero() || pruned_before.is_some_and(|pruned| pruned >= prune_before) {
+            return;
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "✂️ Pruning substate versions destroyed before {} (current: {})", prune_before, epoch
+        );
+        self.handle.update_status(|status| status.is_running = true);
+        let state_store = self.state_store.clone();
+        let batch_size = self.config.batch_size;
+        // Pruning may delete many rows, so it is run on a blocking thread
+        let result = task::spawn_blocking(move || prune_before_epoch(&state_store, epoch, prune_before, batch_size))
+            .await
+            .map_err(|e| anyhow!("Substate pruning task panicked: {e}"))
+            .and_then(|r| r.map_err(Into::into));
+
+        match result {
+            Ok(run) => {
+                info!(
+                    target: LOG_TARGET,
+                    "✂️ Pruned {} substate version(s) and {} state transition(s) before {} in {}ms ({} bytes)",
+                    run.num_substates_pruned,
+                    run.num_state_transitions_pruned,
+                    prune_before,
+                    run.duration_ms,
+                    run.bytes_reclaimed
+                );
+                self.handle.update_status(|status| {
+                    status.is_running = false;
+                    status.pruned_before_epoch = Some(prune_before);
+                    status.total_substates_pruned += run.num_substates_pruned;
+                    status.total_state_transitions_pruned += run.num_state_transitions_pruned;
+                    status.total_bytes_reclaimed += run.bytes_reclaimed;
+                    status.last_run = Some(run);
+                });
+            },
+            Err(err) => {
+                error!(target: LOG_TARGET, "Substate pruning failed: {}", err);
+                self.handle.update_status(|status| status.is_running = false);
+            },
+        }
+    }
+}
+
+fn prune_before_epoch(
+    state_store: &SqliteStateStore<PeerAddress>,
+    epoch: Epoch,
+    prune_before: Epoch,
+    batch_size: usize,
+) -> Result<SubstatePruningRun, StorageError> {
+    let timer = Instant::now();
+    // State transitions are pruned first so that the substates they reference can be pruned
+    let num_state_transitions_pruned =
+        state_store.with_write_tx(|tx| StateTransition::prune_before(tx, prune_before))?;
+
+    // Substates are pruned in batches so that the database is not locked for long periods
+    let mut num_substates_pruned = 0;
+    let mut bytes_reclaimed = 0;
+    loop {
+        let pruned =
+            state_store.with_write_tx(|tx| SubstateRecord::prune_destroyed_before(tx, prune_before, batch_size))?;
+        num_substates_pruned += pruned.num_substates;
+        bytes_reclaimed += pruned.num_bytes;
+        if pruned.num_substates < batch_size as u64 {
+            break;
+        }
+    }
+
+    Ok(SubstatePruningRun {
+        epoch,
+        pruned_before_epoch: prune_before,
+        num_substates_pruned,
+        num_state_transitions_pruned,
+        bytes_reclaimed,
+        duration_ms: u64::try_from(timer.elapsed().as_millis()).unwrap_or(u64::MAX),
+    })
+}
@@ -23,6 +23,12 @@ pub enum TransactionValidationError {
     InvalidTemplateAddress(#[from] TemplateManagerError),
     #[error("No fee instructions")]
     NoFeeInstructions,
+    #[error("Transaction {transaction_id} declares a fee of {declared_fee} which is less than the minimum fee {min_fee}")]
+    FeeTooLow {
+        transaction_id: TransactionId,
+        declared_fee: u64,
+        min_fee: u64,
+    },
     #[error("Output substate exists in transaction {transaction_id}")]
     OutputSubstateExists { transaction_id: TransactionId },
     #[error("Validator fee claim instruction in transaction {transaction_id} contained invalid epoch {given_epoch}")]
@@ -23,3 +23,42 @@ impl Validator<Transaction> for FeeTransactionValidator {
         Ok(())
     }
 }
+
+/// Rejects transactions that declare a maximum fee below the configured minimum
+#[derive(Debug)]
+pub struct MinimumFeeValidator {
+    min_fee: u64,
+}
+
+impl MinimumFeeValidator {
+    pub fn new(min_fee: u64) -> Self {
+        Self { min_fee }
+    }
+}
+
+impl Validator<Transaction> for MinimumFeeValidator {
+    type Context = ();
+    type Error = TransactionValidationError;
+
+    fn validate(&self, _context: &(), transaction: &Transaction) -> Result<(), TransactionValidationError> {
+        if self.min_fee == 0 {
+            return Ok(());
+        }
+        let declared_fee = transaction
+            .declared_max_fee()
+            .and_then(|fee| fee.as_u64_checked())
+            .unwrap_or(0);
+        if declared_fee < self.min_fee {
+            warn!(
+                target: LOG_TARGET,
+                "MinimumFeeValidator - FAIL: Declared fee {} is less than the minimum fee {}", declared_fee, self.min_fee
+            );
+            return Err(TransactionValidationError::FeeTooLow {
+                transaction_id: *transaction.id(),
+                declared_fee,
+                min_fee: self.min_fee,
+            });
+        }
+        Ok(())
+    }
+}
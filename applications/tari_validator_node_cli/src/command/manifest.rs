@@ -37,8 +37,11 @@ impl ManifestSubcommand {
         match self {
             ManifestSubcommand::Check(args) => {
                 let contents = get_contents(args.manifest)?;
-                let instructions =
-                    tari_transaction_manifest::parse_manifest(&contents, HashMap::default(), HashMap::default())?;
+                let instructions = tari_transaction_manifest::parse_manifest(
+                    &contents,
+                    parse_globals(args.globals)?,
+                    HashMap::default(),
+                )?;
                 // TODO: improve output
                 println!("Instructions: {:#?}", instructions.instructions);
                 println!("Fee Instructions: {:#?}", instructions.fee_instructions);
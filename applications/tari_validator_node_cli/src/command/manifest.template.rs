@@ -5,7 +5,10 @@ use template_7c793e0e945c10228ceb08bfa7ccd1e673ba609dc7200f6a7721278de6231625 as
 
 fn main() {
     info!("Hello, world!");
-    // Account::new();
-    // let mut account = global!["account"];
+    // Globals are provided when submitting the manifest e.g.
+    // `transactions submit --manifest <file> -g account=component_... -g faucet=component_...`
+    // let account = global!["account"];
+    // let faucet = global!["faucet"];
+    // let funds = faucet.take_free_coins();
     // account.deposit(funds);
 }
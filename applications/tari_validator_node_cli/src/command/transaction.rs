@@ -81,7 +81,13 @@ pub struct GetArgs {
 #[derive(Debug, Args, Clone)]
 pub struct SubmitArgs {
     #[clap(subcommand)]
-    pub instruction: CliInstruction,
+    pub instruction: Option<CliInstruction>,
+    /// Submit the instructions in a transaction manifest file instead of a single instruction
+    #[clap(long, short = 'm')]
+    pub manifest: Option<PathBuf>,
+    /// A list of globals to be used by the manifest in the format `name=value`
+    #[clap(long, short = 'g', requires = "manifest")]
+    pub input_variables: Vec<String>,
     #[clap(flatten)]
     pub common: CommonSubmitArgs,
 }
@@ -173,7 +179,29 @@ pub async fn handle_submit(
     base_dir: impl AsRef<Path>,
     client: &mut ValidatorNodeClient,
 ) -> Result<SubmitTransactionResponse, anyhow::Error> {
-    let SubmitArgs { instruction, common } = args;
+    let SubmitArgs {
+        instruction,
+        manifest,
+        input_variables,
+        common,
+    } = args;
+    let instruction = match (instruction, manifest) {
+        (Some(_), Some(_)) => return Err(anyhow!("Specify either an instruction or a manifest, not both")),
+        (None, Some(manifest)) => {
+            return handle_submit_manifest(
+                SubmitManifestArgs {
+                    manifest,
+                    input_variables,
+                    common,
+                },
+                base_dir,
+                client,
+            )
+            .await;
+        },
+        (None, None) => return Err(anyhow!("Either an instruction or --manifest must be provided")),
+        (Some(instruction), None) => instruction,
+    };
     let instruction = match instruction {
         CliInstruction::CallFunction {
             template_address,
@@ -210,7 +238,14 @@ async fn handle_submit_manifest(
         manifest::parse_globals(args.input_variables)?,
         Default::default(),
     )?;
-    submit_transaction(instructions.instructions, args.common, base_dir, client).await
+    submit_transaction_with_fee_instructions(
+        instructions.fee_instructions,
+        instructions.instructions,
+        args.common,
+        base_dir,
+        client,
+    )
+    .await
 }
 
 pub async fn submit_transaction(
@@ -218,6 +253,16 @@ pub async fn submit_transaction(
     common: CommonSubmitArgs,
     base_dir: impl AsRef<Path>,
     client: &mut ValidatorNodeClient,
+) -> Result<SubmitTransactionResponse, anyhow::Error> {
+    submit_transaction_with_fee_instructions(vec![], instructions, common, base_dir, client).await
+}
+
+pub async fn submit_transaction_with_fee_instructions(
+    fee_instructions: Vec<Instruction>,
+    instructions: Vec<Instruction>,
+    common: CommonSubmitArgs,
+    base_dir: impl AsRef<Path>,
+    client: &mut ValidatorNodeClient,
 ) -> Result<SubmitTransactionResponse, anyhow::Error> {
     let component_manager = ComponentManager::init(base_dir.as_ref())?;
     let key_manager = KeyManager::init(base_dir)?;
@@ -226,7 +271,7 @@ pub async fn submit_transaction(
         .ok_or_else(|| anyhow::anyhow!("No active key. Use `keys use [public key hex]` to set one."))?;
 
     let inputs = if common.inputs.is_empty() {
-        load_inputs(&instructions, &component_manager)?
+        load_inputs(fee_instructions.iter().chain(&instructions), &component_manager)?
     } else {
         common.inputs
     };
@@ -234,10 +279,11 @@ pub async fn submit_transaction(
     // Convert to shard id
     let inputs = inputs.into_iter().collect::<Vec<_>>();
 
-    summarize_request(&instructions, &inputs, 1, common.dry_run);
+    summarize_request(&fee_instructions, &instructions, &inputs, 1, common.dry_run);
     println!();
 
     let transaction = Transaction::builder()
+        .with_fee_instructions(fee_instructions)
         .with_instructions(instructions)
         .with_inputs(inputs)
         .sign(&key.secret_key)
@@ -318,7 +364,13 @@ async fn wait_for_transaction_result(
     }
 }
 
-fn summarize_request(instructions: &[Instruction], inputs: &[SubstateRequirement], fee: u64, is_dry_run: bool) {
+fn summarize_request(
+    fee_instructions: &[Instruction],
+    instructions: &[Instruction],
+    inputs: &[SubstateRequirement],
+    fee: u64,
+    is_dry_run: bool,
+) {
     if is_dry_run {
         println!("NOTE: Dry run is enabled. This transaction will not be processed by the network.");
         println!();
@@ -333,6 +385,13 @@ fn summarize_request(instructions: &[Instruction], inputs: &[SubstateRequirement
         }
     }
     println!();
+    if !fee_instructions.is_empty() {
+        println!("🌟 Submitting fee instructions:");
+        for instruction in fee_instructions {
+            println!("- {}", instruction);
+        }
+        println!();
+    }
     println!("🌟 Submitting instructions:");
     for instruction in instructions {
         println!("- {}", instruction);
@@ -580,8 +639,8 @@ fn format_tuple(subtypes: &[Type], result: &InstructionResult) -> String {
     format!("{}: {}", tuple_type, result_json)
 }
 
-fn load_inputs(
-    instructions: &[Instruction],
+fn load_inputs<'a, I: IntoIterator<Item = &'a Instruction>>(
+    instructions: I,
     component_manager: &ComponentManager,
 ) -> Result<Vec<SubstateRequirement>, anyhow::Error> {
     let mut inputs = Vec::new();
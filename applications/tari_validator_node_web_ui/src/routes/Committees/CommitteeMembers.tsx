@@ -30,7 +30,7 @@ import Committee from "./CommitteeSingle";
 import { VNContext } from "../../App";
 
 async function getMembers(currentEpoch: number, shardKey: string, publicKey: string) {
-  const committee = await getCommittee({ epoch: currentEpoch, substate_address: shardKey });
+  const committee = await getCommittee({ epoch: currentEpoch, substate_address: shardKey, shard_group: null });
   const committeeMembers = committee?.committee?.members;
   if (!committeeMembers || committeeMembers.length === 0) {
     throw new Error("Committee members not found");
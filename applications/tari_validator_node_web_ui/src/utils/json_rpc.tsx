@@ -33,11 +33,16 @@ import type {
   GetCommitteeRequest,
   GetCommitteeResponse,
   VNGetCommsStatsResponse,
+  VNGetBandwidthStatsResponse,
   VNGetConnectionsResponse,
   GetEpochManagerStatsResponse,
   VNGetIdentityResponse,
   GetMempoolStatsResponse,
+  GetMempoolTransactionsResponse,
+  EvictMempoolTransactionRequest,
+  EvictMempoolTransactionResponse,
   GetNetworkCommitteeResponse,
+  GetNetworkCommitteesRequest,
   GetRecentTransactionsResponse,
   GetShardKeyRequest,
   GetShardKeyResponse,
@@ -142,13 +147,20 @@ export const getTemplates = (request: GetTemplatesRequest): Promise<GetTemplates
 export const getIdentity = (): Promise<VNGetIdentityResponse> => jsonRpc("get_identity");
 
 export const getMempoolStats = (): Promise<GetMempoolStatsResponse> => jsonRpc("get_mempool_stats");
+export const getMempoolTransactions = (): Promise<GetMempoolTransactionsResponse> =>
+  jsonRpc("get_mempool_transactions");
+export const evictMempoolTransaction = (
+  request: EvictMempoolTransactionRequest,
+): Promise<EvictMempoolTransactionResponse> => jsonRpc("evict_mempool_transaction", request);
 export const getEpochManagerStats = (): Promise<GetEpochManagerStatsResponse> => jsonRpc("get_epoch_manager_stats");
 export const getShardKey = (request: GetShardKeyRequest): Promise<GetShardKeyResponse> =>
   jsonRpc("get_shard_key", request);
 export const getCommittee = (request: GetCommitteeRequest): Promise<GetCommitteeResponse> =>
   jsonRpc("get_committee", request);
 export const getAllVns = (request: VNGetAllVnsRequest): Promise<VNGetAllVnsResponse> => jsonRpc("get_all_vns", request);
-export const getNetworkCommittees = (): Promise<GetNetworkCommitteeResponse> => jsonRpc("get_network_committees", {});
+export const getNetworkCommittees = (
+  request: GetNetworkCommitteesRequest = { epoch: null },
+): Promise<GetNetworkCommitteeResponse> => jsonRpc("get_network_committees", request);
 export const getFees = (request: VNGetValidatorFeesRequest): Promise<VNGetValidatorFeesResponse> =>
   jsonRpc("get_fees", request);
 
@@ -156,3 +168,4 @@ export const getFees = (request: VNGetValidatorFeesRequest): Promise<VNGetValida
 export const addPeer = (request: VNAddPeerRequest) => jsonRpc("add_peer", request);
 export const getCommsStats = (): Promise<VNGetCommsStatsResponse> => jsonRpc("get_comms_stats");
 export const getConnections = (): Promise<VNGetConnectionsResponse> => jsonRpc("get_connections");
+export const getBandwidthStats = (): Promise<VNGetBandwidthStatsResponse> => jsonRpc("get_bandwidth_stats");
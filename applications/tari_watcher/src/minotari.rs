@@ -14,7 +14,7 @@ use tari_sidechain::EvictionProof;
 use tonic::transport::Channel;
 use url::Url;
 
-use crate::helpers::read_registration_file;
+use crate::helpers::{read_registration_file, ValidatorNodeRegistration};
 
 #[derive(Clone)]
 pub struct MinotariNodes {
@@ -121,6 +121,17 @@ impl MinotariNodes {
                     self.node_registration_file.display()
                 )
             })?;
+        let resp = self.send_registration(info).await?;
+
+        info!("VN registration request sent successfully");
+
+        Ok(resp)
+    }
+
+    async fn send_registration(
+        &self,
+        info: ValidatorNodeRegistration,
+    ) -> anyhow::Result<RegisterValidatorNodeResponse> {
         let sig = info.signature.signature();
         let resp = self
             .connect_wallet()
@@ -142,8 +153,6 @@ impl MinotariNodes {
             bail!("Failed to register VN: {}", resp.failure_message);
         }
 
-        info!("VN registration request sent successfully");
-
         Ok(resp)
     }
 
@@ -152,7 +161,7 @@ impl MinotariNodes {
         transaction_def: LayerOneTransactionDef<serde_json::Value>,
     ) -> anyhow::Result<()> {
         let proof_type = transaction_def.proof_type;
-        let resp = match proof_type {
+        let tx_id = match proof_type {
             LayerOnePayloadType::EvictionProof => {
                 let proof = serde_json::from_value::<EvictionProof>(transaction_def.payload)?;
                 info!(
@@ -171,11 +180,16 @@ impl MinotariNodes {
                         sidechain_deployment_key: vec![],
                     })
                     .await?;
-                resp.into_inner()
+                resp.into_inner().tx_id
+            },
+            LayerOnePayloadType::ValidatorNodeRegistration => {
+                let registration = serde_json::from_value::<ValidatorNodeRegistration>(transaction_def.payload)?;
+                info!("Preparing to send a re-registration transaction for VN {}", registration.public_key);
+                self.send_registration(registration).await?.transaction_id
             },
         };
 
-        info!("{} transaction sent successfully (tx_id={})", proof_type, resp.tx_id);
+        info!("{} transaction sent successfully (tx_id={})", proof_type, tx_id);
 
         Ok(())
     }
@@ -89,6 +89,9 @@ export function rejectReasonToString(reason: RejectReason | null): string {
   if ("FeesNotPaid" in reason) {
     return `FeesNotPaid(${reason.FeesNotPaid})`;
   }
+  if ("SandboxViolation" in reason) {
+    return `SandboxViolation(${reason.SandboxViolation})`;
+  }
   if ("ForeignShardGroupDecidedToAbort" in reason) {
     return `ForeignShardGroupDecidedToAbort(shard group ${reason.ForeignShardGroupDecidedToAbort.start_shard}-${reason.ForeignShardGroupDecidedToAbort.end_shard})`;
   }
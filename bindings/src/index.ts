@@ -4,9 +4,11 @@
 export * from "./types/AbortReason";
 export * from "./types/AccessRule";
 export * from "./types/Account";
+export * from "./types/AccountKind";
 export * from "./types/Amount";
 export * from "./types/Arg";
 export * from "./types/ArgDef";
+export * from "./types/AuditedVaultBalance";
 export * from "./types/AuthHook";
 export * from "./types/Block";
 export * from "./types/BlockHeader";
@@ -27,6 +29,7 @@ export * from "./types/ConfidentialOutputStatement";
 export * from "./types/ConfidentialStatement";
 export * from "./types/ConfidentialTransferInputSelection";
 export * from "./types/ConfidentialWithdrawProof";
+export * from "./types/Contact";
 export * from "./types/Decision";
 export * from "./types/ElgamalVerifiableBalance";
 export * from "./types/EntityId";
@@ -46,7 +49,12 @@ export * from "./types/FeeReceipt";
 export * from "./types/FeeSource";
 export * from "./types/FinalizeResult";
 export * from "./types/ForeignProposalAtom";
+export * from "./types/ForeignReceiveGap";
 export * from "./types/FunctionDef";
+export * from "./types/GasUsage";
+export * from "./types/HistoricalBalanceSource";
+export * from "./types/HistoricalVaultBalance";
+export * from "./types/ImportedTransaction";
 export * from "./types/IndexedValue";
 export * from "./types/IndexedWellKnownTypes";
 export * from "./types/Instruction";
@@ -57,8 +65,12 @@ export * from "./types/LeaderFee";
 export * from "./types/LockFlag";
 export * from "./types/LogEntry";
 export * from "./types/LogLevel";
+export * from "./types/MemoryUsage";
 export * from "./types/Metadata";
 export * from "./types/MintConfidentialOutputAtom";
+export * from "./types/Misbehaviour";
+export * from "./types/MisbehaviourEvidence";
+export * from "./types/MisbehaviourKind";
 export * from "./types/NetworkCommitteeInfo";
 export * from "./types/NodeHeight";
 export * from "./types/NonFungible";
@@ -74,29 +86,41 @@ export * from "./types/Ordering";
 export * from "./types/OwnerRule";
 export * from "./types/PeerAddress";
 export * from "./types/ProofId";
+export * from "./types/ProposalEquivocationAtom";
 export * from "./types/PublishedTemplate";
 export * from "./types/PublishedTemplateAddress";
+export * from "./types/PublishedTemplateModel";
 export * from "./types/QuorumCertificate";
 export * from "./types/QuorumDecision";
+export * from "./types/ReceivedForeignIndex";
 export * from "./types/RejectReason";
 export * from "./types/RequireRule";
 export * from "./types/Resource";
 export * from "./types/ResourceAccessRules";
 export * from "./types/ResourceAddress";
+export * from "./types/ResourceAmount";
 export * from "./types/ResourceContainer";
 export * from "./types/ResourceType";
 export * from "./types/RestrictedAccessRule";
 export * from "./types/ResumeNodeAtom";
 export * from "./types/RuleRequirement";
+export * from "./types/ScheduledTransaction";
+export * from "./types/ScheduledTransactionStatus";
 export * from "./types/Shard";
 export * from "./types/ShardEvidence";
 export * from "./types/ShardGroup";
 export * from "./types/ShardGroupEvidence";
+export * from "./types/ShardGroupTrace";
+export * from "./types/ShardGroupTraceStatus";
+export * from "./types/SignedVote";
 export * from "./types/Substate";
+export * from "./types/SubstateAccess";
+export * from "./types/SubstateAccessStats";
 export * from "./types/SubstateAddress";
 export * from "./types/SubstateDestroyed";
 export * from "./types/SubstateDiff";
 export * from "./types/SubstateId";
+export * from "./types/SubstateInclusionProof";
 export * from "./types/SubstateLockType";
 export * from "./types/SubstateRecord";
 export * from "./types/SubstateRequirement";
@@ -104,17 +128,25 @@ export * from "./types/SubstateRequirementLockIntent";
 export * from "./types/SubstateType";
 export * from "./types/SubstateValue";
 export * from "./types/SuspendNodeAtom";
+export * from "./types/TemplateAbort";
 export * from "./types/TemplateDef";
 export * from "./types/TemplateDefV1";
+export * from "./types/TemplateSubstateAccess";
+export * from "./types/TemplateType";
 export * from "./types/Transaction";
 export * from "./types/TransactionAtom";
+export * from "./types/TransactionBlockInclusion";
+export * from "./types/TransactionEvent";
+export * from "./types/TransactionFailure";
 export * from "./types/TransactionPoolRecord";
 export * from "./types/TransactionPoolStage";
 export * from "./types/TransactionReceipt";
 export * from "./types/TransactionReceiptAddress";
 export * from "./types/TransactionResult";
+export * from "./types/TransactionSchedule";
 export * from "./types/TransactionSignature";
 export * from "./types/TransactionStatus";
+export * from "./types/TransactionTrace";
 export * from "./types/Type";
 export * from "./types/UnclaimedConfidentialOutput";
 export * from "./types/UnclaimedConfidentialOutputAddress";
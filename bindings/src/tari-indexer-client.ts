@@ -35,3 +35,45 @@ export * from "./types/tari-indexer-client/GetNonFungibleCollectionsResponse";
 export * from "./types/tari-indexer-client/IndexerSubmitTransactionResponse";
 export * from "./types/tari-indexer-client/IndexerConnectionDirection";
 export * from "./types/tari-indexer-client/ListSubstateItem";
+export * from "./types/tari-indexer-client/GetSubstateDiffRequest";
+export * from "./types/tari-indexer-client/GetSubstateDiffResponse";
+export * from "./types/tari-indexer-client/SubstateFieldChange";
+export * from "./types/tari-indexer-client/SubstateFieldChangeKind";
+export * from "./types/tari-indexer-client/ApiKeysIssueRequest";
+export * from "./types/tari-indexer-client/ApiKeysIssueResponse";
+export * from "./types/tari-indexer-client/ApiKeysRevokeRequest";
+export * from "./types/tari-indexer-client/ApiKeysRevokeResponse";
+export * from "./types/tari-indexer-client/ApiKeysListRequest";
+export * from "./types/tari-indexer-client/ApiKeysListResponse";
+export * from "./types/tari-indexer-client/ApiKeyInfo";
+export * from "./types/tari-indexer-client/ApiKeyUsage";
+export * from "./types/tari-indexer-client/GetPendingTransactionsRequest";
+export * from "./types/tari-indexer-client/GetPendingTransactionsResponse";
+export * from "./types/tari-indexer-client/GetPendingTransactionRequest";
+export * from "./types/tari-indexer-client/GetPendingTransactionResponse";
+export * from "./types/tari-indexer-client/IndexerPendingTransaction";
+export * from "./types/tari-indexer-client/PendingTransactionCommittee";
+export * from "./types/tari-indexer-client/GetEpochStatsRequest";
+export * from "./types/tari-indexer-client/GetEpochStatsResponse";
+export * from "./types/tari-indexer-client/IndexerEpochStats";
+export * from "./types/tari-indexer-client/IndexerQuery";
+export * from "./types/tari-indexer-client/IndexerQueryResult";
+export * from "./types/tari-indexer-client/IndexerQueryStatus";
+export * from "./types/tari-indexer-client/SubmitQueryRequest";
+export * from "./types/tari-indexer-client/SubmitQueryResponse";
+export * from "./types/tari-indexer-client/GetQueryResultRequest";
+export * from "./types/tari-indexer-client/GetQueryResultResponse";
+export * from "./types/tari-indexer-client/IndexerGetEventsRequest";
+export * from "./types/tari-indexer-client/IndexerGetEventsResponse";
+export * from "./types/tari-indexer-client/ValidatorMetadata";
+export * from "./types/tari-indexer-client/ValidatorIdentity";
+export * from "./types/tari-indexer-client/GetValidatorIdentityChallengeRequest";
+export * from "./types/tari-indexer-client/GetValidatorIdentityChallengeResponse";
+export * from "./types/tari-indexer-client/RegisterValidatorIdentityRequest";
+export * from "./types/tari-indexer-client/RegisterValidatorIdentityResponse";
+export * from "./types/tari-indexer-client/GetValidatorIdentityRequest";
+export * from "./types/tari-indexer-client/GetValidatorIdentityResponse";
+export * from "./types/tari-indexer-client/ListValidatorIdentitiesRequest";
+export * from "./types/tari-indexer-client/ListValidatorIdentitiesResponse";
+export * from "./types/tari-indexer-client/GetSubstateAccessStatsRequest";
+export * from "./types/tari-indexer-client/GetSubstateAccessStatsResponse";
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountKind } from "./AccountKind";
 import type { SubstateId } from "./SubstateId";
 
 export interface Account {
@@ -6,4 +7,5 @@ export interface Account {
   address: SubstateId;
   key_index: number;
   is_default: boolean;
+  kind: AccountKind;
 }
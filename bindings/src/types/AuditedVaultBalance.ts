@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "./Amount";
+import type { ComponentAddress } from "./ComponentAddress";
+import type { ResourceAddress } from "./ResourceAddress";
+import type { VaultId } from "./VaultId";
+
+export interface AuditedVaultBalance {
+  resource_address: ResourceAddress;
+  view_key_index: number;
+  vault_id: VaultId;
+  owner: ComponentAddress | null;
+  balance: Amount;
+  num_outputs: number;
+  num_unresolved: number;
+  vault_version: number;
+}
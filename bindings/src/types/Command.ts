@@ -2,6 +2,7 @@
 import type { EvictNodeAtom } from "./EvictNodeAtom";
 import type { ForeignProposalAtom } from "./ForeignProposalAtom";
 import type { MintConfidentialOutputAtom } from "./MintConfidentialOutputAtom";
+import type { ProposalEquivocationAtom } from "./ProposalEquivocationAtom";
 import type { TransactionAtom } from "./TransactionAtom";
 
 export type Command =
@@ -16,4 +17,5 @@ export type Command =
   | { ForeignProposal: ForeignProposalAtom }
   | { MintConfidentialOutput: MintConfidentialOutputAtom }
   | { EvictNode: EvictNodeAtom }
+  | { ProposalEquivocation: ProposalEquivocationAtom }
   | "EndEpoch";
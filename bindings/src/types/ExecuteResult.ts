@@ -1,7 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { FinalizeResult } from "./FinalizeResult";
+import type { SubstateAccessStats } from "./SubstateAccessStats";
 
 export interface ExecuteResult {
   finalize: FinalizeResult;
   execution_time: { secs: number; nanos: number };
+  substate_access_stats: SubstateAccessStats | null;
 }
@@ -1,8 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Amount } from "./Amount";
 import type { FeeBreakdown } from "./FeeBreakdown";
+import type { GasUsage } from "./GasUsage";
 
 export interface FeeCostBreakdown {
   total_fees_charged: Amount;
   breakdown: FeeBreakdown;
+  gas_usage: GasUsage;
 }
@@ -1,9 +1,13 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Amount } from "./Amount";
 import type { FeeBreakdown } from "./FeeBreakdown";
+import type { GasUsage } from "./GasUsage";
+import type { MemoryUsage } from "./MemoryUsage";
 
 export interface FeeReceipt {
   total_fee_payment: Amount;
   total_fees_paid: Amount;
   cost_breakdown: FeeBreakdown;
+  memory_usage: MemoryUsage;
+  gas_usage: GasUsage;
 }
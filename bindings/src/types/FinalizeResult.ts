@@ -3,6 +3,7 @@ import type { Event } from "./Event";
 import type { FeeReceipt } from "./FeeReceipt";
 import type { InstructionResult } from "./InstructionResult";
 import type { LogEntry } from "./LogEntry";
+import type { TemplateAbort } from "./TemplateAbort";
 import type { TransactionResult } from "./TransactionResult";
 
 export interface FinalizeResult {
@@ -12,4 +13,5 @@ export interface FinalizeResult {
   execution_results: Array<InstructionResult>;
   result: TransactionResult;
   fee_receipt: FeeReceipt;
+  abort: TemplateAbort | null;
 }
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { ShardGroup } from "./ShardGroup";
+
+export interface ForeignReceiveGap {
+  foreign_shard_group: ShardGroup;
+  epoch: Epoch;
+  from_index: number;
+  to_index: number;
+  num_received: number;
+  detected_at: string | null;
+  resolved_at: string | null;
+}
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "./Amount";
+import type { HistoricalBalanceSource } from "./HistoricalBalanceSource";
+import type { ResourceAddress } from "./ResourceAddress";
+import type { VaultId } from "./VaultId";
+
+export interface HistoricalVaultBalance {
+  vault_id: VaultId;
+  resource_address: ResourceAddress;
+  vault_version: number;
+  revealed_balance: Amount;
+  confidential_balance: Amount | null;
+  source: HistoricalBalanceSource;
+}
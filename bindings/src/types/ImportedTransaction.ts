@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "./Amount";
+import type { SubstateId } from "./SubstateId";
+import type { TransactionStatus } from "./TransactionStatus";
+
+export interface ImportedTransaction {
+  transaction_id: string;
+  status: TransactionStatus;
+  final_fee: Amount | null;
+  accounts: Array<SubstateId>;
+  imported_at: string;
+}
@@ -7,6 +7,7 @@ import type { ConfidentialClaim } from "./ConfidentialClaim";
 import type { LogLevel } from "./LogLevel";
 import type { OwnerRule } from "./OwnerRule";
 import type { ResourceAddress } from "./ResourceAddress";
+import type { TemplateType } from "./TemplateType";
 
 export type Instruction =
   | {
@@ -25,4 +26,5 @@ export type Instruction =
   | { ClaimValidatorFees: { epoch: number; validator_public_key: string } }
   | "DropAllProofsInWorkspace"
   | { AssertBucketContains: { key: Array<number>; resource_address: ResourceAddress; min_amount: Amount } }
-  | { PublishTemplate: { binary: Array<number> } };
+  | { PublishTemplate: { binary: Array<number>; previous_version: Uint8Array | null; template_type: TemplateType } }
+  | { MigrateComponent: { component_address: ComponentAddress; template_address: Uint8Array } };
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+export interface MemoryUsage {
+  peak_wasm_memory_bytes: number;
+  host_allocated_bytes: number;
+}
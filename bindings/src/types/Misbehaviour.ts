@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockHeader } from "./BlockHeader";
+import type { ProposalEquivocationAtom } from "./ProposalEquivocationAtom";
+import type { QuorumCertificate } from "./QuorumCertificate";
+import type { SignedVote } from "./SignedVote";
+
+export type Misbehaviour =
+  | { EquivocatingProposal: ProposalEquivocationAtom }
+  | { DoubleVote: { first: SignedVote; second: SignedVote } }
+  | { InvalidQuorumCertificate: { block_header: BlockHeader; justify: QuorumCertificate; reason: string } };
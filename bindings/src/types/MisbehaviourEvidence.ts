@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Misbehaviour } from "./Misbehaviour";
+
+export interface MisbehaviourEvidence {
+  misbehaviour: Misbehaviour;
+  recorded_at: string | null;
+}
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockHeader } from "./BlockHeader";
+
+export interface ProposalEquivocationAtom {
+  first: BlockHeader;
+  second: BlockHeader;
+}
@@ -1,5 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PublishedTemplateAddress } from "./PublishedTemplateAddress";
+import type { TemplateType } from "./TemplateType";
 
 export interface PublishedTemplate {
   binary: Array<number>;
+  template_type: TemplateType;
+  author: string | null;
+  template_version: number;
+  previous_version: PublishedTemplateAddress | null;
+  lineage_root: PublishedTemplateAddress | null;
 }
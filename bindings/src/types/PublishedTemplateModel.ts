@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "./SubstateId";
+
+export interface PublishedTemplateModel {
+  template_address: string;
+  author_account: SubstateId;
+  transaction_id: string;
+  binary_size: number;
+  published_at: string;
+}
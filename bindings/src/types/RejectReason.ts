@@ -8,4 +8,5 @@ export type RejectReason =
   | { FailedToLockOutputs: string }
   | { ForeignShardGroupDecidedToAbort: { start_shard: number; end_shard: number } }
   | { FeesNotPaid: string }
+  | { SandboxViolation: string }
   | "Unknown";
@@ -5,7 +5,9 @@ export interface ResourceAccessRules {
   mintable: AccessRule;
   burnable: AccessRule;
   recallable: AccessRule;
+  freezable: AccessRule;
   withdrawable: AccessRule;
   depositable: AccessRule;
   update_non_fungible_data: AccessRule;
+  update_metadata: AccessRule;
 }
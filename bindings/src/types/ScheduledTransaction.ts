@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "./Amount";
+import type { ComponentAddress } from "./ComponentAddress";
+import type { Epoch } from "./Epoch";
+import type { Instruction } from "./Instruction";
+import type { ScheduledTransactionStatus } from "./ScheduledTransactionStatus";
+import type { TransactionSchedule } from "./TransactionSchedule";
+
+export interface ScheduledTransaction {
+  id: number;
+  name: string;
+  schedule: TransactionSchedule;
+  instructions: Array<Instruction>;
+  fee_account: ComponentAddress;
+  max_fee: Amount;
+  max_runs: number | null;
+  num_runs: number;
+  status: ScheduledTransactionStatus;
+  next_run_at: string | null;
+  next_run_epoch: Epoch | null;
+  last_transaction_id: string | null;
+  last_error: string | null;
+  created_at: string;
+}
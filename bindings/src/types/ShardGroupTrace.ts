@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Decision } from "./Decision";
+import type { ShardGroup } from "./ShardGroup";
+import type { ShardGroupTraceStatus } from "./ShardGroupTraceStatus";
+import type { TransactionBlockInclusion } from "./TransactionBlockInclusion";
+
+export interface ShardGroupTrace {
+  shard_group: ShardGroup;
+  is_local: boolean;
+  status: ShardGroupTraceStatus;
+  decision: Decision | null;
+  blocks: Array<TransactionBlockInclusion>;
+}
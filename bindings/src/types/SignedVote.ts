@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+import type { QuorumDecision } from "./QuorumDecision";
+import type { ValidatorSignature } from "./ValidatorSignature";
+
+export interface SignedVote {
+  epoch: Epoch;
+  block_id: string;
+  decision: QuorumDecision;
+  signature: ValidatorSignature;
+}
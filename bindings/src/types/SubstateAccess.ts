@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "./SubstateId";
+
+export interface SubstateAccess {
+  substate_id: SubstateId;
+  num_reads: number;
+  num_writes: number;
+  size: number;
+}
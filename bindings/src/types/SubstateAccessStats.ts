@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateAccess } from "./SubstateAccess";
+import type { TemplateSubstateAccess } from "./TemplateSubstateAccess";
+
+export interface SubstateAccessStats {
+  templates: Array<TemplateSubstateAccess>;
+  hot_substates: Array<SubstateAccess>;
+}
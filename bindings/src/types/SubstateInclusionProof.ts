@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface SubstateInclusionProof {
+  shard_proof: any;
+  shard_state_root: string;
+  state_root_proof: any;
+}
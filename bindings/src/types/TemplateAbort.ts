@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TemplateAbort {
+  message: string;
+  template_address: string;
+  function: string;
+  instruction_index: number;
+  is_fee_instruction: boolean;
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TemplateSubstateAccess {
+  template_address: string | null;
+  num_reads: number;
+  num_writes: number;
+  num_substates: number;
+  bytes_read: number;
+  bytes_written: number;
+}
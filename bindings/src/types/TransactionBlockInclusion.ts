@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Decision } from "./Decision";
+import type { Epoch } from "./Epoch";
+import type { NodeHeight } from "./NodeHeight";
+
+export interface TransactionBlockInclusion {
+  block_id: string;
+  epoch: Epoch;
+  height: NodeHeight;
+  command: string;
+  decision: Decision;
+  is_committed: boolean | null;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Event } from "./Event";
+
+export interface TransactionEvent {
+  transaction_id: string;
+  block_id: string;
+  index: number;
+  event: Event;
+}
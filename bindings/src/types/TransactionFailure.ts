@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateRequirement } from "./SubstateRequirement";
+import type { TransactionStatus } from "./TransactionStatus";
+
+export interface TransactionFailure {
+  transaction_id: string;
+  status: TransactionStatus;
+  reason: string;
+  inputs: Array<SubstateRequirement>;
+  retried_as: string | null;
+  failed_at: string;
+  attempt: number;
+}
@@ -8,6 +8,7 @@ export interface TransactionPoolRecord {
   transaction_id: string;
   evidence: Evidence;
   transaction_fee: number;
+  priority_fee: number;
   leader_fee: LeaderFee | null;
   stage: TransactionPoolStage;
   pending_stage: TransactionPoolStage | null;
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "./Epoch";
+
+export type TransactionSchedule =
+  | { Interval: { seconds: number } }
+  | { Cron: { expression: string } }
+  | { Epoch: { epoch: Epoch; repeat_every: number | null } };
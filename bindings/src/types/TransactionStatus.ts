@@ -7,4 +7,5 @@ export type TransactionStatus =
   | "Accepted"
   | "Rejected"
   | "InvalidTransaction"
-  | "OnlyFeeAccepted";
+  | "OnlyFeeAccepted"
+  | "Expired";
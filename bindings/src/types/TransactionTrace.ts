@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Decision } from "./Decision";
+import type { ShardGroup } from "./ShardGroup";
+import type { ShardGroupTrace } from "./ShardGroupTrace";
+import type { TransactionPoolStage } from "./TransactionPoolStage";
+
+export interface TransactionTrace {
+  transaction_id: string;
+  local_shard_group: ShardGroup;
+  final_decision: Decision | null;
+  pool_stage: TransactionPoolStage | null;
+  local_decision: Decision | null;
+  remote_decision: Decision | null;
+  shard_groups: Array<ShardGroupTrace>;
+}
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiKeyUsage } from "./ApiKeyUsage";
+
+export interface ApiKeyInfo {
+  key_id: string;
+  name: string;
+  allowed_methods: Array<string> | null;
+  rate_limit_per_minute: number | null;
+  created_at: number;
+  revoked_at: number | null;
+  last_used_at: number | null;
+  usage: ApiKeyUsage;
+}
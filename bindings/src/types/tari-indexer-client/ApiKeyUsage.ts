@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ApiKeyUsage {
+  total_requests: number;
+  rate_limited_requests: number;
+  denied_requests: number;
+}
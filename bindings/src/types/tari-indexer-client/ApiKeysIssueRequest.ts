@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ApiKeysIssueRequest {
+  name: string;
+  allowed_methods: Array<string> | null;
+  rate_limit_per_minute: number | null;
+}
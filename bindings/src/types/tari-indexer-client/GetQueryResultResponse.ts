@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IndexerQueryResult } from "./IndexerQueryResult";
+import type { IndexerQueryStatus } from "./IndexerQueryStatus";
+
+export interface GetQueryResultResponse {
+  status: IndexerQueryStatus;
+  result: IndexerQueryResult | null;
+}
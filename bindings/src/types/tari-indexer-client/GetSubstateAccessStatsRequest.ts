@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateRequirement } from "../SubstateRequirement";
+import type { Transaction } from "../Transaction";
+
+export interface GetSubstateAccessStatsRequest {
+  transaction: Transaction;
+  required_substates: Array<SubstateRequirement>;
+  substate_overrides: Array<[any, any]>;
+}
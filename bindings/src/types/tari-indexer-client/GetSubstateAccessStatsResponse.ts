@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FeeReceipt } from "../FeeReceipt";
+import type { SubstateAccessStats } from "../SubstateAccessStats";
+
+export interface GetSubstateAccessStatsResponse {
+  transaction_id: string;
+  stats: SubstateAccessStats;
+  fee_receipt: FeeReceipt;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "../SubstateId";
+
+export interface GetSubstateDiffRequest {
+  address: SubstateId;
+  from_version: number;
+  to_version: number | null;
+}
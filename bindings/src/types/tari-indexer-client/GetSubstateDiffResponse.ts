@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateFieldChange } from "./SubstateFieldChange";
+import type { SubstateId } from "../SubstateId";
+
+export interface GetSubstateDiffResponse {
+  address: SubstateId;
+  from_version: number;
+  to_version: number;
+  template_address: string | null;
+  template_name: string | null;
+  changes: Array<SubstateFieldChange>;
+}
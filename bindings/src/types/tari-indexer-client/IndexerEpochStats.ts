@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+
+export interface IndexerEpochStats {
+  epoch: Epoch;
+  num_blocks: number;
+  num_transactions: number;
+  num_events: number;
+  num_stored_events: number;
+  updated_at: number;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "../SubstateId";
+
+export interface IndexerGetEventsRequest {
+  topic: string | null;
+  substate_id: SubstateId | null;
+  offset: number | null;
+  limit: number | null;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PendingTransactionCommittee } from "./PendingTransactionCommittee";
+
+export interface IndexerPendingTransaction {
+  transaction_id: string;
+  first_seen_at: number;
+  last_seen_at: number;
+  committees: Array<PendingTransactionCommittee>;
+}
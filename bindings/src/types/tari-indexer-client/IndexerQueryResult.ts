@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IndexerEpochStats } from "./IndexerEpochStats";
+import type { ListSubstateItem } from "./ListSubstateItem";
+
+export type IndexerQueryResult =
+  | { ListSubstates: { substates: Array<ListSubstateItem> } }
+  | { EpochStats: { stats: Array<IndexerEpochStats> } }
+  | { NonFungibleCollections: { collections: Array<[string, number]> } };
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ShardGroup } from "../ShardGroup";
+import type { TransactionPoolStage } from "../TransactionPoolStage";
+
+export interface PendingTransactionCommittee {
+  shard_group: ShardGroup;
+  stage: TransactionPoolStage;
+  is_ready: boolean;
+}
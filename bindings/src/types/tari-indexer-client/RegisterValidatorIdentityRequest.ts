@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidatorMetadata } from "./ValidatorMetadata";
+import type { ValidatorSignature } from "../ValidatorSignature";
+
+export interface RegisterValidatorIdentityRequest {
+  metadata: ValidatorMetadata;
+  challenge: string;
+  signature: ValidatorSignature;
+}
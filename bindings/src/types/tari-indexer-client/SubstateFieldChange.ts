@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateFieldChangeKind } from "./SubstateFieldChangeKind";
+
+export interface SubstateFieldChange {
+  path: string;
+  kind: SubstateFieldChangeKind;
+  old_value: any;
+  new_value: any;
+}
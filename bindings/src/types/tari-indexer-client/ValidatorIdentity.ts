@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ValidatorMetadata } from "./ValidatorMetadata";
+
+export interface ValidatorIdentity {
+  public_key: string;
+  metadata: ValidatorMetadata;
+  verified_at: number;
+}
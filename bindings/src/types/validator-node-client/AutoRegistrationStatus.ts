@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+
+export interface AutoRegistrationStatus {
+  enabled: boolean;
+  epochs_before_expiry: number;
+  registration_end_epoch: Epoch | null;
+  last_submitted_epoch: Epoch | null;
+  last_submitted_for_end_epoch: Epoch | null;
+  last_error: string | null;
+}
@@ -0,0 +1,20 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+import type { Shard } from "../Shard";
+import type { ShardGroup } from "../ShardGroup";
+import type { ShadowExecutionSummary } from "./ShadowExecutionSummary";
+
+export interface EpochTransitionRehearsal {
+  current_epoch: Epoch;
+  next_epoch: Epoch;
+  current_shard_group: ShardGroup | null;
+  next_shard_group: ShardGroup | null;
+  next_num_validators: number;
+  next_num_committees: number;
+  next_committee_size: number;
+  shards_to_sync: Array<Shard>;
+  shards_to_hand_off: Array<Shard>;
+  pending_transactions: number;
+  warnings: Array<string>;
+  shadow_execution: ShadowExecutionSummary | null;
+}
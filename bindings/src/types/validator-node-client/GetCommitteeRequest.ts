@@ -1,8 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Epoch } from "../Epoch";
+import type { ShardGroup } from "../ShardGroup";
 import type { SubstateAddress } from "../SubstateAddress";
 
 export interface GetCommitteeRequest {
   epoch: Epoch;
-  substate_address: SubstateAddress;
+  substate_address: SubstateAddress | null;
+  shard_group: ShardGroup | null;
 }
@@ -1,7 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Committee } from "../Committee";
 import type { PeerAddress } from "../PeerAddress";
+import type { ShardGroup } from "../ShardGroup";
 
 export interface GetCommitteeResponse {
+  shard_group: ShardGroup;
   committee: Committee<PeerAddress>;
 }
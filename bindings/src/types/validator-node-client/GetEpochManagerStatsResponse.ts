@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CommitteeInfo } from "../CommitteeInfo";
 import type { Epoch } from "../Epoch";
+import type { AutoRegistrationStatus } from "./AutoRegistrationStatus";
 
 export interface GetEpochManagerStatsResponse {
   current_epoch: Epoch;
@@ -9,4 +10,5 @@ export interface GetEpochManagerStatsResponse {
   is_valid: boolean;
   start_epoch: Epoch | null;
   committee_info: CommitteeInfo | null;
+  auto_registration: AutoRegistrationStatus;
 }
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "../SubstateId";
+
+export interface GetEventsRequest {
+  transaction_id: string | null;
+  topic: string | null;
+  substate_id: SubstateId | null;
+  limit: number | null;
+  offset: number | null;
+}
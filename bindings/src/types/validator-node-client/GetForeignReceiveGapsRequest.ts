@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface GetForeignReceiveGapsRequest {
+  include_resolved: boolean;
+  limit: number | null;
+  offset: number | null;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ForeignReceiveGap } from "../ForeignReceiveGap";
+import type { ReceivedForeignIndex } from "../ReceivedForeignIndex";
+import type { Shard } from "../Shard";
+
+export interface GetForeignReceiveGapsResponse {
+  gaps: Array<ForeignReceiveGap>;
+  last_received: Record<Shard, ReceivedForeignIndex>;
+}
@@ -4,5 +4,6 @@ import type { VNCommitteeShardInfo } from "./VNCommitteeShardInfo";
 
 export interface GetNetworkCommitteeResponse {
   current_epoch: Epoch;
+  epoch: Epoch;
   committees: Array<VNCommitteeShardInfo>;
 }
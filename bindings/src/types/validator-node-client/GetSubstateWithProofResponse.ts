@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BlockHeader } from "../BlockHeader";
+import type { SubstateInclusionProof } from "../SubstateInclusionProof";
+import type { SubstateValue } from "../SubstateValue";
+
+export interface GetSubstateWithProofResponse {
+  value: SubstateValue;
+  created_by_tx: string;
+  block_header: BlockHeader;
+  proof: SubstateInclusionProof;
+}
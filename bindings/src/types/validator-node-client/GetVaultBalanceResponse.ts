@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { ResourceType } from "../ResourceType";
+
+export interface GetVaultBalanceResponse {
+  version: number;
+  resource_address: string;
+  resource_type: ResourceType;
+  balance: Amount;
+  locked_balance: Amount;
+  num_confidential_outputs: number;
+  num_non_fungibles: number;
+}
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Shard } from "../Shard";
+
+export interface MempoolTransactionInfo {
+  transaction_id: string;
+  input_shards: Array<Shard>;
+  age: { secs: number; nanos: number };
+  declared_max_fee: number | null;
+  fee_per_instruction: number;
+  deferred_until: number | null;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface ShadowExecutionSummary {
+  handed_off_substates: number;
+  accepted: number;
+  rejected: number;
+  awaiting_sync: number;
+  awaiting_foreign_inputs: number;
+}
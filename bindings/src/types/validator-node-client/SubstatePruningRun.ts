@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+
+export interface SubstatePruningRun {
+  epoch: Epoch;
+  pruned_before_epoch: Epoch;
+  num_substates_pruned: number;
+  num_state_transitions_pruned: number;
+  bytes_reclaimed: number;
+  duration_ms: number;
+}
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+import type { SubstatePruningRun } from "./SubstatePruningRun";
+
+export interface SubstatePruningStatus {
+  enabled: boolean;
+  retention_epochs: number;
+  is_running: boolean;
+  pruned_before_epoch: Epoch | null;
+  last_run: SubstatePruningRun | null;
+  total_substates_pruned: number;
+  total_state_transitions_pruned: number;
+  total_bytes_reclaimed: number;
+}
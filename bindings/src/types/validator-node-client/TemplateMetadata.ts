@@ -4,4 +4,7 @@ export interface TemplateMetadata {
   name: string;
   address: string;
   binary_sha: Array<number>;
+  author_public_key: string | null;
+  template_type: string;
+  status: string;
 }
@@ -1,9 +1,11 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ShardGroup } from "../ShardGroup";
 import type { SubstateAddress } from "../SubstateAddress";
 import type { ValidatorNode } from "./ValidatorNode";
 
 export interface VNCommitteeShardInfo {
   shard: number;
+  shard_group: ShardGroup;
   substate_address_range: { start: SubstateAddress; end: SubstateAddress };
   validators: Array<ValidatorNode>;
 }
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { VNBandwidthStats } from "./VNBandwidthStats";
+
+export interface VNGetBandwidthStatsResponse {
+  total: VNBandwidthStats;
+  per_peer: Record<string, VNBandwidthStats>;
+  per_message_type: Record<string, VNBandwidthStats>;
+}
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BalanceStatementEntry } from "./BalanceStatementEntry";
+import type { SubstateId } from "../SubstateId";
+
+export interface AccountBalanceStatement {
+  account: SubstateId;
+  balances: Array<BalanceStatementEntry>;
+  public_key: string;
+  timestamp: number;
+  nonce: string | null;
+}
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+
+export interface AccountsCreateBalanceProofRequest {
+  account: ComponentAddressOrName | null;
+  view_key_id: number | null;
+  refresh: boolean;
+  nonce: string | null;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+import type { Epoch } from "../Epoch";
+
+export interface AccountsGetBalanceAtEpochRequest {
+  account: ComponentAddressOrName | null;
+  epoch: Epoch;
+}
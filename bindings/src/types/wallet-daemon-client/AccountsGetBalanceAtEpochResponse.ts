@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Epoch } from "../Epoch";
+import type { HistoricalVaultBalance } from "../HistoricalVaultBalance";
+import type { SubstateId } from "../SubstateId";
+
+export interface AccountsGetBalanceAtEpochResponse {
+  address: SubstateId;
+  epoch: Epoch;
+  balances: Array<HistoricalVaultBalance>;
+}
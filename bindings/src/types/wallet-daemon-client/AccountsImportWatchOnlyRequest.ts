@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddress } from "../ComponentAddress";
+
+export interface AccountsImportWatchOnlyRequest {
+  account_name: string | null;
+  address: ComponentAddress;
+  owner_public_key: string;
+  view_key: string | null;
+}
@@ -1,14 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { Amount } from "../Amount";
+import type { AmountInput } from "./AmountInput";
 import type { ComponentAddressOrName } from "./ComponentAddressOrName";
 import type { ResourceAddress } from "../ResourceAddress";
 
 export interface AccountsTransferRequest {
   account: ComponentAddressOrName | null;
-  amount: Amount;
+  amount: AmountInput;
   resource_address: ResourceAddress;
   destination_public_key: string;
-  max_fee: Amount | null;
+  max_fee: AmountInput | null;
   proof_from_badge_resource: string | null;
   dry_run: boolean;
+  fee_account: ComponentAddressOrName | null;
 }
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DaemonAttestation } from "./DaemonAttestation";
+
+export interface AttestationGetResponse {
+  attestation: DaemonAttestation;
+  signature: { public_nonce: string; signature: string };
+}
@@ -11,4 +11,6 @@ export interface BalanceEntry {
   resource_type: ResourceType;
   confidential_balance: Amount;
   token_symbol: string | null;
+  decimals: number | null;
+  is_frozen: boolean;
 }
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { ResourceAddress } from "../ResourceAddress";
+import type { ResourceType } from "../ResourceType";
+import type { SubstateId } from "../SubstateId";
+
+export interface BalanceStatementEntry {
+  vault_address: SubstateId;
+  resource_address: ResourceAddress;
+  resource_type: ResourceType;
+  revealed_balance: Amount;
+  confidential_balance: Amount;
+  view_key_balance: Amount | null;
+  token_symbol: string | null;
+}
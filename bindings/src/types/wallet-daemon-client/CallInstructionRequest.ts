@@ -5,7 +5,7 @@ import type { SubstateRequirement } from "../SubstateRequirement";
 
 export interface CallInstructionRequest {
   instructions: Array<Instruction>;
-  fee_account: ComponentAddressOrName;
+  fee_account: ComponentAddressOrName | null;
   dump_outputs_into: ComponentAddressOrName | null;
   max_fee: number;
   inputs: Array<SubstateRequirement>;
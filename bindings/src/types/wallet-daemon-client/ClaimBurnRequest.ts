@@ -1,10 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { Amount } from "../Amount";
+import type { AmountInput } from "./AmountInput";
 import type { ComponentAddressOrName } from "./ComponentAddressOrName";
 
 export interface ClaimBurnRequest {
   account: ComponentAddressOrName | null;
   claim_proof: string;
-  max_fee: Amount | null;
+  max_fee: AmountInput | null;
   key_id: number | null;
 }
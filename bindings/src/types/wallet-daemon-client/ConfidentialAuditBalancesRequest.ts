@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ResourceAddress } from "../ResourceAddress";
+
+export interface ConfidentialAuditBalancesRequest {
+  resource_address: ResourceAddress;
+  view_key_id: number;
+  refresh: boolean;
+  minimum_expected_value: number | null;
+  maximum_expected_value: number | null;
+}
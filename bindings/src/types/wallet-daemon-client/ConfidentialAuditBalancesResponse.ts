@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { AuditedVaultBalance } from "../AuditedVaultBalance";
+
+export interface ConfidentialAuditBalancesResponse {
+  balances: Array<AuditedVaultBalance>;
+  total_balance: Amount;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddress } from "../ComponentAddress";
+
+export interface ContactsAddRequest {
+  name: string;
+  component_address: ComponentAddress | null;
+  public_key: string | null;
+}
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface DaemonAttestation {
+  version: string;
+  build_hash: string;
+  enabled_features: Array<string>;
+  config_fingerprint: string;
+  public_key: string;
+  timestamp: number;
+  nonce: string | null;
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface IndexerEndpointStatus {
+  url: string;
+  is_active: boolean;
+  is_healthy: boolean | null;
+  latency_ms: number | null;
+  last_checked_at: number | null;
+  last_error: string | null;
+}
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IndexerEndpointStatus } from "./IndexerEndpointStatus";
+
+export interface NetworkStatusResponse {
+  active_endpoint: string;
+  endpoints: Array<IndexerEndpointStatus>;
+}
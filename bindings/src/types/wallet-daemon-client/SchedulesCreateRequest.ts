@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+import type { Instruction } from "../Instruction";
+import type { TransactionSchedule } from "../TransactionSchedule";
+
+export interface SchedulesCreateRequest {
+  name: string;
+  schedule: TransactionSchedule;
+  instructions: Array<Instruction>;
+  fee_account: ComponentAddressOrName | null;
+  max_fee: Amount | null;
+  max_runs: number | null;
+}
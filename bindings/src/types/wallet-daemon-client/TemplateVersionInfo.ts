@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TemplateVersionInfo {
+  template_address: string;
+  template_version: number;
+  previous_version: string | null;
+  author_public_key: string | null;
+}
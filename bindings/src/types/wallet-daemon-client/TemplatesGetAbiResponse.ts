@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TemplateDef } from "../TemplateDef";
+
+export interface TemplatesGetAbiResponse {
+  template_address: string;
+  abi: TemplateDef;
+  binary_size: number;
+}
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { TemplateType } from "../TemplateType";
+import type { ComponentAddressOrName } from "./ComponentAddressOrName";
+import type { TemplateBinarySource } from "./TemplateBinarySource";
+
+export interface TemplatesPublishRequest {
+  binary: TemplateBinarySource;
+  template_type: TemplateType;
+  previous_version: string | null;
+  fee_account: ComponentAddressOrName | null;
+  max_fee: Amount | null;
+  dry_run: boolean;
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { FinalizeResult } from "../FinalizeResult";
+
+export interface TemplatesPublishResponse {
+  transaction_id: string;
+  template_address: string;
+  fee: Amount;
+  result: FinalizeResult;
+}
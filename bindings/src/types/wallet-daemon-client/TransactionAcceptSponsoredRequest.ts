@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ComponentAddress } from "../ComponentAddress";
+import type { Transaction } from "../Transaction";
+
+export interface TransactionAcceptSponsoredRequest {
+  transaction: Transaction;
+  sponsor: ComponentAddress;
+  signing_key_index: number | null;
+}
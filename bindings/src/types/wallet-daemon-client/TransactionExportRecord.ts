@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { FinalizeResult } from "../FinalizeResult";
+import type { TransactionStatus } from "../TransactionStatus";
+
+export interface TransactionExportRecord {
+  transaction_id: string;
+  status: TransactionStatus;
+  final_fee: Amount | null;
+  finalize: FinalizeResult | null;
+  execution_time_ms: number | null;
+  finalized_time_ms: number | null;
+  last_update_time: string;
+}
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UnsignedTransaction } from "../UnsignedTransaction";
+
+export interface TransactionExportUnsignedRequest {
+  transaction: UnsignedTransaction;
+  signing_key_index: number | null;
+  detect_inputs: boolean;
+}
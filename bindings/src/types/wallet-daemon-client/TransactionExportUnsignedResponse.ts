@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UnsignedTransaction } from "../UnsignedTransaction";
+
+export interface TransactionExportUnsignedResponse {
+  blob: string;
+  transaction: UnsignedTransaction;
+  required_signers: Array<string>;
+}
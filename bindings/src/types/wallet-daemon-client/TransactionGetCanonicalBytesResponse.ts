@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TransactionGetCanonicalBytesResponse {
+  signature_fields: string;
+  signature_domain_separation_tag: string;
+  signature_message: string;
+  transaction_id_preimage: string;
+  transaction_id_domain_separation_tag: string;
+  transaction_id: string;
+}
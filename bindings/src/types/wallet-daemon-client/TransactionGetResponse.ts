@@ -8,4 +8,5 @@ export interface TransactionGetResponse {
   result: FinalizeResult | null;
   status: TransactionStatus;
   last_update_time: string;
+  annotation: string | null;
 }
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Amount } from "../Amount";
+import type { ComponentAddress } from "../ComponentAddress";
+import type { Instruction } from "../Instruction";
+
+export interface TransactionReviewSponsoredResponse {
+  sponsor: ComponentAddress;
+  max_fee: Amount | null;
+  existing_signers: Array<string>;
+  involved_accounts: Array<ComponentAddress>;
+  fee_instructions: Array<Instruction>;
+  instructions: Array<Instruction>;
+}
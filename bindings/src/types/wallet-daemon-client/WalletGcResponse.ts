@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SubstateId } from "../SubstateId";
+
+export interface WalletGcResponse {
+  is_dry_run: boolean;
+  removed_substates: Array<SubstateId>;
+  removed_proofs: Array<number>;
+}
@@ -19,6 +19,20 @@ export * from "./types/validator-node-client/VNGetValidatorFeesResponse";
 export * from "./types/validator-node-client/VNGetAllVnsResponse";
 export * from "./types/validator-node-client/TemplateAbi";
 export * from "./types/validator-node-client/GetMempoolStatsResponse";
+export * from "./types/validator-node-client/VNBandwidthStats";
+export * from "./types/validator-node-client/VNGetBandwidthStatsResponse";
+export * from "./types/validator-node-client/GetMempoolTransactionsResponse";
+export * from "./types/validator-node-client/MempoolTransactionInfo";
+export * from "./types/validator-node-client/EvictMempoolTransactionRequest";
+export * from "./types/validator-node-client/EvictMempoolTransactionResponse";
+export * from "./types/validator-node-client/GetMisbehaviourEvidenceRequest";
+export * from "./types/validator-node-client/GetMisbehaviourEvidenceResponse";
+export * from "./types/validator-node-client/GetEventsRequest";
+export * from "./types/validator-node-client/GetEventsResponse";
+export * from "./types/validator-node-client/GetForeignReceiveGapsRequest";
+export * from "./types/validator-node-client/GetForeignReceiveGapsResponse";
+export * from "./types/validator-node-client/TraceTransactionRequest";
+export * from "./types/validator-node-client/TraceTransactionResponse";
 export * from "./types/validator-node-client/TemplateMetadata";
 export * from "./types/validator-node-client/GetBlockResponse";
 export * from "./types/validator-node-client/VNLogLevel";
@@ -34,12 +48,15 @@ export * from "./types/validator-node-client/GetTransactionResponse";
 export * from "./types/validator-node-client/GetStateResponse";
 export * from "./types/validator-node-client/VNGetSubstateRequest";
 export * from "./types/validator-node-client/VNGetSubstateResponse";
+export * from "./types/validator-node-client/GetSubstateWithProofRequest";
+export * from "./types/validator-node-client/GetSubstateWithProofResponse";
 export * from "./types/validator-node-client/ListBlocksResponse";
 export * from "./types/validator-node-client/SubstateStatus";
 export * from "./types/validator-node-client/ValidatorNode";
 export * from "./types/validator-node-client/GetFilteredBlocksCountRequest";
 export * from "./types/validator-node-client/ListBlocksRequest";
 export * from "./types/validator-node-client/GetEpochManagerStatsResponse";
+export * from "./types/validator-node-client/AutoRegistrationStatus";
 export * from "./types/validator-node-client/GetTxPoolResponse";
 export * from "./types/validator-node-client/VNConnection";
 export * from "./types/validator-node-client/GetConsensusStatusResponse";
@@ -60,4 +77,15 @@ export * from "./types/validator-node-client/GetBlocksResponse";
 export * from "./types/validator-node-client/VNGetIdentityResponse";
 export * from "./types/validator-node-client/GetSubstatesByTransactionRequest";
 export * from "./types/validator-node-client/GetNetworkCommitteeResponse";
+export * from "./types/validator-node-client/GetNetworkCommitteesRequest";
 export * from "./types/validator-node-client/VNGetValidatorFeesRequest";
+export * from "./types/validator-node-client/EpochTransitionRehearsal";
+export * from "./types/validator-node-client/GetEpochTransitionRehearsalResponse";
+export * from "./types/validator-node-client/ShadowExecutionSummary";
+export * from "./types/validator-node-client/GetTxPoolByPriorityRequest";
+export * from "./types/validator-node-client/GetTxPoolByPriorityResponse";
+export * from "./types/validator-node-client/GetSubstatePruningStatusResponse";
+export * from "./types/validator-node-client/GetVaultBalanceRequest";
+export * from "./types/validator-node-client/GetVaultBalanceResponse";
+export * from "./types/validator-node-client/SubstatePruningRun";
+export * from "./types/validator-node-client/SubstatePruningStatus";
@@ -72,6 +72,7 @@ export * from "./types/wallet-daemon-client/ClaimValidatorFeesResponse";
 export * from "./types/wallet-daemon-client/ConfidentialTransferResponse";
 export * from "./types/wallet-daemon-client/TemplatesGetResponse";
 export * from "./types/wallet-daemon-client/AccountsTransferRequest";
+export * from "./types/wallet-daemon-client/AmountInput";
 export * from "./types/wallet-daemon-client/AuthLoginRequest";
 export * from "./types/wallet-daemon-client/AuthGetAllJwtResponse";
 export * from "./types/wallet-daemon-client/AccountsCreateFreeTestCoinsRequest";
@@ -83,6 +84,10 @@ export * from "./types/wallet-daemon-client/KeysCreateRequest";
 export * from "./types/wallet-daemon-client/ProofsGenerateRequest";
 export * from "./types/wallet-daemon-client/TransactionGetResultResponse";
 export * from "./types/wallet-daemon-client/KeysSetActiveResponse";
+export * from "./types/wallet-daemon-client/KeysGetSeedWordsRequest";
+export * from "./types/wallet-daemon-client/KeysGetSeedWordsResponse";
+export * from "./types/wallet-daemon-client/KeysVerifySeedWordsRequest";
+export * from "./types/wallet-daemon-client/KeysVerifySeedWordsResponse";
 export * from "./types/wallet-daemon-client/AuthLoginDenyResponse";
 export * from "./types/wallet-daemon-client/TransactionGetResultRequest";
 export * from "./types/wallet-daemon-client/AccountsCreateResponse";
@@ -90,3 +95,72 @@ export * from "./types/wallet-daemon-client/AccountsGetBalancesRequest";
 export * from "./types/wallet-daemon-client/AccountInfo";
 export * from "./types/wallet-daemon-client/SubstatesListRequest";
 export * from "./types/wallet-daemon-client/ComponentAddressOrName";
+export * from "./types/wallet-daemon-client/ConfidentialAuditBalancesRequest";
+export * from "./types/wallet-daemon-client/ConfidentialAuditBalancesResponse";
+export * from "./types/wallet-daemon-client/TransactionExportFormat";
+export * from "./types/wallet-daemon-client/TransactionsExportRequest";
+export * from "./types/wallet-daemon-client/TransactionExportRecord";
+export * from "./types/wallet-daemon-client/TemplateBinarySource";
+export * from "./types/wallet-daemon-client/TemplatesPublishRequest";
+export * from "./types/wallet-daemon-client/TemplatesPublishResponse";
+export * from "./types/wallet-daemon-client/TemplatesGetAbiRequest";
+export * from "./types/wallet-daemon-client/TemplatesGetAbiResponse";
+export * from "./types/wallet-daemon-client/TemplatesListVersionsRequest";
+export * from "./types/wallet-daemon-client/TemplatesListVersionsResponse";
+export * from "./types/wallet-daemon-client/TemplateVersionInfo";
+export * from "./types/wallet-daemon-client/TemplatesMigrateComponentRequest";
+export * from "./types/wallet-daemon-client/TemplatesMigrateComponentResponse";
+export * from "./types/wallet-daemon-client/AttestationGetRequest";
+export * from "./types/wallet-daemon-client/DaemonAttestation";
+export * from "./types/wallet-daemon-client/AttestationGetResponse";
+export * from "./types/wallet-daemon-client/TransactionGetFailuresRequest";
+export * from "./types/wallet-daemon-client/TransactionGetFailuresResponse";
+export * from "./types/wallet-daemon-client/TransactionRetryRequest";
+export * from "./types/wallet-daemon-client/TransactionRetryResponse";
+export * from "./types/wallet-daemon-client/TransactionSetAnnotationRequest";
+export * from "./types/wallet-daemon-client/TransactionSetAnnotationResponse";
+export * from "./types/wallet-daemon-client/AccountsRecoverRequest";
+export * from "./types/wallet-daemon-client/AccountsRecoverResponse";
+export * from "./types/wallet-daemon-client/WalletLockRequest";
+export * from "./types/wallet-daemon-client/WalletLockResponse";
+export * from "./types/wallet-daemon-client/WalletUnlockRequest";
+export * from "./types/wallet-daemon-client/WalletUnlockResponse";
+export * from "./types/wallet-daemon-client/AccountsImportWatchOnlyRequest";
+export * from "./types/wallet-daemon-client/AccountsImportWatchOnlyResponse";
+export * from "./types/wallet-daemon-client/AccountsImportRequest";
+export * from "./types/wallet-daemon-client/AccountsImportResponse";
+export * from "./types/wallet-daemon-client/ContactsAddRequest";
+export * from "./types/wallet-daemon-client/ContactsAddResponse";
+export * from "./types/wallet-daemon-client/ContactsListRequest";
+export * from "./types/wallet-daemon-client/ContactsListResponse";
+export * from "./types/wallet-daemon-client/ContactsRemoveRequest";
+export * from "./types/wallet-daemon-client/ContactsRemoveResponse";
+export * from "./types/wallet-daemon-client/SchedulesCreateRequest";
+export * from "./types/wallet-daemon-client/SchedulesCreateResponse";
+export * from "./types/wallet-daemon-client/SchedulesListRequest";
+export * from "./types/wallet-daemon-client/SchedulesListResponse";
+export * from "./types/wallet-daemon-client/SchedulesCancelRequest";
+export * from "./types/wallet-daemon-client/SchedulesCancelResponse";
+export * from "./types/wallet-daemon-client/WalletGcRequest";
+export * from "./types/wallet-daemon-client/WalletGcResponse";
+export * from "./types/wallet-daemon-client/TransactionReviewSponsoredRequest";
+export * from "./types/wallet-daemon-client/TransactionReviewSponsoredResponse";
+export * from "./types/wallet-daemon-client/TransactionAcceptSponsoredRequest";
+export * from "./types/wallet-daemon-client/TransactionAcceptSponsoredResponse";
+export * from "./types/wallet-daemon-client/EventsListRequest";
+export * from "./types/wallet-daemon-client/EventsListResponse";
+export * from "./types/wallet-daemon-client/AccountsCreateBalanceProofRequest";
+export * from "./types/wallet-daemon-client/AccountBalanceStatement";
+export * from "./types/wallet-daemon-client/BalanceStatementEntry";
+export * from "./types/wallet-daemon-client/AccountsCreateBalanceProofResponse";
+export * from "./types/wallet-daemon-client/NetworkStatusRequest";
+export * from "./types/wallet-daemon-client/NetworkStatusResponse";
+export * from "./types/wallet-daemon-client/IndexerEndpointStatus";
+export * from "./types/wallet-daemon-client/TransactionExportUnsignedRequest";
+export * from "./types/wallet-daemon-client/TransactionExportUnsignedResponse";
+export * from "./types/wallet-daemon-client/TransactionImportSignedRequest";
+export * from "./types/wallet-daemon-client/TransactionImportSignedResponse";
+export * from "./types/wallet-daemon-client/AccountsGetBalanceAtEpochRequest";
+export * from "./types/wallet-daemon-client/AccountsGetBalanceAtEpochResponse";
+export * from "./types/wallet-daemon-client/TransactionGetCanonicalBytesRequest";
+export * from "./types/wallet-daemon-client/TransactionGetCanonicalBytesResponse";
@@ -13,12 +13,18 @@ import {
   AccountsCreateFreeTestCoinsResponse,
   AccountsCreateRequest,
   AccountsCreateResponse,
+  AccountsCreateBalanceProofRequest,
+  AccountsCreateBalanceProofResponse,
   AccountsGetBalancesRequest,
   AccountsGetBalancesResponse,
   AccountsListRequest,
   AccountsListResponse,
+  AccountsRecoverRequest,
+  AccountsRecoverResponse,
   AccountsTransferRequest,
   AccountsTransferResponse,
+  AttestationGetRequest,
+  AttestationGetResponse,
   AuthGetAllJwtRequest,
   AuthGetAllJwtResponse,
   AuthRevokeTokenRequest,
@@ -32,6 +38,8 @@ import {
   ConfidentialTransferResponse,
   ConfidentialViewVaultBalanceRequest,
   ConfidentialViewVaultBalanceResponse,
+  EventsListRequest,
+  EventsListResponse,
   KeyBranch,
   KeysCreateRequest,
   KeysCreateResponse,
@@ -54,10 +62,20 @@ import {
   TemplatesGetResponse,
   TransactionGetAllRequest,
   TransactionGetAllResponse,
+  TransactionGetFailuresRequest,
+  TransactionGetFailuresResponse,
   TransactionGetRequest,
   TransactionGetResponse,
   TransactionGetResultRequest,
   TransactionGetResultResponse,
+  TransactionRetryRequest,
+  TransactionRetryResponse,
+  TransactionSetAnnotationRequest,
+  TransactionSetAnnotationResponse,
+  TransactionReviewSponsoredRequest,
+  TransactionReviewSponsoredResponse,
+  TransactionAcceptSponsoredRequest,
+  TransactionAcceptSponsoredResponse,
   TransactionSubmitRequest,
   TransactionSubmitResponse,
   TransactionWaitResultRequest,
@@ -94,12 +112,18 @@ export type {
   AccountsCreateFreeTestCoinsResponse,
   AccountsCreateRequest,
   AccountsCreateResponse,
+  AccountsCreateBalanceProofRequest,
+  AccountsCreateBalanceProofResponse,
   AccountsGetBalancesRequest,
   AccountsGetBalancesResponse,
   AccountsListRequest,
   AccountsListResponse,
+  AccountsRecoverRequest,
+  AccountsRecoverResponse,
   AccountsTransferRequest,
   AccountsTransferResponse,
+  AttestationGetRequest,
+  AttestationGetResponse,
   AuthGetAllJwtRequest,
   AuthGetAllJwtResponse,
   AuthRevokeTokenRequest,
@@ -113,6 +137,8 @@ export type {
   ConfidentialTransferResponse,
   ConfidentialViewVaultBalanceRequest,
   ConfidentialViewVaultBalanceResponse,
+  EventsListRequest,
+  EventsListResponse,
   KeyBranch,
   KeysCreateRequest,
   KeysCreateResponse,
@@ -135,10 +161,20 @@ export type {
   TemplatesGetResponse,
   TransactionGetAllRequest,
   TransactionGetAllResponse,
+  TransactionGetFailuresRequest,
+  TransactionGetFailuresResponse,
   TransactionGetRequest,
   TransactionGetResponse,
   TransactionGetResultRequest,
   TransactionGetResultResponse,
+  TransactionRetryRequest,
+  TransactionRetryResponse,
+  TransactionSetAnnotationRequest,
+  TransactionSetAnnotationResponse,
+  TransactionReviewSponsoredRequest,
+  TransactionReviewSponsoredResponse,
+  TransactionAcceptSponsoredRequest,
+  TransactionAcceptSponsoredResponse,
   TransactionSubmitRequest,
   TransactionSubmitResponse,
   TransactionWaitResultRequest,
@@ -224,6 +260,12 @@ export class WalletDaemonClient {
     return this.__invokeRpc("accounts.get_balances", params);
   }
 
+  public accountsCreateBalanceProof(
+    params: AccountsCreateBalanceProofRequest,
+  ): Promise<AccountsCreateBalanceProofResponse> {
+    return this.__invokeRpc("accounts.create_balance_proof", params);
+  }
+
   public accountsList(params: AccountsListRequest): Promise<AccountsListResponse> {
     return this.__invokeRpc("accounts.list", params);
   }
@@ -248,6 +290,10 @@ export class WalletDaemonClient {
     return this.__invokeRpc("accounts.set_default", params);
   }
 
+  public accountsRecover(params: AccountsRecoverRequest): Promise<AccountsRecoverResponse> {
+    return this.__invokeRpc("accounts.recover", params);
+  }
+
 
   public submitTransaction(params: TransactionSubmitRequest): Promise<TransactionSubmitResponse> {
     return this.__invokeRpc("transactions.submit", params);
@@ -261,10 +307,38 @@ export class WalletDaemonClient {
     return this.__invokeRpc("substates.list", params);
   }
 
+  public eventsList(params: EventsListRequest): Promise<EventsListResponse> {
+    return this.__invokeRpc("events.list", params);
+  }
+
   public transactionsList(params: TransactionGetAllRequest): Promise<TransactionGetAllResponse> {
     return this.__invokeRpc("transactions.get_all", params);
   }
 
+  public transactionsGetFailures(params: TransactionGetFailuresRequest): Promise<TransactionGetFailuresResponse> {
+    return this.__invokeRpc("transactions.get_failures", params);
+  }
+
+  public transactionsRetry(params: TransactionRetryRequest): Promise<TransactionRetryResponse> {
+    return this.__invokeRpc("transactions.retry", params);
+  }
+
+  public transactionsSetAnnotation(params: TransactionSetAnnotationRequest): Promise<TransactionSetAnnotationResponse> {
+    return this.__invokeRpc("transactions.set_annotation", params);
+  }
+
+  public transactionsReviewSponsored(
+    params: TransactionReviewSponsoredRequest,
+  ): Promise<TransactionReviewSponsoredResponse> {
+    return this.__invokeRpc("transactions.review_sponsored", params);
+  }
+
+  public transactionsAcceptSponsored(
+    params: TransactionAcceptSponsoredRequest,
+  ): Promise<TransactionAcceptSponsoredResponse> {
+    return this.__invokeRpc("transactions.accept_sponsored", params);
+  }
+
   public transactionsGet(params: TransactionGetRequest): Promise<TransactionGetResponse> {
     return this.__invokeRpc("transactions.get", params);
   }
@@ -317,6 +391,10 @@ export class WalletDaemonClient {
     return this.__invokeRpc("webrtc.start", params);
   }
 
+  public getAttestation(params: AttestationGetRequest): Promise<AttestationGetResponse> {
+    return this.__invokeRpc("attestation.get", params);
+  }
+
   public settingsGet(): Promise<SettingsGetResponse> {
     return this.__invokeRpc("settings.get");
   }
@@ -31,27 +31,62 @@ use crate::{
     types::{
         AddPeerRequest,
         AddPeerResponse,
+        ApiKeysIssueRequest,
+        ApiKeysIssueResponse,
+        ApiKeysListRequest,
+        ApiKeysListResponse,
+        ApiKeysRevokeRequest,
+        ApiKeysRevokeResponse,
         GetEpochManagerStatsResponse,
+        GetEpochStatsRequest,
+        GetEpochStatsResponse,
+        GetEventsRequest,
+        GetEventsResponse,
         GetNonFungiblesRequest,
         GetNonFungiblesResponse,
+        GetPendingTransactionRequest,
+        GetPendingTransactionResponse,
+        GetPendingTransactionsRequest,
+        GetPendingTransactionsResponse,
+        GetQueryResultRequest,
+        GetQueryResultResponse,
+        GetSubstateDiffRequest,
+        GetSubstateDiffResponse,
         GetSubstateRequest,
         GetSubstateResponse,
         GetTemplateDefinitionRequest,
         GetTemplateDefinitionResponse,
         GetTransactionResultRequest,
         GetTransactionResultResponse,
+        GetValidatorIdentityChallengeRequest,
+        GetValidatorIdentityChallengeResponse,
+        GetValidatorIdentityRequest,
+        GetValidatorIdentityResponse,
         ListSubstatesRequest,
         ListSubstatesResponse,
+        ListValidatorIdentitiesRequest,
+        ListValidatorIdentitiesResponse,
+        RegisterValidatorIdentityRequest,
+        RegisterValidatorIdentityResponse,
+        SubmitQueryRequest,
+        SubmitQueryResponse,
         SubmitTransactionRequest,
         SubmitTransactionResponse,
     },
 };
 
+/// The HTTP header used to send the API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+/// The HTTP header used to send the trace ID of the user action that caused the request
+pub const TRACE_ID_HEADER: &str = "x-request-id";
+
 #[derive(Debug, Clone)]
 pub struct IndexerJsonRpcClient {
     client: reqwest::Client,
     endpoint: Url,
     request_id: i64,
+    api_key: Option<String>,
+    trace_id: Option<String>,
 }
 
 impl IndexerJsonRpcClient {
@@ -68,9 +103,24 @@ impl IndexerJsonRpcClient {
             client,
             endpoint: endpoint.into_url()?,
             request_id: 0,
+            api_key: None,
+            trace_id: None,
         })
     }
 
+    /// Sets the API key that is sent with every request. This is required if the indexer has API keys enabled.
+    pub fn with_api_key<T: Into<String>>(mut self, api_key: T) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Sets the trace ID that is sent with every request so that the requests can be correlated with the user action
+    /// that caused them in the indexer logs.
+    pub fn with_trace_id<T: Into<String>>(mut self, trace_id: T) -> Self {
+        self.trace_id = Some(trace_id.into());
+        self
+    }
+
     fn next_request_id(&mut self) -> i64 {
         self.request_id += 1;
         self.request_id
@@ -84,6 +134,13 @@ impl IndexerJsonRpcClient {
         self.send_request("get_substate", req).await
     }
 
+    pub async fn get_substate_diff(
+        &mut self,
+        req: GetSubstateDiffRequest,
+    ) -> Result<GetSubstateDiffResponse, IndexerClientError> {
+        self.send_request("get_substate_diff", req).await
+    }
+
     pub async fn list_substates(
         &mut self,
         req: ListSubstatesRequest,
@@ -114,6 +171,14 @@ impl IndexerJsonRpcClient {
         self.send_request("submit_transaction", req).await
     }
 
+    /// Executes the transaction as a dry run and returns statistics of the substates that it accesses
+    pub async fn get_substate_access_stats(
+        &mut self,
+        req: GetSubstateAccessStatsRequest,
+    ) -> Result<GetSubstateAccessStatsResponse, IndexerClientError> {
+        self.send_request("get_substate_access_stats", req).await
+    }
+
     pub async fn get_transaction_result(
         &mut self,
         req: GetTransactionResultRequest,
@@ -139,6 +204,88 @@ impl IndexerJsonRpcClient {
         self.send_request("get_epoch_manager_stats", ()).await
     }
 
+    pub async fn api_keys_issue(
+        &mut self,
+        req: ApiKeysIssueRequest,
+    ) -> Result<ApiKeysIssueResponse, IndexerClientError> {
+        self.send_request("api_keys.issue", req).await
+    }
+
+    pub async fn api_keys_revoke(
+        &mut self,
+        req: ApiKeysRevokeRequest,
+    ) -> Result<ApiKeysRevokeResponse, IndexerClientError> {
+        self.send_request("api_keys.revoke", req).await
+    }
+
+    pub async fn api_keys_list(&mut self, req: ApiKeysListRequest) -> Result<ApiKeysListResponse, IndexerClientError> {
+        self.send_request("api_keys.list", req).await
+    }
+
+    pub async fn get_validator_identity_challenge(
+        &mut self,
+        req: GetValidatorIdentityChallengeRequest,
+    ) -> Result<GetValidatorIdentityChallengeResponse, IndexerClientError> {
+        self.send_request("validators.identity_challenge", req).await
+    }
+
+    pub async fn register_validator_identity(
+        &mut self,
+        req: RegisterValidatorIdentityRequest,
+    ) -> Result<RegisterValidatorIdentityResponse, IndexerClientError> {
+        self.send_request("validators.register_identity", req).await
+    }
+
+    pub async fn get_validator_identity(
+        &mut self,
+        req: GetValidatorIdentityRequest,
+    ) -> Result<GetValidatorIdentityResponse, IndexerClientError> {
+        self.send_request("validators.get_identity", req).await
+    }
+
+    pub async fn list_validator_identities(
+        &mut self,
+        req: ListValidatorIdentitiesRequest,
+    ) -> Result<ListValidatorIdentitiesResponse, IndexerClientError> {
+        self.send_request("validators.list_identities", req).await
+    }
+
+    pub async fn get_epoch_stats(
+        &mut self,
+        req: GetEpochStatsRequest,
+    ) -> Result<GetEpochStatsResponse, IndexerClientError> {
+        self.send_request("get_epoch_stats", req).await
+    }
+
+    pub async fn get_events(&mut self, req: GetEventsRequest) -> Result<GetEventsResponse, IndexerClientError> {
+        self.send_request("get_events", req).await
+    }
+
+    pub async fn submit_query(&mut self, req: SubmitQueryRequest) -> Result<SubmitQueryResponse, IndexerClientError> {
+        self.send_request("submit_query", req).await
+    }
+
+    pub async fn get_query_result(
+        &mut self,
+        req: GetQueryResultRequest,
+    ) -> Result<GetQueryResultResponse, IndexerClientError> {
+        self.send_request("get_query_result", req).await
+    }
+
+    pub async fn get_pending_transactions(
+        &mut self,
+        req: GetPendingTransactionsRequest,
+    ) -> Result<GetPendingTransactionsResponse, IndexerClientError> {
+        self.send_request("get_pending_transactions", req).await
+    }
+
+    pub async fn get_pending_transaction(
+        &mut self,
+        req: GetPendingTransactionRequest,
+    ) -> Result<GetPendingTransactionResponse, IndexerClientError> {
+        self.send_request("get_pending_transaction", req).await
+    }
+
     async fn send_request<T: Serialize, R: DeserializeOwned>(
         &mut self,
         method: &str,
@@ -157,12 +304,14 @@ impl IndexerJsonRpcClient {
                 "params": params,
             }
         );
-        let resp = self
-            .client
-            .post(self.endpoint.clone())
-            .body(request_json.to_string())
-            .send()
-            .await?;
+        let mut request = self.client.post(self.endpoint.clone()).body(request_json.to_string());
+        if let Some(ref api_key) = self.api_key {
+            request = request.header(API_KEY_HEADER, api_key);
+        }
+        if let Some(ref trace_id) = self.trace_id {
+            request = request.header(TRACE_ID_HEADER, trace_id);
+        }
+        let resp = request.send().await?;
         let val = resp.json().await?;
         let resp = jsonrpc_result(val)?;
         // Response might not deserialize to R....
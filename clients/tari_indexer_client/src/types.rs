@@ -1,7 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -9,12 +9,15 @@ use serde_json::Value as JsonValue;
 use serde_with::{serde_as, DisplayFromStr};
 use tari_base_node_client::types::BaseLayerValidatorNode;
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_common_types::{substate_type::SubstateType, Epoch, SubstateRequirement};
-use tari_dan_storage::consensus_models::Decision;
+use tari_dan_common_types::{substate_type::SubstateType, Epoch, ShardGroup, SubstateRequirement};
+use tari_dan_storage::consensus_models::{Decision, TransactionPoolStage, ValidatorSignature};
 use tari_engine_types::{
     commit_result::ExecuteResult,
+    events::Event,
+    fees::FeeReceipt,
     serde_with as serde_tools,
-    substate::{Substate, SubstateId},
+    substate::{Substate, SubstateId, SubstateValue},
+    substate_access::SubstateAccessStats,
     TemplateAddress,
 };
 use tari_template_abi::TemplateDef;
@@ -100,6 +103,69 @@ pub struct GetSubstateResponse {
     pub created_by_transaction: TransactionId,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetSubstateDiffRequest {
+    #[serde(with = "serde_tools::string")]
+    pub address: SubstateId,
+    pub from_version: u32,
+    /// The version to compare against. Defaults to the latest version.
+    #[serde(default)]
+    pub to_version: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetSubstateDiffResponse {
+    #[serde(with = "serde_tools::string")]
+    pub address: SubstateId,
+    pub from_version: u32,
+    pub to_version: u32,
+    /// The template of the component, if the substate is a component
+    #[serde(default, with = "serde_tools::string::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub template_address: Option<TemplateAddress>,
+    pub template_name: Option<String>,
+    pub changes: Vec<SubstateFieldChange>,
+}
+
+/// A change to a single field of a substate. The path is made up of object keys and array indexes e.g.
+/// `state.balances[2].amount`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct SubstateFieldChange {
+    pub path: String,
+    pub kind: SubstateFieldChangeKind,
+    #[cfg_attr(feature = "ts", ts(type = "any"))]
+    pub old_value: Option<JsonValue>,
+    #[cfg_attr(feature = "ts", ts(type = "any"))]
+    pub new_value: Option<JsonValue>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub enum SubstateFieldChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
@@ -144,6 +210,10 @@ pub struct SubmitTransactionRequest {
     #[serde(default)]
     pub required_substates: Vec<SubstateRequirement>,
     pub is_dry_run: bool,
+    /// Substate values that override the network state when executing a dry run. Only valid if `is_dry_run` is true.
+    #[serde(default, with = "serde_tools::vec")]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[any, any]>"))]
+    pub substate_overrides: HashMap<SubstateId, SubstateValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -526,3 +596,557 @@ pub struct GetTemplateDefinitionResponse {
     pub name: String,
     pub definition: TemplateDef,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysIssueRequest {
+    pub name: String,
+    /// The JSON-RPC methods that the key may call. If not provided, all non-admin methods are allowed.
+    #[serde(default)]
+    pub allowed_methods: Option<Vec<String>>,
+    /// The maximum number of requests per minute. If not provided, the configured default limit applies.
+    #[serde(default)]
+    pub rate_limit_per_minute: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysIssueResponse {
+    /// The secret API key. This is only returned once and cannot be recovered from the indexer.
+    pub api_key: String,
+    pub info: ApiKeyInfo,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysRevokeRequest {
+    pub key_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysRevokeResponse {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysListRequest {
+    /// Include revoked keys in the response
+    #[serde(default)]
+    pub include_revoked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeysListResponse {
+    pub keys: Vec<ApiKeyInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeyInfo {
+    /// The public identifier of the key, used to revoke it
+    pub key_id: String,
+    pub name: String,
+    pub allowed_methods: Option<Vec<String>>,
+    pub rate_limit_per_minute: Option<u32>,
+    /// Unix timestamp in seconds
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub created_at: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub revoked_at: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub last_used_at: Option<u64>,
+    pub usage: ApiKeyUsage,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ApiKeyUsage {
+    /// The total number of requests made with the key
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_requests: u64,
+    /// The number of requests rejected due to the rate limit since the indexer started
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub rate_limited_requests: u64,
+    /// The number of requests rejected because the method is not allowed since the indexer started
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub denied_requests: u64,
+}
+
+/// Metadata that a validator operator publishes about their validator node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ValidatorMetadata {
+    pub name: String,
+    #[serde(default)]
+    pub website: Option<String>,
+    #[serde(default)]
+    pub contact: Option<String>,
+}
+
+impl ValidatorMetadata {
+    /// Returns the message that the validator node key signs to register this metadata with the indexer-issued
+    /// challenge
+    pub fn challenge_message(&self, challenge: &str) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(b"tari.indexer.validator_identity.v1");
+        for field in [
+            Some(challenge),
+            Some(self.name.as_str()),
+            self.website.as_deref(),
+            self.contact.as_deref(),
+        ] {
+            match field {
+                Some(field) => {
+                    message.push(1);
+                    message.extend_from_slice(&(field.len() as u64).to_le_bytes());
+                    message.extend_from_slice(field.as_bytes());
+                },
+                None => message.push(0),
+            }
+        }
+        message
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ValidatorIdentity {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    pub metadata: ValidatorMetadata,
+    /// Unix timestamp in seconds of the registration of the metadata
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub verified_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetValidatorIdentityChallengeRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetValidatorIdentityChallengeResponse {
+    /// Hex encoded challenge. The challenge may only be used once.
+    pub challenge: String,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct RegisterValidatorIdentityRequest {
+    pub metadata: ValidatorMetadata,
+    pub challenge: String,
+    /// Signature of [ValidatorMetadata::challenge_message] by the validator node key
+    pub signature: ValidatorSignature,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct RegisterValidatorIdentityResponse {
+    pub identity: ValidatorIdentity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetValidatorIdentityRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetValidatorIdentityResponse {
+    pub identity: ValidatorIdentity,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ListValidatorIdentitiesRequest {
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct ListValidatorIdentitiesResponse {
+    pub identities: Vec<ValidatorIdentity>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetPendingTransactionsRequest {
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetPendingTransactionsResponse {
+    pub transactions: Vec<IndexerPendingTransaction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetPendingTransactionRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetPendingTransactionResponse {
+    pub transaction: IndexerPendingTransaction,
+}
+
+/// A transaction that is in the pool of at least one committee in the current epoch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct IndexerPendingTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    /// Unix timestamp in seconds of the first poll that reported the transaction
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub first_seen_at: u64,
+    /// Unix timestamp in seconds of the last poll that reported the transaction
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub last_seen_at: u64,
+    pub committees: Vec<PendingTransactionCommittee>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct PendingTransactionCommittee {
+    pub shard_group: ShardGroup,
+    pub stage: TransactionPoolStage,
+    /// True if the transaction is ready to be proposed at its current stage
+    pub is_ready: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetEpochStatsRequest {
+    /// The maximum number of epochs to return. Defaults to 100.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetEpochStatsResponse {
+    /// The stats of the most recent epochs, most recent first
+    pub stats: Vec<IndexerEpochStats>,
+}
+
+/// Counts of the data scanned by the indexer in an epoch. Stats are retained independently of the substates and
+/// events that they count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct IndexerEpochStats {
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_blocks: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_transactions: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_events: u64,
+    /// The number of events that matched the event filters and were stored
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_stored_events: u64,
+    /// Unix timestamp in seconds of the last update to the stats
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub updated_at: u64,
+}
+
+/// A heavy query that is executed by the indexer's query worker pool instead of the interactive API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub enum IndexerQuery {
+    ListSubstates {
+        #[serde(default, with = "serde_tools::string::option")]
+        #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+        filter_by_template: Option<TemplateAddress>,
+        filter_by_type: Option<SubstateType>,
+        #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+        limit: Option<u64>,
+        #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+        offset: Option<u64>,
+    },
+    EpochStats {
+        #[cfg_attr(feature = "ts", ts(type = "number"))]
+        limit: u64,
+    },
+    NonFungibleCollections,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub enum IndexerQueryResult {
+    ListSubstates {
+        substates: Vec<ListSubstateItem>,
+    },
+    EpochStats {
+        stats: Vec<IndexerEpochStats>,
+    },
+    NonFungibleCollections {
+        #[cfg_attr(feature = "ts", ts(type = "Array<[string, number]>"))]
+        collections: Vec<(String, i64)>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub enum IndexerQueryStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed { error: String },
+    /// The query did not complete within the configured time limit
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct SubmitQueryRequest {
+    pub query: IndexerQuery,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct SubmitQueryResponse {
+    /// The id used to fetch the result with `get_query_result`. If an identical query completed recently, the id of
+    /// that query is returned and its result is served from the cache.
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub query_id: u64,
+    pub status: IndexerQueryStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetQueryResultRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub query_id: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetQueryResultResponse {
+    pub status: IndexerQueryStatus,
+    /// The result of the query, once it has completed
+    pub result: Option<IndexerQueryResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(
+        export,
+        export_to = "../../bindings/src/types/tari-indexer-client/",
+        rename = "IndexerGetEventsRequest"
+    )
+)]
+pub struct GetEventsRequest {
+    /// Only return events with this topic
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Only return events emitted by this substate
+    #[serde(default)]
+    pub substate_id: Option<SubstateId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<u32>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(
+        export,
+        export_to = "../../bindings/src/types/tari-indexer-client/",
+        rename = "IndexerGetEventsResponse"
+    )
+)]
+pub struct GetEventsResponse {
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetSubstateAccessStatsRequest {
+    pub transaction: Transaction,
+    #[serde(default)]
+    pub required_substates: Vec<SubstateRequirement>,
+    /// Substate values that override the network state when executing the transaction
+    #[serde(default, with = "serde_tools::vec")]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[any, any]>"))]
+    pub substate_overrides: HashMap<SubstateId, SubstateValue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/tari-indexer-client/")
+)]
+pub struct GetSubstateAccessStatsResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub stats: SubstateAccessStats,
+    /// The fees that the transaction would be charged, including the storage fees for the bytes written
+    pub fee_receipt: FeeReceipt,
+}
@@ -28,6 +28,7 @@ use reqwest::{header, header::HeaderMap, IntoUrl, Url};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json as json;
 use serde_json::json;
+use tari_engine_types::rpc_encoding::RpcEncoding;
 
 use crate::types::*;
 
@@ -36,6 +37,7 @@ pub struct ValidatorNodeClient {
     client: reqwest::Client,
     endpoint: Url,
     request_id: i64,
+    response_encoding: RpcEncoding,
 }
 
 impl ValidatorNodeClient {
@@ -52,9 +54,17 @@ impl ValidatorNodeClient {
             client,
             endpoint: endpoint.into_url()?,
             request_id: 0,
+            response_encoding: RpcEncoding::Json,
         })
     }
 
+    /// Sets the encoding that is requested for responses. CBOR responses are smaller and faster to decode than JSON,
+    /// which matters for large responses such as substates and transaction results.
+    pub fn with_response_encoding(mut self, encoding: RpcEncoding) -> Self {
+        self.response_encoding = encoding;
+        self
+    }
+
     pub fn endpoint(&self) -> &Url {
         &self.endpoint
     }
@@ -67,10 +77,95 @@ impl ValidatorNodeClient {
         self.send_request("get_epoch_manager_stats", json!({})).await
     }
 
+    pub async fn get_epoch_transition_rehearsal(
+        &mut self,
+    ) -> Result<GetEpochTransitionRehearsalResponse, ValidatorNodeClientError> {
+        self.send_request("get_epoch_transition_rehearsal", json!({})).await
+    }
+
+    pub async fn get_vault_balance(
+        &mut self,
+        request: GetVaultBalanceRequest,
+    ) -> Result<GetVaultBalanceResponse, ValidatorNodeClientError> {
+        self.send_request("get_vault_balance", request).await
+    }
+
+    pub async fn get_substate_pruning_status(
+        &mut self,
+    ) -> Result<GetSubstatePruningStatusResponse, ValidatorNodeClientError> {
+        self.send_request("get_substate_pruning_status", json!({})).await
+    }
+
+    pub async fn get_tx_pool_by_priority(
+        &mut self,
+        request: GetTxPoolByPriorityRequest,
+    ) -> Result<GetTxPoolByPriorityResponse, ValidatorNodeClientError> {
+        self.send_request("get_tx_pool_by_priority", request).await
+    }
+
+    pub async fn get_misbehaviour_evidence(
+        &mut self,
+        request: GetMisbehaviourEvidenceRequest,
+    ) -> Result<GetMisbehaviourEvidenceResponse, ValidatorNodeClientError> {
+        self.send_request("get_misbehaviour_evidence", request).await
+    }
+
+    pub async fn get_events(
+        &mut self,
+        request: GetEventsRequest,
+    ) -> Result<GetEventsResponse, ValidatorNodeClientError> {
+        self.send_request("get_events", request).await
+    }
+
+    pub async fn get_foreign_receive_gaps(
+        &mut self,
+        request: GetForeignReceiveGapsRequest,
+    ) -> Result<GetForeignReceiveGapsResponse, ValidatorNodeClientError> {
+        self.send_request("get_foreign_receive_gaps", request).await
+    }
+
+    pub async fn trace_transaction(
+        &mut self,
+        request: TraceTransactionRequest,
+    ) -> Result<TraceTransactionResponse, ValidatorNodeClientError> {
+        self.send_request("trace_transaction", request).await
+    }
+
+    pub async fn get_bandwidth_stats(&mut self) -> Result<GetBandwidthStatsResponse, ValidatorNodeClientError> {
+        self.send_request("get_bandwidth_stats", json!({})).await
+    }
+
+    pub async fn get_mempool_transactions(
+        &mut self,
+    ) -> Result<GetMempoolTransactionsResponse, ValidatorNodeClientError> {
+        self.send_request("get_mempool_transactions", json!({})).await
+    }
+
+    pub async fn evict_mempool_transaction(
+        &mut self,
+        request: EvictMempoolTransactionRequest,
+    ) -> Result<EvictMempoolTransactionResponse, ValidatorNodeClientError> {
+        self.send_request("evict_mempool_transaction", request).await
+    }
+
     pub async fn get_consensus_status(&mut self) -> Result<GetConsensusStatusResponse, ValidatorNodeClientError> {
         self.send_request("get_consensus_status", json!({})).await
     }
 
+    pub async fn get_committee(
+        &mut self,
+        request: GetCommitteeRequest,
+    ) -> Result<GetCommitteeResponse, ValidatorNodeClientError> {
+        self.send_request("get_committee", request).await
+    }
+
+    pub async fn get_network_committees(
+        &mut self,
+        request: GetNetworkCommitteesRequest,
+    ) -> Result<GetNetworkCommitteeResponse, ValidatorNodeClientError> {
+        self.send_request("get_network_committees", request).await
+    }
+
     pub async fn get_active_templates(
         &mut self,
         request: GetTemplatesRequest,
@@ -89,6 +184,13 @@ impl ValidatorNodeClient {
         self.send_request("get_substate", request).await
     }
 
+    pub async fn get_substate_with_proof(
+        &mut self,
+        request: GetSubstateWithProofRequest,
+    ) -> Result<GetSubstateWithProofResponse, ValidatorNodeClientError> {
+        self.send_request("get_substate_with_proof", request).await
+    }
+
     pub async fn get_fees(
         &mut self,
         request: GetValidatorFeesRequest,
@@ -182,10 +284,11 @@ impl ValidatorNodeClient {
         let resp = self
             .client
             .post(self.endpoint.clone())
+            .header(header::ACCEPT, self.response_encoding.content_type())
             .body(request_json.to_string())
             .send()
             .await?;
-        let val = resp.json().await?;
+        let val = decode_response(resp).await?;
         let resp = jsonrpc_result(val)?;
         // Response might not deserialize to R....
         match serde_json::from_value(resp) {
@@ -198,6 +301,17 @@ impl ValidatorNodeClient {
     }
 }
 
+/// Decodes the response body in the encoding given by its content type. A server that does not support the requested
+/// encoding responds with JSON.
+async fn decode_response(resp: reqwest::Response) -> Result<json::Value, ValidatorNodeClientError> {
+    let encoding =
+        RpcEncoding::from_content_type(resp.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()));
+    let body = resp.bytes().await?;
+    encoding
+        .decode(&body)
+        .map_err(|e| ValidatorNodeClientError::InvalidResponse { message: e.to_string() })
+}
+
 fn jsonrpc_result(val: json::Value) -> Result<json::Value, ValidatorNodeClientError> {
     if let Some(err) = val.get("error") {
         let code = err.get("code").and_then(|c| c.as_i64()).unwrap_or(-1);
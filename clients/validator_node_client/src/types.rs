@@ -20,7 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{ops::RangeInclusive, sync::Arc, time::Duration};
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc, time::Duration};
 
 use multiaddr::Multiaddr;
 use serde::{Deserialize, Serialize};
@@ -32,17 +32,24 @@ use tari_dan_common_types::{
     Epoch,
     NodeHeight,
     PeerAddress,
+    ShardGroup,
     SubstateAddress,
 };
 use tari_dan_storage::{
     consensus_models::{
         Block,
+        BlockHeader,
         BlockId,
         Decision,
         ExecutedTransaction,
+        ForeignReceiveGap,
+        MisbehaviourEvidence,
         QuorumDecision,
+        ReceivedForeignIndex,
         SubstateRecord,
+        TransactionEvent,
         TransactionPoolRecord,
+        TransactionTrace,
     },
     global::models,
     Ordering,
@@ -52,8 +59,13 @@ use tari_engine_types::{
     fees::FeeCostBreakdown,
     serde_with,
     substate::{SubstateId, SubstateValue},
+    substate_proof::SubstateInclusionProof,
     TemplateAddress,
 };
+use tari_template_lib::{
+    models::{Amount, ResourceAddress, VaultId},
+    resource::ResourceType,
+};
 use tari_transaction::{Transaction, TransactionId};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
@@ -180,6 +192,11 @@ pub struct TemplateMetadata {
     pub address: TemplateAddress,
     /// SHA hash of binary
     pub binary_sha: Vec<u8>,
+    /// The public key of the template author. This is None for builtin templates.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub author_public_key: Option<PublicKey>,
+    pub template_type: String,
+    pub status: String,
 }
 
 /// A request to submit a transaction
@@ -436,6 +453,31 @@ pub struct GetTxPoolResponse {
     pub tx_pool: Vec<TransactionPoolRecord>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTxPoolByPriorityRequest {
+    /// The maximum number of transactions to return. If not set, all transactions in the pool are returned.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetTxPoolByPriorityResponse {
+    /// The transactions in the pool in the order that they will be proposed. Transactions that are already in progress
+    /// are proposed before new transactions, which are ordered by priority fee.
+    pub tx_pool: Vec<TransactionPoolRecord>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -509,19 +551,37 @@ pub enum LogLevel {
 )]
 pub struct GetCommitteeRequest {
     pub epoch: Epoch,
-    pub substate_address: SubstateAddress,
+    /// Returns the committee of the shard group containing this substate address. Either this or `shard_group` must
+    /// be provided.
+    #[serde(default)]
+    pub substate_address: Option<SubstateAddress>,
+    #[serde(default)]
+    pub shard_group: Option<ShardGroup>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/validator-node-client/")
 )]
 pub struct GetCommitteeResponse {
+    pub shard_group: ShardGroup,
     pub committee: Committee<PeerAddress>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetNetworkCommitteesRequest {
+    /// The epoch of the committees. Defaults to the current epoch.
+    #[serde(default)]
+    pub epoch: Option<Epoch>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -530,6 +590,8 @@ pub struct GetCommitteeResponse {
 )]
 pub struct GetNetworkCommitteeResponse {
     pub current_epoch: Epoch,
+    /// The epoch of the committees
+    pub epoch: Epoch,
     pub committees: Vec<CommitteeShardInfo>,
 }
 
@@ -544,8 +606,10 @@ pub struct GetNetworkCommitteeResponse {
     )
 )]
 pub struct CommitteeShardInfo {
+    /// The first shard of the shard group
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub shard: Shard,
+    pub shard_group: ShardGroup,
     pub substate_address_range: RangeInclusive<SubstateAddress>,
     pub validators: Vec<ValidatorNode>,
 }
@@ -653,6 +717,33 @@ pub struct GetSubstateResponse {
     pub status: SubstateStatus,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSubstateWithProofRequest {
+    pub address: SubstateId,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSubstateWithProofResponse {
+    pub value: SubstateValue,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub created_by_tx: TransactionId,
+    /// The header of the committed block that the proof is generated against
+    pub block_header: BlockHeader,
+    /// Proves that the substate is included in the `state_merkle_root` of the block header
+    pub proof: SubstateInclusionProof,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -724,6 +815,191 @@ pub struct GetEpochManagerStatsResponse {
     pub is_valid: bool,
     pub start_epoch: Option<Epoch>,
     pub committee_info: Option<CommitteeInfo>,
+    pub auto_registration: AutoRegistrationStatus,
+}
+
+/// The status of the automatic re-registration of the local validator node before its registration expires
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct AutoRegistrationStatus {
+    pub enabled: bool,
+    /// The number of epochs before the registration expires at which a re-registration is submitted
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub epochs_before_expiry: u64,
+    /// The last epoch of the current registration, or None if the node is not registered or the registration does not
+    /// expire
+    pub registration_end_epoch: Option<Epoch>,
+    /// The epoch in which a re-registration was last submitted
+    pub last_submitted_epoch: Option<Epoch>,
+    /// The registration end epoch that the last re-registration was submitted for
+    pub last_submitted_for_end_epoch: Option<Epoch>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetEpochTransitionRehearsalResponse {
+    pub rehearsal: EpochTransitionRehearsal,
+}
+
+/// A simulation of the local validator's transition into the next epoch, based on the validator registrations known so
+/// far. The rehearsal does not affect consensus.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct EpochTransitionRehearsal {
+    pub current_epoch: Epoch,
+    pub next_epoch: Epoch,
+    /// The shard group of the local validator in the current epoch, if registered
+    pub current_shard_group: Option<ShardGroup>,
+    /// The projected shard group of the local validator in the next epoch, if registered
+    pub next_shard_group: Option<ShardGroup>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub next_num_validators: u64,
+    pub next_num_committees: u32,
+    /// The number of validators in the projected local committee
+    pub next_committee_size: u32,
+    /// Shards that the local validator manages in the next epoch but not in the current epoch. The state of these
+    /// shards must be synced before the validator can participate.
+    pub shards_to_sync: Vec<Shard>,
+    /// Shards that the local validator manages in the current epoch but not in the next epoch
+    pub shards_to_hand_off: Vec<Shard>,
+    /// The number of transactions in the local transaction pool at the time of the rehearsal
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub pending_transactions: u64,
+    /// Issues that an operator should address before the transition
+    pub warnings: Vec<String>,
+    /// The outcome of shadow executing the pending transactions. Only set by the periodic rehearsal if shadow
+    /// execution is enabled.
+    pub shadow_execution: Option<ShadowExecutionSummary>,
+}
+
+/// The outcome of executing pending transactions against a throwaway store seeded with the state that the local
+/// validator would hold in the next epoch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct ShadowExecutionSummary {
+    /// The number of substates copied from the live state store into the throwaway store
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub handed_off_substates: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub accepted: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub rejected: u64,
+    /// Transactions with an input in a shard that must be synced before the transition
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub awaiting_sync: u64,
+    /// Transactions with an input that is managed by another committee in the next epoch
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub awaiting_foreign_inputs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetVaultBalanceRequest {
+    #[serde(with = "serde_with::string")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub vault_id: VaultId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetVaultBalanceResponse {
+    /// The latest version of the vault substate
+    pub version: u32,
+    #[serde(with = "serde_with::string")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub resource_address: ResourceAddress,
+    pub resource_type: ResourceType,
+    /// The balance of the vault. For confidential resources this is only the revealed balance.
+    pub balance: Amount,
+    /// The part of the balance that is locked by transactions that are in progress
+    pub locked_balance: Amount,
+    /// The number of confidential outputs held in the vault. The value of these outputs is not known to the validator.
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_confidential_outputs: u64,
+    /// The number of non-fungible tokens held in the vault
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_non_fungibles: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetSubstatePruningStatusResponse {
+    pub status: SubstatePruningStatus,
+}
+
+/// The status of the pruning of historical substate versions on the validator node. Totals are counted from the time
+/// the node was started.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct SubstatePruningStatus {
+    pub enabled: bool,
+    /// The number of epochs for which destroyed substate versions are retained
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub retention_epochs: u64,
+    pub is_running: bool,
+    /// Destroyed substate versions and state transitions before this epoch have been pruned
+    pub pruned_before_epoch: Option<Epoch>,
+    pub last_run: Option<SubstatePruningRun>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_substates_pruned: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_state_transitions_pruned: u64,
+    /// The total size of the pruned substate values. The database file only shrinks once it is compacted.
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub total_bytes_reclaimed: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct SubstatePruningRun {
+    /// The epoch in which pruning ran
+    pub epoch: Epoch,
+    pub pruned_before_epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_substates_pruned: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_state_transitions_pruned: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_reclaimed: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub duration_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -882,6 +1158,47 @@ pub struct GetConnectionsResponse {
     pub connections: Vec<Connection>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(
+        export,
+        export_to = "../../bindings/src/types/validator-node-client/",
+        rename = "VNBandwidthStats"
+    )
+)]
+pub struct BandwidthStats {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_sent: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_received: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub messages_sent: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub messages_received: u64,
+    /// Messages that were dropped because a per-peer bandwidth limit was exceeded
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub messages_throttled: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(
+        export,
+        export_to = "../../bindings/src/types/validator-node-client/",
+        rename = "VNGetBandwidthStatsResponse"
+    )
+)]
+pub struct GetBandwidthStatsResponse {
+    pub total: BandwidthStats,
+    /// Keyed by peer id
+    pub per_peer: HashMap<String, BandwidthStats>,
+    pub per_message_type: HashMap<String, BandwidthStats>,
+}
+
 #[derive(Serialize, Debug)]
 #[cfg_attr(
     feature = "ts",
@@ -891,3 +1208,178 @@ pub struct GetConnectionsResponse {
 pub struct GetMempoolStatsResponse {
     pub size: usize,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetMempoolTransactionsResponse {
+    /// The pending transactions, oldest first
+    pub transactions: Vec<MempoolTransactionInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct MempoolTransactionInfo {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub input_shards: Vec<Shard>,
+    /// The time since the transaction was received by the mempool
+    #[cfg_attr(feature = "ts", ts(type = "{secs: number, nanos: number}"))]
+    pub age: Duration,
+    /// The maximum fee declared by the fee instructions of the transaction, if any
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub declared_max_fee: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub fee_per_instruction: u64,
+    /// Set if the transaction is held back until this epoch
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub deferred_until: Option<Epoch>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct EvictMempoolTransactionRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct EvictMempoolTransactionResponse {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetMisbehaviourEvidenceRequest {
+    /// Only return evidence of misbehaviour in this epoch
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub epoch: Option<Epoch>,
+    /// Only return evidence against this validator
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub offender: Option<PublicKey>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetMisbehaviourEvidenceResponse {
+    /// Recorded evidence, most recent first
+    pub evidence: Vec<MisbehaviourEvidence>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetEventsRequest {
+    /// Only return events emitted by this transaction
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub transaction_id: Option<TransactionId>,
+    /// Only return events with this topic
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Only return events emitted by this substate
+    #[serde(default)]
+    pub substate_id: Option<SubstateId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetEventsResponse {
+    /// Events emitted by committed transactions, most recent first
+    pub events: Vec<TransactionEvent>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetForeignReceiveGapsRequest {
+    /// Also return gaps for which all missing foreign proposals have since been received
+    #[serde(default)]
+    pub include_resolved: bool,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub limit: Option<u64>,
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct GetForeignReceiveGapsResponse {
+    /// Gaps in the foreign indexes received from foreign shard groups, most recent first
+    pub gaps: Vec<ForeignReceiveGap>,
+    /// The last foreign index received from each foreign shard
+    pub last_received: HashMap<Shard, ReceivedForeignIndex>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct TraceTransactionRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/validator-node-client/")
+)]
+pub struct TraceTransactionResponse {
+    pub trace: TransactionTrace,
+}
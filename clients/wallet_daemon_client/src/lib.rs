@@ -32,14 +32,18 @@ use std::{
 
 use json::Value;
 use reqwest::{
-    header::{self, HeaderMap, AUTHORIZATION},
+    header::{self, HeaderMap, AUTHORIZATION, CONTENT_TYPE},
     IntoUrl,
+    RequestBuilder,
     Url,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json as json;
 use serde_json::json;
+use tari_common_types::types::PublicKey;
+use tari_engine_types::rpc_encoding::RpcEncoding;
 use tari_template_lib::models::ComponentAddress;
+use tari_utilities::hex::Hex;
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 use types::{
@@ -47,6 +51,8 @@ use types::{
     AccountsCreateFreeTestCoinsResponse,
     AccountsTransferRequest,
     AccountsTransferResponse,
+    AttestationGetRequest,
+    AttestationGetResponse,
     AuthLoginAcceptRequest,
     AuthLoginAcceptResponse,
     AuthLoginDenyRequest,
@@ -79,47 +85,112 @@ use crate::{
         AccountGetResponse,
         AccountSetDefaultRequest,
         AccountSetDefaultResponse,
+        AccountsCreateBalanceProofRequest,
+        AccountsCreateBalanceProofResponse,
         AccountsCreateRequest,
         AccountsCreateResponse,
+        AccountsGetBalanceAtEpochRequest,
+        AccountsGetBalanceAtEpochResponse,
         AccountsGetBalancesRequest,
         AccountsGetBalancesResponse,
+        AccountsImportRequest,
+        AccountsImportResponse,
+        AccountsImportWatchOnlyRequest,
+        AccountsImportWatchOnlyResponse,
         AccountsInvokeRequest,
         AccountsInvokeResponse,
         AccountsListRequest,
         AccountsListResponse,
+        AccountsRecoverRequest,
+        AccountsRecoverResponse,
         AuthGetAllJwtRequest,
         AuthGetAllJwtResponse,
         AuthRevokeTokenRequest,
         AuthRevokeTokenResponse,
         ClaimValidatorFeesRequest,
         ClaimValidatorFeesResponse,
+        ConfidentialAuditBalancesRequest,
+        ConfidentialAuditBalancesResponse,
         ConfidentialCreateOutputProofRequest,
         ConfidentialCreateOutputProofResponse,
         ConfidentialTransferRequest,
         ConfidentialTransferResponse,
         ConfidentialViewVaultBalanceRequest,
         ConfidentialViewVaultBalanceResponse,
+        ContactsAddRequest,
+        ContactsAddResponse,
+        ContactsListRequest,
+        ContactsListResponse,
+        ContactsRemoveRequest,
+        ContactsRemoveResponse,
+        EventsListRequest,
+        EventsListResponse,
         GetValidatorFeesRequest,
         GetValidatorFeesResponse,
         KeyBranch,
         KeysCreateRequest,
         KeysCreateResponse,
+        KeysGetSeedWordsRequest,
+        KeysGetSeedWordsResponse,
         KeysListRequest,
         KeysListResponse,
         KeysSetActiveRequest,
         KeysSetActiveResponse,
+        KeysVerifySeedWordsRequest,
+        KeysVerifySeedWordsResponse,
+        NetworkStatusRequest,
+        NetworkStatusResponse,
         RevealFundsRequest,
         RevealFundsResponse,
+        SchedulesCancelRequest,
+        SchedulesCancelResponse,
+        SchedulesCreateRequest,
+        SchedulesCreateResponse,
+        SchedulesListRequest,
+        SchedulesListResponse,
+        SubstatesGetRequest,
+        SubstatesGetResponse,
+        TemplatesGetAbiRequest,
+        TemplatesGetAbiResponse,
+        TemplatesListVersionsRequest,
+        TemplatesListVersionsResponse,
+        TemplatesMigrateComponentRequest,
+        TemplatesMigrateComponentResponse,
+        TemplatesPublishRequest,
+        TemplatesPublishResponse,
+        TransactionAcceptSponsoredRequest,
+        TransactionAcceptSponsoredResponse,
+        TransactionExportUnsignedRequest,
+        TransactionExportUnsignedResponse,
+        TransactionGetCanonicalBytesRequest,
+        TransactionGetCanonicalBytesResponse,
+        TransactionGetFailuresRequest,
+        TransactionGetFailuresResponse,
         TransactionGetRequest,
         TransactionGetResponse,
         TransactionGetResultRequest,
         TransactionGetResultResponse,
+        TransactionImportSignedRequest,
+        TransactionImportSignedResponse,
+        TransactionRetryRequest,
+        TransactionRetryResponse,
+        TransactionReviewSponsoredRequest,
+        TransactionReviewSponsoredResponse,
+        TransactionSetAnnotationRequest,
+        TransactionSetAnnotationResponse,
         TransactionSubmitDryRunRequest,
         TransactionSubmitDryRunResponse,
         TransactionSubmitRequest,
         TransactionSubmitResponse,
         TransactionWaitResultRequest,
         TransactionWaitResultResponse,
+        TransactionsExportRequest,
+        WalletGcRequest,
+        WalletGcResponse,
+        WalletLockRequest,
+        WalletLockResponse,
+        WalletUnlockRequest,
+        WalletUnlockResponse,
     },
 };
 
@@ -183,12 +254,52 @@ impl From<String> for ComponentAddressOrName {
     }
 }
 
+/// A public key or the name of a contact in the wallet address book that has a public key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PublicKeyOrContact {
+    PublicKey(PublicKey),
+    Contact(String),
+}
+
+impl Display for PublicKeyOrContact {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PublicKey(public_key) => write!(f, "{}", public_key),
+            Self::Contact(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for PublicKeyOrContact {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(public_key) = PublicKey::from_hex(s) {
+            Ok(Self::PublicKey(public_key))
+        } else {
+            Ok(Self::Contact(s.to_string()))
+        }
+    }
+}
+
+impl From<PublicKey> for PublicKeyOrContact {
+    fn from(public_key: PublicKey) -> Self {
+        Self::PublicKey(public_key)
+    }
+}
+
+/// The HTTP header used to send and return the trace ID of a request
+pub const TRACE_ID_HEADER: &str = "x-request-id";
+
 #[derive(Debug, Clone)]
 pub struct WalletDaemonClient {
     client: reqwest::Client,
     endpoint: Url,
     request_id: i64,
     token: Option<String>,
+    trace_id: Option<String>,
+    last_trace_id: Option<String>,
+    response_encoding: RpcEncoding,
 }
 
 impl WalletDaemonClient {
@@ -206,6 +317,9 @@ impl WalletDaemonClient {
             endpoint: endpoint.into_url()?,
             request_id: 0,
             token,
+            trace_id: None,
+            last_trace_id: None,
+            response_encoding: RpcEncoding::Json,
         })
     }
 
@@ -214,10 +328,54 @@ impl WalletDaemonClient {
         self
     }
 
+    /// Sets the trace ID that is sent with subsequent requests. If no trace ID is set, the wallet daemon generates one
+    /// for each request.
+    pub fn set_trace_id(&mut self, trace_id: Option<String>) -> &mut Self {
+        self.trace_id = trace_id;
+        self
+    }
+
+    /// Sets the encoding that is requested for subsequent responses. CBOR responses are smaller and faster to decode
+    /// than JSON, which matters for large responses such as transaction results.
+    pub fn set_response_encoding(&mut self, encoding: RpcEncoding) -> &mut Self {
+        self.response_encoding = encoding;
+        self
+    }
+
+    /// Returns the trace ID that the wallet daemon returned for the last request
+    pub fn last_trace_id(&self) -> Option<&str> {
+        self.last_trace_id.as_deref()
+    }
+
     // pub async fn get_identity(&mut self) -> Result<GetIdentityResponse, WalletDaemonClientError> {
     //     self.send_request("identities.get", json!({})).await
     // }
 
+    pub async fn get_attestation<T: Borrow<AttestationGetRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<AttestationGetResponse, WalletDaemonClientError> {
+        self.send_request("attestation.get", request.borrow()).await
+    }
+
+    pub async fn lock_wallet(&mut self) -> Result<WalletLockResponse, WalletDaemonClientError> {
+        self.send_request("wallet.lock", &WalletLockRequest {}).await
+    }
+
+    pub async fn unlock_wallet<T: Borrow<WalletUnlockRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<WalletUnlockResponse, WalletDaemonClientError> {
+        self.send_request("wallet.unlock", request.borrow()).await
+    }
+
+    pub async fn gc_wallet<T: Borrow<WalletGcRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<WalletGcResponse, WalletDaemonClientError> {
+        self.send_request("wallet.gc", request.borrow()).await
+    }
+
     pub async fn create_key(&mut self, branch: KeyBranch) -> Result<KeysCreateResponse, WalletDaemonClientError> {
         self.send_request("keys.create", &KeysCreateRequest {
             branch,
@@ -247,6 +405,26 @@ impl WalletDaemonClient {
         self.send_request("keys.list", &KeysListRequest { branch }).await
     }
 
+    pub async fn get_seed_words(
+        &mut self,
+        passphrase: Option<String>,
+    ) -> Result<KeysGetSeedWordsResponse, WalletDaemonClientError> {
+        self.send_request("keys.get_seed_words", &KeysGetSeedWordsRequest { passphrase })
+            .await
+    }
+
+    pub async fn verify_seed_words(
+        &mut self,
+        seed_words: Vec<String>,
+        passphrase: Option<String>,
+    ) -> Result<KeysVerifySeedWordsResponse, WalletDaemonClientError> {
+        self.send_request("keys.verify_seed_words", &KeysVerifySeedWordsRequest {
+            seed_words,
+            passphrase,
+        })
+        .await
+    }
+
     pub async fn get_transaction<T: Borrow<TransactionGetRequest>>(
         &mut self,
         request: T,
@@ -282,6 +460,131 @@ impl WalletDaemonClient {
         self.send_request("transactions.submit_dry_run", request.borrow()).await
     }
 
+    pub async fn get_transaction_failures<T: Borrow<TransactionGetFailuresRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionGetFailuresResponse, WalletDaemonClientError> {
+        self.send_request("transactions.get_failures", request.borrow()).await
+    }
+
+    pub async fn retry_transaction<T: Borrow<TransactionRetryRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionRetryResponse, WalletDaemonClientError> {
+        self.send_request("transactions.retry", request.borrow()).await
+    }
+
+    pub async fn set_transaction_annotation<T: Borrow<TransactionSetAnnotationRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionSetAnnotationResponse, WalletDaemonClientError> {
+        self.send_request("transactions.set_annotation", request.borrow()).await
+    }
+
+    pub async fn review_sponsored_transaction<T: Borrow<TransactionReviewSponsoredRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionReviewSponsoredResponse, WalletDaemonClientError> {
+        self.send_request("transactions.review_sponsored", request.borrow()).await
+    }
+
+    pub async fn accept_sponsored_transaction<T: Borrow<TransactionAcceptSponsoredRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionAcceptSponsoredResponse, WalletDaemonClientError> {
+        self.send_request("transactions.accept_sponsored", request.borrow()).await
+    }
+
+    /// Exports the transaction, with its inputs resolved, for signing on an offline machine
+    pub async fn export_unsigned_transaction<T: Borrow<TransactionExportUnsignedRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionExportUnsignedResponse, WalletDaemonClientError> {
+        self.send_request("transactions.export_unsigned", request.borrow()).await
+    }
+
+    /// Verifies and submits a transaction that was signed on an offline machine
+    pub async fn import_signed_transaction<T: Borrow<TransactionImportSignedRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionImportSignedResponse, WalletDaemonClientError> {
+        self.send_request("transactions.import_signed", request.borrow()).await
+    }
+
+    pub async fn get_transaction_canonical_bytes<T: Borrow<TransactionGetCanonicalBytesRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TransactionGetCanonicalBytesResponse, WalletDaemonClientError> {
+        self.send_request("transactions.get_canonical_bytes", request.borrow()).await
+    }
+
+    /// Exports all transactions in the requested format. The export is not a JSON-RPC response, the caller should
+    /// read the (chunked) response body as it is streamed e.g. using `Response::chunk`.
+    pub async fn export_transactions<T: Borrow<TransactionsExportRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<reqwest::Response, WalletDaemonClientError> {
+        let resp = self
+            .jrpc_request_builder("transactions.export", request.borrow())
+            .send()
+            .await?;
+        // Any error that occurs before the export starts is returned as a JSON-RPC error response
+        let is_jrpc_response = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .map_or(false, |v| v.as_bytes().starts_with(b"application/json"));
+        if is_jrpc_response {
+            let val = resp.json().await?;
+            jsonrpc_result(val)?;
+            return Err(WalletDaemonClientError::InvalidResponse {
+                message: "Expected a transaction export but got a JSON-RPC result".to_string(),
+            });
+        }
+        Ok(resp)
+    }
+
+    pub async fn substates_get<T: Borrow<SubstatesGetRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<SubstatesGetResponse, WalletDaemonClientError> {
+        self.send_request("substates.get", request.borrow()).await
+    }
+
+    pub async fn list_events<T: Borrow<EventsListRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<EventsListResponse, WalletDaemonClientError> {
+        self.send_request("events.list", request.borrow()).await
+    }
+
+    pub async fn publish_template<T: Borrow<TemplatesPublishRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TemplatesPublishResponse, WalletDaemonClientError> {
+        self.send_request("templates.publish", request.borrow()).await
+    }
+
+    pub async fn get_template_abi<T: Borrow<TemplatesGetAbiRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TemplatesGetAbiResponse, WalletDaemonClientError> {
+        self.send_request("templates.get_abi", request.borrow()).await
+    }
+
+    pub async fn list_template_versions<T: Borrow<TemplatesListVersionsRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TemplatesListVersionsResponse, WalletDaemonClientError> {
+        self.send_request("templates.list_versions", request.borrow()).await
+    }
+
+    pub async fn migrate_component<T: Borrow<TemplatesMigrateComponentRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<TemplatesMigrateComponentResponse, WalletDaemonClientError> {
+        self.send_request("templates.migrate_component", request.borrow()).await
+    }
+
     pub async fn create_account<T: Borrow<AccountsCreateRequest>>(
         &mut self,
         request: T,
@@ -303,6 +606,20 @@ impl WalletDaemonClient {
         self.send_request("accounts.get_balances", request.borrow()).await
     }
 
+    pub async fn get_account_balance_at_epoch<T: Borrow<AccountsGetBalanceAtEpochRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<AccountsGetBalanceAtEpochResponse, WalletDaemonClientError> {
+        self.send_request("accounts.get_balance_at_epoch", request.borrow()).await
+    }
+
+    pub async fn create_balance_proof<T: Borrow<AccountsCreateBalanceProofRequest>>(
+        &mut self,
+        request: T,
+    ) -> Result<AccountsCreateBalanceProofResponse, WalletDaemonClientError> {
+        self.send_request("accounts.create_balance_proof", request.borrow()).await
+    }
+
     pub async fn get_validator_fee_summary<T: Borrow<GetValidatorFeesRequest>>(
         &mut self,
         request: T,
@@ -347,6 +664,28 @@ impl WalletDaemonClient {
             .await
     }
 
+    pub async fn accounts_recover(
+        &mut self,
+        gap_limit: Option<u64>,
+    ) -> Result<AccountsRecoverResponse, WalletDaemonClientError> {
+        self.send_request("accounts.recover", &AccountsRecoverRequest { gap_limit })
+            .await
+    }
+
+    pub async fn accounts_import<T: Borrow<AccountsImportRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<AccountsImportResponse, WalletDaemonClientError> {
+        self.send_request("accounts.import", req.borrow()).await
+    }
+
+    pub async fn accounts_import_watch_only<T: Borrow<AccountsImportWatchOnlyRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<AccountsImportWatchOnlyResponse, WalletDaemonClientError> {
+        self.send_request("accounts.import_watch_only", req.borrow()).await
+    }
+
     pub async fn accounts_transfer<T: Borrow<AccountsTransferRequest>>(
         &mut self,
         req: T,
@@ -440,6 +779,13 @@ impl WalletDaemonClient {
         self.send_request("confidential.view_vault_balance", req.borrow()).await
     }
 
+    pub async fn audit_balances<T: Borrow<ConfidentialAuditBalancesRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<ConfidentialAuditBalancesResponse, WalletDaemonClientError> {
+        self.send_request("confidential.audit_balances", req.borrow()).await
+    }
+
     pub async fn auth_request<T: Borrow<AuthLoginRequest>>(
         &mut self,
         req: T,
@@ -475,6 +821,49 @@ impl WalletDaemonClient {
         self.send_request("auth.get_all_jwt", req.borrow()).await
     }
 
+    pub async fn contacts_add<T: Borrow<ContactsAddRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<ContactsAddResponse, WalletDaemonClientError> {
+        self.send_request("contacts.add", req.borrow()).await
+    }
+
+    pub async fn contacts_list(&mut self) -> Result<ContactsListResponse, WalletDaemonClientError> {
+        self.send_request("contacts.list", &ContactsListRequest {}).await
+    }
+
+    pub async fn contacts_remove<T: Borrow<ContactsRemoveRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<ContactsRemoveResponse, WalletDaemonClientError> {
+        self.send_request("contacts.remove", req.borrow()).await
+    }
+
+    pub async fn schedules_create<T: Borrow<SchedulesCreateRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<SchedulesCreateResponse, WalletDaemonClientError> {
+        self.send_request("schedules.create", req.borrow()).await
+    }
+
+    pub async fn schedules_list<T: Borrow<SchedulesListRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<SchedulesListResponse, WalletDaemonClientError> {
+        self.send_request("schedules.list", req.borrow()).await
+    }
+
+    pub async fn schedules_cancel<T: Borrow<SchedulesCancelRequest>>(
+        &mut self,
+        req: T,
+    ) -> Result<SchedulesCancelResponse, WalletDaemonClientError> {
+        self.send_request("schedules.cancel", req.borrow()).await
+    }
+
+    pub async fn network_status(&mut self) -> Result<NetworkStatusResponse, WalletDaemonClientError> {
+        self.send_request("network.status", &NetworkStatusRequest {}).await
+    }
+
     pub async fn webrtc_start<T: Borrow<WebRtcStartRequest>>(
         &mut self,
         req: T,
@@ -487,7 +876,7 @@ impl WalletDaemonClient {
         self.request_id
     }
 
-    async fn jrpc_call<T: Serialize>(&mut self, method: &str, params: &T) -> Result<Value, WalletDaemonClientError> {
+    fn jrpc_request_builder<T: Serialize>(&mut self, method: &str, params: &T) -> RequestBuilder {
         let request_json = json!(
             {
                 "jsonrpc": "2.0",
@@ -501,8 +890,30 @@ impl WalletDaemonClient {
             // If we don't have the token and the method is anything else than "auth.login" it will fail.
             builder = builder.header(AUTHORIZATION, format!("Bearer {}", token));
         }
-        let resp = builder.body(request_json.to_string()).send().await?;
-        let val = resp.json().await?;
+        if let Some(trace_id) = &self.trace_id {
+            builder = builder.header(TRACE_ID_HEADER, trace_id);
+        }
+        builder.body(request_json.to_string())
+    }
+
+    async fn jrpc_call<T: Serialize>(&mut self, method: &str, params: &T) -> Result<Value, WalletDaemonClientError> {
+        let resp = self
+            .jrpc_request_builder(method, params)
+            .header(header::ACCEPT, self.response_encoding.content_type())
+            .send()
+            .await?;
+        self.last_trace_id = resp
+            .headers()
+            .get(TRACE_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        // A server that does not support the requested encoding responds with JSON
+        let encoding =
+            RpcEncoding::from_content_type(resp.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()));
+        let body = resp.bytes().await?;
+        let val = encoding
+            .decode(&body)
+            .map_err(|e| WalletDaemonClientError::InvalidResponse { message: e.to_string() })?;
         jsonrpc_result(val)
     }
 
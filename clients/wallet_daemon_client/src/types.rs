@@ -24,22 +24,36 @@
 // inside the code generated by serde macros so we allow it for the whole module.
 #![allow(clippy::mutable_key_type)]
 
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
 
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use tari_common_types::types::PublicKey;
+use tari_common_types::types::{PrivateKey, PublicKey, Signature};
 use tari_dan_common_types::{substate_type::SubstateType, Epoch, SubstateAddress, SubstateRequirement};
 use tari_dan_wallet_sdk::{
     apis::{confidential_transfer::ConfidentialTransferInputSelection, jwt::Claims, key_manager},
-    models::{Account, ConfidentialProofId, NonFungibleToken, TransactionStatus},
+    models::{
+        Account,
+        AuditedVaultBalance,
+        ConfidentialProofId,
+        Contact,
+        HistoricalVaultBalance,
+        NonFungibleToken,
+        ScheduledTransaction,
+        TransactionFailure,
+        TransactionSchedule,
+        TransactionStatus,
+    },
 };
 use tari_engine_types::{
     commit_result::{ExecuteResult, FinalizeResult},
+    events::Event,
     instruction::Instruction,
     instruction_result::InstructionResult,
+    published_template::TemplateType,
+    resource_amount::{ResourceAmount, ResourceAmountError},
     serde_with,
-    substate::{Substate, SubstateId},
+    substate::{Substate, SubstateId, SubstateValue},
     TemplateAddress,
 };
 use tari_template_abi::TemplateDef;
@@ -49,13 +63,14 @@ use tari_template_lib::{
     models::{Amount, ConfidentialOutputStatement, NonFungibleId, ResourceAddress, VaultId},
     prelude::{ComponentAddress, ConfidentialWithdrawProof, ResourceType},
 };
-use tari_transaction::{Transaction, TransactionId, UnsignedTransaction};
+use tari_transaction::{Transaction, TransactionId, TransactionSignature, UnsignedTransaction};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
 use crate::{
     serialize::{opt_string_or_struct, string_or_struct},
     ComponentAddressOrName,
+    PublicKeyOrContact,
 };
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -66,8 +81,9 @@ use crate::{
 )]
 pub struct CallInstructionRequest {
     pub instructions: Vec<Instruction>,
-    #[serde(deserialize_with = "string_or_struct")]
-    pub fee_account: ComponentAddressOrName,
+    /// The account that pays the fees. If not provided, the wallet's fee payer strategy selects the account.
+    #[serde(default, deserialize_with = "opt_string_or_struct")]
+    pub fee_account: Option<ComponentAddressOrName>,
     #[serde(default, deserialize_with = "opt_string_or_struct")]
     pub dump_outputs_into: Option<ComponentAddressOrName>,
     #[cfg_attr(feature = "ts", ts(type = "number"))]
@@ -141,6 +157,12 @@ pub struct TransactionSubmitDryRunRequest {
     pub detect_inputs: bool,
     #[cfg_attr(feature = "ts", ts(type = "Array<number>"))]
     pub proof_ids: Vec<ConfidentialProofId>,
+    /// Substate values that replace (or are added to) the substates used to execute the dry run, e.g. to simulate a
+    /// vault balance or depend on the outputs of a pending transaction. Overridden substates are not fetched from the
+    /// network.
+    #[serde(default, with = "serde_with::vec")]
+    #[cfg_attr(feature = "ts", ts(type = "Array<[any, any]>"))]
+    pub substate_overrides: HashMap<SubstateId, SubstateValue>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -179,6 +201,69 @@ pub struct TransactionGetResponse {
     pub result: Option<FinalizeResult>,
     pub status: TransactionStatus,
     pub last_update_time: NaiveDateTime,
+    /// The user's note for the transaction, if any
+    #[serde(default)]
+    pub annotation: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub enum TransactionExportFormat {
+    #[default]
+    Csv,
+    JsonLines,
+}
+
+impl TransactionExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TransactionExportFormat::Csv => "text/csv",
+            TransactionExportFormat::JsonLines => "application/jsonl",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            TransactionExportFormat::Csv => "csv",
+            TransactionExportFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionsExportRequest {
+    #[serde(default)]
+    pub format: TransactionExportFormat,
+}
+
+/// A single exported transaction. In JSON Lines exports each line is one record.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionExportRecord {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    pub final_fee: Option<Amount>,
+    pub finalize: Option<FinalizeResult>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub execution_time_ms: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub finalized_time_ms: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub last_update_time: NaiveDateTime,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -202,6 +287,220 @@ pub struct TransactionGetAllResponse {
     pub transactions: Vec<(Transaction, Option<FinalizeResult>, TransactionStatus, NaiveDateTime)>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetFailuresRequest {
+    /// If true, failures that have already been retried are included
+    #[serde(default)]
+    pub include_retried: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetFailuresResponse {
+    pub failures: Vec<TransactionFailure>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionRetryRequest {
+    /// The id of the failed transaction to retry
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionRetryResponse {
+    /// The id of the new transaction
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionSetAnnotationRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    /// The note to attach to the transaction. If not set, the existing note is removed.
+    pub annotation: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionSetAnnotationResponse {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionReviewSponsoredRequest {
+    /// A transaction built by a sponsor (e.g. a dapp) with fee instructions that pay from the sponsor component
+    pub transaction: Transaction,
+    /// The component that is expected to pay the fees
+    pub sponsor: ComponentAddress,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionReviewSponsoredResponse {
+    pub sponsor: ComponentAddress,
+    /// The maximum fee that the sponsor pays, if the fee instructions declare one
+    pub max_fee: Option<Amount>,
+    /// The public keys that have already signed the transaction
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub existing_signers: Vec<PublicKey>,
+    /// The wallet accounts used by the instructions. Accepting the transaction signs it with the owner key of each of
+    /// these accounts.
+    pub involved_accounts: Vec<ComponentAddress>,
+    pub fee_instructions: Vec<Instruction>,
+    pub instructions: Vec<Instruction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionAcceptSponsoredRequest {
+    pub transaction: Transaction,
+    /// The component that is expected to pay the fees
+    pub sponsor: ComponentAddress,
+    /// Sign with this transaction key instead of the owner keys of the involved accounts
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub signing_key_index: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionAcceptSponsoredResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionExportUnsignedRequest {
+    pub transaction: UnsignedTransaction,
+    /// The transaction key that is expected to sign the transaction. If not set, the active transaction key is used.
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub signing_key_index: Option<u64>,
+    /// Resolve the inputs, and their dependencies, that are referenced by the instructions to their current versions.
+    /// If false, the provided transaction must contain versioned inputs.
+    pub detect_inputs: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionExportUnsignedResponse {
+    /// The encoded transaction to sign on an offline machine
+    pub blob: String,
+    /// The transaction with resolved inputs that is contained in the blob
+    pub transaction: UnsignedTransaction,
+    #[cfg_attr(feature = "ts", ts(type = "Array<string>"))]
+    pub required_signers: Vec<PublicKey>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionImportSignedRequest {
+    /// The encoded transaction produced by signing an exported transaction on an offline machine
+    pub blob: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionImportSignedResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetCanonicalBytesRequest {
+    pub transaction: UnsignedTransaction,
+    /// The signatures that are included in the transaction id. May be empty for a transaction that is not yet signed.
+    #[serde(default)]
+    pub signatures: Vec<TransactionSignature>,
+}
+
+/// The canonical bytes of a transaction, hex encoded. Each hash is a domain separated Blake2b hash of the length of
+/// the domain separation tag as a little-endian u64, the tag and the canonical bytes.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TransactionGetCanonicalBytesResponse {
+    /// The CBOR encoding of the transaction fields that each signer signs
+    pub signature_fields: String,
+    pub signature_domain_separation_tag: String,
+    /// The Blake2b-512 hash of the signature fields that each signer signs with a Schnorr signature
+    pub signature_message: String,
+    /// The CBOR encoding of the signatures followed by the CBOR encoding of the unsigned transaction
+    pub transaction_id_preimage: String,
+    pub transaction_id_domain_separation_tag: String,
+    /// The Blake2b-256 hash of the transaction id preimage
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -315,6 +614,51 @@ pub struct KeysSetActiveResponse {
     pub public_key: PublicKey,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct KeysGetSeedWordsRequest {
+    /// If provided, the passphrase is required (in addition to the seed words) to restore the wallet
+    pub passphrase: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct KeysGetSeedWordsResponse {
+    pub seed_words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct KeysVerifySeedWordsRequest {
+    pub seed_words: Vec<String>,
+    pub passphrase: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct KeysVerifySeedWordsResponse {
+    /// True if the seed words and passphrase restore the wallet root key
+    pub is_valid: bool,
+    /// The reason that the seed words are not valid
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -474,6 +818,30 @@ pub struct AccountsGetBalancesResponse {
     pub balances: Vec<BalanceEntry>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetBalanceAtEpochRequest {
+    #[serde(deserialize_with = "opt_string_or_struct")]
+    pub account: Option<ComponentAddressOrName>,
+    pub epoch: Epoch,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsGetBalanceAtEpochResponse {
+    pub address: SubstateId,
+    pub epoch: Epoch,
+    pub balances: Vec<HistoricalVaultBalance>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -488,14 +856,31 @@ pub struct BalanceEntry {
     pub resource_type: ResourceType,
     pub confidential_balance: Amount,
     pub token_symbol: Option<String>,
+    /// The number of decimal places used to display amounts of this resource. Balances are always given in the
+    /// smallest unit.
+    pub decimals: Option<u8>,
+    /// True if the resource auditor has frozen the vault
+    #[serde(default)]
+    pub is_frozen: bool,
 }
 
 impl BalanceEntry {
+    /// Returns the revealed balance together with the resource decimals
+    pub fn revealed_amount(&self) -> Result<ResourceAmount, ResourceAmountError> {
+        ResourceAmount::new(self.balance, self.decimals.unwrap_or(0))
+    }
+
+    /// Converts a display string (e.g. "1.5") into an amount in the smallest unit of this resource
+    pub fn parse_display_amount(&self, s: &str) -> Result<Amount, ResourceAmountError> {
+        ResourceAmount::from_display_str(s, self.decimals.unwrap_or(0)).map(|a| a.amount())
+    }
+
     pub fn to_balance_string(&self) -> String {
         let symbol = self.token_symbol.as_deref().unwrap_or_default();
         match self.resource_type {
-            ResourceType::Fungible => {
-                format!("{} {}", self.balance, symbol)
+            ResourceType::Fungible => match self.revealed_amount() {
+                Ok(amount) => format!("{} {}", amount, symbol),
+                Err(_) => format!("{} {}", self.balance, symbol),
             },
             ResourceType::NonFungible => {
                 format!("{} {} tokens", self.balance, symbol)
@@ -513,13 +898,164 @@ impl BalanceEntry {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// An amount given either in the smallest unit of the resource, as a [ResourceAmount] or as a decimal display string
+/// (e.g. "1.5"). Display amounts are converted using the `DECIMALS` metadata of the resource.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct AccountGetRequest {
+pub enum AmountInput {
+    Amount(Amount),
+    ResourceAmount(ResourceAmount),
+    Display(String),
+}
+
+impl AmountInput {
+    /// Returns the amount if it is already given in the smallest unit of the resource and no decimals are required to
+    /// convert it.
+    pub fn as_raw_amount(&self) -> Option<Amount> {
+        match self {
+            AmountInput::Amount(amount) => Some(*amount),
+            _ => None,
+        }
+    }
+
+    /// Converts this input into an amount in the smallest unit of a resource that uses `decimals` decimal places.
+    pub fn to_amount(&self, decimals: u8) -> Result<Amount, ResourceAmountError> {
+        match self {
+            AmountInput::Amount(amount) => Ok(*amount),
+            AmountInput::ResourceAmount(amount) => {
+                if amount.decimals() != decimals {
+                    return Err(ResourceAmountError::DecimalsMismatch {
+                        left: amount.decimals(),
+                        right: decimals,
+                    });
+                }
+                ResourceAmount::new(amount.amount(), decimals).map(|a| a.amount())
+            },
+            AmountInput::Display(s) => ResourceAmount::from_display_str(s, decimals).map(|a| a.amount()),
+        }
+    }
+}
+
+impl From<Amount> for AmountInput {
+    fn from(amount: Amount) -> Self {
+        AmountInput::Amount(amount)
+    }
+}
+
+impl From<ResourceAmount> for AmountInput {
+    fn from(amount: ResourceAmount) -> Self {
+        AmountInput::ResourceAmount(amount)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsCreateBalanceProofRequest {
+    #[serde(default, deserialize_with = "opt_string_or_struct")]
+    pub account: Option<ComponentAddressOrName>,
+    /// If set, confidential balances are additionally opened with this view key using the balances recorded by the
+    /// last confidential audit
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub view_key_id: Option<u64>,
+    /// If true, the account balances (and audited balances, if a view key is given) are refreshed from the network
+    /// before the statement is signed
+    #[serde(default)]
+    pub refresh: bool,
+    /// An arbitrary value that is included in the signed statement. An auditor should provide a fresh nonce so that a
+    /// previously issued statement cannot be replayed.
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
+
+/// A statement of the balances of an account at a point in time, signed by the account owner key
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountBalanceStatement {
+    pub account: SubstateId,
+    pub balances: Vec<BalanceStatementEntry>,
+    /// The account owner public key that signed this statement
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub public_key: PublicKey,
+    /// The unix timestamp in seconds at which the statement was signed
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub timestamp: u64,
+    pub nonce: Option<String>,
+}
+
+impl AccountBalanceStatement {
+    /// Returns the message that is signed by the account owner key. This is the JSON encoding of the statement.
+    pub fn to_signing_message(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("AccountBalanceStatement serialization is infallible")
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct BalanceStatementEntry {
+    pub vault_address: SubstateId,
+    #[serde(with = "serde_with::string")]
+    pub resource_address: ResourceAddress,
+    pub resource_type: ResourceType,
+    pub revealed_balance: Amount,
+    /// The confidential balance known to the wallet
+    pub confidential_balance: Amount,
+    /// The confidential balance opened with the requested view key. This is None if no view key was requested or the
+    /// vault has not been audited.
+    pub view_key_balance: Option<Amount>,
+    pub token_symbol: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsCreateBalanceProofResponse {
+    pub statement: AccountBalanceStatement,
+    #[cfg_attr(feature = "ts", ts(type = "{public_nonce : string, signature: string}"))]
+    pub signature: Signature,
+}
+
+impl AccountsCreateBalanceProofResponse {
+    /// Returns true if the signature is valid for the statement. See [verify_balance_statement].
+    pub fn verify(&self) -> bool {
+        verify_balance_statement(&self.statement, &self.signature)
+    }
+}
+
+/// Returns true if the signature is valid for the balance statement and the public key it contains. This does not
+/// require a connection to the wallet daemon. Auditors should additionally check that the public key is the owner key
+/// of the account, that the timestamp is recent and that the nonce is the one they provided.
+pub fn verify_balance_statement(statement: &AccountBalanceStatement, signature: &Signature) -> bool {
+    signature.verify(&statement.public_key, statement.to_signing_message())
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountGetRequest {
     #[serde(deserialize_with = "string_or_struct")]
     pub name_or_address: ComponentAddressOrName,
 }
@@ -565,6 +1101,84 @@ pub struct AccountSetDefaultRequest {
 )]
 pub struct AccountSetDefaultResponse {}
 
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsRecoverRequest {
+    /// The number of consecutive unused keys after which to stop scanning. Defaults to 20.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub gap_limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsRecoverResponse {
+    /// The accounts that were found on the network and added to the wallet
+    pub accounts: Vec<AccountInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsImportRequest {
+    pub account_name: Option<String>,
+    /// The externally generated secret key of the account owner. The account component must already exist on the
+    /// network.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub secret_key: PrivateKey,
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsImportResponse {
+    pub account: AccountInfo,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsImportWatchOnlyRequest {
+    pub account_name: Option<String>,
+    pub address: ComponentAddress,
+    /// The public key of the account owner
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub owner_public_key: PublicKey,
+    /// The secret view key used to decrypt the confidential balances of the account. If not provided, confidential
+    /// balances are not shown.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub view_key: Option<PrivateKey>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AccountsImportWatchOnlyResponse {
+    pub account: Account,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -574,14 +1188,19 @@ pub struct AccountSetDefaultResponse {}
 pub struct AccountsTransferRequest {
     #[serde(deserialize_with = "opt_string_or_struct")]
     pub account: Option<ComponentAddressOrName>,
-    pub amount: Amount,
+    pub amount: AmountInput,
     pub resource_address: ResourceAddress,
+    /// The public key of the recipient or the name of a contact in the address book
+    #[serde(deserialize_with = "string_or_struct")]
     #[cfg_attr(feature = "ts", ts(type = "string"))]
-    pub destination_public_key: PublicKey,
-    pub max_fee: Option<Amount>,
+    pub destination_public_key: PublicKeyOrContact,
+    pub max_fee: Option<AmountInput>,
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub proof_from_badge_resource: Option<ResourceAddress>,
     pub dry_run: bool,
+    /// The account that pays the fees. If not provided, the wallet's fee payer strategy selects the account.
+    #[serde(default, deserialize_with = "opt_string_or_struct")]
+    pub fee_account: Option<ComponentAddressOrName>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -687,12 +1306,14 @@ pub struct ConfidentialCreateOutputProofResponse {
 pub struct ConfidentialTransferRequest {
     #[serde(deserialize_with = "opt_string_or_struct")]
     pub account: Option<ComponentAddressOrName>,
-    pub amount: Amount,
+    pub amount: AmountInput,
     pub input_selection: ConfidentialTransferInputSelection,
     pub resource_address: ResourceAddress,
+    /// The public key of the recipient or the name of a contact in the address book
+    #[serde(deserialize_with = "string_or_struct")]
     #[cfg_attr(feature = "ts", ts(type = "string"))]
-    pub destination_public_key: PublicKey,
-    pub max_fee: Option<Amount>,
+    pub destination_public_key: PublicKeyOrContact,
+    pub max_fee: Option<AmountInput>,
     pub output_to_revealed: bool,
     #[cfg_attr(feature = "ts", ts(type = "string | null"))]
     pub proof_from_badge_resource: Option<ResourceAddress>,
@@ -739,6 +1360,38 @@ pub struct ConfidentialViewVaultBalanceResponse {
     pub balances: HashMap<PublicKey, Option<u64>>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ConfidentialAuditBalancesRequest {
+    pub resource_address: ResourceAddress,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub view_key_id: u64,
+    /// If true, all account vaults of the resource are scanned before returning. Otherwise, the balances recorded by
+    /// the last scan are returned.
+    #[serde(default)]
+    pub refresh: bool,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub minimum_expected_value: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub maximum_expected_value: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ConfidentialAuditBalancesResponse {
+    pub balances: Vec<AuditedVaultBalance>,
+    /// The sum of all audited vault balances
+    pub total_balance: Amount,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
@@ -751,7 +1404,7 @@ pub struct ClaimBurnRequest {
     // TODO: make this a type
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub claim_proof: serde_json::Value,
-    pub max_fee: Option<Amount>,
+    pub max_fee: Option<AmountInput>,
     #[cfg_attr(feature = "ts", ts(type = "number | null"))]
     pub key_id: Option<u64>,
 }
@@ -1065,7 +1718,7 @@ pub struct GetValidatorFeesResponse {
 pub struct ClaimValidatorFeesRequest {
     #[serde(default, deserialize_with = "opt_string_or_struct")]
     pub account: Option<ComponentAddressOrName>,
-    pub max_fee: Option<Amount>,
+    pub max_fee: Option<AmountInput>,
     #[cfg_attr(feature = "ts", ts(type = "string"))]
     pub validator_public_key: PublicKey,
     pub epoch: Epoch,
@@ -1119,13 +1772,18 @@ pub struct SettingsGetResponse {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct SubstatesListRequest {
-    #[serde(default, deserialize_with = "serde_with::string::option::deserialize")]
-    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
-    pub filter_by_template: Option<TemplateAddress>,
-    pub filter_by_type: Option<SubstateType>,
-    pub limit: Option<u64>,
-    pub offset: Option<u64>,
+pub struct NetworkStatusRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct NetworkStatusResponse {
+    /// The indexer endpoint that requests are currently sent to
+    pub active_endpoint: String,
+    pub endpoints: Vec<IndexerEndpointStatus>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1134,8 +1792,19 @@ pub struct SubstatesListRequest {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct SubstatesListResponse {
-    pub substates: Vec<WalletSubstateRecord>,
+pub struct IndexerEndpointStatus {
+    pub url: String,
+    pub is_active: bool,
+    /// None if no request has been made to the endpoint yet
+    pub is_healthy: Option<bool>,
+    /// The round trip time of the last health check in milliseconds
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub latency_ms: Option<u64>,
+    /// Unix timestamp in seconds of the last health check or request
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub last_checked_at: Option<u64>,
+    /// The error from the last failed health check or request, if the endpoint is unhealthy
+    pub last_error: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1144,8 +1813,25 @@ pub struct SubstatesListResponse {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct SubstatesGetRequest {
-    pub substate_id: SubstateId,
+pub struct WalletLockRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct WalletLockResponse {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct WalletUnlockRequest {
+    /// The wallet database password. If the database is not yet encrypted, it is encrypted with this password.
+    pub password: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1154,9 +1840,18 @@ pub struct SubstatesGetRequest {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct SubstatesGetResponse {
-    pub record: WalletSubstateRecord,
-    pub value: Substate,
+pub struct WalletUnlockResponse {}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct WalletGcRequest {
+    /// If true, the records that would be removed are reported but nothing is removed
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1165,26 +1860,56 @@ pub struct SubstatesGetResponse {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct WalletSubstateRecord {
-    pub substate_id: SubstateId,
-    pub parent_id: Option<SubstateId>,
-    pub module_name: Option<String>,
-    pub version: u32,
-    #[serde(default, with = "serde_with::string::option")]
-    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
-    pub template_address: Option<TemplateAddress>,
+pub struct WalletGcResponse {
+    pub is_dry_run: bool,
+    /// Substate records that are not reachable from any account in the wallet
+    pub removed_substates: Vec<SubstateId>,
+    /// Confidential proofs that were never bound to a finalized transaction
+    #[cfg_attr(feature = "ts", ts(type = "Array<number>"))]
+    pub removed_proofs: Vec<ConfidentialProofId>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct AttestationGetRequest {
+    /// An arbitrary value that is included in the signed statement. Callers should provide a fresh nonce so that a
+    /// previously issued attestation cannot be replayed.
+    #[serde(default)]
+    pub nonce: Option<String>,
 }
 
+/// A statement describing the running wallet daemon build and configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[cfg_attr(
     feature = "ts",
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct TemplatesGetRequest {
+pub struct DaemonAttestation {
+    pub version: String,
+    /// The git commit hash that the daemon was built from, or "unknown" if it was not built from a git checkout
+    pub build_hash: String,
+    pub enabled_features: Vec<String>,
+    /// Hex-encoded hash of the daemon configuration. Secrets (e.g. the JWT secret key) are excluded.
+    pub config_fingerprint: String,
+    /// The wallet identity public key that signed this statement
     #[cfg_attr(feature = "ts", ts(type = "string"))]
-    #[serde(with = "serde_with::string")]
-    pub template_address: TemplateAddress,
+    pub public_key: PublicKey,
+    /// The unix timestamp in seconds at which the statement was signed
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub timestamp: u64,
+    pub nonce: Option<String>,
+}
+
+impl DaemonAttestation {
+    /// Returns the message that is signed by the wallet identity key. This is the JSON encoding of the statement.
+    pub fn to_signing_message(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("DaemonAttestation serialization is infallible")
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -1193,6 +1918,426 @@ pub struct TemplatesGetRequest {
     derive(TS),
     ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
 )]
-pub struct TemplatesGetResponse {
-    pub template_definition: TemplateDef,
+pub struct AttestationGetResponse {
+    pub attestation: DaemonAttestation,
+    #[cfg_attr(feature = "ts", ts(type = "{public_nonce : string, signature: string}"))]
+    pub signature: Signature,
+}
+
+impl AttestationGetResponse {
+    /// Returns true if the signature is valid for the attestation and the public key it contains. Callers should
+    /// additionally check that the public key is the expected identity key for the wallet.
+    pub fn verify(&self) -> bool {
+        self.signature
+            .verify(&self.attestation.public_key, self.attestation.to_signing_message())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SubstatesListRequest {
+    #[serde(default, deserialize_with = "serde_with::string::option::deserialize")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub filter_by_template: Option<TemplateAddress>,
+    pub filter_by_type: Option<SubstateType>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SubstatesListResponse {
+    pub substates: Vec<WalletSubstateRecord>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SubstatesGetRequest {
+    pub substate_id: SubstateId,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SubstatesGetResponse {
+    pub record: WalletSubstateRecord,
+    pub value: Substate,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct WalletSubstateRecord {
+    pub substate_id: SubstateId,
+    pub parent_id: Option<SubstateId>,
+    pub module_name: Option<String>,
+    pub version: u32,
+    #[serde(default, with = "serde_with::string::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub template_address: Option<TemplateAddress>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct EventsListRequest {
+    /// Only return events with this topic
+    #[serde(default)]
+    pub topic: Option<String>,
+    /// Only return events emitted by this substate
+    #[serde(default)]
+    pub substate_id: Option<SubstateId>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct EventsListResponse {
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesGetRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesGetResponse {
+    pub template_definition: TemplateDef,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesGetAbiRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesGetAbiResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+    /// The ABI extracted from the published WASM binary or flow definition
+    pub abi: TemplateDef,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub binary_size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesListVersionsRequest {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesListVersionsResponse {
+    /// The versions of the template from the first version up to and including the requested template
+    pub versions: Vec<TemplateVersionInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplateVersionInfo {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+    pub template_version: u32,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    #[serde(with = "serde_with::string::option")]
+    pub previous_version: Option<TemplateAddress>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub author_public_key: Option<PublicKey>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesMigrateComponentRequest {
+    #[serde(with = "serde_with::string")]
+    pub component_address: ComponentAddress,
+    /// The newer version of the component template to migrate to
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+    /// The account that pays the fees. If not provided, the wallet's fee payer strategy selects the account.
+    #[serde(default, deserialize_with = "opt_string_or_struct")]
+    pub fee_account: Option<ComponentAddressOrName>,
+    pub max_fee: Option<Amount>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesMigrateComponentResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub fee: Amount,
+    pub result: FinalizeResult,
+}
+
+/// The source of a compiled WASM template binary or JSON flow definition
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub enum TemplateBinarySource {
+    /// The base64 encoded WASM binary or flow JSON
+    Binary(
+        #[serde(with = "serde_with::base64")]
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        Vec<u8>,
+    ),
+    /// A path to the WASM or flow JSON file on the wallet daemon host. Relative paths are resolved against the
+    /// configured template binary directory and the file must be inside of that directory.
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    Path(PathBuf),
+    /// A URL from which the wallet daemon downloads the WASM binary or flow JSON
+    Url(String),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesPublishRequest {
+    pub binary: TemplateBinarySource,
+    /// The type of template in `binary`. Defaults to WASM.
+    #[serde(default)]
+    pub template_type: TemplateType,
+    /// If provided, the template is published as the next version of this template. The previous version must have
+    /// been published by the fee account.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    #[serde(default, with = "serde_with::string::option")]
+    pub previous_version: Option<TemplateAddress>,
+    /// The account that signs the transaction and pays the fees. If not provided, the wallet's fee payer strategy
+    /// selects the account.
+    #[serde(default, deserialize_with = "opt_string_or_struct")]
+    pub fee_account: Option<ComponentAddressOrName>,
+    pub max_fee: Option<Amount>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct TemplatesPublishResponse {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    #[serde(with = "serde_with::string")]
+    pub template_address: TemplateAddress,
+    pub fee: Amount,
+    pub result: FinalizeResult,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsAddRequest {
+    pub name: String,
+    pub component_address: Option<ComponentAddress>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub public_key: Option<PublicKey>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsAddResponse {
+    pub contact: Contact,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsListRequest {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsListResponse {
+    pub contacts: Vec<Contact>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsRemoveRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct ContactsRemoveResponse {}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesCreateRequest {
+    pub name: String,
+    pub schedule: TransactionSchedule,
+    pub instructions: Vec<Instruction>,
+    /// The account that pays the fees and signs each submission. The default account is used if not set.
+    pub fee_account: Option<ComponentAddressOrName>,
+    pub max_fee: Option<Amount>,
+    /// The maximum number of submissions. If not set, the transaction is submitted until the schedule is cancelled.
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub max_runs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesCreateResponse {
+    pub scheduled_transaction: ScheduledTransaction,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesListRequest {
+    /// If true, completed and cancelled schedules are included
+    #[serde(default)]
+    pub include_finished: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesListResponse {
+    pub scheduled_transactions: Vec<ScheduledTransaction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesCancelRequest {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(TS),
+    ts(export, export_to = "../../bindings/src/types/wallet-daemon-client/")
+)]
+pub struct SchedulesCancelResponse {
+    pub scheduled_transaction: ScheduledTransaction,
 }
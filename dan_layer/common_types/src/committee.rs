@@ -10,7 +10,7 @@ use tari_engine_types::substate::SubstateId;
 
 use crate::{shard::Shard, Epoch, NumPreshards, ShardGroup, SubstateAddress};
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Default, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default, Hash)]
 #[cfg_attr(
     feature = "ts",
     derive(ts_rs::TS),
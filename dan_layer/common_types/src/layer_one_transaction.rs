@@ -14,12 +14,14 @@ pub struct LayerOneTransactionDef<T> {
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum LayerOnePayloadType {
     EvictionProof,
+    ValidatorNodeRegistration,
 }
 
 impl Display for LayerOnePayloadType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LayerOnePayloadType::EvictionProof => write!(f, "EvictionProof"),
+            LayerOnePayloadType::ValidatorNodeRegistration => write!(f, "ValidatorNodeRegistration"),
         }
     }
 }
@@ -16,4 +16,11 @@ pub trait TemplateProvider: Send + Sync + Clone + 'static {
         template_address: TemplateAddress,
         template: &[u8],
     ) -> Result<(), Self::Error>;
+
+    fn add_flow_template(
+        &self,
+        author_public_key: PublicKey,
+        template_address: TemplateAddress,
+        flow_json: &str,
+    ) -> Result<(), Self::Error>;
 }
@@ -48,6 +48,21 @@ pub struct ConsensusConstants {
     pub epochs_per_era: Epoch,
     /// Maximum size in bytes for a template WASM binary.
     pub template_binary_max_size_bytes: usize,
+    /// Maximum combined size in bytes of the WASM memories used while executing a transaction.
+    pub max_wasm_memory_bytes: u64,
+    /// Maximum number of bytes that the engine may allocate on behalf of a transaction.
+    pub max_host_allocated_bytes: u64,
+    /// Maximum gas that a transaction may consume executing WASM, regardless of the fee that it declares.
+    pub max_gas_per_transaction: u64,
+    /// Maximum number of 64KiB pages that the memory of a template instance may grow to.
+    pub max_wasm_memory_pages: u32,
+    /// Maximum number of elements in a table of a template instance.
+    pub max_wasm_table_elements: u32,
+    /// Maximum wall time of a template instance, including nested template calls.
+    pub max_template_execution_time: Duration,
+    /// The number of epochs between state snapshots. A snapshot of the committee's substates is taken at the end of
+    /// every epoch that is a multiple of this value, and is used to fast-sync new validator nodes.
+    pub state_snapshot_interval: Epoch,
 }
 
 impl ConsensusConstants {
@@ -66,6 +81,13 @@ impl ConsensusConstants {
             fee_exhaust_divisor: 20, // 5%
             epochs_per_era: Epoch(10),
             template_binary_max_size_bytes: 1000 * 1000 * 5, // 5 MB
+            max_wasm_memory_bytes: 8 * 1024 * 1024,          // 8 MiB
+            max_host_allocated_bytes: 16 * 1024 * 1024,      // 16 MiB
+            max_gas_per_transaction: 100_000_000,
+            max_wasm_memory_pages: 32, // 2 MiB
+            max_wasm_table_elements: 10_000,
+            max_template_execution_time: Duration::from_secs(10),
+            state_snapshot_interval: Epoch(5),
         }
     }
 }
@@ -24,6 +24,8 @@ use tari_dan_storage::{
         LockedBlock,
         NoVoteReason,
         PendingShardStateTreeDiff,
+        ProposalEquivocation,
+        ProposalEquivocationAtom,
         QuorumCertificate,
         QuorumDecision,
         SubstateChange,
@@ -65,6 +67,8 @@ pub struct BlockDecision {
     pub finalized_transactions: Vec<Vec<TransactionPoolRecord>>,
     pub end_of_epoch: Option<Epoch>,
     pub high_qc: HighQc,
+    /// Contains the blocks that were committed as a result of this decision
+    pub committed_blocks: Vec<LeafBlock>,
     pub committed_blocks_with_evictions: Vec<Block>,
 }
 
@@ -86,6 +90,7 @@ pub struct ProposedBlockChangeSet {
     proposed_utxo_mints: Vec<UnclaimedConfidentialOutputAddress>,
     no_vote_reason: Option<NoVoteReason>,
     evict_nodes: Vec<PublicKey>,
+    proposed_equivocations: Vec<ProposalEquivocationAtom>,
 }
 
 impl ProposedBlockChangeSet {
@@ -101,6 +106,7 @@ impl ProposedBlockChangeSet {
             proposed_utxo_mints: Vec::new(),
             no_vote_reason: None,
             evict_nodes: Vec::new(),
+            proposed_equivocations: Vec::new(),
         }
     }
 
@@ -178,8 +184,9 @@ impl ProposedBlockChangeSet {
             );
             self.proposed_utxo_mints.shrink_to(MEM_MAX_PROPOSED_UTXO_MINTS_SIZE);
         }
-        // evict_nodes is typically rare, so rather release all memory
+        // evict_nodes and proposed_equivocations are typically rare, so rather release all memory
         self.evict_nodes = vec![];
+        self.proposed_equivocations = vec![];
         self.no_vote_reason = None;
     }
 
@@ -217,6 +224,11 @@ impl ProposedBlockChangeSet {
         self
     }
 
+    pub fn set_proposal_equivocation_proposed_in(&mut self, atom: ProposalEquivocationAtom) -> &mut Self {
+        self.proposed_equivocations.push(atom);
+        self
+    }
+
     pub fn apply_transaction_update(&self, tx_rec_mut: &mut TransactionPoolRecord) {
         if let Some(update) = self.transaction_changes.get(tx_rec_mut.transaction_id()) {
             update.apply_update(tx_rec_mut);
@@ -392,6 +404,10 @@ impl ProposedBlockChangeSet {
             ValidatorConsensusStats::evict_node(tx, node, self.block.block_id)?;
         }
 
+        for atom in &self.proposed_equivocations {
+            ProposalEquivocation::set_proposed_in_block(tx, atom, &self.block.block_id)?;
+        }
+
         Ok(())
     }
 
@@ -444,6 +460,10 @@ impl ProposedBlockChangeSet {
         for node in &self.evict_nodes {
             debug!(target: LOG_TARGET, "[drop] EvictNode: {node}");
         }
+
+        for atom in &self.proposed_equivocations {
+            debug!(target: LOG_TARGET, "[drop] ProposedEquivocation: {atom}");
+        }
     }
 }
 
@@ -476,6 +496,13 @@ impl Display for ProposedBlockChangeSet {
         if !self.proposed_utxo_mints.is_empty() {
             write!(f, " ProposedUtxoMints: {} mint(s), ", self.proposed_utxo_mints.len())?;
         }
+        if !self.proposed_equivocations.is_empty() {
+            write!(
+                f,
+                " ProposedEquivocations: {} equivocation(s), ",
+                self.proposed_equivocations.len()
+            )?;
+        }
         write!(f, ")")
     }
 }
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::{ControlFlow, Deref},
 };
 
@@ -12,10 +12,12 @@ use tari_common::configuration::Network;
 use tari_common_types::types::FixedHash;
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
+    optional::Optional,
     shard::Shard,
     Epoch,
     NodeAddressable,
     NodeHeight,
+    NumPreshards,
     ShardGroup,
 };
 use tari_dan_storage::{
@@ -23,10 +25,15 @@ use tari_dan_storage::{
         Block,
         BlockHeader,
         BlockId,
+        Command,
         EpochCheckpoint,
+        EvidenceValidationError,
         LeafBlock,
         PendingShardStateTreeDiff,
         QuorumCertificate,
+        StateSnapshot,
+        StateTransition,
+        StateTransitionId,
         SubstateChange,
         ValidatorConsensusStats,
         VersionedStateHashTreeDiff,
@@ -42,6 +49,7 @@ use crate::{
     hotstuff::{
         substate_store::{ShardScopedTreeStoreReader, ShardedStateTree},
         HotStuffError,
+        ProposalValidationError,
     },
     traits::LeaderStrategy,
 };
@@ -250,6 +258,28 @@ pub fn calculate_state_merkle_root<'a, TTx: StateStoreReadTransaction, I: IntoIt
     ))
 }
 
+/// Validates the evidence of every transaction in the block. The justify QC of the block may be referenced by the
+/// evidence before it has been stored.
+pub fn validate_block_evidence<TTx: StateStoreReadTransaction>(
+    tx: &TTx,
+    block: &Block,
+    num_preshards: NumPreshards,
+) -> Result<(), ProposalValidationError> {
+    for atom in block.commands().iter().filter_map(|c| c.transaction()) {
+        atom.evidence
+            .validate(tx, num_preshards, &[block.justify()])
+            .map_err(|err| match err {
+                EvidenceValidationError::StorageError(err) => ProposalValidationError::StorageError(err),
+                err => ProposalValidationError::InvalidEvidence {
+                    block_id: *block.id(),
+                    transaction_id: atom.id,
+                    details: err.to_string(),
+                },
+            })?;
+    }
+    Ok(())
+}
+
 pub(crate) fn create_epoch_checkpoint<TTx>(
     tx: &mut TTx,
     epoch: Epoch,
@@ -293,6 +323,58 @@ where
     Ok(checkpoint)
 }
 
+/// Persists a snapshot of the substates of each shard in the checkpoint if the checkpoint epoch is a snapshot epoch.
+/// Snapshots taken before the previous snapshot are deleted.
+pub(crate) fn create_state_snapshots<TTx>(
+    tx: &mut TTx,
+    checkpoint: &EpochCheckpoint,
+    snapshot_interval: Epoch,
+) -> Result<(), HotStuffError>
+where
+    TTx: StateStoreWriteTransaction + Deref,
+    TTx::Target: StateStoreReadTransaction,
+{
+    let epoch = checkpoint.block().epoch();
+    if snapshot_interval.is_zero() || epoch.as_u64() % snapshot_interval.as_u64() != 0 {
+        return Ok(());
+    }
+
+    for (shard, state_root) in checkpoint.shard_roots() {
+        let Some(state_version) = tx.state_tree_versions_get_latest(*shard)? else {
+            continue;
+        };
+        let last_transition_id = StateTransition::get_last_id(&**tx, *shard)
+            .optional()?
+            .unwrap_or_else(|| StateTransitionId::initial(*shard));
+        let snapshot = StateSnapshot::create(tx, epoch, *shard, state_version, *state_root, last_transition_id)?;
+        info!(target: LOG_TARGET, "📸 Created {snapshot}");
+    }
+
+    // The previous snapshot is kept so that nodes that are part way through loading it are able to complete
+    StateSnapshot::delete_all_before(tx, epoch.saturating_sub(snapshot_interval))?;
+
+    Ok(())
+}
+
+/// Returns the foreign shard groups that are sent the block as a foreign proposal once it is locked
+pub(crate) fn get_foreign_proposal_shard_groups<'a, I: IntoIterator<Item = &'a Command>>(
+    commands: I,
+    local_shard_group: ShardGroup,
+    local_committee_info: &CommitteeInfo,
+) -> HashSet<ShardGroup> {
+    commands
+        .into_iter()
+        .filter_map(|c| {
+            c.local_prepare()
+                // No need to broadcast LocalPrepare if the committee is output only
+                .filter(|atom| !atom.evidence.is_committee_output_only(local_committee_info))
+                .or_else(|| c.local_accept())
+        })
+        .flat_map(|p| p.evidence.shard_groups_iter().copied())
+        .filter(|shard_group| local_shard_group != *shard_group)
+        .collect()
+}
+
 pub(crate) fn filter_diff_for_committee(committee_info: &CommitteeInfo, diff: &SubstateDiff) -> SubstateDiff {
     let mut filtered_diff = SubstateDiff::new();
     filtered_diff
@@ -11,4 +11,10 @@ pub struct HotstuffConfig {
     pub network: Network,
     pub sidechain_id: Option<RistrettoPublicKey>,
     pub consensus_constants: ConsensusConstants,
+    /// The maximum number of transactions in a block that are executed in parallel. Parallel execution is disabled if
+    /// this is less than 2.
+    pub max_parallel_executions: usize,
+    /// The maximum number of foreign proposals for future epochs that are held in memory. Once the limit is reached,
+    /// the proposals furthest in the future are evicted to storage and loaded again when their epoch is reached.
+    pub max_buffered_foreign_proposals: usize,
 }
@@ -0,0 +1,126 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Handling of transactions that are in the transaction pool when an epoch ends.
+//!
+//! This runs when the EndEpoch block is committed. EndEpoch blocks contain no transactions, so every block before it
+//! is committed and all honest replicas have the same view of the pool. The rules below only depend on that view and
+//! on the committee layout of both epochs, so every replica arrives at the same result.
+//!
+//! 1. If the committee layout is unchanged (same number of committees and the same local shard group), transactions are
+//!    carried over into the next epoch. Pledges, substate locks and evidence remain valid because the next epoch has
+//!    the same shard groups.
+//! 2. A transaction that has not been proposed (New) and has a max epoch before the next epoch is aborted. It is marked
+//!    as ready so that the abort is sequenced in the first blocks of the next epoch.
+//! 3. If the layout changed, transactions that have not been LOCAL-ACCEPTED are released: they are removed from the
+//!    pool, their substate locks and foreign pledges are removed and they are requeued. Requeued transactions are
+//!    validated and routed again as if they were new, so that the committees of the next epoch pledge their inputs.
+//!    This is safe because a transaction can only be committed once every involved shard group has LOCAL-ACCEPTED it.
+//! 4. Transactions that are LOCAL-ACCEPTED or later are always carried over. Our local decision has been shared with
+//!    other shard groups, so it cannot be changed. Foreign proposals are validated against the committee of the epoch
+//!    in which they were proposed.
+//!
+//! Known limitation: if the layout changes and another shard group aborts (by releasing) a transaction that we have
+//! LOCAL-ACCEPTED, we never receive its LOCAL-ACCEPT and the transaction stays in the pool.
+
+use std::ops::Deref;
+
+use log::*;
+use tari_dan_common_types::Epoch;
+use tari_dan_storage::{
+    consensus_models::{SubstateRecord, TransactionPoolRecord, TransactionPoolStage},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+};
+use tari_engine_types::commit_result::RejectReason;
+use tari_transaction::TransactionId;
+
+use crate::hotstuff::HotStuffError;
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::epoch_boundary";
+
+#[derive(Debug, Clone, Default)]
+pub struct EpochBoundaryOutcome {
+    pub num_carried_over: usize,
+    pub num_expired: usize,
+    /// Transactions that were removed from the pool and must be resubmitted in the next epoch
+    pub requeued: Vec<TransactionId>,
+}
+
+pub(crate) fn process_transactions_at_epoch_boundary<TTx>(
+    tx: &mut TTx,
+    next_epoch: Epoch,
+    is_layout_changed: bool,
+) -> Result<EpochBoundaryOutcome, HotStuffError>
+where
+    TTx: StateStoreWriteTransaction + Deref,
+    TTx::Target: StateStoreReadTransaction,
+{
+    let mut outcome = EpochBoundaryOutcome::default();
+    let mut expired = Vec::new();
+
+    for rec in tx.transaction_pool_get_all()? {
+        let stage = rec.current_stage();
+        if is_layout_changed &&
+            (stage < TransactionPoolStage::LocalAccepted || stage == TransactionPoolStage::LocalOnly)
+        {
+            outcome.requeued.push(*rec.transaction_id());
+            continue;
+        }
+
+        if stage == TransactionPoolStage::New && rec.current_decision().is_commit() {
+            let transaction = rec.get_transaction(&**tx)?;
+            if transaction
+                .transaction()
+                .max_epoch()
+                .is_some_and(|max_epoch| max_epoch < next_epoch)
+            {
+                expired.push((rec, transaction));
+                continue;
+            }
+        }
+
+        outcome.num_carried_over += 1;
+    }
+
+    for (rec, mut transaction) in expired {
+        info!(
+            target: LOG_TARGET,
+            "⌛️ Transaction {} expires before {}. Aborting.",
+            rec.transaction_id(),
+            next_epoch
+        );
+        transaction
+            .set_abort_reason(RejectReason::InvalidTransaction(format!(
+                "Transaction expired before {next_epoch}"
+            )))
+            .update(tx)?;
+        rec.remove(tx)?;
+        tx.transaction_pool_insert_new(
+            *rec.transaction_id(),
+            transaction.current_decision(),
+            rec.priority_fee(),
+            true,
+        )?;
+        outcome.num_expired += 1;
+    }
+
+    if !outcome.requeued.is_empty() {
+        release_transactions(tx, &outcome.requeued)?;
+    }
+
+    Ok(outcome)
+}
+
+fn release_transactions<TTx>(tx: &mut TTx, transaction_ids: &[TransactionId]) -> Result<(), HotStuffError>
+where
+    TTx: StateStoreWriteTransaction + Deref,
+    TTx::Target: StateStoreReadTransaction,
+{
+    // Also removes foreign pledges and lock conflicts for these transactions
+    TransactionPoolRecord::remove_all(tx, transaction_ids)?;
+    // Pending executions belong to blocks in the previous epoch, so the transaction is executed again when it is
+    // proposed in the next epoch
+    SubstateRecord::unlock_all(tx, transaction_ids.iter().peekable())?;
+    Ok(())
+}
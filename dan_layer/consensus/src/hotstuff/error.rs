@@ -255,6 +255,12 @@ pub enum ProposalValidationError {
     DummyBlockWithSignature { block_id: BlockId },
     #[error("Dummy block {block_id} includes commands")]
     DummyBlockWithCommands { block_id: BlockId },
+    #[error("Block {block_id} contains invalid evidence for transaction {transaction_id}: {details}")]
+    InvalidEvidence {
+        block_id: BlockId,
+        transaction_id: TransactionId,
+        details: String,
+    },
     #[error("Malformed block {block_id}: {details}")]
     MalformedBlock { block_id: BlockId, details: String },
     #[error("Block {block_id} is for a future epoch. Current epoch: {current_epoch}, block epoch: {block_epoch}")]
@@ -264,3 +270,11 @@ pub enum ProposalValidationError {
         block_epoch: Epoch,
     },
 }
+
+impl ProposalValidationError {
+    /// Returns true if the justify QC of a proposal is provably invalid i.e. it does not contain a quorum of valid
+    /// signatures. Failures that may be caused by our own view of the committee are not included.
+    pub fn is_invalid_quorum_certificate(&self) -> bool {
+        matches!(self, Self::QCInvalidSignature { .. } | Self::QuorumWasNotReached { .. })
+    }
+}
@@ -1,8 +1,10 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
+use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{Epoch, NodeHeight, ShardGroup};
 use tari_dan_storage::consensus_models::{BlockId, LeafBlock};
+use tari_transaction::TransactionId;
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum HotstuffEvent {
@@ -29,4 +31,15 @@ pub enum HotstuffEvent {
         epoch: Epoch,
         registered_shard_group: Option<ShardGroup>,
     },
+    #[error("{} transaction(s) released at the epoch boundary must be resubmitted in {epoch}", transaction_ids.len())]
+    TransactionsRequeued {
+        epoch: Epoch,
+        transaction_ids: Vec<TransactionId>,
+    },
+    #[error("Leader {proposed_by} proposed conflicting blocks at height {height} in epoch {epoch}")]
+    ProposalEquivocationDetected {
+        epoch: Epoch,
+        height: NodeHeight,
+        proposed_by: PublicKey,
+    },
 }
@@ -0,0 +1,403 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Fault injection for consensus tests. The messaging wrappers in this module consult a [FaultInjector] for every
+//! message that is sent or received, and drop, delay or duplicate the message as instructed. This allows tests to
+//! simulate a failed or slow leader for chosen heights and assert on the resulting view changes and recovery.
+
+use std::{
+    future::Future,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::*;
+use tari_dan_common_types::{NodeHeight, ShardGroup};
+use tokio::time::{self, Instant};
+
+use crate::{
+    messages::HotstuffMessage,
+    traits::{InboundMessaging, InboundMessagingError, MessagePriority, OutboundMessaging, OutboundMessagingError},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::fault_injection";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageDirection {
+    Inbound,
+    Outbound,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    Drop,
+    Delay(Duration),
+    /// Deliver the message and this many additional copies of it
+    Duplicate(usize),
+}
+
+pub trait FaultInjector: Send + Sync + 'static {
+    /// Returns the fault to apply to the message, or None if the message should be delivered as normal
+    fn fault_for(&self, direction: MessageDirection, message: &HotstuffMessage) -> Option<Fault>;
+}
+
+/// Returns the height that the message is for, if the message is for a particular height of the local chain
+pub fn message_height(message: &HotstuffMessage) -> Option<NodeHeight> {
+    match message {
+        HotstuffMessage::NewView(msg) => Some(msg.new_height),
+        HotstuffMessage::Proposal(msg) => Some(msg.block.height()),
+        HotstuffMessage::Vote(msg) => Some(msg.unverified_block_height),
+        _ => None,
+    }
+}
+
+/// Applies a fault to messages of a type (see [HotstuffMessage::as_type_str]) sent or received at the given heights
+#[derive(Debug, Clone)]
+pub struct FaultRule {
+    pub direction: MessageDirection,
+    pub message_type: String,
+    /// If set, the rule only applies to messages for these heights. Messages without a height never match.
+    pub heights: Option<RangeInclusive<NodeHeight>>,
+    pub fault: Fault,
+}
+
+impl FaultRule {
+    pub fn new<T: Into<String>>(direction: MessageDirection, message_type: T, fault: Fault) -> Self {
+        Self {
+            direction,
+            message_type: message_type.into(),
+            heights: None,
+            fault,
+        }
+    }
+
+    pub fn for_heights(mut self, heights: RangeInclusive<NodeHeight>) -> Self {
+        self.heights = Some(heights);
+        self
+    }
+
+    pub fn matches(&self, direction: MessageDirection, message: &HotstuffMessage) -> bool {
+        if self.direction != direction || self.message_type != message.as_type_str() {
+            return false;
+        }
+        match self.heights {
+            Some(ref heights) => message_height(message).is_some_and(|height| heights.contains(&height)),
+            None => true,
+        }
+    }
+}
+
+/// A fault injector whose rules can be changed while the node is running. Clones share the same rules. The first rule
+/// that matches a message is applied.
+#[derive(Debug, Clone, Default)]
+pub struct ScriptedFaultInjector {
+    rules: Arc<Mutex<Vec<FaultRule>>>,
+}
+
+impl ScriptedFaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_rule(&self, rule: FaultRule) {
+        self.rules.lock().unwrap().push(rule);
+    }
+
+    pub fn clear(&self) {
+        self.rules.lock().unwrap().clear();
+    }
+}
+
+impl FaultInjector for ScriptedFaultInjector {
+    fn fault_for(&self, direction: MessageDirection, message: &HotstuffMessage) -> Option<Fault> {
+        self.rules
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|rule| rule.matches(direction, message))
+            .map(|rule| rule.fault)
+    }
+}
+
+/// Wraps inbound messaging to apply the faults given by the injector to received messages
+pub struct FaultInjectingInboundMessaging<TInbound: InboundMessaging> {
+    inner: TInbound,
+    injector: Arc<dyn FaultInjector>,
+    /// Delayed and duplicated messages, and the time from which they may be delivered
+    pending: Vec<(Instant, TInbound::Addr, HotstuffMessage)>,
+}
+
+impl<TInbound: InboundMessaging> FaultInjectingInboundMessaging<TInbound> {
+    pub fn new(inner: TInbound, injector: Arc<dyn FaultInjector>) -> Self {
+        Self {
+            inner,
+            injector,
+            pending: Vec::new(),
+        }
+    }
+
+    fn take_ready(&mut self, now: Instant) -> Option<(TInbound::Addr, HotstuffMessage)> {
+        let (index, _) = self
+            .pending
+            .iter()
+            .enumerate()
+            .filter(|(_, (ready_at, _, _))| *ready_at <= now)
+            .min_by_key(|(_, (ready_at, _, _))| *ready_at)?;
+        let (_, from, msg) = self.pending.remove(index);
+        Some((from, msg))
+    }
+}
+
+impl<TInbound: InboundMessaging + Send> InboundMessaging for FaultInjectingInboundMessaging<TInbound> {
+    type Addr = TInbound::Addr;
+
+    async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+        loop {
+            if let Some(ready) = self.take_ready(Instant::now()) {
+                return Some(Ok(ready));
+            }
+
+            let next_ready_at = self.pending.iter().map(|(ready_at, _, _)| *ready_at).min();
+            let result = match next_ready_at {
+                Some(ready_at) => tokio::select! {
+                    result = self.inner.next_message() => result,
+                    _ = time::sleep_until(ready_at) => continue,
+                },
+                None => self.inner.next_message().await,
+            };
+            let Some(result) = result else {
+                // Deliver any delayed messages before signalling that the inner messaging has ended
+                match next_ready_at {
+                    Some(ready_at) => {
+                        time::sleep_until(ready_at).await;
+                        continue;
+                    },
+                    None => return None,
+                }
+            };
+            let (from, msg) = match result {
+                Ok(msg) => msg,
+                Err(err) => return Some(Err(err)),
+            };
+
+            match self.injector.fault_for(MessageDirection::Inbound, &msg) {
+                None => return Some(Ok((from, msg))),
+                Some(Fault::Drop) => {
+                    debug!(target: LOG_TARGET, "💉 Dropping inbound {} from {}", msg, from);
+                },
+                Some(Fault::Delay(delay)) => {
+                    debug!(target: LOG_TARGET, "💉 Delaying inbound {} from {} by {:.2?}", msg, from, delay);
+                    self.pending.push((Instant::now() + delay, from, msg));
+                },
+                Some(Fault::Duplicate(copies)) => {
+                    debug!(target: LOG_TARGET, "💉 Duplicating inbound {} from {} {} time(s)", msg, from, copies);
+                    let now = Instant::now();
+                    for _ in 0..copies {
+                        self.pending.push((now, from.clone(), msg.clone()));
+                    }
+                    return Some(Ok((from, msg)));
+                },
+            }
+        }
+    }
+}
+
+/// Wraps outbound messaging to apply the faults given by the injector to sent messages. Delayed messages are sent from
+/// a separate task so that the sender is not blocked.
+#[derive(Clone)]
+pub struct FaultInjectingOutboundMessaging<TOutbound> {
+    inner: TOutbound,
+    injector: Arc<dyn FaultInjector>,
+}
+
+impl<TOutbound> FaultInjectingOutboundMessaging<TOutbound> {
+    pub fn new(inner: TOutbound, injector: Arc<dyn FaultInjector>) -> Self {
+        Self { inner, injector }
+    }
+}
+
+impl<TOutbound> FaultInjectingOutboundMessaging<TOutbound>
+where TOutbound: OutboundMessaging + Clone + Send + Sync + 'static
+{
+    /// Applies the fault for the message. The message is sent (possibly more than once) by calling `send` on the inner
+    /// messaging, unless it is dropped.
+    async fn with_fault<F, Fut>(&self, message: HotstuffMessage, send: F) -> Result<(), OutboundMessagingError>
+    where
+        F: Fn(TOutbound, HotstuffMessage) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(), OutboundMessagingError>> + Send + 'static,
+    {
+        match self.injector.fault_for(MessageDirection::Outbound, &message) {
+            None => send(self.inner.clone(), message).await,
+            Some(Fault::Drop) => {
+                debug!(target: LOG_TARGET, "💉 Dropping outbound {}", message);
+                Ok(())
+            },
+            Some(Fault::Delay(delay)) => {
+                debug!(target: LOG_TARGET, "💉 Delaying outbound {} by {:.2?}", message, delay);
+                let inner = self.inner.clone();
+                tokio::spawn(async move {
+                    time::sleep(delay).await;
+                    if let Err(err) = send(inner, message).await {
+                        warn!(target: LOG_TARGET, "Failed to send delayed message: {}", err);
+                    }
+                });
+                Ok(())
+            },
+            Some(Fault::Duplicate(copies)) => {
+                debug!(target: LOG_TARGET, "💉 Duplicating outbound {} {} time(s)", message, copies);
+                for _ in 0..copies {
+                    send(self.inner.clone(), message.clone()).await?;
+                }
+                send(self.inner.clone(), message).await
+            },
+        }
+    }
+}
+
+impl<TOutbound> OutboundMessaging for FaultInjectingOutboundMessaging<TOutbound>
+where TOutbound: OutboundMessaging + Clone + Send + Sync + 'static
+{
+    type Addr = TOutbound::Addr;
+
+    async fn send_self<T: Into<HotstuffMessage> + Send>(&mut self, message: T) -> Result<(), OutboundMessagingError> {
+        self.with_fault(message.into(), |mut inner, message| async move {
+            inner.send_self(message).await
+        })
+        .await
+    }
+
+    async fn send_with_priority<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+        priority: MessagePriority,
+    ) -> Result<(), OutboundMessagingError> {
+        self.with_fault(message.into(), move |mut inner, message| {
+            let to = to.clone();
+            async move { inner.send_with_priority(to, message, priority).await }
+        })
+        .await
+    }
+
+    async fn multicast<T, I>(
+        &mut self,
+        shard_group: ShardGroup,
+        addresses: I,
+        message: T,
+    ) -> Result<(), OutboundMessagingError>
+    where
+        I: IntoIterator<Item = Self::Addr> + Send,
+        T: Into<HotstuffMessage> + Send,
+    {
+        let addresses = addresses.into_iter().collect::<Vec<_>>();
+        self.with_fault(message.into(), move |mut inner, message| {
+            let addresses = addresses.clone();
+            async move { inner.multicast(shard_group, addresses, message).await }
+        })
+        .await
+    }
+
+    async fn broadcast<T>(&mut self, shard_group: ShardGroup, message: T) -> Result<(), OutboundMessagingError>
+    where T: Into<HotstuffMessage> + Send {
+        self.with_fault(message.into(), move |mut inner, message| async move {
+            inner.broadcast(shard_group, message).await
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use tari_dan_common_types::Epoch;
+    use tari_dan_storage::consensus_models::BlockId;
+
+    use super::*;
+    use crate::messages::ForeignProposalNotificationMessage;
+
+    struct TestInbound(VecDeque<HotstuffMessage>);
+
+    impl InboundMessaging for TestInbound {
+        type Addr = String;
+
+        async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+            self.0.pop_front().map(|msg| Ok(("peer".to_string(), msg)))
+        }
+    }
+
+    fn notification(block: u8) -> HotstuffMessage {
+        HotstuffMessage::ForeignProposalNotification(ForeignProposalNotificationMessage {
+            block_id: BlockId::from([block; 32]),
+            epoch: Epoch(1),
+        })
+    }
+
+    fn block_ids(messages: Vec<HotstuffMessage>) -> Vec<BlockId> {
+        messages
+            .into_iter()
+            .map(|msg| match msg {
+                HotstuffMessage::ForeignProposalNotification(msg) => msg.block_id,
+                _ => panic!("unexpected message"),
+            })
+            .collect()
+    }
+
+    async fn collect(messaging: &mut FaultInjectingInboundMessaging<TestInbound>) -> Vec<HotstuffMessage> {
+        let mut messages = Vec::new();
+        while let Some(result) = messaging.next_message().await {
+            messages.push(result.unwrap().1);
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn it_drops_and_duplicates_inbound_messages() {
+        let injector = ScriptedFaultInjector::new();
+        injector.add_rule(FaultRule::new(
+            MessageDirection::Inbound,
+            "ForeignProposalNotification",
+            Fault::Duplicate(1),
+        ));
+        let inbound = TestInbound(VecDeque::from([notification(1), notification(2)]));
+        let mut messaging = FaultInjectingInboundMessaging::new(inbound, Arc::new(injector.clone()));
+        let first = messaging.next_message().await.unwrap().unwrap().1;
+        let second = messaging.next_message().await.unwrap().unwrap().1;
+        assert_eq!(block_ids(vec![first, second]), vec![BlockId::from([1; 32]); 2]);
+
+        injector.clear();
+        injector.add_rule(FaultRule::new(
+            MessageDirection::Inbound,
+            "ForeignProposalNotification",
+            Fault::Drop,
+        ));
+        assert!(collect(&mut messaging).await.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn it_delays_inbound_messages() {
+        let injector = ScriptedFaultInjector::new();
+        injector.add_rule(FaultRule::new(
+            MessageDirection::Inbound,
+            "ForeignProposalNotification",
+            Fault::Delay(Duration::from_secs(1)),
+        ));
+        let inbound = TestInbound(VecDeque::from([notification(1)]));
+        let mut messaging = FaultInjectingInboundMessaging::new(inbound, Arc::new(injector));
+        let start = Instant::now();
+        let messages = collect(&mut messaging).await;
+        assert_eq!(block_ids(messages), vec![BlockId::from([1; 32])]);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn it_only_matches_messages_for_the_given_heights() {
+        let rule = FaultRule::new(MessageDirection::Outbound, "ForeignProposalNotification", Fault::Drop);
+        assert!(rule.matches(MessageDirection::Outbound, &notification(1)));
+        assert!(!rule.matches(MessageDirection::Inbound, &notification(1)));
+        // Notifications have no height
+        let rule = rule.for_heights(NodeHeight(0)..=NodeHeight(10));
+        assert!(!rule.matches(MessageDirection::Outbound, &notification(1)));
+    }
+}
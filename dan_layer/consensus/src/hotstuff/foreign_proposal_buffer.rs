@@ -0,0 +1,126 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::BlockId;
+
+use crate::messages::ForeignProposalMessage;
+
+type BufferKey = (Epoch, NodeHeight, BlockId);
+
+/// Holds foreign proposals for epochs that the local node has not yet reached. The buffer holds at most `capacity`
+/// proposals. When it is full, the proposal that is furthest in the future is evicted, since it will be the last to
+/// be processed.
+#[derive(Debug)]
+pub struct ForeignProposalBuffer<TAddr> {
+    capacity: usize,
+    proposals: BTreeMap<BufferKey, (TAddr, ForeignProposalMessage)>,
+}
+
+impl<TAddr> ForeignProposalBuffer<TAddr> {
+    /// Returns a new buffer. The capacity is at least one.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            proposals: BTreeMap::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.proposals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.proposals.is_empty()
+    }
+
+    /// Adds a proposal to the buffer. If the buffer is over capacity, the proposal furthest in the future is evicted
+    /// and returned. This may be the proposal that was pushed.
+    pub fn push(&mut self, from: TAddr, proposal: ForeignProposalMessage) -> Option<(TAddr, ForeignProposalMessage)> {
+        let key = (
+            proposal.justify_qc.epoch(),
+            proposal.block.height(),
+            *proposal.block.id(),
+        );
+        self.proposals.insert(key, (from, proposal));
+        if self.proposals.len() > self.capacity {
+            return self.proposals.pop_last().map(|(_, entry)| entry);
+        }
+        None
+    }
+
+    /// Removes and returns the earliest proposal if it is for `current_epoch` or before
+    pub fn pop_ready(&mut self, current_epoch: Epoch) -> Option<(TAddr, ForeignProposalMessage)> {
+        let entry = self.proposals.first_entry()?;
+        let (epoch, _, _) = entry.key();
+        if *epoch > current_epoch {
+            return None;
+        }
+        Some(entry.remove())
+    }
+
+    pub fn clear(&mut self) {
+        self.proposals.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_common::configuration::Network;
+    use tari_dan_common_types::{NumPreshards, ShardGroup};
+    use tari_dan_storage::consensus_models::{Block, BlockPledge, QuorumCertificate};
+
+    use super::*;
+
+    fn proposal_for(epoch: u64) -> ForeignProposalMessage {
+        let justify_qc = QuorumCertificate::genesis(Epoch(epoch), ShardGroup::all_shards(NumPreshards::P1));
+        let block = Block::genesis(
+            Network::LocalNet,
+            Epoch(epoch),
+            ShardGroup::all_shards(NumPreshards::P1),
+            Default::default(),
+            None,
+        );
+        ForeignProposalMessage {
+            block,
+            justify_qc,
+            block_pledge: BlockPledge::new(),
+        }
+    }
+
+    #[test]
+    fn it_evicts_the_proposal_furthest_in_the_future() {
+        let mut buffer = ForeignProposalBuffer::new(2);
+        assert!(buffer.push(1, proposal_for(3)).is_none());
+        assert!(buffer.push(2, proposal_for(5)).is_none());
+
+        let (from, evicted) = buffer.push(3, proposal_for(4)).unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(evicted.justify_qc.epoch(), Epoch(5));
+
+        // The pushed proposal is evicted if it is the furthest in the future
+        let (from, _) = buffer.push(4, proposal_for(6)).unwrap();
+        assert_eq!(from, 4);
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn it_returns_proposals_once_the_epoch_is_reached() {
+        let mut buffer = ForeignProposalBuffer::new(10);
+        buffer.push(1, proposal_for(4));
+        buffer.push(2, proposal_for(3));
+
+        assert!(buffer.pop_ready(Epoch(2)).is_none());
+        let (from, _) = buffer.pop_ready(Epoch(3)).unwrap();
+        assert_eq!(from, 2);
+        assert!(buffer.pop_ready(Epoch(3)).is_none());
+        assert_eq!(buffer.pop_ready(Epoch(5)).unwrap().0, 1);
+        assert!(buffer.is_empty());
+    }
+}
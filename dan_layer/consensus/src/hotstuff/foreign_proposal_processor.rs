@@ -387,6 +387,7 @@ pub fn process_foreign_block<TTx: StateStoreReadTransaction>(
             Command::LocalOnly(_) |
             Command::ForeignProposal(_) |
             Command::EvictNode(_) |
+            Command::ProposalEquivocation(_) |
             Command::MintConfidentialOutput(_) => {
                 // Disregard
                 continue;
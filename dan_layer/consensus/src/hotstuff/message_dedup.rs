@@ -0,0 +1,270 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use log::*;
+use tari_dan_common_types::Epoch;
+use tari_engine_types::hashing::{hasher32, EngineHashDomainLabel};
+use tari_epoch_manager::EpochManagerEvent;
+use tokio::sync::broadcast;
+
+use crate::{
+    messages::HotstuffMessage,
+    traits::{InboundMessaging, InboundMessagingError},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::message_dedup";
+
+type MessageHash = [u8; 32];
+
+#[derive(Debug, Clone)]
+pub struct MessageDedupConfig {
+    /// How long a received message is remembered
+    pub window: Duration,
+    /// The maximum number of messages that are remembered. The oldest messages are forgotten first.
+    pub max_entries: usize,
+    /// Messages for an epoch more than this many epochs before the current epoch are dropped
+    pub max_epochs_behind: u64,
+    /// Messages for an epoch more than this many epochs after the current epoch are dropped
+    pub max_epochs_ahead: u64,
+}
+
+impl Default for MessageDedupConfig {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(5 * 60),
+            max_entries: 100_000,
+            max_epochs_behind: 2,
+            max_epochs_ahead: 2,
+        }
+    }
+}
+
+/// Wraps inbound messaging to drop messages that were already received within the dedup window, and messages for
+/// epochs far from the current epoch. This protects the hotstuff worker from replayed and duplicated (e.g. gossiped
+/// or rebroadcast) messages.
+///
+/// Requests (missing transactions, catch up sync and foreign proposal requests) are passed through unchanged, since
+/// a peer legitimately repeats a request that was not answered, and a peer that is catching up may be many epochs
+/// behind.
+pub struct DeduplicatingInboundMessaging<TInbound> {
+    inner: TInbound,
+    config: MessageDedupConfig,
+    epoch_events: broadcast::Receiver<EpochManagerEvent>,
+    current_epoch: Option<Epoch>,
+    seen: SeenMessages,
+}
+
+impl<TInbound: InboundMessaging> DeduplicatingInboundMessaging<TInbound> {
+    /// Creates a new wrapper. The current epoch is tracked from the epoch manager events, and messages are not checked
+    /// against the current epoch until the first epoch change is received.
+    pub fn new(
+        inner: TInbound,
+        config: MessageDedupConfig,
+        epoch_events: broadcast::Receiver<EpochManagerEvent>,
+    ) -> Self {
+        Self {
+            inner,
+            config,
+            epoch_events,
+            current_epoch: None,
+            seen: SeenMessages::default(),
+        }
+    }
+
+    fn update_current_epoch(&mut self) {
+        loop {
+            match self.epoch_events.try_recv() {
+                Ok(EpochManagerEvent::EpochChanged { epoch, .. }) => self.current_epoch = Some(epoch),
+                // Missed events are replaced by the following events
+                Err(broadcast::error::TryRecvError::Lagged(_)) => {},
+                Err(broadcast::error::TryRecvError::Empty) | Err(broadcast::error::TryRecvError::Closed) => break,
+            }
+        }
+    }
+
+    fn is_epoch_allowed(&self, epoch: Epoch, current_epoch: Epoch) -> bool {
+        epoch.as_u64().saturating_add(self.config.max_epochs_behind) >= current_epoch.as_u64() &&
+            epoch.as_u64() <= current_epoch.as_u64().saturating_add(self.config.max_epochs_ahead)
+    }
+}
+
+impl<TInbound: InboundMessaging + Send> InboundMessaging for DeduplicatingInboundMessaging<TInbound> {
+    type Addr = TInbound::Addr;
+
+    async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+        loop {
+            let (from, msg) = match self.inner.next_message().await? {
+                Ok(msg) => msg,
+                Err(err) => return Some(Err(err)),
+            };
+
+            if is_request(&msg) {
+                return Some(Ok((from, msg)));
+            }
+
+            self.update_current_epoch();
+            if let Some(current_epoch) = self.current_epoch {
+                let epoch = msg.epoch();
+                if !self.is_epoch_allowed(epoch, current_epoch) {
+                    warn!(
+                        target: LOG_TARGET,
+                        "⚠️ Dropping {} from {} for epoch {} which is too far from the current epoch {}",
+                        msg.as_type_str(),
+                        from,
+                        epoch,
+                        current_epoch
+                    );
+                    continue;
+                }
+            }
+
+            if !self.seen.insert(message_hash(&msg), Instant::now(), &self.config) {
+                debug!(target: LOG_TARGET, "🗑️ Dropping duplicate {} from {}", msg.as_type_str(), from);
+                continue;
+            }
+
+            return Some(Ok((from, msg)));
+        }
+    }
+}
+
+fn is_request(msg: &HotstuffMessage) -> bool {
+    matches!(
+        msg,
+        HotstuffMessage::MissingTransactionsRequest(_) |
+            HotstuffMessage::CatchUpSyncRequest(_) |
+            HotstuffMessage::ForeignProposalRequest(_)
+    )
+}
+
+fn message_hash(msg: &HotstuffMessage) -> MessageHash {
+    hasher32(EngineHashDomainLabel::ConsensusMessage)
+        .chain(msg)
+        .result()
+        .into_array()
+}
+
+/// The hashes of the messages received within the dedup window, in the order that they were received
+#[derive(Debug, Default)]
+struct SeenMessages {
+    hashes: HashSet<MessageHash>,
+    order: VecDeque<(MessageHash, Instant)>,
+}
+
+impl SeenMessages {
+    /// Returns true if the message was not seen within the window, and remembers it
+    fn insert(&mut self, hash: MessageHash, now: Instant, config: &MessageDedupConfig) -> bool {
+        while let Some((oldest, received_at)) = self.order.front() {
+            if now.saturating_duration_since(*received_at) < config.window {
+                break;
+            }
+            self.hashes.remove(oldest);
+            self.order.pop_front();
+        }
+
+        if self.hashes.contains(&hash) {
+            return false;
+        }
+        if self.order.len() >= config.max_entries {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.hashes.remove(&oldest);
+            }
+        }
+        self.hashes.insert(hash);
+        self.order.push_back((hash, now));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_dan_storage::consensus_models::BlockId;
+
+    use super::*;
+    use crate::messages::ForeignProposalNotificationMessage;
+
+    struct TestInbound(VecDeque<HotstuffMessage>);
+
+    impl InboundMessaging for TestInbound {
+        type Addr = String;
+
+        async fn next_message(&mut self) -> Option<Result<(Self::Addr, HotstuffMessage), InboundMessagingError>> {
+            self.0.pop_front().map(|msg| Ok(("peer".to_string(), msg)))
+        }
+    }
+
+    fn notification(block: u8, epoch: u64) -> HotstuffMessage {
+        HotstuffMessage::ForeignProposalNotification(ForeignProposalNotificationMessage {
+            block_id: BlockId::from([block; 32]),
+            epoch: Epoch(epoch),
+        })
+    }
+
+    async fn collect(messaging: &mut DeduplicatingInboundMessaging<TestInbound>) -> Vec<HotstuffMessage> {
+        let mut messages = Vec::new();
+        while let Some(result) = messaging.next_message().await {
+            messages.push(result.unwrap().1);
+        }
+        messages
+    }
+
+    #[test]
+    fn it_forgets_messages_after_the_window() {
+        let config = MessageDedupConfig {
+            window: Duration::from_secs(10),
+            max_entries: 2,
+            ..Default::default()
+        };
+        let start = Instant::now();
+        let mut seen = SeenMessages::default();
+        assert!(seen.insert([1; 32], start, &config));
+        assert!(!seen.insert([1; 32], start + Duration::from_secs(9), &config));
+        assert!(seen.insert([1; 32], start + Duration::from_secs(10), &config));
+
+        // The oldest message is forgotten once the maximum is reached
+        assert!(seen.insert([2; 32], start + Duration::from_secs(10), &config));
+        assert!(seen.insert([3; 32], start + Duration::from_secs(10), &config));
+        assert!(seen.insert([1; 32], start + Duration::from_secs(10), &config));
+        assert!(!seen.insert([3; 32], start + Duration::from_secs(10), &config));
+    }
+
+    #[tokio::test]
+    async fn it_drops_duplicate_messages() {
+        let (_tx, rx) = broadcast::channel(1);
+        let inbound = TestInbound(VecDeque::from([notification(1, 1), notification(1, 1), notification(2, 1)]));
+        let mut messaging = DeduplicatingInboundMessaging::new(inbound, MessageDedupConfig::default(), rx);
+
+        let messages = collect(&mut messaging).await;
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(
+            &messages[1],
+            HotstuffMessage::ForeignProposalNotification(msg) if msg.block_id == BlockId::from([2; 32])
+        ));
+    }
+
+    #[tokio::test]
+    async fn it_drops_messages_for_distant_epochs() {
+        let (tx, rx) = broadcast::channel(1);
+        tx.send(EpochManagerEvent::EpochChanged {
+            epoch: Epoch(10),
+            registered_shard_group: None,
+        })
+        .unwrap();
+        let inbound = TestInbound(VecDeque::from([
+            notification(1, 7),
+            notification(2, 8),
+            notification(3, 12),
+            notification(4, 13),
+        ]));
+        let mut messaging = DeduplicatingInboundMessaging::new(inbound, MessageDedupConfig::default(), rx);
+
+        let messages = collect(&mut messaging).await;
+        let epochs = messages.iter().map(|msg| msg.epoch()).collect::<Vec<_>>();
+        assert_eq!(epochs, vec![Epoch(8), Epoch(12)]);
+    }
+}
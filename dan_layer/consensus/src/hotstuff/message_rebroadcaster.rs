@@ -0,0 +1,128 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{sync::Arc, time::Duration};
+
+use log::*;
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tokio::{sync::watch, task, time};
+
+use crate::{
+    messages::HotstuffMessage,
+    traits::{ConsensusSpec, OutboundMessaging},
+};
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::message_rebroadcaster";
+
+/// The maximum number of times that a message is rebroadcast
+const MAX_REBROADCASTS: u32 = 3;
+
+/// Rebroadcasts the last vote or NEWVIEW sent by this node to the next leader, so that a single dropped message does
+/// not force a leader timeout. The message is sent again with exponential backoff until a proposal at or after the
+/// acknowledging height is received, a newer message replaces it, or the maximum number of rebroadcasts is reached.
+/// Receivers ignore messages that they have already processed.
+#[derive(Clone)]
+pub struct MessageRebroadcaster<TAddr> {
+    tx_pending: Arc<watch::Sender<Option<PendingMessage<TAddr>>>>,
+}
+
+impl<TAddr: Clone + Send + Sync + 'static> MessageRebroadcaster<TAddr> {
+    /// Spawns the rebroadcast task. The first rebroadcast is sent after an eighth of the block time, so that all
+    /// rebroadcasts are sent before the leader times out.
+    pub fn spawn<TConsensusSpec>(
+        block_time: Duration,
+        outbound_messaging: TConsensusSpec::OutboundMessaging,
+    ) -> Self
+    where TConsensusSpec: ConsensusSpec<Addr = TAddr> {
+        let (tx_pending, rx_pending) = watch::channel(None);
+        task::spawn(run::<TConsensusSpec>(block_time / 8, rx_pending, outbound_messaging));
+        Self {
+            tx_pending: Arc::new(tx_pending),
+        }
+    }
+
+    /// Rebroadcasts the message to the leader until a proposal at or after `acknowledged_at` is received. Replaces any
+    /// message that is currently being rebroadcast.
+    pub fn track(&self, to: TAddr, message: HotstuffMessage, epoch: Epoch, acknowledged_at: NodeHeight) {
+        self.tx_pending.send_replace(Some(PendingMessage {
+            to,
+            message,
+            epoch,
+            acknowledged_at,
+        }));
+    }
+
+    /// Stops rebroadcasting if the proposal at the given epoch and height acknowledges the pending message
+    pub fn acknowledge(&self, epoch: Epoch, height: NodeHeight) {
+        self.tx_pending.send_if_modified(|pending| {
+            let is_acknowledged = pending
+                .as_ref()
+                .is_some_and(|p| epoch > p.epoch || (epoch == p.epoch && height >= p.acknowledged_at));
+            if is_acknowledged {
+                *pending = None;
+            }
+            is_acknowledged
+        });
+    }
+
+    /// Stops rebroadcasting the pending message, if any
+    pub fn clear(&self) {
+        self.tx_pending.send_if_modified(|pending| pending.take().is_some());
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PendingMessage<TAddr> {
+    to: TAddr,
+    message: HotstuffMessage,
+    epoch: Epoch,
+    acknowledged_at: NodeHeight,
+}
+
+async fn run<TConsensusSpec: ConsensusSpec>(
+    initial_delay: Duration,
+    mut rx_pending: watch::Receiver<Option<PendingMessage<TConsensusSpec::Addr>>>,
+    mut outbound_messaging: TConsensusSpec::OutboundMessaging,
+) {
+    loop {
+        let pending = rx_pending.borrow_and_update().clone();
+        if let Some(pending) = pending {
+            let mut delay = initial_delay;
+            let mut is_changed = false;
+            for attempt in 1..=MAX_REBROADCASTS {
+                tokio::select! {
+                    // The message was acknowledged or replaced
+                    result = rx_pending.changed() => {
+                        if result.is_err() {
+                            return;
+                        }
+                        is_changed = true;
+                        break;
+                    },
+                    _ = time::sleep(delay) => {
+                        debug!(
+                            target: LOG_TARGET,
+                            "🔁 Rebroadcasting {} to {} (attempt {}/{})",
+                            pending.message,
+                            pending.to,
+                            attempt,
+                            MAX_REBROADCASTS
+                        );
+                        if let Err(err) = outbound_messaging.send(pending.to.clone(), pending.message.clone()).await {
+                            warn!(target: LOG_TARGET, "Failed to rebroadcast {}: {}", pending.message, err);
+                        }
+                        delay *= 2;
+                    },
+                }
+            }
+            if is_changed {
+                continue;
+            }
+        }
+
+        // Wait for the next message. An error means that the hotstuff worker has shut down
+        if rx_pending.changed().await.is_err() {
+            break;
+        }
+    }
+}
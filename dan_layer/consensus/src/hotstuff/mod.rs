@@ -3,6 +3,7 @@
 mod common;
 mod config;
 mod current_view;
+mod epoch_boundary;
 mod error;
 mod event;
 mod on_beat;
@@ -22,12 +23,21 @@ mod on_receive_vote;
 // mod on_sync_response;
 mod block_change_set;
 pub mod eviction_proof;
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+mod foreign_proposal_buffer;
 mod foreign_proposal_processor;
+mod message_dedup;
+mod message_rebroadcaster;
 mod on_catch_up_sync;
 mod on_message_validate;
 mod pacemaker;
 mod pacemaker_handle;
+mod peer_rate_limiter;
+mod proposal_equivocation;
 mod state_machine;
+#[cfg(feature = "test-hooks")]
+mod stepper;
 pub mod substate_store;
 mod transaction_manager;
 mod vote_collector;
@@ -38,5 +48,10 @@ pub use config::HotstuffConfig;
 pub use current_view::*;
 pub use error::*;
 pub use event::*;
+pub use message_dedup::{DeduplicatingInboundMessaging, MessageDedupConfig};
+#[cfg(feature = "test-hooks")]
+pub use pacemaker_handle::PacemakerRequest;
 pub use state_machine::*;
+#[cfg(feature = "test-hooks")]
+pub use stepper::*;
 pub use worker::*;
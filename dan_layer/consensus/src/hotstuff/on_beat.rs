@@ -24,6 +24,14 @@ impl OnBeat {
         self.receiver.changed().await.expect("sender can never be dropped")
     }
 
+    /// Returns true if a beat was signalled since the last wait, without waiting
+    #[cfg(feature = "test-hooks")]
+    pub fn try_wait(&mut self) -> bool {
+        let has_changed = self.receiver.has_changed().expect("sender can never be dropped");
+        self.receiver.borrow_and_update();
+        has_changed
+    }
+
     pub fn beat(&self) {
         self.sender.send(()).expect("receiver can never be dropped")
     }
@@ -26,6 +26,14 @@ impl OnForceBeat {
         *self.receiver.borrow()
     }
 
+    /// Returns the forced height if a beat was signalled since the last wait, without waiting
+    #[cfg(feature = "test-hooks")]
+    pub fn try_wait(&mut self) -> Option<Option<NodeHeight>> {
+        let has_changed = self.receiver.has_changed().expect("sender can never be dropped");
+        let forced_height = *self.receiver.borrow_and_update();
+        has_changed.then_some(forced_height)
+    }
+
     pub fn beat(&self, new_height: Option<NodeHeight>) {
         self.sender.send(new_height).expect("receiver can never be dropped")
     }
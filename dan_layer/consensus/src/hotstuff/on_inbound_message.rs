@@ -5,15 +5,25 @@ use std::collections::{BTreeMap, VecDeque};
 
 use log::*;
 use tari_dan_common_types::{Epoch, NodeAddressable, NodeHeight};
+use tari_dan_storage::{consensus_models::EvictedForeignProposal, StateStore};
 
 use crate::{
-    hotstuff::error::HotStuffError,
-    messages::HotstuffMessage,
+    hotstuff::{
+        error::HotStuffError,
+        foreign_proposal_buffer::ForeignProposalBuffer,
+        peer_rate_limiter::PeerRateLimiter,
+    },
+    messages::{ForeignProposalMessage, HotstuffMessage, MAX_MISSING_TRANSACTIONS_PER_REQUEST},
     traits::{hooks::ConsensusHooks, ConsensusSpec, InboundMessaging},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::inbound_messages";
 
+/// The number of transactions per second that a peer may request from us in missing transaction requests
+const MAX_REQUESTED_TRANSACTIONS_PER_PEER_PER_SEC: u64 = 1000;
+/// The number of transactions that a peer may request in a burst of missing transaction requests
+const MAX_REQUESTED_TRANSACTIONS_PER_PEER_BURST: u64 = 2 * MAX_MISSING_TRANSACTIONS_PER_REQUEST as u64;
+
 type IncomingMessageResult<TAddr> = Result<Option<(TAddr, HotstuffMessage)>, HotStuffError>;
 
 pub struct OnInboundMessage<TConsensusSpec: ConsensusSpec> {
@@ -22,9 +32,14 @@ pub struct OnInboundMessage<TConsensusSpec: ConsensusSpec> {
 }
 
 impl<TConsensusSpec: ConsensusSpec> OnInboundMessage<TConsensusSpec> {
-    pub fn new(inbound_messaging: TConsensusSpec::InboundMessaging, hooks: TConsensusSpec::Hooks) -> Self {
+    pub fn new(
+        max_buffered_foreign_proposals: usize,
+        store: TConsensusSpec::StateStore,
+        inbound_messaging: TConsensusSpec::InboundMessaging,
+        hooks: TConsensusSpec::Hooks,
+    ) -> Self {
         Self {
-            message_buffer: MessageBuffer::new(inbound_messaging),
+            message_buffer: MessageBuffer::new(max_buffered_foreign_proposals, store, inbound_messaging, hooks.clone()),
             hooks,
         }
     }
@@ -64,14 +79,33 @@ impl<TConsensusSpec: ConsensusSpec> OnInboundMessage<TConsensusSpec> {
 type EpochAndHeight = (Epoch, NodeHeight);
 pub struct MessageBuffer<TConsensusSpec: ConsensusSpec> {
     buffer: BTreeMap<EpochAndHeight, VecDeque<(TConsensusSpec::Addr, HotstuffMessage)>>,
+    foreign_proposals: ForeignProposalBuffer<TConsensusSpec::Addr>,
+    /// The epoch for which all evicted foreign proposals have been loaded from storage
+    evicted_loaded_epoch: Option<Epoch>,
+    store: TConsensusSpec::StateStore,
     inbound_messaging: TConsensusSpec::InboundMessaging,
+    missing_transaction_requests_limiter: PeerRateLimiter<TConsensusSpec::Addr>,
+    hooks: TConsensusSpec::Hooks,
 }
 
 impl<TConsensusSpec: ConsensusSpec> MessageBuffer<TConsensusSpec> {
-    pub fn new(inbound_messaging: TConsensusSpec::InboundMessaging) -> Self {
+    pub fn new(
+        max_buffered_foreign_proposals: usize,
+        store: TConsensusSpec::StateStore,
+        inbound_messaging: TConsensusSpec::InboundMessaging,
+        hooks: TConsensusSpec::Hooks,
+    ) -> Self {
         Self {
             buffer: BTreeMap::new(),
+            foreign_proposals: ForeignProposalBuffer::new(max_buffered_foreign_proposals),
+            evicted_loaded_epoch: None,
+            store,
             inbound_messaging,
+            missing_transaction_requests_limiter: PeerRateLimiter::new(
+                MAX_REQUESTED_TRANSACTIONS_PER_PEER_PER_SEC,
+                MAX_REQUESTED_TRANSACTIONS_PER_PEER_BURST,
+            ),
+            hooks,
         }
     }
 
@@ -85,6 +119,11 @@ impl<TConsensusSpec: ConsensusSpec> MessageBuffer<TConsensusSpec> {
         // Clear buffer with lower (epoch, heights)
         self.buffer = self.buffer.split_off(&(current_epoch, next_height));
 
+        // Foreign proposals that were buffered until we reached their epoch
+        if let Some((from, msg)) = self.next_ready_foreign_proposal(current_epoch)? {
+            return Ok(Some((from, HotstuffMessage::ForeignProposal(msg))));
+        }
+
         // Check if message is in the buffer
         if let Some(buffer) = self.buffer.get_mut(&(current_epoch, next_height)) {
             if let Some(msg_tuple) = buffer.pop_front() {
@@ -95,14 +134,21 @@ impl<TConsensusSpec: ConsensusSpec> MessageBuffer<TConsensusSpec> {
         while let Some(result) = self.inbound_messaging.next_message().await {
             let (from, msg) = result?;
 
-            // If we receive an FP that is greater than our current epoch, we buffer it
-            if let HotstuffMessage::ForeignProposal(ref m) = msg {
-                if m.justify_qc.epoch() > current_epoch {
-                    self.push_to_buffer(m.justify_qc.epoch(), NodeHeight::zero(), from, msg);
+            if let HotstuffMessage::MissingTransactionsRequest(ref m) = msg {
+                if !self.is_missing_transactions_request_allowed(&from, m.transactions.len()) {
                     continue;
                 }
             }
 
+            // If we receive an FP that is greater than our current epoch, we buffer it
+            let msg = match msg {
+                HotstuffMessage::ForeignProposal(m) if m.justify_qc.epoch() > current_epoch => {
+                    self.buffer_foreign_proposal(from, m)?;
+                    continue;
+                },
+                msg => msg,
+            };
+
             match msg_epoch_and_height(&msg) {
                 // Discard old message
                 Some((e, h)) if e < current_epoch || (e == current_epoch && h < next_height) => {
@@ -150,6 +196,105 @@ impl<TConsensusSpec: ConsensusSpec> MessageBuffer<TConsensusSpec> {
 
     pub fn clear_buffer(&mut self) {
         self.buffer.clear();
+        self.foreign_proposals.clear();
+        self.hooks.on_foreign_proposals_buffered(0);
+    }
+
+    fn is_missing_transactions_request_allowed(
+        &mut self,
+        from: &TConsensusSpec::Addr,
+        num_transactions: usize,
+    ) -> bool {
+        if num_transactions > MAX_MISSING_TRANSACTIONS_PER_REQUEST {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ {} requested {} missing transactions which exceeds the maximum of {}. Discarding request",
+                from,
+                num_transactions,
+                MAX_MISSING_TRANSACTIONS_PER_REQUEST
+            );
+            return false;
+        }
+        if !self
+            .missing_transaction_requests_limiter
+            .try_consume(from.clone(), num_transactions as u64)
+        {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ {} exceeded the missing transactions request rate limit. Discarding request for {} transaction(s)",
+                from,
+                num_transactions
+            );
+            return false;
+        }
+        true
+    }
+
+    fn buffer_foreign_proposal(
+        &mut self,
+        from: TConsensusSpec::Addr,
+        proposal: ForeignProposalMessage,
+    ) -> Result<(), HotStuffError> {
+        if let Some((from, evicted)) = self.foreign_proposals.push(from, proposal) {
+            warn!(
+                target: LOG_TARGET,
+                "⚠️ Foreign proposal buffer is full ({} proposals). Evicting {} from {} to storage",
+                self.foreign_proposals.capacity(),
+                evicted.block,
+                from
+            );
+            self.hooks.on_foreign_proposal_evicted(&evicted.block);
+            let evicted = EvictedForeignProposal::new(from, evicted.into());
+            self.store.with_write_tx(|tx| evicted.insert(tx))?;
+        }
+        self.hooks.on_foreign_proposals_buffered(self.foreign_proposals.len());
+        Ok(())
+    }
+
+    /// Returns the next buffered foreign proposal for the current epoch or before. Proposals that were evicted to
+    /// storage are loaded back into the buffer once per epoch.
+    fn next_ready_foreign_proposal(
+        &mut self,
+        current_epoch: Epoch,
+    ) -> Result<Option<(TConsensusSpec::Addr, ForeignProposalMessage)>, HotStuffError> {
+        if let Some(entry) = self.pop_ready_foreign_proposal(current_epoch) {
+            return Ok(Some(entry));
+        }
+        if self.evicted_loaded_epoch == Some(current_epoch) {
+            return Ok(None);
+        }
+
+        // Load at most as many proposals as the buffer can hold so that none of the ready proposals are evicted again
+        let limit = self.foreign_proposals.capacity();
+        let evicted = self
+            .store
+            .with_write_tx(|tx| EvictedForeignProposal::remove_ready(tx, current_epoch, limit))?;
+        if evicted.len() < limit {
+            self.evicted_loaded_epoch = Some(current_epoch);
+        }
+        if !evicted.is_empty() {
+            info!(
+                target: LOG_TARGET,
+                "Loaded {} evicted foreign proposal(s) for epoch {} or before",
+                evicted.len(),
+                current_epoch
+            );
+        }
+        for proposal in evicted {
+            let (from, proposal) = proposal.into_parts();
+            self.buffer_foreign_proposal(from, proposal.into())?;
+        }
+
+        Ok(self.pop_ready_foreign_proposal(current_epoch))
+    }
+
+    fn pop_ready_foreign_proposal(
+        &mut self,
+        current_epoch: Epoch,
+    ) -> Option<(TConsensusSpec::Addr, ForeignProposalMessage)> {
+        let entry = self.foreign_proposals.pop_ready(current_epoch)?;
+        self.hooks.on_foreign_proposals_buffered(self.foreign_proposals.len());
+        Some(entry)
     }
 
     fn push_to_buffer(&mut self, epoch: Epoch, height: NodeHeight, from: TConsensusSpec::Addr, msg: HotstuffMessage) {
@@ -1,7 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use log::*;
 use tari_dan_common_types::{
@@ -10,25 +13,49 @@ use tari_dan_common_types::{
     NodeHeight,
 };
 use tari_dan_storage::{
-    consensus_models::{Block, BlockId, ForeignParkedProposal, ForeignProposal, TransactionRecord},
+    consensus_models::{
+        Block,
+        BlockId,
+        ForeignParkedProposal,
+        ForeignProposal,
+        Misbehaviour,
+        MisbehaviourEvidence,
+        TransactionRecord,
+    },
     StateStore,
     StateStoreWriteTransaction,
 };
 use tari_epoch_manager::EpochManagerReader;
 use tari_transaction::TransactionId;
-use tokio::sync::broadcast;
+use tokio::{sync::broadcast, time::Instant};
 
 use super::config::HotstuffConfig;
 use crate::{
     block_validations,
-    hotstuff::{error::HotStuffError, CurrentView, HotstuffEvent, ProposalValidationError},
-    messages::{ForeignProposalMessage, HotstuffMessage, MissingTransactionsRequest, ProposalMessage},
+    hotstuff::{
+        error::HotStuffError,
+        proposal_equivocation::record_proposal_equivocation_if_any,
+        CurrentView,
+        HotstuffEvent,
+        ProposalValidationError,
+    },
+    messages::{
+        ForeignProposalMessage,
+        HotstuffMessage,
+        MissingTransactionsRequest,
+        ProposalMessage,
+        MAX_MISSING_TRANSACTIONS_PER_REQUEST,
+    },
     tracing::TraceTimer,
     traits::{ConsensusSpec, OutboundMessaging},
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_message_validate";
 
+/// How long missing transactions are collected before they are requested, so that transactions missing from several
+/// parked blocks are requested together
+const MISSING_TRANSACTIONS_BATCH_DELAY: Duration = Duration::from_millis(50);
+
 pub struct OnMessageValidate<TConsensusSpec: ConsensusSpec> {
     config: HotstuffConfig,
     store: TConsensusSpec::StateStore,
@@ -41,6 +68,8 @@ pub struct OnMessageValidate<TConsensusSpec: ConsensusSpec> {
     /// Keep track of max 16 in-flight requests
     active_missing_transaction_requests: SimpleFixedArray<u32, 16>,
     current_request_id: u32,
+    pending_missing_transactions: HashMap<(TConsensusSpec::Addr, Epoch), PendingMissingTransactions>,
+    missing_transactions_flush_deadline: Option<Instant>,
 }
 
 impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
@@ -65,6 +94,8 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
             tx_events,
             active_missing_transaction_requests: SimpleFixedArray::new(),
             current_request_id: 0,
+            pending_missing_transactions: HashMap::new(),
+            missing_transactions_flush_deadline: None,
         }
     }
 
@@ -90,7 +121,7 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
                     warn!(target: LOG_TARGET, "❓Received missing transactions (req_id = {}) from {} that we did not request. Discarding message", msg.request_id, from);
                     return Ok(MessageValidationResult::Discard);
                 }
-                if msg.transactions.len() > 1000 {
+                if msg.transactions.len() > MAX_MISSING_TRANSACTIONS_PER_REQUEST {
                     warn!(target: LOG_TARGET, "⚠️Peer sent more than the maximum amount of transactions. Discarding message");
                     return Ok(MessageValidationResult::Discard);
                 }
@@ -103,26 +134,56 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         }
     }
 
-    pub async fn request_missing_transactions(
+    /// Queues a request for the transactions that a parked block is missing. Queued transactions are requested from
+    /// each peer in batches once the deadline returned by `missing_transactions_flush_deadline` is reached.
+    pub fn queue_missing_transactions_request(
         &mut self,
         to: TConsensusSpec::Addr,
         block_id: BlockId,
         epoch: Epoch,
         missing_txs: HashSet<TransactionId>,
-    ) -> Result<(), HotStuffError> {
-        let request_id = self.next_request_id();
-        self.active_missing_transaction_requests.insert(request_id);
-        self.outbound_messaging
-            .send(
-                to,
-                HotstuffMessage::MissingTransactionsRequest(MissingTransactionsRequest {
-                    request_id,
-                    block_id,
-                    epoch,
-                    transactions: missing_txs,
-                }),
-            )
-            .await?;
+    ) {
+        let pending = self.pending_missing_transactions.entry((to, epoch)).or_default();
+        pending.block_ids.push(block_id);
+        pending.transactions.extend(missing_txs);
+        self.missing_transactions_flush_deadline
+            .get_or_insert_with(|| Instant::now() + MISSING_TRANSACTIONS_BATCH_DELAY);
+    }
+
+    pub fn missing_transactions_flush_deadline(&self) -> Option<Instant> {
+        self.missing_transactions_flush_deadline
+    }
+
+    /// Sends requests for all queued missing transactions
+    pub async fn flush_missing_transactions_requests(&mut self) -> Result<(), HotStuffError> {
+        self.missing_transactions_flush_deadline = None;
+        let pending = std::mem::take(&mut self.pending_missing_transactions);
+        for ((to, epoch), PendingMissingTransactions { block_ids, transactions }) in pending {
+            let transactions = transactions.into_iter().collect::<Vec<_>>();
+            for chunk in transactions.chunks(MAX_MISSING_TRANSACTIONS_PER_REQUEST) {
+                let request_id = self.next_request_id();
+                self.active_missing_transaction_requests.insert(request_id);
+                debug!(
+                    target: LOG_TARGET,
+                    "Requesting {} missing transaction(s) for {} block(s) from {} (req_id = {})",
+                    chunk.len(),
+                    block_ids.len(),
+                    to,
+                    request_id
+                );
+                self.outbound_messaging
+                    .send(
+                        to.clone(),
+                        HotstuffMessage::MissingTransactionsRequest(MissingTransactionsRequest {
+                            request_id,
+                            epoch,
+                            block_ids: block_ids.clone(),
+                            transactions: chunk.iter().copied().collect(),
+                        }),
+                    )
+                    .await?;
+            }
+        }
         Ok(())
     }
 
@@ -160,6 +221,7 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         }
 
         if let Err(err) = self.check_local_proposal(&proposal.block, local_committee, local_committee_info) {
+            self.record_invalid_quorum_certificate_if_any(&proposal.block, &err)?;
             return Ok(MessageValidationResult::Invalid {
                 from,
                 message: HotstuffMessage::Proposal(proposal),
@@ -167,6 +229,19 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
             });
         }
 
+        // The proposal is validly signed by the leader, so check whether the leader sent a different proposal for the
+        // same height
+        if let Some(atom) = self
+            .store
+            .with_write_tx(|tx| record_proposal_equivocation_if_any(tx, proposal.block.header()))?
+        {
+            let _ignore = self.tx_events.send(HotstuffEvent::ProposalEquivocationDetected {
+                epoch: atom.epoch(),
+                height: atom.height(),
+                proposed_by: atom.proposed_by().clone(),
+            });
+        }
+
         self.handle_missing_transactions_local_block(from, local_committee_info, proposal)
     }
 
@@ -223,6 +298,31 @@ impl<TConsensusSpec: ConsensusSpec> OnMessageValidate<TConsensusSpec> {
         )
     }
 
+    /// The leader and block signature are checked before the justify QC, so a proposal that fails QC validation was
+    /// signed by the leader and is evidence of misbehaviour.
+    fn record_invalid_quorum_certificate_if_any(
+        &self,
+        block: &Block,
+        err: &HotStuffError,
+    ) -> Result<(), HotStuffError> {
+        let HotStuffError::ProposalValidationError(err) = err else {
+            return Ok(());
+        };
+        if !err.is_invalid_quorum_certificate() {
+            return Ok(());
+        }
+
+        let misbehaviour = Misbehaviour::InvalidQuorumCertificate {
+            block_header: block.header().clone(),
+            justify: block.justify().clone(),
+            reason: err.to_string(),
+        };
+        warn!(target: LOG_TARGET, "🎭 Detected invalid QC: {}", misbehaviour);
+        self.store
+            .with_write_tx(|tx| MisbehaviourEvidence::new(misbehaviour).insert(tx))?;
+        Ok(())
+    }
+
     fn check_foreign_proposal(
         &self,
         block: &Block,
@@ -449,6 +549,12 @@ pub enum MessageValidationResult<TAddr> {
 }
 
 #[derive(Debug, Clone)]
+#[derive(Debug, Default)]
+struct PendingMissingTransactions {
+    block_ids: Vec<BlockId>,
+    transactions: HashSet<TransactionId>,
+}
+
 struct SimpleFixedArray<T, const SZ: usize> {
     elems: [Option<T>; SZ],
     ptr: usize,
@@ -9,7 +9,12 @@ use tari_dan_storage::{
 };
 
 use crate::{
-    hotstuff::{get_next_block_height_and_leader, pacemaker_handle::PaceMakerHandle, HotStuffError},
+    hotstuff::{
+        get_next_block_height_and_leader,
+        message_rebroadcaster::MessageRebroadcaster,
+        pacemaker_handle::PaceMakerHandle,
+        HotStuffError,
+    },
     messages::{HotstuffMessage, NewViewMessage, VoteMessage},
     traits::{ConsensusSpec, OutboundMessaging},
 };
@@ -21,6 +26,7 @@ pub struct OnNextSyncViewHandler<TConsensusSpec: ConsensusSpec> {
     outbound_messaging: TConsensusSpec::OutboundMessaging,
     leader_strategy: TConsensusSpec::LeaderStrategy,
     pacemaker: PaceMakerHandle,
+    message_rebroadcaster: MessageRebroadcaster<TConsensusSpec::Addr>,
 }
 
 impl<TConsensusSpec: ConsensusSpec> OnNextSyncViewHandler<TConsensusSpec> {
@@ -29,12 +35,14 @@ impl<TConsensusSpec: ConsensusSpec> OnNextSyncViewHandler<TConsensusSpec> {
         outbound_messaging: TConsensusSpec::OutboundMessaging,
         leader_strategy: TConsensusSpec::LeaderStrategy,
         pacemaker: PaceMakerHandle,
+        message_rebroadcaster: MessageRebroadcaster<TConsensusSpec::Addr>,
     ) -> Self {
         Self {
             store,
             outbound_messaging,
             leader_strategy,
             pacemaker,
+            message_rebroadcaster,
         }
     }
 
@@ -83,9 +91,11 @@ impl<TConsensusSpec: ConsensusSpec> OnNextSyncViewHandler<TConsensusSpec> {
             last_vote,
         };
 
-        self.outbound_messaging
-            .send(next_leader.clone(), HotstuffMessage::NewView(message))
-            .await?;
+        let message = HotstuffMessage::NewView(message);
+        self.outbound_messaging.send(next_leader.clone(), message.clone()).await?;
+        // The next leader acknowledges the NEWVIEW by proposing at the new height
+        self.message_rebroadcaster
+            .track(next_leader.clone(), message, epoch, new_height);
 
         Ok(())
     }
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeSet, HashMap},
     fmt::Display,
     num::NonZeroU64,
 };
@@ -14,7 +14,6 @@ use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
     option::DisplayContainer,
     optional::Optional,
-    shard::Shard,
     Epoch,
     ExtraData,
     NodeHeight,
@@ -39,6 +38,7 @@ use tari_dan_storage::{
         LeafBlock,
         LockedBlock,
         PendingShardStateTreeDiff,
+        ProposalEquivocation,
         QuorumCertificate,
         SubstateChange,
         SubstateRequirementLockIntent,
@@ -63,6 +63,7 @@ use crate::{
         calculate_state_merkle_root,
         error::HotStuffError,
         filter_diff_for_committee,
+        get_foreign_proposal_shard_groups,
         substate_store::PendingSubstateStore,
         transaction_manager::{
             ConsensusTransactionManager,
@@ -75,7 +76,13 @@ use crate::{
     },
     messages::{HotstuffMessage, ProposalMessage},
     tracing::TraceTimer,
-    traits::{ConsensusSpec, OutboundMessaging, ValidatorSignatureService, WriteableSubstateStore},
+    traits::{
+        hooks::ConsensusHooks,
+        ConsensusSpec,
+        OutboundMessaging,
+        ValidatorSignatureService,
+        WriteableSubstateStore,
+    },
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_local_propose";
@@ -96,11 +103,13 @@ pub struct OnPropose<TConsensusSpec: ConsensusSpec> {
     transaction_manager: ConsensusTransactionManager<TConsensusSpec::TransactionExecutor, TConsensusSpec::StateStore>,
     signing_service: TConsensusSpec::SignatureService,
     outbound_messaging: TConsensusSpec::OutboundMessaging,
+    hooks: TConsensusSpec::Hooks,
 }
 
 impl<TConsensusSpec> OnPropose<TConsensusSpec>
 where TConsensusSpec: ConsensusSpec
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         config: HotstuffConfig,
         store: TConsensusSpec::StateStore,
@@ -112,6 +121,7 @@ where TConsensusSpec: ConsensusSpec
         >,
         signing_service: TConsensusSpec::SignatureService,
         outbound_messaging: TConsensusSpec::OutboundMessaging,
+        hooks: TConsensusSpec::Hooks,
     ) -> Self {
         Self {
             config,
@@ -121,6 +131,7 @@ where TConsensusSpec: ConsensusSpec
             transaction_manager,
             signing_service,
             outbound_messaging,
+            hooks,
         }
     }
 
@@ -235,6 +246,7 @@ where TConsensusSpec: ConsensusSpec
     ) -> Result<(), HotStuffError> {
         let epoch = next_block.epoch();
         let leaf_block = next_block.as_leaf_block();
+        self.hooks.on_block_proposed(&next_block);
         let msg = HotstuffMessage::Proposal(ProposalMessage {
             block: next_block,
             foreign_proposals,
@@ -261,7 +273,11 @@ where TConsensusSpec: ConsensusSpec
                 committee.len(), local_committee_info.num_shard_group_members(), leaf_block,
             );
 
-            if let Err(err) = self.outbound_messaging.multicast(committee.into_addresses(), msg).await {
+            if let Err(err) = self
+                .outbound_messaging
+                .multicast(local_committee_info.shard_group(), committee.into_addresses(), msg)
+                .await
+            {
                 warn!(
                     target: LOG_TARGET,
                     "Failed to multicast proposal to local committee: {}",
@@ -450,6 +466,12 @@ where TConsensusSpec: ConsensusSpec
                             .evict_nodes
                             .into_iter()
                             .map(|public_key| Command::EvictNode(EvictNodeAtom { public_key })),
+                    )
+                    .chain(
+                        batch
+                            .proposal_equivocations
+                            .iter()
+                            .map(|equivocation| Command::ProposalEquivocation(equivocation.to_atom())),
                     ),
             )
         };
@@ -489,6 +511,13 @@ where TConsensusSpec: ConsensusSpec
             }
         }
 
+        self.execute_new_transactions_in_parallel(
+            tx,
+            start_of_chain_block.epoch(),
+            local_committee_info,
+            batch.transactions.iter().filter(|t| t.current_stage().is_new()).map(|t| t.transaction_id()),
+        )?;
+
         // batch is empty for is_empty, is_epoch_end and is_epoch_start blocks
         let mut substate_store = PendingSubstateStore::new(
             tx,
@@ -555,12 +584,15 @@ where TConsensusSpec: ConsensusSpec
         )?;
         timer.done();
 
-        let non_local_shards = get_non_local_shards(substate_store.diff(), local_committee_info);
-
-        let foreign_counters = ForeignSendCounters::get_or_default(tx, parent_block.block_id())?;
-        let foreign_indexes = non_local_shards
+        // Each foreign shard that is sent this block as a foreign proposal is given the next foreign index, so that
+        // foreign shards can detect proposals that they missed. Counters are carried over from the justified block.
+        let foreign_shard_groups =
+            get_foreign_proposal_shard_groups(&commands, local_committee_info.shard_group(), local_committee_info);
+        let foreign_counters = ForeignSendCounters::get_or_default(tx, high_qc_certificate.block_id())?;
+        let foreign_indexes = foreign_shard_groups
             .iter()
-            .map(|shard| (*shard, foreign_counters.get_count(*shard) + 1))
+            .flat_map(|shard_group| shard_group.shard_iter())
+            .map(|shard| (shard, foreign_counters.get_count(shard) + 1))
             .collect();
 
         let mut header = BlockHeader::create(
@@ -638,6 +670,21 @@ where TConsensusSpec: ConsensusSpec
 
         remaining_block_size = subtract_block_size_checked(remaining_block_size, burnt_utxos.len());
 
+        let proposal_equivocations = remaining_block_size
+            .map(|size| ProposalEquivocation::get_all_unproposed(tx, start_of_chain_block.block_id(), size))
+            .transpose()?
+            .unwrap_or_default();
+
+        if !proposal_equivocations.is_empty() {
+            debug!(
+                target: LOG_TARGET,
+                "🌿 Found {} proposal equivocation(s) for next block",
+                proposal_equivocations.len()
+            );
+        }
+
+        remaining_block_size = subtract_block_size_checked(remaining_block_size, proposal_equivocations.len());
+
         let evict_nodes = remaining_block_size
             .map(|max| {
                 let num_evicted =
@@ -645,12 +692,31 @@ where TConsensusSpec: ConsensusSpec
                 let max_allowed_to_evict = u64::from(local_committee_info.max_failures())
                     .saturating_sub(num_evicted)
                     .min(max as u64);
-                ValidatorConsensusStats::get_nodes_to_evict(
+
+                // Leaders with committed equivocation evidence are evicted before nodes that missed proposals
+                let mut evict_nodes = Vec::new();
+                for public_key in ProposalEquivocation::get_committed_proposers(tx, start_of_chain_block.epoch())? {
+                    if evict_nodes.len() as u64 >= max_allowed_to_evict {
+                        break;
+                    }
+                    if !ValidatorConsensusStats::is_node_evicted(tx, start_of_chain_block.block_id(), &public_key)? {
+                        evict_nodes.push(public_key);
+                    }
+                }
+
+                let missed_proposal_nodes = ValidatorConsensusStats::get_nodes_to_evict(
                     tx,
                     start_of_chain_block.block_id(),
                     self.config.consensus_constants.missed_proposal_evict_threshold,
-                    max_allowed_to_evict,
-                )
+                    max_allowed_to_evict.saturating_sub(evict_nodes.len() as u64),
+                )?;
+                for public_key in missed_proposal_nodes {
+                    if !evict_nodes.contains(&public_key) {
+                        evict_nodes.push(public_key);
+                    }
+                }
+
+                Ok::<_, HotStuffError>(evict_nodes)
             })
             .transpose()?
             .unwrap_or_default();
@@ -682,6 +748,7 @@ where TConsensusSpec: ConsensusSpec
             burnt_utxos,
             transactions,
             evict_nodes,
+            proposal_equivocations,
         })
     }
 
@@ -958,6 +1025,30 @@ where TConsensusSpec: ConsensusSpec
         Ok(atom)
     }
 
+    /// Executes the new transactions that do not conflict with each other in parallel, so that the results are
+    /// available when the transactions are prepared.
+    fn execute_new_transactions_in_parallel<'a, I: IntoIterator<Item = &'a TransactionId>>(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+        epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        transaction_ids: I,
+    ) -> Result<(), HotStuffError> {
+        let transactions = transaction_ids
+            .into_iter()
+            .map(|id| TransactionRecord::get(tx, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.transaction_manager
+            .execute_in_parallel(
+                tx,
+                epoch,
+                local_committee_info,
+                transactions,
+                self.config.max_parallel_executions,
+            )
+            .map_err(|e| HotStuffError::TransactionExecutorError(e.to_string()))
+    }
+
     fn execute_transaction(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
@@ -995,34 +1086,25 @@ where TConsensusSpec: ConsensusSpec
     }
 }
 
-pub fn get_non_local_shards(diff: &[SubstateChange], local_committee_info: &CommitteeInfo) -> HashSet<Shard> {
-    diff.iter()
-        .map(|ch| {
-            ch.versioned_substate_id()
-                .to_substate_address()
-                .to_shard(local_committee_info.num_preshards())
-        })
-        .filter(|shard| local_committee_info.shard_group().contains(shard))
-        .collect()
-}
-
 #[derive(Default)]
 struct ProposalBatch {
     pub foreign_proposals: Vec<ForeignProposal>,
     pub burnt_utxos: Vec<BurntUtxo>,
     pub transactions: Vec<TransactionPoolRecord>,
     pub evict_nodes: Vec<PublicKey>,
+    pub proposal_equivocations: Vec<ProposalEquivocation>,
 }
 
 impl Display for ProposalBatch {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{} transaction(s), {} foreign proposal(s), {} UTXOs, {} evict",
+            "{} transaction(s), {} foreign proposal(s), {} UTXOs, {} evict, {} equivocation(s)",
             self.transactions.len(),
             self.foreign_proposals.len(),
             self.burnt_utxos.len(),
-            self.evict_nodes.len()
+            self.evict_nodes.len(),
+            self.proposal_equivocations.len()
         )
     }
 }
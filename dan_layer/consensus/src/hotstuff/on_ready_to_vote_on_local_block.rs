@@ -31,10 +31,13 @@ use tari_dan_storage::{
         MintConfidentialOutputAtom,
         NoVoteReason,
         PendingShardStateTreeDiff,
+        ProposalEquivocation,
+        ProposalEquivocationAtom,
         QuorumDecision,
         SubstateChange,
         SubstateRecord,
         TransactionAtom,
+        TransactionEvent,
         TransactionPool,
         TransactionPoolRecord,
         TransactionPoolStage,
@@ -143,6 +146,7 @@ where TConsensusSpec: ConsensusSpec
         let mut finalized_transactions = Vec::new();
         let mut end_of_epoch = None;
         let mut maybe_high_qc = None;
+        let mut committed_blocks = Vec::new();
         let mut committed_blocks_with_evictions = Vec::new();
 
         if change_set.is_accept() {
@@ -157,6 +161,7 @@ where TConsensusSpec: ConsensusSpec
                 },
                 |tx, last_exec, commit_block| {
                     let committed = self.on_commit(tx, last_exec, &commit_block, local_committee_info)?;
+                    committed_blocks.push(commit_block.as_leaf_block());
                     if commit_block.is_epoch_end() {
                         end_of_epoch = Some(commit_block.epoch());
                     }
@@ -195,6 +200,7 @@ where TConsensusSpec: ConsensusSpec
             finalized_transactions,
             end_of_epoch,
             high_qc,
+            committed_blocks,
             committed_blocks_with_evictions,
         })
     }
@@ -311,6 +317,26 @@ where TConsensusSpec: ConsensusSpec
         let mut total_leader_fee = 0;
         let locked_block = LockedBlock::get(tx, block.epoch())?;
 
+        // Execute the transactions that will be prepared in this block and do not conflict with each other in parallel
+        let new_transactions = block
+            .commands()
+            .iter()
+            .filter_map(|cmd| match cmd {
+                Command::LocalOnly(atom) | Command::Prepare(atom) => Some(atom.id()),
+                _ => None,
+            })
+            .map(|id| TransactionRecord::get(tx, id))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.transaction_manager
+            .execute_in_parallel(
+                tx,
+                block.epoch(),
+                local_committee_info,
+                new_transactions,
+                self.config.max_parallel_executions,
+            )
+            .map_err(|e| HotStuffError::TransactionExecutorError(e.to_string()))?;
+
         for cmd in block.commands() {
             match cmd {
                 Command::LocalOnly(atom) => {
@@ -469,7 +495,12 @@ where TConsensusSpec: ConsensusSpec
                     }
 
                     let stats = ValidatorConsensusStats::get_by_public_key(tx, block.epoch(), &atom.public_key)?;
-                    if stats.missed_proposals < self.config.consensus_constants.missed_proposal_evict_threshold {
+                    // A leader with committed equivocation evidence is evicted regardless of its missed proposals
+                    let has_equivocated =
+                        ProposalEquivocation::is_committed_for_proposer(tx, block.epoch(), &atom.public_key)?;
+                    if !has_equivocated &&
+                        stats.missed_proposals < self.config.consensus_constants.missed_proposal_evict_threshold
+                    {
                         warn!(
                             target: LOG_TARGET,
                             "❌ NO VOTE: {} (actual missed count: {}, threshold: {})", NoVoteReason::ShouldNotEvictNode, stats.missed_proposals, self.config.consensus_constants.missed_proposal_evict_threshold
@@ -481,12 +512,21 @@ where TConsensusSpec: ConsensusSpec
 
                     info!(
                         target: LOG_TARGET,
-                        "💀 EVICTING node: {} with missed count {}",
+                        "💀 EVICTING node: {} with missed count {} (equivocated: {})",
                         atom.public_key,
-                        stats.missed_proposals
+                        stats.missed_proposals,
+                        has_equivocated
                     );
                     proposed_block_change_set.add_evict_node(atom.public_key.clone());
                 },
+                Command::ProposalEquivocation(atom) => {
+                    if let Some(reason) =
+                        self.evaluate_proposal_equivocation_command(tx, block, atom, proposed_block_change_set)?
+                    {
+                        proposed_block_change_set.no_vote(reason);
+                        return Ok(());
+                    }
+                },
                 Command::EndEpoch => {
                     if !can_propose_epoch_end {
                         warn!(
@@ -601,6 +641,10 @@ where TConsensusSpec: ConsensusSpec
             }));
         }
 
+        if let Some(reason) = self.check_transaction_is_active(tx, block, &tx_rec)? {
+            return Ok(Some(reason));
+        }
+
         // TODO(perf): proposer shouldn't have to do this twice, esp. executing the transaction and locking
         let prepared = self
             .transaction_manager
@@ -782,6 +826,10 @@ where TConsensusSpec: ConsensusSpec
             }));
         }
 
+        if let Some(reason) = self.check_transaction_is_active(tx, block, &tx_rec)? {
+            return Ok(Some(reason));
+        }
+
         let prepared = self
             .transaction_manager
             .prepare(
@@ -1638,6 +1686,86 @@ where TConsensusSpec: ConsensusSpec
         Ok(None)
     }
 
+    fn evaluate_proposal_equivocation_command(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+        block: &Block,
+        atom: &ProposalEquivocationAtom,
+        proposed_block_change_set: &mut ProposedBlockChangeSet,
+    ) -> Result<Option<NoVoteReason>, HotStuffError> {
+        if let Err(err) = atom.validate() {
+            warn!(
+                target: LOG_TARGET,
+                "❌ NO VOTE: Invalid proposal equivocation evidence {}: {}",
+                atom,
+                err
+            );
+            return Ok(Some(NoVoteReason::InvalidProposalEquivocationEvidence));
+        }
+
+        // Evidence must be for an earlier height in the current epoch of the local shard group
+        if atom.epoch() != block.epoch() || atom.shard_group() != block.shard_group() || atom.height() >= block.height()
+        {
+            warn!(
+                target: LOG_TARGET,
+                "❌ NO VOTE: Proposal equivocation evidence {} ({}) is not applicable to block {}",
+                atom,
+                atom.shard_group(),
+                block,
+            );
+            return Ok(Some(NoVoteReason::InvalidProposalEquivocationEvidence));
+        }
+
+        if atom.is_committed(tx)? {
+            warn!(
+                target: LOG_TARGET,
+                "❌ NO VOTE: Proposal equivocation evidence {} has already been committed",
+                atom,
+            );
+            return Ok(Some(NoVoteReason::ProposalEquivocationAlreadyCommitted));
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "🎭 Leader {} equivocated at height {} in {}",
+            atom.proposed_by(),
+            atom.height(),
+            atom.epoch(),
+        );
+        proposed_block_change_set.set_proposal_equivocation_proposed_in(atom.clone());
+
+        Ok(None)
+    }
+
+    /// Transactions that are only valid from a future epoch are held back by the mempool. A leader that proposes one
+    /// early is not voted for.
+    fn check_transaction_is_active(
+        &self,
+        tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
+        block: &Block,
+        tx_rec: &TransactionPoolRecord,
+    ) -> Result<Option<NoVoteReason>, HotStuffError> {
+        let transaction = tx_rec.get_transaction(tx)?;
+        let Some(min_epoch) = transaction.transaction().min_epoch() else {
+            return Ok(None);
+        };
+        if min_epoch <= block.epoch() {
+            return Ok(None);
+        }
+
+        warn!(
+            target: LOG_TARGET,
+            "❌ Transaction {} in block {} is not valid until {}",
+            tx_rec.transaction_id(),
+            block,
+            min_epoch,
+        );
+        Ok(Some(NoVoteReason::TransactionNotYetActive {
+            min_epoch,
+            block_epoch: block.epoch(),
+        }))
+    }
+
     fn execute_transaction(
         &self,
         tx: &<TConsensusSpec::StateStore as StateStore>::ReadTransaction<'_>,
@@ -1755,6 +1883,10 @@ where TConsensusSpec: ConsensusSpec
             atom.mark_as_committed_in_epoch(tx, block.epoch())?;
         }
 
+        for atom in block.all_proposal_equivocations() {
+            atom.mark_as_committed(tx, block.id())?;
+        }
+
         // NOTE: this must happen before we commit the substate diff because the state transitions use this version
         let pending = block.remove_pending_tree_diff_and_return(tx)?;
         let mut state_tree = ShardedStateTree::new(tx);
@@ -1773,6 +1905,15 @@ where TConsensusSpec: ConsensusSpec
             SubstateRecord::unlock_all(tx, finalized_transactions.iter().map(|t| t.transaction_id()).peekable())?;
             TransactionRecord::finalize_all(tx, *block.id(), &finalized_transactions)?;
 
+            // Index the events emitted by committed transactions
+            let (finalized_records, _) =
+                TransactionRecord::get_any(&**tx, finalized_transactions.iter().map(|t| t.transaction_id()))?;
+            let events = finalized_records
+                .iter()
+                .flat_map(|rec| TransactionEvent::from_finalized_transaction(*block.id(), rec))
+                .collect::<Vec<_>>();
+            TransactionEvent::insert_all(tx, &events)?;
+
             debug!(
                 target: LOG_TARGET,
                 "✅ {} transactions finalized",
@@ -1,11 +1,14 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::ops::RangeInclusive;
+
 use log::*;
-use tari_dan_common_types::{committee::CommitteeInfo, Epoch, ShardGroup};
+use tari_dan_common_types::{committee::CommitteeInfo, Epoch, NodeHeight, ShardGroup};
 use tari_dan_storage::{
-    consensus_models::{Block, ForeignProposal, ForeignReceiveCounters, QuorumCertificate},
+    consensus_models::{Block, BlockId, ForeignProposal, ForeignReceiveCounters, ForeignReceiveGap, QuorumCertificate},
     StateStore,
+    StateStoreReadTransaction,
 };
 use tari_epoch_manager::EpochManagerReader;
 
@@ -22,6 +25,10 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_foreign_proposal";
+/// The maximum number of foreign proposals that are sent in reply to a request for a range of foreign indexes
+const MAX_FOREIGN_PROPOSALS_PER_REQUEST: usize = 50;
+/// The number of block heights that are loaded at a time when searching for blocks by foreign index
+const FOREIGN_INDEX_SEARCH_PAGE_SIZE: u64 = 100;
 
 #[derive(Clone)]
 pub struct OnReceiveForeignProposalHandler<TConsensusSpec: ConsensusSpec> {
@@ -70,18 +77,64 @@ where TConsensusSpec: ConsensusSpec
             .epoch_manager
             .get_committee_info_by_validator_public_key(proposal.block.epoch(), proposal.block.proposed_by().clone())
             .await?;
-        self.store
+        let maybe_gap = self
+            .store
             .with_write_tx(|tx| self.validate_and_save(tx, proposal, local_committee_info, &foreign_committee_info))?;
+        if let Some(gap) = maybe_gap {
+            self.request_missing_foreign_proposals(&gap, local_committee_info)
+                .await?;
+        }
 
         // Foreign proposals to propose
         self.pacemaker.beat();
         Ok(())
     }
 
+    /// Requests the foreign proposals in the gap from a member of the foreign committee
+    pub async fn request_missing_foreign_proposals(
+        &mut self,
+        gap: &ForeignReceiveGap,
+        local_committee_info: &CommitteeInfo,
+    ) -> Result<(), HotStuffError> {
+        let f = local_committee_info.max_failures() as usize;
+        let committee = self
+            .epoch_manager
+            .get_committee_by_shard_group(gap.epoch, gap.foreign_shard_group, Some(f + 1))
+            .await?;
+
+        let Some((selected, _)) = committee.shuffled().next() else {
+            warn!(
+                target: LOG_TARGET,
+                "FOREIGN PROPOSAL: No validator selected for the shard group {}",
+                gap.foreign_shard_group,
+            );
+            return Ok(());
+        };
+
+        info!(
+            target: LOG_TARGET,
+            "🌐 REQUEST missing foreign proposals ({}) from {}",
+            gap,
+            selected,
+        );
+        self.outbound_messaging
+            .send(
+                selected.clone(),
+                HotstuffMessage::ForeignProposalRequest(ForeignProposalRequestMessage::ByForeignIndexRange {
+                    for_shard_group: local_committee_info.shard_group(),
+                    epoch: gap.epoch,
+                    from_index: gap.from_index,
+                    to_index: gap.to_index,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn handle_notification_received(
         &mut self,
         from: TConsensusSpec::Addr,
-        current_epoch: Epoch,
         message: ForeignProposalNotificationMessage,
         local_committee_info: &CommitteeInfo,
     ) -> Result<(), HotStuffError> {
@@ -118,10 +171,11 @@ where TConsensusSpec: ConsensusSpec
             return Ok(());
         }
 
+        // The proposal may be from the previous epoch, so request it from the committee that proposed it
         let f = local_committee_info.max_failures() as usize;
         let committee = self
             .epoch_manager
-            .get_committee_by_shard_group(current_epoch, foreign_committee_info.shard_group(), Some(f + 1))
+            .get_committee_by_shard_group(message.epoch, foreign_committee_info.shard_group(), Some(f + 1))
             .await?;
 
         let Some((selected, _)) = committee.shuffled().next() else {
@@ -165,53 +219,37 @@ where TConsensusSpec: ConsensusSpec
                 for_shard_group,
                 ..
             } => {
-                let (block, justify_qc, mut block_pledge) = self.store.with_read_tx(|tx| {
-                    let block = Block::get(tx, &block_id)?;
-                    let justify_qc = QuorumCertificate::get_by_block_id(tx, &block_id)?;
-                    let block_pledge = block.get_block_pledge(tx)?;
-                    Ok::<_, HotStuffError>((block, justify_qc, block_pledge))
+                self.send_foreign_proposal(from, &block_id, for_shard_group, local_committee_info)
+                    .await?;
+            },
+            ForeignProposalRequestMessage::ByForeignIndexRange {
+                for_shard_group,
+                epoch,
+                from_index,
+                to_index,
+            } => {
+                let block_ids = self.store.with_read_tx(|tx| {
+                    find_blocks_by_foreign_index(
+                        tx,
+                        epoch,
+                        local_committee_info.shard_group(),
+                        for_shard_group,
+                        from_index..=to_index,
+                    )
                 })?;
-
                 info!(
                     target: LOG_TARGET,
-                    "🌐 REPLY foreign proposal {} to {}. justify: {} ({}), parent: {}",
-                    block,
+                    "🌐 REPLY {} foreign proposal(s) with foreign indexes {}-{} in {} to {}",
+                    block_ids.len(),
+                    from_index,
+                    to_index,
+                    epoch,
                     for_shard_group,
-                    justify_qc.block_id(),
-                    justify_qc.block_height(),
-                    block.parent()
                 );
-
-                let applicable_transactions = block
-                    .commands()
-                    .iter()
-                    .filter_map(|c| {
-                        c.local_prepare()
-                            // No need to broadcast LocalPrepare if the committee is output only
-                            .filter(|atom| !atom.evidence.is_committee_output_only(local_committee_info))
-                            .or_else(|| c.local_accept())
-                    })
-                    .filter(|atom| {
-                        atom.evidence
-                            .shard_groups_iter()
-                            .any(|shard_group| *shard_group == for_shard_group)
-                    })
-                    .map(|atom| atom.id)
-                    .collect();
-
-                // Only send the pledges for the involved shard group that requested them
-                block_pledge.retain_transactions(&applicable_transactions);
-
-                self.outbound_messaging
-                    .send(
-                        from,
-                        HotstuffMessage::ForeignProposal(ForeignProposalMessage {
-                            block,
-                            justify_qc,
-                            block_pledge,
-                        }),
-                    )
-                    .await?;
+                for block_id in block_ids {
+                    self.send_foreign_proposal(from.clone(), &block_id, for_shard_group, local_committee_info)
+                        .await?;
+                }
             },
             ForeignProposalRequestMessage::ByTransactionId { .. } => {
                 error!(
@@ -224,13 +262,72 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 
+    async fn send_foreign_proposal(
+        &mut self,
+        to: TConsensusSpec::Addr,
+        block_id: &BlockId,
+        for_shard_group: ShardGroup,
+        local_committee_info: &CommitteeInfo,
+    ) -> Result<(), HotStuffError> {
+        let (block, justify_qc, mut block_pledge) = self.store.with_read_tx(|tx| {
+            let block = Block::get(tx, block_id)?;
+            let justify_qc = QuorumCertificate::get_by_block_id(tx, block_id)?;
+            let block_pledge = block.get_block_pledge(tx)?;
+            Ok::<_, HotStuffError>((block, justify_qc, block_pledge))
+        })?;
+
+        info!(
+            target: LOG_TARGET,
+            "🌐 REPLY foreign proposal {} to {}. justify: {} ({}), parent: {}",
+            block,
+            for_shard_group,
+            justify_qc.block_id(),
+            justify_qc.block_height(),
+            block.parent()
+        );
+
+        let applicable_transactions = block
+            .commands()
+            .iter()
+            .filter_map(|c| {
+                c.local_prepare()
+                    // No need to broadcast LocalPrepare if the committee is output only
+                    .filter(|atom| !atom.evidence.is_committee_output_only(local_committee_info))
+                    .or_else(|| c.local_accept())
+            })
+            .filter(|atom| {
+                atom.evidence
+                    .shard_groups_iter()
+                    .any(|shard_group| *shard_group == for_shard_group)
+            })
+            .map(|atom| atom.id)
+            .collect();
+
+        // Only send the pledges for the involved shard group that requested them
+        block_pledge.retain_transactions(&applicable_transactions);
+
+        self.outbound_messaging
+            .send(
+                to,
+                HotstuffMessage::ForeignProposal(ForeignProposalMessage {
+                    block,
+                    justify_qc,
+                    block_pledge,
+                }),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Validates and saves the foreign proposal. Returns the gap in foreign indexes that the proposal revealed, if any.
     pub fn validate_and_save(
         &self,
         tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
         proposal: ForeignProposal,
         local_committee_info: &CommitteeInfo,
         foreign_committee_info: &CommitteeInfo,
-    ) -> Result<(), HotStuffError> {
+    ) -> Result<Option<ForeignReceiveGap>, HotStuffError> {
         // TODO: validate justify_qc
         let mut foreign_receive_counter = ForeignReceiveCounters::get_or_default(&**tx)?;
 
@@ -246,7 +343,7 @@ where TConsensusSpec: ConsensusSpec
                 err
             );
             // Invalid blocks should not cause the state machine to transition to Error
-            return Ok(());
+            return Ok(None);
         }
 
         foreign_receive_counter.increment_group(foreign_committee_info.shard_group());
@@ -259,7 +356,7 @@ where TConsensusSpec: ConsensusSpec
                 proposal.justify_qc().block_id(),
                 proposal.block().id(),
             );
-            return Ok(());
+            return Ok(None);
         }
 
         info!(
@@ -269,10 +366,46 @@ where TConsensusSpec: ConsensusSpec
             proposal.justify_qc(),
         );
 
+        let maybe_gap = self.record_foreign_index(tx, &mut foreign_receive_counter, &proposal, local_committee_info)?;
         foreign_receive_counter.save(tx)?;
         proposal.upsert(tx, None)?;
 
-        Ok(())
+        Ok(maybe_gap)
+    }
+
+    fn record_foreign_index(
+        &self,
+        tx: &mut <TConsensusSpec::StateStore as StateStore>::WriteTransaction<'_>,
+        foreign_receive_counter: &mut ForeignReceiveCounters,
+        proposal: &ForeignProposal,
+        local_committee_info: &CommitteeInfo,
+    ) -> Result<Option<ForeignReceiveGap>, HotStuffError> {
+        let block = proposal.block();
+        // Every local shard is given the same foreign index
+        let Some(index) = local_committee_info
+            .shard_group()
+            .shard_iter()
+            .find_map(|shard| block.get_foreign_counter(&shard))
+        else {
+            return Ok(None);
+        };
+
+        let foreign_shard_group = block.shard_group();
+        let Some(missing) = foreign_receive_counter.record_received(foreign_shard_group, block.epoch(), index) else {
+            ForeignReceiveGap::record_received(tx, foreign_shard_group, block.epoch(), index)?;
+            return Ok(None);
+        };
+
+        let gap = ForeignReceiveGap::new(foreign_shard_group, block.epoch(), missing);
+        warn!(
+            target: LOG_TARGET,
+            "⚠️ FOREIGN PROPOSAL: Received foreign index {} in block {}. Missing {}",
+            index,
+            block.id(),
+            gap
+        );
+        gap.insert(tx)?;
+        Ok(Some(gap))
     }
 
     fn validate_proposed_block(
@@ -310,3 +443,50 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 }
+
+/// Returns the committed blocks of the local shard group that were given a foreign index in the range for the foreign
+/// shard group
+fn find_blocks_by_foreign_index<TTx: StateStoreReadTransaction>(
+    tx: &TTx,
+    epoch: Epoch,
+    local_shard_group: ShardGroup,
+    for_shard_group: ShardGroup,
+    foreign_indexes: RangeInclusive<u64>,
+) -> Result<Vec<BlockId>, HotStuffError> {
+    if foreign_indexes.is_empty() {
+        return Ok(vec![]);
+    }
+    let num_requested = (foreign_indexes.end() - foreign_indexes.start()).saturating_add(1) as usize;
+    let max_blocks = num_requested.min(MAX_FOREIGN_PROPOSALS_PER_REQUEST);
+    let max_height = tx.blocks_max_height()?;
+
+    let mut block_ids = Vec::with_capacity(max_blocks);
+    let mut start_height = NodeHeight::zero();
+    while start_height <= max_height && block_ids.len() < max_blocks {
+        let end_height = start_height + NodeHeight(FOREIGN_INDEX_SEARCH_PAGE_SIZE - 1);
+        let blocks = Block::get_all_blocks_between(
+            tx,
+            epoch,
+            local_shard_group,
+            start_height,
+            end_height,
+            false,
+            FOREIGN_INDEX_SEARCH_PAGE_SIZE,
+        )?;
+        let matching = blocks
+            .into_iter()
+            .filter(|block| block.is_committed())
+            .filter(|block| {
+                for_shard_group
+                    .shard_iter()
+                    .filter_map(|shard| block.get_foreign_counter(&shard))
+                    .any(|index| foreign_indexes.contains(&index))
+            })
+            .map(|block| *block.id());
+        block_ids.extend(matching);
+        start_height = end_height + NodeHeight(1);
+    }
+
+    block_ids.truncate(max_blocks);
+    Ok(block_ids)
+}
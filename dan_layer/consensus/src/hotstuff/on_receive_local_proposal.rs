@@ -1,10 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{
-    collections::{HashMap, HashSet},
-    mem,
-};
+use std::{collections::HashMap, mem};
 
 use log::*;
 use tari_dan_common_types::{
@@ -31,19 +28,25 @@ use tari_dan_storage::{
 };
 use tari_epoch_manager::EpochManagerReader;
 use tokio::{sync::broadcast, task};
+use tracing::info_span;
 
 use crate::{
     hotstuff::{
         block_change_set::ProposedBlockChangeSet,
         calculate_dummy_blocks_from_justify,
         create_epoch_checkpoint,
+        create_state_snapshots,
+        epoch_boundary::process_transactions_at_epoch_boundary,
         error::HotStuffError,
         eviction_proof::generate_eviction_proofs,
+        get_foreign_proposal_shard_groups,
         get_next_block_height_and_leader,
+        message_rebroadcaster::MessageRebroadcaster,
         on_ready_to_vote_on_local_block::OnReadyToVoteOnLocalBlock,
         on_receive_foreign_proposal::OnReceiveForeignProposalHandler,
         pacemaker_handle::PaceMakerHandle,
         transaction_manager::ConsensusTransactionManager,
+        validate_block_evidence,
         HotstuffConfig,
         HotstuffEvent,
         ProposalValidationError,
@@ -74,6 +77,7 @@ pub struct OnReceiveLocalProposalHandler<TConsensusSpec: ConsensusSpec> {
     on_receive_foreign_proposal: OnReceiveForeignProposalHandler<TConsensusSpec>,
     tx_events: broadcast::Sender<HotstuffEvent>,
     hooks: TConsensusSpec::Hooks,
+    message_rebroadcaster: MessageRebroadcaster<TConsensusSpec::Addr>,
 }
 
 impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec> {
@@ -93,6 +97,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         >,
         config: HotstuffConfig,
         hooks: TConsensusSpec::Hooks,
+        message_rebroadcaster: MessageRebroadcaster<TConsensusSpec::Addr>,
     ) -> Self {
         let local_validator_pk = vote_signing_service.public_key().clone();
         Self {
@@ -119,6 +124,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 transaction_manager,
             ),
             change_set: None,
+            message_rebroadcaster,
         }
     }
 
@@ -182,7 +188,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             foreign_committees.insert(shard_group, foreign_committee_info);
         }
 
-        self.store.with_write_tx(|tx| {
+        let foreign_receive_gaps = self.store.with_write_tx(|tx| {
             // TODO: Implement guaranteed finality in the face of a non-cooperating remote shard group.
             // Suggested strategy:
             // Given a transaction that is awaiting a foreign proposal for REQUEST_FOREIGN_PROPOSAL_TIMEOUT (e.g. 50)
@@ -196,6 +202,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             // - Set abort and ready = true
             // self.update_foreign_proposal_transactions(tx, valid_block.block())?;
 
+            let mut foreign_receive_gaps = Vec::new();
             for foreign_proposal in foreign_proposals {
                 if foreign_proposal.exists(&**tx)? {
                     // This is expected behaviour, we may receive the same foreign proposal multiple times
@@ -209,19 +216,26 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                 }
                 let shard_group = foreign_proposal.block().shard_group();
 
-                self.on_receive_foreign_proposal.validate_and_save(
+                let maybe_gap = self.on_receive_foreign_proposal.validate_and_save(
                     tx,
                     foreign_proposal,
                     local_committee_info,
                     foreign_committees.get(&shard_group).unwrap(),
                 )?;
+                foreign_receive_gaps.extend(maybe_gap);
             }
 
             self.save_block(tx, &valid_block)?;
             info!(target: LOG_TARGET, "✅ Block {} is valid and persisted.", valid_block);
-            Ok::<_, HotStuffError>(())
+            Ok::<_, HotStuffError>(foreign_receive_gaps)
         })?;
 
+        for gap in foreign_receive_gaps {
+            self.on_receive_foreign_proposal
+                .request_missing_foreign_proposals(&gap, local_committee_info)
+                .await?;
+        }
+
         let result = self
             .process_block(
                 current_epoch,
@@ -351,7 +365,11 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
 
         self.hooks
             .on_local_block_decide(&valid_block, block_decision.quorum_decision);
+        for block in &block_decision.committed_blocks {
+            self.hooks.on_block_committed(block);
+        }
         for t in block_decision.finalized_transactions.into_iter().flatten() {
+            let _span = info_span!("transaction", transaction_id = %t.transaction_id()).entered();
             self.hooks.on_transaction_finalized(&t.into_current_transaction_atom());
         }
         self.propose_newly_locked_blocks(local_committee_info, block_decision.locked_blocks);
@@ -359,6 +377,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         if let Some(epoch) = block_decision.end_of_epoch {
             let next_epoch = epoch + Epoch(1);
             let mut registered_shard_group = None;
+            let mut requeued_transactions = Vec::new();
 
             // If we're registered for the next epoch. Create a new genesis block.
             if let Some(vn) = self.epoch_manager.get_our_validator_node(next_epoch).await.optional()? {
@@ -368,9 +387,12 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                     .shard_key
                     .to_shard_group(self.config.consensus_constants.num_preshards, num_committees);
                 registered_shard_group = Some(next_shard_group);
-                self.store.with_write_tx(|tx| {
+                let is_layout_changed = num_committees != local_committee_info.num_committees() ||
+                    next_shard_group != local_committee_info.shard_group();
+                let outcome = self.store.with_write_tx(|tx| {
                     // Generate checkpoint
-                    create_epoch_checkpoint(tx, epoch, local_committee_info.shard_group())?;
+                    let checkpoint = create_epoch_checkpoint(tx, epoch, local_committee_info.shard_group())?;
+                    create_state_snapshots(tx, &checkpoint, self.config.consensus_constants.state_snapshot_interval)?;
 
                     // Create the next genesis
                     let mut genesis = Block::genesis(
@@ -391,10 +413,20 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                     genesis.as_last_voted().set(tx)?;
                     genesis.justify().as_high_qc().set(tx)?;
 
+                    let outcome = process_transactions_at_epoch_boundary(tx, next_epoch, is_layout_changed)?;
                     cleanup_epoch(tx, epoch)?;
 
-                    Ok::<_, HotStuffError>(())
+                    Ok::<_, HotStuffError>(outcome)
                 })?;
+                info!(
+                    target: LOG_TARGET,
+                    "⭐️ Transactions at end of {epoch}: {} carried over, {} expired, {} requeued (layout changed: {})",
+                    outcome.num_carried_over,
+                    outcome.num_expired,
+                    outcome.requeued.len(),
+                    is_layout_changed,
+                );
+                requeued_transactions = outcome.requeued;
 
                 // TODO: We should exit consensus to sync for the epoch - when this is implemented, we will not
                 // need to create the genesis, set the pacemaker, etc.
@@ -405,10 +437,17 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
                     "💤 Our validator node is not registered for epoch {next_epoch}.",
                 )
             }
+            self.hooks.on_epoch_changed(next_epoch);
             self.publish_event(HotstuffEvent::EpochChanged {
                 epoch: next_epoch,
                 registered_shard_group,
             });
+            if !requeued_transactions.is_empty() {
+                self.publish_event(HotstuffEvent::TransactionsRequeued {
+                    epoch: next_epoch,
+                    transaction_ids: requeued_transactions,
+                });
+            }
         }
 
         // Propose quickly for the end of epoch chain
@@ -449,9 +488,11 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             signature: vote.signature.clone(),
         };
 
-        self.outbound_messaging
-            .send(leader.clone(), HotstuffMessage::Vote(vote))
-            .await?;
+        let message = HotstuffMessage::Vote(vote);
+        self.outbound_messaging.send(leader.clone(), message.clone()).await?;
+        // The leader acknowledges the vote by proposing the next block
+        self.message_rebroadcaster
+            .track(leader.clone(), message, block.epoch(), block.height() + NodeHeight(1));
 
         self.store.with_write_tx(|tx| last_sent_vote.set(tx))?;
 
@@ -498,9 +539,11 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             last_vote: Some(vote),
         };
 
-        self.outbound_messaging
-            .send(leader.clone(), HotstuffMessage::NewView(message))
-            .await?;
+        let message = HotstuffMessage::NewView(message);
+        self.outbound_messaging.send(leader.clone(), message.clone()).await?;
+        // The leader acknowledges the NEWVIEW by proposing at the new height
+        self.message_rebroadcaster
+            .track(leader.clone(), message, block.epoch(), new_height);
 
         self.store.with_write_tx(|tx| last_sent_vote.set(tx))?;
 
@@ -534,6 +577,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             unverified_block_height: block.height(),
             decision,
             signature,
+            block_header: Some(block.header().clone()),
         })
     }
 
@@ -711,7 +755,7 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
         current_epoch: Epoch,
         candidate_block: Block,
         local_committee: &Committee<TConsensusSpec::Addr>,
-        _local_committee_info: &CommitteeInfo,
+        local_committee_info: &CommitteeInfo,
     ) -> Result<ValidBlock, HotStuffError> {
         if Block::has_been_justified(tx, candidate_block.id())
             .optional()?
@@ -767,6 +811,8 @@ impl<TConsensusSpec: ConsensusSpec> OnReceiveLocalProposalHandler<TConsensusSpec
             .into());
         }
 
+        validate_block_evidence(tx, &candidate_block, local_committee_info.num_preshards())?;
+
         let justify_block = if candidate_block.justify().justifies_zero_block() {
             // The justified block is the zero block (epoch 0). However, we instead need the genesis block for the
             // epoch.
@@ -930,18 +976,8 @@ async fn broadcast_foreign_proposal_if_required<TConsensusSpec: ConsensusSpec>(
 
     let validator = epoch_manager.get_our_validator_node(block.epoch()).await?;
     let local_shard_group = validator.shard_key.to_shard_group(num_preshards, num_committees);
-    let non_local_shard_groups = block
-        .commands()
-        .iter()
-        .filter_map(|c| {
-            c.local_prepare()
-                // No need to broadcast LocalPrepare if the committee is output only
-                .filter(|atom| !atom.evidence.is_committee_output_only(local_committee_info))
-                .or_else(|| c.local_accept())
-        })
-        .flat_map(|p| p.evidence.shard_groups_iter().copied())
-        .filter(|shard_group| local_shard_group != *shard_group)
-        .collect::<HashSet<_>>();
+    let non_local_shard_groups =
+        get_foreign_proposal_shard_groups(block.commands(), local_shard_group, local_committee_info);
     if non_local_shard_groups.is_empty() {
         debug!(
             target: LOG_TARGET,
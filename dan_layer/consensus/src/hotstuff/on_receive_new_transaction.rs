@@ -52,7 +52,7 @@ where TConsensusSpec: ConsensusSpec
         local_committee_info: &CommitteeInfo,
     ) -> Result<(), HotStuffError> {
         let _timer = TraceTimer::debug(LOG_TARGET, "OnReceiveRequestedTransactions");
-        info!(target: LOG_TARGET, "Receiving {} requested transactions for block {} from {:?}", msg.transactions.len(), msg.block_id, from);
+        info!(target: LOG_TARGET, "Receiving {} requested transactions for {} block(s) from {:?}", msg.transactions.len(), msg.block_ids.len(), from);
         self.store.with_write_tx(|tx| {
             let recs = TransactionRecord::get_any_or_build(&**tx, msg.transactions)?;
             let mut batch = Vec::with_capacity(recs.len());
@@ -117,6 +117,22 @@ where TConsensusSpec: ConsensusSpec
             return Ok(None);
         }
 
+        // The transaction is not yet valid. The mempool holds on to it and resubmits it once the epoch is reached.
+        if let Some(min_epoch) = rec
+            .transaction()
+            .min_epoch()
+            .filter(|min_epoch| *min_epoch > current_epoch)
+        {
+            info!(
+                target: LOG_TARGET,
+                "⏳️ Transaction {} is deferred until {} (current epoch: {}). Consensus will ignore it.",
+                rec.id(),
+                min_epoch,
+                current_epoch
+            );
+            return Ok(None);
+        }
+
         let result = self.executor.validate(&**tx, current_epoch, rec.transaction());
 
         if let Err(err) = result {
@@ -153,8 +169,7 @@ where TConsensusSpec: ConsensusSpec
         transaction: &TransactionRecord,
         is_ready: bool,
     ) -> Result<(), HotStuffError> {
-        self.transaction_pool
-            .insert_new(tx, *transaction.id(), transaction.current_decision(), is_ready)?;
+        self.transaction_pool.insert_new(tx, transaction, is_ready)?;
         Ok(())
     }
 }
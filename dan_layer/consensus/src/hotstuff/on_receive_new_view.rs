@@ -59,6 +59,17 @@ where TConsensusSpec: ConsensusSpec
         self.newview_message_counts.clear();
     }
 
+    fn is_duplicate_new_view(
+        &self,
+        from: &TConsensusSpec::Addr,
+        new_height: NodeHeight,
+        high_qc: &QuorumCertificate,
+    ) -> bool {
+        self.newview_message_counts
+            .get(&(new_height, *high_qc.block_id()))
+            .is_some_and(|senders| senders.contains(from))
+    }
+
     fn collect_new_views(
         &mut self,
         from: TConsensusSpec::Addr,
@@ -104,6 +115,12 @@ where TConsensusSpec: ConsensusSpec
             return Ok(());
         }
 
+        // NEWVIEWs are rebroadcast until the leader proposes, so we may receive the same NEWVIEW more than once
+        if self.is_duplicate_new_view(&from, new_height, &high_qc) {
+            debug!(target: LOG_TARGET, "🌟 Ignoring duplicate NEWVIEW {new_height} from {from}");
+            return Ok(());
+        }
+
         let is_qc_valid = self.store.with_read_tx(|tx| {
             // If we already have this QC (locally calculated hash matches), we do not need to validate this again
             if !high_qc.exists(tx)? {
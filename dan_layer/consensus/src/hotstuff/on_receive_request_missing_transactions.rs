@@ -1,9 +1,15 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
 use log::*;
-use tari_dan_common_types::option::DisplayContainer;
+use tari_dan_common_types::{option::DisplayContainer, Epoch};
 use tari_dan_storage::{consensus_models::TransactionRecord, StateStore};
+use tokio::{sync::mpsc, task};
 
 use crate::{
     hotstuff::error::HotStuffError,
@@ -14,28 +20,95 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::on_receive_request_missing_transactions";
 
+/// The maximum number of requests that may be queued for the responder, and that may be waiting to be queued. Further
+/// requests are discarded until the responder catches up.
+const MAX_QUEUED_REQUESTS: usize = 64;
+
+/// Queues missing transaction requests for a responder task, so that the hotstuff worker is not blocked by reading
+/// and sending the requested transactions.
 pub struct OnReceiveRequestMissingTransactions<TConsensusSpec: ConsensusSpec> {
-    store: TConsensusSpec::StateStore,
-    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    tx_requests: mpsc::Sender<QueuedRequest<TConsensusSpec::Addr>>,
+    next_seq: u64,
 }
 
 impl<TConsensusSpec> OnReceiveRequestMissingTransactions<TConsensusSpec>
 where TConsensusSpec: ConsensusSpec
 {
     pub fn new(store: TConsensusSpec::StateStore, outbound_messaging: TConsensusSpec::OutboundMessaging) -> Self {
-        Self {
+        let (tx_requests, rx_requests) = mpsc::channel(MAX_QUEUED_REQUESTS);
+        let responder = MissingTransactionsResponder::<TConsensusSpec> {
             store,
             outbound_messaging,
+            rx_requests,
+            queue: BinaryHeap::new(),
+        };
+        task::spawn(responder.run());
+        Self { tx_requests, next_seq: 0 }
+    }
+
+    pub fn handle(&mut self, from: TConsensusSpec::Addr, msg: MissingTransactionsRequest) -> Result<(), HotStuffError> {
+        info!(
+            target: LOG_TARGET,
+            "{} requested {} missing transaction(s) from {} block(s)",
+            from,
+            msg.transactions.len(),
+            msg.block_ids.len()
+        );
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        match self.tx_requests.try_send(QueuedRequest { seq, from, msg }) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(req)) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "⚠️ Missing transactions responder is busy. Discarding request {} from {}", req.msg.request_id, req.from
+                );
+                Ok(())
+            },
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(HotStuffError::InternalChannelClosed {
+                context: "OnReceiveRequestMissingTransactions::handle",
+            }),
+        }
+    }
+}
+
+struct MissingTransactionsResponder<TConsensusSpec: ConsensusSpec> {
+    store: TConsensusSpec::StateStore,
+    outbound_messaging: TConsensusSpec::OutboundMessaging,
+    rx_requests: mpsc::Receiver<QueuedRequest<TConsensusSpec::Addr>>,
+    queue: BinaryHeap<QueuedRequest<TConsensusSpec::Addr>>,
+}
+
+impl<TConsensusSpec: ConsensusSpec> MissingTransactionsResponder<TConsensusSpec> {
+    async fn run(mut self) {
+        loop {
+            // Take everything that has arrived so that the highest priority request is served next. Once the queue is
+            // full, requests wait in the channel.
+            while self.queue.len() < MAX_QUEUED_REQUESTS {
+                let Ok(req) = self.rx_requests.try_recv() else {
+                    break;
+                };
+                self.queue.push(req);
+            }
+
+            let req = match self.queue.pop() {
+                Some(req) => req,
+                None => match self.rx_requests.recv().await {
+                    Some(req) => req,
+                    // The hotstuff worker has shut down
+                    None => break,
+                },
+            };
+
+            if let Err(err) = self.respond(req).await {
+                warn!(target: LOG_TARGET, "Failed to respond to missing transactions request: {}", err);
+            }
         }
     }
 
-    pub async fn handle(
-        &mut self,
-        from: TConsensusSpec::Addr,
-        msg: MissingTransactionsRequest,
-    ) -> Result<(), HotStuffError> {
-        let _timer = TraceTimer::debug(LOG_TARGET, "OnReceiveRequestMissingTransactions");
-        info!(target: LOG_TARGET, "{} requested {} missing transaction(s) from block {}", from, msg.transactions.len(), msg.block_id);
+    async fn respond(&mut self, req: QueuedRequest<TConsensusSpec::Addr>) -> Result<(), HotStuffError> {
+        let _timer = TraceTimer::debug(LOG_TARGET, "MissingTransactionsResponder::respond");
+        let QueuedRequest { from, msg, .. } = req;
         let (txs, missing) = self
             .store
             .with_read_tx(|tx| TransactionRecord::get_any(tx, &msg.transactions))?;
@@ -52,7 +125,7 @@ where TConsensusSpec: ConsensusSpec
                 HotstuffMessage::MissingTransactionsResponse(MissingTransactionsResponse {
                     request_id: msg.request_id,
                     epoch: msg.epoch,
-                    block_id: msg.block_id,
+                    block_ids: msg.block_ids,
                     transactions: txs.into_iter().map(|tx| tx.into_transaction()).collect(),
                 }),
             )
@@ -60,3 +133,37 @@ where TConsensusSpec: ConsensusSpec
         Ok(())
     }
 }
+
+/// Requests for the most recent epoch are served first, since requests for older epochs are unlikely to help the
+/// requester make progress. Within an epoch smaller requests are served first, then in the order they were received.
+struct QueuedRequest<TAddr> {
+    seq: u64,
+    from: TAddr,
+    msg: MissingTransactionsRequest,
+}
+
+impl<TAddr> QueuedRequest<TAddr> {
+    fn priority_key(&self) -> (Epoch, Reverse<usize>, Reverse<u64>) {
+        (self.msg.epoch, Reverse(self.msg.transactions.len()), Reverse(self.seq))
+    }
+}
+
+impl<TAddr> PartialEq for QueuedRequest<TAddr> {
+    fn eq(&self, other: &Self) -> bool {
+        self.seq == other.seq
+    }
+}
+
+impl<TAddr> Eq for QueuedRequest<TAddr> {}
+
+impl<TAddr> PartialOrd for QueuedRequest<TAddr> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<TAddr> Ord for QueuedRequest<TAddr> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority_key().cmp(&other.priority_key())
+    }
+}
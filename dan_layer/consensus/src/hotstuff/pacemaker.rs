@@ -60,6 +60,12 @@ impl PaceMaker {
         self.pace_maker_handle.clone()
     }
 
+    /// Removes and returns the requests that have been sent to the pacemaker without processing them
+    #[cfg(feature = "test-hooks")]
+    pub fn take_requests(&mut self) -> Vec<PacemakerRequest> {
+        std::iter::from_fn(|| self.handle_receiver.try_recv().ok()).collect()
+    }
+
     pub fn spawn(mut self) {
         let handle = self.clone_handle();
         let on_beat = handle.get_on_beat();
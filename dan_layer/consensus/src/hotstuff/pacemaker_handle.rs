@@ -12,6 +12,7 @@ use crate::hotstuff::{
     HotStuffError,
 };
 
+#[derive(Debug, Clone)]
 pub enum PacemakerRequest {
     Reset {
         high_qc_height: Option<NodeHeight>,
@@ -0,0 +1,90 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, hash::Hash, time::Instant};
+
+/// Once this many peers are tracked, peers that have fully recovered their allowance are forgotten
+const MAX_TRACKED_PEERS: usize = 1024;
+
+/// Limits the rate at which each peer may consume a resource. Each peer is allowed a burst of `burst` units that is
+/// replenished at `units_per_sec`.
+#[derive(Debug)]
+pub struct PeerRateLimiter<TAddr> {
+    units_per_sec: u64,
+    burst: u64,
+    buckets: HashMap<TAddr, Bucket>,
+}
+
+impl<TAddr: Eq + Hash> PeerRateLimiter<TAddr> {
+    pub fn new(units_per_sec: u64, burst: u64) -> Self {
+        Self {
+            units_per_sec,
+            burst,
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Returns true and consumes `units` from the peer's allowance if the peer has enough remaining, otherwise returns
+    /// false.
+    pub fn try_consume(&mut self, peer: TAddr, units: u64) -> bool {
+        self.try_consume_at(peer, units, Instant::now())
+    }
+
+    fn try_consume_at(&mut self, peer: TAddr, units: u64, now: Instant) -> bool {
+        if self.buckets.len() >= MAX_TRACKED_PEERS {
+            let (units_per_sec, burst) = (self.units_per_sec, self.burst);
+            self.buckets
+                .retain(|_, bucket| bucket.available(units_per_sec, burst, now) < burst as f64);
+        }
+
+        let burst = self.burst;
+        let bucket = self.buckets.entry(peer).or_insert_with(|| Bucket {
+            tokens: burst as f64,
+            last_refill: now,
+        });
+        let available = bucket.available(self.units_per_sec, burst, now);
+        bucket.last_refill = now;
+        if available < units as f64 {
+            bucket.tokens = available;
+            return false;
+        }
+        bucket.tokens = available - units as f64;
+        true
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn available(&self, units_per_sec: u64, burst: u64, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        (self.tokens + elapsed * units_per_sec as f64).min(burst as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn it_limits_each_peer_independently() {
+        let start = Instant::now();
+        let mut limiter = PeerRateLimiter::new(100, 200);
+
+        assert!(limiter.try_consume_at("a", 150, start));
+        assert!(!limiter.try_consume_at("a", 100, start));
+        assert!(limiter.try_consume_at("b", 200, start));
+
+        // 50 remaining + 50 replenished after half a second
+        assert!(!limiter.try_consume_at("a", 101, start + Duration::from_millis(500)));
+        assert!(limiter.try_consume_at("a", 100, start + Duration::from_millis(500)));
+        // The allowance never exceeds the burst
+        assert!(!limiter.try_consume_at("b", 201, start + Duration::from_secs(10)));
+    }
+}
@@ -0,0 +1,88 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::ops::Deref;
+
+use log::*;
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        BlockHeader,
+        Misbehaviour,
+        MisbehaviourEvidence,
+        ProposalEquivocation,
+        ProposalEquivocationAtom,
+    },
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+};
+
+use crate::hotstuff::HotStuffError;
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::proposal_equivocation";
+
+/// Checks if the proposer of the given signed header has signed a different block for the same epoch, shard group and
+/// height that we already have. If so, the equivocation evidence is returned. No evidence is returned if evidence for
+/// the proposer at this height has already been recorded.
+pub fn find_proposal_equivocation<TTx: StateStoreReadTransaction>(
+    tx: &TTx,
+    header: &BlockHeader,
+) -> Result<Option<ProposalEquivocationAtom>, HotStuffError> {
+    if header.is_dummy() || header.is_genesis() || header.signature().is_none() {
+        return Ok(None);
+    }
+
+    if ProposalEquivocation::exists(tx, header.epoch(), header.proposed_by(), header.height())? {
+        return Ok(None);
+    }
+
+    for block_id in Block::get_ids_by_epoch_and_height(tx, header.epoch(), header.height())? {
+        if block_id == *header.id() {
+            continue;
+        }
+        let existing = Block::get(tx, &block_id)?;
+        if existing.is_dummy() ||
+            existing.proposed_by() != header.proposed_by() ||
+            existing.shard_group() != header.shard_group()
+        {
+            continue;
+        }
+
+        let atom = ProposalEquivocationAtom::new(existing.header().clone(), header.clone());
+        match atom.validate() {
+            Ok(()) => return Ok(Some(atom)),
+            Err(err) => {
+                // The header we received was not validly signed, so this is not evidence of equivocation
+                debug!(
+                    target: LOG_TARGET,
+                    "Conflicting header for {} is not valid equivocation evidence: {}", block_id, err
+                );
+            },
+        }
+    }
+
+    Ok(None)
+}
+
+/// Records equivocation evidence for the proposer of the given header, if any. Returns the evidence if it is new.
+pub fn record_proposal_equivocation_if_any<TTx>(
+    tx: &mut TTx,
+    header: &BlockHeader,
+) -> Result<Option<ProposalEquivocationAtom>, HotStuffError>
+where
+    TTx: StateStoreWriteTransaction + Deref,
+    TTx::Target: StateStoreReadTransaction,
+{
+    let Some(atom) = find_proposal_equivocation(&**tx, header)? else {
+        return Ok(None);
+    };
+
+    warn!(
+        target: LOG_TARGET,
+        "🎭 Detected proposal equivocation: {}",
+        atom
+    );
+    ProposalEquivocation::new(atom.clone()).insert(tx)?;
+    MisbehaviourEvidence::new(Misbehaviour::EquivocatingProposal(atom.clone())).insert(tx)?;
+    Ok(Some(atom))
+}
@@ -0,0 +1,118 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Drives the hotstuff worker one step at a time so that tests can assert on precise state transitions. The pacemaker
+//! is not spawned, so beats and leader timeouts only happen when they are given as a [StepInput]. Outbound messages are
+//! sent using the `OutboundMessaging` implementation that the worker was created with.
+
+use tari_dan_common_types::NodeHeight;
+use tari_transaction::Transaction;
+use tokio::sync::broadcast;
+
+use crate::{
+    hotstuff::{
+        on_beat::OnBeat,
+        on_force_beat::OnForceBeat,
+        pacemaker_handle::PacemakerRequest,
+        CurrentView,
+        HotStuffError,
+        HotstuffEvent,
+        HotstuffWorker,
+    },
+    messages::HotstuffMessage,
+    traits::ConsensusSpec,
+};
+
+/// An input to the hotstuff worker. Timer inputs (`ForceBeat` with no forced height and `LeaderTimeout`) stand in for
+/// the pacemaker timers.
+#[derive(Debug, Clone)]
+pub enum StepInput<TAddr> {
+    /// A message from a peer. The message is validated and handled immediately, it is not buffered until the view it
+    /// is for.
+    Message { from: TAddr, message: HotstuffMessage },
+    NewTransaction { transaction: Transaction, num_pending: usize },
+    Beat,
+    /// The block timer expired (forced_height is None) or NEWVIEW quorum was reached (forced_height is Some)
+    ForceBeat { forced_height: Option<NodeHeight> },
+    LeaderTimeout,
+}
+
+/// The outputs of a single step
+#[derive(Debug, Default)]
+pub struct StepOutput {
+    pub events: Vec<HotstuffEvent>,
+    /// Requests sent to the pacemaker. These are not processed when stepping.
+    pub pacemaker_requests: Vec<PacemakerRequest>,
+    /// True if the worker signalled a beat. The beat is not handled unless it is given as the next input.
+    pub beat: bool,
+    /// Set to the forced height if the worker signalled a forced beat
+    pub force_beat: Option<Option<NodeHeight>>,
+    pub error: Option<HotStuffError>,
+}
+
+impl StepOutput {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+pub struct HotstuffStepper<TConsensusSpec: ConsensusSpec> {
+    worker: HotstuffWorker<TConsensusSpec>,
+    rx_events: broadcast::Receiver<HotstuffEvent>,
+    on_beat: OnBeat,
+    on_force_beat: OnForceBeat,
+}
+
+impl<TConsensusSpec: ConsensusSpec> HotstuffStepper<TConsensusSpec> {
+    pub fn new(worker: HotstuffWorker<TConsensusSpec>) -> Self {
+        Self {
+            rx_events: worker.subscribe_events(),
+            on_beat: worker.pacemaker().get_on_beat(),
+            on_force_beat: worker.pacemaker().get_on_force_beat(),
+            worker,
+        }
+    }
+
+    /// Creates the genesis block if required and sets the current view. This must be called before the first step.
+    pub async fn start(&mut self) -> StepOutput {
+        let result = self.worker.start_stepping().await;
+        self.collect_output(result.err())
+    }
+
+    pub async fn step(&mut self, input: StepInput<TConsensusSpec::Addr>) -> StepOutput {
+        let result = self.worker.step(input).await;
+        self.collect_output(result.err())
+    }
+
+    pub fn current_view(&self) -> &CurrentView {
+        self.worker.pacemaker().current_view()
+    }
+
+    pub fn worker(&self) -> &HotstuffWorker<TConsensusSpec> {
+        &self.worker
+    }
+
+    pub fn into_worker(self) -> HotstuffWorker<TConsensusSpec> {
+        self.worker
+    }
+
+    fn collect_output(&mut self, error: Option<HotStuffError>) -> StepOutput {
+        let mut events = Vec::new();
+        loop {
+            match self.rx_events.try_recv() {
+                Ok(event) => events.push(event),
+                // Some events were dropped because the channel is full. The remaining events are still returned.
+                Err(broadcast::error::TryRecvError::Lagged(_)) => {},
+                Err(_) => break,
+            }
+        }
+
+        StepOutput {
+            events,
+            pacemaker_requests: self.worker.take_pacemaker_requests(),
+            beat: self.on_beat.try_wait(),
+            force_beat: self.on_force_beat.try_wait(),
+            error,
+        }
+    }
+}
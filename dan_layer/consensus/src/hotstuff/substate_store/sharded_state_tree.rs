@@ -5,13 +5,15 @@ use std::collections::HashMap;
 
 use indexmap::IndexMap;
 use log::debug;
-use tari_dan_common_types::{shard::Shard, ShardGroup};
+use tari_dan_common_types::{shard::Shard, ShardGroup, VersionedSubstateId};
 use tari_dan_storage::{
     consensus_models::{PendingShardStateTreeDiff, VersionedStateHashTreeDiff},
     StateStoreReadTransaction,
     StateStoreWriteTransaction,
 };
+use tari_engine_types::substate_proof::SubstateInclusionProof;
 use tari_state_tree::{
+    compute_proof_for_hashes,
     memory_store::MemoryTreeStore,
     JmtStorageError,
     RootStateTree,
@@ -161,6 +163,45 @@ impl<TTx: StateStoreReadTransaction> ShardedStateTree<&TTx> {
         let root_hash = state_tree.get_root_hash(version)?;
         Ok(root_hash)
     }
+
+    /// Generates a proof that the substate is included in the state of the shard group. Returns None if the substate
+    /// is not in the state tree of the shard.
+    pub fn get_substate_inclusion_proof(
+        &self,
+        shard_group: ShardGroup,
+        shard: Shard,
+        id: &VersionedSubstateId,
+    ) -> Result<Option<SubstateInclusionProof>, StateTreeError> {
+        let Some(version) = self.get_current_version(shard)? else {
+            return Ok(None);
+        };
+
+        let scoped_store = ShardScopedTreeStoreReader::new(self.tx, shard);
+        let mut store = StagedTreeStore::new(&scoped_store);
+        if let Some(diffs) = self.pending_diffs.get(&shard) {
+            for diff in diffs {
+                store.apply_pending_diff(diff.diff.clone());
+            }
+        }
+        let state_tree = SpreadPrefixStateTree::new(&mut store);
+        let (_, maybe_value, shard_proof) = state_tree.get_proof(version, id)?;
+        if maybe_value.is_none() {
+            return Ok(None);
+        }
+        let shard_state_root = state_tree.get_root_hash(version)?;
+
+        let mut shard_state_roots = Vec::with_capacity(shard_group.len());
+        for s in shard_group.shard_iter() {
+            if s == shard {
+                shard_state_roots.push(shard_state_root);
+            } else {
+                shard_state_roots.push(self.get_state_root_for_shard(s)?);
+            }
+        }
+        let (_, state_root_proof) = compute_proof_for_hashes(shard_state_roots.into_iter(), shard_state_root)?;
+
+        Ok(Some(SubstateInclusionProof::new(shard_proof, shard_state_root, state_root_proof)))
+    }
 }
 
 impl<TTx: StateStoreWriteTransaction> ShardedStateTree<&mut TTx> {
@@ -22,6 +22,7 @@ use tari_dan_storage::{
         BlockTransactionExecution,
         Decision,
         ExecutedTransaction,
+        SubstateRecord,
         SubstateRequirementLockIntent,
         TransactionExecution,
         TransactionRecord,
@@ -35,7 +36,12 @@ use tari_engine_types::{
 };
 use tari_transaction::{Transaction, TransactionId};
 
-use super::{PledgedTransaction, PreparedTransaction};
+use super::{
+    parallel::{execute_in_parallel, select_non_conflicting},
+    PledgedTransaction,
+    PreparedTransaction,
+    SpeculativeExecutionCache,
+};
 use crate::{
     hotstuff::substate_store::{LockStatus, PendingSubstateStore},
     tracing::TraceTimer,
@@ -47,6 +53,7 @@ const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::block_transaction_exec
 #[derive(Debug, Clone)]
 pub struct ConsensusTransactionManager<TExecutor, TStateStore> {
     executor: TExecutor,
+    speculative_executions: SpeculativeExecutionCache,
     _store: PhantomData<TStateStore>,
 }
 
@@ -56,6 +63,7 @@ impl<TStateStore: StateStore, TExecutor: BlockTransactionExecutor<TStateStore>>
     pub fn new(executor: TExecutor) -> Self {
         Self {
             executor,
+            speculative_executions: SpeculativeExecutionCache::new(),
             _store: PhantomData,
         }
     }
@@ -142,11 +150,130 @@ impl<TStateStore: StateStore, TExecutor: BlockTransactionExecutor<TStateStore>>
             return Ok(execution.into_transaction_execution());
         }
 
+        if let Some(executed) = self
+            .speculative_executions
+            .take(transaction.id(), current_epoch, resolved_inputs)
+        {
+            info!(
+                target: LOG_TARGET,
+                "🔮 PREPARE: Using speculative execution of transaction {}",
+                executed.id(),
+            );
+            return Ok(executed.into_execution());
+        }
+
         let executed = self.executor.execute(transaction, current_epoch, resolved_inputs)?;
 
         Ok(executed.into_execution())
     }
 
+    /// Executes a transaction whose inputs are all local against the latest committed state and caches the result, so
+    /// that the transaction does not have to be executed when it is prepared if its inputs have not changed in the
+    /// meantime. Transactions with foreign inputs are ignored.
+    pub fn execute_speculatively(
+        &self,
+        store: &TStateStore,
+        current_epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        transaction: Transaction,
+    ) -> Result<(), BlockTransactionExecutorError> {
+        if self.speculative_executions.contains(transaction.id()) ||
+            !transaction
+                .all_inputs_iter()
+                .all(|input| local_committee_info.includes_substate_id(&input.substate_id))
+        {
+            return Ok(());
+        }
+
+        let maybe_inputs = store.with_read_tx(|tx| resolve_committed_inputs::<TStateStore>(tx, &transaction))?;
+
+        let Some(resolved_inputs) = maybe_inputs else {
+            return Ok(());
+        };
+
+        let transaction_id = *transaction.id();
+        let executed = self.executor.execute(transaction, current_epoch, &resolved_inputs)?;
+        if self
+            .speculative_executions
+            .insert(current_epoch, &resolved_inputs, executed)
+        {
+            debug!(target: LOG_TARGET, "🔮 Speculatively executed transaction {}", transaction_id);
+        }
+        Ok(())
+    }
+
+    /// Executes the given transactions ahead of time on up to `max_parallel_executions` threads and caches the
+    /// results, so that they do not have to be executed one after the other when they are prepared. Only transactions
+    /// whose inputs are all local and committed, and that do not declare or lock a substate of a preceding
+    /// transaction, are executed. The remaining transactions are executed when they are prepared, so the block is processed in the same
+    /// order and with the same results as sequential execution.
+    pub fn execute_in_parallel(
+        &self,
+        tx: &TStateStore::ReadTransaction<'_>,
+        current_epoch: Epoch,
+        local_committee_info: &CommitteeInfo,
+        transactions: Vec<TransactionRecord>,
+        max_parallel_executions: usize,
+    ) -> Result<(), BlockTransactionExecutorError>
+    where
+        TExecutor: Sync,
+    {
+        if max_parallel_executions <= 1 {
+            return Ok(());
+        }
+
+        let mut jobs = Vec::new();
+        let selected = select_non_conflicting(&transactions);
+        for transaction in transactions
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| selected.contains(i))
+            .map(|(_, transaction)| transaction.into_transaction())
+        {
+            if self.speculative_executions.contains(transaction.id()) ||
+                !transaction
+                    .all_inputs_iter()
+                    .all(|input| local_committee_info.includes_substate_id(&input.substate_id))
+            {
+                continue;
+            }
+            if let Some(resolved_inputs) = resolve_committed_inputs::<TStateStore>(tx, &transaction)? {
+                jobs.push((transaction, resolved_inputs));
+            }
+        }
+
+        // There is nothing to gain from executing a single transaction ahead of time
+        if jobs.len() < 2 {
+            return Ok(());
+        }
+
+        let _timer = TraceTimer::info(LOG_TARGET, "execute_in_parallel").with_iterations(jobs.len());
+        let results = execute_in_parallel(jobs, max_parallel_executions, |(transaction, resolved_inputs)| {
+            let transaction_id = *transaction.id();
+            let result = self.executor.execute(transaction, current_epoch, &resolved_inputs);
+            (transaction_id, resolved_inputs, result)
+        });
+
+        for (transaction_id, resolved_inputs, result) in results {
+            match result {
+                Ok(executed) => {
+                    if self
+                        .speculative_executions
+                        .insert(current_epoch, &resolved_inputs, executed)
+                    {
+                        debug!(target: LOG_TARGET, "⚡ Executed transaction {} in parallel", transaction_id);
+                    }
+                },
+                // The transaction is executed again when it is prepared, which will surface the error if it persists
+                Err(err) => {
+                    warn!(target: LOG_TARGET, "⚠️ Parallel execution of transaction {transaction_id} failed: {err}");
+                },
+            }
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_lines)]
     pub fn prepare(
         &self,
@@ -307,3 +434,26 @@ impl<TStateStore: StateStore, TExecutor: BlockTransactionExecutor<TStateStore>>
         }
     }
 }
+
+/// Resolves the inputs of a transaction from the latest committed state. Returns None if any input is not up, in which
+/// case the transaction will be aborted or its inputs will be created by a transaction that is not yet committed.
+fn resolve_committed_inputs<TStateStore: StateStore>(
+    tx: &TStateStore::ReadTransaction<'_>,
+    transaction: &Transaction,
+) -> Result<Option<HashMap<SubstateRequirement, Substate>>, BlockTransactionExecutorError> {
+    let mut resolved_inputs = HashMap::with_capacity(transaction.num_unique_inputs());
+    for input in transaction.all_inputs_iter() {
+        let maybe_substate = match input.version() {
+            Some(version) => {
+                let id = VersionedSubstateId::new(input.substate_id.clone(), version);
+                SubstateRecord::get(tx, &id.to_substate_address()).optional()?
+            },
+            None => SubstateRecord::get_latest(tx, &input.substate_id).optional()?,
+        };
+        let Some(substate) = maybe_substate.filter(|s| s.is_up()) else {
+            return Ok(None);
+        };
+        resolved_inputs.insert(input, substate.into_substate());
+    }
+    Ok(Some(resolved_inputs))
+}
@@ -5,9 +5,12 @@ mod manager;
 pub use manager::*;
 
 mod lock_deps;
+mod parallel;
 mod pledged;
 mod prepared;
+mod speculative;
 
 pub use lock_deps::*;
 pub use pledged::*;
 pub use prepared::*;
+pub use speculative::*;
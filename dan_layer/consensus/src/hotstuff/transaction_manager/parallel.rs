@@ -0,0 +1,193 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+use tari_crypto::tari_utilities::ByteArray;
+use tari_dan_storage::consensus_models::TransactionRecord;
+use tari_engine_types::{fee_claim::FeeClaimAddress, substate::SubstateId};
+
+/// Returns the indexes of the transactions that do not declare or lock a substate that is also declared or locked by a
+/// preceding transaction. These transactions can be executed in parallel against the same state, and produce the same
+/// result as sequential execution because no preceding transaction in the block can change the substates that they
+/// use.
+pub fn select_non_conflicting(transactions: &[TransactionRecord]) -> Vec<usize> {
+    let mut seen = HashSet::<SubstateId>::new();
+    let mut selected = Vec::with_capacity(transactions.len());
+    for (i, transaction) in transactions.iter().enumerate() {
+        // If the substates used by a transaction are unknown, no later transaction can be shown not to conflict with it
+        let Some(substate_ids) = conflict_set(transaction) else {
+            break;
+        };
+        let mut is_conflicting = false;
+        for id in substate_ids {
+            // Keep adding substates so that later transactions conflict with this one
+            is_conflicting |= !seen.insert(id);
+        }
+        if !is_conflicting {
+            selected.push(i);
+        }
+    }
+    selected
+}
+
+/// Returns the substates that the transaction declares or locks: its inputs, the substates referenced by its
+/// instructions, the fee claims that it outputs and, if the transaction has been executed before, the substates that
+/// it locked and the outputs that it produced. Returns None if the referenced substates cannot be decoded.
+fn conflict_set(transaction: &TransactionRecord) -> Option<HashSet<SubstateId>> {
+    let mut substate_ids = transaction.transaction().to_referenced_substates().ok()?;
+    substate_ids.extend(transaction.transaction().all_inputs_substate_ids_iter().cloned());
+    substate_ids.extend(
+        transaction
+            .transaction()
+            .fee_claims()
+            .map(|(epoch, validator)| FeeClaimAddress::from_addr(epoch.as_u64(), validator.as_bytes()).into()),
+    );
+    substate_ids.extend(
+        transaction
+            .resolved_inputs()
+            .into_iter()
+            .flatten()
+            .chain(transaction.resulting_outputs().into_iter().flatten())
+            .map(|lock| lock.substate_id().clone()),
+    );
+    Some(substate_ids)
+}
+
+/// Applies `f` to each job on up to `max_workers` threads. The results are returned in the order of the jobs,
+/// regardless of the order in which they complete.
+pub fn execute_in_parallel<T, R, F>(jobs: Vec<T>, max_workers: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let num_jobs = jobs.len();
+    let num_workers = max_workers.clamp(1, num_jobs.max(1));
+    if num_workers == 1 {
+        return jobs.into_iter().map(f).collect();
+    }
+
+    let jobs = jobs.into_iter().map(|job| Mutex::new(Some(job))).collect::<Vec<_>>();
+    let results = (0..num_jobs).map(|_| Mutex::new(None)).collect::<Vec<_>>();
+    let next_job = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..num_workers {
+            scope.spawn(|| loop {
+                let i = next_job.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+                let job = job.lock().unwrap().take().expect("each job is taken once");
+                *results[i].lock().unwrap() = Some(f(job));
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .unwrap()
+                .expect("all jobs are executed before the scope ends")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_common_types::types::{PrivateKey, PublicKey};
+    use tari_dan_common_types::{SubstateRequirement, VersionedSubstateId};
+    use tari_dan_storage::consensus_models::VersionedSubstateIdLockIntent;
+    use tari_engine_types::{instruction::Instruction, template_models::ComponentAddress};
+    use tari_transaction::Transaction;
+
+    use super::*;
+
+    fn component(seed: u8) -> ComponentAddress {
+        ComponentAddress::from_array([seed; 32])
+    }
+
+    fn transaction_with_inputs(inputs: &[u8]) -> TransactionRecord {
+        TransactionRecord::new(
+            Transaction::builder()
+                .with_inputs(inputs.iter().map(|i| SubstateRequirement::unversioned(component(*i))))
+                .sign(&PrivateKey::default())
+                .build(),
+        )
+    }
+
+    #[test]
+    fn it_selects_transactions_without_conflicting_inputs() {
+        let transactions = vec![
+            transaction_with_inputs(&[1, 2]),
+            transaction_with_inputs(&[3]),
+            // Conflicts with the first transaction
+            transaction_with_inputs(&[2, 4]),
+            // Conflicts with the previous (unselected) transaction
+            transaction_with_inputs(&[4]),
+            transaction_with_inputs(&[5]),
+        ];
+        assert_eq!(select_non_conflicting(&transactions), vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn it_includes_outputs_and_locks_in_the_conflict_set() {
+        let claim_fees = || {
+            TransactionRecord::new(
+                Transaction::builder()
+                    .add_instruction(Instruction::ClaimValidatorFees {
+                        epoch: 1,
+                        validator_public_key: PublicKey::default(),
+                    })
+                    .sign(&PrivateKey::default())
+                    .build(),
+            )
+        };
+
+        let mut locks_input = transaction_with_inputs(&[]);
+        locks_input.resolved_inputs = Some(vec![VersionedSubstateIdLockIntent::read(
+            VersionedSubstateId::new(component(1), 0),
+            true,
+        )]);
+        let mut outputs_input = transaction_with_inputs(&[]);
+        outputs_input.resulting_outputs = Some(vec![VersionedSubstateIdLockIntent::output(VersionedSubstateId::new(
+            component(2),
+            0,
+        ))]);
+
+        let transactions = vec![
+            transaction_with_inputs(&[1, 2]),
+            // Locked the input of the first transaction when it was executed
+            locks_input,
+            // Output the input of the first transaction when it was executed
+            outputs_input,
+            claim_fees(),
+            // Outputs the same fee claim as the previous transaction
+            claim_fees(),
+        ];
+        assert_eq!(select_non_conflicting(&transactions), vec![0, 3]);
+    }
+
+    #[test]
+    fn it_returns_results_in_job_order() {
+        let jobs = (0..100u64).collect::<Vec<_>>();
+        let results = execute_in_parallel(jobs, 8, |i| {
+            // Later jobs complete first
+            thread::sleep(std::time::Duration::from_micros(100 - i));
+            i * 2
+        });
+        assert_eq!(results, (0..100u64).map(|i| i * 2).collect::<Vec<_>>());
+
+        assert!(execute_in_parallel(Vec::<u64>::new(), 8, |i| i).is_empty());
+    }
+}
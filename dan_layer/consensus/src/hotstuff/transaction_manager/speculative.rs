@@ -0,0 +1,98 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use log::*;
+use tari_dan_common_types::{Epoch, SubstateRequirement};
+use tari_dan_storage::consensus_models::ExecutedTransaction;
+use tari_engine_types::substate::{Substate, SubstateId};
+use tari_transaction::TransactionId;
+
+const LOG_TARGET: &str = "tari::dan::consensus::hotstuff::speculative_execution";
+
+/// The maximum number of speculative executions that are cached. Further executions are discarded until the cached
+/// executions are used.
+const MAX_CACHED_EXECUTIONS: usize = 1000;
+
+/// Caches the results of transactions that were executed ahead of time against the latest committed state. A cached
+/// result is only used if the transaction is prepared in the same epoch with exactly the same input versions, because
+/// execution is deterministic given the inputs. If the inputs differ the result is discarded and the transaction is
+/// executed as usual.
+#[derive(Debug, Clone, Default)]
+pub struct SpeculativeExecutionCache {
+    executions: Arc<Mutex<HashMap<TransactionId, SpeculativeExecution>>>,
+}
+
+impl SpeculativeExecutionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, transaction_id: &TransactionId) -> bool {
+        self.executions.lock().unwrap().contains_key(transaction_id)
+    }
+
+    /// Caches the execution. Returns false if the cache is full and the execution was discarded.
+    pub fn insert(
+        &self,
+        epoch: Epoch,
+        resolved_inputs: &HashMap<SubstateRequirement, Substate>,
+        executed: ExecutedTransaction,
+    ) -> bool {
+        let mut executions = self.executions.lock().unwrap();
+        // Executions from previous epochs can never be used
+        executions.retain(|_, execution| execution.epoch >= epoch);
+        if executions.len() >= MAX_CACHED_EXECUTIONS {
+            debug!(
+                target: LOG_TARGET,
+                "Speculative execution cache is full. Discarding execution of {}",
+                executed.id()
+            );
+            return false;
+        }
+        executions.insert(*executed.id(), SpeculativeExecution {
+            epoch,
+            input_versions: input_versions(resolved_inputs),
+            executed,
+        });
+        true
+    }
+
+    /// Removes and returns the cached execution for the transaction if it was executed in the given epoch with the
+    /// same input versions.
+    pub fn take(
+        &self,
+        transaction_id: &TransactionId,
+        epoch: Epoch,
+        resolved_inputs: &HashMap<SubstateRequirement, Substate>,
+    ) -> Option<ExecutedTransaction> {
+        let execution = self.executions.lock().unwrap().remove(transaction_id)?;
+        if execution.epoch != epoch || execution.input_versions != input_versions(resolved_inputs) {
+            info!(
+                target: LOG_TARGET,
+                "🔮 Discarding speculative execution of {}: inputs have changed since it was executed", transaction_id
+            );
+            return None;
+        }
+
+        Some(execution.executed)
+    }
+}
+
+#[derive(Debug)]
+struct SpeculativeExecution {
+    epoch: Epoch,
+    input_versions: BTreeMap<SubstateId, u32>,
+    executed: ExecutedTransaction,
+}
+
+fn input_versions(resolved_inputs: &HashMap<SubstateRequirement, Substate>) -> BTreeMap<SubstateId, u32> {
+    resolved_inputs
+        .iter()
+        .map(|(req, substate)| (req.substate_id().clone(), substate.version()))
+        .collect()
+}
@@ -6,14 +6,23 @@ use tari_common::configuration::Network;
 use tari_common_types::types::FixedHash;
 use tari_dan_common_types::{committee::CommitteeInfo, optional::Optional, Epoch};
 use tari_dan_storage::{
-    consensus_models::{Block, HighQc, QuorumCertificate, QuorumDecision, ValidatorSignature, Vote},
+    consensus_models::{
+        Block,
+        HighQc,
+        Misbehaviour,
+        MisbehaviourEvidence,
+        QuorumCertificate,
+        QuorumDecision,
+        ValidatorSignature,
+        Vote,
+    },
     global::models::ValidatorNode,
     StateStore,
 };
 use tari_epoch_manager::EpochManagerReader;
 
 use crate::{
-    hotstuff::error::HotStuffError,
+    hotstuff::{error::HotStuffError, proposal_equivocation::record_proposal_equivocation_if_any},
     messages::VoteMessage,
     tracing::TraceTimer,
     traits::{ConsensusSpec, VoteSignatureService},
@@ -117,26 +126,55 @@ where TConsensusSpec: ConsensusSpec
         sender_vn: ValidatorNode<TConsensusSpec::Addr>,
     ) -> Result<Option<(QuorumCertificate, HighQc)>, HotStuffError> {
         self.store.with_write_tx(|tx| {
+            // Replicas include the header of the block they voted for, so we can detect a leader that sent a different
+            // proposal to us
+            if let Some(header) = message.block_header.as_ref() {
+                if *header.id() == message.block_id {
+                    record_proposal_equivocation_if_any(tx, header)?;
+                } else {
+                    warn!(
+                        target: LOG_TARGET,
+                        "❓️ Vote from {} for block {} included the header of a different block {}",
+                        sender_vn.address,
+                        message.block_id,
+                        header.id()
+                    );
+                }
+            }
+
             let sender_leaf_hash = sender_vn.get_node_hash(self.network);
 
-            let exists = Vote {
+            let vote = Vote {
                 epoch: message.epoch,
                 block_id: message.block_id,
                 decision: message.decision,
                 sender_leaf_hash,
                 signature: message.signature,
-            }
-            .save(tx)?;
+            };
 
-            if exists {
-                warn!(
-                    target: LOG_TARGET,
-                    "❓️ Received duplicate vote for block {} from {}",
-                    message.block_id,
-                    sender_vn.address
-                );
+            if let Some(existing) =
+                Vote::get_by_block_and_sender(&**tx, &message.block_id, &sender_leaf_hash).optional()?
+            {
+                if existing.decision == vote.decision {
+                    // Votes are rebroadcast until the leader proposes, so duplicates are expected
+                    debug!(
+                        target: LOG_TARGET,
+                        "❓️ Received duplicate vote for block {} from {}",
+                        message.block_id,
+                        sender_vn.address
+                    );
+                } else {
+                    // Both votes have been validated, so the sender signed conflicting decisions for the same block
+                    let misbehaviour = Misbehaviour::DoubleVote {
+                        first: existing.into(),
+                        second: vote.into(),
+                    };
+                    warn!(target: LOG_TARGET, "🎭 Detected double vote: {}", misbehaviour);
+                    MisbehaviourEvidence::new(misbehaviour).insert(tx)?;
+                }
                 return Ok(None);
             }
+            vote.insert(tx)?;
 
             let count = Vote::count_for_block(&**tx, &message.block_id)?;
             // We only generate the next high qc once when we have a quorum of votes. Any subsequent votes are not
@@ -33,8 +33,15 @@ use tari_epoch_manager::{EpochManagerEvent, EpochManagerReader};
 use tari_shutdown::ShutdownSignal;
 use tari_state_tree::SPARSE_MERKLE_PLACEHOLDER_HASH;
 use tari_transaction::{Transaction, TransactionId};
-use tokio::sync::{broadcast, mpsc};
+use tokio::{
+    sync::{broadcast, mpsc},
+    task,
+    time,
+};
+use tracing::{info_span, Instrument};
 
+#[cfg(feature = "test-hooks")]
+use super::{pacemaker_handle::PacemakerRequest, stepper::StepInput};
 use super::{
     calculate_last_dummy_block,
     config::HotstuffConfig,
@@ -45,6 +52,7 @@ use crate::{
     hotstuff::{
         error::HotStuffError,
         event::HotstuffEvent,
+        message_rebroadcaster::MessageRebroadcaster,
         on_catch_up_sync::OnCatchUpSync,
         on_catch_up_sync_request::OnSyncRequest,
         on_inbound_message::OnInboundMessage,
@@ -90,6 +98,8 @@ pub struct HotstuffWorker<TConsensusSpec: ConsensusSpec> {
     on_propose: OnPropose<TConsensusSpec>,
     on_sync_request: OnSyncRequest<TConsensusSpec>,
     on_catch_up_sync: OnCatchUpSync<TConsensusSpec>,
+    message_rebroadcaster: MessageRebroadcaster<TConsensusSpec::Addr>,
+    transaction_manager: ConsensusTransactionManager<TConsensusSpec::TransactionExecutor, TConsensusSpec::StateStore>,
 
     state_store: TConsensusSpec::StateStore,
     leader_strategy: TConsensusSpec::LeaderStrategy,
@@ -127,6 +137,10 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             signing_service.clone(),
         );
         let transaction_manager = ConsensusTransactionManager::new(transaction_executor.clone());
+        let message_rebroadcaster = MessageRebroadcaster::spawn::<TConsensusSpec>(
+            config.consensus_constants.pacemaker_block_time,
+            outbound_messaging.clone(),
+        );
 
         Self {
             local_validator_addr: local_validator_addr.clone(),
@@ -136,7 +150,12 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             rx_new_transactions,
             rx_missing_transactions,
 
-            on_inbound_message: OnInboundMessage::new(inbound_messaging, hooks.clone()),
+            on_inbound_message: OnInboundMessage::new(
+                config.max_buffered_foreign_proposals,
+                state_store.clone(),
+                inbound_messaging,
+                hooks.clone(),
+            ),
             on_message_validate: OnMessageValidate::new(
                 config.clone(),
                 state_store.clone(),
@@ -153,6 +172,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 outbound_messaging.clone(),
                 leader_strategy.clone(),
                 pacemaker.clone_handle(),
+                message_rebroadcaster.clone(),
             ),
             on_receive_local_proposal: OnReceiveLocalProposalHandler::new(
                 state_store.clone(),
@@ -166,6 +186,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 transaction_manager.clone(),
                 config.clone(),
                 hooks.clone(),
+                message_rebroadcaster.clone(),
             ),
             on_receive_foreign_proposal: OnReceiveForeignProposalHandler::new(
                 state_store.clone(),
@@ -196,13 +217,16 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 state_store.clone(),
                 epoch_manager.clone(),
                 transaction_pool.clone(),
-                transaction_manager,
+                transaction_manager.clone(),
                 signing_service,
                 outbound_messaging.clone(),
+                hooks.clone(),
             ),
 
             on_sync_request: OnSyncRequest::new(state_store.clone(), outbound_messaging.clone()),
             on_catch_up_sync: OnCatchUpSync::new(state_store.clone(), pacemaker.clone_handle(), outbound_messaging),
+            message_rebroadcaster,
+            transaction_manager,
 
             state_store,
             leader_strategy,
@@ -221,6 +245,17 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
     }
 
     pub async fn start(&mut self) -> Result<(), HotStuffError> {
+        let local_committee_info = self.prepare().await?;
+        let local_committee = self
+            .epoch_manager
+            .get_local_committee(self.pacemaker.current_view().get_epoch())
+            .await?;
+        self.run(local_committee_info, local_committee).await?;
+        Ok(())
+    }
+
+    /// Creates the genesis block if required and starts the pacemaker from the last epoch/height
+    async fn prepare(&mut self) -> Result<CommitteeInfo, HotStuffError> {
         let current_epoch = self.epoch_manager.current_epoch().await?;
         let local_committee_info = self.epoch_manager.get_local_committee_info(current_epoch).await?;
 
@@ -244,14 +279,13 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         self.pacemaker
             .start(current_epoch, current_height, high_qc.block_height())
             .await?;
+        self.hooks.on_epoch_changed(current_epoch);
         self.publish_event(HotstuffEvent::EpochChanged {
             epoch: current_epoch,
             registered_shard_group: Some(local_committee_info.shard_group()),
         });
 
-        let local_committee = self.epoch_manager.get_local_committee(current_epoch).await?;
-        self.run(local_committee_info, local_committee).await?;
-        Ok(())
+        Ok(local_committee_info)
     }
 
     async fn run(
@@ -298,6 +332,8 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                 current_height.as_u64()
             );
 
+            let missing_transactions_flush_deadline = self.on_message_validate.missing_transactions_flush_deadline();
+
             tokio::select! {
                 Ok(event) = epoch_manager_events.recv() => {
                     self.on_epoch_manager_event(event).await?;
@@ -317,8 +353,9 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     }
                 },
 
-                Some((tx_id, pending)) = self.rx_new_transactions.recv() => {
-                    if let Err(err) = self.on_new_transaction(tx_id, pending, current_epoch, current_height, &local_committee_info, &local_committee).await {
+                Some((transaction, pending)) = self.rx_new_transactions.recv() => {
+                    let span = info_span!("transaction", transaction_id = %transaction.id());
+                    if let Err(err) = self.on_new_transaction(transaction, pending, current_epoch, current_height, &local_committee_info, &local_committee).instrument(span).await {
                         self.hooks.on_error(&err);
                         error!(target: LOG_TARGET, "🚨Error handling new transaction: {}", err);
                     }
@@ -342,6 +379,13 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     }
                 },
 
+                _ = time::sleep_until(missing_transactions_flush_deadline.unwrap_or_else(time::Instant::now)), if missing_transactions_flush_deadline.is_some() => {
+                    if let Err(err) = self.on_message_validate.flush_missing_transactions_requests().await {
+                        self.hooks.on_error(&err);
+                        error!(target: LOG_TARGET, "🚨Error requesting missing transactions: {}", err);
+                    }
+                },
+
                 _ = on_leader_timeout.wait() => {
                     if let Err(e) = self.on_leader_timeout(current_epoch, current_height,  &local_committee).await {
                         self.on_failure("on_leader_timeout", &e).await;
@@ -358,6 +402,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
 
         self.on_receive_new_view.clear_new_views();
         self.on_inbound_message.clear_buffer();
+        self.message_rebroadcaster.clear();
         // This only happens if we're shutting down.
         if let Err(err) = self.pacemaker.stop().await {
             debug!(target: LOG_TARGET, "Pacemaker channel dropped: {}", err);
@@ -436,9 +481,12 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
                     }
                 }
 
-                self.on_message_validate
-                    .request_missing_transactions(request_from_address, block_id, epoch, missing_txs)
-                    .await?;
+                self.on_message_validate.queue_missing_transactions_request(
+                    request_from_address,
+                    block_id,
+                    epoch,
+                    missing_txs,
+                );
                 Ok(())
             },
             MessageValidationResult::Discard => Ok(()),
@@ -490,6 +538,9 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         );
 
         self.hooks.on_transaction_ready(transaction.id());
+        if transaction.abort_reason().is_none() {
+            self.spawn_speculative_execution(current_epoch, *local_committee_info, transaction.transaction().clone());
+        }
 
         if self
             .check_if_block_can_be_unparked(
@@ -514,6 +565,29 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         Ok(())
     }
 
+    /// Executes the transaction against the latest committed state in the background, so that the result does not
+    /// have to be computed when the transaction is prepared.
+    fn spawn_speculative_execution(
+        &self,
+        current_epoch: Epoch,
+        local_committee_info: CommitteeInfo,
+        transaction: Transaction,
+    ) {
+        let transaction_manager = self.transaction_manager.clone();
+        let state_store = self.state_store.clone();
+        task::spawn_blocking(move || {
+            let transaction_id = *transaction.id();
+            if let Err(err) = transaction_manager.execute_speculatively(
+                &state_store,
+                current_epoch,
+                &local_committee_info,
+                transaction,
+            ) {
+                warn!(target: LOG_TARGET, "⚠️ Speculative execution of transaction {transaction_id} failed: {err}");
+            }
+        });
+    }
+
     /// Returns true if a block was unparked, otherwise false
     async fn check_if_block_can_be_unparked<
         'a,
@@ -621,6 +695,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         }
         self.on_receive_new_view.clear_new_views();
         self.on_inbound_message.clear_buffer();
+        self.message_rebroadcaster.clear();
     }
 
     /// Read and discard messages. This should be used only when consensus is inactive.
@@ -858,7 +933,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             HotstuffMessage::ForeignProposalNotification(msg) => log_err(
                 "on_receive_foreign_proposal (notification)",
                 self.on_receive_foreign_proposal
-                    .handle_notification_received(from, current_epoch, msg, local_committee_info)
+                    .handle_notification_received(from, msg, local_committee_info)
                     .await,
             ),
             HotstuffMessage::ForeignProposalRequest(msg) => log_err(
@@ -875,7 +950,7 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
             ),
             HotstuffMessage::MissingTransactionsRequest(msg) => log_err(
                 "on_receive_request_missing_transactions",
-                self.on_receive_request_missing_txs.handle(from, msg).await,
+                self.on_receive_request_missing_txs.handle(from, msg),
             ),
             HotstuffMessage::MissingTransactionsResponse(msg) => log_err(
                 "on_receive_new_transaction",
@@ -907,6 +982,9 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
         msg: ProposalMessage,
     ) -> Result<(), HotStuffError> {
         let proposed_by = msg.block.proposed_by().clone();
+        // The leader has moved on, so our last vote or NEWVIEW no longer needs to be rebroadcast
+        self.message_rebroadcaster
+            .acknowledge(msg.block.epoch(), msg.block.height());
         match log_err(
             "on_receive_local_proposal",
             self.on_receive_local_proposal
@@ -982,6 +1060,121 @@ impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
     }
 }
 
+#[cfg(feature = "test-hooks")]
+impl<TConsensusSpec: ConsensusSpec> HotstuffWorker<TConsensusSpec> {
+    /// Does the same as `start` except that the pacemaker is not spawned and the event loop is not run
+    pub(super) async fn start_stepping(&mut self) -> Result<(), HotStuffError> {
+        self.prepare().await?;
+        let current_epoch = self.pacemaker.current_view().get_epoch();
+        self.request_initial_catch_up_sync(current_epoch).await
+    }
+
+    /// Handles a single input in the same way as the event loop does. Blocks that can be unparked as a result, and
+    /// missing transaction requests that were queued, are handled before returning.
+    pub(super) async fn step(&mut self, input: StepInput<TConsensusSpec::Addr>) -> Result<(), HotStuffError> {
+        let current_epoch = self.pacemaker.current_view().get_epoch();
+        let current_height = self.pacemaker.current_view().get_height();
+        let local_committee_info = self.epoch_manager.get_local_committee_info(current_epoch).await?;
+        let local_committee = self.epoch_manager.get_local_committee(current_epoch).await?;
+
+        match input {
+            StepInput::Message { from, message } => {
+                self.hooks.on_message_received(&message);
+                let result = self
+                    .on_unvalidated_message(
+                        current_epoch,
+                        current_height,
+                        Ok((from, message)),
+                        &local_committee_info,
+                        &local_committee,
+                    )
+                    .await;
+                self.fail_on_error("on_unvalidated_message", result).await?;
+            },
+            StepInput::NewTransaction { transaction, num_pending } => {
+                if let Err(err) = self
+                    .on_new_transaction(
+                        transaction,
+                        num_pending,
+                        current_epoch,
+                        current_height,
+                        &local_committee_info,
+                        &local_committee,
+                    )
+                    .await
+                {
+                    self.hooks.on_error(&err);
+                    return Err(err);
+                }
+            },
+            StepInput::Beat => {
+                let result = self
+                    .on_beat(current_epoch, &local_committee_info, &local_committee)
+                    .await;
+                self.fail_on_error("on_beat", result).await?;
+            },
+            StepInput::ForceBeat { forced_height } => {
+                let result = self
+                    .on_force_beat(current_epoch, forced_height, &local_committee_info, &local_committee)
+                    .await;
+                self.fail_on_error("propose_if_leader", result).await?;
+            },
+            StepInput::LeaderTimeout => {
+                let result = self
+                    .on_leader_timeout(current_epoch, current_height, &local_committee)
+                    .await;
+                self.fail_on_error("on_leader_timeout", result).await?;
+            },
+        }
+
+        while let Ok(batch) = self.rx_missing_transactions.try_recv() {
+            let current_height = self.pacemaker.current_view().get_height();
+            if let Err(err) = self
+                .check_if_block_can_be_unparked(
+                    current_epoch,
+                    current_height,
+                    batch.iter(),
+                    &local_committee_info,
+                    &local_committee,
+                )
+                .await
+            {
+                self.hooks.on_error(&err);
+                return Err(err);
+            }
+        }
+
+        if self.on_message_validate.missing_transactions_flush_deadline().is_some() {
+            if let Err(err) = self.on_message_validate.flush_missing_transactions_requests().await {
+                self.hooks.on_error(&err);
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the requests that were sent to the pacemaker since the last call. The pacemaker is not spawned when
+    /// stepping, so these requests are never processed.
+    pub(super) fn take_pacemaker_requests(&mut self) -> Vec<PacemakerRequest> {
+        self.pacemaker_worker
+            .as_mut()
+            .map(|pacemaker| pacemaker.take_requests())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn subscribe_events(&self) -> broadcast::Receiver<HotstuffEvent> {
+        self.tx_events.subscribe()
+    }
+
+    async fn fail_on_error(&mut self, context: &str, result: Result<(), HotStuffError>) -> Result<(), HotStuffError> {
+        if let Err(err) = &result {
+            self.on_failure(context, err).await;
+        }
+        result
+    }
+}
+
 impl<TConsensusSpec: ConsensusSpec> Debug for HotstuffWorker<TConsensusSpec> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("HotstuffWorker")
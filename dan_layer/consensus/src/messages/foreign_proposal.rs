@@ -100,6 +100,14 @@ pub enum ForeignProposalRequestMessage {
         for_shard_group: ShardGroup,
         epoch: Epoch,
     },
+    /// Request the foreign proposals with foreign indexes in the inclusive range. This is used when the receiving
+    /// shard group detects a gap in the foreign indexes of the proposals that it has received.
+    ByForeignIndexRange {
+        for_shard_group: ShardGroup,
+        epoch: Epoch,
+        from_index: u64,
+        to_index: u64,
+    },
 }
 
 impl ForeignProposalRequestMessage {
@@ -107,6 +115,7 @@ impl ForeignProposalRequestMessage {
         match self {
             Self::ByBlockId { epoch, .. } => *epoch,
             Self::ByTransactionId { epoch, .. } => *epoch,
+            Self::ByForeignIndexRange { epoch, .. } => *epoch,
         }
     }
 }
@@ -136,6 +145,18 @@ impl Display for ForeignProposalRequestMessage {
                     epoch, for_shard_group, transaction_id
                 )
             },
+            Self::ByForeignIndexRange {
+                for_shard_group,
+                epoch,
+                from_index,
+                to_index,
+            } => {
+                write!(
+                    f,
+                    "ForeignProposalRequestMessage(ByForeignIndexRange, {}, {}, {}-{})",
+                    epoch, for_shard_group, from_index, to_index
+                )
+            },
         }
     }
 }
@@ -104,17 +104,17 @@ impl Display for HotstuffMessage {
             HotstuffMessage::MissingTransactionsRequest(msg) => {
                 write!(
                     f,
-                    "RequestMissingTransactions({} transaction(s), block: {}, epoch: {})",
+                    "RequestMissingTransactions({} transaction(s), {} block(s), epoch: {})",
                     msg.transactions.len(),
-                    msg.block_id,
+                    msg.block_ids.len(),
                     msg.epoch
                 )
             },
             HotstuffMessage::MissingTransactionsResponse(msg) => write!(
                 f,
-                "RequestedTransaction({} transaction(s), block: {}, epoch: {})",
+                "RequestedTransaction({} transaction(s), {} block(s), epoch: {})",
                 msg.transactions.len(),
-                msg.block_id,
+                msg.block_ids.len(),
                 msg.epoch
             ),
             HotstuffMessage::CatchUpSyncRequest(msg) => write!(f, "SyncRequest({})", msg.high_qc),
@@ -8,10 +8,14 @@ use tari_dan_common_types::Epoch;
 use tari_dan_storage::consensus_models::BlockId;
 use tari_transaction::TransactionId;
 
+/// The maximum number of transactions that may be requested in, or returned from, a single missing transactions request
+pub const MAX_MISSING_TRANSACTIONS_PER_REQUEST: usize = 1000;
+
 #[derive(Debug, Clone, Serialize)]
 pub struct MissingTransactionsRequest {
     pub request_id: u32,
     pub epoch: Epoch,
-    pub block_id: BlockId,
+    /// The parked blocks that the requested transactions are missing for
+    pub block_ids: Vec<BlockId>,
     pub transactions: HashSet<TransactionId>,
 }
@@ -10,6 +10,6 @@ use tari_transaction::Transaction;
 pub struct MissingTransactionsResponse {
     pub request_id: u32,
     pub epoch: Epoch,
-    pub block_id: BlockId,
+    pub block_ids: Vec<BlockId>,
     pub transactions: Vec<Transaction>,
 }
@@ -5,7 +5,7 @@ use std::fmt::Display;
 
 use serde::Serialize;
 use tari_dan_common_types::{Epoch, NodeHeight};
-use tari_dan_storage::consensus_models::{BlockId, LastSentVote, QuorumDecision, ValidatorSignature};
+use tari_dan_storage::consensus_models::{BlockHeader, BlockId, LastSentVote, QuorumDecision, ValidatorSignature};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct VoteMessage {
@@ -18,6 +18,10 @@ pub struct VoteMessage {
     pub unverified_block_height: NodeHeight,
     pub decision: QuorumDecision,
     pub signature: ValidatorSignature,
+    /// The proposer-signed header of the block that this vote is for. This allows the recipient to detect a leader
+    /// that proposed conflicting blocks at the same height to different replicas. It is not included when a previous
+    /// vote is resent.
+    pub block_header: Option<BlockHeader>,
 }
 
 impl From<LastSentVote> for VoteMessage {
@@ -28,6 +32,7 @@ impl From<LastSentVote> for VoteMessage {
             unverified_block_height: value.block_height,
             decision: value.decision,
             signature: value.signature,
+            block_header: None,
         }
     }
 }
@@ -1,14 +1,17 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_dan_common_types::NodeHeight;
-use tari_dan_storage::consensus_models::{QuorumDecision, TransactionAtom, ValidBlock};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_dan_storage::consensus_models::{Block, LeafBlock, QuorumDecision, TransactionAtom, ValidBlock};
 use tari_transaction::TransactionId;
 
 use crate::{hotstuff::HotStuffError, messages::HotstuffMessage};
 
 pub trait ConsensusHooks {
     fn on_local_block_decide(&mut self, block: &ValidBlock, decision: Option<QuorumDecision>);
+    fn on_block_proposed(&mut self, block: &Block);
+    fn on_block_committed(&mut self, block: &LeafBlock);
+    fn on_epoch_changed(&mut self, epoch: Epoch);
 
     fn on_block_validation_failed<E: ToString>(&mut self, err: &E);
     fn on_message_received(&mut self, message: &HotstuffMessage);
@@ -18,6 +21,9 @@ pub trait ConsensusHooks {
 
     fn on_needs_sync(&mut self, local_height: NodeHeight, remote_qc_height: NodeHeight);
 
+    fn on_foreign_proposals_buffered(&mut self, num_buffered: usize);
+    fn on_foreign_proposal_evicted(&mut self, block: &Block);
+
     fn on_transaction_ready(&mut self, tx_id: &TransactionId);
     fn on_transaction_finalized(&mut self, transaction: &TransactionAtom);
 }
@@ -44,6 +50,24 @@ impl<T: ConsensusHooks> ConsensusHooks for OptionalHooks<T> {
         }
     }
 
+    fn on_block_proposed(&mut self, block: &Block) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_block_proposed(block);
+        }
+    }
+
+    fn on_block_committed(&mut self, block: &LeafBlock) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_block_committed(block);
+        }
+    }
+
+    fn on_epoch_changed(&mut self, epoch: Epoch) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_epoch_changed(epoch);
+        }
+    }
+
     fn on_block_validation_failed<E: ToString>(&mut self, err: &E) {
         if let Some(inner) = self.inner.as_mut() {
             inner.on_block_validation_failed(err);
@@ -80,6 +104,18 @@ impl<T: ConsensusHooks> ConsensusHooks for OptionalHooks<T> {
         }
     }
 
+    fn on_foreign_proposals_buffered(&mut self, num_buffered: usize) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_foreign_proposals_buffered(num_buffered);
+        }
+    }
+
+    fn on_foreign_proposal_evicted(&mut self, block: &Block) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.on_foreign_proposal_evicted(block);
+        }
+    }
+
     fn on_transaction_ready(&mut self, tx_id: &TransactionId) {
         if let Some(inner) = self.inner.as_mut() {
             inner.on_transaction_ready(tx_id);
@@ -105,6 +141,12 @@ pub struct NoopHooks;
 impl ConsensusHooks for NoopHooks {
     fn on_local_block_decide(&mut self, _block: &ValidBlock, _decision: Option<QuorumDecision>) {}
 
+    fn on_block_proposed(&mut self, _block: &Block) {}
+
+    fn on_block_committed(&mut self, _block: &LeafBlock) {}
+
+    fn on_epoch_changed(&mut self, _epoch: Epoch) {}
+
     fn on_block_validation_failed<E: ToString>(&mut self, _: &E) {}
 
     fn on_message_received(&mut self, _message: &HotstuffMessage) {}
@@ -117,6 +159,10 @@ impl ConsensusHooks for NoopHooks {
 
     fn on_needs_sync(&mut self, _local_height: NodeHeight, _remote_qc_height: NodeHeight) {}
 
+    fn on_foreign_proposals_buffered(&mut self, _num_buffered: usize) {}
+
+    fn on_foreign_proposal_evicted(&mut self, _block: &Block) {}
+
     fn on_transaction_ready(&mut self, _tx_id: &TransactionId) {}
 
     fn on_transaction_finalized(&mut self, _transaction: &TransactionAtom) {}
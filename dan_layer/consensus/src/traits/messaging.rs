@@ -36,17 +36,32 @@ pub trait OutboundMessaging {
         message: T,
     ) -> impl Future<Output = Result<(), OutboundMessagingError>> + Send;
 
-    /// Send a message to a specific node
+    /// Send a message to a specific node on the lane for the message's priority (see [MessagePriority::for_message])
     fn send<T: Into<HotstuffMessage> + Send>(
         &mut self,
         to: Self::Addr,
         message: T,
+    ) -> impl Future<Output = Result<(), OutboundMessagingError>> + Send {
+        let message = message.into();
+        let priority = MessagePriority::for_message(&message);
+        self.send_with_priority(to, message, priority)
+    }
+
+    /// Send a message to a specific node on the given lane. Each lane is queued separately so that a backlog of low
+    /// priority messages does not delay high priority messages.
+    fn send_with_priority<T: Into<HotstuffMessage> + Send>(
+        &mut self,
+        to: Self::Addr,
+        message: T,
+        priority: MessagePriority,
     ) -> impl Future<Output = Result<(), OutboundMessagingError>> + Send;
 
     /// Send a direct message to all nodes in a shard group. Each message is separately queued and sent directly to each
-    /// node in a shard group.
+    /// node in a shard group on the lane for the message's priority. Implementations may batch several messages to the
+    /// same shard group into a single network frame.
     fn multicast<T, I>(
         &mut self,
+        shard_group: ShardGroup,
         addresses: I,
         message: T,
     ) -> impl Future<Output = Result<(), OutboundMessagingError>> + Send
@@ -66,6 +81,38 @@ pub trait OutboundMessaging {
         T: Into<HotstuffMessage> + Send;
 }
 
+/// The outbound lane that a message is queued on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessagePriority {
+    /// Bulk messages that are not needed for the current view to make progress
+    Low,
+    /// Consensus-critical messages e.g. votes and proposals
+    High,
+}
+
+impl MessagePriority {
+    pub fn for_message(message: &HotstuffMessage) -> Self {
+        match message {
+            HotstuffMessage::MissingTransactionsResponse(_) | HotstuffMessage::SyncResponse(_) => Self::Low,
+            HotstuffMessage::NewView(_) |
+            HotstuffMessage::Proposal(_) |
+            HotstuffMessage::ForeignProposal(_) |
+            HotstuffMessage::ForeignProposalNotification(_) |
+            HotstuffMessage::ForeignProposalRequest(_) |
+            HotstuffMessage::Vote(_) |
+            HotstuffMessage::MissingTransactionsRequest(_) |
+            HotstuffMessage::CatchUpSyncRequest(_) => Self::High,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::High => "high",
+        }
+    }
+}
+
 pub trait InboundMessaging {
     type Addr: NodeAddressable + Send;
 
@@ -8,31 +8,43 @@
 //! Use `Test::builder().debug_sql("/tmp/test{}.db")...` to create a database file for each validator
 //! where {} is replaced with the node address.
 
-use std::time::Duration;
+use std::{collections::HashSet, sync::Mutex, time::Duration};
 
 use tari_common_types::types::PrivateKey;
 use tari_consensus::{hotstuff::HotStuffError, messages::HotstuffMessage};
-use tari_dan_common_types::{optional::Optional, Epoch, LockIntent, NodeHeight, SubstateRequirement};
+use tari_dan_common_types::{
+    optional::Optional,
+    Epoch,
+    LockIntent,
+    NodeHeight,
+    SubstateRequirement,
+    VersionedSubstateId,
+};
 use tari_dan_storage::{
     consensus_models::{
         AbortReason,
         BlockId,
         Command,
         Decision,
+        EpochCheckpoint,
+        StateSnapshot,
+        SubstateRecord,
         SubstateRequirementLockIntent,
         TransactionRecord,
         VersionedSubstateIdLockIntent,
     },
     StateStore,
     StateStoreReadTransaction,
+    StorageError,
 };
-use tari_engine_types::commit_result::RejectReason;
-use tari_transaction::Transaction;
+use tari_engine_types::{commit_result::RejectReason, substate::SubstateId};
+use tari_transaction::{Transaction, TransactionId};
 
 use crate::support::{
     build_transaction_from,
     helpers,
     logging::setup_logger,
+    random_substates_ids_for_committee_generator,
     ExecuteSpec,
     Test,
     TestAddress,
@@ -853,6 +865,125 @@ async fn single_shard_input_conflict() {
     log::info!("total messages sent: {}", test.network().total_messages_sent());
 }
 
+/// Runs the transactions in a single shard group with the given number of parallel executions and returns the final
+/// decision of each transaction and the latest version of each substate that the transactions use.
+async fn run_transactions_with_parallelism(
+    max_parallel_executions: usize,
+    inputs: &[VersionedSubstateId],
+    executions: &[ExecuteSpec],
+) -> (
+    Vec<(TransactionId, Option<Decision>)>,
+    Vec<(SubstateId, Option<(u32, bool)>)>,
+) {
+    let mut test = Test::builder()
+        .add_committee(0, vec!["1", "2"])
+        .with_max_parallel_executions(max_parallel_executions)
+        .start()
+        .await;
+    test.create_substates(TestVnDestination::All, inputs);
+    for execution in executions {
+        test.add_execution_at_destination(TestVnDestination::All, execution.clone());
+        test.send_transaction_to_destination(
+            TestVnDestination::All,
+            TransactionRecord::new(execution.transaction.clone()),
+        )
+        .await;
+    }
+
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height > NodeHeight(30) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    let results = test
+        .get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let decisions = executions
+                .iter()
+                .map(|execution| {
+                    TransactionRecord::get(tx, execution.transaction.id()).map(|rec| (*rec.id(), rec.final_decision()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let substates = inputs
+                .iter()
+                .map(|input| input.substate_id())
+                .chain(executions.iter().flat_map(|execution| &execution.new_outputs))
+                .map(|id| {
+                    // Aborted transactions do not create their outputs
+                    SubstateRecord::get_latest(tx, id).optional().map(|substate| {
+                        (
+                            id.clone(),
+                            substate.map(|substate| (substate.version(), substate.is_destroyed())),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok::<_, StorageError>((decisions, substates))
+        })
+        .unwrap();
+
+    test.assert_clean_shutdown().await;
+    results
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn parallel_and_sequential_execution_produce_the_same_results() {
+    setup_logger();
+    let inputs = random_substates_ids_for_committee_generator(0, 1)
+        .take(5)
+        .map(|id| VersionedSubstateId::new(id, 0))
+        .collect::<Vec<_>>();
+    let outputs = random_substates_ids_for_committee_generator(0, 1)
+        .take(3)
+        .collect::<Vec<_>>();
+
+    let execution = |inputs: &[&VersionedSubstateId], decision: Decision, new_outputs: Vec<SubstateId>| {
+        let transaction = Transaction::builder()
+            .with_inputs(inputs.iter().map(|input| SubstateRequirement::from((*input).clone())))
+            .sign(&PrivateKey::default())
+            .build();
+        ExecuteSpec {
+            transaction,
+            decision,
+            fee: 1,
+            inputs: inputs
+                .iter()
+                .map(|input| VersionedSubstateIdLockIntent::write((*input).clone(), true).into())
+                .collect(),
+            new_outputs,
+        }
+    };
+    let executions = vec![
+        execution(&[&inputs[0]], Decision::Commit, vec![outputs[0].clone()]),
+        execution(&[&inputs[1], &inputs[2]], Decision::Commit, vec![outputs[1].clone()]),
+        // Conflicts with the previous transaction
+        execution(&[&inputs[2]], Decision::Commit, vec![]),
+        execution(
+            &[&inputs[3]],
+            Decision::Abort(AbortReason::ExecutionFailure),
+            vec![outputs[2].clone()],
+        ),
+        execution(&[&inputs[4]], Decision::Commit, vec![]),
+    ];
+
+    let sequential = run_transactions_with_parallelism(1, &inputs, &executions).await;
+    let parallel = run_transactions_with_parallelism(4, &inputs, &executions).await;
+    assert_eq!(parallel, sequential);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn epoch_change() {
     setup_logger();
@@ -911,6 +1042,170 @@ async fn epoch_change() {
     log::info!("total messages sent: {}", test.network().total_messages_sent());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn state_snapshot_created_at_epoch_end() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2"]).start().await;
+
+    test.start_epoch(Epoch(1)).await;
+    let mut remaining_txs = 10;
+
+    loop {
+        if remaining_txs > 0 {
+            test.send_transaction_to_all(Decision::Commit, 1, 5, 1).await;
+        }
+        remaining_txs -= 1;
+        if remaining_txs == 5 {
+            test.start_epoch(Epoch(2)).await;
+        }
+
+        let (_, _, epoch, height) = test.on_block_committed().await;
+        if height.as_u64() > 1 && epoch == 2u64 {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf1.height > NodeHeight(30) {
+            panic!("Epoch 2 not reached after {} blocks", leaf1.height);
+        }
+    }
+
+    // The harness takes a snapshot every epoch. Each snapshot must match the checkpoint's state root for the shard.
+    test.get_validator(&TestAddress::new("1"))
+        .state_store
+        .with_read_tx(|tx| {
+            let checkpoint = EpochCheckpoint::get(tx, Epoch(1))?;
+            assert!(!checkpoint.shard_roots().is_empty(), "No state changes in epoch 1");
+            for (shard, state_root) in checkpoint.shard_roots() {
+                let snapshot = StateSnapshot::get(tx, Epoch(1), *shard)?;
+                assert_eq!(snapshot.state_root, *state_root);
+                let substates = snapshot.get_substates(tx, 0, snapshot.num_substates + 1)?;
+                assert_eq!(substates.len() as u64, snapshot.num_substates);
+            }
+            Ok::<_, HotStuffError>(())
+        })
+        .unwrap();
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn epoch_change_mid_prepare() {
+    setup_logger();
+    let mut test = Test::builder()
+        .add_committee(0, vec!["1", "2"])
+        .add_committee(1, vec!["3", "4"])
+        .start()
+        .await;
+
+    test.start_epoch(Epoch(1)).await;
+    let (tx1, _, _) = test.send_transaction_to_all(Decision::Commit, 100, 2, 2).await;
+    let (tx2, _, _) = test.send_transaction_to_all(Decision::Commit, 100, 2, 2).await;
+
+    // Change epoch once the transactions have been proposed but before they are finalized
+    test.on_block_committed().await;
+    test.start_epoch(Epoch(2)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        let leaf1 = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        let leaf3 = test.get_validator(&TestAddress::new("3")).get_leaf_block();
+        if leaf1.height > NodeHeight(40) || leaf3.height > NodeHeight(40) {
+            panic!(
+                "Not all transaction committed after {}/{} blocks",
+                leaf1.height, leaf3.height,
+            );
+        }
+    }
+
+    // The committees are the same in both epochs, so the transactions are carried over and committed
+    test.assert_all_validators_have_decision(tx1.id(), Decision::Commit)
+        .await;
+    test.assert_all_validators_have_decision(tx2.id(), Decision::Commit)
+        .await;
+
+    test.assert_clean_shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn deferred_transaction_is_only_sequenced_from_min_epoch() {
+    setup_logger();
+    let mut test = Test::builder().add_committee(0, vec!["1", "2"]).start().await;
+
+    let inputs = test.create_substates_on_vns(TestVnDestination::All, 1);
+    let deferred = build_transaction_from(
+        Transaction::builder()
+            .with_inputs(inputs.iter().cloned().map(Into::into))
+            .with_min_epoch(Some(Epoch(2)))
+            .sign(&PrivateKey::default())
+            .build(),
+        Decision::Commit,
+    );
+    test.create_execution_at_destination_for_transaction(
+        TestVnDestination::All,
+        &deferred,
+        inputs
+            .iter()
+            .map(|input| SubstateRequirementLockIntent::write(input.clone(), input.version()))
+            .collect(),
+        vec![],
+    );
+    test.send_transaction_to_destination(TestVnDestination::All, deferred.clone())
+        .await;
+    let (tx1, _, _) = test.send_transaction_to_all(Decision::Commit, 1, 1, 1).await;
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(10) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_have_decision(tx1.id(), Decision::Commit)
+        .await;
+    // Consensus ignores the transaction until it is resubmitted (by the mempool) in the activation epoch
+    test.with_all_validators(|vn| {
+        let exists = vn
+            .state_store
+            .with_read_tx(|tx| TransactionRecord::exists(tx, deferred.id()))
+            .unwrap();
+        assert!(!exists, "{} sequenced deferred transaction early", vn.address);
+    });
+
+    test.start_epoch(Epoch(2)).await;
+    test.send_transaction_to_destination(TestVnDestination::All, deferred.clone())
+        .await;
+    test.wait_for_pool_count(TestVnDestination::All, 1).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height >= NodeHeight(30) {
+            panic!("Deferred transaction not committed after {} blocks", leaf.height);
+        }
+    }
+
+    test.assert_all_validators_have_decision(deferred.id(), Decision::Commit)
+        .await;
+
+    test.assert_clean_shutdown().await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn leader_failure_node_goes_down() {
     setup_logger();
@@ -1036,6 +1331,50 @@ async fn foreign_block_distribution() {
     test.assert_clean_shutdown().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn dropped_votes_are_rebroadcast() {
+    setup_logger();
+    let seen_votes = Mutex::new(HashSet::new());
+    let mut test = Test::builder()
+        .with_test_timeout(Duration::from_secs(60))
+        .with_message_filter(Box::new(move |from: &TestAddress, _to: &TestAddress, msg| {
+            let HotstuffMessage::Vote(vote) = msg else {
+                return true;
+            };
+            // Drop the first copy of every vote, so that the leader only receives the rebroadcast
+            !seen_votes.lock().unwrap().insert((from.clone(), vote.block_id))
+        }))
+        .add_committee(0, vec!["1", "2", "3", "4"])
+        .start()
+        .await;
+    for _ in 0..5 {
+        test.send_transaction_to_all(Decision::Commit, 1, 2, 1).await;
+    }
+
+    test.start_epoch(Epoch(1)).await;
+
+    loop {
+        test.on_block_committed().await;
+
+        if test.is_transaction_pool_empty() {
+            break;
+        }
+
+        // Leader failures would result in dummy blocks
+        let leaf = test.get_validator(&TestAddress::new("1")).get_leaf_block();
+        if leaf.height > NodeHeight(20) {
+            panic!("Not all transaction committed after {} blocks", leaf.height);
+        }
+    }
+
+    assert!(test.network().total_messages_filtered() > 0);
+    test.assert_all_validators_at_same_height().await;
+    test.assert_all_validators_committed();
+
+    test.assert_clean_shutdown().await;
+    log::info!("total messages sent: {}", test.network().total_messages_sent());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn single_shard_unversioned_inputs() {
     setup_logger();
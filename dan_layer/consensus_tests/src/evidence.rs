@@ -0,0 +1,122 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use tari_common::configuration::Network;
+use tari_common_types::types::{FixedHash, PublicKey};
+use tari_consensus::hotstuff::{validate_block_evidence, ProposalValidationError};
+use tari_dan_common_types::{
+    Epoch,
+    ExtraData,
+    NodeHeight,
+    NumPreshards,
+    PeerAddress,
+    ShardGroup,
+    SubstateAddress,
+    SubstateLockType,
+};
+use tari_dan_storage::{
+    consensus_models::{
+        Block,
+        BlockId,
+        Command,
+        Decision,
+        Evidence,
+        QcId,
+        QuorumCertificate,
+        ShardGroupEvidence,
+        TransactionAtom,
+    },
+    StateStore,
+};
+use tari_state_store_sqlite::SqliteStateStore;
+use tari_transaction::TransactionId;
+
+const NUM_PRESHARDS: NumPreshards = NumPreshards::P4;
+
+fn local_address() -> SubstateAddress {
+    SubstateAddress::zero()
+}
+
+fn foreign_address() -> SubstateAddress {
+    SubstateAddress::from_bytes(&[0xff; SubstateAddress::LENGTH]).unwrap()
+}
+
+fn create_block(justify: QuorumCertificate, evidence: Evidence) -> Block {
+    let atom = TransactionAtom {
+        id: TransactionId::new([1u8; 32]),
+        decision: Decision::Commit,
+        evidence,
+        transaction_fee: 0,
+        leader_fee: None,
+    };
+    Block::create(
+        Network::LocalNet,
+        BlockId::zero(),
+        justify,
+        NodeHeight(1),
+        Epoch(1),
+        local_address().to_shard_group(NUM_PRESHARDS, 2),
+        PublicKey::default(),
+        [Command::LocalPrepare(atom)].into(),
+        FixedHash::zero(),
+        0,
+        BTreeMap::new(),
+        None,
+        0,
+        0,
+        FixedHash::zero(),
+        ExtraData::new(),
+    )
+    .unwrap()
+}
+
+fn create_evidence(local_qc: QcId, foreign_qc: QcId) -> Evidence {
+    let local_shard_group = local_address().to_shard_group(NUM_PRESHARDS, 2);
+    let foreign_shard_group = foreign_address().to_shard_group(NUM_PRESHARDS, 2);
+    [
+        (
+            local_shard_group,
+            ShardGroupEvidence::new([(local_address(), SubstateLockType::Write)], Some(local_qc), None),
+        ),
+        (
+            foreign_shard_group,
+            ShardGroupEvidence::new([(foreign_address(), SubstateLockType::Write)], Some(foreign_qc), None),
+        ),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn it_rejects_evidence_with_unknown_or_foreign_qcs() {
+    let store = SqliteStateStore::<PeerAddress>::connect(":memory:").unwrap();
+    let local_shard_group = local_address().to_shard_group(NUM_PRESHARDS, 2);
+    let foreign_shard_group = foreign_address().to_shard_group(NUM_PRESHARDS, 2);
+    assert_ne!(local_shard_group, foreign_shard_group);
+
+    // The justify QC of the proposal has not been stored yet
+    let justify = QuorumCertificate::genesis(Epoch(1), local_shard_group);
+    let foreign_qc = QuorumCertificate::genesis(Epoch(1), foreign_shard_group);
+    store.with_write_tx(|tx| foreign_qc.insert(tx)).unwrap();
+
+    store
+        .with_read_tx(|tx| {
+            let block = create_block(justify.clone(), create_evidence(*justify.id(), *foreign_qc.id()));
+            validate_block_evidence(tx, &block, NUM_PRESHARDS).unwrap();
+
+            let unknown_qc = QcId::new([1u8; 32]);
+            let block = create_block(justify.clone(), create_evidence(*justify.id(), unknown_qc));
+            let err = validate_block_evidence(tx, &block, NUM_PRESHARDS).unwrap_err();
+            assert!(matches!(err, ProposalValidationError::InvalidEvidence { .. }), "{err}");
+
+            // The foreign QC is known but was certified by another shard group
+            let block = create_block(justify.clone(), create_evidence(*foreign_qc.id(), *foreign_qc.id()));
+            let err = validate_block_evidence(tx, &block, NUM_PRESHARDS).unwrap_err();
+            assert!(matches!(err, ProposalValidationError::InvalidEvidence { .. }), "{err}");
+
+            Ok::<_, ProposalValidationError>(())
+        })
+        .unwrap();
+}
@@ -5,8 +5,12 @@ mod consensus;
 #[cfg(test)]
 mod dummy_blocks;
 #[cfg(test)]
+mod evidence;
+#[cfg(test)]
 mod eviction_proof;
 #[cfg(test)]
+mod stepper;
+#[cfg(test)]
 mod substate_store;
 #[cfg(test)]
 mod support;
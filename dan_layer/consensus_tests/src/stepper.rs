@@ -0,0 +1,49 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_consensus::{
+    hotstuff::{HotstuffEvent, PacemakerRequest, StepInput},
+    messages::HotstuffMessage,
+};
+use tari_dan_common_types::{Epoch, NodeHeight};
+use tari_shutdown::Shutdown;
+
+use crate::support::{logging::setup_logger, Test};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn leader_proposes_when_block_timer_expires() {
+    setup_logger();
+    let shutdown = Shutdown::new();
+    // The round robin leader for height 1 is the second member of the committee
+    let (mut channels, mut stepper) = Test::builder()
+        .add_committee(0, vec!["1", "2"])
+        .build_stepper("2", Epoch(1), shutdown.to_signal())
+        .await;
+
+    let output = stepper.start().await;
+    assert!(output.is_ok(), "start failed: {:?}", output.error);
+    assert!(output
+        .events
+        .iter()
+        .any(|e| matches!(e, HotstuffEvent::EpochChanged { epoch, .. } if *epoch == Epoch(1))));
+    assert!(output
+        .pacemaker_requests
+        .iter()
+        .any(|req| matches!(req, PacemakerRequest::Start { .. })));
+    assert_eq!(stepper.current_view().get_epoch(), Epoch(1));
+
+    // Nothing to propose, so the leader waits for the block timer
+    let output = stepper.step(StepInput::Beat).await;
+    assert!(output.is_ok(), "beat failed: {:?}", output.error);
+    assert!(channels.rx_broadcast.try_recv().is_err());
+
+    let output = stepper.step(StepInput::ForceBeat { forced_height: None }).await;
+    assert!(output.is_ok(), "force beat failed: {:?}", output.error);
+    let (to, msg) = channels.rx_broadcast.try_recv().unwrap();
+    assert_eq!(to.len(), 2);
+    let HotstuffMessage::Proposal(proposal) = msg else {
+        panic!("Expected a proposal but got {msg}");
+    };
+    assert_eq!(proposal.block.height(), NodeHeight(1));
+    assert_eq!(proposal.block.epoch(), Epoch(1));
+}
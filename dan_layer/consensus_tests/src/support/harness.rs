@@ -13,7 +13,7 @@ use log::info;
 use tari_common::configuration::Network;
 use tari_consensus::{
     consensus_constants::ConsensusConstants,
-    hotstuff::{HotstuffConfig, HotstuffEvent},
+    hotstuff::{HotstuffConfig, HotstuffEvent, HotstuffStepper},
 };
 use tari_dan_common_types::{
     committee::Committee,
@@ -52,6 +52,7 @@ use crate::support::{
     network::{spawn_network, TestNetwork, TestVnDestination},
     validator::Validator,
     RoundRobinLeaderStrategy,
+    TestConsensusSpec,
     ValidatorChannels,
 };
 
@@ -164,6 +165,12 @@ impl Test {
             },
         };
 
+        self.create_substates(dest, &substate_ids);
+        substate_ids
+    }
+
+    /// Creates the given substates on the validators at the destination that hold them
+    pub fn create_substates(&self, dest: TestVnDestination, substate_ids: &[VersionedSubstateId]) {
         let substates = substate_ids
             .iter()
             .map(|id| {
@@ -196,8 +203,6 @@ impl Test {
                 })
                 .unwrap();
         });
-
-        substate_ids
     }
 
     pub fn build_outputs_for_committee(&self, committee_no: u32, num_outputs: usize) -> Vec<SubstateId> {
@@ -540,7 +545,16 @@ impl TestBuilder {
                     fee_exhaust_divisor: 20,
                     epochs_per_era: Epoch(10),
                     template_binary_max_size_bytes: 1000 * 1000 * 5,
+                    max_wasm_memory_bytes: 8 * 1024 * 1024,
+                    max_host_allocated_bytes: 16 * 1024 * 1024,
+                    max_gas_per_transaction: 100_000_000,
+                    max_wasm_memory_pages: 32,
+                    max_wasm_table_elements: 10_000,
+                    max_template_execution_time: Duration::from_secs(10),
+                    state_snapshot_interval: Epoch(1),
                 },
+                max_parallel_executions: 4,
+                max_buffered_foreign_proposals: 100,
             },
         }
     }
@@ -592,6 +606,11 @@ impl TestBuilder {
         self
     }
 
+    pub fn with_max_parallel_executions(mut self, max_parallel_executions: usize) -> Self {
+        self.config.max_parallel_executions = max_parallel_executions;
+        self
+    }
+
     pub fn modify_consensus_constants<F: FnOnce(&mut ConsensusConstants)>(mut self, f: F) -> Self {
         f(&mut self.config.consensus_constants);
         self
@@ -637,6 +656,42 @@ impl TestBuilder {
             .unzip()
     }
 
+    /// Builds the validator with the given address so that it can be driven one step at a time. The other validators in
+    /// the committees are not spawned.
+    pub async fn build_stepper(
+        self,
+        address: &'static str,
+        epoch: Epoch,
+        shutdown_signal: ShutdownSignal,
+    ) -> (ValidatorChannels, HotstuffStepper<TestConsensusSpec>) {
+        let committees = build_committees(self.committees);
+        let (tx_epoch_events, _) = broadcast::channel(10);
+        let mut epoch_manager = TestEpochManager::new(tx_epoch_events);
+        epoch_manager.add_committees(committees).await;
+        let num_committees = epoch_manager.get_num_committees(Epoch(0)).await.unwrap();
+
+        let address = TestAddress::new(address);
+        let (vn, shard_group) = epoch_manager
+            .all_validators()
+            .await
+            .into_iter()
+            .find(|(vn, _)| vn.address == address)
+            .expect("address is not a member of any committee");
+        epoch_manager.set_current_epoch(epoch, shard_group).await;
+
+        let (sk, pk) = helpers::derive_keypair_from_address(&vn.address);
+        Validator::builder()
+            .with_sql_url(self.sql_address)
+            .with_config(self.config)
+            .with_address_and_secret_key(vn.address.clone(), sk)
+            .with_shard(vn.shard_key)
+            .with_shard_group(shard_group)
+            .with_epoch_manager(epoch_manager.clone_for(vn.address, pk, vn.shard_key))
+            .with_leader_strategy(RoundRobinLeaderStrategy::new())
+            .with_num_committees(num_committees)
+            .build_stepper(shutdown_signal)
+    }
+
     pub async fn start(mut self) -> Test {
         if let Some(ref sql_file) = self.debug_sql_file {
             // Delete any previous database files
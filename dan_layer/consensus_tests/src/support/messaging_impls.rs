@@ -3,7 +3,13 @@
 
 use tari_consensus::{
     messages::HotstuffMessage,
-    traits::{InboundMessaging, InboundMessagingError, OutboundMessaging, OutboundMessagingError},
+    traits::{
+        InboundMessaging,
+        InboundMessagingError,
+        MessagePriority,
+        OutboundMessaging,
+        OutboundMessagingError,
+    },
 };
 use tari_dan_common_types::ShardGroup;
 use tari_epoch_manager::EpochManagerReader;
@@ -51,10 +57,11 @@ impl OutboundMessaging for TestOutboundMessaging {
             })
     }
 
-    async fn send<T: Into<HotstuffMessage> + Send>(
+    async fn send_with_priority<T: Into<HotstuffMessage> + Send>(
         &mut self,
         to: Self::Addr,
         message: T,
+        _priority: MessagePriority,
     ) -> Result<(), OutboundMessagingError> {
         self.tx_leader
             .send((to, message.into()))
@@ -64,7 +71,12 @@ impl OutboundMessaging for TestOutboundMessaging {
             })
     }
 
-    async fn multicast<T, I>(&mut self, addresses: I, message: T) -> Result<(), OutboundMessagingError>
+    async fn multicast<T, I>(
+        &mut self,
+        _shard_group: ShardGroup,
+        addresses: I,
+        message: T,
+    ) -> Result<(), OutboundMessagingError>
     where
         I: IntoIterator<Item = Self::Addr> + Send,
         T: Into<HotstuffMessage> + Send,
@@ -92,7 +104,7 @@ impl OutboundMessaging for TestOutboundMessaging {
             .await
             .map_err(|e| OutboundMessagingError::UpstreamError(e.into()))?
             .into_addresses();
-        self.multicast(peers, message).await
+        self.multicast(shard_group, peers, message).await
     }
 }
 
@@ -82,6 +82,9 @@ pub fn create_execution_result_for_transaction(
                     total_fee_payment: fee.try_into().unwrap(),
                     total_fees_paid: fee.try_into().unwrap(),
                     cost_breakdown: FeeBreakdown::default(),
+                    memory_usage: Default::default(),
+                gas_usage: Default::default(),
+                    gas_usage: Default::default(),
                 },
             }),
         );
@@ -103,9 +106,12 @@ pub fn create_execution_result_for_transaction(
                 total_fee_payment: fee.try_into().unwrap(),
                 total_fees_paid: fee.try_into().unwrap(),
                 cost_breakdown: FeeBreakdown::default(),
+                memory_usage: Default::default(),
+                gas_usage: Default::default(),
             },
         ),
         execution_time: Duration::from_secs(0),
+        substate_access_stats: None,
     };
 
     result
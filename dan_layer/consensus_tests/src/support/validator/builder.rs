@@ -3,7 +3,15 @@
 
 use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_consensus::{
-    hotstuff::{ConsensusCurrentState, ConsensusWorker, ConsensusWorkerContext, HotstuffConfig, HotstuffWorker},
+    hotstuff::{
+        ConsensusCurrentState,
+        ConsensusWorker,
+        ConsensusWorkerContext,
+        HotstuffConfig,
+        HotstuffEvent,
+        HotstuffStepper,
+        HotstuffWorker,
+    },
     traits::hooks::NoopHooks,
 };
 use tari_crypto::keys::PublicKey as _;
@@ -108,6 +116,56 @@ impl ValidatorBuilder {
             self.public_key
         );
 
+        let (channels, worker, tx_events) = self.build_worker(shutdown_signal.clone());
+        let epoch_manager = self.epoch_manager.as_ref().unwrap().clone_for(
+            self.address.clone(),
+            self.public_key.clone(),
+            self.shard_address,
+        );
+
+        let (tx_current_state, rx_current_state) = watch::channel(ConsensusCurrentState::default());
+        let context = ConsensusWorkerContext {
+            epoch_manager: epoch_manager.clone(),
+            hotstuff: worker,
+            state_sync: AlwaysSyncedSyncManager,
+            tx_current_state: tx_current_state.clone(),
+        };
+
+        let mut worker = ConsensusWorker::new(shutdown_signal).no_initial_delay();
+        let handle = tokio::spawn(async move { worker.run(context).await });
+
+        let validator = Validator {
+            address: self.address.clone(),
+            _shard_address: self.shard_address,
+            shard_group: self.shard_group,
+            num_committees: self.num_committees,
+            transaction_executions: self.transaction_executions.clone(),
+            state_store: channels.state_store.clone(),
+            epoch_manager,
+            events: tx_events.subscribe(),
+            current_state_machine_state: rx_current_state,
+            handle,
+        };
+        (channels, validator)
+    }
+
+    /// Builds a validator that is driven one step at a time by the test instead of being spawned
+    pub fn build_stepper(
+        &self,
+        shutdown_signal: ShutdownSignal,
+    ) -> (ValidatorChannels, HotstuffStepper<TestConsensusSpec>) {
+        let (channels, worker, _) = self.build_worker(shutdown_signal);
+        (channels, HotstuffStepper::new(worker))
+    }
+
+    fn build_worker(
+        &self,
+        shutdown_signal: ShutdownSignal,
+    ) -> (
+        ValidatorChannels,
+        HotstuffWorker<TestConsensusSpec>,
+        broadcast::Sender<HotstuffEvent>,
+    ) {
         let (tx_broadcast, rx_broadcast) = mpsc::channel(100);
         let (tx_new_transactions, rx_new_transactions) = mpsc::channel(100);
         let (tx_hs_message, rx_hs_message) = mpsc::channel(100);
@@ -146,43 +204,20 @@ impl ValidatorBuilder {
             transaction_executor,
             tx_events.clone(),
             NoopHooks,
-            shutdown_signal.clone(),
+            shutdown_signal,
         );
 
-        let (tx_current_state, rx_current_state) = watch::channel(ConsensusCurrentState::default());
-        let context = ConsensusWorkerContext {
-            epoch_manager: epoch_manager.clone(),
-            hotstuff: worker,
-            state_sync: AlwaysSyncedSyncManager,
-            tx_current_state: tx_current_state.clone(),
-        };
-
-        let mut worker = ConsensusWorker::new(shutdown_signal).no_initial_delay();
-        let handle = tokio::spawn(async move { worker.run(context).await });
-
         let channels = ValidatorChannels {
             address: self.address.clone(),
             shard_group: self.shard_group,
             num_committees: self.num_committees,
-            state_store: store.clone(),
+            state_store: store,
             tx_new_transactions,
             tx_hs_message,
             rx_broadcast,
             rx_leader,
         };
 
-        let validator = Validator {
-            address: self.address.clone(),
-            _shard_address: self.shard_address,
-            shard_group: self.shard_group,
-            num_committees: self.num_committees,
-            transaction_executions: self.transaction_executions.clone(),
-            state_store: store,
-            epoch_manager,
-            events: tx_events.subscribe(),
-            current_state_machine_state: rx_current_state,
-            handle,
-        };
-        (channels, validator)
+        (channels, worker, tx_events)
     }
 }
@@ -0,0 +1,79 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Benchmarks method calls on components with large states. Run with `cargo bench -p tari_dan_engine`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tari_engine_types::instruction::Instruction;
+use tari_template_lib::{args, models::ComponentAddress};
+use tari_template_test_tooling::TemplateTest;
+
+/// Counts heap allocations so that the allocations per call can be reported alongside the timings
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const STATE_SIZES: [u64; 3] = [100, 1_000, 10_000];
+const METHODS: [&str; 3] = ["get_item", "touch", "increment"];
+
+fn call_method(template_test: &mut TemplateTest, component_address: ComponentAddress, method: &str) {
+    let args = if method == "get_item" { args![0u64] } else { args![] };
+    // The result is not committed so that every iteration runs against the same state
+    template_test
+        .try_execute_instructions(
+            vec![],
+            vec![Instruction::CallMethod {
+                component_address,
+                method: method.to_string(),
+                args,
+            }],
+            vec![],
+        )
+        .unwrap()
+        .expect_success();
+}
+
+fn bench_large_component_state(c: &mut Criterion) {
+    let mut template_test = TemplateTest::new(["tests/templates/large_state"]);
+    let mut group = c.benchmark_group("large_component_state");
+
+    for num_items in STATE_SIZES {
+        let component_address: ComponentAddress =
+            template_test.call_function("LargeState", "new", args![num_items], vec![]);
+
+        for method in METHODS {
+            let before = ALLOCATIONS.load(Ordering::Relaxed);
+            call_method(&mut template_test, component_address, method);
+            let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+            println!("{method} with {num_items} items: {allocations} allocations per call");
+
+            group.bench_with_input(BenchmarkId::new(method, num_items), &num_items, |b, _| {
+                b.iter(|| call_method(&mut template_test, component_address, method))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_large_component_state);
+criterion_main!(benches);
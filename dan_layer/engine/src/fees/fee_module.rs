@@ -1,10 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::io;
-
-use tari_bor::encode_into_std_writer;
-use tari_engine_types::fees::FeeSource;
+use tari_engine_types::{fees::FeeSource, substate::storage_size};
 
 use super::FeeTable;
 use crate::runtime::{RuntimeModule, RuntimeModuleError, StateTracker};
@@ -26,6 +23,8 @@ impl FeeModule {
 impl RuntimeModule for FeeModule {
     fn on_initialize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
         track.add_fee_charge(FeeSource::Initial, self.initial_cost);
+        // The gas budget of the transaction is derived from its declared max fee at this price
+        track.set_gas_per_fee_unit(self.fee_table.gas_per_fee_unit());
         Ok(())
     }
 
@@ -35,13 +34,7 @@ impl RuntimeModule for FeeModule {
     }
 
     fn on_before_finalize(&self, track: &StateTracker) -> Result<(), RuntimeModuleError> {
-        let total_storage = track.with_substates_to_persist(|changes| {
-            let mut counter = ByteCounter::new();
-            for substate in changes.values() {
-                encode_into_std_writer(substate, &mut counter)?;
-            }
-            Ok::<_, RuntimeModuleError>(counter.get())
-        })?;
+        let total_storage = track.with_substates_to_persist(|changes| storage_size(changes.values()))?;
 
         // TODO: Cost per byte of storage is reduced by a pretty arbitrarily chosen factor (floor(cost/0.333...))
         const STORAGE_COST_REDUCTION_DIVISOR: u64 = 3;
@@ -58,34 +51,8 @@ impl RuntimeModule for FeeModule {
             track.num_events() as u64 * self.fee_table.per_event_cost(),
         );
 
-        Ok(())
-    }
-}
-
-// TODO: This may become available in tari_utilities in future
-#[derive(Debug, Clone, Default)]
-struct ByteCounter {
-    count: usize,
-}
-
-impl ByteCounter {
-    pub fn new() -> Self {
-        Default::default()
-    }
-
-    pub fn get(&self) -> usize {
-        self.count
-    }
-}
-
-impl io::Write for ByteCounter {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let len = buf.len();
-        self.count += len;
-        Ok(len)
-    }
+        track.add_fee_charge(FeeSource::Gas, track.gas_fee());
 
-    fn flush(&mut self) -> io::Result<()> {
         Ok(())
     }
 }
@@ -7,6 +7,8 @@ pub struct FeeTable {
     pub per_byte_storage_cost: u64,
     pub per_event_cost: u64,
     pub per_log_cost: u64,
+    /// The amount of gas that costs one unit of fee. Zero means that gas is not charged.
+    pub gas_per_fee_unit: u64,
 }
 
 impl FeeTable {
@@ -16,6 +18,7 @@ impl FeeTable {
             per_byte_storage_cost: 0,
             per_event_cost: 0,
             per_log_cost: 0,
+            gas_per_fee_unit: 0,
         }
     }
 
@@ -34,4 +37,8 @@ impl FeeTable {
     pub fn per_log_cost(&self) -> u64 {
         self.per_log_cost
     }
+
+    pub fn gas_per_fee_unit(&self) -> u64 {
+        self.gas_per_fee_unit
+    }
 }
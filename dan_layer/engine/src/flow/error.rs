@@ -7,6 +7,8 @@ use thiserror::Error;
 pub enum FlowEngineError {
     #[error("The instruction execution failed: Inner error:{inner}")]
     InstructionFailed { inner: String },
+    #[error("Invalid flow definition: {details}")]
+    InvalidDefinition { details: String },
     #[error("Missing argument: {name}")]
     MissingArgument { name: String },
     #[error(transparent)]
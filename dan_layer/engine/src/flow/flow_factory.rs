@@ -7,7 +7,7 @@ use d3ne::WorkersBuilder;
 use serde_json::Value as JsValue;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 use tari_engine_types::instruction_result::InstructionResult;
-use tari_template_abi::{ArgDef, FunctionDef, TemplateDef, TemplateDefV1, Type};
+use tari_template_abi::{FunctionDef, TemplateDef};
 
 use crate::{
     flow::{FlowContext, FlowEngineError, FlowInstance},
@@ -16,8 +16,6 @@ use crate::{
     template::LoadedTemplate,
 };
 
-pub const TARI_VERSION: &str = env!("CARGO_PKG_VERSION");
-
 #[derive(Debug, Clone)]
 pub struct FlowFactory {
     name: String,
@@ -29,23 +27,7 @@ impl FlowFactory {
     pub fn try_create<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
         flow_definition: FlowFunctionDefinition,
     ) -> Result<Self, FlowEngineError> {
-        let template_def = TemplateDef::V1(TemplateDefV1 {
-            template_name: flow_definition.name.clone(),
-            tari_version: TARI_VERSION.to_owned(),
-            functions: vec![FunctionDef {
-                name: "main".to_string(),
-                arguments: flow_definition
-                    .args
-                    .iter()
-                    .map(|a| ArgDef {
-                        name: a.name.clone(),
-                        arg_type: a.arg_type.to_type(),
-                    })
-                    .collect(),
-                output: Type::Unit,
-                is_mut: false,
-            }],
-        });
+        let template_def = flow_definition.to_template_def();
 
         let _test_build = FlowInstance::try_build(
             flow_definition.flow.clone(),
@@ -59,6 +41,15 @@ impl FlowFactory {
         })
     }
 
+    /// Parses and validates a JSON encoded [FlowFunctionDefinition]
+    pub fn try_from_json<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>>(
+        flow_json: &[u8],
+    ) -> Result<Self, FlowEngineError> {
+        let definition = serde_json::from_slice::<FlowFunctionDefinition>(flow_json)
+            .map_err(|e| FlowEngineError::InvalidDefinition { details: e.to_string() })?;
+        Self::try_create::<TTemplateProvider>(definition)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
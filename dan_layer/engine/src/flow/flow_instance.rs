@@ -34,8 +34,9 @@ impl FlowInstance {
         workers: Workers<FlowContext<TTemplateProvider>>,
     ) -> Result<Self, FlowEngineError> {
         let engine = Engine::new("tari_engine@0.1.0".to_string(), workers);
-        // dbg!(&value);
-        let nodes = engine.parse_value(value).expect("could not create engine");
+        let nodes = engine
+            .parse_value(value)
+            .map_err(|e| FlowEngineError::InvalidDefinition { details: e.to_string() })?;
         Ok(FlowInstance {
             // process: value,
             nodes,
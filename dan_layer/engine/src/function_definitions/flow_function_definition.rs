@@ -2,12 +2,38 @@
 // SPDX-License-Identifier: BSD-3-Clause
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsValue;
+use tari_template_abi::{ArgDef, FunctionDef, TemplateDef, TemplateDefV1, Type};
 
 use crate::function_definitions::FunctionArgDefinition;
 
+pub const TARI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FlowFunctionDefinition {
     pub name: String,
     pub args: Vec<FunctionArgDefinition>,
     pub flow: JsValue,
 }
+
+impl FlowFunctionDefinition {
+    /// Returns the ABI of the flow. A flow has a single `main` function.
+    pub fn to_template_def(&self) -> TemplateDef {
+        TemplateDef::V1(TemplateDefV1 {
+            template_name: self.name.clone(),
+            tari_version: TARI_VERSION.to_owned(),
+            functions: vec![FunctionDef {
+                name: "main".to_string(),
+                arguments: self
+                    .args
+                    .iter()
+                    .map(|a| ArgDef {
+                        name: a.name.clone(),
+                        arg_type: a.arg_type.to_type(),
+                    })
+                    .collect(),
+                output: Type::Unit,
+                is_mut: false,
+            }],
+        })
+    }
+}
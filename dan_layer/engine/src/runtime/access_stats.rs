@@ -0,0 +1,202 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use indexmap::IndexMap;
+use tari_engine_types::{
+    lock::LockFlag,
+    substate::{SubstateId, SubstateValue},
+    substate_access::{SubstateAccess, SubstateAccessStats, TemplateSubstateAccess},
+    TemplateAddress,
+};
+
+use crate::runtime::RuntimeError;
+
+/// The maximum number of substates included in the hot substates of the statistics
+const MAX_HOT_SUBSTATES: usize = 10;
+
+/// Records the substates that are locked and created by each template during execution. This is only used to report
+/// statistics to template authors and does not affect the execution result.
+#[derive(Debug, Clone, Default)]
+pub struct SubstateAccessState {
+    templates: BTreeMap<Option<TemplateAddress>, TemplateAccess>,
+    substates: BTreeMap<SubstateId, SubstateAccessEntry>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TemplateAccess {
+    num_reads: u64,
+    num_writes: u64,
+    substates: BTreeSet<SubstateId>,
+    bytes_read: u64,
+    bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SubstateAccessEntry {
+    num_reads: u64,
+    num_writes: u64,
+    size: u64,
+    last_writer: Option<Option<TemplateAddress>>,
+}
+
+impl SubstateAccessState {
+    /// Records a lock on an existing substate of `size` bytes. `template` is None if the lock was taken by the engine
+    /// outside of a template call.
+    pub fn record_lock(&mut self, template: Option<TemplateAddress>, id: &SubstateId, lock_flag: LockFlag, size: u64) {
+        let template_access = self.templates.entry(template).or_default();
+        template_access.substates.insert(id.clone());
+        template_access.bytes_read += size;
+        let entry = self.substates.entry(id.clone()).or_default();
+        entry.size = size;
+        if lock_flag.is_write() {
+            template_access.num_writes += 1;
+            entry.num_writes += 1;
+            entry.last_writer = Some(template);
+        } else {
+            template_access.num_reads += 1;
+            entry.num_reads += 1;
+        }
+    }
+
+    /// Records the creation of a new substate
+    pub fn record_new(&mut self, template: Option<TemplateAddress>, id: &SubstateId) {
+        let template_access = self.templates.entry(template).or_default();
+        template_access.substates.insert(id.clone());
+        template_access.num_writes += 1;
+        let entry = self.substates.entry(id.clone()).or_default();
+        entry.num_writes += 1;
+        entry.last_writer = Some(template);
+    }
+
+    /// Attributes the size of each substate that is stored by the transaction to the template that last wrote to it.
+    /// Substates that no template wrote to (e.g. fee vaults updated when fees are finalized) are attributed to the
+    /// engine.
+    pub fn record_persisted(&mut self, substates: &IndexMap<SubstateId, SubstateValue>) -> Result<(), RuntimeError> {
+        for (id, value) in substates {
+            let size = tari_bor::encoded_len(value)? as u64;
+            let entry = self.substates.entry(id.clone()).or_default();
+            entry.size = size;
+            let writer = entry.last_writer.flatten();
+            self.templates.entry(writer).or_default().bytes_written += size;
+        }
+        Ok(())
+    }
+
+    pub fn to_stats(&self) -> SubstateAccessStats {
+        let mut templates = self
+            .templates
+            .iter()
+            .map(|(template_address, access)| TemplateSubstateAccess {
+                template_address: *template_address,
+                num_reads: access.num_reads,
+                num_writes: access.num_writes,
+                num_substates: access.substates.len() as u64,
+                bytes_read: access.bytes_read,
+                bytes_written: access.bytes_written,
+            })
+            .collect::<Vec<_>>();
+        // Stable sort, so templates with equal writes remain in address order
+        templates.sort_by(|a, b| b.bytes_written.cmp(&a.bytes_written));
+
+        let mut hot_substates = self
+            .substates
+            .iter()
+            .map(|(id, entry)| SubstateAccess {
+                substate_id: id.clone(),
+                num_reads: entry.num_reads,
+                num_writes: entry.num_writes,
+                size: entry.size,
+            })
+            .collect::<Vec<_>>();
+        hot_substates.sort_by(|a, b| b.num_accesses().cmp(&a.num_accesses()));
+        hot_substates.truncate(MAX_HOT_SUBSTATES);
+
+        SubstateAccessStats {
+            templates,
+            hot_substates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tari_engine_types::component::{ComponentBody, ComponentHeader};
+    use tari_template_lib::models::ComponentAddress;
+
+    use super::*;
+
+    fn component(n: u8) -> SubstateId {
+        ComponentAddress::from_array([n; 32]).into()
+    }
+
+    #[test]
+    fn it_counts_accesses_per_template() {
+        let template_a = TemplateAddress::from_array([1; 32]);
+        let template_b = TemplateAddress::from_array([2; 32]);
+        let mut state = SubstateAccessState::default();
+        state.record_lock(None, &component(1), LockFlag::Read, 10);
+        state.record_lock(Some(template_a), &component(1), LockFlag::Write, 10);
+        state.record_lock(Some(template_a), &component(1), LockFlag::Read, 10);
+        state.record_lock(Some(template_a), &component(2), LockFlag::Read, 5);
+        state.record_new(Some(template_b), &component(3));
+
+        let stats = state.to_stats();
+        let a = stats
+            .templates
+            .iter()
+            .find(|t| t.template_address == Some(template_a))
+            .unwrap();
+        assert_eq!(a.num_reads, 2);
+        assert_eq!(a.num_writes, 1);
+        assert_eq!(a.num_substates, 2);
+        assert_eq!(a.bytes_read, 25);
+        let engine = stats.templates.iter().find(|t| t.template_address.is_none()).unwrap();
+        assert_eq!(engine.num_reads, 1);
+
+        assert_eq!(stats.hot_substates[0].substate_id, component(1));
+        assert_eq!(stats.hot_substates[0].num_accesses(), 3);
+    }
+
+    #[test]
+    fn it_attributes_persisted_bytes_to_the_last_writer() {
+        let template_a = TemplateAddress::from_array([1; 32]);
+        let template_b = TemplateAddress::from_array([2; 32]);
+        let mut state = SubstateAccessState::default();
+        state.record_lock(Some(template_a), &component(1), LockFlag::Write, 10);
+        state.record_lock(Some(template_b), &component(1), LockFlag::Write, 10);
+
+        let value = SubstateValue::Component(ComponentHeader {
+            template_address: template_b,
+            module_name: "Test".to_string(),
+            owner_key: None,
+            owner_rule: Default::default(),
+            access_rules: Default::default(),
+            entity_id: Default::default(),
+            body: ComponentBody {
+                state: tari_bor::Value::Null,
+            },
+        });
+        let size = tari_bor::encoded_len(&value).unwrap() as u64;
+        let persisted = IndexMap::from([(component(1), value.clone()), (component(2), value)]);
+        state.record_persisted(&persisted).unwrap();
+
+        let stats = state.to_stats();
+        let b = stats
+            .templates
+            .iter()
+            .find(|t| t.template_address == Some(template_b))
+            .unwrap();
+        assert_eq!(b.bytes_written, size);
+        // Not written by a template
+        let engine = stats.templates.iter().find(|t| t.template_address.is_none()).unwrap();
+        assert_eq!(engine.bytes_written, size);
+        let a = stats
+            .templates
+            .iter()
+            .find(|t| t.template_address == Some(template_a))
+            .unwrap();
+        assert_eq!(a.bytes_written, 0);
+    }
+}
@@ -50,7 +50,7 @@ use tari_template_lib::models::{
 
 use super::workspace::WorkspaceError;
 use crate::{
-    runtime::{locking::LockError, ActionIdent, RuntimeModuleError},
+    runtime::{locking::LockError, ActionIdent, MemoryKind, RuntimeModuleError, SandboxViolation},
     state_store::StateStoreError,
 };
 
@@ -112,6 +112,8 @@ pub enum RuntimeError {
     CurrentFrameError { details: String },
     #[error("Vault not found with id ({vault_id})")]
     VaultNotFound { vault_id: VaultId },
+    #[error("Vault {vault_id} is frozen")]
+    VaultFrozen { vault_id: VaultId },
     #[error("Non-fungible token not found with address {resource_address} and id {nft_id}")]
     NonFungibleNotFound {
         resource_address: ResourceAddress,
@@ -173,6 +175,22 @@ pub enum RuntimeError {
     },
     #[error("Template {template_address} not found")]
     TemplateNotFound { template_address: TemplateAddress },
+    #[error("Only the author of template {template_address} may publish a new version of it")]
+    TemplateAuthorMismatch { template_address: TemplateAddress },
+    #[error("Template {template_address} is not a version of template {expected_template_address}")]
+    TemplateLineageMismatch {
+        template_address: TemplateAddress,
+        expected_template_address: TemplateAddress,
+    },
+    #[error(
+        "Cannot migrate component {component_address} from template version {current_version} to version \
+         {new_version}. Components may only be migrated to a newer version."
+    )]
+    TemplateVersionNotNewer {
+        component_address: ComponentAddress,
+        current_version: u32,
+        new_version: u32,
+    },
     #[error("Insufficient fees paid: required {required_fee}, paid {fees_paid}")]
     InsufficientFeesPaid { required_fee: Amount, fees_paid: Amount },
     #[error("No fee checkpoint")]
@@ -199,6 +217,12 @@ pub enum RuntimeError {
         method: String,
         details: String,
     },
+    #[error("Panic! {message}")]
+    TemplateAborted {
+        template_address: TemplateAddress,
+        function: String,
+        message: String,
+    },
     #[error("Fee claim not permitted for epoch {epoch} vn address {address:.10}")]
     FeeClaimNotPermitted { epoch: Epoch, address: PublicKey },
     #[error("Virtual substate not found: {address}")]
@@ -232,6 +256,16 @@ pub enum RuntimeError {
     NoActiveCallFrame,
     #[error("Max call depth {max_depth} exceeded")]
     MaxCallDepthExceeded { max_depth: usize },
+    #[error("{kind} memory limit of {limit} bytes exceeded ({used} bytes used)")]
+    MemoryLimitExceeded { kind: MemoryKind, used: u64, limit: u64 },
+    #[error("Transaction gas limit of {limit} exceeded")]
+    OutOfGas { limit: u64 },
+    #[error("Sandbox violation: {0}")]
+    SandboxViolation(#[from] SandboxViolation),
+    #[error("Text {kind} length {len} exceeds the limit of {limit} bytes")]
+    TextLimitExceeded { kind: &'static str, len: usize, limit: usize },
+    #[error("Invalid regex: {details}")]
+    InvalidRegex { details: String },
     #[error("{action} can only be called from within a component context")]
     NotInComponentContext { action: ActionIdent },
     #[error("Duplicate bucket {bucket_id}")]
@@ -298,6 +332,15 @@ pub enum TransactionCommitError {
     DanglingProofs { count: usize },
     #[error("Locked value (amount: {locked_amount}) remaining in vault {vault_id}")]
     DanglingLockedValueInVault { vault_id: VaultId, locked_amount: Amount },
+    #[error(
+        "Flash loan from vault {vault_id} was not repaid. The vault balance is {balance} but must be at least \
+         {required_balance}"
+    )]
+    FlashLoanNotRepaid {
+        vault_id: VaultId,
+        required_balance: Amount,
+        balance: Amount,
+    },
     #[error("{count} dangling address allocations remain after transaction execution")]
     DanglingAddressAllocations { count: usize },
     #[error("{} orphaned substate(s) detected: {}", .substates.len(), .substates.join(", "))]
@@ -0,0 +1,166 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_engine_types::fees::GasUsage;
+
+use crate::runtime::RuntimeError;
+
+/// The size of a WASM memory page
+const WASM_PAGE_SIZE: u64 = 64 * 1024;
+
+/// Limits on the gas that a single transaction execution may consume
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimits {
+    /// The maximum gas that a transaction may consume, regardless of the fee that it declares
+    pub max_gas_per_transaction: u64,
+    /// The gas consumed for each page that a WASM linear memory grows by
+    pub gas_per_memory_page: u64,
+}
+
+impl Default for GasLimits {
+    fn default() -> Self {
+        Self {
+            max_gas_per_transaction: 100_000_000,
+            gas_per_memory_page: 10_000,
+        }
+    }
+}
+
+/// Tracks the gas consumed by a transaction against its budget. The budget is the maximum gas per transaction, reduced
+/// to the gas that the declared max fee pays for once a gas price has been set.
+#[derive(Debug, Clone)]
+pub struct GasState {
+    limits: GasLimits,
+    declared_max_fee: Option<u64>,
+    gas_per_fee_unit: u64,
+    instructions: u64,
+    memory_growth: u64,
+}
+
+impl GasState {
+    pub fn new(limits: GasLimits, declared_max_fee: Option<u64>) -> Self {
+        Self {
+            limits,
+            declared_max_fee,
+            gas_per_fee_unit: 0,
+            instructions: 0,
+            memory_growth: 0,
+        }
+    }
+
+    /// Sets the amount of gas that costs one unit of fee. Zero means that gas is not charged, in which case the budget
+    /// is not limited by the declared max fee.
+    pub fn set_gas_per_fee_unit(&mut self, gas_per_fee_unit: u64) {
+        self.gas_per_fee_unit = gas_per_fee_unit;
+    }
+
+    pub fn limit(&self) -> u64 {
+        match self.declared_max_fee {
+            Some(max_fee) if self.gas_per_fee_unit > 0 => max_fee
+                .saturating_mul(self.gas_per_fee_unit)
+                .min(self.limits.max_gas_per_transaction),
+            _ => self.limits.max_gas_per_transaction,
+        }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit().saturating_sub(self.total())
+    }
+
+    pub fn consume_instructions(&mut self, gas: u64) -> Result<(), RuntimeError> {
+        let consumed = gas.min(self.remaining());
+        self.instructions += consumed;
+        self.check_consumed(consumed, gas)
+    }
+
+    /// Consumes gas for a WASM linear memory that grew from `previous_bytes` to `current_bytes`. Memory that is
+    /// released is not refunded.
+    pub fn consume_memory_growth(&mut self, previous_bytes: u64, current_bytes: u64) -> Result<(), RuntimeError> {
+        let grown_pages = current_bytes.saturating_sub(previous_bytes).div_ceil(WASM_PAGE_SIZE);
+        let gas = grown_pages.saturating_mul(self.limits.gas_per_memory_page);
+        let consumed = gas.min(self.remaining());
+        self.memory_growth += consumed;
+        self.check_consumed(consumed, gas)
+    }
+
+    /// The fee charged for the gas consumed so far
+    pub fn fee(&self) -> u64 {
+        if self.gas_per_fee_unit == 0 {
+            return 0;
+        }
+        self.total().div_ceil(self.gas_per_fee_unit)
+    }
+
+    pub fn to_gas_usage(&self) -> GasUsage {
+        GasUsage {
+            instructions: self.instructions,
+            memory_growth: self.memory_growth,
+            limit: self.limit(),
+        }
+    }
+
+    fn total(&self) -> u64 {
+        self.instructions + self.memory_growth
+    }
+
+    fn check_consumed(&self, consumed: u64, requested: u64) -> Result<(), RuntimeError> {
+        if consumed < requested {
+            return Err(RuntimeError::OutOfGas { limit: self.limit() });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> GasLimits {
+        GasLimits {
+            max_gas_per_transaction: 1000,
+            gas_per_memory_page: 10,
+        }
+    }
+
+    #[test]
+    fn it_limits_the_budget_by_the_declared_max_fee() {
+        let mut state = GasState::new(limits(), Some(5));
+        assert_eq!(state.limit(), 1000);
+        state.set_gas_per_fee_unit(100);
+        assert_eq!(state.limit(), 500);
+        state.set_gas_per_fee_unit(1000);
+        assert_eq!(state.limit(), 1000);
+
+        let mut state = GasState::new(limits(), None);
+        state.set_gas_per_fee_unit(100);
+        assert_eq!(state.limit(), 1000);
+    }
+
+    #[test]
+    fn it_consumes_gas_for_instructions_and_memory_growth() {
+        let mut state = GasState::new(limits(), Some(5));
+        state.set_gas_per_fee_unit(100);
+        state.consume_instructions(150).unwrap();
+        state.consume_memory_growth(0, WASM_PAGE_SIZE + 1).unwrap();
+        // Released memory is not refunded
+        state.consume_memory_growth(2 * WASM_PAGE_SIZE, 0).unwrap();
+
+        assert_eq!(state.to_gas_usage(), GasUsage {
+            instructions: 150,
+            memory_growth: 20,
+            limit: 500,
+        });
+        assert_eq!(state.remaining(), 330);
+        assert_eq!(state.fee(), 2);
+    }
+
+    #[test]
+    fn it_errors_when_the_budget_is_exhausted() {
+        let mut state = GasState::new(limits(), None);
+        state.consume_instructions(990).unwrap();
+        let err = state.consume_instructions(11).unwrap_err();
+        assert!(matches!(err, RuntimeError::OutOfGas { limit: 1000 }));
+        assert_eq!(state.to_gas_usage().total(), 1000);
+        assert_eq!(state.remaining(), 0);
+    }
+}
@@ -20,7 +20,7 @@
 //   WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use log::{warn, *};
 use tari_common::configuration::Network;
@@ -35,14 +35,15 @@ use tari_engine_types::{
     entity_id_provider::EntityIdProvider,
     events::Event,
     hashing::{hasher32, template_hasher32, EngineHashDomainLabel},
-    indexed_value::IndexedValue,
+    indexed_value::{IndexedValue, IndexedWellKnownTypes},
     instruction_result::InstructionResult,
     lock::LockFlag,
     logs::LogEntry,
-    published_template::{PublishedTemplate, PublishedTemplateAddress},
-    resource::Resource,
-    resource_container::ResourceContainer,
+    published_template::{PublishedTemplate, PublishedTemplateAddress, TemplateType},
+    resource::{validate_metadata, Resource},
+    resource_container::{ResourceContainer, ResourceError},
     substate::{SubstateId, SubstateValue},
+    substate_access::SubstateAccessStats,
     vault::Vault,
     TemplateAddress,
 };
@@ -65,6 +66,7 @@ use tari_template_lib::{
         ConsensusAction,
         CreateComponentArg,
         CreateResourceArg,
+        FreezeVaultArg,
         GenerateRandomAction,
         InvokeResult,
         LogLevel,
@@ -77,10 +79,13 @@ use tari_template_lib::{
         ResourceAction,
         ResourceGetNonFungibleArg,
         ResourceRef,
+        ResourceUpdateMetadataArg,
         ResourceUpdateNonFungibleDataArg,
+        TextAction,
         VaultAction,
         VaultCreateProofByFungibleAmountArg,
         VaultCreateProofByNonFungiblesArg,
+        VaultFlashBorrowArg,
         VaultWithdrawArg,
         WorkspaceAction,
     },
@@ -116,9 +121,11 @@ use crate::{
         RuntimeError,
         RuntimeInterface,
         RuntimeModule,
+        SandboxLimits,
     },
     template::LoadedTemplate,
-    transaction::TransactionProcessor,
+    transaction::{TransactionError, TransactionProcessor},
+    wasm::WasmExecutionError,
 };
 
 const LOG_TARGET: &str = "tari::dan::engine::runtime::impl";
@@ -314,6 +321,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     action_ident: action.into(),
                     details: details.to_string(),
                 },
+                RuntimeError::TemplateAborted { .. } => RuntimeError::AccessDeniedAuthHook {
+                    action_ident: action.into(),
+                    details: e.to_string(),
+                },
                 _ => e,
             })?;
         // Enforce that the return type is actually empty. We cannot rely on InstructionResult::return_type field
@@ -333,10 +344,26 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
     ) -> Result<InstructionResult, RuntimeError> {
         let call_runtime = Runtime::new(Arc::new(self.clone()));
         TransactionProcessor::call_method(&*self.template_provider, &call_runtime, component_address, method, args)
-            .map_err(|e| RuntimeError::CrossTemplateCallMethodError {
-                component_address: *component_address,
-                method: method.to_string(),
-                details: e.to_string(),
+            .map_err(|e| match e {
+                TransactionError::TemplateAborted {
+                    template_address,
+                    function,
+                    message,
+                } => RuntimeError::TemplateAborted {
+                    template_address,
+                    function,
+                    message,
+                },
+                // Sandbox violations in nested calls reject the transaction in the same way as in the outer call
+                TransactionError::RuntimeError(err @ RuntimeError::SandboxViolation(_)) |
+                TransactionError::WasmExecutionError(WasmExecutionError::RuntimeError(
+                    err @ RuntimeError::SandboxViolation(_),
+                )) => err,
+                e => RuntimeError::CrossTemplateCallMethodError {
+                    component_address: *component_address,
+                    method: method.to_string(),
+                    details: e.to_string(),
+                },
             })
     }
 
@@ -355,10 +382,26 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
             function,
             args,
         )
-        .map_err(|e| RuntimeError::CrossTemplateCallFunctionError {
-            template_address: *template_address,
-            function: function.to_string(),
-            details: e.to_string(),
+        .map_err(|e| match e {
+            TransactionError::TemplateAborted {
+                template_address,
+                function,
+                message,
+            } => RuntimeError::TemplateAborted {
+                template_address,
+                function,
+                message,
+            },
+            // Sandbox violations in nested calls reject the transaction in the same way as in the outer call
+            TransactionError::RuntimeError(err @ RuntimeError::SandboxViolation(_)) |
+            TransactionError::WasmExecutionError(WasmExecutionError::RuntimeError(
+                err @ RuntimeError::SandboxViolation(_),
+            )) => err,
+            e => RuntimeError::CrossTemplateCallFunctionError {
+                template_address: *template_address,
+                function: function.to_string(),
+                details: e.to_string(),
+            },
         })
     }
 
@@ -471,6 +514,15 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         self.tracker.write_with(|state| state.load_component(address).cloned())
     }
 
+    fn get_component_template_address(&self, address: &ComponentAddress) -> Result<TemplateAddress, RuntimeError> {
+        self.invoke_modules_on_runtime_call("get_component_template_address")?;
+        self.tracker.write_with(|state| {
+            state
+                .load_component(address)
+                .map(|component| component.template_address)
+        })
+    }
+
     fn lock_component(&self, address: &ComponentAddress, lock_flag: LockFlag) -> Result<LockedSubstate, RuntimeError> {
         self.tracker.lock_substate(&SubstateId::Component(*address), lock_flag)
     }
@@ -524,6 +576,14 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         }
     }
 
+    fn get_component_scope(&self, lock: &LockedSubstate) -> Result<(EntityId, IndexedWellKnownTypes), RuntimeError> {
+        self.tracker.read_with(|state| {
+            let component = state.get_component(lock)?;
+            let component_scope = IndexedWellKnownTypes::from_value(component.state())?;
+            Ok((component.entity_id, component_scope))
+        })
+    }
+
     fn get_substate(&self, lock: &LockedSubstate) -> Result<SubstateValue, RuntimeError> {
         self.tracker.read_with(|state| {
             let (_, substate) = state.store().get_locked_substate(lock.lock_id())?;
@@ -615,7 +675,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     }
 
                     let component = state.get_component(&component_lock)?;
-                    let result = InvokeResult::encode(component.state())?;
+                    let result = InvokeResult::from_value(component.state().clone());
                     if !is_already_locked {
                         state.unlock_substate(component_lock)?;
                     }
@@ -653,10 +713,13 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                         });
                     }
 
+                    // Most mutable methods write back the state they were called with. Comparing against the current
+                    // state first avoids copying and re-indexing the component when nothing has changed.
+                    if component_state == *state.get_component(&component_lock)?.state() {
+                        return Ok(InvokeResult::unit());
+                    }
+
                     state.modify_component_with(&component_lock, |component| {
-                        if component_state == *component.state() {
-                            return false;
-                        }
                         component.body.set(component_state);
                         true
                     })?;
@@ -721,19 +784,16 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
 
                 args.assert_no_args("Component::GetTemplateAddress")?;
 
-                // The template can never change so we'll just fetch the component
-                self.tracker.read_with(|state| {
-                    let substate = state.store().get_unmodified_substate(&component_address.into())?;
-                    let component = substate
-                        .substate_value()
-                        .component()
-                        .ok_or(RuntimeError::ComponentNotFound {
-                            address: component_address,
-                        })?;
-
+                // The template may have been migrated in this transaction, so we load the current component state
+                self.tracker.write_with(|state| {
+                    let component = state.load_component(&component_address)?;
                     Ok(InvokeResult::encode(&component.template_address)?)
                 })
             },
+            ComponentAction::MigrateTemplate => Err(RuntimeError::InvalidArgument {
+                argument: "action",
+                reason: "Components can only be migrated using the MigrateComponent instruction".to_string(),
+            }),
         }
     }
 
@@ -776,6 +836,11 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     });
                 }
 
+                validate_metadata(&arg.metadata).map_err(|e| RuntimeError::InvalidArgument {
+                    argument: "CreateResourceArg",
+                    reason: e.to_string(),
+                })?;
+
                 let owner_key = match &arg.owner_rule {
                     OwnerRule::OwnedBySigner => {
                         Some(to_ristretto_public_key_bytes(&self.transaction_signer_public_key))
@@ -953,6 +1018,47 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     ))?)
                 })
             },
+            ResourceAction::FreezeVault | ResourceAction::UnfreezeVault => {
+                let is_frozen = matches!(action, ResourceAction::FreezeVault);
+                let resource_address =
+                    resource_ref
+                        .as_resource_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "resource_ref",
+                            reason: "Freeze resource action requires a resource address".to_string(),
+                        })?;
+                let arg: FreezeVaultArg = args.assert_one_arg()?;
+
+                let (maybe_auth_hook, auth_caller) = self.tracker.write_with(|state_mut| {
+                    let resource_lock =
+                        state_mut.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
+
+                    let resource = state_mut.get_resource(&resource_lock)?;
+
+                    state_mut.authorization().check_resource_access_rules(
+                        ResourceAuthAction::Freeze,
+                        resource.as_ownership(),
+                        resource.access_rules(),
+                    )?;
+
+                    let auth_hook = resource.auth_hook().cloned();
+                    let auth_caller = state_mut.get_auth_caller()?;
+
+                    state_mut.unlock_substate(resource_lock)?;
+                    Ok::<_, RuntimeError>((auth_hook, auth_caller))
+                })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Freeze)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    let vault_lock = state.lock_substate(&arg.vault_id.into(), LockFlag::Write)?;
+                    state.set_vault_frozen(&vault_lock, &resource_address, is_frozen)?;
+                    state.unlock_substate(vault_lock)?;
+                    Ok(InvokeResult::unit())
+                })
+            },
             ResourceAction::GetNonFungible => {
                 let resource_address =
                     resource_ref
@@ -1039,6 +1145,68 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::unit())
                 })
             },
+            ResourceAction::GetMetadata => {
+                let resource_address =
+                    resource_ref
+                        .as_resource_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "resource_ref",
+                            reason: "GetMetadata resource action requires a resource address".to_string(),
+                        })?;
+
+                args.assert_no_args("ResourceAction::GetMetadata")?;
+
+                self.tracker.write_with(|state| {
+                    let locked = state.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Read)?;
+                    let resource = state.get_resource(&locked)?;
+                    let metadata = resource.metadata().clone();
+                    state.unlock_substate(locked)?;
+                    Ok(InvokeResult::encode(&metadata)?)
+                })
+            },
+            ResourceAction::UpdateMetadata => {
+                let resource_address =
+                    resource_ref
+                        .as_resource_address()
+                        .ok_or_else(|| RuntimeError::InvalidArgument {
+                            argument: "resource_ref",
+                            reason: "UpdateMetadata resource action requires a resource address".to_string(),
+                        })?;
+                let arg: ResourceUpdateMetadataArg = args.assert_one_arg()?;
+
+                let (resource_lock, maybe_auth_hook, auth_caller) = self.tracker.write_with(|state_mut| {
+                    let resource_lock =
+                        state_mut.lock_substate(&SubstateId::Resource(resource_address), LockFlag::Write)?;
+
+                    let resource = state_mut.get_resource(&resource_lock)?;
+
+                    state_mut.authorization().check_resource_access_rules(
+                        ResourceAuthAction::UpdateMetadata,
+                        resource.as_ownership(),
+                        resource.access_rules(),
+                    )?;
+
+                    let auth_caller = state_mut.get_auth_caller()?;
+                    Ok::<_, RuntimeError>((resource_lock, resource.auth_hook().cloned(), auth_caller))
+                })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::UpdateMetadata)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    let resource_mut = state.get_resource_mut(&resource_lock)?;
+                    resource_mut
+                        .set_metadata_entry(arg.key, arg.value)
+                        .map_err(|e| RuntimeError::InvalidArgument {
+                            argument: "ResourceUpdateMetadataArg",
+                            reason: e.to_string(),
+                        })?;
+                    state.unlock_substate(resource_lock)?;
+
+                    Ok(InvokeResult::unit())
+                })
+            },
             ResourceAction::UpdateAccessRules => {
                 let resource_address =
                     resource_ref
@@ -1097,6 +1265,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
             }
         }
 
+        if action.is_denied_when_frozen() {
+            if let Some(vault_id) = vault_ref.vault_id() {
+                self.tracker.write_with(|state| state.check_vault_not_frozen(vault_id))?;
+            }
+        }
+
         match action {
             VaultAction::Create => {
                 let resource_address = vault_ref
@@ -1303,6 +1477,20 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(InvokeResult::encode(&balance)?)
                 })
             },
+            VaultAction::IsFrozen => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "IsFrozen vault action requires a vault id".to_string(),
+                })?;
+                args.assert_no_args("Vault::IsFrozen")?;
+
+                self.tracker.write_with(|state| {
+                    let vault_lock = state.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Read)?;
+                    let is_frozen = state.get_vault(&vault_lock)?.is_frozen();
+                    state.unlock_substate(vault_lock)?;
+                    Ok(InvokeResult::encode(&is_frozen)?)
+                })
+            },
             VaultAction::GetLockedBalance => {
                 let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
                     argument: "vault_ref",
@@ -1627,6 +1815,241 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     Ok(result)
                 })
             },
+            VaultAction::WithdrawMultiple => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "WithdrawMultiple vault action requires a vault id".to_string(),
+                })?;
+                let amounts: Vec<Amount> = args.assert_one_arg()?;
+                if amounts.iter().any(|amount| !amount.is_positive()) {
+                    return Err(RuntimeError::InvalidArgument {
+                        argument: "amounts",
+                        reason: "All withdraw amounts must be positive".to_string(),
+                    });
+                }
+                let total = amounts
+                    .iter()
+                    .try_fold(Amount::zero(), |total, amount| total.checked_add(*amount))
+                    .ok_or_else(|| RuntimeError::InvalidArgument {
+                        argument: "amounts",
+                        reason: "Total withdraw amount overflows".to_string(),
+                    })?;
+
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                    self.tracker.write_with(|state_mut| {
+                        let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
+
+                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+
+                        let resource_lock =
+                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Read)?;
+
+                        let resource = state_mut.get_resource(&resource_lock)?;
+
+                        state_mut.authorization().check_resource_access_rules(
+                            ResourceAuthAction::Withdraw,
+                            resource.as_ownership(),
+                            resource.access_rules(),
+                        )?;
+
+                        let auth_caller = state_mut.get_auth_caller()?;
+                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+                    })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Withdraw)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    // Withdraw the total in one go so that either all or none of the amounts are withdrawn
+                    let mut container = state.get_vault_mut(&vault_lock)?.withdraw(total)?;
+
+                    let mut buckets = Vec::with_capacity(amounts.len());
+                    for amount in amounts {
+                        let resource_container = container.withdraw(amount)?;
+                        // Emit a builtin event for each withdraw
+                        self.emit_vault_events(
+                            VAULT_WITHDRAW_TOPIC,
+                            vault_id,
+                            &vault_lock,
+                            amount,
+                            resource_container.resource_type(),
+                            state,
+                        )?;
+
+                        let bucket_id = state.id_provider()?.new_bucket_id();
+                        state.new_bucket(bucket_id, resource_container)?;
+                        buckets.push(tari_template_lib::models::Bucket::from_id(bucket_id));
+                    }
+
+                    state.unlock_substate(vault_lock)?;
+                    state.unlock_substate(resource_lock)?;
+
+                    Ok(InvokeResult::encode(&buckets)?)
+                })
+            },
+            VaultAction::DepositMultiple => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "DepositMultiple vault action requires a vault id".to_string(),
+                })?;
+                let bucket_ids: Vec<BucketId> = args.assert_one_arg()?;
+
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                    self.tracker.write_with(|state_mut| {
+                        let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
+
+                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+
+                        let resource_lock =
+                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Read)?;
+
+                        let resource = state_mut.get_resource(&resource_lock)?;
+
+                        state_mut.authorization().check_resource_access_rules(
+                            ResourceAuthAction::Deposit,
+                            resource.as_ownership(),
+                            resource.access_rules(),
+                        )?;
+
+                        let auth_caller = state_mut.get_auth_caller()?;
+                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+                    })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Deposit)?;
+                }
+
+                self.tracker.write_with(move |state_mut| {
+                    // Check all buckets before taking any of them so that either all or none are deposited
+                    let vault_resource_address = *state_mut.get_vault(&vault_lock)?.resource_address();
+                    let mut seen = HashSet::with_capacity(bucket_ids.len());
+                    for bucket_id in &bucket_ids {
+                        if !seen.insert(*bucket_id) {
+                            return Err(RuntimeError::DuplicateBucket { bucket_id: *bucket_id });
+                        }
+                        let bucket = state_mut.get_bucket(*bucket_id)?;
+                        // It is invalid to deposit a bucket that has locked funds
+                        if !bucket.locked_amount().is_zero() {
+                            return Err(RuntimeError::InvalidOpDepositLockedBucket {
+                                bucket_id: *bucket_id,
+                                locked_amount: bucket.locked_amount(),
+                            });
+                        }
+                        if *bucket.resource_address() != vault_resource_address {
+                            return Err(ResourceError::ResourceAddressMismatch {
+                                expected: vault_resource_address,
+                                actual: *bucket.resource_address(),
+                            }
+                            .into());
+                        }
+                    }
+
+                    for bucket_id in bucket_ids {
+                        let bucket = state_mut.take_bucket(bucket_id)?;
+
+                        // Emit a builtin event for each deposit
+                        self.emit_vault_events(
+                            VAULT_DEPOSIT_TOPIC.to_owned(),
+                            vault_id,
+                            &vault_lock,
+                            bucket.amount(),
+                            bucket.resource_type(),
+                            state_mut,
+                        )?;
+
+                        let vault_mut = state_mut.get_vault_mut(&vault_lock)?;
+                        vault_mut.deposit(bucket)?;
+                    }
+
+                    state_mut.unlock_substate(resource_lock)?;
+                    state_mut.unlock_substate(vault_lock)?;
+
+                    Ok(InvokeResult::unit())
+                })
+            },
+            VaultAction::FlashBorrow => {
+                let vault_id = vault_ref.vault_id().ok_or_else(|| RuntimeError::InvalidArgument {
+                    argument: "vault_ref",
+                    reason: "FlashBorrow vault action requires a vault id".to_string(),
+                })?;
+                let arg: VaultFlashBorrowArg = args.assert_one_arg()?;
+                if !arg.amount.is_positive() {
+                    return Err(RuntimeError::InvalidArgument {
+                        argument: "amount",
+                        reason: "Flash borrow amount must be positive".to_string(),
+                    });
+                }
+                if arg.fee.is_negative() {
+                    return Err(RuntimeError::InvalidArgument {
+                        argument: "fee",
+                        reason: "Flash borrow fee must not be negative".to_string(),
+                    });
+                }
+
+                let (vault_lock, resource_lock, maybe_auth_hook, auth_caller) =
+                    self.tracker.write_with(|state_mut| {
+                        let vault_lock = state_mut.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Write)?;
+
+                        let resource_address = state_mut.get_vault(&vault_lock)?.resource_address();
+
+                        let resource_lock =
+                            state_mut.lock_substate(&SubstateId::Resource(*resource_address), LockFlag::Read)?;
+
+                        let resource = state_mut.get_resource(&resource_lock)?;
+                        if resource.resource_type() != ResourceType::Fungible {
+                            return Err(RuntimeError::InvalidArgument {
+                                argument: "vault_ref",
+                                reason: format!(
+                                    "Only fungible resources can be flash borrowed but vault {} contains a {} resource",
+                                    vault_id,
+                                    resource.resource_type()
+                                ),
+                            });
+                        }
+
+                        state_mut.authorization().check_resource_access_rules(
+                            ResourceAuthAction::Withdraw,
+                            resource.as_ownership(),
+                            resource.access_rules(),
+                        )?;
+
+                        let auth_caller = state_mut.get_auth_caller()?;
+                        Ok::<_, RuntimeError>((vault_lock, resource_lock, resource.auth_hook().cloned(), auth_caller))
+                    })?;
+
+                if let Some(auth_hook) = maybe_auth_hook {
+                    self.invoke_resource_access_hook(auth_hook, auth_caller, ResourceAuthAction::Withdraw)?;
+                }
+
+                self.tracker.write_with(|state| {
+                    let vault_mut = state.get_vault_mut(&vault_lock)?;
+                    let balance_before = vault_mut.balance();
+                    let resource_container = vault_mut.withdraw(arg.amount)?;
+
+                    // The engine checks that the loan was repaid when the transaction is finalized
+                    state.record_flash_loan(vault_id, balance_before, arg.amount, arg.fee)?;
+
+                    // Emit a builtin event for the withdraw
+                    self.emit_vault_events(
+                        VAULT_WITHDRAW_TOPIC,
+                        vault_id,
+                        &vault_lock,
+                        arg.amount,
+                        resource_container.resource_type(),
+                        state,
+                    )?;
+
+                    let bucket_id = state.id_provider()?.new_bucket_id();
+                    state.new_bucket(bucket_id, resource_container)?;
+
+                    state.unlock_substate(vault_lock)?;
+                    state.unlock_substate(resource_lock)?;
+
+                    let bucket = tari_template_lib::models::Bucket::from_id(bucket_id);
+                    Ok(InvokeResult::encode(&bucket)?)
+                })
+            },
         }
     }
 
@@ -2142,6 +2565,13 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         }
     }
 
+    fn text_invoke(&self, action: TextAction) -> Result<InvokeResult, RuntimeError> {
+        self.invoke_modules_on_runtime_call("text_invoke")?;
+        let (result, gas) = self.tracker.text_limits().execute(action)?;
+        self.tracker.consume_wasm_gas(gas)?;
+        Ok(result)
+    }
+
     fn call_invoke(&self, action: CallAction, args: EngineArgs) -> Result<InvokeResult, RuntimeError> {
         self.invoke_modules_on_runtime_call("call_invoke")?;
         debug!(
@@ -2312,6 +2742,10 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         })
     }
 
+    fn substate_access_stats(&self) -> Option<SubstateAccessStats> {
+        self.tracker.substate_access_stats()
+    }
+
     fn check_component_access_rules(&self, method: &str, locked: &LockedSubstate) -> Result<(), RuntimeError> {
         self.tracker
             .read_with(|state| state.authorization().check_component_access_rules(method, locked))
@@ -2332,6 +2766,26 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(())
     }
 
+    fn update_wasm_memory_usage(&self, previous_bytes: u64, current_bytes: u64) -> Result<(), RuntimeError> {
+        self.tracker.update_wasm_memory_usage(previous_bytes, current_bytes)
+    }
+
+    fn record_host_allocation(&self, bytes: u64) -> Result<(), RuntimeError> {
+        self.tracker.add_host_allocation(bytes)
+    }
+
+    fn consume_wasm_gas(&self, gas: u64) -> Result<(), RuntimeError> {
+        self.tracker.consume_wasm_gas(gas)
+    }
+
+    fn remaining_gas(&self) -> u64 {
+        self.tracker.remaining_gas()
+    }
+
+    fn sandbox_limits(&self) -> &SandboxLimits {
+        self.tracker.sandbox_limits()
+    }
+
     fn builtin_template_invoke(&self, action: BuiltinTemplateAction) -> Result<InvokeResult, RuntimeError> {
         self.invoke_modules_on_runtime_call("builtin_template_invoke")?;
 
@@ -2345,7 +2799,12 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
         Ok(InvokeResult::encode(&address)?)
     }
 
-    fn publish_template(&self, template: Vec<u8>) -> Result<(), RuntimeError> {
+    fn publish_template(
+        &self,
+        template: Vec<u8>,
+        template_type: TemplateType,
+        previous_version: Option<TemplateAddress>,
+    ) -> Result<(), RuntimeError> {
         self.tracker.write_with(|state| {
             let binary_hash = template_hasher32().chain(&template).result();
             let template_address = PublishedTemplateAddress::from_hash(
@@ -2354,16 +2813,73 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate>> RuntimeInte
                     .chain(&binary_hash)
                     .result(),
             );
-            state.new_substate(
-                template_address,
-                SubstateValue::Template(PublishedTemplate { binary: template }),
-            )?;
+
+            let published = match previous_version {
+                Some(previous_address) => {
+                    let previous = state.load_published_template(&previous_address)?;
+                    if previous.author.as_ref() != Some(&self.transaction_signer_public_key) {
+                        return Err(RuntimeError::TemplateAuthorMismatch {
+                            template_address: previous_address,
+                        });
+                    }
+                    PublishedTemplate::new_version(template, template_type, previous_address.into(), previous)
+                },
+                None => PublishedTemplate::new(template, template_type, self.transaction_signer_public_key.clone()),
+            };
+
+            state.new_substate(template_address, SubstateValue::Template(published))?;
             let scope_mut = state.current_call_scope_mut()?;
             scope_mut.move_node_to_owned(&template_address.into())?;
 
             Ok(())
         })
     }
+
+    fn migrate_component_template(
+        &self,
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+    ) -> Result<(), RuntimeError> {
+        self.invoke_modules_on_runtime_call("migrate_component_template")?;
+
+        self.tracker.write_with(|state| {
+            let component_lock = state.lock_substate(&SubstateId::Component(component_address), LockFlag::Write)?;
+            let component = state.get_component(&component_lock)?;
+            state
+                .authorization()
+                .require_ownership(ComponentAction::MigrateTemplate, component.as_ownership())?;
+            let current_template_address = component.template_address;
+
+            let current = state.load_published_template(&current_template_address)?;
+            let current_lineage_root = current.lineage_root_or(current_template_address.into());
+            let current_version = current.template_version;
+
+            let new = state.load_published_template(&template_address)?;
+            if new.lineage_root_or(template_address.into()) != current_lineage_root {
+                return Err(RuntimeError::TemplateLineageMismatch {
+                    template_address,
+                    expected_template_address: current_template_address,
+                });
+            }
+            if new.template_version <= current_version {
+                return Err(RuntimeError::TemplateVersionNotNewer {
+                    component_address,
+                    current_version,
+                    new_version: new.template_version,
+                });
+            }
+
+            // The component state is not changed. New template versions are responsible for remaining compatible
+            // with the state of previous versions.
+            state.modify_component_with(&component_lock, |component| {
+                component.template_address = template_address;
+                true
+            })?;
+            state.unlock_substate(component_lock)?;
+
+            Ok(())
+        })
+    }
 }
 
 fn validate_component_access_rule_methods(
@@ -0,0 +1,142 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::Display;
+
+use tari_engine_types::fees::MemoryUsage;
+
+use crate::runtime::RuntimeError;
+
+/// Hard limits on the memory that a single transaction execution may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryLimits {
+    /// The maximum combined size of the WASM linear memories that may be in use at the same time (e.g. for nested
+    /// component calls)
+    pub max_wasm_memory_bytes: u64,
+    /// The maximum total number of bytes that the engine may allocate on behalf of the transaction
+    pub max_host_allocated_bytes: u64,
+}
+
+impl Default for MemoryLimits {
+    fn default() -> Self {
+        Self {
+            max_wasm_memory_bytes: 8 * 1024 * 1024,     // 8MiB
+            max_host_allocated_bytes: 16 * 1024 * 1024, // 16MiB
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryKind {
+    Wasm,
+    Host,
+}
+
+impl Display for MemoryKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryKind::Wasm => write!(f, "WASM"),
+            MemoryKind::Host => write!(f, "host"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MemoryState {
+    limits: MemoryLimits,
+    wasm_memory_in_use: u64,
+    peak_wasm_memory: u64,
+    host_allocated: u64,
+}
+
+impl MemoryState {
+    pub fn new(limits: MemoryLimits) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    /// Updates the memory attributed to a WASM instance that previously used `previous_bytes` and now uses
+    /// `current_bytes`. An instance that has been dropped reports a `current_bytes` of zero.
+    pub fn update_wasm_memory(&mut self, previous_bytes: u64, current_bytes: u64) -> Result<(), RuntimeError> {
+        let in_use = self
+            .wasm_memory_in_use
+            .saturating_sub(previous_bytes)
+            .saturating_add(current_bytes);
+        self.wasm_memory_in_use = in_use;
+        self.peak_wasm_memory = self.peak_wasm_memory.max(in_use);
+        if in_use > self.limits.max_wasm_memory_bytes {
+            return Err(RuntimeError::MemoryLimitExceeded {
+                kind: MemoryKind::Wasm,
+                used: in_use,
+                limit: self.limits.max_wasm_memory_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn add_host_allocation(&mut self, bytes: u64) -> Result<(), RuntimeError> {
+        self.host_allocated = self.host_allocated.saturating_add(bytes);
+        if self.host_allocated > self.limits.max_host_allocated_bytes {
+            return Err(RuntimeError::MemoryLimitExceeded {
+                kind: MemoryKind::Host,
+                used: self.host_allocated,
+                limit: self.limits.max_host_allocated_bytes,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn to_memory_usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            peak_wasm_memory_bytes: self.peak_wasm_memory,
+            host_allocated_bytes: self.host_allocated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> MemoryLimits {
+        MemoryLimits {
+            max_wasm_memory_bytes: 100,
+            max_host_allocated_bytes: 50,
+        }
+    }
+
+    #[test]
+    fn it_tracks_peak_wasm_memory_across_instances() {
+        let mut state = MemoryState::new(limits());
+        state.update_wasm_memory(0, 40).unwrap();
+        state.update_wasm_memory(0, 30).unwrap();
+        state.update_wasm_memory(30, 50).unwrap();
+        state.update_wasm_memory(50, 0).unwrap();
+        state.update_wasm_memory(40, 0).unwrap();
+
+        assert_eq!(state.to_memory_usage().peak_wasm_memory_bytes, 90);
+        assert_eq!(state.wasm_memory_in_use, 0);
+    }
+
+    #[test]
+    fn it_errors_when_limits_are_exceeded() {
+        let mut state = MemoryState::new(limits());
+        state.update_wasm_memory(0, 60).unwrap();
+        let err = state.update_wasm_memory(0, 41).unwrap_err();
+        assert!(matches!(err, RuntimeError::MemoryLimitExceeded {
+            kind: MemoryKind::Wasm,
+            used: 101,
+            limit: 100
+        }));
+
+        state.add_host_allocation(50).unwrap();
+        let err = state.add_host_allocation(1).unwrap_err();
+        assert!(matches!(err, RuntimeError::MemoryLimitExceeded {
+            kind: MemoryKind::Host,
+            used: 51,
+            limit: 50
+        }));
+    }
+}
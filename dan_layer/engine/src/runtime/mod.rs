@@ -20,6 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod access_stats;
 mod auth;
 pub use auth::{AuthParams, AuthorizationScope};
 
@@ -39,6 +40,14 @@ mod module;
 pub use module::{RuntimeModule, RuntimeModuleError};
 
 mod fee_state;
+mod gas_state;
+pub use gas_state::GasLimits;
+mod memory_state;
+pub use memory_state::{MemoryKind, MemoryLimits};
+mod sandbox;
+pub use sandbox::{SandboxLimits, SandboxViolation};
+mod text;
+pub use text::TextLimits;
 mod tracker;
 
 mod locking;
@@ -60,9 +69,12 @@ use tari_engine_types::{
     commit_result::FinalizeResult,
     component::ComponentHeader,
     confidential::ConfidentialClaim,
-    indexed_value::IndexedValue,
+    indexed_value::{IndexedValue, IndexedWellKnownTypes},
     lock::LockFlag,
+    published_template::TemplateType,
     substate::SubstateValue,
+    substate_access::SubstateAccessStats,
+    TemplateAddress,
 };
 use tari_template_lib::{
     args::{
@@ -83,6 +95,7 @@ use tari_template_lib::{
         ProofRef,
         ResourceAction,
         ResourceRef,
+        TextAction,
         VaultAction,
         WorkspaceAction,
     },
@@ -101,8 +114,14 @@ pub trait RuntimeInterface: Send + Sync {
 
     fn load_component(&self, address: &ComponentAddress) -> Result<ComponentHeader, RuntimeError>;
 
+    /// Returns the template address of the component without copying the component state
+    fn get_component_template_address(&self, address: &ComponentAddress) -> Result<TemplateAddress, RuntimeError>;
+
     fn lock_component(&self, address: &ComponentAddress, lock_flag: LockFlag) -> Result<LockedSubstate, RuntimeError>;
 
+    /// Returns the entity id and the indexed state of a locked component without copying the component state
+    fn get_component_scope(&self, lock: &LockedSubstate) -> Result<(EntityId, IndexedWellKnownTypes), RuntimeError>;
+
     fn get_substate(&self, lock: &LockedSubstate) -> Result<SubstateValue, RuntimeError>;
     fn component_invoke(
         &self,
@@ -151,6 +170,8 @@ pub trait RuntimeInterface: Send + Sync {
 
     fn generate_random_invoke(&self, action: GenerateRandomAction) -> Result<InvokeResult, RuntimeError>;
 
+    fn text_invoke(&self, action: TextAction) -> Result<InvokeResult, RuntimeError>;
+
     fn generate_uuid(&self) -> Result<[u8; 32], RuntimeError>;
 
     fn set_last_instruction_output(&self, value: IndexedValue) -> Result<(), RuntimeError>;
@@ -163,6 +184,7 @@ pub trait RuntimeInterface: Send + Sync {
     fn reset_to_fee_checkpoint(&self) -> Result<(), RuntimeError>;
     fn finalize(&self) -> Result<FinalizeResult, RuntimeError>;
     fn validate_finalized(&self) -> Result<(), RuntimeError>;
+    fn substate_access_stats(&self) -> Option<SubstateAccessStats>;
 
     fn caller_context_invoke(
         &self,
@@ -181,7 +203,27 @@ pub trait RuntimeInterface: Send + Sync {
     fn push_call_frame(&self, frame: PushCallFrame) -> Result<(), RuntimeError>;
     fn pop_call_frame(&self) -> Result<(), RuntimeError>;
 
-    fn publish_template(&self, template: Vec<u8>) -> Result<(), RuntimeError>;
+    /// Updates the memory attributed to a WASM instance from `previous_bytes` to `current_bytes`. Dropped instances
+    /// report `current_bytes` as zero.
+    fn update_wasm_memory_usage(&self, previous_bytes: u64, current_bytes: u64) -> Result<(), RuntimeError>;
+    fn record_host_allocation(&self, bytes: u64) -> Result<(), RuntimeError>;
+    /// Consumes gas for executed WASM instructions. Returns an error if the gas budget of the transaction is exhausted.
+    fn consume_wasm_gas(&self, gas: u64) -> Result<(), RuntimeError>;
+    fn remaining_gas(&self) -> u64;
+    fn sandbox_limits(&self) -> &SandboxLimits;
+
+    fn publish_template(
+        &self,
+        template: Vec<u8>,
+        template_type: TemplateType,
+        previous_version: Option<TemplateAddress>,
+    ) -> Result<(), RuntimeError>;
+
+    fn migrate_component_template(
+        &self,
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+    ) -> Result<(), RuntimeError>;
 }
 
 #[derive(Clone)]
@@ -0,0 +1,155 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use tari_template_abi::EngineOp;
+
+use crate::runtime::RuntimeError;
+
+/// Limits on the resources available to each WASM template instance. Exceeding a limit is a sandbox violation, which
+/// rejects the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxLimits {
+    /// The maximum number of 64KiB pages that the linear memory of an instance may grow to
+    pub max_memory_pages: u32,
+    /// The maximum number of elements in a table of an instance
+    pub max_table_elements: u32,
+    /// The maximum wall time of an instance, including the time spent in nested template calls. This is checked on
+    /// every engine call, the instructions executed in between are bounded by the gas limit.
+    pub max_execution_time: Duration,
+    /// The engine calls that templates are allowed to make. All engine calls are allowed if this is None.
+    pub allowed_engine_ops: Option<Vec<EngineOp>>,
+}
+
+impl SandboxLimits {
+    pub fn check_engine_op(&self, op: EngineOp) -> Result<(), RuntimeError> {
+        match &self.allowed_engine_ops {
+            Some(allowed) if !allowed.contains(&op) => Err(SandboxViolation::EngineOpNotAllowed { op }.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that an instance that started at `started_at` has not exceeded the maximum execution time
+    pub fn check_execution_time(&self, started_at: Instant) -> Result<(), RuntimeError> {
+        if started_at.elapsed() > self.max_execution_time {
+            return Err(SandboxViolation::ExecutionTimeExceeded {
+                limit: self.max_execution_time,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    pub fn check_memory_pages(&self, pages: u32) -> Result<(), RuntimeError> {
+        if pages > self.max_memory_pages {
+            return Err(SandboxViolation::MemoryLimitExceeded {
+                limit: self.max_memory_pages,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    pub fn check_table_elements(&self, elements: u32) -> Result<(), RuntimeError> {
+        if elements > self.max_table_elements {
+            return Err(SandboxViolation::TableLimitExceeded {
+                limit: self.max_table_elements,
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl Default for SandboxLimits {
+    fn default() -> Self {
+        Self {
+            max_memory_pages: 32, // 2MiB
+            max_table_elements: 10_000,
+            max_execution_time: Duration::from_secs(10),
+            allowed_engine_ops: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SandboxViolation {
+    MemoryLimitExceeded { limit: u32 },
+    TableLimitExceeded { limit: u32 },
+    ExecutionTimeExceeded { limit: Duration },
+    ImportNotAllowed { module: String, name: String },
+    EngineOpNotAllowed { op: EngineOp },
+}
+
+impl Display for SandboxViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SandboxViolation::MemoryLimitExceeded { limit } => write!(f, "memory limit of {} pages exceeded", limit),
+            SandboxViolation::TableLimitExceeded { limit } => write!(f, "table limit of {} elements exceeded", limit),
+            SandboxViolation::ExecutionTimeExceeded { limit } => {
+                write!(f, "execution time limit of {:.2?} exceeded", limit)
+            },
+            SandboxViolation::ImportNotAllowed { module, name } => {
+                write!(f, "import {}::{} is not allowed", module, name)
+            },
+            SandboxViolation::EngineOpNotAllowed { op } => write!(f, "engine call {:?} is not allowed", op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_checks_the_engine_op_allowlist() {
+        let limits = SandboxLimits::default();
+        limits.check_engine_op(EngineOp::EmitEvent).unwrap();
+
+        let limits = SandboxLimits {
+            allowed_engine_ops: Some(vec![EngineOp::EmitLog]),
+            ..Default::default()
+        };
+        limits.check_engine_op(EngineOp::EmitLog).unwrap();
+        let err = limits.check_engine_op(EngineOp::EmitEvent).unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::SandboxViolation(SandboxViolation::EngineOpNotAllowed {
+                op: EngineOp::EmitEvent
+            })
+        ));
+    }
+
+    #[test]
+    fn it_checks_the_execution_time() {
+        let limits = SandboxLimits {
+            max_execution_time: Duration::from_secs(1),
+            ..Default::default()
+        };
+        limits.check_execution_time(Instant::now()).unwrap();
+        let err = limits
+            .check_execution_time(Instant::now() - Duration::from_secs(2))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            RuntimeError::SandboxViolation(SandboxViolation::ExecutionTimeExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn it_checks_memory_and_table_sizes() {
+        let limits = SandboxLimits {
+            max_memory_pages: 2,
+            max_table_elements: 10,
+            ..Default::default()
+        };
+        limits.check_memory_pages(2).unwrap();
+        limits.check_memory_pages(3).unwrap_err();
+        limits.check_table_elements(10).unwrap();
+        limits.check_table_elements(11).unwrap_err();
+    }
+}
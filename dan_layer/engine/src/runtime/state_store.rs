@@ -1,15 +1,17 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{collections::HashMap, mem};
+use std::mem;
 
-use indexmap::IndexMap;
+use indexmap::{map::Entry, IndexMap};
 use tari_dan_common_types::optional::Optional;
 use tari_engine_types::{
     component::ComponentHeader,
     lock::{LockFlag, LockId},
+    published_template::{PublishedTemplate, PublishedTemplateAddress},
     substate::{Substate, SubstateId, SubstateValue},
     vault::Vault,
+    TemplateAddress,
 };
 use tari_template_lib::models::{ComponentAddress, VaultId};
 
@@ -21,12 +23,14 @@ use crate::{
     state_store::{memory::ReadOnlyMemoryStateStore, StateReader},
 };
 
+/// Substates that have not been mutated are read in place from the shared read-only state store. A substate is only
+/// copied into the working set the first time it is mutated, so locking and reading large components does not copy
+/// their state.
 #[derive(Debug, Clone)]
 pub struct WorkingStateStore {
     // This must be ordered deterministically since we use this to create the substate diff
     new_substates: IndexMap<SubstateId, SubstateValue>,
 
-    loaded_substates: HashMap<SubstateId, SubstateValue>,
     locked_substates: LockedSubstates,
 
     state_store: ReadOnlyMemoryStateStore,
@@ -36,7 +40,6 @@ impl WorkingStateStore {
     pub fn new(state_store: ReadOnlyMemoryStateStore) -> Self {
         Self {
             new_substates: IndexMap::new(),
-            loaded_substates: HashMap::new(),
             locked_substates: Default::default(),
             state_store,
         }
@@ -47,7 +50,6 @@ impl WorkingStateStore {
             return Err(RuntimeError::SubstateNotFound { id: address.clone() });
         }
         let lock_id = self.locked_substates.try_lock(address, lock_flag)?;
-        self.load(address)?;
         Ok(lock_id)
     }
 
@@ -74,18 +76,21 @@ impl WorkingStateStore {
         callback: F,
     ) -> Result<Option<R>, RuntimeError> {
         let lock = self.locked_substates.get(lock_id, LockFlag::Write)?;
-        if let Some(mut substate) = self.loaded_substates.remove(lock.address()) {
+        if !self.new_substates.contains_key(lock.address()) {
+            let mut substate = self
+                .get_unmodified_ref(lock.address())
+                .ok_or_else(|| LockError::SubstateNotLocked {
+                    address: lock.address().clone(),
+                })?
+                .clone();
             return match callback(lock.address(), &mut substate)? {
                 Some(ret) => {
                     self.new_substates.insert(lock.address().clone(), substate);
                     Ok(Some(ret))
                 },
-                None => {
-                    // It is undefined to mutate the state and return None from the callback. We do not assert this
-                    // however which is risky.
-                    self.loaded_substates.insert(lock.address().clone(), substate);
-                    Ok(None)
-                },
+                // It is undefined to mutate the state and return None from the callback. We do not assert this
+                // however which is risky.
+                None => Ok(None),
             };
         }
 
@@ -109,30 +114,37 @@ impl WorkingStateStore {
     fn get_ref(&self, address: &SubstateId) -> Result<&SubstateValue, LockError> {
         self.new_substates
             .get(address)
-            .or_else(|| self.loaded_substates.get(address))
+            .or_else(|| self.get_unmodified_ref(address))
             .ok_or_else(|| LockError::SubstateNotLocked {
                 address: address.clone(),
             })
     }
 
-    fn get_for_mut(&mut self, address: &SubstateId) -> Result<&mut SubstateValue, LockError> {
-        if let Some(substate) = self.loaded_substates.remove(address) {
-            self.new_substates.insert(address.clone(), substate);
-        }
+    fn get_unmodified_ref(&self, address: &SubstateId) -> Option<&SubstateValue> {
+        self.state_store
+            .get_state(address)
+            .ok()
+            .map(|substate| substate.substate_value())
+    }
 
-        if let Some(substate_mut) = self.new_substates.get_mut(address) {
-            return Ok(substate_mut);
+    fn get_for_mut(&mut self, address: &SubstateId) -> Result<&mut SubstateValue, LockError> {
+        match self.new_substates.entry(address.clone()) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                // Copy on write
+                let substate = self
+                    .state_store
+                    .get_state(address)
+                    .map_err(|_| LockError::SubstateNotLocked {
+                        address: address.clone(),
+                    })?;
+                Ok(entry.insert(substate.substate_value().clone()))
+            },
         }
-
-        Err(LockError::SubstateNotLocked {
-            address: address.clone(),
-        })
     }
 
     pub fn exists(&self, id: &SubstateId) -> Result<bool, RuntimeError> {
-        let exists = self.new_substates.contains_key(id) ||
-            self.loaded_substates.contains_key(id) ||
-            self.state_store.exists(id)?;
+        let exists = self.new_substates.contains_key(id) || self.state_store.exists(id)?;
         Ok(exists)
     }
 
@@ -144,21 +156,17 @@ impl WorkingStateStore {
         Ok(())
     }
 
-    fn load(&mut self, id: &SubstateId) -> Result<(), RuntimeError> {
-        if self.new_substates.contains_key(id) {
-            return Ok(());
-        }
-        if self.loaded_substates.contains_key(id) {
-            return Ok(());
+    /// Returns the current value of the substate without locking or copying it
+    fn load(&self, id: &SubstateId) -> Result<&SubstateValue, RuntimeError> {
+        if let Some(substate) = self.new_substates.get(id) {
+            return Ok(substate);
         }
         let substate = self
             .state_store
             .get_state(id)
             .optional()?
             .ok_or_else(|| RuntimeError::SubstateNotFound { id: id.clone() })?;
-        let substate = substate.substate_value().clone();
-        self.loaded_substates.insert(id.clone(), substate);
-        Ok(())
+        Ok(substate.substate_value())
     }
 
     pub fn take_mutated_substates(&mut self) -> IndexMap<SubstateId, SubstateValue> {
@@ -181,16 +189,40 @@ impl WorkingStateStore {
     }
 
     /// Load and get the component without a lock
-    pub fn load_component(&mut self, address: &ComponentAddress) -> Result<&ComponentHeader, RuntimeError> {
+    pub fn load_component(&self, address: &ComponentAddress) -> Result<&ComponentHeader, RuntimeError> {
         let addr = SubstateId::Component(*address);
-        self.load(&addr)?;
-        let component = self.get_ref(&addr)?;
+        let component = self.load(&addr)?;
         component.component().ok_or_else(|| RuntimeError::InvariantError {
             function: "load_component",
             details: format!("Substate at address {} is not a component", addr),
         })
     }
 
+    /// Load and get the vault without a lock
+    pub fn load_vault(&self, vault_id: &VaultId) -> Result<&Vault, RuntimeError> {
+        let addr = SubstateId::Vault(*vault_id);
+        let vault = self.load(&addr)?;
+        vault.vault().ok_or_else(|| RuntimeError::InvariantError {
+            function: "load_vault",
+            details: format!("Substate at address {} is not a vault", addr),
+        })
+    }
+
+    /// Load and get a published template without a lock
+    pub fn load_published_template(
+        &self,
+        template_address: &TemplateAddress,
+    ) -> Result<&PublishedTemplate, RuntimeError> {
+        let addr = SubstateId::Template(PublishedTemplateAddress::from_hash(*template_address));
+        let template = self.load(&addr)?;
+        template
+            .published_template()
+            .ok_or_else(|| RuntimeError::InvariantError {
+                function: "load_published_template",
+                details: format!("Substate at address {} is not a published template", addr),
+            })
+    }
+
     pub(super) fn get_unmodified_substate(&self, address: &SubstateId) -> Result<&Substate, RuntimeError> {
         self.state_store
             .get_state(address)
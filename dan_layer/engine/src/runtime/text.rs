@@ -0,0 +1,214 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use regex::{Regex, RegexBuilder};
+use tari_template_lib::args::{InvokeResult, NormalizationForm, TextAction};
+use unicode_normalization::UnicodeNormalization;
+
+use crate::runtime::RuntimeError;
+
+/// Limits on the text utilities that are available to templates. These bound the work done by the engine for a single
+/// call, so that every validator produces the same result or the same error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimits {
+    /// The maximum length in bytes of an input or output text
+    pub max_text_len: usize,
+    /// The maximum length in bytes of a regex pattern or replacement
+    pub max_pattern_len: usize,
+    /// The maximum size in bytes of a compiled regex
+    pub max_compiled_regex_size: usize,
+    /// The gas consumed for each byte of input and output text
+    pub gas_per_byte: u64,
+}
+
+impl TextLimits {
+    /// Executes a text action and returns the result along with the gas that it consumed
+    pub fn execute(&self, action: TextAction) -> Result<(InvokeResult, u64), RuntimeError> {
+        match action {
+            TextAction::RegexIsMatch { pattern, text } => {
+                self.check_text_len(&text)?;
+                let regex = self.compile_regex(&pattern)?;
+                let gas = self.gas_for(&[&pattern, &text]);
+                Ok((InvokeResult::encode(&regex.is_match(&text))?, gas))
+            },
+            TextAction::RegexFind { pattern, text } => {
+                self.check_text_len(&text)?;
+                let regex = self.compile_regex(&pattern)?;
+                let found = regex.find(&text).map(|m| m.as_str().to_string());
+                let gas = self.gas_for(&[&pattern, &text]);
+                Ok((InvokeResult::encode(&found)?, gas))
+            },
+            TextAction::RegexReplaceAll {
+                pattern,
+                text,
+                replacement,
+            } => {
+                self.check_text_len(&text)?;
+                self.check_pattern_len("replacement", &replacement)?;
+                let regex = self.compile_regex(&pattern)?;
+                let replaced = regex.replace_all(&text, replacement.as_str());
+                self.check_text_len(&replaced)?;
+                let gas = self.gas_for(&[&pattern, &text, &replacement, &replaced]);
+                Ok((InvokeResult::encode(&replaced)?, gas))
+            },
+            TextAction::Normalize { text, form } => {
+                self.check_text_len(&text)?;
+                let normalized = match form {
+                    NormalizationForm::Nfc => text.nfc().collect::<String>(),
+                    NormalizationForm::Nfd => text.nfd().collect(),
+                    NormalizationForm::Nfkc => text.nfkc().collect(),
+                    NormalizationForm::Nfkd => text.nfkd().collect(),
+                };
+                self.check_text_len(&normalized)?;
+                let gas = self.gas_for(&[&text, &normalized]);
+                Ok((InvokeResult::encode(&normalized)?, gas))
+            },
+        }
+    }
+
+    fn compile_regex(&self, pattern: &str) -> Result<Regex, RuntimeError> {
+        self.check_pattern_len("pattern", pattern)?;
+        RegexBuilder::new(pattern)
+            .size_limit(self.max_compiled_regex_size)
+            .dfa_size_limit(self.max_compiled_regex_size)
+            .build()
+            .map_err(|err| RuntimeError::InvalidRegex {
+                details: err.to_string(),
+            })
+    }
+
+    fn check_text_len(&self, text: &str) -> Result<(), RuntimeError> {
+        if text.len() > self.max_text_len {
+            return Err(RuntimeError::TextLimitExceeded {
+                kind: "text",
+                len: text.len(),
+                limit: self.max_text_len,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_pattern_len(&self, kind: &'static str, pattern: &str) -> Result<(), RuntimeError> {
+        if pattern.len() > self.max_pattern_len {
+            return Err(RuntimeError::TextLimitExceeded {
+                kind,
+                len: pattern.len(),
+                limit: self.max_pattern_len,
+            });
+        }
+        Ok(())
+    }
+
+    fn gas_for(&self, texts: &[&str]) -> u64 {
+        let bytes = texts.iter().map(|t| t.len() as u64).sum::<u64>();
+        bytes.saturating_mul(self.gas_per_byte)
+    }
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        Self {
+            max_text_len: 64 * 1024,
+            max_pattern_len: 1024,
+            max_compiled_regex_size: 256 * 1024,
+            gas_per_byte: 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execute<T: serde::de::DeserializeOwned>(limits: &TextLimits, action: TextAction) -> (T, u64) {
+        let (result, gas) = limits.execute(action).unwrap();
+        (result.decode().unwrap(), gas)
+    }
+
+    #[test]
+    fn it_executes_regex_actions() {
+        let limits = TextLimits::default();
+        let (is_match, gas) = execute::<bool>(&limits, TextAction::RegexIsMatch {
+            pattern: "^[a-z]+$".to_string(),
+            text: "hello".to_string(),
+        });
+        assert!(is_match);
+        assert_eq!(gas, 13 * limits.gas_per_byte);
+
+        let (found, _) = execute::<Option<String>>(&limits, TextAction::RegexFind {
+            pattern: r"\d+".to_string(),
+            text: "abc 123 456".to_string(),
+        });
+        assert_eq!(found.as_deref(), Some("123"));
+
+        let (replaced, _) = execute::<String>(&limits, TextAction::RegexReplaceAll {
+            pattern: r"(\w+)@(\w+)".to_string(),
+            text: "alice@tari bob@dan".to_string(),
+            replacement: "$2:$1".to_string(),
+        });
+        assert_eq!(replaced, "tari:alice dan:bob");
+    }
+
+    #[test]
+    fn it_normalizes_unicode() {
+        let limits = TextLimits::default();
+        let (nfc, _) = execute::<String>(&limits, TextAction::Normalize {
+            text: "e\u{301}".to_string(),
+            form: NormalizationForm::Nfc,
+        });
+        assert_eq!(nfc, "\u{e9}");
+        let (nfkd, _) = execute::<String>(&limits, TextAction::Normalize {
+            text: "\u{fb01}".to_string(),
+            form: NormalizationForm::Nfkd,
+        });
+        assert_eq!(nfkd, "fi");
+    }
+
+    #[test]
+    fn it_errors_when_limits_are_exceeded() {
+        let limits = TextLimits {
+            max_text_len: 4,
+            max_pattern_len: 4,
+            max_compiled_regex_size: 1024,
+            gas_per_byte: 1,
+        };
+        let err = limits
+            .execute(TextAction::RegexIsMatch {
+                pattern: "a".to_string(),
+                text: "aaaaa".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::TextLimitExceeded {
+            kind: "text",
+            len: 5,
+            limit: 4
+        }));
+
+        let err = limits
+            .execute(TextAction::RegexIsMatch {
+                pattern: "a{1000}".to_string(),
+                text: "a".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::TextLimitExceeded { kind: "pattern", .. }));
+
+        // The compiled size of a short pattern can exceed the limit
+        let err = limits
+            .execute(TextAction::RegexIsMatch {
+                pattern: r"\w{9}".to_string(),
+                text: "a".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::InvalidRegex { .. }));
+
+        // Replacements are checked against the output length
+        let err = limits
+            .execute(TextAction::RegexReplaceAll {
+                pattern: "a".to_string(),
+                text: "aaa".to_string(),
+                replacement: "bb".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(err, RuntimeError::TextLimitExceeded { len: 6, .. }));
+    }
+}
@@ -38,6 +38,7 @@ use tari_engine_types::{
     lock::LockFlag,
     logs::LogEntry,
     substate::{SubstateId, SubstateValue},
+    substate_access::SubstateAccessStats,
     virtual_substate::VirtualSubstates,
     TemplateAddress,
 };
@@ -50,8 +51,13 @@ use tari_template_lib::{
 
 use crate::{
     runtime::{
+        access_stats::SubstateAccessState,
         locking::LockedSubstate,
+        gas_state::{GasLimits, GasState},
+        memory_state::{MemoryLimits, MemoryState},
+        sandbox::SandboxLimits,
         scope::{CallScope, PushCallFrame},
+        text::TextLimits,
         working_state::WorkingState,
         workspace::Workspace,
         RuntimeError,
@@ -65,6 +71,14 @@ const LOG_TARGET: &str = "tari::dan::engine::runtime::state_tracker";
 pub struct StateTracker {
     working_state: Arc<RwLock<WorkingState>>,
     fee_checkpoint: Arc<Mutex<Option<WorkingState>>>,
+    /// Memory accounting is not part of the working state so that usage is preserved across fee checkpoint resets
+    memory_state: Arc<Mutex<MemoryState>>,
+    /// Gas consumed before a fee checkpoint reset is still charged
+    gas_state: Arc<Mutex<GasState>>,
+    sandbox_limits: Arc<SandboxLimits>,
+    text_limits: TextLimits,
+    /// Substate access statistics, if enabled. These are not reset by a fee checkpoint reset.
+    access_stats: Option<Arc<Mutex<SubstateAccessState>>>,
 }
 
 impl StateTracker {
@@ -73,15 +87,28 @@ impl StateTracker {
         virtual_substates: VirtualSubstates,
         initial_call_scope: CallScope,
         transaction_hash: Hash,
+        memory_limits: MemoryLimits,
+        gas_limits: GasLimits,
+        declared_max_fee: Option<u64>,
+        sandbox_limits: SandboxLimits,
+        text_limits: TextLimits,
+        collect_access_stats: bool,
     ) -> Self {
+        let access_stats = collect_access_stats.then(|| Arc::new(Mutex::new(SubstateAccessState::default())));
         Self {
             working_state: Arc::new(RwLock::new(WorkingState::new(
                 state_store,
                 virtual_substates,
                 initial_call_scope,
                 transaction_hash,
+                access_stats.clone(),
             ))),
             fee_checkpoint: Arc::new(Mutex::new(None)),
+            memory_state: Arc::new(Mutex::new(MemoryState::new(memory_limits))),
+            gas_state: Arc::new(Mutex::new(GasState::new(gas_limits, declared_max_fee))),
+            sandbox_limits: Arc::new(sandbox_limits),
+            text_limits,
+            access_stats,
         }
     }
 
@@ -284,10 +311,16 @@ impl StateTracker {
             });
         }
         // Resolve the transfers to the fee pool resource and vault refunds
-        let transaction_receipt = state.finalize_fees(&mut substates_to_persist)?;
+        let mut transaction_receipt = state.finalize_fees(&mut substates_to_persist)?;
+        transaction_receipt.fee_receipt.memory_usage = self.memory_state.lock().unwrap().to_memory_usage();
+        transaction_receipt.fee_receipt.gas_usage = self.gas_state.lock().unwrap().to_gas_usage();
 
         let fee_receipt = transaction_receipt.fee_receipt.clone();
 
+        if let Some(access_stats) = &self.access_stats {
+            access_stats.lock().unwrap().record_persisted(&substates_to_persist)?;
+        }
+
         let result = state.generate_substate_diff(transaction_receipt, substates_to_persist);
 
         let result = match result {
@@ -306,6 +339,53 @@ impl StateTracker {
         Ok(finalized)
     }
 
+    /// Returns the substate access statistics, if they were collected
+    pub fn substate_access_stats(&self) -> Option<SubstateAccessStats> {
+        self.access_stats
+            .as_ref()
+            .map(|access_stats| access_stats.lock().unwrap().to_stats())
+    }
+
+    pub fn update_wasm_memory_usage(&self, previous_bytes: u64, current_bytes: u64) -> Result<(), RuntimeError> {
+        self.memory_state
+            .lock()
+            .unwrap()
+            .update_wasm_memory(previous_bytes, current_bytes)?;
+        self.gas_state
+            .lock()
+            .unwrap()
+            .consume_memory_growth(previous_bytes, current_bytes)
+    }
+
+    pub fn add_host_allocation(&self, bytes: u64) -> Result<(), RuntimeError> {
+        self.memory_state.lock().unwrap().add_host_allocation(bytes)
+    }
+
+    pub fn consume_wasm_gas(&self, gas: u64) -> Result<(), RuntimeError> {
+        self.gas_state.lock().unwrap().consume_instructions(gas)
+    }
+
+    pub fn remaining_gas(&self) -> u64 {
+        self.gas_state.lock().unwrap().remaining()
+    }
+
+    pub fn set_gas_per_fee_unit(&self, gas_per_fee_unit: u64) {
+        self.gas_state.lock().unwrap().set_gas_per_fee_unit(gas_per_fee_unit);
+    }
+
+    /// The fee charged for the gas consumed so far
+    pub fn gas_fee(&self) -> u64 {
+        self.gas_state.lock().unwrap().fee()
+    }
+
+    pub fn sandbox_limits(&self) -> &SandboxLimits {
+        &self.sandbox_limits
+    }
+
+    pub fn text_limits(&self) -> &TextLimits {
+        &self.text_limits
+    }
+
     pub fn fee_checkpoint(&self) -> Result<(), RuntimeError> {
         self.read_with(|state| {
             // Check that the checkpoint is in a valid state
@@ -79,8 +79,8 @@ impl<'a> Authorization<'a> {
         let scope = self.state.current_call_scope()?.auth_scope();
 
         // Check ownership.
-        // A resource is only recallable by explicit access rules
-        if !action.is_recall() && check_ownership(self.state, scope, resource_ownership)? {
+        // A resource is only recallable or freezable by explicit access rules
+        if !action.is_recall() && !action.is_freeze() && check_ownership(self.state, scope, resource_ownership)? {
             // Owner can invoke any resource method
             return Ok(());
         }
@@ -5,6 +5,7 @@ use std::{
     cmp,
     collections::{BTreeSet, HashMap, HashSet},
     mem,
+    sync::{Arc, Mutex},
 };
 
 use indexmap::IndexMap;
@@ -24,6 +25,7 @@ use tari_engine_types::{
     logs::LogEntry,
     non_fungible::NonFungibleContainer,
     proof::{ContainerRef, LockedResource, Proof},
+    published_template::PublishedTemplate,
     resource::Resource,
     resource_container::{ResourceContainer, ResourceError},
     substate::{Substate, SubstateDiff, SubstateId, SubstateValue},
@@ -42,6 +44,7 @@ use tari_template_lib::{
         ComponentAddress,
         NonFungibleAddress,
         ProofId,
+        ResourceAddress,
         UnclaimedConfidentialOutputAddress,
         VaultId,
     },
@@ -52,6 +55,7 @@ use tari_template_lib::{
 use super::workspace::Workspace;
 use crate::{
     runtime::{
+        access_stats::SubstateAccessState,
         address_allocation::AllocatedAddress,
         fee_state::FeeState,
         locking::LockedSubstate,
@@ -89,6 +93,10 @@ pub(super) struct WorkingState {
     initial_call_scope: CallScope,
 
     fee_state: FeeState,
+    /// Vaults that were flash borrowed from in this transaction
+    flash_loans: IndexMap<VaultId, FlashLoan>,
+    /// Shared with the fee checkpoint so that accesses made before a reset are still reported
+    access_stats: Option<Arc<Mutex<SubstateAccessState>>>,
 }
 
 impl WorkingState {
@@ -97,6 +105,7 @@ impl WorkingState {
         virtual_substates: VirtualSubstates,
         initial_call_scope: CallScope,
         transaction_hash: Hash,
+        access_stats: Option<Arc<Mutex<SubstateAccessState>>>,
     ) -> Self {
         Self {
             transaction_hash,
@@ -117,7 +126,9 @@ impl WorkingState {
             call_frames: Vec::new(),
             initial_call_scope,
             fee_state: FeeState::new(),
+            flash_loans: IndexMap::new(),
             object_ids: ObjectIds::new(1000),
+            access_stats,
         }
     }
 
@@ -145,12 +156,23 @@ impl WorkingState {
     ) -> Result<(), RuntimeError> {
         let address = address.into();
         self.current_call_scope_mut()?.add_substate_to_scope(address.clone())?;
+        if let Some(access_stats) = &self.access_stats {
+            let template = self.current_template().ok().map(|(addr, _)| *addr);
+            access_stats.lock().unwrap().record_new(template, &address);
+        }
         self.store.insert(address, value.into())?;
         Ok(())
     }
 
     pub fn lock_substate(&mut self, addr: &SubstateId, lock_flag: LockFlag) -> Result<LockedSubstate, RuntimeError> {
         let lock_id = self.store.try_lock(addr, lock_flag)?;
+        if let Some(access_stats) = &self.access_stats {
+            let (_, value) = self.store.get_locked_substate(lock_id)?;
+            let size = tari_bor::encoded_len(value)? as u64;
+            // Locks taken outside of a call frame are made by the engine
+            let template = self.current_template().ok().map(|(addr, _)| *addr);
+            access_stats.lock().unwrap().record_lock(template, addr, lock_flag, size);
+        }
         Ok(LockedSubstate::new(addr.clone(), lock_id, lock_flag))
     }
 
@@ -341,6 +363,18 @@ impl WorkingState {
             }
         }
 
+        for (vault_id, loan) in &self.flash_loans {
+            let balance = self.store.load_vault(vault_id)?.balance();
+            if balance < loan.required_balance {
+                return Err(TransactionCommitError::FlashLoanNotRepaid {
+                    vault_id: *vault_id,
+                    required_balance: loan.required_balance,
+                    balance,
+                }
+                .into());
+            }
+        }
+
         if self.call_frame_depth() != 0 {
             return Err(RuntimeError::CallFrameRemainingOnStack {
                 remaining: self.call_frame_depth(),
@@ -650,6 +684,45 @@ impl WorkingState {
         Ok(resource_container)
     }
 
+    pub fn set_vault_frozen(
+        &mut self,
+        vault_lock: &LockedSubstate,
+        resource_address: &ResourceAddress,
+        is_frozen: bool,
+    ) -> Result<(), RuntimeError> {
+        let vault_mut = self.get_vault_mut(vault_lock)?;
+        if vault_mut.resource_address() != resource_address {
+            return Err(RuntimeError::InvalidArgument {
+                argument: "vault_id",
+                reason: format!(
+                    "Vault {} contains resource {} but {} was requested",
+                    vault_lock.address(),
+                    vault_mut.resource_address(),
+                    resource_address
+                ),
+            });
+        }
+
+        debug!(
+            target: LOG_TARGET,
+            "{} vault {}",
+            if is_frozen { "Freezing" } else { "Unfreezing" },
+            vault_lock.address()
+        );
+        vault_mut.set_frozen(is_frozen);
+        Ok(())
+    }
+
+    pub fn check_vault_not_frozen(&mut self, vault_id: VaultId) -> Result<(), RuntimeError> {
+        let vault_lock = self.lock_substate(&SubstateId::Vault(vault_id), LockFlag::Read)?;
+        let is_frozen = self.get_vault(&vault_lock)?.is_frozen();
+        self.unlock_substate(vault_lock)?;
+        if is_frozen {
+            return Err(RuntimeError::VaultFrozen { vault_id });
+        }
+        Ok(())
+    }
+
     pub fn new_bucket(&mut self, bucket_id: BucketId, resource: ResourceContainer) -> Result<(), RuntimeError> {
         debug!(
             target: LOG_TARGET,
@@ -896,6 +969,9 @@ impl WorkingState {
                 total_fee_payment,
                 total_fees_paid: fee_resource.amount(),
                 cost_breakdown: mem::take(&mut self.fee_state.fee_charges),
+                // Memory and gas usage are tracked outside of the working state and are set by the state tracker
+                memory_usage: Default::default(),
+                gas_usage: Default::default(),
             },
         })
     }
@@ -1019,6 +1095,7 @@ impl WorkingState {
             VirtualSubstates::new(),
             CallScope::new(),
             self.transaction_hash,
+            self.access_stats.clone(),
         );
         mem::replace(self, new_state)
     }
@@ -1039,6 +1116,42 @@ impl WorkingState {
         self.store.load_component(component_address)
     }
 
+    pub fn load_published_template(
+        &mut self,
+        template_address: &TemplateAddress,
+    ) -> Result<&PublishedTemplate, RuntimeError> {
+        self.store.load_published_template(template_address)
+    }
+
+    /// Records a flash borrow of `amount` from the vault. The vault must hold at least its balance before the first
+    /// borrow plus the fees of all borrows when the transaction is finalized.
+    pub fn record_flash_loan(
+        &mut self,
+        vault_id: VaultId,
+        balance_before: Amount,
+        amount: Amount,
+        fee: Amount,
+    ) -> Result<(), RuntimeError> {
+        let loan = self.flash_loans.entry(vault_id).or_insert_with(|| FlashLoan {
+            borrowed: Amount::zero(),
+            required_balance: balance_before,
+        });
+        let overflow = || RuntimeError::NumericConversionError {
+            details: format!("Flash loan from vault {} overflowed", vault_id),
+        };
+        loan.borrowed = loan.borrowed.checked_add(amount).ok_or_else(overflow)?;
+        loan.required_balance = loan.required_balance.checked_add(fee).ok_or_else(overflow)?;
+        debug!(
+            target: LOG_TARGET,
+            "Flash borrow of {} from vault {} (total borrowed: {}, required balance: {})",
+            amount,
+            vault_id,
+            loan.borrowed,
+            loan.required_balance
+        );
+        Ok(())
+    }
+
     pub fn check_all_substates_known(&self, value: &IndexedWellKnownTypes) -> Result<(), RuntimeError> {
         for addr in value.referenced_substates() {
             if !self.substate_exists(&addr)? {
@@ -1191,3 +1304,10 @@ impl WorkingState {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone)]
+struct FlashLoan {
+    borrowed: Amount,
+    /// The minimum balance of the vault when the transaction is finalized
+    required_balance: Amount,
+}
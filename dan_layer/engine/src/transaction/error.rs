@@ -20,10 +20,17 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_engine_types::indexed_value::IndexedValueError;
+use tari_engine_types::{
+    commit_result::{RejectReason, TemplateAbort},
+    indexed_value::IndexedValueError,
+};
 use tari_template_lib::{models::TemplateAddress, HashParseError};
 
-use crate::{runtime::RuntimeError, template::TemplateLoaderError, wasm::WasmExecutionError};
+use crate::{
+    runtime::{RuntimeError, SandboxViolation},
+    template::TemplateLoaderError,
+    wasm::WasmExecutionError,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionError {
@@ -31,6 +38,12 @@ pub enum TransactionError {
     WasmExecutionError(#[from] WasmExecutionError),
     #[error("Template not found at address {address}")]
     TemplateNotFound { address: TemplateAddress },
+    #[error("Template {address} has name '{actual}' but the component template is named '{expected}'")]
+    TemplateNameMismatch {
+        address: TemplateAddress,
+        expected: String,
+        actual: String,
+    },
     #[error(transparent)]
     RuntimeError(#[from] RuntimeError),
     #[error(transparent)]
@@ -53,4 +66,82 @@ pub enum TransactionError {
     TemplateProvider(String),
     #[error("Converting to hash error: {0}")]
     HashConversion(#[from] HashParseError),
+    #[error("Panic! {message}")]
+    TemplateAborted {
+        template_address: TemplateAddress,
+        function: String,
+        message: String,
+    },
+}
+
+impl TransactionError {
+    /// Returns the sandbox violation that caused this error, if any
+    pub fn sandbox_violation(&self) -> Option<&SandboxViolation> {
+        match self {
+            Self::RuntimeError(RuntimeError::SandboxViolation(violation)) |
+            Self::WasmExecutionError(WasmExecutionError::RuntimeError(RuntimeError::SandboxViolation(violation))) => {
+                Some(violation)
+            },
+            _ => None,
+        }
+    }
+
+    pub fn to_reject_reason(&self) -> RejectReason {
+        match self.sandbox_violation() {
+            Some(violation) => RejectReason::SandboxViolation(violation.to_string()),
+            None => RejectReason::ExecutionFailure(self.to_string()),
+        }
+    }
+
+    /// Returns the structured abort payload if this error was caused by a template panic or trap
+    pub fn to_template_abort(&self, instruction_index: u32, is_fee_instruction: bool) -> Option<TemplateAbort> {
+        let (template_address, function, message) = match self {
+            Self::TemplateAborted {
+                template_address,
+                function,
+                message,
+            } |
+            Self::RuntimeError(RuntimeError::TemplateAborted {
+                template_address,
+                function,
+                message,
+            }) => (template_address, function, message),
+            _ => return None,
+        };
+
+        Some(TemplateAbort {
+            message: message.clone(),
+            template_address: *template_address,
+            function: function.clone(),
+            instruction_index,
+            is_fee_instruction,
+        })
+    }
+
+    /// Converts a template panic or trap that occurred while invoking `function` into a `TemplateAborted` error. A
+    /// panic in a nested cross-template call is passed through so that the innermost template is reported.
+    pub(crate) fn into_template_abort(self, template_address: &TemplateAddress, function: &str) -> Self {
+        match self {
+            Self::WasmExecutionError(WasmExecutionError::RuntimeError(RuntimeError::TemplateAborted {
+                template_address,
+                function,
+                message,
+            })) => Self::TemplateAborted {
+                template_address,
+                function,
+                message,
+            },
+            Self::WasmExecutionError(WasmExecutionError::Panic { message, .. }) => Self::TemplateAborted {
+                template_address: *template_address,
+                function: function.to_string(),
+                message,
+            },
+            Self::WasmExecutionError(WasmExecutionError::WasmRuntimeError(err)) => Self::TemplateAborted {
+                template_address: *template_address,
+                function: function.to_string(),
+                message: format!("WASM trap: {}", err.message()),
+            },
+            err => err,
+        }
+    }
 }
@@ -28,13 +28,14 @@ use tari_common::configuration::Network;
 use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{services::template_provider::TemplateProvider, Epoch};
 use tari_engine_types::{
-    commit_result::{ExecuteResult, FinalizeResult, RejectReason, TransactionResult},
+    commit_result::{ExecuteResult, FinalizeResult, RejectReason, TemplateAbort, TransactionResult},
     component::new_component_address_from_public_key,
     entity_id_provider::EntityIdProvider,
     indexed_value::IndexedWellKnownTypes,
     instruction::Instruction,
     instruction_result::InstructionResult,
     lock::LockFlag,
+    published_template::TemplateType,
     virtual_substate::VirtualSubstates,
 };
 use tari_template_abi::FunctionDef;
@@ -53,14 +54,19 @@ use tari_transaction::Transaction;
 use tari_utilities::ByteArray;
 
 use crate::{
+    flow::FlowFactory,
     runtime::{
         scope::{CallScope, PushCallFrame},
         AuthParams,
         AuthorizationScope,
+        GasLimits,
+        MemoryLimits,
         Runtime,
         RuntimeInterfaceImpl,
         RuntimeModule,
+        SandboxLimits,
         StateTracker,
+        TextLimits,
     },
     state_store::memory::ReadOnlyMemoryStateStore,
     template::LoadedTemplate,
@@ -77,6 +83,13 @@ const ACCOUNT_CONSTRUCTOR_FUNCTION: &str = "create";
 pub struct TransactionProcessorConfig {
     pub network: Network,
     pub template_binary_max_size_bytes: usize,
+    pub memory_limits: MemoryLimits,
+    pub gas_limits: GasLimits,
+    pub sandbox_limits: SandboxLimits,
+    pub text_limits: TextLimits,
+    /// Collect substate access statistics and include them in the execute result. This is intended for dry runs, so
+    /// that template authors can see which substates their templates access.
+    pub collect_substate_access_stats: bool,
 }
 
 impl TransactionProcessorConfig {
@@ -90,6 +103,11 @@ impl Default for TransactionProcessorConfig {
         Self {
             network: Default::default(),
             template_binary_max_size_bytes: 1000 * 1000 * 5, // 5MB
+            memory_limits: MemoryLimits::default(),
+            gas_limits: GasLimits::default(),
+            sandbox_limits: SandboxLimits::default(),
+            text_limits: TextLimits::default(),
+            collect_substate_access_stats: false,
         }
     }
 }
@@ -110,6 +128,31 @@ impl TransactionProcessorConfigBuilder {
         self
     }
 
+    pub fn with_memory_limits(&mut self, memory_limits: MemoryLimits) -> &mut Self {
+        self.config.memory_limits = memory_limits;
+        self
+    }
+
+    pub fn with_gas_limits(&mut self, gas_limits: GasLimits) -> &mut Self {
+        self.config.gas_limits = gas_limits;
+        self
+    }
+
+    pub fn with_sandbox_limits(&mut self, sandbox_limits: SandboxLimits) -> &mut Self {
+        self.config.sandbox_limits = sandbox_limits;
+        self
+    }
+
+    pub fn with_text_limits(&mut self, text_limits: TextLimits) -> &mut Self {
+        self.config.text_limits = text_limits;
+        self
+    }
+
+    pub fn with_substate_access_stats(&mut self, enabled: bool) -> &mut Self {
+        self.config.collect_substate_access_stats = enabled;
+        self
+    }
+
     pub fn build(&self) -> TransactionProcessorConfig {
         self.config.clone()
     }
@@ -162,7 +205,18 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             initial_call_scope.add_substate_to_owned(input.substate_id.clone());
         }
 
-        let tracker = StateTracker::new(state_db, virtual_substates, initial_call_scope, transaction.hash());
+        let tracker = StateTracker::new(
+            state_db,
+            virtual_substates,
+            initial_call_scope,
+            transaction.hash(),
+            config.memory_limits,
+            config.gas_limits,
+            transaction.declared_max_fee().and_then(|fee| fee.as_u64_checked()),
+            config.sandbox_limits.clone(),
+            config.text_limits,
+            config.collect_substate_access_stats,
+        );
 
         // TODO: We'll have a "notarized" transaction that is signed by a single key. It signs a challenge incl. all the
         // signatures of the transaction. We could define this signature as the "default" owner or we
@@ -191,7 +245,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
 
         let (fee_instructions, instructions) = transaction.into_instructions();
 
-        let fee_exec_results = Self::process_instructions(&config, &template_provider, &runtime, fee_instructions);
+        let fee_exec_results =
+            Self::process_instructions(&config, &template_provider, &runtime, fee_instructions, true);
 
         let fee_exec_result = match fee_exec_results {
             Ok(execution_results) => {
@@ -204,22 +259,24 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                     return Ok(ExecuteResult {
                         finalize,
                         execution_time: timer.elapsed(),
+                        substate_access_stats: runtime.interface().substate_access_stats(),
                     });
                 }
                 execution_results
             },
-            Err(err) => {
+            Err(InstructionsError { error, abort }) => {
+                let mut finalize = FinalizeResult::new_rejected(transaction_hash, error.to_reject_reason());
+                finalize.abort = abort;
                 return Ok(ExecuteResult {
-                    finalize: FinalizeResult::new_rejected(
-                        transaction_hash,
-                        RejectReason::ExecutionFailure(err.to_string()),
-                    ),
+                    finalize,
                     execution_time: timer.elapsed(),
+                    substate_access_stats: runtime.interface().substate_access_stats(),
                 });
             },
         };
 
-        let instruction_result = Self::process_instructions(&config, &*template_provider, &runtime, instructions);
+        let instruction_result =
+            Self::process_instructions(&config, &*template_provider, &runtime, instructions, false);
 
         match instruction_result {
             Ok(execution_results) => {
@@ -232,10 +289,11 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                 Ok(ExecuteResult {
                     finalize,
                     execution_time: timer.elapsed(),
+                    substate_access_stats: runtime.interface().substate_access_stats(),
                 })
             },
             // This can happen e.g if you have dangling buckets after running the instructions
-            Err(err) => {
+            Err(InstructionsError { error, abort }) => {
                 // Reset the state to when the state at the end of the fee instructions. The fee charges for the
                 // successful instructions are still charged even though the transaction failed.
                 runtime.interface().reset_to_fee_checkpoint()?;
@@ -248,11 +306,13 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                         .accept()
                         .cloned()
                         .expect("The fee transaction should be there"),
-                    RejectReason::ExecutionFailure(err.to_string()),
+                    error.to_reject_reason(),
                 );
+                finalize.abort = abort;
                 Ok(ExecuteResult {
                     finalize,
                     execution_time: timer.elapsed(),
+                    substate_access_stats: runtime.interface().substate_access_stats(),
                 })
             },
         }
@@ -263,18 +323,27 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         template_provider: &TTemplateProvider,
         runtime: &Runtime,
         instructions: Vec<Instruction>,
-    ) -> Result<Vec<InstructionResult>, TransactionError> {
-        let result: Result<_, _> = instructions
-            .into_iter()
-            .map(|instruction| Self::process_instruction(config, template_provider, runtime, instruction))
-            .collect();
+        is_fee_instructions: bool,
+    ) -> Result<Vec<InstructionResult>, InstructionsError> {
+        let mut results = Vec::with_capacity(instructions.len());
+        for (index, instruction) in instructions.into_iter().enumerate() {
+            let result = Self::process_instruction(config, template_provider, runtime, instruction).map_err(|error| {
+                let abort = error.to_template_abort(index as u32, is_fee_instructions);
+                InstructionsError { error, abort }
+            })?;
+            results.push(result);
+        }
 
         // check that the finalized state is valid
-        if result.is_ok() {
-            runtime.interface().validate_finalized()?;
-        }
+        runtime
+            .interface()
+            .validate_finalized()
+            .map_err(|err| InstructionsError {
+                error: err.into(),
+                abort: None,
+            })?;
 
-        result
+        Ok(results)
     }
 
     fn process_instruction(
@@ -347,7 +416,15 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
                 )?;
                 Ok(InstructionResult::empty())
             },
-            Instruction::PublishTemplate { binary } => Self::publish_template(config, runtime, binary),
+            Instruction::PublishTemplate {
+                binary,
+                previous_version,
+                template_type,
+            } => Self::publish_template(config, runtime, binary, template_type, previous_version),
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => Self::migrate_component(template_provider, runtime, component_address, template_address),
         }
     }
 
@@ -370,6 +447,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         config: &TransactionProcessorConfig,
         runtime: &Runtime,
         binary: Vec<u8>,
+        template_type: TemplateType,
+        previous_version: Option<TemplateAddress>,
     ) -> Result<InstructionResult, TransactionError> {
         if binary.len() > config.template_binary_max_size_bytes {
             return Err(TransactionError::WasmBinaryTooBig(
@@ -379,10 +458,53 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         }
 
         // validate binary
-        WasmModule::load_template_from_code(binary.as_slice())?;
+        match template_type {
+            TemplateType::Wasm => {
+                WasmModule::load_template_from_code(binary.as_slice())?;
+            },
+            TemplateType::Flow => {
+                FlowFactory::try_from_json::<TTemplateProvider>(&binary)?;
+            },
+        }
 
         // creating new substate
-        runtime.interface().publish_template(binary)?;
+        runtime
+            .interface()
+            .publish_template(binary, template_type, previous_version)?;
+
+        Ok(InstructionResult::empty())
+    }
+
+    /// Migrates a component to a newer version of its template. The new version must have the same template name as
+    /// the current template of the component.
+    pub fn migrate_component(
+        template_provider: &TTemplateProvider,
+        runtime: &Runtime,
+        component_address: ComponentAddress,
+        template_address: TemplateAddress,
+    ) -> Result<InstructionResult, TransactionError> {
+        let template = template_provider
+            .get_template_module(&template_address)
+            .map_err(|e| TransactionError::FailedToLoadTemplate {
+                address: template_address,
+                details: e.to_string(),
+            })?
+            .ok_or(TransactionError::TemplateNotFound {
+                address: template_address,
+            })?;
+
+        let component = runtime.interface().load_component(&component_address)?;
+        if template.template_def().template_name() != component.module_name {
+            return Err(TransactionError::TemplateNameMismatch {
+                address: template_address,
+                expected: component.module_name,
+                actual: template.template_def().template_name().to_string(),
+            });
+        }
+
+        runtime
+            .interface()
+            .migrate_component_template(component_address, template_address)?;
 
         Ok(InstructionResult::empty())
     }
@@ -456,7 +578,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             entity_id: account_address.entity_id(),
         })?;
 
-        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, args)?;
+        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, args)
+            .map_err(|err| err.into_template_abort(&ACCOUNT_TEMPLATE_ADDRESS, ACCOUNT_CONSTRUCTOR_FUNCTION))?;
 
         runtime.interface().validate_return_value(&result.indexed)?;
 
@@ -501,7 +624,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             entity_id: runtime.interface().next_entity_id()?,
         })?;
 
-        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, args)?;
+        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, args)
+            .map_err(|err| err.into_template_abort(template_address, function))?;
 
         runtime.interface().validate_return_value(&result.indexed)?;
 
@@ -517,8 +641,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         method: &str,
         args: Vec<Arg>,
     ) -> Result<InstructionResult, TransactionError> {
-        let component = runtime.interface().load_component(component_address)?;
-        let template_address = component.template_address;
+        let template_address = runtime.interface().get_component_template_address(component_address)?;
 
         let template = template_provider
             .get_template_module(&template_address)
@@ -550,7 +673,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             .map(IndexedWellKnownTypes::from_value)
             .collect::<Result<_, _>>()?;
 
-        let component_scope = IndexedWellKnownTypes::from_value(component.state())?;
+        let (entity_id, component_scope) = runtime.interface().get_component_scope(&component_lock)?;
 
         runtime.interface().push_call_frame(PushCallFrame::ForComponent {
             template_address,
@@ -558,7 +681,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
             component_scope,
             component_lock: component_lock.clone(),
             arg_scope: Box::new(arg_scope),
-            entity_id: component.entity_id,
+            entity_id,
         })?;
 
         // This must come after the call frame as that defines the authorization scope
@@ -570,7 +693,8 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         final_args.push(to_value(component_address)?);
         final_args.extend(args);
 
-        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, final_args)?;
+        let result = Self::invoke_template(template, template_provider, runtime.clone(), function_def, final_args)
+            .map_err(|err| err.into_template_abort(&template_address, method))?;
 
         runtime.interface().validate_return_value(&result.indexed)?;
         runtime.interface().pop_call_frame()?;
@@ -587,7 +711,7 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
     ) -> Result<InstructionResult, TransactionError> {
         let result = match module {
             LoadedTemplate::Wasm(loaded) => {
-                let mut store = loaded.create_store();
+                let mut store = loaded.create_store(runtime.interface().sandbox_limits());
                 let mut process = WasmProcess::init(&mut store, loaded, runtime)?;
                 process.invoke(&mut store, &function_def, args)?
             },
@@ -606,3 +730,9 @@ impl<TTemplateProvider: TemplateProvider<Template = LoadedTemplate> + 'static> T
         Ok(result)
     }
 }
+
+/// The error that caused an instruction to fail, with the abort payload if a template panicked
+struct InstructionsError {
+    error: TransactionError,
+    abort: Option<TemplateAbort>,
+}
@@ -23,7 +23,12 @@
 use std::{
     fmt::{Debug, Formatter},
     ops::Range,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::Instant,
 };
 
 use tari_template_abi::{TemplateDef, ABI_TEMPLATE_DEF_GLOBAL_NAME};
@@ -41,7 +46,11 @@ use wasmer::{
 
 use crate::{
     runtime::RuntimeError,
-    wasm::{mem_writer::MemWriter, WasmExecutionError},
+    wasm::{
+        mem_writer::MemWriter,
+        metering::{get_remaining_points, set_remaining_points, MeteringPoints},
+        WasmExecutionError,
+    },
 };
 
 #[derive(Clone)]
@@ -51,6 +60,13 @@ pub struct WasmEnv<T> {
     mem_alloc: Option<TypedFunction<u32, WasmPtr<u8>>>,
     last_panic: Arc<Mutex<Option<String>>>,
     last_engine_error: Arc<Mutex<Option<RuntimeError>>>,
+    /// The size of the linear memory that was last reported to the runtime for memory accounting
+    reported_memory_size: Arc<AtomicU64>,
+    /// The instance is required to read and set its metering points
+    instance: Option<Instance>,
+    /// The metering points of the instance after gas was last accounted for
+    metered_points: Arc<AtomicU64>,
+    started_at: Instant,
 }
 
 impl<T: Send + 'static> WasmEnv<T> {
@@ -61,6 +77,10 @@ impl<T: Send + 'static> WasmEnv<T> {
             mem_alloc: None,
             last_panic: Arc::new(Mutex::new(None)),
             last_engine_error: Arc::new(Mutex::new(None)),
+            reported_memory_size: Arc::new(AtomicU64::new(0)),
+            instance: None,
+            metered_points: Arc::new(AtomicU64::new(0)),
+            started_at: Instant::now(),
         }
     }
 
@@ -89,6 +109,39 @@ impl<T: Send + 'static> WasmEnv<T> {
         self.last_engine_error.lock().unwrap().take()
     }
 
+    /// Records `size` as the reported memory size and returns the previously reported size
+    pub(super) fn swap_reported_memory_size(&self, size: u64) -> u64 {
+        self.reported_memory_size.swap(size, Ordering::SeqCst)
+    }
+
+    /// Sets the metering points of the instance to the given amount of gas
+    pub(super) fn set_remaining_gas<S: AsStoreMut>(&self, store: &mut S, gas: u64) -> Result<(), WasmExecutionError> {
+        set_remaining_points(store, self.get_instance()?, gas);
+        self.metered_points.store(gas, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Returns the gas consumed by the instance since the gas was last accounted for. If the metering points are
+    /// exhausted, more gas than the instance had remaining is returned.
+    pub(super) fn take_consumed_gas<S: AsStoreMut>(&self, store: &mut S) -> Result<u64, WasmExecutionError> {
+        let previous = self.metered_points.load(Ordering::SeqCst);
+        let (consumed, remaining) = match get_remaining_points(store, self.get_instance()?) {
+            MeteringPoints::Remaining(remaining) => (previous.saturating_sub(remaining), remaining),
+            MeteringPoints::Exhausted => (previous.saturating_add(1), 0),
+        };
+        self.metered_points.store(remaining, Ordering::SeqCst);
+        Ok(consumed)
+    }
+
+    /// The time at which the environment was created, before the instance was created
+    pub(super) fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    pub(super) fn memory_size<S: AsStoreRef>(&self, store: &S) -> Result<u64, WasmExecutionError> {
+        Ok(self.get_memory()?.view(store).data_size())
+    }
+
     pub(super) fn load_abi<S: AsStoreMut>(
         &self,
         store: &mut S,
@@ -171,6 +224,10 @@ impl<T: Send + 'static> WasmEnv<T> {
             .ok_or_else(|| WasmExecutionError::MissingAbiFunction { function: "tari_alloc" })
     }
 
+    fn get_instance(&self) -> Result<&Instance, WasmExecutionError> {
+        self.instance.as_ref().ok_or(WasmExecutionError::InstanceNotSet)
+    }
+
     fn get_memory(&self) -> Result<&Memory, WasmExecutionError> {
         let memory = self.memory.as_ref().ok_or_else(|| WasmExecutionError::MemoryNotSet)?;
         Ok(memory)
@@ -183,6 +240,11 @@ impl<T: Clone + Sync + Send> WasmEnv<T> {
         self
     }
 
+    pub fn set_instance(&mut self, instance: Instance) -> &mut Self {
+        self.instance = Some(instance);
+        self
+    }
+
     pub fn set_alloc_funcs(&mut self, mem_alloc: TypedFunction<u32, WasmPtr<u8>>) -> &mut Self {
         self.mem_alloc = Some(mem_alloc);
         self
@@ -35,6 +35,8 @@ pub enum WasmExecutionError {
     MemoryAllocationFailed,
     #[error("BUG: memory not set in environment")]
     MemoryNotSet,
+    #[error("BUG: instance not set in environment")]
+    InstanceNotSet,
     #[error("Missing function {function}")]
     MissingAbiFunction { function: &'static str },
     #[error("Runtime error: {0}")]
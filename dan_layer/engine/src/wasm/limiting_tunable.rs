@@ -13,7 +13,7 @@ use wasmer::{
     Tunables,
 };
 
-/// A custom tunables that allows you to set a memory limit.
+/// A custom tunables that allows you to set memory and table limits.
 ///
 /// After adjusting the memory limits, it delegates all other logic
 /// to the base tunables.
@@ -22,13 +22,19 @@ pub struct LimitingTunables<T: Tunables> {
     /// Since Wasmer ensures there is only none or one memory, this is practically
     /// an upper limit for the guest memory.
     limit: Pages,
+    /// The maximum number of elements that a table is allowed to have
+    table_limit: u32,
     /// The base implementation we delegate all the logic to
     base: T,
 }
 
 impl<T: Tunables> LimitingTunables<T> {
-    pub fn new(base: T, limit: Pages) -> Self {
-        Self { limit, base }
+    pub fn new(base: T, limit: Pages, table_limit: u32) -> Self {
+        Self {
+            limit,
+            table_limit,
+            base,
+        }
     }
 
     /// Takes an input memory type as requested by the guest and sets
@@ -64,6 +70,28 @@ impl<T: Tunables> LimitingTunables<T> {
 
         Ok(())
     }
+
+    /// Takes an input table type as requested by the guest and sets a maximum if missing
+    fn adjust_table(&self, requested: &TableType) -> TableType {
+        let mut adjusted = *requested;
+        if requested.maximum.is_none() {
+            adjusted.maximum = Some(self.table_limit);
+        }
+        adjusted
+    }
+
+    /// Ensures the a given table type does not exceed the table limit. Call this after adjusting the table.
+    fn validate_table(&self, ty: &TableType) -> Result<(), String> {
+        if ty.minimum > self.table_limit {
+            return Err("Minimum exceeds the allowed table limit".to_string());
+        }
+
+        match ty.maximum {
+            Some(max) if max > self.table_limit => Err("Maximum exceeds the allowed table limit".to_string()),
+            Some(_) => Ok(()),
+            None => Err("Maximum unset".to_string()),
+        }
+    }
 }
 
 impl<T: Tunables> Tunables for LimitingTunables<T> {
@@ -107,20 +135,24 @@ impl<T: Tunables> Tunables for LimitingTunables<T> {
 
     /// Create a table owned by the host given a [`TableType`] and a [`TableStyle`].
     ///
-    /// Delegated to base.
+    /// The requested table type is validated, adjusted to the limited and then passed to base.
     fn create_host_table(&self, ty: &TableType, style: &TableStyle) -> Result<vm::VMTable, String> {
-        self.base.create_host_table(ty, style)
+        let adjusted = self.adjust_table(ty);
+        self.validate_table(&adjusted)?;
+        self.base.create_host_table(&adjusted, style)
     }
 
     /// Create a table owned by the VM given a [`TableType`] and a [`TableStyle`].
     ///
-    /// Delegated to base.
+    /// The requested table type is validated, adjusted to the limited and then passed to base.
     unsafe fn create_vm_table(
         &self,
         ty: &TableType,
         style: &TableStyle,
         vm_definition_location: NonNull<VMTableDefinition>,
     ) -> Result<vm::VMTable, String> {
-        self.base.create_vm_table(ty, style, vm_definition_location)
+        let adjusted = self.adjust_table(ty);
+        self.validate_table(&adjusted)?;
+        self.base.create_vm_table(&adjusted, style, vm_definition_location)
     }
 }
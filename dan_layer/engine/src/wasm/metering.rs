@@ -21,10 +21,14 @@
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use wasmer::{wasmparser::Operator, ModuleMiddleware};
+pub use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 use wasmer_middlewares::Metering;
 
-pub fn middleware(limit: u64) -> impl ModuleMiddleware {
-    Metering::new(limit, cost_function)
+/// The initial points of an instance. The remaining gas of the transaction is set on the instance before it is called.
+const INITIAL_POINTS: u64 = 100_000_000;
+
+pub fn middleware() -> impl ModuleMiddleware {
+    Metering::new(INITIAL_POINTS, cost_function)
 }
 
 #[allow(clippy::too_many_lines)]
@@ -14,7 +14,7 @@ pub use module::{LoadedWasmTemplate, WasmModule};
 mod metering;
 mod process;
 
-pub use process::WasmProcess;
+pub use process::{WasmProcess, ENGINE_TARI_VERSION};
 
 mod limiting_tunable;
 mod mem_writer;
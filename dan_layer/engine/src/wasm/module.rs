@@ -43,6 +43,7 @@ use wasmer::{
 };
 
 use crate::{
+    runtime::SandboxLimits,
     template::{LoadedTemplate, TemplateLoaderError, TemplateModuleLoader},
     wasm::{environment::WasmEnv, limiting_tunable::LimitingTunables, metering, WasmExecutionError},
 };
@@ -92,15 +93,11 @@ impl WasmModule {
     }
 
     fn create_engine() -> Engine {
-        const MEMORY_PAGE_LIMIT: Pages = Pages(32); // 2MiB = 32 * 65,536
-        let base = BaseTunables::for_target(&Target::default());
-        let tunables = LimitingTunables::new(base, MEMORY_PAGE_LIMIT);
         let mut compiler = Cranelift::new();
         compiler.opt_level(CraneliftOptLevel::Speed).canonicalize_nans(true);
-        // TODO: Configure metering limit
-        compiler.push_middleware(Arc::new(metering::middleware(100_000_000)));
+        compiler.push_middleware(Arc::new(metering::middleware()));
         let mut engine = Engine::from(compiler);
-        engine.set_tunables(tunables);
+        engine.set_tunables(create_tunables(&SandboxLimits::default()));
 
         engine
     }
@@ -138,8 +135,11 @@ impl LoadedWasmTemplate {
         &self.engine
     }
 
-    pub fn create_store(&self) -> Store {
-        Store::new(self.engine.clone())
+    /// Creates a store in which the memories and tables of instances are limited by the sandbox limits
+    pub fn create_store(&self, limits: &SandboxLimits) -> Store {
+        let mut engine = self.engine.clone();
+        engine.set_tunables(create_tunables(limits));
+        Store::new(engine)
     }
 
     pub fn template_name(&self) -> &str {
@@ -170,6 +170,11 @@ impl fmt::Debug for LoadedWasmTemplate {
     }
 }
 
+fn create_tunables(limits: &SandboxLimits) -> LimitingTunables<BaseTunables> {
+    let base = BaseTunables::for_target(&Target::default());
+    LimitingTunables::new(base, Pages(limits.max_memory_pages), limits.max_table_elements)
+}
+
 fn validate_instance<S: AsStoreMut>(
     store: &mut S,
     instance: &Instance,
@@ -39,16 +39,30 @@ use tari_template_lib::{
         NonFungibleInvokeArg,
         ProofInvokeArg,
         ResourceInvokeArg,
+        TextInvokeArg,
         VaultInvokeArg,
         WorkspaceInvokeArg,
     },
     AbiContext,
 };
-use wasmer::{imports, AsStoreMut, Function, FunctionEnv, FunctionEnvMut, Instance, Store, StoreMut, WasmPtr};
+use wasmer::{
+    imports,
+    AsStoreMut,
+    AsStoreRef,
+    ExternType,
+    Function,
+    FunctionEnv,
+    FunctionEnvMut,
+    Instance,
+    Store,
+    StoreMut,
+    WasmPtr,
+    WASM_PAGE_SIZE,
+};
 
 use super::version::are_versions_compatible;
 use crate::{
-    runtime::Runtime,
+    runtime::{Runtime, RuntimeError, SandboxViolation},
     traits::Invokable,
     wasm::{
         environment::{AllocPtr, WasmEnv},
@@ -60,6 +74,8 @@ use crate::{
 
 const LOG_TARGET: &str = "tari::dan::engine::wasm::process";
 pub const ENGINE_TARI_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// The host functions that templates may import from the "env" module
+const HOST_FUNCTIONS: &[&str] = &["tari_engine", "debug", "on_panic"];
 
 pub struct WasmProcess {
     module: LoadedWasmTemplate,
@@ -70,6 +86,7 @@ pub struct WasmProcess {
 impl WasmProcess {
     pub fn init(store: &mut Store, module: LoadedWasmTemplate, state: Runtime) -> Result<Self, WasmExecutionError> {
         Self::validate_template_tari_version(&module)?;
+        Self::validate_sandbox(&module, &state)?;
 
         let mut env = WasmEnv::new(state);
         let fn_env = FunctionEnv::new(store, env.clone());
@@ -88,10 +105,13 @@ impl WasmProcess {
         fn_env
             .as_mut(store)
             .set_memory(memory.clone())
-            .set_alloc_funcs(mem_alloc.clone());
+            .set_alloc_funcs(mem_alloc.clone())
+            .set_instance(instance.clone());
 
         // Also set these for the local copy
-        env.set_memory(memory).set_alloc_funcs(mem_alloc);
+        env.set_memory(memory)
+            .set_alloc_funcs(mem_alloc)
+            .set_instance(instance.clone());
 
         Ok(Self { module, env, instance })
     }
@@ -139,6 +159,21 @@ impl WasmProcess {
 
         log::debug!(target: LOG_TARGET, "Engine call: {:?}", op);
 
+        if let Err(err) = Self::check_sandbox_limits(env_mut, op)
+            .and_then(|_| Self::update_memory_usage(env_mut, &store))
+            .and_then(|_| Self::account_for_gas(env_mut, &mut store))
+            .and_then(|_| {
+                env_mut.state().interface().record_host_allocation(arg.len() as u64)?;
+                Ok(())
+            })
+        {
+            log::error!(target: LOG_TARGET, "{}", err);
+            if let WasmExecutionError::RuntimeError(e) = err {
+                env_mut.set_last_engine_error(e);
+            }
+            return WasmPtr::null();
+        }
+
         let result = match op {
             EngineOp::EmitLog => Self::handle(store, env_mut, arg, |env, arg: EmitLogArg| {
                 env.interface().emit_log(arg.level, arg.message)
@@ -192,8 +227,17 @@ impl WasmProcess {
                     env.interface().builtin_template_invoke(arg.action)
                 })
             },
+            EngineOp::TextInvoke => Self::handle(store, env_mut, arg, |env, arg: TextInvokeArg| {
+                env.interface().text_invoke(arg.action)
+            }),
         };
 
+        // Account for the gas and time used by the call, including any nested template calls
+        let (env_mut, mut store) = env.data_and_store_mut();
+        let result = Self::account_for_gas(env_mut, &mut store)
+            .and_then(|_| Self::check_execution_time(env_mut))
+            .and(result);
+
         result.unwrap_or_else(|err| {
             if let Err(err) = env
                 .data()
@@ -226,16 +270,97 @@ impl WasmProcess {
         let decoded = decode_exact(&args).map_err(WasmExecutionError::EngineArgDecodeFailed)?;
         let resp = f(env_mut.state_mut(), decoded)?;
         let len = encoded_len(&resp)?;
+        env_mut.state().interface().record_host_allocation(len as u64)?;
         let ptr = env_mut.alloc(&mut store, len as u32)?;
         let mut writer = env_mut.memory_writer(&mut store, ptr)?;
         encode_with_len_to_writer(&mut writer, &resp)?;
         Ok(ptr)
     }
 
+    /// Reports any change in the size of the linear memory of this instance to the runtime
+    fn update_memory_usage<S: AsStoreRef>(env: &WasmEnv<Runtime>, store: &S) -> Result<(), WasmExecutionError> {
+        let current = env.memory_size(store)?;
+        let previous = env.swap_reported_memory_size(current);
+        if previous != current {
+            env.state().interface().update_wasm_memory_usage(previous, current)?;
+        }
+        Ok(())
+    }
+
+    /// Consumes the gas used by this instance since gas was last accounted for, and sets the metering points of the
+    /// instance to the remaining gas of the transaction. Other instances (e.g. nested component calls) may have
+    /// consumed gas in the meantime.
+    fn account_for_gas<S: AsStoreMut>(env: &WasmEnv<Runtime>, store: &mut S) -> Result<(), WasmExecutionError> {
+        let consumed = env.take_consumed_gas(store)?;
+        env.state().interface().consume_wasm_gas(consumed)?;
+        env.set_remaining_gas(store, env.state().interface().remaining_gas())?;
+        Ok(())
+    }
+
+    /// Checks that the engine call is allowed and that the instance has not exceeded its execution time
+    fn check_sandbox_limits(env: &WasmEnv<Runtime>, op: EngineOp) -> Result<(), WasmExecutionError> {
+        env.state().interface().sandbox_limits().check_engine_op(op)?;
+        Self::check_execution_time(env)
+    }
+
+    fn check_execution_time(env: &WasmEnv<Runtime>) -> Result<(), WasmExecutionError> {
+        env.state()
+            .interface()
+            .sandbox_limits()
+            .check_execution_time(env.started_at())?;
+        Ok(())
+    }
+
+    /// Returns a memory limit violation if the linear memory of this instance cannot grow any further. A failure to
+    /// grow the memory aborts the template, so a trap at the memory limit is reported as a sandbox violation.
+    fn memory_limit_violation<S: AsStoreRef>(&self, store: &S) -> Result<Option<RuntimeError>, WasmExecutionError> {
+        let limit = self.env.state().interface().sandbox_limits().max_memory_pages;
+        let memory_size = self.env.memory_size(store)?;
+        if memory_size < u64::from(limit) * WASM_PAGE_SIZE as u64 {
+            return Ok(None);
+        }
+        Ok(Some(SandboxViolation::MemoryLimitExceeded { limit }.into()))
+    }
+
+    /// Releases the memory attributed to this instance once it is no longer in use
+    fn release_memory_usage(&self) -> Result<(), WasmExecutionError> {
+        let previous = self.env.swap_reported_memory_size(0);
+        self.env.state().interface().update_wasm_memory_usage(previous, 0)?;
+        Ok(())
+    }
+
     fn encoded_abi_context(&self) -> Vec<u8> {
         encode(&AbiContext {}).unwrap()
     }
 
+    /// Checks that the template only imports the host functions and that its exported memories and tables are
+    /// within the sandbox limits. Tables that are not exported are limited when the instance is created.
+    fn validate_sandbox(module: &LoadedWasmTemplate, state: &Runtime) -> Result<(), WasmExecutionError> {
+        let limits = state.interface().sandbox_limits();
+        for import in module.wasm_module().imports() {
+            let is_host_function = import.module() == "env" &&
+                HOST_FUNCTIONS.contains(&import.name()) &&
+                matches!(import.ty(), ExternType::Function(_));
+            if !is_host_function {
+                return Err(RuntimeError::from(SandboxViolation::ImportNotAllowed {
+                    module: import.module().to_string(),
+                    name: import.name().to_string(),
+                })
+                .into());
+            }
+        }
+
+        for export in module.wasm_module().exports() {
+            match export.ty() {
+                ExternType::Memory(ty) => limits.check_memory_pages(ty.minimum.0)?,
+                ExternType::Table(ty) => limits.check_table_elements(ty.minimum)?,
+                _ => {},
+            }
+        }
+
+        Ok(())
+    }
+
     /// Determine if the version of the template_lib crate in the WASM is valid.
     /// This is just a placeholder that logs the result, as we don't manage version incompatibilities yet
     fn validate_template_tari_version(module: &LoadedWasmTemplate) -> Result<(), WasmExecutionError> {
@@ -273,8 +398,19 @@ impl Invokable<Store> for WasmProcess {
         let main_name = format!("{}_main", self.module.template_name());
         let func: MainFunction = self.instance.exports.get_typed_function(store, &main_name)?;
 
+        Self::update_memory_usage(&self.env, &*store)?;
+        self.env.set_remaining_gas(store, self.env.state().interface().remaining_gas())?;
         let call_info_ptr = self.alloc_and_write(store, &call_info)?;
         let res = func.call(store, call_info_ptr.as_wasm_ptr(), call_info_ptr.len());
+        // Account for any memory growth and gas used since the last engine call before the instance is released. If
+        // the gas ran out, the call fails with a trap, so running out of gas takes precedence over the call result.
+        let gas_usage = Self::account_for_gas(&self.env, store);
+        let execution_time = Self::check_execution_time(&self.env);
+        let memory_usage = Self::update_memory_usage(&self.env, &*store);
+        self.release_memory_usage()?;
+        gas_usage?;
+        execution_time?;
+        memory_usage?;
         // No need to free since the exported function should free the memory by dropping it at the end - however, if it
         // does not the memory will be freed once the VM is destructed
         // self.env.as_ref(store).free(store, call_info_ptr)?;
@@ -291,6 +427,9 @@ impl Invokable<Store> for WasmProcess {
                         runtime_error: err,
                     });
                 }
+                if let Some(violation) = self.memory_limit_violation(store)? {
+                    return Err(WasmExecutionError::RuntimeError(violation));
+                }
                 eprintln!("Error calling function: {}", err);
                 return Err(err.into());
             },
@@ -3,7 +3,8 @@
 
 use std::iter;
 
-use tari_engine_types::{commit_result::RejectReason, instruction::Instruction};
+use tari_dan_engine::{fees::FeeTable, runtime::MemoryLimits};
+use tari_engine_types::{commit_result::RejectReason, fees::FeeSource, instruction::Instruction};
 use tari_template_lib::{
     args,
     constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
@@ -403,3 +404,128 @@ fn dangling_bucket_pay_fees() {
     assert!(!payment.total_fees_paid().is_zero());
     assert_eq!(orig_balance - new_balance, payment.total_fees_paid());
 }
+
+#[test]
+fn it_includes_memory_usage_in_fee_receipt() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    test.enable_fees();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_function(test.get_template_address("State"), "new", args![])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    test.disable_fees();
+
+    let memory_usage = result.finalize.fee_receipt.memory_usage;
+    assert!(memory_usage.peak_wasm_memory_bytes > 0);
+    assert!(memory_usage.host_allocated_bytes > 0);
+    assert!(memory_usage.peak_wasm_memory_bytes <= MemoryLimits::default().max_wasm_memory_bytes);
+}
+
+#[test]
+fn it_rejects_transactions_that_exceed_memory_limits() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+    let (_, owner_token, private_key) = test.create_funded_account();
+    let template_address = test.get_template_address("State");
+
+    // A single WASM page
+    test.set_memory_limits(MemoryLimits {
+        max_wasm_memory_bytes: 64 * 1024,
+        ..Default::default()
+    });
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(template_address, "new", args![])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token.clone()],
+    );
+    assert!(
+        reason.to_string().contains("WASM memory limit of 65536 bytes exceeded"),
+        "Unexpected reject reason: {reason}"
+    );
+
+    test.set_memory_limits(MemoryLimits {
+        max_host_allocated_bytes: 1,
+        ..Default::default()
+    });
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(template_address, "new", args![])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+    assert!(
+        reason.to_string().contains("host memory limit of 1 bytes exceeded"),
+        "Unexpected reject reason: {reason}"
+    );
+}
+
+#[test]
+fn it_charges_for_gas() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    test.enable_fees();
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_function(test.get_template_address("State"), "new", args![])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    test.disable_fees();
+
+    let fee_receipt = result.finalize.fee_receipt;
+    let gas_usage = fee_receipt.gas_usage;
+    assert!(gas_usage.instructions > 0);
+    assert!(gas_usage.memory_growth > 0);
+    assert!(gas_usage.total() <= gas_usage.limit);
+    let gas_fee = fee_receipt
+        .cost_breakdown
+        .iter()
+        .find(|(source, _)| **source == FeeSource::Gas)
+        .map(|(_, fee)| *fee)
+        .unwrap();
+    assert_eq!(gas_fee, gas_usage.total().div_ceil(test.fee_table().gas_per_fee_unit()));
+}
+
+#[test]
+fn it_limits_gas_to_the_declared_max_fee() {
+    let mut test = TemplateTest::new(["tests/templates/state"]);
+
+    let (account, owner_token, private_key) = test.create_funded_account();
+
+    let fee_table = FeeTable {
+        gas_per_fee_unit: 1000,
+        ..test.fee_table().clone()
+    };
+    test.set_fee_table(fee_table);
+    test.enable_fees();
+
+    let result = test.execute_expect_commit(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account, Amount(1000))
+            .call_function(test.get_template_address("State"), "new", args![])
+            .sign(&private_key)
+            .build(),
+        vec![owner_token],
+    );
+
+    test.disable_fees();
+
+    assert_eq!(result.finalize.fee_receipt.gas_usage.limit, 1000 * 1000);
+}
@@ -0,0 +1,107 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_engine::runtime::TransactionCommitError;
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress, ResourceAddress, VaultId},
+};
+use tari_template_test_tooling::{support::assert_error::assert_reject_reason, TemplateTest};
+use tari_transaction::Transaction;
+
+fn create_lender(test: &mut TemplateTest, fee: Amount) -> (ComponentAddress, ResourceAddress, VaultId) {
+    let (lender, resource): (ComponentAddress, ResourceAddress) =
+        test.call_function("FlashLender", "new", args![fee], vec![]);
+    let vault_id = test.extract_component_value(lender, "$.vault");
+    (lender, resource, vault_id)
+}
+
+#[test]
+fn it_allows_borrowing_from_multiple_vaults_if_repaid_with_fees() {
+    let mut test = TemplateTest::new(["tests/templates/flash_loan"]);
+    let (lender_a, resource_a, _) = create_lender(&mut test, Amount(5));
+    let (lender_b, _, _) = create_lender(&mut test, Amount(0));
+    let (account, owner_proof, secret_key) = test.create_empty_account();
+
+    // Give the borrower enough tokens to pay the fees of lender A
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(lender_a, "take_free", args![Amount(10)])
+            .put_last_instruction_output_on_workspace("tokens")
+            .call_method(account, "deposit", args![Workspace("tokens")])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    // Borrow different resources from both lenders and repay each loan before the transaction completes. Lender A
+    // charges a fee for each of the two loans.
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(lender_a, "borrow", args![Amount(100)])
+            .put_last_instruction_output_on_workspace("loan_a")
+            .call_method(lender_b, "borrow", args![Amount(300)])
+            .put_last_instruction_output_on_workspace("loan_b")
+            .call_method(lender_a, "borrow", args![Amount(50)])
+            .put_last_instruction_output_on_workspace("loan_a2")
+            .call_method(account, "withdraw", args![resource_a, Amount(10)])
+            .put_last_instruction_output_on_workspace("fee")
+            .call_method(lender_b, "repay", args![Workspace("loan_b")])
+            .call_method(lender_a, "repay", args![Workspace("loan_a")])
+            .call_method(lender_a, "repay", args![Workspace("loan_a2")])
+            .call_method(lender_a, "repay", args![Workspace("fee")])
+            .sign(&secret_key)
+            .build(),
+        vec![owner_proof],
+    );
+
+    let balance_a: Amount = test.call_method(lender_a, "balance", args![], vec![]);
+    let balance_b: Amount = test.call_method(lender_b, "balance", args![], vec![]);
+    assert_eq!(balance_a, Amount(1000));
+    assert_eq!(balance_b, Amount(1000));
+}
+
+#[test]
+fn it_rejects_a_loan_that_is_not_repaid() {
+    let mut test = TemplateTest::new(["tests/templates/flash_loan"]);
+    let (lender, _, vault_id) = create_lender(&mut test, Amount(0));
+    let (account, _, _) = test.create_empty_account();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(lender, "borrow", args![Amount(100)])
+            .put_last_instruction_output_on_workspace("loan")
+            .call_method(account, "deposit", args![Workspace("loan")])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert_reject_reason(reason, TransactionCommitError::FlashLoanNotRepaid {
+        vault_id,
+        required_balance: Amount(1000),
+        balance: Amount(900),
+    });
+}
+
+#[test]
+fn it_rejects_a_loan_that_is_repaid_without_the_fee() {
+    let mut test = TemplateTest::new(["tests/templates/flash_loan"]);
+    let (lender, _, vault_id) = create_lender(&mut test, Amount(10));
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(lender, "borrow", args![Amount(100)])
+            .put_last_instruction_output_on_workspace("loan")
+            .call_method(lender, "repay", args![Workspace("loan")])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    assert_reject_reason(reason, TransactionCommitError::FlashLoanNotRepaid {
+        vault_id,
+        required_balance: Amount(1010),
+        balance: Amount(1000),
+    });
+}
@@ -8,10 +8,13 @@ use tari_dan_engine::wasm::compile::compile_template;
 use tari_engine_types::{
     commit_result::{RejectReason, TransactionResult},
     hashing::{hasher32, template_hasher32, EngineHashDomainLabel},
-    published_template::PublishedTemplateAddress,
+    published_template::{PublishedTemplateAddress, TemplateType},
     substate::{SubstateId, SubstateValue},
 };
-use tari_template_lib::models::Amount;
+use tari_template_lib::{
+    args,
+    models::{Amount, NonFungibleAddress, TemplateAddress},
+};
 use tari_template_test_tooling::TemplateTest;
 use tari_transaction::Transaction;
 
@@ -105,6 +108,260 @@ fn publish_template_too_big_binary() {
     }
 }
 
+#[test]
+fn publish_flow_template_success() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(250_000));
+    let flow_json = r#"{"name":"EmptyFlow","args":[],"flow":{"id":"tari_engine@0.1.0","nodes":{}}}"#;
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_flow_template(flow_json.to_string(), None)
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof],
+    );
+
+    let diff = result.finalize.result.accept().unwrap();
+    let template = diff
+        .up_iter()
+        .find_map(|(_, substate)| substate.substate_value().published_template())
+        .unwrap();
+    assert_eq!(template.template_type, TemplateType::Flow);
+    assert_eq!(template.binary, flow_json.as_bytes());
+}
+
+#[test]
+fn publish_flow_template_invalid_definition() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(250_000));
+    let result = test.execute_expect_failure(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_flow_template(r#"{"name":"MissingFlow","args":[]}"#.to_string(), None)
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof],
+    );
+
+    let RejectReason::ExecutionFailure(error) = result else {
+        panic!("Expected execution failure, got {:?}", result);
+    };
+    assert!(error.starts_with("Invalid flow definition:"), "{}", error);
+}
+
+#[test]
+fn publish_template_version_and_migrate_component() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(1_000_000));
+    let v1 = compile_template("tests/templates/upgradable_state", &[]).unwrap();
+    let v2 = compile_template("tests/templates/upgradable_state", &["v2"]).unwrap();
+
+    let v1_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(v1.code().to_vec())
+            .sign(&account_key)
+            .build(),
+        owner_proof.clone(),
+    );
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(v1_address, "new", args![])
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof.clone()],
+    );
+    let component_address = result
+        .finalize
+        .result
+        .expect("Component creation failed")
+        .up_iter()
+        .find_map(|(id, _)| id.as_component_address())
+        .unwrap();
+
+    let v2_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template_version(v2.code().to_vec(), v1_address)
+            .sign(&account_key)
+            .build(),
+        owner_proof.clone(),
+    );
+    let v2_template = test
+        .read_only_state_store()
+        .get_substate(&SubstateId::Template(v2_address.into()))
+        .unwrap()
+        .into_substate_value()
+        .into_published_template()
+        .unwrap();
+    assert_eq!(v2_template.template_version, 1);
+    assert_eq!(v2_template.previous_version, Some(v1_address.into()));
+    assert_eq!(v2_template.lineage_root, Some(v1_address.into()));
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(component_address, "set", args![5u32])
+            .migrate_component(component_address, v2_address)
+            .call_method(component_address, "increment", args![])
+            .call_method(component_address, "get", args![])
+            .call_method(component_address, "version", args![])
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof],
+    );
+    // The state of the first version is retained and the methods of the second version are available
+    assert_eq!(result.expect_return::<u32>(3), 6);
+    assert_eq!(result.expect_return::<u32>(4), 2);
+
+    let component = test.read_only_state_store().get_component(component_address).unwrap();
+    assert_eq!(component.template_address, v2_address);
+}
+
+#[test]
+fn publish_template_version_requires_author() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(1_000_000));
+    let (other_address, other_proof, other_key, _) = test.create_custom_funded_account(Amount(1_000_000));
+    let v1 = compile_template("tests/templates/upgradable_state", &[]).unwrap();
+    let v2 = compile_template("tests/templates/upgradable_state", &["v2"]).unwrap();
+
+    let v1_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(v1.code().to_vec())
+            .sign(&account_key)
+            .build(),
+        owner_proof,
+    );
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .fee_transaction_pay_from_component(other_address, Amount(200_000))
+            .publish_template_version(v2.code().to_vec(), v1_address)
+            .sign(&other_key)
+            .build(),
+        vec![other_proof],
+    );
+    assert!(reason.to_string().contains("Only the author"), "{}", reason);
+}
+
+#[test]
+fn migrate_component_requires_owner_and_lineage() {
+    let mut test = TemplateTest::new(Vec::<String>::new());
+    let (account_address, owner_proof, account_key, _) = test.create_custom_funded_account(Amount(1_000_000));
+    let (_, other_proof, other_key, _) = test.create_custom_funded_account(Amount(1_000_000));
+    let v1 = compile_template("tests/templates/upgradable_state", &[]).unwrap();
+    let v2 = compile_template("tests/templates/upgradable_state", &["v2"]).unwrap();
+
+    let v1_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(v1.code().to_vec())
+            .sign(&account_key)
+            .build(),
+        owner_proof.clone(),
+    );
+    // Published as a new template rather than as a version of v1
+    let unrelated_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template(v2.code().to_vec())
+            .sign(&account_key)
+            .build(),
+        owner_proof.clone(),
+    );
+    let v2_address = publish(
+        &mut test,
+        Transaction::builder()
+            .fee_transaction_pay_from_component(account_address, Amount(200_000))
+            .publish_template_version(v2.code().to_vec(), v1_address)
+            .sign(&account_key)
+            .build(),
+        owner_proof.clone(),
+    );
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(v1_address, "new", args![])
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof.clone()],
+    );
+    let component_address = result
+        .finalize
+        .result
+        .expect("Component creation failed")
+        .up_iter()
+        .find_map(|(id, _)| id.as_component_address())
+        .unwrap();
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .migrate_component(component_address, v2_address)
+            .sign(&other_key)
+            .build(),
+        vec![other_proof],
+    );
+    assert!(reason.to_string().contains("You must be the owner"), "{}", reason);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .migrate_component(component_address, unrelated_address)
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof.clone()],
+    );
+    assert!(
+        reason.to_string().contains("is not a version of template"),
+        "{}",
+        reason
+    );
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .migrate_component(component_address, v2_address)
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof.clone()],
+    );
+
+    // Downgrading is not permitted
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .migrate_component(component_address, v1_address)
+            .sign(&account_key)
+            .build(),
+        vec![owner_proof],
+    );
+    assert!(
+        reason.to_string().contains("may only be migrated to a newer version"),
+        "{}",
+        reason
+    );
+}
+
+fn publish(test: &mut TemplateTest, transaction: Transaction, owner_proof: NonFungibleAddress) -> TemplateAddress {
+    let result = test.execute_expect_success(transaction, vec![owner_proof]);
+    result
+        .finalize
+        .result
+        .expect("Publish failed")
+        .up_iter()
+        .find_map(|(id, _)| match id {
+            SubstateId::Template(address) => Some(address.as_hash()),
+            _ => None,
+        })
+        .unwrap()
+}
+
 fn generate_random_binary(size_in_bytes: usize) -> Vec<u8> {
     iter::repeat_with(random).take(size_in_bytes).collect()
 }
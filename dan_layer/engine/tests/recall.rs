@@ -3,12 +3,17 @@
 
 use std::collections::BTreeMap;
 
+use tari_dan_engine::runtime::RuntimeError;
 use tari_template_lib::{
     args,
-    models::{Amount, NonFungibleId, ResourceAddress, VaultId},
+    auth::ResourceAuthAction,
+    models::{Amount, ComponentAddress, NonFungibleId, ResourceAddress, VaultId},
 };
 use tari_template_test_tooling::{
-    support::confidential::{generate_confidential_proof, generate_withdraw_proof},
+    support::{
+        assert_error::{assert_access_denied_for_action, assert_reject_reason},
+        confidential::{generate_confidential_proof, generate_withdraw_proof},
+    },
     TemplateTest,
 };
 use tari_transaction::Transaction;
@@ -86,3 +91,107 @@ fn it_recalls_all_resource_types() {
     let confidential_balance = result.finalize.execution_results[6].decode::<Amount>().unwrap();
     assert_eq!(confidential_balance, Amount(6));
 }
+
+fn setup_recall_with_account(
+    test: &mut TemplateTest,
+) -> (ComponentAddress, ComponentAddress, ResourceAddress, ResourceAddress) {
+    let recall_template = test.get_template_address("Recall");
+    let (account, _, _) = test.create_empty_account();
+
+    let (mut initial_supply, mask, _) = generate_confidential_proof(Amount(1000), None);
+    initial_supply.output_revealed_amount = Amount(1000);
+
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_function(recall_template, "new", args![initial_supply])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    let recall_component = result.finalize.execution_results[0].get_value("$.0").unwrap().unwrap();
+    let fungible_resource = result.finalize.execution_results[0].get_value("$.1").unwrap().unwrap();
+    let non_fungible_resource = result.finalize.execution_results[0].get_value("$.2").unwrap().unwrap();
+
+    let withdraw = generate_withdraw_proof(&mask, Amount(10), Some(Amount(980)), Amount(10));
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(recall_component, "withdraw_some", args![withdraw.proof])
+            .put_last_instruction_output_on_workspace("buckets")
+            .call_method(account, "deposit", args![Workspace("buckets.0")])
+            .call_method(account, "deposit", args![Workspace("buckets.1")])
+            .call_method(account, "deposit", args![Workspace("buckets.2")])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    (recall_component, account, fungible_resource, non_fungible_resource)
+}
+
+#[test]
+fn it_freezes_and_unfreezes_vaults() {
+    let mut test = TemplateTest::new(["tests/templates/recall"]);
+    let (recall_component, account, fungible_resource, _) = setup_recall_with_account(&mut test);
+
+    let vaults: BTreeMap<ResourceAddress, VaultId> = test.extract_component_value(account, "$.vaults");
+    let fungible_vault = vaults[&fungible_resource];
+
+    test.execute_expect_success(
+        Transaction::builder()
+            .call_method(recall_component, "freeze", args![fungible_resource, fungible_vault])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    // The account owner cannot withdraw from a frozen vault
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(account, "withdraw", args![fungible_resource, Amount(1)])
+            .put_last_instruction_output_on_workspace("bucket")
+            .call_method(account, "deposit", args![Workspace("bucket")])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert_reject_reason(reason, RuntimeError::VaultFrozen {
+        vault_id: fungible_vault,
+    });
+
+    // Frozen funds can still be recalled
+    let result = test.execute_expect_success(
+        Transaction::builder()
+            .call_method(recall_component, "recall_fungible", args![fungible_vault, Amount(6)])
+            .call_method(recall_component, "unfreeze", args![fungible_resource, fungible_vault])
+            .call_method(account, "withdraw", args![fungible_resource, Amount(1)])
+            .put_last_instruction_output_on_workspace("bucket")
+            .call_method(account, "deposit", args![Workspace("bucket")])
+            .call_method(account, "balance", args![fungible_resource])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+
+    let fungible_balance = result.finalize.execution_results[4].decode::<Amount>().unwrap();
+    assert_eq!(fungible_balance, Amount(4));
+}
+
+#[test]
+fn it_denies_freezing_without_access_rule() {
+    let mut test = TemplateTest::new(["tests/templates/recall"]);
+    let (recall_component, account, _, non_fungible_resource) = setup_recall_with_account(&mut test);
+
+    let vaults: BTreeMap<ResourceAddress, VaultId> = test.extract_component_value(account, "$.vaults");
+    let non_fungible_vault = vaults[&non_fungible_resource];
+
+    // The non-fungible resource is recallable but not freezable
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(recall_component, "freeze", args![non_fungible_resource, non_fungible_vault])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert_access_denied_for_action(reason, ResourceAuthAction::Freeze);
+}
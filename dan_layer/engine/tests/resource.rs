@@ -1,8 +1,17 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_template_lib::{args, models::ComponentAddress};
-use tari_template_test_tooling::{support::confidential::generate_confidential_proof, TemplateTest};
+use tari_template_lib::{
+    args,
+    auth::ResourceAuthAction,
+    models::{ComponentAddress, Metadata},
+    resource::{DECIMALS, TOKEN_NAME, TOKEN_SYMBOL},
+};
+use tari_template_test_tooling::{
+    support::{assert_error::assert_access_denied_for_action, confidential::generate_confidential_proof},
+    TemplateTest,
+};
+use tari_transaction::Transaction;
 
 #[test]
 fn fungible_join() {
@@ -25,3 +34,100 @@ fn confidential_join() {
     let (output, _, _) = generate_confidential_proof(1000.into(), None);
     test.call_method::<()>(component, "confidential_join", args![output], vec![]);
 }
+
+#[test]
+fn fungible_withdraw_and_deposit_multiple() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let component: ComponentAddress = test.call_function("ResourceTest", "new", args![], vec![]);
+    test.call_method::<()>(component, "fungible_withdraw_and_deposit_multiple", args![], vec![]);
+}
+
+#[test]
+fn withdraw_and_deposit_multiple_are_all_or_nothing() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let component: ComponentAddress = test.call_function("ResourceTest", "new", args![], vec![]);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "fungible_withdraw_multiple_insufficient", args![])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert!(reason.to_string().contains("insufficient funds"), "{}", reason);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "deposit_multiple_mismatched", args![])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert!(
+        reason.to_string().contains("Resource addresses do not match"),
+        "{}",
+        reason
+    );
+}
+
+#[test]
+fn resource_metadata_can_be_queried_and_updated() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let component: ComponentAddress = test.call_function("ResourceTest", "new", args![], vec![]);
+
+    let metadata: Metadata = test.call_method(component, "fungible_metadata", args![], vec![]);
+    assert_eq!(metadata.get(TOKEN_SYMBOL).unwrap(), "TST");
+    assert_eq!(metadata.get(TOKEN_NAME).unwrap(), "Test token");
+    assert_eq!(metadata.get(DECIMALS).unwrap(), "2");
+
+    test.call_method::<()>(
+        component,
+        "update_fungible_metadata",
+        args![TOKEN_NAME, "Renamed token"],
+        vec![],
+    );
+    test.call_method::<()>(component, "remove_fungible_metadata", args![TOKEN_SYMBOL], vec![]);
+
+    let metadata: Metadata = test.call_method(component, "fungible_metadata", args![], vec![]);
+    assert_eq!(metadata.get(TOKEN_NAME).unwrap(), "Renamed token");
+    assert!(!metadata.contains_key(TOKEN_SYMBOL));
+}
+
+#[test]
+fn resource_metadata_must_be_valid() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let component: ComponentAddress = test.call_function("ResourceTest", "new", args![], vec![]);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_function(test.get_template_address("ResourceTest"), "create_with_invalid_decimals", args![])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert!(reason.to_string().contains("DECIMALS"), "{}", reason);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "update_fungible_metadata", args![DECIMALS, "lots"])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert!(reason.to_string().contains("DECIMALS"), "{}", reason);
+}
+
+#[test]
+fn resource_metadata_updates_require_access_rule() {
+    let mut test = TemplateTest::new(vec!["tests/templates/resource"]);
+    let component: ComponentAddress = test.call_function("ResourceTest", "new", args![], vec![]);
+
+    let reason = test.execute_expect_failure(
+        Transaction::builder()
+            .call_method(component, "update_non_fungible_metadata", args![TOKEN_NAME, "Renamed token"])
+            .sign(test.get_test_secret_key())
+            .build(),
+        vec![],
+    );
+    assert_access_denied_for_action(reason, ResourceAuthAction::UpdateMetadata);
+}
@@ -0,0 +1,53 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_engine_types::{commit_result::RejectReason, substate::SubstateId};
+use tari_template_lib::{
+    args,
+    models::{Amount, ComponentAddress},
+};
+use tari_template_test_tooling::{SubstateType, TemplateTest};
+use tari_transaction::Transaction;
+
+#[test]
+fn it_executes_transactions_signed_by_multiple_identities() {
+    let mut test = TemplateTest::new(["tests/templates/state", "tests/templates/tuples"]);
+    let state_template = test.get_template_address("State");
+    let tuple_template = test.get_template_address("Tuple");
+    test.enable_fees();
+
+    let mut scenario = test.scenario();
+    scenario.pay_fees_from_signer(Amount(1000));
+    scenario.add_identity("alice");
+    scenario.add_identity("bob");
+
+    let step = scenario.execute("alice", Transaction::builder().call_function(state_template, "new", args![]));
+    let state: ComponentAddress = step.output(0);
+    step.assert_up(&SubstateId::Component(state)).assert_fee_at_most(Amount(1000));
+    assert_eq!(step.signer, "alice");
+
+    let step = scenario.execute("bob", Transaction::builder().call_method(state, "set", args![42u32]));
+    step.assert_up(&SubstateId::Component(state)).assert_down(&SubstateId::Component(state));
+    assert!(step.up_ids(SubstateType::Resource).is_empty());
+
+    let step = scenario.execute("bob", Transaction::builder().call_function(tuple_template, "new", args![]));
+    let (tuple, message): (ComponentAddress, String) = step.output(0);
+    assert_eq!(message, "Hello World!");
+    assert!(step.up_ids(SubstateType::Component).contains(&&SubstateId::Component(tuple)));
+
+    let reason = scenario.execute_expect_failure(
+        "alice",
+        Transaction::builder().call_method(state, "this_doesnt_exist", args![]),
+    );
+    assert!(matches!(reason, RejectReason::ExecutionFailure(_)));
+
+    // The fees of the failed transaction are still charged
+    assert_eq!(scenario.steps().len(), 4);
+    assert!(!scenario.step(3).fee_charged().is_zero());
+    let total = scenario.steps().iter().map(|s| s.fee_charged()).sum::<Amount>();
+    assert_eq!(scenario.total_fees_charged(), total);
+
+    scenario.test().disable_fees();
+    let value: u32 = scenario.test().call_method(state, "get", args![], vec![]);
+    assert_eq!(value, 42);
+}
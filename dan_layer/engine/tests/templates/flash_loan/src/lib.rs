@@ -0,0 +1,45 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+use tari_template_lib::prelude::*;
+
+#[template]
+mod template {
+    use super::*;
+
+    pub struct FlashLender {
+        vault: Vault,
+        fee: Amount,
+    }
+
+    impl FlashLender {
+        pub fn new(fee: Amount) -> (Component<Self>, ResourceAddress) {
+            let bucket = ResourceBuilder::fungible().initial_supply(Amount(1000));
+            let resource_address = bucket.resource_address();
+
+            let component = Component::new(Self {
+                vault: Vault::from_bucket(bucket),
+                fee,
+            })
+            .with_access_rules(AccessRules::allow_all())
+            .create();
+
+            (component, resource_address)
+        }
+
+        pub fn borrow(&mut self, amount: Amount) -> Bucket {
+            self.vault.flash_borrow(amount, self.fee)
+        }
+
+        pub fn repay(&mut self, bucket: Bucket) {
+            self.vault.deposit(bucket);
+        }
+
+        pub fn take_free(&mut self, amount: Amount) -> Bucket {
+            self.vault.withdraw(amount)
+        }
+
+        pub fn balance(&self) -> Amount {
+            self.vault.balance()
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use tari_template_lib::prelude::*;
+
+/// A component with a large state, used to test and benchmark component state access
+#[template]
+mod large_state_template {
+    use super::*;
+
+    pub struct LargeState {
+        items: Vec<u64>,
+        labels: BTreeMap<u64, String>,
+        counter: u64,
+    }
+
+    impl LargeState {
+        pub fn new(num_items: u64) -> Component<Self> {
+            Component::new(Self {
+                items: (0..num_items).collect(),
+                labels: (0..num_items).map(|i| (i, format!("item-{}", i))).collect(),
+                counter: 0,
+            })
+            .with_access_rules(AccessRules::new().default(rule!(allow_all)))
+            .create()
+        }
+
+        pub fn get_item(&self, index: u64) -> Option<u64> {
+            self.items.get(index as usize).copied()
+        }
+
+        pub fn counter(&self) -> u64 {
+            self.counter
+        }
+
+        /// A mutable method that does not change the state
+        pub fn touch(&mut self) {}
+
+        pub fn increment(&mut self) -> u64 {
+            self.counter += 1;
+            self.counter
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod oracle_template {
+    use super::*;
+
+    /// A minimal oracle that implements the standard oracle interface
+    pub struct TestOracle {
+        base: ResourceAddress,
+        quote: ResourceAddress,
+        price: oracle::PriceData,
+    }
+
+    impl TestOracle {
+        pub fn new(base: ResourceAddress, quote: ResourceAddress) -> Component<Self> {
+            Component::new(Self {
+                base,
+                quote,
+                price: oracle::PriceData {
+                    price: Amount::zero(),
+                    decimals: 0,
+                    epoch: Consensus::current_epoch(),
+                    timestamp: 0,
+                },
+            })
+            .with_access_rules(AccessRules::allow_all())
+            .create()
+        }
+
+        pub fn set_price(&mut self, price: Amount, decimals: u8, timestamp: u64) {
+            self.price = oracle::PriceData {
+                price,
+                decimals,
+                epoch: Consensus::current_epoch(),
+                timestamp,
+            };
+        }
+
+        pub fn get_price(&self, base: ResourceAddress, quote: ResourceAddress) -> oracle::PriceData {
+            assert!(base == self.base && quote == self.quote, "Unsupported price pair");
+            self.price
+        }
+
+        // Consumer functions that read the price through the standard oracle interface
+
+        pub fn get_fresh_price(
+            oracle_address: ComponentAddress,
+            base: ResourceAddress,
+            quote: ResourceAddress,
+            max_age_epochs: u64,
+        ) -> Amount {
+            oracle::PriceOracle::new(oracle_address)
+                .get_fresh_price(base, quote, max_age_epochs)
+                .price
+        }
+
+        pub fn get_price_observed_since(
+            oracle_address: ComponentAddress,
+            base: ResourceAddress,
+            quote: ResourceAddress,
+            min_timestamp: u64,
+        ) -> Amount {
+            let price = oracle::PriceOracle::new(oracle_address).get_price(base, quote);
+            price.assert_observed_since(min_timestamp);
+            price.price
+        }
+    }
+}
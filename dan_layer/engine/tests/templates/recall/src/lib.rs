@@ -19,7 +19,7 @@ mod template {
             confidential_supply: ConfidentialOutputStatement,
         ) -> (Component<Self>, ResourceAddress, ResourceAddress, ResourceAddress) {
             let fungible = ResourceBuilder::fungible()
-                .recallable(rule!(allow_all))
+                .auditor(rule!(allow_all))
                 .initial_supply(Amount(1_000_000));
 
             let fungible_resource = fungible.resource_address();
@@ -100,6 +100,14 @@ mod template {
             self.confidential.deposit(bucket);
         }
 
+        pub fn freeze(&self, resource: ResourceAddress, vault_id: VaultId) {
+            ResourceManager::get(resource).freeze_vault(vault_id);
+        }
+
+        pub fn unfreeze(&self, resource: ResourceAddress, vault_id: VaultId) {
+            ResourceManager::get(resource).unfreeze_vault(vault_id);
+        }
+
         pub fn get_balances(&self) -> (Amount, Amount, Amount) {
             (
                 self.fungible.balance(),
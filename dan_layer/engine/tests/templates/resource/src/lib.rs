@@ -34,8 +34,14 @@ mod template {
 
     impl ResourceTest {
         pub fn new() -> Component<Self> {
-            let fungible = ResourceBuilder::fungible().initial_supply(1000);
+            let fungible = ResourceBuilder::fungible()
+                .with_token_symbol("TST")
+                .with_name("Test token")
+                .with_decimals(2)
+                .update_metadata(rule!(allow_all))
+                .initial_supply(1000);
             let non_fungible = ResourceBuilder::non_fungible()
+                .with_owner_rule(OwnerRule::None)
                 .initial_supply([NonFungibleId::from_u64(1), NonFungibleId::from_u64(2)]);
             let confidential = ResourceBuilder::confidential()
                 .mintable(rule!(allow_all))
@@ -50,6 +56,28 @@ mod template {
             .create()
         }
 
+        pub fn create_with_invalid_decimals() -> Bucket {
+            ResourceBuilder::fungible()
+                .add_metadata("DECIMALS", "lots")
+                .initial_supply(1000)
+        }
+
+        pub fn fungible_metadata(&self) -> Metadata {
+            ResourceManager::get(self.fungible.resource_address()).metadata()
+        }
+
+        pub fn update_fungible_metadata(&self, key: String, value: String) {
+            ResourceManager::get(self.fungible.resource_address()).update_metadata(key, value);
+        }
+
+        pub fn remove_fungible_metadata(&self, key: String) {
+            ResourceManager::get(self.fungible.resource_address()).remove_metadata(key);
+        }
+
+        pub fn update_non_fungible_metadata(&self, key: String, value: String) {
+            ResourceManager::get(self.non_fungible.resource_address()).update_metadata(key, value);
+        }
+
         pub fn fungible_join(&self) {
             let b1 = self.fungible.withdraw(10);
             let b2 = self.fungible.withdraw(900);
@@ -58,6 +86,28 @@ mod template {
             self.fungible.deposit(joined);
         }
 
+        pub fn fungible_withdraw_and_deposit_multiple(&self) {
+            let buckets = self.fungible.withdraw_multiple([10, 20, 30]);
+            assert_eq!(buckets.len(), 3);
+            assert_eq!(buckets[0].amount(), 10);
+            assert_eq!(buckets[1].amount(), 20);
+            assert_eq!(buckets[2].amount(), 30);
+            assert_eq!(self.fungible.balance(), 940);
+
+            self.fungible.deposit_multiple(buckets);
+            assert_eq!(self.fungible.balance(), 1000);
+        }
+
+        pub fn fungible_withdraw_multiple_insufficient(&self) -> Vec<Bucket> {
+            self.fungible.withdraw_multiple([500, 501])
+        }
+
+        pub fn deposit_multiple_mismatched(&self) {
+            let fungible = self.fungible.withdraw(10);
+            let non_fungible = self.non_fungible.withdraw_non_fungible(NonFungibleId::from_u64(1));
+            self.fungible.deposit_multiple([fungible, non_fungible]);
+        }
+
         pub fn non_fungible_join(&self) {
             let b1 = self.non_fungible.withdraw_non_fungible(NonFungibleId::from_u64(1));
             let b2 = self.non_fungible.withdraw_non_fungible(NonFungibleId::from_u64(2));
@@ -0,0 +1,29 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[template]
+mod text_template {
+    use super::*;
+
+    pub struct TextTest {}
+
+    impl TextTest {
+        pub fn is_match(pattern: String, input: String) -> bool {
+            text::regex_is_match(&pattern, &input)
+        }
+
+        pub fn find(pattern: String, input: String) -> Option<String> {
+            text::regex_find(&pattern, &input)
+        }
+
+        pub fn replace_all(pattern: String, input: String, replacement: String) -> String {
+            text::regex_replace_all(&pattern, &input, &replacement)
+        }
+
+        pub fn normalize_nfc(input: String) -> String {
+            text::normalize(&input, text::NormalizationForm::Nfc)
+        }
+    }
+}
@@ -0,0 +1,69 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::prelude::*;
+
+#[cfg(not(feature = "v2"))]
+#[template]
+mod upgradable_state_template {
+    use super::*;
+
+    pub struct UpgradableState {
+        value: u32,
+    }
+
+    impl UpgradableState {
+        pub fn new() -> Component<Self> {
+            Component::new(Self { value: 0 })
+                .with_access_rules(AccessRules::new().default(rule!(allow_all)))
+                .create()
+        }
+
+        pub fn set(&mut self, value: u32) {
+            self.value = value;
+        }
+
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+
+        pub fn version(&self) -> u32 {
+            1
+        }
+    }
+}
+
+#[cfg(feature = "v2")]
+#[template]
+mod upgradable_state_template {
+    use super::*;
+
+    /// The state must remain compatible with the first version
+    pub struct UpgradableState {
+        value: u32,
+    }
+
+    impl UpgradableState {
+        pub fn new() -> Component<Self> {
+            Component::new(Self { value: 0 })
+                .with_access_rules(AccessRules::new().default(rule!(allow_all)))
+                .create()
+        }
+
+        pub fn set(&mut self, value: u32) {
+            self.value = value;
+        }
+
+        pub fn get(&self) -> u32 {
+            self.value
+        }
+
+        pub fn version(&self) -> u32 {
+            2
+        }
+
+        pub fn increment(&mut self) {
+            self.value += 1;
+        }
+    }
+}
@@ -22,6 +22,7 @@
 use std::iter;
 
 use tari_dan_engine::{
+    runtime::{GasLimits, RuntimeError},
     template::{TemplateLoaderError, TemplateModuleLoader},
     wasm::{compile::compile_template, WasmExecutionError},
 };
@@ -111,6 +112,39 @@ fn state_create_multiple_in_one_call() {
     assert_eq!(count, 10);
 }
 
+#[test]
+fn large_state_is_only_written_when_changed() {
+    let mut template_test = TemplateTest::new(["tests/templates/large_state"]);
+    let component_address: ComponentAddress = template_test.call_function("LargeState", "new", args![1000u64], vec![]);
+
+    let call_method = |method: &str| Instruction::CallMethod {
+        component_address,
+        method: method.to_string(),
+        args: args![],
+    };
+
+    let result = template_test
+        .execute_and_commit(vec![call_method("touch")], vec![])
+        .unwrap();
+    let diff = result.finalize.result.expect("touch failed");
+    assert!(diff
+        .up_iter()
+        .all(|(id, _)| *id != SubstateId::Component(component_address)));
+
+    let result = template_test
+        .execute_and_commit(vec![call_method("increment")], vec![])
+        .unwrap();
+    let diff = result.finalize.result.expect("increment failed");
+    assert!(diff
+        .up_iter()
+        .any(|(id, _)| *id == SubstateId::Component(component_address)));
+
+    let counter: u64 = template_test.call_method(component_address, "counter", args![], vec![]);
+    assert_eq!(counter, 1);
+    let item: Option<u64> = template_test.call_method(component_address, "get_item", args![999u64], vec![]);
+    assert_eq!(item, Some(999));
+}
+
 #[test]
 fn test_composed() {
     let mut template_test = TemplateTest::new(vec!["tests/templates/state", "tests/templates/hello_world"]);
@@ -331,8 +365,132 @@ fn test_errors_on_infinite_loop() {
             .build(),
         vec![],
     );
-    // Transaction failed: Execution failure: RuntimeError: unreachable\n    at tari_free (<module>[327]:0x2390c)
-    assert_reject_reason(reason, wasmer::RuntimeError::new("unreachable"))
+    assert_reject_reason(reason, RuntimeError::OutOfGas {
+        limit: GasLimits::default().max_gas_per_transaction,
+    })
+}
+
+mod sandbox {
+    use std::time::Duration;
+
+    use tari_dan_engine::runtime::{SandboxLimits, SandboxViolation};
+    use tari_template_abi::EngineOp;
+
+    use super::*;
+
+    fn call_state_new(test: &mut TemplateTest) -> RejectReason {
+        test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(test.get_template_address("State"), "new", args![])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        )
+    }
+
+    fn assert_sandbox_violation(reason: RejectReason, violation: SandboxViolation) {
+        assert!(
+            matches!(reason, RejectReason::SandboxViolation(_)),
+            "Expected a sandbox violation but got \"{}\"",
+            reason
+        );
+        assert_reject_reason(reason, violation);
+    }
+
+    #[test]
+    fn it_rejects_templates_that_exceed_the_memory_limit() {
+        let mut test = TemplateTest::new(vec!["tests/templates/state"]);
+        test.set_sandbox_limits(SandboxLimits {
+            max_memory_pages: 1,
+            ..Default::default()
+        });
+        let reason = call_state_new(&mut test);
+        assert_sandbox_violation(reason, SandboxViolation::MemoryLimitExceeded { limit: 1 });
+    }
+
+    #[test]
+    fn it_rejects_templates_that_exceed_the_execution_time() {
+        let mut test = TemplateTest::new(vec!["tests/templates/state"]);
+        test.set_sandbox_limits(SandboxLimits {
+            max_execution_time: Duration::ZERO,
+            ..Default::default()
+        });
+        let reason = call_state_new(&mut test);
+        assert_sandbox_violation(reason, SandboxViolation::ExecutionTimeExceeded {
+            limit: Duration::ZERO,
+        });
+    }
+
+    #[test]
+    fn it_rejects_engine_calls_that_are_not_allowed() {
+        let mut test = TemplateTest::new(vec!["tests/templates/state"]);
+        test.set_sandbox_limits(SandboxLimits {
+            allowed_engine_ops: Some(vec![EngineOp::EmitLog]),
+            ..Default::default()
+        });
+        let reason = call_state_new(&mut test);
+        assert!(
+            matches!(reason, RejectReason::SandboxViolation(ref msg) if msg.contains("is not allowed")),
+            "Unexpected reject reason: {}",
+            reason
+        );
+
+        // All engine calls are allowed by default
+        test.set_sandbox_limits(SandboxLimits::default());
+        test.call_function::<ComponentAddress>("State", "new", args![], vec![]);
+    }
+}
+
+mod text {
+    use tari_dan_engine::runtime::TextLimits;
+
+    use super::*;
+
+    #[test]
+    fn it_executes_text_utilities() {
+        let mut test = TemplateTest::new(vec!["tests/templates/text"]);
+        let is_match: bool = test.call_function("TextTest", "is_match", args![r"^\w+@tari$", "alice@tari"], vec![]);
+        assert!(is_match);
+
+        let found: Option<String> = test.call_function("TextTest", "find", args![r"\d+", "abc 123"], vec![]);
+        assert_eq!(found.as_deref(), Some("123"));
+
+        let replaced: String = test.call_function("TextTest", "replace_all", args!["a+", "baaad", "a"], vec![]);
+        assert_eq!(replaced, "bad");
+
+        let normalized: String = test.call_function("TextTest", "normalize_nfc", args!["e\u{301}"], vec![]);
+        assert_eq!(normalized, "\u{e9}");
+    }
+
+    #[test]
+    fn it_rejects_text_that_exceeds_the_limits() {
+        let mut test = TemplateTest::new(vec!["tests/templates/text"]);
+        test.set_text_limits(TextLimits {
+            max_text_len: 4,
+            ..Default::default()
+        });
+        let reason = test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(test.get_template_address("TextTest"), "is_match", args!["a", "aaaaa"])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        );
+        assert_reject_reason(reason, RuntimeError::TextLimitExceeded {
+            kind: "text",
+            len: 5,
+            limit: 4,
+        });
+
+        let reason = test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(test.get_template_address("TextTest"), "is_match", args!["(", "a"])
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        );
+        assert!(reason.to_string().contains("Invalid regex"), "Unexpected reject reason: {}", reason);
+    }
 }
 
 mod errors {
@@ -362,6 +520,13 @@ mod errors {
             },
             reason => panic!("Unexpected transaction reject reason: {}", reason),
         }
+
+        let abort = result.finalize.abort.expect("Expected an abort payload");
+        assert_eq!(abort.message, "This error message should be included in the execution result");
+        assert_eq!(abort.template_address, template_test.get_template_address("Errors"));
+        assert_eq!(abort.function, "panic");
+        assert_eq!(abort.instruction_index, 0);
+        assert!(!abort.is_fee_instruction);
     }
 
     #[test]
@@ -411,6 +576,81 @@ mod consensus {
     }
 }
 
+mod oracle {
+    use tari_template_lib::{
+        args::Arg,
+        constants::{PUBLIC_IDENTITY_RESOURCE_ADDRESS, XTR},
+    };
+
+    use super::*;
+
+    fn execute_expect_failure(test: &mut TemplateTest, function: &str, args: Vec<Arg>) -> RejectReason {
+        test.execute_expect_failure(
+            Transaction::builder()
+                .call_function(test.get_template_address("TestOracle"), function, args)
+                .sign(test.get_test_secret_key())
+                .build(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn it_aborts_if_the_price_is_stale() {
+        let mut test = TemplateTest::new(vec!["tests/templates/oracle"]);
+        let (base, quote) = (XTR, PUBLIC_IDENTITY_RESOURCE_ADDRESS);
+        test.set_virtual_substate(VirtualSubstateId::CurrentEpoch, VirtualSubstate::CurrentEpoch(10));
+        let oracle: ComponentAddress = test.call_function("TestOracle", "new", args![base, quote], vec![]);
+        test.call_method::<()>(oracle, "set_price", args![Amount(12_345), 2u8, 1_000u64], vec![]);
+
+        test.set_virtual_substate(VirtualSubstateId::CurrentEpoch, VirtualSubstate::CurrentEpoch(12));
+        let price: Amount = test.call_function(
+            "TestOracle",
+            "get_fresh_price",
+            args![oracle, base, quote, 2u64],
+            vec![],
+        );
+        assert_eq!(price, Amount(12_345));
+
+        let reason = execute_expect_failure(&mut test, "get_fresh_price", args![oracle, base, quote, 1u64]);
+        assert!(
+            reason
+                .to_string()
+                .contains("Stale oracle price: published in epoch 10, current epoch is 12"),
+            "Unexpected reject reason: {}",
+            reason
+        );
+    }
+
+    #[test]
+    fn it_aborts_if_the_price_was_observed_too_early() {
+        let mut test = TemplateTest::new(vec!["tests/templates/oracle"]);
+        let (base, quote) = (XTR, PUBLIC_IDENTITY_RESOURCE_ADDRESS);
+        let oracle: ComponentAddress = test.call_function("TestOracle", "new", args![base, quote], vec![]);
+        test.call_method::<()>(oracle, "set_price", args![Amount(12_345), 2u8, 1_000u64], vec![]);
+
+        let price: Amount = test.call_function(
+            "TestOracle",
+            "get_price_observed_since",
+            args![oracle, base, quote, 1_000u64],
+            vec![],
+        );
+        assert_eq!(price, Amount(12_345));
+
+        let reason = execute_expect_failure(
+            &mut test,
+            "get_price_observed_since",
+            args![oracle, base, quote, 1_001u64],
+        );
+        assert!(
+            reason
+                .to_string()
+                .contains("Stale oracle price: observed at 1000, which is before 1001"),
+            "Unexpected reject reason: {}",
+            reason
+        );
+    }
+}
+
 mod fungible {
     use super::*;
 
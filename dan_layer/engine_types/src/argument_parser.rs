@@ -42,7 +42,7 @@ fn convert_value_to_arg(arg: json::Value) -> Result<Arg, ArgParseError> {
         let parsed = json::from_value(arg)?;
         Ok(parsed)
     } else {
-        let value = convert_to_cbor(arg);
+        let value = json_to_cbor(arg);
         let arg = Arg::literal(value)?;
         Ok(arg)
     }
@@ -178,7 +178,9 @@ impl From<ParsedArg<'_>> for Arg {
     }
 }
 
-fn convert_to_cbor(value: json::Value) -> tari_bor::Value {
+/// Converts a JSON value to a CBOR value. Strings are parsed using the same special syntax as [parse_arg] e.g.
+/// component_xxxx.. becomes a ComponentAddress value.
+pub fn json_to_cbor(value: json::Value) -> tari_bor::Value {
     match value {
         json::Value::Null => tari_bor::Value::Null,
         json::Value::Bool(v) => tari_bor::Value::Bool(v),
@@ -213,10 +215,10 @@ fn convert_to_cbor(value: json::Value) -> tari_bor::Value {
             },
             Err(_) => tari_bor::Value::Text(s),
         },
-        json::Value::Array(arr) => tari_bor::Value::Array(arr.into_iter().map(convert_to_cbor).collect::<Vec<_>>()),
+        json::Value::Array(arr) => tari_bor::Value::Array(arr.into_iter().map(json_to_cbor).collect::<Vec<_>>()),
         json::Value::Object(map) => tari_bor::Value::Map(
             map.into_iter()
-                .map(|(k, v)| (tari_bor::Value::Text(k), convert_to_cbor(v)))
+                .map(|(k, v)| (tari_bor::Value::Text(k), json_to_cbor(v)))
                 .collect(),
         ),
     }
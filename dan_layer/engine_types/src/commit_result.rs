@@ -26,7 +26,7 @@ use std::{
 };
 
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use tari_template_lib::Hash;
+use tari_template_lib::{models::TemplateAddress, Hash};
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
@@ -37,6 +37,7 @@ use crate::{
     logs::LogEntry,
     serde_with,
     substate::SubstateDiff,
+    substate_access::SubstateAccessStats,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +47,10 @@ pub struct ExecuteResult {
     pub finalize: FinalizeResult,
     #[cfg_attr(feature = "ts", ts(type = "{secs: number, nanos: number}"))]
     pub execution_time: Duration,
+    /// Statistics of the substates accessed during execution. Only collected if enabled in the transaction processor
+    /// config, e.g. for dry runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub substate_access_stats: Option<SubstateAccessStats>,
 }
 
 impl ExecuteResult {
@@ -53,6 +58,7 @@ impl ExecuteResult {
         Self {
             finalize: FinalizeResult::new_rejected(transaction_hash, reason),
             execution_time: Duration::default(),
+            substate_access_stats: None,
         }
     }
 
@@ -135,6 +141,10 @@ pub struct FinalizeResult {
     pub execution_results: Vec<InstructionResult>,
     pub result: TransactionResult,
     pub fee_receipt: FeeReceipt,
+    /// Set if the transaction failed because a template panicked or trapped
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "TemplateAbort | null"))]
+    pub abort: Option<TemplateAbort>,
 }
 
 impl FinalizeResult {
@@ -152,6 +162,7 @@ impl FinalizeResult {
             execution_results: Vec::new(),
             result,
             fee_receipt,
+            abort: None,
         }
     }
 
@@ -163,6 +174,7 @@ impl FinalizeResult {
             execution_results: Vec::new(),
             result: TransactionResult::Reject(reason),
             fee_receipt: FeeReceipt::default(),
+            abort: None,
         }
     }
 
@@ -209,6 +221,36 @@ impl FinalizeResult {
     }
 }
 
+/// Describes a template panic (or trap) that aborted a transaction. If the panic occurred in a cross-template call,
+/// the template and function are those of the innermost call that panicked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TemplateAbort {
+    pub message: String,
+    #[serde(with = "serde_with::hex")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub template_address: TemplateAddress,
+    pub function: String,
+    /// The index of the instruction that aborted, within the fee instructions if `is_fee_instruction` is true,
+    /// otherwise within the main instructions
+    pub instruction_index: u32,
+    pub is_fee_instruction: bool,
+}
+
+impl Display for TemplateAbort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} instruction {} aborted in {}::{}: {}",
+            if self.is_fee_instruction { "Fee" } else { "Main" },
+            self.instruction_index,
+            self.template_address,
+            self.function,
+            self.message
+        )
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub enum TransactionResult {
@@ -294,9 +336,22 @@ pub enum RejectReason {
     FailedToLockOutputs(String),
     ForeignShardGroupDecidedToAbort { start_shard: u32, end_shard: u32 },
     FeesNotPaid(String),
+    /// A template exceeded a resource limit of the WASM sandbox or made a call that the sandbox does not allow
+    SandboxViolation(String),
     Unknown,
 }
 
+impl RejectReason {
+    /// Returns true if the transaction was rejected because one or more inputs had already been consumed or were
+    /// locked by another transaction. Such a transaction may succeed if it is resubmitted with up-to-date inputs.
+    pub fn is_input_conflict(&self) -> bool {
+        matches!(
+            self,
+            RejectReason::OneOrMoreInputsNotFound(_) | RejectReason::FailedToLockInputs(_)
+        )
+    }
+}
+
 impl Display for RejectReason {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
@@ -309,6 +364,7 @@ impl Display for RejectReason {
                 write!(f, "Foreign shard group ({start_shard}-{end_shard}) decided to abort")
             },
             RejectReason::FeesNotPaid(msg) => write!(f, "Fee not paid: {}", msg),
+            RejectReason::SandboxViolation(msg) => write!(f, "Sandbox violation: {}", msg),
             RejectReason::Unknown => write!(f, "<unknown reject reason - this is not valid>"),
         }
     }
@@ -20,6 +20,12 @@ pub struct FeeReceipt {
     pub total_fees_paid: Amount,
     /// Breakdown of fee costs
     pub cost_breakdown: FeeBreakdown,
+    /// Peak memory used while executing the transaction
+    #[serde(default)]
+    pub memory_usage: MemoryUsage,
+    /// Gas consumed by executing WASM templates
+    #[serde(default)]
+    pub gas_usage: GasUsage,
 }
 
 impl FeeReceipt {
@@ -27,6 +33,7 @@ impl FeeReceipt {
         FeeCostBreakdown {
             total_fees_charged: self.total_fees_charged(),
             breakdown: self.cost_breakdown.clone(),
+            gas_usage: self.gas_usage,
         }
     }
 
@@ -64,6 +71,37 @@ impl FeeReceipt {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct MemoryUsage {
+    /// The peak combined size of the WASM linear memories that were in use at the same time
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub peak_wasm_memory_bytes: u64,
+    /// The total number of bytes allocated by the engine on behalf of the transaction
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub host_allocated_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct GasUsage {
+    /// Gas consumed by executed WASM instructions
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub instructions: u64,
+    /// Gas consumed by growing WASM linear memory
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub memory_growth: u64,
+    /// The gas budget of the transaction
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub limit: u64,
+}
+
+impl GasUsage {
+    pub fn total(&self) -> u64 {
+        self.instructions.saturating_add(self.memory_growth)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Hash, Eq, PartialEq, PartialOrd, Ord)]
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub enum FeeSource {
@@ -72,6 +110,7 @@ pub enum FeeSource {
     Storage,
     Events,
     Logs,
+    Gas,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -108,6 +147,8 @@ impl FeeBreakdown {
 pub struct FeeCostBreakdown {
     pub total_fees_charged: Amount,
     pub breakdown: FeeBreakdown,
+    #[serde(default)]
+    pub gas_usage: GasUsage,
 }
 
 #[derive(Debug)]
@@ -81,6 +81,17 @@ impl TariHasher32 {
         self
     }
 
+    /// Updates the hasher with bytes that are already encoded. Hashing the CBOR encoding of a value with this function
+    /// gives the same result as [`Self::update`].
+    pub fn update_raw(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    pub fn chain_raw(mut self, bytes: &[u8]) -> Self {
+        self.update_raw(bytes);
+        self
+    }
+
     pub fn digest<T: Serialize + ?Sized>(self, data: &T) -> Hash {
         self.chain(data).result()
     }
@@ -135,6 +146,17 @@ impl TariHasher64 {
         self
     }
 
+    /// Updates the hasher with bytes that are already encoded. Hashing the CBOR encoding of a value with this function
+    /// gives the same result as [`Self::update`].
+    pub fn update_raw(&mut self, bytes: &[u8]) {
+        self.hasher.update(bytes);
+    }
+
+    pub fn chain_raw(mut self, bytes: &[u8]) -> Self {
+        self.update_raw(bytes);
+        self
+    }
+
     pub fn digest<T: Serialize + ?Sized>(self, data: &T) -> [u8; 64] {
         self.chain(data).result()
     }
@@ -184,6 +206,7 @@ pub enum EngineHashDomainLabel {
     QuorumCertificate,
     SubstateValue,
     ViewKey,
+    ConsensusMessage,
 }
 
 impl EngineHashDomainLabel {
@@ -208,6 +231,12 @@ impl EngineHashDomainLabel {
             Self::SubstateValue => "SubstateValue",
             Self::ViewKey => "ViewKey",
             Self::TemplateAddress => "TemplateAddress",
+            Self::ConsensusMessage => "ConsensusMessage",
         }
     }
+
+    /// Returns the domain separation tag that is prepended to the hash input of hashers with this label
+    pub fn domain_separation_tag(&self) -> String {
+        TariEngineHashDomain::domain_separation_tag(self.as_label())
+    }
 }
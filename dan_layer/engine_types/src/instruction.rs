@@ -14,7 +14,7 @@ use tari_template_lib::{
 #[cfg(feature = "ts")]
 use ts_rs::TS;
 
-use crate::{confidential::ConfidentialClaim, serde_with};
+use crate::{confidential::ConfidentialClaim, published_template::TemplateType, serde_with};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
@@ -69,6 +69,21 @@ pub enum Instruction {
     },
     PublishTemplate {
         binary: Vec<u8>,
+        /// If set, the template is published as the next version of this template. The previous version must have
+        /// been published by the transaction signer.
+        #[serde(default)]
+        #[cfg_attr(feature = "ts", ts(type = "Uint8Array | null"))]
+        previous_version: Option<TemplateAddress>,
+        #[serde(default)]
+        template_type: TemplateType,
+    },
+    /// Migrates a component to a newer version of its template. Must be signed by the component owner.
+    MigrateComponent {
+        #[serde(with = "serde_with::string")]
+        component_address: ComponentAddress,
+        #[serde(with = "serde_with::hex")]
+        #[cfg_attr(feature = "ts", ts(type = "Uint8Array"))]
+        template_address: TemplateAddress,
     },
 }
 
@@ -151,9 +166,26 @@ impl Display for Instruction {
                     key, resource_address, min_amount
                 )
             },
-            Instruction::PublishTemplate { .. } => {
-                write!(f, "PublishTemplate")
+            Instruction::PublishTemplate {
+                previous_version,
+                template_type,
+                ..
+            } => match previous_version {
+                Some(previous_version) => write!(
+                    f,
+                    "PublishTemplate {{ template_type: {}, previous_version: {} }}",
+                    template_type, previous_version
+                ),
+                None => write!(f, "PublishTemplate {{ template_type: {} }}", template_type),
             },
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => write!(
+                f,
+                "MigrateComponent {{ component_address: {}, template_address: {} }}",
+                component_address, template_address
+            ),
         }
     }
 }
@@ -19,9 +19,13 @@ pub mod non_fungible;
 pub mod non_fungible_index;
 pub mod proof;
 pub mod resource;
+pub mod resource_amount;
 pub mod resource_container;
+pub mod rpc_encoding;
 pub mod serde_with;
 pub mod substate;
+pub mod substate_access;
+pub mod substate_proof;
 pub mod transaction_receipt;
 pub mod vault;
 pub mod virtual_substate;
@@ -35,7 +39,7 @@ pub mod id_provider;
 mod argument_parser;
 pub mod published_template;
 
-pub use argument_parser::parse_arg;
+pub use argument_parser::{json_to_cbor, parse_arg};
 
 pub mod template_models {
     pub use tari_template_lib::models::*;
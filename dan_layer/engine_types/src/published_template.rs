@@ -8,6 +8,7 @@ use std::{
 };
 
 use tari_bor::{BorTag, Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
 use tari_template_lib::{
     models::{BinaryTag, KeyParseError, ObjectKey},
     Hash,
@@ -69,6 +70,52 @@ impl borsh::BorshSerialize for PublishedTemplateAddress {
     }
 }
 
+/// The executable format of a published template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum TemplateType {
+    /// A compiled WASM binary
+    #[default]
+    Wasm,
+    /// A UTF-8 encoded JSON flow definition
+    Flow,
+}
+
+impl TemplateType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateType::Wasm => "Wasm",
+            TemplateType::Flow => "Flow",
+        }
+    }
+
+    pub fn is_flow(&self) -> bool {
+        matches!(self, TemplateType::Flow)
+    }
+}
+
+impl Display for TemplateType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TemplateType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "wasm" => Ok(TemplateType::Wasm),
+            "flow" => Ok(TemplateType::Flow),
+            _ => Err(format!("Invalid template type '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -76,5 +123,59 @@ impl borsh::BorshSerialize for PublishedTemplateAddress {
     ts(export, export_to = "../../bindings/src/types/")
 )]
 pub struct PublishedTemplate {
+    /// The template executable. For flow templates this is the JSON flow definition.
     pub binary: Vec<u8>,
+    #[serde(default)]
+    pub template_type: TemplateType,
+    /// The public key of the author that published the template. Only the author may publish new versions of the
+    /// template. Templates published before versioning was introduced have no recorded author and cannot be upgraded.
+    #[serde(default)]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub author: Option<PublicKey>,
+    /// The version of this template within its lineage. The first published version is 0.
+    #[serde(default)]
+    pub template_version: u32,
+    /// The template that this template is a new version of, if any
+    #[serde(default)]
+    pub previous_version: Option<PublishedTemplateAddress>,
+    /// The first version of the lineage this template belongs to. None if this template is the first version.
+    #[serde(default)]
+    pub lineage_root: Option<PublishedTemplateAddress>,
+}
+
+impl PublishedTemplate {
+    /// Creates the first version of a new template lineage
+    pub fn new(binary: Vec<u8>, template_type: TemplateType, author: PublicKey) -> Self {
+        Self {
+            binary,
+            template_type,
+            author: Some(author),
+            template_version: 0,
+            previous_version: None,
+            lineage_root: None,
+        }
+    }
+
+    /// Creates the next version of the given template
+    pub fn new_version(
+        binary: Vec<u8>,
+        template_type: TemplateType,
+        previous_address: PublishedTemplateAddress,
+        previous: &Self,
+    ) -> Self {
+        Self {
+            binary,
+            template_type,
+            author: previous.author.clone(),
+            template_version: previous.template_version + 1,
+            previous_version: Some(previous_address),
+            lineage_root: Some(previous.lineage_root_or(previous_address)),
+        }
+    }
+
+    /// Returns the root of the lineage, given the address of this template. Two templates are versions of the same
+    /// template if their lineage roots are equal.
+    pub fn lineage_root_or(&self, this_address: PublishedTemplateAddress) -> PublishedTemplateAddress {
+        self.lineage_root.unwrap_or(this_address)
+    }
 }
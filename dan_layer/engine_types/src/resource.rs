@@ -26,7 +26,7 @@ use tari_template_lib::{
     auth::{AuthHook, OwnerRule, Ownership, ResourceAccessRules},
     crypto::RistrettoPublicKeyBytes,
     models::{Amount, Metadata},
-    resource::{ResourceType, TOKEN_SYMBOL},
+    resource::{ResourceType, DECIMALS, IMAGE_URL, TOKEN_NAME, TOKEN_SYMBOL},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -140,7 +140,66 @@ impl Resource {
         &self.metadata
     }
 
+    /// Sets the metadata entry, or removes it if the value is None. Returns an error if the value of a well-known entry
+    /// is invalid, in which case the metadata is not changed.
+    pub fn set_metadata_entry(&mut self, key: String, value: Option<String>) -> Result<(), ResourceMetadataError> {
+        match value {
+            Some(value) => {
+                validate_metadata_entry(&key, &value)?;
+                self.metadata.insert(key, value);
+            },
+            None => {
+                self.metadata.remove(&key);
+            },
+        }
+        Ok(())
+    }
+
     pub fn token_symbol(&self) -> Option<&str> {
         self.metadata.get(TOKEN_SYMBOL).map(|s| s.as_str())
     }
+
+    pub fn name(&self) -> Option<&str> {
+        self.metadata.get(TOKEN_NAME).map(|s| s.as_str())
+    }
+
+    pub fn image_url(&self) -> Option<&str> {
+        self.metadata.get(IMAGE_URL).map(|s| s.as_str())
+    }
+
+    /// Returns the number of decimal places declared in the resource metadata, if any and valid.
+    pub fn decimals(&self) -> Option<u8> {
+        self.metadata.get(DECIMALS).and_then(|s| s.parse().ok())
+    }
+}
+
+/// Checks that the values of the well-known metadata entries are valid. Other entries are not checked.
+pub fn validate_metadata(metadata: &Metadata) -> Result<(), ResourceMetadataError> {
+    for (key, value) in metadata.iter() {
+        validate_metadata_entry(key, value)?;
+    }
+    Ok(())
+}
+
+fn validate_metadata_entry(key: &str, value: &str) -> Result<(), ResourceMetadataError> {
+    match key {
+        DECIMALS => {
+            value
+                .parse::<u8>()
+                .map_err(|_| ResourceMetadataError::InvalidDecimals { value: value.to_string() })?;
+        },
+        TOKEN_SYMBOL | TOKEN_NAME if value.trim().is_empty() => {
+            return Err(ResourceMetadataError::EmptyValue { key: key.to_string() });
+        },
+        _ => {},
+    }
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ResourceMetadataError {
+    #[error("Metadata entry {key} must not be empty")]
+    EmptyValue { key: String },
+    #[error("Metadata entry DECIMALS must be a number between 0 and 255, got '{value}'")]
+    InvalidDecimals { value: String },
 }
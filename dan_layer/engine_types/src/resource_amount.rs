@@ -0,0 +1,261 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use tari_template_lib::models::Amount;
+
+use crate::resource::Resource;
+
+/// The maximum number of decimal places a resource may declare. 10^18 is the largest power of ten that fits in an i64.
+pub const MAX_DECIMALS: u8 = 18;
+
+/// An [Amount] of a resource together with the number of decimal places the resource uses for display. The inner
+/// amount is always denominated in the smallest indivisible unit of the resource (e.g. µT for XTR).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct ResourceAmount {
+    amount: Amount,
+    decimals: u8,
+}
+
+impl ResourceAmount {
+    pub fn new(amount: Amount, decimals: u8) -> Result<Self, ResourceAmountError> {
+        if decimals > MAX_DECIMALS {
+            return Err(ResourceAmountError::TooManyDecimals { decimals });
+        }
+        Ok(Self { amount, decimals })
+    }
+
+    /// Creates a new amount of the given resource. Resources that do not declare decimals are indivisible.
+    pub fn for_resource(amount: Amount, resource: &Resource) -> Result<Self, ResourceAmountError> {
+        Self::new(amount, resource.decimals().unwrap_or(0))
+    }
+
+    /// Parses a display string such as "1.5" into an amount of the smallest unit. The string may contain at most
+    /// `decimals` digits after the decimal point.
+    pub fn from_display_str(s: &str, decimals: u8) -> Result<Self, ResourceAmountError> {
+        if decimals > MAX_DECIMALS {
+            return Err(ResourceAmountError::TooManyDecimals { decimals });
+        }
+        let invalid = || ResourceAmountError::InvalidDisplayString { value: s.to_string() };
+
+        let s = s.trim();
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return Err(invalid());
+        }
+        if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        if fraction.len() > usize::from(decimals) {
+            return Err(ResourceAmountError::PrecisionExceeded {
+                value: s.to_string(),
+                decimals,
+            });
+        }
+
+        let scale = 10i64.pow(u32::from(decimals));
+        let whole = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<i64>().map_err(|_| ResourceAmountError::Overflow)?
+        };
+        // Right-pad the fraction so that it is expressed in the smallest unit e.g. "5" with 3 decimals is 500
+        let fraction = if fraction.is_empty() {
+            0
+        } else {
+            format!("{:0<width$}", fraction, width = usize::from(decimals))
+                .parse::<i64>()
+                .map_err(|_| ResourceAmountError::Overflow)?
+        };
+
+        let value = whole
+            .checked_mul(scale)
+            .and_then(|v| v.checked_add(fraction))
+            .ok_or(ResourceAmountError::Overflow)?;
+
+        Ok(Self {
+            amount: Amount::new(if negative { -value } else { value }),
+            decimals,
+        })
+    }
+
+    /// The amount in the smallest unit of the resource
+    pub fn amount(&self) -> Amount {
+        self.amount
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    pub fn to_display_string(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Result<Self, ResourceAmountError> {
+        self.check_decimals(other)?;
+        let amount = self
+            .amount
+            .checked_add(other.amount)
+            .ok_or(ResourceAmountError::Overflow)?;
+        Ok(Self { amount, ..*self })
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Result<Self, ResourceAmountError> {
+        self.check_decimals(other)?;
+        let amount = self
+            .amount
+            .checked_sub(other.amount)
+            .ok_or(ResourceAmountError::Overflow)?;
+        Ok(Self { amount, ..*self })
+    }
+
+    fn check_decimals(&self, other: &Self) -> Result<(), ResourceAmountError> {
+        if self.decimals != other.decimals {
+            return Err(ResourceAmountError::DecimalsMismatch {
+                left: self.decimals,
+                right: other.decimals,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<ResourceAmount> for Amount {
+    fn from(value: ResourceAmount) -> Self {
+        value.amount
+    }
+}
+
+impl Display for ResourceAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.decimals == 0 {
+            return write!(f, "{}", self.amount);
+        }
+        let scale = 10u64.pow(u32::from(self.decimals));
+        let value = self.amount.value().unsigned_abs();
+        let sign = if self.amount.is_negative() { "-" } else { "" };
+        let fraction = format!("{:0width$}", value % scale, width = usize::from(self.decimals));
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, value / scale)
+        } else {
+            write!(f, "{}{}.{}", sign, value / scale, fraction)
+        }
+    }
+}
+
+impl FromStr for ResourceAmount {
+    type Err = ResourceAmountError;
+
+    /// Parses a string in the form `<display amount>@<decimals>` e.g. `1.5@6`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, decimals) = s
+            .split_once('@')
+            .ok_or_else(|| ResourceAmountError::InvalidDisplayString { value: s.to_string() })?;
+        let decimals = decimals
+            .parse()
+            .map_err(|_| ResourceAmountError::InvalidDisplayString { value: s.to_string() })?;
+        Self::from_display_str(value, decimals)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ResourceAmountError {
+    #[error("Invalid amount '{value}'")]
+    InvalidDisplayString { value: String },
+    #[error("Amount '{value}' has more than {decimals} decimal places")]
+    PrecisionExceeded { value: String, decimals: u8 },
+    #[error("Resource decimals {decimals} exceeds the maximum of {MAX_DECIMALS}")]
+    TooManyDecimals { decimals: u8 },
+    #[error("Cannot combine amounts with {left} and {right} decimals")]
+    DecimalsMismatch { left: u8, right: u8 },
+    #[error("Amount overflowed")]
+    Overflow,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_display_strings() {
+        let amount = ResourceAmount::from_display_str("1.5", 6).unwrap();
+        assert_eq!(amount.amount(), Amount(1_500_000));
+        let amount = ResourceAmount::from_display_str("0.000001", 6).unwrap();
+        assert_eq!(amount.amount(), Amount(1));
+        let amount = ResourceAmount::from_display_str(".25", 2).unwrap();
+        assert_eq!(amount.amount(), Amount(25));
+        let amount = ResourceAmount::from_display_str("-3", 1).unwrap();
+        assert_eq!(amount.amount(), Amount(-30));
+        let amount = ResourceAmount::from_display_str("42", 0).unwrap();
+        assert_eq!(amount.amount(), Amount(42));
+    }
+
+    #[test]
+    fn it_rejects_invalid_display_strings() {
+        assert_eq!(
+            ResourceAmount::from_display_str("1.0000001", 6).unwrap_err(),
+            ResourceAmountError::PrecisionExceeded {
+                value: "1.0000001".to_string(),
+                decimals: 6
+            }
+        );
+        assert!(ResourceAmount::from_display_str("1.5", 0).is_err());
+        assert!(ResourceAmount::from_display_str("", 6).is_err());
+        assert!(ResourceAmount::from_display_str(".", 6).is_err());
+        assert!(ResourceAmount::from_display_str("1,5", 6).is_err());
+        assert!(ResourceAmount::from_display_str("1e6", 6).is_err());
+        assert_eq!(
+            ResourceAmount::from_display_str("10000000000000", 6).unwrap_err(),
+            ResourceAmountError::Overflow
+        );
+    }
+
+    #[test]
+    fn it_displays_amounts() {
+        let amount = ResourceAmount::new(Amount(1_500_000), 6).unwrap();
+        assert_eq!(amount.to_display_string(), "1.5");
+        let amount = ResourceAmount::new(Amount(2_000_000), 6).unwrap();
+        assert_eq!(amount.to_display_string(), "2");
+        let amount = ResourceAmount::new(Amount(-1), 6).unwrap();
+        assert_eq!(amount.to_display_string(), "-0.000001");
+        let amount = ResourceAmount::new(Amount(123), 0).unwrap();
+        assert_eq!(amount.to_display_string(), "123");
+    }
+
+    #[test]
+    fn it_round_trips_display_strings() {
+        for s in ["0", "1", "0.1", "123.456789", "-7.05"] {
+            let amount = ResourceAmount::from_display_str(s, 6).unwrap();
+            assert_eq!(amount.to_display_string(), s);
+        }
+        let amount: ResourceAmount = "1.5@6".parse().unwrap();
+        assert_eq!(amount.amount(), Amount(1_500_000));
+    }
+
+    #[test]
+    fn it_only_combines_amounts_with_equal_decimals() {
+        let a = ResourceAmount::new(Amount(10), 2).unwrap();
+        let b = ResourceAmount::new(Amount(5), 2).unwrap();
+        assert_eq!(a.checked_add(&b).unwrap().amount(), Amount(15));
+        assert_eq!(a.checked_sub(&b).unwrap().amount(), Amount(5));
+
+        let c = ResourceAmount::new(Amount(5), 3).unwrap();
+        assert_eq!(a.checked_add(&c).unwrap_err(), ResourceAmountError::DecimalsMismatch {
+            left: 2,
+            right: 3
+        });
+    }
+}
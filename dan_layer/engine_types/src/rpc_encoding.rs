@@ -0,0 +1,128 @@
+//    Copyright 2024 The Tari Project
+//    SPDX-License-Identifier: BSD-3-Clause
+
+//! Encodings of JSON-RPC responses. JSON is used by default. A client may request a compact binary (CBOR) response by
+//! sending `Accept: application/cbor`, which avoids the CPU and bandwidth cost of JSON for large results such as
+//! execution results and substates. The response structure is the same in both encodings.
+
+use serde::{de::DeserializeOwned, Serialize};
+use tari_bor::BorError;
+
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+pub const CBOR_CONTENT_TYPE: &str = "application/cbor";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RpcEncoding {
+    #[default]
+    Json,
+    Cbor,
+}
+
+impl RpcEncoding {
+    /// Returns the encoding requested by the value of an `Accept` header. JSON is used unless CBOR is accepted.
+    pub fn from_accept_header(accept: Option<&str>) -> Self {
+        let accepts_cbor = accept.is_some_and(|accept| accept.split(',').any(is_cbor_media_type));
+        if accepts_cbor {
+            Self::Cbor
+        } else {
+            Self::Json
+        }
+    }
+
+    /// Returns the encoding of a response with the given `Content-Type` header
+    pub fn from_content_type(content_type: Option<&str>) -> Self {
+        if content_type.is_some_and(is_cbor_media_type) {
+            Self::Cbor
+        } else {
+            Self::Json
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::Cbor => CBOR_CONTENT_TYPE,
+        }
+    }
+
+    pub fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, RpcEncodingError> {
+        match self {
+            Self::Json => Ok(serde_json::to_vec(value)?),
+            Self::Cbor => Ok(tari_bor::encode(value)?),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, RpcEncodingError> {
+        match self {
+            Self::Json => Ok(serde_json::from_slice(bytes)?),
+            Self::Cbor => Ok(tari_bor::decode(bytes)?),
+        }
+    }
+}
+
+fn is_cbor_media_type(media_type: &str) -> bool {
+    // Ignore parameters e.g. "application/cbor; q=0.9"
+    media_type
+        .split(';')
+        .next()
+        .is_some_and(|media_type| media_type.trim().eq_ignore_ascii_case(CBOR_CONTENT_TYPE))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RpcEncodingError {
+    #[error("JSON encoding error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("CBOR encoding error: {0}")]
+    Cbor(#[from] BorError),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tari_template_lib::models::{Amount, ComponentAddress};
+
+    use super::*;
+    use crate::substate::SubstateId;
+
+    #[test]
+    fn it_negotiates_the_encoding() {
+        assert_eq!(RpcEncoding::from_accept_header(None), RpcEncoding::Json);
+        assert_eq!(RpcEncoding::from_accept_header(Some("*/*")), RpcEncoding::Json);
+        assert_eq!(
+            RpcEncoding::from_accept_header(Some("application/json, application/cbor;q=0.9")),
+            RpcEncoding::Cbor
+        );
+        assert_eq!(RpcEncoding::from_content_type(Some("application/cbor")), RpcEncoding::Cbor);
+        assert_eq!(
+            RpcEncoding::from_content_type(Some("application/json; charset=utf-8")),
+            RpcEncoding::Json
+        );
+    }
+
+    #[test]
+    fn it_round_trips_json_rpc_responses() {
+        let substate_id = SubstateId::Component(ComponentAddress::from_array([1u8; 32]));
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "substate_id": substate_id,
+                "amount": Amount(-123),
+                "data": vec![0u8; 1024],
+                "nested": { "version": u32::MAX, "empty": null },
+            },
+        });
+
+        for encoding in [RpcEncoding::Json, RpcEncoding::Cbor] {
+            let bytes = encoding.encode(&response).unwrap();
+            let decoded: serde_json::Value = encoding.decode(&bytes).unwrap();
+            assert_eq!(decoded, response);
+            let decoded_id: SubstateId = serde_json::from_value(decoded["result"]["substate_id"].clone()).unwrap();
+            assert_eq!(decoded_id, substate_id);
+        }
+
+        let json_len = RpcEncoding::Json.encode(&response).unwrap().len();
+        let cbor_len = RpcEncoding::Cbor.encode(&response).unwrap().len();
+        assert!(cbor_len < json_len);
+    }
+}
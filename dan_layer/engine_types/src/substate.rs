@@ -21,13 +21,14 @@
 //   USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashSet,
     fmt::{Display, Formatter},
     str::FromStr,
 };
 
 use borsh::BorshSerialize;
 use serde::{Deserialize, Serialize};
-use tari_bor::{decode, decode_exact, encode, BorError};
+use tari_bor::{decode, decode_exact, encode, encoded_len, BorError};
 use tari_common_types::types::FixedHash;
 use tari_template_lib::{
     models::{
@@ -495,6 +496,13 @@ impl SubstateValue {
         }
     }
 
+    pub fn into_published_template(self) -> Option<PublishedTemplate> {
+        match self {
+            SubstateValue::Template(template) => Some(template),
+            _ => None,
+        }
+    }
+
     pub fn into_vault(self) -> Option<Vault> {
         match self {
             SubstateValue::Vault(vault) => Some(vault),
@@ -772,6 +780,58 @@ impl SubstateDiff {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Combines this diff with a diff that was produced by a subsequent execution stage. The result is normalized.
+    pub fn merge(&mut self, other: SubstateDiff) -> &mut Self {
+        self.up_substates.extend(other.up_substates);
+        self.down_substates.extend(other.down_substates);
+        self.normalize()
+    }
+
+    /// Removes redundant entries from the diff. If a substate is upped more than once, only the last write is kept. A
+    /// substate version that is upped and then downed within the diff never needs to be stored, so both entries are
+    /// removed. Duplicate downs are removed.
+    pub fn normalize(&mut self) -> &mut Self {
+        // Keep the last write for each substate address
+        let mut seen = HashSet::with_capacity(self.up_substates.len());
+        let mut up_substates = Vec::with_capacity(self.up_substates.len());
+        for (id, substate) in self.up_substates.drain(..).rev() {
+            if seen.insert(id.clone()) {
+                up_substates.push((id, substate));
+            }
+        }
+        up_substates.reverse();
+
+        let mut seen = HashSet::with_capacity(self.down_substates.len());
+        self.down_substates.retain(|down| seen.insert(down.clone()));
+
+        // Cancel out substate versions that are created and destroyed within the diff
+        let cancelled = up_substates
+            .iter()
+            .map(|(id, substate)| (id.clone(), substate.version()))
+            .filter(|up| seen.contains(up))
+            .collect::<HashSet<_>>();
+        if !cancelled.is_empty() {
+            up_substates.retain(|(id, substate)| !cancelled.contains(&(id.clone(), substate.version())));
+            self.down_substates.retain(|down| !cancelled.contains(down));
+        }
+
+        self.up_substates = up_substates;
+        self
+    }
+
+    /// Returns the number of bytes of substate values that are stored when the diff is applied
+    pub fn storage_size(&self) -> Result<usize, BorError> {
+        storage_size(self.up_substates.iter().map(|(_, substate)| substate.substate_value()))
+    }
+}
+
+/// Returns the number of bytes required to store the given substate values. This is the size that storage fees are
+/// charged on.
+pub fn storage_size<'a, I: IntoIterator<Item = &'a SubstateValue>>(values: I) -> Result<usize, BorError> {
+    values
+        .into_iter()
+        .try_fold(0, |total, value| Ok(total + encoded_len(value)?))
 }
 
 #[cfg(test)]
@@ -835,4 +895,86 @@ mod tests {
             check("template_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff");
         }
     }
+
+    mod substate_diff {
+        use super::*;
+
+        fn substate_id(n: u8) -> SubstateId {
+            SubstateId::from_str(&format!(
+                "nft_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff_u32_{}",
+                n
+            ))
+            .unwrap()
+        }
+
+        fn substate(version: u32, data: u64) -> Substate {
+            Substate::new(
+                version,
+                NonFungibleContainer::new(tari_bor::Value::Integer(data.into()), tari_bor::Value::Null),
+            )
+        }
+
+        #[test]
+        fn it_keeps_the_last_write_for_each_address() {
+            let mut diff = SubstateDiff::new();
+            diff.up(substate_id(1), substate(1, 1));
+            diff.up(substate_id(2), substate(1, 2));
+            diff.up(substate_id(1), substate(1, 3));
+            diff.down(substate_id(3), 0);
+            diff.down(substate_id(3), 0);
+            diff.normalize();
+
+            let ups = diff.up_iter().collect::<Vec<_>>();
+            assert_eq!(ups.len(), 2);
+            assert_eq!(*ups[0].0, substate_id(2));
+            assert_eq!(*ups[1].0, substate_id(1));
+            assert_eq!(
+                encode(ups[1].1.substate_value()).unwrap(),
+                encode(substate(1, 3).substate_value()).unwrap()
+            );
+            assert_eq!(diff.down_len(), 1);
+        }
+
+        #[test]
+        fn it_cancels_substates_that_are_upped_and_downed() {
+            let mut diff = SubstateDiff::new();
+            diff.down(substate_id(1), 0);
+            diff.up(substate_id(1), substate(1, 1));
+            diff.down(substate_id(1), 1);
+            diff.up(substate_id(2), substate(0, 2));
+            diff.normalize();
+
+            assert_eq!(diff.up_len(), 1);
+            assert_eq!(*diff.up_iter().next().unwrap().0, substate_id(2));
+            assert_eq!(diff.down_iter().collect::<Vec<_>>(), vec![&(substate_id(1), 0)]);
+        }
+
+        #[test]
+        fn it_merges_sequential_diffs() {
+            let mut first = SubstateDiff::new();
+            first.down(substate_id(1), 0);
+            first.up(substate_id(1), substate(1, 1));
+
+            let mut second = SubstateDiff::new();
+            second.down(substate_id(1), 1);
+            second.up(substate_id(1), substate(2, 2));
+
+            first.merge(second);
+            assert_eq!(first.up_len(), 1);
+            assert_eq!(first.up_iter().next().unwrap().1.version(), 2);
+            assert_eq!(first.down_iter().collect::<Vec<_>>(), vec![&(substate_id(1), 0)]);
+        }
+
+        #[test]
+        fn it_calculates_the_storage_size_of_upped_substates() {
+            let mut diff = SubstateDiff::new();
+            assert_eq!(diff.storage_size().unwrap(), 0);
+
+            let value = substate(0, 1);
+            let expected = encoded_len(value.substate_value()).unwrap();
+            diff.up(substate_id(1), value);
+            diff.down(substate_id(2), 0);
+            assert_eq!(diff.storage_size().unwrap(), expected);
+        }
+    }
 }
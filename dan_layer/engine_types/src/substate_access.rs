@@ -0,0 +1,65 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_template_lib::models::TemplateAddress;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{serde_with, substate::SubstateId};
+
+/// Statistics of the substates that were accessed while executing a transaction. These are collected for dry runs so
+/// that template authors can see which data their templates read and write, and how that contributes to fees.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SubstateAccessStats {
+    /// Substate accesses grouped by the template that made them, ordered by the number of bytes written
+    pub templates: Vec<TemplateSubstateAccess>,
+    /// The most frequently accessed substates, ordered by the number of accesses
+    pub hot_substates: Vec<SubstateAccess>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TemplateSubstateAccess {
+    /// The template that accessed the substates, or None for accesses made by the engine outside of a template call
+    /// e.g. to claim a burn or pay fees
+    #[serde(with = "serde_with::hex::option")]
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub template_address: Option<TemplateAddress>,
+    /// The number of read locks taken
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_reads: u64,
+    /// The number of write locks taken
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_writes: u64,
+    /// The number of distinct substates accessed
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_substates: u64,
+    /// The total encoded size of the substates when they were locked
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_read: u64,
+    /// The total encoded size of the substates last written by the template that are stored by the transaction. This
+    /// is the size that storage fees are charged on.
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub bytes_written: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SubstateAccess {
+    pub substate_id: SubstateId,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_reads: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_writes: u64,
+    /// The encoded size of the substate, as stored by the transaction if it was written
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub size: u64,
+}
+
+impl SubstateAccess {
+    pub fn num_accesses(&self) -> u64 {
+        self.num_reads + self.num_writes
+    }
+}
@@ -0,0 +1,83 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::FixedHash;
+use tari_jellyfish::{jmt_node_hash, LeafKey, SparseMerkleProofExt, TreeHash};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::substate::{hash_substate, SubstateId, SubstateValue};
+
+/// Proof that a substate value is included in the committed state of a block.
+///
+/// The proof has two parts: a proof that the substate is a leaf of its shard's state tree, and a proof that the root of
+/// that shard state tree is a leaf of the tree that makes up the block's `state_merkle_root`. A light client that
+/// trusts a block header can use this to verify a substate returned by an untrusted validator node.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SubstateInclusionProof {
+    /// Inclusion proof of the substate in the shard state tree
+    #[cfg_attr(feature = "ts", ts(type = "any"))]
+    pub shard_proof: SparseMerkleProofExt,
+    /// The state tree root of the shard that contains the substate
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub shard_state_root: TreeHash,
+    /// Inclusion proof of the shard state root in the block state merkle root
+    #[cfg_attr(feature = "ts", ts(type = "any"))]
+    pub state_root_proof: SparseMerkleProofExt,
+}
+
+impl SubstateInclusionProof {
+    pub fn new(
+        shard_proof: SparseMerkleProofExt,
+        shard_state_root: TreeHash,
+        state_root_proof: SparseMerkleProofExt,
+    ) -> Self {
+        Self {
+            shard_proof,
+            shard_state_root,
+            state_root_proof,
+        }
+    }
+
+    /// Verifies that the substate with the given id, version and value is included in the state with the given
+    /// `state_merkle_root`.
+    pub fn verify(
+        &self,
+        substate_id: &SubstateId,
+        version: u32,
+        value: &SubstateValue,
+        state_merkle_root: &FixedHash,
+    ) -> Result<(), SubstateProofError> {
+        let leaf_key = substate_leaf_key(substate_id, version);
+        let value_hash = TreeHash::new(hash_substate(value, version).into_array());
+        self.shard_proof
+            .verify_inclusion(&self.shard_state_root, &leaf_key, &value_hash)
+            .map_err(|e| SubstateProofError::InvalidShardProof { details: e.to_string() })?;
+
+        let state_merkle_root = TreeHash::new(state_merkle_root.into_array());
+        self.state_root_proof
+            .verify_inclusion(
+                &state_merkle_root,
+                &LeafKey::new(self.shard_state_root),
+                &self.shard_state_root,
+            )
+            .map_err(|e| SubstateProofError::InvalidStateRootProof { details: e.to_string() })?;
+
+        Ok(())
+    }
+}
+
+/// Returns the state tree leaf key for the substate. This is the key mapping used by the validator node state tree.
+pub fn substate_leaf_key(substate_id: &SubstateId, version: u32) -> LeafKey {
+    LeafKey::new(jmt_node_hash(&(substate_id, version)))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubstateProofError {
+    #[error("Substate is not included in the shard state tree: {details}")]
+    InvalidShardProof { details: String },
+    #[error("Shard state root is not included in the state merkle root: {details}")]
+    InvalidStateRootProof { details: String },
+}
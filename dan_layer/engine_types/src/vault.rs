@@ -43,15 +43,27 @@ use crate::{
 #[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
 pub struct Vault {
     resource_container: ResourceContainer,
+    /// A frozen vault does not allow deposits or withdrawals. Only the resource auditor may freeze or unfreeze a vault.
+    #[serde(default)]
+    is_frozen: bool,
 }
 
 impl Vault {
     pub fn new(resource: ResourceContainer) -> Self {
         Self {
             resource_container: resource,
+            is_frozen: false,
         }
     }
 
+    pub fn is_frozen(&self) -> bool {
+        self.is_frozen
+    }
+
+    pub fn set_frozen(&mut self, is_frozen: bool) {
+        self.is_frozen = is_frozen;
+    }
+
     pub fn deposit(&mut self, bucket: Bucket) -> Result<(), ResourceError> {
         self.resource_container.deposit(bucket.into_resource())?;
         Ok(())
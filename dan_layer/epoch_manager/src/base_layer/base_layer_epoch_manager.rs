@@ -43,7 +43,7 @@ use tari_utilities::byte_array::ByteArray;
 use tokio::sync::{broadcast, oneshot};
 
 use crate::{
-    base_layer::config::EpochManagerConfig,
+    base_layer::{config::EpochManagerConfig, ProjectedCommitteeAssignment},
     error::EpochManagerError,
     traits::LayerOneTransactionSubmitter,
     EpochManagerEvent,
@@ -504,6 +504,44 @@ where
         ))
     }
 
+    /// Calculates the committee assignment for the given epoch in the same way as when the epoch begins, without
+    /// persisting it. This allows the assignment of an upcoming epoch to be inspected in advance.
+    pub fn get_projected_committee_assignment(
+        &self,
+        epoch: Epoch,
+    ) -> Result<ProjectedCommitteeAssignment, EpochManagerError> {
+        let mut tx = self.global_db.create_transaction()?;
+        let vns = self
+            .global_db
+            .validator_nodes(&mut tx)
+            .get_all_registered_within_start_epoch(epoch)?;
+
+        let num_preshards = self.config.num_preshards;
+        let num_committees = calculate_num_committees(vns.len() as u64, self.config.committee_size);
+        let local_shard_group = vns
+            .iter()
+            .find(|vn| vn.public_key == self.node_public_key)
+            .map(|vn| vn.shard_key.to_shard_group(num_preshards, num_committees));
+        let local_committee_size = local_shard_group.map_or(0, |shard_group| {
+            vns.iter()
+                .filter(|vn| vn.shard_key.to_shard_group(num_preshards, num_committees) == shard_group)
+                .count()
+        });
+        let local_committee_size =
+            u32::try_from(local_committee_size).map_err(|_| EpochManagerError::IntegerOverflow {
+                func: "get_projected_committee_assignment",
+            })?;
+
+        Ok(ProjectedCommitteeAssignment {
+            epoch,
+            num_preshards,
+            num_validators: vns.len() as u64,
+            num_committees,
+            local_shard_group,
+            local_committee_size,
+        })
+    }
+
     pub fn get_number_of_committees(&self, epoch: Epoch) -> Result<u32, EpochManagerError> {
         let num_vns = self.get_total_validator_count(epoch)?;
         Ok(calculate_num_committees(num_vns, self.config.committee_size))
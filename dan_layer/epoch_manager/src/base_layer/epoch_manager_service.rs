@@ -256,6 +256,9 @@ where
             EpochManagerRequest::GetNumCommittees { epoch, reply } => {
                 handle(reply, self.inner.get_num_committees(epoch), context)
             },
+            EpochManagerRequest::GetProjectedCommitteeAssignment { epoch, reply } => {
+                handle(reply, self.inner.get_projected_committee_assignment(epoch), context)
+            },
             EpochManagerRequest::GetCommitteeForShardGroup {
                 epoch,
                 shard_group,
@@ -19,7 +19,7 @@ use tari_sidechain::EvictionProof;
 use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::{
-    base_layer::types::EpochManagerRequest,
+    base_layer::types::{EpochManagerRequest, ProjectedCommitteeAssignment},
     error::EpochManagerError,
     traits::EpochManagerReader,
     EpochManagerEvent,
@@ -181,6 +181,21 @@ impl<TAddr: NodeAddressable> EpochManagerHandle<TAddr> {
 
         rx.await.map_err(|_| EpochManagerError::ReceiveError)?
     }
+
+    /// Returns the committee assignment that the given epoch would have based on the currently known validator
+    /// registrations
+    pub async fn get_projected_committee_assignment(
+        &self,
+        epoch: Epoch,
+    ) -> Result<ProjectedCommitteeAssignment, EpochManagerError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx_request
+            .send(EpochManagerRequest::GetProjectedCommitteeAssignment { epoch, reply: tx })
+            .await
+            .map_err(|_| EpochManagerError::SendError)?;
+
+        rx.await.map_err(|_| EpochManagerError::ReceiveError)?
+    }
 }
 
 #[async_trait]
@@ -9,6 +9,7 @@ use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components
 use tari_dan_common_types::{
     committee::{Committee, CommitteeInfo},
     Epoch,
+    NumPreshards,
     ShardGroup,
     SubstateAddress,
 };
@@ -151,4 +152,22 @@ pub enum EpochManagerRequest<TAddr> {
         proof: Box<EvictionProof>,
         reply: Reply<()>,
     },
+    GetProjectedCommitteeAssignment {
+        epoch: Epoch,
+        reply: Reply<ProjectedCommitteeAssignment>,
+    },
+}
+
+/// The committee assignment for an epoch calculated from the validator registrations that are currently known. For an
+/// upcoming epoch, further registrations may be added before the epoch begins so the assignment may still change.
+#[derive(Debug, Clone)]
+pub struct ProjectedCommitteeAssignment {
+    pub epoch: Epoch,
+    pub num_preshards: NumPreshards,
+    pub num_validators: u64,
+    pub num_committees: u32,
+    /// The shard group of the local validator, or None if it is not registered for the epoch
+    pub local_shard_group: Option<ShardGroup>,
+    /// The number of validators assigned to the local shard group
+    pub local_committee_size: u32,
 }
@@ -0,0 +1,63 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Writes valid, deterministic seed inputs for each fuzz target to `corpus/<target>/`. Starting from valid messages
+//! lets the fuzzer reach the conversion code without first having to discover the protobuf structure.
+//!
+//! Usage: cargo run --bin generate_seed_corpus
+
+use std::{fs, io, path::Path};
+
+use prost::Message;
+use tari_common_types::types::PrivateKey;
+use tari_crypto::tari_utilities::ByteArray;
+use tari_dan_p2p::{proto, DanMessage, NewTransactionMessage};
+use tari_template_lib::{args, models::Amount, prelude::ComponentAddress, Hash};
+use tari_transaction::Transaction;
+
+fn main() -> io::Result<()> {
+    let corpus_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus");
+
+    let transactions = seed_transactions();
+    for (i, transaction) in transactions.iter().enumerate() {
+        let msg = proto::transaction::Transaction::from(transaction);
+        write_seed(&corpus_dir.join("transaction"), &format!("seed-{i}"), &msg)?;
+
+        let msg = proto::network::DanMessage::from(&DanMessage::NewTransaction(Box::new(NewTransactionMessage {
+            transaction: transaction.clone(),
+        })));
+        write_seed(&corpus_dir.join("dan_message"), &format!("seed-{i}"), &msg)?;
+    }
+
+    println!("Wrote {} seed(s) to {}", transactions.len() * 2, corpus_dir.display());
+    Ok(())
+}
+
+fn seed_transactions() -> Vec<Transaction> {
+    // A fixed key so that the corpus is reproducible
+    let mut key_bytes = [0u8; 32];
+    key_bytes[0] = 1;
+    let secret_key = PrivateKey::from_canonical_bytes(&key_bytes).unwrap();
+    let component = ComponentAddress::from_array([1u8; 32]);
+
+    vec![
+        Transaction::builder().sign(&secret_key).build(),
+        Transaction::builder()
+            .fee_transaction_pay_from_component(component, Amount(1000))
+            .call_function(Hash::from_array([2u8; 32]), "new", args![Amount(123), "seed"])
+            .call_method(component, "balance", args![])
+            .put_last_instruction_output_on_workspace("balance")
+            .drop_all_proofs_in_workspace()
+            .sign(&secret_key)
+            .build(),
+        Transaction::builder()
+            .publish_template(b"\0asm\x01\0\0\0".to_vec())
+            .sign(&secret_key)
+            .build(),
+    ]
+}
+
+fn write_seed<M: Message>(dir: &Path, name: &str, msg: &M) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(name), msg.encode_to_vec())
+}
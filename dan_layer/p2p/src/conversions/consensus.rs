@@ -67,9 +67,11 @@ use tari_dan_storage::{
         HighQc,
         LeaderFee,
         MintConfidentialOutputAtom,
+        ProposalEquivocationAtom,
         QcId,
         QuorumCertificate,
         QuorumDecision,
+        ShardGroupEvidence,
         SubstateDestroyed,
         SubstatePledge,
         SubstatePledges,
@@ -148,10 +150,40 @@ impl TryFrom<proto::consensus::HotStuffMessage> for HotstuffMessage {
             proto::consensus::hot_stuff_message::Message::SyncResponse(msg) => {
                 HotstuffMessage::SyncResponse(msg.try_into()?)
             },
+            proto::consensus::hot_stuff_message::Message::Batch(_) => {
+                return Err(anyhow!("A message batch must be unpacked before it is converted"));
+            },
         })
     }
 }
 
+impl proto::consensus::HotStuffMessage {
+    /// Wraps several messages into a single message so that they are sent in one network frame
+    pub fn batch(messages: Vec<Self>) -> Self {
+        Self {
+            message: Some(proto::consensus::hot_stuff_message::Message::Batch(
+                proto::consensus::HotStuffMessageBatch { messages },
+            )),
+        }
+    }
+
+    /// Returns the messages in a batch, or the message itself if it is not a batch. Nested batches are rejected.
+    pub fn unbatch(self) -> Result<Vec<Self>, anyhow::Error> {
+        let Some(proto::consensus::hot_stuff_message::Message::Batch(batch)) = self.message else {
+            return Ok(vec![self]);
+        };
+        if batch.messages.iter().any(|msg| {
+            matches!(
+                msg.message,
+                Some(proto::consensus::hot_stuff_message::Message::Batch(_))
+            )
+        }) {
+            return Err(anyhow!("Message batches cannot be nested"));
+        }
+        Ok(batch.messages)
+    }
+}
+
 //---------------------------------- NewView --------------------------------------------//
 
 impl From<&NewViewMessage> for proto::consensus::NewViewMessage {
@@ -323,6 +355,21 @@ impl From<&ForeignProposalRequestMessage> for proto::consensus::ForeignProposalR
                     },
                 )),
             },
+            ForeignProposalRequestMessage::ByForeignIndexRange {
+                for_shard_group,
+                epoch,
+                from_index,
+                to_index,
+            } => Self {
+                request: Some(proto::consensus::foreign_proposal_request::Request::ByForeignIndexRange(
+                    proto::consensus::ForeignProposalRequestByForeignIndexRange {
+                        for_shard_group: for_shard_group.encode_as_u32(),
+                        epoch: epoch.as_u64(),
+                        from_index: *from_index,
+                        to_index: *to_index,
+                    },
+                )),
+            },
         }
     }
 }
@@ -349,6 +396,15 @@ impl TryFrom<proto::consensus::ForeignProposalRequest> for ForeignProposalReques
                     epoch: Epoch(by_transaction_id.epoch),
                 }
             },
+            proto::consensus::foreign_proposal_request::Request::ByForeignIndexRange(by_index_range) => {
+                ForeignProposalRequestMessage::ByForeignIndexRange {
+                    for_shard_group: ShardGroup::decode_from_u32(by_index_range.for_shard_group)
+                        .ok_or_else(|| anyhow!("Invalid ShardGroup"))?,
+                    epoch: Epoch(by_index_range.epoch),
+                    from_index: by_index_range.from_index,
+                    to_index: by_index_range.to_index,
+                }
+            },
         })
     }
 }
@@ -466,6 +522,7 @@ impl From<&VoteMessage> for proto::consensus::VoteMessage {
             block_height: msg.unverified_block_height.as_u64(),
             decision: i32::from(msg.decision.as_u8()),
             signature: Some((&msg.signature).into()),
+            block_header: msg.block_header.as_ref().map(Into::into),
         }
     }
 }
@@ -484,6 +541,7 @@ impl TryFrom<proto::consensus::VoteMessage> for VoteMessage {
                 .signature
                 .ok_or_else(|| anyhow!("Signature is missing"))?
                 .try_into()?,
+            block_header: value.block_header.map(TryInto::try_into).transpose()?,
         })
     }
 }
@@ -494,7 +552,7 @@ impl From<&MissingTransactionsRequest> for proto::consensus::MissingTransactions
         Self {
             request_id: msg.request_id,
             epoch: msg.epoch.as_u64(),
-            block_id: msg.block_id.as_bytes().to_vec(),
+            block_ids: msg.block_ids.iter().map(|id| id.as_bytes().to_vec()).collect(),
             transaction_ids: msg.transactions.iter().map(|tx_id| tx_id.as_bytes().to_vec()).collect(),
         }
     }
@@ -507,7 +565,11 @@ impl TryFrom<proto::consensus::MissingTransactionsRequest> for MissingTransactio
         Ok(MissingTransactionsRequest {
             request_id: value.request_id,
             epoch: Epoch(value.epoch),
-            block_id: BlockId::try_from(value.block_id)?,
+            block_ids: value
+                .block_ids
+                .into_iter()
+                .map(BlockId::try_from)
+                .collect::<Result<_, _>>()?,
             transactions: value
                 .transaction_ids
                 .into_iter()
@@ -523,7 +585,7 @@ impl From<&MissingTransactionsResponse> for proto::consensus::MissingTransaction
         Self {
             request_id: msg.request_id,
             epoch: msg.epoch.as_u64(),
-            block_id: msg.block_id.as_bytes().to_vec(),
+            block_ids: msg.block_ids.iter().map(|id| id.as_bytes().to_vec()).collect(),
             transactions: msg.transactions.iter().map(|tx| tx.into()).collect(),
         }
     }
@@ -536,7 +598,11 @@ impl TryFrom<proto::consensus::MissingTransactionsResponse> for MissingTransacti
         Ok(MissingTransactionsResponse {
             request_id: value.request_id,
             epoch: Epoch(value.epoch),
-            block_id: BlockId::try_from(value.block_id)?,
+            block_ids: value
+                .block_ids
+                .into_iter()
+                .map(BlockId::try_from)
+                .collect::<Result<_, _>>()?,
             transactions: value
                 .transactions
                 .into_iter()
@@ -627,6 +693,68 @@ fn try_convert_proto_block_header(
     }
 }
 
+//---------------------------------- StandaloneBlockHeader --------------------------------------------//
+
+impl From<&consensus_models::BlockHeader> for proto::consensus::StandaloneBlockHeader {
+    fn from(value: &consensus_models::BlockHeader) -> Self {
+        Self {
+            block_id: value.id().as_bytes().to_vec(),
+            justify_id: value.justify_id().as_bytes().to_vec(),
+            command_merkle_root: value.command_merkle_root().as_slice().to_vec(),
+            header: Some(value.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::StandaloneBlockHeader> for consensus_models::BlockHeader {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::StandaloneBlockHeader) -> Result<Self, Self::Error> {
+        let header = value
+            .header
+            .ok_or_else(|| anyhow!("StandaloneBlockHeader: header not provided"))?;
+
+        let network = u8::try_from(header.network)
+            .map_err(|_| anyhow!("StandaloneBlockHeader: Invalid network byte {}", header.network))?
+            .try_into()?;
+        let shard_group = ShardGroup::decode_from_u32(header.shard_group).ok_or_else(|| {
+            anyhow!(
+                "StandaloneBlockHeader: shard_group ({}) is not a valid",
+                header.shard_group
+            )
+        })?;
+        let proposed_by = PublicKey::from_canonical_bytes(&header.proposed_by)
+            .map_err(|_| anyhow!("StandaloneBlockHeader: Invalid proposed_by"))?;
+        let extra_data = header
+            .extra_data
+            .ok_or_else(|| anyhow!("ExtraData not provided"))?
+            .try_into()?;
+
+        // The block id is taken as given. Consumers of standalone headers (e.g. equivocation evidence) must check that
+        // it matches the header contents.
+        Ok(consensus_models::BlockHeader::load(
+            value.block_id.try_into()?,
+            network,
+            header.parent_id.try_into()?,
+            value.justify_id.try_into()?,
+            NodeHeight(header.height),
+            Epoch(header.epoch),
+            shard_group,
+            proposed_by,
+            header.state_merkle_root.try_into()?,
+            header.total_leader_fee,
+            header.is_dummy,
+            decode_exact(&header.foreign_indexes)?,
+            header.signature.map(TryInto::try_into).transpose()?,
+            header.timestamp,
+            header.base_layer_block_height,
+            header.base_layer_block_hash.try_into()?,
+            extra_data,
+            value.command_merkle_root.try_into()?,
+        ))
+    }
+}
+
 //---------------------------------- Block --------------------------------------------//
 
 impl From<&consensus_models::Block> for proto::consensus::Block {
@@ -699,6 +827,9 @@ impl From<&Command> for proto::consensus::Command {
                 proto::consensus::command::Command::MintConfidentialOutput(atom.into())
             },
             Command::EvictNode(atom) => proto::consensus::command::Command::EvictNode(atom.into()),
+            Command::ProposalEquivocation(atom) => {
+                proto::consensus::command::Command::ProposalEquivocation(atom.into())
+            },
             Command::EndEpoch => proto::consensus::command::Command::EndEpoch(true),
         };
 
@@ -727,6 +858,9 @@ impl TryFrom<proto::consensus::Command> for Command {
                 Command::MintConfidentialOutput(atom.try_into()?)
             },
             proto::consensus::command::Command::EvictNode(atom) => Command::EvictNode(atom.try_into()?),
+            proto::consensus::command::Command::ProposalEquivocation(atom) => {
+                Command::ProposalEquivocation(atom.try_into()?)
+            },
             proto::consensus::command::Command::EndEpoch(_) => Command::EndEpoch,
         })
     }
@@ -851,6 +985,31 @@ impl TryFrom<proto::consensus::EvictNodeAtom> for EvictNodeAtom {
     }
 }
 
+// -------------------------------- ProposalEquivocationAtom -------------------------------- //
+
+impl From<&ProposalEquivocationAtom> for proto::consensus::ProposalEquivocationAtom {
+    fn from(value: &ProposalEquivocationAtom) -> Self {
+        Self {
+            first: Some(value.first().into()),
+            second: Some(value.second().into()),
+        }
+    }
+}
+
+impl TryFrom<proto::consensus::ProposalEquivocationAtom> for ProposalEquivocationAtom {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::consensus::ProposalEquivocationAtom) -> Result<Self, Self::Error> {
+        let first = value
+            .first
+            .ok_or_else(|| anyhow!("ProposalEquivocationAtom: first header not provided"))?;
+        let second = value
+            .second
+            .ok_or_else(|| anyhow!("ProposalEquivocationAtom: second header not provided"))?;
+        Ok(Self::new(first.try_into()?, second.try_into()?))
+    }
+}
+
 // -------------------------------- Decision -------------------------------- //
 
 impl From<Decision> for proto::consensus::Decision {
@@ -930,9 +1089,29 @@ impl TryFrom<proto::consensus::Decision> for Decision {
 
 impl From<&Evidence> for proto::consensus::Evidence {
     fn from(value: &Evidence) -> Self {
-        // TODO: we may want to write out the protobuf here
         Self {
-            encoded_evidence: encode(value).unwrap(),
+            shard_groups: value
+                .iter()
+                .map(|(shard_group, evidence)| proto::consensus::ShardGroupEvidence {
+                    shard_group: shard_group.encode_as_u32(),
+                    substates: evidence
+                        .substates()
+                        .iter()
+                        .map(|(address, lock_type)| proto::consensus::EvidenceSubstate {
+                            address: Some(address.into()),
+                            lock_type: proto::consensus::SubstateLockType::from(*lock_type) as i32,
+                        })
+                        .collect(),
+                    prepare_qc: evidence
+                        .prepare_qc()
+                        .map(|qc_id| qc_id.as_bytes().to_vec())
+                        .unwrap_or_default(),
+                    accept_qc: evidence
+                        .accept_qc()
+                        .map(|qc_id| qc_id.as_bytes().to_vec())
+                        .unwrap_or_default(),
+                })
+                .collect(),
         }
     }
 }
@@ -941,8 +1120,46 @@ impl TryFrom<proto::consensus::Evidence> for Evidence {
     type Error = anyhow::Error;
 
     fn try_from(value: proto::consensus::Evidence) -> Result<Self, Self::Error> {
-        Ok(decode_exact(&value.encoded_evidence)?)
+        // The order of shard groups and substates is preserved because it is committed to in the block
+        let mut evidence = Evidence::empty();
+        for shard_group_evidence in value.shard_groups {
+            let shard_group = ShardGroup::decode_from_u32(shard_group_evidence.shard_group)
+                .ok_or_else(|| anyhow!("Evidence shard_group is not a valid shard group"))?;
+            if evidence.contains(&shard_group) {
+                return Err(anyhow!("Evidence contains duplicate shard group {shard_group}"));
+            }
+            let num_substates = shard_group_evidence.substates.len();
+            let substates = shard_group_evidence
+                .substates
+                .into_iter()
+                .map(|substate| {
+                    let lock_type = substate.lock_type().try_into()?;
+                    let address = substate
+                        .address
+                        .ok_or_else(|| anyhow!("Evidence substate address not provided"))?
+                        .try_into()?;
+                    Ok((address, lock_type))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            let shard_group_evidence = ShardGroupEvidence::new(
+                substates,
+                decode_optional_qc_id(shard_group_evidence.prepare_qc)?,
+                decode_optional_qc_id(shard_group_evidence.accept_qc)?,
+            );
+            if shard_group_evidence.substates().len() != num_substates {
+                return Err(anyhow!("Evidence for shard group {shard_group} contains duplicate substates"));
+            }
+            *evidence.add_shard_group(shard_group) = shard_group_evidence;
+        }
+        Ok(evidence)
+    }
+}
+
+fn decode_optional_qc_id(bytes: Vec<u8>) -> Result<Option<QcId>, anyhow::Error> {
+    if bytes.is_empty() {
+        return Ok(None);
     }
+    Ok(Some(QcId::try_from(bytes)?))
 }
 
 // -------------------------------- QuorumCertificate -------------------------------- //
@@ -7,6 +7,7 @@ use anyhow::anyhow;
 use tari_dan_common_types::{shard::Shard, Epoch};
 use tari_dan_storage::consensus_models::{
     EpochCheckpoint,
+    StateSnapshot,
     StateTransition,
     StateTransitionId,
     SubstateCreatedProof,
@@ -207,3 +208,36 @@ impl From<EpochCheckpoint> for proto::rpc::EpochCheckpoint {
         }
     }
 }
+
+//---------------------------------- StateSnapshot --------------------------------------------//
+
+impl TryFrom<proto::rpc::StateSnapshot> for StateSnapshot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: proto::rpc::StateSnapshot) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: Epoch(value.epoch),
+            shard: Shard::from(value.shard),
+            state_version: value.state_version,
+            state_root: TreeHash::try_from_bytes(&value.state_root)?,
+            last_transition_id: value
+                .last_transition_id
+                .ok_or_else(|| anyhow!("last_transition_id not provided"))?
+                .try_into()?,
+            num_substates: value.num_substates,
+        })
+    }
+}
+
+impl From<StateSnapshot> for proto::rpc::StateSnapshot {
+    fn from(value: StateSnapshot) -> Self {
+        Self {
+            epoch: value.epoch.as_u64(),
+            shard: value.shard.as_u32(),
+            state_version: value.state_version,
+            state_root: value.state_root.to_vec(),
+            last_transition_id: Some(value.last_transition_id.into()),
+            num_substates: value.num_substates,
+        }
+    }
+}
@@ -27,7 +27,12 @@ use tari_bor::{decode_exact, encode};
 use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
 use tari_crypto::{ristretto::RistrettoComSig, tari_utilities::ByteArray};
 use tari_dan_common_types::{Epoch, SubstateRequirement, VersionedSubstateId};
-use tari_engine_types::{confidential::ConfidentialClaim, instruction::Instruction, substate::SubstateId};
+use tari_engine_types::{
+    confidential::ConfidentialClaim,
+    instruction::Instruction,
+    published_template::TemplateType,
+    substate::SubstateId,
+};
 use tari_template_lib::{
     args::Arg,
     auth::OwnerRule,
@@ -48,7 +53,10 @@ use tari_transaction::{Transaction, UnsignedTransaction};
 use crate::{
     proto::{
         self,
-        transaction::{instruction::InstructionType, OptionalVersion},
+        transaction::{
+            instruction::{InstructionType, TemplateType as ProtoTemplateType},
+            OptionalVersion,
+        },
     },
     utils::checked_copy_fixed,
     NewTransactionMessage,
@@ -261,6 +269,21 @@ impl TryFrom<proto::transaction::Instruction> for Instruction {
             },
             InstructionType::PublishTemplate => Instruction::PublishTemplate {
                 binary: request.template_binary,
+                previous_version: if request.template_address.is_empty() {
+                    None
+                } else {
+                    Some(request.template_address.try_into()?)
+                },
+                template_type: match ProtoTemplateType::try_from(request.template_type)
+                    .map_err(|e| anyhow!("invalid template_type {e}"))?
+                {
+                    ProtoTemplateType::Wasm => TemplateType::Wasm,
+                    ProtoTemplateType::Flow => TemplateType::Flow,
+                },
+            },
+            InstructionType::MigrateComponent => Instruction::MigrateComponent {
+                component_address: ObjectKey::try_from(request.component_address)?.into(),
+                template_address: request.template_address.try_into()?,
             },
         };
 
@@ -343,9 +366,26 @@ impl From<Instruction> for proto::transaction::Instruction {
                 result.resource_address = resource_address.as_bytes().to_vec();
                 result.min_amount = min_amount.0
             },
-            Instruction::PublishTemplate { binary } => {
+            Instruction::PublishTemplate {
+                binary,
+                previous_version,
+                template_type,
+            } => {
                 result.instruction_type = InstructionType::PublishTemplate as i32;
                 result.template_binary = binary;
+                result.template_address = previous_version.map(|a| a.to_vec()).unwrap_or_default();
+                result.template_type = match template_type {
+                    TemplateType::Wasm => ProtoTemplateType::Wasm,
+                    TemplateType::Flow => ProtoTemplateType::Flow,
+                } as i32;
+            },
+            Instruction::MigrateComponent {
+                component_address,
+                template_address,
+            } => {
+                result.instruction_type = InstructionType::MigrateComponent as i32;
+                result.component_address = component_address.as_bytes().to_vec();
+                result.template_address = template_address.to_vec();
             },
         }
         result
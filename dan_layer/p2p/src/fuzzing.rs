@@ -0,0 +1,85 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Entry points for the fuzz targets in `dan_layer/p2p/fuzz`. Each target decodes arbitrary bytes the same way the
+//! networking layer does and, if the input is accepted, checks that re-encoding the decoded message is stable.
+//!
+//! The check is differential: the bytes are decoded into the domain type (A), converted back to protobuf and decoded
+//! again (B). Converting B back to protobuf must produce the same message as converting A, and the encoded bytes must
+//! decode to the same protobuf message. Any disagreement means that a peer receiving our re-broadcast of a message
+//! would see a different message to the one we validated.
+
+use std::fmt::Debug;
+
+use prost::Message;
+use tari_consensus::messages::HotstuffMessage;
+use tari_transaction::Transaction;
+
+use crate::{proto, DanMessage};
+
+/// Decodes a [proto::transaction::Transaction] as received from a peer
+pub fn fuzz_transaction(data: &[u8]) {
+    check_round_trip::<proto::transaction::Transaction, Transaction>(data, |tx| tx.into(), Equivalence::Exact);
+}
+
+/// Decodes a [proto::consensus::HotStuffMessage] as received by the consensus gossip and messaging services
+pub fn fuzz_hotstuff_message(data: &[u8]) {
+    // Some consensus messages contain HashSets, so the order of repeated fields may differ between conversions
+    check_round_trip::<proto::consensus::HotStuffMessage, HotstuffMessage>(
+        data,
+        |msg| msg.into(),
+        Equivalence::EncodedLength,
+    );
+}
+
+/// Decodes a [proto::network::DanMessage] as received by the mempool gossip service
+pub fn fuzz_dan_message(data: &[u8]) {
+    check_round_trip::<proto::network::DanMessage, DanMessage>(data, |msg| msg.into(), Equivalence::Exact);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Equivalence {
+    /// The re-encoded messages must be identical
+    Exact,
+    /// The re-encoded messages must have the same encoded length. Used for messages that do not have a canonical
+    /// ordering of repeated fields.
+    EncodedLength,
+}
+
+fn check_round_trip<P, T>(data: &[u8], to_proto: fn(&T) -> P, equivalence: Equivalence)
+where
+    P: Message + Default + PartialEq + Debug,
+    T: TryFrom<P> + Debug,
+{
+    let Ok(msg) = P::decode(data) else {
+        return;
+    };
+    // Invalid messages are expected, we only check that they are rejected without panicking
+    let Ok(first) = T::try_from(msg) else {
+        return;
+    };
+
+    let first_proto = to_proto(&first);
+    let encoded = first_proto.encode_to_vec();
+    let decoded_proto = P::decode(encoded.as_slice()).expect("re-encoded message failed to decode");
+    assert_eq!(
+        first_proto, decoded_proto,
+        "protobuf encoding is not stable for decoded message {first:?}"
+    );
+
+    let second = T::try_from(decoded_proto)
+        .unwrap_or_else(|_| panic!("re-encoded message was rejected. Original decoded message: {first:?}"));
+    let second_proto = to_proto(&second);
+    match equivalence {
+        Equivalence::Exact => assert_eq!(
+            first_proto, second_proto,
+            "decoding the re-encoded message produced a different message"
+        ),
+        Equivalence::EncodedLength => assert_eq!(
+            first_proto.encoded_len(),
+            second_proto.encoded_len(),
+            "decoding the re-encoded message produced a message of a different length. First: {first_proto:?}, \
+             second: {second_proto:?}"
+        ),
+    }
+}
@@ -3,6 +3,8 @@
 
 mod block_sync;
 mod conversions;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 pub mod proto;
 
 mod message;
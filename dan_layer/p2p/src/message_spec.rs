@@ -12,4 +12,22 @@ impl MessageSpec for TariMessagingSpec {
     type ConsensusGossipMessage = proto::consensus::HotStuffMessage;
     type Message = proto::consensus::HotStuffMessage;
     type TransactionGossipMessage = proto::network::DanMessage;
+
+    fn message_type(message: &Self::Message) -> &'static str {
+        use proto::consensus::hot_stuff_message::Message;
+        match message.message {
+            Some(Message::NewView(_)) => "NewView",
+            Some(Message::Proposal(_)) => "Proposal",
+            Some(Message::ForeignProposal(_)) => "ForeignProposal",
+            Some(Message::ForeignProposalNotification(_)) => "ForeignProposalNotification",
+            Some(Message::ForeignProposalRequest(_)) => "ForeignProposalRequest",
+            Some(Message::Vote(_)) => "Vote",
+            Some(Message::RequestMissingTransactions(_)) => "MissingTransactionsRequest",
+            Some(Message::RequestedTransaction(_)) => "MissingTransactionsResponse",
+            Some(Message::SyncRequest(_)) => "CatchUpSyncRequest",
+            Some(Message::SyncResponse(_)) => "SyncResponse",
+            Some(Message::Batch(_)) => "Batch",
+            None => "Unknown",
+        }
+    }
 }
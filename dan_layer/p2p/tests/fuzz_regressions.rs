@@ -0,0 +1,48 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Replays crash artifacts produced by the fuzz targets. To add a regression, copy the artifact from
+//! `fuzz/artifacts/<target>/` to `fuzz/regressions/<target>/`.
+
+#![cfg(feature = "fuzzing")]
+
+use std::{fs, path::Path};
+
+use tari_dan_p2p::fuzzing;
+
+fn replay(target: &str, fuzz_fn: fn(&[u8])) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("regressions")
+        .join(target);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return;
+    };
+    for entry in entries {
+        let path = entry.unwrap().path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        let data = fs::read(&path).unwrap();
+        eprintln!("Replaying {}", path.display());
+        fuzz_fn(&data);
+    }
+}
+
+#[test]
+fn transaction_regressions() {
+    replay("transaction", fuzzing::fuzz_transaction);
+}
+
+#[test]
+fn hotstuff_message_regressions() {
+    replay("hotstuff_message", fuzzing::fuzz_hotstuff_message);
+}
+
+#[test]
+fn dan_message_regressions() {
+    replay("dan_message", fuzzing::fuzz_dan_message);
+}
@@ -21,15 +21,22 @@ use tari_dan_common_types::{
     ShardGroup,
     VersionedSubstateId,
 };
-use tari_dan_p2p::proto::rpc::{GetCheckpointRequest, GetCheckpointResponse, SyncStateRequest};
+use tari_dan_p2p::proto::rpc::{
+    GetCheckpointRequest,
+    GetCheckpointResponse,
+    GetStateSnapshotChunkRequest,
+    SyncStateRequest,
+};
 use tari_dan_storage::{
     consensus_models::{
         EpochCheckpoint,
         LeafBlock,
         QcId,
+        StateSnapshot,
         StateTransition,
         StateTransitionId,
         SubstateCreatedProof,
+        SubstateData,
         SubstateDestroyedProof,
         SubstateRecord,
         SubstateUpdate,
@@ -116,6 +123,16 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress>
     ) -> Result<Option<Version>, CommsRpcConsensusSyncError> {
         let current_epoch = self.epoch_manager.current_epoch().await?;
 
+        // A node without any state for the shard loads the latest snapshot, so that only the state transitions that
+        // follow the snapshot are replayed
+        let has_state = self
+            .state_store
+            .with_read_tx(|tx| tx.state_tree_versions_get_latest(shard))?
+            .is_some();
+        if !has_state {
+            self.load_state_snapshot(client, shard, checkpoint, current_epoch).await?;
+        }
+
         let last_state_transition_id = self
             .state_store
             .with_read_tx(|tx| StateTransition::get_last_id(tx, shard))
@@ -245,6 +262,150 @@ where TConsensusSpec: ConsensusSpec<Addr = PeerAddress>
         Ok(current_version)
     }
 
+    /// Loads the most recent state snapshot for the shard from the peer. If the peer does not have a snapshot for the
+    /// shard, nothing is loaded and the state is synced from the first state transition.
+    async fn load_state_snapshot(
+        &self,
+        client: &mut ValidatorNodeRpcClient,
+        shard: Shard,
+        checkpoint: &EpochCheckpoint,
+        current_epoch: Epoch,
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        let Some((snapshot, num_chunks, mut substates)) = self
+            .fetch_state_snapshot_chunk(client, shard, Epoch::zero(), 0, current_epoch)
+            .await?
+        else {
+            info!(target: LOG_TARGET, "🛜No state snapshot available for {shard}");
+            return Ok(());
+        };
+
+        self.validate_state_snapshot(&snapshot, shard, current_epoch)?;
+        info!(target: LOG_TARGET, "🛜Loading {snapshot} in {num_chunks} chunk(s)");
+
+        for chunk_index in 1..num_chunks {
+            let (chunk_snapshot, _, chunk) = self
+                .fetch_state_snapshot_chunk(client, shard, snapshot.epoch, chunk_index, current_epoch)
+                .await?
+                .ok_or_else(|| {
+                    CommsRpcConsensusSyncError::InvalidResponse(anyhow!(
+                        "Chunk {chunk_index} of the state snapshot for {shard} was not found"
+                    ))
+                })?;
+            if chunk_snapshot.epoch != snapshot.epoch || chunk_snapshot.state_root != snapshot.state_root {
+                return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow!(
+                    "Chunk {chunk_index} belongs to a different state snapshot ({chunk_snapshot})"
+                )));
+            }
+            substates.extend(chunk);
+        }
+
+        if substates.len() as u64 != snapshot.num_substates {
+            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow!(
+                "Expected {} substates in the state snapshot for {shard} but received {}",
+                snapshot.num_substates,
+                substates.len()
+            )));
+        }
+
+        // The snapshot is loaded in a single transaction, so nothing is persisted if the state root does not match
+        self.state_store.with_write_tx(|tx| {
+            let mut store = ShardScopedTreeStoreWriter::new(tx, shard);
+            let changes = substates.iter().map(|substate| SubstateTreeChange::Up {
+                id: substate.to_versioned_substate_id(),
+                value_hash: hash_substate(&substate.substate_value, substate.version),
+            });
+            let mut state_tree = SpreadPrefixStateTree::new(&mut store);
+            let state_root = state_tree.put_substate_changes(None, 1, changes)?;
+            if state_root != snapshot.state_root {
+                return Err(CommsRpcConsensusSyncError::StateRootMismatch {
+                    expected: snapshot.state_root,
+                    actual: state_root,
+                });
+            }
+            store.set_version(1)?;
+
+            for substate in substates {
+                let transition = StateTransition {
+                    id: StateTransitionId::new(snapshot.epoch, shard, 0),
+                    update: SubstateUpdate::Create(SubstateCreatedProof { substate }),
+                };
+                self.commit_update(store.transaction(), checkpoint, transition)?;
+            }
+
+            // Continue the state transition sequence from the last transition in the snapshot
+            StateTransition::rebase(store.transaction(), shard, snapshot.last_transition_id.seq())?;
+
+            Ok::<_, CommsRpcConsensusSyncError>(())
+        })?;
+
+        info!(target: LOG_TARGET, "🛜Loaded {snapshot}");
+        Ok(())
+    }
+
+    async fn fetch_state_snapshot_chunk(
+        &self,
+        client: &mut ValidatorNodeRpcClient,
+        shard: Shard,
+        epoch: Epoch,
+        chunk_index: u64,
+        current_epoch: Epoch,
+    ) -> Result<Option<(StateSnapshot, u64, Vec<SubstateData>)>, CommsRpcConsensusSyncError> {
+        let resp = match client
+            .get_state_snapshot_chunk(GetStateSnapshotChunkRequest {
+                epoch: epoch.as_u64(),
+                shard: shard.as_u32(),
+                chunk_index,
+                current_epoch: current_epoch.as_u64(),
+            })
+            .await
+        {
+            Ok(resp) => resp,
+            Err(RpcError::RequestFailed(err)) if err.is_not_found() => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let snapshot = resp
+            .snapshot
+            .ok_or_else(|| anyhow!("State snapshot not provided"))
+            .and_then(StateSnapshot::try_from)
+            .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+        let substates = resp
+            .substates
+            .into_iter()
+            .map(SubstateData::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(CommsRpcConsensusSyncError::InvalidResponse)?;
+
+        Ok(Some((snapshot, resp.num_chunks, substates)))
+    }
+
+    fn validate_state_snapshot(
+        &self,
+        snapshot: &StateSnapshot,
+        shard: Shard,
+        current_epoch: Epoch,
+    ) -> Result<(), CommsRpcConsensusSyncError> {
+        if snapshot.shard != shard {
+            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow!(
+                "Received state snapshot for {} which is not the expected shard {}",
+                snapshot.shard,
+                shard
+            )));
+        }
+
+        if snapshot.epoch.is_zero() || snapshot.epoch >= current_epoch {
+            return Err(CommsRpcConsensusSyncError::InvalidResponse(anyhow!(
+                "Received state snapshot for epoch {} but expected a snapshot before the current epoch {}",
+                snapshot.epoch,
+                current_epoch
+            )));
+        }
+
+        // The snapshot itself is not signed. The state root after the remaining state transitions are applied is
+        // checked against the checkpoint, which is.
+        Ok(())
+    }
+
     fn get_state_root_for_shard(
         &self,
         shard: Shard,
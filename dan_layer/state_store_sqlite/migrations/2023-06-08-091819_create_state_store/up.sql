@@ -101,6 +101,22 @@ create table foreign_parked_blocks
 -- block_id must be unique. Optimise fetching by block_id
 create unique index foreign_parked_blocks_uniq_idx_id on foreign_parked_blocks (block_id);
 
+-- Foreign proposals for future epochs that did not fit in the in-memory buffer
+create table evicted_foreign_proposals
+(
+    id            integer   not null primary key AUTOINCREMENT,
+    block_id      text      not NULL,
+    epoch         bigint    not NULL,
+    block_height  bigint    not NULL,
+    sender        text      not NULL,
+    block         text      not NULL,
+    block_pledges text      not NULL,
+    justify_qc    text      not NULL,
+    created_at    timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create unique index evicted_foreign_proposals_uniq_idx_block_id on evicted_foreign_proposals (block_id);
+create index evicted_foreign_proposals_idx_epoch_block_height on evicted_foreign_proposals (epoch, block_height);
+
 CREATE TABLE foreign_missing_transactions
 (
     id              integer   not NULL primary key AUTOINCREMENT,
@@ -174,6 +190,8 @@ create unique index substates_uniq_substate_id_and_version on substates (substat
 -- querying for transaction ids that either Upd or Downd a substate
 create index substates_idx_created_by_transaction on substates (created_by_transaction);
 create index substates_idx_destroyed_by_transaction on substates (destroyed_by_transaction) where destroyed_by_transaction is not null;
+-- querying for historical substate versions to prune
+create index substates_idx_destroyed_at_epoch on substates (destroyed_at_epoch) where destroyed_at_epoch is not null;
 
 create table foreign_substate_pledges
 (
@@ -325,6 +343,23 @@ create table transaction_executions
 
 create unique index transaction_executions_uniq_block_id_transaction_id on transaction_executions (block_id, transaction_id);
 
+create table transaction_events
+(
+    id               integer   NOT NULL primary key AUTOINCREMENT,
+    transaction_id   text      NOT NULL,
+    block_id         text      NOT NULL,
+    event_index      integer   NOT NULL,
+    topic            text      NOT NULL,
+    substate_id      text      NULL,
+    event            text      NOT NULL,
+    created_at       timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (transaction_id) REFERENCES transactions (transaction_id),
+    UNIQUE (transaction_id, event_index)
+);
+
+create index transaction_events_idx_topic on transaction_events (topic);
+create index transaction_events_idx_substate_id on transaction_events (substate_id);
+
 create table transaction_pool
 (
     id                integer   not null primary key AUTOINCREMENT,
@@ -334,6 +369,7 @@ create table transaction_pool
     remote_decision   text      null,
     evidence          text      null,
     transaction_fee   bigint    not null DEFAULT 0,
+    priority_fee      bigint    not null DEFAULT 0,
     leader_fee        text      null,
     stage             text      not null,
     pending_stage     text      null,
@@ -346,6 +382,7 @@ create table transaction_pool
 create unique index transaction_pool_uniq_idx_transaction_id on transaction_pool (transaction_id);
 create index transaction_pool_idx_is_ready on transaction_pool (is_ready);
 create index transaction_pool_idx_stage_is_ready on transaction_pool (stage, is_ready);
+create index transaction_pool_idx_stage_priority_fee on transaction_pool (stage, priority_fee);
 
 create table transaction_pool_state_updates
 (
@@ -423,11 +460,27 @@ CREATE TABLE foreign_send_counters
 
 CREATE TABLE foreign_receive_counters
 (
-    id         integer   not NULL primary key AUTOINCREMENT,
-    counters   text      not NULL,
-    created_at timestamp not NULL DEFAULT CURRENT_TIMESTAMP
+    id            integer   not NULL primary key AUTOINCREMENT,
+    counters      text      not NULL,
+    last_received text      not NULL,
+    created_at    timestamp not NULL DEFAULT CURRENT_TIMESTAMP
 );
 
+CREATE TABLE foreign_receive_gaps
+(
+    id                  integer   not NULL primary key AUTOINCREMENT,
+    foreign_shard_group integer   not NULL,
+    epoch               bigint    not NULL,
+    from_index          bigint    not NULL,
+    to_index            bigint    not NULL,
+    num_received        bigint    not NULL DEFAULT 0,
+    resolved_at         timestamp NULL,
+    created_at          timestamp not NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (foreign_shard_group, epoch, from_index)
+);
+
+CREATE INDEX foreign_receive_gaps_idx_resolved_at ON foreign_receive_gaps (resolved_at);
+
 CREATE TABLE burnt_utxos
 (
     id                       integer   not null primary key AUTOINCREMENT,
@@ -440,6 +493,36 @@ CREATE TABLE burnt_utxos
     UNIQUE (commitment)
 );
 
+CREATE TABLE proposal_equivocations
+(
+    id                       integer   not null primary key AUTOINCREMENT,
+    epoch                    bigint    not NULL,
+    proposed_by              text      not NULL,
+    block_height             bigint    not NULL,
+    first_block_id           text      not NULL,
+    second_block_id          text      not NULL,
+    evidence                 text      not NULL,
+    proposed_in_block        text      NULL REFERENCES blocks (block_id),
+    proposed_in_block_height bigint    NULL,
+    committed_in_block       text      NULL,
+    created_at               timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (epoch, proposed_by, block_height)
+);
+
+CREATE TABLE misbehaviour_evidence
+(
+    id         integer   not null primary key AUTOINCREMENT,
+    epoch      bigint    not NULL,
+    offender   text      not NULL,
+    kind       text      not NULL,
+    block_id   text      not NULL,
+    evidence   text      not NULL,
+    created_at timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (offender, kind, block_id)
+);
+
+CREATE INDEX misbehaviour_evidence_idx_epoch_offender ON misbehaviour_evidence (epoch, offender);
+
 CREATE TABLE state_tree
 (
     id    integer not NULL primary key AUTOINCREMENT,
@@ -488,6 +571,34 @@ CREATE TABLE epoch_checkpoints
     created_at   timestamp not NULL DEFAULT CURRENT_TIMESTAMP
 );
 
+-- Snapshots of the live substates of a shard at the end of an epoch
+CREATE TABLE state_snapshots
+(
+    id                    integer   not NULL primary key AUTOINCREMENT,
+    epoch                 bigint    not NULL,
+    shard                 int       not NULL,
+    state_version         bigint    not NULL,
+    state_root            text      not NULL,
+    last_transition_epoch bigint    not NULL,
+    last_transition_seq   bigint    not NULL,
+    num_substates         bigint    not NULL,
+    created_at            timestamp not NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (epoch, shard)
+);
+
+CREATE TABLE state_snapshot_substates
+(
+    id                     integer not NULL primary key AUTOINCREMENT,
+    epoch                  bigint  not NULL,
+    shard                  int     not NULL,
+    substate_id            text    not NULL,
+    version                int     not NULL,
+    data                   text    not NULL,
+    created_by_transaction text    not NULL
+);
+
+CREATE INDEX state_snapshot_substates_idx_epoch_shard on state_snapshot_substates (epoch, shard);
+
 -- An append-only store of state transitions
 CREATE TABLE state_transitions
 (
@@ -509,6 +620,8 @@ CREATE TABLE state_transitions
 );
 CREATE UNIQUE INDEX state_transitions_shard_seq on state_transitions (shard, seq);
 CREATE INDEX state_transitions_epoch on state_transitions (epoch);
+-- used to check whether a substate is referenced by a state transition before it is pruned
+CREATE INDEX state_transitions_substate_address on state_transitions (substate_address);
 
 CREATE TABLE validator_epoch_stats
 (
@@ -52,6 +52,7 @@ use tari_dan_storage::{
         ForeignProposalAtom,
         ForeignProposalStatus,
         ForeignReceiveCounters,
+        ForeignReceiveGap,
         ForeignSendCounters,
         HighQc,
         LastExecuted,
@@ -61,16 +62,21 @@ use tari_dan_storage::{
         LeafBlock,
         LockedBlock,
         LockedSubstateValue,
+        MisbehaviourEvidence,
         PendingShardStateTreeDiff,
+        ProposalEquivocation,
         QcId,
         QuorumCertificate,
+        StateSnapshot,
         StateTransition,
         StateTransitionId,
         SubstateChange,
+        SubstateData,
         SubstateLock,
         SubstatePledge,
         SubstatePledges,
         SubstateRecord,
+        TransactionEvent,
         TransactionPoolConfirmedStage,
         TransactionPoolRecord,
         TransactionPoolStage,
@@ -553,6 +559,40 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .collect()
     }
 
+    fn foreign_proposals_get_all_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<ForeignProposal>, StorageError> {
+        use crate::schema::{foreign_proposals, quorum_certificates};
+
+        let foreign_proposals = foreign_proposals::table
+            .left_join(quorum_certificates::table.on(foreign_proposals::justify_qc_id.eq(quorum_certificates::qc_id)))
+            .filter(foreign_proposals::commands.like(format!("%\"{}\"%", serialize_hex(transaction_id))))
+            .order_by(foreign_proposals::epoch.asc())
+            .then_order_by(foreign_proposals::height.asc())
+            .limit(limit as i64)
+            .get_results::<(sql_models::ForeignProposal, Option<sql_models::QuorumCertificate>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_proposals_get_all_by_transaction",
+                source: e,
+            })?;
+
+        foreign_proposals
+            .into_iter()
+            .map(|(proposal, qc)| {
+                let justify_qc = qc.ok_or_else(|| SqliteStorageError::DbInconsistency {
+                    operation: "foreign_proposals_get_all_by_transaction",
+                    details: format!(
+                        "foreign proposal {} references non-existent quorum certificate {}",
+                        proposal.block_id, proposal.justify_qc_id
+                    ),
+                })?;
+                proposal.try_convert(justify_qc)
+            })
+            .collect()
+    }
+
     fn foreign_proposals_exists(&self, block_id: &BlockId) -> Result<bool, StorageError> {
         use crate::schema::foreign_proposals;
 
@@ -693,6 +733,32 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         counter.try_into()
     }
 
+    fn foreign_receive_gaps_get_all(
+        &self,
+        include_resolved: bool,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<ForeignReceiveGap>, StorageError> {
+        use crate::schema::foreign_receive_gaps;
+
+        let mut query = foreign_receive_gaps::table.into_boxed();
+        if !include_resolved {
+            query = query.filter(foreign_receive_gaps::resolved_at.is_null());
+        }
+
+        let gaps = query
+            .order_by(foreign_receive_gaps::id.desc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .get_results::<sql_models::ForeignReceiveGap>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_receive_gaps_get_all",
+                source: e,
+            })?;
+
+        gaps.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn transactions_get(&self, tx_id: &TransactionId) -> Result<TransactionRecord, StorageError> {
         use crate::schema::transactions;
 
@@ -777,6 +843,29 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .collect()
     }
 
+    fn transactions_get_deferred(&self) -> Result<Vec<TransactionRecord>, StorageError> {
+        use crate::schema::{transaction_pool, transactions};
+
+        let transactions = transactions::table
+            .filter(transactions::min_epoch.is_not_null())
+            .filter(transactions::final_decision.is_null())
+            .filter(transactions::abort_details.is_null())
+            .filter(
+                transactions::transaction_id.ne_all(transaction_pool::table.select(transaction_pool::transaction_id)),
+            )
+            .order_by(transactions::created_at.asc())
+            .get_results::<sql_models::Transaction>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transactions_get_deferred",
+                source: e,
+            })?;
+
+        transactions
+            .into_iter()
+            .map(|transaction| transaction.try_into())
+            .collect()
+    }
+
     fn transaction_executions_get(
         &self,
         tx_id: &TransactionId,
@@ -1008,6 +1097,43 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
             .collect()
     }
 
+    fn blocks_get_all_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<Block>, StorageError> {
+        use crate::schema::{blocks, quorum_certificates};
+
+        // Commands are stored as JSON, in which transaction ids are quoted hex strings
+        let results = blocks::table
+            .left_join(quorum_certificates::table.on(blocks::qc_id.eq(quorum_certificates::qc_id)))
+            .select((blocks::all_columns, quorum_certificates::all_columns.nullable()))
+            .filter(blocks::commands.like(format!("%\"{}\"%", serialize_hex(transaction_id))))
+            .order_by(blocks::epoch.asc())
+            .then_order_by(blocks::height.asc())
+            .limit(limit as i64)
+            .get_results::<(sql_models::Block, Option<sql_models::QuorumCertificate>)>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "blocks_get_all_by_transaction",
+                source: e,
+            })?;
+
+        results
+            .into_iter()
+            .map(|(block, qc)| {
+                let qc = qc.ok_or_else(|| SqliteStorageError::DbInconsistency {
+                    operation: "blocks_get_all_by_transaction",
+                    details: format!(
+                        "block {} references non-existent quorum certificate {}",
+                        block.block_id, block.qc_id
+                    ),
+                })?;
+
+                block.try_convert(qc)
+            })
+            .collect()
+    }
+
     fn blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError> {
         use crate::schema::blocks;
 
@@ -1688,7 +1814,8 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
                 .filter(transaction_pool::stage.eq(TransactionPoolStage::New.to_string()))
                 // Filter out any transactions that are in lock conflict
                 .filter(transaction_pool::transaction_id.ne_all(lock_conflicts::table.select(lock_conflicts::transaction_id).filter(lock_conflicts::is_local_only.eq(false))))
-                .order_by(transaction_pool::transaction_id.asc())
+                // Propose the highest paying transactions first
+                .order_by((transaction_pool::priority_fee.desc(), transaction_pool::transaction_id.asc()))
                 .limit(new_limit as i64)
                 .get_results::<sql_models::TransactionPoolRecord>(self.connection())
                 .map_err(|e| SqliteStorageError::DieselError {
@@ -2046,6 +2173,21 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count > 0)
     }
 
+    fn substates_count_up(&self) -> Result<u64, StorageError> {
+        use crate::schema::substates;
+
+        let count = substates::table
+            .count()
+            .filter(substates::destroyed_by_transaction.is_null())
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substates_count_up",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
     fn substates_get_n_after(&self, n: usize, after: &SubstateAddress) -> Result<Vec<SubstateRecord>, StorageError> {
         use crate::schema::substates;
 
@@ -2350,6 +2492,61 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         checkpoint.try_into()
     }
 
+    fn state_snapshots_get(&self, epoch: Epoch, shard: Shard) -> Result<StateSnapshot, StorageError> {
+        use crate::schema::state_snapshots;
+
+        let snapshot = state_snapshots::table
+            .filter(state_snapshots::epoch.eq(epoch.as_u64() as i64))
+            .filter(state_snapshots::shard.eq(shard.as_u32() as i32))
+            .first::<sql_models::StateSnapshot>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshots_get",
+                source: e,
+            })?;
+
+        snapshot.try_into()
+    }
+
+    fn state_snapshots_get_latest(&self, shard: Shard, max_epoch: Epoch) -> Result<StateSnapshot, StorageError> {
+        use crate::schema::state_snapshots;
+
+        let snapshot = state_snapshots::table
+            .filter(state_snapshots::shard.eq(shard.as_u32() as i32))
+            .filter(state_snapshots::epoch.le(max_epoch.as_u64() as i64))
+            .order_by(state_snapshots::epoch.desc())
+            .first::<sql_models::StateSnapshot>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshots_get_latest",
+                source: e,
+            })?;
+
+        snapshot.try_into()
+    }
+
+    fn state_snapshot_substates_get(
+        &self,
+        epoch: Epoch,
+        shard: Shard,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SubstateData>, StorageError> {
+        use crate::schema::state_snapshot_substates;
+
+        let substates = state_snapshot_substates::table
+            .filter(state_snapshot_substates::epoch.eq(epoch.as_u64() as i64))
+            .filter(state_snapshot_substates::shard.eq(shard.as_u32() as i32))
+            .order_by(state_snapshot_substates::id.asc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .get_results::<sql_models::StateSnapshotSubstate>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshot_substates_get",
+                source: e,
+            })?;
+
+        substates.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn foreign_substate_pledges_exists_for_address<T: ToSubstateAddress>(
         &self,
         transaction_id: &TransactionId,
@@ -2472,6 +2669,179 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count as u64)
     }
 
+    fn proposal_equivocations_exists(
+        &self,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        height: NodeHeight,
+    ) -> Result<bool, StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let count = proposal_equivocations::table
+            .count()
+            .filter(proposal_equivocations::epoch.eq(epoch.as_u64() as i64))
+            .filter(proposal_equivocations::proposed_by.eq(proposed_by.to_hex()))
+            .filter(proposal_equivocations::block_height.eq(height.as_u64() as i64))
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_exists",
+                source: e,
+            })?;
+
+        Ok(count > 0)
+    }
+
+    fn proposal_equivocations_is_committed(
+        &self,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        height: NodeHeight,
+    ) -> Result<bool, StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let count = proposal_equivocations::table
+            .count()
+            .filter(proposal_equivocations::epoch.eq(epoch.as_u64() as i64))
+            .filter(proposal_equivocations::proposed_by.eq(proposed_by.to_hex()))
+            .filter(proposal_equivocations::block_height.eq(height.as_u64() as i64))
+            .filter(proposal_equivocations::committed_in_block.is_not_null())
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_is_committed",
+                source: e,
+            })?;
+
+        Ok(count > 0)
+    }
+
+    fn proposal_equivocations_get_all_unproposed(
+        &self,
+        leaf_block: &BlockId,
+        limit: usize,
+    ) -> Result<Vec<ProposalEquivocation>, StorageError> {
+        use crate::schema::proposal_equivocations;
+        if !self.blocks_exists(leaf_block)? {
+            return Err(StorageError::NotFound {
+                item: "Block",
+                key: leaf_block.to_string(),
+            });
+        }
+
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let locked_block = self.get_current_locked_block()?;
+        let exclude_block_ids = self.get_block_ids_with_commands_between(&locked_block.block_id, leaf_block)?;
+
+        let equivocations = proposal_equivocations::table
+            .filter(proposal_equivocations::committed_in_block.is_null())
+            // Evidence is only actionable in the epoch in which it occurred
+            .filter(proposal_equivocations::epoch.eq(locked_block.epoch.as_u64() as i64))
+            .filter(
+                proposal_equivocations::proposed_in_block.is_null().or(proposal_equivocations::proposed_in_block
+                    .ne_all(exclude_block_ids)
+                    .and(proposal_equivocations::proposed_in_block_height.gt(locked_block.height.as_u64() as i64))),
+            )
+            .limit(limit as i64)
+            .get_results::<sql_models::ProposalEquivocation>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_get_all_unproposed",
+                source: e,
+            })?;
+
+        equivocations.into_iter().map(TryInto::try_into).collect()
+    }
+
+    fn proposal_equivocations_get_committed_proposers(&self, epoch: Epoch) -> Result<Vec<PublicKey>, StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let pks = proposal_equivocations::table
+            .select(proposal_equivocations::proposed_by)
+            .distinct()
+            .filter(proposal_equivocations::epoch.eq(epoch.as_u64() as i64))
+            .filter(proposal_equivocations::committed_in_block.is_not_null())
+            .get_results::<String>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_get_committed_proposers",
+                source: e,
+            })?;
+
+        pks.iter()
+            .map(|s| {
+                PublicKey::from_hex(s).map_err(|e| StorageError::DecodingError {
+                    operation: "proposal_equivocations_get_committed_proposers",
+                    item: "public key",
+                    details: format!("Failed to decode public key: {e}"),
+                })
+            })
+            .collect()
+    }
+
+    fn misbehaviour_evidence_get_all(
+        &self,
+        epoch: Option<Epoch>,
+        offender: Option<&PublicKey>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<MisbehaviourEvidence>, StorageError> {
+        use crate::schema::misbehaviour_evidence;
+
+        let mut query = misbehaviour_evidence::table.into_boxed();
+        if let Some(epoch) = epoch {
+            query = query.filter(misbehaviour_evidence::epoch.eq(epoch.as_u64() as i64));
+        }
+        if let Some(offender) = offender {
+            query = query.filter(misbehaviour_evidence::offender.eq(offender.to_hex()));
+        }
+
+        let evidence = query
+            .order_by(misbehaviour_evidence::id.desc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .get_results::<sql_models::MisbehaviourEvidence>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "misbehaviour_evidence_get_all",
+                source: e,
+            })?;
+
+        evidence.into_iter().map(TryInto::try_into).collect()
+    }
+
+    fn transaction_events_get_all(
+        &self,
+        transaction_id: Option<&TransactionId>,
+        topic: Option<&str>,
+        substate_id: Option<&SubstateId>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<TransactionEvent>, StorageError> {
+        use crate::schema::transaction_events;
+
+        let mut query = transaction_events::table.into_boxed();
+        if let Some(transaction_id) = transaction_id {
+            query = query.filter(transaction_events::transaction_id.eq(serialize_hex(transaction_id)));
+        }
+        if let Some(topic) = topic {
+            query = query.filter(transaction_events::topic.eq(topic.to_string()));
+        }
+        if let Some(substate_id) = substate_id {
+            query = query.filter(transaction_events::substate_id.eq(substate_id.to_string()));
+        }
+
+        let events = query
+            .order_by(transaction_events::id.desc())
+            .limit(limit as i64)
+            .offset(offset as i64)
+            .get_results::<sql_models::TransactionEvent>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_events_get_all",
+                source: e,
+            })?;
+
+        events.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn foreign_parked_blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError> {
         use crate::schema::foreign_parked_blocks;
 
@@ -2487,6 +2857,20 @@ impl<'tx, TAddr: NodeAddressable + Serialize + DeserializeOwned + 'tx> StateStor
         Ok(count > 0)
     }
 
+    fn evicted_foreign_proposals_count(&self) -> Result<u64, StorageError> {
+        use crate::schema::evicted_foreign_proposals;
+
+        let count = evicted_foreign_proposals::table
+            .count()
+            .get_result::<i64>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "evicted_foreign_proposals_count",
+                source: e,
+            })?;
+
+        Ok(count as u64)
+    }
+
     fn validator_epoch_stats_get(
         &self,
         epoch: Epoch,
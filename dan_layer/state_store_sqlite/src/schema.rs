@@ -111,6 +111,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    evicted_foreign_proposals (id) {
+        id -> Integer,
+        block_id -> Text,
+        epoch -> BigInt,
+        block_height -> BigInt,
+        sender -> Text,
+        block -> Text,
+        block_pledges -> Text,
+        justify_qc -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     evicted_nodes (id) {
         id -> Integer,
@@ -178,6 +192,20 @@ diesel::table! {
     foreign_receive_counters (id) {
         id -> Integer,
         counters -> Text,
+        last_received -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    foreign_receive_gaps (id) {
+        id -> Integer,
+        foreign_shard_group -> Integer,
+        epoch -> BigInt,
+        from_index -> BigInt,
+        to_index -> BigInt,
+        num_received -> BigInt,
+        resolved_at -> Nullable<Timestamp>,
         created_at -> Timestamp,
     }
 }
@@ -290,6 +318,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    misbehaviour_evidence (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        offender -> Text,
+        kind -> Text,
+        block_id -> Text,
+        evidence -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     missing_transactions (id) {
         id -> Integer,
@@ -340,6 +380,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    proposal_equivocations (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        proposed_by -> Text,
+        block_height -> BigInt,
+        first_block_id -> Text,
+        second_block_id -> Text,
+        evidence -> Text,
+        proposed_in_block -> Nullable<Text>,
+        proposed_in_block_height -> Nullable<BigInt>,
+        committed_in_block -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     quorum_certificates (id) {
         id -> Integer,
@@ -353,6 +409,32 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    state_snapshot_substates (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        shard -> Integer,
+        substate_id -> Text,
+        version -> Integer,
+        data -> Text,
+        created_by_transaction -> Text,
+    }
+}
+
+diesel::table! {
+    state_snapshots (id) {
+        id -> Integer,
+        epoch -> BigInt,
+        shard -> Integer,
+        state_version -> BigInt,
+        state_root -> Text,
+        last_transition_epoch -> BigInt,
+        last_transition_seq -> BigInt,
+        num_substates -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     state_transitions (id) {
         id -> Integer,
@@ -424,6 +506,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transaction_events (id) {
+        id -> Integer,
+        transaction_id -> Text,
+        block_id -> Text,
+        event_index -> Integer,
+        topic -> Text,
+        substate_id -> Nullable<Text>,
+        event -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     transaction_executions (id) {
         id -> Integer,
@@ -447,6 +542,7 @@ diesel::table! {
         remote_decision -> Nullable<Text>,
         evidence -> Nullable<Text>,
         transaction_fee -> BigInt,
+        priority_fee -> BigInt,
         leader_fee -> Nullable<Text>,
         stage -> Text,
         pending_stage -> Nullable<Text>,
@@ -559,11 +655,13 @@ diesel::allow_tables_to_appear_in_same_query!(
     diagnostic_deleted_blocks,
     diagnostics_no_votes,
     epoch_checkpoints,
+    evicted_foreign_proposals,
     evicted_nodes,
     foreign_missing_transactions,
     foreign_parked_blocks,
     foreign_proposals,
     foreign_receive_counters,
+    foreign_receive_gaps,
     foreign_send_counters,
     foreign_substate_pledges,
     high_qcs,
@@ -574,15 +672,20 @@ diesel::allow_tables_to_appear_in_same_query!(
     leaf_blocks,
     lock_conflicts,
     locked_block,
+    misbehaviour_evidence,
     missing_transactions,
     parked_blocks,
     pending_state_tree_diffs,
+    proposal_equivocations,
     quorum_certificates,
+    state_snapshot_substates,
+    state_snapshots,
     state_transitions,
     state_tree,
     state_tree_shard_versions,
     substate_locks,
     substates,
+    transaction_events,
     transaction_executions,
     transaction_pool,
     transaction_pool_history,
@@ -0,0 +1,38 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_common_types::NodeAddressable;
+use tari_dan_storage::{consensus_models, StorageError};
+use time::PrimitiveDateTime;
+
+use crate::serialization::deserialize_json;
+
+#[derive(Debug, Clone, Queryable)]
+pub struct EvictedForeignProposal {
+    pub id: i32,
+    pub block_id: String,
+    pub epoch: i64,
+    pub block_height: i64,
+    pub sender: String,
+    pub block: String,
+    pub block_pledges: String,
+    pub justify_qc: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl<TAddr: NodeAddressable> TryFrom<EvictedForeignProposal> for consensus_models::EvictedForeignProposal<TAddr> {
+    type Error = StorageError;
+
+    fn try_from(value: EvictedForeignProposal) -> Result<Self, Self::Error> {
+        let from = deserialize_json(&value.sender)?;
+        let block = deserialize_json(&value.block)?;
+        let block_pledge = deserialize_json(&value.block_pledges)?;
+        let justify_qc = deserialize_json(&value.justify_qc)?;
+
+        Ok(consensus_models::EvictedForeignProposal::new(
+            from,
+            consensus_models::ForeignProposal::new(block, block_pledge, justify_qc),
+        ))
+    }
+}
@@ -133,6 +133,7 @@ impl TryFrom<ForeignSendCounters> for consensus_models::ForeignSendCounters {
 pub struct ForeignReceiveCounters {
     pub id: i32,
     pub counters: String,
+    pub last_received: String,
     pub created_at: PrimitiveDateTime,
 }
 
@@ -142,6 +143,43 @@ impl TryFrom<ForeignReceiveCounters> for consensus_models::ForeignReceiveCounter
     fn try_from(value: ForeignReceiveCounters) -> Result<Self, Self::Error> {
         Ok(Self {
             counters: deserialize_json(&value.counters)?,
+            last_received: deserialize_json(&value.last_received)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct ForeignReceiveGap {
+    pub id: i32,
+    pub foreign_shard_group: i32,
+    pub epoch: i64,
+    pub from_index: i64,
+    pub to_index: i64,
+    pub num_received: i64,
+    pub resolved_at: Option<PrimitiveDateTime>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<ForeignReceiveGap> for consensus_models::ForeignReceiveGap {
+    type Error = StorageError;
+
+    fn try_from(value: ForeignReceiveGap) -> Result<Self, Self::Error> {
+        let foreign_shard_group = ShardGroup::decode_from_u32(value.foreign_shard_group as u32).ok_or_else(|| {
+            StorageError::DataInconsistency {
+                details: format!(
+                    "Foreign receive gap id={} shard_group ({}) is not valid",
+                    value.id, value.foreign_shard_group as u32
+                ),
+            }
+        })?;
+        Ok(Self {
+            foreign_shard_group,
+            epoch: Epoch(value.epoch as u64),
+            from_index: value.from_index as u64,
+            to_index: value.to_index as u64,
+            num_received: value.num_received as u64,
+            detected_at: Some(value.created_at),
+            resolved_at: value.resolved_at,
         })
     }
 }
@@ -0,0 +1,30 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_storage::{consensus_models, StorageError};
+use time::PrimitiveDateTime;
+
+use crate::serialization::deserialize_json;
+
+#[derive(Debug, Clone, Queryable)]
+pub struct MisbehaviourEvidence {
+    pub id: i32,
+    pub epoch: i64,
+    pub offender: String,
+    pub kind: String,
+    pub block_id: String,
+    pub evidence: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<MisbehaviourEvidence> for consensus_models::MisbehaviourEvidence {
+    type Error = StorageError;
+
+    fn try_from(value: MisbehaviourEvidence) -> Result<Self, Self::Error> {
+        Ok(Self {
+            misbehaviour: deserialize_json(&value.evidence)?,
+            recorded_at: Some(value.created_at),
+        })
+    }
+}
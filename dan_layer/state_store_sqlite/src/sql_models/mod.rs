@@ -6,16 +6,21 @@ mod block_diff;
 mod bookkeeping;
 mod burnt_utxo;
 mod epoch_checkpoint;
+mod evicted_foreign_proposal;
 mod foreign_parked_block;
 mod foreign_proposal;
 mod foreign_substate_pledge;
 mod leaf_block;
+mod misbehaviour_evidence;
 mod pending_state_tree_diff;
+mod proposal_equivocation;
 mod quorum_certificate;
+mod state_snapshot;
 mod state_transition;
 mod substate;
 mod substate_lock;
 mod transaction;
+mod transaction_event;
 mod transaction_execution;
 mod transaction_pool;
 mod vote;
@@ -25,16 +30,21 @@ pub use block_diff::*;
 pub use bookkeeping::*;
 pub use burnt_utxo::*;
 pub use epoch_checkpoint::*;
+pub use evicted_foreign_proposal::*;
 pub use foreign_parked_block::*;
 pub use foreign_proposal::*;
 pub use foreign_substate_pledge::*;
 pub use leaf_block::*;
+pub use misbehaviour_evidence::*;
 pub use pending_state_tree_diff::*;
+pub use proposal_equivocation::*;
 pub use quorum_certificate::*;
+pub use state_snapshot::*;
 pub use state_transition::*;
 pub use substate::*;
 pub use substate_lock::*;
 pub use transaction::*;
+pub use transaction_event::*;
 pub use transaction_execution::*;
 pub use transaction_pool::*;
 pub use vote::*;
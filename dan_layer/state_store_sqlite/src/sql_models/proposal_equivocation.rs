@@ -0,0 +1,43 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_storage::{consensus_models, StorageError};
+use time::PrimitiveDateTime;
+
+use crate::serialization::{deserialize_hex_try_from, deserialize_json};
+
+#[derive(Debug, Clone, Queryable)]
+pub struct ProposalEquivocation {
+    pub id: i32,
+    pub epoch: i64,
+    pub proposed_by: String,
+    pub block_height: i64,
+    pub first_block_id: String,
+    pub second_block_id: String,
+    pub evidence: String,
+    pub proposed_in_block: Option<String>,
+    pub proposed_in_block_height: Option<i64>,
+    pub committed_in_block: Option<String>,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<ProposalEquivocation> for consensus_models::ProposalEquivocation {
+    type Error = StorageError;
+
+    fn try_from(value: ProposalEquivocation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            atom: deserialize_json(&value.evidence)?,
+            proposed_in_block: value
+                .proposed_in_block
+                .as_deref()
+                .map(deserialize_hex_try_from)
+                .transpose()?,
+            committed_in_block: value
+                .committed_in_block
+                .as_deref()
+                .map(deserialize_hex_try_from)
+                .transpose()?,
+        })
+    }
+}
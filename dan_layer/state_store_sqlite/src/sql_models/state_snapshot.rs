@@ -0,0 +1,74 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_common_types::{shard::Shard, Epoch};
+use tari_dan_storage::{consensus_models, consensus_models::StateTransitionId, StorageError};
+use tari_state_tree::TreeHash;
+use time::PrimitiveDateTime;
+
+use crate::serialization::{deserialize_hex, deserialize_hex_try_from, deserialize_json, parse_from_string};
+
+#[derive(Debug, Clone, Queryable)]
+pub struct StateSnapshot {
+    pub id: i32,
+    pub epoch: i64,
+    pub shard: i32,
+    pub state_version: i64,
+    pub state_root: String,
+    pub last_transition_epoch: i64,
+    pub last_transition_seq: i64,
+    pub num_substates: i64,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<StateSnapshot> for consensus_models::StateSnapshot {
+    type Error = StorageError;
+
+    fn try_from(value: StateSnapshot) -> Result<Self, Self::Error> {
+        let shard = Shard::from(value.shard as u32);
+        let state_root =
+            TreeHash::try_from_bytes(&deserialize_hex(&value.state_root)?).map_err(|e| StorageError::DecodingError {
+                operation: "StateSnapshot::try_from",
+                item: "state_root",
+                details: e.to_string(),
+            })?;
+
+        Ok(Self {
+            epoch: Epoch(value.epoch as u64),
+            shard,
+            state_version: value.state_version as u64,
+            state_root,
+            last_transition_id: StateTransitionId::new(
+                Epoch(value.last_transition_epoch as u64),
+                shard,
+                value.last_transition_seq as u64,
+            ),
+            num_substates: value.num_substates as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Queryable)]
+pub struct StateSnapshotSubstate {
+    pub id: i32,
+    pub epoch: i64,
+    pub shard: i32,
+    pub substate_id: String,
+    pub version: i32,
+    pub data: String,
+    pub created_by_transaction: String,
+}
+
+impl TryFrom<StateSnapshotSubstate> for consensus_models::SubstateData {
+    type Error = StorageError;
+
+    fn try_from(value: StateSnapshotSubstate) -> Result<Self, Self::Error> {
+        Ok(Self {
+            substate_id: parse_from_string(&value.substate_id)?,
+            version: value.version as u32,
+            substate_value: deserialize_json(&value.data)?,
+            created_by_transaction: deserialize_hex_try_from(&value.created_by_transaction)?,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use diesel::Queryable;
+use tari_dan_storage::{consensus_models, StorageError};
+use time::PrimitiveDateTime;
+
+use crate::serialization::{deserialize_hex_try_from, deserialize_json};
+
+#[derive(Debug, Clone, Queryable)]
+pub struct TransactionEvent {
+    pub id: i32,
+    pub transaction_id: String,
+    pub block_id: String,
+    pub event_index: i32,
+    pub topic: String,
+    pub substate_id: Option<String>,
+    pub event: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl TryFrom<TransactionEvent> for consensus_models::TransactionEvent {
+    type Error = StorageError;
+
+    fn try_from(value: TransactionEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction_id: deserialize_hex_try_from(&value.transaction_id)?,
+            block_id: deserialize_hex_try_from(&value.block_id)?,
+            index: value.event_index as u32,
+            event: deserialize_json(&value.event)?,
+        })
+    }
+}
@@ -21,6 +21,7 @@ pub struct TransactionPoolRecord {
     pub remote_decision: Option<String>,
     pub evidence: Option<String>,
     pub transaction_fee: i64,
+    pub priority_fee: i64,
     pub leader_fee: Option<String>,
     pub stage: String,
     // TODO: This is the last stage update, but does not reflect the actual stage (which comes from the
@@ -73,6 +74,7 @@ impl TransactionPoolRecord {
             deserialize_hex_try_from(&self.transaction_id)?,
             evidence,
             transaction_fee as u64,
+            self.priority_fee as u64,
             leader_fee,
             parse_from_string(&self.stage)?,
             pending_stage,
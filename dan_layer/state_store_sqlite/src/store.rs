@@ -8,7 +8,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use diesel::{sql_query, Connection, RunQueryDsl, SqliteConnection};
+use diesel::{sql_query, Connection, QueryableByName, RunQueryDsl, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use log::log;
 use serde::{de::DeserializeOwned, Serialize};
@@ -60,6 +60,46 @@ impl<TAddr> SqliteStateStore<TAddr> {
             })?;
         Ok(())
     }
+
+    /// Rebuilds the database file, reclaiming free pages left behind by deleted rows. This requires exclusive access
+    /// and may take some time on large databases.
+    pub fn vacuum(&self) -> Result<(), StorageError> {
+        sql_query("VACUUM;")
+            .execute(&mut *self.connection.lock().unwrap())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "vacuum",
+            })?;
+        Ok(())
+    }
+
+    /// Refreshes the table and index statistics used by the query planner
+    pub fn analyze(&self) -> Result<(), StorageError> {
+        sql_query("ANALYZE;")
+            .execute(&mut *self.connection.lock().unwrap())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "analyze",
+            })?;
+        Ok(())
+    }
+
+    /// Returns the size of the database file in bytes
+    pub fn database_size(&self) -> Result<u64, StorageError> {
+        #[derive(Debug, QueryableByName)]
+        struct DatabaseSize {
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            size: i64,
+        }
+
+        let result = sql_query("SELECT page_count * page_size AS size FROM pragma_page_count(), pragma_page_size();")
+            .get_result::<DatabaseSize>(&mut *self.connection.lock().unwrap())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "database_size",
+            })?;
+        Ok(result.size.try_into().unwrap_or(0))
+    }
 }
 
 // Manually implement the Debug implementation because `SqliteConnection` does not implement the Debug trait
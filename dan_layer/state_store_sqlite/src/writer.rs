@@ -7,13 +7,14 @@ use diesel::{
     dsl,
     dsl::count_star,
     sql_query,
-    sql_types::Text,
+    sql_types::{BigInt, Integer, Text},
     AsChangeset,
     BoolExpressionMethods,
     ExpressionMethods,
     NullableExpressionMethods,
     OptionalExtension,
     QueryDsl,
+    QueryableByName,
     RunQueryDsl,
     SqliteConnection,
 };
@@ -39,10 +40,12 @@ use tari_dan_storage::{
         BurntUtxo,
         Decision,
         EpochCheckpoint,
+        EvictedForeignProposal,
         ForeignParkedProposal,
         ForeignProposal,
         ForeignProposalStatus,
         ForeignReceiveCounters,
+        ForeignReceiveGap,
         ForeignSendCounters,
         HighQc,
         LastExecuted,
@@ -52,15 +55,21 @@ use tari_dan_storage::{
         LeafBlock,
         LockConflict,
         LockedBlock,
+        MisbehaviourEvidence,
         NoVoteReason,
         PendingShardStateTreeDiff,
+        ProposalEquivocationAtom,
+        PrunedSubstates,
         QcId,
         QuorumCertificate,
+        StateSnapshot,
+        StateTransitionId,
         SubstateChange,
         SubstateLock,
         SubstatePledge,
         SubstatePledges,
         SubstateRecord,
+        TransactionEvent,
         TransactionPoolConfirmedStage,
         TransactionPoolRecord,
         TransactionPoolStage,
@@ -75,7 +84,7 @@ use tari_dan_storage::{
     StorageError,
 };
 use tari_engine_types::{substate::SubstateId, template_models::UnclaimedConfidentialOutputAddress};
-use tari_state_tree::{Node, NodeKey, StaleTreeNode, TreeNode, Version};
+use tari_state_tree::{Node, NodeKey, StaleTreeNode, TreeHash, TreeNode, Version};
 use tari_transaction::TransactionId;
 use tari_utilities::{hex::Hex, ByteArray};
 use time::{OffsetDateTime, PrimitiveDateTime};
@@ -788,7 +797,10 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
     ) -> Result<(), StorageError> {
         use crate::schema::foreign_receive_counters;
 
-        let insert = (foreign_receive_counters::counters.eq(serialize_json(&foreign_receive_counter.counters)?),);
+        let insert = (
+            foreign_receive_counters::counters.eq(serialize_json(&foreign_receive_counter.counters)?),
+            foreign_receive_counters::last_received.eq(serialize_json(&foreign_receive_counter.last_received)?),
+        );
 
         diesel::insert_into(foreign_receive_counters::table)
             .values(insert)
@@ -801,6 +813,69 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn foreign_receive_gaps_insert(&mut self, gap: &ForeignReceiveGap) -> Result<(), StorageError> {
+        use crate::schema::foreign_receive_gaps;
+
+        let values = (
+            foreign_receive_gaps::foreign_shard_group.eq(gap.foreign_shard_group.encode_as_u32() as i32),
+            foreign_receive_gaps::epoch.eq(gap.epoch.as_u64() as i64),
+            foreign_receive_gaps::from_index.eq(gap.from_index as i64),
+            foreign_receive_gaps::to_index.eq(gap.to_index as i64),
+            foreign_receive_gaps::num_received.eq(gap.num_received as i64),
+        );
+
+        diesel::insert_into(foreign_receive_gaps::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_receive_gaps_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn foreign_receive_gaps_record_received(
+        &mut self,
+        foreign_shard_group: ShardGroup,
+        epoch: Epoch,
+        index: u64,
+    ) -> Result<(), StorageError> {
+        use crate::schema::foreign_receive_gaps;
+
+        let foreign_shard_group = foreign_shard_group.encode_as_u32() as i32;
+        let num_updated = diesel::update(foreign_receive_gaps::table)
+            .filter(foreign_receive_gaps::foreign_shard_group.eq(foreign_shard_group))
+            .filter(foreign_receive_gaps::epoch.eq(epoch.as_u64() as i64))
+            .filter(foreign_receive_gaps::from_index.le(index as i64))
+            .filter(foreign_receive_gaps::to_index.ge(index as i64))
+            .filter(foreign_receive_gaps::resolved_at.is_null())
+            .set(foreign_receive_gaps::num_received.eq(foreign_receive_gaps::num_received + 1))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "foreign_receive_gaps_record_received",
+                source: e,
+            })?;
+
+        if num_updated > 0 {
+            let num_missing = foreign_receive_gaps::to_index - foreign_receive_gaps::from_index + 1;
+            diesel::update(foreign_receive_gaps::table)
+                .filter(foreign_receive_gaps::foreign_shard_group.eq(foreign_shard_group))
+                .filter(foreign_receive_gaps::epoch.eq(epoch.as_u64() as i64))
+                .filter(foreign_receive_gaps::num_received.ge(num_missing))
+                .filter(foreign_receive_gaps::resolved_at.is_null())
+                .set(foreign_receive_gaps::resolved_at.eq(now()))
+                .execute(self.connection())
+                .map_err(|e| SqliteStorageError::DieselError {
+                    operation: "foreign_receive_gaps_record_received",
+                    source: e,
+                })?;
+        }
+
+        Ok(())
+    }
+
     fn transactions_insert(&mut self, tx_rec: &TransactionRecord) -> Result<(), StorageError> {
         use crate::schema::transactions;
 
@@ -1042,6 +1117,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         &mut self,
         tx_id: TransactionId,
         decision: Decision,
+        priority_fee: u64,
         is_ready: bool,
     ) -> Result<(), StorageError> {
         use crate::schema::transaction_pool;
@@ -1049,6 +1125,7 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         let insert = (
             transaction_pool::transaction_id.eq(serialize_hex(tx_id)),
             transaction_pool::original_decision.eq(decision.to_string()),
+            transaction_pool::priority_fee.eq(priority_fee as i64),
             transaction_pool::stage.eq(TransactionPoolStage::New.to_string()),
             transaction_pool::is_ready.eq(is_ready),
         );
@@ -1481,6 +1558,69 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         blocks.into_iter().map(TryInto::try_into).collect()
     }
 
+    fn evicted_foreign_proposals_insert(
+        &mut self,
+        proposal: &EvictedForeignProposal<Self::Addr>,
+    ) -> Result<(), StorageError> {
+        use crate::schema::evicted_foreign_proposals;
+
+        let values = (
+            evicted_foreign_proposals::block_id.eq(serialize_hex(proposal.block().id())),
+            evicted_foreign_proposals::epoch.eq(proposal.epoch().as_u64() as i64),
+            evicted_foreign_proposals::block_height.eq(proposal.block().height().as_u64() as i64),
+            evicted_foreign_proposals::sender.eq(serialize_json(proposal.from())?),
+            evicted_foreign_proposals::block.eq(serialize_json(proposal.block())?),
+            evicted_foreign_proposals::block_pledges.eq(serialize_json(proposal.block_pledge())?),
+            evicted_foreign_proposals::justify_qc.eq(serialize_json(proposal.justify_qc())?),
+        );
+
+        diesel::insert_into(evicted_foreign_proposals::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "evicted_foreign_proposals_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn evicted_foreign_proposals_remove_up_to_epoch(
+        &mut self,
+        epoch: Epoch,
+        limit: usize,
+    ) -> Result<Vec<EvictedForeignProposal<Self::Addr>>, StorageError> {
+        use crate::schema::evicted_foreign_proposals;
+
+        let proposals = evicted_foreign_proposals::table
+            .filter(evicted_foreign_proposals::epoch.le(epoch.as_u64() as i64))
+            .order_by((
+                evicted_foreign_proposals::epoch.asc(),
+                evicted_foreign_proposals::block_height.asc(),
+            ))
+            .limit(limit as i64)
+            .get_results::<sql_models::EvictedForeignProposal>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "evicted_foreign_proposals_remove_up_to_epoch",
+                source: e,
+            })?;
+
+        if proposals.is_empty() {
+            return Ok(vec![]);
+        }
+
+        diesel::delete(evicted_foreign_proposals::table)
+            .filter(evicted_foreign_proposals::id.eq_any(proposals.iter().map(|p| p.id)))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "evicted_foreign_proposals_remove_up_to_epoch",
+                source: e,
+            })?;
+
+        proposals.into_iter().map(TryInto::try_into).collect()
+    }
+
     fn votes_insert(&mut self, vote: &Vote) -> Result<(), StorageError> {
         use crate::schema::votes;
 
@@ -1737,6 +1877,127 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn substates_prune_destroyed_before(
+        &mut self,
+        epoch: Epoch,
+        limit: usize,
+    ) -> Result<PrunedSubstates, StorageError> {
+        use crate::schema::substates;
+
+        #[derive(Debug, QueryableByName)]
+        struct PrunableSubstate {
+            #[diesel(sql_type = Integer)]
+            id: i32,
+            #[diesel(sql_type = BigInt)]
+            data_size: i64,
+        }
+
+        // Only versions that have been superseded and are no longer referenced by a state transition are pruned
+        let prunable = sql_query(
+            r#"
+                SELECT s.id, LENGTH(s.data) as data_size
+                FROM substates s
+                WHERE s.destroyed_at_epoch < ?
+                  AND EXISTS (SELECT 1 FROM substates n WHERE n.substate_id = s.substate_id AND n.version > s.version)
+                  AND NOT EXISTS (SELECT 1 FROM state_transitions t WHERE t.substate_address = s.address)
+                LIMIT ?"#,
+        )
+        .bind::<BigInt, _>(epoch.as_u64() as i64)
+        .bind::<BigInt, _>(limit as i64)
+        .get_results::<PrunableSubstate>(self.connection())
+        .map_err(|e| SqliteStorageError::DieselError {
+            operation: "substates_prune_destroyed_before",
+            source: e,
+        })?;
+
+        if prunable.is_empty() {
+            return Ok(PrunedSubstates::default());
+        }
+
+        let num_bytes = prunable.iter().map(|s| s.data_size as u64).sum();
+        let ids = prunable.iter().map(|s| s.id).collect::<Vec<_>>();
+        let num_substates = diesel::delete(substates::table)
+            .filter(substates::id.eq_any(ids))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "substates_prune_destroyed_before",
+                source: e,
+            })?;
+
+        Ok(PrunedSubstates {
+            num_substates: num_substates as u64,
+            num_bytes,
+        })
+    }
+
+    fn state_transitions_rebase(&mut self, shard: Shard, last_seq: u64) -> Result<(), StorageError> {
+        use crate::schema::state_transitions;
+
+        let max_seq = state_transitions::table
+            .select(dsl::max(state_transitions::seq))
+            .filter(state_transitions::shard.eq(shard.as_u32() as i32))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_transitions_rebase",
+                source: e,
+            })?;
+
+        let Some(max_seq) = max_seq else {
+            return Ok(());
+        };
+        if (last_seq as i64) < max_seq {
+            return Err(StorageError::QueryError {
+                reason: format!(
+                    "state_transitions_rebase: cannot rebase {} transitions for {} to end at seq {}",
+                    max_seq, shard, last_seq
+                ),
+            });
+        }
+
+        let offset = last_seq as i64 - max_seq;
+        if offset == 0 {
+            return Ok(());
+        }
+
+        // Sequence numbers are unique per shard, so the transitions are negated first to avoid conflicts while shifting
+        sql_query("UPDATE state_transitions SET seq = -(seq + ?) WHERE shard = ?")
+            .bind::<BigInt, _>(offset)
+            .bind::<Integer, _>(shard.as_u32() as i32)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_transitions_rebase",
+                source: e,
+            })?;
+
+        sql_query("UPDATE state_transitions SET seq = -seq WHERE shard = ? AND seq < 0")
+            .bind::<Integer, _>(shard.as_u32() as i32)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_transitions_rebase",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn state_transitions_prune_before(&mut self, epoch: Epoch) -> Result<u64, StorageError> {
+        // The next sequence number is derived from the last transition, so the last transition of each shard is kept
+        let num_deleted = sql_query(
+            r#"
+                DELETE FROM state_transitions
+                WHERE epoch < ?
+                  AND seq < (SELECT MAX(t.seq) FROM state_transitions t WHERE t.shard = state_transitions.shard)"#,
+        )
+        .bind::<BigInt, _>(epoch.as_u64() as i64)
+        .execute(self.connection())
+        .map_err(|e| SqliteStorageError::DieselError {
+            operation: "state_transitions_prune_before",
+            source: e,
+        })?;
+
+        Ok(num_deleted as u64)
+    }
+
     fn foreign_substate_pledges_save(
         &mut self,
         transaction_id: &TransactionId,
@@ -1997,6 +2258,84 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn state_snapshots_create(
+        &mut self,
+        epoch: Epoch,
+        shard: Shard,
+        state_version: Version,
+        state_root: TreeHash,
+        last_transition_id: StateTransitionId,
+    ) -> Result<StateSnapshot, StorageError> {
+        use crate::schema::state_snapshots;
+
+        // Copy the live substates of the shard into the snapshot
+        let num_substates = sql_query(
+            r#"
+            INSERT INTO state_snapshot_substates (epoch, shard, substate_id, version, data, created_by_transaction)
+            SELECT ?, created_by_shard, substate_id, version, data, created_by_transaction
+              FROM substates
+             WHERE created_by_shard = ? AND destroyed_by_transaction IS NULL
+             ORDER BY id ASC
+        "#,
+        )
+        .bind::<BigInt, _>(epoch.as_u64() as i64)
+        .bind::<Integer, _>(shard.as_u32() as i32)
+        .execute(self.connection())
+        .map_err(|e| SqliteStorageError::DieselError {
+            operation: "state_snapshots_create",
+            source: e,
+        })?;
+
+        let values = (
+            state_snapshots::epoch.eq(epoch.as_u64() as i64),
+            state_snapshots::shard.eq(shard.as_u32() as i32),
+            state_snapshots::state_version.eq(state_version as i64),
+            state_snapshots::state_root.eq(serialize_hex(state_root.to_vec())),
+            state_snapshots::last_transition_epoch.eq(last_transition_id.epoch().as_u64() as i64),
+            state_snapshots::last_transition_seq.eq(last_transition_id.seq() as i64),
+            state_snapshots::num_substates.eq(num_substates as i64),
+        );
+
+        diesel::insert_into(state_snapshots::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshots_create",
+                source: e,
+            })?;
+
+        Ok(StateSnapshot {
+            epoch,
+            shard,
+            state_version,
+            state_root,
+            last_transition_id,
+            num_substates: num_substates as u64,
+        })
+    }
+
+    fn state_snapshots_delete_before(&mut self, epoch: Epoch) -> Result<(), StorageError> {
+        use crate::schema::{state_snapshot_substates, state_snapshots};
+
+        diesel::delete(state_snapshot_substates::table)
+            .filter(state_snapshot_substates::epoch.lt(epoch.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshots_delete_before",
+                source: e,
+            })?;
+
+        diesel::delete(state_snapshots::table)
+            .filter(state_snapshots::epoch.lt(epoch.as_u64() as i64))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "state_snapshots_delete_before",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn burnt_utxos_insert(&mut self, burnt_utxo: &BurntUtxo) -> Result<(), StorageError> {
         use crate::schema::burnt_utxos;
 
@@ -2090,6 +2429,166 @@ impl<'tx, TAddr: NodeAddressable + 'tx> StateStoreWriteTransaction for SqliteSta
         Ok(())
     }
 
+    fn proposal_equivocations_insert(&mut self, atom: &ProposalEquivocationAtom) -> Result<(), StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let values = (
+            proposal_equivocations::epoch.eq(atom.epoch().as_u64() as i64),
+            proposal_equivocations::proposed_by.eq(atom.proposed_by().to_hex()),
+            proposal_equivocations::block_height.eq(atom.height().as_u64() as i64),
+            proposal_equivocations::first_block_id.eq(serialize_hex(atom.first().id())),
+            proposal_equivocations::second_block_id.eq(serialize_hex(atom.second().id())),
+            proposal_equivocations::evidence.eq(serialize_json(atom)?),
+        );
+
+        // Only one piece of evidence is kept per proposer and height
+        diesel::insert_into(proposal_equivocations::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn proposal_equivocations_set_proposed_block(
+        &mut self,
+        atom: &ProposalEquivocationAtom,
+        proposed_in_block: &BlockId,
+    ) -> Result<(), StorageError> {
+        use crate::schema::{blocks, proposal_equivocations};
+
+        let proposed_in_block_hex = serialize_hex(proposed_in_block);
+        let num_affected = diesel::update(proposal_equivocations::table)
+            .filter(proposal_equivocations::epoch.eq(atom.epoch().as_u64() as i64))
+            .filter(proposal_equivocations::proposed_by.eq(atom.proposed_by().to_hex()))
+            .filter(proposal_equivocations::block_height.eq(atom.height().as_u64() as i64))
+            .set((
+                proposal_equivocations::proposed_in_block.eq(&proposed_in_block_hex),
+                proposal_equivocations::proposed_in_block_height.eq(blocks::table
+                    .select(blocks::height)
+                    .filter(blocks::block_id.eq(&proposed_in_block_hex))
+                    .single_value()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_set_proposed_block",
+                source: e,
+            })?;
+
+        if num_affected == 0 {
+            return Err(StorageError::NotFound {
+                item: "proposal_equivocation",
+                key: atom.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn proposal_equivocations_clear_proposed_block(&mut self, proposed_in_block: &BlockId) -> Result<(), StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let proposed_in_block_hex = serialize_hex(proposed_in_block);
+        diesel::update(proposal_equivocations::table)
+            .filter(proposal_equivocations::proposed_in_block.eq(&proposed_in_block_hex))
+            .set((
+                proposal_equivocations::proposed_in_block.eq(None::<String>),
+                proposal_equivocations::proposed_in_block_height.eq(None::<i64>),
+            ))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_clear_proposed_block",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn proposal_equivocations_set_committed(
+        &mut self,
+        atom: &ProposalEquivocationAtom,
+        committed_in_block: &BlockId,
+    ) -> Result<(), StorageError> {
+        use crate::schema::proposal_equivocations;
+
+        let num_affected = diesel::update(proposal_equivocations::table)
+            .filter(proposal_equivocations::epoch.eq(atom.epoch().as_u64() as i64))
+            .filter(proposal_equivocations::proposed_by.eq(atom.proposed_by().to_hex()))
+            .filter(proposal_equivocations::block_height.eq(atom.height().as_u64() as i64))
+            .set(proposal_equivocations::committed_in_block.eq(serialize_hex(committed_in_block)))
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "proposal_equivocations_set_committed",
+                source: e,
+            })?;
+
+        if num_affected == 0 {
+            return Err(StorageError::NotFound {
+                item: "proposal_equivocation",
+                key: atom.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn misbehaviour_evidence_insert(&mut self, evidence: &MisbehaviourEvidence) -> Result<(), StorageError> {
+        use crate::schema::misbehaviour_evidence;
+
+        let misbehaviour = &evidence.misbehaviour;
+        let values = (
+            misbehaviour_evidence::epoch.eq(misbehaviour.epoch().as_u64() as i64),
+            misbehaviour_evidence::offender.eq(misbehaviour.offender().to_hex()),
+            misbehaviour_evidence::kind.eq(misbehaviour.kind().to_string()),
+            misbehaviour_evidence::block_id.eq(serialize_hex(misbehaviour.block_id())),
+            misbehaviour_evidence::evidence.eq(serialize_json(misbehaviour)?),
+        );
+
+        // Only the first evidence of each kind is kept per offender and block
+        diesel::insert_into(misbehaviour_evidence::table)
+            .values(values)
+            .on_conflict_do_nothing()
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "misbehaviour_evidence_insert",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    fn transaction_events_insert_all(&mut self, events: &[TransactionEvent]) -> Result<(), StorageError> {
+        use crate::schema::transaction_events;
+
+        let values = events
+            .iter()
+            .map(|event| {
+                Ok((
+                    transaction_events::transaction_id.eq(serialize_hex(event.transaction_id)),
+                    transaction_events::block_id.eq(serialize_hex(event.block_id)),
+                    transaction_events::event_index.eq(event.index as i32),
+                    transaction_events::topic.eq(event.event.topic()),
+                    transaction_events::substate_id.eq(event.event.substate_id().map(|id| id.to_string())),
+                    transaction_events::event.eq(serialize_json(&event.event)?),
+                ))
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+
+        diesel::insert_into(transaction_events::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| SqliteStorageError::DieselError {
+                operation: "transaction_events_insert_all",
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
     fn lock_conflicts_insert_all<'a, I: IntoIterator<Item = (&'a TransactionId, &'a Vec<LockConflict>)>>(
         &mut self,
         block_id: &BlockId,
@@ -72,9 +72,9 @@ mod confirm_all_transitions {
         .unwrap();
         block1.insert(&mut tx).unwrap();
 
-        tx.transaction_pool_insert_new(atom1.id, atom1.decision, true).unwrap();
-        tx.transaction_pool_insert_new(atom2.id, atom2.decision, true).unwrap();
-        tx.transaction_pool_insert_new(atom3.id, atom3.decision, true).unwrap();
+        tx.transaction_pool_insert_new(atom1.id, atom1.decision, 0, true).unwrap();
+        tx.transaction_pool_insert_new(atom2.id, atom2.decision, 0, true).unwrap();
+        tx.transaction_pool_insert_new(atom3.id, atom3.decision, 0, true).unwrap();
         let block_id = *block1.id();
 
         let transactions = tx.transaction_pool_get_all().unwrap();
@@ -143,3 +143,134 @@ mod confirm_all_transitions {
         tx.rollback().unwrap();
     }
 }
+
+mod transaction_pool {
+    use tari_dan_common_types::NumPreshards;
+
+    use super::*;
+
+    #[test]
+    fn it_returns_new_transactions_in_order_of_priority_fee() {
+        let db = create_db();
+        // Need FK=off because otherwise we'd have to create transactions for each in the pool
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let zero_block = Block::zero_block(Default::default(), NumPreshards::P64);
+        zero_block.insert(&mut tx).unwrap();
+        zero_block.as_locked_block().set(&mut tx).unwrap();
+
+        let low = create_tx_atom();
+        let high = create_tx_atom();
+        let mid = create_tx_atom();
+        tx.transaction_pool_insert_new(low.id, low.decision, 10, true).unwrap();
+        tx.transaction_pool_insert_new(high.id, high.decision, 30, true).unwrap();
+        tx.transaction_pool_insert_new(mid.id, mid.decision, 20, true).unwrap();
+
+        let recs = tx.transaction_pool_get_many_ready(2, zero_block.id()).unwrap();
+        let ids = recs.iter().map(|rec| *rec.transaction_id()).collect::<Vec<_>>();
+        assert_eq!(ids, vec![high.id, mid.id]);
+        assert_eq!(recs[0].priority_fee(), 30);
+
+        tx.rollback().unwrap();
+    }
+}
+
+mod maintenance {
+    use super::*;
+
+    #[test]
+    fn it_vacuums_and_analyzes_the_database() {
+        let db = create_db();
+        let size = db.database_size().unwrap();
+        assert!(size > 0);
+
+        db.analyze().unwrap();
+        db.vacuum().unwrap();
+    }
+}
+
+mod substate_pruning {
+    use std::str::FromStr;
+
+    use tari_dan_common_types::{shard::Shard, VersionedSubstateId};
+    use tari_dan_storage::consensus_models::{BlockId, QcId, StateTransition, SubstateRecord};
+    use tari_engine_types::{non_fungible::NonFungibleContainer, substate::SubstateId};
+
+    use super::*;
+
+    fn create_substate<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        n: u8,
+        version: u32,
+        epoch: Epoch,
+    ) -> VersionedSubstateId {
+        let substate_id = SubstateId::from_str(&format!(
+            "nft_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff_u32_{}",
+            n
+        ))
+        .unwrap();
+        let id = VersionedSubstateId::new(substate_id.clone(), version);
+        SubstateRecord::new(
+            substate_id,
+            version,
+            NonFungibleContainer::no_data().into(),
+            Shard::zero(),
+            epoch,
+            NodeHeight(1),
+            BlockId::zero(),
+            create_tx_atom().id,
+            QcId::zero(),
+        )
+        .create(tx)
+        .unwrap();
+        id
+    }
+
+    fn destroy_substate<TTx: StateStoreWriteTransaction>(tx: &mut TTx, id: &VersionedSubstateId, epoch: Epoch) {
+        SubstateRecord::destroy(
+            tx,
+            id.clone(),
+            Shard::zero(),
+            epoch,
+            NodeHeight(1),
+            &QcId::zero(),
+            &create_tx_atom().id,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn it_prunes_superseded_versions_destroyed_before_the_epoch() {
+        let db = create_db();
+        db.foreign_keys_off().unwrap();
+        let mut tx = db.create_write_tx().unwrap();
+
+        let superseded = create_substate(&mut tx, 1, 0, Epoch(1));
+        destroy_substate(&mut tx, &superseded, Epoch(1));
+        let current = create_substate(&mut tx, 1, 1, Epoch(1));
+        // A destroyed substate without a later version is never pruned so that it cannot be recreated
+        let burnt = create_substate(&mut tx, 2, 0, Epoch(1));
+        destroy_substate(&mut tx, &burnt, Epoch(1));
+        let recent = create_substate(&mut tx, 3, 0, Epoch(5));
+        destroy_substate(&mut tx, &recent, Epoch(5));
+        let recent_current = create_substate(&mut tx, 3, 1, Epoch(5));
+
+        // Substates are referenced by state transitions until the transitions are pruned
+        let pruned = SubstateRecord::prune_destroyed_before(&mut tx, Epoch(3), 10).unwrap();
+        assert_eq!(pruned.num_substates, 0);
+
+        let num_transitions = StateTransition::prune_before(&mut tx, Epoch(3)).unwrap();
+        assert_eq!(num_transitions, 5);
+        let pruned = SubstateRecord::prune_destroyed_before(&mut tx, Epoch(3), 10).unwrap();
+        assert_eq!(pruned.num_substates, 1);
+        assert!(pruned.num_bytes > 0);
+
+        assert!(!SubstateRecord::exists(&*tx, &superseded).unwrap());
+        for id in [&current, &burnt, &recent, &recent_current] {
+            assert!(SubstateRecord::exists(&*tx, id).unwrap());
+        }
+
+        tx.rollback().unwrap();
+    }
+}
@@ -2,7 +2,8 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use tari_dan_common_types::VersionedSubstateId;
-use tari_jellyfish::{jmt_node_hash, LeafKey, TreeHash};
+use tari_engine_types::substate_proof::substate_leaf_key;
+use tari_jellyfish::{LeafKey, TreeHash};
 
 pub trait DbKeyMapper<T> {
     fn map_to_leaf_key(id: &T) -> LeafKey;
@@ -12,8 +13,7 @@ pub struct SpreadPrefixKeyMapper;
 
 impl DbKeyMapper<VersionedSubstateId> for SpreadPrefixKeyMapper {
     fn map_to_leaf_key(id: &VersionedSubstateId) -> LeafKey {
-        let hash = jmt_node_hash(id);
-        LeafKey::new(hash)
+        substate_leaf_key(id.substate_id(), id.version())
     }
 }
 
@@ -4,8 +4,21 @@
 
 use std::collections::{BTreeSet, HashSet};
 
+use tari_common_types::types::FixedHash;
+use tari_engine_types::{
+    fee_claim::FeeClaim,
+    substate::{hash_substate, SubstateValue},
+    substate_proof::SubstateInclusionProof,
+};
 use tari_jellyfish::{StaleTreeNode, Version, SPARSE_MERKLE_PLACEHOLDER_HASH};
-use tari_state_tree::memory_store::MemoryTreeStore;
+use tari_state_tree::{
+    compute_merkle_root_for_hashes,
+    compute_proof_for_hashes,
+    memory_store::MemoryTreeStore,
+    SpreadPrefixStateTree,
+    SubstateTreeChange,
+};
+use tari_template_lib::models::Amount;
 
 use crate::support::{change, hash_value_from_seed, make_value, HashTreeTester};
 mod support;
@@ -230,3 +243,53 @@ fn proofs() {
     let hash = hash_value_from_seed(50);
     proof.verify_inclusion(&root_hash, &key, &hash).unwrap_err();
 }
+
+#[test]
+fn substate_inclusion_proof() {
+    let id = make_value(1);
+    let value = SubstateValue::FeeClaim(FeeClaim {
+        epoch: 1,
+        validator_public_key: Default::default(),
+        amount: Amount::new(100),
+    });
+    let value_hash = hash_substate(&value, id.version());
+
+    let mut store = MemoryTreeStore::new();
+    let mut tree = SpreadPrefixStateTree::new(&mut store);
+    let shard_state_root = tree
+        .put_substate_changes(None, 1, vec![
+            SubstateTreeChange::Up { id: id.clone(), value_hash },
+            change(2, Some(40)),
+        ])
+        .unwrap();
+    let (_, proof_value, shard_proof) = tree.get_proof(1, &id).unwrap();
+    assert!(proof_value.is_some());
+
+    let shard_state_roots = vec![hash_value_from_seed(1), shard_state_root, hash_value_from_seed(2)];
+    let state_merkle_root = compute_merkle_root_for_hashes(shard_state_roots.iter().copied().peekable()).unwrap();
+    let (_, state_root_proof) = compute_proof_for_hashes(shard_state_roots.into_iter(), shard_state_root).unwrap();
+    let state_merkle_root = FixedHash::new(state_merkle_root.into_array());
+
+    let proof = SubstateInclusionProof::new(shard_proof, shard_state_root, state_root_proof);
+    proof
+        .verify(id.substate_id(), id.version(), &value, &state_merkle_root)
+        .unwrap();
+
+    // Fail to verify a different value
+    let other_value = SubstateValue::FeeClaim(FeeClaim {
+        epoch: 1,
+        validator_public_key: Default::default(),
+        amount: Amount::new(101),
+    });
+    proof
+        .verify(id.substate_id(), id.version(), &other_value, &state_merkle_root)
+        .unwrap_err();
+    // Fail to verify a different version
+    proof
+        .verify(id.substate_id(), id.version() + 1, &value, &state_merkle_root)
+        .unwrap_err();
+    // Fail to verify against a different state root
+    proof
+        .verify(id.substate_id(), id.version(), &value, &FixedHash::zero())
+        .unwrap_err();
+}
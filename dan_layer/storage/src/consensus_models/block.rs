@@ -44,6 +44,7 @@ use super::{
     HighQc,
     MintConfidentialOutputAtom,
     PendingShardStateTreeDiff,
+    ProposalEquivocationAtom,
     QuorumCertificate,
     SubstateChange,
     SubstateDestroyedProof,
@@ -317,6 +318,10 @@ impl Block {
         self.commands.iter().filter_map(|c| c.evict_node())
     }
 
+    pub fn all_proposal_equivocations(&self) -> impl Iterator<Item = &ProposalEquivocationAtom> + '_ {
+        self.commands.iter().filter_map(|c| c.proposal_equivocation())
+    }
+
     pub fn all_confidential_output_mints(&self) -> impl Iterator<Item = &MintConfidentialOutputAtom> + '_ {
         self.commands.iter().filter_map(|c| c.mint_confidential_output())
     }
@@ -527,6 +532,15 @@ impl Block {
         )
     }
 
+    /// Returns the blocks that include the transaction, ordered by epoch and height
+    pub fn get_all_by_transaction<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.blocks_get_all_by_transaction(transaction_id, limit)
+    }
+
     pub fn get_last_n_in_epoch<TTx: StateStoreReadTransaction>(
         tx: &TTx,
         n: usize,
@@ -1202,6 +1216,7 @@ where
     tx.transaction_executions_remove_any_by_block_id(block_id)?;
     tx.foreign_proposals_clear_proposed_in(block_id)?;
     tx.burnt_utxos_clear_proposed_block(block_id)?;
+    tx.proposal_equivocations_clear_proposed_block(block_id)?;
     tx.lock_conflicts_remove_by_block_id(block_id)?;
 
     Block::delete_record(tx, block_id)?;
@@ -8,8 +8,9 @@ use std::{
 
 use borsh::BorshSerialize;
 use serde::{Deserialize, Serialize};
+use strum_macros::AsRefStr;
 use tari_common_types::types::{FixedHash, PublicKey};
-use tari_dan_common_types::{hashing::command_hasher, Epoch, ShardGroup};
+use tari_dan_common_types::{hashing::command_hasher, Epoch, NodeHeight, ShardGroup};
 use tari_template_lib::models::UnclaimedConfidentialOutputAddress;
 use tari_transaction::TransactionId;
 
@@ -20,6 +21,7 @@ use super::{
     ForeignProposalAtom,
     LeaderFee,
     MintConfidentialOutputAtom,
+    ProposalEquivocationAtom,
     TransactionRecord,
 };
 use crate::{
@@ -85,7 +87,7 @@ impl Display for TransactionAtom {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, AsRefStr)]
 #[cfg_attr(
     feature = "ts",
     derive(ts_rs::TS),
@@ -114,6 +116,8 @@ pub enum Command {
     ForeignProposal(ForeignProposalAtom),
     MintConfidentialOutput(MintConfidentialOutputAtom),
     EvictNode(EvictNodeAtom),
+    /// Evidence that a leader proposed conflicting blocks at the same height
+    ProposalEquivocation(ProposalEquivocationAtom),
     EndEpoch,
 }
 
@@ -121,7 +125,8 @@ pub enum Command {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum CommandOrdering<'a> {
     EvictNode,
-    // EvictNode,
+    /// Ordered by proposer, epoch and height. There is at most one equivocation per proposer and height.
+    ProposalEquivocation(&'a PublicKey, Epoch, NodeHeight),
     /// Foreign proposals should come first in the block so that they are processed before commands
     ForeignProposal(ShardGroup, &'a BlockId),
     MintConfidentialOutput(&'a UnclaimedConfidentialOutputAddress),
@@ -143,6 +148,7 @@ impl Command {
             Command::ForeignProposal(_) |
             Command::MintConfidentialOutput(_) |
             Command::EvictNode(_) |
+            Command::ProposalEquivocation(_) |
             Command::EndEpoch => None,
         }
     }
@@ -163,6 +169,9 @@ impl Command {
             },
             Command::MintConfidentialOutput(mint) => CommandOrdering::MintConfidentialOutput(&mint.commitment),
             Command::EvictNode(_) => CommandOrdering::EvictNode,
+            Command::ProposalEquivocation(atom) => {
+                CommandOrdering::ProposalEquivocation(atom.proposed_by(), atom.epoch(), atom.height())
+            },
             Command::EndEpoch => CommandOrdering::EndEpoch,
         }
     }
@@ -220,6 +229,13 @@ impl Command {
         }
     }
 
+    pub fn proposal_equivocation(&self) -> Option<&ProposalEquivocationAtom> {
+        match self {
+            Command::ProposalEquivocation(atom) => Some(atom),
+            _ => None,
+        }
+    }
+
     pub fn mint_confidential_output(&self) -> Option<&MintConfidentialOutputAtom> {
         match self {
             Command::MintConfidentialOutput(mint) => Some(mint),
@@ -298,6 +314,7 @@ impl Display for Command {
             Command::ForeignProposal(fp) => write!(f, "ForeignProposal {}", fp.block_id),
             Command::MintConfidentialOutput(mint) => write!(f, "MintConfidentialOutput({})", mint.commitment),
             Command::EvictNode(atom) => write!(f, "EvictNode({atom})"),
+            Command::ProposalEquivocation(atom) => write!(f, "ProposalEquivocation({atom})"),
             Command::EndEpoch => write!(f, "EndEpoch"),
         }
     }
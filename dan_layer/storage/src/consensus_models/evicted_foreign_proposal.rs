@@ -0,0 +1,83 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use tari_dan_common_types::Epoch;
+
+use crate::{
+    consensus_models::{Block, BlockPledge, ForeignProposal, QuorumCertificate},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// A foreign proposal for a future epoch that was evicted from the in-memory buffer because the buffer was full. It is
+/// loaded back into the buffer once the local node reaches the epoch of the proposal.
+#[derive(Debug, Clone)]
+pub struct EvictedForeignProposal<TAddr> {
+    from: TAddr,
+    proposal: ForeignProposal,
+}
+
+impl<TAddr> EvictedForeignProposal<TAddr> {
+    pub fn new(from: TAddr, proposal: ForeignProposal) -> Self {
+        Self { from, proposal }
+    }
+
+    pub fn from(&self) -> &TAddr {
+        &self.from
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.proposal.justify_qc.epoch()
+    }
+
+    pub fn block(&self) -> &Block {
+        &self.proposal.block
+    }
+
+    pub fn block_pledge(&self) -> &BlockPledge {
+        &self.proposal.block_pledge
+    }
+
+    pub fn justify_qc(&self) -> &QuorumCertificate {
+        &self.proposal.justify_qc
+    }
+
+    pub fn into_parts(self) -> (TAddr, ForeignProposal) {
+        (self.from, self.proposal)
+    }
+}
+
+impl<TAddr> EvictedForeignProposal<TAddr> {
+    /// Stores the evicted proposal. A proposal that has already been stored is ignored.
+    pub fn insert<TTx: StateStoreWriteTransaction<Addr = TAddr>>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.evicted_foreign_proposals_insert(self)
+    }
+
+    /// Removes and returns at most `limit` stored proposals for `epoch` or before, ordered by epoch and height
+    pub fn remove_ready<TTx: StateStoreWriteTransaction<Addr = TAddr>>(
+        tx: &mut TTx,
+        epoch: Epoch,
+        limit: usize,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.evicted_foreign_proposals_remove_up_to_epoch(epoch, limit)
+    }
+
+    pub fn count<TTx: StateStoreReadTransaction<Addr = TAddr>>(tx: &TTx) -> Result<u64, StorageError> {
+        tx.evicted_foreign_proposals_count()
+    }
+}
+
+impl<TAddr: Display> Display for EvictedForeignProposal<TAddr> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "EvictedForeignProposal(from: {}, block: {}, epoch: {})",
+            self.from,
+            self.block(),
+            self.epoch()
+        )
+    }
+}
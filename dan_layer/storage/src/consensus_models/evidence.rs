@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{
     borsh::indexmap as indexmap_borsh,
     committee::CommitteeInfo,
+    optional::Optional,
     NumPreshards,
     ShardGroup,
     SubstateAddress,
@@ -18,7 +19,11 @@ use tari_dan_common_types::{
 };
 use tari_engine_types::serde_with;
 
-use crate::consensus_models::{QcId, VersionedSubstateIdLockIntent};
+use crate::{
+    consensus_models::{QcId, QuorumCertificate, VersionedSubstateIdLockIntent},
+    StateStoreReadTransaction,
+    StorageError,
+};
 
 const LOG_TARGET: &str = "tari::dan::consensus_models::evidence";
 
@@ -177,6 +182,74 @@ impl Evidence {
         self.evidence.len()
     }
 
+    /// Checks that the evidence is well-formed: every shard group is within the preshard range and does not overlap
+    /// another shard group, every substate belongs to the shard group that it is listed under and every prepare/accept
+    /// QC is known and was certified by the shard group that it is listed under. QCs that have been received but are
+    /// not yet stored (e.g. the justify QC of the block being validated) are provided in `unsaved_qcs`.
+    pub fn validate<TTx: StateStoreReadTransaction>(
+        &self,
+        tx: &TTx,
+        num_preshards: NumPreshards,
+        unsaved_qcs: &[&QuorumCertificate],
+    ) -> Result<(), EvidenceValidationError> {
+        self.validate_shard_groups(num_preshards)?;
+        for (shard_group, evidence) in &self.evidence {
+            for qc_id in evidence.prepare_qc.iter().chain(evidence.accept_qc.iter()) {
+                let qc_shard_group = match unsaved_qcs.iter().find(|qc| qc.id() == qc_id) {
+                    Some(qc) => qc.shard_group(),
+                    None => QuorumCertificate::get(tx, qc_id)
+                        .optional()?
+                        .ok_or(EvidenceValidationError::UnknownQc {
+                            shard_group: *shard_group,
+                            qc_id: *qc_id,
+                        })?
+                        .shard_group(),
+                };
+                if qc_shard_group != *shard_group {
+                    return Err(EvidenceValidationError::QcShardGroupMismatch {
+                        shard_group: *shard_group,
+                        qc_id: *qc_id,
+                        qc_shard_group,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_shard_groups(&self, num_preshards: NumPreshards) -> Result<(), EvidenceValidationError> {
+        for (i, (shard_group, evidence)) in self.evidence.iter().enumerate() {
+            if shard_group.start() > shard_group.end() || shard_group.end().as_u32() >= num_preshards.as_u32() {
+                return Err(EvidenceValidationError::InvalidShardGroup {
+                    shard_group: *shard_group,
+                    num_preshards,
+                });
+            }
+            if let Some(other) = self
+                .evidence
+                .keys()
+                .skip(i + 1)
+                .find(|other| shard_group.overlaps_shard_group(other))
+            {
+                return Err(EvidenceValidationError::OverlappingShardGroups {
+                    shard_group: *shard_group,
+                    other: *other,
+                });
+            }
+            if let Some(address) = evidence
+                .substates
+                .keys()
+                .find(|address| !shard_group.contains(&address.to_shard(num_preshards)))
+            {
+                return Err(EvidenceValidationError::SubstateNotInShardGroup {
+                    shard_group: *shard_group,
+                    address: *address,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Add or update shard groups, substates and locks into Evidence. Existing prepare/accept QC IDs are not changed.
     pub fn update(&mut self, other: &Evidence) -> &mut Self {
         for (sg, evidence) in other.iter() {
@@ -228,6 +301,19 @@ pub struct ShardGroupEvidence {
 }
 
 impl ShardGroupEvidence {
+    /// Creates shard group evidence from its parts. The order of the substates is preserved.
+    pub fn new<I: IntoIterator<Item = (SubstateAddress, SubstateLockType)>>(
+        substates: I,
+        prepare_qc: Option<QcId>,
+        accept_qc: Option<QcId>,
+    ) -> Self {
+        Self {
+            substates: substates.into_iter().collect(),
+            prepare_qc,
+            accept_qc,
+        }
+    }
+
     pub fn insert(&mut self, address: SubstateAddress, lock: SubstateLockType) -> &mut Self {
         self.substates.insert_sorted(address, lock);
         self
@@ -247,6 +333,14 @@ impl ShardGroupEvidence {
         &self.substates
     }
 
+    pub fn prepare_qc(&self) -> Option<&QcId> {
+        self.prepare_qc.as_ref()
+    }
+
+    pub fn accept_qc(&self) -> Option<&QcId> {
+        self.accept_qc.as_ref()
+    }
+
     pub fn sort_substates(&mut self) {
         self.substates.sort_keys();
     }
@@ -280,6 +374,32 @@ impl Display for ShardGroupEvidence {
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum EvidenceValidationError {
+    #[error("Shard group {shard_group} is not valid for {num_preshards} preshards")]
+    InvalidShardGroup {
+        shard_group: ShardGroup,
+        num_preshards: NumPreshards,
+    },
+    #[error("Shard group {shard_group} overlaps shard group {other}")]
+    OverlappingShardGroups { shard_group: ShardGroup, other: ShardGroup },
+    #[error("Substate {address} is not in shard group {shard_group}")]
+    SubstateNotInShardGroup {
+        shard_group: ShardGroup,
+        address: SubstateAddress,
+    },
+    #[error("QC {qc_id} in the evidence for shard group {shard_group} is not known")]
+    UnknownQc { shard_group: ShardGroup, qc_id: QcId },
+    #[error("QC {qc_id} in the evidence for shard group {shard_group} was certified by shard group {qc_shard_group}")]
+    QcShardGroupMismatch {
+        shard_group: ShardGroup,
+        qc_id: QcId,
+        qc_shard_group: ShardGroup,
+    },
+    #[error("Storage error: {0}")]
+    StorageError(#[from] StorageError),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +464,41 @@ mod tests {
             SubstateLockType::Output
         );
     }
+
+    #[test]
+    fn it_validates_shard_groups_and_substates() {
+        let num_preshards = NumPreshards::P4;
+        let address = SubstateAddress::zero();
+        let shard_group = address.to_shard_group(num_preshards, 2);
+
+        let mut evidence = Evidence::empty();
+        evidence
+            .add_shard_group(shard_group)
+            .insert(address, SubstateLockType::Write);
+        evidence.validate_shard_groups(num_preshards).unwrap();
+
+        let mut evidence = Evidence::empty();
+        evidence
+            .add_shard_group(ShardGroup::new(2, 3))
+            .insert(address, SubstateLockType::Write);
+        assert!(matches!(
+            evidence.validate_shard_groups(num_preshards),
+            Err(EvidenceValidationError::SubstateNotInShardGroup { .. })
+        ));
+
+        let mut evidence = Evidence::empty();
+        evidence.add_shard_group(ShardGroup::new(0, 1));
+        evidence.add_shard_group(ShardGroup::new(1, 2));
+        assert!(matches!(
+            evidence.validate_shard_groups(num_preshards),
+            Err(EvidenceValidationError::OverlappingShardGroups { .. })
+        ));
+
+        let mut evidence = Evidence::empty();
+        evidence.add_shard_group(ShardGroup::new(2, 4));
+        assert!(matches!(
+            evidence.validate_shard_groups(num_preshards),
+            Err(EvidenceValidationError::InvalidShardGroup { .. })
+        ));
+    }
 }
@@ -11,6 +11,7 @@ use std::{
 use borsh::BorshSerialize;
 use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{Epoch, ShardGroup};
+use tari_transaction::TransactionId;
 
 use super::{Block, BlockId, BlockPledge, QuorumCertificate};
 use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
@@ -88,6 +89,15 @@ impl ForeignProposal {
         tx.foreign_proposals_get_any(block_ids)
     }
 
+    /// Returns the foreign proposals that include the transaction, ordered by epoch and height
+    pub fn get_all_by_transaction<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.foreign_proposals_get_all_by_transaction(transaction_id, limit)
+    }
+
     pub fn exists<TTx: StateStoreReadTransaction>(&self, tx: &TTx) -> Result<bool, StorageError> {
         Self::record_exists(tx, self.block.id())
     }
@@ -1,15 +1,29 @@
 //    Copyright 2023 The Tari Project
 //    SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::RangeInclusive};
 
-use tari_dan_common_types::{optional::Optional, shard::Shard, ShardGroup};
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{optional::Optional, shard::Shard, Epoch, ShardGroup};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
 
 use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
 
 #[derive(Debug, Clone)]
 pub struct ForeignReceiveCounters {
     pub counters: HashMap<Shard, u64>,
+    /// The highest foreign index received from each foreign shard
+    pub last_received: HashMap<Shard, ReceivedForeignIndex>,
+}
+
+/// The foreign index of a proposal received from a foreign shard. Foreign indexes are sequential per epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ReceivedForeignIndex {
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub index: u64,
 }
 
 impl Default for ForeignReceiveCounters {
@@ -22,6 +36,7 @@ impl ForeignReceiveCounters {
     pub fn new() -> Self {
         Self {
             counters: HashMap::new(),
+            last_received: HashMap::new(),
         }
     }
 
@@ -35,6 +50,45 @@ impl ForeignReceiveCounters {
     pub fn get_count(&self, shard: &Shard) -> u64 {
         self.counters.get(shard).copied().unwrap_or_default()
     }
+
+    /// Returns the last foreign index received from the foreign shard in the given epoch, or 0 if none has been
+    /// received.
+    pub fn get_last_received(&self, shard: &Shard, epoch: Epoch) -> u64 {
+        self.last_received
+            .get(shard)
+            .filter(|last| last.epoch == epoch)
+            .map(|last| last.index)
+            .unwrap_or_default()
+    }
+
+    /// Records the foreign index of a proposal received from the foreign shard group. Returns the range of foreign
+    /// indexes that were skipped if the index is not the next expected index.
+    pub fn record_received(
+        &mut self,
+        foreign_shard_group: ShardGroup,
+        epoch: Epoch,
+        index: u64,
+    ) -> Option<RangeInclusive<u64>> {
+        let last_index = foreign_shard_group
+            .shard_iter()
+            .map(|shard| self.get_last_received(&shard, epoch))
+            .max()
+            .unwrap_or_default();
+        // A lower index is a late proposal, which may fill a gap that was previously recorded
+        if index <= last_index {
+            return None;
+        }
+
+        for shard in foreign_shard_group.shard_iter() {
+            self.last_received.insert(shard, ReceivedForeignIndex { epoch, index });
+        }
+
+        if index == last_index + 1 {
+            None
+        } else {
+            Some(last_index + 1..=index - 1)
+        }
+    }
 }
 
 impl ForeignReceiveCounters {
@@ -47,3 +101,25 @@ impl ForeignReceiveCounters {
         Ok(tx.foreign_receive_counters_get().optional()?.unwrap_or_default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_returns_skipped_foreign_indexes() {
+        let mut counters = ForeignReceiveCounters::new();
+        let shard_group = ShardGroup::new(0, 1);
+
+        assert_eq!(counters.record_received(shard_group, Epoch(1), 1), None);
+        assert_eq!(counters.record_received(shard_group, Epoch(1), 4), Some(2..=3));
+        // Late proposals do not move the last received index back
+        assert_eq!(counters.record_received(shard_group, Epoch(1), 2), None);
+        assert_eq!(counters.get_last_received(&Shard::from(1), Epoch(1)), 4);
+        assert_eq!(counters.record_received(shard_group, Epoch(1), 5), None);
+
+        // Foreign indexes restart in each epoch
+        assert_eq!(counters.record_received(shard_group, Epoch(2), 1), None);
+        assert_eq!(counters.record_received(shard_group, Epoch(2), 3), Some(2..=2));
+    }
+}
@@ -0,0 +1,99 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{Epoch, ShardGroup};
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
+
+/// A range of foreign indexes that were skipped by the proposals received from a foreign shard group i.e. foreign
+/// proposals that this node has missed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ForeignReceiveGap {
+    pub foreign_shard_group: ShardGroup,
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub from_index: u64,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub to_index: u64,
+    /// The number of missing proposals that have since been received
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_received: u64,
+    /// The time that the gap was detected. None if the gap has not been stored.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub detected_at: Option<time::PrimitiveDateTime>,
+    /// The time that the last missing proposal was received
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub resolved_at: Option<time::PrimitiveDateTime>,
+}
+
+impl ForeignReceiveGap {
+    pub fn new(foreign_shard_group: ShardGroup, epoch: Epoch, missing: RangeInclusive<u64>) -> Self {
+        Self {
+            foreign_shard_group,
+            epoch,
+            from_index: *missing.start(),
+            to_index: *missing.end(),
+            num_received: 0,
+            detected_at: None,
+            resolved_at: None,
+        }
+    }
+
+    pub fn num_missing(&self) -> u64 {
+        (self.to_index - self.from_index + 1).saturating_sub(self.num_received)
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.resolved_at.is_some()
+    }
+}
+
+impl ForeignReceiveGap {
+    pub fn insert<TTx: StateStoreWriteTransaction>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.foreign_receive_gaps_insert(self)
+    }
+
+    /// Records that the proposal with the given foreign index was received. If it was missing, the gap that contains
+    /// it is resolved once all of its proposals have been received.
+    pub fn record_received<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        foreign_shard_group: ShardGroup,
+        epoch: Epoch,
+        index: u64,
+    ) -> Result<(), StorageError> {
+        tx.foreign_receive_gaps_record_received(foreign_shard_group, epoch, index)
+    }
+
+    /// Returns recorded gaps, most recent first. Resolved gaps are only returned if `include_resolved` is true.
+    pub fn get_all<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        include_resolved: bool,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.foreign_receive_gaps_get_all(include_resolved, limit, offset)
+    }
+}
+
+impl Display for ForeignReceiveGap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} foreign indexes {}-{} in {} ({} missing)",
+            self.foreign_shard_group,
+            self.from_index,
+            self.to_index,
+            self.epoch,
+            self.num_missing()
+        )
+    }
+}
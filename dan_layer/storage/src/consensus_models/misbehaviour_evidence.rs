@@ -0,0 +1,176 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::Epoch;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{
+        BlockHeader,
+        BlockId,
+        ProposalEquivocationAtom,
+        QuorumCertificate,
+        QuorumDecision,
+        ValidatorSignature,
+        Vote,
+    },
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, AsRefStr, EnumString)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum MisbehaviourKind {
+    EquivocatingProposal,
+    DoubleVote,
+    InvalidQuorumCertificate,
+}
+
+impl Display for MisbehaviourKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+/// A vote exactly as it was signed by the voter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct SignedVote {
+    pub epoch: Epoch,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    pub decision: QuorumDecision,
+    pub signature: ValidatorSignature,
+}
+
+impl From<Vote> for SignedVote {
+    fn from(vote: Vote) -> Self {
+        Self {
+            epoch: vote.epoch,
+            block_id: vote.block_id,
+            decision: vote.decision,
+            signature: vote.signature,
+        }
+    }
+}
+
+/// The signed messages that show that a validator misbehaved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum Misbehaviour {
+    /// The validator signed two different proposals for the same epoch, shard group and height
+    EquivocatingProposal(ProposalEquivocationAtom),
+    /// The validator signed votes with different decisions for the same block
+    DoubleVote { first: SignedVote, second: SignedVote },
+    /// The validator signed a proposal that is justified by a quorum certificate that failed validation
+    InvalidQuorumCertificate {
+        block_header: BlockHeader,
+        justify: QuorumCertificate,
+        reason: String,
+    },
+}
+
+impl Misbehaviour {
+    pub fn kind(&self) -> MisbehaviourKind {
+        match self {
+            Self::EquivocatingProposal(_) => MisbehaviourKind::EquivocatingProposal,
+            Self::DoubleVote { .. } => MisbehaviourKind::DoubleVote,
+            Self::InvalidQuorumCertificate { .. } => MisbehaviourKind::InvalidQuorumCertificate,
+        }
+    }
+
+    pub fn offender(&self) -> &PublicKey {
+        match self {
+            Self::EquivocatingProposal(atom) => atom.proposed_by(),
+            Self::DoubleVote { first, .. } => first.signature.public_key(),
+            Self::InvalidQuorumCertificate { block_header, .. } => block_header.proposed_by(),
+        }
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        match self {
+            Self::EquivocatingProposal(atom) => atom.epoch(),
+            Self::DoubleVote { first, .. } => first.epoch,
+            Self::InvalidQuorumCertificate { block_header, .. } => block_header.epoch(),
+        }
+    }
+
+    /// The block that the misbehaviour relates to. Evidence is recorded once per offender, kind and block.
+    pub fn block_id(&self) -> &BlockId {
+        match self {
+            Self::EquivocatingProposal(atom) => atom.first().id(),
+            Self::DoubleVote { first, .. } => &first.block_id,
+            Self::InvalidQuorumCertificate { block_header, .. } => block_header.id(),
+        }
+    }
+}
+
+impl Display for Misbehaviour {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} by {} in {} (block {})",
+            self.kind(),
+            self.offender(),
+            self.epoch(),
+            self.block_id()
+        )
+    }
+}
+
+/// Evidence of byzantine behaviour by a validator that was observed by this node. The evidence is kept locally so that
+/// it can be used for slashing or governance actions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct MisbehaviourEvidence {
+    pub misbehaviour: Misbehaviour,
+    /// The time that the evidence was first recorded. None if the evidence has not been stored.
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub recorded_at: Option<time::PrimitiveDateTime>,
+}
+
+impl MisbehaviourEvidence {
+    pub fn new(misbehaviour: Misbehaviour) -> Self {
+        Self {
+            misbehaviour,
+            recorded_at: None,
+        }
+    }
+
+    pub fn kind(&self) -> MisbehaviourKind {
+        self.misbehaviour.kind()
+    }
+
+    pub fn offender(&self) -> &PublicKey {
+        self.misbehaviour.offender()
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.misbehaviour.epoch()
+    }
+}
+
+impl MisbehaviourEvidence {
+    /// Inserts the evidence if evidence of the same kind has not already been recorded for the offender and block.
+    pub fn insert<TTx: StateStoreWriteTransaction>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.misbehaviour_evidence_insert(self)
+    }
+
+    /// Returns recorded evidence, most recent first, optionally filtered by epoch and offender.
+    pub fn get_all<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        epoch: Option<Epoch>,
+        offender: Option<&PublicKey>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.misbehaviour_evidence_get_all(epoch, offender, limit, offset)
+    }
+}
@@ -8,11 +8,13 @@ mod block_pledges;
 mod burnt_utxo;
 mod command;
 mod epoch_checkpoint;
+mod evicted_foreign_proposal;
 mod evidence;
 mod executed_transaction;
 mod foreign_parked_proposal;
 mod foreign_proposal;
 mod foreign_receive_counters;
+mod foreign_receive_gap;
 mod foreign_send_counters;
 mod high_qc;
 mod last_executed;
@@ -24,9 +26,12 @@ mod leaf_block;
 mod lock_confict;
 mod lock_intent;
 mod locked_block;
+mod misbehaviour_evidence;
 mod no_vote;
+mod proposal_equivocation;
 mod quorum;
 mod quorum_certificate;
+mod state_snapshot;
 mod state_transition;
 mod state_tree_diff;
 mod substate;
@@ -34,9 +39,11 @@ mod substate_change;
 mod substate_lock;
 mod transaction;
 mod transaction_decision;
+mod transaction_event;
 mod transaction_execution;
 mod transaction_pool;
 mod transaction_pool_status_update;
+mod transaction_trace;
 mod validated_block;
 mod validator_stats;
 mod vote;
@@ -49,11 +56,13 @@ pub use block_pledges::*;
 pub use burnt_utxo::*;
 pub use command::*;
 pub use epoch_checkpoint::*;
+pub use evicted_foreign_proposal::*;
 pub use evidence::*;
 pub use executed_transaction::*;
 pub use foreign_parked_proposal::*;
 pub use foreign_proposal::*;
 pub use foreign_receive_counters::*;
+pub use foreign_receive_gap::*;
 pub use foreign_send_counters::*;
 pub use high_qc::*;
 pub use last_executed::*;
@@ -65,9 +74,12 @@ pub use leaf_block::*;
 pub use lock_confict::*;
 pub use lock_intent::*;
 pub use locked_block::*;
+pub use misbehaviour_evidence::*;
 pub use no_vote::*;
+pub use proposal_equivocation::*;
 pub use quorum::*;
 pub use quorum_certificate::*;
+pub use state_snapshot::*;
 pub use state_transition::*;
 pub use state_tree_diff::*;
 pub use substate::*;
@@ -75,9 +87,11 @@ pub use substate_change::*;
 pub use substate_lock::*;
 pub use transaction::*;
 pub use transaction_decision::*;
+pub use transaction_event::*;
 pub use transaction_execution::*;
 pub use transaction_pool::*;
 pub use transaction_pool_status_update::*;
+pub use transaction_trace::*;
 pub use validated_block::*;
 pub use validator_stats::*;
 pub use vote::*;
@@ -1,6 +1,8 @@
 //   Copyright 2024 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_dan_common_types::Epoch;
+
 use crate::consensus_models::{Decision, TransactionPoolStage};
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -14,6 +16,8 @@ pub enum NoVoteReason {
     },
     #[error("The transaction is not in the pool")]
     TransactionNotInPool,
+    #[error("The transaction is not valid until {min_epoch} but was proposed in {block_epoch}")]
+    TransactionNotYetActive { min_epoch: Epoch, block_epoch: Epoch },
     #[error("Decision disagreement. Local: {local:?}, Remote: {remote:?}")]
     DecisionDisagreement { local: Decision, remote: Decision },
     #[error("Fee disagreement")]
@@ -60,6 +64,10 @@ pub enum NoVoteReason {
     NodeAlreadyEvicted,
     #[error("Leader proposed to evict a node but it is not permitted to suspend more than f nodes")]
     CannotEvictNodeBelowQuorumThreshold,
+    #[error("Leader proposed invalid proposal equivocation evidence")]
+    InvalidProposalEquivocationEvidence,
+    #[error("Leader proposed proposal equivocation evidence that has already been committed")]
+    ProposalEquivocationAlreadyCommitted,
 }
 
 impl NoVoteReason {
@@ -68,6 +76,7 @@ impl NoVoteReason {
             Self::AlreadyVotedAtHeight => "ShouldNotVote",
             Self::StageDisagreement { .. } => "StageDisagreement",
             Self::TransactionNotInPool => "TransactionNotInPool",
+            Self::TransactionNotYetActive { .. } => "TransactionNotYetActive",
             Self::DecisionDisagreement { .. } => "DecisionDisagreement",
             Self::FeeDisagreement => "FeeDisagreement",
             Self::LeaderFeeDisagreement => "LeaderFeeDisagreement",
@@ -91,6 +100,8 @@ impl NoVoteReason {
             Self::NodeAlreadyEvicted => "NodeAlreadyEvicted",
             Self::ShouldNotEvictNode => "ShouldNotEvictNode",
             Self::CannotEvictNodeBelowQuorumThreshold => "CannotSuspendNodeBelowQuorumThreshold",
+            Self::InvalidProposalEquivocationEvidence => "InvalidProposalEquivocationEvidence",
+            Self::ProposalEquivocationAlreadyCommitted => "ProposalEquivocationAlreadyCommitted",
         }
     }
 }
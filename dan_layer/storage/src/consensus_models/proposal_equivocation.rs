@@ -0,0 +1,328 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    io::Write,
+};
+
+use borsh::BorshSerialize;
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::{Epoch, NodeHeight, ShardGroup};
+
+use crate::{
+    consensus_models::{BlockHeader, BlockId},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// Evidence that a leader signed two different blocks for the same epoch, shard group and height.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct ProposalEquivocationAtom {
+    first: BlockHeader,
+    second: BlockHeader,
+}
+
+impl ProposalEquivocationAtom {
+    /// Creates the evidence from two conflicting headers. The headers are ordered by block id so that the same
+    /// equivocation always results in the same atom regardless of the order in which the headers were received.
+    pub fn new(a: BlockHeader, b: BlockHeader) -> Self {
+        if a.id() <= b.id() {
+            Self { first: a, second: b }
+        } else {
+            Self { first: b, second: a }
+        }
+    }
+
+    pub fn first(&self) -> &BlockHeader {
+        &self.first
+    }
+
+    pub fn second(&self) -> &BlockHeader {
+        &self.second
+    }
+
+    pub fn proposed_by(&self) -> &PublicKey {
+        self.first.proposed_by()
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.first.epoch()
+    }
+
+    pub fn shard_group(&self) -> ShardGroup {
+        self.first.shard_group()
+    }
+
+    pub fn height(&self) -> NodeHeight {
+        self.first.height()
+    }
+
+    /// Checks that the evidence is self-consistent i.e. both headers are signed by the same proposer for the same
+    /// epoch, shard group and height, and are different blocks. This does not check that the proposer is a member of
+    /// the committee.
+    pub fn validate(&self) -> Result<(), ProposalEquivocationError> {
+        if self.first.id() >= self.second.id() {
+            return Err(ProposalEquivocationError::NotDistinctOrdered {
+                first: *self.first.id(),
+                second: *self.second.id(),
+            });
+        }
+        if self.first.proposed_by() != self.second.proposed_by() {
+            return Err(ProposalEquivocationError::ProposerMismatch);
+        }
+        if self.first.epoch() != self.second.epoch() ||
+            self.first.shard_group() != self.second.shard_group() ||
+            self.first.height() != self.second.height()
+        {
+            return Err(ProposalEquivocationError::PositionMismatch);
+        }
+
+        for header in [&self.first, &self.second] {
+            if header.is_dummy() {
+                return Err(ProposalEquivocationError::DummyBlock { block_id: *header.id() });
+            }
+            let calculated_id = header.calculate_id();
+            if calculated_id != *header.id() {
+                return Err(ProposalEquivocationError::BlockIdMismatch {
+                    block_id: *header.id(),
+                    calculated_id,
+                });
+            }
+            let signature = header
+                .signature()
+                .ok_or(ProposalEquivocationError::MissingSignature { block_id: *header.id() })?;
+            if !signature.verify(header.proposed_by(), header.id()) {
+                return Err(ProposalEquivocationError::InvalidSignature { block_id: *header.id() });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ProposalEquivocationAtom {
+    pub fn is_committed<TTx: StateStoreReadTransaction>(&self, tx: &TTx) -> Result<bool, StorageError> {
+        tx.proposal_equivocations_is_committed(self.epoch(), self.proposed_by(), self.height())
+    }
+
+    pub fn mark_as_committed<TTx: StateStoreWriteTransaction>(
+        &self,
+        tx: &mut TTx,
+        committed_in_block: &BlockId,
+    ) -> Result<(), StorageError> {
+        tx.proposal_equivocations_insert(self)?;
+        tx.proposal_equivocations_set_committed(self, committed_in_block)
+    }
+}
+
+impl PartialEq for ProposalEquivocationAtom {
+    fn eq(&self, other: &Self) -> bool {
+        self.first.id() == other.first.id() && self.second.id() == other.second.id()
+    }
+}
+
+impl Eq for ProposalEquivocationAtom {}
+
+impl BorshSerialize for ProposalEquivocationAtom {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        // The block ids commit to the contents of each header
+        BorshSerialize::serialize(self.first.id(), writer)?;
+        BorshSerialize::serialize(self.second.id(), writer)
+    }
+}
+
+impl Display for ProposalEquivocationAtom {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} proposed {} and {}",
+            self.proposed_by(),
+            self.epoch(),
+            self.height(),
+            self.first.id(),
+            self.second.id()
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProposalEquivocation {
+    pub atom: ProposalEquivocationAtom,
+    pub proposed_in_block: Option<BlockId>,
+    pub committed_in_block: Option<BlockId>,
+}
+
+impl ProposalEquivocation {
+    pub fn new(atom: ProposalEquivocationAtom) -> Self {
+        Self {
+            atom,
+            proposed_in_block: None,
+            committed_in_block: None,
+        }
+    }
+
+    pub fn to_atom(&self) -> ProposalEquivocationAtom {
+        self.atom.clone()
+    }
+}
+
+impl ProposalEquivocation {
+    /// Inserts the evidence if evidence for the same proposer, epoch and height has not already been recorded.
+    pub fn insert<TTx: StateStoreWriteTransaction>(&self, tx: &mut TTx) -> Result<(), StorageError> {
+        tx.proposal_equivocations_insert(&self.atom)
+    }
+
+    pub fn exists<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        height: NodeHeight,
+    ) -> Result<bool, StorageError> {
+        tx.proposal_equivocations_exists(epoch, proposed_by, height)
+    }
+
+    pub fn set_proposed_in_block<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        atom: &ProposalEquivocationAtom,
+        proposed_in_block: &BlockId,
+    ) -> Result<(), StorageError> {
+        tx.proposal_equivocations_insert(atom)?;
+        tx.proposal_equivocations_set_proposed_block(atom, proposed_in_block)
+    }
+
+    pub fn get_all_unproposed<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        block_id: &BlockId,
+        limit: usize,
+    ) -> Result<Vec<ProposalEquivocation>, StorageError> {
+        tx.proposal_equivocations_get_all_unproposed(block_id, limit)
+    }
+
+    /// Returns the proposers that have committed equivocation evidence against them in the given epoch.
+    pub fn get_committed_proposers<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        epoch: Epoch,
+    ) -> Result<Vec<PublicKey>, StorageError> {
+        tx.proposal_equivocations_get_committed_proposers(epoch)
+    }
+
+    pub fn is_committed_for_proposer<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+    ) -> Result<bool, StorageError> {
+        Ok(Self::get_committed_proposers(tx, epoch)?.contains(proposed_by))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProposalEquivocationError {
+    #[error("Equivocation evidence must contain two distinct blocks ordered by id (first: {first}, second: {second})")]
+    NotDistinctOrdered { first: BlockId, second: BlockId },
+    #[error("Equivocation evidence blocks were proposed by different validators")]
+    ProposerMismatch,
+    #[error("Equivocation evidence blocks are not for the same epoch, shard group and height")]
+    PositionMismatch,
+    #[error("Equivocation evidence block {block_id} is a dummy block")]
+    DummyBlock { block_id: BlockId },
+    #[error("Equivocation evidence block {block_id} does not match its calculated id {calculated_id}")]
+    BlockIdMismatch { block_id: BlockId, calculated_id: BlockId },
+    #[error("Equivocation evidence block {block_id} is not signed")]
+    MissingSignature { block_id: BlockId },
+    #[error("Equivocation evidence block {block_id} has an invalid signature")]
+    InvalidSignature { block_id: BlockId },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_common_types::types::{FixedHash, PrivateKey};
+    use tari_crypto::keys::{PublicKey as _, SecretKey};
+    use tari_dan_common_types::ExtraData;
+
+    use super::*;
+    use crate::consensus_models::{QcId, ValidatorSchnorrSignature};
+
+    fn create_signed_header(secret_key: &PrivateKey, height: u64, timestamp: u64) -> BlockHeader {
+        let mut header = BlockHeader::create(
+            Network::LocalNet,
+            BlockId::zero(),
+            QcId::zero(),
+            NodeHeight(height),
+            Epoch(1),
+            ShardGroup::new(0, 63),
+            PublicKey::from_secret_key(secret_key),
+            FixedHash::zero(),
+            &BTreeSet::new(),
+            0,
+            BTreeMap::new(),
+            None,
+            timestamp,
+            0,
+            FixedHash::zero(),
+            ExtraData::new(),
+        )
+        .unwrap();
+        let signature = ValidatorSchnorrSignature::sign(secret_key, header.id(), &mut OsRng).unwrap();
+        header.set_signature(signature);
+        header
+    }
+
+    #[test]
+    fn it_validates_conflicting_signed_headers() {
+        let secret_key = PrivateKey::random(&mut OsRng);
+        let a = create_signed_header(&secret_key, 10, 1);
+        let b = create_signed_header(&secret_key, 10, 2);
+
+        let atom = ProposalEquivocationAtom::new(a.clone(), b.clone());
+        atom.validate().unwrap();
+        assert_eq!(atom, ProposalEquivocationAtom::new(b, a));
+    }
+
+    #[test]
+    fn it_rejects_invalid_evidence() {
+        let secret_key = PrivateKey::random(&mut OsRng);
+        let a = create_signed_header(&secret_key, 10, 1);
+
+        let atom = ProposalEquivocationAtom::new(a.clone(), a.clone());
+        assert!(matches!(
+            atom.validate(),
+            Err(ProposalEquivocationError::NotDistinctOrdered { .. })
+        ));
+
+        let other = create_signed_header(&PrivateKey::random(&mut OsRng), 10, 2);
+        let atom = ProposalEquivocationAtom::new(a.clone(), other);
+        assert!(matches!(
+            atom.validate(),
+            Err(ProposalEquivocationError::ProposerMismatch)
+        ));
+
+        let higher = create_signed_header(&secret_key, 11, 2);
+        let atom = ProposalEquivocationAtom::new(a.clone(), higher);
+        assert!(matches!(
+            atom.validate(),
+            Err(ProposalEquivocationError::PositionMismatch)
+        ));
+
+        let mut forged = create_signed_header(&secret_key, 10, 2);
+        forged.set_signature(
+            ValidatorSchnorrSignature::sign(&PrivateKey::random(&mut OsRng), forged.id(), &mut OsRng).unwrap(),
+        );
+        let atom = ProposalEquivocationAtom::new(a, forged);
+        assert!(matches!(
+            atom.validate(),
+            Err(ProposalEquivocationError::InvalidSignature { .. })
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt::Display;
+
+use tari_dan_common_types::{shard::Shard, Epoch};
+use tari_state_tree::{TreeHash, Version};
+
+use crate::{
+    consensus_models::{StateTransitionId, SubstateData},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// A merkle-rooted snapshot of all live substates in a shard at the end of an epoch. A new validator node can load the
+/// snapshot instead of replaying every state transition since genesis, and then sync the state transitions that
+/// follow `last_transition_id`.
+#[derive(Debug, Clone)]
+pub struct StateSnapshot {
+    pub epoch: Epoch,
+    pub shard: Shard,
+    /// The state tree version of the shard at the time the snapshot was taken
+    pub state_version: Version,
+    /// The state tree root of the shard at the time the snapshot was taken
+    pub state_root: TreeHash,
+    /// The last state transition that is included in the snapshot
+    pub last_transition_id: StateTransitionId,
+    pub num_substates: u64,
+}
+
+impl StateSnapshot {
+    /// Persists a snapshot of the live substates in the shard. This must be called in the same transaction that
+    /// commits the epoch end block so that the snapshot matches the state root.
+    pub fn create<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        epoch: Epoch,
+        shard: Shard,
+        state_version: Version,
+        state_root: TreeHash,
+        last_transition_id: StateTransitionId,
+    ) -> Result<Self, StorageError> {
+        tx.state_snapshots_create(epoch, shard, state_version, state_root, last_transition_id)
+    }
+
+    pub fn get<TTx: StateStoreReadTransaction>(tx: &TTx, epoch: Epoch, shard: Shard) -> Result<Self, StorageError> {
+        tx.state_snapshots_get(epoch, shard)
+    }
+
+    /// Returns the most recent snapshot for the shard that was taken at or before `max_epoch`
+    pub fn get_latest<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        shard: Shard,
+        max_epoch: Epoch,
+    ) -> Result<Self, StorageError> {
+        tx.state_snapshots_get_latest(shard, max_epoch)
+    }
+
+    /// Returns up to `limit` substates of the snapshot, skipping the first `offset` substates. Substates are always
+    /// returned in the same order.
+    pub fn get_substates<TTx: StateStoreReadTransaction>(
+        &self,
+        tx: &TTx,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SubstateData>, StorageError> {
+        tx.state_snapshot_substates_get(self.epoch, self.shard, offset, limit)
+    }
+
+    /// Deletes all snapshots taken before the given epoch
+    pub fn delete_all_before<TTx: StateStoreWriteTransaction>(tx: &mut TTx, epoch: Epoch) -> Result<(), StorageError> {
+        tx.state_snapshots_delete_before(epoch)
+    }
+}
+
+impl Display for StateSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StateSnapshot: {} {}, v{}, root={}, last_transition={}, num_substates={}",
+            self.epoch, self.shard, self.state_version, self.state_root, self.last_transition_id, self.num_substates
+        )
+    }
+}
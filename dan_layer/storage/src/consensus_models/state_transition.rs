@@ -9,7 +9,7 @@ use std::{
 
 use tari_dan_common_types::{shard::Shard, Epoch};
 
-use crate::{consensus_models::SubstateUpdate, StateStoreReadTransaction, StorageError};
+use crate::{consensus_models::SubstateUpdate, StateStoreReadTransaction, StateStoreWriteTransaction, StorageError};
 
 #[derive(Debug, Clone)]
 pub struct StateTransition {
@@ -33,6 +33,23 @@ impl StateTransition {
     ) -> Result<StateTransitionId, StorageError> {
         tx.state_transitions_get_last_id(shard)
     }
+
+    /// Renumbers the state transitions of the shard so that the last transition has the sequence number `last_seq`.
+    /// This is used after a snapshot is loaded so that later state syncs resume from the snapshot's last transition.
+    pub fn rebase<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        shard: Shard,
+        last_seq: u64,
+    ) -> Result<(), StorageError> {
+        tx.state_transitions_rebase(shard, last_seq)
+    }
+
+    /// Deletes the state transitions that occurred before the given epoch, returning the number of deleted
+    /// transitions. The last transition of each shard is kept so that the sequence continues from it. Peers that
+    /// request state transitions that have been pruned must sync from a state snapshot instead.
+    pub fn prune_before<TTx: StateStoreWriteTransaction>(tx: &mut TTx, epoch: Epoch) -> Result<u64, StorageError> {
+        tx.state_transitions_prune_before(epoch)
+    }
 }
 
 impl Display for StateTransition {
@@ -328,6 +328,27 @@ impl SubstateRecord {
             destroyed_justify,
         )
     }
+
+    /// Deletes up to `limit` substate versions that were destroyed before the given epoch and have been superseded by a
+    /// later version. The last version of a substate is always kept, even if it is destroyed, so that it cannot be
+    /// recreated. Substates that are referenced by a remaining state transition are also kept so that the state
+    /// transitions can still be synced.
+    pub fn prune_destroyed_before<TTx: StateStoreWriteTransaction>(
+        tx: &mut TTx,
+        epoch: Epoch,
+        limit: usize,
+    ) -> Result<PrunedSubstates, StorageError> {
+        tx.substates_prune_destroyed_before(epoch, limit)
+    }
+}
+
+/// The result of pruning historical substate versions
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrunedSubstates {
+    /// The number of substate versions that were deleted
+    pub num_substates: u64,
+    /// The total size of the substate values that were deleted
+    pub num_bytes: u64,
 }
 
 #[derive(Debug, Clone)]
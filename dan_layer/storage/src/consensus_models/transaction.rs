@@ -190,6 +190,7 @@ impl TransactionRecord {
                     FinalizeResult::new_rejected(self.transaction.id().into_array().into(), reason.clone())
                 }),
                 execution_time,
+                substate_access_stats: None,
             }
         } else {
             // If there's no abort reason or execution result, return None here
@@ -228,6 +229,7 @@ impl TransactionRecord {
                         )
                     }),
                     execution_time,
+                    substate_access_stats: None,
                 })
             }
         })
@@ -345,6 +347,10 @@ impl TransactionRecord {
         tx.transactions_get_paginated(limit, offset, ordering)
     }
 
+    pub fn get_deferred<TTx: StateStoreReadTransaction>(tx: &TTx) -> Result<Vec<Self>, StorageError> {
+        tx.transactions_get_deferred()
+    }
+
     pub fn get_local_pledges<TTx: StateStoreReadTransaction>(&self, tx: &TTx) -> Result<SubstatePledges, StorageError> {
         let locked_values = tx.substate_locks_get_locked_substates_for_transaction(self.id())?;
         locked_values
@@ -56,7 +56,7 @@ impl From<&RejectReason> for AbortReason {
         match reject_reason {
             RejectReason::Unknown => Self::None,
             RejectReason::InvalidTransaction(_) => Self::InvalidTransaction,
-            RejectReason::ExecutionFailure(_) => Self::ExecutionFailure,
+            RejectReason::ExecutionFailure(_) | RejectReason::SandboxViolation(_) => Self::ExecutionFailure,
             RejectReason::OneOrMoreInputsNotFound(_) => Self::OneOrMoreInputsNotFound,
             RejectReason::FailedToLockInputs(_) => Self::LockInputsFailed,
             RejectReason::FailedToLockOutputs(_) => Self::LockOutputsFailed,
@@ -0,0 +1,80 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+use tari_engine_types::{events::Event, substate::SubstateId};
+use tari_transaction::TransactionId;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{BlockId, Decision, TransactionRecord},
+    StateStoreReadTransaction,
+    StateStoreWriteTransaction,
+    StorageError,
+};
+
+/// An event emitted by a committed transaction. Events are indexed by transaction, topic and the substate (typically a
+/// component) that emitted them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransactionEvent {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    /// The block in which the transaction was committed
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    /// The position of the event in the events emitted by the transaction
+    pub index: u32,
+    pub event: Event,
+}
+
+impl TransactionEvent {
+    /// Returns the events of a finalized transaction. Events are only returned if the transaction was committed and
+    /// its result was accepted in full, otherwise the events did not happen.
+    pub fn from_finalized_transaction(block_id: BlockId, transaction: &TransactionRecord) -> Vec<Self> {
+        if transaction.final_decision() != Some(Decision::Commit) {
+            return vec![];
+        }
+        let Some(result) = transaction.execution_result() else {
+            return vec![];
+        };
+        if !result.finalize.is_full_accept() {
+            return vec![];
+        }
+
+        result
+            .finalize
+            .events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| Self {
+                transaction_id: *transaction.id(),
+                block_id,
+                index: index as u32,
+                event: event.clone(),
+            })
+            .collect()
+    }
+}
+
+impl TransactionEvent {
+    pub fn insert_all<TTx: StateStoreWriteTransaction>(tx: &mut TTx, events: &[Self]) -> Result<(), StorageError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        tx.transaction_events_insert_all(events)
+    }
+
+    /// Returns committed events, most recent first, filtered by the filters that are set
+    pub fn get_all<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        transaction_id: Option<&TransactionId>,
+        topic: Option<&str>,
+        substate_id: Option<&SubstateId>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<Self>, StorageError> {
+        tx.transaction_events_get_all(transaction_id, topic, substate_id, limit, offset)
+    }
+}
@@ -10,7 +10,7 @@ use std::{
 };
 
 use log::*;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use tari_dan_common_types::{
     committee::CommitteeInfo,
     optional::{IsNotFoundError, Optional},
@@ -83,11 +83,15 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
     pub fn insert_new(
         &self,
         tx: &mut TStateStore::WriteTransaction<'_>,
-        tx_id: TransactionId,
-        decision: Decision,
+        transaction: &TransactionRecord,
         is_ready: bool,
     ) -> Result<(), TransactionPoolError> {
-        tx.transaction_pool_insert_new(tx_id, decision, is_ready)?;
+        tx.transaction_pool_insert_new(
+            *transaction.id(),
+            transaction.current_decision(),
+            transaction.transaction().fee_per_instruction(),
+            is_ready,
+        )?;
         Ok(())
     }
 
@@ -98,7 +102,7 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
     ) -> Result<(), TransactionPoolError> {
         // TODO(perf)
         for (transaction, is_ready) in transactions {
-            tx.transaction_pool_insert_new(*transaction.id(), transaction.current_decision(), is_ready)?;
+            self.insert_new(tx, transaction, is_ready)?;
         }
         Ok(())
     }
@@ -192,7 +196,7 @@ impl<TStateStore: StateStore> TransactionPool<TStateStore> {
 }
 
 // Ord: ensure that the enum variants are ordered in the order of their progression
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 #[cfg_attr(
     feature = "ts",
     derive(ts_rs::TS),
@@ -334,6 +338,10 @@ pub struct TransactionPoolRecord {
     evidence: Evidence,
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     transaction_fee: u64,
+    /// The declared fee per instruction of the transaction. New transactions with a higher priority fee are proposed
+    /// first.
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    priority_fee: u64,
     leader_fee: Option<LeaderFee>,
     stage: TransactionPoolStage,
     pending_stage: Option<TransactionPoolStage>,
@@ -348,6 +356,7 @@ impl TransactionPoolRecord {
         id: TransactionId,
         evidence: Evidence,
         transaction_fee: u64,
+        priority_fee: u64,
         leader_fee: Option<LeaderFee>,
         stage: TransactionPoolStage,
         pending_stage: Option<TransactionPoolStage>,
@@ -360,6 +369,7 @@ impl TransactionPoolRecord {
             transaction_id: id,
             evidence,
             transaction_fee,
+            priority_fee,
             leader_fee,
             stage,
             pending_stage,
@@ -430,6 +440,10 @@ impl TransactionPoolRecord {
         self.transaction_fee
     }
 
+    pub fn priority_fee(&self) -> u64 {
+        self.priority_fee
+    }
+
     /// Returns the committed stage of the transaction. This is the stage that has been confirmed by the local shard.
     pub fn committed_stage(&self) -> TransactionPoolStage {
         self.stage
@@ -0,0 +1,212 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::{Epoch, NodeHeight, ShardGroup};
+use tari_transaction::TransactionId;
+#[cfg(feature = "ts")]
+use ts_rs::TS;
+
+use crate::{
+    consensus_models::{
+        Block,
+        BlockId,
+        Decision,
+        Evidence,
+        ForeignProposal,
+        LeafBlock,
+        LockedBlock,
+        ShardGroupEvidence,
+        TransactionAtom,
+        TransactionPoolStage,
+        TransactionRecord,
+    },
+    StateStoreReadTransaction,
+    StorageError,
+};
+
+/// The maximum number of local and foreign blocks that are loaded for a trace
+const MAX_TRACED_BLOCKS: u64 = 100;
+
+/// The state of a transaction in each of its involved shard groups, as seen by the local node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransactionTrace {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub local_shard_group: ShardGroup,
+    /// The final decision, once the transaction has been finalized
+    pub final_decision: Option<Decision>,
+    /// The stage of the transaction in the local pool. None if the transaction is not in the pool.
+    pub pool_stage: Option<TransactionPoolStage>,
+    pub local_decision: Option<Decision>,
+    pub remote_decision: Option<Decision>,
+    pub shard_groups: Vec<ShardGroupTrace>,
+}
+
+impl TransactionTrace {
+    /// Returns the trace of the transaction. Uncommitted pool changes are included up to the leaf block of the epoch.
+    pub fn get<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        transaction_id: &TransactionId,
+        epoch: Epoch,
+        local_shard_group: ShardGroup,
+    ) -> Result<Self, StorageError> {
+        let transaction = TransactionRecord::get(tx, transaction_id)?;
+        let pool_record = if tx.transaction_pool_exists(transaction_id)? {
+            let locked_block = LockedBlock::get(tx, epoch)?;
+            let leaf_block = LeafBlock::get(tx, epoch)?;
+            Some(tx.transaction_pool_get_for_blocks(locked_block.block_id(), leaf_block.block_id(), transaction_id)?)
+        } else {
+            None
+        };
+
+        let local_blocks = Block::get_all_by_transaction(tx, transaction_id, MAX_TRACED_BLOCKS)?;
+        let foreign_blocks = ForeignProposal::get_all_by_transaction(tx, transaction_id, MAX_TRACED_BLOCKS)?;
+
+        // The pool has the most recent evidence. Once the transaction has left the pool, the last local block that
+        // included it has the final evidence.
+        let evidence = pool_record.as_ref().map(|rec| rec.evidence()).or_else(|| {
+            local_blocks
+                .iter()
+                .rev()
+                .find_map(|block| find_transaction_atom(block, transaction_id))
+                .map(|(_, atom)| &atom.evidence)
+        });
+
+        let mut shard_groups = evidence
+            .into_iter()
+            .flat_map(Evidence::iter)
+            .map(|(shard_group, evidence)| {
+                let trace = ShardGroupTrace::new(*shard_group, *shard_group == local_shard_group, Some(evidence));
+                (*shard_group, trace)
+            })
+            .collect::<IndexMap<_, _>>();
+
+        let local_inclusions = local_blocks.iter().filter_map(|block| {
+            TransactionBlockInclusion::from_block(block, transaction_id, Some(block.is_committed()))
+        });
+        let foreign_inclusions = foreign_blocks
+            .iter()
+            .filter_map(|proposal| TransactionBlockInclusion::from_block(proposal.block(), transaction_id, None));
+        for (shard_group, inclusion) in local_inclusions.chain(foreign_inclusions) {
+            shard_groups
+                .entry(shard_group)
+                .or_insert_with(|| ShardGroupTrace::new(shard_group, shard_group == local_shard_group, None))
+                .add_block(inclusion);
+        }
+
+        Ok(Self {
+            transaction_id: *transaction_id,
+            local_shard_group,
+            final_decision: transaction.final_decision,
+            pool_stage: pool_record.as_ref().map(|rec| rec.current_stage()),
+            local_decision: pool_record.as_ref().map(|rec| rec.current_local_decision()),
+            remote_decision: pool_record.as_ref().and_then(|rec| rec.remote_decision()),
+            shard_groups: shard_groups.into_values().collect(),
+        })
+    }
+}
+
+/// The state of a transaction in one of its involved shard groups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct ShardGroupTrace {
+    pub shard_group: ShardGroup,
+    pub is_local: bool,
+    pub status: ShardGroupTraceStatus,
+    /// The decision in the most recent block from the shard group that included the transaction
+    pub decision: Option<Decision>,
+    /// The blocks from the shard group that included the transaction, ordered by epoch and height
+    pub blocks: Vec<TransactionBlockInclusion>,
+}
+
+impl ShardGroupTrace {
+    fn new(shard_group: ShardGroup, is_local: bool, evidence: Option<&ShardGroupEvidence>) -> Self {
+        let status = match evidence {
+            Some(evidence) => ShardGroupTraceStatus::from_evidence(evidence),
+            None => ShardGroupTraceStatus::AwaitingPrepare,
+        };
+        Self {
+            shard_group,
+            is_local,
+            status,
+            decision: None,
+            blocks: vec![],
+        }
+    }
+
+    fn add_block(&mut self, inclusion: TransactionBlockInclusion) {
+        self.decision = Some(inclusion.decision);
+        self.blocks.push(inclusion);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub enum ShardGroupTraceStatus {
+    /// No evidence that the shard group prepared the transaction has been received
+    AwaitingPrepare,
+    /// The shard group prepared the transaction, but no evidence that it accepted the transaction has been received
+    AwaitingAccept,
+    /// The shard group accepted the transaction
+    Accepted,
+}
+
+impl ShardGroupTraceStatus {
+    fn from_evidence(evidence: &ShardGroupEvidence) -> Self {
+        if evidence.is_accept_justified() {
+            Self::Accepted
+        } else if evidence.is_prepare_justified() {
+            Self::AwaitingAccept
+        } else {
+            Self::AwaitingPrepare
+        }
+    }
+}
+
+/// A block that included the transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(TS), ts(export, export_to = "../../bindings/src/types/"))]
+pub struct TransactionBlockInclusion {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub block_id: BlockId,
+    pub epoch: Epoch,
+    pub height: NodeHeight,
+    /// The name of the command that included the transaction e.g. LocalPrepare
+    pub command: String,
+    pub decision: Decision,
+    /// Whether the block has been committed. None for foreign blocks.
+    pub is_committed: Option<bool>,
+}
+
+impl TransactionBlockInclusion {
+    fn from_block(
+        block: &Block,
+        transaction_id: &TransactionId,
+        is_committed: Option<bool>,
+    ) -> Option<(ShardGroup, Self)> {
+        let (command, atom) = find_transaction_atom(block, transaction_id)?;
+        Some((block.shard_group(), Self {
+            block_id: *block.id(),
+            epoch: block.epoch(),
+            height: block.height(),
+            command: command.to_string(),
+            decision: atom.decision,
+            is_committed,
+        }))
+    }
+}
+
+fn find_transaction_atom<'a>(
+    block: &'a Block,
+    transaction_id: &TransactionId,
+) -> Option<(&'a str, &'a TransactionAtom)> {
+    block.commands().iter().find_map(|command| {
+        command
+            .transaction()
+            .filter(|atom| atom.id() == transaction_id)
+            .map(|atom| (command.as_ref(), atom))
+    })
+}
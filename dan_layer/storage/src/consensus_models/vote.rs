@@ -81,6 +81,14 @@ impl Vote {
         tx.votes_count_for_block(block_id).map(|v| v as usize)
     }
 
+    pub fn get_by_block_and_sender<TTx: StateStoreReadTransaction>(
+        tx: &TTx,
+        block_id: &BlockId,
+        sender_leaf_hash: &FixedHash,
+    ) -> Result<Self, StorageError> {
+        tx.votes_get_by_block_and_sender(block_id, sender_leaf_hash)
+    }
+
     pub fn get_for_block<TTx: StateStoreReadTransaction>(
         tx: &TTx,
         block_id: &BlockId,
@@ -69,6 +69,13 @@ pub trait GlobalDbAdapter: AtomicDb + Send + Sync + Clone {
         limit: usize,
     ) -> Result<Vec<DbTemplate>, Self::Error>;
 
+    /// Returns all known versions of the template lineage with the given root, ordered by version
+    fn get_template_versions(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        lineage_root: &[u8],
+    ) -> Result<Vec<DbTemplate>, Self::Error>;
+
     fn insert_template(&self, tx: &mut Self::DbTransaction<'_>, template: DbTemplate) -> Result<(), Self::Error>;
     fn update_template(
         &self,
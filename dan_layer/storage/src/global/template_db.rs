@@ -50,6 +50,10 @@ impl<'a, 'tx, TGlobalDbAdapter: GlobalDbAdapter> TemplateDb<'a, 'tx, TGlobalDbAd
         self.backend.get_pending_templates(self.tx, limit)
     }
 
+    pub fn get_template_versions(&mut self, lineage_root: &[u8]) -> Result<Vec<DbTemplate>, TGlobalDbAdapter::Error> {
+        self.backend.get_template_versions(self.tx, lineage_root)
+    }
+
     pub fn insert_template(&mut self, template: DbTemplate) -> Result<(), TGlobalDbAdapter::Error> {
         self.backend.insert_template(self.tx, template)
     }
@@ -76,6 +80,12 @@ pub struct DbTemplate {
     pub url: Option<String>,
     pub status: TemplateStatus,
     pub added_at: NaiveDateTime,
+    /// The version of the template within its lineage. The first published version is 0.
+    pub template_version: u32,
+    /// The template that this template is a new version of, if any
+    pub previous_version: Option<TemplateAddress>,
+    /// The first version of the lineage. None if this template is the first version.
+    pub lineage_root: Option<TemplateAddress>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -23,7 +23,7 @@ use tari_dan_common_types::{
     VersionedSubstateId,
 };
 use tari_engine_types::substate::SubstateId;
-use tari_state_tree::{Node, NodeKey, StaleTreeNode, Version};
+use tari_state_tree::{Node, NodeKey, StaleTreeNode, TreeHash, Version};
 use tari_template_lib::models::UnclaimedConfidentialOutputAddress;
 use tari_transaction::TransactionId;
 #[cfg(feature = "ts")]
@@ -38,11 +38,13 @@ use crate::{
         BurntUtxo,
         Decision,
         EpochCheckpoint,
+        EvictedForeignProposal,
         ForeignParkedProposal,
         ForeignProposal,
         ForeignProposalAtom,
         ForeignProposalStatus,
         ForeignReceiveCounters,
+        ForeignReceiveGap,
         ForeignSendCounters,
         HighQc,
         LastExecuted,
@@ -53,16 +55,23 @@ use crate::{
         LockConflict,
         LockedBlock,
         LockedSubstateValue,
+        MisbehaviourEvidence,
         NoVoteReason,
         PendingShardStateTreeDiff,
+        ProposalEquivocation,
+        ProposalEquivocationAtom,
+        PrunedSubstates,
         QcId,
         QuorumCertificate,
+        StateSnapshot,
         StateTransition,
         StateTransitionId,
         SubstateChange,
+        SubstateData,
         SubstateLock,
         SubstatePledges,
         SubstateRecord,
+        TransactionEvent,
         TransactionPoolConfirmedStage,
         TransactionPoolRecord,
         TransactionPoolStage,
@@ -126,6 +135,12 @@ pub trait StateStoreReadTransaction: Sized {
         &self,
         block_ids: I,
     ) -> Result<Vec<ForeignProposal>, StorageError>;
+    /// Returns foreign proposals with a command for the transaction, ordered by epoch and height
+    fn foreign_proposals_get_all_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<ForeignProposal>, StorageError>;
     fn foreign_proposals_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
     fn foreign_proposals_has_unconfirmed(&self, epoch: Epoch) -> Result<bool, StorageError>;
     fn foreign_proposals_get_all_new(
@@ -141,6 +156,12 @@ pub trait StateStoreReadTransaction: Sized {
 
     fn foreign_send_counters_get(&self, block_id: &BlockId) -> Result<ForeignSendCounters, StorageError>;
     fn foreign_receive_counters_get(&self) -> Result<ForeignReceiveCounters, StorageError>;
+    fn foreign_receive_gaps_get_all(
+        &self,
+        include_resolved: bool,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<ForeignReceiveGap>, StorageError>;
     fn transactions_get(&self, tx_id: &TransactionId) -> Result<TransactionRecord, StorageError>;
     fn transactions_exists(&self, tx_id: &TransactionId) -> Result<bool, StorageError>;
 
@@ -154,6 +175,9 @@ pub trait StateStoreReadTransaction: Sized {
         offset: u64,
         asc_desc_created_at: Option<Ordering>,
     ) -> Result<Vec<TransactionRecord>, StorageError>;
+    /// Returns the transactions with a min_epoch that have neither been added to the transaction pool nor finalized or
+    /// aborted i.e. transactions that the mempool deferred until their min_epoch
+    fn transactions_get_deferred(&self) -> Result<Vec<TransactionRecord>, StorageError>;
 
     fn transaction_executions_get(
         &self,
@@ -180,6 +204,12 @@ pub trait StateStoreReadTransaction: Sized {
         include_dummy_blocks: bool,
         limit: u64,
     ) -> Result<Vec<Block>, StorageError>;
+    /// Returns blocks with a command for the transaction, ordered by epoch and height
+    fn blocks_get_all_by_transaction(
+        &self,
+        transaction_id: &TransactionId,
+        limit: u64,
+    ) -> Result<Vec<Block>, StorageError>;
     fn blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
     fn blocks_is_ancestor(&self, descendant: &BlockId, ancestor: &BlockId) -> Result<bool, StorageError>;
     fn blocks_get_all_by_parent(&self, parent: &BlockId) -> Result<Vec<Block>, StorageError>;
@@ -280,6 +310,8 @@ pub trait StateStoreReadTransaction: Sized {
         S: Borrow<VersionedSubstateId>;
 
     fn substates_exists_for_transaction(&self, transaction_id: &TransactionId) -> Result<bool, StorageError>;
+    /// Returns the number of substates that have not been destroyed
+    fn substates_count_up(&self) -> Result<u64, StorageError>;
 
     fn substates_get_n_after(&self, n: usize, after: &SubstateAddress) -> Result<Vec<SubstateRecord>, StorageError>;
 
@@ -330,6 +362,17 @@ pub trait StateStoreReadTransaction: Sized {
     // -------------------------------- Epoch checkpoint -------------------------------- //
     fn epoch_checkpoint_get(&self, epoch: Epoch) -> Result<EpochCheckpoint, StorageError>;
 
+    // -------------------------------- State snapshots -------------------------------- //
+    fn state_snapshots_get(&self, epoch: Epoch, shard: Shard) -> Result<StateSnapshot, StorageError>;
+    fn state_snapshots_get_latest(&self, shard: Shard, max_epoch: Epoch) -> Result<StateSnapshot, StorageError>;
+    fn state_snapshot_substates_get(
+        &self,
+        epoch: Epoch,
+        shard: Shard,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SubstateData>, StorageError>;
+
     // -------------------------------- Foreign Substate Pledges -------------------------------- //
     fn foreign_substate_pledges_exists_for_address<T: ToSubstateAddress>(
         &self,
@@ -351,9 +394,51 @@ pub trait StateStoreReadTransaction: Sized {
 
     fn burnt_utxos_count(&self) -> Result<u64, StorageError>;
 
+    // -------------------------------- ProposalEquivocations -------------------------------- //
+    fn proposal_equivocations_exists(
+        &self,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        height: NodeHeight,
+    ) -> Result<bool, StorageError>;
+    fn proposal_equivocations_is_committed(
+        &self,
+        epoch: Epoch,
+        proposed_by: &PublicKey,
+        height: NodeHeight,
+    ) -> Result<bool, StorageError>;
+    fn proposal_equivocations_get_all_unproposed(
+        &self,
+        leaf_block: &BlockId,
+        limit: usize,
+    ) -> Result<Vec<ProposalEquivocation>, StorageError>;
+    fn proposal_equivocations_get_committed_proposers(&self, epoch: Epoch) -> Result<Vec<PublicKey>, StorageError>;
+
+    // -------------------------------- MisbehaviourEvidence -------------------------------- //
+    fn misbehaviour_evidence_get_all(
+        &self,
+        epoch: Option<Epoch>,
+        offender: Option<&PublicKey>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<MisbehaviourEvidence>, StorageError>;
+
+    // -------------------------------- TransactionEvent -------------------------------- //
+    fn transaction_events_get_all(
+        &self,
+        transaction_id: Option<&TransactionId>,
+        topic: Option<&str>,
+        substate_id: Option<&SubstateId>,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Vec<TransactionEvent>, StorageError>;
+
     // -------------------------------- Foreign parked block -------------------------------- //
     fn foreign_parked_blocks_exists(&self, block_id: &BlockId) -> Result<bool, StorageError>;
 
+    // -------------------------------- Evicted foreign proposals -------------------------------- //
+    fn evicted_foreign_proposals_count(&self) -> Result<u64, StorageError>;
+
     // -------------------------------- ValidatorNodeStats -------------------------------- //
     fn validator_epoch_stats_get(
         &self,
@@ -435,6 +520,13 @@ pub trait StateStoreWriteTransaction {
         &mut self,
         foreign_send_counter: &ForeignReceiveCounters,
     ) -> Result<(), StorageError>;
+    fn foreign_receive_gaps_insert(&mut self, gap: &ForeignReceiveGap) -> Result<(), StorageError>;
+    fn foreign_receive_gaps_record_received(
+        &mut self,
+        foreign_shard_group: ShardGroup,
+        epoch: Epoch,
+        index: u64,
+    ) -> Result<(), StorageError>;
 
     // -------------------------------- Transaction -------------------------------- //
     fn transactions_insert(&mut self, transaction: &TransactionRecord) -> Result<(), StorageError>;
@@ -462,6 +554,7 @@ pub trait StateStoreWriteTransaction {
         &mut self,
         tx_id: TransactionId,
         decision: Decision,
+        priority_fee: u64,
         is_ready: bool,
     ) -> Result<(), StorageError>;
     fn transaction_pool_add_pending_update(
@@ -507,6 +600,17 @@ pub trait StateStoreWriteTransaction {
         transaction_id: &TransactionId,
     ) -> Result<Vec<ForeignParkedProposal>, StorageError>;
 
+    // -------------------------------- Evicted foreign proposals -------------------------------- //
+    fn evicted_foreign_proposals_insert(
+        &mut self,
+        proposal: &EvictedForeignProposal<Self::Addr>,
+    ) -> Result<(), StorageError>;
+    fn evicted_foreign_proposals_remove_up_to_epoch(
+        &mut self,
+        epoch: Epoch,
+        limit: usize,
+    ) -> Result<Vec<EvictedForeignProposal<Self::Addr>>, StorageError>;
+
     // -------------------------------- Votes -------------------------------- //
     fn votes_insert(&mut self, vote: &Vote) -> Result<(), StorageError>;
 
@@ -536,6 +640,9 @@ pub trait StateStoreWriteTransaction {
         destroyed_transaction_id: &TransactionId,
         destroyed_qc_id: &QcId,
     ) -> Result<(), StorageError>;
+    fn substates_prune_destroyed_before(&mut self, epoch: Epoch, limit: usize) -> Result<PrunedSubstates, StorageError>;
+    fn state_transitions_rebase(&mut self, shard: Shard, last_seq: u64) -> Result<(), StorageError>;
+    fn state_transitions_prune_before(&mut self, epoch: Epoch) -> Result<u64, StorageError>;
 
     // -------------------------------- Foreign pledges -------------------------------- //
 
@@ -578,6 +685,17 @@ pub trait StateStoreWriteTransaction {
     // -------------------------------- Epoch checkpoint -------------------------------- //
     fn epoch_checkpoint_save(&mut self, checkpoint: &EpochCheckpoint) -> Result<(), StorageError>;
 
+    // -------------------------------- State snapshots -------------------------------- //
+    fn state_snapshots_create(
+        &mut self,
+        epoch: Epoch,
+        shard: Shard,
+        state_version: Version,
+        state_root: TreeHash,
+        last_transition_id: StateTransitionId,
+    ) -> Result<StateSnapshot, StorageError>;
+    fn state_snapshots_delete_before(&mut self, epoch: Epoch) -> Result<(), StorageError>;
+
     // -------------------------------- BurntUtxo -------------------------------- //
     fn burnt_utxos_insert(&mut self, burnt_utxo: &BurntUtxo) -> Result<(), StorageError>;
     fn burnt_utxos_set_proposed_block(
@@ -588,6 +706,26 @@ pub trait StateStoreWriteTransaction {
     fn burnt_utxos_clear_proposed_block(&mut self, proposed_in_block: &BlockId) -> Result<(), StorageError>;
     fn burnt_utxos_delete(&mut self, commitment: &UnclaimedConfidentialOutputAddress) -> Result<(), StorageError>;
 
+    // -------------------------------- ProposalEquivocations -------------------------------- //
+    fn proposal_equivocations_insert(&mut self, atom: &ProposalEquivocationAtom) -> Result<(), StorageError>;
+    fn proposal_equivocations_set_proposed_block(
+        &mut self,
+        atom: &ProposalEquivocationAtom,
+        proposed_in_block: &BlockId,
+    ) -> Result<(), StorageError>;
+    fn proposal_equivocations_clear_proposed_block(&mut self, proposed_in_block: &BlockId) -> Result<(), StorageError>;
+    fn proposal_equivocations_set_committed(
+        &mut self,
+        atom: &ProposalEquivocationAtom,
+        committed_in_block: &BlockId,
+    ) -> Result<(), StorageError>;
+
+    // -------------------------------- MisbehaviourEvidence -------------------------------- //
+    fn misbehaviour_evidence_insert(&mut self, evidence: &MisbehaviourEvidence) -> Result<(), StorageError>;
+
+    // -------------------------------- TransactionEvent -------------------------------- //
+    fn transaction_events_insert_all(&mut self, events: &[TransactionEvent]) -> Result<(), StorageError>;
+
     // -------------------------------- Lock conflicts -------------------------------- //
     fn lock_conflicts_insert_all<'a, I: IntoIterator<Item = (&'a TransactionId, &'a Vec<LockConflict>)>>(
         &mut self,
@@ -0,0 +1,5 @@
+DROP INDEX templates_lineage_root_index;
+
+ALTER TABLE templates DROP COLUMN template_version;
+ALTER TABLE templates DROP COLUMN previous_version;
+ALTER TABLE templates DROP COLUMN lineage_root;
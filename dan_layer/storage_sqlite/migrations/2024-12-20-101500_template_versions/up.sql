@@ -0,0 +1,8 @@
+-- The version of the template within its lineage. The first published version is 0.
+ALTER TABLE templates ADD COLUMN template_version INTEGER NOT NULL DEFAULT 0;
+-- The address of the template that this template is a new version of
+ALTER TABLE templates ADD COLUMN previous_version BLOB NULL;
+-- The address of the first version of the lineage. NULL if this template is the first version.
+ALTER TABLE templates ADD COLUMN lineage_root BLOB NULL;
+
+CREATE INDEX templates_lineage_root_index ON templates (lineage_root);
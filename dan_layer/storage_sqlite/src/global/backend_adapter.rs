@@ -227,6 +227,9 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                 url: t.url,
                 status: t.status.parse().expect("DB status corrupted"),
                 added_at: t.added_at,
+                template_version: t.template_version as u32,
+                previous_version: t.previous_version.map(TemplateAddress::try_from_vec).transpose()?,
+                lineage_root: t.lineage_root.map(TemplateAddress::try_from_vec).transpose()?,
             })),
             None => Ok(None),
         }
@@ -264,6 +267,9 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                     url: t.url,
                     status: t.status.parse().expect("DB status corrupted"),
                     added_at: t.added_at,
+                    template_version: t.template_version as u32,
+                    previous_version: t.previous_version.map(TemplateAddress::try_from_vec).transpose()?,
+                    lineage_root: t.lineage_root.map(TemplateAddress::try_from_vec).transpose()?,
                 })
             })
             .collect()
@@ -299,6 +305,51 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
                     url: t.url,
                     status: t.status.parse().expect("DB status corrupted"),
                     added_at: t.added_at,
+                    template_version: t.template_version as u32,
+                    previous_version: t.previous_version.map(TemplateAddress::try_from_vec).transpose()?,
+                    lineage_root: t.lineage_root.map(TemplateAddress::try_from_vec).transpose()?,
+                })
+            })
+            .collect()
+    }
+
+    fn get_template_versions(
+        &self,
+        tx: &mut Self::DbTransaction<'_>,
+        lineage_root: &[u8],
+    ) -> Result<Vec<DbTemplate>, Self::Error> {
+        use crate::global::schema::templates::dsl;
+        let templates = dsl::templates
+            .filter(
+                templates::template_address
+                    .eq(lineage_root)
+                    .or(templates::lineage_root.eq(lineage_root)),
+            )
+            .order_by(templates::template_version.asc())
+            .get_results::<TemplateModel>(tx.connection())
+            .map_err(|source| SqliteStorageError::DieselError {
+                source,
+                operation: "get_template_versions".to_string(),
+            })?;
+
+        templates
+            .into_iter()
+            .map(|t| {
+                Ok(DbTemplate {
+                    author_public_key: FixedHash::try_from(t.author_public_key.as_slice())?,
+                    template_name: t.template_name,
+                    expected_hash: t.expected_hash.try_into()?,
+                    template_address: TemplateAddress::try_from_vec(t.template_address)?,
+                    template_type: t.template_type.parse().expect("DB template type corrupted"),
+                    compiled_code: t.compiled_code,
+                    flow_json: t.flow_json,
+                    manifest: t.manifest,
+                    url: t.url,
+                    status: t.status.parse().expect("DB status corrupted"),
+                    added_at: t.added_at,
+                    template_version: t.template_version as u32,
+                    previous_version: t.previous_version.map(TemplateAddress::try_from_vec).transpose()?,
+                    lineage_root: t.lineage_root.map(TemplateAddress::try_from_vec).transpose()?,
                 })
             })
             .collect()
@@ -315,6 +366,9 @@ impl<TAddr: NodeAddressable> GlobalDbAdapter for SqliteGlobalDbAdapter<TAddr> {
             flow_json: item.flow_json,
             status: item.status.as_str().to_string(),
             manifest: item.manifest,
+            template_version: item.template_version as i32,
+            previous_version: item.previous_version.map(|a| a.to_vec()),
+            lineage_root: item.lineage_root.map(|a| a.to_vec()),
         };
         diesel::insert_into(templates::table)
             .values(new_template)
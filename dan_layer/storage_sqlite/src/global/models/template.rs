@@ -39,6 +39,9 @@ pub struct TemplateModel {
     pub url: Option<String>,
     pub status: String,
     pub added_at: NaiveDateTime,
+    pub template_version: i32,
+    pub previous_version: Option<Vec<u8>>,
+    pub lineage_root: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Insertable)]
@@ -53,6 +56,9 @@ pub struct NewTemplateModel {
     pub flow_json: Option<String>,
     pub status: String,
     pub manifest: Option<String>,
+    pub template_version: i32,
+    pub previous_version: Option<Vec<u8>>,
+    pub lineage_root: Option<Vec<u8>>,
 }
 
 #[derive(Debug, AsChangeset)]
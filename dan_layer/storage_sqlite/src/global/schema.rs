@@ -63,6 +63,9 @@ diesel::table! {
         url -> Nullable<Text>,
         status -> Text,
         added_at -> Timestamp,
+        template_version -> Integer,
+        previous_version -> Nullable<Binary>,
+        lineage_root -> Nullable<Binary>,
     }
 }
 
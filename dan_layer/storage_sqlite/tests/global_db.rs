@@ -6,8 +6,9 @@ use rand::rngs::OsRng;
 use tari_common_types::types::{FixedHash, PublicKey};
 use tari_crypto::keys::PublicKey as _;
 use tari_dan_common_types::{Epoch, NumPreshards, PeerAddress, ShardGroup, SubstateAddress};
-use tari_dan_storage::global::{GlobalDb, ValidatorNodeDb};
+use tari_dan_storage::global::{DbTemplate, DbTemplateType, GlobalDb, TemplateStatus, ValidatorNodeDb};
 use tari_dan_storage_sqlite::global::SqliteGlobalDbAdapter;
+use tari_engine_types::TemplateAddress;
 use tari_utilities::ByteArray;
 
 fn create_db() -> GlobalDb<SqliteGlobalDbAdapter<PeerAddress>> {
@@ -103,3 +104,54 @@ fn change_committee_shard_group() {
         .unwrap();
     assert_eq!(vns.len(), 2);
 }
+
+fn new_template(
+    address: TemplateAddress,
+    template_version: u32,
+    previous_version: Option<TemplateAddress>,
+    lineage_root: Option<TemplateAddress>,
+) -> DbTemplate {
+    DbTemplate {
+        author_public_key: FixedHash::default(),
+        template_address: address,
+        template_name: "Test".to_string(),
+        expected_hash: FixedHash::default(),
+        template_type: DbTemplateType::Wasm,
+        compiled_code: Some(vec![]),
+        flow_json: None,
+        manifest: None,
+        url: None,
+        status: TemplateStatus::Active,
+        added_at: chrono::Utc::now().naive_utc(),
+        template_version,
+        previous_version,
+        lineage_root,
+    }
+}
+
+#[test]
+fn get_template_versions() {
+    let db = create_db();
+    let mut tx = db.create_transaction().unwrap();
+    let mut templates = db.templates(&mut tx);
+    let root = TemplateAddress::from_array([1u8; 32]);
+    let v1 = TemplateAddress::from_array([2u8; 32]);
+    let v2 = TemplateAddress::from_array([3u8; 32]);
+    let unrelated = TemplateAddress::from_array([4u8; 32]);
+    templates
+        .insert_template(new_template(v2, 2, Some(v1), Some(root)))
+        .unwrap();
+    templates.insert_template(new_template(root, 0, None, None)).unwrap();
+    templates
+        .insert_template(new_template(unrelated, 0, None, None))
+        .unwrap();
+    templates
+        .insert_template(new_template(v1, 1, Some(root), Some(root)))
+        .unwrap();
+
+    let versions = templates.get_template_versions(&root).unwrap();
+    let addresses = versions.iter().map(|t| t.template_address).collect::<Vec<_>>();
+    assert_eq!(addresses, vec![root, v1, v2]);
+    assert_eq!(versions[2].previous_version, Some(v1));
+    assert_eq!(versions[2].lineage_root, Some(root));
+}
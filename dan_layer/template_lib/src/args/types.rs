@@ -127,6 +127,9 @@ pub enum ComponentAction {
     SetState,
     SetAccessRules,
     GetTemplateAddress,
+    /// Migrates the component to a newer version of its template. This can only be performed by the component owner
+    /// using the MigrateComponent transaction instruction.
+    MigrateTemplate,
 }
 
 /// Encapsulates all the ways that a component can be referenced
@@ -219,7 +222,11 @@ pub enum ResourceAction {
     Create,
     Mint,
     Recall,
+    FreezeVault,
+    UnfreezeVault,
     UpdateNonFungibleData,
+    UpdateMetadata,
+    GetMetadata,
     GetTotalSupply,
     GetResourceType,
     GetNonFungible,
@@ -281,6 +288,13 @@ pub struct ResourceUpdateNonFungibleDataArg {
     pub data: tari_bor::Value,
 }
 
+/// A resource metadata update operation argument. The entry is removed if the value is None.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ResourceUpdateMetadataArg {
+    pub key: String,
+    pub value: Option<String>,
+}
+
 /// A convenience enum that allows to specify resource types
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ResourceDiscriminator {
@@ -303,6 +317,12 @@ pub struct RecallResourceArg {
     pub vault_id: VaultId,
     pub resource: ResourceDiscriminator,
 }
+
+/// A vault freeze or unfreeze operation argument
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FreezeVaultArg {
+    pub vault_id: VaultId,
+}
 // -------------------------------- Vault -------------------------------- //
 
 /// A vault operation argument
@@ -331,6 +351,10 @@ pub enum VaultAction {
     CreateProofByNonFungibles,
     CreateProofByConfidentialResource,
     GetNonFungibles,
+    WithdrawMultiple,
+    DepositMultiple,
+    IsFrozen,
+    FlashBorrow,
 }
 
 impl VaultAction {
@@ -343,7 +367,27 @@ impl VaultAction {
                 GetResourceAddress |
                 GetNonFungibleIds |
                 GetCommitmentCount |
-                GetNonFungibles
+                GetNonFungibles |
+                IsFrozen
+        )
+    }
+
+    /// Returns true if the action may not be performed on a frozen vault
+    pub fn is_denied_when_frozen(&self) -> bool {
+        use VaultAction::*;
+        matches!(
+            self,
+            Deposit |
+                Withdraw |
+                ConfidentialReveal |
+                PayFee |
+                CreateProofByResource |
+                CreateProofByFungibleAmount |
+                CreateProofByNonFungibles |
+                CreateProofByConfidentialResource |
+                WithdrawMultiple |
+                DepositMultiple |
+                FlashBorrow
         )
     }
 }
@@ -356,6 +400,14 @@ pub enum VaultWithdrawArg {
     Confidential { proof: Box<ConfidentialWithdrawProof> },
 }
 
+/// A vault flash borrow operation argument
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VaultFlashBorrowArg {
+    pub amount: Amount,
+    /// The amount that must be returned to the vault in addition to the borrowed amount
+    pub fee: Amount,
+}
+
 // -------------------------------- Confidential -------------------------------- //
 
 /// A confidential resource reveal operation argument
@@ -502,6 +554,45 @@ pub enum GenerateRandomAction {
     GetRandomBytes { len: u32 },
 }
 
+// -------------------------------- Text -------------------------------- //
+
+/// A text operation argument
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TextInvokeArg {
+    pub action: TextAction,
+}
+
+/// The possible text operations. These are executed by the engine within deterministic limits.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TextAction {
+    RegexIsMatch {
+        pattern: String,
+        text: String,
+    },
+    RegexFind {
+        pattern: String,
+        text: String,
+    },
+    RegexReplaceAll {
+        pattern: String,
+        text: String,
+        replacement: String,
+    },
+    Normalize {
+        text: String,
+        form: NormalizationForm,
+    },
+}
+
+/// A unicode normalization form
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NormalizationForm {
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+}
+
 // -------------------------------- CallerContext -------------------------------- //
 
 /// A caller context operation argument
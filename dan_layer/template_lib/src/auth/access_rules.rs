@@ -168,9 +168,11 @@ pub enum ResourceAuthAction {
     Mint,
     Burn,
     Recall,
+    Freeze,
     Withdraw,
     Deposit,
     UpdateNonFungibleData,
+    UpdateMetadata,
     UpdateAccessRules,
 }
 
@@ -178,6 +180,10 @@ impl ResourceAuthAction {
     pub fn is_recall(&self) -> bool {
         matches!(self, Self::Recall)
     }
+
+    pub fn is_freeze(&self) -> bool {
+        matches!(self, Self::Freeze)
+    }
 }
 
 /// Information needed to specify access rules to a resource
@@ -187,16 +193,20 @@ pub struct ResourceAccessRules {
     mintable: AccessRule,
     burnable: AccessRule,
     recallable: AccessRule,
+    #[serde(default = "deny_all")]
+    freezable: AccessRule,
     withdrawable: AccessRule,
     depositable: AccessRule,
     update_non_fungible_data: AccessRule,
+    #[serde(default = "deny_all")]
+    update_metadata: AccessRule,
 }
 
 impl ResourceAccessRules {
     /// Builds a new set of access rules for a resource.
     ///
     /// By default:
-    /// * Minting, burning and recalling are disabled for all users
+    /// * Minting, burning, recalling, freezing and metadata updates are disabled for all users
     /// * Withdrawals, deposits and non-fungible data updates are allowed for all users
     pub fn new() -> Self {
         Self {
@@ -204,10 +214,12 @@ impl ResourceAccessRules {
             mintable: AccessRule::DenyAll,
             burnable: AccessRule::DenyAll,
             recallable: AccessRule::DenyAll,
+            freezable: AccessRule::DenyAll,
             // But explicitly disable withdrawing, updating and/or depositing
             withdrawable: AccessRule::AllowAll,
             depositable: AccessRule::AllowAll,
             update_non_fungible_data: AccessRule::AllowAll,
+            update_metadata: AccessRule::DenyAll,
         }
     }
 
@@ -217,9 +229,11 @@ impl ResourceAccessRules {
             mintable: AccessRule::DenyAll,
             burnable: AccessRule::DenyAll,
             recallable: AccessRule::DenyAll,
+            freezable: AccessRule::DenyAll,
             withdrawable: AccessRule::DenyAll,
             depositable: AccessRule::DenyAll,
             update_non_fungible_data: AccessRule::DenyAll,
+            update_metadata: AccessRule::DenyAll,
         }
     }
 
@@ -242,6 +256,19 @@ impl ResourceAccessRules {
         self
     }
 
+    /// Sets up who can freeze and unfreeze vaults of the resource.
+    /// No tokens can be deposited into or withdrawn from a frozen vault, but they can still be recalled
+    pub fn freezable(mut self, rule: AccessRule) -> Self {
+        self.freezable = rule;
+        self
+    }
+
+    /// Sets up the auditor (regulator) of the resource. The auditor can recall tokens from and freeze any vault of the
+    /// resource. This is equivalent to setting both `recallable` and `freezable` to the same rule.
+    pub fn auditor(self, rule: AccessRule) -> Self {
+        self.recallable(rule.clone()).freezable(rule)
+    }
+
     /// Sets up who can withdraw tokens of the resource from any vault
     pub fn withdrawable(mut self, rule: AccessRule) -> Self {
         self.withdrawable = rule;
@@ -260,15 +287,23 @@ impl ResourceAccessRules {
         self
     }
 
+    /// Sets up who can update the metadata of the resource. The resource owner can always update the metadata.
+    pub fn update_metadata(mut self, rule: AccessRule) -> Self {
+        self.update_metadata = rule;
+        self
+    }
+
     /// Returns a reference to the access rule for the specified action
     pub fn get_access_rule(&self, action: &ResourceAuthAction) -> &AccessRule {
         match action {
             ResourceAuthAction::Mint => &self.mintable,
             ResourceAuthAction::Burn => &self.burnable,
             ResourceAuthAction::Recall => &self.recallable,
+            ResourceAuthAction::Freeze => &self.freezable,
             ResourceAuthAction::Withdraw => &self.withdrawable,
             ResourceAuthAction::Deposit => &self.depositable,
             ResourceAuthAction::UpdateNonFungibleData => &self.update_non_fungible_data,
+            ResourceAuthAction::UpdateMetadata => &self.update_metadata,
             // Only owner can do this
             ResourceAuthAction::UpdateAccessRules => &AccessRule::DenyAll,
         }
@@ -281,6 +316,10 @@ impl Default for ResourceAccessRules {
     }
 }
 
+fn deny_all() -> AccessRule {
+    AccessRule::DenyAll
+}
+
 #[macro_export]
 macro_rules! rule {
     (allow_all) => {
@@ -21,7 +21,6 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use serde::{de::DeserializeOwned, Serialize};
-use tari_bor::{from_value, to_value};
 use tari_template_abi::{call_engine, EngineOp};
 
 use crate::{
@@ -98,13 +97,12 @@ impl ComponentManager {
             args: invoke_args![],
         });
 
-        let component: tari_bor::Value = result.decode().expect("failed to decode component state from engine");
-        from_value(&component).expect("Failed to decode component state")
+        // Decode the component straight from the result, without an intermediate copy of the state
+        result.decode().expect("Failed to decode component state")
     }
 
     /// Update the component state
     pub fn set_state<T: Serialize>(&self, state: T) {
-        let state = to_value(&state).expect("Failed to encode component state");
         let _result = call_engine::<_, InvokeResult>(EngineOp::ComponentInvoke, &ComponentInvokeArg {
             component_ref: ComponentRef::Ref(self.address),
             action: ComponentAction::SetState,
@@ -45,8 +45,10 @@ pub mod caller_context;
 mod context;
 pub use context::{get_context, init_context, AbiContext};
 
+pub mod oracle;
 pub mod rand;
 pub mod resource;
+pub mod text;
 
 pub mod crypto;
 pub mod events;
@@ -49,6 +49,10 @@ impl Metadata {
         self
     }
 
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
     pub fn get(&self, key: &str) -> Option<&String> {
         self.0.get(key)
     }
@@ -57,6 +61,10 @@ impl Metadata {
         self.0.contains_key(key)
     }
 
+    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, String, String> {
+        self.0.iter()
+    }
+
     pub fn merge(&mut self, other: Metadata) -> &mut Self {
         self.0.extend(other.0.into_inner());
         self
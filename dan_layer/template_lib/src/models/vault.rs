@@ -44,6 +44,7 @@ use crate::{
         VaultAction,
         VaultCreateProofByFungibleAmountArg,
         VaultCreateProofByNonFungiblesArg,
+        VaultFlashBorrowArg,
         VaultInvokeArg,
         VaultWithdrawArg,
     },
@@ -201,6 +202,20 @@ impl Vault {
         result.decode::<()>().expect("deposit failed");
     }
 
+    /// Deposit all the tokens from each of the provided buckets into the vault in a single operation.
+    /// Either all buckets are deposited or, if any of them cannot be deposited (e.g. they contain a different
+    /// resource), none are and the call panics.
+    pub fn deposit_multiple<I: IntoIterator<Item = Bucket>>(&self, buckets: I) {
+        let bucket_ids = buckets.into_iter().map(|bucket| bucket.id()).collect::<Vec<_>>();
+        let result: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::DepositMultiple,
+            args: invoke_args![bucket_ids],
+        });
+
+        result.decode::<()>().expect("deposit_multiple failed");
+    }
+
     /// Withdraw an `amount` of tokens from the vault into a new bucket.
     pub fn withdraw<T: Into<Amount>>(&self, amount: T) -> Bucket {
         let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
@@ -212,6 +227,40 @@ impl Vault {
         resp.decode().expect("failed to decode Bucket")
     }
 
+    /// Withdraw each of the given amounts of tokens from the vault into a new bucket, in a single operation. The
+    /// buckets are returned in the same order as the amounts. Either all amounts are withdrawn or, if the vault
+    /// does not hold enough tokens for all of them, none are and the call panics.
+    pub fn withdraw_multiple<I, T>(&self, amounts: I) -> Vec<Bucket>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Amount>,
+    {
+        let amounts = amounts.into_iter().map(Into::into).collect::<Vec<Amount>>();
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::WithdrawMultiple,
+            args: invoke_args![amounts],
+        });
+
+        resp.decode().expect("failed to decode Vec<Bucket>")
+    }
+
+    /// Borrow an `amount` of tokens from the vault for the rest of the transaction. The engine rejects the transaction
+    /// unless the borrowed amount plus `fee` has been deposited back into the vault by the time the transaction
+    /// completes. Only fungible tokens can be borrowed.
+    pub fn flash_borrow<A: Into<Amount>, F: Into<Amount>>(&self, amount: A, fee: F) -> Bucket {
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::FlashBorrow,
+            args: invoke_args![VaultFlashBorrowArg {
+                amount: amount.into(),
+                fee: fee.into(),
+            }],
+        });
+
+        resp.decode().expect("failed to decode Bucket")
+    }
+
     /// Withdraw a single non-fungible token from the vault into a new bucket.
     /// It will panic if the vault does not contain the specified non-fungible token
     pub fn withdraw_non_fungible(&self, id: NonFungibleId) -> Bucket {
@@ -272,6 +321,18 @@ impl Vault {
         resp.decode().expect("failed to decode Amount")
     }
 
+    /// Returns true if the vault has been frozen by the resource auditor. No tokens can be deposited into or withdrawn
+    /// from a frozen vault.
+    pub fn is_frozen(&self) -> bool {
+        let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
+            vault_ref: self.vault_ref(),
+            action: VaultAction::IsFrozen,
+            args: invoke_args![],
+        });
+
+        resp.decode().expect("failed to decode bool")
+    }
+
     /// Returns how many Pederson commitments (related to confidential balances) this vault holds
     pub fn commitment_count(&self) -> u32 {
         let resp: InvokeResult = call_engine(EngineOp::VaultInvoke, &VaultInvokeArg {
@@ -0,0 +1,125 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+//! A standard interface for price oracles. An oracle is a component that implements the [`GET_PRICE_METHOD`] method,
+//! which takes the base and quote resource addresses and returns [`PriceData`]. Templates that consume prices should
+//! call oracles through [`PriceOracle`] and check the freshness of the price before using it.
+//!
+//! The engine does not have access to the time, so the freshness of a price is measured in epochs. The `timestamp` is
+//! the time that the price was observed by the oracle's off-chain source and can only be checked against a time that
+//! the caller provides.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    component::ComponentManager,
+    consensus::Consensus,
+    models::{Amount, ComponentAddress, ResourceAddress},
+};
+
+/// The name of the component method that returns the latest price. The method must have the signature
+/// `fn get_price(&self, base: ResourceAddress, quote: ResourceAddress) -> PriceData`.
+pub const GET_PRICE_METHOD: &str = "get_price";
+
+/// A price published by an oracle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceData {
+    /// The price of one unit of the base resource in units of the quote resource, multiplied by `10^decimals`
+    pub price: Amount,
+    pub decimals: u8,
+    /// The epoch in which the price was published to the oracle
+    pub epoch: u64,
+    /// The time, in seconds since the unix epoch, that the price was observed by the oracle's source
+    pub timestamp: u64,
+}
+
+impl PriceData {
+    /// Returns the number of epochs that have passed since the price was published
+    pub fn age_in_epochs(&self, current_epoch: u64) -> u64 {
+        current_epoch.saturating_sub(self.epoch)
+    }
+
+    /// Returns true if the price was published at most `max_age_epochs` epochs before `current_epoch`. A price
+    /// published in a future epoch is never fresh.
+    pub fn is_fresh(&self, current_epoch: u64, max_age_epochs: u64) -> bool {
+        self.epoch <= current_epoch && self.age_in_epochs(current_epoch) <= max_age_epochs
+    }
+
+    /// Aborts the transaction if the price was published more than `max_age_epochs` epochs before the current epoch
+    pub fn assert_fresh(&self, max_age_epochs: u64) {
+        let current_epoch = Consensus::current_epoch();
+        assert!(
+            self.is_fresh(current_epoch, max_age_epochs),
+            "Stale oracle price: published in epoch {}, current epoch is {} and the maximum age is {} epoch(s)",
+            self.epoch,
+            current_epoch,
+            max_age_epochs
+        );
+    }
+
+    /// Aborts the transaction if the price was observed before `min_timestamp`
+    pub fn assert_observed_since(&self, min_timestamp: u64) {
+        assert!(
+            self.timestamp >= min_timestamp,
+            "Stale oracle price: observed at {}, which is before {}",
+            self.timestamp,
+            min_timestamp
+        );
+    }
+}
+
+/// Calls a component that implements the oracle interface
+pub struct PriceOracle {
+    component: ComponentManager,
+}
+
+impl PriceOracle {
+    /// Returns a new `PriceOracle` for the oracle component at `address`
+    pub fn new(address: ComponentAddress) -> Self {
+        Self {
+            component: ComponentManager::get(address),
+        }
+    }
+
+    pub fn address(&self) -> ComponentAddress {
+        self.component.component_address()
+    }
+
+    /// Returns the latest price of `base` in `quote`. The freshness of the price is not checked.
+    pub fn get_price(&self, base: ResourceAddress, quote: ResourceAddress) -> PriceData {
+        self.component.call(GET_PRICE_METHOD, args![base, quote])
+    }
+
+    /// Returns the latest price of `base` in `quote`, aborting the transaction if it was published more than
+    /// `max_age_epochs` epochs ago
+    pub fn get_fresh_price(&self, base: ResourceAddress, quote: ResourceAddress, max_age_epochs: u64) -> PriceData {
+        let price = self.get_price(base, quote);
+        price.assert_fresh(max_age_epochs);
+        price
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price_in_epoch(epoch: u64) -> PriceData {
+        PriceData {
+            price: Amount(1_000),
+            decimals: 2,
+            epoch,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn it_checks_the_age_of_the_price() {
+        let price = price_in_epoch(10);
+        assert!(price.is_fresh(10, 0));
+        assert!(price.is_fresh(12, 2));
+        assert!(!price.is_fresh(13, 2));
+        // Published in a future epoch
+        assert!(!price.is_fresh(9, 2));
+        assert_eq!(price.age_in_epochs(9), 0);
+    }
+}
@@ -61,9 +61,11 @@ pub use crate::{
         Vault,
         VaultId,
     },
+    oracle,
     rand,
     resource::{ResourceBuilder, ResourceManager, ResourceType},
     rule,
     template::{BuiltinTemplate, TemplateManager},
+    text,
     warn,
 };
@@ -1,7 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use super::{IMAGE_URL, TOKEN_SYMBOL};
+use super::{DECIMALS, IMAGE_URL, TOKEN_NAME, TOKEN_SYMBOL};
 use crate::{
     args::MintArg,
     auth::{AccessRule, AuthHook, OwnerRule, ResourceAccessRules},
@@ -74,6 +74,26 @@ impl ConfidentialResourceBuilder {
         self
     }
 
+    /// Sets up who can freeze and unfreeze vaults of the resource.
+    /// No tokens can be deposited into or withdrawn from a frozen vault, but they can still be recalled
+    pub fn freezable(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.freezable(rule);
+        self
+    }
+
+    /// Sets up the auditor (regulator) of the resource, who can recall tokens from and freeze any vault of the
+    /// resource
+    pub fn auditor(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.auditor(rule);
+        self
+    }
+
+    /// Sets up who can update the metadata of the resource after it has been created
+    pub fn update_metadata(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.update_metadata(rule);
+        self
+    }
+
     /// Sets up who can withdraw tokens of the resource from any vault
     pub fn withdrawable(mut self, rule: AccessRule) -> Self {
         self.access_rules = self.access_rules.withdrawable(rule);
@@ -104,11 +124,21 @@ impl ConfidentialResourceBuilder {
         self
     }
 
+    /// Sets up the human-readable name of the resource
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        self.add_metadata(TOKEN_NAME, name)
+    }
+
     /// Sets up the image URL of the resource
     pub fn with_image_url(self, url: String) -> Self {
         self.add_metadata(IMAGE_URL, url)
     }
 
+    /// Sets up the number of decimal places used to display amounts of the resource
+    pub fn with_decimals(self, decimals: u8) -> Self {
+        self.add_metadata(DECIMALS, decimals.to_string())
+    }
+
     /// Specify a hook method that will be called to authorize actions on the resource.
     /// The signature of the method must be `fn(action: ResourceAuthAction, caller: CallerContext)`.
     /// The method should panic to deny the action.
@@ -33,7 +33,13 @@ use crate::resource::builder::{
 /// Metadata key used as convention to represent the symbol (a.k.a. ticker) of a token. Meant as a shorthand,
 /// user-friendly identification of the underlying token
 pub const TOKEN_SYMBOL: &str = "SYMBOL";
+/// Metadata key used as convention to represent the full, human-readable name of a token
+pub const TOKEN_NAME: &str = "NAME";
+/// Metadata key used as convention to represent the URL of an image (e.g. an icon) that represents the token
 pub const IMAGE_URL: &str = "IMAGE_URL";
+/// Metadata key used as convention to represent the number of decimal places used when displaying amounts of a
+/// fungible token. Amounts are always stored in the smallest unit, a resource without this key is indivisible.
+pub const DECIMALS: &str = "DECIMALS";
 
 /// Utility for building resources inside templates
 pub struct ResourceBuilder;
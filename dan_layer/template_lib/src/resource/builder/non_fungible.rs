@@ -4,7 +4,7 @@
 use serde::Serialize;
 use tari_bor::to_value;
 
-use super::{IMAGE_URL, TOKEN_SYMBOL};
+use super::{IMAGE_URL, TOKEN_NAME, TOKEN_SYMBOL};
 use crate::{
     args::MintArg,
     auth::{AccessRule, AuthHook, OwnerRule, ResourceAccessRules},
@@ -65,6 +65,26 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    /// Sets up who can freeze and unfreeze vaults of the resource.
+    /// No tokens can be deposited into or withdrawn from a frozen vault, but they can still be recalled
+    pub fn freezable(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.freezable(rule);
+        self
+    }
+
+    /// Sets up the auditor (regulator) of the resource, who can recall tokens from and freeze any vault of the
+    /// resource
+    pub fn auditor(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.auditor(rule);
+        self
+    }
+
+    /// Sets up who can update the metadata of the resource after it has been created
+    pub fn update_metadata(mut self, rule: AccessRule) -> Self {
+        self.access_rules = self.access_rules.update_metadata(rule);
+        self
+    }
+
     /// Sets up who can withdraw tokens of the resource from any vault
     pub fn withdrawable(mut self, rule: AccessRule) -> Self {
         self.access_rules = self.access_rules.withdrawable(rule);
@@ -101,6 +121,11 @@ impl NonFungibleResourceBuilder {
         self
     }
 
+    /// Sets up the human-readable name of the resource
+    pub fn with_name<S: Into<String>>(self, name: S) -> Self {
+        self.add_metadata(TOKEN_NAME, name)
+    }
+
     /// Sets up the image URL of the resource
     pub fn with_image_url(self, url: String) -> Self {
         self.add_metadata(IMAGE_URL, url)
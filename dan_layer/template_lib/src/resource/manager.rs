@@ -29,6 +29,7 @@ use tari_template_abi::{call_engine, rust::collections::BTreeMap, EngineOp};
 use crate::{
     args::{
         CreateResourceArg,
+        FreezeVaultArg,
         InvokeResult,
         MintArg,
         MintResourceArg,
@@ -38,6 +39,7 @@ use crate::{
         ResourceGetNonFungibleArg,
         ResourceInvokeArg,
         ResourceRef,
+        ResourceUpdateMetadataArg,
         ResourceUpdateNonFungibleDataArg,
     },
     auth::{OwnerRule, ResourceAccessRules},
@@ -320,6 +322,45 @@ impl ResourceManager {
         })
     }
 
+    /// Freezes the specified vault. No tokens can be deposited into or withdrawn from a frozen vault until it is
+    /// unfrozen, but tokens can still be recalled.
+    ///
+    /// It will panic if:
+    /// * The caller doesn't have permissions (via access rules) for freezing
+    /// * The vault does not contain tokens of the resource
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_id` - The vault to freeze
+    pub fn freeze_vault(&self, vault_id: VaultId) {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::FreezeVault,
+            args: invoke_args![FreezeVaultArg { vault_id }],
+        });
+
+        resp.decode().expect("[freeze_vault] Failed")
+    }
+
+    /// Unfreezes the specified vault
+    ///
+    /// It will panic if:
+    /// * The caller doesn't have permissions (via access rules) for freezing
+    /// * The vault does not contain tokens of the resource
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_id` - The vault to unfreeze
+    pub fn unfreeze_vault(&self, vault_id: VaultId) {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::UnfreezeVault,
+            args: invoke_args![FreezeVaultArg { vault_id }],
+        });
+
+        resp.decode().expect("[unfreeze_vault] Failed")
+    }
+
     /// Returns the total supply of tokens for the resource being managed
     pub fn total_supply(&self) -> Amount {
         let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
@@ -358,6 +399,42 @@ impl ResourceManager {
         resp.decode().expect("[update_non_fungible_data] Failed")
     }
 
+    /// Returns the metadata of the resource being managed
+    pub fn metadata(&self) -> Metadata {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::GetMetadata,
+            args: invoke_args![],
+        });
+
+        resp.decode().expect("[metadata] Failed to decode Metadata")
+    }
+
+    /// Sets the metadata entry `key` of the resource being managed to `value`. Well-known entries (e.g. `DECIMALS`)
+    /// must have a valid value.
+    ///
+    /// It will panic if:
+    /// * The caller is not the owner of the resource and doesn't have permissions (via access rules) for updating
+    ///   metadata
+    /// * The value of a well-known entry is invalid
+    pub fn update_metadata<K: Into<String>, V: Into<String>>(&self, key: K, value: V) {
+        self.update_metadata_internal(ResourceUpdateMetadataArg {
+            key: key.into(),
+            value: Some(value.into()),
+        })
+    }
+
+    /// Removes the metadata entry `key` from the resource being managed, if it exists
+    ///
+    /// It will panic if the caller is not the owner of the resource and doesn't have permissions (via access rules) for
+    /// updating metadata
+    pub fn remove_metadata<K: Into<String>>(&self, key: K) {
+        self.update_metadata_internal(ResourceUpdateMetadataArg {
+            key: key.into(),
+            value: None,
+        })
+    }
+
     /// Updates access rules that determine who can operate the resource
     /// It will panic if the caller doesn't have permissions for updating access rules
     pub fn set_access_rules(&self, access_rules: ResourceAccessRules) {
@@ -391,4 +468,14 @@ impl ResourceManager {
         let bucket_id = resp.decode().expect("Failed to decode Bucket");
         Bucket::from_id(bucket_id)
     }
+
+    fn update_metadata_internal(&self, arg: ResourceUpdateMetadataArg) {
+        let resp: InvokeResult = call_engine(EngineOp::ResourceInvoke, &ResourceInvokeArg {
+            resource_ref: self.expect_resource_address(),
+            action: ResourceAction::UpdateMetadata,
+            args: invoke_args![arg],
+        });
+
+        resp.decode().expect("[update_metadata] Failed")
+    }
 }
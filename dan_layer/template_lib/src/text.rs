@@ -0,0 +1,56 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+//! Text utilities that are executed by the engine. Regex matching and unicode normalization are expensive to implement
+//! in WASM and their results may differ between compiler and crate versions, so the engine executes them
+//! deterministically and charges gas for the length of the input.
+//!
+//! Regexes use the syntax of the [regex](https://docs.rs/regex) crate, which guarantees matching in linear time.
+
+use tari_template_abi::{call_engine, EngineOp};
+
+pub use crate::args::NormalizationForm;
+use crate::args::{InvokeResult, TextAction, TextInvokeArg};
+
+/// Returns true if the regex `pattern` matches anywhere in `text`
+pub fn regex_is_match(pattern: &str, text: &str) -> bool {
+    invoke(TextAction::RegexIsMatch {
+        pattern: pattern.into(),
+        text: text.into(),
+    })
+    .decode()
+    .expect("Failed to decode regex match result")
+}
+
+/// Returns the leftmost match of the regex `pattern` in `text`, if any
+pub fn regex_find(pattern: &str, text: &str) -> Option<String> {
+    invoke(TextAction::RegexFind {
+        pattern: pattern.into(),
+        text: text.into(),
+    })
+    .decode()
+    .expect("Failed to decode regex find result")
+}
+
+/// Replaces all matches of the regex `pattern` in `text` with `replacement`. The replacement may refer to capture
+/// groups e.g. `$1` or `${name}`.
+pub fn regex_replace_all(pattern: &str, text: &str, replacement: &str) -> String {
+    invoke(TextAction::RegexReplaceAll {
+        pattern: pattern.into(),
+        text: text.into(),
+        replacement: replacement.into(),
+    })
+    .decode()
+    .expect("Failed to decode regex replace result")
+}
+
+/// Returns `text` in the unicode normalization `form`
+pub fn normalize(text: &str, form: NormalizationForm) -> String {
+    invoke(TextAction::Normalize { text: text.into(), form })
+        .decode()
+        .expect("Failed to decode normalized text")
+}
+
+fn invoke(action: TextAction) -> InvokeResult {
+    call_engine(EngineOp::TextInvoke, &TextInvokeArg { action })
+}
@@ -0,0 +1,11 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tari_template_test_tooling::fuzzing;
+
+fuzz_target!(|data: &[u8]| {
+    fuzzing::fuzz_transactions(data);
+});
@@ -0,0 +1,284 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Cross-version engine compatibility matrix. A suite is a sequence of transaction manifests, so that it does not
+//! depend on the API of any one engine version. The suite is executed on a fresh ledger of each engine in the matrix
+//! and the outcome of every step is compared with the outcome of the first (baseline) engine.
+//!
+//! The current engine is always the baseline. Previous engine versions are selected with `engine-v<version>` feature
+//! flags: the feature enables the previous engine as a renamed dependency, and the engine is added to
+//! [EngineMatrix::from_features] by implementing [MatrixEngine] for it.
+
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use tari_dan_engine::wasm::ENGINE_TARI_VERSION;
+use tari_engine_types::commit_result::ExecuteResult;
+use tari_transaction_manifest::ManifestValue;
+
+use crate::TemplateTest;
+
+/// A step of a [MatrixSuite]
+#[derive(Debug, Clone)]
+pub struct MatrixStep {
+    pub name: String,
+    /// The body of the manifest main function. The templates of the suite are imported by name.
+    pub manifest: String,
+    pub variables: Vec<(String, ManifestValue)>,
+}
+
+/// The templates and manifest steps that are executed on every engine of an [EngineMatrix]. Each step is signed by the
+/// default test key and executed in order. Accepted steps are committed, so later steps may use their outputs.
+#[derive(Debug, Clone, Default)]
+pub struct MatrixSuite {
+    templates: Vec<PathBuf>,
+    steps: Vec<MatrixStep>,
+}
+
+impl MatrixSuite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_template<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.templates.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn add_step(&mut self, name: &str, manifest: &str) -> &mut Self {
+        self.add_step_with_variables(name, manifest, [])
+    }
+
+    pub fn add_step_with_variables<'a, I: IntoIterator<Item = (&'a str, ManifestValue)>>(
+        &mut self,
+        name: &str,
+        manifest: &str,
+        variables: I,
+    ) -> &mut Self {
+        self.steps.push(MatrixStep {
+            name: name.to_string(),
+            manifest: manifest.to_string(),
+            variables: variables.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        });
+        self
+    }
+
+    pub fn templates(&self) -> &[PathBuf] {
+        &self.templates
+    }
+
+    pub fn steps(&self) -> &[MatrixStep] {
+        &self.steps
+    }
+}
+
+/// The observable behavior of an engine for a single step. Only engine-independent values are kept so that outcomes
+/// of different engine versions can be compared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepOutcome {
+    pub accepted: bool,
+    pub reject_reason: Option<String>,
+    pub logs: Vec<String>,
+    pub substates_created: usize,
+    pub substates_destroyed: usize,
+}
+
+impl StepOutcome {
+    pub fn from_result(result: &ExecuteResult) -> Self {
+        let finalize = &result.finalize;
+        let diff = finalize.result.accept();
+        Self {
+            accepted: finalize.full_reject().is_none(),
+            reject_reason: finalize.full_reject().map(|reason| reason.to_string()),
+            logs: finalize.logs.iter().map(|log| log.message.clone()).collect(),
+            substates_created: diff.map_or(0, |diff| diff.up_len()),
+            substates_destroyed: diff.map_or(0, |diff| diff.down_len()),
+        }
+    }
+
+    /// The outcome of a step that could not be executed at all, e.g. an invalid manifest
+    pub fn failed<T: Display>(err: T) -> Self {
+        Self {
+            accepted: false,
+            reject_reason: Some(err.to_string()),
+            logs: vec![],
+            substates_created: 0,
+            substates_destroyed: 0,
+        }
+    }
+}
+
+impl Display for StepOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.reject_reason {
+            Some(ref reason) => write!(f, "rejected ({})", reason)?,
+            None => write!(f, "accepted")?,
+        }
+        write!(
+            f,
+            ", {} substate(s) created, {} substate(s) destroyed, {} log(s)",
+            self.substates_created,
+            self.substates_destroyed,
+            self.logs.len()
+        )
+    }
+}
+
+/// An engine version that a [MatrixSuite] can be executed on
+pub trait MatrixEngine {
+    fn version(&self) -> &str;
+
+    /// Executes the suite on a fresh ledger and returns the outcome of each step, in order
+    fn run_suite(&self, suite: &MatrixSuite) -> Vec<StepOutcome>;
+}
+
+/// The engine in this workspace
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurrentEngine;
+
+impl MatrixEngine for CurrentEngine {
+    fn version(&self) -> &str {
+        ENGINE_TARI_VERSION
+    }
+
+    fn run_suite(&self, suite: &MatrixSuite) -> Vec<StepOutcome> {
+        let mut test = TemplateTest::new(suite.templates());
+        test.enable_deterministic_signing();
+        let proofs = vec![test.get_test_proof()];
+
+        suite
+            .steps()
+            .iter()
+            .map(|step| {
+                let variables = step.variables.iter().map(|(k, v)| (k.as_str(), v.clone()));
+                let instructions = match test.parse_manifest_instructions(&step.manifest, variables) {
+                    Ok(instructions) => instructions,
+                    Err(err) => return StepOutcome::failed(err),
+                };
+                match test.try_execute_instructions(vec![], instructions, proofs.clone()) {
+                    Ok(result) => {
+                        if let Some(diff) = result.finalize.result.accept() {
+                            test.commit_diff(diff);
+                        }
+                        StepOutcome::from_result(&result)
+                    },
+                    Err(err) => StepOutcome::failed(err),
+                }
+            })
+            .collect()
+    }
+}
+
+/// A step for which an engine behaved differently to the baseline engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BehaviorDifference {
+    pub engine_version: String,
+    pub step: String,
+    /// The outcome of the baseline engine, or None if the baseline engine did not produce an outcome for the step
+    pub expected: Option<StepOutcome>,
+    /// The outcome of the engine, or None if the engine did not produce an outcome for the step
+    pub actual: Option<StepOutcome>,
+}
+
+impl Display for BehaviorDifference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let display = |outcome: &Option<StepOutcome>| {
+            outcome
+                .as_ref()
+                .map_or_else(|| "no outcome".to_string(), |outcome| outcome.to_string())
+        };
+        write!(
+            f,
+            "engine {} step '{}': expected {}, got {}",
+            self.engine_version,
+            self.step,
+            display(&self.expected),
+            display(&self.actual)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatrixReport {
+    pub baseline_version: String,
+    pub engine_versions: Vec<String>,
+    pub differences: Vec<BehaviorDifference>,
+}
+
+impl MatrixReport {
+    pub fn is_compatible(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// Panics with every behavior difference if any engine differs from the baseline
+    pub fn assert_compatible(&self) {
+        assert!(
+            self.is_compatible(),
+            "Behavior differs from engine {}:\n{}",
+            self.baseline_version,
+            self.differences
+                .iter()
+                .map(|diff| format!("- {}", diff))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
+
+/// Executes a [MatrixSuite] on several engine versions and reports behavior differences to the first engine
+pub struct EngineMatrix {
+    engines: Vec<Box<dyn MatrixEngine>>,
+}
+
+impl EngineMatrix {
+    /// A matrix of the current engine and every previous engine version enabled by an `engine-v<version>` feature
+    pub fn from_features() -> Self {
+        Self {
+            engines: vec![Box::new(CurrentEngine)],
+        }
+    }
+
+    /// A matrix of the given engines. The first engine is the baseline.
+    pub fn with_engines(engines: Vec<Box<dyn MatrixEngine>>) -> Self {
+        Self { engines }
+    }
+
+    pub fn add_engine<T: MatrixEngine + 'static>(&mut self, engine: T) -> &mut Self {
+        self.engines.push(Box::new(engine));
+        self
+    }
+
+    pub fn run(&self, suite: &MatrixSuite) -> MatrixReport {
+        let (baseline, others) = self
+            .engines
+            .split_first()
+            .expect("EngineMatrix must contain at least one engine");
+        let expected = baseline.run_suite(suite);
+
+        let mut differences = Vec::new();
+        for engine in others {
+            let actual = engine.run_suite(suite);
+            for (i, step) in suite.steps().iter().enumerate() {
+                let expected = expected.get(i);
+                let actual = actual.get(i);
+                if expected != actual {
+                    differences.push(BehaviorDifference {
+                        engine_version: engine.version().to_string(),
+                        step: step.name.clone(),
+                        expected: expected.cloned(),
+                        actual: actual.cloned(),
+                    });
+                }
+            }
+        }
+
+        MatrixReport {
+            baseline_version: baseline.version().to_string(),
+            engine_versions: self.engines.iter().map(|e| e.version().to_string()).collect(),
+            differences,
+        }
+    }
+}
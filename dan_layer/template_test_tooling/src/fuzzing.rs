@@ -0,0 +1,326 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Entry points for the engine fuzz targets in `dan_layer/template_test_tooling/fuzz`. Each input is decoded into a
+//! sequence of transactions that call the functions and methods of the engine test templates with generated arguments.
+//! The transactions are signed deterministically and executed against an in-memory store, and the result of each
+//! accepted transaction is committed so that later transactions can call the components that it created.
+//!
+//! The engine must never panic, and the substate diff of every accepted transaction must hold these invariants:
+//! - every downed substate exists in the store at the downed version (no dangling downs)
+//! - a substate is only downed and upped once per transaction
+//! - an existing substate is upped at the version after the downed version, and a new substate is upped at version 0
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::OnceLock,
+};
+
+use tari_bor::Value;
+use tari_dan_engine::abi::{ArgDef, FunctionDef, Type};
+use tari_engine_types::{
+    instruction::Instruction,
+    substate::{SubstateDiff, SubstateId},
+};
+use tari_template_lib::{
+    args::{Arg, LogLevel},
+    models::{Amount, ComponentAddress, ResourceAddress, TemplateAddress},
+};
+
+use crate::{test_faucet_component, Package, TemplateTest};
+
+/// The engine test templates (in `dan_layer/engine/tests/templates`) that are called by the fuzzer
+const TEST_TEMPLATES: &[&str] = &["state", "hello_world", "tuples", "resource", "nft/basic_nft", "events"];
+const MAX_TRANSACTIONS: usize = 4;
+const MAX_INSTRUCTIONS: usize = 8;
+/// The maximum nesting of generated Vec and tuple arguments
+const MAX_ARG_DEPTH: usize = 3;
+const MAX_STRING_LEN: u8 = 32;
+const WORKSPACE_KEYS: &[&[u8]] = &[b"a", b"b", b"c"];
+
+static PACKAGE: OnceLock<Package> = OnceLock::new();
+
+/// Executes the transactions generated from the input and checks the substate invariants of each accepted transaction
+pub fn fuzz_transactions(data: &[u8]) {
+    let package = PACKAGE.get_or_init(|| {
+        let templates_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../engine/tests/templates");
+        TemplateTest::build_package(TEST_TEMPLATES.iter().map(|name| templates_dir.join(name)))
+    });
+
+    let mut test = TemplateTest::with_package(package.clone());
+    test.enable_deterministic_signing();
+    let mut generator = InstructionGenerator::new(FuzzInput::new(data), package);
+
+    for _ in 0..MAX_TRANSACTIONS {
+        if generator.input.is_empty() {
+            break;
+        }
+        let instructions = generator.generate_instructions(&test);
+        // Invalid transactions are expected, only panics and broken invariants are failures
+        let Ok(result) = test.try_execute_instructions(vec![], instructions, vec![test.get_test_proof()]) else {
+            continue;
+        };
+        let Some(diff) = result.finalize.result.accept() else {
+            continue;
+        };
+        check_substate_invariants(&test, diff);
+        generator.add_outputs(diff);
+        test.commit_diff(diff);
+    }
+}
+
+fn check_substate_invariants(test: &TemplateTest, diff: &SubstateDiff) {
+    let store = test.read_only_state_store();
+
+    let mut downs = HashMap::new();
+    for (id, version) in diff.down_iter() {
+        let substate = store
+            .get_substate(id)
+            .unwrap_or_else(|_| panic!("Dangling down: substate {} does not exist", id));
+        assert_eq!(
+            substate.version(),
+            *version,
+            "Substate {} was downed at version {} but the current version is {}",
+            id,
+            version,
+            substate.version()
+        );
+        assert!(downs.insert(id, *version).is_none(), "Substate {} was downed twice", id);
+    }
+
+    let mut ups = HashSet::new();
+    for (id, substate) in diff.up_iter() {
+        assert!(ups.insert(id), "Substate {} was upped twice", id);
+        match downs.get(id) {
+            Some(version) => assert_eq!(
+                substate.version(),
+                version + 1,
+                "Substate {} was downed at version {} and upped at version {}",
+                id,
+                version,
+                substate.version()
+            ),
+            None => {
+                assert!(
+                    store.get_substate(id).is_err(),
+                    "Substate {} was upped without downing the existing substate",
+                    id
+                );
+                assert_eq!(
+                    substate.version(),
+                    0,
+                    "New substate {} was upped at version {}",
+                    id,
+                    substate.version()
+                );
+            },
+        }
+    }
+}
+
+struct InstructionGenerator<'a> {
+    input: FuzzInput<'a>,
+    /// Functions that do not take self, sorted by template address so that inputs are reproducible
+    functions: Vec<(TemplateAddress, FunctionDef)>,
+    methods: HashMap<TemplateAddress, Vec<FunctionDef>>,
+    components: Vec<ComponentAddress>,
+    resources: Vec<ResourceAddress>,
+}
+
+impl<'a> InstructionGenerator<'a> {
+    fn new(input: FuzzInput<'a>, package: &Package) -> Self {
+        let mut template_defs = package.get_template_defs().into_iter().collect::<Vec<_>>();
+        template_defs.sort_by_key(|(address, _)| *address);
+
+        let mut functions = Vec::new();
+        let mut methods = HashMap::<_, Vec<_>>::new();
+        for (address, def) in template_defs {
+            for function in def.functions() {
+                if is_method(function) {
+                    methods.entry(address).or_default().push(function.clone());
+                } else {
+                    functions.push((address, function.clone()));
+                }
+            }
+        }
+
+        Self {
+            input,
+            functions,
+            methods,
+            components: vec![test_faucet_component()],
+            resources: vec![],
+        }
+    }
+
+    fn generate_instructions(&mut self, test: &TemplateTest) -> Vec<Instruction> {
+        let num_instructions = usize::from(self.input.next_u8()) % MAX_INSTRUCTIONS + 1;
+        (0..num_instructions)
+            .filter_map(|_| self.generate_instruction(test))
+            .collect()
+    }
+
+    fn generate_instruction(&mut self, test: &TemplateTest) -> Option<Instruction> {
+        let instruction = match self.input.next_u8() % 5 {
+            0 => {
+                let (template_address, function) = self.input.choose(&self.functions)?.clone();
+                Instruction::CallFunction {
+                    template_address,
+                    function: function.name.clone(),
+                    args: self.generate_args(&function.arguments),
+                }
+            },
+            1 => {
+                let component_address = *self.input.choose(&self.components)?;
+                let component = test.read_only_state_store().get_component(component_address).ok()?;
+                let method = self
+                    .input
+                    .choose(self.methods.get(&component.template_address)?)?
+                    .clone();
+                Instruction::CallMethod {
+                    component_address,
+                    method: method.name.clone(),
+                    args: self.generate_args(&method.arguments[1..]),
+                }
+            },
+            2 => Instruction::PutLastInstructionOutputOnWorkspace {
+                key: self.workspace_key(),
+            },
+            3 => Instruction::DropAllProofsInWorkspace,
+            _ => Instruction::EmitLog {
+                level: LogLevel::Info,
+                message: self.input.next_string(),
+            },
+        };
+        Some(instruction)
+    }
+
+    fn generate_args(&mut self, arguments: &[ArgDef]) -> Vec<Arg> {
+        arguments
+            .iter()
+            .map(|arg| {
+                // Some arguments refer to the workspace so that the outputs of previous instructions (e.g. buckets)
+                // can be passed
+                if self.input.next_u8() % 8 == 0 {
+                    return Arg::Workspace(self.workspace_key());
+                }
+                let value = self.generate_value(&arg.arg_type, 0);
+                Arg::literal(value).expect("CBOR values can always be encoded")
+            })
+            .collect()
+    }
+
+    fn generate_value(&mut self, ty: &Type, depth: usize) -> Value {
+        if depth > MAX_ARG_DEPTH {
+            return Value::Null;
+        }
+        match ty {
+            Type::Unit => Value::Null,
+            Type::Bool => Value::Bool(self.input.next_u8() % 2 == 1),
+            Type::I8 => Value::Integer((self.input.next_u8() as i8).into()),
+            Type::I16 => Value::Integer((self.input.next_u64() as i16).into()),
+            Type::I32 => Value::Integer((self.input.next_u64() as i32).into()),
+            Type::I64 | Type::I128 => Value::Integer((self.input.next_u64() as i64).into()),
+            Type::U8 => Value::Integer(self.input.next_u8().into()),
+            Type::U16 => Value::Integer((self.input.next_u64() as u16).into()),
+            Type::U32 => Value::Integer((self.input.next_u64() as u32).into()),
+            Type::U64 | Type::U128 => Value::Integer(self.input.next_u64().into()),
+            Type::String => Value::Text(self.input.next_string()),
+            Type::Vec(ty) => {
+                let len = self.input.next_u8() % 4;
+                Value::Array((0..len).map(|_| self.generate_value(ty, depth + 1)).collect())
+            },
+            Type::Tuple(types) => Value::Array(types.iter().map(|ty| self.generate_value(ty, depth + 1)).collect()),
+            Type::Other { name } => self.generate_other_value(name),
+        }
+    }
+
+    fn generate_other_value(&mut self, name: &str) -> Value {
+        let value = match name {
+            "Amount" => tari_bor::to_value(&Amount::new(self.input.next_u64() as i64)),
+            "ComponentAddress" => match self.input.choose(&self.components) {
+                Some(address) => tari_bor::to_value(address),
+                None => return Value::Null,
+            },
+            "ResourceAddress" => match self.input.choose(&self.resources) {
+                Some(address) => tari_bor::to_value(address),
+                None => return Value::Null,
+            },
+            // Types that cannot be generated are expected to fail to decode
+            _ => return Value::Null,
+        };
+        value.expect("template types can always be encoded")
+    }
+
+    fn workspace_key(&mut self) -> Vec<u8> {
+        self.input
+            .choose(WORKSPACE_KEYS)
+            .copied()
+            .unwrap_or_default()
+            .to_vec()
+    }
+
+    /// Records the components and resources created by a committed transaction so that later instructions can use them
+    fn add_outputs(&mut self, diff: &SubstateDiff) {
+        for (id, _) in diff.up_iter() {
+            match id {
+                SubstateId::Component(address) if !self.components.contains(address) => {
+                    self.components.push(*address);
+                },
+                SubstateId::Resource(address) if !self.resources.contains(address) => {
+                    self.resources.push(*address);
+                },
+                _ => {},
+            }
+        }
+    }
+}
+
+fn is_method(function: &FunctionDef) -> bool {
+    function.arguments.first().is_some_and(|arg| arg.name == "self")
+}
+
+/// Reads values from the fuzz input. Once the input is exhausted, zeros are returned.
+struct FuzzInput<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> FuzzInput<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn next_bytes(&mut self, len: usize) -> &'a [u8] {
+        let (bytes, rest) = self.data.split_at(len.min(self.data.len()));
+        self.data = rest;
+        bytes
+    }
+
+    fn next_u8(&mut self) -> u8 {
+        self.next_bytes(1).first().copied().unwrap_or(0)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        let bytes = self.next_bytes(buf.len());
+        buf[..bytes.len()].copy_from_slice(bytes);
+        u64::from_le_bytes(buf)
+    }
+
+    fn next_string(&mut self) -> String {
+        let len = self.next_u8() % MAX_STRING_LEN;
+        String::from_utf8_lossy(self.next_bytes(usize::from(len))).into_owned()
+    }
+
+    fn choose<'b, T>(&mut self, items: &'b [T]) -> Option<&'b T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(usize::from(self.next_u8()) % items.len())
+    }
+}
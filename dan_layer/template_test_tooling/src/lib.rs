@@ -1,13 +1,19 @@
 //  Copyright 2022 The Tari Project
 //  SPDX-License-Identifier: BSD-3-Clause
 
+#[cfg(feature = "engine-matrix")]
+pub mod engine_matrix;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
 mod package_builder;
 mod read_only_state_store;
+mod scenario;
 pub mod support;
 mod template_test;
 mod track_calls;
 
 pub use package_builder::Package;
+pub use scenario::{Identity, Scenario, ScenarioStep};
 pub use template_test::{test_faucet_component, SubstateType, TemplateTest};
 
 pub mod crypto {
@@ -11,6 +11,7 @@ use tari_common_types::types::PublicKey;
 use tari_dan_common_types::services::template_provider::TemplateProvider;
 use tari_dan_engine::{
     abi::TemplateDef,
+    flow::{FlowEngineError, FlowFactory},
     template::{LoadedTemplate, TemplateLoaderError, TemplateModuleLoader},
     wasm::{compile::compile_template, WasmModule},
 };
@@ -99,6 +100,8 @@ impl PackageBuilder {
 pub enum PackageError {
     #[error("Template load error: {0}")]
     TemplateLoad(#[from] TemplateLoaderError),
+    #[error("Flow template error: {0}")]
+    FlowTemplate(#[from] FlowEngineError),
 }
 impl TemplateProvider for Package {
     type Error = PackageError;
@@ -123,4 +126,18 @@ impl TemplateProvider for Package {
             .insert(template_address, WasmModule::load_template_from_code(template)?);
         Ok(())
     }
+
+    fn add_flow_template(
+        &self,
+        _author_public_key: PublicKey,
+        template_address: tari_engine_types::TemplateAddress,
+        flow_json: &str,
+    ) -> Result<(), Self::Error> {
+        let factory = FlowFactory::try_from_json::<Self>(flow_json.as_bytes())?;
+        self.templates
+            .lock()
+            .unwrap()
+            .insert(template_address, LoadedTemplate::Flow(factory));
+        Ok(())
+    }
 }
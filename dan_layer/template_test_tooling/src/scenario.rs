@@ -0,0 +1,219 @@
+//  Copyright 2024 The Tari Project
+//  SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use tari_crypto::ristretto::{RistrettoPublicKey, RistrettoSecretKey};
+use tari_engine_types::{
+    commit_result::{ExecuteResult, RejectReason},
+    substate::{SubstateDiff, SubstateId},
+};
+use tari_template_lib::models::{Amount, ComponentAddress, NonFungibleAddress};
+use tari_transaction::TransactionBuilder;
+
+use crate::{SubstateType, TemplateTest};
+
+/// The amount of coins each identity account is funded with
+const IDENTITY_FUNDS: Amount = Amount::new(1_000_000_000);
+
+/// A signing identity and its account
+#[derive(Debug, Clone)]
+pub struct Identity {
+    pub account: ComponentAddress,
+    pub owner_proof: NonFungibleAddress,
+    pub public_key: RistrettoPublicKey,
+    pub secret_key: RistrettoSecretKey,
+}
+
+/// Executes a sequence of transactions against the in-memory ledger of a [TemplateTest]. Each transaction is signed by
+/// one of the named identities of the scenario, and the result of each step is kept so that tests can assert on the
+/// substate diffs and fees of the whole scenario.
+pub struct Scenario<'a> {
+    test: &'a mut TemplateTest,
+    identities: HashMap<String, Identity>,
+    steps: Vec<ScenarioStep>,
+    max_fee: Option<Amount>,
+}
+
+impl<'a> Scenario<'a> {
+    pub fn new(test: &'a mut TemplateTest) -> Self {
+        Self {
+            test,
+            identities: HashMap::new(),
+            steps: Vec::new(),
+            max_fee: None,
+        }
+    }
+
+    /// Each transaction pays fees of up to `max_fee` from the account of its signer. Fees must be enabled on the
+    /// [TemplateTest] for fees to be charged.
+    pub fn pay_fees_from_signer(&mut self, max_fee: Amount) -> &mut Self {
+        self.max_fee = Some(max_fee);
+        self
+    }
+
+    /// Creates a new identity with a funded account. Panics if the name is already used.
+    pub fn add_identity(&mut self, name: &str) -> &Identity {
+        assert!(
+            !self.identities.contains_key(name),
+            "Identity '{}' already exists in the scenario",
+            name
+        );
+        let (account, owner_proof, secret_key, public_key) = self.test.create_custom_funded_account(IDENTITY_FUNDS);
+        self.identities.entry(name.to_string()).or_insert(Identity {
+            account,
+            owner_proof,
+            public_key,
+            secret_key,
+        })
+    }
+
+    /// Returns the identity with the given name. Panics if the identity does not exist.
+    pub fn identity(&self, name: &str) -> &Identity {
+        self.identities
+            .get(name)
+            .unwrap_or_else(|| panic!("Identity '{}' does not exist in the scenario", name))
+    }
+
+    pub fn test(&mut self) -> &mut TemplateTest {
+        self.test
+    }
+
+    /// Signs the transaction as `signer` and executes it with the signer's ownership proof. Panics if the transaction
+    /// fails.
+    pub fn execute(&mut self, signer: &str, builder: TransactionBuilder) -> &ScenarioStep {
+        let step = self.execute_step(signer, builder);
+        if let Some(reason) = step.result.finalize.full_reject() {
+            panic!("Step {} (signed by '{}') failed: {}", step.index, signer, reason);
+        }
+        step
+    }
+
+    /// Signs the transaction as `signer` and executes it with the signer's ownership proof. Panics if the transaction
+    /// succeeds.
+    pub fn execute_expect_failure(&mut self, signer: &str, builder: TransactionBuilder) -> RejectReason {
+        let step = self.execute_step(signer, builder);
+        match step.result.finalize.full_reject() {
+            Some(reason) => reason.clone(),
+            None => panic!(
+                "Step {} (signed by '{}') succeeded but it was expected to fail",
+                step.index, signer
+            ),
+        }
+    }
+
+    /// Returns all executed steps in the order that they were executed
+    pub fn steps(&self) -> &[ScenarioStep] {
+        &self.steps
+    }
+
+    /// Returns the step at the given index. Panics if the step has not been executed.
+    pub fn step(&self, index: usize) -> &ScenarioStep {
+        self.steps
+            .get(index)
+            .unwrap_or_else(|| panic!("Step {} has not been executed", index))
+    }
+
+    /// The total fees charged for all steps of the scenario
+    pub fn total_fees_charged(&self) -> Amount {
+        self.steps.iter().map(|step| step.fee_charged()).sum()
+    }
+
+    fn execute_step(&mut self, signer: &str, builder: TransactionBuilder) -> &ScenarioStep {
+        let identity = self.identity(signer).clone();
+        let builder = match self.max_fee {
+            Some(max_fee) => builder.fee_transaction_pay_from_component(identity.account, max_fee),
+            None => builder,
+        };
+        let transaction = builder.sign(&identity.secret_key).build();
+        let result = self
+            .test
+            .execute_and_commit_on_success(transaction, vec![identity.owner_proof]);
+
+        self.steps.push(ScenarioStep {
+            index: self.steps.len(),
+            signer: signer.to_string(),
+            result,
+        });
+        self.steps.last().unwrap()
+    }
+}
+
+/// The result of a transaction executed in a [Scenario]
+#[derive(Debug, Clone)]
+pub struct ScenarioStep {
+    pub index: usize,
+    /// The name of the identity that signed the transaction
+    pub signer: String,
+    pub result: ExecuteResult,
+}
+
+impl ScenarioStep {
+    /// Returns the committed substate diff of the step. Panics if the transaction was rejected.
+    pub fn diff(&self) -> &SubstateDiff {
+        self.result.expect_finalization_success()
+    }
+
+    /// Returns the ids of the substates of the given type that were created or updated by the step
+    pub fn up_ids(&self, ty: SubstateType) -> Vec<&SubstateId> {
+        self.diff()
+            .up_iter()
+            .map(|(id, _)| id)
+            .filter(|id| ty.matches(id))
+            .collect()
+    }
+
+    /// Returns the ids of the substates that were downed by the step
+    pub fn down_ids(&self) -> Vec<&SubstateId> {
+        self.diff().down_iter().map(|(id, _)| id).collect()
+    }
+
+    pub fn assert_up(&self, id: &SubstateId) -> &Self {
+        assert!(
+            self.diff().up_iter().any(|(up, _)| up == id),
+            "Step {}: expected substate {} to be up",
+            self.index,
+            id
+        );
+        self
+    }
+
+    pub fn assert_down(&self, id: &SubstateId) -> &Self {
+        assert!(
+            self.diff().down_iter().any(|(down, _)| down == id),
+            "Step {}: expected substate {} to be down",
+            self.index,
+            id
+        );
+        self
+    }
+
+    pub fn fee_charged(&self) -> Amount {
+        self.result.finalize.fee_receipt.total_fees_charged()
+    }
+
+    pub fn assert_fee_at_most(&self, max_fee: Amount) -> &Self {
+        let fee = self.fee_charged();
+        assert!(
+            fee <= max_fee,
+            "Step {}: fee {} exceeds the maximum of {}",
+            self.index,
+            fee,
+            max_fee
+        );
+        self
+    }
+
+    /// Decodes the result of the instruction at the given index. Panics if the result cannot be decoded.
+    pub fn output<T: DeserializeOwned>(&self, instruction_index: usize) -> T {
+        self.result.finalize.execution_results[instruction_index]
+            .decode()
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Step {}: failed to decode the result of instruction {}: {}",
+                    self.index, instruction_index, e
+                )
+            })
+    }
+}
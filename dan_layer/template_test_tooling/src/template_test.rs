@@ -18,10 +18,14 @@ use tari_crypto::{
     ristretto::{RistrettoPublicKey, RistrettoSecretKey},
     tari_utilities::{hex::Hex, ByteArray},
 };
-use tari_dan_common_types::{crypto::create_key_pair_from_seed, VersionedSubstateId};
+use tari_dan_common_types::{
+    crypto::create_key_pair_from_seed,
+    services::template_provider::TemplateProvider,
+    VersionedSubstateId,
+};
 use tari_dan_engine::{
     fees::{FeeModule, FeeTable},
-    runtime::{AuthParams, RuntimeModule},
+    runtime::{AuthParams, GasLimits, MemoryLimits, RuntimeModule, SandboxLimits, TextLimits},
     state_store::{memory::MemoryStateStore, new_memory_store, StateWriter},
     template::LoadedTemplate,
     transaction::{TransactionError, TransactionProcessor, TransactionProcessorConfig},
@@ -32,8 +36,9 @@ use tari_engine_types::{
     component::{ComponentBody, ComponentHeader},
     id_provider::{IdProvider, ObjectIds},
     instruction::Instruction,
+    published_template::TemplateType,
     resource_container::ResourceContainer,
-    substate::{Substate, SubstateDiff, SubstateId},
+    substate::{Substate, SubstateDiff, SubstateId, SubstateValue},
     vault::Vault,
     virtual_substate::{VirtualSubstate, VirtualSubstateId, VirtualSubstates},
 };
@@ -47,10 +52,10 @@ use tari_template_lib::{
     prelude::{ComponentAccessRules, CONFIDENTIAL_TARI_RESOURCE_ADDRESS},
     Hash,
 };
-use tari_transaction::Transaction;
+use tari_transaction::{Transaction, TransactionBuilder};
 use tari_transaction_manifest::{parse_manifest, ManifestValue};
 
-use crate::{read_only_state_store::ReadOnlyStateStore, track_calls::TrackCallsModule, Package};
+use crate::{read_only_state_store::ReadOnlyStateStore, track_calls::TrackCallsModule, Package, Scenario};
 
 pub fn test_faucet_component() -> ComponentAddress {
     ComponentAddress::new(ObjectKey::from_array([0xfau8; ObjectKey::LENGTH]))
@@ -66,12 +71,23 @@ pub struct TemplateTest {
     state_store: MemoryStateStore,
     enable_fees: bool,
     fee_table: FeeTable,
+    memory_limits: MemoryLimits,
+    gas_limits: GasLimits,
+    sandbox_limits: SandboxLimits,
+    text_limits: TextLimits,
     virtual_substates: VirtualSubstates,
     key_seed: u8,
+    deterministic_signing: bool,
 }
 
 impl TemplateTest {
     pub fn new<I: IntoIterator<Item = P>, P: AsRef<Path>>(template_paths: I) -> Self {
+        Self::with_package(Self::build_package(template_paths))
+    }
+
+    /// Compiles the given templates into a package that also contains the builtin templates and the test faucet. The
+    /// package can be used to create many tests without compiling the templates each time.
+    pub fn build_package<I: IntoIterator<Item = P>, P: AsRef<Path>>(template_paths: I) -> Package {
         let mut builder = Package::builder();
 
         // Add builtin templates
@@ -86,8 +102,12 @@ impl TemplateTest {
             builder.add_template(path);
         }
 
-        let package = builder.build();
+        builder.build()
+    }
 
+    /// Creates a test with a package created by [TemplateTest::build_package]. Templates published in the test are
+    /// added to the package.
+    pub fn with_package(package: Package) -> Self {
         let mut test = Self::from_package(package);
         test.bootstrap_state(1_000_000.into());
         test
@@ -128,8 +148,14 @@ impl TemplateTest {
                 per_byte_storage_cost: 1,
                 per_event_cost: 1,
                 per_log_cost: 1,
+                gas_per_fee_unit: 1_000_000,
             },
+            memory_limits: MemoryLimits::default(),
+            gas_limits: GasLimits::default(),
+            sandbox_limits: SandboxLimits::default(),
+            text_limits: TextLimits::default(),
             key_seed: 1,
+            deterministic_signing: false,
         }
     }
 
@@ -201,11 +227,43 @@ impl TemplateTest {
         self
     }
 
+    /// Signs the transactions created by the test with a deterministic nonce. Transaction ids, and therefore the ids of
+    /// the substates created by each transaction, are the same every time the test runs.
+    pub fn enable_deterministic_signing(&mut self) -> &mut Self {
+        self.deterministic_signing = true;
+        self
+    }
+
+    pub fn set_memory_limits(&mut self, memory_limits: MemoryLimits) -> &mut Self {
+        self.memory_limits = memory_limits;
+        self
+    }
+
+    pub fn set_gas_limits(&mut self, gas_limits: GasLimits) -> &mut Self {
+        self.gas_limits = gas_limits;
+        self
+    }
+
+    pub fn set_sandbox_limits(&mut self, sandbox_limits: SandboxLimits) -> &mut Self {
+        self.sandbox_limits = sandbox_limits;
+        self
+    }
+
+    pub fn set_text_limits(&mut self, text_limits: TextLimits) -> &mut Self {
+        self.text_limits = text_limits;
+        self
+    }
+
     pub fn set_virtual_substate(&mut self, address: VirtualSubstateId, value: VirtualSubstate) -> &mut Self {
         self.virtual_substates.insert(address, value);
         self
     }
 
+    /// Starts a scenario of transactions signed by multiple identities, executed against the state of this test
+    pub fn scenario(&mut self) -> Scenario<'_> {
+        Scenario::new(self)
+    }
+
     pub fn read_only_state_store(&self) -> ReadOnlyStateStore<'_> {
         ReadOnlyStateStore::new(&self.state_store)
     }
@@ -240,7 +298,7 @@ impl TemplateTest {
             .unwrap_or_else(|| panic!("No output of type {:?}", ty))
     }
 
-    fn commit_diff(&mut self, diff: &SubstateDiff) {
+    pub(crate) fn commit_diff(&mut self, diff: &SubstateDiff) {
         self.last_outputs.clear();
 
         for (address, _) in diff.down_iter() {
@@ -250,6 +308,26 @@ impl TemplateTest {
 
         for (address, substate) in diff.up_iter() {
             eprintln!("UP substate: {}", address);
+            // Make published templates available to subsequent transactions, like a validator node would
+            if let (SubstateId::Template(template_address), SubstateValue::Template(template)) =
+                (address, substate.substate_value())
+            {
+                let author = template.author.clone().unwrap_or_default();
+                match template.template_type {
+                    TemplateType::Wasm => self
+                        .package
+                        .add_wasm_template(author, template_address.as_hash(), &template.binary)
+                        .unwrap(),
+                    TemplateType::Flow => self
+                        .package
+                        .add_flow_template(
+                            author,
+                            template_address.as_hash(),
+                            std::str::from_utf8(&template.binary).unwrap(),
+                        )
+                        .unwrap(),
+                }
+            }
             self.last_outputs.insert(address.clone());
             self.state_store.set_state(address.clone(), substate.clone()).unwrap();
         }
@@ -391,15 +469,14 @@ impl TemplateTest {
         let (owner_proof, public_key, secret_key) = self.create_owner_proof();
         let old_fail_fees = self.enable_fees;
         self.enable_fees = false;
-        let result = self.execute_expect_success(
+        let transaction = self.sign_transaction(
             Transaction::builder()
                 .call_method(test_faucet_component(), "take_free_coins", args![])
                 .put_last_instruction_output_on_workspace("bucket")
-                .create_account_with_bucket(public_key, "bucket")
-                .sign(&secret_key)
-                .build(),
-            vec![owner_proof.clone()],
+                .create_account_with_bucket(public_key, "bucket"),
+            &secret_key,
         );
+        let result = self.execute_expect_success(transaction, vec![owner_proof.clone()]);
 
         let component = result.finalize.execution_results[2]
             .decode::<ComponentAddress>()
@@ -421,15 +498,14 @@ impl TemplateTest {
         let (owner_proof, public_key, secret_key) = self.create_owner_proof();
         let old_fail_fees = self.enable_fees;
         self.enable_fees = false;
-        let result = self.execute_expect_success(
+        let transaction = self.sign_transaction(
             Transaction::builder()
                 .call_method(test_faucet_component(), "take_free_coins_custom", args![amount])
                 .put_last_instruction_output_on_workspace("bucket")
-                .create_account_with_bucket(public_key.clone(), "bucket")
-                .sign(&secret_key)
-                .build(),
-            vec![owner_proof.clone()],
+                .create_account_with_bucket(public_key.clone(), "bucket"),
+            &secret_key,
         );
+        let result = self.execute_expect_success(transaction, vec![owner_proof.clone()]);
 
         let component = result.finalize.execution_results[2]
             .decode::<ComponentAddress>()
@@ -439,6 +515,14 @@ impl TemplateTest {
         (component, owner_proof, secret_key, public_key)
     }
 
+    fn sign_transaction(&self, builder: TransactionBuilder, secret_key: &RistrettoSecretKey) -> Transaction {
+        if self.deterministic_signing {
+            builder.sign_deterministic(secret_key).build()
+        } else {
+            builder.sign(secret_key).build()
+        }
+    }
+
     fn next_key_seed(&mut self) -> u8 {
         let seed = self.key_seed;
         self.key_seed += 1;
@@ -458,11 +542,12 @@ impl TemplateTest {
         instructions: Vec<Instruction>,
         proofs: Vec<NonFungibleAddress>,
     ) -> Result<ExecuteResult, TransactionError> {
-        let transaction = Transaction::builder()
-            .with_fee_instructions(fee_instructions)
-            .with_instructions(instructions)
-            .sign(&self.secret_key)
-            .build();
+        let transaction = self.sign_transaction(
+            Transaction::builder()
+                .with_fee_instructions(fee_instructions)
+                .with_instructions(instructions),
+            &self.secret_key,
+        );
 
         self.try_execute(transaction, proofs)
     }
@@ -484,6 +569,10 @@ impl TemplateTest {
         let processor = TransactionProcessor::new(
             TransactionProcessorConfig::builder()
                 .with_network(Network::LocalNet)
+                .with_memory_limits(self.memory_limits)
+                .with_gas_limits(self.gas_limits)
+                .with_sandbox_limits(self.sandbox_limits.clone())
+                .with_text_limits(self.text_limits)
                 .build(),
             self.package.clone(),
             self.state_store.clone().into_read_only(),
@@ -609,6 +698,16 @@ impl TemplateTest {
         variables: I,
         proofs: Vec<NonFungibleAddress>,
     ) -> anyhow::Result<ExecuteResult> {
+        let instructions = self.parse_manifest_instructions(manifest, variables).unwrap();
+        self.execute_and_commit(instructions, proofs)
+    }
+
+    /// Parses the body of a manifest main function. All templates in the package are imported by name.
+    pub fn parse_manifest_instructions<'a, I: IntoIterator<Item = (&'a str, ManifestValue)>>(
+        &self,
+        manifest: &str,
+        variables: I,
+    ) -> anyhow::Result<Vec<Instruction>> {
         let template_imports = self
             .name_to_template
             .iter()
@@ -622,9 +721,8 @@ impl TemplateTest {
             &manifest,
             variables.into_iter().map(|(a, b)| (a.to_string(), b)).collect(),
             Default::default(),
-        )
-        .unwrap();
-        self.execute_and_commit(instructions.instructions, proofs)
+        )?;
+        Ok(instructions.instructions)
     }
 
     pub fn print_state(&self) {
@@ -0,0 +1,71 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+#![cfg(feature = "engine-matrix")]
+
+use tari_template_test_tooling::{
+    engine_matrix::{CurrentEngine, EngineMatrix, MatrixEngine, MatrixSuite, StepOutcome},
+    test_faucet_component,
+};
+
+/// The current engine, except that the outcome of the last step is flipped
+struct DivergentEngine;
+
+impl MatrixEngine for DivergentEngine {
+    fn version(&self) -> &str {
+        "divergent"
+    }
+
+    fn run_suite(&self, suite: &MatrixSuite) -> Vec<StepOutcome> {
+        let mut outcomes = CurrentEngine.run_suite(suite);
+        if let Some(last) = outcomes.last_mut() {
+            last.accepted = !last.accepted;
+        }
+        outcomes
+    }
+}
+
+fn faucet_suite() -> MatrixSuite {
+    let mut suite = MatrixSuite::new();
+    suite
+        .add_step("mint", "TestFaucet::mint(Amount(1000));")
+        .add_step_with_variables(
+            "take coins without depositing them",
+            r#"
+            let faucet = var!["faucet"];
+            faucet.take_free_coins();
+            "#,
+            [("faucet", test_faucet_component().into())],
+        );
+    suite
+}
+
+#[test]
+fn it_reports_no_differences_for_the_same_engine() {
+    let suite = faucet_suite();
+    let outcomes = CurrentEngine.run_suite(&suite);
+    assert_eq!(outcomes.len(), 2);
+    assert!(outcomes[0].accepted, "{}", outcomes[0]);
+    // The bucket is dangling
+    assert!(!outcomes[1].accepted, "{}", outcomes[1]);
+
+    let mut matrix = EngineMatrix::from_features();
+    matrix.add_engine(CurrentEngine);
+    let report = matrix.run(&suite);
+    report.assert_compatible();
+}
+
+#[test]
+fn it_reports_behavior_differences_per_step() {
+    let mut matrix = EngineMatrix::from_features();
+    matrix.add_engine(DivergentEngine);
+    let report = matrix.run(&faucet_suite());
+
+    assert!(!report.is_compatible());
+    assert_eq!(report.differences.len(), 1);
+    let difference = &report.differences[0];
+    assert_eq!(difference.engine_version, "divergent");
+    assert_eq!(difference.step, "take coins without depositing them");
+    assert!(!difference.expected.as_ref().unwrap().accepted);
+    assert!(difference.actual.as_ref().unwrap().accepted);
+}
@@ -3,7 +3,12 @@
 
 use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_dan_common_types::{Epoch, SubstateRequirement};
-use tari_engine_types::{confidential::ConfidentialClaim, instruction::Instruction, TemplateAddress};
+use tari_engine_types::{
+    confidential::ConfidentialClaim,
+    instruction::Instruction,
+    published_template::TemplateType,
+    TemplateAddress,
+};
 use tari_template_lib::{
     args,
     args::Arg,
@@ -136,7 +141,39 @@ impl TransactionBuilder {
 
     /// Publishing a WASM template.
     pub fn publish_template(self, binary: Vec<u8>) -> Self {
-        self.add_instruction(Instruction::PublishTemplate { binary })
+        self.add_instruction(Instruction::PublishTemplate {
+            binary,
+            previous_version: None,
+            template_type: TemplateType::Wasm,
+        })
+    }
+
+    /// Publishing a WASM template as the next version of a template previously published by the signer.
+    pub fn publish_template_version(self, binary: Vec<u8>, previous_version: TemplateAddress) -> Self {
+        self.add_instruction(Instruction::PublishTemplate {
+            binary,
+            previous_version: Some(previous_version),
+            template_type: TemplateType::Wasm,
+        })
+    }
+
+    /// Publishing a JSON flow template, optionally as the next version of a template previously published by the
+    /// signer.
+    pub fn publish_flow_template(self, flow_json: String, previous_version: Option<TemplateAddress>) -> Self {
+        self.add_instruction(Instruction::PublishTemplate {
+            binary: flow_json.into_bytes(),
+            previous_version,
+            template_type: TemplateType::Flow,
+        })
+    }
+
+    /// Migrates a component to a newer version of its template. The transaction must be signed by the component
+    /// owner.
+    pub fn migrate_component(self, component_address: ComponentAddress, template_address: TemplateAddress) -> Self {
+        self.add_instruction(Instruction::MigrateComponent {
+            component_address,
+            template_address,
+        })
     }
 
     pub fn claim_burn(self, claim: ConfidentialClaim) -> Self {
@@ -227,6 +264,13 @@ impl TransactionBuilder {
         self
     }
 
+    /// Signs the transaction with a deterministic nonce. See [TransactionSignature::sign_deterministic].
+    pub fn sign_deterministic(mut self, secret_key: &PrivateKey) -> Self {
+        self.signatures
+            .push(TransactionSignature::sign_deterministic(secret_key, &self.unsigned_transaction));
+        self
+    }
+
     pub fn build(self) -> Transaction {
         Transaction::new(self.unsigned_transaction, self.signatures)
     }
@@ -0,0 +1,118 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_engine_types::hashing::{hasher32, hasher64, EngineHashDomainLabel};
+
+use crate::{Transaction, TransactionId, TransactionSignature};
+
+/// The canonical bytes of a transaction that are signed and hashed. External signers (e.g. hardware wallets) and
+/// auditors can use these to check that their encoding of a transaction matches the encoding used by the network.
+///
+/// Both hashes are domain separated Blake2b hashes. The hash input is the length of the domain separation tag as a
+/// little-endian u64, followed by the tag and then the canonical bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionCanonicalBytes {
+    signature_fields: Vec<u8>,
+    transaction_id_preimage: Vec<u8>,
+}
+
+impl TransactionCanonicalBytes {
+    pub fn new(signature_fields: Vec<u8>, transaction_id_preimage: Vec<u8>) -> Self {
+        Self {
+            signature_fields,
+            transaction_id_preimage,
+        }
+    }
+
+    pub fn from_transaction(transaction: &Transaction) -> Self {
+        let mut transaction_id_preimage = vec![];
+        tari_bor::encode_into_std_writer(transaction.signatures(), &mut transaction_id_preimage)
+            .expect("INVARIANT VIOLATION: encoding into a Vec failed");
+        tari_bor::encode_into_std_writer(transaction.unsigned_transaction(), &mut transaction_id_preimage)
+            .expect("INVARIANT VIOLATION: encoding into a Vec failed");
+
+        Self {
+            signature_fields: TransactionSignature::encode_signature_fields(transaction.unsigned_transaction()),
+            transaction_id_preimage,
+        }
+    }
+
+    /// The CBOR encoding of the transaction fields that each signer signs
+    pub fn signature_fields(&self) -> &[u8] {
+        &self.signature_fields
+    }
+
+    /// The CBOR encoding of the signatures followed by the CBOR encoding of the unsigned transaction
+    pub fn transaction_id_preimage(&self) -> &[u8] {
+        &self.transaction_id_preimage
+    }
+
+    /// The domain separation tag of the signature message hash (Blake2b-512)
+    pub fn signature_domain_separation_tag() -> String {
+        EngineHashDomainLabel::TransactionSignature.domain_separation_tag()
+    }
+
+    /// The domain separation tag of the transaction id hash (Blake2b-256)
+    pub fn transaction_id_domain_separation_tag() -> String {
+        EngineHashDomainLabel::Transaction.domain_separation_tag()
+    }
+
+    /// Returns the 64-byte message that each signer signs with a Schnorr signature
+    pub fn signature_message(&self) -> [u8; 64] {
+        hasher64(EngineHashDomainLabel::TransactionSignature)
+            .chain_raw(&self.signature_fields)
+            .result()
+    }
+
+    pub fn calculate_transaction_id(&self) -> TransactionId {
+        hasher32(EngineHashDomainLabel::Transaction)
+            .chain_raw(&self.transaction_id_preimage)
+            .result()
+            .into_array()
+            .into()
+    }
+
+    /// Returns true if the transaction id recomputed from the canonical bytes is `transaction_id`
+    pub fn verify_transaction_id(&self, transaction_id: &TransactionId) -> bool {
+        self.calculate_transaction_id() == *transaction_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+    use tari_crypto::{keys::SecretKey, ristretto::RistrettoSecretKey};
+    use tari_dan_common_types::Epoch;
+
+    use super::*;
+
+    fn signed_transaction() -> Transaction {
+        Transaction::builder()
+            .with_min_epoch(Some(Epoch(1)))
+            .with_max_epoch(Some(Epoch(10)))
+            .sign(&RistrettoSecretKey::random(&mut OsRng))
+            .build()
+    }
+
+    #[test]
+    fn it_recomputes_the_transaction_id() {
+        let transaction = signed_transaction();
+        let canonical = TransactionCanonicalBytes::from_transaction(&transaction);
+        assert!(canonical.verify_transaction_id(transaction.id()));
+
+        let mut preimage = canonical.transaction_id_preimage().to_vec();
+        *preimage.last_mut().unwrap() ^= 1;
+        let tampered = TransactionCanonicalBytes::new(canonical.signature_fields().to_vec(), preimage);
+        assert!(!tampered.verify_transaction_id(transaction.id()));
+    }
+
+    #[test]
+    fn it_returns_the_signed_message() {
+        let transaction = signed_transaction();
+        let canonical = TransactionCanonicalBytes::from_transaction(&transaction);
+        let signature = &transaction.signatures()[0];
+        assert!(signature
+            .signature()
+            .verify(signature.public_key(), canonical.signature_message()));
+    }
+}
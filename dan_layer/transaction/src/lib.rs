@@ -21,12 +21,16 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod builder;
+mod canonical;
+mod offline;
 mod signature;
 mod transaction;
 mod transaction_id;
 mod unsigned_transaction;
 
 pub use builder::TransactionBuilder;
+pub use canonical::TransactionCanonicalBytes;
+pub use offline::*;
 pub use signature::TransactionSignature;
 pub use tari_engine_types::instruction::Instruction;
 pub use transaction::*;
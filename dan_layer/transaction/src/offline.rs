@@ -0,0 +1,198 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tari_bor::BorError;
+use tari_common_types::types::PublicKey;
+use tari_crypto::{
+    keys::PublicKey as _,
+    ristretto::RistrettoSecretKey,
+    tari_utilities::hex::{from_hex, to_hex, HexError},
+};
+
+use crate::{Transaction, TransactionSignature, UnsignedTransaction};
+
+/// The version of the offline transaction blob encoding. Blobs with a different version are rejected.
+pub const OFFLINE_TRANSACTION_BLOB_VERSION: u8 = 1;
+
+// A blob is the hex encoding of a version byte, a kind byte and the CBOR encoding of the payload. CBOR is also the
+// encoding that transaction signatures and ids commit to, so the payload must re-encode to exactly the same bytes.
+const UNSIGNED_BLOB_KIND: u8 = 0;
+const SIGNED_BLOB_KIND: u8 = 1;
+
+/// An unsigned transaction that is exported for signing on an offline (air-gapped) machine. The inputs are resolved to
+/// specific versions before the export, so that the offline machine does not need to access the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineUnsignedTransaction {
+    pub transaction: UnsignedTransaction,
+    /// The public keys that are expected to sign the transaction
+    pub required_signers: Vec<PublicKey>,
+}
+
+impl OfflineUnsignedTransaction {
+    pub fn new(transaction: UnsignedTransaction, required_signers: Vec<PublicKey>) -> Self {
+        Self {
+            transaction,
+            required_signers,
+        }
+    }
+
+    pub fn to_blob(&self) -> String {
+        encode_blob(UNSIGNED_BLOB_KIND, self)
+    }
+
+    pub fn from_blob(blob: &str) -> Result<Self, OfflineTransactionError> {
+        decode_blob(UNSIGNED_BLOB_KIND, blob)
+    }
+
+    /// Signs the transaction with each of the given secret keys. If the exporting wallet named the required signers,
+    /// every key must belong to one of them.
+    pub fn sign(self, secret_keys: &[RistrettoSecretKey]) -> Result<OfflineSignedTransaction, OfflineTransactionError> {
+        let mut signatures = Vec::with_capacity(secret_keys.len());
+        for secret in secret_keys {
+            let public_key = PublicKey::from_secret_key(secret);
+            if !self.required_signers.is_empty() && !self.required_signers.contains(&public_key) {
+                return Err(OfflineTransactionError::UnexpectedSigner { public_key });
+            }
+            signatures.push(TransactionSignature::sign(secret, &self.transaction));
+        }
+        Ok(OfflineSignedTransaction {
+            transaction: self.transaction,
+            signatures,
+        })
+    }
+}
+
+/// A transaction that was signed on an offline machine and is imported for submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineSignedTransaction {
+    pub transaction: UnsignedTransaction,
+    pub signatures: Vec<TransactionSignature>,
+}
+
+impl OfflineSignedTransaction {
+    pub fn to_blob(&self) -> String {
+        encode_blob(SIGNED_BLOB_KIND, self)
+    }
+
+    pub fn from_blob(blob: &str) -> Result<Self, OfflineTransactionError> {
+        decode_blob(SIGNED_BLOB_KIND, blob)
+    }
+
+    /// Returns the signed transaction. The transaction id is calculated from the transaction and signatures and is not
+    /// taken from the blob.
+    pub fn into_transaction(self) -> Transaction {
+        Transaction::new(self.transaction, self.signatures)
+    }
+}
+
+fn encode_blob<T: Serialize>(kind: u8, value: &T) -> String {
+    let mut bytes = vec![OFFLINE_TRANSACTION_BLOB_VERSION, kind];
+    tari_bor::encode_into_std_writer(value, &mut bytes).expect("INVARIANT VIOLATION: encoding into a Vec failed");
+    to_hex(&bytes)
+}
+
+fn decode_blob<T: Serialize + DeserializeOwned>(kind: u8, blob: &str) -> Result<T, OfflineTransactionError> {
+    let bytes = from_hex(blob.trim())?;
+    let [version, blob_kind, payload @ ..] = bytes.as_slice() else {
+        return Err(OfflineTransactionError::BlobTooShort);
+    };
+    if *version != OFFLINE_TRANSACTION_BLOB_VERSION {
+        return Err(OfflineTransactionError::UnsupportedVersion {
+            version: *version,
+            expected: OFFLINE_TRANSACTION_BLOB_VERSION,
+        });
+    }
+    if *blob_kind != kind {
+        return Err(OfflineTransactionError::UnexpectedBlobKind {
+            kind: *blob_kind,
+            expected: kind,
+        });
+    }
+    let value: T = tari_bor::decode_exact(payload)?;
+    // A blob that does not re-encode to the same bytes may not decode to the transaction that the user reviewed
+    if tari_bor::encode(&value)? != payload {
+        return Err(OfflineTransactionError::NonCanonicalEncoding);
+    }
+    Ok(value)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineTransactionError {
+    #[error("Invalid hex in offline transaction blob: {0}")]
+    InvalidHex(#[from] HexError),
+    #[error("Offline transaction blob is too short")]
+    BlobTooShort,
+    #[error("Unsupported offline transaction blob version {version}, expected version {expected}")]
+    UnsupportedVersion { version: u8, expected: u8 },
+    #[error("Unexpected offline transaction blob kind {kind}, expected kind {expected}")]
+    UnexpectedBlobKind { kind: u8, expected: u8 },
+    #[error("Failed to decode offline transaction blob: {0}")]
+    DecodeFailed(#[from] BorError),
+    #[error("Offline transaction blob is not canonically encoded")]
+    NonCanonicalEncoding,
+    #[error("Key {public_key} is not a required signer of the transaction")]
+    UnexpectedSigner { public_key: PublicKey },
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::SecretKey;
+    use tari_dan_common_types::Epoch;
+
+    use super::*;
+
+    fn unsigned_transaction(required_signers: Vec<PublicKey>) -> OfflineUnsignedTransaction {
+        let transaction = Transaction::builder()
+            .with_min_epoch(Some(Epoch(1)))
+            .build_unsigned_transaction();
+        OfflineUnsignedTransaction::new(transaction, required_signers)
+    }
+
+    #[test]
+    fn it_signs_a_decoded_blob() {
+        let secret = RistrettoSecretKey::random(&mut OsRng);
+        let blob = unsigned_transaction(vec![PublicKey::from_secret_key(&secret)]).to_blob();
+        let unsigned = OfflineUnsignedTransaction::from_blob(&blob).unwrap();
+        let signed = OfflineSignedTransaction::from_blob(&unsigned.sign(&[secret.clone()]).unwrap().to_blob()).unwrap();
+
+        let transaction = signed.into_transaction();
+        assert!(transaction.verify_all_signatures());
+        assert_eq!(
+            *transaction.signatures()[0].public_key(),
+            PublicKey::from_secret_key(&secret)
+        );
+    }
+
+    #[test]
+    fn it_rejects_unexpected_signers() {
+        let required = RistrettoSecretKey::random(&mut OsRng);
+        let other = RistrettoSecretKey::random(&mut OsRng);
+        let unsigned = unsigned_transaction(vec![PublicKey::from_secret_key(&required)]);
+        assert!(matches!(
+            unsigned.sign(&[other]),
+            Err(OfflineTransactionError::UnexpectedSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_invalid_blobs() {
+        let blob = unsigned_transaction(vec![]).to_blob();
+        assert!(matches!(
+            OfflineSignedTransaction::from_blob(&blob),
+            Err(OfflineTransactionError::UnexpectedBlobKind { .. })
+        ));
+
+        let mut bytes = from_hex(&blob).unwrap();
+        bytes[0] = OFFLINE_TRANSACTION_BLOB_VERSION + 1;
+        assert!(matches!(
+            OfflineUnsignedTransaction::from_blob(&to_hex(&bytes)),
+            Err(OfflineTransactionError::UnsupportedVersion { .. })
+        ));
+
+        let mut bytes = from_hex(&blob).unwrap();
+        bytes.push(0);
+        assert!(OfflineUnsignedTransaction::from_blob(&to_hex(&bytes)).is_err());
+    }
+}
@@ -6,8 +6,9 @@ use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use tari_common_types::types::{PublicKey, Signature};
 use tari_crypto::{
-    keys::PublicKey as PublicKeyT,
+    keys::{PublicKey as PublicKeyT, SecretKey as SecretKeyT},
     ristretto::{RistrettoPublicKey, RistrettoSecretKey},
+    tari_utilities::ByteArray,
 };
 use tari_dan_common_types::{Epoch, SubstateRequirement};
 use tari_engine_types::{
@@ -43,6 +44,25 @@ impl TransactionSignature {
         }
     }
 
+    /// Signs the transaction with a nonce derived from the secret key and the transaction, so that signing the same
+    /// transaction with the same key always gives the same signature and transaction id. Used by tests and fuzzing,
+    /// where executions must be reproducible.
+    pub fn sign_deterministic(secret_key: &RistrettoSecretKey, transaction: &UnsignedTransaction) -> Self {
+        let public_key = RistrettoPublicKey::from_secret_key(secret_key);
+        let message = Self::create_message(transaction);
+        let nonce = hasher64(EngineHashDomainLabel::TransactionSignature)
+            .chain(secret_key.as_bytes())
+            .chain(&message[..])
+            .result();
+        let nonce = RistrettoSecretKey::from_uniform_bytes(&nonce)
+            .expect("INVARIANT VIOLATION: RistrettoSecretKey::from_uniform_bytes and hash output length mismatch");
+
+        Self {
+            signature: Signature::sign_with_nonce_and_message(secret_key, nonce, message).unwrap(),
+            public_key,
+        }
+    }
+
     pub fn verify(&self, transaction: &UnsignedTransaction) -> bool {
         let message = Self::create_message(transaction);
         self.signature.verify(&self.public_key, message)
@@ -56,6 +76,13 @@ impl TransactionSignature {
         &self.public_key
     }
 
+    /// Returns the CBOR encoding of the transaction fields that are signed. See
+    /// [`crate::TransactionCanonicalBytes::signature_message`] for the message that is signed.
+    pub fn encode_signature_fields(transaction: &UnsignedTransaction) -> Vec<u8> {
+        tari_bor::encode(&TransactionSignatureFields::from(transaction))
+            .expect("INVARIANT VIOLATION: encoding signature fields failed")
+    }
+
     fn create_message(transaction: &UnsignedTransaction) -> [u8; 64] {
         let signature_fields = TransactionSignatureFields::from(transaction);
         hasher64(EngineHashDomainLabel::TransactionSignature)
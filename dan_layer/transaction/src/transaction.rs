@@ -14,9 +14,18 @@ use tari_engine_types::{
     instruction::Instruction,
     substate::SubstateId,
 };
-use tari_template_lib::{models::ComponentAddress, Hash};
+use tari_template_lib::{
+    models::{Amount, ComponentAddress},
+    Hash,
+};
 
-use crate::{builder::TransactionBuilder, transaction_id::TransactionId, TransactionSignature, UnsignedTransaction};
+use crate::{
+    builder::TransactionBuilder,
+    transaction_id::TransactionId,
+    TransactionCanonicalBytes,
+    TransactionSignature,
+    UnsignedTransaction,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(
@@ -74,6 +83,11 @@ impl Transaction {
         &self.id
     }
 
+    /// Returns the canonical bytes that are signed and hashed to produce the transaction id
+    pub fn canonical_bytes(&self) -> TransactionCanonicalBytes {
+        TransactionCanonicalBytes::from_transaction(self)
+    }
+
     pub fn check_id(&self) -> bool {
         let id = self.calculate_hash();
         id == self.id
@@ -236,6 +250,16 @@ impl Transaction {
     pub fn has_inputs_without_version(&self) -> bool {
         self.inputs().iter().any(|i| i.version().is_none())
     }
+
+    /// See [UnsignedTransaction::declared_max_fee]
+    pub fn declared_max_fee(&self) -> Option<Amount> {
+        self.transaction.declared_max_fee()
+    }
+
+    /// See [UnsignedTransaction::fee_per_instruction]
+    pub fn fee_per_instruction(&self) -> u64 {
+        self.transaction.fee_per_instruction()
+    }
 }
 
 impl Display for Transaction {
@@ -12,7 +12,7 @@ use tari_engine_types::{
     instruction::Instruction,
     substate::SubstateId,
 };
-use tari_template_lib::models::ComponentAddress;
+use tari_template_lib::models::{Amount, ComponentAddress, ConfidentialWithdrawProof};
 
 use crate::{builder::TransactionBuilder, Transaction, TransactionSignature};
 
@@ -128,8 +128,39 @@ impl UnsignedTransaction {
         self.inputs().iter().any(|i| i.version().is_none())
     }
 
+    /// Returns the maximum fee declared by the fee instructions i.e. the amount passed to `pay_fee` or the revealed
+    /// amount withdrawn by `pay_fee_confidential`. Returns None if the fee instructions do not declare a fee.
+    pub fn declared_max_fee(&self) -> Option<Amount> {
+        self.fee_instructions()
+            .iter()
+            .filter_map(declared_fee)
+            .reduce(|a, b| a.saturating_add(b))
+    }
+
+    /// Returns the declared maximum fee divided by the total number of instructions, or 0 if no fee is declared.
+    pub fn fee_per_instruction(&self) -> u64 {
+        let num_instructions = (self.fee_instructions.len() + self.instructions.len()).max(1) as u64;
+        self.declared_max_fee()
+            .and_then(|fee| fee.as_u64_checked())
+            .map_or(0, |fee| fee / num_instructions)
+    }
+
     pub fn sign(self, secret: &RistrettoSecretKey) -> Transaction {
         let signature = TransactionSignature::sign(secret, &self);
         Transaction::new(self, vec![signature])
     }
 }
+
+fn declared_fee(instruction: &Instruction) -> Option<Amount> {
+    let Instruction::CallMethod { method, args, .. } = instruction else {
+        return None;
+    };
+    let arg = args.first()?.as_literal_bytes()?;
+    match method.as_str() {
+        "pay_fee" => tari_bor::decode::<Amount>(arg).ok(),
+        "pay_fee_confidential" => tari_bor::decode::<ConfidentialWithdrawProof>(arg)
+            .ok()
+            .map(|proof| proof.output_proof.output_revealed_amount),
+        _ => None,
+    }
+}
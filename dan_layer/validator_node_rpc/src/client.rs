@@ -7,13 +7,13 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tari_bor::{decode, decode_exact, encode};
-use tari_dan_common_types::{NodeAddressable, PeerAddress, SubstateAddress};
+use tari_dan_common_types::{Epoch, NodeAddressable, PeerAddress, SubstateAddress};
 use tari_dan_p2p::{
     proto,
     proto::rpc::{GetTransactionResultRequest, PayloadResultStatus, SubmitTransactionRequest, SubstateStatus},
     TariMessagingSpec,
 };
-use tari_dan_storage::consensus_models::Decision;
+use tari_dan_storage::consensus_models::{Decision, TransactionPoolStage};
 use tari_engine_types::{
     commit_result::ExecuteResult,
     substate::{Substate, SubstateId, SubstateValue},
@@ -45,6 +45,7 @@ pub trait ValidatorNodeRpcClient: Send + Sync {
 
     async fn get_substate(&mut self, shard: SubstateAddress) -> Result<SubstateResult, Self::Error>;
     async fn get_virtual_substate(&mut self, address: VirtualSubstateId) -> Result<VirtualSubstate, Self::Error>;
+    async fn get_pending_transactions(&mut self, limit: usize) -> Result<PendingTransactions, Self::Error>;
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -62,6 +63,20 @@ pub struct FinalizedResult {
     pub abort_details: Option<String>,
 }
 
+/// The transactions in the transaction pool of a validator node that have not been finalized
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingTransactions {
+    pub epoch: Epoch,
+    pub transactions: Vec<PendingTransaction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingTransaction {
+    pub transaction_id: TransactionId,
+    pub stage: TransactionPoolStage,
+    pub is_ready: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum SubstateResult {
     DoesNotExist,
@@ -194,6 +209,40 @@ impl<TMsg: MessageSpec> ValidatorNodeRpcClient for TariValidatorNodeRpcClient<TM
         decode_exact(&resp.substate).map_err(|e| ValidatorNodeRpcClientError::InvalidResponse(anyhow!(e)))
     }
 
+    async fn get_pending_transactions(&mut self, limit: usize) -> Result<PendingTransactions, Self::Error> {
+        let mut client = self.client_connection().await?;
+        let request = proto::rpc::GetPendingTransactionsRequest {
+            limit: u32::try_from(limit).unwrap_or(u32::MAX),
+        };
+        let resp = client.get_pending_transactions(request).await?;
+
+        let transactions = resp
+            .transactions
+            .into_iter()
+            .map(|tx| {
+                let transaction_id = tx.transaction_id.try_into().map_err(|_| {
+                    ValidatorNodeRpcClientError::InvalidResponse(anyhow!(
+                        "Node returned an invalid or empty transaction id"
+                    ))
+                })?;
+                let stage = tx
+                    .stage
+                    .parse()
+                    .map_err(|e| ValidatorNodeRpcClientError::InvalidResponse(anyhow!("{e}")))?;
+                Ok(PendingTransaction {
+                    transaction_id,
+                    stage,
+                    is_ready: tx.is_ready,
+                })
+            })
+            .collect::<Result<_, ValidatorNodeRpcClientError>>()?;
+
+        Ok(PendingTransactions {
+            epoch: Epoch(resp.epoch),
+            transactions,
+        })
+    }
+
     async fn get_finalized_transaction_result(
         &mut self,
         transaction_id: TransactionId,
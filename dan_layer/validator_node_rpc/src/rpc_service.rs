@@ -54,4 +54,16 @@ pub trait ValidatorNodeRpcService: Send + Sync + 'static {
         &self,
         request: Request<proto::SyncStateRequest>,
     ) -> Result<Streaming<proto::SyncStateResponse>, RpcStatus>;
+
+    #[rpc(method = 9)]
+    async fn get_pending_transactions(
+        &self,
+        request: Request<proto::GetPendingTransactionsRequest>,
+    ) -> Result<Response<proto::GetPendingTransactionsResponse>, RpcStatus>;
+
+    #[rpc(method = 10)]
+    async fn get_state_snapshot_chunk(
+        &self,
+        request: Request<proto::GetStateSnapshotChunkRequest>,
+    ) -> Result<Response<proto::GetStateSnapshotChunkResponse>, RpcStatus>;
 }
@@ -0,0 +1,260 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_crypto::keys::PublicKey as _;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_engine_types::{component::new_component_address_from_public_key, substate::SubstateId};
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_transaction::TransactionId;
+
+use crate::{
+    apis::{
+        accounts::{AccountsApi, AccountsApiError},
+        key_manager,
+        key_manager::{KeyManagerApi, KeyManagerApiError},
+        substate::{SubstateApiError, SubstatesApi, ValidatorScanResult},
+    },
+    network::WalletNetworkInterface,
+    storage::WalletStore,
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::account_recovery";
+
+/// The default number of consecutive unused keys after which account recovery stops scanning
+pub const DEFAULT_RECOVERY_GAP_LIMIT: u64 = 20;
+
+/// Recovers the accounts of a wallet from its key manager seed or from imported keys. Account component addresses are
+/// derived from the owner public key, so each transaction key is checked for an account on the network.
+pub struct AccountRecoveryApi<'a, TStore, TNetworkInterface> {
+    key_manager_api: KeyManagerApi<'a, TStore>,
+    accounts_api: AccountsApi<'a, TStore>,
+    substate_api: SubstatesApi<'a, TStore, TNetworkInterface>,
+}
+
+impl<'a, TStore, TNetworkInterface> AccountRecoveryApi<'a, TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(
+        key_manager_api: KeyManagerApi<'a, TStore>,
+        accounts_api: AccountsApi<'a, TStore>,
+        substate_api: SubstatesApi<'a, TStore, TNetworkInterface>,
+    ) -> Self {
+        Self {
+            key_manager_api,
+            accounts_api,
+            substate_api,
+        }
+    }
+
+    /// Scans transaction keys from index 0 until `gap_limit` consecutive keys have no account on the network. Each
+    /// account found that is not already known to the wallet is added along with its owner key index. Accounts are
+    /// added without a name. If the wallet has no default account, the first recovered account becomes the default.
+    ///
+    /// Only the account substate is saved. The vaults and balances of recovered accounts are populated when the
+    /// account is next refreshed.
+    pub async fn recover_accounts(&self, gap_limit: u64) -> Result<Vec<RecoveredAccount>, AccountRecoveryApiError> {
+        if gap_limit == 0 {
+            return Err(AccountRecoveryApiError::InvalidGapLimit);
+        }
+
+        let mut has_default = self.accounts_api.get_default().optional()?.is_some();
+        let mut recovered = Vec::new();
+        let mut num_unused = 0u64;
+        let mut index = 0u64;
+
+        while num_unused < gap_limit {
+            let key = self
+                .key_manager_api
+                .derive_key(key_manager::TRANSACTION_BRANCH, index)?;
+            let public_key = PublicKey::from_secret_key(&key.key);
+            let address = SubstateId::Component(new_component_address_from_public_key(
+                &ACCOUNT_TEMPLATE_ADDRESS,
+                &public_key,
+            ));
+
+            if self.accounts_api.exists_by_address(&address)? {
+                debug!(target: LOG_TARGET, "Account {} at key index {} is already known", address, index);
+                num_unused = 0;
+                index += 1;
+                continue;
+            }
+
+            let Some(ValidatorScanResult {
+                address: versioned_address,
+                created_by_tx,
+                ..
+            }) = self.scan_for_account(&address).await?
+            else {
+                num_unused += 1;
+                index += 1;
+                continue;
+            };
+
+            info!(target: LOG_TARGET, "🔑 Recovered account {} at key index {}", address, index);
+            self.key_manager_api
+                .add_key_index(key_manager::TRANSACTION_BRANCH, index)?;
+            self.substate_api.save_root(created_by_tx, versioned_address)?;
+            let is_default = !has_default;
+            self.accounts_api.add_account(None, &address, index, is_default)?;
+            has_default = true;
+
+            recovered.push(RecoveredAccount {
+                address,
+                key_index: index,
+                public_key,
+                is_default,
+                created_by_tx,
+            });
+            num_unused = 0;
+            index += 1;
+        }
+
+        info!(
+            target: LOG_TARGET,
+            "🔑 Account recovery scanned {} key(s) and recovered {} account(s)",
+            index,
+            recovered.len()
+        );
+        Ok(recovered)
+    }
+
+    /// Adds the accounts that have been created on the network for transaction keys that the wallet has issued but
+    /// that have no account in the wallet. A transfer to a public key creates the account at the address derived from
+    /// the key if it does not exist, so the first deposit to a key that the wallet handed out (e.g. as an exchange
+    /// deposit address) is detected by its account appearing on the network.
+    pub async fn detect_deposit_accounts(&self) -> Result<Vec<RecoveredAccount>, AccountRecoveryApiError> {
+        let mut has_default = self.accounts_api.get_default().optional()?.is_some();
+        let mut detected = Vec::new();
+
+        for (index, public_key, _) in self.key_manager_api.get_all_keys(key_manager::TRANSACTION_BRANCH)? {
+            let address = SubstateId::Component(new_component_address_from_public_key(
+                &ACCOUNT_TEMPLATE_ADDRESS,
+                &public_key,
+            ));
+            if self.accounts_api.exists_by_address(&address)? {
+                continue;
+            }
+
+            let Some(ValidatorScanResult {
+                address: versioned_address,
+                created_by_tx,
+                ..
+            }) = self.scan_for_account(&address).await?
+            else {
+                continue;
+            };
+
+            info!(
+                target: LOG_TARGET,
+                "📥 Detected deposit account {} for key index {} created in transaction {}",
+                address,
+                index,
+                created_by_tx
+            );
+            self.substate_api.save_root(created_by_tx, versioned_address)?;
+            let is_default = !has_default;
+            self.accounts_api.add_account(None, &address, index, is_default)?;
+            has_default = true;
+
+            detected.push(RecoveredAccount {
+                address,
+                key_index: index,
+                public_key,
+                is_default,
+                created_by_tx,
+            });
+        }
+
+        Ok(detected)
+    }
+
+    /// Imports an account using an externally generated owner key, for example from a wallet created with other
+    /// tooling. The account component address is derived from the owner public key and must exist on the network.
+    /// The key cannot be derived from the wallet seed, so it is stored on the imported key branch.
+    pub async fn import_account(
+        &self,
+        name: Option<&str>,
+        secret_key: &PrivateKey,
+        is_default: bool,
+    ) -> Result<RecoveredAccount, AccountRecoveryApiError> {
+        let public_key = PublicKey::from_secret_key(secret_key);
+        let address = SubstateId::Component(new_component_address_from_public_key(
+            &ACCOUNT_TEMPLATE_ADDRESS,
+            &public_key,
+        ));
+
+        if self.accounts_api.exists_by_address(&address)? {
+            return Err(AccountsApiError::AccountAlreadyExists { address }.into());
+        }
+
+        let Some(ValidatorScanResult {
+            address: versioned_address,
+            created_by_tx,
+            ..
+        }) = self.scan_for_account(&address).await?
+        else {
+            return Err(AccountRecoveryApiError::AccountNotFound { address });
+        };
+
+        let key_index = self.key_manager_api.import_key(secret_key)?;
+        self.substate_api.save_root(created_by_tx, versioned_address)?;
+        let is_default = is_default || self.accounts_api.get_default().optional()?.is_none();
+        self.accounts_api
+            .add_imported_account(name, &address, key_index, is_default)?;
+        info!(
+            target: LOG_TARGET,
+            "🔑 Imported account {} with imported key index {}", address, key_index
+        );
+
+        Ok(RecoveredAccount {
+            address,
+            key_index,
+            public_key,
+            is_default,
+            created_by_tx,
+        })
+    }
+
+    /// Returns the account component at the address, or None if there is no component at the address
+    async fn scan_for_account(
+        &self,
+        address: &SubstateId,
+    ) -> Result<Option<ValidatorScanResult>, AccountRecoveryApiError> {
+        let Some(scan_result) = self.substate_api.scan_for_substate(address, None).await.optional()? else {
+            return Ok(None);
+        };
+        if scan_result.substate.component().is_none() {
+            warn!(target: LOG_TARGET, "Substate {} is not a component. Skipping.", address);
+            return Ok(None);
+        }
+        Ok(Some(scan_result))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecoveredAccount {
+    pub address: SubstateId,
+    pub key_index: u64,
+    pub public_key: PublicKey,
+    pub is_default: bool,
+    pub created_by_tx: TransactionId,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountRecoveryApiError {
+    #[error("Gap limit must be greater than zero")]
+    InvalidGapLimit,
+    #[error("No account found on the network at {address}")]
+    AccountNotFound { address: SubstateId },
+    #[error("Key manager error: {0}")]
+    KeyManagerError(#[from] KeyManagerApiError),
+    #[error("Accounts API error: {0}")]
+    AccountsApiError(#[from] AccountsApiError),
+    #[error("Substate API error: {0}")]
+    SubstateApiError(#[from] SubstateApiError),
+}
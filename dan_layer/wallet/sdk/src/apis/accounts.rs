@@ -1,11 +1,12 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_dan_common_types::optional::{IsNotFoundError, Optional};
 use tari_engine_types::substate::SubstateId;
 use tari_template_lib::{
     models::{Amount, ResourceAddress},
-    prelude::ResourceType,
+    prelude::{ResourceType, CONFIDENTIAL_TARI_RESOURCE_ADDRESS},
 };
 
 use crate::{
@@ -42,6 +43,67 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
         Ok(())
     }
 
+    /// Adds an account that is tracked using the owner public key. The wallet cannot sign for a watch-only account, so
+    /// it is never the default account.
+    pub fn add_watch_only_account(
+        &self,
+        account_name: Option<&str>,
+        account_address: &SubstateId,
+        owner_public_key: &PublicKey,
+        view_key: Option<&PrivateKey>,
+    ) -> Result<(), AccountsApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        if let Some(name) = account_name {
+            if tx.accounts_get_by_name(name).optional()?.is_some() {
+                tx.rollback()?;
+                return Err(AccountsApiError::AccountNameAlreadyExists { name: name.to_string() });
+            }
+        }
+        if tx.accounts_get(account_address).optional()?.is_some() {
+            tx.rollback()?;
+            return Err(AccountsApiError::AccountAlreadyExists {
+                address: account_address.clone(),
+            });
+        }
+        tx.accounts_insert_watch_only(account_name, account_address, owner_public_key, view_key)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Adds an account whose owner key was imported into the wallet. `imported_key_index` is the index of the key on
+    /// the imported key branch.
+    pub fn add_imported_account(
+        &self,
+        account_name: Option<&str>,
+        account_address: &SubstateId,
+        imported_key_index: u64,
+        is_default: bool,
+    ) -> Result<(), AccountsApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        if let Some(name) = account_name {
+            if tx.accounts_get_by_name(name).optional()?.is_some() {
+                tx.rollback()?;
+                return Err(AccountsApiError::AccountNameAlreadyExists { name: name.to_string() });
+            }
+        }
+        if tx.accounts_get(account_address).optional()?.is_some() {
+            tx.rollback()?;
+            return Err(AccountsApiError::AccountAlreadyExists {
+                address: account_address.clone(),
+            });
+        }
+        tx.accounts_insert_imported(account_name, account_address, imported_key_index, is_default)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_view_key(&self, account_address: &SubstateId) -> Result<Option<PrivateKey>, AccountsApiError> {
+        let view_key = self
+            .store
+            .with_read_tx(|tx| tx.accounts_get_view_key(account_address))?;
+        Ok(view_key)
+    }
+
     pub fn get_many(&self, offset: u64, limit: u64) -> Result<Vec<Account>, AccountsApiError> {
         let mut tx = self.store.create_read_tx()?;
         let accounts = tx.accounts_get_many(offset, limit)?;
@@ -87,12 +149,20 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
         Ok(())
     }
 
+    pub fn set_vault_frozen(&self, vault_address: &SubstateId, is_frozen: bool) -> Result<(), AccountsApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        tx.vaults_set_frozen(vault_address, is_frozen)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn get_vault_balance(&self, vault_address: &SubstateId) -> Result<VaultBalance, AccountsApiError> {
         let vault = self.store.with_read_tx(|tx| tx.vaults_get(vault_address))?;
         Ok(VaultBalance {
             account: vault.account_address,
             confidential: vault.confidential_balance,
             revealed: vault.revealed_balance,
+            is_frozen: vault.is_frozen,
         })
     }
 
@@ -153,6 +223,12 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
 
     pub fn set_default_account(&self, account_addr: &SubstateId) -> Result<(), AccountsApiError> {
         let mut tx = self.store.create_write_tx()?;
+        if tx.accounts_get(account_addr)?.is_watch_only() {
+            tx.rollback()?;
+            return Err(AccountsApiError::WatchOnlyAccount {
+                address: account_addr.clone(),
+            });
+        }
         tx.accounts_set_default(account_addr)?;
         tx.commit()?;
         Ok(())
@@ -165,6 +241,7 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
         resource_address: ResourceAddress,
         resource_type: ResourceType,
         token_symbol: Option<String>,
+        decimals: Option<u8>,
     ) -> Result<(), AccountsApiError> {
         let mut tx = self.store.create_write_tx()?;
         tx.vaults_insert(VaultModel {
@@ -176,6 +253,8 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
             confidential_balance: Amount::zero(),
             locked_revealed_balance: Amount::zero(),
             token_symbol,
+            decimals,
+            is_frozen: false,
         })?;
         tx.commit()?;
         Ok(())
@@ -192,6 +271,27 @@ impl<'a, TStore: WalletStore> AccountsApi<'a, TStore> {
         let vaults = tx.vaults_get_by_account(account)?;
         Ok(vaults)
     }
+
+    /// Returns all accounts that have an available revealed Tari balance with which to pay fees. Watch-only accounts
+    /// are excluded because they cannot sign.
+    pub fn get_fee_funded_accounts(&self) -> Result<Vec<Account>, AccountsApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let count = tx.accounts_count()?;
+        let accounts = tx.accounts_get_many(0, count)?;
+        let mut funded = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            if account.is_watch_only() {
+                continue;
+            }
+            let vault = tx
+                .vaults_get_by_resource(&account.address, &CONFIDENTIAL_TARI_RESOURCE_ADDRESS)
+                .optional()?;
+            if vault.is_some_and(|v| v.available_revealed_balance().is_positive()) {
+                funded.push(account);
+            }
+        }
+        Ok(funded)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -200,6 +300,10 @@ pub enum AccountsApiError {
     StoreError(#[from] WalletStorageError),
     #[error("Account name already exists: {name}")]
     AccountNameAlreadyExists { name: String },
+    #[error("Account already exists: {address}")]
+    AccountAlreadyExists { address: SubstateId },
+    #[error("Account {address} is watch-only and cannot sign")]
+    WatchOnlyAccount { address: SubstateId },
 }
 
 impl IsNotFoundError for AccountsApiError {
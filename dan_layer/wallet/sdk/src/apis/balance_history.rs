@@ -0,0 +1,191 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use log::*;
+use tari_dan_common_types::{
+    optional::{IsNotFoundError, Optional},
+    Epoch,
+};
+use tari_engine_types::substate::SubstateId;
+
+use crate::{
+    models::{HistoricalBalanceSource, HistoricalVaultBalance, VaultModel, VersionedSubstateId},
+    network::WalletNetworkInterface,
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::balance_history";
+
+/// Reconstructs the balances of an account at a past epoch. The wallet records the epoch at which it observed each
+/// version of an account's vaults. The balance of a version that is no longer current is fetched from the network,
+/// which only works as long as the network has not pruned that version.
+pub struct BalanceHistoryApi<'a, TStore, TNetworkInterface> {
+    store: &'a TStore,
+    network_interface: &'a TNetworkInterface,
+}
+
+impl<'a, TStore, TNetworkInterface> BalanceHistoryApi<'a, TStore, TNetworkInterface>
+where
+    TStore: WalletStore,
+    TNetworkInterface: WalletNetworkInterface,
+    TNetworkInterface::Error: IsNotFoundError,
+{
+    pub fn new(store: &'a TStore, network_interface: &'a TNetworkInterface) -> Self {
+        Self {
+            store,
+            network_interface,
+        }
+    }
+
+    /// Records that the account vault was at the given version in `epoch`
+    pub fn record_vault_version(
+        &self,
+        account_address: &SubstateId,
+        vault_address: &VersionedSubstateId,
+        epoch: Epoch,
+    ) -> Result<(), BalanceHistoryApiError> {
+        self.store
+            .with_write_tx(|tx| tx.vault_history_insert(account_address, vault_address, epoch))?;
+        Ok(())
+    }
+
+    /// Returns the balance of each account vault at the end of `epoch`. Vaults that did not exist at `epoch` are not
+    /// included.
+    pub async fn get_balances_at_epoch(
+        &self,
+        account_address: &SubstateId,
+        epoch: Epoch,
+    ) -> Result<Vec<HistoricalVaultBalance>, BalanceHistoryApiError> {
+        let current_epoch = self
+            .network_interface
+            .get_current_epoch()
+            .await
+            .map_err(|e| BalanceHistoryApiError::NetworkInterfaceError(e.into()))?;
+        if epoch > current_epoch {
+            return Err(BalanceHistoryApiError::FutureEpoch { epoch, current_epoch });
+        }
+
+        let earliest_epoch = self
+            .store
+            .with_read_tx(|tx| tx.vault_history_get_earliest_epoch(account_address))?
+            .ok_or_else(|| BalanceHistoryApiError::NoHistoryRecorded {
+                account: account_address.clone(),
+            })?;
+        if epoch < earliest_epoch {
+            return Err(BalanceHistoryApiError::EpochBeforeHistory {
+                account: account_address.clone(),
+                epoch,
+                earliest_epoch,
+            });
+        }
+
+        let vaults_at_epoch = self.store.with_read_tx(|tx| {
+            let mut vaults_at_epoch = vec![];
+            for vault in tx.vaults_get_by_account(account_address)? {
+                // A vault without a version at or before the epoch did not exist yet
+                let Some(version_at_epoch) = tx.vault_history_get_at_epoch(&vault.address, epoch)? else {
+                    continue;
+                };
+                let current_version = tx
+                    .substates_get(&vault.address)
+                    .optional()?
+                    .map(|substate| substate.address.version);
+                vaults_at_epoch.push((vault, version_at_epoch, current_version));
+            }
+            Ok::<_, WalletStorageError>(vaults_at_epoch)
+        })?;
+
+        let mut balances = Vec::with_capacity(vaults_at_epoch.len());
+        for (vault, version_at_epoch, current_version) in vaults_at_epoch {
+            let balance = if current_version == Some(version_at_epoch.version) {
+                Self::local_balance(&vault, version_at_epoch.version)?
+            } else {
+                self.fetch_balance(&version_at_epoch).await?
+            };
+            balances.push(balance);
+        }
+
+        Ok(balances)
+    }
+
+    fn local_balance(vault: &VaultModel, version: u32) -> Result<HistoricalVaultBalance, BalanceHistoryApiError> {
+        let vault_id = vault.address.as_vault_id().ok_or_else(|| {
+            BalanceHistoryApiError::UnexpectedSubstate(format!("Substate {} is not a vault", vault.address))
+        })?;
+        Ok(HistoricalVaultBalance {
+            vault_id,
+            resource_address: vault.resource_address,
+            vault_version: version,
+            revealed_balance: vault.revealed_balance,
+            confidential_balance: Some(vault.confidential_balance),
+            source: HistoricalBalanceSource::Local,
+        })
+    }
+
+    async fn fetch_balance(
+        &self,
+        vault_address: &VersionedSubstateId,
+    ) -> Result<HistoricalVaultBalance, BalanceHistoryApiError> {
+        debug!(
+            target: LOG_TARGET,
+            "Fetching vault {} at version {}", vault_address.substate_id, vault_address.version
+        );
+        let result = self
+            .network_interface
+            .query_substate(&vault_address.substate_id, Some(vault_address.version), false)
+            .await
+            .optional()
+            .map_err(|e| BalanceHistoryApiError::NetworkInterfaceError(e.into()))?
+            .ok_or_else(|| BalanceHistoryApiError::HistoryPruned {
+                vault: vault_address.substate_id.clone(),
+                version: vault_address.version,
+            })?;
+
+        let vault_id = vault_address.substate_id.as_vault_id();
+        let (Some(vault_id), Some(vault)) = (vault_id, result.substate.into_substate_value().into_vault()) else {
+            return Err(BalanceHistoryApiError::UnexpectedSubstate(format!(
+                "Substate {} is not a vault",
+                vault_address.substate_id
+            )));
+        };
+
+        Ok(HistoricalVaultBalance {
+            vault_id,
+            resource_address: *vault.resource_address(),
+            vault_version: result.version,
+            revealed_balance: vault.balance(),
+            confidential_balance: None,
+            source: HistoricalBalanceSource::Indexer,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BalanceHistoryApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("Network interface error: {0}")]
+    NetworkInterfaceError(anyhow::Error),
+    #[error("The wallet has not recorded any balance history for account {account}")]
+    NoHistoryRecorded { account: SubstateId },
+    #[error(
+        "Epoch {epoch} is before the earliest epoch {earliest_epoch} recorded by the wallet for account {account}"
+    )]
+    EpochBeforeHistory {
+        account: SubstateId,
+        epoch: Epoch,
+        earliest_epoch: Epoch,
+    },
+    #[error("Vault {vault} at version {version} has been pruned from the network")]
+    HistoryPruned { vault: SubstateId, version: u32 },
+    #[error("Epoch {epoch} is after the current epoch {current_epoch}")]
+    FutureEpoch { epoch: Epoch, current_epoch: Epoch },
+    #[error("Unexpected substate: {0}")]
+    UnexpectedSubstate(String),
+}
+
+impl IsNotFoundError for BalanceHistoryApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
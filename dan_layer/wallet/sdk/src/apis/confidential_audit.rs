@@ -0,0 +1,136 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use log::*;
+use tari_engine_types::{confidential::ValueLookupTable, vault::Vault};
+use tari_template_lib::{
+    models::{Amount, ResourceAddress, VaultId},
+    prelude::ComponentAddress,
+};
+
+use crate::{
+    apis::{
+        confidential_crypto::ConfidentialCryptoApi,
+        key_manager::{self, KeyManagerApi, KeyManagerApiError},
+    },
+    models::AuditedVaultBalance,
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::confidential_audit";
+
+/// Read-only balance auditing for confidential resources that were created with a view key. The auditor can
+/// determine the balance of any vault of the resource but has no ability to spend the funds.
+pub struct ConfidentialAuditApi<'a, TStore> {
+    store: &'a TStore,
+    key_manager_api: KeyManagerApi<'a, TStore>,
+    crypto_api: ConfidentialCryptoApi,
+}
+
+impl<'a, TStore: WalletStore> ConfidentialAuditApi<'a, TStore> {
+    pub fn new(
+        store: &'a TStore,
+        key_manager_api: KeyManagerApi<'a, TStore>,
+        crypto_api: ConfidentialCryptoApi,
+    ) -> Self {
+        Self {
+            store,
+            key_manager_api,
+            crypto_api,
+        }
+    }
+
+    /// Decrypts the viewable balances of all confidential outputs in the vault using the view key at
+    /// `view_key_index` and stores the resulting balance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn audit_vault<TLookup>(
+        &self,
+        view_key_index: u64,
+        vault_id: VaultId,
+        vault_version: u32,
+        owner: Option<ComponentAddress>,
+        vault: &Vault,
+        value_range: RangeInclusive<u64>,
+        lookup: &mut TLookup,
+    ) -> Result<AuditedVaultBalance, ConfidentialAuditApiError>
+    where
+        TLookup: ValueLookupTable,
+        TLookup::Error: Display,
+    {
+        let commitments = vault
+            .get_confidential_commitments()
+            .ok_or(ConfidentialAuditApiError::NotConfidentialVault { vault_id })?;
+
+        let view_key = self
+            .key_manager_api
+            .derive_key(key_manager::VIEW_KEY_BRANCH, view_key_index)?;
+
+        let values = self
+            .crypto_api
+            .try_brute_force_commitment_balances(&view_key.key, commitments.values(), value_range, lookup)
+            .map_err(|e| ConfidentialAuditApiError::ValueLookupError(e.to_string()))?;
+
+        let num_outputs = commitments.len() as u32;
+        let resolved = values.iter().flatten().collect::<Vec<_>>();
+        let num_unresolved = num_outputs - resolved.len() as u32;
+        let balance = resolved
+            .into_iter()
+            .try_fold(0u64, |acc, v| acc.checked_add(*v))
+            .and_then(|v| i64::try_from(v).ok())
+            .map(Amount::new)
+            .ok_or(ConfidentialAuditApiError::BalanceOverflow { vault_id })?;
+
+        if num_unresolved > 0 {
+            warn!(
+                target: LOG_TARGET,
+                "Unable to determine the value of {} of {} outputs in vault {}", num_unresolved, num_outputs, vault_id
+            );
+        }
+
+        let audited = AuditedVaultBalance {
+            resource_address: *vault.resource_address(),
+            view_key_index,
+            vault_id,
+            owner,
+            balance,
+            num_outputs,
+            num_unresolved,
+            vault_version,
+        };
+
+        self.store.with_write_tx(|tx| tx.audited_balances_upsert(&audited))?;
+        Ok(audited)
+    }
+
+    pub fn get_balances(
+        &self,
+        resource_address: &ResourceAddress,
+        view_key_index: u64,
+    ) -> Result<Vec<AuditedVaultBalance>, ConfidentialAuditApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let balances = tx.audited_balances_get_by_resource(resource_address, view_key_index)?;
+        Ok(balances)
+    }
+
+    pub fn get_audited_resources(&self) -> Result<Vec<(ResourceAddress, u64)>, ConfidentialAuditApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let resources = tx.audited_balances_get_audited_resources()?;
+        Ok(resources)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfidentialAuditApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("Key manager error: {0}")]
+    KeyManagerError(#[from] KeyManagerApiError),
+    #[error("Vault {vault_id} is not a confidential vault")]
+    NotConfidentialVault { vault_id: VaultId },
+    #[error("Value lookup error: {0}")]
+    ValueLookupError(String),
+    #[error("Audited balance of vault {vault_id} overflowed")]
+    BalanceOverflow { vault_id: VaultId },
+}
@@ -2,11 +2,10 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use log::*;
-use tari_common_types::types::PublicKey;
+use tari_common_types::types::PrivateKey;
 use tari_dan_common_types::optional::{IsNotFoundError, Optional};
 use tari_dan_wallet_crypto::{kdfs, ConfidentialOutputMaskAndValue};
 use tari_engine_types::{confidential::ConfidentialOutput, substate::SubstateId};
-use tari_key_manager::key_manager::DerivedKey;
 use tari_template_lib::models::Amount;
 use tari_transaction::TransactionId;
 
@@ -14,7 +13,6 @@ use crate::{
     apis::{
         accounts::{AccountsApi, AccountsApiError},
         confidential_crypto::{ConfidentialCryptoApi, ConfidentialCryptoApiError},
-        key_manager,
         key_manager::{KeyManagerApi, KeyManagerApiError},
     },
     models::{Account, ConfidentialOutputModel, ConfidentialProofId, OutputStatus},
@@ -225,10 +223,24 @@ impl<'a, TStore: WalletStore> ConfidentialOutputsApi<'a, TStore> {
         outputs: I,
     ) -> Result<(), ConfidentialOutputsApiError> {
         let account = self.accounts_api.get_account_by_address(account_addr)?;
-        // We do not support changing of account key at this time
-        let key = self
-            .key_manager_api
-            .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+        let key = if account.is_watch_only() {
+            // Outputs of a watch-only account can only be unblinded if a view key was provided
+            match self.accounts_api.get_view_key(account_addr)? {
+                Some(view_key) => view_key,
+                None => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "No view key for watch-only account {}. Skipping confidential outputs.", account_addr
+                    );
+                    return Ok(());
+                },
+            }
+        } else {
+            // We do not support changing of account key at this time
+            self.key_manager_api
+                .derive_key(account.key_branch(), account.key_index)?
+                .key
+        };
         let mut tx = self.store.create_write_tx()?;
         for output in outputs {
             match tx.outputs_get_by_commitment(&output.commitment).optional()? {
@@ -266,14 +278,14 @@ impl<'a, TStore: WalletStore> ConfidentialOutputsApi<'a, TStore> {
     fn validate_output(
         &self,
         account: &Account,
-        key: &DerivedKey<PublicKey>,
+        key: &PrivateKey,
         vault_address: &SubstateId,
         output: &ConfidentialOutput,
     ) -> Result<ConfidentialOutputModel, ConfidentialOutputsApiError> {
         let unblinded_result = self.crypto_api.unblind_output(
             &output.commitment,
             &output.encrypted_data,
-            &key.key,
+            key,
             &output.stealth_public_nonce,
         );
         let (value, status) = match unblinded_result {
@@ -73,6 +73,10 @@ where
         spend_amount: Amount,
         input_selection: ConfidentialTransferInputSelection,
     ) -> Result<InputsToSpend, ConfidentialTransferApiError> {
+        let key_branch = self
+            .accounts_api
+            .get_account_by_address(&from_account.into())?
+            .key_branch();
         let src_vault = self
             .accounts_api
             .get_vault_by_resource(&from_account.into(), &resource_address)?;
@@ -86,9 +90,7 @@ where
                 let (confidential_inputs, _) =
                     self.outputs_api
                         .lock_outputs_by_amount(&src_vault.address, spend_amount, proof_id)?;
-                let confidential_inputs = self
-                    .outputs_api
-                    .resolve_output_masks(confidential_inputs, key_manager::TRANSACTION_BRANCH)?;
+                let confidential_inputs = self.outputs_api.resolve_output_masks(confidential_inputs, key_branch)?;
 
                 info!(
                     target: LOG_TARGET,
@@ -147,9 +149,7 @@ where
                 let (confidential_inputs, _) =
                     self.outputs_api
                         .lock_outputs_by_amount(&src_vault.address, confidential_to_spend, proof_id)?;
-                let confidential_inputs = self
-                    .outputs_api
-                    .resolve_output_masks(confidential_inputs, key_manager::TRANSACTION_BRANCH)?;
+                let confidential_inputs = self.outputs_api.resolve_output_masks(confidential_inputs, key_branch)?;
 
                 let total_confidential_spent = confidential_inputs.iter().map(|i| i.value).sum::<u64>();
 
@@ -192,9 +192,7 @@ where
 
                 self.outputs_api.lock_revealed_funds(proof_id, revealed_to_spend)?;
 
-                let confidential_inputs = self
-                    .outputs_api
-                    .resolve_output_masks(confidential_inputs, key_manager::TRANSACTION_BRANCH)?;
+                let confidential_inputs = self.outputs_api.resolve_output_masks(confidential_inputs, key_branch)?;
 
                 Ok(InputsToSpend {
                     confidential: confidential_inputs,
@@ -291,7 +289,7 @@ where
 
         let account_secret = self
             .key_manager_api
-            .derive_key(key_manager::TRANSACTION_BRANCH, account.key_index)?;
+            .derive_key(account.key_branch(), account.key_index)?;
         let account_public_key = PublicKey::from_secret_key(&account_secret.key);
 
         // Generate fee proof
@@ -25,7 +25,6 @@ impl<'a, TStore: WalletStore> ConfigApi<'a, TStore> {
 
     pub fn set<T: Serialize>(&self, key: ConfigKey, value: &T, is_encrypted: bool) -> Result<(), ConfigApiError> {
         let mut tx = self.store.create_write_tx()?;
-        // TODO: Actually encrypt if is_encrypted is true
         tx.config_set(key.as_key_str(), value, is_encrypted)?;
         tx.commit()?;
         Ok(())
@@ -35,6 +34,9 @@ impl<'a, TStore: WalletStore> ConfigApi<'a, TStore> {
 pub enum ConfigKey {
     CipherSeed,
     IndexerUrl,
+    BootstrapImported,
+    TransactionAnnotations,
+    MetadataSyncBase,
 }
 
 impl ConfigKey {
@@ -42,6 +44,9 @@ impl ConfigKey {
         match self {
             ConfigKey::CipherSeed => "cipher_seed",
             ConfigKey::IndexerUrl => "indexer_url",
+            ConfigKey::BootstrapImported => "bootstrap_imported",
+            ConfigKey::TransactionAnnotations => "transaction_annotations",
+            ConfigKey::MetadataSyncBase => "metadata_sync_base",
         }
     }
 }
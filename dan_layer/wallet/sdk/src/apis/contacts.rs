@@ -0,0 +1,86 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use tari_common_types::types::PublicKey;
+use tari_crypto::tari_utilities::hex::Hex;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_template_lib::models::ComponentAddress;
+
+use crate::{
+    models::Contact,
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+/// The wallet address book of named counterparties
+pub struct ContactsApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore: WalletStore> ContactsApi<'a, TStore> {
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    /// Adds a contact. A contact must have a component address, a public key or both. The name may not be a valid
+    /// component address or public key so that it can be used in place of either.
+    pub fn add_contact(&self, contact: &Contact) -> Result<(), ContactsApiError> {
+        if contact.name.is_empty() ||
+            ComponentAddress::from_str(&contact.name).is_ok() ||
+            PublicKey::from_hex(&contact.name).is_ok()
+        {
+            return Err(ContactsApiError::InvalidName {
+                name: contact.name.clone(),
+            });
+        }
+        if contact.component_address.is_none() && contact.public_key.is_none() {
+            return Err(ContactsApiError::NoAddressOrPublicKey {
+                name: contact.name.clone(),
+            });
+        }
+        let mut tx = self.store.create_write_tx()?;
+        if tx.contacts_get_by_name(&contact.name).optional()?.is_some() {
+            tx.rollback()?;
+            return Err(ContactsApiError::ContactAlreadyExists {
+                name: contact.name.clone(),
+            });
+        }
+        tx.contacts_insert(contact)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_contact(&self, name: &str) -> Result<Contact, ContactsApiError> {
+        let contact = self.store.with_read_tx(|tx| tx.contacts_get_by_name(name))?;
+        Ok(contact)
+    }
+
+    pub fn get_contacts(&self) -> Result<Vec<Contact>, ContactsApiError> {
+        let contacts = self.store.with_read_tx(|tx| tx.contacts_get_all())?;
+        Ok(contacts)
+    }
+
+    pub fn remove_contact(&self, name: &str) -> Result<(), ContactsApiError> {
+        self.store.with_write_tx(|tx| tx.contacts_delete(name))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ContactsApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("Contact '{name}' already exists")]
+    ContactAlreadyExists { name: String },
+    #[error("Invalid contact name '{name}'")]
+    InvalidName { name: String },
+    #[error("Contact '{name}' must have a component address or public key")]
+    NoAddressOrPublicKey { name: String },
+}
+
+impl IsNotFoundError for ContactsApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
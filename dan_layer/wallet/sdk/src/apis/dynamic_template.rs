@@ -0,0 +1,262 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde_json as json;
+use tari_bor::{encode, to_value};
+use tari_engine_types::{instruction::Instruction, json_to_cbor, TemplateAddress};
+use tari_template_abi::{ArgDef, FunctionDef, TemplateDef, Type};
+use tari_template_lib::{
+    args::Arg,
+    models::{Amount, ComponentAddress},
+};
+
+use crate::network::WalletNetworkInterface;
+
+pub struct DynamicTemplateApi<'a, TNetworkInterface> {
+    network_interface: &'a TNetworkInterface,
+}
+
+impl<'a, TNetworkInterface> DynamicTemplateApi<'a, TNetworkInterface>
+where TNetworkInterface: WalletNetworkInterface
+{
+    pub fn new(network_interface: &'a TNetworkInterface) -> Self {
+        Self { network_interface }
+    }
+
+    /// Fetches the ABI of the template and returns a client that builds instructions against it
+    pub async fn load(&self, template_address: TemplateAddress) -> Result<DynamicTemplateClient, DynamicTemplateError> {
+        let definition = self
+            .network_interface
+            .fetch_template_definition(template_address)
+            .await
+            .map_err(|e| DynamicTemplateError::NetworkInterfaceError(e.into()))?;
+        Ok(DynamicTemplateClient::new(template_address, definition))
+    }
+}
+
+/// Builds instructions for a template using its ABI. Function names, argument counts and argument types are checked
+/// when the instruction is built, so an invalid call fails before a transaction is signed or submitted.
+///
+/// Arguments are given as JSON values and encoded according to the argument type in the ABI. At the top level, a
+/// string of the form `Workspace(<key>)` refers to the output of a previous instruction instead.
+#[derive(Debug, Clone)]
+pub struct DynamicTemplateClient {
+    template_address: TemplateAddress,
+    definition: TemplateDef,
+}
+
+impl DynamicTemplateClient {
+    pub fn new(template_address: TemplateAddress, definition: TemplateDef) -> Self {
+        Self {
+            template_address,
+            definition,
+        }
+    }
+
+    pub fn template_address(&self) -> &TemplateAddress {
+        &self.template_address
+    }
+
+    pub fn template_name(&self) -> &str {
+        self.definition.template_name()
+    }
+
+    pub fn definition(&self) -> &TemplateDef {
+        &self.definition
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<&FunctionDef> {
+        self.definition.get_function(name)
+    }
+
+    /// Builds a CallFunction instruction for a template function that does not take a component receiver
+    pub fn call_function(&self, function: &str, args: Vec<json::Value>) -> Result<Instruction, DynamicTemplateError> {
+        let def = self.find_function(function)?;
+        if is_method(def) {
+            return Err(DynamicTemplateError::ExpectedFunction {
+                name: function.to_string(),
+            });
+        }
+        let args = encode_args(def, &def.arguments, args)?;
+        Ok(Instruction::CallFunction {
+            template_address: self.template_address,
+            function: function.to_string(),
+            args,
+        })
+    }
+
+    /// Builds a CallMethod instruction for a template method. The component is expected to be an instance of this
+    /// template; this is not checked.
+    pub fn call_method(
+        &self,
+        component_address: ComponentAddress,
+        method: &str,
+        args: Vec<json::Value>,
+    ) -> Result<Instruction, DynamicTemplateError> {
+        let def = self.find_function(method)?;
+        if !is_method(def) {
+            return Err(DynamicTemplateError::ExpectedMethod {
+                name: method.to_string(),
+            });
+        }
+        // The receiver is not an argument of the call
+        let args = encode_args(def, &def.arguments[1..], args)?;
+        Ok(Instruction::CallMethod {
+            component_address,
+            method: method.to_string(),
+            args,
+        })
+    }
+
+    fn find_function(&self, name: &str) -> Result<&FunctionDef, DynamicTemplateError> {
+        self.get_function(name)
+            .ok_or_else(|| DynamicTemplateError::FunctionNotFound {
+                template_name: self.template_name().to_string(),
+                name: name.to_string(),
+            })
+    }
+}
+
+fn is_method(def: &FunctionDef) -> bool {
+    def.arguments.first().is_some_and(|arg| arg.name == "self")
+}
+
+fn encode_args(
+    def: &FunctionDef,
+    arg_defs: &[ArgDef],
+    args: Vec<json::Value>,
+) -> Result<Vec<Arg>, DynamicTemplateError> {
+    if arg_defs.len() != args.len() {
+        return Err(DynamicTemplateError::ArgumentCountMismatch {
+            name: def.name.clone(),
+            expected: arg_defs.len(),
+            got: args.len(),
+        });
+    }
+
+    arg_defs
+        .iter()
+        .zip(args)
+        .map(|(arg_def, value)| {
+            encode_arg(&arg_def.arg_type, value).map_err(|details| DynamicTemplateError::InvalidArgument {
+                function: def.name.clone(),
+                argument: arg_def.name.clone(),
+                details,
+            })
+        })
+        .collect()
+}
+
+fn encode_arg(ty: &Type, value: json::Value) -> Result<Arg, String> {
+    if let Some(key) = value.as_str().and_then(|s| strip_workspace(s.trim())) {
+        return Ok(Arg::Workspace(key.as_bytes().to_vec()));
+    }
+    let value = to_cbor_value(ty, value)?;
+    let bytes = encode(&value).map_err(|e| e.to_string())?;
+    Ok(Arg::Literal(bytes))
+}
+
+fn strip_workspace(s: &str) -> Option<&str> {
+    s.strip_prefix("Workspace(").and_then(|s| s.strip_suffix(')'))
+}
+
+fn to_cbor_value(ty: &Type, value: json::Value) -> Result<tari_bor::Value, String> {
+    match ty {
+        Type::Unit => match value {
+            json::Value::Null => Ok(tari_bor::Value::Null),
+            v => Err(format!("expected null, got {v}")),
+        },
+        Type::Bool => match value {
+            json::Value::Bool(b) => Ok(tari_bor::Value::Bool(b)),
+            json::Value::String(s) if s == "true" || s == "false" => Ok(tari_bor::Value::Bool(s == "true")),
+            v => Err(format!("expected a boolean, got {v}")),
+        },
+        Type::I8 => integer_value::<i8>(ty, &value),
+        Type::I16 => integer_value::<i16>(ty, &value),
+        Type::I32 => integer_value::<i32>(ty, &value),
+        Type::I64 => integer_value::<i64>(ty, &value),
+        Type::I128 => integer_value::<i128>(ty, &value),
+        Type::U8 => integer_value::<u8>(ty, &value),
+        Type::U16 => integer_value::<u16>(ty, &value),
+        Type::U32 => integer_value::<u32>(ty, &value),
+        Type::U64 => integer_value::<u64>(ty, &value),
+        Type::U128 => integer_value::<u128>(ty, &value),
+        Type::String => match value {
+            json::Value::String(s) => Ok(tari_bor::Value::Text(s)),
+            v => Err(format!("expected a string, got {v}")),
+        },
+        Type::Vec(inner) => match value {
+            json::Value::Array(items) => {
+                let values = items
+                    .into_iter()
+                    .map(|item| to_cbor_value(inner, item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(tari_bor::Value::Array(values))
+            },
+            v => Err(format!("expected an array, got {v}")),
+        },
+        Type::Tuple(types) => match value {
+            json::Value::Array(items) if items.len() == types.len() => {
+                let values = types
+                    .iter()
+                    .zip(items)
+                    .map(|(ty, item)| to_cbor_value(ty, item))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(tari_bor::Value::Array(values))
+            },
+            v => Err(format!("expected an array of {} element(s), got {v}", types.len())),
+        },
+        Type::Other { name } if name == "Amount" => {
+            let amount = parse_integer::<i64>(&value)
+                .or_else(|| {
+                    value
+                        .as_str()
+                        .and_then(|s| s.trim().strip_prefix("Amount(")?.strip_suffix(')')?.parse().ok())
+                })
+                .ok_or_else(|| format!("expected an amount, got {value}"))?;
+            to_value(&Amount(amount)).map_err(|e| e.to_string())
+        },
+        // Other types (addresses, metadata, structs etc.) use the same conversion as untyped JSON arguments
+        Type::Other { .. } => Ok(json_to_cbor(value)),
+    }
+}
+
+fn integer_value<T>(ty: &Type, value: &json::Value) -> Result<tari_bor::Value, String>
+where T: std::str::FromStr + TryFrom<i128> + serde::Serialize {
+    let n = parse_integer::<T>(value).ok_or_else(|| format!("expected {ty}, got {value}"))?;
+    to_value(&n).map_err(|e| e.to_string())
+}
+
+fn parse_integer<T>(value: &json::Value) -> Option<T>
+where T: std::str::FromStr + TryFrom<i128> {
+    match value {
+        json::Value::Number(n) => n
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+            .and_then(|n| T::try_from(n).ok()),
+        // Large integers may be given as strings to avoid loss of precision in JSON
+        json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DynamicTemplateError {
+    #[error("Network interface error: {0}")]
+    NetworkInterfaceError(anyhow::Error),
+    #[error("Template {template_name} has no function or method named '{name}'")]
+    FunctionNotFound { template_name: String, name: String },
+    #[error("'{name}' is a method and must be called on a component")]
+    ExpectedFunction { name: String },
+    #[error("'{name}' is a function and cannot be called on a component")]
+    ExpectedMethod { name: String },
+    #[error("'{name}' expects {expected} argument(s) but {got} were given")]
+    ArgumentCountMismatch { name: String, expected: usize, got: usize },
+    #[error("Invalid argument '{argument}' for '{function}': {details}")]
+    InvalidArgument {
+        function: String,
+        argument: String,
+        details: String,
+    },
+}
@@ -0,0 +1,189 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use chrono::Utc;
+use log::*;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_engine_types::substate::SubstateId;
+
+use crate::{
+    models::{ConfidentialProof, ConfidentialProofId},
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::apis::garbage_collection";
+
+/// Removes wallet store records that are no longer needed so that long-lived wallets do not grow unboundedly
+pub struct GarbageCollectionApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore: WalletStore> GarbageCollectionApi<'a, TStore> {
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    /// Finds and, unless `dry_run` is set, removes:
+    /// - substate records that are not reachable from a tracked account. A substate is reachable if it is an account,
+    ///   a vault of an account or the resource of such a vault, or if its parent is reachable.
+    /// - confidential proofs that were never bound to a finalized transaction. Proofs that are not bound to any
+    ///   transaction are only removed once they are older than `unbound_proof_min_age`, since they may belong to a
+    ///   transaction that is still being built. Outputs and revealed funds locked by removed proofs are released.
+    pub fn collect_garbage(
+        &self,
+        unbound_proof_min_age: Duration,
+        dry_run: bool,
+    ) -> Result<GarbageCollectionReport, GarbageCollectionApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        let unreachable_substates = Self::find_unreachable_substates(&mut tx)?;
+        let orphaned_proofs = Self::find_orphaned_proofs(&mut tx, unbound_proof_min_age)?;
+
+        let report = GarbageCollectionReport {
+            is_dry_run: dry_run,
+            removed_substates: unreachable_substates,
+            removed_proofs: orphaned_proofs.iter().map(|p| p.id).collect(),
+        };
+
+        if dry_run {
+            tx.rollback()?;
+            return Ok(report);
+        }
+
+        for substate_id in &report.removed_substates {
+            debug!(target: LOG_TARGET, "Removing unreachable substate {}", substate_id);
+            tx.substates_remove(substate_id)?;
+        }
+        for proof in orphaned_proofs {
+            debug!(
+                target: LOG_TARGET,
+                "Removing orphaned proof {} (transaction: {})",
+                proof.id,
+                proof
+                    .transaction_id
+                    .map_or_else(|| "none".to_string(), |id| id.to_string())
+            );
+            tx.outputs_release_by_proof_id(proof.id)?;
+            if !proof.locked_revealed_amount.is_zero() {
+                tx.vaults_unlock_revealed_funds(proof.id)?;
+            }
+            tx.proofs_delete(proof.id)?;
+        }
+        tx.commit()?;
+
+        if !report.is_empty() {
+            info!(
+                target: LOG_TARGET,
+                "🗑️ Removed {} unreachable substate(s) and {} orphaned proof(s)",
+                report.removed_substates.len(),
+                report.removed_proofs.len()
+            );
+        }
+        Ok(report)
+    }
+
+    fn find_unreachable_substates(
+        tx: &mut TStore::WriteTransaction<'_>,
+    ) -> Result<Vec<SubstateId>, GarbageCollectionApiError> {
+        let num_accounts = tx.accounts_count()?;
+        let accounts = tx.accounts_get_many(0, num_accounts)?;
+        let mut roots = HashSet::new();
+        for account in accounts {
+            for vault in tx.vaults_get_by_account(&account.address)? {
+                roots.insert(vault.address);
+                roots.insert(SubstateId::Resource(vault.resource_address));
+            }
+            roots.insert(account.address);
+        }
+
+        let substates = tx.substates_get_all(None, None, None, None)?;
+        let parents = substates
+            .iter()
+            .map(|s| (&s.address.substate_id, s.parent_address.as_ref()))
+            .collect::<HashMap<_, _>>();
+
+        let unreachable = substates
+            .iter()
+            .map(|s| &s.address.substate_id)
+            .filter(|id| !is_reachable(id, &parents, &roots))
+            .cloned()
+            .collect();
+        Ok(unreachable)
+    }
+
+    fn find_orphaned_proofs(
+        tx: &mut TStore::WriteTransaction<'_>,
+        unbound_proof_min_age: Duration,
+    ) -> Result<Vec<ConfidentialProof>, GarbageCollectionApiError> {
+        let now = Utc::now().naive_utc();
+        let mut orphaned = Vec::new();
+        for proof in tx.proofs_get_all()? {
+            let is_orphaned = match proof.transaction_id {
+                Some(transaction_id) => match tx.transactions_get(transaction_id).optional()? {
+                    // A failed transaction without a result (e.g. expired) was never finalized
+                    Some(transaction) => transaction.finalize.is_none() && transaction.status.is_failure(),
+                    None => true,
+                },
+                None => now
+                    .signed_duration_since(proof.created_at)
+                    .to_std()
+                    .is_ok_and(|age| age >= unbound_proof_min_age),
+            };
+            if is_orphaned {
+                orphaned.push(proof);
+            }
+        }
+        Ok(orphaned)
+    }
+}
+
+/// Returns true if the substate or one of its ancestors is a root
+fn is_reachable(
+    id: &SubstateId,
+    parents: &HashMap<&SubstateId, Option<&SubstateId>>,
+    roots: &HashSet<SubstateId>,
+) -> bool {
+    let mut current = id;
+    // Bounded by the number of substates in case the parent records contain a cycle
+    for _ in 0..=parents.len() {
+        if roots.contains(current) {
+            return true;
+        }
+        match parents.get(current).copied().flatten() {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+#[derive(Debug, Clone)]
+pub struct GarbageCollectionReport {
+    pub is_dry_run: bool,
+    /// The substate records that were removed, or would be removed if this is a dry run
+    pub removed_substates: Vec<SubstateId>,
+    /// The proofs that were removed, or would be removed if this is a dry run
+    pub removed_proofs: Vec<ConfidentialProofId>,
+}
+
+impl GarbageCollectionReport {
+    pub fn is_empty(&self) -> bool {
+        self.removed_substates.is_empty() && self.removed_proofs.is_empty()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GarbageCollectionApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+}
+
+impl IsNotFoundError for GarbageCollectionApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
@@ -3,14 +3,19 @@
 
 use blake2::Blake2b;
 use digest::consts::U64;
-use tari_common_types::types::PublicKey;
+use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_crypto::keys::PublicKey as PublicKeyTrait;
 //
-use tari_crypto::ristretto::RistrettoPublicKey;
+use tari_crypto::{
+    ristretto::RistrettoPublicKey,
+    tari_utilities::{hidden::Hidden, SafePassword},
+};
 use tari_dan_common_types::optional::Optional;
 use tari_key_manager::{
     cipher_seed::CipherSeed,
     key_manager::{DerivedKey, KeyManager},
+    mnemonic::{Mnemonic, MnemonicLanguage},
+    SeedWords,
 };
 
 use crate::storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter};
@@ -19,6 +24,14 @@ pub type WalletKeyManager = KeyManager<RistrettoPublicKey, Blake2b<U64>>;
 
 pub const TRANSACTION_BRANCH: &str = "transactions";
 pub const VIEW_KEY_BRANCH: &str = "view_key";
+/// The branch of the key used to identify the wallet itself e.g. to sign attestations. Only index 0 is used.
+pub const IDENTITY_BRANCH: &str = "identity";
+/// Keys on this branch were imported into the wallet and are loaded from storage rather than derived from the seed
+pub const IMPORTED_KEY_BRANCH: &str = "imported";
+/// The key used to encrypt synced wallet metadata. Only index 0 is used and the key never leaves the wallet.
+pub const METADATA_SYNC_BRANCH: &str = "metadata_sync";
+/// The language of the seed words that the wallet root key is backed up with
+pub const SEED_WORDS_LANGUAGE: MnemonicLanguage = MnemonicLanguage::English;
 
 pub struct KeyManagerApi<'a, TStore> {
     store: &'a TStore,
@@ -57,6 +70,10 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
     }
 
     pub fn derive_key(&self, branch: &str, index: u64) -> Result<DerivedKey<RistrettoPublicKey>, KeyManagerApiError> {
+        if branch == IMPORTED_KEY_BRANCH {
+            let key = self.store.with_read_tx(|tx| tx.imported_keys_get(index))?;
+            return Ok(DerivedKey { key, key_index: index });
+        }
         let km = self.get_or_create_key_manager(branch)?;
         let key = km
                 .derive_key(index)
@@ -65,6 +82,29 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
         Ok(key)
     }
 
+    /// Stores an externally generated secret key and returns its index on the imported key branch. If the key has
+    /// already been imported, the existing index is returned.
+    pub fn import_key(&self, secret_key: &PrivateKey) -> Result<u64, KeyManagerApiError> {
+        let public_key = PublicKey::from_secret_key(secret_key);
+        let mut tx = self.store.create_write_tx()?;
+        if let Some(index) = tx.imported_keys_get_index(&public_key).optional()? {
+            tx.rollback()?;
+            return Ok(index);
+        }
+        let index = tx.imported_keys_insert(&public_key, secret_key)?;
+        tx.commit()?;
+        Ok(index)
+    }
+
+    /// Returns the index of the imported key with the given public key, or None if the key has not been imported
+    pub fn get_imported_key_index(&self, public_key: &PublicKey) -> Result<Option<u64>, KeyManagerApiError> {
+        let index = self
+            .store
+            .with_read_tx(|tx| tx.imported_keys_get_index(public_key))
+            .optional()?;
+        Ok(index)
+    }
+
     pub fn next_key(&self, branch: &str) -> Result<DerivedKey<RistrettoPublicKey>, KeyManagerApiError> {
         let mut tx = self.store.create_write_tx()?;
         let index = tx.key_manager_get_last_index(branch).optional()?.unwrap_or(0);
@@ -78,6 +118,18 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
         Ok(key)
     }
 
+    /// Adds the key index to the wallet's known keys for the branch. This is a no-op if the index is already known.
+    pub fn add_key_index(&self, branch: &str, index: u64) -> Result<(), KeyManagerApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        if tx.key_manager_get_all(branch)?.iter().any(|(i, _)| *i == index) {
+            tx.rollback()?;
+            return Ok(());
+        }
+        tx.key_manager_insert(branch, index)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn set_active_key(&self, branch: &str, index: u64) -> Result<(), KeyManagerApiError> {
         let mut tx = self.store.create_write_tx()?;
         tx.key_manager_set_active_index(branch, index)?;
@@ -128,6 +180,27 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
         Ok(PublicKey::from_secret_key(&key.key))
     }
 
+    /// Returns the seed words from which the wallet root key, and therefore every derived key, can be restored. If a
+    /// passphrase is provided, the same passphrase is required to restore the wallet from the seed words.
+    pub fn get_seed_words(&self, passphrase: Option<SafePassword>) -> Result<SeedWords, KeyManagerApiError> {
+        let seed_words = self.cipher_seed.to_mnemonic(SEED_WORDS_LANGUAGE, passphrase)?;
+        Ok(seed_words)
+    }
+
+    /// Checks that the seed words and passphrase restore the wallet root key. This is used to confirm that a backup of
+    /// the seed words is correct before it is relied upon.
+    pub fn verify_seed_words(
+        &self,
+        seed_words: &SeedWords,
+        passphrase: Option<SafePassword>,
+    ) -> Result<(), KeyManagerApiError> {
+        let cipher_seed = cipher_seed_from_seed_words(seed_words, passphrase)?;
+        if cipher_seed.entropy() != self.cipher_seed.entropy() {
+            return Err(KeyManagerApiError::SeedWordsMismatch);
+        }
+        Ok(())
+    }
+
     fn get_or_create_key_manager(&self, branch: &str) -> Result<WalletKeyManager, KeyManagerApiError> {
         let mut tx = self.store.create_write_tx()?;
         let index = match tx.key_manager_get_active_index(branch).optional()? {
@@ -149,6 +222,26 @@ impl<'a, TStore: WalletStore> KeyManagerApi<'a, TStore> {
     }
 }
 
+/// Parses whitespace separated seed words
+pub fn parse_seed_words(seed_words: &str) -> SeedWords {
+    SeedWords::new(
+        seed_words
+            .split_whitespace()
+            .map(|word| Hidden::hide(word.to_string()))
+            .collect(),
+    )
+}
+
+/// Restores a wallet root key from seed words. The passphrase must match the passphrase that was provided when the
+/// seed words were created.
+pub fn cipher_seed_from_seed_words(
+    seed_words: &SeedWords,
+    passphrase: Option<SafePassword>,
+) -> Result<CipherSeed, KeyManagerApiError> {
+    let cipher_seed = CipherSeed::from_mnemonic(seed_words, passphrase)?;
+    Ok(cipher_seed)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum KeyManagerApiError {
     #[error("Store error: {0}")]
@@ -157,4 +250,6 @@ pub enum KeyManagerApiError {
     KeyManagerError(#[from] tari_key_manager::error::KeyManagerError),
     #[error("Key for public key {key}, branch {branch} not found")]
     KeyNotFound { key: PublicKey, branch: String },
+    #[error("The seed words do not restore the wallet root key")]
+    SeedWordsMismatch,
 }
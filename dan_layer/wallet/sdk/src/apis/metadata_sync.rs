@@ -0,0 +1,262 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Wallet metadata that may be synced between devices that share a wallet seed. The metadata document intentionally
+//! contains no key material: account key indexes, imported and view keys and the cipher seed are never included, so
+//! a leaked document (or sync passphrase) does not expose funds.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::optional::{IsNotFoundError, Optional};
+use tari_template_lib::models::ComponentAddress;
+use tari_transaction::TransactionId;
+
+use crate::{
+    apis::config::ConfigKey,
+    models::Contact,
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+/// The syncable, non-key metadata of a wallet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletMetadata {
+    /// Account names keyed by account address. Accounts without a name are not included.
+    #[serde(default)]
+    pub account_names: BTreeMap<String, String>,
+    /// Address book entries keyed by contact name
+    #[serde(default)]
+    pub contacts: BTreeMap<String, ContactMetadata>,
+    /// User notes keyed by transaction id
+    #[serde(default)]
+    pub transaction_annotations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactMetadata {
+    pub component_address: Option<ComponentAddress>,
+    pub public_key: Option<PublicKey>,
+}
+
+/// The result of a three-way merge of wallet metadata
+#[derive(Debug, Clone, Default)]
+pub struct MetadataMerge {
+    pub merged: WalletMetadata,
+    /// Entries that were changed both locally and remotely since the last sync. The local value is kept for these.
+    pub conflicts: Vec<MetadataConflict>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataConflict {
+    pub kind: &'static str,
+    pub key: String,
+}
+
+impl WalletMetadata {
+    /// Merges the local and remote metadata, given the metadata at the last successful sync (`base`). An entry that
+    /// changed on only one side since the last sync takes the changed value, including removals. An entry that
+    /// changed on both sides to different values is a conflict and the local value is kept, so the device that syncs
+    /// last wins and all devices converge on their next sync.
+    pub fn merge(base: &WalletMetadata, local: &WalletMetadata, remote: &WalletMetadata) -> MetadataMerge {
+        let mut conflicts = Vec::new();
+        let merged = WalletMetadata {
+            account_names: merge_entries(
+                "account_name",
+                &base.account_names,
+                &local.account_names,
+                &remote.account_names,
+                &mut conflicts,
+            ),
+            contacts: merge_entries(
+                "contact",
+                &base.contacts,
+                &local.contacts,
+                &remote.contacts,
+                &mut conflicts,
+            ),
+            transaction_annotations: merge_entries(
+                "transaction_annotation",
+                &base.transaction_annotations,
+                &local.transaction_annotations,
+                &remote.transaction_annotations,
+                &mut conflicts,
+            ),
+        };
+        MetadataMerge { merged, conflicts }
+    }
+}
+
+fn merge_entries<K, V>(
+    kind: &'static str,
+    base: &BTreeMap<K, V>,
+    local: &BTreeMap<K, V>,
+    remote: &BTreeMap<K, V>,
+    conflicts: &mut Vec<MetadataConflict>,
+) -> BTreeMap<K, V>
+where
+    K: Ord + Clone + Display,
+    V: Clone + PartialEq,
+{
+    let keys = base.keys().chain(local.keys()).chain(remote.keys()).collect::<BTreeSet<_>>();
+    let mut merged = BTreeMap::new();
+    for key in keys {
+        let (base, local, remote) = (base.get(key), local.get(key), remote.get(key));
+        let value = if local == remote || local == base {
+            remote
+        } else if remote == base {
+            local
+        } else {
+            conflicts.push(MetadataConflict {
+                kind,
+                key: key.to_string(),
+            });
+            local
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    merged
+}
+
+/// Reads and applies the syncable wallet metadata, and stores the transaction annotations that are only used by
+/// metadata sync
+pub struct MetadataSyncApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore: WalletStore> MetadataSyncApi<'a, TStore> {
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    /// Returns the current metadata of this wallet
+    pub fn export_metadata(&self) -> Result<WalletMetadata, MetadataSyncApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let num_accounts = tx.accounts_count()?;
+        let account_names = tx
+            .accounts_get_many(0, num_accounts)?
+            .into_iter()
+            .filter_map(|account| account.name.map(|name| (account.address.to_string(), name)))
+            .collect();
+        let contacts = tx
+            .contacts_get_all()?
+            .into_iter()
+            .map(|contact| {
+                (contact.name, ContactMetadata {
+                    component_address: contact.component_address,
+                    public_key: contact.public_key,
+                })
+            })
+            .collect();
+        let transaction_annotations = tx
+            .config_get(ConfigKey::TransactionAnnotations.as_key_str())
+            .optional()?
+            .map(|record| record.value)
+            .unwrap_or_default();
+
+        Ok(WalletMetadata {
+            account_names,
+            contacts,
+            transaction_annotations,
+        })
+    }
+
+    /// Returns the metadata at the last successful sync, or empty metadata if the wallet has never been synced
+    pub fn get_sync_base(&self) -> Result<WalletMetadata, MetadataSyncApiError> {
+        let base = self
+            .store
+            .with_read_tx(|tx| tx.config_get(ConfigKey::MetadataSyncBase.as_key_str()).optional())?;
+        Ok(base.map(|record| record.value).unwrap_or_default())
+    }
+
+    /// Updates the local wallet to match the metadata, and records it as the base of the next sync. Names of accounts
+    /// that are not in this wallet are retained in the metadata but otherwise ignored, since accounts (and their
+    /// keys) are never created by metadata sync.
+    pub fn apply_metadata(&self, metadata: &WalletMetadata) -> Result<(), MetadataSyncApiError> {
+        let current = self.export_metadata()?;
+        let mut tx = self.store.create_write_tx()?;
+        let num_accounts = tx.accounts_count()?;
+        for account in tx.accounts_get_many(0, num_accounts)? {
+            let name = metadata.account_names.get(&account.address.to_string());
+            if name != account.name.as_ref() {
+                tx.accounts_update(&account.address, name.map(String::as_str))?;
+            }
+        }
+
+        for name in current.contacts.keys() {
+            if !metadata.contacts.contains_key(name) {
+                tx.contacts_delete(name)?;
+            }
+        }
+        for (name, contact) in &metadata.contacts {
+            match current.contacts.get(name) {
+                Some(existing) if existing == contact => continue,
+                Some(_) => tx.contacts_delete(name)?,
+                None => {},
+            }
+            tx.contacts_insert(&Contact {
+                name: name.clone(),
+                component_address: contact.component_address,
+                public_key: contact.public_key.clone(),
+            })?;
+        }
+
+        tx.config_set(
+            ConfigKey::TransactionAnnotations.as_key_str(),
+            &metadata.transaction_annotations,
+            false,
+        )?;
+        tx.config_set(ConfigKey::MetadataSyncBase.as_key_str(), metadata, false)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Sets the annotation of a transaction, or removes it if `annotation` is None
+    pub fn set_transaction_annotation(
+        &self,
+        transaction_id: &TransactionId,
+        annotation: Option<String>,
+    ) -> Result<(), MetadataSyncApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        let mut annotations: BTreeMap<String, String> = tx
+            .config_get(ConfigKey::TransactionAnnotations.as_key_str())
+            .optional()?
+            .map(|record| record.value)
+            .unwrap_or_default();
+        match annotation {
+            Some(annotation) => annotations.insert(transaction_id.to_string(), annotation),
+            None => annotations.remove(&transaction_id.to_string()),
+        };
+        tx.config_set(ConfigKey::TransactionAnnotations.as_key_str(), &annotations, false)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn get_transaction_annotation(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> Result<Option<String>, MetadataSyncApiError> {
+        let annotations = self.store.with_read_tx(|tx| {
+            tx.config_get::<BTreeMap<String, String>>(ConfigKey::TransactionAnnotations.as_key_str())
+                .optional()
+        })?;
+        Ok(annotations.and_then(|record| record.value.get(&transaction_id.to_string()).cloned()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MetadataSyncApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+}
+
+impl IsNotFoundError for MetadataSyncApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
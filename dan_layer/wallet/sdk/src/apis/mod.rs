@@ -1,13 +1,22 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+pub mod account_recovery;
 pub mod accounts;
+pub mod balance_history;
+pub mod confidential_audit;
 pub mod confidential_crypto;
 pub mod confidential_outputs;
 pub mod confidential_transfer;
 pub mod config;
+pub mod contacts;
+pub mod dynamic_template;
+pub mod garbage_collection;
 pub mod jwt;
 pub mod key_manager;
+pub mod metadata_sync;
 pub mod non_fungible_tokens;
+pub mod scheduled_transactions;
 pub mod substate;
+pub mod templates;
 pub mod transaction;
@@ -0,0 +1,158 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::NaiveDateTime;
+use tari_dan_common_types::{optional::IsNotFoundError, Epoch};
+use tari_engine_types::instruction::Instruction;
+use tari_template_lib::models::{Amount, ComponentAddress};
+use tari_transaction::TransactionId;
+
+use crate::{
+    models::{ScheduledTransaction, ScheduledTransactionStatus, TransactionSchedule},
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+/// Transactions that are submitted automatically according to a schedule
+pub struct ScheduledTransactionsApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore: WalletStore> ScheduledTransactionsApi<'a, TStore> {
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    /// Creates a new active scheduled transaction that is first due according to the schedule after `now`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create(
+        &self,
+        name: String,
+        schedule: TransactionSchedule,
+        instructions: Vec<Instruction>,
+        fee_account: ComponentAddress,
+        max_fee: Amount,
+        max_runs: Option<u64>,
+        now: NaiveDateTime,
+    ) -> Result<ScheduledTransaction, ScheduledTransactionsApiError> {
+        schedule
+            .validate()
+            .map_err(|e| ScheduledTransactionsApiError::InvalidSchedule { details: e.to_string() })?;
+        if instructions.is_empty() {
+            return Err(ScheduledTransactionsApiError::NoInstructions);
+        }
+        if max_runs == Some(0) {
+            return Err(ScheduledTransactionsApiError::InvalidMaxRuns);
+        }
+
+        let mut scheduled = ScheduledTransaction {
+            id: 0,
+            name,
+            next_run_at: schedule.next_time_after(now),
+            next_run_epoch: schedule.first_epoch(),
+            schedule,
+            instructions,
+            fee_account,
+            max_fee,
+            max_runs,
+            num_runs: 0,
+            status: ScheduledTransactionStatus::Active,
+            last_transaction_id: None,
+            last_error: None,
+            created_at: now,
+        };
+        scheduled.id = self
+            .store
+            .with_write_tx(|tx| tx.scheduled_transactions_insert(&scheduled))?;
+        Ok(scheduled)
+    }
+
+    pub fn get(&self, id: u64) -> Result<ScheduledTransaction, ScheduledTransactionsApiError> {
+        let scheduled = self.store.with_read_tx(|tx| tx.scheduled_transactions_get(id))?;
+        Ok(scheduled)
+    }
+
+    pub fn get_all(
+        &self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, ScheduledTransactionsApiError> {
+        let scheduled = self
+            .store
+            .with_read_tx(|tx| tx.scheduled_transactions_get_all(status))?;
+        Ok(scheduled)
+    }
+
+    /// Returns the active scheduled transactions that are due at the given time and epoch. Epoch based schedules are
+    /// never due if the current epoch is not known.
+    pub fn get_due(
+        &self,
+        now: NaiveDateTime,
+        current_epoch: Option<Epoch>,
+    ) -> Result<Vec<ScheduledTransaction>, ScheduledTransactionsApiError> {
+        let active = self.get_all(Some(ScheduledTransactionStatus::Active))?;
+        Ok(active
+            .into_iter()
+            .filter(|scheduled| scheduled.is_due(now, current_epoch))
+            .collect())
+    }
+
+    /// Cancels an active scheduled transaction so that it is no longer submitted
+    pub fn cancel(&self, id: u64) -> Result<ScheduledTransaction, ScheduledTransactionsApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        let mut scheduled = tx.scheduled_transactions_get(id)?;
+        if !scheduled.is_active() {
+            tx.rollback()?;
+            return Err(ScheduledTransactionsApiError::NotActive {
+                id,
+                status: scheduled.status,
+            });
+        }
+        scheduled.status = ScheduledTransactionStatus::Cancelled;
+        tx.scheduled_transactions_update(&scheduled)?;
+        tx.commit()?;
+        Ok(scheduled)
+    }
+
+    /// Records the result of submitting a scheduled transaction and advances it to its next run
+    pub fn record_run(
+        &self,
+        id: u64,
+        now: NaiveDateTime,
+        current_epoch: Option<Epoch>,
+        result: Result<TransactionId, String>,
+    ) -> Result<ScheduledTransaction, ScheduledTransactionsApiError> {
+        let mut tx = self.store.create_write_tx()?;
+        let mut scheduled = tx.scheduled_transactions_get(id)?;
+        // The schedule may have been cancelled while the transaction was being submitted
+        let was_active = scheduled.is_active();
+        scheduled.record_run(now, current_epoch, result);
+        if !was_active {
+            scheduled.status = ScheduledTransactionStatus::Cancelled;
+        }
+        tx.scheduled_transactions_update(&scheduled)?;
+        tx.commit()?;
+        Ok(scheduled)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduledTransactionsApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+    #[error("Invalid schedule: {details}")]
+    InvalidSchedule { details: String },
+    #[error("Scheduled transaction must contain at least one instruction")]
+    NoInstructions,
+    #[error("Maximum number of runs must be greater than zero")]
+    InvalidMaxRuns,
+    #[error("Scheduled transaction {id} is not active (status: {status})")]
+    NotActive {
+        id: u64,
+        status: ScheduledTransactionStatus,
+    },
+}
+
+impl IsNotFoundError for ScheduledTransactionsApiError {
+    fn is_not_found_error(&self) -> bool {
+        matches!(self, Self::StoreError(e) if e.is_not_found_error())
+    }
+}
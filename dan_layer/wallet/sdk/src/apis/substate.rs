@@ -13,6 +13,7 @@ use tari_engine_types::{
     indexed_value::{IndexedValueError, IndexedWellKnownTypes},
     substate::{SubstateId, SubstateValue},
     transaction_receipt::TransactionReceiptAddress,
+    vault::Vault,
     TemplateAddress,
 };
 use tari_transaction::TransactionId;
@@ -194,6 +195,48 @@ where
         })
     }
 
+    /// Scans the network for the latest version of each vault that is held in the state of the account component.
+    /// Vaults that do not exist according to the network are skipped.
+    pub async fn scan_account_vaults(
+        &self,
+        account_address: &SubstateId,
+    ) -> Result<Vec<(VersionedSubstateId, Vault)>, SubstateApiError> {
+        let ValidatorScanResult { substate, .. } = self.scan_for_substate(account_address, None).await?;
+        let component = substate.component().ok_or_else(|| {
+            SubstateApiError::InvalidValidatorNodeResponse(format!("Substate {} is not a component", account_address))
+        })?;
+        let value = IndexedWellKnownTypes::from_value(component.state())?;
+
+        let mut vaults = Vec::with_capacity(value.vault_ids().len());
+        for vault_id in value.vault_ids() {
+            let vault_substate_id = SubstateId::Vault(*vault_id);
+            let scan_result = self.scan_for_substate(&vault_substate_id, None).await.optional()?;
+            match scan_result {
+                Some(ValidatorScanResult {
+                    address,
+                    substate: SubstateValue::Vault(vault),
+                    ..
+                }) => vaults.push((address, vault)),
+                Some(_) => {
+                    return Err(SubstateApiError::InvalidValidatorNodeResponse(format!(
+                        "Substate {} is not a vault",
+                        vault_substate_id
+                    )));
+                },
+                None => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Vault {} for account {} does not exist according to the network",
+                        vault_substate_id,
+                        account_address
+                    );
+                },
+            }
+        }
+
+        Ok(vaults)
+    }
+
     pub fn save_root(
         &self,
         created_by_tx: TransactionId,
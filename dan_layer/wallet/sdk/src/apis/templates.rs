@@ -0,0 +1,46 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_engine_types::{substate::SubstateId, TemplateAddress};
+use tari_transaction::TransactionId;
+
+use crate::{
+    models::PublishedTemplateModel,
+    storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+
+pub struct TemplatesApi<'a, TStore> {
+    store: &'a TStore,
+}
+
+impl<'a, TStore: WalletStore> TemplatesApi<'a, TStore> {
+    pub fn new(store: &'a TStore) -> Self {
+        Self { store }
+    }
+
+    /// Records a template that has been successfully published by the given account
+    pub fn add_published_template(
+        &self,
+        template_address: &TemplateAddress,
+        author_account: &SubstateId,
+        transaction_id: TransactionId,
+        binary_size: u64,
+    ) -> Result<(), TemplatesApiError> {
+        self.store.with_write_tx(|tx| {
+            tx.published_templates_insert(template_address, author_account, transaction_id, binary_size)
+        })?;
+        Ok(())
+    }
+
+    pub fn get_published_templates(&self) -> Result<Vec<PublishedTemplateModel>, TemplatesApiError> {
+        let mut tx = self.store.create_read_tx()?;
+        let templates = tx.published_templates_get_all()?;
+        Ok(templates)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TemplatesApiError {
+    #[error("Store error: {0}")]
+    StoreError(#[from] WalletStorageError),
+}
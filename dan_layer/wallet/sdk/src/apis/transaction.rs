@@ -1,22 +1,33 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use log::*;
+use tari_common_types::types::PublicKey;
 use tari_dan_common_types::{
     optional::{IsNotFoundError, Optional},
+    Epoch,
     SubstateRequirement,
 };
+use tari_dan_storage::consensus_models::Decision;
 use tari_engine_types::{
+    commit_result::ExecuteResult,
     indexed_value::{IndexedValueError, IndexedWellKnownTypes},
-    substate::SubstateDiff,
+    substate::{SubstateDiff, SubstateId, SubstateValue},
 };
 use tari_template_lib::prelude::ComponentAddress;
-use tari_transaction::{Transaction, TransactionId};
+use tari_transaction::{Transaction, TransactionId, UnsignedTransaction};
 
 use crate::{
-    models::{NewAccountInfo, TransactionStatus, VersionedSubstateId, WalletTransaction},
+    models::{
+        ImportedTransaction,
+        NewAccountInfo,
+        TransactionFailure,
+        TransactionStatus,
+        VersionedSubstateId,
+        WalletTransaction,
+    },
     network::{TransactionFinalizedResult, WalletNetworkInterface},
     storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter},
 };
@@ -96,6 +107,7 @@ where
         &self,
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<WalletTransaction, TransactionApiError> {
         self.store
             .with_write_tx(|tx| tx.transactions_insert(&transaction, &required_substates, None, true))?;
@@ -103,7 +115,7 @@ where
         let tx_id = *transaction.id();
         let query = self
             .network_interface
-            .submit_dry_run_transaction(transaction, required_substates)
+            .submit_dry_run_transaction(transaction, required_substates, substate_overrides)
             .await
             .map_err(|e| TransactionApiError::NetworkInterfaceError(e.to_string()))?;
 
@@ -150,6 +162,12 @@ where
         Ok(transactions)
     }
 
+    /// Returns an iterator over all (non-dry-run) transactions in the order they were inserted. Transactions are
+    /// loaded from the store in batches of `batch_size`, and the store is not locked between batches.
+    pub fn transactions_iter(&self, batch_size: u64) -> TransactionsIter<'a, TStore> {
+        TransactionsIter::new(self.store, batch_size)
+    }
+
     pub async fn check_and_store_finalized_transaction(
         &self,
         transaction_id: TransactionId,
@@ -181,23 +199,10 @@ where
                 execution_result,
                 execution_time,
                 finalized_time,
-                abort_details: _,
+                abort_details,
                 ..
             } => {
-                let new_status = if final_decision.is_commit() {
-                    match execution_result.as_ref() {
-                        Some(execution_result) => {
-                            if execution_result.finalize.is_fee_only() {
-                                TransactionStatus::OnlyFeeAccepted
-                            } else {
-                                TransactionStatus::Accepted
-                            }
-                        },
-                        None => TransactionStatus::Accepted,
-                    }
-                } else {
-                    TransactionStatus::Rejected
-                };
+                let new_status = finalized_status(&final_decision, execution_result.as_deref());
 
                 // let qc_resp = self.network_interface
                 //     .fetch_transaction_quorum_certificates(GetTransactionQcsRequest { hash })
@@ -233,6 +238,16 @@ where
                         Some(finalized_time),
                     )?;
 
+                    if !transaction.is_dry_run && new_status.is_failure() {
+                        let reason = execution_result
+                            .as_ref()
+                            .and_then(|e| e.finalize.full_reject())
+                            .map(|reason| reason.to_string())
+                            .or(abort_details)
+                            .unwrap_or_else(|| format!("Transaction finalized with status {}", new_status));
+                        self.record_failure(tx, &transaction, new_status, &reason)?;
+                    }
+
                     // if the transaction being processed is confidential,
                     // we should make sure that the account's locked outputs
                     // are either set to spent or released, depending if the
@@ -256,6 +271,291 @@ where
         }
     }
 
+    /// Imports a transaction that was submitted by another wallet. The result of the transaction is fetched from the
+    /// network and the substates of this wallet's accounts and vaults that it changed are updated. Vaults that the
+    /// transaction added to this wallet's accounts are not created here, they are picked up the next time that the
+    /// account is refreshed. If the transaction has already been imported, the existing record is returned.
+    pub async fn import_external_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<ImportedTransaction, TransactionApiError> {
+        let (existing, is_own_transaction) = self.store.with_read_tx(|tx| {
+            let existing = tx.imported_transactions_get(transaction_id).optional()?;
+            let is_own_transaction = tx.transactions_get(transaction_id).optional()?.is_some();
+            Ok::<_, WalletStorageError>((existing, is_own_transaction))
+        })?;
+        if let Some(existing) = existing {
+            return Ok(existing);
+        }
+        if is_own_transaction {
+            return Err(TransactionApiError::TransactionAlreadyInWallet { transaction_id });
+        }
+
+        let resp = self
+            .network_interface
+            .query_transaction_result(transaction_id)
+            .await
+            .map_err(|e| TransactionApiError::NetworkInterfaceError(e.to_string()))?;
+        let TransactionFinalizedResult::Finalized {
+            final_decision,
+            execution_result,
+            ..
+        } = resp.result
+        else {
+            return Err(TransactionApiError::TransactionNotFinalized { transaction_id });
+        };
+
+        let status = finalized_status(&final_decision, execution_result.as_deref());
+        let final_fee = execution_result
+            .as_ref()
+            .map(|e| e.finalize.fee_receipt.total_fees_charged());
+
+        self.store.with_write_tx(|tx| {
+            let diff = execution_result
+                .as_ref()
+                .and_then(|e| e.finalize.result.accept())
+                .filter(|_| final_decision.is_commit());
+            let accounts = match diff {
+                Some(diff) => self.reconcile_external_result(tx, transaction_id, diff)?,
+                None => vec![],
+            };
+            if accounts.is_empty() {
+                return Err(TransactionApiError::TransactionDoesNotAffectWallet { transaction_id });
+            }
+
+            info!(
+                target: LOG_TARGET,
+                "Importing transaction {} ({}) that changed {} account(s)",
+                transaction_id,
+                status,
+                accounts.len()
+            );
+            tx.imported_transactions_insert(transaction_id, status, final_fee, &accounts)?;
+            let imported = tx.imported_transactions_get(transaction_id)?;
+            Ok(imported)
+        })
+    }
+
+    /// Checks a transaction that was signed on an offline machine before it is submitted. The transaction must have at
+    /// least one signature, all signatures must be valid, all inputs must be versioned, it must not already be in the
+    /// wallet and it must not have expired.
+    pub async fn verify_offline_signed_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<(), TransactionApiError> {
+        let transaction_id = *transaction.id();
+        let invalid = |details: &str| TransactionApiError::InvalidOfflineSignedTransaction {
+            transaction_id,
+            details: details.to_string(),
+        };
+
+        if transaction.signatures().is_empty() {
+            return Err(invalid("the transaction is not signed"));
+        }
+        if !transaction.verify_all_signatures() {
+            return Err(invalid("the transaction has an invalid signature"));
+        }
+        if transaction.unsigned_transaction().has_inputs_without_version() {
+            return Err(invalid("the transaction has inputs that are not versioned"));
+        }
+        if self
+            .store
+            .with_read_tx(|tx| tx.transactions_get(transaction_id).optional())?
+            .is_some()
+        {
+            return Err(TransactionApiError::TransactionAlreadyInWallet { transaction_id });
+        }
+
+        if let Some(max_epoch) = transaction.unsigned_transaction().max_epoch() {
+            let current_epoch = self
+                .network_interface
+                .get_current_epoch()
+                .await
+                .map_err(|e| TransactionApiError::NetworkInterfaceError(e.to_string()))?;
+            if max_epoch < current_epoch {
+                return Err(invalid(&format!(
+                    "the transaction expired at epoch {} and the current epoch is {}",
+                    max_epoch, current_epoch
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the transactions that were imported from other wallets, most recently imported first
+    pub fn get_imported_transactions(&self) -> Result<Vec<ImportedTransaction>, TransactionApiError> {
+        let imported = self.store.with_read_tx(|tx| tx.imported_transactions_get_all())?;
+        Ok(imported)
+    }
+
+    /// Marks a pending transaction whose max_epoch has passed as expired. The transaction is recorded as a failure so
+    /// that it can be rebuilt with [Self::rebuild_failed_transaction], and any outputs locked by it are released.
+    /// Returns None if the transaction is no longer pending.
+    pub fn mark_expired(
+        &self,
+        transaction_id: TransactionId,
+        current_epoch: Epoch,
+    ) -> Result<Option<WalletTransaction>, TransactionApiError> {
+        self.store.with_write_tx(|tx| {
+            let transaction = tx.transactions_get(transaction_id)?;
+            if !matches!(transaction.status, TransactionStatus::New | TransactionStatus::Pending) {
+                return Ok(None);
+            }
+
+            tx.transactions_set_result_and_status(
+                transaction_id,
+                None,
+                None,
+                None,
+                TransactionStatus::Expired,
+                None,
+                None,
+            )?;
+            let reason = format!(
+                "Transaction expired in epoch {} (max_epoch = {})",
+                current_epoch,
+                transaction
+                    .transaction
+                    .max_epoch()
+                    .map_or_else(|| "none".to_string(), |e| e.to_string())
+            );
+            self.record_failure(tx, &transaction, TransactionStatus::Expired, &reason)?;
+            self.release_all_outputs_for_transaction_internal(tx, transaction_id)?;
+
+            let transaction = tx.transactions_get(transaction_id)?;
+            Ok::<_, TransactionApiError>(Some(transaction))
+        })
+    }
+
+    /// Returns transactions that were rejected by the network, most recent first
+    pub fn get_failures(&self, include_retried: bool) -> Result<Vec<TransactionFailure>, TransactionApiError> {
+        let failures = self
+            .store
+            .with_read_tx(|tx| tx.transaction_failures_get_all(include_retried))?;
+        Ok(failures)
+    }
+
+    pub fn get_failure(&self, transaction_id: TransactionId) -> Result<TransactionFailure, TransactionApiError> {
+        let failure = self
+            .store
+            .with_read_tx(|tx| tx.transaction_failures_get(transaction_id))?;
+        Ok(failure)
+    }
+
+    /// Rebuilds a failed transaction so that it can be retried. The inputs of the failed transaction are re-detected
+    /// at the latest versions known to the network. Inputs that are not found on the network are left unversioned.
+    /// The returned transaction must be signed and submitted by the caller, after which
+    /// [Self::mark_failure_retried] should be called.
+    pub async fn rebuild_failed_transaction(
+        &self,
+        transaction_id: TransactionId,
+    ) -> Result<RebuiltTransaction, TransactionApiError> {
+        let (failure, failed) = self.store.with_read_tx(|tx| {
+            let failure = tx.transaction_failures_get(transaction_id)?;
+            let failed = tx.transactions_get(transaction_id)?;
+            Ok::<_, WalletStorageError>((failure, failed))
+        })?;
+
+        if let Some(retried_as) = failure.retried_as {
+            return Err(TransactionApiError::AlreadyRetried {
+                transaction_id,
+                retried_as,
+            });
+        }
+
+        let mut inputs = Vec::with_capacity(failure.inputs.len());
+        for input in failure.inputs {
+            let latest = self
+                .network_interface
+                .query_substate(input.substate_id(), None, false)
+                .await
+                .optional()
+                .map_err(|e| TransactionApiError::NetworkInterfaceError(e.to_string()))?;
+            let version = latest.map(|s| s.version);
+            debug!(
+                target: LOG_TARGET,
+                "Retrying transaction {}: input {} v{:?} -> v{:?}",
+                transaction_id,
+                input.substate_id(),
+                input.version(),
+                version
+            );
+            inputs.push(SubstateRequirement::new(input.into_substate_id(), version));
+        }
+
+        let (mut unsigned_transaction, signatures, _) = failed.transaction.into_parts();
+        unsigned_transaction.inputs = inputs.into_iter().collect();
+
+        Ok(RebuiltTransaction {
+            unsigned_transaction,
+            signers: signatures.iter().map(|s| s.public_key().clone()).collect(),
+            // Versions for these are filled in by the network when the transaction is submitted
+            required_substates: failed
+                .required_substates
+                .into_iter()
+                .map(|r| SubstateRequirement::unversioned(r.into_substate_id()))
+                .collect(),
+            new_account_info: failed.new_account_info,
+        })
+    }
+
+    pub fn mark_failure_retried(
+        &self,
+        transaction_id: TransactionId,
+        retried_as: TransactionId,
+    ) -> Result<(), TransactionApiError> {
+        self.store
+            .with_write_tx(|tx| tx.transaction_failures_set_retried(transaction_id, retried_as))?;
+        Ok(())
+    }
+
+    /// Records the inputs of a failed transaction, filling in the versions known to the wallet for any inputs that
+    /// were submitted without a version
+    fn record_failure(
+        &self,
+        tx: &mut TStore::WriteTransaction<'_>,
+        transaction: &WalletTransaction,
+        status: TransactionStatus,
+        reason: &str,
+    ) -> Result<(), TransactionApiError> {
+        let mut inputs = Vec::<SubstateRequirement>::new();
+        for input in transaction
+            .transaction
+            .all_inputs_iter()
+            .chain(transaction.required_substates.iter().cloned())
+        {
+            if inputs.iter().any(|i| i.substate_id() == input.substate_id()) {
+                continue;
+            }
+            let version = match input.version() {
+                Some(version) => Some(version),
+                None => tx
+                    .substates_get(input.substate_id())
+                    .optional()?
+                    .map(|s| s.address.version),
+            };
+            inputs.push(SubstateRequirement::new(input.into_substate_id(), version));
+        }
+
+        // If this transaction is itself a retry, it is the next attempt of the original transaction
+        let attempt = tx
+            .transaction_failures_get_by_retried_as(*transaction.transaction.id())
+            .optional()?
+            .map_or(0, |prev| prev.attempt + 1);
+
+        info!(
+            target: LOG_TARGET,
+            "Recording failure for transaction {} ({}, attempt {}): {}",
+            transaction.transaction.id(),
+            status,
+            attempt,
+            reason
+        );
+        tx.transaction_failures_insert(*transaction.transaction.id(), status, reason, &inputs, attempt)?;
+        Ok(())
+    }
+
     pub fn release_all_outputs_for_transaction(
         &self,
         transaction_id: TransactionId,
@@ -279,6 +579,54 @@ where
         Ok(())
     }
 
+    /// Updates the substates of this wallet's accounts and vaults that were changed by a transaction submitted by
+    /// another wallet. Substates that do not belong to this wallet are ignored. Returns the addresses of the accounts
+    /// that were changed.
+    fn reconcile_external_result(
+        &self,
+        tx: &mut TStore::WriteTransaction<'_>,
+        transaction_id: TransactionId,
+        diff: &SubstateDiff,
+    ) -> Result<Vec<SubstateId>, TransactionApiError> {
+        let mut accounts = Vec::new();
+        for (id, substate) in diff.up_iter() {
+            let account_address = if let Some(header) = substate.substate_value().component() {
+                if tx.accounts_get(id).optional()?.is_none() {
+                    continue;
+                }
+                tx.substates_upsert_root(
+                    transaction_id,
+                    VersionedSubstateId {
+                        substate_id: id.clone(),
+                        version: substate.version(),
+                    },
+                    Some(header.module_name.clone()),
+                    Some(header.template_address),
+                )?;
+                id.clone()
+            } else if let Some(vault) = substate.substate_value().vault() {
+                let Some(vault_model) = tx.vaults_get(id).optional()? else {
+                    continue;
+                };
+                tx.substates_upsert_child(transaction_id, vault_model.account_address.clone(), VersionedSubstateId {
+                    substate_id: id.clone(),
+                    version: substate.version(),
+                })?;
+                tx.vaults_update(id, vault.balance(), vault_model.confidential_balance)?;
+                vault_model.account_address
+            } else {
+                continue;
+            };
+
+            debug!(target: LOG_TARGET, "Transaction {} changed {} in account {}", transaction_id, id, account_address);
+            if !accounts.contains(&account_address) {
+                accounts.push(account_address);
+            }
+        }
+
+        Ok(accounts)
+    }
+
     fn commit_result(
         &self,
         tx: &mut TStore::WriteTransaction<'_>,
@@ -363,6 +711,64 @@ where
     }
 }
 
+pub struct TransactionsIter<'a, TStore> {
+    store: &'a TStore,
+    batch_size: u64,
+    cursor: Option<u64>,
+    batch: VecDeque<WalletTransaction>,
+    is_done: bool,
+}
+
+impl<'a, TStore: WalletStore> TransactionsIter<'a, TStore> {
+    fn new(store: &'a TStore, batch_size: u64) -> Self {
+        Self {
+            store,
+            batch_size: batch_size.max(1),
+            cursor: None,
+            batch: VecDeque::new(),
+            is_done: false,
+        }
+    }
+
+    fn fetch_next_batch(&mut self) -> Result<(), TransactionApiError> {
+        let page = self
+            .store
+            .with_read_tx(|tx| tx.transactions_fetch_after(self.cursor, self.batch_size))?;
+        if (page.len() as u64) < self.batch_size {
+            self.is_done = true;
+        }
+        if let Some((cursor, _)) = page.last() {
+            self.cursor = Some(*cursor);
+        }
+        self.batch.extend(page.into_iter().map(|(_, tx)| tx));
+        Ok(())
+    }
+}
+
+impl<TStore: WalletStore> Iterator for TransactionsIter<'_, TStore> {
+    type Item = Result<WalletTransaction, TransactionApiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.batch.is_empty() && !self.is_done {
+            if let Err(err) = self.fetch_next_batch() {
+                self.is_done = true;
+                return Some(Err(err));
+            }
+        }
+        self.batch.pop_front().map(Ok)
+    }
+}
+
+/// A failed transaction that has been rebuilt with fresh inputs
+#[derive(Debug, Clone)]
+pub struct RebuiltTransaction {
+    pub unsigned_transaction: UnsignedTransaction,
+    /// The public keys that signed the failed transaction
+    pub signers: Vec<PublicKey>,
+    pub required_substates: Vec<SubstateRequirement>,
+    pub new_account_info: Option<NewAccountInfo>,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionApiError {
     #[error("Store error: {0}")]
@@ -373,6 +779,33 @@ pub enum TransactionApiError {
     IndexedValueError(#[from] IndexedValueError),
     #[error("Invalid transaction query response: {details}")]
     InvalidTransactionQueryResponse { details: String },
+    #[error("Transaction {transaction_id} has already been retried as {retried_as}")]
+    AlreadyRetried {
+        transaction_id: TransactionId,
+        retried_as: TransactionId,
+    },
+    #[error("Transaction {transaction_id} was submitted by this wallet and cannot be imported")]
+    TransactionAlreadyInWallet { transaction_id: TransactionId },
+    #[error("Transaction {transaction_id} has not been finalized")]
+    TransactionNotFinalized { transaction_id: TransactionId },
+    #[error("Transaction {transaction_id} did not change any accounts in this wallet")]
+    TransactionDoesNotAffectWallet { transaction_id: TransactionId },
+    #[error("Offline signed transaction {transaction_id} is invalid: {details}")]
+    InvalidOfflineSignedTransaction {
+        transaction_id: TransactionId,
+        details: String,
+    },
+}
+
+/// Returns the status of a transaction that has been finalized by the network
+fn finalized_status(final_decision: &Decision, execution_result: Option<&ExecuteResult>) -> TransactionStatus {
+    if !final_decision.is_commit() {
+        return TransactionStatus::Rejected;
+    }
+    match execution_result {
+        Some(execution_result) if execution_result.finalize.is_fee_only() => TransactionStatus::OnlyFeeAccepted,
+        _ => TransactionStatus::Accepted,
+    }
 }
 
 impl IsNotFoundError for TransactionApiError {
@@ -10,6 +10,6 @@ mod sdk;
 pub use sdk::{DanWalletSdk, WalletSdkConfig};
 pub mod network;
 
-pub use tari_key_manager::cipher_seed::CipherSeed;
+pub use tari_key_manager::{cipher_seed::CipherSeed, SeedWords};
 
 pub type WalletSecretKey = tari_key_manager::key_manager::DerivedKey<tari_crypto::ristretto::RistrettoPublicKey>;
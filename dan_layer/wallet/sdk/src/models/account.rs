@@ -4,8 +4,11 @@
 use std::fmt::{Display, Formatter};
 
 use tari_bor::{Deserialize, Serialize};
+use tari_common_types::types::PublicKey;
 use tari_engine_types::substate::SubstateId;
 
+use crate::apis::key_manager;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(
     feature = "ts",
@@ -18,6 +21,23 @@ pub struct Account {
     #[cfg_attr(feature = "ts", ts(type = "number"))]
     pub key_index: u64,
     pub is_default: bool,
+    #[serde(default)]
+    pub kind: AccountKind,
+}
+
+impl Account {
+    /// Returns true if the wallet does not hold the owner key for this account and therefore cannot sign for it
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self.kind, AccountKind::WatchOnly { .. })
+    }
+
+    /// Returns the key manager branch of the account owner key
+    pub fn key_branch(&self) -> &'static str {
+        match self.kind {
+            AccountKind::Imported => key_manager::IMPORTED_KEY_BRANCH,
+            AccountKind::Owned | AccountKind::WatchOnly { .. } => key_manager::TRANSACTION_BRANCH,
+        }
+    }
 }
 
 impl Display for Account {
@@ -35,3 +55,23 @@ pub struct NewAccountInfo {
     pub key_index: u64,
     pub is_default: bool,
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum AccountKind {
+    /// The account owner key is derived from the wallet seed using the account key index
+    #[default]
+    Owned,
+    /// The account owner key was imported into the wallet and cannot be derived from the wallet seed. The account key
+    /// index refers to the imported key.
+    Imported,
+    /// The account is tracked using the owner public key. The wallet cannot sign for this account.
+    WatchOnly {
+        #[cfg_attr(feature = "ts", ts(type = "string"))]
+        owner_public_key: PublicKey,
+    },
+}
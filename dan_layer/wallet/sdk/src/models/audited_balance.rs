@@ -0,0 +1,35 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::{
+    models::{Amount, ResourceAddress, VaultId},
+    prelude::ComponentAddress,
+};
+
+/// The balance of a confidential vault as determined by an auditor holding the resource view key.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct AuditedVaultBalance {
+    pub resource_address: ResourceAddress,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub view_key_index: u64,
+    pub vault_id: VaultId,
+    /// The account component that owns the vault, if known
+    pub owner: Option<ComponentAddress>,
+    /// The sum of all confidential outputs that could be decrypted with the view key
+    pub balance: Amount,
+    pub num_outputs: u32,
+    /// The number of outputs for which the value could not be determined within the brute force range
+    pub num_unresolved: u32,
+    pub vault_version: u32,
+}
+
+impl AuditedVaultBalance {
+    pub fn is_fully_resolved(&self) -> bool {
+        self.num_unresolved == 0
+    }
+}
@@ -0,0 +1,33 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common_types::types::PublicKey;
+use tari_engine_types::component::new_component_address_from_public_key;
+use tari_template_builtin::ACCOUNT_TEMPLATE_ADDRESS;
+use tari_template_lib::models::ComponentAddress;
+
+/// A named counterparty in the wallet address book
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct Contact {
+    pub name: String,
+    pub component_address: Option<ComponentAddress>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub public_key: Option<PublicKey>,
+}
+
+impl Contact {
+    /// Returns the component address of the contact. If no address was given, the address of the account owned by the
+    /// contact public key is returned.
+    pub fn account_address(&self) -> Option<ComponentAddress> {
+        self.component_address.or_else(|| {
+            self.public_key
+                .as_ref()
+                .map(|pk| new_component_address_from_public_key(&ACCOUNT_TEMPLATE_ADDRESS, pk))
+        })
+    }
+}
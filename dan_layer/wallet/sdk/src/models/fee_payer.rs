@@ -0,0 +1,17 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde::{Deserialize, Serialize};
+
+/// Determines which account pays the transaction fees when a request does not specify a fee account.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeePayerStrategy {
+    /// Fees are paid by the account the transaction spends from, or the default account if there is no such account
+    #[default]
+    SameAsSource,
+    /// Fees are always paid by the given account name or component address
+    Designated(String),
+    /// Fees are paid by each account holding a revealed Tari balance in turn
+    RoundRobin,
+}
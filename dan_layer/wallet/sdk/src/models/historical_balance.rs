@@ -0,0 +1,36 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_template_lib::models::{Amount, ResourceAddress, VaultId};
+
+/// The balance of an account vault at a past epoch
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct HistoricalVaultBalance {
+    pub vault_id: VaultId,
+    pub resource_address: ResourceAddress,
+    /// The version of the vault that was current at the requested epoch
+    pub vault_version: u32,
+    pub revealed_balance: Amount,
+    /// The confidential balance is only known if the version is the wallet's current version of the vault. Past
+    /// confidential outputs are not retained by the wallet.
+    pub confidential_balance: Option<Amount>,
+    pub source: HistoricalBalanceSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum HistoricalBalanceSource {
+    /// The balance was taken from the wallet database
+    Local,
+    /// The balance was taken from the vault substate provided by the indexer
+    Indexer,
+}
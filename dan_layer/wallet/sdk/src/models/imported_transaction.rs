@@ -0,0 +1,29 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_engine_types::substate::SubstateId;
+use tari_template_lib::models::Amount;
+use tari_transaction::TransactionId;
+
+use crate::models::TransactionStatus;
+
+/// A record of a transaction that was submitted by another wallet and imported into this wallet because it changed
+/// one or more of this wallet's accounts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct ImportedTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    pub final_fee: Option<Amount>,
+    /// The accounts in this wallet whose substates were changed by the transaction
+    pub accounts: Vec<SubstateId>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub imported_at: NaiveDateTime,
+}
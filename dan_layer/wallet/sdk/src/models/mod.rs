@@ -7,6 +7,9 @@ pub use account::*;
 mod config;
 pub use config::Config;
 
+mod contact;
+pub use contact::*;
+
 mod wallet_transaction;
 pub use wallet_transaction::*;
 
@@ -24,3 +27,24 @@ pub use vault::*;
 
 mod non_fungible_tokens;
 pub use non_fungible_tokens::*;
+
+mod audited_balance;
+pub use audited_balance::*;
+
+mod historical_balance;
+pub use historical_balance::*;
+
+mod published_template;
+pub use published_template::*;
+
+mod transaction_failure;
+pub use transaction_failure::*;
+
+mod imported_transaction;
+pub use imported_transaction::*;
+
+mod fee_payer;
+pub use fee_payer::*;
+
+mod scheduled_transaction;
+pub use scheduled_transaction::*;
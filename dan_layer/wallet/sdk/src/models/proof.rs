@@ -1,6 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use chrono::NaiveDateTime;
+use tari_template_lib::models::Amount;
+use tari_transaction::TransactionId;
+
 pub type ConfidentialProofId = u64;
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -8,3 +12,12 @@ pub struct ConfidentialProofEntry {
     pub id: ConfidentialProofId,
     pub account_name: String,
 }
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConfidentialProof {
+    pub id: ConfidentialProofId,
+    /// The transaction that the proof was used in, or None if the proof was never bound to a transaction
+    pub transaction_id: Option<TransactionId>,
+    pub locked_revealed_amount: Amount,
+    pub created_at: NaiveDateTime,
+}
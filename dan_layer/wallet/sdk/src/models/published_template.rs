@@ -0,0 +1,27 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::NaiveDateTime;
+use tari_engine_types::{serde_with, substate::SubstateId, TemplateAddress};
+use tari_transaction::TransactionId;
+
+/// A template that was published by this wallet
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct PublishedTemplateModel {
+    #[serde(with = "serde_with::string")]
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub template_address: TemplateAddress,
+    /// The account that signed the publish transaction and paid the fees
+    pub author_account: SubstateId,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub binary_size: u64,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub published_at: NaiveDateTime,
+}
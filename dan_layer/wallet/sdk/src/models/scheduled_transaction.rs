@@ -0,0 +1,219 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::Epoch;
+use tari_engine_types::instruction::Instruction;
+use tari_template_lib::models::{Amount, ComponentAddress};
+use tari_transaction::TransactionId;
+
+/// A transaction that the wallet builds, signs and submits automatically according to a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct ScheduledTransaction {
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub id: u64,
+    pub name: String,
+    pub schedule: TransactionSchedule,
+    pub instructions: Vec<Instruction>,
+    /// The account that pays the transaction fees and signs the transaction
+    pub fee_account: ComponentAddress,
+    pub max_fee: Amount,
+    /// The maximum number of times the transaction is submitted. If None, the transaction is submitted until the
+    /// schedule is cancelled.
+    #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+    pub max_runs: Option<u64>,
+    #[cfg_attr(feature = "ts", ts(type = "number"))]
+    pub num_runs: u64,
+    pub status: ScheduledTransactionStatus,
+    /// The time at which a time based schedule is next due
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub next_run_at: Option<NaiveDateTime>,
+    /// The epoch at which an epoch based schedule is next due
+    pub next_run_epoch: Option<Epoch>,
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub last_transaction_id: Option<TransactionId>,
+    /// The error of the last run, if it failed to build or submit the transaction
+    pub last_error: Option<String>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub created_at: NaiveDateTime,
+}
+
+impl ScheduledTransaction {
+    pub fn is_active(&self) -> bool {
+        self.status == ScheduledTransactionStatus::Active
+    }
+
+    /// Returns true if the transaction should be submitted at the given time and epoch
+    pub fn is_due(&self, now: NaiveDateTime, current_epoch: Option<Epoch>) -> bool {
+        if !self.is_active() {
+            return false;
+        }
+        match self.schedule {
+            TransactionSchedule::Epoch { .. } => self
+                .next_run_epoch
+                .zip(current_epoch)
+                .map_or(false, |(next, current)| next <= current),
+            _ => self.next_run_at.map_or(false, |next| next <= now),
+        }
+    }
+
+    /// Records a run at the given time and epoch and advances the schedule. The schedule is completed once the maximum
+    /// number of runs is reached or it has no further runs.
+    pub fn record_run(
+        &mut self,
+        now: NaiveDateTime,
+        current_epoch: Option<Epoch>,
+        result: Result<TransactionId, String>,
+    ) {
+        self.num_runs += 1;
+        match result {
+            Ok(transaction_id) => {
+                self.last_transaction_id = Some(transaction_id);
+                self.last_error = None;
+            },
+            Err(err) => {
+                self.last_error = Some(err);
+            },
+        }
+
+        self.next_run_at = self.schedule.next_time_after(now);
+        self.next_run_epoch = current_epoch.and_then(|epoch| self.schedule.next_epoch_after(epoch));
+        let is_finished = self.max_runs.map_or(false, |max| self.num_runs >= max) ||
+            (self.next_run_at.is_none() && self.next_run_epoch.is_none());
+        if is_finished {
+            self.status = ScheduledTransactionStatus::Completed;
+        }
+    }
+}
+
+/// Determines when a scheduled transaction is submitted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum TransactionSchedule {
+    /// Submitted repeatedly with the given number of seconds between submissions
+    Interval {
+        #[cfg_attr(feature = "ts", ts(type = "number"))]
+        seconds: u64,
+    },
+    /// Submitted at the times matching a cron expression in UTC, including the seconds field e.g. "0 0 * * * *" runs
+    /// on the hour
+    Cron { expression: String },
+    /// Submitted once the network reaches the given epoch, and then every `repeat_every` epochs if set
+    Epoch {
+        epoch: Epoch,
+        #[cfg_attr(feature = "ts", ts(type = "number | null"))]
+        repeat_every: Option<u64>,
+    },
+}
+
+impl TransactionSchedule {
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        match self {
+            Self::Interval { seconds } if *seconds == 0 => Err(anyhow!("Schedule interval must be greater than zero")),
+            Self::Cron { expression } => {
+                cron::Schedule::from_str(expression)
+                    .map_err(|e| anyhow!("Invalid cron expression '{}': {}", expression, e))?;
+                Ok(())
+            },
+            Self::Epoch {
+                repeat_every: Some(0), ..
+            } => Err(anyhow!("Schedule epoch repeat must be greater than zero")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the first due time after the given time for time based schedules
+    pub fn next_time_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            Self::Interval { seconds } => i64::try_from(*seconds)
+                .ok()
+                .and_then(chrono::Duration::try_seconds)
+                .and_then(|interval| after.checked_add_signed(interval)),
+            Self::Cron { expression } => {
+                let schedule = cron::Schedule::from_str(expression).ok()?;
+                schedule
+                    .after(&Utc.from_utc_datetime(&after))
+                    .next()
+                    .map(|next| next.naive_utc())
+            },
+            Self::Epoch { .. } => None,
+        }
+    }
+
+    /// Returns the first due epoch for an epoch based schedule, if it has not yet been run
+    pub fn first_epoch(&self) -> Option<Epoch> {
+        match self {
+            Self::Epoch { epoch, .. } => Some(*epoch),
+            _ => None,
+        }
+    }
+
+    /// Returns the next due epoch after running in the given epoch for epoch based schedules
+    pub fn next_epoch_after(&self, current_epoch: Epoch) -> Option<Epoch> {
+        match self {
+            Self::Epoch {
+                repeat_every: Some(repeat_every),
+                ..
+            } => current_epoch.as_u64().checked_add(*repeat_every).map(Epoch),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub enum ScheduledTransactionStatus {
+    Active,
+    /// The schedule has no further runs
+    Completed,
+    Cancelled,
+}
+
+impl ScheduledTransactionStatus {
+    pub fn as_key_str(&self) -> &'static str {
+        match self {
+            ScheduledTransactionStatus::Active => "Active",
+            ScheduledTransactionStatus::Completed => "Completed",
+            ScheduledTransactionStatus::Cancelled => "Cancelled",
+        }
+    }
+}
+
+impl FromStr for ScheduledTransactionStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Active" => Ok(ScheduledTransactionStatus::Active),
+            "Completed" => Ok(ScheduledTransactionStatus::Completed),
+            "Cancelled" => Ok(ScheduledTransactionStatus::Cancelled),
+            _ => Err(anyhow!("Invalid ScheduledTransactionStatus: {}", s)),
+        }
+    }
+}
+
+impl Display for ScheduledTransactionStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_key_str())
+    }
+}
@@ -0,0 +1,35 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_dan_common_types::SubstateRequirement;
+use tari_transaction::TransactionId;
+
+use crate::models::TransactionStatus;
+
+/// A record of a transaction that was rejected by the network. Failures are kept so that the cause can be inspected
+/// and the transaction retried with up-to-date inputs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "ts",
+    derive(ts_rs::TS),
+    ts(export, export_to = "../../bindings/src/types/")
+)]
+pub struct TransactionFailure {
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub transaction_id: TransactionId,
+    pub status: TransactionStatus,
+    pub reason: String,
+    /// The inputs of the transaction together with the versions that were known to the wallet when the transaction
+    /// failed. An input has no version if the wallet did not know the version.
+    pub inputs: Vec<SubstateRequirement>,
+    /// The id of the transaction that was submitted to retry this transaction, if any
+    #[cfg_attr(feature = "ts", ts(type = "string | null"))]
+    pub retried_as: Option<TransactionId>,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub failed_at: NaiveDateTime,
+    /// The number of times the original transaction had been retried when this transaction failed. This is zero if
+    /// this transaction is not a retry.
+    pub attempt: u32,
+}
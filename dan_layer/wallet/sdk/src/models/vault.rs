@@ -1,7 +1,10 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use tari_engine_types::substate::SubstateId;
+use tari_engine_types::{
+    resource_amount::{ResourceAmount, ResourceAmountError},
+    substate::SubstateId,
+};
 use tari_template_lib::{
     models::{Amount, ResourceAddress},
     resource::ResourceType,
@@ -17,12 +20,21 @@ pub struct VaultModel {
     pub revealed_balance: Amount,
     pub locked_revealed_balance: Amount,
     pub token_symbol: Option<String>,
+    /// The number of decimal places declared by the resource, if any
+    pub decimals: Option<u8>,
+    /// True if the resource auditor has frozen this vault. Funds cannot be withdrawn from or deposited into a frozen
+    /// vault.
+    pub is_frozen: bool,
 }
 
 impl VaultModel {
     pub fn available_revealed_balance(&self) -> Amount {
         self.revealed_balance - self.locked_revealed_balance
     }
+
+    pub fn revealed_resource_amount(&self) -> Result<ResourceAmount, ResourceAmountError> {
+        ResourceAmount::new(self.revealed_balance, self.decimals.unwrap_or(0))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -30,4 +42,5 @@ pub struct VaultBalance {
     pub account: SubstateId,
     pub confidential: Amount,
     pub revealed: Amount,
+    pub is_frozen: bool,
 }
@@ -42,9 +42,22 @@ pub enum TransactionStatus {
     Rejected,
     InvalidTransaction,
     OnlyFeeAccepted,
+    /// The transaction was not finalized before its max_epoch had passed
+    Expired,
 }
 
 impl TransactionStatus {
+    /// Returns true if the transaction was finalized without its instructions being committed
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            TransactionStatus::Rejected |
+                TransactionStatus::InvalidTransaction |
+                TransactionStatus::OnlyFeeAccepted |
+                TransactionStatus::Expired
+        )
+    }
+
     pub fn as_key_str(&self) -> &'static str {
         match self {
             TransactionStatus::New => "New",
@@ -54,6 +67,7 @@ impl TransactionStatus {
             TransactionStatus::Rejected => "Rejected",
             TransactionStatus::InvalidTransaction => "InvalidTransaction",
             TransactionStatus::OnlyFeeAccepted => "OnlyFeeAccepted",
+            TransactionStatus::Expired => "Expired",
         }
     }
 }
@@ -70,6 +84,7 @@ impl FromStr for TransactionStatus {
             "Rejected" => Ok(TransactionStatus::Rejected),
             "InvalidTransaction" => Ok(TransactionStatus::InvalidTransaction),
             "OnlyFeeAccepted" => Ok(TransactionStatus::OnlyFeeAccepted),
+            "Expired" => Ok(TransactionStatus::Expired),
             _ => Err(anyhow!("Invalid TransactionStatus: {}", s)),
         }
     }
@@ -1,16 +1,16 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use tari_dan_common_types::{substate_type::SubstateType, SubstateRequirement};
+use tari_dan_common_types::{substate_type::SubstateType, Epoch, SubstateRequirement};
 use tari_dan_storage::consensus_models::Decision;
 use tari_engine_types::{
     commit_result::ExecuteResult,
-    substate::{Substate, SubstateId},
+    substate::{Substate, SubstateId, SubstateValue},
 };
 use tari_template_abi::TemplateDef;
 use tari_template_lib::prelude::TemplateAddress;
@@ -41,10 +41,13 @@ pub trait WalletNetworkInterface {
         required_substates: Vec<SubstateRequirement>,
     ) -> Result<TransactionId, Self::Error>;
 
+    /// Executes the transaction without committing it. The `substate_overrides` replace the network state of the
+    /// given substates for the execution.
     async fn submit_dry_run_transaction(
         &self,
         transaction: Transaction,
         required_substates: Vec<SubstateRequirement>,
+        substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<TransactionQueryResult, Self::Error>;
 
     async fn query_transaction_result(
@@ -53,6 +56,8 @@ pub trait WalletNetworkInterface {
     ) -> Result<TransactionQueryResult, Self::Error>;
 
     async fn fetch_template_definition(&self, template_address: TemplateAddress) -> Result<TemplateDef, Self::Error>;
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error>;
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
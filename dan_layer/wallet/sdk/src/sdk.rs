@@ -9,15 +9,24 @@ use tari_key_manager::cipher_seed::CipherSeed;
 
 use crate::{
     apis::{
+        account_recovery::AccountRecoveryApi,
         accounts::AccountsApi,
+        balance_history::BalanceHistoryApi,
+        confidential_audit::ConfidentialAuditApi,
         confidential_crypto::ConfidentialCryptoApi,
         confidential_outputs::ConfidentialOutputsApi,
         confidential_transfer::ConfidentialTransferApi,
         config::{ConfigApi, ConfigApiError, ConfigKey},
+        contacts::ContactsApi,
+        dynamic_template::DynamicTemplateApi,
+        garbage_collection::GarbageCollectionApi,
         jwt::JwtApi,
         key_manager::KeyManagerApi,
+        metadata_sync::MetadataSyncApi,
         non_fungible_tokens::NonFungibleTokensApi,
+        scheduled_transactions::ScheduledTransactionsApi,
         substate::SubstatesApi,
+        templates::TemplatesApi,
         transaction::TransactionApi,
     },
     network::WalletNetworkInterface,
@@ -26,7 +35,8 @@ use crate::{
 
 #[derive(Debug, Clone)]
 pub struct WalletSdkConfig {
-    /// Encryption password for the wallet database. NOTE: Not yet implemented, this field is ignored
+    /// Encryption password for the wallet database. If set, the database is unlocked (or encrypted, if it is not yet
+    /// encrypted) with this password when the SDK is initialized.
     pub password: Option<SafePassword>,
     // TODO: remove JWT stuff from wallet SDK. The SDK should not have anything to do with JWTs, this is a web/jrpc
     //       handler concern. It appears that the main reason it is done this way is to use the wallet database to
@@ -55,6 +65,9 @@ where
         indexer: TNetworkInterface,
         config: WalletSdkConfig,
     ) -> Result<Self, WalletSdkError> {
+        if let Some(password) = config.password.as_ref() {
+            store.unlock(password)?;
+        }
         let cipher_seed = Self::get_or_create_cipher_seed(&store)?;
 
         Ok(Self {
@@ -97,6 +110,14 @@ where
         AccountsApi::new(&self.store)
     }
 
+    pub fn balance_history_api(&self) -> BalanceHistoryApi<'_, TStore, TNetworkInterface> {
+        BalanceHistoryApi::new(&self.store, &self.network_interface)
+    }
+
+    pub fn account_recovery_api(&self) -> AccountRecoveryApi<'_, TStore, TNetworkInterface> {
+        AccountRecoveryApi::new(self.key_manager_api(), self.accounts_api(), self.substate_api())
+    }
+
     pub fn confidential_crypto_api(&self) -> ConfidentialCryptoApi {
         ConfidentialCryptoApi::new()
     }
@@ -124,10 +145,51 @@ where
         )
     }
 
+    pub fn confidential_audit_api(&self) -> ConfidentialAuditApi<'_, TStore> {
+        ConfidentialAuditApi::new(&self.store, self.key_manager_api(), self.confidential_crypto_api())
+    }
+
     pub fn non_fungible_api(&self) -> NonFungibleTokensApi<'_, TStore> {
         NonFungibleTokensApi::new(&self.store)
     }
 
+    pub fn templates_api(&self) -> TemplatesApi<'_, TStore> {
+        TemplatesApi::new(&self.store)
+    }
+
+    pub fn contacts_api(&self) -> ContactsApi<'_, TStore> {
+        ContactsApi::new(&self.store)
+    }
+
+    pub fn scheduled_transactions_api(&self) -> ScheduledTransactionsApi<'_, TStore> {
+        ScheduledTransactionsApi::new(&self.store)
+    }
+
+    pub fn garbage_collection_api(&self) -> GarbageCollectionApi<'_, TStore> {
+        GarbageCollectionApi::new(&self.store)
+    }
+
+    pub fn metadata_sync_api(&self) -> MetadataSyncApi<'_, TStore> {
+        MetadataSyncApi::new(&self.store)
+    }
+
+    pub fn dynamic_template_api(&self) -> DynamicTemplateApi<'_, TNetworkInterface> {
+        DynamicTemplateApi::new(&self.network_interface)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.store.is_locked()
+    }
+
+    pub fn lock(&self) {
+        self.store.lock();
+    }
+
+    pub fn unlock(&self, password: &SafePassword) -> Result<(), WalletSdkError> {
+        self.store.unlock(password)?;
+        Ok(())
+    }
+
     fn get_or_create_cipher_seed(store: &TStore) -> Result<CipherSeed, WalletSdkError> {
         let config_api = ConfigApi::new(store);
         let maybe_cipher_seed = config_api.get(ConfigKey::CipherSeed).optional()?;
@@ -6,8 +6,9 @@ use std::{
     time::Duration,
 };
 
-use tari_common_types::types::Commitment;
-use tari_dan_common_types::{optional::IsNotFoundError, substate_type::SubstateType, SubstateRequirement};
+use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
+use tari_crypto::tari_utilities::SafePassword;
+use tari_dan_common_types::{optional::IsNotFoundError, substate_type::SubstateType, Epoch, SubstateRequirement};
 use tari_dan_storage::consensus_models::QuorumCertificate;
 use tari_engine_types::{commit_result::FinalizeResult, substate::SubstateId, TemplateAddress};
 use tari_template_lib::{
@@ -18,13 +19,21 @@ use tari_transaction::{Transaction, TransactionId};
 
 use crate::models::{
     Account,
+    AuditedVaultBalance,
     ConfidentialOutputModel,
+    ConfidentialProof,
     ConfidentialProofId,
     Config,
+    Contact,
+    ImportedTransaction,
     NewAccountInfo,
     NonFungibleToken,
     OutputStatus,
+    PublishedTemplateModel,
+    ScheduledTransaction,
+    ScheduledTransactionStatus,
     SubstateModel,
+    TransactionFailure,
     TransactionStatus,
     VaultModel,
     VersionedSubstateId,
@@ -40,6 +49,14 @@ pub trait WalletStore {
     fn create_read_tx(&self) -> Result<Self::ReadTransaction<'_>, WalletStorageError>;
     fn create_write_tx(&self) -> Result<Self::WriteTransaction<'_>, WalletStorageError>;
 
+    /// Returns true if the store is encrypted and the encryption key is not loaded
+    fn is_locked(&self) -> bool;
+    /// Discards the encryption key. Encrypted data cannot be read or written until the store is unlocked.
+    fn lock(&self);
+    /// Loads the encryption key derived from the given password. If the store is not yet encrypted, encryption is
+    /// enabled with this password and any existing data is encrypted.
+    fn unlock(&self, password: &SafePassword) -> Result<(), WalletStorageError>;
+
     fn with_write_tx<F: FnOnce(&mut Self::WriteTransaction<'_>) -> Result<R, E>, R, E>(&self, f: F) -> Result<R, E>
     where E: From<WalletStorageError> {
         let mut tx = self.create_write_tx()?;
@@ -91,6 +108,10 @@ pub enum WalletStorageError {
     OperationError { operation: &'static str, details: String },
     #[error("Data inconsistency for operation {operation}: {details}")]
     DataInconsistent { operation: &'static str, details: String },
+    #[error("Wallet database is locked")]
+    Locked,
+    #[error("Invalid wallet database password")]
+    InvalidPassword,
 }
 
 impl IsNotFoundError for WalletStorageError {
@@ -117,6 +138,8 @@ pub trait WalletStoreReader {
     fn key_manager_get_all(&mut self, branch: &str) -> Result<Vec<(u64, bool)>, WalletStorageError>;
     fn key_manager_get_active_index(&mut self, branch: &str) -> Result<u64, WalletStorageError>;
     fn key_manager_get_last_index(&mut self, branch: &str) -> Result<u64, WalletStorageError>;
+    fn imported_keys_get(&mut self, index: u64) -> Result<PrivateKey, WalletStorageError>;
+    fn imported_keys_get_index(&mut self, public_key: &PublicKey) -> Result<u64, WalletStorageError>;
     // Config
     fn config_get<T: serde::de::DeserializeOwned>(&mut self, key: &str) -> Result<Config<T>, WalletStorageError>;
     // JWT
@@ -128,6 +151,13 @@ pub trait WalletStoreReader {
         status: Option<TransactionStatus>,
         component: Option<ComponentAddress>,
     ) -> Result<Vec<WalletTransaction>, WalletStorageError>;
+    /// Fetches up to `limit` non-dry-run transactions in insertion order, starting after the given cursor. Each
+    /// transaction is returned with the cursor that can be used to fetch the transactions that follow it.
+    fn transactions_fetch_after(
+        &mut self,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, WalletTransaction)>, WalletStorageError>;
     // Substates
     fn substates_get(&mut self, address: &SubstateId) -> Result<SubstateModel, WalletStorageError>;
     fn substates_get_all(
@@ -145,6 +175,8 @@ pub trait WalletStoreReader {
     fn accounts_count(&mut self) -> Result<u64, WalletStorageError>;
     fn accounts_get_by_name(&mut self, name: &str) -> Result<Account, WalletStorageError>;
     fn accounts_get_by_vault(&mut self, vault_address: &SubstateId) -> Result<Account, WalletStorageError>;
+    /// Returns the view key of a watch-only account, if one was provided
+    fn accounts_get_view_key(&mut self, address: &SubstateId) -> Result<Option<PrivateKey>, WalletStorageError>;
 
     // Vaults
     fn vaults_get(&mut self, address: &SubstateId) -> Result<VaultModel, WalletStorageError>;
@@ -156,6 +188,20 @@ pub trait WalletStoreReader {
     ) -> Result<VaultModel, WalletStorageError>;
     fn vaults_get_by_account(&mut self, account_addr: &SubstateId) -> Result<Vec<VaultModel>, WalletStorageError>;
 
+    // Vault history
+    /// Returns the version of the vault that was current at `epoch` i.e. the latest version recorded at or before
+    /// `epoch`, or None if no version was recorded at or before `epoch`
+    fn vault_history_get_at_epoch(
+        &mut self,
+        vault_address: &SubstateId,
+        epoch: Epoch,
+    ) -> Result<Option<VersionedSubstateId>, WalletStorageError>;
+    /// Returns the earliest epoch at which a vault version was recorded for the account
+    fn vault_history_get_earliest_epoch(
+        &mut self,
+        account_address: &SubstateId,
+    ) -> Result<Option<Epoch>, WalletStorageError>;
+
     // Outputs
     fn outputs_get_unspent_balance(&mut self, vault_address: &SubstateId) -> Result<u64, WalletStorageError>;
     fn outputs_get_locked_by_proof(
@@ -178,6 +224,7 @@ pub trait WalletStoreReader {
         &mut self,
         transaction_id: TransactionId,
     ) -> Result<Vec<ConfidentialProofId>, WalletStorageError>;
+    fn proofs_get_all(&mut self) -> Result<Vec<ConfidentialProof>, WalletStorageError>;
 
     // Non fungible tokens
     fn non_fungible_token_get_by_nft_id(
@@ -196,6 +243,55 @@ pub trait WalletStoreReader {
         &mut self,
         nft_id: NonFungibleId,
     ) -> Result<ResourceAddress, WalletStorageError>;
+
+    // Audited balances
+    fn audited_balances_get_by_resource(
+        &mut self,
+        resource_address: &ResourceAddress,
+        view_key_index: u64,
+    ) -> Result<Vec<AuditedVaultBalance>, WalletStorageError>;
+    /// Returns all distinct (resource, view key index) pairs that have been audited
+    fn audited_balances_get_audited_resources(&mut self) -> Result<Vec<(ResourceAddress, u64)>, WalletStorageError>;
+
+    // Published templates
+    fn published_templates_get_all(&mut self) -> Result<Vec<PublishedTemplateModel>, WalletStorageError>;
+
+    // Contacts
+    fn contacts_get_by_name(&mut self, name: &str) -> Result<Contact, WalletStorageError>;
+    fn contacts_get_all(&mut self) -> Result<Vec<Contact>, WalletStorageError>;
+
+    // Transaction failures
+    fn transaction_failures_get(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionFailure, WalletStorageError>;
+    /// Returns all transaction failures, most recent first. If `include_retried` is false, failures that have already
+    /// been retried are excluded.
+    fn transaction_failures_get_all(
+        &mut self,
+        include_retried: bool,
+    ) -> Result<Vec<TransactionFailure>, WalletStorageError>;
+    /// Returns the failure that was retried as the given transaction
+    fn transaction_failures_get_by_retried_as(
+        &mut self,
+        retried_as: TransactionId,
+    ) -> Result<TransactionFailure, WalletStorageError>;
+
+    // Imported transactions
+    fn imported_transactions_get(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<ImportedTransaction, WalletStorageError>;
+    /// Returns all imported transactions, most recently imported first
+    fn imported_transactions_get_all(&mut self) -> Result<Vec<ImportedTransaction>, WalletStorageError>;
+
+    // Scheduled transactions
+    fn scheduled_transactions_get(&mut self, id: u64) -> Result<ScheduledTransaction, WalletStorageError>;
+    /// Returns all scheduled transactions, optionally filtered by status, in the order they were created
+    fn scheduled_transactions_get_all(
+        &mut self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, WalletStorageError>;
 }
 
 pub trait WalletStoreWriter {
@@ -211,6 +307,11 @@ pub trait WalletStoreWriter {
     // Key manager
     fn key_manager_insert(&mut self, branch: &str, index: u64) -> Result<(), WalletStorageError>;
     fn key_manager_set_active_index(&mut self, branch: &str, index: u64) -> Result<(), WalletStorageError>;
+    fn imported_keys_insert(
+        &mut self,
+        public_key: &PublicKey,
+        secret_key: &PrivateKey,
+    ) -> Result<u64, WalletStorageError>;
 
     // Config
     fn config_set<T: serde::Serialize>(
@@ -265,6 +366,21 @@ pub trait WalletStoreWriter {
         is_default: bool,
     ) -> Result<(), WalletStorageError>;
 
+    fn accounts_insert_watch_only(
+        &mut self,
+        account_name: Option<&str>,
+        substate_id: &SubstateId,
+        owner_public_key: &PublicKey,
+        view_key: Option<&PrivateKey>,
+    ) -> Result<(), WalletStorageError>;
+    fn accounts_insert_imported(
+        &mut self,
+        account_name: Option<&str>,
+        substate_id: &SubstateId,
+        imported_key_index: u64,
+        is_default: bool,
+    ) -> Result<(), WalletStorageError>;
+
     fn accounts_update(&mut self, substate_id: &SubstateId, new_name: Option<&str>) -> Result<(), WalletStorageError>;
 
     // Vaults
@@ -275,6 +391,7 @@ pub trait WalletStoreWriter {
         revealed_balance: Amount,
         confidential_balance: Amount,
     ) -> Result<(), WalletStorageError>;
+    fn vaults_set_frozen(&mut self, vault_address: &SubstateId, is_frozen: bool) -> Result<(), WalletStorageError>;
     fn vaults_lock_revealed_funds(
         &mut self,
         proof_id: ConfidentialProofId,
@@ -286,6 +403,16 @@ pub trait WalletStoreWriter {
     ) -> Result<(), WalletStorageError>;
     fn vaults_unlock_revealed_funds(&mut self, proof_id: ConfidentialProofId) -> Result<(), WalletStorageError>;
 
+    // Vault history
+    /// Records that the vault was at `vault_address.version` in `epoch`. A version that has already been recorded is
+    /// ignored.
+    fn vault_history_insert(
+        &mut self,
+        account_address: &SubstateId,
+        vault_address: &VersionedSubstateId,
+        epoch: Epoch,
+    ) -> Result<(), WalletStorageError>;
+
     // Confidential Outputs
     fn outputs_lock_smallest_amount(
         &mut self,
@@ -309,4 +436,50 @@ pub trait WalletStoreWriter {
 
     // Non fungible tokens
     fn non_fungible_token_upsert(&mut self, non_fungible_token: &NonFungibleToken) -> Result<(), WalletStorageError>;
+
+    // Audited balances
+    fn audited_balances_upsert(&mut self, balance: &AuditedVaultBalance) -> Result<(), WalletStorageError>;
+
+    // Published templates
+    fn published_templates_insert(
+        &mut self,
+        template_address: &TemplateAddress,
+        author_account: &SubstateId,
+        transaction_id: TransactionId,
+        binary_size: u64,
+    ) -> Result<(), WalletStorageError>;
+
+    // Contacts
+    fn contacts_insert(&mut self, contact: &Contact) -> Result<(), WalletStorageError>;
+    fn contacts_delete(&mut self, name: &str) -> Result<(), WalletStorageError>;
+
+    // Transaction failures
+    fn transaction_failures_insert(
+        &mut self,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        reason: &str,
+        inputs: &[SubstateRequirement],
+        attempt: u32,
+    ) -> Result<(), WalletStorageError>;
+    fn transaction_failures_set_retried(
+        &mut self,
+        transaction_id: TransactionId,
+        retried_as: TransactionId,
+    ) -> Result<(), WalletStorageError>;
+
+    // Imported transactions
+    fn imported_transactions_insert(
+        &mut self,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        final_fee: Option<Amount>,
+        accounts: &[SubstateId],
+    ) -> Result<(), WalletStorageError>;
+
+    // Scheduled transactions
+    /// Inserts a new scheduled transaction and returns its id. The id and created_at fields are ignored.
+    fn scheduled_transactions_insert(&mut self, scheduled: &ScheduledTransaction) -> Result<u64, WalletStorageError>;
+    /// Updates the run state and status of a scheduled transaction
+    fn scheduled_transactions_update(&mut self, scheduled: &ScheduledTransaction) -> Result<(), WalletStorageError>;
 }
@@ -1,12 +1,12 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
-use std::{convert::Infallible, time::Duration};
+use std::{collections::HashMap, convert::Infallible, time::Duration};
 
 use async_trait::async_trait;
 use tari_common_types::types::Commitment;
 use tari_crypto::commitment::HomomorphicCommitmentFactory;
-use tari_dan_common_types::{optional::Optional, SubstateRequirement};
+use tari_dan_common_types::{optional::Optional, Epoch, SubstateRequirement};
 use tari_dan_wallet_sdk::{
     models::{ConfidentialOutputModel, ConfidentialProofId, OutputStatus},
     network::{SubstateQueryResult, TransactionQueryResult, WalletNetworkInterface},
@@ -15,7 +15,10 @@ use tari_dan_wallet_sdk::{
     WalletSdkConfig,
 };
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
-use tari_engine_types::{confidential::get_commitment_factory, substate::SubstateId};
+use tari_engine_types::{
+    confidential::get_commitment_factory,
+    substate::{SubstateId, SubstateValue},
+};
 use tari_template_abi::TemplateDef;
 use tari_template_lib::{
     constants::CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
@@ -157,6 +160,7 @@ impl Test {
                 CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
                 ResourceType::Confidential,
                 Some("TEST".to_string()),
+                None,
             )
             .unwrap();
 
@@ -254,6 +258,7 @@ impl WalletNetworkInterface for PanicIndexer {
         &self,
         _transaction: Transaction,
         _required_substates: Vec<SubstateRequirement>,
+        _substate_overrides: HashMap<SubstateId, SubstateValue>,
     ) -> Result<TransactionQueryResult, Self::Error> {
         panic!("PanicIndexer called")
     }
@@ -279,4 +284,8 @@ impl WalletNetworkInterface for PanicIndexer {
     ) -> Result<tari_dan_wallet_sdk::network::SubstateListResult, Self::Error> {
         panic!("PanicIndexer called")
     }
+
+    async fn get_current_epoch(&self) -> Result<Epoch, Self::Error> {
+        panic!("PanicIndexer called")
+    }
 }
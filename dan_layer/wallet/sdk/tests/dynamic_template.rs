@@ -0,0 +1,167 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use serde_json::json;
+use tari_bor::decode_exact;
+use tari_dan_wallet_sdk::apis::dynamic_template::{DynamicTemplateClient, DynamicTemplateError};
+use tari_engine_types::instruction::Instruction;
+use tari_template_abi::{ArgDef, FunctionDef, TemplateDef, TemplateDefV1, Type};
+use tari_template_lib::{
+    args::Arg,
+    models::{Amount, ComponentAddress, ObjectKey},
+};
+
+#[test]
+fn it_builds_a_function_call() {
+    let client = test_client();
+    let instruction = client
+        .call_function("new", vec![json!(123), json!("Amount(1000)")])
+        .unwrap();
+
+    let Instruction::CallFunction { function, args, .. } = instruction else {
+        panic!("Expected CallFunction instruction");
+    };
+    assert_eq!(function, "new");
+    assert_eq!(decode_literal::<u32>(&args[0]), 123);
+    assert_eq!(decode_literal::<Amount>(&args[1]), Amount(1000));
+}
+
+#[test]
+fn it_builds_a_method_call_without_the_receiver() {
+    let client = test_client();
+    let component = ComponentAddress::new(ObjectKey::default());
+    let instruction = client
+        .call_method(component, "set_owners", vec![json!(["alice", "bob"]), json!([true, 7])])
+        .unwrap();
+
+    let Instruction::CallMethod {
+        component_address,
+        method,
+        args,
+    } = instruction
+    else {
+        panic!("Expected CallMethod instruction");
+    };
+    assert_eq!(component_address, component);
+    assert_eq!(method, "set_owners");
+    assert_eq!(decode_literal::<Vec<String>>(&args[0]), vec!["alice", "bob"]);
+    assert_eq!(decode_literal::<(bool, u8)>(&args[1]), (true, 7));
+}
+
+#[test]
+fn it_passes_workspace_references_through() {
+    let client = test_client();
+    let component = ComponentAddress::new(ObjectKey::default());
+    let instruction = client
+        .call_method(component, "deposit", vec![json!("Workspace(bucket)")])
+        .unwrap();
+
+    let Instruction::CallMethod { args, .. } = instruction else {
+        panic!("Expected CallMethod instruction");
+    };
+    assert_eq!(args[0], Arg::Workspace(b"bucket".to_vec()));
+}
+
+#[test]
+fn it_rejects_invalid_calls() {
+    let client = test_client();
+    let component = ComponentAddress::new(ObjectKey::default());
+
+    let err = client.call_function("does_not_exist", vec![]).unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::FunctionNotFound { .. }));
+
+    let err = client
+        .call_function("deposit", vec![json!("Workspace(bucket)")])
+        .unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::ExpectedFunction { .. }));
+
+    let err = client
+        .call_method(component, "new", vec![json!(1), json!(1)])
+        .unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::ExpectedMethod { .. }));
+
+    let err = client.call_function("new", vec![json!(1)]).unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::ArgumentCountMismatch {
+        expected: 2,
+        got: 1,
+        ..
+    }));
+
+    // Out of range for u32
+    let err = client.call_function("new", vec![json!(-1), json!(1)]).unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::InvalidArgument { ref argument, .. } if argument == "count"));
+
+    let err = client
+        .call_method(component, "set_owners", vec![json!("alice"), json!([true, 7])])
+        .unwrap_err();
+    assert!(matches!(err, DynamicTemplateError::InvalidArgument { ref argument, .. } if argument == "owners"));
+}
+
+fn decode_literal<T: serde::de::DeserializeOwned>(arg: &Arg) -> T {
+    match arg {
+        Arg::Literal(bytes) => decode_exact(bytes).unwrap(),
+        Arg::Workspace(_) => panic!("Expected literal argument"),
+    }
+}
+
+fn test_client() -> DynamicTemplateClient {
+    let receiver = ArgDef {
+        name: "self".to_string(),
+        arg_type: Type::Other {
+            name: "&mut self".to_string(),
+        },
+    };
+    let definition = TemplateDef::V1(TemplateDefV1 {
+        template_name: "Test".to_string(),
+        tari_version: "0.1.0".to_string(),
+        functions: vec![
+            FunctionDef {
+                name: "new".to_string(),
+                arguments: vec![
+                    ArgDef {
+                        name: "count".to_string(),
+                        arg_type: Type::U32,
+                    },
+                    ArgDef {
+                        name: "supply".to_string(),
+                        arg_type: Type::Other {
+                            name: "Amount".to_string(),
+                        },
+                    },
+                ],
+                output: Type::Other {
+                    name: "Component<Test>".to_string(),
+                },
+                is_mut: false,
+            },
+            FunctionDef {
+                name: "set_owners".to_string(),
+                arguments: vec![
+                    receiver.clone(),
+                    ArgDef {
+                        name: "owners".to_string(),
+                        arg_type: Type::Vec(Box::new(Type::String)),
+                    },
+                    ArgDef {
+                        name: "flags".to_string(),
+                        arg_type: Type::Tuple(vec![Type::Bool, Type::U8]),
+                    },
+                ],
+                output: Type::Unit,
+                is_mut: true,
+            },
+            FunctionDef {
+                name: "deposit".to_string(),
+                arguments: vec![receiver, ArgDef {
+                    name: "bucket".to_string(),
+                    arg_type: Type::Other {
+                        name: "Bucket".to_string(),
+                    },
+                }],
+                output: Type::Unit,
+                is_mut: true,
+            },
+        ],
+    });
+    DynamicTemplateClient::new(Default::default(), definition)
+}
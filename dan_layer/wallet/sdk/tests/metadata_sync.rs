@@ -0,0 +1,52 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::BTreeMap;
+
+use tari_dan_wallet_sdk::apis::metadata_sync::{MetadataConflict, WalletMetadata};
+
+fn metadata(account_names: &[(&str, &str)]) -> WalletMetadata {
+    WalletMetadata {
+        account_names: account_names
+            .iter()
+            .map(|(address, name)| (address.to_string(), name.to_string()))
+            .collect::<BTreeMap<_, _>>(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn it_takes_changes_made_on_one_side() {
+    let base = metadata(&[("a", "alice"), ("b", "bob"), ("c", "carol")]);
+    // Renamed a, removed b
+    let local = metadata(&[("a", "alice2"), ("c", "carol")]);
+    // Renamed c, added d
+    let remote = metadata(&[("a", "alice"), ("b", "bob"), ("c", "carol2"), ("d", "dave")]);
+
+    let merge = WalletMetadata::merge(&base, &local, &remote);
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(
+        merge.merged,
+        metadata(&[("a", "alice2"), ("c", "carol2"), ("d", "dave")])
+    );
+}
+
+#[test]
+fn it_keeps_the_local_value_on_conflict() {
+    let base = metadata(&[("a", "alice")]);
+    let local = metadata(&[("a", "alice_local"), ("b", "bob")]);
+    // The same change on both sides is not a conflict
+    let remote = metadata(&[("a", "alice_remote"), ("b", "bob")]);
+
+    let merge = WalletMetadata::merge(&base, &local, &remote);
+    assert_eq!(merge.merged, local);
+    assert_eq!(merge.conflicts, vec![MetadataConflict {
+        kind: "account_name",
+        key: "a".to_string(),
+    }]);
+
+    // Once the local value is synced, the other device takes it
+    let merge = WalletMetadata::merge(&remote, &remote, &local);
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(merge.merged, local);
+}
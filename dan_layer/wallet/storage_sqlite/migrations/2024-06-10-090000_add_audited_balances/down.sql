@@ -0,0 +1 @@
+DROP TABLE audited_balances;
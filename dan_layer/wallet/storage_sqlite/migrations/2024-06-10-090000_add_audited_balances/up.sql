@@ -0,0 +1,18 @@
+-- Audited balances
+CREATE TABLE audited_balances
+(
+    id               INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    resource_address TEXT     NOT NULL,
+    view_key_index   BIGINT   NOT NULL,
+    vault_address    TEXT     NOT NULL,
+    owner_address    TEXT     NULL,
+    balance          BIGINT   NOT NULL,
+    num_outputs      INTEGER  NOT NULL,
+    num_unresolved   INTEGER  NOT NULL,
+    vault_version    INTEGER  NOT NULL,
+    created_at       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX audited_balances_uniq_vault_view_key ON audited_balances (vault_address, view_key_index);
+CREATE INDEX audited_balances_idx_resource_view_key ON audited_balances (resource_address, view_key_index);
@@ -0,0 +1,2 @@
+ALTER TABLE vaults
+    ADD COLUMN decimals INTEGER NULL;
@@ -0,0 +1,12 @@
+-- Templates published by this wallet
+CREATE TABLE published_templates
+(
+    id               INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    template_address TEXT     NOT NULL,
+    author_account   TEXT     NOT NULL,
+    transaction_id   TEXT     NOT NULL,
+    binary_size      BIGINT   NOT NULL,
+    created_at       DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX published_templates_uniq_template_address ON published_templates (template_address);
@@ -0,0 +1,13 @@
+-- Transactions that were rejected by the network, together with the inputs known at the time
+CREATE TABLE transaction_failures
+(
+    id                INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    transaction_id    TEXT     NOT NULL,
+    status            TEXT     NOT NULL,
+    reason            TEXT     NOT NULL,
+    inputs            TEXT     NOT NULL,
+    retried_as        TEXT     NULL,
+    created_at        DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX transaction_failures_uniq_transaction_id ON transaction_failures (transaction_id);
@@ -0,0 +1,5 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+ALTER TABLE transaction_failures
+    DROP COLUMN attempt;
@@ -0,0 +1,6 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- The number of times the original transaction had been retried when this transaction failed
+ALTER TABLE transaction_failures
+    ADD COLUMN attempt INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+DROP TABLE database_encryption;
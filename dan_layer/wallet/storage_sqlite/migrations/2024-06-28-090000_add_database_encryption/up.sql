@@ -0,0 +1,13 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- Present if the wallet database is encrypted. Contains at most one row.
+CREATE TABLE database_encryption
+(
+    id             INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    -- Hex-encoded salt used to derive the encryption key from the password
+    salt           TEXT     NOT NULL,
+    -- A known value encrypted with the encryption key, used to check the password
+    password_check TEXT     NOT NULL,
+    created_at     DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
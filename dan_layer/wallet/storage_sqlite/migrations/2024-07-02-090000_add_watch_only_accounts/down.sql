@@ -0,0 +1,7 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+ALTER TABLE accounts
+    DROP COLUMN view_key;
+ALTER TABLE accounts
+    DROP COLUMN watch_only_public_key;
@@ -0,0 +1,9 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- Set for watch-only accounts, for which the wallet does not hold the owner key
+ALTER TABLE accounts
+    ADD COLUMN watch_only_public_key TEXT NULL;
+-- Optional key used to unblind the confidential outputs of a watch-only account
+ALTER TABLE accounts
+    ADD COLUMN view_key TEXT NULL;
@@ -0,0 +1,6 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+ALTER TABLE accounts
+    DROP COLUMN is_imported;
+DROP TABLE imported_keys;
@@ -0,0 +1,17 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+-- Keys that were generated outside of the wallet and cannot be derived from the wallet seed
+CREATE TABLE imported_keys
+(
+    id          INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    public_key  TEXT                              NOT NULL,
+    secret_key  TEXT                              NOT NULL,
+    created_at  TIMESTAMP                         NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX imported_keys_uniq_public_key ON imported_keys (public_key);
+
+-- Set for accounts whose owner key index refers to an imported key
+ALTER TABLE accounts
+    ADD COLUMN is_imported BOOLEAN NOT NULL DEFAULT FALSE;
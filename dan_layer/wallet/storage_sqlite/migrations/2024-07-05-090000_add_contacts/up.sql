@@ -0,0 +1,13 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+CREATE TABLE contacts
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name              TEXT                              NOT NULL,
+    component_address TEXT                              NULL,
+    public_key        TEXT                              NULL,
+    created_at        TIMESTAMP                         NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX contacts_uniq_name ON contacts (name);
@@ -0,0 +1,23 @@
+--  // Copyright 2024 The Tari Project
+--  // SPDX-License-Identifier: BSD-3-Clause
+
+CREATE TABLE scheduled_transactions
+(
+    id                  INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name                TEXT                              NOT NULL,
+    schedule            TEXT                              NOT NULL,
+    instructions        TEXT                              NOT NULL,
+    fee_account         TEXT                              NOT NULL,
+    max_fee             BIGINT                            NOT NULL,
+    max_runs            BIGINT                            NULL,
+    num_runs            BIGINT                            NOT NULL DEFAULT 0,
+    status              TEXT                              NOT NULL,
+    next_run_at         TIMESTAMP                         NULL,
+    next_run_epoch      BIGINT                            NULL,
+    last_transaction_id TEXT                              NULL,
+    last_error          TEXT                              NULL,
+    created_at          TIMESTAMP                         NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at          TIMESTAMP                         NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX scheduled_transactions_idx_status ON scheduled_transactions (status);
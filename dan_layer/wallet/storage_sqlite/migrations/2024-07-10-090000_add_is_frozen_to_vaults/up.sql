@@ -0,0 +1,2 @@
+ALTER TABLE vaults
+    ADD COLUMN is_frozen BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,12 @@
+-- Transactions submitted by other wallets that changed accounts in this wallet
+CREATE TABLE imported_transactions
+(
+    id                INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    transaction_id    TEXT     NOT NULL,
+    status            TEXT     NOT NULL,
+    final_fee         BIGINT   NULL,
+    accounts          TEXT     NOT NULL,
+    created_at        DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX imported_transactions_uniq_transaction_id ON imported_transactions (transaction_id);
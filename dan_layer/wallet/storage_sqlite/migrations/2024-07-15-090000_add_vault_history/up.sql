@@ -0,0 +1,13 @@
+-- The epoch at which the wallet first observed each version of an account's vaults
+CREATE TABLE vault_history
+(
+    id              INTEGER  NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account_address TEXT     NOT NULL,
+    vault_address   TEXT     NOT NULL,
+    version         INTEGER  NOT NULL,
+    epoch           BIGINT   NOT NULL,
+    created_at      DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX vault_history_uniq_vault_address_version ON vault_history (vault_address, version);
+CREATE INDEX vault_history_idx_account_address_epoch ON vault_history (account_address, epoch);
@@ -0,0 +1,177 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::sync::Arc;
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit},
+    Key,
+    XChaCha20Poly1305,
+    XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{de::DeserializeOwned, Serialize};
+use tari_dan_wallet_sdk::storage::WalletStorageError;
+use tari_utilities::{
+    hex::{from_hex, to_hex},
+    SafePassword,
+};
+use zeroize::Zeroizing;
+
+use crate::serialization::{deserialize_json, serialize_json};
+
+/// Prefix of column values that are encrypted. Serialized JSON never starts with this prefix.
+const ENCRYPTED_VALUE_PREFIX: &str = "enc:";
+/// Encrypted with the database key when encryption is enabled, and used to check the password when unlocking
+const PASSWORD_CHECK_PLAINTEXT: &[u8] = b"tari_dan_wallet_database";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+pub(crate) fn generate_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+/// Encrypts and decrypts column values with a key derived from the wallet password
+pub struct DatabaseCipher {
+    cipher: XChaCha20Poly1305,
+}
+
+impl DatabaseCipher {
+    pub fn derive(password: &SafePassword, salt: &[u8]) -> Result<Self, WalletStorageError> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::default()
+            .hash_password_into(password.reveal(), salt, &mut *key)
+            .map_err(|e| WalletStorageError::general("derive_database_key", e))?;
+        Ok(Self {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(&*key)),
+        })
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<String, WalletStorageError> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|e| WalletStorageError::EncodingError {
+                operation: "encrypt",
+                item: "column value",
+                details: e.to_string(),
+            })?;
+        let mut bytes = nonce.to_vec();
+        bytes.extend(ciphertext);
+        Ok(format!("{}{}", ENCRYPTED_VALUE_PREFIX, to_hex(&bytes)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<Zeroizing<Vec<u8>>, WalletStorageError> {
+        let decoding_error = |details: String| WalletStorageError::DecodingError {
+            operation: "decrypt",
+            item: "column value",
+            details,
+        };
+        let hex = value
+            .strip_prefix(ENCRYPTED_VALUE_PREFIX)
+            .ok_or_else(|| decoding_error("value is not encrypted".to_string()))?;
+        let bytes = from_hex(hex).map_err(|e| decoding_error(e.to_string()))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(decoding_error("value is too short".to_string()));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|e| decoding_error(e.to_string()))?;
+        Ok(Zeroizing::new(plaintext))
+    }
+
+    pub fn create_password_check(&self) -> Result<String, WalletStorageError> {
+        self.encrypt(PASSWORD_CHECK_PLAINTEXT)
+    }
+
+    pub fn verify_password_check(&self, password_check: &str) -> Result<(), WalletStorageError> {
+        // Decryption only succeeds with the correct key
+        let plaintext = self
+            .decrypt(password_check)
+            .map_err(|_| WalletStorageError::InvalidPassword)?;
+        if plaintext.as_slice() != PASSWORD_CHECK_PLAINTEXT {
+            return Err(WalletStorageError::InvalidPassword);
+        }
+        Ok(())
+    }
+
+    /// Encrypts the value if it is not already encrypted
+    pub fn encrypt_if_plaintext(&self, value: String) -> Result<String, WalletStorageError> {
+        if is_encrypted_value(&value) {
+            return Ok(value);
+        }
+        self.encrypt(value.as_bytes())
+    }
+}
+
+pub fn is_encrypted_value(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_VALUE_PREFIX)
+}
+
+/// The encryption state of the database at the time a transaction was started
+#[derive(Clone, Default)]
+pub struct Encryption {
+    is_enabled: bool,
+    cipher: Option<Arc<DatabaseCipher>>,
+}
+
+impl Encryption {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn locked() -> Self {
+        Self {
+            is_enabled: true,
+            cipher: None,
+        }
+    }
+
+    pub fn unlocked(cipher: Arc<DatabaseCipher>) -> Self {
+        Self {
+            is_enabled: true,
+            cipher: Some(cipher),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.is_enabled && self.cipher.is_none()
+    }
+
+    /// Serializes the value to JSON, encrypting it if encryption is enabled
+    pub fn serialize_json<T: Serialize + ?Sized>(&self, value: &T) -> Result<String, WalletStorageError> {
+        let json = serialize_json(value)?;
+        if !self.is_enabled {
+            return Ok(json);
+        }
+        self.cipher()?.encrypt(json.as_bytes())
+    }
+
+    /// Deserializes a JSON value that may have been encrypted
+    pub fn deserialize_json<T: DeserializeOwned>(&self, value: &str) -> Result<T, WalletStorageError> {
+        if !is_encrypted_value(value) {
+            return deserialize_json(value);
+        }
+        let plaintext = self.cipher()?.decrypt(value)?;
+        let json = std::str::from_utf8(&plaintext).map_err(|e| WalletStorageError::DecodingError {
+            operation: "decrypt",
+            item: "column value",
+            details: e.to_string(),
+        })?;
+        deserialize_json(json)
+    }
+
+    fn cipher(&self) -> Result<&DatabaseCipher, WalletStorageError> {
+        self.cipher.as_deref().ok_or(WalletStorageError::Locked)
+    }
+}
@@ -3,6 +3,7 @@
 #[macro_use]
 extern crate diesel;
 
+mod encryption;
 mod models;
 mod reader;
 mod schema;
@@ -13,19 +14,31 @@ use std::{
     fmt::{Debug, Formatter},
     fs::create_dir_all,
     path::Path,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, RwLock},
 };
 
 use diesel::{sql_query, Connection, RunQueryDsl, SqliteConnection};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use tari_dan_wallet_sdk::storage::{WalletStorageError, WalletStore};
+use log::*;
+use tari_dan_wallet_sdk::storage::{WalletStorageError, WalletStore, WalletStoreWriter};
+use tari_utilities::{
+    hex::{from_hex, to_hex},
+    SafePassword,
+};
+
+use crate::{
+    encryption::{generate_salt, DatabaseCipher, Encryption},
+    reader::ReadTransaction,
+    writer::WriteTransaction,
+};
 
-use crate::{reader::ReadTransaction, writer::WriteTransaction};
+const LOG_TARGET: &str = "tari::dan::wallet_sdk::storage_sqlite";
 
 #[derive(Clone)]
 pub struct SqliteWalletStore {
     // MUTEX: required to make Sync
     connection: Arc<Mutex<SqliteConnection>>,
+    encryption: Arc<RwLock<Encryption>>,
 }
 
 impl SqliteWalletStore {
@@ -42,16 +55,38 @@ impl SqliteWalletStore {
 
         Ok(Self {
             connection: Arc::new(Mutex::new(connection)),
+            encryption: Arc::new(RwLock::new(Encryption::disabled())),
         })
     }
 
     pub fn run_migrations(&self) -> Result<(), WalletStorageError> {
-        let mut conn = self.connection.lock().unwrap();
-        const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
-        conn.run_pending_migrations(MIGRATIONS)
-            .map_err(|source| WalletStorageError::general("migrate", source))?;
+        {
+            let mut conn = self.connection.lock().unwrap();
+            const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
+            conn.run_pending_migrations(MIGRATIONS)
+                .map_err(|source| WalletStorageError::general("migrate", source))?;
+        }
+        self.load_encryption_state()?;
         Ok(())
     }
+
+    /// Returns true if the database is encrypted, regardless of whether it is locked
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.read().unwrap().is_enabled()
+    }
+
+    fn load_encryption_state(&self) -> Result<(), WalletStorageError> {
+        let is_encrypted = self.create_read_tx()?.database_encryption_get()?.is_some();
+        let mut encryption = self.encryption.write().unwrap();
+        if is_encrypted && !encryption.is_enabled() {
+            *encryption = Encryption::locked();
+        }
+        Ok(())
+    }
+
+    fn encryption(&self) -> Encryption {
+        self.encryption.read().unwrap().clone()
+    }
 }
 
 impl WalletStore for SqliteWalletStore {
@@ -63,7 +98,7 @@ impl WalletStore for SqliteWalletStore {
         sql_query("BEGIN")
             .execute(&mut *lock)
             .map_err(|e| WalletStorageError::general("BEGIN transaction", e))?;
-        Ok(ReadTransaction::new(lock))
+        Ok(ReadTransaction::new(lock, self.encryption()))
     }
 
     fn create_write_tx(&self) -> Result<Self::WriteTransaction<'_>, WalletStorageError> {
@@ -71,7 +106,48 @@ impl WalletStore for SqliteWalletStore {
         sql_query("BEGIN")
             .execute(&mut *lock)
             .map_err(|e| WalletStorageError::general("BEGIN transaction", e))?;
-        Ok(WriteTransaction::new(lock))
+        Ok(WriteTransaction::new(lock, self.encryption()))
+    }
+
+    fn is_locked(&self) -> bool {
+        self.encryption.read().unwrap().is_locked()
+    }
+
+    fn lock(&self) {
+        let mut encryption = self.encryption.write().unwrap();
+        if encryption.is_enabled() {
+            *encryption = Encryption::locked();
+            info!(target: LOG_TARGET, "🔒 Wallet database locked");
+        }
+    }
+
+    fn unlock(&self, password: &SafePassword) -> Result<(), WalletStorageError> {
+        let mut tx = self.create_write_tx()?;
+        let cipher = match tx.database_encryption_get()? {
+            Some((salt, password_check)) => {
+                let salt = from_hex(&salt).map_err(|e| WalletStorageError::DecodingError {
+                    operation: "unlock",
+                    item: "database_encryption.salt",
+                    details: e.to_string(),
+                })?;
+                let cipher = DatabaseCipher::derive(password, &salt)?;
+                cipher.verify_password_check(&password_check)?;
+                tx.rollback()?;
+                cipher
+            },
+            None => {
+                let salt = generate_salt();
+                let cipher = DatabaseCipher::derive(password, &salt)?;
+                tx.database_encryption_enable(&to_hex(&salt), &cipher)?;
+                tx.commit()?;
+                info!(target: LOG_TARGET, "🔐 Wallet database encryption enabled");
+                cipher
+            },
+        };
+
+        *self.encryption.write().unwrap() = Encryption::unlocked(Arc::new(cipher));
+        info!(target: LOG_TARGET, "🔓 Wallet database unlocked");
+        Ok(())
     }
 }
 
@@ -3,7 +3,9 @@
 
 use chrono::NaiveDateTime;
 use diesel::{Identifiable, Queryable};
-use tari_engine_types::substate::InvalidSubstateIdFormat;
+use tari_common_types::types::PublicKey;
+use tari_dan_wallet_sdk::{models::AccountKind, storage::WalletStorageError};
+use tari_utilities::hex::Hex;
 
 use crate::schema::accounts;
 
@@ -17,17 +19,37 @@ pub struct Account {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub is_default: bool,
+    pub watch_only_public_key: Option<String>,
+    pub view_key: Option<String>,
+    pub is_imported: bool,
 }
 
 impl TryFrom<Account> for tari_dan_wallet_sdk::models::Account {
-    type Error = InvalidSubstateIdFormat;
+    type Error = WalletStorageError;
 
     fn try_from(account: Account) -> Result<Self, Self::Error> {
+        let kind = match account.watch_only_public_key {
+            Some(public_key) => AccountKind::WatchOnly {
+                owner_public_key: PublicKey::from_hex(&public_key).map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_from",
+                    item: "account.watch_only_public_key",
+                    details: e.to_string(),
+                })?,
+            },
+            None if account.is_imported => AccountKind::Imported,
+            None => AccountKind::Owned,
+        };
+
         Ok(Self {
             name: account.name,
-            address: account.address.parse()?,
+            address: account.address.parse().map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_from",
+                item: "account.address",
+                details: format!("{}", e),
+            })?,
             key_index: account.owner_key_index as u64,
             is_default: account.is_default,
+            kind,
         })
     }
 }
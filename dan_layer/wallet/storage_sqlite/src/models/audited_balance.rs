@@ -0,0 +1,63 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{models::AuditedVaultBalance, storage::WalletStorageError};
+use tari_template_lib::{
+    models::{Amount, ResourceAddress, VaultId},
+    prelude::ComponentAddress,
+};
+
+use crate::schema::audited_balances;
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = audited_balances)]
+pub struct AuditedBalance {
+    pub id: i32,
+    pub resource_address: String,
+    pub view_key_index: i64,
+    pub vault_address: String,
+    pub owner_address: Option<String>,
+    pub balance: i64,
+    pub num_outputs: i32,
+    pub num_unresolved: i32,
+    pub vault_version: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AuditedBalance {
+    pub(crate) fn try_into_model(self) -> Result<AuditedVaultBalance, WalletStorageError> {
+        Ok(AuditedVaultBalance {
+            resource_address: ResourceAddress::from_str(&self.resource_address).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "audited_balances.resource_address",
+                    details: e.to_string(),
+                }
+            })?,
+            view_key_index: self.view_key_index as u64,
+            vault_id: VaultId::from_str(&self.vault_address).map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_into_model",
+                item: "audited_balances.vault_address",
+                details: e.to_string(),
+            })?,
+            owner: self
+                .owner_address
+                .map(|owner| ComponentAddress::from_str(&owner))
+                .transpose()
+                .map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "audited_balances.owner_address",
+                    details: e.to_string(),
+                })?,
+            balance: Amount(self.balance),
+            num_outputs: self.num_outputs as u32,
+            num_unresolved: self.num_unresolved as u32,
+            vault_version: self.vault_version as u32,
+        })
+    }
+}
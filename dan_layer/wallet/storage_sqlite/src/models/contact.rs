@@ -0,0 +1,53 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_common_types::types::PublicKey;
+use tari_dan_wallet_sdk::storage::WalletStorageError;
+use tari_template_lib::models::ComponentAddress;
+use tari_utilities::hex::Hex;
+
+use crate::schema::contacts;
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = contacts)]
+pub struct Contact {
+    pub id: i32,
+    pub name: String,
+    pub component_address: Option<String>,
+    pub public_key: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<Contact> for tari_dan_wallet_sdk::models::Contact {
+    type Error = WalletStorageError;
+
+    fn try_from(contact: Contact) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: contact.name,
+            component_address: contact
+                .component_address
+                .as_deref()
+                .map(ComponentAddress::from_str)
+                .transpose()
+                .map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_from",
+                    item: "contact.component_address",
+                    details: e.to_string(),
+                })?,
+            public_key: contact
+                .public_key
+                .as_deref()
+                .map(PublicKey::from_hex)
+                .transpose()
+                .map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_from",
+                    item: "contact.public_key",
+                    details: e.to_string(),
+                })?,
+        })
+    }
+}
@@ -0,0 +1,46 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{models::TransactionStatus, storage::WalletStorageError};
+use tari_transaction::TransactionId;
+
+use crate::{schema::imported_transactions, serialization::deserialize_json};
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = imported_transactions)]
+pub struct ImportedTransaction {
+    pub id: i32,
+    pub transaction_id: String,
+    pub status: String,
+    pub final_fee: Option<i64>,
+    pub accounts: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<ImportedTransaction> for tari_dan_wallet_sdk::models::ImportedTransaction {
+    type Error = WalletStorageError;
+
+    fn try_from(imported: ImportedTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction_id: TransactionId::from_hex(&imported.transaction_id).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_from",
+                    item: "imported_transaction.transaction_id",
+                    details: e.to_string(),
+                }
+            })?,
+            status: TransactionStatus::from_str(&imported.status).map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_from",
+                item: "imported_transaction.status",
+                details: e.to_string(),
+            })?,
+            final_fee: imported.final_fee.map(Into::into),
+            accounts: deserialize_json(&imported.accounts)?,
+            imported_at: imported.created_at,
+        })
+    }
+}
@@ -7,6 +7,9 @@ pub use account::Account;
 mod config;
 pub use config::Config;
 
+mod contact;
+pub use contact::Contact;
+
 mod output;
 pub use output::ConfidentialOutput;
 
@@ -26,3 +29,18 @@ pub use non_fungible_tokens::NonFungibleToken;
 mod proof;
 // Currently only used internally
 pub(crate) use proof::Proof;
+
+mod audited_balance;
+pub use audited_balance::AuditedBalance;
+
+mod published_template;
+pub use published_template::PublishedTemplate;
+
+mod transaction_failure;
+pub use transaction_failure::TransactionFailure;
+
+mod imported_transaction;
+pub use imported_transaction::ImportedTransaction;
+
+mod scheduled_transaction;
+pub use scheduled_transaction::ScheduledTransaction;
@@ -3,6 +3,12 @@
 
 use chrono::NaiveDateTime;
 use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{
+    models::{ConfidentialProof, ConfidentialProofId},
+    storage::WalletStorageError,
+};
+use tari_template_lib::models::Amount;
+use tari_transaction::TransactionId;
 
 use crate::schema::proofs;
 
@@ -16,3 +22,23 @@ pub struct Proof {
     pub locked_revealed_amount: i64,
     pub created_at: NaiveDateTime,
 }
+
+impl Proof {
+    pub(crate) fn try_into_model(self) -> Result<ConfidentialProof, WalletStorageError> {
+        Ok(ConfidentialProof {
+            id: self.id as ConfidentialProofId,
+            transaction_id: self
+                .transaction_hash
+                .map(|hash| {
+                    TransactionId::from_hex(&hash).map_err(|e| WalletStorageError::DecodingError {
+                        operation: "try_into_model",
+                        item: "proof.transaction_hash",
+                        details: e.to_string(),
+                    })
+                })
+                .transpose()?,
+            locked_revealed_amount: Amount::new(self.locked_revealed_amount),
+            created_at: self.created_at,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{models::PublishedTemplateModel, storage::WalletStorageError};
+use tari_engine_types::{substate::SubstateId, TemplateAddress};
+use tari_transaction::TransactionId;
+
+use crate::schema::published_templates;
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = published_templates)]
+pub struct PublishedTemplate {
+    pub id: i32,
+    pub template_address: String,
+    pub author_account: String,
+    pub transaction_id: String,
+    pub binary_size: i64,
+    pub created_at: NaiveDateTime,
+}
+
+impl PublishedTemplate {
+    pub(crate) fn try_into_model(self) -> Result<PublishedTemplateModel, WalletStorageError> {
+        Ok(PublishedTemplateModel {
+            template_address: TemplateAddress::from_str(&self.template_address).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "published_template.template_address",
+                    details: e.to_string(),
+                }
+            })?,
+            author_account: SubstateId::from_str(&self.author_account).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "published_template.author_account",
+                    details: e.to_string(),
+                }
+            })?,
+            transaction_id: TransactionId::from_hex(&self.transaction_id).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "published_template.transaction_id",
+                    details: e.to_string(),
+                }
+            })?,
+            binary_size: self.binary_size as u64,
+            published_at: self.created_at,
+        })
+    }
+}
@@ -0,0 +1,95 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    models::{ScheduledTransaction as ScheduledTransactionModel, ScheduledTransactionStatus},
+    storage::WalletStorageError,
+};
+use tari_template_lib::models::{Amount, ComponentAddress};
+use tari_transaction::TransactionId;
+
+use crate::{encryption::Encryption, schema::scheduled_transactions, serialization::deserialize_json};
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = scheduled_transactions)]
+pub struct ScheduledTransaction {
+    pub id: i32,
+    pub name: String,
+    pub schedule: String,
+    pub instructions: String,
+    pub fee_account: String,
+    pub max_fee: i64,
+    pub max_runs: Option<i64>,
+    pub num_runs: i64,
+    pub status: String,
+    pub next_run_at: Option<NaiveDateTime>,
+    pub next_run_epoch: Option<i64>,
+    pub last_transaction_id: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ScheduledTransaction {
+    pub(crate) fn try_into_model(
+        self,
+        encryption: &Encryption,
+    ) -> Result<ScheduledTransactionModel, WalletStorageError> {
+        Ok(ScheduledTransactionModel {
+            id: to_u64(self.id.into(), "scheduled_transaction.id")?,
+            name: self.name,
+            schedule: deserialize_json(&self.schedule)?,
+            instructions: encryption.deserialize_json(&self.instructions)?,
+            fee_account: ComponentAddress::from_str(&self.fee_account).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "scheduled_transaction.fee_account",
+                    details: e.to_string(),
+                }
+            })?,
+            max_fee: Amount::new(self.max_fee),
+            max_runs: self
+                .max_runs
+                .map(|n| to_u64(n, "scheduled_transaction.max_runs"))
+                .transpose()?,
+            num_runs: to_u64(self.num_runs, "scheduled_transaction.num_runs")?,
+            status: ScheduledTransactionStatus::from_str(&self.status).map_err(|e| {
+                WalletStorageError::DecodingError {
+                    operation: "try_into_model",
+                    item: "scheduled_transaction.status",
+                    details: e.to_string(),
+                }
+            })?,
+            next_run_at: self.next_run_at,
+            next_run_epoch: self
+                .next_run_epoch
+                .map(|epoch| to_u64(epoch, "scheduled_transaction.next_run_epoch").map(Epoch))
+                .transpose()?,
+            last_transaction_id: self
+                .last_transaction_id
+                .map(|id| {
+                    TransactionId::from_hex(&id).map_err(|e| WalletStorageError::DecodingError {
+                        operation: "try_into_model",
+                        item: "scheduled_transaction.last_transaction_id",
+                        details: e.to_string(),
+                    })
+                })
+                .transpose()?,
+            last_error: self.last_error,
+            created_at: self.created_at,
+        })
+    }
+}
+
+fn to_u64(n: i64, item: &'static str) -> Result<u64, WalletStorageError> {
+    u64::try_from(n).map_err(|_| WalletStorageError::DecodingError {
+        operation: "try_into_model",
+        item,
+        details: format!("{} is negative", n),
+    })
+}
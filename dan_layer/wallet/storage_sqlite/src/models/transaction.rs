@@ -11,7 +11,7 @@ use tari_dan_wallet_sdk::{
 };
 use tari_transaction::UnsignedTransaction;
 
-use crate::{schema::transactions, serialization::deserialize_json};
+use crate::{encryption::Encryption, schema::transactions};
 
 #[derive(Debug, Clone, Queryable, Identifiable)]
 #[diesel(table_name = transactions)]
@@ -38,15 +38,15 @@ pub struct Transaction {
 }
 
 impl Transaction {
-    pub fn try_into_wallet_transaction(self) -> Result<WalletTransaction, WalletStorageError> {
-        let signatures = deserialize_json(&self.signatures)?;
-        let inputs = deserialize_json(&self.inputs)?;
+    pub fn try_into_wallet_transaction(self, encryption: &Encryption) -> Result<WalletTransaction, WalletStorageError> {
+        let signatures = encryption.deserialize_json(&self.signatures)?;
+        let inputs = encryption.deserialize_json(&self.inputs)?;
 
         Ok(WalletTransaction {
             transaction: tari_transaction::Transaction::new(
                 UnsignedTransaction {
-                    fee_instructions: deserialize_json(&self.fee_instructions)?,
-                    instructions: deserialize_json(&self.instructions)?,
+                    fee_instructions: encryption.deserialize_json(&self.fee_instructions)?,
+                    instructions: encryption.deserialize_json(&self.instructions)?,
                     inputs,
                     min_epoch: self.min_epoch.map(|epoch| Epoch(epoch as u64)),
                     max_epoch: self.max_epoch.map(|epoch| Epoch(epoch as u64)),
@@ -58,11 +58,23 @@ impl Transaction {
                 item: "status",
                 details: e.to_string(),
             })?,
-            finalize: self.result.as_deref().map(deserialize_json).transpose()?,
+            finalize: self
+                .result
+                .as_deref()
+                .map(|r| encryption.deserialize_json(r))
+                .transpose()?,
             final_fee: self.final_fee.map(|f| f.into()),
-            qcs: self.qcs.map(|q| deserialize_json(&q)).transpose()?.unwrap_or_default(),
-            required_substates: deserialize_json(&self.required_substates)?,
-            new_account_info: self.new_account_info.as_deref().map(deserialize_json).transpose()?,
+            qcs: self
+                .qcs
+                .map(|q| encryption.deserialize_json(&q))
+                .transpose()?
+                .unwrap_or_default(),
+            required_substates: encryption.deserialize_json(&self.required_substates)?,
+            new_account_info: self
+                .new_account_info
+                .as_deref()
+                .map(|i| encryption.deserialize_json(i))
+                .transpose()?,
             is_dry_run: self.is_dry_run,
             execution_time: self
                 .executed_time_ms
@@ -0,0 +1,60 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Queryable};
+use tari_dan_wallet_sdk::{
+    models::{TransactionFailure as TransactionFailureModel, TransactionStatus},
+    storage::WalletStorageError,
+};
+use tari_transaction::TransactionId;
+
+use crate::{encryption::Encryption, schema::transaction_failures};
+
+#[derive(Debug, Clone, Queryable, Identifiable)]
+#[diesel(table_name = transaction_failures)]
+pub struct TransactionFailure {
+    pub id: i32,
+    pub transaction_id: String,
+    pub status: String,
+    pub reason: String,
+    pub inputs: String,
+    pub retried_as: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub attempt: i32,
+}
+
+impl TransactionFailure {
+    pub(crate) fn try_into_model(self, encryption: &Encryption) -> Result<TransactionFailureModel, WalletStorageError> {
+        Ok(TransactionFailureModel {
+            transaction_id: parse_transaction_id(&self.transaction_id, "transaction_failure.transaction_id")?,
+            status: TransactionStatus::from_str(&self.status).map_err(|e| WalletStorageError::DecodingError {
+                operation: "try_into_model",
+                item: "transaction_failure.status",
+                details: e.to_string(),
+            })?,
+            reason: self.reason,
+            inputs: encryption.deserialize_json(&self.inputs)?,
+            retried_as: self
+                .retried_as
+                .map(|id| parse_transaction_id(&id, "transaction_failure.retried_as"))
+                .transpose()?,
+            failed_at: self.created_at,
+            attempt: self.attempt.try_into().map_err(|_| WalletStorageError::DecodingError {
+                operation: "try_into_model",
+                item: "transaction_failure.attempt",
+                details: format!("attempt {} is negative", self.attempt),
+            })?,
+        })
+    }
+}
+
+fn parse_transaction_id(s: &str, item: &'static str) -> Result<TransactionId, WalletStorageError> {
+    TransactionId::from_hex(s).map_err(|e| WalletStorageError::DecodingError {
+        operation: "try_into_model",
+        item,
+        details: e.to_string(),
+    })
+}
@@ -28,6 +28,8 @@ pub struct Vault {
     pub token_symbol: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub decimals: Option<i32>,
+    pub is_frozen: bool,
 }
 
 impl Vault {
@@ -51,9 +53,19 @@ impl Vault {
             })?,
             resource_type: db_str_to_resource_type(&self.resource_type)?,
             token_symbol: self.token_symbol,
+            decimals: self
+                .decimals
+                .map(u8::try_from)
+                .transpose()
+                .map_err(|e| WalletStorageError::DecodingError {
+                    operation: "try_into_vault",
+                    item: "vault.decimals",
+                    details: e.to_string(),
+                })?,
             revealed_balance: Amount(self.revealed_balance),
             locked_revealed_balance: Amount(self.locked_revealed_balance),
             confidential_balance: Amount(self.confidential_balance),
+            is_frozen: self.is_frozen,
         })
     }
 }
@@ -5,7 +5,7 @@ use std::{collections::HashMap, str::FromStr, sync::MutexGuard};
 
 use bigdecimal::{BigDecimal, ToPrimitive};
 use diesel::{
-    dsl::sum,
+    dsl::{min, sum},
     sql_query,
     BoolExpressionMethods,
     JoinOnDsl,
@@ -17,27 +17,33 @@ use diesel::{
 };
 use log::error;
 use serde::de::DeserializeOwned;
-use tari_common_types::types::Commitment;
-use tari_dan_common_types::substate_type::SubstateType;
+use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
+use tari_dan_common_types::{substate_type::SubstateType, Epoch};
 use tari_dan_wallet_sdk::{
     models::{
         Account,
+        AuditedVaultBalance,
         ConfidentialOutputModel,
+        ConfidentialProof,
         ConfidentialProofId,
         Config,
+        Contact,
+        ImportedTransaction,
         NonFungibleToken,
         OutputStatus,
+        PublishedTemplateModel,
+        ScheduledTransaction,
+        ScheduledTransactionStatus,
         SubstateModel,
+        TransactionFailure as TransactionFailureModel,
         TransactionStatus,
         VaultModel,
+        VersionedSubstateId,
         WalletTransaction,
     },
     storage::{WalletStorageError, WalletStoreReader},
 };
-use tari_engine_types::{
-    substate::{InvalidSubstateIdFormat, SubstateId},
-    TemplateAddress,
-};
+use tari_engine_types::{substate::SubstateId, TemplateAddress};
 use tari_template_lib::{
     models::{ResourceAddress, VaultId},
     prelude::{ComponentAddress, NonFungibleId},
@@ -47,25 +53,42 @@ use tari_utilities::hex::Hex;
 
 use crate::{
     diesel::{ExpressionMethods, NullableExpressionMethods},
+    encryption::Encryption,
     models,
-    serialization::deserialize_json,
 };
 
 const LOG_TARGET: &str = "tari::dan::wallet_sdk::storage_sqlite::reader";
 
 pub struct ReadTransaction<'a> {
     connection: MutexGuard<'a, SqliteConnection>,
+    encryption: Encryption,
     is_done: bool,
 }
 
 impl<'a> ReadTransaction<'a> {
-    pub fn new(connection: MutexGuard<'a, SqliteConnection>) -> Self {
+    pub fn new(connection: MutexGuard<'a, SqliteConnection>, encryption: Encryption) -> Self {
         Self {
             connection,
+            encryption,
             is_done: false,
         }
     }
 
+    pub(super) fn encryption(&self) -> &Encryption {
+        &self.encryption
+    }
+
+    /// Returns the salt and password check value if the database is encrypted
+    pub(super) fn database_encryption_get(&mut self) -> Result<Option<(String, String)>, WalletStorageError> {
+        use crate::schema::database_encryption;
+
+        database_encryption::table
+            .select((database_encryption::salt, database_encryption::password_check))
+            .first(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("database_encryption_get", e))
+    }
+
     pub(super) fn is_done(&self) -> bool {
         self.is_done
     }
@@ -149,6 +172,41 @@ impl WalletStoreReader for ReadTransaction<'_> {
             })
     }
 
+    fn imported_keys_get(&mut self, index: u64) -> Result<PrivateKey, WalletStorageError> {
+        use crate::schema::imported_keys;
+
+        let secret_key = imported_keys::table
+            .select(imported_keys::secret_key)
+            .filter(imported_keys::id.eq(index as i32))
+            .first::<String>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("imported_keys_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "imported_keys_get",
+                entity: "imported_key".to_string(),
+                key: index.to_string(),
+            })?;
+
+        self.encryption.deserialize_json(&secret_key)
+    }
+
+    fn imported_keys_get_index(&mut self, public_key: &PublicKey) -> Result<u64, WalletStorageError> {
+        use crate::schema::imported_keys;
+
+        imported_keys::table
+            .select(imported_keys::id)
+            .filter(imported_keys::public_key.eq(public_key.to_hex()))
+            .first(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("imported_keys_get_index", e))?
+            .map(|id: i32| id as u64)
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "imported_keys_get_index",
+                entity: "imported_key".to_string(),
+                key: public_key.to_hex(),
+            })
+    }
+
     // -------------------------------- Config -------------------------------- //
     fn config_get<T: DeserializeOwned>(&mut self, key: &str) -> Result<Config<T>, WalletStorageError> {
         use crate::schema::config;
@@ -166,7 +224,7 @@ impl WalletStoreReader for ReadTransaction<'_> {
 
         Ok(Config {
             key: config.key,
-            value: deserialize_json(&config.value)?,
+            value: self.encryption.deserialize_json(&config.value)?,
             is_encrypted: config.is_encrypted,
             created_at: 0,
             updated_at: 0,
@@ -199,7 +257,7 @@ impl WalletStoreReader for ReadTransaction<'_> {
                 key: transaction_id.to_string(),
             })?;
 
-        let transaction = row.try_into_wallet_transaction()?;
+        let transaction = row.try_into_wallet_transaction(&self.encryption)?;
         Ok(transaction)
     }
 
@@ -226,7 +284,38 @@ impl WalletStoreReader for ReadTransaction<'_> {
             .load::<models::Transaction>(self.connection())
             .map_err(|e| WalletStorageError::general("transactions_fetch_all", e))?;
 
-        rows.into_iter().map(|row| row.try_into_wallet_transaction()).collect()
+        rows.into_iter()
+            .map(|row| row.try_into_wallet_transaction(&self.encryption))
+            .collect()
+    }
+
+    fn transactions_fetch_after(
+        &mut self,
+        cursor: Option<u64>,
+        limit: u64,
+    ) -> Result<Vec<(u64, WalletTransaction)>, WalletStorageError> {
+        use crate::schema::transactions;
+
+        let cursor = cursor
+            .map(i32::try_from)
+            .transpose()
+            .map_err(|e| WalletStorageError::general("transactions_fetch_after", e))?;
+        let limit = i64::try_from(limit).map_err(|e| WalletStorageError::general("transactions_fetch_after", e))?;
+
+        let rows = transactions::table
+            .filter(transactions::dry_run.eq(false))
+            .filter(transactions::id.gt(cursor.unwrap_or(0)))
+            .order(transactions::id.asc())
+            .limit(limit)
+            .load::<models::Transaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("transactions_fetch_after", e))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let cursor = row.id as u64;
+                row.try_into_wallet_transaction(&self.encryption).map(|tx| (cursor, tx))
+            })
+            .collect()
     }
 
     // -------------------------------- Substates -------------------------------- //
@@ -391,7 +480,7 @@ impl WalletStoreReader for ReadTransaction<'_> {
 
         let account = row
             .try_into()
-            .map_err(|e: InvalidSubstateIdFormat| WalletStorageError::DecodingError {
+            .map_err(|e: WalletStorageError| WalletStorageError::DecodingError {
                 operation: "accounts_get_by_name",
                 item: "account",
                 details: e.to_string(),
@@ -427,7 +516,7 @@ impl WalletStoreReader for ReadTransaction<'_> {
 
         let account = row
             .try_into()
-            .map_err(|e: InvalidSubstateIdFormat| WalletStorageError::DecodingError {
+            .map_err(|e: WalletStorageError| WalletStorageError::DecodingError {
                 operation: "accounts_get_by_vault",
                 item: "account",
                 details: e.to_string(),
@@ -435,6 +524,26 @@ impl WalletStoreReader for ReadTransaction<'_> {
         Ok(account)
     }
 
+    fn accounts_get_view_key(&mut self, address: &SubstateId) -> Result<Option<PrivateKey>, WalletStorageError> {
+        use crate::schema::accounts;
+
+        let view_key = accounts::table
+            .select(accounts::view_key)
+            .filter(accounts::address.eq(address.to_string()))
+            .first::<Option<String>>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("accounts_get_view_key", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "accounts_get_view_key",
+                entity: "account".to_string(),
+                key: address.to_string(),
+            })?;
+
+        view_key
+            .map(|view_key| self.encryption.deserialize_json(&view_key))
+            .transpose()
+    }
+
     // -------------------------------- Vaults -------------------------------- //
     fn vaults_get(&mut self, address: &SubstateId) -> Result<VaultModel, WalletStorageError> {
         use crate::schema::{accounts, vaults};
@@ -541,6 +650,44 @@ impl WalletStoreReader for ReadTransaction<'_> {
         Ok(vaults)
     }
 
+    // -------------------------------- Vault history -------------------------------- //
+    fn vault_history_get_at_epoch(
+        &mut self,
+        vault_address: &SubstateId,
+        epoch: Epoch,
+    ) -> Result<Option<VersionedSubstateId>, WalletStorageError> {
+        use crate::schema::vault_history;
+
+        let version = vault_history::table
+            .filter(vault_history::vault_address.eq(vault_address.to_string()))
+            .filter(vault_history::epoch.le(epoch.as_u64() as i64))
+            .order((vault_history::epoch.desc(), vault_history::version.desc()))
+            .select(vault_history::version)
+            .first::<i32>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("vault_history_get_at_epoch", e))?;
+
+        Ok(version.map(|version| VersionedSubstateId {
+            substate_id: vault_address.clone(),
+            version: version as u32,
+        }))
+    }
+
+    fn vault_history_get_earliest_epoch(
+        &mut self,
+        account_address: &SubstateId,
+    ) -> Result<Option<Epoch>, WalletStorageError> {
+        use crate::schema::vault_history;
+
+        let epoch = vault_history::table
+            .filter(vault_history::account_address.eq(account_address.to_string()))
+            .select(min(vault_history::epoch))
+            .first::<Option<i64>>(self.connection())
+            .map_err(|e| WalletStorageError::general("vault_history_get_earliest_epoch", e))?;
+
+        Ok(epoch.map(|epoch| Epoch(epoch as u64)))
+    }
+
     // -------------------------------- Outputs -------------------------------- //
     fn outputs_get_unspent_balance(&mut self, vault_address: &SubstateId) -> Result<u64, WalletStorageError> {
         use crate::schema::{outputs, vaults};
@@ -706,6 +853,17 @@ impl WalletStoreReader for ReadTransaction<'_> {
         Ok(proof_ids.into_iter().map(|id| id as u64).collect())
     }
 
+    fn proofs_get_all(&mut self) -> Result<Vec<ConfidentialProof>, WalletStorageError> {
+        use crate::schema::proofs;
+
+        let rows = proofs::table
+            .order(proofs::id.asc())
+            .load::<models::Proof>(self.connection())
+            .map_err(|e| WalletStorageError::general("proofs_get_all", e))?;
+
+        rows.into_iter().map(|row| row.try_into_model()).collect()
+    }
+
     fn non_fungible_token_get_by_nft_id(
         &mut self,
         nft_id: NonFungibleId,
@@ -820,6 +978,220 @@ impl WalletStoreReader for ReadTransaction<'_> {
             details: e.to_string(),
         })
     }
+
+    // -------------------------------- Audited balances -------------------------------- //
+    fn audited_balances_get_by_resource(
+        &mut self,
+        resource_address: &ResourceAddress,
+        view_key_index: u64,
+    ) -> Result<Vec<AuditedVaultBalance>, WalletStorageError> {
+        use crate::schema::audited_balances;
+
+        let rows = audited_balances::table
+            .filter(audited_balances::resource_address.eq(resource_address.to_string()))
+            .filter(audited_balances::view_key_index.eq(view_key_index as i64))
+            .order(audited_balances::owner_address.asc())
+            .load::<models::AuditedBalance>(self.connection())
+            .map_err(|e| WalletStorageError::general("audited_balances_get_by_resource", e))?;
+
+        rows.into_iter().map(|row| row.try_into_model()).collect()
+    }
+
+    fn audited_balances_get_audited_resources(&mut self) -> Result<Vec<(ResourceAddress, u64)>, WalletStorageError> {
+        use crate::schema::audited_balances;
+
+        let rows = audited_balances::table
+            .select((audited_balances::resource_address, audited_balances::view_key_index))
+            .distinct()
+            .get_results::<(String, i64)>(self.connection())
+            .map_err(|e| WalletStorageError::general("audited_balances_get_audited_resources", e))?;
+
+        rows.into_iter()
+            .map(|(resource_address, view_key_index)| {
+                let resource_address =
+                    ResourceAddress::from_str(&resource_address).map_err(|e| WalletStorageError::DecodingError {
+                        operation: "audited_balances_get_audited_resources",
+                        item: "audited_balances.resource_address",
+                        details: e.to_string(),
+                    })?;
+                Ok((resource_address, view_key_index as u64))
+            })
+            .collect()
+    }
+
+    // -------------------------------- Published templates -------------------------------- //
+    fn published_templates_get_all(&mut self) -> Result<Vec<PublishedTemplateModel>, WalletStorageError> {
+        use crate::schema::published_templates;
+
+        let rows = published_templates::table
+            .order(published_templates::id.asc())
+            .load::<models::PublishedTemplate>(self.connection())
+            .map_err(|e| WalletStorageError::general("published_templates_get_all", e))?;
+
+        rows.into_iter().map(|row| row.try_into_model()).collect()
+    }
+
+    // -------------------------------- Contacts -------------------------------- //
+    fn contacts_get_by_name(&mut self, name: &str) -> Result<Contact, WalletStorageError> {
+        use crate::schema::contacts;
+
+        let row = contacts::table
+            .filter(contacts::name.eq(name))
+            .first::<models::Contact>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("contacts_get_by_name", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "contacts_get_by_name",
+                entity: "contact".to_string(),
+                key: name.to_string(),
+            })?;
+
+        row.try_into()
+    }
+
+    fn contacts_get_all(&mut self) -> Result<Vec<Contact>, WalletStorageError> {
+        use crate::schema::contacts;
+
+        let rows = contacts::table
+            .order(contacts::name.asc())
+            .load::<models::Contact>(self.connection())
+            .map_err(|e| WalletStorageError::general("contacts_get_all", e))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    // -------------------------------- Transaction failures -------------------------------- //
+    fn transaction_failures_get(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<TransactionFailureModel, WalletStorageError> {
+        use crate::schema::transaction_failures;
+
+        let row = transaction_failures::table
+            .filter(transaction_failures::transaction_id.eq(transaction_id.to_string()))
+            .first::<models::TransactionFailure>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("transaction_failures_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "transaction_failures_get",
+                entity: "transaction_failure".to_string(),
+                key: transaction_id.to_string(),
+            })?;
+
+        row.try_into_model(&self.encryption)
+    }
+
+    fn transaction_failures_get_all(
+        &mut self,
+        include_retried: bool,
+    ) -> Result<Vec<TransactionFailureModel>, WalletStorageError> {
+        use crate::schema::transaction_failures;
+
+        let mut query = transaction_failures::table.into_boxed();
+        if !include_retried {
+            query = query.filter(transaction_failures::retried_as.is_null());
+        }
+
+        let rows = query
+            .order(transaction_failures::id.desc())
+            .load::<models::TransactionFailure>(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_failures_get_all", e))?;
+
+        rows.into_iter()
+            .map(|row| row.try_into_model(&self.encryption))
+            .collect()
+    }
+
+    fn transaction_failures_get_by_retried_as(
+        &mut self,
+        retried_as: TransactionId,
+    ) -> Result<TransactionFailureModel, WalletStorageError> {
+        use crate::schema::transaction_failures;
+
+        let row = transaction_failures::table
+            .filter(transaction_failures::retried_as.eq(retried_as.to_string()))
+            .first::<models::TransactionFailure>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("transaction_failures_get_by_retried_as", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "transaction_failures_get_by_retried_as",
+                entity: "transaction_failure".to_string(),
+                key: retried_as.to_string(),
+            })?;
+
+        row.try_into_model(&self.encryption)
+    }
+
+    // -------------------------------- Imported transactions -------------------------------- //
+    fn imported_transactions_get(
+        &mut self,
+        transaction_id: TransactionId,
+    ) -> Result<ImportedTransaction, WalletStorageError> {
+        use crate::schema::imported_transactions;
+
+        let row = imported_transactions::table
+            .filter(imported_transactions::transaction_id.eq(transaction_id.to_string()))
+            .first::<models::ImportedTransaction>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("imported_transactions_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "imported_transactions_get",
+                entity: "imported_transaction".to_string(),
+                key: transaction_id.to_string(),
+            })?;
+
+        row.try_into()
+    }
+
+    fn imported_transactions_get_all(&mut self) -> Result<Vec<ImportedTransaction>, WalletStorageError> {
+        use crate::schema::imported_transactions;
+
+        let rows = imported_transactions::table
+            .order(imported_transactions::id.desc())
+            .load::<models::ImportedTransaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("imported_transactions_get_all", e))?;
+
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    // -------------------------------- Scheduled transactions -------------------------------- //
+    fn scheduled_transactions_get(&mut self, id: u64) -> Result<ScheduledTransaction, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let row = scheduled_transactions::table
+            .filter(scheduled_transactions::id.eq(id as i32))
+            .first::<models::ScheduledTransaction>(self.connection())
+            .optional()
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_get", e))?
+            .ok_or_else(|| WalletStorageError::NotFound {
+                operation: "scheduled_transactions_get",
+                entity: "scheduled_transaction".to_string(),
+                key: id.to_string(),
+            })?;
+
+        row.try_into_model(&self.encryption)
+    }
+
+    fn scheduled_transactions_get_all(
+        &mut self,
+        status: Option<ScheduledTransactionStatus>,
+    ) -> Result<Vec<ScheduledTransaction>, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let mut query = scheduled_transactions::table.into_boxed();
+        if let Some(status) = status {
+            query = query.filter(scheduled_transactions::status.eq(status.as_key_str()));
+        }
+
+        let rows = query
+            .order(scheduled_transactions::id.asc())
+            .load::<models::ScheduledTransaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_get_all", e))?;
+
+        rows.into_iter()
+            .map(|row| row.try_into_model(&self.encryption))
+            .collect()
+    }
 }
 
 impl Drop for ReadTransaction<'_> {
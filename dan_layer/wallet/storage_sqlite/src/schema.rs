@@ -9,6 +9,25 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         is_default -> Bool,
+        watch_only_public_key -> Nullable<Text>,
+        view_key -> Nullable<Text>,
+        is_imported -> Bool,
+    }
+}
+
+diesel::table! {
+    audited_balances (id) {
+        id -> Integer,
+        resource_address -> Text,
+        view_key_index -> BigInt,
+        vault_address -> Text,
+        owner_address -> Nullable<Text>,
+        balance -> BigInt,
+        num_outputs -> Integer,
+        num_unresolved -> Integer,
+        vault_version -> Integer,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
     }
 }
 
@@ -33,6 +52,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    contacts (id) {
+        id -> Integer,
+        name -> Text,
+        component_address -> Nullable<Text>,
+        public_key -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    database_encryption (id) {
+        id -> Integer,
+        salt -> Text,
+        password_check -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    imported_keys (id) {
+        id -> Integer,
+        public_key -> Text,
+        secret_key -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    imported_transactions (id) {
+        id -> Integer,
+        transaction_id -> Text,
+        status -> Text,
+        final_fee -> Nullable<BigInt>,
+        accounts -> Text,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     key_manager_states (id) {
         id -> Integer,
@@ -87,6 +145,37 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    published_templates (id) {
+        id -> Integer,
+        template_address -> Text,
+        author_account -> Text,
+        transaction_id -> Text,
+        binary_size -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scheduled_transactions (id) {
+        id -> Integer,
+        name -> Text,
+        schedule -> Text,
+        instructions -> Text,
+        fee_account -> Text,
+        max_fee -> BigInt,
+        max_runs -> Nullable<BigInt>,
+        num_runs -> BigInt,
+        status -> Text,
+        next_run_at -> Nullable<Timestamp>,
+        next_run_epoch -> Nullable<BigInt>,
+        last_transaction_id -> Nullable<Text>,
+        last_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     substates (id) {
         id -> Integer,
@@ -100,6 +189,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transaction_failures (id) {
+        id -> Integer,
+        transaction_id -> Text,
+        status -> Text,
+        reason -> Text,
+        inputs -> Text,
+        retried_as -> Nullable<Text>,
+        created_at -> Timestamp,
+        attempt -> Integer,
+    }
+}
+
 diesel::table! {
     transactions (id) {
         id -> Integer,
@@ -124,6 +226,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    vault_history (id) {
+        id -> Integer,
+        account_address -> Text,
+        vault_address -> Text,
+        version -> Integer,
+        epoch -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     vaults (id) {
         id -> Integer,
@@ -137,6 +250,8 @@ diesel::table! {
         token_symbol -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        decimals -> Nullable<Integer>,
+        is_frozen -> Bool,
     }
 }
 
@@ -149,13 +264,22 @@ diesel::joinable!(vaults -> accounts (account_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     accounts,
+    audited_balances,
     auth_status,
     config,
+    contacts,
+    database_encryption,
+    imported_keys,
+    imported_transactions,
     key_manager_states,
     non_fungible_tokens,
     outputs,
     proofs,
+    published_templates,
+    scheduled_transactions,
     substates,
+    transaction_failures,
     transactions,
+    vault_history,
     vaults,
 );
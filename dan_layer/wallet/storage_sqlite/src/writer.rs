@@ -13,16 +13,19 @@ use diesel::{OptionalExtension, QueryDsl, RunQueryDsl, SqliteConnection};
 use log::*;
 use serde::Serialize;
 use tari_bor::json_encoding::CborValueJsonSerializeWrapper;
-use tari_common_types::types::{Commitment, PublicKey};
-use tari_dan_common_types::SubstateRequirement;
+use tari_common_types::types::{Commitment, PrivateKey, PublicKey};
+use tari_dan_common_types::{Epoch, SubstateRequirement};
 use tari_dan_storage::consensus_models::QuorumCertificate;
 use tari_dan_wallet_sdk::{
     models::{
+        AuditedVaultBalance,
         ConfidentialOutputModel,
         ConfidentialProofId,
+        Contact,
         NewAccountInfo,
         NonFungibleToken,
         OutputStatus,
+        ScheduledTransaction,
         SubstateModel,
         TransactionStatus,
         VaultModel,
@@ -37,6 +40,7 @@ use tari_utilities::hex::Hex;
 
 use crate::{
     diesel::ExpressionMethods,
+    encryption::{DatabaseCipher, Encryption},
     models::{self},
     reader::ReadTransaction,
     serialization::serialize_json,
@@ -51,12 +55,123 @@ pub struct WriteTransaction<'a> {
 }
 
 impl<'a> WriteTransaction<'a> {
-    pub fn new(connection: MutexGuard<'a, SqliteConnection>) -> Self {
+    pub fn new(connection: MutexGuard<'a, SqliteConnection>, encryption: Encryption) -> Self {
         Self {
-            transaction: ReadTransaction::new(connection),
+            transaction: ReadTransaction::new(connection, encryption),
         }
     }
 
+    /// Enables encryption with the given cipher and encrypts all existing plaintext values
+    pub(super) fn database_encryption_enable(
+        &mut self,
+        salt: &str,
+        cipher: &DatabaseCipher,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::{
+            accounts,
+            config,
+            database_encryption,
+            imported_keys,
+            scheduled_transactions,
+            transaction_failures,
+            transactions,
+        };
+
+        diesel::insert_into(database_encryption::table)
+            .values((
+                database_encryption::salt.eq(salt),
+                database_encryption::password_check.eq(cipher.create_password_check()?),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+
+        let configs = config::table
+            .select((config::id, config::value))
+            .load::<(i32, String)>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for (id, value) in configs {
+            diesel::update(config::table)
+                .set(config::value.eq(cipher.encrypt_if_plaintext(value)?))
+                .filter(config::id.eq(id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        let rows = transactions::table
+            .load::<models::Transaction>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for row in rows {
+            diesel::update(transactions::table)
+                .set((
+                    transactions::instructions.eq(cipher.encrypt_if_plaintext(row.instructions)?),
+                    transactions::signatures.eq(cipher.encrypt_if_plaintext(row.signatures)?),
+                    transactions::fee_instructions.eq(cipher.encrypt_if_plaintext(row.fee_instructions)?),
+                    transactions::inputs.eq(cipher.encrypt_if_plaintext(row.inputs)?),
+                    transactions::result.eq(row.result.map(|v| cipher.encrypt_if_plaintext(v)).transpose()?),
+                    transactions::qcs.eq(row.qcs.map(|v| cipher.encrypt_if_plaintext(v)).transpose()?),
+                    transactions::required_substates.eq(cipher.encrypt_if_plaintext(row.required_substates)?),
+                    transactions::new_account_info.eq(row
+                        .new_account_info
+                        .map(|v| cipher.encrypt_if_plaintext(v))
+                        .transpose()?),
+                ))
+                .filter(transactions::id.eq(row.id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        let failures = transaction_failures::table
+            .select((transaction_failures::id, transaction_failures::inputs))
+            .load::<(i32, String)>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for (id, inputs) in failures {
+            diesel::update(transaction_failures::table)
+                .set(transaction_failures::inputs.eq(cipher.encrypt_if_plaintext(inputs)?))
+                .filter(transaction_failures::id.eq(id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        let scheduled = scheduled_transactions::table
+            .select((scheduled_transactions::id, scheduled_transactions::instructions))
+            .load::<(i32, String)>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for (id, instructions) in scheduled {
+            diesel::update(scheduled_transactions::table)
+                .set(scheduled_transactions::instructions.eq(cipher.encrypt_if_plaintext(instructions)?))
+                .filter(scheduled_transactions::id.eq(id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        let view_keys = accounts::table
+            .select((accounts::id, accounts::view_key))
+            .filter(accounts::view_key.is_not_null())
+            .load::<(i32, Option<String>)>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for (id, view_key) in view_keys {
+            diesel::update(accounts::table)
+                .set(accounts::view_key.eq(view_key.map(|v| cipher.encrypt_if_plaintext(v)).transpose()?))
+                .filter(accounts::id.eq(id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        let secret_keys = imported_keys::table
+            .select((imported_keys::id, imported_keys::secret_key))
+            .load::<(i32, String)>(self.connection())
+            .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        for (id, secret_key) in secret_keys {
+            diesel::update(imported_keys::table)
+                .set(imported_keys::secret_key.eq(cipher.encrypt_if_plaintext(secret_key)?))
+                .filter(imported_keys::id.eq(id))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("database_encryption_enable", e))?;
+        }
+
+        Ok(())
+    }
+
     fn get_proof(&mut self, proof_id: ConfidentialProofId) -> Result<models::Proof, WalletStorageError> {
         use crate::schema::proofs;
 
@@ -225,6 +340,29 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         Ok(())
     }
 
+    fn imported_keys_insert(
+        &mut self,
+        public_key: &PublicKey,
+        secret_key: &PrivateKey,
+    ) -> Result<u64, WalletStorageError> {
+        use crate::schema::imported_keys;
+
+        let secret_key = self.encryption().serialize_json(secret_key)?;
+        diesel::insert_into(imported_keys::table)
+            .values((
+                imported_keys::public_key.eq(public_key.to_hex()),
+                imported_keys::secret_key.eq(secret_key),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("imported_keys_insert", e))?;
+
+        let last_inserted_id: i32 =
+            diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>("last_insert_rowid()"))
+                .get_result(self.connection())
+                .map_err(|e| WalletStorageError::general("imported_keys_insert", e))?;
+        Ok(last_inserted_id as u64)
+    }
+
     // -------------------------------- Config -------------------------------- //
 
     fn config_set<T: Serialize>(&mut self, key: &str, value: &T, is_encrypted: bool) -> Result<(), WalletStorageError> {
@@ -241,7 +379,7 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         if exists {
             diesel::update(config::table)
                 .set((
-                    config::value.eq(serialize_json(value)?),
+                    config::value.eq(self.encryption().serialize_json(value)?),
                     config::is_encrypted.eq(is_encrypted),
                     config::updated_at.eq(diesel::dsl::now),
                 ))
@@ -252,7 +390,7 @@ impl WalletStoreWriter for WriteTransaction<'_> {
             diesel::insert_into(config::table)
                 .values((
                     config::key.eq(key),
-                    config::value.eq(serialize_json(value)?),
+                    config::value.eq(self.encryption().serialize_json(value)?),
                     config::is_encrypted.eq(is_encrypted),
                 ))
                 .execute(self.connection())
@@ -275,13 +413,15 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         diesel::insert_into(transactions::table)
             .values((
                 transactions::hash.eq(transaction.id().to_string()),
-                transactions::fee_instructions.eq(serialize_json(transaction.fee_instructions())?),
-                transactions::instructions.eq(serialize_json(transaction.instructions())?),
-                transactions::signatures.eq(serialize_json(transaction.signatures())?),
-                transactions::inputs.eq(serialize_json(transaction.inputs())?),
+                transactions::fee_instructions.eq(self.encryption().serialize_json(transaction.fee_instructions())?),
+                transactions::instructions.eq(self.encryption().serialize_json(transaction.instructions())?),
+                transactions::signatures.eq(self.encryption().serialize_json(transaction.signatures())?),
+                transactions::inputs.eq(self.encryption().serialize_json(transaction.inputs())?),
                 transactions::status.eq(TransactionStatus::New.as_key_str()),
-                transactions::required_substates.eq(serialize_json(&required_substates)?),
-                transactions::new_account_info.eq(new_account_info.map(serialize_json).transpose()?),
+                transactions::required_substates.eq(self.encryption().serialize_json(&required_substates)?),
+                transactions::new_account_info.eq(new_account_info
+                    .map(|info| self.encryption().serialize_json(info))
+                    .transpose()?),
                 transactions::dry_run.eq(is_dry_run),
             ))
             .execute(self.connection())
@@ -304,10 +444,12 @@ impl WalletStoreWriter for WriteTransaction<'_> {
 
         let num_rows = diesel::update(transactions::table)
             .set((
-                transactions::result.eq(result.map(serialize_json).transpose()?),
+                transactions::result.eq(result
+                    .map(|result| self.encryption().serialize_json(result))
+                    .transpose()?),
                 transactions::status.eq(new_status.as_key_str()),
                 transactions::final_fee.eq(final_fee.map(|v| v.value())),
-                transactions::qcs.eq(qcs.map(serialize_json).transpose()?),
+                transactions::qcs.eq(qcs.map(|qcs| self.encryption().serialize_json(qcs)).transpose()?),
                 transactions::executed_time_ms
                     .eq(execution_time.map(|v| i64::try_from(v.as_millis()).unwrap_or(i64::MAX))),
                 transactions::finalized_time_ms
@@ -465,6 +607,62 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         Ok(())
     }
 
+    fn accounts_insert_watch_only(
+        &mut self,
+        account_name: Option<&str>,
+        address: &SubstateId,
+        owner_public_key: &PublicKey,
+        view_key: Option<&PrivateKey>,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::accounts;
+
+        let view_key = view_key.map(|k| self.encryption().serialize_json(k)).transpose()?;
+
+        diesel::insert_into(accounts::table)
+            .values((
+                accounts::name.eq(account_name),
+                accounts::address.eq(address.to_string()),
+                accounts::owner_key_index.eq(0i64),
+                accounts::is_default.eq(false),
+                accounts::watch_only_public_key.eq(owner_public_key.to_hex()),
+                accounts::view_key.eq(view_key),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("accounts_insert_watch_only", e))?;
+
+        Ok(())
+    }
+
+    fn accounts_insert_imported(
+        &mut self,
+        account_name: Option<&str>,
+        address: &SubstateId,
+        imported_key_index: u64,
+        is_default: bool,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::accounts;
+
+        if is_default {
+            diesel::update(accounts::table)
+                .set(accounts::is_default.eq(false))
+                .execute(self.connection())
+                .map_err(|e| WalletStorageError::general("accounts_insert_imported clear previous default", e))?;
+        }
+
+        diesel::insert_into(accounts::table)
+            .values((
+                accounts::name.eq(account_name),
+                accounts::address.eq(address.to_string()),
+                accounts::owner_key_index.eq(imported_key_index as i64),
+                accounts::is_default.eq(is_default),
+                accounts::is_imported.eq(true),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("accounts_insert_imported", e))?;
+
+        Ok(())
+    }
+
     fn accounts_update(&mut self, address: &SubstateId, new_name: Option<&str>) -> Result<(), WalletStorageError> {
         use crate::schema::accounts;
 
@@ -504,6 +702,7 @@ impl WalletStoreWriter for WriteTransaction<'_> {
             vaults::resource_address.eq(vault.resource_address.to_string()),
             vaults::resource_type.eq(format!("{:?}", vault.resource_type)),
             vaults::token_symbol.eq(vault.token_symbol),
+            vaults::decimals.eq(vault.decimals.map(i32::from)),
         );
         diesel::insert_into(vaults::table)
             .values(values)
@@ -543,6 +742,26 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         Ok(())
     }
 
+    fn vaults_set_frozen(&mut self, vault_address: &SubstateId, is_frozen: bool) -> Result<(), WalletStorageError> {
+        use crate::schema::vaults;
+
+        let num_rows = diesel::update(vaults::table)
+            .set(vaults::is_frozen.eq(is_frozen))
+            .filter(vaults::address.eq(vault_address.to_string()))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("vaults_set_frozen", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "vaults_set_frozen",
+                entity: "vault".to_string(),
+                key: vault_address.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
     fn vaults_lock_revealed_funds(
         &mut self,
         proof_id: ConfidentialProofId,
@@ -642,6 +861,31 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         Ok(())
     }
 
+    // -------------------------------- Vault history -------------------------------- //
+
+    fn vault_history_insert(
+        &mut self,
+        account_address: &SubstateId,
+        vault_address: &VersionedSubstateId,
+        epoch: Epoch,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::vault_history;
+
+        diesel::insert_into(vault_history::table)
+            .values((
+                vault_history::account_address.eq(account_address.to_string()),
+                vault_history::vault_address.eq(vault_address.substate_id.to_string()),
+                vault_history::version.eq(vault_address.version as i32),
+                vault_history::epoch.eq(epoch.as_u64() as i64),
+            ))
+            .on_conflict((vault_history::vault_address, vault_history::version))
+            .do_nothing()
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("vault_history_insert", e))?;
+
+        Ok(())
+    }
+
     // -------------------------------- Outputs -------------------------------- //
 
     fn outputs_lock_smallest_amount(
@@ -914,6 +1158,229 @@ impl WalletStoreWriter for WriteTransaction<'_> {
         );
         Ok(())
     }
+
+    // -------------------------------- Audited balances -------------------------------- //
+    fn audited_balances_upsert(&mut self, balance: &AuditedVaultBalance) -> Result<(), WalletStorageError> {
+        use crate::schema::audited_balances;
+
+        let values = (
+            audited_balances::resource_address.eq(balance.resource_address.to_string()),
+            audited_balances::view_key_index.eq(balance.view_key_index as i64),
+            audited_balances::vault_address.eq(balance.vault_id.to_string()),
+            audited_balances::owner_address.eq(balance.owner.map(|owner| owner.to_string())),
+            audited_balances::balance.eq(balance.balance.value()),
+            audited_balances::num_outputs.eq(balance.num_outputs as i32),
+            audited_balances::num_unresolved.eq(balance.num_unresolved as i32),
+            audited_balances::vault_version.eq(balance.vault_version as i32),
+        );
+
+        diesel::insert_into(audited_balances::table)
+            .values(values)
+            .on_conflict((audited_balances::vault_address, audited_balances::view_key_index))
+            .do_update()
+            .set((
+                audited_balances::owner_address.eq(balance.owner.map(|owner| owner.to_string())),
+                audited_balances::balance.eq(balance.balance.value()),
+                audited_balances::num_outputs.eq(balance.num_outputs as i32),
+                audited_balances::num_unresolved.eq(balance.num_unresolved as i32),
+                audited_balances::vault_version.eq(balance.vault_version as i32),
+                audited_balances::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("audited_balances_upsert", e))?;
+
+        Ok(())
+    }
+
+    // -------------------------------- Published templates -------------------------------- //
+    fn published_templates_insert(
+        &mut self,
+        template_address: &TemplateAddress,
+        author_account: &SubstateId,
+        transaction_id: TransactionId,
+        binary_size: u64,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::published_templates;
+
+        let values = (
+            published_templates::template_address.eq(template_address.to_string()),
+            published_templates::author_account.eq(author_account.to_string()),
+            published_templates::transaction_id.eq(transaction_id.to_string()),
+            published_templates::binary_size.eq(binary_size as i64),
+        );
+
+        diesel::insert_into(published_templates::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("published_templates_insert", e))?;
+
+        Ok(())
+    }
+
+    // -------------------------------- Contacts -------------------------------- //
+    fn contacts_insert(&mut self, contact: &Contact) -> Result<(), WalletStorageError> {
+        use crate::schema::contacts;
+
+        let values = (
+            contacts::name.eq(&contact.name),
+            contacts::component_address.eq(contact.component_address.map(|a| a.to_string())),
+            contacts::public_key.eq(contact.public_key.as_ref().map(|pk| pk.to_hex())),
+        );
+
+        diesel::insert_into(contacts::table)
+            .values(values)
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("contacts_insert", e))?;
+
+        Ok(())
+    }
+
+    fn contacts_delete(&mut self, name: &str) -> Result<(), WalletStorageError> {
+        use crate::schema::contacts;
+
+        let num_rows = diesel::delete(contacts::table)
+            .filter(contacts::name.eq(name))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("contacts_delete", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "contacts_delete",
+                entity: "contact".to_string(),
+                key: name.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------- Transaction failures -------------------------------- //
+    fn transaction_failures_insert(
+        &mut self,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        reason: &str,
+        inputs: &[SubstateRequirement],
+        attempt: u32,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::transaction_failures;
+
+        diesel::insert_into(transaction_failures::table)
+            .values((
+                transaction_failures::transaction_id.eq(transaction_id.to_string()),
+                transaction_failures::status.eq(status.as_key_str()),
+                transaction_failures::reason.eq(reason),
+                transaction_failures::inputs.eq(self.encryption().serialize_json(inputs)?),
+                transaction_failures::attempt.eq(attempt as i32),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_failures_insert", e))?;
+
+        Ok(())
+    }
+
+    fn transaction_failures_set_retried(
+        &mut self,
+        transaction_id: TransactionId,
+        retried_as: TransactionId,
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::transaction_failures;
+
+        let num_rows = diesel::update(transaction_failures::table)
+            .set(transaction_failures::retried_as.eq(retried_as.to_string()))
+            .filter(transaction_failures::transaction_id.eq(transaction_id.to_string()))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("transaction_failures_set_retried", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "transaction_failures_set_retried",
+                entity: "transaction_failure".to_string(),
+                key: transaction_id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    // -------------------------------- Imported transactions -------------------------------- //
+    fn imported_transactions_insert(
+        &mut self,
+        transaction_id: TransactionId,
+        status: TransactionStatus,
+        final_fee: Option<Amount>,
+        accounts: &[SubstateId],
+    ) -> Result<(), WalletStorageError> {
+        use crate::schema::imported_transactions;
+
+        diesel::insert_into(imported_transactions::table)
+            .values((
+                imported_transactions::transaction_id.eq(transaction_id.to_string()),
+                imported_transactions::status.eq(status.as_key_str()),
+                imported_transactions::final_fee.eq(final_fee.map(|v| v.value())),
+                imported_transactions::accounts.eq(serialize_json(accounts)?),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("imported_transactions_insert", e))?;
+
+        Ok(())
+    }
+
+    // -------------------------------- Scheduled transactions -------------------------------- //
+    fn scheduled_transactions_insert(&mut self, scheduled: &ScheduledTransaction) -> Result<u64, WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        diesel::insert_into(scheduled_transactions::table)
+            .values((
+                scheduled_transactions::name.eq(&scheduled.name),
+                scheduled_transactions::schedule.eq(serialize_json(&scheduled.schedule)?),
+                scheduled_transactions::instructions.eq(self.encryption().serialize_json(&scheduled.instructions)?),
+                scheduled_transactions::fee_account.eq(scheduled.fee_account.to_string()),
+                scheduled_transactions::max_fee.eq(scheduled.max_fee.value()),
+                scheduled_transactions::max_runs.eq(scheduled.max_runs.map(|n| n as i64)),
+                scheduled_transactions::num_runs.eq(scheduled.num_runs as i64),
+                scheduled_transactions::status.eq(scheduled.status.as_key_str()),
+                scheduled_transactions::next_run_at.eq(scheduled.next_run_at),
+                scheduled_transactions::next_run_epoch.eq(scheduled.next_run_epoch.map(|e| e.as_u64() as i64)),
+                scheduled_transactions::last_transaction_id.eq(scheduled.last_transaction_id.map(|id| id.to_string())),
+                scheduled_transactions::last_error.eq(scheduled.last_error.as_deref()),
+            ))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_insert", e))?;
+        let last_inserted_id: i32 =
+            diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>("last_insert_rowid()"))
+                .get_result(self.connection())
+                .map_err(|e| WalletStorageError::general("scheduled_transactions_insert", e))?;
+        Ok(last_inserted_id as u64)
+    }
+
+    fn scheduled_transactions_update(&mut self, scheduled: &ScheduledTransaction) -> Result<(), WalletStorageError> {
+        use crate::schema::scheduled_transactions;
+
+        let num_rows = diesel::update(scheduled_transactions::table)
+            .set((
+                scheduled_transactions::num_runs.eq(scheduled.num_runs as i64),
+                scheduled_transactions::status.eq(scheduled.status.as_key_str()),
+                scheduled_transactions::next_run_at.eq(scheduled.next_run_at),
+                scheduled_transactions::next_run_epoch.eq(scheduled.next_run_epoch.map(|e| e.as_u64() as i64)),
+                scheduled_transactions::last_transaction_id.eq(scheduled.last_transaction_id.map(|id| id.to_string())),
+                scheduled_transactions::last_error.eq(scheduled.last_error.as_deref()),
+                scheduled_transactions::updated_at.eq(diesel::dsl::now),
+            ))
+            .filter(scheduled_transactions::id.eq(scheduled.id as i32))
+            .execute(self.connection())
+            .map_err(|e| WalletStorageError::general("scheduled_transactions_update", e))?;
+
+        if num_rows == 0 {
+            return Err(WalletStorageError::NotFound {
+                operation: "scheduled_transactions_update",
+                entity: "scheduled_transaction".to_string(),
+                key: scheduled.id.to_string(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
 impl Drop for WriteTransaction<'_> {
@@ -3,9 +3,18 @@
 
 use std::str::FromStr;
 
-use tari_dan_wallet_sdk::storage::{WalletStore, WalletStoreReader, WalletStoreWriter};
+use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_dan_wallet_sdk::{
+    apis::accounts::{AccountsApi, AccountsApiError},
+    models::AccountKind,
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
 use tari_engine_types::substate::SubstateId;
+use tari_template_lib::{
+    models::Amount,
+    prelude::{ResourceType, CONFIDENTIAL_TARI_RESOURCE_ADDRESS},
+};
 
 #[test]
 fn update_account() {
@@ -22,3 +31,94 @@ fn update_account() {
     let account = tx.accounts_get_by_name("foo").unwrap();
     assert_eq!(account.name.as_deref(), Some("foo"));
 }
+
+#[test]
+fn get_fee_funded_accounts() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let accounts_api = AccountsApi::new(&db);
+
+    let funded =
+        SubstateId::from_str("component_91bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let unfunded =
+        SubstateId::from_str("component_81bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let no_vault =
+        SubstateId::from_str("component_71bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    accounts_api.add_account(Some("funded"), &funded, 0, true).unwrap();
+    accounts_api.add_account(Some("unfunded"), &unfunded, 1, false).unwrap();
+    accounts_api.add_account(Some("no_vault"), &no_vault, 2, false).unwrap();
+
+    let funded_vault =
+        SubstateId::from_str("vault_91bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let unfunded_vault =
+        SubstateId::from_str("vault_81bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    for (account, vault) in [(&funded, &funded_vault), (&unfunded, &unfunded_vault)] {
+        accounts_api
+            .add_vault(
+                account.clone(),
+                vault.clone(),
+                CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
+                ResourceType::Confidential,
+                Some("XTR".to_string()),
+                Some(6),
+            )
+            .unwrap();
+    }
+    accounts_api
+        .update_vault_balance(&funded_vault, Amount(1000), Amount::zero())
+        .unwrap();
+
+    let accounts = accounts_api.get_fee_funded_accounts().unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].address, funded);
+}
+
+#[test]
+fn add_imported_account() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let accounts_api = AccountsApi::new(&db);
+
+    let address =
+        SubstateId::from_str("component_91bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    accounts_api
+        .add_imported_account(Some("imported"), &address, 5, true)
+        .unwrap();
+
+    let account = accounts_api.get_account_by_name("imported").unwrap();
+    assert_eq!(account.kind, AccountKind::Imported);
+    assert_eq!(account.key_index, 5);
+    assert!(account.is_default);
+    assert!(!account.is_watch_only());
+}
+
+#[test]
+fn add_watch_only_account() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let accounts_api = AccountsApi::new(&db);
+
+    let owned =
+        SubstateId::from_str("component_91bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let watched =
+        SubstateId::from_str("component_81bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    accounts_api.add_account(Some("owned"), &owned, 0, true).unwrap();
+    let owner_public_key = PublicKey::default();
+    let view_key = PrivateKey::default();
+    accounts_api
+        .add_watch_only_account(Some("watched"), &watched, &owner_public_key, Some(&view_key))
+        .unwrap();
+
+    let account = accounts_api.get_account_by_name("watched").unwrap();
+    assert!(account.is_watch_only());
+    assert_eq!(account.kind, AccountKind::WatchOnly { owner_public_key });
+    assert_eq!(accounts_api.get_view_key(&watched).unwrap(), Some(view_key));
+    assert!(accounts_api.get_view_key(&owned).unwrap().is_none());
+
+    let err = accounts_api
+        .add_watch_only_account(Some("other"), &watched, &PublicKey::default(), None)
+        .unwrap_err();
+    assert!(matches!(err, AccountsApiError::AccountAlreadyExists { .. }));
+    let err = accounts_api.set_default_account(&watched).unwrap_err();
+    assert!(matches!(err, AccountsApiError::WatchOnlyAccount { .. }));
+}
@@ -0,0 +1,53 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::{
+    models::AuditedVaultBalance,
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_template_lib::models::{Amount, ResourceAddress, VaultId};
+
+#[test]
+fn upsert_and_get_audited_balances() {
+    let resource_address: ResourceAddress = "resource_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff"
+        .parse()
+        .unwrap();
+    let vault_id: VaultId = "vault_d9e4a7ce7dbaa73ce10aabf309dd702054756a813f454ef13564f298ffffffff"
+        .parse()
+        .unwrap();
+
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    let mut balance = AuditedVaultBalance {
+        resource_address,
+        view_key_index: 0,
+        vault_id,
+        owner: None,
+        balance: Amount(100),
+        num_outputs: 2,
+        num_unresolved: 1,
+        vault_version: 0,
+    };
+    db.with_write_tx(|tx| tx.audited_balances_upsert(&balance)).unwrap();
+
+    balance.balance = Amount(150);
+    balance.num_unresolved = 0;
+    balance.vault_version = 1;
+    db.with_write_tx(|tx| tx.audited_balances_upsert(&balance)).unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    let balances = tx.audited_balances_get_by_resource(&resource_address, 0).unwrap();
+    assert_eq!(balances.len(), 1);
+    assert_eq!(balances[0].vault_id, vault_id);
+    assert_eq!(balances[0].balance, Amount(150));
+    assert_eq!(balances[0].vault_version, 1);
+    assert!(balances[0].is_fully_resolved());
+
+    let balances = tx.audited_balances_get_by_resource(&resource_address, 1).unwrap();
+    assert!(balances.is_empty());
+
+    let resources = tx.audited_balances_get_audited_resources().unwrap();
+    assert_eq!(resources, vec![(resource_address, 0)]);
+}
@@ -0,0 +1,45 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_common_types::types::PublicKey;
+use tari_dan_common_types::optional::Optional;
+use tari_dan_wallet_sdk::{
+    models::Contact,
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_template_lib::models::{ComponentAddress, ObjectKey};
+
+#[test]
+fn insert_get_and_delete_contacts() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let alice = Contact {
+        name: "alice".to_string(),
+        component_address: Some(ComponentAddress::from_array([1u8; ObjectKey::LENGTH])),
+        public_key: None,
+    };
+    let bob = Contact {
+        name: "bob".to_string(),
+        component_address: None,
+        public_key: Some(PublicKey::default()),
+    };
+
+    let mut tx = db.create_write_tx().unwrap();
+    tx.contacts_insert(&alice).unwrap();
+    tx.contacts_insert(&bob).unwrap();
+    assert!(tx.contacts_insert(&alice).is_err());
+    tx.commit().unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    assert_eq!(tx.contacts_get_by_name("alice").unwrap(), alice);
+    assert_eq!(tx.contacts_get_by_name("bob").unwrap(), bob);
+    assert_eq!(tx.contacts_get_all().unwrap(), vec![alice, bob]);
+    drop(tx);
+
+    let mut tx = db.create_write_tx().unwrap();
+    tx.contacts_delete("alice").unwrap();
+    assert!(tx.contacts_delete("alice").optional().unwrap().is_none());
+    assert!(tx.contacts_get_by_name("alice").optional().unwrap().is_none());
+    tx.commit().unwrap();
+}
@@ -0,0 +1,47 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::storage::{WalletStorageError, WalletStore, WalletStoreReader, WalletStoreWriter};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_utilities::SafePassword;
+
+fn set_value(db: &SqliteWalletStore, key: &str, value: u32) -> Result<(), WalletStorageError> {
+    let mut tx = db.create_write_tx()?;
+    tx.config_set(key, &value, false)?;
+    tx.commit()
+}
+
+fn get_value(db: &SqliteWalletStore, key: &str) -> Result<u32, WalletStorageError> {
+    let mut tx = db.create_read_tx()?;
+    Ok(tx.config_get::<u32>(key)?.value)
+}
+
+#[test]
+fn it_encrypts_existing_values_and_locks() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    set_value(&db, "before", 1).unwrap();
+    assert!(!db.is_encrypted());
+    assert!(!db.is_locked());
+
+    let password = SafePassword::from("correct horse battery staple".to_string());
+    db.unlock(&password).unwrap();
+    assert!(db.is_encrypted());
+    assert!(!db.is_locked());
+    set_value(&db, "after", 2).unwrap();
+    assert_eq!(get_value(&db, "before").unwrap(), 1);
+    assert_eq!(get_value(&db, "after").unwrap(), 2);
+
+    db.lock();
+    assert!(db.is_locked());
+    assert!(matches!(get_value(&db, "before"), Err(WalletStorageError::Locked)));
+    assert!(matches!(set_value(&db, "locked", 3), Err(WalletStorageError::Locked)));
+
+    let wrong = SafePassword::from("wrong".to_string());
+    assert!(matches!(db.unlock(&wrong), Err(WalletStorageError::InvalidPassword)));
+    assert!(db.is_locked());
+
+    db.unlock(&password).unwrap();
+    assert_eq!(get_value(&db, "before").unwrap(), 1);
+    assert_eq!(get_value(&db, "after").unwrap(), 2);
+}
@@ -0,0 +1,79 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{str::FromStr, time::Duration};
+
+use tari_dan_common_types::optional::Optional;
+use tari_dan_wallet_sdk::{
+    apis::garbage_collection::GarbageCollectionApi,
+    models::VersionedSubstateId,
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+
+#[test]
+fn removes_substates_unreachable_from_accounts() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    let account =
+        SubstateId::from_str("component_91bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let account_child =
+        SubstateId::from_str("component_81bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let unreachable =
+        SubstateId::from_str("component_71bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+    let unreachable_child =
+        SubstateId::from_str("component_61bef6af37bfb39b20260275c37a9e8acfc0517127284cd8f05944c8ffffffff").unwrap();
+
+    let hash = TransactionId::default();
+    let mut tx = db.create_write_tx().unwrap();
+    tx.accounts_insert(Some("test"), &account, 0, true).unwrap();
+    for root in [&account, &unreachable] {
+        tx.substates_upsert_root(
+            hash,
+            VersionedSubstateId {
+                substate_id: root.clone(),
+                version: 0,
+            },
+            None,
+            None,
+        )
+        .unwrap();
+    }
+    for (parent, child) in [(&account, &account_child), (&unreachable, &unreachable_child)] {
+        tx.substates_upsert_child(hash, parent.clone(), VersionedSubstateId {
+            substate_id: child.clone(),
+            version: 0,
+        })
+        .unwrap();
+    }
+    tx.commit().unwrap();
+
+    let gc = GarbageCollectionApi::new(&db);
+    let report = gc.collect_garbage(Duration::from_secs(60 * 60), true).unwrap();
+    assert!(report.is_dry_run);
+    assert_eq!(report.removed_substates.len(), 2);
+    assert!(report.removed_substates.contains(&unreachable));
+    assert!(report.removed_substates.contains(&unreachable_child));
+    assert!(report.removed_proofs.is_empty());
+    // Nothing is removed in a dry run
+    let mut tx = db.create_read_tx().unwrap();
+    assert!(tx.substates_get(&unreachable).optional().unwrap().is_some());
+    drop(tx);
+
+    let report = gc.collect_garbage(Duration::from_secs(60 * 60), false).unwrap();
+    assert!(!report.is_dry_run);
+    assert_eq!(report.removed_substates.len(), 2);
+
+    let mut tx = db.create_read_tx().unwrap();
+    assert!(tx.substates_get(&account).optional().unwrap().is_some());
+    assert!(tx.substates_get(&account_child).optional().unwrap().is_some());
+    assert!(tx.substates_get(&unreachable).optional().unwrap().is_none());
+    assert!(tx.substates_get(&unreachable_child).optional().unwrap().is_none());
+    drop(tx);
+
+    let report = gc.collect_garbage(Duration::from_secs(60 * 60), false).unwrap();
+    assert!(report.is_empty());
+}
@@ -1,6 +1,7 @@
 //   Copyright 2023 The Tari Project
 //   SPDX-License-Identifier: BSD-3-Clause
 
+use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_dan_common_types::optional::Optional;
 use tari_dan_wallet_sdk::storage::{WalletStore, WalletStoreReader, WalletStoreWriter};
 use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
@@ -28,3 +29,20 @@ fn get_and_set_branch_index() {
     let index = tx.key_manager_get_active_index("another").unwrap();
     assert_eq!(index, 2);
 }
+
+#[test]
+fn insert_and_get_imported_key() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let public_key = PublicKey::default();
+    let secret_key = PrivateKey::default();
+    let mut tx = db.create_write_tx().unwrap();
+    assert!(tx.imported_keys_get_index(&public_key).optional().unwrap().is_none());
+    let index = tx.imported_keys_insert(&public_key, &secret_key).unwrap();
+    tx.commit().unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    assert_eq!(tx.imported_keys_get_index(&public_key).unwrap(), index);
+    assert_eq!(tx.imported_keys_get(index).unwrap(), secret_key);
+    assert!(tx.imported_keys_get(index + 1).optional().unwrap().is_none());
+}
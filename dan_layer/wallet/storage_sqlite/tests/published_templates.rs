@@ -0,0 +1,37 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_wallet_sdk::storage::{WalletStore, WalletStoreReader, WalletStoreWriter};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::{substate::SubstateId, TemplateAddress};
+use tari_transaction::TransactionId;
+
+#[test]
+fn insert_and_get_published_templates() {
+    let template_address = TemplateAddress::from_array([1u8; 32]);
+    let author_account: SubstateId = "component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff"
+        .parse()
+        .unwrap();
+
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    db.with_write_tx(|tx| {
+        tx.published_templates_insert(&template_address, &author_account, TransactionId::default(), 1024)
+    })
+    .unwrap();
+
+    // The same template cannot be recorded twice
+    db.with_write_tx(|tx| {
+        tx.published_templates_insert(&template_address, &author_account, TransactionId::default(), 1024)
+    })
+    .unwrap_err();
+
+    let mut tx = db.create_read_tx().unwrap();
+    let templates = tx.published_templates_get_all().unwrap();
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].template_address, template_address);
+    assert_eq!(templates[0].author_account, author_account);
+    assert_eq!(templates[0].transaction_id, TransactionId::default());
+    assert_eq!(templates[0].binary_size, 1024);
+}
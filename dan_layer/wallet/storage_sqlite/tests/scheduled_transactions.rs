@@ -0,0 +1,83 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use chrono::NaiveDateTime;
+use tari_dan_common_types::Epoch;
+use tari_dan_wallet_sdk::{
+    models::{ScheduledTransaction, ScheduledTransactionStatus, TransactionSchedule},
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_template_lib::models::{Amount, ComponentAddress, ObjectKey};
+use tari_transaction::TransactionId;
+
+fn new_scheduled_transaction(name: &str, schedule: TransactionSchedule) -> ScheduledTransaction {
+    let now = chrono::Utc::now().naive_utc();
+    ScheduledTransaction {
+        id: 0,
+        name: name.to_string(),
+        next_run_at: schedule.next_time_after(now),
+        next_run_epoch: schedule.first_epoch(),
+        schedule,
+        instructions: vec![],
+        fee_account: ComponentAddress::from_array([1u8; ObjectKey::LENGTH]),
+        max_fee: Amount(1000),
+        max_runs: Some(2),
+        num_runs: 0,
+        status: ScheduledTransactionStatus::Active,
+        last_transaction_id: None,
+        last_error: None,
+        created_at: NaiveDateTime::default(),
+    }
+}
+
+#[test]
+fn insert_update_and_filter_scheduled_transactions() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    let mut tx = db.create_write_tx().unwrap();
+    let interval_id = tx
+        .scheduled_transactions_insert(&new_scheduled_transaction("interval", TransactionSchedule::Interval {
+            seconds: 60,
+        }))
+        .unwrap();
+    let epoch_id = tx
+        .scheduled_transactions_insert(&new_scheduled_transaction("epoch", TransactionSchedule::Epoch {
+            epoch: Epoch(10),
+            repeat_every: None,
+        }))
+        .unwrap();
+    tx.commit().unwrap();
+    assert_ne!(interval_id, epoch_id);
+
+    let mut tx = db.create_read_tx().unwrap();
+    let mut scheduled = tx.scheduled_transactions_get(epoch_id).unwrap();
+    assert_eq!(scheduled.id, epoch_id);
+    assert_eq!(scheduled.name, "epoch");
+    assert_eq!(scheduled.next_run_epoch, Some(Epoch(10)));
+    assert_eq!(scheduled.next_run_at, None);
+    assert_eq!(scheduled.max_fee, Amount(1000));
+    drop(tx);
+
+    let transaction_id = TransactionId::new([2u8; 32]);
+    scheduled.record_run(chrono::Utc::now().naive_utc(), Some(Epoch(10)), Ok(transaction_id));
+    assert_eq!(scheduled.status, ScheduledTransactionStatus::Completed);
+    let mut tx = db.create_write_tx().unwrap();
+    tx.scheduled_transactions_update(&scheduled).unwrap();
+    tx.commit().unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    let scheduled = tx.scheduled_transactions_get(epoch_id).unwrap();
+    assert_eq!(scheduled.num_runs, 1);
+    assert_eq!(scheduled.last_transaction_id, Some(transaction_id));
+    assert_eq!(scheduled.status, ScheduledTransactionStatus::Completed);
+
+    let active = tx
+        .scheduled_transactions_get_all(Some(ScheduledTransactionStatus::Active))
+        .unwrap();
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].id, interval_id);
+    assert_eq!(active[0].schedule, TransactionSchedule::Interval { seconds: 60 });
+    assert_eq!(tx.scheduled_transactions_get_all(None).unwrap().len(), 2);
+}
@@ -2,7 +2,7 @@
 //   SPDX-License-Identifier: BSD-3-Clause
 
 use tari_common_types::types::PrivateKey;
-use tari_dan_common_types::optional::Optional;
+use tari_dan_common_types::{optional::Optional, Epoch};
 use tari_dan_wallet_sdk::{
     models::TransactionStatus,
     storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
@@ -31,3 +31,37 @@ fn get_and_insert_transaction() {
     assert_eq!(transaction.id(), returned.transaction.id());
     assert_eq!(returned.status, TransactionStatus::default());
 }
+
+#[test]
+fn fetch_transactions_after_cursor() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+    let mut tx = db.create_write_tx().unwrap();
+    let transactions = (0..5)
+        .map(|i| {
+            Transaction::builder()
+                .with_min_epoch(Some(Epoch(i)))
+                .sign(&PrivateKey::default())
+                .build()
+        })
+        .collect::<Vec<_>>();
+    for (i, transaction) in transactions.iter().enumerate() {
+        // Dry runs are excluded
+        tx.transactions_insert(transaction, &[], None, i == 2).unwrap();
+    }
+    tx.commit().unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    let page = tx.transactions_fetch_after(None, 2).unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].1.transaction.id(), transactions[0].id());
+    assert_eq!(page[1].1.transaction.id(), transactions[1].id());
+
+    let page = tx.transactions_fetch_after(Some(page[1].0), 2).unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].1.transaction.id(), transactions[3].id());
+    assert_eq!(page[1].1.transaction.id(), transactions[4].id());
+
+    let page = tx.transactions_fetch_after(Some(page[1].0), 2).unwrap();
+    assert!(page.is_empty());
+}
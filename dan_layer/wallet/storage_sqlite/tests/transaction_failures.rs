@@ -0,0 +1,112 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use tari_dan_common_types::{optional::Optional, SubstateRequirement};
+use tari_dan_wallet_sdk::{
+    models::TransactionStatus,
+    storage::{WalletStore, WalletStoreReader, WalletStoreWriter},
+};
+use tari_dan_wallet_storage_sqlite::SqliteWalletStore;
+use tari_engine_types::substate::SubstateId;
+use tari_transaction::TransactionId;
+
+#[test]
+fn insert_get_and_retry_transaction_failures() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    let component: SubstateId = "component_7cbfe29101c24924b1b6ccefbfff98986d648622272ae24f7585dab5ffffffff"
+        .parse()
+        .unwrap();
+    let inputs = vec![SubstateRequirement::with_version(component, 3)];
+    let failed_id = TransactionId::new([1u8; 32]);
+    let retry_id = TransactionId::new([2u8; 32]);
+
+    db.with_write_tx(|tx| {
+        tx.transaction_failures_insert(failed_id, TransactionStatus::Rejected, "Input is DOWN", &inputs, 0)
+    })
+    .unwrap();
+
+    let mut tx = db.create_read_tx().unwrap();
+    let failure = tx.transaction_failures_get(failed_id).unwrap();
+    assert_eq!(failure.status, TransactionStatus::Rejected);
+    assert_eq!(failure.reason, "Input is DOWN");
+    assert_eq!(failure.inputs, inputs);
+    assert_eq!(failure.inputs[0].version(), Some(3));
+    assert_eq!(failure.retried_as, None);
+    assert!(tx.transaction_failures_get(retry_id).optional().unwrap().is_none());
+    drop(tx);
+
+    db.with_write_tx(|tx| tx.transaction_failures_set_retried(failed_id, retry_id))
+        .unwrap();
+    // Unknown failures cannot be marked as retried
+    db.with_write_tx(|tx| tx.transaction_failures_set_retried(retry_id, failed_id))
+        .unwrap_err();
+
+    let mut tx = db.create_read_tx().unwrap();
+    assert!(tx.transaction_failures_get_all(false).unwrap().is_empty());
+    let failures = tx.transaction_failures_get_all(true).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].retried_as, Some(retry_id));
+
+    let failure = tx.transaction_failures_get_by_retried_as(retry_id).unwrap();
+    assert_eq!(failure.transaction_id, failed_id);
+    assert_eq!(failure.attempt, 0);
+    drop(tx);
+
+    // The retry fails too
+    db.with_write_tx(|tx| {
+        tx.transaction_failures_insert(retry_id, TransactionStatus::Rejected, "Input is DOWN", &inputs, 1)
+    })
+    .unwrap();
+    let mut tx = db.create_read_tx().unwrap();
+    assert_eq!(tx.transaction_failures_get(retry_id).unwrap().attempt, 1);
+}
+
+#[test]
+fn attempt_is_carried_through_retries() {
+    let db = SqliteWalletStore::try_open(":memory:").unwrap();
+    db.run_migrations().unwrap();
+
+    let original_id = TransactionId::new([1u8; 32]);
+    let first_retry_id = TransactionId::new([2u8; 32]);
+    let second_retry_id = TransactionId::new([3u8; 32]);
+
+    // Each retry is recorded with the attempt of the failure that it retried plus one, as the transaction API does
+    let mut failed_id = original_id;
+    for retry_id in [first_retry_id, second_retry_id] {
+        let mut tx = db.create_read_tx().unwrap();
+        let attempt = tx
+            .transaction_failures_get_by_retried_as(failed_id)
+            .optional()
+            .unwrap()
+            .map_or(0, |prev| prev.attempt + 1);
+        drop(tx);
+        db.with_write_tx(|tx| {
+            tx.transaction_failures_insert(failed_id, TransactionStatus::Rejected, "Input is DOWN", &[], attempt)?;
+            tx.transaction_failures_set_retried(failed_id, retry_id)
+        })
+        .unwrap();
+        failed_id = retry_id;
+    }
+
+    let mut tx = db.create_read_tx().unwrap();
+    let failure = tx.transaction_failures_get_by_retried_as(first_retry_id).unwrap();
+    assert_eq!(failure.transaction_id, original_id);
+    assert_eq!(failure.attempt, 0);
+    let failure = tx.transaction_failures_get_by_retried_as(second_retry_id).unwrap();
+    assert_eq!(failure.transaction_id, first_retry_id);
+    assert_eq!(failure.attempt, 1);
+    assert_eq!(tx.transaction_failures_get(first_retry_id).unwrap().attempt, 1);
+    // The original transaction is not a retry and the last retry has not failed
+    assert!(tx
+        .transaction_failures_get_by_retried_as(original_id)
+        .optional()
+        .unwrap()
+        .is_none());
+    assert!(tx
+        .transaction_failures_get(second_retry_id)
+        .optional()
+        .unwrap()
+        .is_none());
+}
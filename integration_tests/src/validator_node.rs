@@ -28,6 +28,7 @@ use std::{
 use reqwest::Url;
 use tari_common::configuration::{CommonConfig, StringList};
 use tari_common_types::types::PublicKey;
+use tari_consensus::hotstuff::fault_injection::ScriptedFaultInjector;
 use tari_dan_app_utilities::p2p_config::PeerSeedsConfig;
 use tari_p2p::Network;
 use tari_shutdown::Shutdown;
@@ -55,6 +56,8 @@ pub struct ValidatorNodeProcess {
     pub handle: task::JoinHandle<Result<(), anyhow::Error>>,
     pub temp_dir_path: PathBuf,
     pub shutdown: Shutdown,
+    /// Faults applied to the consensus messages of this node. Rules can be added while the node is running.
+    pub fault_injector: ScriptedFaultInjector,
 }
 
 impl ValidatorNodeProcess {
@@ -125,6 +128,8 @@ pub async fn spawn_validator_node(
     );
     // Connect to shard db
     let temp_dir_path = temp_dir.clone();
+    let fault_injector = ScriptedFaultInjector::new();
+    let vn_fault_injector = fault_injector.clone();
     let handle = task::spawn(async move {
         let mut config = ApplicationConfig {
             common: CommonConfig::default(),
@@ -150,6 +155,7 @@ pub async fn spawn_validator_node(
         config.validator_node.p2p.listener_port = port;
 
         config.validator_node.fee_claim_public_key = key.public_key;
+        config.validator_node.fault_injector = Some(vn_fault_injector);
 
         // Add all other VNs as peer seeds
         config.peer_seeds.peer_seeds = StringList::from(peer_seeds);
@@ -176,6 +182,7 @@ pub async fn spawn_validator_node(
         json_rpc_port,
         temp_dir_path,
         shutdown,
+        fault_injector,
     }
 }
 
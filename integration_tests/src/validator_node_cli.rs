@@ -108,7 +108,9 @@ pub async fn create_component(
     };
 
     let args = SubmitArgs {
-        instruction,
+        instruction: Some(instruction),
+        manifest: None,
+        input_variables: vec![],
         common: CommonSubmitArgs {
             wait_for_result: true,
             wait_for_result_timeout: Some(300),
@@ -286,7 +288,9 @@ async fn call_method_inner(
 
     println!("Inputs: {}", component);
     let args = SubmitArgs {
-        instruction,
+        instruction: Some(instruction),
+        manifest: None,
+        input_variables: vec![],
         common: CommonSubmitArgs {
             wait_for_result: true,
             wait_for_result_timeout: Some(60),
@@ -98,7 +98,7 @@ pub async fn claim_burn(
             "reciprocal_claim_public_key": BASE64.encode(reciprocal_claim_public_key.as_bytes()),
             "range_proof": BASE64.encode(range_proof.as_bytes()),
         }),
-        max_fee: Some(Amount(max_fee)),
+        max_fee: Some(Amount(max_fee).into()),
         key_id: None,
     };
 
@@ -862,12 +862,13 @@ pub async fn transfer(
 
     let request = AccountsTransferRequest {
         account,
-        amount,
+        amount: amount.into(),
         resource_address,
-        destination_public_key,
-        max_fee,
+        destination_public_key: destination_public_key.into(),
+        max_fee: max_fee.map(Into::into),
         proof_from_badge_resource: None,
         dry_run: false,
+        fee_account: None,
     };
 
     let resp = client.accounts_transfer(request).await.unwrap();
@@ -889,9 +890,9 @@ pub async fn confidential_transfer(
 
     let request = ConfidentialTransferRequest {
         account,
-        amount,
-        destination_public_key,
-        max_fee,
+        amount: amount.into(),
+        destination_public_key: destination_public_key.into(),
+        max_fee: max_fee.map(Into::into),
         resource_address: CONFIDENTIAL_TARI_RESOURCE_ADDRESS,
         proof_from_badge_resource: None,
         dry_run: false,
@@ -0,0 +1,88 @@
+# Copyright 2024 The Tari Project
+# SPDX-License-Identifier: BSD-3-Clause
+
+@concurrent
+@fault_injection
+Feature: Consensus fault injection scenarios
+
+  Scenario: Committee recovers when a leader does not propose
+    # Initialize a base node, wallet, miner and several VNs
+    Given a base node BASE
+    Given a wallet WALLET connected to base node BASE
+    Given a miner MINER connected to base node BASE and wallet WALLET
+
+    # Initialize an indexer
+    Given an indexer IDX connected to base node BASE
+    # Initialize the wallet daemon
+    Given a wallet daemon WALLET_D connected to indexer IDX
+
+    # Initialize VNs
+    Given a seed validator node VN1 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN2 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN3 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN4 connected to base node BASE and wallet daemon WALLET_D
+
+    When miner MINER mines 9 new blocks
+    When wallet WALLET has at least 20000 T
+    When validator node VN1 sends a registration transaction to base wallet WALLET
+    When validator node VN2 sends a registration transaction to base wallet WALLET
+    When validator node VN3 sends a registration transaction to base wallet WALLET
+    When validator node VN4 sends a registration transaction to base wallet WALLET
+
+    When miner MINER mines 26 new blocks
+    Then all validators have scanned to height 32
+    And indexer IDX has scanned to height 32
+    Then all validator nodes are listed as registered
+
+    When indexer IDX connects to all other validators
+
+    When all validator nodes have started epoch 3
+
+    # VN4 is still online and votes, but never proposes, so the other VNs must change view whenever it is the leader
+    When validator node VN4 drops outbound Proposal messages for heights 0 to 1000
+
+    # Submit some transactions to speed up block production
+    Then I create an account ACC_1 via the wallet daemon WALLET_D with 10000 free coins
+    Then I create an account ACC_2 via the wallet daemon WALLET_D with 10000 free coins
+    Then I create an account ACC_3 via the wallet daemon WALLET_D with 10000 free coins
+
+    Then validator node VN1 has a dummy block between heights 0 and 1000 within 60 seconds
+
+    # Once VN4 proposes again, the committee continues to make progress
+    When I clear the consensus faults on validator node VN4
+    Then I create an account ACC_4 via the wallet daemon WALLET_D with 10000 free coins
+    Then I create an account ACC_5 via the wallet daemon WALLET_D with 10000 free coins
+
+  Scenario: Duplicated and delayed votes do not stall consensus
+    Given a base node BASE
+    Given a wallet WALLET connected to base node BASE
+    Given a miner MINER connected to base node BASE and wallet WALLET
+    Given an indexer IDX connected to base node BASE
+    Given a wallet daemon WALLET_D connected to indexer IDX
+
+    Given a seed validator node VN1 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN2 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN3 connected to base node BASE and wallet daemon WALLET_D
+    Given a seed validator node VN4 connected to base node BASE and wallet daemon WALLET_D
+
+    When miner MINER mines 9 new blocks
+    When wallet WALLET has at least 20000 T
+    When validator node VN1 sends a registration transaction to base wallet WALLET
+    When validator node VN2 sends a registration transaction to base wallet WALLET
+    When validator node VN3 sends a registration transaction to base wallet WALLET
+    When validator node VN4 sends a registration transaction to base wallet WALLET
+
+    When miner MINER mines 26 new blocks
+    Then all validators have scanned to height 32
+    And indexer IDX has scanned to height 32
+    Then all validator nodes are listed as registered
+
+    When indexer IDX connects to all other validators
+
+    When all validator nodes have started epoch 3
+
+    When validator node VN2 duplicates outbound Vote messages for heights 0 to 1000
+    When validator node VN3 delays outbound Vote messages for heights 0 to 1000 by 500ms
+
+    Then I create an account ACC_1 via the wallet daemon WALLET_D with 10000 free coins
+    Then I create an account ACC_2 via the wallet daemon WALLET_D with 10000 free coins
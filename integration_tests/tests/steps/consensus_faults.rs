@@ -0,0 +1,134 @@
+//    Copyright 2024 The Tari Project
+//    SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use cucumber::{then, when};
+use integration_tests::TariWorld;
+use tari_consensus::hotstuff::fault_injection::{Fault, FaultRule, MessageDirection};
+use tari_dan_common_types::NodeHeight;
+use tari_validator_node_client::types::GetBlocksRequest;
+
+fn add_fault_rule(
+    world: &mut TariWorld,
+    vn_name: &str,
+    direction: &str,
+    message_type: String,
+    from_height: u64,
+    to_height: u64,
+    fault: Fault,
+) {
+    let direction = match direction {
+        "inbound" => MessageDirection::Inbound,
+        "outbound" => MessageDirection::Outbound,
+        _ => panic!("Invalid message direction '{direction}'. Expected inbound or outbound"),
+    };
+    let rule =
+        FaultRule::new(direction, message_type, fault).for_heights(NodeHeight(from_height)..=NodeHeight(to_height));
+    world.get_validator_node(vn_name).fault_injector.add_rule(rule);
+}
+
+#[when(expr = "validator node {word} drops {word} {word} messages for heights {int} to {int}")]
+async fn when_validator_node_drops_messages(
+    world: &mut TariWorld,
+    vn_name: String,
+    direction: String,
+    message_type: String,
+    from_height: u64,
+    to_height: u64,
+) {
+    add_fault_rule(
+        world,
+        &vn_name,
+        &direction,
+        message_type,
+        from_height,
+        to_height,
+        Fault::Drop,
+    );
+}
+
+#[when(expr = "validator node {word} delays {word} {word} messages for heights {int} to {int} by {int}ms")]
+async fn when_validator_node_delays_messages(
+    world: &mut TariWorld,
+    vn_name: String,
+    direction: String,
+    message_type: String,
+    from_height: u64,
+    to_height: u64,
+    delay_ms: u64,
+) {
+    add_fault_rule(
+        world,
+        &vn_name,
+        &direction,
+        message_type,
+        from_height,
+        to_height,
+        Fault::Delay(Duration::from_millis(delay_ms)),
+    );
+}
+
+#[when(expr = "validator node {word} duplicates {word} {word} messages for heights {int} to {int}")]
+async fn when_validator_node_duplicates_messages(
+    world: &mut TariWorld,
+    vn_name: String,
+    direction: String,
+    message_type: String,
+    from_height: u64,
+    to_height: u64,
+) {
+    add_fault_rule(
+        world,
+        &vn_name,
+        &direction,
+        message_type,
+        from_height,
+        to_height,
+        Fault::Duplicate(1),
+    );
+}
+
+#[when(expr = "I clear the consensus faults on validator node {word}")]
+async fn when_i_clear_consensus_faults(world: &mut TariWorld, vn_name: String) {
+    world.get_validator_node(&vn_name).fault_injector.clear();
+}
+
+/// Dummy blocks are only created when the leader for a height failed to propose, so they show that a view change
+/// occurred
+#[then(expr = "validator node {word} has a dummy block between heights {int} and {int} within {int} seconds")]
+async fn then_validator_node_has_dummy_block(
+    world: &mut TariWorld,
+    vn_name: String,
+    from_height: u64,
+    to_height: u64,
+    timeout_secs: u64,
+) {
+    let vn = world.get_validator_node(&vn_name);
+    let mut client = vn.create_client();
+    for _ in 0..timeout_secs {
+        let resp = client
+            .list_blocks_paginated(GetBlocksRequest {
+                limit: 100,
+                offset: 0,
+                ordering_index: None,
+                ordering: None,
+                filter_index: None,
+                filter: None,
+            })
+            .await
+            .unwrap();
+        let has_dummy = resp
+            .blocks
+            .iter()
+            .any(|b| b.is_dummy() && (from_height..=to_height).contains(&b.height().as_u64()));
+        if has_dummy {
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    panic!(
+        "Validator node {} has no dummy block between heights {} and {} after {} seconds",
+        vn_name, from_height, to_height, timeout_secs
+    );
+}
@@ -3,6 +3,7 @@
 
 mod base_node;
 mod common;
+mod consensus_faults;
 mod indexer;
 mod miner;
 mod network;
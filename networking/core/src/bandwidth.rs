@@ -0,0 +1,208 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+use std::{collections::HashMap, time::Instant};
+
+use libp2p::PeerId;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BandwidthStats {
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    /// The number of messages that were dropped because a per-peer throttle was exceeded
+    pub messages_throttled: u64,
+}
+
+impl BandwidthStats {
+    fn record(&mut self, direction: Direction, num_bytes: usize) {
+        let num_bytes = num_bytes as u64;
+        match direction {
+            Direction::Inbound => {
+                self.bytes_received = self.bytes_received.saturating_add(num_bytes);
+                self.messages_received = self.messages_received.saturating_add(1);
+            },
+            Direction::Outbound => {
+                self.bytes_sent = self.bytes_sent.saturating_add(num_bytes);
+                self.messages_sent = self.messages_sent.saturating_add(1);
+            },
+        }
+    }
+}
+
+/// Bytes sent and received since the networking service started
+#[derive(Debug, Clone, Default)]
+pub struct BandwidthReport {
+    pub total: BandwidthStats,
+    /// Only includes traffic that is attributable to a peer i.e. direct messages and received gossip
+    pub per_peer: HashMap<PeerId, BandwidthStats>,
+    pub per_message_type: HashMap<String, BandwidthStats>,
+}
+
+/// Per-peer bandwidth limits. A limit of None is unlimited.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BandwidthLimits {
+    pub max_inbound_bytes_per_peer_per_sec: Option<u64>,
+    pub max_outbound_bytes_per_peer_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Inbound,
+    Outbound,
+}
+
+pub(crate) struct BandwidthTracker {
+    limits: BandwidthLimits,
+    report: BandwidthReport,
+    inbound_throttles: HashMap<PeerId, Throttle>,
+    outbound_throttles: HashMap<PeerId, Throttle>,
+}
+
+impl BandwidthTracker {
+    pub fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            limits,
+            report: BandwidthReport::default(),
+            inbound_throttles: HashMap::new(),
+            outbound_throttles: HashMap::new(),
+        }
+    }
+
+    /// Returns true if a message of `num_bytes` may be exchanged with the peer without exceeding its throttle. A
+    /// message that is not allowed is counted as throttled.
+    pub fn try_acquire(&mut self, peer_id: PeerId, direction: Direction, message_type: &str, num_bytes: usize) -> bool {
+        let (throttles, limit) = match direction {
+            Direction::Inbound => (&mut self.inbound_throttles, self.limits.max_inbound_bytes_per_peer_per_sec),
+            Direction::Outbound => (&mut self.outbound_throttles, self.limits.max_outbound_bytes_per_peer_per_sec),
+        };
+        let Some(limit) = limit else {
+            return true;
+        };
+        let throttle = throttles.entry(peer_id).or_insert_with(|| Throttle::new(limit));
+        if throttle.try_consume(num_bytes, Instant::now()) {
+            return true;
+        }
+
+        self.report.total.messages_throttled += 1;
+        self.report.per_peer.entry(peer_id).or_default().messages_throttled += 1;
+        self.stats_for_message_type(message_type).messages_throttled += 1;
+        false
+    }
+
+    /// Records a message that was exchanged with the given peer, or with no specific peer (i.e. published gossip)
+    pub fn record(&mut self, peer_id: Option<PeerId>, direction: Direction, message_type: &str, num_bytes: usize) {
+        self.report.total.record(direction, num_bytes);
+        if let Some(peer_id) = peer_id {
+            self.report.per_peer.entry(peer_id).or_default().record(direction, num_bytes);
+        }
+        self.stats_for_message_type(message_type).record(direction, num_bytes);
+    }
+
+    /// Removes the throttle state for a peer that is no longer connected. Accumulated stats are kept.
+    pub fn on_peer_disconnected(&mut self, peer_id: &PeerId) {
+        self.inbound_throttles.remove(peer_id);
+        self.outbound_throttles.remove(peer_id);
+    }
+
+    pub fn report(&self) -> &BandwidthReport {
+        &self.report
+    }
+
+    fn stats_for_message_type(&mut self, message_type: &str) -> &mut BandwidthStats {
+        // Avoids allocating a key for every message once the message type has been seen
+        if !self.report.per_message_type.contains_key(message_type) {
+            self.report
+                .per_message_type
+                .insert(message_type.to_string(), BandwidthStats::default());
+        }
+        self.report
+            .per_message_type
+            .get_mut(message_type)
+            .expect("message type was inserted above")
+    }
+}
+
+/// A token bucket that refills at `bytes_per_sec` up to one second's worth of bytes. A message is allowed if any
+/// tokens remain and may overdraw the bucket, so that messages larger than the limit are delayed rather than never
+/// sent.
+#[derive(Debug)]
+struct Throttle {
+    bytes_per_sec: u64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Throttle {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec,
+            tokens: bytes_per_sec as f64,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn try_consume(&mut self, num_bytes: usize, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+        let capacity = self.bytes_per_sec as f64;
+        self.tokens = (self.tokens + elapsed * capacity).min(capacity);
+        if self.tokens <= 0.0 {
+            return false;
+        }
+        self.tokens -= num_bytes as f64;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn it_allows_overdraw_and_refills_over_time() {
+        let start = Instant::now();
+        let mut throttle = Throttle {
+            bytes_per_sec: 1000,
+            tokens: 1000.0,
+            last_refill: start,
+        };
+        // A message larger than the limit is allowed if the bucket is not empty
+        assert!(throttle.try_consume(1500, start));
+        assert!(!throttle.try_consume(1, start));
+        // 500 bytes of debt is repaid after half a second
+        assert!(!throttle.try_consume(1, start + Duration::from_millis(500)));
+        assert!(throttle.try_consume(1, start + Duration::from_millis(600)));
+    }
+
+    #[test]
+    fn it_records_stats_and_counts_throttled_messages() {
+        let peer_id = PeerId::random();
+        let mut tracker = BandwidthTracker::new(BandwidthLimits {
+            max_inbound_bytes_per_peer_per_sec: None,
+            max_outbound_bytes_per_peer_per_sec: Some(100),
+        });
+
+        assert!(tracker.try_acquire(peer_id, Direction::Inbound, "Vote", 1000));
+        tracker.record(Some(peer_id), Direction::Inbound, "Vote", 1000);
+        assert!(tracker.try_acquire(peer_id, Direction::Outbound, "Proposal", 200));
+        tracker.record(Some(peer_id), Direction::Outbound, "Proposal", 200);
+        assert!(!tracker.try_acquire(peer_id, Direction::Outbound, "Proposal", 200));
+        tracker.record(None, Direction::Outbound, "gossip/consensus", 50);
+
+        let report = tracker.report();
+        assert_eq!(report.total, BandwidthStats {
+            bytes_sent: 250,
+            bytes_received: 1000,
+            messages_sent: 2,
+            messages_received: 1,
+            messages_throttled: 1,
+        });
+        assert_eq!(report.per_peer[&peer_id].bytes_sent, 200);
+        assert_eq!(report.per_message_type["Proposal"].messages_throttled, 1);
+        assert_eq!(report.per_message_type["gossip/consensus"].bytes_sent, 50);
+    }
+}
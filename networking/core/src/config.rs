@@ -5,6 +5,8 @@ use std::time::Duration;
 
 use libp2p::Multiaddr;
 
+use crate::BandwidthLimits;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub swarm: tari_swarm::Config,
@@ -13,6 +15,7 @@ pub struct Config {
     pub announce: bool,
     pub check_connections_interval: Duration,
     pub known_local_public_address: Vec<Multiaddr>,
+    pub bandwidth_limits: BandwidthLimits,
 }
 
 impl Default for Config {
@@ -24,6 +27,7 @@ impl Default for Config {
             announce: false,
             check_connections_interval: Duration::from_secs(2 * 60 * 60),
             known_local_public_address: vec![],
+            bandwidth_limits: BandwidthLimits::default(),
         }
     }
 }
@@ -22,7 +22,7 @@
 
 use std::io;
 
-use libp2p::{gossipsub, gossipsub::SubscriptionError, swarm::DialError, TransportError};
+use libp2p::{gossipsub, gossipsub::SubscriptionError, swarm::DialError, PeerId, TransportError};
 use tari_rpc_framework::RpcError;
 use tari_swarm::{messaging, substream, TariSwarmError};
 use tokio::sync::{mpsc, oneshot};
@@ -55,6 +55,8 @@ pub enum NetworkingError {
     PeerSyncError(#[from] tari_swarm::peersync::Error),
     #[error("Messaging is disabled")]
     MessagingDisabled,
+    #[error("Bandwidth limit exceeded for peer {peer_id}")]
+    BandwidthLimitExceeded { peer_id: PeerId },
 }
 
 impl From<oneshot::error::RecvError> for NetworkingError {
@@ -33,10 +33,14 @@ use tari_rpc_framework::{
     RpcClientBuilder,
     Substream,
 };
-use tari_swarm::substream::{NegotiatedSubstream, ProtocolNotification};
+use tari_swarm::{
+    inbound_guard::InboundConnectionStats,
+    substream::{NegotiatedSubstream, ProtocolNotification},
+};
 use tokio::sync::{broadcast, mpsc, oneshot};
 
 use crate::{
+    bandwidth::BandwidthReport,
     connection::Connection,
     error::NetworkingHandleError,
     event::NetworkingEvent,
@@ -100,6 +104,12 @@ pub enum NetworkingRequest<TMsg: MessageSpec> {
     GetLocalPeerInfo {
         reply_tx: oneshot::Sender<Result<PeerInfo, NetworkingError>>,
     },
+    GetBandwidthReport {
+        reply_tx: oneshot::Sender<Result<BandwidthReport, NetworkingError>>,
+    },
+    GetInboundConnectionStats {
+        reply_tx: oneshot::Sender<Result<InboundConnectionStats, NetworkingError>>,
+    },
     SetWantPeers(HashSet<PeerId>),
 }
 
@@ -271,6 +281,24 @@ impl<TMsg: MessageSpec> NetworkingHandle<TMsg> {
         rx.await?
     }
 
+    pub async fn get_bandwidth_report(&self) -> Result<BandwidthReport, NetworkingError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx_request
+            .send(NetworkingRequest::GetBandwidthReport { reply_tx: tx })
+            .await?;
+        rx.await?
+    }
+
+    /// Returns the number of inbound connection attempts that were accepted, rate limited or denied since the
+    /// networking service started
+    pub async fn get_inbound_connection_stats(&self) -> Result<InboundConnectionStats, NetworkingError> {
+        let (tx, rx) = oneshot::channel();
+        self.tx_request
+            .send(NetworkingRequest::GetInboundConnectionStats { reply_tx: tx })
+            .await?;
+        rx.await?
+    }
+
     pub async fn get_local_peer_info(&self) -> Result<PeerInfo, NetworkingError> {
         let (tx, rx) = oneshot::channel();
         self.tx_request
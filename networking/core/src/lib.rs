@@ -16,6 +16,7 @@ mod worker;
 mod error;
 pub use error::NetworkingError;
 
+mod bandwidth;
 mod config;
 mod connection;
 mod event;
@@ -27,13 +28,21 @@ mod peer;
 mod relay_state;
 mod spawn;
 
+pub use bandwidth::{BandwidthLimits, BandwidthReport, BandwidthStats};
 pub use config::*;
 pub use connection::*;
 pub use handle::*;
 pub use message::*;
 pub use spawn::*;
 pub use tari_swarm::{
-    config::{Config as SwarmConfig, LimitPerInterval, RelayCircuitLimits, RelayReservationLimits},
+    config::{
+        Config as SwarmConfig,
+        InboundConnectionLimits,
+        LimitPerInterval,
+        RelayCircuitLimits,
+        RelayReservationLimits,
+    },
+    inbound_guard::InboundConnectionStats,
     is_supported_multiaddr,
 };
 
@@ -7,4 +7,9 @@ pub trait MessageSpec {
     type Message: fmt::Debug + Send;
     type TransactionGossipMessage: fmt::Debug + Send;
     type ConsensusGossipMessage: fmt::Debug + Send;
+
+    /// Returns a label for the type of the message, used to account for bandwidth by message type
+    fn message_type(_message: &Self::Message) -> &'static str {
+        "Message"
+    }
 }
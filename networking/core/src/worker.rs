@@ -36,6 +36,7 @@ use log::*;
 use tari_rpc_framework::Substream;
 use tari_shutdown::ShutdownSignal;
 use tari_swarm::{
+    inbound_guard,
     is_supported_multiaddr,
     messaging,
     messaging::{prost, prost::ProstCodec},
@@ -51,6 +52,7 @@ use tokio::{
 };
 
 use crate::{
+    bandwidth::{BandwidthTracker, Direction},
     connection::Connection,
     event::NetworkingEvent,
     global_ip::GlobalIp,
@@ -60,6 +62,7 @@ use crate::{
     MessageSpec,
     MessagingMode,
     NetworkingError,
+    TOPIC_DELIMITER,
 };
 
 const LOG_TARGET: &str = "tari::dan::networking::service::worker";
@@ -87,6 +90,7 @@ where
     swarm: TariSwarm<ProstCodec<TMsg::Message>>,
     config: crate::Config,
     relays: RelayState,
+    bandwidth: BandwidthTracker,
     is_initial_bootstrap_complete: bool,
     has_sent_announce: bool,
     shutdown_signal: ShutdownSignal,
@@ -121,6 +125,7 @@ where
             relays: RelayState::new(known_relay_nodes),
             topic_peers: HashMap::new(),
             swarm,
+            bandwidth: BandwidthTracker::new(config.bandwidth_limits),
             config,
             is_initial_bootstrap_complete: false,
             has_sent_announce: false,
@@ -215,6 +220,16 @@ where
                 message,
                 reply_tx,
             } => {
+                let message_type = TMsg::message_type(&message);
+                let num_bytes = message.encoded_len();
+                if !self
+                    .bandwidth
+                    .try_acquire(peer, Direction::Outbound, message_type, num_bytes)
+                {
+                    debug!(target: LOG_TARGET, "🚦 Outbound bandwidth limit exceeded for peer {}. Dropping {message_type} message", peer);
+                    let _ignore = reply_tx.send(Err(NetworkingError::BandwidthLimitExceeded { peer_id: peer }));
+                    return Ok(());
+                }
                 match self
                     .swarm
                     .behaviour_mut()
@@ -224,6 +239,8 @@ where
                 {
                     Some(Ok(_)) => {
                         debug!(target: LOG_TARGET, "📢 Queued message to peer {}", peer);
+                        self.bandwidth
+                            .record(Some(peer), Direction::Outbound, message_type, num_bytes);
                         let _ignore = reply_tx.send(Ok(()));
                     },
                     Some(Err(err)) => {
@@ -242,6 +259,8 @@ where
                 reply_tx,
             } => {
                 let len = destination.len();
+                let message_type = TMsg::message_type(&message);
+                let num_bytes = message.encoded_len();
                 let Some(messaging_mut) = &mut self.swarm.behaviour_mut().messaging.as_mut() else {
                     warn!(target: LOG_TARGET, "Sent multicast message but messaging is disabled");
                     let _ignore = reply_tx.send(Err(NetworkingError::MessagingDisabled));
@@ -250,8 +269,17 @@ where
 
                 let mut num_sent = 0;
                 for peer in destination {
+                    if !self
+                        .bandwidth
+                        .try_acquire(peer, Direction::Outbound, message_type, num_bytes)
+                    {
+                        debug!(target: LOG_TARGET, "🚦 Outbound bandwidth limit exceeded for peer {}. Dropping {message_type} message", peer);
+                        continue;
+                    }
                     match messaging_mut.send_message(peer, message.clone()) {
                         Ok(_) => {
+                            self.bandwidth
+                                .record(Some(peer), Direction::Outbound, message_type, num_bytes);
                             num_sent += 1;
                         },
                         Err(err) => {
@@ -266,15 +294,22 @@ where
                 topic,
                 message,
                 reply_tx,
-            } => match self.swarm.behaviour_mut().gossipsub.publish(topic, message) {
-                Ok(msg_id) => {
-                    debug!(target: LOG_TARGET, "📢 Published gossipsub message: {}", msg_id);
-                    let _ignore = reply_tx.send(Ok(()));
-                },
-                Err(err) => {
-                    debug!(target: LOG_TARGET, "🚨 Failed to publish gossipsub message: {}", err);
-                    let _ignore = reply_tx.send(Err(err.into()));
-                },
+            } => {
+                let message_type = gossip_message_type(topic.hash().as_str());
+                let num_bytes = message.len();
+                match self.swarm.behaviour_mut().gossipsub.publish(topic, message) {
+                    Ok(msg_id) => {
+                        debug!(target: LOG_TARGET, "📢 Published gossipsub message: {}", msg_id);
+                        // Gossip is published to the mesh rather than a specific peer
+                        self.bandwidth
+                            .record(None, Direction::Outbound, &message_type, num_bytes);
+                        let _ignore = reply_tx.send(Ok(()));
+                    },
+                    Err(err) => {
+                        debug!(target: LOG_TARGET, "🚨 Failed to publish gossipsub message: {}", err);
+                        let _ignore = reply_tx.send(Err(err.into()));
+                    },
+                }
             },
             NetworkingRequest::SubscribeTopic {
                 topic,
@@ -356,6 +391,12 @@ where
                 };
                 let _ignore = reply_tx.send(Ok(peer));
             },
+            NetworkingRequest::GetBandwidthReport { reply_tx } => {
+                let _ignore = reply_tx.send(Ok(self.bandwidth.report().clone()));
+            },
+            NetworkingRequest::GetInboundConnectionStats { reply_tx } => {
+                let _ignore = reply_tx.send(Ok(*self.swarm.behaviour().inbound_guard.stats()));
+            },
             NetworkingRequest::SetWantPeers(peers) => {
                 info!(target: LOG_TARGET, "🧭 Setting want peers to {:?}", peers);
                 self.swarm.behaviour_mut().peer_sync.want_peers(peers).await?;
@@ -439,6 +480,9 @@ where
                     },
                 }
                 shrink_hashmap_if_required(&mut self.active_connections);
+                if !self.active_connections.contains_key(&peer_id) {
+                    self.bandwidth.on_peer_disconnected(&peer_id);
+                }
                 if let Some(selected) = self.relays.selected_relay() {
                     if selected.circuit_connection_id == Some(connection_id) {
                         // Our selected relay has disconnected, attempt to reserve another
@@ -557,6 +601,19 @@ where
             }) => match message.source {
                 Some(source) => {
                     info!(target: LOG_TARGET, "📢 Gossipsub message: [{topic}] {message_id} ({bytes} bytes) from {source}", topic = message.topic, bytes = message.data.len());
+                    let message_type = gossip_message_type(message.topic.as_str());
+                    let num_bytes = message.data.len();
+                    if !self.bandwidth.try_acquire(propagation_source, Direction::Inbound, &message_type, num_bytes) {
+                        debug!(target: LOG_TARGET, "🚦 Inbound bandwidth limit exceeded for peer {propagation_source}. Ignoring gossip message {message_id}");
+                        self.swarm.behaviour_mut().gossipsub.report_message_validation_result(
+                            &message_id,
+                            &propagation_source,
+                            gossipsub::MessageAcceptance::Ignore,
+                        )?;
+                        return Ok(());
+                    }
+                    self.bandwidth
+                        .record(Some(propagation_source), Direction::Inbound, &message_type, num_bytes);
                     self.on_gossipsub_message(message_id, propagation_source, source, message)
                         .await?;
                 },
@@ -581,6 +638,16 @@ where
                 length,
             }) => {
                 info!(target: LOG_TARGET, "📧 Rx Messaging: peer {peer_id} ({length} bytes)");
+                let message_type = TMsg::message_type(&message);
+                if !self
+                    .bandwidth
+                    .try_acquire(peer_id, Direction::Inbound, message_type, length)
+                {
+                    debug!(target: LOG_TARGET, "🚦 Inbound bandwidth limit exceeded for peer {peer_id}. Dropping {message_type} message");
+                    return Ok(());
+                }
+                self.bandwidth
+                    .record(Some(peer_id), Direction::Inbound, message_type, length);
                 let _ignore = self.messaging_mode.send_message(peer_id, message);
             },
             Messaging(event) => {
@@ -593,6 +660,9 @@ where
                 // This is unreachable as connection-limits has no events
                 info!(target: LOG_TARGET, "ℹ️ ConnectionLimits event");
             },
+            InboundGuard(inbound_guard::Event::AddressBanned { address, duration }) => {
+                warn!(target: LOG_TARGET, "🚫 Banned {address} for {duration:.2?} after repeated connection rate limit or handshake violations");
+            },
             Mdns(event) => {
                 self.on_mdns_event(event)?;
             },
@@ -991,6 +1061,12 @@ where
     }
 }
 
+/// Gossip messages are accounted for by topic prefix rather than the full topic, which includes the shard group
+fn gossip_message_type(topic: &str) -> String {
+    let prefix = topic.split_once(TOPIC_DELIMITER).map_or(topic, |(prefix, _)| prefix);
+    format!("gossip/{prefix}")
+}
+
 fn is_p2p_address(address: &Multiaddr) -> bool {
     address.iter().any(|p| matches!(p, Protocol::P2p(_)))
 }
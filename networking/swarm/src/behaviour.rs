@@ -4,12 +4,14 @@
 use std::{
     collections::{hash_map::DefaultHasher, HashSet},
     hash::Hasher,
+    time::Duration,
 };
 
 use libp2p::{
     autonat,
     connection_limits,
     connection_limits::ConnectionLimits,
+    core::{muxing::StreamMuxerBox, transport::timeout::TransportTimeout, upgrade, Transport},
     dcutr,
     gossipsub,
     identify,
@@ -17,10 +19,12 @@ use libp2p::{
     mdns,
     noise,
     ping,
+    quic,
     relay,
     swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     tcp,
     yamux,
+    PeerId,
     StreamProtocol,
     Swarm,
     SwarmBuilder,
@@ -33,6 +37,7 @@ use libp2p_substream as substream;
 use crate::{
     config::{Config, RelayCircuitLimits, RelayReservationLimits},
     error::TariSwarmError,
+    inbound_guard,
 };
 
 #[derive(NetworkBehaviour)]
@@ -42,6 +47,7 @@ where TCodec: messaging::Codec + Send + Clone + 'static
     pub ping: ping::Behaviour,
     pub dcutr: dcutr::Behaviour,
     pub connection_limits: connection_limits::Behaviour,
+    pub inbound_guard: inbound_guard::Behaviour,
 
     pub relay: Toggle<relay::Behaviour>,
     pub relay_client: relay::client::Behaviour,
@@ -77,8 +83,8 @@ where
 {
     let swarm = SwarmBuilder::with_existing_identity(identity)
         .with_tokio()
-        .with_tcp(tcp::Config::new().nodelay(true), noise_config, yamux::Config::default)?
-        .with_quic()
+        .with_other_transport(|keypair| create_tcp_transport(keypair, config.handshake_timeout))?
+        .with_other_transport(|keypair| create_quic_transport(keypair, config.handshake_timeout))?
         .with_relay_client(noise_config, yamux::Config::default)?
         .with_behaviour(|keypair, relay_client| {
             let local_peer_id = keypair.public().to_peer_id();
@@ -136,8 +142,11 @@ where
 
             // Connection limits
             let connection_limits = connection_limits::Behaviour::new(
-                ConnectionLimits::default().with_max_established_per_peer(config.max_connections_per_peer),
+                ConnectionLimits::default()
+                    .with_max_established_per_peer(config.max_connections_per_peer)
+                    .with_max_pending_incoming(config.max_pending_incoming_connections),
             );
+            let inbound_guard = inbound_guard::Behaviour::new(config.inbound_connection_limits);
 
             // mDNS
             let maybe_mdns = if config.enable_mdns {
@@ -164,6 +173,7 @@ where
                 substream,
                 messaging: Toggle::from(messaging),
                 connection_limits,
+                inbound_guard,
                 mdns: Toggle::from(maybe_mdns),
                 peer_sync,
             })
@@ -175,6 +185,31 @@ where
     Ok(swarm)
 }
 
+/// TCP with noise and yamux. Connections that do not complete the upgrade within the handshake timeout are dropped, so
+/// that peers cannot hold pending connections open.
+fn create_tcp_transport(
+    keypair: &Keypair,
+    handshake_timeout: Duration,
+) -> Result<impl Transport<Output = (PeerId, StreamMuxerBox)>, noise::Error> {
+    let transport = tcp::tokio::Transport::new(tcp::Config::new().nodelay(true))
+        .upgrade(upgrade::Version::V1Lazy)
+        .authenticate(noise_config(keypair)?)
+        .multiplex(yamux::Config::default())
+        .map(|(peer_id, muxer), _| (peer_id, StreamMuxerBox::new(muxer)));
+    Ok(TransportTimeout::new(transport, handshake_timeout))
+}
+
+fn create_quic_transport(
+    keypair: &Keypair,
+    handshake_timeout: Duration,
+) -> Result<impl Transport<Output = (PeerId, StreamMuxerBox)>, noise::Error> {
+    let mut config = quic::Config::new(keypair);
+    config.handshake_timeout = handshake_timeout;
+    let transport = quic::tokio::Transport::new(config)
+        .map(|(peer_id, connection), _| (peer_id, StreamMuxerBox::new(connection)));
+    Ok(transport)
+}
+
 fn create_relay_config(circuit: &RelayCircuitLimits, reservations: &RelayReservationLimits) -> relay::Config {
     let mut config = relay::Config {
         reservation_rate_limiters: vec![],
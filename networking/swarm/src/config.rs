@@ -18,6 +18,11 @@ pub struct Config {
     pub enable_relay: bool,
     pub enable_messaging: bool,
     pub idle_connection_timeout: Duration,
+    /// The maximum time allowed to upgrade a connection (security and multiplexer handshakes) before it is dropped
+    pub handshake_timeout: Duration,
+    /// The maximum number of inbound connections that may be handshaking at the same time
+    pub max_pending_incoming_connections: Option<u32>,
+    pub inbound_connection_limits: InboundConnectionLimits,
     pub relay_circuit_limits: RelayCircuitLimits,
     pub relay_reservation_limits: RelayReservationLimits,
     pub identify_interval: Duration,
@@ -36,6 +41,9 @@ impl Default for Config {
             enable_relay: false,
             enable_messaging: true,
             idle_connection_timeout: Duration::from_secs(10 * 60),
+            handshake_timeout: Duration::from_secs(10),
+            max_pending_incoming_connections: Some(128),
+            inbound_connection_limits: InboundConnectionLimits::default(),
             relay_circuit_limits: RelayCircuitLimits::default(),
             relay_reservation_limits: RelayReservationLimits::default(),
             // This is the default for identify
@@ -45,6 +53,33 @@ impl Default for Config {
         }
     }
 }
+
+/// Limits on inbound connection attempts by IP address. Relayed connections are not limited.
+#[derive(Debug, Clone)]
+pub struct InboundConnectionLimits {
+    /// The maximum number of inbound connection attempts from an IP address per interval
+    pub per_ip: Option<LimitPerInterval>,
+    /// Ban an IP address that exceeds `per_ip` or fails handshakes this many times per interval
+    pub ban_after: Option<LimitPerInterval>,
+    pub ban_duration: Duration,
+}
+
+impl Default for InboundConnectionLimits {
+    fn default() -> Self {
+        Self {
+            per_ip: Some(LimitPerInterval {
+                limit: NonZeroU32::new(30).expect("30 > 0"),
+                interval: Duration::from_secs(60),
+            }),
+            ban_after: Some(LimitPerInterval {
+                limit: NonZeroU32::new(20).expect("20 > 0"),
+                interval: Duration::from_secs(5 * 60),
+            }),
+            ban_duration: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RelayCircuitLimits {
     pub max_limit: usize,
@@ -0,0 +1,377 @@
+//   Copyright 2024 The Tari Project
+//   SPDX-License-Identifier: BSD-3-Clause
+
+//! Rate limits inbound connection attempts by IP address. Attempts over the limit and failed handshakes (including
+//! handshakes that time out) count as violations, and an address that commits too many violations is banned for a
+//! while. Connections from banned addresses are denied before the handshake, so they cost the node very little.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use libp2p::{
+    core::{multiaddr::Protocol, transport::PortUse, Endpoint},
+    swarm::{
+        dummy,
+        ConnectionDenied,
+        ConnectionId,
+        FromSwarm,
+        ListenError,
+        ListenFailure,
+        NetworkBehaviour,
+        THandler,
+        THandlerInEvent,
+        THandlerOutEvent,
+        ToSwarm,
+    },
+    Multiaddr,
+    PeerId,
+};
+
+use crate::config::InboundConnectionLimits;
+
+/// State is pruned once this many addresses are tracked
+const MAX_TRACKED_ADDRESSES: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// Inbound connections from the address are denied until the ban expires
+    AddressBanned { address: IpAddr, duration: Duration },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InboundConnectionStats {
+    pub accepted: u64,
+    pub rate_limited: u64,
+    /// Attempts that were denied because the address is banned
+    pub denied_banned: u64,
+    pub handshake_failures: u64,
+    pub bans: u64,
+}
+
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+pub enum InboundConnectionDenied {
+    #[error("address is banned")]
+    Banned,
+    #[error("too many connection attempts from address")]
+    RateLimited,
+}
+
+pub struct Behaviour {
+    tracker: InboundConnectionTracker,
+}
+
+impl Behaviour {
+    pub fn new(limits: InboundConnectionLimits) -> Self {
+        Self {
+            tracker: InboundConnectionTracker::new(limits),
+        }
+    }
+
+    pub fn stats(&self) -> &InboundConnectionStats {
+        &self.tracker.stats
+    }
+
+    fn on_listen_failure(&mut self, failure: ListenFailure) {
+        // Denied connections have already been counted. Transport errors include failed and timed out handshakes.
+        if !matches!(failure.error, ListenError::Transport(_)) {
+            return;
+        }
+        let Some(address) = ip_address(failure.send_back_addr) else {
+            return;
+        };
+        self.tracker.on_handshake_failure(address, Instant::now());
+    }
+}
+
+impl NetworkBehaviour for Behaviour {
+    type ConnectionHandler = dummy::ConnectionHandler;
+    type ToSwarm = Event;
+
+    fn handle_pending_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _local_addr: &Multiaddr,
+        remote_addr: &Multiaddr,
+    ) -> Result<(), ConnectionDenied> {
+        let Some(address) = ip_address(remote_addr) else {
+            return Ok(());
+        };
+        self.tracker
+            .on_inbound_attempt(address, Instant::now())
+            .map_err(ConnectionDenied::new)
+    }
+
+    fn handle_established_inbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _local_addr: &Multiaddr,
+        _remote_addr: &Multiaddr,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(dummy::ConnectionHandler)
+    }
+
+    fn handle_established_outbound_connection(
+        &mut self,
+        _connection_id: ConnectionId,
+        _peer: PeerId,
+        _addr: &Multiaddr,
+        _role_override: Endpoint,
+        _port_use: PortUse,
+    ) -> Result<THandler<Self>, ConnectionDenied> {
+        Ok(dummy::ConnectionHandler)
+    }
+
+    fn on_swarm_event(&mut self, event: FromSwarm) {
+        if let FromSwarm::ListenFailure(failure) = event {
+            self.on_listen_failure(failure);
+        }
+    }
+
+    fn on_connection_handler_event(
+        &mut self,
+        _peer_id: PeerId,
+        _connection_id: ConnectionId,
+        _event: THandlerOutEvent<Self>,
+    ) {
+        // The dummy handler has no events
+    }
+
+    fn poll(&mut self, _cx: &mut Context<'_>) -> Poll<ToSwarm<Self::ToSwarm, THandlerInEvent<Self>>> {
+        match self.tracker.events.pop_front() {
+            Some(event) => Poll::Ready(ToSwarm::GenerateEvent(event)),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Returns the IP address of a direct connection. Relayed connections are not limited because the address is the
+/// address of the relay, which is shared by all peers that use it.
+fn ip_address(addr: &Multiaddr) -> Option<IpAddr> {
+    if addr.iter().any(|p| matches!(p, Protocol::P2pCircuit)) {
+        return None;
+    }
+    addr.iter().find_map(|p| match p {
+        Protocol::Ip4(ip) => Some(IpAddr::V4(ip)),
+        Protocol::Ip6(ip) => Some(IpAddr::V6(ip)),
+        _ => None,
+    })
+}
+
+struct InboundConnectionTracker {
+    limits: InboundConnectionLimits,
+    addresses: HashMap<IpAddr, AddressState>,
+    stats: InboundConnectionStats,
+    events: VecDeque<Event>,
+}
+
+impl InboundConnectionTracker {
+    fn new(limits: InboundConnectionLimits) -> Self {
+        Self {
+            limits,
+            addresses: HashMap::new(),
+            stats: InboundConnectionStats::default(),
+            events: VecDeque::new(),
+        }
+    }
+
+    fn on_inbound_attempt(&mut self, address: IpAddr, now: Instant) -> Result<(), InboundConnectionDenied> {
+        if self.is_banned(&address, now) {
+            self.stats.denied_banned += 1;
+            return Err(InboundConnectionDenied::Banned);
+        }
+
+        if let Some(per_ip) = self.limits.per_ip.clone() {
+            let attempts = self.state_mut(address, now).attempts.increment(per_ip.interval, now);
+            if attempts > per_ip.limit.get() {
+                self.stats.rate_limited += 1;
+                self.on_violation(address, now);
+                return Err(InboundConnectionDenied::RateLimited);
+            }
+        }
+
+        self.stats.accepted += 1;
+        Ok(())
+    }
+
+    fn on_handshake_failure(&mut self, address: IpAddr, now: Instant) {
+        self.stats.handshake_failures += 1;
+        self.on_violation(address, now);
+    }
+
+    fn is_banned(&self, address: &IpAddr, now: Instant) -> bool {
+        self.addresses
+            .get(address)
+            .and_then(|state| state.banned_until)
+            .is_some_and(|until| until > now)
+    }
+
+    /// Bans the address if it has committed too many violations
+    fn on_violation(&mut self, address: IpAddr, now: Instant) {
+        let Some(ban_after) = self.limits.ban_after.clone() else {
+            return;
+        };
+        let duration = self.limits.ban_duration;
+        let state = self.state_mut(address, now);
+        if state.violations.increment(ban_after.interval, now) < ban_after.limit.get() {
+            return;
+        }
+
+        state.banned_until = Some(now + duration);
+        state.violations = Window::new(now);
+        self.stats.bans += 1;
+        self.events.push_back(Event::AddressBanned { address, duration });
+    }
+
+    fn state_mut(&mut self, address: IpAddr, now: Instant) -> &mut AddressState {
+        if self.addresses.len() >= MAX_TRACKED_ADDRESSES && !self.addresses.contains_key(&address) {
+            self.prune(now);
+        }
+        self.addresses.entry(address).or_insert_with(|| AddressState::new(now))
+    }
+
+    /// Removes addresses that are not banned and have no recent attempts or violations
+    fn prune(&mut self, now: Instant) {
+        let limits = &self.limits;
+        self.addresses.retain(|_, state| {
+            state.banned_until.is_some_and(|until| until > now) ||
+                limits
+                    .per_ip
+                    .as_ref()
+                    .is_some_and(|l| !state.attempts.is_expired(l.interval, now)) ||
+                limits
+                    .ban_after
+                    .as_ref()
+                    .is_some_and(|l| !state.violations.is_expired(l.interval, now))
+        });
+        self.addresses.shrink_to_fit();
+    }
+}
+
+struct AddressState {
+    attempts: Window,
+    violations: Window,
+    banned_until: Option<Instant>,
+}
+
+impl AddressState {
+    fn new(now: Instant) -> Self {
+        Self {
+            attempts: Window::new(now),
+            violations: Window::new(now),
+            banned_until: None,
+        }
+    }
+}
+
+/// Counts events in a fixed time window
+struct Window {
+    start: Instant,
+    count: u32,
+}
+
+impl Window {
+    fn new(now: Instant) -> Self {
+        Self { start: now, count: 0 }
+    }
+
+    fn is_expired(&self, interval: Duration, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= interval
+    }
+
+    /// Counts an event and returns the number of events in the current window
+    fn increment(&mut self, interval: Duration, now: Instant) -> u32 {
+        if self.is_expired(interval, now) {
+            *self = Self::new(now);
+        }
+        self.count = self.count.saturating_add(1);
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::config::LimitPerInterval;
+
+    fn limits() -> InboundConnectionLimits {
+        InboundConnectionLimits {
+            per_ip: Some(LimitPerInterval {
+                limit: NonZeroU32::new(2).unwrap(),
+                interval: Duration::from_secs(10),
+            }),
+            ban_after: Some(LimitPerInterval {
+                limit: NonZeroU32::new(3).unwrap(),
+                interval: Duration::from_secs(60),
+            }),
+            ban_duration: Duration::from_secs(100),
+        }
+    }
+
+    #[test]
+    fn it_rate_limits_attempts_per_address() {
+        let mut tracker = InboundConnectionTracker::new(limits());
+        let now = Instant::now();
+        let address = IpAddr::from([10, 0, 0, 1]);
+        let other = IpAddr::from([10, 0, 0, 2]);
+
+        assert!(tracker.on_inbound_attempt(address, now).is_ok());
+        assert!(tracker.on_inbound_attempt(address, now).is_ok());
+        assert!(matches!(
+            tracker.on_inbound_attempt(address, now),
+            Err(InboundConnectionDenied::RateLimited)
+        ));
+        assert!(tracker.on_inbound_attempt(other, now).is_ok());
+        // The window has passed
+        assert!(tracker
+            .on_inbound_attempt(address, now + Duration::from_secs(10))
+            .is_ok());
+
+        assert_eq!(tracker.stats, InboundConnectionStats {
+            accepted: 4,
+            rate_limited: 1,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn it_bans_repeat_offenders() {
+        let mut tracker = InboundConnectionTracker::new(limits());
+        let now = Instant::now();
+        let address = IpAddr::from([10, 0, 0, 1]);
+
+        tracker.on_handshake_failure(address, now);
+        tracker.on_handshake_failure(address, now);
+        assert!(tracker.events.is_empty());
+        tracker.on_handshake_failure(address, now);
+        assert!(matches!(
+            tracker.events.pop_front(),
+            Some(Event::AddressBanned { duration, .. }) if duration == Duration::from_secs(100)
+        ));
+        assert!(matches!(
+            tracker.on_inbound_attempt(address, now + Duration::from_secs(99)),
+            Err(InboundConnectionDenied::Banned)
+        ));
+        assert!(tracker
+            .on_inbound_attempt(address, now + Duration::from_secs(100))
+            .is_ok());
+
+        assert_eq!(tracker.stats.handshake_failures, 3);
+        assert_eq!(tracker.stats.bans, 1);
+        assert_eq!(tracker.stats.denied_banned, 1);
+    }
+
+    #[test]
+    fn it_does_not_limit_relayed_connections() {
+        let relayed: Multiaddr = "/ip4/10.0.0.1/tcp/1234/p2p-circuit".parse().unwrap();
+        assert_eq!(ip_address(&relayed), None);
+        let direct: Multiaddr = "/ip4/10.0.0.1/tcp/1234".parse().unwrap();
+        assert_eq!(ip_address(&direct), Some(IpAddr::from([10, 0, 0, 1])));
+    }
+}
@@ -4,6 +4,7 @@
 mod behaviour;
 pub mod config;
 mod error;
+pub mod inbound_guard;
 mod protocol_version;
 
 pub use behaviour::*;
@@ -55,6 +55,7 @@ impl Runner {
             XTR,
             ResourceType::Confidential,
             Some("XTR".to_string()),
+            None,
         )?;
         let account = self.sdk.accounts_api().get_account_by_address(account)?;
 
@@ -205,6 +206,7 @@ impl Runner {
                     *vault.resource_address(),
                     vault.resource_type(),
                     None,
+                    None,
                 )?;
             }
         }
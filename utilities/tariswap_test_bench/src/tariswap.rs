@@ -178,6 +178,7 @@ impl Runner {
                     tariswaps[0].lp_resource_address,
                     ResourceType::NonFungible,
                     Some("LP".to_string()),
+                    None,
                 )?;
             }
             info!("⏳️ Added liquidity to pools {}-{}", i * 200, (i + 1) * 200);